@@ -0,0 +1,113 @@
+package handlers
+
+import (
+	"errors"
+	"fmt"
+	"io"
+	"net/http"
+	"strconv"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/artifacts"
+	"github.com/neural-chilli/aceryx/internal/cases"
+	"github.com/neural-chilli/aceryx/internal/vault"
+)
+
+type ArtifactHandlers struct {
+	Service *artifacts.Service
+	Queue   *cases.CreateQueue
+}
+
+func NewArtifactHandlers(svc *artifacts.Service, queue *cases.CreateQueue) *ArtifactHandlers {
+	return &ArtifactHandlers{Service: svc, Queue: queue}
+}
+
+func (h *ArtifactHandlers) List(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	caseID, ok := h.executionCase(w, r, principal.TenantID)
+	if !ok {
+		return
+	}
+	items, err := h.Service.List(r.Context(), principal.TenantID, caseID)
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"artifacts": items})
+}
+
+func (h *ArtifactHandlers) Download(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	caseID, ok := h.executionCase(w, r, principal.TenantID)
+	if !ok {
+		return
+	}
+	artifactID, ok := parseUUIDPath(w, r, "artifact_id", "invalid_artifact_id")
+	if !ok {
+		return
+	}
+	rec, body, err := h.Service.Open(r.Context(), principal.TenantID, caseID, artifactID)
+	if err != nil {
+		if errors.Is(err, artifacts.ErrNotFound) {
+			writeError(w, http.StatusNotFound, "not_found")
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	defer func() { _ = body.Close() }()
+	disposition := "attachment"
+	if vault.DisplayModeForMime(rec.ContentType) == "inline" {
+		disposition = "inline"
+	}
+	w.Header().Set("Content-Type", rec.ContentType)
+	w.Header().Set("Content-Length", strconv.FormatInt(rec.SizeBytes, 10))
+	w.Header().Set("Content-Disposition", fmt.Sprintf("%s; filename=%q", disposition, rec.Name))
+	w.Header().Set("X-Content-Type-Options", "nosniff")
+	w.WriteHeader(http.StatusOK)
+	_, _ = io.Copy(w, body)
+}
+
+// executionCase resolves the {id} path segment to a case. It accepts the
+// execution ID returned by asynchronous case creation as well as a case ID.
+func (h *ArtifactHandlers) executionCase(w http.ResponseWriter, r *http.Request, tenantID uuid.UUID) (uuid.UUID, bool) {
+	id, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return uuid.Nil, false
+	}
+	if h.Service == nil {
+		writeError(w, http.StatusNotFound, "not_found")
+		return uuid.Nil, false
+	}
+	return resolveExecutionCase(w, r, h.Queue, tenantID, id)
+}
+
+// resolveExecutionCase maps an execution ID returned by asynchronous case
+// creation to its case; any other ID is taken to be a case ID. It writes the
+// error response and fails for an execution whose case does not exist yet.
+func resolveExecutionCase(w http.ResponseWriter, r *http.Request, queue *cases.CreateQueue, tenantID, id uuid.UUID) (uuid.UUID, bool) {
+	if queue == nil {
+		return id, true
+	}
+	job, err := queue.Get(r.Context(), tenantID, id)
+	switch {
+	case errors.Is(err, cases.ErrJobNotFound):
+		return id, true
+	case err != nil:
+		writeInternalServerError(w, r, err)
+		return uuid.Nil, false
+	case job.CaseID == nil:
+		writeError(w, http.StatusNotFound, "not_found")
+		return uuid.Nil, false
+	}
+	return *job.CaseID, true
+}
@@ -7,6 +7,7 @@ import (
 	"log/slog"
 	"net/http"
 	"strings"
+	"time"
 
 	"github.com/google/uuid"
 	"github.com/neural-chilli/aceryx/api/middleware"
@@ -18,6 +19,7 @@ type AuthHandlers struct {
 	Auth       *rbac.AuthService
 	Principals *rbac.PrincipalService
 	Roles      *rbac.RoleService
+	Tokens     *rbac.TokenService
 }
 
 func NewAuthHandlers(auth *rbac.AuthService, principals *rbac.PrincipalService, roles *rbac.RoleService) *AuthHandlers {
@@ -68,6 +70,34 @@ func (h *AuthHandlers) Login(w http.ResponseWriter, r *http.Request) {
 			"tenant_id", resp.Principal.TenantID.String(),
 		)...,
 	)
+	setSessionCookie(w, r, resp.Token, resp.ExpiresAt)
+	writeJSON(w, http.StatusOK, resp)
+}
+
+// Session restores the login context from the session cookie or bearer
+// token, so the web UI keeps its user across reloads and new tabs.
+func (h *AuthHandlers) Session(w http.ResponseWriter, r *http.Request) {
+	token, fromCookie := middleware.RequestToken(r)
+	if token == "" {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	resp, err := h.Auth.Session(r.Context(), token)
+	if err != nil {
+		if errors.Is(err, rbac.ErrInvalidToken) || errors.Is(err, rbac.ErrExpiredToken) {
+			if fromCookie {
+				clearSessionCookie(w, r)
+			}
+			writeError(w, http.StatusUnauthorized, "unauthenticated")
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	if fromCookie {
+		// The cookie is HttpOnly; handing its value to scripts would undo that.
+		resp.Token = ""
+	}
 	writeJSON(w, http.StatusOK, resp)
 }
 
@@ -87,6 +117,7 @@ func (h *AuthHandlers) Logout(w http.ResponseWriter, r *http.Request) {
 			"tenant_id", principal.TenantID.String(),
 		)...,
 	)
+	clearSessionCookie(w, r)
 	writeJSON(w, http.StatusOK, map[string]any{"status": "ok"})
 }
 
@@ -292,3 +323,31 @@ func (h *AuthHandlers) UpdateRolePermissions(w http.ResponseWriter, r *http.Requ
 	}
 	writeJSON(w, http.StatusOK, role)
 }
+
+func setSessionCookie(w http.ResponseWriter, r *http.Request, token string, expiresAt time.Time) {
+	http.SetCookie(w, &http.Cookie{
+		Name:     middleware.SessionCookieName,
+		Value:    token,
+		Path:     "/",
+		Expires:  expiresAt,
+		HttpOnly: true,
+		Secure:   requestIsHTTPS(r),
+		SameSite: http.SameSiteStrictMode,
+	})
+}
+
+func clearSessionCookie(w http.ResponseWriter, r *http.Request) {
+	http.SetCookie(w, &http.Cookie{
+		Name:     middleware.SessionCookieName,
+		Value:    "",
+		Path:     "/",
+		MaxAge:   -1,
+		HttpOnly: true,
+		Secure:   requestIsHTTPS(r),
+		SameSite: http.SameSiteStrictMode,
+	})
+}
+
+func requestIsHTTPS(r *http.Request) bool {
+	return r.TLS != nil || strings.EqualFold(strings.TrimSpace(r.Header.Get("X-Forwarded-Proto")), "https")
+}
@@ -4,6 +4,7 @@ import (
 	"database/sql"
 	"encoding/json"
 	"errors"
+	"io"
 	"net/http"
 	"strconv"
 	"strings"
@@ -13,12 +14,20 @@ import (
 	"github.com/neural-chilli/aceryx/api/middleware"
 	"github.com/neural-chilli/aceryx/internal/cases"
 	"github.com/neural-chilli/aceryx/internal/engine"
+	"github.com/neural-chilli/aceryx/internal/progress"
+	"github.com/neural-chilli/aceryx/internal/rbac"
+	"github.com/neural-chilli/aceryx/internal/workflows"
 )
 
 type CaseHandlers struct {
 	CaseTypes *cases.CaseTypeService
 	Cases     *cases.CaseService
 	Reports   *cases.ReportsService
+	Queue     *cases.CreateQueue
+	Engine    *engine.Engine
+	Progress  *progress.Broker
+	Tokens    *rbac.TokenService
+	Workflows *workflows.Service
 }
 
 func NewCaseHandlers(ct *cases.CaseTypeService, cs *cases.CaseService, rs *cases.ReportsService) *CaseHandlers {
@@ -89,6 +98,29 @@ func (h *CaseHandlers) GetCaseType(w http.ResponseWriter, r *http.Request) {
 	writeJSON(w, http.StatusOK, ct)
 }
 
+// authorizeTokenCaseType refuses a service token scoped to workflows from
+// starting a case of a type none of them handle, and reports whether the
+// request may go on.
+func (h *CaseHandlers) authorizeTokenCaseType(w http.ResponseWriter, r *http.Request, principal *middleware.Principal, caseType string) bool {
+	if principal.Scope == nil || len(principal.Scope.WorkflowIDs) == 0 {
+		return true
+	}
+	if h.Tokens == nil {
+		writeError(w, http.StatusForbidden, "forbidden")
+		return false
+	}
+	allowed, err := h.Tokens.AllowsCaseType(r.Context(), principal.TenantID, principal.Scope, caseType)
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return false
+	}
+	if !allowed {
+		writeError(w, http.StatusForbidden, "forbidden")
+		return false
+	}
+	return true
+}
+
 func (h *CaseHandlers) CreateCase(w http.ResponseWriter, r *http.Request) {
 	principal := middleware.PrincipalFromContext(r.Context())
 	if principal == nil {
@@ -100,8 +132,50 @@ func (h *CaseHandlers) CreateCase(w http.ResponseWriter, r *http.Request) {
 		writeError(w, http.StatusBadRequest, "invalid_json")
 		return
 	}
+	lane, ok := requestLane(req.Lane, principal.Type)
+	if !ok {
+		writeError(w, http.StatusBadRequest, "invalid_lane")
+		return
+	}
+	req.Lane = lane
+	if req.MockProfile != "" && !h.Cases.MockProfilesEnabled() {
+		writeError(w, http.StatusBadRequest, "mock_profiles_disabled")
+		return
+	}
+	if !principal.Scope.Allows("cases:create") {
+		writeError(w, http.StatusForbidden, "forbidden")
+		return
+	}
+	if !h.authorizeTokenCaseType(w, r, principal, req.CaseType) {
+		return
+	}
+	if err := h.Cases.AuthorizeExecution(r.Context(), principal.TenantID, principal.ID, req.CaseType); err != nil {
+		if errors.Is(err, cases.ErrExecutionForbidden) {
+			writeError(w, http.StatusForbidden, "forbidden")
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	if r.URL.Query().Get("async") == "true" {
+		if err := h.Cases.AdmitExecution(r.Context(), principal.TenantID, principal.ID); err != nil {
+			if !writeQuotaError(w, err) {
+				writeInternalServerError(w, r, err)
+			}
+			return
+		}
+		h.enqueueCase(w, r, principal.TenantID, principal.ID, req)
+		return
+	}
 	c, validation, err := h.Cases.CreateCase(r.Context(), principal.TenantID, principal.ID, req)
 	if err != nil {
+		if writeQuotaError(w, err) {
+			return
+		}
+		if errors.Is(err, cases.ErrExecutionForbidden) {
+			writeError(w, http.StatusForbidden, "forbidden")
+			return
+		}
 		writeError(w, http.StatusBadRequest, err.Error())
 		return
 	}
@@ -112,6 +186,82 @@ func (h *CaseHandlers) CreateCase(w http.ResponseWriter, r *http.Request) {
 	writeJSON(w, http.StatusCreated, c)
 }
 
+// requestLane picks the execution lane for a case created over the API.
+// People default to the interactive lane; other callers default to standard
+// and cannot claim interactive.
+func requestLane(requested engine.Lane, principalType string) (engine.Lane, bool) {
+	if requested == "" && principalType == "human" {
+		return engine.LaneInteractive, true
+	}
+	lane, ok := engine.ParseLane(string(requested))
+	if !ok {
+		return "", false
+	}
+	if lane == engine.LaneInteractive && principalType != "human" {
+		lane = engine.LaneStandard
+	}
+	return lane, true
+}
+
+func (h *CaseHandlers) enqueueCase(w http.ResponseWriter, r *http.Request, tenantID, createdBy uuid.UUID, req cases.CreateCaseRequest) {
+	if h.Queue == nil {
+		writeError(w, http.StatusServiceUnavailable, "async_unavailable")
+		return
+	}
+	job, err := h.Queue.Enqueue(r.Context(), tenantID, createdBy, req)
+	if err != nil {
+		if errors.Is(err, cases.ErrQueueFull) {
+			w.Header().Set("Retry-After", "5")
+			writeError(w, http.StatusServiceUnavailable, "queue_full")
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	w.Header().Set("Location", "/api/v1/executions/"+job.ID.String())
+	writeJSON(w, http.StatusAccepted, job)
+}
+
+// GetExecution reports an asynchronous case creation. Once the case exists,
+// progress reflects its workflow.
+func (h *CaseHandlers) GetExecution(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	id, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	if h.Queue == nil {
+		writeError(w, http.StatusNotFound, "not_found")
+		return
+	}
+	job, err := h.Queue.Get(r.Context(), principal.TenantID, id)
+	if errors.Is(err, cases.ErrJobNotFound) {
+		writeError(w, http.StatusNotFound, "not_found")
+		return
+	}
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	out := map[string]any{"execution": job}
+	if job.CaseID != nil && h.Engine != nil {
+		if status, err := h.Engine.GetStatus(r.Context(), principal.TenantID, *job.CaseID); err == nil {
+			out["workflow"] = map[string]any{
+				"status":          status.Status,
+				"current_step":    status.CurrentStep,
+				"completed_steps": status.CompletedSteps,
+				"pending_tasks":   status.PendingTasks,
+				"progress_pct":    status.ProgressPct,
+			}
+		}
+	}
+	writeJSON(w, http.StatusOK, out)
+}
+
 func (h *CaseHandlers) GetCase(w http.ResponseWriter, r *http.Request) {
 	principal := middleware.PrincipalFromContext(r.Context())
 	if principal == nil {
@@ -271,6 +421,42 @@ func (h *CaseHandlers) CancelCase(w http.ResponseWriter, r *http.Request) {
 	writeJSON(w, http.StatusOK, map[string]string{"status": "cancelled"})
 }
 
+func (h *CaseHandlers) ResumeCase(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	id, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	var req struct {
+		StepIDs []string `json:"step_ids"`
+	}
+	if r.ContentLength != 0 {
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil && !errors.Is(err, io.EOF) {
+			writeError(w, http.StatusBadRequest, "invalid_json")
+			return
+		}
+	}
+	resumed, err := h.Cases.ResumeCase(r.Context(), principal.TenantID, id, principal.ID, req.StepIDs)
+	if err != nil {
+		switch {
+		case errors.Is(err, sql.ErrNoRows):
+			writeError(w, http.StatusNotFound, "not_found")
+		case errors.Is(err, engine.ErrNothingToResume):
+			writeError(w, http.StatusConflict, "nothing_to_resume")
+		case errors.Is(err, engine.ErrCaseCancelled):
+			writeError(w, http.StatusConflict, "case_cancelled")
+		default:
+			writeInternalServerError(w, r, err)
+		}
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"status": "resumed", "steps": resumed})
+}
+
 func (h *CaseHandlers) SearchCases(w http.ResponseWriter, r *http.Request) {
 	principal := middleware.PrincipalFromContext(r.Context())
 	if principal == nil {
@@ -439,7 +625,7 @@ func writeJSON(w http.ResponseWriter, status int, payload interface{}) {
 }
 
 func writeError(w http.ResponseWriter, status int, msg string) {
-	writeJSON(w, status, map[string]string{"error": msg})
+	writeJSON(w, status, middleware.ErrorBody(w, msg))
 }
 
 func splitCSV(in string) []string {
@@ -0,0 +1,47 @@
+package handlers
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"testing"
+	"time"
+)
+
+func TestWriteJSONConditional(t *testing.T) {
+	modified := time.Date(2026, 3, 1, 10, 0, 0, 500, time.UTC)
+	payload := map[string]string{"name": "loan"}
+
+	rr := httptest.NewRecorder()
+	writeJSONConditional(rr, httptest.NewRequest(http.MethodGet, "/workflows/1", nil), payload, modified)
+	etag := rr.Header().Get("ETag")
+	if rr.Code != http.StatusOK || etag == "" || rr.Header().Get("Last-Modified") != "Sun, 01 Mar 2026 10:00:00 GMT" {
+		t.Fatalf("unexpected response %d %v", rr.Code, rr.Header())
+	}
+
+	tests := []struct {
+		name   string
+		header string
+		value  string
+		want   int
+	}{
+		{name: "matching etag", header: "If-None-Match", value: etag, want: http.StatusNotModified},
+		{name: "weak etag in list", header: "If-None-Match", value: `"other", W/` + etag, want: http.StatusNotModified},
+		{name: "stale etag", header: "If-None-Match", value: `"other"`, want: http.StatusOK},
+		{name: "not modified since", header: "If-Modified-Since", value: "Sun, 01 Mar 2026 10:00:00 GMT", want: http.StatusNotModified},
+		{name: "modified since", header: "If-Modified-Since", value: "Sun, 01 Mar 2026 09:59:59 GMT", want: http.StatusOK},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			req := httptest.NewRequest(http.MethodGet, "/workflows/1", nil)
+			req.Header.Set(tt.header, tt.value)
+			rr := httptest.NewRecorder()
+			writeJSONConditional(rr, req, payload, modified)
+			if rr.Code != tt.want {
+				t.Fatalf("expected %d, got %d", tt.want, rr.Code)
+			}
+			if tt.want == http.StatusNotModified && rr.Body.Len() != 0 {
+				t.Fatalf("304 carried a body: %q", rr.Body.String())
+			}
+		})
+	}
+}
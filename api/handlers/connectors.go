@@ -2,15 +2,24 @@ package handlers
 
 import (
 	"encoding/json"
+	"errors"
+	"math"
 	"net/http"
+	"strconv"
+	"strings"
+	"time"
 
 	"github.com/neural-chilli/aceryx/api/middleware"
 	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/redaction"
 )
 
 type ConnectorHandlers struct {
 	Registry *connectors.Registry
 	Secrets  connectors.SecretStore
+	Guard    *connectors.ExecutionGuard
+	Usage    *connectors.UsageStore
+	Redactor *redaction.Redactor
 }
 
 func NewConnectorHandlers(registry *connectors.Registry, secrets connectors.SecretStore) *ConnectorHandlers {
@@ -22,7 +31,17 @@ func (h *ConnectorHandlers) List(w http.ResponseWriter, r *http.Request) {
 		writeError(w, http.StatusUnauthorized, "unauthenticated")
 		return
 	}
-	writeJSON(w, http.StatusOK, h.Registry.Describe())
+	params, err := parseListParams(r)
+	if err != nil {
+		writeError(w, http.StatusBadRequest, err.Error())
+		return
+	}
+	if params.SortBy != "" && params.SortBy != "name" && params.SortBy != "key" {
+		writeError(w, http.StatusBadRequest, "invalid_sort")
+		return
+	}
+	items, total := h.Registry.DescribePage(params.Offset, params.Limit, params.SortBy, params.Descending)
+	writeJSON(w, http.StatusOK, listEnvelope{Items: items, Total: total, Limit: params.Limit, Offset: params.Offset})
 }
 
 func (h *ConnectorHandlers) TestAction(w http.ResponseWriter, r *http.Request) {
@@ -39,12 +58,18 @@ func (h *ConnectorHandlers) TestAction(w http.ResponseWriter, r *http.Request) {
 		writeError(w, http.StatusNotFound, "connector_action_not_found")
 		return
 	}
+	target := connectorKey + "." + actionKey
+	if err := h.Guard.Allow(principal.TenantID, principal.ID, target); err != nil {
+		writeGuardError(w, err)
+		return
+	}
 
 	var req struct {
 		Auth  map[string]string `json:"auth"`
 		Input map[string]any    `json:"input"`
 	}
 	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		h.Guard.RecordFailure(principal.TenantID, principal.ID, target)
 		writeError(w, http.StatusBadRequest, "invalid_json")
 		return
 	}
@@ -64,9 +89,13 @@ func (h *ConnectorHandlers) TestAction(w http.ResponseWriter, r *http.Request) {
 		})
 	}
 
+	var authSpec connectors.AuthSpec
+	if connector, ok := h.Registry.Get(connectorKey); ok {
+		authSpec = connector.Auth()
+	}
 	// Resolve missing required auth fields via secret store.
-	if connector, ok := h.Registry.Get(connectorKey); ok && h.Secrets != nil {
-		for _, field := range connector.Auth().Fields {
+	if h.Secrets != nil {
+		for _, field := range authSpec.Fields {
 			if req.Auth[field.Key] != "" {
 				continue
 			}
@@ -79,10 +108,106 @@ func (h *ConnectorHandlers) TestAction(w http.ResponseWriter, r *http.Request) {
 
 	req.Input["_tenant_id"] = principal.TenantID.String()
 	req.Input["_actor_id"] = principal.ID.String()
-	result, err := action.Execute(r.Context(), req.Auth, req.Input)
+	start := time.Now()
+	result, err := action.Execute(redaction.WithRedactor(r.Context(), h.Redactor), req.Auth, req.Input)
+	h.Registry.RecordUsage(r.Context(), connectors.UsageRecord{
+		TenantID:     principal.TenantID,
+		ConnectorKey: connectorKey,
+		ActionKey:    actionKey,
+		PrincipalID:  principal.ID,
+		Duration:     time.Since(start),
+		Success:      err == nil,
+		CalledAt:     start,
+	})
 	if err != nil {
+		// Only bad input or credentials count towards a lockout; a failing
+		// upstream is not the caller's doing.
+		if connectors.CallerFault(authSpec, action, req.Auth, req.Input, err) {
+			h.Guard.RecordFailure(principal.TenantID, principal.ID, target)
+		}
 		writeError(w, http.StatusBadRequest, err.Error())
 		return
 	}
+	h.Guard.RecordSuccess(principal.ID)
 	writeJSON(w, http.StatusOK, result)
 }
+
+const (
+	defaultUsageDays = 30
+	maxUsageDays     = 365
+)
+
+// Stats reports a connector's call volume, failures and latency for the
+// caller's tenant over the trailing ?days= days (default 30).
+func (h *ConnectorHandlers) Stats(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	connectorKey := r.PathValue("key")
+	if _, ok := h.Registry.Get(connectorKey); !ok {
+		writeError(w, http.StatusNotFound, "connector_not_found")
+		return
+	}
+	days := defaultUsageDays
+	if raw := strings.TrimSpace(r.URL.Query().Get("days")); raw != "" {
+		n, err := strconv.Atoi(raw)
+		if err != nil || n < 1 || n > maxUsageDays {
+			writeError(w, http.StatusBadRequest, "invalid_days")
+			return
+		}
+		days = n
+	}
+	if h.Usage == nil {
+		writeError(w, http.StatusServiceUnavailable, "usage_stats_unavailable")
+		return
+	}
+	stats, err := h.Usage.Stats(r.Context(), principal.TenantID, connectorKey, days)
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, stats)
+}
+
+// AbuseReports lists execution guard interventions for the caller's tenant.
+func (h *ConnectorHandlers) AbuseReports(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"reports": h.Guard.Reports(principal.TenantID)})
+}
+
+// GuardExecution admits each request to an execute surface through guard
+// before next runs, so the per-principal rate limit and any lockout earned
+// on one surface apply to all of them. It must run behind the auth
+// middleware.
+func GuardExecution(guard *connectors.ExecutionGuard, target string, next http.Handler) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		principal := middleware.PrincipalFromContext(r.Context())
+		if principal == nil {
+			writeError(w, http.StatusUnauthorized, "unauthenticated")
+			return
+		}
+		if err := guard.Allow(principal.TenantID, principal.ID, target); err != nil {
+			writeGuardError(w, err)
+			return
+		}
+		next.ServeHTTP(w, r)
+	})
+}
+
+func writeGuardError(w http.ResponseWriter, err error) {
+	var guardErr *connectors.GuardError
+	if errors.As(err, &guardErr) && guardErr.RetryAfter > 0 {
+		w.Header().Set("Retry-After", strconv.Itoa(int(math.Ceil(guardErr.RetryAfter.Seconds()))))
+	}
+	code := "execution_rate_limited"
+	if errors.Is(err, connectors.ErrExecutionLocked) {
+		code = "execution_locked"
+	}
+	writeError(w, http.StatusTooManyRequests, code)
+}
@@ -0,0 +1,265 @@
+package handlers
+
+import (
+	"encoding/json"
+	"errors"
+	"net/http"
+
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/customtools"
+)
+
+type CustomToolHandlers struct {
+	Store    *customtools.Store
+	Protocol *customtools.CustomHTTPProtocol
+}
+
+func NewCustomToolHandlers(store *customtools.Store, protocol *customtools.CustomHTTPProtocol) *CustomToolHandlers {
+	return &CustomToolHandlers{Store: store, Protocol: protocol}
+}
+
+func (h *CustomToolHandlers) Create(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	var req customtools.CreateRequest
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	tool, err := h.Store.Create(r.Context(), principal.TenantID, principal.ID, req)
+	if err != nil {
+		if errors.Is(err, customtools.ErrToolExists) {
+			writeError(w, http.StatusConflict, "already_exists")
+			return
+		}
+		writeError(w, http.StatusBadRequest, err.Error())
+		return
+	}
+	writeJSON(w, http.StatusCreated, tool)
+}
+
+func (h *CustomToolHandlers) List(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	tools, err := h.Store.List(r.Context(), principal.TenantID)
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, tools)
+}
+
+func (h *CustomToolHandlers) Get(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	tool, err := h.Store.Get(r.Context(), principal.TenantID, r.PathValue("name"))
+	if err != nil {
+		if errors.Is(err, customtools.ErrToolNotFound) {
+			writeError(w, http.StatusNotFound, "not_found")
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSONConditional(w, r, tool, tool.UpdatedAt)
+}
+
+func (h *CustomToolHandlers) Delete(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	if err := h.Store.Delete(r.Context(), principal.TenantID, r.PathValue("name")); err != nil {
+		if errors.Is(err, customtools.ErrToolNotFound) {
+			writeError(w, http.StatusNotFound, "not_found")
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"status": "deleted"})
+}
+
+// Invoke calls a custom tool with the request body as its arguments, so a
+// definition can be tried out before it is used in a workflow.
+func (h *CustomToolHandlers) Invoke(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	var args json.RawMessage
+	if err := json.NewDecoder(r.Body).Decode(&args); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	output, err := h.Protocol.Invoke(r.Context(), principal.TenantID, r.PathValue("name"), args)
+	if err != nil {
+		if errors.Is(err, customtools.ErrToolNotFound) {
+			writeError(w, http.StatusNotFound, "not_found")
+			return
+		}
+		if errors.Is(err, customtools.ErrInvalidArguments) {
+			writeError(w, http.StatusBadRequest, err.Error())
+			return
+		}
+		writeError(w, http.StatusBadGateway, err.Error())
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"output": output})
+}
+
+func (h *CustomToolHandlers) ListExamples(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	examples, err := h.Store.Examples(r.Context(), principal.TenantID, r.PathValue("name"))
+	if err != nil {
+		if errors.Is(err, customtools.ErrToolNotFound) {
+			writeError(w, http.StatusNotFound, "not_found")
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, examples)
+}
+
+func (h *CustomToolHandlers) CreateExample(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	var req customtools.ExampleRequest
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	example, err := h.Store.AddExample(r.Context(), principal.TenantID, principal.ID, r.PathValue("name"), req)
+	if err != nil {
+		switch {
+		case errors.Is(err, customtools.ErrToolNotFound):
+			writeError(w, http.StatusNotFound, "not_found")
+		case errors.Is(err, customtools.ErrExampleExists):
+			writeError(w, http.StatusConflict, "already_exists")
+		default:
+			writeError(w, http.StatusBadRequest, err.Error())
+		}
+		return
+	}
+	writeJSON(w, http.StatusCreated, example)
+}
+
+func (h *CustomToolHandlers) DeleteExample(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	id, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	if err := h.Store.DeleteExample(r.Context(), principal.TenantID, r.PathValue("name"), id); err != nil {
+		if errors.Is(err, customtools.ErrExampleNotFound) {
+			writeError(w, http.StatusNotFound, "not_found")
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"status": "deleted"})
+}
+
+// RunExample invokes a tool with a saved example's input and reports whether
+// the output matches the example's expected output.
+func (h *CustomToolHandlers) RunExample(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	id, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	run, err := h.Protocol.RunExample(r.Context(), principal.TenantID, r.PathValue("name"), id)
+	if err != nil {
+		switch {
+		case errors.Is(err, customtools.ErrToolNotFound), errors.Is(err, customtools.ErrExampleNotFound):
+			writeError(w, http.StatusNotFound, "not_found")
+		case errors.Is(err, customtools.ErrInvalidArguments):
+			writeError(w, http.StatusBadRequest, err.Error())
+		default:
+			writeError(w, http.StatusBadGateway, err.Error())
+		}
+		return
+	}
+	writeJSON(w, http.StatusOK, run)
+}
+
+// FormSchema describes a form for the tool's input schema, so the playground
+// can render typed fields instead of a raw JSON editor.
+func (h *CustomToolHandlers) FormSchema(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	tool, err := h.Store.Get(r.Context(), principal.TenantID, r.PathValue("name"))
+	if err != nil {
+		if errors.Is(err, customtools.ErrToolNotFound) {
+			writeError(w, http.StatusNotFound, "not_found")
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSONConditional(w, r, customtools.FormFor(tool), tool.UpdatedAt)
+}
+
+// Playground invokes a tool from the playground and optionally saves the call
+// as an example.
+func (h *CustomToolHandlers) Playground(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	var req customtools.PlaygroundRequest
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	run, err := h.Protocol.Playground(r.Context(), principal.TenantID, principal.ID, r.PathValue("name"), req)
+	if err != nil {
+		switch {
+		case errors.Is(err, customtools.ErrToolNotFound):
+			writeError(w, http.StatusNotFound, "not_found")
+		case errors.Is(err, customtools.ErrInvalidArguments):
+			writeError(w, http.StatusBadRequest, err.Error())
+		case errors.Is(err, customtools.ErrExampleExists):
+			// The tool has already run, so return its output with the error.
+			writeJSON(w, http.StatusConflict, map[string]any{"error": "already_exists", "output": run.Output})
+		case run.Output != nil:
+			writeInternalServerError(w, r, err)
+		default:
+			writeError(w, http.StatusBadGateway, err.Error())
+		}
+		return
+	}
+	writeJSON(w, http.StatusOK, run)
+}
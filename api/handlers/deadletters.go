@@ -0,0 +1,74 @@
+package handlers
+
+import (
+	"errors"
+	"net/http"
+	"strconv"
+
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/deadletter"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+type DeadLetterHandlers struct {
+	Store *deadletter.Store
+}
+
+func NewDeadLetterHandlers(store *deadletter.Store) *DeadLetterHandlers {
+	return &DeadLetterHandlers{Store: store}
+}
+
+func (h *DeadLetterHandlers) List(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	status := r.URL.Query().Get("status")
+	if status != "" && status != deadletter.StatusOpen && status != deadletter.StatusRequeued {
+		writeError(w, http.StatusBadRequest, "invalid_status")
+		return
+	}
+	limit := 0
+	if raw := r.URL.Query().Get("limit"); raw != "" {
+		n, err := strconv.Atoi(raw)
+		if err != nil || n < 1 {
+			writeError(w, http.StatusBadRequest, "invalid_limit")
+			return
+		}
+		limit = n
+	}
+	items, err := h.Store.List(r.Context(), principal.TenantID, status, limit)
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"dead_letters": items})
+}
+
+func (h *DeadLetterHandlers) Requeue(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	id, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	entry, err := h.Store.Requeue(r.Context(), principal.TenantID, id, principal.ID)
+	switch {
+	case errors.Is(err, deadletter.ErrNotFound):
+		writeError(w, http.StatusNotFound, "not_found")
+	case errors.Is(err, deadletter.ErrAlreadyRequeued):
+		writeError(w, http.StatusConflict, "already_requeued")
+	case errors.Is(err, engine.ErrNothingToResume):
+		writeError(w, http.StatusConflict, "step_not_failed")
+	case errors.Is(err, engine.ErrCaseCancelled):
+		writeError(w, http.StatusConflict, "case_cancelled")
+	case err != nil:
+		writeInternalServerError(w, r, err)
+	default:
+		writeJSON(w, http.StatusOK, entry)
+	}
+}
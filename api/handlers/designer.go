@@ -0,0 +1,70 @@
+package handlers
+
+import (
+	"net/http"
+	"slices"
+	"strings"
+
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/designer"
+)
+
+var paletteKinds = []string{designer.KindStep, designer.KindConnector, designer.KindAIComponent, designer.KindPlugin, designer.KindMCPTool}
+
+type DesignerHandlers struct {
+	Palette *designer.Service
+}
+
+func NewDesignerHandlers(palette *designer.Service) *DesignerHandlers {
+	return &DesignerHandlers{Palette: palette}
+}
+
+// GetPalette returns the designer palette grouped by category. q searches
+// names, labels and descriptions; kind and category take comma-separated
+// lists. limit and offset page over items before grouping.
+func (h *DesignerHandlers) GetPalette(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	params, err := parseListParams(r)
+	if err != nil {
+		writeError(w, http.StatusBadRequest, err.Error())
+		return
+	}
+	if params.SortBy != "" {
+		writeError(w, http.StatusBadRequest, "invalid_sort")
+		return
+	}
+	q := r.URL.Query()
+	query := designer.Query{
+		Search:     strings.TrimSpace(q.Get("q")),
+		Categories: splitCSV(q.Get("category")),
+		Limit:      params.Limit,
+		Offset:     params.Offset,
+	}
+	if len(query.Search) > maxSearchQueryLength {
+		writeError(w, http.StatusBadRequest, "query_too_long")
+		return
+	}
+	for _, kind := range splitCSV(q.Get("kind")) {
+		if !slices.Contains(paletteKinds, kind) {
+			writeError(w, http.StatusBadRequest, "invalid_kind")
+			return
+		}
+		query.Kinds = append(query.Kinds, kind)
+	}
+
+	result, err := h.Palette.Palette(r.Context(), principal.TenantID, query)
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{
+		"categories": result.Categories,
+		"total":      result.Total,
+		"limit":      params.Limit,
+		"offset":     params.Offset,
+	})
+}
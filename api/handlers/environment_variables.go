@@ -0,0 +1,61 @@
+package handlers
+
+import (
+	"encoding/json"
+	"errors"
+	"net/http"
+
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/workflows"
+)
+
+// GetEnvironmentVariables returns the tenant's variable set for the
+// environment in the path.
+func (h *WorkflowHandlers) GetEnvironmentVariables(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	environment := r.PathValue("name")
+	values, err := h.Service.EnvironmentVariables(r.Context(), principal.TenantID, environment)
+	if err != nil {
+		if errors.Is(err, workflows.ErrInvalidVariableSet) {
+			writeError(w, http.StatusBadRequest, "invalid_environment")
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"environment": environment, "variables": values})
+}
+
+// PutEnvironmentVariables replaces the tenant's variable set for the
+// environment in the path.
+func (h *WorkflowHandlers) PutEnvironmentVariables(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	var req struct {
+		Variables map[string]any `json:"variables"`
+	}
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	environment := r.PathValue("name")
+	if err := h.Service.PutEnvironmentVariables(r.Context(), principal.TenantID, principal.ID, environment, req.Variables); err != nil {
+		if errors.Is(err, workflows.ErrInvalidVariableSet) {
+			writeError(w, http.StatusBadRequest, err.Error())
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	if req.Variables == nil {
+		req.Variables = map[string]any{}
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"environment": environment, "variables": req.Variables})
+}
@@ -0,0 +1,100 @@
+package handlers
+
+import (
+	"encoding/json"
+	"errors"
+	"net/http"
+	"strconv"
+
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/events"
+)
+
+type EventHandlers struct {
+	Bus *events.Bus
+}
+
+func NewEventHandlers(bus *events.Bus) *EventHandlers {
+	return &EventHandlers{Bus: bus}
+}
+
+func (h *EventHandlers) CreateSubscription(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	var req events.SubscriptionRequest
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	out, err := h.Bus.Subscribe(r.Context(), principal.TenantID, principal.ID, req)
+	if err != nil {
+		if errors.Is(err, events.ErrInvalidSubscription) {
+			writeError(w, http.StatusBadRequest, err.Error())
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusCreated, out)
+}
+
+func (h *EventHandlers) ListSubscriptions(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	out, err := h.Bus.Subscriptions(r.Context(), principal.TenantID)
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"subscriptions": out, "event_types": events.Types})
+}
+
+func (h *EventHandlers) DeleteSubscription(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	id, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	err := h.Bus.Unsubscribe(r.Context(), principal.TenantID, id)
+	switch {
+	case errors.Is(err, events.ErrSubscriptionNotFound):
+		writeError(w, http.StatusNotFound, "not_found")
+	case err != nil:
+		writeInternalServerError(w, r, err)
+	default:
+		w.WriteHeader(http.StatusNoContent)
+	}
+}
+
+func (h *EventHandlers) ListDeliveries(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	id, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	limit, _ := strconv.Atoi(r.URL.Query().Get("limit"))
+	out, err := h.Bus.Deliveries(r.Context(), principal.TenantID, id, limit)
+	if err != nil {
+		if errors.Is(err, events.ErrSubscriptionNotFound) {
+			writeError(w, http.StatusNotFound, "not_found")
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"deliveries": out})
+}
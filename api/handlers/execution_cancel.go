@@ -0,0 +1,50 @@
+package handlers
+
+import (
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"io"
+	"net/http"
+
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+// CancelExecution cancels a running execution: unfinished steps are skipped
+// and in-flight HTTP and LLM calls are aborted. {id} is a case ID or an
+// asynchronous execution ID.
+func (h *CaseHandlers) CancelExecution(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	id, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	caseID, ok := resolveExecutionCase(w, r, h.Queue, principal.TenantID, id)
+	if !ok {
+		return
+	}
+	var req struct {
+		Reason string `json:"reason"`
+	}
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil && !errors.Is(err, io.EOF) {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	if err := h.Cases.CancelCase(r.Context(), principal.TenantID, caseID, principal.ID, req.Reason); err != nil {
+		switch {
+		case errors.Is(err, sql.ErrNoRows):
+			writeError(w, http.StatusNotFound, "not_found")
+		case errors.Is(err, engine.ErrCaseCancelled):
+			writeError(w, http.StatusConflict, "already_cancelled")
+		default:
+			writeInternalServerError(w, r, err)
+		}
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]string{"status": "cancelled", "case_id": caseID.String()})
+}
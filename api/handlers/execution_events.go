@@ -0,0 +1,151 @@
+package handlers
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"net/http"
+	"time"
+
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/cases"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+const (
+	executionPollInterval = 250 * time.Millisecond
+	sseKeepAliveInterval  = 15 * time.Second
+)
+
+// StreamExecutionEvents follows a workflow run as server-sent events. The id
+// is an execution id from an asynchronous case creation or a case id. An
+// execution reports "execution" events until its case exists; then a
+// "snapshot" of every step's status is sent, followed by a "step" event for
+// each lifecycle change.
+func (h *CaseHandlers) StreamExecutionEvents(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	id, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	if h.Engine == nil || h.Progress == nil {
+		writeError(w, http.StatusNotFound, "not_found")
+		return
+	}
+	ctx := r.Context()
+
+	var job *cases.CreateJob
+	if h.Queue != nil {
+		if found, ok := h.Queue.Get(principal.TenantID, id); ok {
+			job = &found
+		}
+	}
+	if job == nil {
+		if _, err := h.Engine.StepProgress(ctx, principal.TenantID, id); err != nil {
+			if errors.Is(err, engine.ErrNotFound) {
+				writeError(w, http.StatusNotFound, "not_found")
+				return
+			}
+			writeInternalServerError(w, r, err)
+			return
+		}
+	}
+
+	rc := http.NewResponseController(w)
+	w.Header().Set("Content-Type", "text/event-stream")
+	w.Header().Set("Cache-Control", "no-cache")
+	w.Header().Set("Connection", "keep-alive")
+	w.Header().Set("X-Accel-Buffering", "no")
+	w.WriteHeader(http.StatusOK)
+	send := func(event string, data any) bool {
+		if err := writeSSE(w, event, data); err != nil {
+			return false
+		}
+		return rc.Flush() == nil
+	}
+	keepAlive := time.NewTicker(sseKeepAliveInterval)
+	defer keepAlive.Stop()
+	ping := func() bool {
+		if _, err := io.WriteString(w, ": keep-alive\n\n"); err != nil {
+			return false
+		}
+		return rc.Flush() == nil
+	}
+
+	caseID := id
+	if job != nil {
+		poll := time.NewTicker(executionPollInterval)
+		defer poll.Stop()
+		lastStatus := ""
+		for job.CaseID == nil {
+			if job.Status != lastStatus {
+				if !send("execution", job) {
+					return
+				}
+				lastStatus = job.Status
+			}
+			if job.Status == cases.JobFailed {
+				return
+			}
+			select {
+			case <-ctx.Done():
+				return
+			case <-keepAlive.C:
+				if !ping() {
+					return
+				}
+			case <-poll.C:
+			}
+			found, ok := h.Queue.Get(principal.TenantID, id)
+			if !ok {
+				return
+			}
+			job = &found
+		}
+		if !send("execution", job) {
+			return
+		}
+		caseID = *job.CaseID
+	}
+
+	// Subscribe before the snapshot so no change between the two is missed;
+	// events older than the snapshot only repeat what it already shows.
+	events, cancel := h.Progress.Subscribe(caseID)
+	defer cancel()
+	steps, err := h.Engine.StepProgress(ctx, principal.TenantID, caseID)
+	if err != nil {
+		send("error", map[string]string{"error": "progress_unavailable"})
+		return
+	}
+	if !send("snapshot", map[string]any{"case_id": caseID, "steps": steps}) {
+		return
+	}
+	for {
+		select {
+		case <-ctx.Done():
+			return
+		case <-keepAlive.C:
+			if !ping() {
+				return
+			}
+		case event, ok := <-events:
+			if !ok || !send("step", event) {
+				return
+			}
+		}
+	}
+}
+
+func writeSSE(w io.Writer, event string, data any) error {
+	raw, err := json.Marshal(data)
+	if err != nil {
+		return err
+	}
+	_, err = fmt.Fprintf(w, "event: %s\ndata: %s\n\n", event, raw)
+	return err
+}
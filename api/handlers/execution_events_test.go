@@ -0,0 +1,28 @@
+package handlers
+
+import (
+	"bytes"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+)
+
+func TestStreamExecutionEventsUnauthenticated(t *testing.T) {
+	h := NewCaseHandlers(nil, nil, nil)
+	rr := httptest.NewRecorder()
+	h.StreamExecutionEvents(rr, httptest.NewRequest(http.MethodGet, "/api/v1/executions/123/events", nil))
+	if rr.Code != http.StatusUnauthorized {
+		t.Fatalf("expected 401, got %d", rr.Code)
+	}
+}
+
+func TestWriteSSE(t *testing.T) {
+	var buf bytes.Buffer
+	if err := writeSSE(&buf, "step", map[string]any{"step_id": "review", "status": "running"}); err != nil {
+		t.Fatalf("writeSSE: %v", err)
+	}
+	want := "event: step\ndata: {\"status\":\"running\",\"step_id\":\"review\"}\n\n"
+	if buf.String() != want {
+		t.Fatalf("unexpected frame %q", buf.String())
+	}
+}
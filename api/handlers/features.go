@@ -0,0 +1,57 @@
+package handlers
+
+import (
+	"encoding/json"
+	"errors"
+	"net/http"
+
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/features"
+)
+
+type FeatureHandlers struct {
+	Service *features.Service
+}
+
+func NewFeatureHandlers(svc *features.Service) *FeatureHandlers {
+	return &FeatureHandlers{Service: svc}
+}
+
+func (h *FeatureHandlers) List(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	items, err := h.Service.List(r.Context(), principal.TenantID)
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"features": items})
+}
+
+func (h *FeatureHandlers) Update(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	var req struct {
+		Enabled *bool `json:"enabled"`
+	}
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	state, err := h.Service.Set(r.Context(), principal.TenantID, r.PathValue("key"), req.Enabled, principal.ID)
+	if errors.Is(err, features.ErrUnknownFlag) {
+		writeError(w, http.StatusNotFound, "feature_not_found")
+		return
+	}
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, state)
+}
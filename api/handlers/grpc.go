@@ -0,0 +1,35 @@
+package handlers
+
+import (
+	"encoding/json"
+	"net/http"
+
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/grpcclient"
+)
+
+type GrpcHandlers struct {
+	Protocol *grpcclient.GrpcProtocol
+}
+
+func NewGrpcHandlers(protocol *grpcclient.GrpcProtocol) *GrpcHandlers {
+	return &GrpcHandlers{Protocol: protocol}
+}
+
+func (h *GrpcHandlers) Discover(w http.ResponseWriter, r *http.Request) {
+	if middleware.PrincipalFromContext(r.Context()) == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	var req grpcclient.Target
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	tools, err := h.Protocol.Discover(r.Context(), req)
+	if err != nil {
+		writeError(w, http.StatusBadRequest, err.Error())
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"tools": tools})
+}
@@ -7,7 +7,9 @@ import (
 	"os"
 	"time"
 
+	"github.com/neural-chilli/aceryx/internal/connectors"
 	"github.com/neural-chilli/aceryx/internal/engine"
+	"github.com/neural-chilli/aceryx/internal/migrations"
 	"github.com/neural-chilli/aceryx/internal/notify"
 	"github.com/neural-chilli/aceryx/internal/observability"
 	"github.com/prometheus/client_golang/prometheus/promhttp"
@@ -20,10 +22,24 @@ type HealthHandlers struct {
 	version   string
 	startedAt time.Time
 	vaultPath string
+	protocols []protocolCheck
+
+	// ConnectorCache, when set, has its hit and miss counts reported.
+	ConnectorCache *connectors.ResultCache
 }
 
 type componentCheck map[string]any
 
+// ProtocolHealthCheck reports whether an integration protocol (MCP, triggers,
+// ...) is healthy. A nil error means healthy.
+type ProtocolHealthCheck func(ctx context.Context) error
+
+type protocolCheck struct {
+	name     string
+	required bool
+	check    ProtocolHealthCheck
+}
+
 func NewHealthHandlers(db *sql.DB, eng *engine.Engine, hub *notify.Hub) *HealthHandlers {
 	vaultPath := os.Getenv("ACERYX_VAULT_ROOT")
 	if vaultPath == "" {
@@ -34,11 +50,29 @@ func NewHealthHandlers(db *sql.DB, eng *engine.Engine, hub *notify.Hub) *HealthH
 		eng:       eng,
 		hub:       hub,
 		version:   "1.0.0",
-		startedAt: time.Now().UTC(),
+		startedAt: observability.ProcessStartedAt(),
 		vaultPath: vaultPath,
 	}
 }
 
+// AddProtocolCheck registers a protocol health check. An unhealthy required
+// protocol makes the instance not ready; an unhealthy optional protocol only
+// marks it degraded.
+func (h *HealthHandlers) AddProtocolCheck(name string, required bool, check ProtocolHealthCheck) {
+	h.protocols = append(h.protocols, protocolCheck{name: name, required: required, check: check})
+}
+
+// CheckProtocol runs the health check registered under name. It reports
+// false when there is none.
+func (h *HealthHandlers) CheckProtocol(ctx context.Context, name string) (bool, error) {
+	for _, p := range h.protocols {
+		if p.name == name {
+			return true, p.check(ctx)
+		}
+	}
+	return false, nil
+}
+
 func (h *HealthHandlers) Metrics() http.Handler {
 	return promhttp.Handler()
 }
@@ -47,6 +81,9 @@ func (h *HealthHandlers) Liveness(w http.ResponseWriter, _ *http.Request) {
 	writeJSON(w, http.StatusOK, map[string]any{"status": "ok"})
 }
 
+// Readiness reports whether this instance should receive traffic: Postgres is
+// reachable, every embedded migration is applied and required protocols are
+// healthy. Unhealthy optional protocols yield 200 with status "degraded".
 func (h *HealthHandlers) Readiness(w http.ResponseWriter, r *http.Request) {
 	if h.db == nil {
 		writeJSON(w, http.StatusServiceUnavailable, map[string]any{"status": "not_ready", "reason": "db_unconfigured"})
@@ -59,12 +96,43 @@ func (h *HealthHandlers) Readiness(w http.ResponseWriter, r *http.Request) {
 		writeJSON(w, http.StatusServiceUnavailable, map[string]any{"status": "not_ready", "reason": "postgres_unhealthy"})
 		return
 	}
-	var migrationCount int
-	if err := h.db.QueryRowContext(ctx, `SELECT COUNT(*) FROM schema_migrations`).Scan(&migrationCount); err != nil || migrationCount == 0 {
+	latest, err := migrations.LatestVersion()
+	if err != nil {
+		writeJSON(w, http.StatusServiceUnavailable, map[string]any{"status": "not_ready", "reason": "migrations_unreadable"})
+		return
+	}
+	var applied int
+	if err := h.db.QueryRowContext(ctx, `SELECT COALESCE(MAX(version), 0) FROM schema_migrations`).Scan(&applied); err != nil || applied == 0 || applied < latest {
 		writeJSON(w, http.StatusServiceUnavailable, map[string]any{"status": "not_ready", "reason": "migrations_not_applied"})
 		return
 	}
-	writeJSON(w, http.StatusOK, map[string]any{"status": "ready"})
+
+	checks, requiredOK, allOK := h.checkProtocols(ctx)
+	switch {
+	case !requiredOK:
+		writeJSON(w, http.StatusServiceUnavailable, map[string]any{"status": "not_ready", "reason": "protocol_unhealthy", "protocols": checks})
+	case !allOK:
+		writeJSON(w, http.StatusOK, map[string]any{"status": "degraded", "protocols": checks})
+	default:
+		writeJSON(w, http.StatusOK, map[string]any{"status": "ready", "protocols": checks})
+	}
+}
+
+func (h *HealthHandlers) checkProtocols(ctx context.Context) (map[string]componentCheck, bool, bool) {
+	checks := make(map[string]componentCheck, len(h.protocols))
+	requiredOK, allOK := true, true
+	for _, p := range h.protocols {
+		if err := p.check(ctx); err != nil {
+			checks[p.name] = componentCheck{"status": "unhealthy", "required": p.required, "error": err.Error()}
+			allOK = false
+			if p.required {
+				requiredOK = false
+			}
+			continue
+		}
+		checks[p.name] = componentCheck{"status": "healthy", "required": p.required}
+	}
+	return checks, requiredOK, allOK
 }
 
 func (h *HealthHandlers) Health(w http.ResponseWriter, r *http.Request) {
@@ -87,6 +155,16 @@ func (h *HealthHandlers) Health(w http.ResponseWriter, r *http.Request) {
 	if checks["websocket_hub"]["status"] != "healthy" {
 		healthy = false
 	}
+	if h.ConnectorCache != nil {
+		checks["connector_cache"] = h.checkConnectorCache()
+	}
+	protocols, requiredOK, _ := h.checkProtocols(r.Context())
+	for name, check := range protocols {
+		checks["protocol:"+name] = check
+	}
+	if !requiredOK {
+		healthy = false
+	}
 
 	status := "healthy"
 	code := http.StatusOK
@@ -150,6 +228,22 @@ func (h *HealthHandlers) checkWebSocketHub() componentCheck {
 	return componentCheck{"status": "healthy", "connections": h.hub.TotalConnections()}
 }
 
+func (h *HealthHandlers) checkConnectorCache() componentCheck {
+	stats := h.ConnectorCache.Stats()
+	hitRatio := 0.0
+	if lookups := stats.Hits + stats.Misses; lookups > 0 {
+		hitRatio = float64(stats.Hits) / float64(lookups)
+	}
+	return componentCheck{
+		"status":    "healthy",
+		"entries":   stats.Entries,
+		"hits":      stats.Hits,
+		"misses":    stats.Misses,
+		"hit_ratio": hitRatio,
+		"actions":   stats.Actions,
+	}
+}
+
 // Health keeps compatibility with earlier tests/consumers.
 func Health(w http.ResponseWriter, r *http.Request) {
 	NewHealthHandlers(nil, nil, nil).Health(w, r)
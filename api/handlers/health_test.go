@@ -13,6 +13,8 @@ import (
 	"path/filepath"
 	"sync"
 	"testing"
+
+	"github.com/neural-chilli/aceryx/internal/migrations"
 )
 
 type fakeHealthState struct {
@@ -21,6 +23,17 @@ type fakeHealthState struct {
 	migrationErr   error
 }
 
+// appliedLatest is a fake state whose schema_migrations includes every
+// embedded migration.
+func appliedLatest(t *testing.T) *fakeHealthState {
+	t.Helper()
+	latest, err := migrations.LatestVersion()
+	if err != nil {
+		t.Fatalf("latest migration version: %v", err)
+	}
+	return &fakeHealthState{migrationCount: latest}
+}
+
 type fakeHealthDriver struct{}
 
 type fakeHealthConn struct {
@@ -63,8 +76,8 @@ func (c *fakeHealthConn) QueryContext(_ context.Context, query string, _ []drive
 	if c.state.migrationErr != nil {
 		return nil, c.state.migrationErr
 	}
-	if query == "SELECT COUNT(*) FROM schema_migrations" {
-		return &fakeHealthRows{cols: []string{"count"}, vals: [][]driver.Value{{int64(c.state.migrationCount)}}, i: -1}, nil
+	if query == "SELECT COALESCE(MAX(version), 0) FROM schema_migrations" {
+		return &fakeHealthRows{cols: []string{"max"}, vals: [][]driver.Value{{int64(c.state.migrationCount)}}, i: -1}, nil
 	}
 	return nil, errors.New("unexpected query")
 }
@@ -138,7 +151,15 @@ func TestReadinessBeforeAndAfterMigrations(t *testing.T) {
 		t.Fatalf("expected 503 before migrations, got %d", rr.Code)
 	}
 
-	readyDB := openFakeHealthDB(t, &fakeHealthState{migrationCount: 3})
+	staleDB := openFakeHealthDB(t, &fakeHealthState{migrationCount: 3})
+	stale := NewHealthHandlers(staleDB, nil, nil)
+	rrStale := httptest.NewRecorder()
+	stale.Readiness(rrStale, httptest.NewRequest(http.MethodGet, "/readyz", nil))
+	if rrStale.Code != http.StatusServiceUnavailable {
+		t.Fatalf("expected 503 with pending migrations, got %d", rrStale.Code)
+	}
+
+	readyDB := openFakeHealthDB(t, appliedLatest(t))
 	ready := NewHealthHandlers(readyDB, nil, nil)
 	rr2 := httptest.NewRecorder()
 	ready.Readiness(rr2, httptest.NewRequest(http.MethodGet, "/readyz", nil))
@@ -147,6 +168,42 @@ func TestReadinessBeforeAndAfterMigrations(t *testing.T) {
 	}
 }
 
+func TestReadiness_ProtocolChecks(t *testing.T) {
+	t.Setenv("ACERYX_VAULT_ROOT", t.TempDir())
+	down := func(context.Context) error { return errors.New("circuit open") }
+	up := func(context.Context) error { return nil }
+
+	cases := []struct {
+		name       string
+		required   bool
+		check      ProtocolHealthCheck
+		wantCode   int
+		wantStatus string
+	}{
+		{name: "healthy", required: true, check: up, wantCode: http.StatusOK, wantStatus: "ready"},
+		{name: "optional_down", required: false, check: down, wantCode: http.StatusOK, wantStatus: "degraded"},
+		{name: "required_down", required: true, check: down, wantCode: http.StatusServiceUnavailable, wantStatus: "not_ready"},
+	}
+	for _, tc := range cases {
+		t.Run(tc.name, func(t *testing.T) {
+			h := NewHealthHandlers(openFakeHealthDB(t, appliedLatest(t)), nil, nil)
+			h.AddProtocolCheck("mcp", tc.required, tc.check)
+			rr := httptest.NewRecorder()
+			h.Readiness(rr, httptest.NewRequest(http.MethodGet, "/health/ready", nil))
+			if rr.Code != tc.wantCode {
+				t.Fatalf("expected %d, got %d", tc.wantCode, rr.Code)
+			}
+			var body map[string]any
+			if err := json.Unmarshal(rr.Body.Bytes(), &body); err != nil {
+				t.Fatalf("decode body: %v", err)
+			}
+			if body["status"] != tc.wantStatus {
+				t.Fatalf("expected status %s, got %v", tc.wantStatus, body["status"])
+			}
+		})
+	}
+}
+
 func TestHealth_VaultUnhealthyWhenPathInvalid(t *testing.T) {
 	badPath := filepath.Join(t.TempDir(), "missing", "vault")
 	_ = os.RemoveAll(badPath)
@@ -2,6 +2,8 @@ package handlers
 
 import (
 	"encoding/json"
+	"errors"
+	"io"
 	"net/http"
 	"net/url"
 	"strings"
@@ -84,14 +86,28 @@ func (h *MCPHandlers) Refresh(w http.ResponseWriter, r *http.Request) {
 		return
 	}
 	var req mcp.RefreshRequest
-	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil && !errors.Is(err, io.EOF) {
 		writeError(w, http.StatusBadRequest, "invalid_json")
 		return
 	}
-	tools, err := h.API.Refresh(r.Context(), principal.TenantID, req)
+	tools, report, err := h.API.Refresh(r.Context(), principal.TenantID, req)
+	if err != nil {
+		writeJSON(w, http.StatusBadRequest, map[string]any{"error": err.Error(), "report": report})
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"tools": tools, "report": report})
+}
+
+func (h *MCPHandlers) ToolVersions(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	versions, err := h.API.ToolVersions(r.Context(), principal.TenantID, r.URL.Query().Get("server_url"), r.URL.Query().Get("tool"))
 	if err != nil {
 		writeError(w, http.StatusBadRequest, err.Error())
 		return
 	}
-	writeJSON(w, http.StatusOK, map[string]any{"tools": tools})
+	writeJSON(w, http.StatusOK, map[string]any{"versions": versions})
 }
@@ -0,0 +1,119 @@
+package handlers
+
+import (
+	"encoding/json"
+	"errors"
+	"net/http"
+
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/mocktools"
+)
+
+type MockProfileHandlers struct {
+	Store *mocktools.Store
+}
+
+func NewMockProfileHandlers(store *mocktools.Store) *MockProfileHandlers {
+	return &MockProfileHandlers{Store: store}
+}
+
+func (h *MockProfileHandlers) Create(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	var req mocktools.ProfileRequest
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	profile, err := h.Store.Create(r.Context(), principal.TenantID, principal.ID, req)
+	if err != nil {
+		switch {
+		case errors.Is(err, mocktools.ErrProfileExists):
+			writeError(w, http.StatusConflict, "already_exists")
+		case errors.Is(err, mocktools.ErrInvalidProfile):
+			writeError(w, http.StatusBadRequest, err.Error())
+		default:
+			writeInternalServerError(w, r, err)
+		}
+		return
+	}
+	writeJSON(w, http.StatusCreated, profile)
+}
+
+func (h *MockProfileHandlers) List(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	profiles, err := h.Store.List(r.Context(), principal.TenantID)
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, profiles)
+}
+
+func (h *MockProfileHandlers) Get(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	profile, err := h.Store.Get(r.Context(), principal.TenantID, r.PathValue("name"))
+	if err != nil {
+		if errors.Is(err, mocktools.ErrProfileNotFound) {
+			writeError(w, http.StatusNotFound, "not_found")
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSONConditional(w, r, profile, profile.UpdatedAt)
+}
+
+func (h *MockProfileHandlers) Update(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	var req mocktools.ProfileRequest
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	profile, err := h.Store.Update(r.Context(), principal.TenantID, r.PathValue("name"), req)
+	if err != nil {
+		switch {
+		case errors.Is(err, mocktools.ErrProfileNotFound):
+			writeError(w, http.StatusNotFound, "not_found")
+		case errors.Is(err, mocktools.ErrInvalidProfile):
+			writeError(w, http.StatusBadRequest, err.Error())
+		default:
+			writeInternalServerError(w, r, err)
+		}
+		return
+	}
+	writeJSON(w, http.StatusOK, profile)
+}
+
+func (h *MockProfileHandlers) Delete(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	if err := h.Store.Delete(r.Context(), principal.TenantID, r.PathValue("name")); err != nil {
+		if errors.Is(err, mocktools.ErrProfileNotFound) {
+			writeError(w, http.StatusNotFound, "not_found")
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"status": "deleted"})
+}
@@ -0,0 +1,65 @@
+package handlers
+
+import (
+	"errors"
+	"net/http"
+	"strconv"
+	"strings"
+)
+
+const (
+	defaultListLimit = 50
+	maxListLimit     = 500
+)
+
+var (
+	errInvalidLimit   = errors.New("invalid_limit")
+	errInvalidOffset  = errors.New("invalid_offset")
+	errInvalidSortDir = errors.New("invalid_sort_dir")
+)
+
+// listParams are the paging and ordering query parameters shared by list
+// endpoints: limit, offset, sort_by and sort_dir.
+type listParams struct {
+	Limit      int
+	Offset     int
+	SortBy     string
+	Descending bool
+}
+
+// listEnvelope is the response body of paginated list endpoints. NextCursor
+// is set by endpoints that support cursor paging when another page follows.
+type listEnvelope struct {
+	Items      any    `json:"items"`
+	Total      int    `json:"total"`
+	Limit      int    `json:"limit"`
+	Offset     int    `json:"offset"`
+	NextCursor string `json:"next_cursor,omitempty"`
+}
+
+func parseListParams(r *http.Request) (listParams, error) {
+	q := r.URL.Query()
+	params := listParams{Limit: defaultListLimit, SortBy: strings.TrimSpace(q.Get("sort_by"))}
+	if raw := strings.TrimSpace(q.Get("limit")); raw != "" {
+		n, err := strconv.Atoi(raw)
+		if err != nil || n < 1 {
+			return listParams{}, errInvalidLimit
+		}
+		params.Limit = min(n, maxListLimit)
+	}
+	if raw := strings.TrimSpace(q.Get("offset")); raw != "" {
+		n, err := strconv.Atoi(raw)
+		if err != nil || n < 0 {
+			return listParams{}, errInvalidOffset
+		}
+		params.Offset = n
+	}
+	switch strings.ToLower(strings.TrimSpace(q.Get("sort_dir"))) {
+	case "", "asc":
+	case "desc":
+		params.Descending = true
+	default:
+		return listParams{}, errInvalidSortDir
+	}
+	return params, nil
+}
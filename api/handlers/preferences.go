@@ -0,0 +1,74 @@
+package handlers
+
+import (
+	"encoding/json"
+	"errors"
+	"net/http"
+
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/rbac"
+)
+
+// GetDisplayPreferences returns the caller's display preferences with the
+// workspace defaults they fall back to.
+func (h *AuthHandlers) GetDisplayPreferences(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	view, err := h.Auth.DisplayPreferences(r.Context(), principal.TenantID, principal.ID)
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, view)
+}
+
+// PutDisplayPreferences replaces the caller's display preferences.
+func (h *AuthHandlers) PutDisplayPreferences(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	var req rbac.DisplayPreferences
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	view, err := h.Auth.UpdateDisplayPreferences(r.Context(), principal.TenantID, principal.ID, req)
+	if err != nil {
+		if errors.Is(err, rbac.ErrInvalidPreference) || errors.Is(err, rbac.ErrThemeNotFound) {
+			writeError(w, http.StatusBadRequest, err.Error())
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, view)
+}
+
+// PutWorkspacePreferences replaces the tenant's default display preferences.
+func (h *AuthHandlers) PutWorkspacePreferences(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	var req rbac.DisplayPreferences
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	prefs, err := h.Auth.UpdateWorkspacePreferences(r.Context(), principal.TenantID, req)
+	if err != nil {
+		if errors.Is(err, rbac.ErrInvalidPreference) {
+			writeError(w, http.StatusBadRequest, err.Error())
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, prefs)
+}
@@ -0,0 +1,69 @@
+package handlers
+
+import (
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"io"
+	"net/http"
+
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/workflows"
+)
+
+// ReplayExecution reruns a case's workflow, or one step, with the inputs
+// recorded when it ran. {id} is a case ID or an asynchronous execution ID.
+func (h *CaseHandlers) ReplayExecution(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	id, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	if h.Workflows == nil {
+		writeError(w, http.StatusNotFound, "not_found")
+		return
+	}
+	caseID, ok := resolveExecutionCase(w, r, h.Queue, principal.TenantID, id)
+	if !ok {
+		return
+	}
+	if principal.Scope != nil && len(principal.Scope.WorkflowIDs) > 0 {
+		c, err := h.Cases.GetCase(r.Context(), principal.TenantID, caseID)
+		if err != nil {
+			if errors.Is(err, sql.ErrNoRows) {
+				writeError(w, http.StatusNotFound, "not_found")
+				return
+			}
+			writeInternalServerError(w, r, err)
+			return
+		}
+		if !h.authorizeTokenCaseType(w, r, principal, c.CaseType) {
+			return
+		}
+	}
+	var req workflows.ReplayRequest
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil && !errors.Is(err, io.EOF) {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	out, err := h.Workflows.Replay(r.Context(), principal.TenantID, principal.ID, caseID, req)
+	if err != nil {
+		switch {
+		case errors.Is(err, sql.ErrNoRows), errors.Is(err, workflows.ErrStepNotFound):
+			writeError(w, http.StatusNotFound, "not_found")
+		case errors.Is(err, workflows.ErrStepNotTestable):
+			writeError(w, http.StatusBadRequest, "step_not_testable")
+		case errors.Is(err, workflows.ErrNoRecordedInputs), errors.Is(err, workflows.ErrReplayRejected):
+			writeError(w, http.StatusUnprocessableEntity, err.Error())
+		case writeQuotaError(w, err):
+		default:
+			writeInternalServerError(w, r, err)
+		}
+		return
+	}
+	writeJSON(w, http.StatusOK, out)
+}
@@ -0,0 +1,70 @@
+package handlers
+
+import (
+	"net/http"
+	"slices"
+	"strings"
+
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/search"
+)
+
+const maxSearchQueryLength = 256
+
+type SearchHandlers struct {
+	Service *search.Service
+}
+
+func NewSearchHandlers(svc *search.Service) *SearchHandlers {
+	return &SearchHandlers{Service: svc}
+}
+
+// Search ranks workflows and tools against q. kind narrows the results to a
+// comma-separated list of document kinds.
+func (h *SearchHandlers) Search(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	query := strings.TrimSpace(r.URL.Query().Get("q"))
+	if query == "" {
+		writeError(w, http.StatusBadRequest, "query_required")
+		return
+	}
+	if len(query) > maxSearchQueryLength {
+		writeError(w, http.StatusBadRequest, "query_too_long")
+		return
+	}
+	params, err := parseListParams(r)
+	if err != nil {
+		writeError(w, http.StatusBadRequest, err.Error())
+		return
+	}
+	if params.SortBy != "" {
+		writeError(w, http.StatusBadRequest, "invalid_sort")
+		return
+	}
+	var kinds []string
+	if raw := strings.TrimSpace(r.URL.Query().Get("kind")); raw != "" {
+		known := h.Service.Kinds()
+		for _, kind := range strings.Split(raw, ",") {
+			kind = strings.TrimSpace(kind)
+			if !slices.Contains(known, kind) {
+				writeError(w, http.StatusBadRequest, "invalid_kind")
+				return
+			}
+			kinds = append(kinds, kind)
+		}
+	}
+
+	hits, err := h.Service.Search(r.Context(), principal.TenantID, query, kinds...)
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	total := len(hits)
+	start := min(params.Offset, total)
+	end := min(start+params.Limit, total)
+	writeJSON(w, http.StatusOK, listEnvelope{Items: hits[start:end], Total: total, Limit: params.Limit, Offset: params.Offset})
+}
@@ -0,0 +1,102 @@
+package handlers
+
+import (
+	"database/sql"
+	"errors"
+	"net/http"
+
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/cluster"
+	"github.com/neural-chilli/aceryx/internal/config"
+	"github.com/neural-chilli/aceryx/internal/engine"
+	"github.com/neural-chilli/aceryx/internal/observability"
+)
+
+type SystemHandlers struct {
+	Reloader *config.Reloader
+	Cluster  *cluster.Node
+	// DB and Engine, when set, have their pool usage reported by Runtime.
+	DB     *sql.DB
+	Engine *engine.Engine
+}
+
+type runtimeResponse struct {
+	observability.RuntimeStats
+	WorkerPool    workerPoolStats             `json:"worker_pool"`
+	DBPool        *dbPoolStats                `json:"db_pool,omitempty"`
+	ToolAnomalies []observability.ToolAnomaly `json:"tool_anomalies"`
+}
+
+type workerPoolStats struct {
+	Active   int                              `json:"active"`
+	Capacity int                              `json:"capacity"`
+	Lanes    map[engine.Lane]engine.LaneStats `json:"lanes,omitempty"`
+}
+
+type dbPoolStats struct {
+	Open           int   `json:"open"`
+	InUse          int   `json:"in_use"`
+	Idle           int   `json:"idle"`
+	MaxOpen        int   `json:"max_open"`
+	WaitCount      int64 `json:"wait_count"`
+	WaitDurationMs int64 `json:"wait_duration_ms"`
+}
+
+func NewSystemHandlers(reloader *config.Reloader) *SystemHandlers {
+	return &SystemHandlers{Reloader: reloader}
+}
+
+// Reload re-reads ACERYX_CONFIG_FILE and applies the settings that can change
+// without a restart.
+func (h *SystemHandlers) Reload(w http.ResponseWriter, r *http.Request) {
+	result, err := h.Reloader.Reload()
+	switch {
+	case errors.Is(err, config.ErrNoConfigFile):
+		writeError(w, http.StatusConflict, "config_file_not_configured")
+	case err != nil:
+		writeInternalServerError(w, r, err)
+	default:
+		writeJSON(w, http.StatusOK, result)
+	}
+}
+
+// ClusterStatus lists the replicas sharing the database, which one leads and
+// when each last sent a heartbeat.
+func (h *SystemHandlers) ClusterStatus(w http.ResponseWriter, r *http.Request) {
+	if h.Cluster == nil {
+		writeError(w, http.StatusNotFound, "not_found")
+		return
+	}
+	out, err := h.Cluster.Status(r.Context())
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, out)
+}
+
+// Runtime reports this replica's uptime, memory, goroutines, worker and
+// database pool usage, storage latency percentiles, and the caller's tenant's
+// anomalous tools.
+func (h *SystemHandlers) Runtime(w http.ResponseWriter, r *http.Request) {
+	out := runtimeResponse{RuntimeStats: observability.ReadRuntimeStats()}
+	if principal := middleware.PrincipalFromContext(r.Context()); principal != nil {
+		out.ToolAnomalies = observability.ToolAnomalies(principal.TenantID.String())
+	} else {
+		out.ToolAnomalies = []observability.ToolAnomaly{}
+	}
+	out.WorkerPool.Active, out.WorkerPool.Capacity = h.Engine.WorkerPoolStats()
+	out.WorkerPool.Lanes = h.Engine.LaneStats()
+	if h.DB != nil {
+		stats := h.DB.Stats()
+		out.DBPool = &dbPoolStats{
+			Open:           stats.OpenConnections,
+			InUse:          stats.InUse,
+			Idle:           stats.Idle,
+			MaxOpen:        stats.MaxOpenConnections,
+			WaitCount:      stats.WaitCount,
+			WaitDurationMs: stats.WaitDuration.Milliseconds(),
+		}
+	}
+	writeJSON(w, http.StatusOK, out)
+}
@@ -0,0 +1,119 @@
+package handlers
+
+import (
+	"encoding/json"
+	"errors"
+	"net/http"
+
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/workflows"
+)
+
+// TagHandlers manage the tenant's workflow tags as a whole. Tags are set on
+// individual workflows through WorkflowHandlers.SetTags.
+type TagHandlers struct {
+	Service *workflows.Service
+}
+
+func NewTagHandlers(service *workflows.Service) *TagHandlers {
+	return &TagHandlers{Service: service}
+}
+
+func (h *TagHandlers) List(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	tags, err := h.Service.ListTags(r.Context(), principal.TenantID)
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"tags": tags})
+}
+
+func (h *TagHandlers) Rename(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	var req struct {
+		Name string `json:"name"`
+	}
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	tag, err := h.Service.RenameTag(r.Context(), principal.TenantID, r.PathValue("name"), req.Name)
+	if err != nil {
+		writeTagError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, tag)
+}
+
+func (h *TagHandlers) Merge(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	var req struct {
+		Sources []string `json:"sources"`
+		Target  string   `json:"target"`
+	}
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	tag, err := h.Service.MergeTags(r.Context(), principal.TenantID, req.Sources, req.Target)
+	if err != nil {
+		writeTagError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, tag)
+}
+
+func (h *TagHandlers) Delete(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	if err := h.Service.DeleteTag(r.Context(), principal.TenantID, r.PathValue("name")); err != nil {
+		writeTagError(w, r, err)
+		return
+	}
+	w.WriteHeader(http.StatusNoContent)
+}
+
+func (h *TagHandlers) Prune(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	deleted, err := h.Service.PruneTags(r.Context(), principal.TenantID)
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"deleted": deleted})
+}
+
+func writeTagError(w http.ResponseWriter, r *http.Request, err error) {
+	switch {
+	case errors.Is(err, workflows.ErrInvalidTag):
+		writeError(w, http.StatusBadRequest, "invalid_tag")
+	case errors.Is(err, workflows.ErrTagNotFound):
+		writeError(w, http.StatusNotFound, "tag_not_found")
+	case errors.Is(err, workflows.ErrTagExists):
+		writeError(w, http.StatusConflict, "tag_exists")
+	case errors.Is(err, workflows.ErrTagInUse):
+		writeError(w, http.StatusConflict, "tag_in_use")
+	default:
+		writeInternalServerError(w, r, err)
+	}
+}
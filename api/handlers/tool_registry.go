@@ -0,0 +1,283 @@
+package handlers
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"io"
+	"net/http"
+	"strings"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/customtools"
+	"github.com/neural-chilli/aceryx/internal/mcp"
+	"github.com/neural-chilli/aceryx/internal/observability"
+)
+
+// ToolRegistryHandlers serve the tool registry: every tool the tenant can
+// call, grouped by the protocol it comes from, with that protocol's health.
+type ToolRegistryHandlers struct {
+	MCP    *mcp.API
+	Custom *customtools.CustomHTTPProtocol
+	Health *HealthHandlers
+}
+
+func NewToolRegistryHandlers(mcpAPI *mcp.API, custom *customtools.CustomHTTPProtocol, health *HealthHandlers) *ToolRegistryHandlers {
+	return &ToolRegistryHandlers{MCP: mcpAPI, Custom: custom, Health: health}
+}
+
+// toolProtocolGroup is one protocol in the registry. Status is "healthy",
+// "degraded" when a source failed its last refresh or one of its tools is
+// anomalous, or "unhealthy" when the protocol's health check fails; Error
+// then says why.
+type toolProtocolGroup struct {
+	Protocol      string       `json:"protocol"`
+	Status        string       `json:"status"`
+	Error         string       `json:"error,omitempty"`
+	LastRefreshed *time.Time   `json:"last_refreshed,omitempty"`
+	ToolCount     int          `json:"tool_count"`
+	Sources       []toolSource `json:"sources"`
+}
+
+// toolSource is where a protocol's tools come from, such as one MCP server.
+// Anomalies are its tools whose recent failure rate or latency on this
+// replica deviates from their baseline.
+type toolSource struct {
+	Name          string                      `json:"name"`
+	Status        string                      `json:"status"`
+	Error         string                      `json:"error,omitempty"`
+	LastRefreshed *time.Time                  `json:"last_refreshed,omitempty"`
+	Tools         []toolSummary               `json:"tools"`
+	Anomalies     []observability.ToolAnomaly `json:"anomalies,omitempty"`
+}
+
+type toolSummary struct {
+	Name        string `json:"name"`
+	Description string `json:"description,omitempty"`
+	Version     string `json:"version,omitempty"`
+}
+
+type toolRefreshRequest struct {
+	Source string `json:"source"`
+}
+
+var toolRegistryProtocols = []string{"mcp", customtools.ProtocolName}
+
+func (h *ToolRegistryHandlers) List(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	groups := make([]toolProtocolGroup, 0, len(toolRegistryProtocols))
+	for _, protocol := range toolRegistryProtocols {
+		group, err := h.group(r.Context(), principal.TenantID, protocol)
+		if err != nil {
+			writeInternalServerError(w, r, err)
+			return
+		}
+		groups = append(groups, group)
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"protocols": groups})
+}
+
+// Refresh re-discovers the tools of one protocol, or of one of its sources
+// when the body names it. Sources that fail keep their tools and report the
+// error inline rather than failing the request.
+func (h *ToolRegistryHandlers) Refresh(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	protocol := strings.ToLower(strings.TrimSpace(r.PathValue("protocol")))
+	if !knownToolProtocol(protocol) {
+		writeError(w, http.StatusNotFound, "unknown_protocol")
+		return
+	}
+	var req toolRefreshRequest
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil && !errors.Is(err, io.EOF) {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	req.Source = strings.TrimSpace(req.Source)
+
+	current, err := h.group(r.Context(), principal.TenantID, protocol)
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	if req.Source != "" && !current.hasSource(req.Source) {
+		writeError(w, http.StatusNotFound, "source_not_found")
+		return
+	}
+
+	failures := map[string]string{}
+	if protocol == "mcp" {
+		// A failed refresh only marks the server stale, so its error is
+		// carried over from the report.
+		_, report, _ := h.MCP.Refresh(r.Context(), principal.TenantID, mcp.RefreshRequest{ServerURL: req.Source})
+		for _, server := range report.Servers {
+			if server.Error != "" {
+				failures[server.ServerURL] = server.Error
+			}
+		}
+	}
+	// Custom tools are defined in Aceryx, so re-reading them is the refresh.
+	group, err := h.group(r.Context(), principal.TenantID, protocol)
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	group.applyFailures(failures)
+	writeJSON(w, http.StatusOK, group)
+}
+
+func (h *ToolRegistryHandlers) group(ctx context.Context, tenantID uuid.UUID, protocol string) (toolProtocolGroup, error) {
+	var group toolProtocolGroup
+	switch protocol {
+	case "mcp":
+		servers, err := h.MCP.List(ctx, tenantID)
+		if err != nil {
+			return toolProtocolGroup{}, err
+		}
+		group = mcpToolGroup(servers)
+	case customtools.ProtocolName:
+		var tools []customtools.Tool
+		if h.Custom != nil {
+			var err error
+			if tools, err = h.Custom.Tools(ctx, tenantID); err != nil {
+				return toolProtocolGroup{}, err
+			}
+		}
+		group = customToolGroup(tools)
+	}
+	group.applyAnomalies(observability.ToolAnomalies(tenantID.String()))
+	if h.Health != nil {
+		if registered, err := h.Health.CheckProtocol(ctx, protocol); registered && err != nil {
+			group.Status = "unhealthy"
+			group.Error = err.Error()
+		}
+	}
+	return group, nil
+}
+
+func mcpToolGroup(servers []mcp.CachedServer) toolProtocolGroup {
+	sources := make([]toolSource, 0, len(servers))
+	for _, server := range servers {
+		tools := make([]toolSummary, 0, len(server.Tools))
+		for _, tool := range server.Tools {
+			tools = append(tools, toolSummary{Name: tool.Name, Description: tool.Description, Version: tool.Version})
+		}
+		refreshed := server.LastDiscovered
+		sources = append(sources, toolSource{
+			Name:          server.ServerURL,
+			Status:        server.Status,
+			Error:         server.ErrorMessage,
+			LastRefreshed: &refreshed,
+			Tools:         tools,
+		})
+	}
+	return newToolProtocolGroup("mcp", sources)
+}
+
+func customToolGroup(tools []customtools.Tool) toolProtocolGroup {
+	if len(tools) == 0 {
+		return newToolProtocolGroup(customtools.ProtocolName, []toolSource{})
+	}
+	source := toolSource{Name: "custom", Status: "active", Tools: make([]toolSummary, 0, len(tools))}
+	for _, tool := range tools {
+		source.Tools = append(source.Tools, toolSummary{Name: tool.Name, Description: tool.Description})
+		if source.LastRefreshed == nil || tool.UpdatedAt.After(*source.LastRefreshed) {
+			updated := tool.UpdatedAt
+			source.LastRefreshed = &updated
+		}
+	}
+	return newToolProtocolGroup(customtools.ProtocolName, []toolSource{source})
+}
+
+func newToolProtocolGroup(protocol string, sources []toolSource) toolProtocolGroup {
+	group := toolProtocolGroup{Protocol: protocol, Sources: sources}
+	group.summarize()
+	return group
+}
+
+// summarize derives the group's status, tool count and last refresh from its
+// sources.
+func (g *toolProtocolGroup) summarize() {
+	g.Status = "healthy"
+	g.ToolCount = 0
+	g.LastRefreshed = nil
+	for _, source := range g.Sources {
+		g.ToolCount += len(source.Tools)
+		if source.Status != "active" {
+			g.Status = "degraded"
+		}
+		if source.LastRefreshed != nil && (g.LastRefreshed == nil || source.LastRefreshed.After(*g.LastRefreshed)) {
+			g.LastRefreshed = source.LastRefreshed
+		}
+	}
+}
+
+func (g *toolProtocolGroup) applyFailures(failures map[string]string) {
+	if len(failures) == 0 {
+		return
+	}
+	for i := range g.Sources {
+		if msg, ok := failures[g.Sources[i].Name]; ok {
+			g.Sources[i].Status = "error"
+			g.Sources[i].Error = msg
+		}
+	}
+	if g.Status == "healthy" {
+		g.Status = "degraded"
+	}
+}
+
+// applyAnomalies attaches the anomalies of the group's protocol to the
+// sources listing their tools, and degrades a healthy group that has any.
+func (g *toolProtocolGroup) applyAnomalies(anomalies []observability.ToolAnomaly) {
+	for _, anomaly := range anomalies {
+		if anomaly.Protocol != g.Protocol {
+			continue
+		}
+		for i := range g.Sources {
+			if !g.Sources[i].hasTool(anomaly.ToolID) {
+				continue
+			}
+			g.Sources[i].Anomalies = append(g.Sources[i].Anomalies, anomaly)
+			if g.Status == "healthy" {
+				g.Status = "degraded"
+			}
+		}
+	}
+}
+
+func (s toolSource) hasTool(name string) bool {
+	for _, tool := range s.Tools {
+		if tool.Name == name {
+			return true
+		}
+	}
+	return false
+}
+
+func (g toolProtocolGroup) hasSource(name string) bool {
+	for _, source := range g.Sources {
+		if source.Name == name {
+			return true
+		}
+	}
+	return false
+}
+
+func knownToolProtocol(protocol string) bool {
+	for _, known := range toolRegistryProtocols {
+		if protocol == known {
+			return true
+		}
+	}
+	return false
+}
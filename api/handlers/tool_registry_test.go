@@ -0,0 +1,83 @@
+package handlers
+
+import (
+	"testing"
+	"time"
+
+	"github.com/neural-chilli/aceryx/internal/customtools"
+	"github.com/neural-chilli/aceryx/internal/mcp"
+	"github.com/neural-chilli/aceryx/internal/observability"
+)
+
+func TestMCPToolGroup(t *testing.T) {
+	older := time.Date(2026, 1, 1, 9, 0, 0, 0, time.UTC)
+	newer := older.Add(time.Hour)
+	group := mcpToolGroup([]mcp.CachedServer{
+		{ServerURL: "https://a.example", Status: "active", LastDiscovered: older, Tools: []mcp.MCPTool{{Name: "lookup"}, {Name: "search"}}},
+		{ServerURL: "https://b.example", Status: "stale", LastDiscovered: newer, Tools: []mcp.MCPTool{{Name: "fetch"}}},
+	})
+	if group.Protocol != "mcp" || group.ToolCount != 3 || len(group.Sources) != 2 {
+		t.Fatalf("unexpected group: %+v", group)
+	}
+	if group.Status != "degraded" {
+		t.Fatalf("expected a stale server to degrade the group, got %s", group.Status)
+	}
+	if group.LastRefreshed == nil || !group.LastRefreshed.Equal(newer) {
+		t.Fatalf("expected last refresh %s, got %v", newer, group.LastRefreshed)
+	}
+
+	if healthy := mcpToolGroup(nil); healthy.Status != "healthy" || healthy.LastRefreshed != nil {
+		t.Fatalf("expected an empty group to be healthy, got %+v", healthy)
+	}
+}
+
+func TestToolProtocolGroupApplyFailures(t *testing.T) {
+	group := mcpToolGroup([]mcp.CachedServer{
+		{ServerURL: "https://a.example", Status: "active"},
+		{ServerURL: "https://b.example", Status: "active"},
+	})
+	group.applyFailures(map[string]string{"https://b.example": "connection refused"})
+	if group.Status != "degraded" {
+		t.Fatalf("expected degraded, got %s", group.Status)
+	}
+	if got := group.Sources[1]; got.Status != "error" || got.Error != "connection refused" {
+		t.Fatalf("expected failure on second source, got %+v", got)
+	}
+	if got := group.Sources[0]; got.Status != "active" || got.Error != "" {
+		t.Fatalf("expected first source untouched, got %+v", got)
+	}
+}
+
+func TestToolProtocolGroupApplyAnomalies(t *testing.T) {
+	group := mcpToolGroup([]mcp.CachedServer{
+		{ServerURL: "https://a.example", Status: "active", Tools: []mcp.MCPTool{{Name: "lookup"}}},
+		{ServerURL: "https://b.example", Status: "active", Tools: []mcp.MCPTool{{Name: "search"}}},
+	})
+	group.applyAnomalies([]observability.ToolAnomaly{
+		{Protocol: "mcp", ToolID: "search", Kind: observability.ToolAnomalyLatency},
+		{Protocol: customtools.ProtocolName, ToolID: "lookup", Kind: observability.ToolAnomalyFailureRate},
+	})
+	if group.Status != "degraded" {
+		t.Fatalf("expected an anomalous tool to degrade the group, got %s", group.Status)
+	}
+	if len(group.Sources[0].Anomalies) != 0 || len(group.Sources[1].Anomalies) != 1 {
+		t.Fatalf("expected the anomaly on the source listing the tool only, got %+v", group.Sources)
+	}
+}
+
+func TestCustomToolGroup(t *testing.T) {
+	updated := time.Date(2026, 3, 1, 0, 0, 0, 0, time.UTC)
+	group := customToolGroup([]customtools.Tool{
+		{Name: "crm_lookup", UpdatedAt: updated.Add(-time.Hour)},
+		{Name: "ledger_post", UpdatedAt: updated},
+	})
+	if group.Protocol != customtools.ProtocolName || group.ToolCount != 2 || group.Status != "healthy" {
+		t.Fatalf("unexpected group: %+v", group)
+	}
+	if group.LastRefreshed == nil || !group.LastRefreshed.Equal(updated) {
+		t.Fatalf("expected last refresh %s, got %v", updated, group.LastRefreshed)
+	}
+	if !knownToolProtocol(customtools.ProtocolName) || knownToolProtocol("grpc") {
+		t.Fatal("unexpected known protocols")
+	}
+}
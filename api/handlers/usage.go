@@ -0,0 +1,103 @@
+package handlers
+
+import (
+	"encoding/json"
+	"errors"
+	"math"
+	"net/http"
+	"strconv"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/quotas"
+)
+
+type UsageHandlers struct {
+	Service *quotas.Service
+}
+
+func NewUsageHandlers(svc *quotas.Service) *UsageHandlers {
+	return &UsageHandlers{Service: svc}
+}
+
+// Get reports the caller's and the workspace's consumption against their
+// quotas in the current windows.
+func (h *UsageHandlers) Get(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	out, err := h.Service.Report(r.Context(), principal.TenantID, principal.ID)
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, out)
+}
+
+func (h *UsageHandlers) ListQuotas(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	items, err := h.Service.ListQuotas(r.Context(), principal.TenantID)
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"quotas": items})
+}
+
+// PutQuota sets the workspace limits, or a user's when principal_id is given.
+func (h *UsageHandlers) PutQuota(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	var req struct {
+		PrincipalID *uuid.UUID `json:"principal_id"`
+		quotas.Limits
+	}
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	if err := h.Service.SetLimits(r.Context(), principal.TenantID, req.PrincipalID, req.Limits, principal.ID); err != nil {
+		if errors.Is(err, quotas.ErrInvalidLimits) {
+			writeError(w, http.StatusBadRequest, err.Error())
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, quotas.Quota{PrincipalID: req.PrincipalID, Limits: req.Limits, UpdatedAt: time.Now().UTC()})
+}
+
+// writeQuotaError answers a request refused by a quota: 429 with Retry-After
+// for the hourly execution limit, 403 for an exhausted monthly budget.
+func writeQuotaError(w http.ResponseWriter, err error) bool {
+	var exceeded *quotas.ExceededError
+	if !errors.As(err, &exceeded) {
+		return false
+	}
+	status, code := http.StatusTooManyRequests, "quota_exceeded"
+	if exceeded.Budget() {
+		status, code = http.StatusForbidden, "budget_exceeded"
+	}
+	retryAfter := int(math.Ceil(time.Until(exceeded.ResetAt).Seconds()))
+	w.Header().Set("Retry-After", strconv.Itoa(max(retryAfter, 1)))
+	writeJSON(w, status, map[string]any{
+		"error":    code,
+		"message":  exceeded.Error(),
+		"scope":    exceeded.Scope,
+		"metric":   exceeded.Metric,
+		"limit":    exceeded.Limit,
+		"used":     exceeded.Used,
+		"reset_at": exceeded.ResetAt,
+	})
+	return true
+}
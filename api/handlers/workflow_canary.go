@@ -0,0 +1,115 @@
+package handlers
+
+import (
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"io"
+	"net/http"
+	"strings"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/workflows"
+)
+
+func (h *WorkflowHandlers) GetCanary(w http.ResponseWriter, r *http.Request) {
+	principal, workflowID, ok := canaryRequest(w, r)
+	if !ok {
+		return
+	}
+	out, err := h.Service.GetCanary(r.Context(), principal.TenantID, workflowID)
+	if err != nil {
+		writeCanaryError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, out)
+}
+
+func (h *WorkflowHandlers) StartCanary(w http.ResponseWriter, r *http.Request) {
+	principal, workflowID, ok := canaryRequest(w, r)
+	if !ok {
+		return
+	}
+	var req workflows.CanaryRequest
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	out, err := h.Service.StartCanary(r.Context(), principal.TenantID, principal.ID, workflowID, req)
+	if err != nil {
+		var validationErrs *workflows.PublishValidationErrors
+		if errors.As(err, &validationErrs) {
+			writeJSON(w, http.StatusBadRequest, validationErrs)
+			return
+		}
+		if strings.HasPrefix(err.Error(), "invalid workflow ast:") {
+			writeError(w, http.StatusBadRequest, err.Error())
+			return
+		}
+		if writeLifecycleError(w, err) {
+			return
+		}
+		writeCanaryError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusCreated, out)
+}
+
+func (h *WorkflowHandlers) PromoteCanary(w http.ResponseWriter, r *http.Request) {
+	principal, workflowID, ok := canaryRequest(w, r)
+	if !ok {
+		return
+	}
+	out, err := h.Service.PromoteCanary(r.Context(), principal.TenantID, workflowID)
+	if err != nil {
+		writeCanaryError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, out)
+}
+
+func (h *WorkflowHandlers) RollbackCanary(w http.ResponseWriter, r *http.Request) {
+	principal, workflowID, ok := canaryRequest(w, r)
+	if !ok {
+		return
+	}
+	var req struct {
+		Reason string `json:"reason"`
+	}
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil && !errors.Is(err, io.EOF) {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	out, err := h.Service.RollbackCanary(r.Context(), principal.TenantID, workflowID, strings.TrimSpace(req.Reason))
+	if err != nil {
+		writeCanaryError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, out)
+}
+
+func canaryRequest(w http.ResponseWriter, r *http.Request) (*middleware.Principal, uuid.UUID, bool) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return nil, uuid.Nil, false
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	return principal, workflowID, ok
+}
+
+func writeCanaryError(w http.ResponseWriter, r *http.Request, err error) {
+	switch {
+	case errors.Is(err, workflows.ErrNoCanary):
+		writeError(w, http.StatusNotFound, err.Error())
+	case errors.Is(err, workflows.ErrCanaryActive):
+		writeError(w, http.StatusConflict, err.Error())
+	case errors.Is(err, workflows.ErrInvalidCanary):
+		writeError(w, http.StatusBadRequest, err.Error())
+	case errors.Is(err, sql.ErrNoRows):
+		writeError(w, http.StatusNotFound, "not_found")
+	default:
+		writeInternalServerError(w, r, err)
+	}
+}
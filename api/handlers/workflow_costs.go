@@ -0,0 +1,41 @@
+package handlers
+
+import (
+	"net/http"
+	"strings"
+	"time"
+
+	"github.com/neural-chilli/aceryx/api/middleware"
+)
+
+// Costs reports a workflow's historical spend in cost units, per step and
+// per day, for cases started since the since parameter (default: a month).
+func (h *WorkflowHandlers) Costs(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	since := time.Now().UTC().AddDate(0, -1, 0)
+	if raw := strings.TrimSpace(r.URL.Query().Get("since")); raw != "" {
+		t, err := time.Parse(time.RFC3339, raw)
+		if err != nil {
+			writeError(w, http.StatusBadRequest, "invalid_since")
+			return
+		}
+		since = t
+	}
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canView); !ok {
+		return
+	}
+	report, err := h.Service.Costs(r.Context(), principal.TenantID, workflowID, since)
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, report)
+}
@@ -0,0 +1,158 @@
+package handlers
+
+import (
+	"database/sql"
+	"errors"
+	"net/http"
+	"strconv"
+	"strings"
+
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/workflows"
+)
+
+// Docs generates documentation for a workflow version as Markdown (the
+// default), HTML or JSON.
+func (h *WorkflowHandlers) Docs(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	version, ok := docsVersion(w, r)
+	if !ok {
+		return
+	}
+	format := strings.ToLower(strings.TrimSpace(r.URL.Query().Get("format")))
+	if format == "" {
+		format = workflows.DocsFormatMarkdown
+	}
+	if format != workflows.DocsFormatMarkdown && format != workflows.DocsFormatHTML && format != workflows.DocsFormatJSON {
+		writeError(w, http.StatusBadRequest, "invalid_format")
+		return
+	}
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canView); !ok {
+		return
+	}
+	docs, err := h.Service.Docs(r.Context(), principal.TenantID, workflowID, version)
+	if err != nil {
+		if errors.Is(err, sql.ErrNoRows) {
+			writeError(w, http.StatusNotFound, "not_found")
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	switch format {
+	case workflows.DocsFormatJSON:
+		writeJSON(w, http.StatusOK, docs)
+	case workflows.DocsFormatHTML:
+		page, err := workflows.RenderDocsHTML(docs)
+		if err != nil {
+			writeInternalServerError(w, r, err)
+			return
+		}
+		w.Header().Set("Content-Type", "text/html; charset=utf-8")
+		w.WriteHeader(http.StatusOK)
+		_, _ = w.Write([]byte(page))
+	default:
+		w.Header().Set("Content-Type", "text/markdown; charset=utf-8")
+		w.WriteHeader(http.StatusOK)
+		_, _ = w.Write([]byte(workflows.RenderDocsMarkdown(docs)))
+	}
+}
+
+// Diagram exports a workflow version's step graph as a Mermaid flowchart
+// (the default) or a Graphviz DOT graph.
+func (h *WorkflowHandlers) Diagram(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	version, ok := docsVersion(w, r)
+	if !ok {
+		return
+	}
+	format := strings.ToLower(strings.TrimSpace(r.URL.Query().Get("format")))
+	if format == "" {
+		format = workflows.DiagramMermaid
+	}
+	if format != workflows.DiagramMermaid && format != workflows.DiagramDOT {
+		writeError(w, http.StatusBadRequest, "invalid_format")
+		return
+	}
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canView); !ok {
+		return
+	}
+	out, err := h.Service.Diagram(r.Context(), principal.TenantID, workflowID, version, format)
+	if err != nil {
+		if errors.Is(err, sql.ErrNoRows) {
+			writeError(w, http.StatusNotFound, "not_found")
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	contentType := "text/vnd.mermaid; charset=utf-8"
+	if format == workflows.DiagramDOT {
+		contentType = "text/vnd.graphviz; charset=utf-8"
+	}
+	w.Header().Set("Content-Type", contentType)
+	w.WriteHeader(http.StatusOK)
+	_, _ = w.Write([]byte(out))
+}
+
+// Dependencies reports the tools, protocols, secrets and external endpoints
+// a workflow version depends on.
+func (h *WorkflowHandlers) Dependencies(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	version, ok := docsVersion(w, r)
+	if !ok {
+		return
+	}
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canView); !ok {
+		return
+	}
+	report, err := h.Service.Dependencies(r.Context(), principal.TenantID, workflowID, version)
+	if err != nil {
+		if errors.Is(err, sql.ErrNoRows) {
+			writeError(w, http.StatusNotFound, "not_found")
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, report)
+}
+
+// docsVersion reads the optional version query parameter; 0 means the latest
+// published version, falling back to the draft.
+func docsVersion(w http.ResponseWriter, r *http.Request) (int, bool) {
+	raw := strings.TrimSpace(r.URL.Query().Get("version"))
+	if raw == "" {
+		return 0, true
+	}
+	version, err := strconv.Atoi(raw)
+	if err != nil || version <= 0 {
+		writeError(w, http.StatusBadRequest, "invalid_version")
+		return 0, false
+	}
+	return version, true
+}
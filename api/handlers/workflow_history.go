@@ -0,0 +1,144 @@
+package handlers
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"io"
+	"net/http"
+	"strconv"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/workflows"
+)
+
+// GetDraftHistory lists recent draft edits so the designer can show its undo
+// stack after a reload.
+func (h *WorkflowHandlers) GetDraftHistory(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canView); !ok {
+		return
+	}
+	limit, _ := strconv.Atoi(r.URL.Query().Get("limit"))
+	out, err := h.Service.DraftHistory(r.Context(), principal.TenantID, workflowID, limit)
+	if err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"operations": out})
+}
+
+func (h *WorkflowHandlers) UndoDraft(w http.ResponseWriter, r *http.Request) {
+	h.revertDraft(w, r, h.Service.UndoDraft)
+}
+
+func (h *WorkflowHandlers) RedoDraft(w http.ResponseWriter, r *http.Request) {
+	h.revertDraft(w, r, h.Service.RedoDraft)
+}
+
+func (h *WorkflowHandlers) revertDraft(w http.ResponseWriter, r *http.Request, revert func(ctx context.Context, tenantID, workflowID uuid.UUID, count int) (json.RawMessage, int, error)) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	var req struct {
+		Count int `json:"count"`
+	}
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil && !errors.Is(err, io.EOF) {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canEdit); !ok {
+		return
+	}
+	ast, reverted, err := revert(r.Context(), principal.TenantID, workflowID, req.Count)
+	if err != nil {
+		if writeLifecycleError(w, err) {
+			return
+		}
+		switch {
+		case errors.Is(err, sql.ErrNoRows):
+			writeError(w, http.StatusNotFound, "not_found")
+		case errors.Is(err, workflows.ErrNothingToRevert):
+			writeError(w, http.StatusConflict, err.Error())
+		default:
+			writeInternalServerError(w, r, err)
+		}
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"status": "reverted", "ast": ast, "reverted": reverted})
+}
+
+// GetDraftStatus reports whether the draft has unpublished edits, so the
+// designer can show which state a workflow is in.
+func (h *WorkflowHandlers) GetDraftStatus(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canView); !ok {
+		return
+	}
+	status, err := h.Service.DraftStatus(r.Context(), principal.TenantID, workflowID)
+	if err != nil {
+		if errors.Is(err, sql.ErrNoRows) {
+			writeError(w, http.StatusNotFound, "not_found")
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, status)
+}
+
+// DiscardDraft resets the draft to the published version.
+func (h *WorkflowHandlers) DiscardDraft(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canEdit); !ok {
+		return
+	}
+	ast, err := h.Service.DiscardDraft(r.Context(), principal.TenantID, principal.ID, workflowID)
+	if err != nil {
+		if writeLifecycleError(w, err) {
+			return
+		}
+		switch {
+		case errors.Is(err, sql.ErrNoRows):
+			writeError(w, http.StatusNotFound, "not_found")
+		case errors.Is(err, workflows.ErrNoPublishedVersion):
+			writeError(w, http.StatusConflict, "not_published")
+		default:
+			writeInternalServerError(w, r, err)
+		}
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"status": "discarded", "ast": ast})
+}
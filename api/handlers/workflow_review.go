@@ -0,0 +1,237 @@
+package handlers
+
+import (
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"io"
+	"net/http"
+	"strings"
+
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/workflows"
+)
+
+// writeLifecycleError writes the response for review and archive errors and
+// reports whether err was one.
+func writeLifecycleError(w http.ResponseWriter, err error) bool {
+	switch {
+	case errors.Is(err, workflows.ErrUnderReview):
+		writeError(w, http.StatusConflict, "under_review")
+	case errors.Is(err, workflows.ErrArchived):
+		writeError(w, http.StatusConflict, "archived")
+	case errors.Is(err, workflows.ErrApprovalsRequired):
+		writeError(w, http.StatusConflict, "approvals_required")
+	case errors.Is(err, workflows.ErrReviewOpen):
+		writeError(w, http.StatusConflict, "review_open")
+	case errors.Is(err, workflows.ErrNoOpenReview):
+		writeError(w, http.StatusConflict, "no_open_review")
+	case errors.Is(err, workflows.ErrSelfReview):
+		writeError(w, http.StatusForbidden, "self_review")
+	default:
+		return false
+	}
+	return true
+}
+
+// GetReview returns the workflow's most recent review.
+func (h *WorkflowHandlers) GetReview(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canView); !ok {
+		return
+	}
+	review, err := h.Service.CurrentReview(r.Context(), principal.TenantID, workflowID)
+	if err != nil {
+		if errors.Is(err, sql.ErrNoRows) {
+			writeError(w, http.StatusNotFound, "not_found")
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, review)
+}
+
+// SubmitReview submits the draft for review, locking it against edits.
+func (h *WorkflowHandlers) SubmitReview(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	var req struct {
+		Note string `json:"note"`
+	}
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil && !errors.Is(err, io.EOF) {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canEdit); !ok {
+		return
+	}
+	review, err := h.Service.SubmitForReview(r.Context(), principal.TenantID, principal.ID, workflowID, req.Note)
+	if err != nil {
+		var validationErrs *workflows.PublishValidationErrors
+		switch {
+		case errors.Is(err, sql.ErrNoRows):
+			writeError(w, http.StatusNotFound, "not_found")
+		case errors.As(err, &validationErrs):
+			writeJSON(w, http.StatusBadRequest, validationErrs)
+		case strings.HasPrefix(err.Error(), "invalid workflow ast:"):
+			writeError(w, http.StatusBadRequest, err.Error())
+		case !writeLifecycleError(w, err):
+			writeInternalServerError(w, r, err)
+		}
+		return
+	}
+	writeJSON(w, http.StatusCreated, review)
+}
+
+// WithdrawReview closes the open review so the draft can be edited again.
+func (h *WorkflowHandlers) WithdrawReview(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canEdit); !ok {
+		return
+	}
+	if err := h.Service.WithdrawReview(r.Context(), principal.TenantID, workflowID); err != nil {
+		if !writeLifecycleError(w, err) {
+			writeInternalServerError(w, r, err)
+		}
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]string{"status": "withdrawn"})
+}
+
+func (h *WorkflowHandlers) ApproveReview(w http.ResponseWriter, r *http.Request) {
+	h.decideReview(w, r, true)
+}
+
+func (h *WorkflowHandlers) RejectReview(w http.ResponseWriter, r *http.Request) {
+	h.decideReview(w, r, false)
+}
+
+func (h *WorkflowHandlers) decideReview(w http.ResponseWriter, r *http.Request, approve bool) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	var req struct {
+		Comment string `json:"comment"`
+	}
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil && !errors.Is(err, io.EOF) {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canView); !ok {
+		return
+	}
+	review, err := h.Service.DecideReview(r.Context(), principal.TenantID, principal.ID, workflowID, approve, req.Comment)
+	if err != nil {
+		if !writeLifecycleError(w, err) {
+			writeInternalServerError(w, r, err)
+		}
+		return
+	}
+	writeJSON(w, http.StatusOK, review)
+}
+
+// PutReviewPolicy sets how many approvals the workflow's drafts need
+// before they can be published.
+func (h *WorkflowHandlers) PutReviewPolicy(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	var req struct {
+		RequiredApprovals int `json:"required_approvals"`
+	}
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	if req.RequiredApprovals < 0 {
+		writeError(w, http.StatusBadRequest, "invalid_required_approvals")
+		return
+	}
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canView); !ok {
+		return
+	}
+	if err := h.Service.SetRequiredApprovals(r.Context(), principal.TenantID, workflowID, req.RequiredApprovals); err != nil {
+		if errors.Is(err, sql.ErrNoRows) {
+			writeError(w, http.StatusNotFound, "not_found")
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]int{"required_approvals": req.RequiredApprovals})
+}
+
+// Archive retires the workflow, withdrawing its published version.
+func (h *WorkflowHandlers) Archive(w http.ResponseWriter, r *http.Request) {
+	h.setArchived(w, r, true)
+}
+
+// Unarchive makes an archived workflow editable again.
+func (h *WorkflowHandlers) Unarchive(w http.ResponseWriter, r *http.Request) {
+	h.setArchived(w, r, false)
+}
+
+func (h *WorkflowHandlers) setArchived(w http.ResponseWriter, r *http.Request, archive bool) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canView); !ok {
+		return
+	}
+	set, state := h.Service.Unarchive, workflows.StateDraft
+	if archive {
+		set, state = h.Service.Archive, workflows.StateArchived
+	}
+	if err := set(r.Context(), principal.TenantID, workflowID); err != nil {
+		switch {
+		case errors.Is(err, sql.ErrNoRows):
+			writeError(w, http.StatusNotFound, "not_found")
+		case !writeLifecycleError(w, err):
+			writeInternalServerError(w, r, err)
+		}
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]string{"state": state})
+}
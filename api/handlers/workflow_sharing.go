@@ -0,0 +1,173 @@
+package handlers
+
+import (
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"net/http"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/workflows"
+)
+
+// tenantAccess reports what the principal's roles allow on every workflow in
+// the tenant. Without an authorizer only ownership and shares count.
+func (h *WorkflowHandlers) tenantAccess(r *http.Request, principal *middleware.Principal) workflows.TenantAccess {
+	if h.Authz == nil {
+		return workflows.TenantAccess{}
+	}
+	allowed := func(permission string) bool {
+		return h.Authz.Authorize(r.Context(), principal.ID, permission) == nil
+	}
+	return workflows.TenantAccess{
+		View:    allowed("workflows:view"),
+		Edit:    allowed("workflows:edit"),
+		Execute: allowed("cases:create"),
+		Share:   allowed("workflows:share"),
+	}
+}
+
+// authorizeWorkflow checks the principal's permissions on one workflow and
+// writes 404 or 403 when it is missing or allow rejects them.
+func (h *WorkflowHandlers) authorizeWorkflow(w http.ResponseWriter, r *http.Request, principal *middleware.Principal, workflowID uuid.UUID, allow func(workflows.Permissions) bool) (workflows.Permissions, bool) {
+	perms, err := h.Service.WorkflowPermissions(r.Context(), principal.TenantID, workflowID, principal.ID, h.tenantAccess(r, principal))
+	switch {
+	case err == sql.ErrNoRows:
+		writeError(w, http.StatusNotFound, "not_found")
+		return perms, false
+	case err != nil:
+		writeInternalServerError(w, r, err)
+		return perms, false
+	case !allow(perms):
+		writeError(w, http.StatusForbidden, "forbidden")
+		return perms, false
+	}
+	return perms, true
+}
+
+func canView(p workflows.Permissions) bool  { return p.CanView }
+func canEdit(p workflows.Permissions) bool  { return p.CanEdit }
+func canShare(p workflows.Permissions) bool { return p.CanShare }
+
+func (h *WorkflowHandlers) ListShares(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canView); !ok {
+		return
+	}
+	shares, err := h.Service.ListShares(r.Context(), principal.TenantID, workflowID)
+	if err != nil {
+		writeShareError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"shares": shares})
+}
+
+// PutShare grants or replaces one principal's or role's access to the workflow.
+func (h *WorkflowHandlers) PutShare(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	var req workflows.ShareRequest
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canShare); !ok {
+		return
+	}
+	share, err := h.Service.ShareWorkflow(r.Context(), principal.TenantID, workflowID, principal.ID, req)
+	if err != nil {
+		writeShareError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, share)
+}
+
+func (h *WorkflowHandlers) DeleteShare(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	granteeID, ok := parseUUIDPath(w, r, "grantee_id", "invalid_grantee_id")
+	if !ok {
+		return
+	}
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canShare); !ok {
+		return
+	}
+	if err := h.Service.UnshareWorkflow(r.Context(), principal.TenantID, workflowID, r.PathValue("grantee_type"), granteeID); err != nil {
+		writeShareError(w, r, err)
+		return
+	}
+	w.WriteHeader(http.StatusNoContent)
+}
+
+// TransferOwnership hands the workflow to another principal. The previous
+// owner keeps only what their roles and shares allow.
+func (h *WorkflowHandlers) TransferOwnership(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	var req struct {
+		OwnerID uuid.UUID `json:"owner_id"`
+	}
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canShare); !ok {
+		return
+	}
+	out, err := h.Service.TransferOwnership(r.Context(), principal.TenantID, workflowID, req.OwnerID)
+	if err != nil {
+		writeShareError(w, r, err)
+		return
+	}
+	items := []workflows.Workflow{out}
+	if err := h.Service.ResolvePermissions(r.Context(), principal.TenantID, principal.ID, h.tenantAccess(r, principal), items); err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, items[0])
+}
+
+func writeShareError(w http.ResponseWriter, r *http.Request, err error) {
+	switch {
+	case err == sql.ErrNoRows:
+		writeError(w, http.StatusNotFound, "not_found")
+	case errors.Is(err, workflows.ErrInvalidShare):
+		writeError(w, http.StatusBadRequest, "invalid_share")
+	case errors.Is(err, workflows.ErrGranteeNotFound):
+		writeError(w, http.StatusNotFound, "grantee_not_found")
+	case errors.Is(err, workflows.ErrShareNotFound):
+		writeError(w, http.StatusNotFound, "share_not_found")
+	default:
+		writeInternalServerError(w, r, err)
+	}
+}
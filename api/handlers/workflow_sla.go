@@ -0,0 +1,81 @@
+package handlers
+
+import (
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"net/http"
+	"strconv"
+
+	"github.com/neural-chilli/aceryx/internal/workflows"
+)
+
+func (h *WorkflowHandlers) GetSLA(w http.ResponseWriter, r *http.Request) {
+	principal, workflowID, ok := canaryRequest(w, r)
+	if !ok {
+		return
+	}
+	out, err := h.Service.GetSLA(r.Context(), principal.TenantID, workflowID)
+	if err != nil {
+		writeSLAError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, out)
+}
+
+func (h *WorkflowHandlers) PutSLA(w http.ResponseWriter, r *http.Request) {
+	principal, workflowID, ok := canaryRequest(w, r)
+	if !ok {
+		return
+	}
+	var req workflows.SLA
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	out, err := h.Service.PutSLA(r.Context(), principal.TenantID, workflowID, req)
+	if err != nil {
+		writeSLAError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, out)
+}
+
+func (h *WorkflowHandlers) DeleteSLA(w http.ResponseWriter, r *http.Request) {
+	principal, workflowID, ok := canaryRequest(w, r)
+	if !ok {
+		return
+	}
+	if err := h.Service.DeleteSLA(r.Context(), principal.TenantID, workflowID); err != nil {
+		writeSLAError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]string{"status": "deleted"})
+}
+
+func (h *WorkflowHandlers) ListSLABreaches(w http.ResponseWriter, r *http.Request) {
+	principal, workflowID, ok := canaryRequest(w, r)
+	if !ok {
+		return
+	}
+	limit, _ := strconv.Atoi(r.URL.Query().Get("limit"))
+	out, err := h.Service.ListSLABreaches(r.Context(), principal.TenantID, workflowID, limit)
+	if err != nil {
+		writeSLAError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, out)
+}
+
+func writeSLAError(w http.ResponseWriter, r *http.Request, err error) {
+	switch {
+	case errors.Is(err, workflows.ErrNoSLA):
+		writeError(w, http.StatusNotFound, err.Error())
+	case errors.Is(err, workflows.ErrInvalidSLA):
+		writeError(w, http.StatusBadRequest, err.Error())
+	case errors.Is(err, sql.ErrNoRows):
+		writeError(w, http.StatusNotFound, "not_found")
+	default:
+		writeInternalServerError(w, r, err)
+	}
+}
@@ -8,13 +8,18 @@ import (
 	"net/http"
 	"strconv"
 	"strings"
+	"time"
 
 	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/rbac"
 	"github.com/neural-chilli/aceryx/internal/workflows"
 )
 
 type WorkflowHandlers struct {
 	Service *workflows.Service
+	// Authz resolves tenant-wide role permissions for the per-workflow
+	// permission flags and checks.
+	Authz *rbac.Service
 }
 
 func NewWorkflowHandlers(service *workflows.Service) *WorkflowHandlers {
@@ -27,12 +32,41 @@ func (h *WorkflowHandlers) List(w http.ResponseWriter, r *http.Request) {
 		writeError(w, http.StatusUnauthorized, "unauthenticated")
 		return
 	}
-	items, err := h.Service.List(r.Context(), principal.TenantID)
+	params, err := parseListParams(r)
 	if err != nil {
+		writeError(w, http.StatusBadRequest, err.Error())
+		return
+	}
+	state := strings.TrimSpace(r.URL.Query().Get("state"))
+	if state != "" && !workflows.ValidState(state) {
+		writeError(w, http.StatusBadRequest, "invalid_state")
+		return
+	}
+	page, err := h.Service.List(r.Context(), principal.TenantID, workflows.ListOptions{
+		Limit:      params.Limit,
+		Offset:     params.Offset,
+		Cursor:     strings.TrimSpace(r.URL.Query().Get("cursor")),
+		Sort:       params.SortBy,
+		Descending: params.Descending,
+		State:      state,
+	})
+	if err != nil {
+		if errors.Is(err, workflows.ErrInvalidSort) {
+			writeError(w, http.StatusBadRequest, "invalid_sort")
+			return
+		}
+		if errors.Is(err, workflows.ErrInvalidCursor) {
+			writeError(w, http.StatusBadRequest, "invalid_cursor")
+			return
+		}
 		writeInternalServerError(w, r, err)
 		return
 	}
-	writeJSON(w, http.StatusOK, items)
+	if err := h.Service.ResolvePermissions(r.Context(), principal.TenantID, principal.ID, h.tenantAccess(r, principal), page.Items); err != nil {
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, listEnvelope{Items: page.Items, Total: page.Total, Limit: params.Limit, Offset: params.Offset, NextCursor: page.NextCursor})
 }
 
 func (h *WorkflowHandlers) Create(w http.ResponseWriter, r *http.Request) {
@@ -58,6 +92,88 @@ func (h *WorkflowHandlers) Create(w http.ResponseWriter, r *http.Request) {
 	writeJSON(w, http.StatusCreated, out)
 }
 
+func (h *WorkflowHandlers) Get(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	perms, ok := h.authorizeWorkflow(w, r, principal, workflowID, canView)
+	if !ok {
+		return
+	}
+	out, err := h.Service.Get(r.Context(), principal.TenantID, workflowID)
+	if err != nil {
+		if err == sql.ErrNoRows {
+			writeError(w, http.StatusNotFound, "not_found")
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	out.Permissions = &perms
+	writeJSONConditional(w, r, out, out.UpdatedAt)
+}
+
+func (h *WorkflowHandlers) Delete(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	err := h.Service.Delete(r.Context(), principal.TenantID, workflowID)
+	switch {
+	case err == sql.ErrNoRows:
+		writeError(w, http.StatusNotFound, "not_found")
+	case errors.Is(err, workflows.ErrWorkflowInUse):
+		writeError(w, http.StatusConflict, "workflow_in_use")
+	case err != nil:
+		writeInternalServerError(w, r, err)
+	default:
+		w.WriteHeader(http.StatusNoContent)
+	}
+}
+
+// SetTags replaces the workflow's tags.
+func (h *WorkflowHandlers) SetTags(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	var req struct {
+		Tags []string `json:"tags"`
+	}
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canEdit); !ok {
+		return
+	}
+	tags, err := h.Service.SetWorkflowTags(r.Context(), principal.TenantID, workflowID, req.Tags)
+	switch {
+	case err == sql.ErrNoRows:
+		writeError(w, http.StatusNotFound, "not_found")
+	case err != nil:
+		writeTagError(w, r, err)
+	default:
+		writeJSON(w, http.StatusOK, map[string]any{"tags": tags})
+	}
+}
+
 func (h *WorkflowHandlers) GetDraft(w http.ResponseWriter, r *http.Request) {
 	principal := middleware.PrincipalFromContext(r.Context())
 	if principal == nil {
@@ -68,6 +184,9 @@ func (h *WorkflowHandlers) GetDraft(w http.ResponseWriter, r *http.Request) {
 	if !ok {
 		return
 	}
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canView); !ok {
+		return
+	}
 	ast, err := h.Service.GetDraftAST(r.Context(), principal.TenantID, workflowID)
 	if err != nil {
 		if err == sql.ErrNoRows {
@@ -77,9 +196,7 @@ func (h *WorkflowHandlers) GetDraft(w http.ResponseWriter, r *http.Request) {
 		writeInternalServerError(w, r, err)
 		return
 	}
-	w.Header().Set("Content-Type", "application/json")
-	w.WriteHeader(http.StatusOK)
-	_, _ = w.Write(ast)
+	writeRawJSONConditional(w, r, ast, time.Time{})
 }
 
 func (h *WorkflowHandlers) PutDraft(w http.ResponseWriter, r *http.Request) {
@@ -97,11 +214,17 @@ func (h *WorkflowHandlers) PutDraft(w http.ResponseWriter, r *http.Request) {
 		writeError(w, http.StatusBadRequest, "invalid_json")
 		return
 	}
-	if err := h.Service.SaveDraftAST(r.Context(), principal.TenantID, workflowID, raw); err != nil {
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canEdit); !ok {
+		return
+	}
+	if err := h.Service.SaveDraftAST(r.Context(), principal.TenantID, principal.ID, workflowID, raw); err != nil {
 		if err == sql.ErrNoRows {
 			writeError(w, http.StatusNotFound, "not_found")
 			return
 		}
+		if writeLifecycleError(w, err) {
+			return
+		}
 		if err.Error() == "ast is required" || strings.HasPrefix(err.Error(), "invalid ast json:") || strings.HasPrefix(err.Error(), "invalid workflow ast:") {
 			writeError(w, http.StatusBadRequest, err.Error())
 			return
@@ -109,7 +232,45 @@ func (h *WorkflowHandlers) PutDraft(w http.ResponseWriter, r *http.Request) {
 		writeInternalServerError(w, r, err)
 		return
 	}
-	writeJSON(w, http.StatusOK, map[string]any{"status": "saved"})
+	writeJSON(w, http.StatusOK, map[string]any{"status": "saved", "lint": h.Service.Lint(raw, "")})
+}
+
+// PutDraftGraph saves the designer canvas as the draft, updating its AST and
+// YAML source together.
+func (h *WorkflowHandlers) PutDraftGraph(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	var graph workflows.Graph
+	if err := json.NewDecoder(r.Body).Decode(&graph); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	if _, ok := h.authorizeWorkflow(w, r, principal, workflowID, canEdit); !ok {
+		return
+	}
+	ast, err := h.Service.SaveDraftGraph(r.Context(), principal.TenantID, principal.ID, workflowID, graph)
+	if err != nil {
+		if writeLifecycleError(w, err) {
+			return
+		}
+		switch {
+		case err == sql.ErrNoRows:
+			writeError(w, http.StatusNotFound, "not_found")
+		case errors.Is(err, workflows.ErrInvalidGraph), strings.HasPrefix(err.Error(), "invalid workflow ast:"):
+			writeError(w, http.StatusBadRequest, err.Error())
+		default:
+			writeInternalServerError(w, r, err)
+		}
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"status": "saved", "ast": ast, "lint": h.Service.Lint(ast, "")})
 }
 
 func (h *WorkflowHandlers) Publish(w http.ResponseWriter, r *http.Request) {
@@ -127,6 +288,9 @@ func (h *WorkflowHandlers) Publish(w http.ResponseWriter, r *http.Request) {
 			writeError(w, http.StatusNotFound, "not_found")
 			return
 		}
+		if writeLifecycleError(w, err) {
+			return
+		}
 		var validationErrs *workflows.PublishValidationErrors
 		if errors.As(err, &validationErrs) {
 			writeJSON(w, http.StatusBadRequest, validationErrs)
@@ -142,6 +306,101 @@ func (h *WorkflowHandlers) Publish(w http.ResponseWriter, r *http.Request) {
 	writeJSON(w, http.StatusOK, map[string]any{"status": "published"})
 }
 
+func (h *WorkflowHandlers) DryRun(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	var req workflows.DryRunRequest
+	if r.ContentLength != 0 {
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil && !errors.Is(err, io.EOF) {
+			writeError(w, http.StatusBadRequest, "invalid_json")
+			return
+		}
+	}
+	out, err := h.Service.DryRun(r.Context(), principal.TenantID, workflowID, req)
+	if err != nil {
+		if err == sql.ErrNoRows {
+			writeError(w, http.StatusNotFound, "not_found")
+			return
+		}
+		writeInternalServerError(w, r, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, out)
+}
+
+// Promote returns a bundle of a workflow version rewritten for the target
+// environment, ready to import on that environment's instance.
+func (h *WorkflowHandlers) Promote(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	var req workflows.PromoteRequest
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return
+	}
+	out, err := h.Service.Promote(r.Context(), principal.TenantID, workflowID, req)
+	if err != nil {
+		switch {
+		case err == sql.ErrNoRows:
+			writeError(w, http.StatusNotFound, "not_found")
+		case errors.Is(err, workflows.ErrUnknownEnvironment), errors.Is(err, workflows.ErrEnvironmentIncomplete):
+			writeError(w, http.StatusUnprocessableEntity, err.Error())
+		default:
+			writeInternalServerError(w, r, err)
+		}
+		return
+	}
+	writeJSON(w, http.StatusOK, out)
+}
+
+func (h *WorkflowHandlers) TestStep(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	workflowID, ok := parseUUIDPath(w, r, "id", "invalid_id")
+	if !ok {
+		return
+	}
+	var req workflows.StepTestRequest
+	if r.ContentLength != 0 {
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil && !errors.Is(err, io.EOF) {
+			writeError(w, http.StatusBadRequest, "invalid_json")
+			return
+		}
+	}
+	out, err := h.Service.TestStep(r.Context(), principal.TenantID, workflowID, r.PathValue("step_id"), req)
+	if err != nil {
+		switch {
+		case err == sql.ErrNoRows:
+			writeError(w, http.StatusNotFound, "not_found")
+		case errors.Is(err, workflows.ErrStepNotFound):
+			writeError(w, http.StatusNotFound, "step_not_found")
+		case errors.Is(err, workflows.ErrStepNotTestable):
+			writeError(w, http.StatusBadRequest, "step_not_testable")
+		default:
+			writeInternalServerError(w, r, err)
+		}
+		return
+	}
+	writeJSON(w, http.StatusOK, out)
+}
+
 func (h *WorkflowHandlers) ExportYAMLLatest(w http.ResponseWriter, r *http.Request) {
 	principal := middleware.PrincipalFromContext(r.Context())
 	if principal == nil {
@@ -223,11 +482,14 @@ func (h *WorkflowHandlers) ImportYAMLDraft(w http.ResponseWriter, r *http.Reques
 		return
 	}
 
-	if err := h.Service.ImportYAMLDraft(r.Context(), principal.TenantID, workflowID, string(data)); err != nil {
+	if err := h.Service.ImportYAMLDraft(r.Context(), principal.TenantID, principal.ID, workflowID, string(data)); err != nil {
 		if err == sql.ErrNoRows {
 			writeError(w, http.StatusNotFound, "not_found")
 			return
 		}
+		if writeLifecycleError(w, err) {
+			return
+		}
 		if err.Error() == "yaml is required" || strings.HasPrefix(err.Error(), "invalid yaml:") || strings.HasPrefix(err.Error(), "invalid workflow ast:") {
 			writeError(w, http.StatusBadRequest, err.Error())
 			return
@@ -235,5 +497,5 @@ func (h *WorkflowHandlers) ImportYAMLDraft(w http.ResponseWriter, r *http.Reques
 		writeInternalServerError(w, r, err)
 		return
 	}
-	writeJSON(w, http.StatusOK, map[string]any{"status": "imported"})
+	writeJSON(w, http.StatusOK, map[string]any{"status": "imported", "lint": h.Service.Lint(nil, string(data))})
 }
@@ -17,10 +17,23 @@ func TestWorkflowHandlersUnauthenticated(t *testing.T) {
 		{name: "create", fn: h.Create, path: "/workflows"},
 		{name: "get_draft", fn: h.GetDraft, path: "/workflows/123/versions/draft"},
 		{name: "put_draft", fn: h.PutDraft, path: "/workflows/123/versions/draft"},
+		{name: "put_draft_graph", fn: h.PutDraftGraph, path: "/workflows/123/versions/draft/graph"},
 		{name: "publish", fn: h.Publish, path: "/workflows/123/publish"},
+		{name: "dry_run", fn: h.DryRun, path: "/workflows/123/dry-run"},
+		{name: "test_step", fn: h.TestStep, path: "/workflows/123/steps/a/test"},
 		{name: "yaml_latest", fn: h.ExportYAMLLatest, path: "/workflows/123/yaml/latest"},
 		{name: "yaml_version", fn: h.ExportYAMLVersion, path: "/workflows/123/yaml/1"},
 		{name: "yaml_import", fn: h.ImportYAMLDraft, path: "/workflows/123/yaml/draft"},
+		{name: "set_tags", fn: h.SetTags, path: "/workflows/123/tags"},
+		{name: "list_shares", fn: h.ListShares, path: "/workflows/123/shares"},
+		{name: "put_share", fn: h.PutShare, path: "/workflows/123/shares"},
+		{name: "delete_share", fn: h.DeleteShare, path: "/workflows/123/shares/role/456"},
+		{name: "transfer", fn: h.TransferOwnership, path: "/workflows/123/transfer"},
+		{name: "submit_review", fn: h.SubmitReview, path: "/workflows/123/review"},
+		{name: "approve_review", fn: h.ApproveReview, path: "/workflows/123/review/approve"},
+		{name: "archive", fn: h.Archive, path: "/workflows/123/archive"},
+		{name: "list_tags", fn: NewTagHandlers(nil).List, path: "/api/v1/tags"},
+		{name: "merge_tags", fn: NewTagHandlers(nil).Merge, path: "/api/v1/tags/merge"},
 	}
 	for _, tt := range tests {
 		t.Run(tt.name, func(t *testing.T) {
@@ -33,3 +46,25 @@ func TestWorkflowHandlersUnauthenticated(t *testing.T) {
 		})
 	}
 }
+
+func TestParseListParams(t *testing.T) {
+	req := httptest.NewRequest(http.MethodGet, "/workflows?limit=9999&offset=20&sort_by=updated_at&sort_dir=desc", nil)
+	params, err := parseListParams(req)
+	if err != nil {
+		t.Fatalf("parseListParams: %v", err)
+	}
+	if params.Limit != maxListLimit || params.Offset != 20 || params.SortBy != "updated_at" || !params.Descending {
+		t.Fatalf("unexpected params %+v", params)
+	}
+
+	defaults, err := parseListParams(httptest.NewRequest(http.MethodGet, "/workflows", nil))
+	if err != nil || defaults.Limit != defaultListLimit || defaults.Offset != 0 || defaults.Descending {
+		t.Fatalf("unexpected defaults %+v err=%v", defaults, err)
+	}
+
+	for _, query := range []string{"limit=0", "limit=abc", "offset=-1", "sort_dir=sideways"} {
+		if _, err := parseListParams(httptest.NewRequest(http.MethodGet, "/workflows?"+query, nil)); err == nil {
+			t.Fatalf("expected error for %s", query)
+		}
+	}
+}
@@ -19,6 +19,7 @@ type Principal struct {
 	Name      string
 	Email     string
 	Roles     []string
+	Scope     *rbac.TokenScope
 }
 
 type principalCtxKey struct{}
@@ -28,23 +29,27 @@ func PrincipalFromContext(ctx context.Context) *Principal {
 	return v
 }
 
+// SessionCookieName is the cookie carrying the signed session token for
+// browser clients. Requests authenticated by it alone that change state must
+// also send X-Requested-With, which cross-site forms cannot set.
+const SessionCookieName = "aceryx_session"
+
 func AuthMiddleware(auth *rbac.AuthService) func(http.Handler) http.Handler {
 	return func(next http.Handler) http.Handler {
 		return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
-			authz := strings.TrimSpace(r.Header.Get("Authorization"))
-			if !strings.HasPrefix(strings.ToLower(authz), "bearer ") {
-				w.Header().Set("Content-Type", "application/json")
-				w.WriteHeader(http.StatusUnauthorized)
-				_ = json.NewEncoder(w).Encode(map[string]string{"error": "unauthenticated"})
+			token, fromCookie := RequestToken(r)
+			if token == "" {
+				writeAuthError(w, http.StatusUnauthorized, "unauthenticated")
+				return
+			}
+			if fromCookie && !isSafeMethod(r.Method) && strings.TrimSpace(r.Header.Get("X-Requested-With")) == "" {
+				writeAuthError(w, http.StatusForbidden, "csrf_header_required")
 				return
 			}
 
-			token := strings.TrimSpace(authz[len("Bearer "):])
 			ap, err := auth.AuthenticateBearer(r.Context(), token)
 			if err != nil {
-				w.Header().Set("Content-Type", "application/json")
-				w.WriteHeader(http.StatusUnauthorized)
-				_ = json.NewEncoder(w).Encode(map[string]string{"error": "unauthenticated"})
+				writeAuthError(w, http.StatusUnauthorized, "unauthenticated")
 				return
 			}
 
@@ -56,6 +61,7 @@ func AuthMiddleware(auth *rbac.AuthService) func(http.Handler) http.Handler {
 				Name:      ap.Name,
 				Email:     ap.Email,
 				Roles:     ap.Roles,
+				Scope:     ap.Scope,
 			})
 			ctx = observability.WithTenantID(ctx, ap.TenantID)
 			ctx = observability.WithPrincipalID(ctx, ap.ID)
@@ -63,3 +69,33 @@ func AuthMiddleware(auth *rbac.AuthService) func(http.Handler) http.Handler {
 		})
 	}
 }
+
+// RequestToken returns the bearer token from the Authorization header or,
+// failing that, the session cookie, reporting whether it came from the cookie.
+func RequestToken(r *http.Request) (string, bool) {
+	authz := strings.TrimSpace(r.Header.Get("Authorization"))
+	if strings.HasPrefix(strings.ToLower(authz), "bearer ") {
+		return strings.TrimSpace(authz[len("Bearer "):]), false
+	}
+	if authz != "" {
+		return "", false
+	}
+	if cookie, err := r.Cookie(SessionCookieName); err == nil && strings.TrimSpace(cookie.Value) != "" {
+		return strings.TrimSpace(cookie.Value), true
+	}
+	return "", false
+}
+
+func isSafeMethod(method string) bool {
+	switch method {
+	case http.MethodGet, http.MethodHead, http.MethodOptions:
+		return true
+	}
+	return false
+}
+
+func writeAuthError(w http.ResponseWriter, status int, code string) {
+	w.Header().Set("Content-Type", "application/json")
+	w.WriteHeader(status)
+	_ = json.NewEncoder(w).Encode(ErrorBody(w, code))
+}
@@ -0,0 +1,42 @@
+package middleware
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"testing"
+)
+
+func TestRequestTokenPrefersAuthorizationHeader(t *testing.T) {
+	req := httptest.NewRequest(http.MethodGet, "/cases", nil)
+	req.AddCookie(&http.Cookie{Name: SessionCookieName, Value: "cookie-token"})
+	if token, fromCookie := RequestToken(req); token != "cookie-token" || !fromCookie {
+		t.Fatalf("expected the cookie token, got %q %v", token, fromCookie)
+	}
+	req.Header.Set("Authorization", "Bearer header-token")
+	if token, fromCookie := RequestToken(req); token != "header-token" || fromCookie {
+		t.Fatalf("expected the header token, got %q %v", token, fromCookie)
+	}
+	req.Header.Set("Authorization", "Basic abc")
+	if token, _ := RequestToken(req); token != "" {
+		t.Fatalf("expected no token for a non-bearer header, got %q", token)
+	}
+}
+
+func TestAuthMiddlewareRequiresCSRFHeaderForCookieWrites(t *testing.T) {
+	next := http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) { w.WriteHeader(http.StatusNoContent) })
+	handler := AuthMiddleware(nil)(next)
+
+	rr := httptest.NewRecorder()
+	handler.ServeHTTP(rr, httptest.NewRequest(http.MethodPost, "/cases", nil))
+	if rr.Code != http.StatusUnauthorized {
+		t.Fatalf("expected 401 without credentials, got %d", rr.Code)
+	}
+
+	req := httptest.NewRequest(http.MethodPost, "/cases", nil)
+	req.AddCookie(&http.Cookie{Name: SessionCookieName, Value: "a.b.c"})
+	rr = httptest.NewRecorder()
+	handler.ServeHTTP(rr, req)
+	if rr.Code != http.StatusForbidden {
+		t.Fatalf("expected 403 for a cookie write without X-Requested-With, got %d", rr.Code)
+	}
+}
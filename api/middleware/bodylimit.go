@@ -0,0 +1,160 @@
+package middleware
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"mime"
+	"net/http"
+	"os"
+	"strconv"
+	"strings"
+)
+
+const (
+	DefaultMaxBodyBytes         = 1 << 20
+	DefaultMaxWorkflowBodyBytes = 8 << 20
+	DefaultMaxIngestBodyBytes   = 10 << 20
+	DefaultMaxJSONDepth         = 64
+)
+
+var (
+	errJSONTooDeep  = errors.New("json nesting exceeds limit")
+	errJSONNullChar = errors.New("json string contains NUL character")
+)
+
+// BodyLimits caps request bodies by route class. Workflow definitions and
+// inbound webhooks get their own, larger limits; multipart uploads are left
+// to their handlers, which enforce document-specific sizes.
+type BodyLimits struct {
+	Default      int64
+	Workflow     int64
+	Ingest       int64
+	MaxJSONDepth int
+}
+
+func BodyLimitsFromEnv() BodyLimits {
+	return BodyLimits{
+		Default:      int64(positiveIntEnv("ACERYX_MAX_BODY_BYTES", DefaultMaxBodyBytes)),
+		Workflow:     int64(positiveIntEnv("ACERYX_MAX_WORKFLOW_BODY_BYTES", DefaultMaxWorkflowBodyBytes)),
+		Ingest:       int64(positiveIntEnv("ACERYX_MAX_INGEST_BODY_BYTES", DefaultMaxIngestBodyBytes)),
+		MaxJSONDepth: positiveIntEnv("ACERYX_MAX_JSON_DEPTH", DefaultMaxJSONDepth),
+	}
+}
+
+// BodyLimitMiddleware rejects oversized bodies with 413 before handlers
+// buffer them. JSON bodies are also checked up front: nesting deeper than
+// MaxJSONDepth, or strings containing NUL (which Postgres cannot store and
+// which truncate values in C-based renderers), are rejected with 400.
+func BodyLimitMiddleware(limits BodyLimits) func(http.Handler) http.Handler {
+	return func(next http.Handler) http.Handler {
+		return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+			if r.Body == nil || r.Body == http.NoBody || isMultipart(r) {
+				next.ServeHTTP(w, r)
+				return
+			}
+			limit := limits.forPath(r.URL.Path)
+			if r.ContentLength > limit {
+				writeBodyError(w, http.StatusRequestEntityTooLarge, "request_too_large")
+				return
+			}
+			if !isJSON(r) {
+				r.Body = http.MaxBytesReader(w, r.Body, limit)
+				next.ServeHTTP(w, r)
+				return
+			}
+			body, err := io.ReadAll(io.LimitReader(r.Body, limit+1))
+			_ = r.Body.Close()
+			if err != nil {
+				writeBodyError(w, http.StatusBadRequest, "invalid_body")
+				return
+			}
+			if int64(len(body)) > limit {
+				writeBodyError(w, http.StatusRequestEntityTooLarge, "request_too_large")
+				return
+			}
+			switch err := checkJSON(body, limits.MaxJSONDepth); {
+			case errors.Is(err, errJSONTooDeep):
+				writeBodyError(w, http.StatusBadRequest, "json_too_deep")
+				return
+			case errors.Is(err, errJSONNullChar):
+				writeBodyError(w, http.StatusBadRequest, "invalid_json_value")
+				return
+			}
+			// Malformed JSON is left for the handler to report in its usual way.
+			r.Body = io.NopCloser(bytes.NewReader(body))
+			r.ContentLength = int64(len(body))
+			next.ServeHTTP(w, r)
+		})
+	}
+}
+
+// checkJSON walks body's tokens without building values. Syntax errors are
+// not reported.
+func checkJSON(body []byte, maxDepth int) error {
+	dec := json.NewDecoder(bytes.NewReader(body))
+	depth := 0
+	for {
+		tok, err := dec.Token()
+		if err != nil {
+			return nil
+		}
+		switch v := tok.(type) {
+		case json.Delim:
+			if v == '{' || v == '[' {
+				depth++
+				if maxDepth > 0 && depth > maxDepth {
+					return fmt.Errorf("%w: %d", errJSONTooDeep, maxDepth)
+				}
+			} else {
+				depth--
+			}
+		case string:
+			if strings.ContainsRune(v, 0) {
+				return errJSONNullChar
+			}
+		}
+	}
+}
+
+func (l BodyLimits) forPath(path string) int64 {
+	switch {
+	case strings.HasPrefix(path, "/webhooks/"), strings.Contains(path, "/channels/webhook/"):
+		return l.Ingest
+	case strings.HasPrefix(path, "/workflows"), strings.HasPrefix(path, "/api/v1/workflows"):
+		return l.Workflow
+	default:
+		return l.Default
+	}
+}
+
+func isMultipart(r *http.Request) bool {
+	mediaType, _, err := mime.ParseMediaType(r.Header.Get("Content-Type"))
+	return err == nil && strings.HasPrefix(mediaType, "multipart/")
+}
+
+func isJSON(r *http.Request) bool {
+	raw := r.Header.Get("Content-Type")
+	if raw == "" {
+		// Most clients of this API post JSON without declaring it.
+		return true
+	}
+	mediaType, _, err := mime.ParseMediaType(raw)
+	return err == nil && (mediaType == "application/json" || strings.HasSuffix(mediaType, "+json"))
+}
+
+func writeBodyError(w http.ResponseWriter, status int, code string) {
+	w.Header().Set("Content-Type", "application/json")
+	w.WriteHeader(status)
+	_ = json.NewEncoder(w).Encode(ErrorBody(w, code))
+}
+
+func positiveIntEnv(key string, fallback int) int {
+	v, err := strconv.Atoi(strings.TrimSpace(os.Getenv(key)))
+	if err != nil || v <= 0 {
+		return fallback
+	}
+	return v
+}
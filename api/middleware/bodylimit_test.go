@@ -0,0 +1,75 @@
+package middleware
+
+import (
+	"io"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+)
+
+func TestBodyLimitMiddleware(t *testing.T) {
+	limits := BodyLimits{Default: 64, Workflow: 256, Ingest: 512, MaxJSONDepth: 3}
+	var got string
+	h := BodyLimitMiddleware(limits)(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		body, err := io.ReadAll(r.Body)
+		if err != nil {
+			http.Error(w, err.Error(), http.StatusRequestEntityTooLarge)
+			return
+		}
+		got = string(body)
+		w.WriteHeader(http.StatusNoContent)
+	}))
+
+	cases := []struct {
+		name        string
+		path        string
+		contentType string
+		body        string
+		status      int
+	}{
+		{name: "small json", path: "/cases", contentType: "application/json", body: `{"a":[1,2]}`, status: http.StatusNoContent},
+		{name: "undeclared json", path: "/cases", body: `{"a":1}`, status: http.StatusNoContent},
+		{name: "too large", path: "/cases", contentType: "application/json", body: `{"a":"` + strings.Repeat("x", 80) + `"}`, status: http.StatusRequestEntityTooLarge},
+		{name: "workflow limit", path: "/workflows/abc/draft", contentType: "application/json", body: `{"a":"` + strings.Repeat("x", 80) + `"}`, status: http.StatusNoContent},
+		{name: "ingest limit", path: "/webhooks/orders", contentType: "application/json", body: `{"a":"` + strings.Repeat("x", 300) + `"}`, status: http.StatusNoContent},
+		{name: "too deep", path: "/cases", contentType: "application/json", body: `{"a":{"b":{"c":{"d":1}}}}`, status: http.StatusBadRequest},
+		{name: "nul in string", path: "/cases", contentType: "application/json", body: `{"a":"x\u0000y"}`, status: http.StatusBadRequest},
+		{name: "malformed passes through", path: "/cases", contentType: "application/json", body: `{"a":`, status: http.StatusNoContent},
+		{name: "non-json capped", path: "/cases", contentType: "text/plain", body: strings.Repeat("x", 80), status: http.StatusRequestEntityTooLarge},
+		{name: "multipart skipped", path: "/cases", contentType: "multipart/form-data; boundary=x", body: strings.Repeat("x", 80), status: http.StatusNoContent},
+	}
+	for _, tc := range cases {
+		t.Run(tc.name, func(t *testing.T) {
+			got = ""
+			req := httptest.NewRequest(http.MethodPost, tc.path, strings.NewReader(tc.body))
+			req.ContentLength = -1
+			if tc.contentType != "" {
+				req.Header.Set("Content-Type", tc.contentType)
+			}
+			rr := httptest.NewRecorder()
+			h.ServeHTTP(rr, req)
+			if rr.Code != tc.status {
+				t.Fatalf("status = %d, want %d (%s)", rr.Code, tc.status, rr.Body.String())
+			}
+			if tc.status == http.StatusNoContent && got != tc.body {
+				t.Fatalf("handler saw body %q, want %q", got, tc.body)
+			}
+		})
+	}
+}
+
+func TestBodyLimitMiddleware_DeclaredLengthRejectedUpFront(t *testing.T) {
+	called := false
+	h := BodyLimitMiddleware(BodyLimits{Default: 8, Workflow: 8, Ingest: 8})(http.HandlerFunc(func(http.ResponseWriter, *http.Request) {
+		called = true
+	}))
+	rr := httptest.NewRecorder()
+	h.ServeHTTP(rr, httptest.NewRequest(http.MethodPost, "/cases", strings.NewReader(`{"a":"long value"}`)))
+	if rr.Code != http.StatusRequestEntityTooLarge || called {
+		t.Fatalf("status = %d, handler called = %v", rr.Code, called)
+	}
+	if !strings.Contains(rr.Body.String(), "request_too_large") {
+		t.Fatalf("unexpected body %s", rr.Body.String())
+	}
+}
@@ -0,0 +1,350 @@
+package middleware
+
+import (
+	"compress/gzip"
+	"io"
+	"log/slog"
+	"mime"
+	"net/http"
+	"os"
+	"strconv"
+	"strings"
+	"sync"
+
+	"github.com/klauspost/compress/zstd"
+)
+
+const (
+	EncodingGzip = "gzip"
+	EncodingZstd = "zstd"
+
+	DefaultCompressionMinBytes = 1024
+)
+
+// DefaultCompressibleTypes are the content type prefixes compressed unless
+// ACERYX_HTTP_COMPRESSION_TYPES overrides them.
+var DefaultCompressibleTypes = []string{
+	"text/",
+	"application/json",
+	"application/javascript",
+	"application/xml",
+	"application/yaml",
+	"application/manifest+json",
+	"image/svg+xml",
+}
+
+// CompressionConfig controls response compression. Encodings are in server
+// preference order; none disables compression. Responses smaller than
+// MinBytes, of a type not matching a Types prefix, or under an Exclude path
+// prefix are sent as is.
+type CompressionConfig struct {
+	Encodings []string
+	MinBytes  int
+	Types     []string
+	Exclude   []string
+}
+
+func CompressionConfigFromEnv() CompressionConfig {
+	cfg := CompressionConfig{
+		Encodings: []string{EncodingZstd, EncodingGzip},
+		MinBytes:  positiveIntEnv("ACERYX_HTTP_COMPRESSION_MIN_BYTES", DefaultCompressionMinBytes),
+		Types:     DefaultCompressibleTypes,
+		Exclude:   splitEnvList("ACERYX_HTTP_COMPRESSION_EXCLUDE"),
+	}
+	if raw, ok := os.LookupEnv("ACERYX_HTTP_COMPRESSION"); ok {
+		cfg.Encodings = nil
+		switch strings.ToLower(strings.TrimSpace(raw)) {
+		case "", "off", "none", "false":
+		default:
+			for _, encoding := range splitEnvList("ACERYX_HTTP_COMPRESSION") {
+				encoding = strings.ToLower(encoding)
+				if encoding != EncodingGzip && encoding != EncodingZstd {
+					slog.Warn("ignoring unsupported response compression", "encoding", encoding)
+					continue
+				}
+				cfg.Encodings = append(cfg.Encodings, encoding)
+			}
+		}
+	}
+	if types := splitEnvList("ACERYX_HTTP_COMPRESSION_TYPES"); len(types) > 0 {
+		cfg.Types = types
+	}
+	return cfg
+}
+
+func splitEnvList(key string) []string {
+	var out []string
+	for _, item := range strings.Split(os.Getenv(key), ",") {
+		if item = strings.TrimSpace(item); item != "" {
+			out = append(out, item)
+		}
+	}
+	return out
+}
+
+// CompressionMiddleware compresses responses with the first configured
+// encoding the client accepts. Event streams, WebSocket upgrades, range
+// requests and already encoded responses are never compressed, and a
+// response that is flushed before reaching MinBytes is sent as is, so
+// streaming handlers keep working.
+func CompressionMiddleware(cfg CompressionConfig) func(http.Handler) http.Handler {
+	return func(next http.Handler) http.Handler {
+		if len(cfg.Encodings) == 0 {
+			return next
+		}
+		return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+			if cfg.excluded(r) {
+				next.ServeHTTP(w, r)
+				return
+			}
+			w.Header().Add("Vary", "Accept-Encoding")
+			encoding := negotiateEncoding(r.Header.Get("Accept-Encoding"), cfg.Encodings)
+			if encoding == "" {
+				next.ServeHTTP(w, r)
+				return
+			}
+			cw := &compressWriter{ResponseWriter: w, cfg: cfg, encoding: encoding, status: http.StatusOK}
+			defer cw.close()
+			next.ServeHTTP(cw, r)
+		})
+	}
+}
+
+func (cfg CompressionConfig) excluded(r *http.Request) bool {
+	if r.Header.Get("Upgrade") != "" || r.Header.Get("Range") != "" {
+		return true
+	}
+	for _, prefix := range cfg.Exclude {
+		if strings.HasPrefix(r.URL.Path, prefix) {
+			return true
+		}
+	}
+	return false
+}
+
+func (cfg CompressionConfig) compressible(contentType string) bool {
+	mediaType, _, err := mime.ParseMediaType(contentType)
+	if err != nil || mediaType == "text/event-stream" {
+		return false
+	}
+	for _, prefix := range cfg.Types {
+		if strings.HasPrefix(mediaType, strings.ToLower(prefix)) {
+			return true
+		}
+	}
+	return false
+}
+
+// negotiateEncoding picks the first of the server's encodings that the
+// Accept-Encoding header allows, honouring q=0 and the * wildcard.
+func negotiateEncoding(header string, encodings []string) string {
+	if strings.TrimSpace(header) == "" {
+		return ""
+	}
+	accepted := map[string]float64{}
+	for _, part := range strings.Split(header, ",") {
+		name, params, _ := strings.Cut(strings.TrimSpace(part), ";")
+		q := 1.0
+		if value, ok := strings.CutPrefix(strings.TrimSpace(params), "q="); ok {
+			if parsed, err := strconv.ParseFloat(value, 64); err == nil {
+				q = parsed
+			}
+		}
+		accepted[strings.ToLower(strings.TrimSpace(name))] = q
+	}
+	for _, encoding := range encodings {
+		q, ok := accepted[encoding]
+		if !ok {
+			q, ok = accepted["*"]
+		}
+		if ok && q > 0 {
+			return encoding
+		}
+	}
+	return ""
+}
+
+type compressMode int
+
+const (
+	modePending compressMode = iota
+	modePassthrough
+	modeCompress
+)
+
+// compressWriter buffers the start of a response until it knows whether to
+// compress: once MinBytes have been written, or at the end of the response.
+type compressWriter struct {
+	http.ResponseWriter
+	cfg         CompressionConfig
+	encoding    string
+	status      int
+	wroteHeader bool
+	mode        compressMode
+	buf         []byte
+	enc         io.WriteCloser
+}
+
+func (c *compressWriter) WriteHeader(code int) {
+	if c.wroteHeader {
+		return
+	}
+	if code >= 100 && code < 200 {
+		c.ResponseWriter.WriteHeader(code)
+		return
+	}
+	c.status = code
+	c.wroteHeader = true
+	if !c.mayCompress() {
+		c.passthrough()
+	}
+}
+
+func (c *compressWriter) Write(p []byte) (int, error) {
+	if !c.wroteHeader {
+		c.WriteHeader(http.StatusOK)
+	}
+	switch c.mode {
+	case modePassthrough:
+		return c.ResponseWriter.Write(p)
+	case modeCompress:
+		return c.enc.Write(p)
+	}
+	if c.Header().Get("Content-Type") == "" {
+		c.Header().Set("Content-Type", http.DetectContentType(append(c.buf, p...)))
+		if !c.mayCompress() {
+			if err := c.passthrough(); err != nil {
+				return 0, err
+			}
+			return c.ResponseWriter.Write(p)
+		}
+	}
+	c.buf = append(c.buf, p...)
+	if len(c.buf) >= c.cfg.MinBytes {
+		if err := c.compress(); err != nil {
+			return 0, err
+		}
+	}
+	return len(p), nil
+}
+
+// Flush sends what has been written so far. A response flushed while still
+// pending is not compressed.
+func (c *compressWriter) Flush() {
+	_ = c.FlushError()
+}
+
+func (c *compressWriter) FlushError() error {
+	switch c.mode {
+	case modePending:
+		if err := c.passthrough(); err != nil {
+			return err
+		}
+	case modeCompress:
+		if flusher, ok := c.enc.(interface{ Flush() error }); ok {
+			if err := flusher.Flush(); err != nil {
+				return err
+			}
+		}
+	}
+	return http.NewResponseController(c.ResponseWriter).Flush()
+}
+
+// Unwrap lets http.ResponseController reach the underlying writer.
+func (c *compressWriter) Unwrap() http.ResponseWriter {
+	return c.ResponseWriter
+}
+
+func (c *compressWriter) mayCompress() bool {
+	h := c.Header()
+	if c.status < 200 || c.status == http.StatusNoContent || c.status == http.StatusNotModified {
+		return false
+	}
+	if h.Get("Content-Encoding") != "" {
+		return false
+	}
+	if length, err := strconv.Atoi(h.Get("Content-Length")); err == nil && length < c.cfg.MinBytes {
+		return false
+	}
+	contentType := h.Get("Content-Type")
+	return contentType == "" || c.cfg.compressible(contentType)
+}
+
+func (c *compressWriter) passthrough() error {
+	c.mode = modePassthrough
+	if !c.wroteHeader {
+		return nil
+	}
+	c.ResponseWriter.WriteHeader(c.status)
+	if len(c.buf) == 0 {
+		return nil
+	}
+	_, err := c.ResponseWriter.Write(c.buf)
+	c.buf = nil
+	return err
+}
+
+func (c *compressWriter) compress() error {
+	c.mode = modeCompress
+	h := c.Header()
+	h.Del("Content-Length")
+	h.Set("Content-Encoding", c.encoding)
+	if etag := h.Get("ETag"); strings.HasPrefix(etag, `"`) {
+		h.Set("ETag", "W/"+etag)
+	}
+	c.ResponseWriter.WriteHeader(c.status)
+	c.enc = newEncoder(c.encoding, c.ResponseWriter)
+	_, err := c.enc.Write(c.buf)
+	c.buf = nil
+	return err
+}
+
+func (c *compressWriter) close() {
+	switch c.mode {
+	case modePending:
+		_ = c.passthrough()
+	case modeCompress:
+		_ = c.enc.Close()
+	}
+}
+
+var (
+	gzipWriters sync.Pool
+	zstdWriters sync.Pool
+)
+
+// pooledEncoder returns its encoder to the pool on Close.
+type pooledEncoder struct {
+	io.Writer
+	flush func() error
+	close func() error
+}
+
+func (p pooledEncoder) Flush() error { return p.flush() }
+func (p pooledEncoder) Close() error { return p.close() }
+
+func newEncoder(encoding string, w io.Writer) io.WriteCloser {
+	if encoding == EncodingZstd {
+		enc, _ := zstdWriters.Get().(*zstd.Encoder)
+		if enc == nil {
+			enc, _ = zstd.NewWriter(nil, zstd.WithEncoderConcurrency(1))
+		}
+		enc.Reset(w)
+		return pooledEncoder{Writer: enc, flush: enc.Flush, close: func() error {
+			err := enc.Close()
+			zstdWriters.Put(enc)
+			return err
+		}}
+	}
+	gz, _ := gzipWriters.Get().(*gzip.Writer)
+	if gz == nil {
+		gz = gzip.NewWriter(w)
+	} else {
+		gz.Reset(w)
+	}
+	return pooledEncoder{Writer: gz, flush: gz.Flush, close: func() error {
+		err := gz.Close()
+		gzipWriters.Put(gz)
+		return err
+	}}
+}
@@ -0,0 +1,99 @@
+package middleware
+
+import (
+	"compress/gzip"
+	"io"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+
+	"github.com/klauspost/compress/zstd"
+)
+
+func TestNegotiateEncoding(t *testing.T) {
+	server := []string{EncodingZstd, EncodingGzip}
+	cases := map[string]string{
+		"":                        "",
+		"gzip":                    EncodingGzip,
+		"gzip, deflate, br, zstd": EncodingZstd,
+		"zstd;q=0, gzip;q=0.5":    EncodingGzip,
+		"*":                       EncodingZstd,
+		"*;q=0, gzip":             EncodingGzip,
+		"identity":                "",
+	}
+	for header, want := range cases {
+		if got := negotiateEncoding(header, server); got != want {
+			t.Errorf("negotiateEncoding(%q) = %q, want %q", header, got, want)
+		}
+	}
+}
+
+func TestCompressionMiddleware(t *testing.T) {
+	cfg := CompressionConfig{Encodings: []string{EncodingZstd, EncodingGzip}, MinBytes: 64, Types: DefaultCompressibleTypes, Exclude: []string{"/downloads/"}}
+	large := `{"items":"` + strings.Repeat("x", 200) + `"}`
+	h := CompressionMiddleware(cfg)(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		switch r.URL.Path {
+		case "/events":
+			w.Header().Set("Content-Type", "text/event-stream")
+			w.WriteHeader(http.StatusOK)
+			_, _ = io.WriteString(w, "data: "+strings.Repeat("x", 200)+"\n\n")
+			_ = http.NewResponseController(w).Flush()
+		case "/small":
+			w.Header().Set("Content-Type", "application/json")
+			_, _ = io.WriteString(w, `{"ok":true}`)
+		case "/image":
+			w.Header().Set("Content-Type", "image/png")
+			_, _ = io.WriteString(w, large)
+		default:
+			w.Header().Set("Content-Type", "application/json")
+			w.Header().Set("ETag", `"abc"`)
+			_, _ = io.WriteString(w, large)
+		}
+	}))
+	serve := func(path, acceptEncoding string) *httptest.ResponseRecorder {
+		req := httptest.NewRequest(http.MethodGet, path, nil)
+		req.Header.Set("Accept-Encoding", acceptEncoding)
+		rec := httptest.NewRecorder()
+		h.ServeHTTP(rec, req)
+		return rec
+	}
+
+	rec := serve("/cases", "gzip")
+	if rec.Header().Get("Content-Encoding") != EncodingGzip || rec.Header().Get("ETag") != `W/"abc"` {
+		t.Fatalf("expected a gzip response with a weak etag, got %v", rec.Header())
+	}
+	gz, err := gzip.NewReader(rec.Body)
+	if err != nil {
+		t.Fatalf("gzip reader: %v", err)
+	}
+	if body, _ := io.ReadAll(gz); string(body) != large {
+		t.Fatalf("unexpected gzip body %q", body)
+	}
+
+	rec = serve("/cases", "gzip, zstd")
+	if rec.Header().Get("Content-Encoding") != EncodingZstd {
+		t.Fatalf("expected zstd to be preferred, got %v", rec.Header())
+	}
+	dec, err := zstd.NewReader(rec.Body)
+	if err != nil {
+		t.Fatalf("zstd reader: %v", err)
+	}
+	defer dec.Close()
+	if body, _ := io.ReadAll(dec); string(body) != large {
+		t.Fatalf("unexpected zstd body %q", body)
+	}
+
+	for _, path := range []string{"/events", "/small", "/image", "/downloads/report.json"} {
+		rec := serve(path, "gzip")
+		if rec.Header().Get("Content-Encoding") != "" {
+			t.Fatalf("%s: expected an uncompressed response, got %v", path, rec.Header())
+		}
+		if rec.Body.Len() == 0 {
+			t.Fatalf("%s: expected the body to be sent", path)
+		}
+	}
+	if got := serve("/cases", "").Header().Get("Vary"); got != "Accept-Encoding" {
+		t.Fatalf("expected Vary: Accept-Encoding, got %q", got)
+	}
+}
@@ -3,19 +3,54 @@ package middleware
 import (
 	"net/http"
 	"strings"
+	"unicode"
 
 	"github.com/google/uuid"
 	"github.com/neural-chilli/aceryx/internal/observability"
 )
 
+const maxCorrelationIDLength = 128
+
+// CorrelationMiddleware tags each request with a correlation ID: the
+// caller's X-Correlation-ID, else its X-Request-Id, else a new one. The ID
+// is echoed in both response headers and carried in the request context to
+// logs, tool calls and error bodies.
 func CorrelationMiddleware(next http.Handler) http.Handler {
 	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
 		correlationID := strings.TrimSpace(r.Header.Get(observability.CorrelationHeader))
 		if correlationID == "" {
+			correlationID = strings.TrimSpace(r.Header.Get(observability.RequestIDHeader))
+		}
+		if !validCorrelationID(correlationID) {
 			correlationID = uuid.NewString()
 		}
 		w.Header().Set(observability.CorrelationHeader, correlationID)
+		w.Header().Set(observability.RequestIDHeader, correlationID)
 		ctx := observability.WithCorrelationID(r.Context(), correlationID)
 		next.ServeHTTP(w, r.WithContext(ctx))
 	})
 }
+
+// validCorrelationID rejects IDs that are empty, overlong or contain
+// characters that could forge log lines or headers downstream.
+func validCorrelationID(id string) bool {
+	if id == "" || len(id) > maxCorrelationIDLength {
+		return false
+	}
+	for _, r := range id {
+		if r > unicode.MaxASCII || !unicode.IsPrint(r) || r == ' ' {
+			return false
+		}
+	}
+	return true
+}
+
+// ErrorBody is the JSON body of an error response: the error code and, when
+// the request has one, its correlation ID for support requests.
+func ErrorBody(w http.ResponseWriter, code string) map[string]string {
+	body := map[string]string{"error": code}
+	if id := w.Header().Get(observability.CorrelationHeader); id != "" {
+		body["correlation_id"] = id
+	}
+	return body
+}
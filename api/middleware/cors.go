@@ -0,0 +1,205 @@
+package middleware
+
+import (
+	"errors"
+	"fmt"
+	"net/http"
+	"net/url"
+	"os"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// DefaultCORSMethods and DefaultCORSHeaders are allowed for cross-origin
+// requests unless ACERYX_CORS_ALLOWED_METHODS or ACERYX_CORS_ALLOWED_HEADERS
+// override them.
+var (
+	DefaultCORSMethods = []string{http.MethodGet, http.MethodPost, http.MethodPut, http.MethodPatch, http.MethodDelete}
+	DefaultCORSHeaders = []string{"Authorization", "Content-Type", "X-Correlation-ID", "X-Request-Id", "X-Requested-With"}
+)
+
+const DefaultCORSMaxAge = 10 * time.Minute
+
+var ErrInvalidCORSConfig = errors.New("invalid CORS configuration")
+
+// CORSConfig controls cross-origin requests. With no AllowedOrigins, CORS is
+// off and browsers only allow same-origin calls. An origin is either "*",
+// an exact origin such as https://app.example.com, or a wildcard subdomain
+// such as https://*.example.com, which matches any depth of subdomain but
+// not example.com itself.
+type CORSConfig struct {
+	AllowedOrigins   []string
+	AllowedMethods   []string
+	AllowedHeaders   []string
+	AllowCredentials bool
+	MaxAge           time.Duration
+}
+
+// CORSConfigFromEnv reads and validates the ACERYX_CORS_* settings.
+func CORSConfigFromEnv() (CORSConfig, error) {
+	cfg := CORSConfig{
+		AllowedOrigins: splitEnvList("ACERYX_CORS_ALLOWED_ORIGINS"),
+		AllowedMethods: DefaultCORSMethods,
+		AllowedHeaders: DefaultCORSHeaders,
+		MaxAge:         DefaultCORSMaxAge,
+	}
+	if methods := splitEnvList("ACERYX_CORS_ALLOWED_METHODS"); len(methods) > 0 {
+		cfg.AllowedMethods = methods
+	}
+	if headers := splitEnvList("ACERYX_CORS_ALLOWED_HEADERS"); len(headers) > 0 {
+		cfg.AllowedHeaders = headers
+	}
+	if raw := strings.TrimSpace(os.Getenv("ACERYX_CORS_ALLOW_CREDENTIALS")); raw != "" {
+		allow, err := strconv.ParseBool(raw)
+		if err != nil {
+			return CORSConfig{}, fmt.Errorf("%w: ACERYX_CORS_ALLOW_CREDENTIALS must be true or false", ErrInvalidCORSConfig)
+		}
+		cfg.AllowCredentials = allow
+	}
+	if raw := strings.TrimSpace(os.Getenv("ACERYX_CORS_MAX_AGE")); raw != "" {
+		maxAge, err := time.ParseDuration(raw)
+		if err != nil || maxAge < 0 {
+			return CORSConfig{}, fmt.Errorf("%w: ACERYX_CORS_MAX_AGE must be a non-negative duration", ErrInvalidCORSConfig)
+		}
+		cfg.MaxAge = maxAge
+	}
+	if err := cfg.Validate(); err != nil {
+		return CORSConfig{}, err
+	}
+	return cfg, nil
+}
+
+// Validate rejects origins that are not scheme://host[:port], a "*" mixed
+// with other origins or combined with credentials (which browsers refuse),
+// and methods or headers that are not valid HTTP tokens.
+func (c CORSConfig) Validate() error {
+	for _, origin := range c.AllowedOrigins {
+		if origin == "*" {
+			if len(c.AllowedOrigins) > 1 {
+				return fmt.Errorf("%w: \"*\" cannot be combined with other origins", ErrInvalidCORSConfig)
+			}
+			if c.AllowCredentials {
+				return fmt.Errorf("%w: \"*\" cannot be used with credentials; list the origins instead", ErrInvalidCORSConfig)
+			}
+			continue
+		}
+		if err := validateOrigin(origin); err != nil {
+			return fmt.Errorf("%w: origin %q: %v", ErrInvalidCORSConfig, origin, err)
+		}
+	}
+	for _, method := range c.AllowedMethods {
+		if !isHTTPToken(method) {
+			return fmt.Errorf("%w: method %q", ErrInvalidCORSConfig, method)
+		}
+	}
+	for _, header := range c.AllowedHeaders {
+		if !isHTTPToken(header) {
+			return fmt.Errorf("%w: header %q", ErrInvalidCORSConfig, header)
+		}
+	}
+	return nil
+}
+
+func validateOrigin(origin string) error {
+	u, err := url.Parse(strings.Replace(origin, "://*.", "://wildcard.", 1))
+	if err != nil {
+		return err
+	}
+	if u.Scheme != "http" && u.Scheme != "https" {
+		return errors.New("scheme must be http or https")
+	}
+	if u.Host == "" || u.User != nil || (u.Path != "" && u.Path != "/") || u.RawQuery != "" || u.Fragment != "" {
+		return errors.New("must be scheme://host[:port]")
+	}
+	if strings.Contains(strings.TrimPrefix(u.Host, "wildcard."), "*") {
+		return errors.New("only a leading *. wildcard is supported")
+	}
+	return nil
+}
+
+func isHTTPToken(s string) bool {
+	if s == "" {
+		return false
+	}
+	for _, r := range s {
+		if r > 0x7e || r <= ' ' || strings.ContainsRune("\"(),/:;<=>?@[\\]{}", r) {
+			return false
+		}
+	}
+	return true
+}
+
+// allows reports whether origin is permitted. Origins compare without case
+// and without a trailing slash, as browsers send them.
+func (c CORSConfig) allows(origin string) bool {
+	origin = strings.ToLower(strings.TrimSuffix(origin, "/"))
+	for _, allowed := range c.AllowedOrigins {
+		allowed = strings.ToLower(strings.TrimSuffix(allowed, "/"))
+		if allowed == "*" || allowed == origin {
+			return true
+		}
+		scheme, rest, ok := strings.Cut(allowed, "://*.")
+		if !ok {
+			continue
+		}
+		// https://*.example.com:8443 matches https://a.example.com:8443.
+		if strings.HasPrefix(origin, scheme+"://") && strings.HasSuffix(origin, "."+rest) {
+			host := strings.TrimSuffix(strings.TrimPrefix(origin, scheme+"://"), "."+rest)
+			if host != "" && !strings.ContainsAny(host, ":/") {
+				return true
+			}
+		}
+	}
+	return false
+}
+
+// CORSMiddleware answers preflight requests and adds CORS headers for
+// allowed origins. Requests from other origins pass through without CORS
+// headers, so the browser blocks them. With credentials, the origin is
+// echoed rather than "*", as browsers require.
+func CORSMiddleware(cfg CORSConfig) func(http.Handler) http.Handler {
+	return func(next http.Handler) http.Handler {
+		if len(cfg.AllowedOrigins) == 0 {
+			return next
+		}
+		wildcard := len(cfg.AllowedOrigins) == 1 && cfg.AllowedOrigins[0] == "*"
+		methods := strings.Join(cfg.AllowedMethods, ", ")
+		headers := strings.Join(cfg.AllowedHeaders, ", ")
+		maxAge := strconv.Itoa(int(cfg.MaxAge.Seconds()))
+		return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+			origin := r.Header.Get("Origin")
+			preflight := r.Method == http.MethodOptions && r.Header.Get("Access-Control-Request-Method") != ""
+			if !wildcard {
+				w.Header().Add("Vary", "Origin")
+			}
+			if origin == "" || !cfg.allows(origin) {
+				if preflight {
+					w.WriteHeader(http.StatusForbidden)
+					return
+				}
+				next.ServeHTTP(w, r)
+				return
+			}
+			if wildcard {
+				w.Header().Set("Access-Control-Allow-Origin", "*")
+			} else {
+				w.Header().Set("Access-Control-Allow-Origin", origin)
+			}
+			if cfg.AllowCredentials {
+				w.Header().Set("Access-Control-Allow-Credentials", "true")
+			}
+			if !preflight {
+				w.Header().Set("Access-Control-Expose-Headers", "X-Correlation-ID, X-Request-Id, Retry-After")
+				next.ServeHTTP(w, r)
+				return
+			}
+			w.Header().Add("Vary", "Access-Control-Request-Method")
+			w.Header().Add("Vary", "Access-Control-Request-Headers")
+			w.Header().Set("Access-Control-Allow-Methods", methods)
+			w.Header().Set("Access-Control-Allow-Headers", headers)
+			w.Header().Set("Access-Control-Max-Age", maxAge)
+			w.WriteHeader(http.StatusNoContent)
+		})
+	}
+}
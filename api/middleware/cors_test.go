@@ -0,0 +1,119 @@
+package middleware
+
+import (
+	"errors"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+)
+
+func TestCORSConfigValidate(t *testing.T) {
+	cases := []struct {
+		name  string
+		cfg   CORSConfig
+		valid bool
+	}{
+		{name: "exact", cfg: CORSConfig{AllowedOrigins: []string{"https://app.example.com", "http://localhost:5173"}}, valid: true},
+		{name: "wildcard_subdomain", cfg: CORSConfig{AllowedOrigins: []string{"https://*.example.com"}, AllowCredentials: true}, valid: true},
+		{name: "any", cfg: CORSConfig{AllowedOrigins: []string{"*"}}, valid: true},
+		{name: "any_with_credentials", cfg: CORSConfig{AllowedOrigins: []string{"*"}, AllowCredentials: true}},
+		{name: "any_mixed", cfg: CORSConfig{AllowedOrigins: []string{"*", "https://app.example.com"}}},
+		{name: "path", cfg: CORSConfig{AllowedOrigins: []string{"https://app.example.com/ui"}}},
+		{name: "scheme", cfg: CORSConfig{AllowedOrigins: []string{"ftp://app.example.com"}}},
+		{name: "inner_wildcard", cfg: CORSConfig{AllowedOrigins: []string{"https://app.*.example.com"}}},
+		{name: "bad_method", cfg: CORSConfig{AllowedOrigins: []string{"*"}, AllowedMethods: []string{"GET POST"}}},
+		{name: "bad_header", cfg: CORSConfig{AllowedOrigins: []string{"*"}, AllowedHeaders: []string{"X-Bad:"}}},
+	}
+	for _, tc := range cases {
+		t.Run(tc.name, func(t *testing.T) {
+			err := tc.cfg.Validate()
+			if tc.valid && err != nil {
+				t.Fatalf("expected valid, got %v", err)
+			}
+			if !tc.valid && !errors.Is(err, ErrInvalidCORSConfig) {
+				t.Fatalf("expected ErrInvalidCORSConfig, got %v", err)
+			}
+		})
+	}
+}
+
+func TestCORSConfigFromEnvRejectsInvalid(t *testing.T) {
+	t.Setenv("ACERYX_CORS_ALLOWED_ORIGINS", "*")
+	t.Setenv("ACERYX_CORS_ALLOW_CREDENTIALS", "true")
+	if _, err := CORSConfigFromEnv(); !errors.Is(err, ErrInvalidCORSConfig) {
+		t.Fatalf("expected ErrInvalidCORSConfig, got %v", err)
+	}
+}
+
+func TestCORSAllows(t *testing.T) {
+	cfg := CORSConfig{AllowedOrigins: []string{"https://app.example.com", "https://*.example.org", "http://*.internal:8080"}}
+	cases := map[string]bool{
+		"https://app.example.com":      true,
+		"https://APP.example.com/":     true,
+		"http://app.example.com":       false,
+		"https://a.example.org":        true,
+		"https://a.b.example.org":      true,
+		"https://example.org":          false,
+		"https://evilexample.org":      false,
+		"https://a.example.org:8443":   false,
+		"http://tools.internal:8080":   true,
+		"http://tools.internal":        false,
+		"https://a.example.org.attack": false,
+	}
+	for origin, want := range cases {
+		if got := cfg.allows(origin); got != want {
+			t.Errorf("allows(%q) = %v, want %v", origin, got, want)
+		}
+	}
+}
+
+func TestCORSMiddleware(t *testing.T) {
+	cfg := CORSConfig{
+		AllowedOrigins:   []string{"https://*.example.com"},
+		AllowedMethods:   DefaultCORSMethods,
+		AllowedHeaders:   DefaultCORSHeaders,
+		AllowCredentials: true,
+		MaxAge:           DefaultCORSMaxAge,
+	}
+	called := false
+	h := CORSMiddleware(cfg)(http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+		called = true
+		w.WriteHeader(http.StatusOK)
+	}))
+
+	preflight := httptest.NewRequest(http.MethodOptions, "/api/v1/cases", nil)
+	preflight.Header.Set("Origin", "https://app.example.com")
+	preflight.Header.Set("Access-Control-Request-Method", http.MethodPost)
+	rr := httptest.NewRecorder()
+	h.ServeHTTP(rr, preflight)
+	if rr.Code != http.StatusNoContent || called {
+		t.Fatalf("expected preflight to be answered with 204, got %d (handler called %v)", rr.Code, called)
+	}
+	if got := rr.Header().Get("Access-Control-Allow-Origin"); got != "https://app.example.com" {
+		t.Fatalf("expected echoed origin, got %q", got)
+	}
+	if rr.Header().Get("Access-Control-Allow-Credentials") != "true" || rr.Header().Get("Access-Control-Max-Age") != "600" {
+		t.Fatalf("unexpected preflight headers %v", rr.Header())
+	}
+	if !strings.Contains(rr.Header().Get("Access-Control-Allow-Headers"), "X-Requested-With") {
+		t.Fatalf("expected the CSRF header to be allowed by default, got %q", rr.Header().Get("Access-Control-Allow-Headers"))
+	}
+
+	req := httptest.NewRequest(http.MethodGet, "/api/v1/cases", nil)
+	req.Header.Set("Origin", "https://app.example.com")
+	rr = httptest.NewRecorder()
+	h.ServeHTTP(rr, req)
+	if !called || rr.Header().Get("Access-Control-Allow-Origin") != "https://app.example.com" {
+		t.Fatalf("expected CORS headers on the actual request, got %v", rr.Header())
+	}
+
+	denied := httptest.NewRequest(http.MethodOptions, "/api/v1/cases", nil)
+	denied.Header.Set("Origin", "https://evil.test")
+	denied.Header.Set("Access-Control-Request-Method", http.MethodPost)
+	rr = httptest.NewRecorder()
+	h.ServeHTTP(rr, denied)
+	if rr.Code != http.StatusForbidden || rr.Header().Get("Access-Control-Allow-Origin") != "" {
+		t.Fatalf("expected disallowed preflight to be refused, got %d %v", rr.Code, rr.Header())
+	}
+}
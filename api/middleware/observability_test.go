@@ -48,6 +48,41 @@ func TestCorrelationIDEchoedWhenProvided(t *testing.T) {
 	}
 }
 
+func TestCorrelationIDFallsBackToRequestID(t *testing.T) {
+	h := CorrelationMiddleware(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		writeAuthError(w, http.StatusUnauthorized, observability.CorrelationIDFromContext(r.Context()))
+	}))
+	req := httptest.NewRequest(http.MethodGet, "/x", nil)
+	req.Header.Set(observability.RequestIDHeader, "req-123")
+	rr := httptest.NewRecorder()
+	h.ServeHTTP(rr, req)
+	if rr.Header().Get(observability.CorrelationHeader) != "req-123" || rr.Header().Get(observability.RequestIDHeader) != "req-123" {
+		t.Fatalf("expected request id to be echoed, got %v", rr.Header())
+	}
+	var body map[string]string
+	if err := json.Unmarshal(rr.Body.Bytes(), &body); err != nil {
+		t.Fatalf("decode error body: %v", err)
+	}
+	if body["error"] != "req-123" || body["correlation_id"] != "req-123" {
+		t.Fatalf("expected correlation id in error body, got %v", body)
+	}
+}
+
+func TestCorrelationIDReplacedWhenInvalid(t *testing.T) {
+	h := CorrelationMiddleware(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		w.WriteHeader(http.StatusNoContent)
+	}))
+	for _, provided := range []string{"bad id\nforged", strings.Repeat("x", maxCorrelationIDLength+1)} {
+		req := httptest.NewRequest(http.MethodGet, "/x", nil)
+		req.Header.Set(observability.CorrelationHeader, provided)
+		rr := httptest.NewRecorder()
+		h.ServeHTTP(rr, req)
+		if _, err := uuid.Parse(rr.Header().Get(observability.CorrelationHeader)); err != nil {
+			t.Fatalf("expected a generated id for %q, got %q", provided, rr.Header().Get(observability.CorrelationHeader))
+		}
+	}
+}
+
 func TestMetricsMiddlewareIncrementsCounterAndDuration(t *testing.T) {
 	before := testutil.ToFloat64(observability.HTTPRequestsTotal.WithLabelValues(http.MethodGet, "/metrics-test", "201"))
 	beforeDuration := testutil.CollectAndCount(observability.HTTPRequestDurationSeconds)
@@ -16,11 +16,15 @@ func RequirePermission(authz *rbac.Service, auth *rbac.AuthService, permission s
 			if principal == nil {
 				w.Header().Set("Content-Type", "application/json")
 				w.WriteHeader(http.StatusUnauthorized)
-				_ = json.NewEncoder(w).Encode(map[string]string{"error": "unauthenticated"})
+				_ = json.NewEncoder(w).Encode(ErrorBody(w, "unauthenticated"))
 				return
 			}
 
-			if err := authz.Authorize(r.Context(), principal.ID, permission); err != nil {
+			err := authz.Authorize(r.Context(), principal.ID, permission)
+			if err == nil && !principal.Scope.Allows(permission) {
+				err = rbac.ErrForbidden
+			}
+			if err != nil {
 				slog.WarnContext(r.Context(), "permission denied",
 					append(observability.RequestAttrs(r.Context()),
 						"permission", permission,
@@ -32,10 +36,23 @@ func RequirePermission(authz *rbac.Service, auth *rbac.AuthService, permission s
 				}
 				w.Header().Set("Content-Type", "application/json")
 				w.WriteHeader(http.StatusForbidden)
-				_ = json.NewEncoder(w).Encode(map[string]string{"error": "forbidden"})
+				_ = json.NewEncoder(w).Encode(ErrorBody(w, "forbidden"))
 				return
 			}
 			next.ServeHTTP(w, r)
 		})
 	}
 }
+
+// DenyScopedTokens rejects principals authenticated with a service token.
+// Routes without a permission check cannot be matched against a token's
+// scope, so scoped tokens may not use them.
+func DenyScopedTokens(next http.Handler) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if principal := PrincipalFromContext(r.Context()); principal != nil && principal.Scope != nil {
+			writeAuthError(w, http.StatusForbidden, "forbidden")
+			return
+		}
+		next.ServeHTTP(w, r)
+	})
+}
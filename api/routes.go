@@ -3,7 +3,9 @@ package api
 import (
 	"context"
 	"database/sql"
+	"errors"
 	"fmt"
+	"log/slog"
 	"net"
 	"net/http"
 	"os"
@@ -19,23 +21,36 @@ import (
 	"github.com/neural-chilli/aceryx/internal/agentic"
 	"github.com/neural-chilli/aceryx/internal/agents"
 	"github.com/neural-chilli/aceryx/internal/ai"
+	"github.com/neural-chilli/aceryx/internal/artifacts"
 	"github.com/neural-chilli/aceryx/internal/assistant"
 	"github.com/neural-chilli/aceryx/internal/audit"
 	"github.com/neural-chilli/aceryx/internal/cases"
 	"github.com/neural-chilli/aceryx/internal/channels"
+	"github.com/neural-chilli/aceryx/internal/chaos"
+	"github.com/neural-chilli/aceryx/internal/cluster"
 	formchannel "github.com/neural-chilli/aceryx/internal/channels/form"
 	webhookchannel "github.com/neural-chilli/aceryx/internal/channels/webhook"
+	"github.com/neural-chilli/aceryx/internal/config"
 	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/connectors/chunkconn"
 	"github.com/neural-chilli/aceryx/internal/connectors/docgenconn"
 	"github.com/neural-chilli/aceryx/internal/connectors/emailconn"
 	"github.com/neural-chilli/aceryx/internal/connectors/gchatconn"
 	"github.com/neural-chilli/aceryx/internal/connectors/httpconn"
 	"github.com/neural-chilli/aceryx/internal/connectors/jiraconn"
+	"github.com/neural-chilli/aceryx/internal/connectors/jsonconn"
+	"github.com/neural-chilli/aceryx/internal/connectors/objectstorageconn"
 	"github.com/neural-chilli/aceryx/internal/connectors/postgresconn"
 	"github.com/neural-chilli/aceryx/internal/connectors/slackconn"
+	"github.com/neural-chilli/aceryx/internal/connectors/soapconn"
 	"github.com/neural-chilli/aceryx/internal/connectors/teamsconn"
+	"github.com/neural-chilli/aceryx/internal/connectors/templateconn"
 	"github.com/neural-chilli/aceryx/internal/connectors/webhookreceiver"
 	"github.com/neural-chilli/aceryx/internal/connectors/webhooksender"
+	"github.com/neural-chilli/aceryx/internal/connectors/xmlconn"
+	"github.com/neural-chilli/aceryx/internal/customtools"
+	"github.com/neural-chilli/aceryx/internal/deadletter"
+	"github.com/neural-chilli/aceryx/internal/designer"
 	"github.com/neural-chilli/aceryx/internal/drivers"
 	"github.com/neural-chilli/aceryx/internal/drivers/duckdb"
 	"github.com/neural-chilli/aceryx/internal/drivers/fileazure"
@@ -52,7 +67,10 @@ import (
 	"github.com/neural-chilli/aceryx/internal/drivers/smtp"
 	"github.com/neural-chilli/aceryx/internal/drivers/sqlite"
 	"github.com/neural-chilli/aceryx/internal/engine"
+	"github.com/neural-chilli/aceryx/internal/events"
 	"github.com/neural-chilli/aceryx/internal/extraction"
+	"github.com/neural-chilli/aceryx/internal/features"
+	"github.com/neural-chilli/aceryx/internal/grpcclient"
 	"github.com/neural-chilli/aceryx/internal/llm"
 	"github.com/neural-chilli/aceryx/internal/llm/anthropic"
 	"github.com/neural-chilli/aceryx/internal/llm/custom"
@@ -61,15 +79,24 @@ import (
 	"github.com/neural-chilli/aceryx/internal/mcp"
 	"github.com/neural-chilli/aceryx/internal/mcpserver"
 	mcpservertools "github.com/neural-chilli/aceryx/internal/mcpserver/tools"
+	"github.com/neural-chilli/aceryx/internal/mocktools"
 	"github.com/neural-chilli/aceryx/internal/notify"
+	"github.com/neural-chilli/aceryx/internal/observability"
 	"github.com/neural-chilli/aceryx/internal/plugins"
 	"github.com/neural-chilli/aceryx/internal/plugins/hostfns"
+	"github.com/neural-chilli/aceryx/internal/progress"
+	"github.com/neural-chilli/aceryx/internal/quotas"
 	"github.com/neural-chilli/aceryx/internal/rag"
 	ragstore "github.com/neural-chilli/aceryx/internal/rag/store"
 	"github.com/neural-chilli/aceryx/internal/rbac"
+	"github.com/neural-chilli/aceryx/internal/redaction"
 	"github.com/neural-chilli/aceryx/internal/reports"
+	"github.com/neural-chilli/aceryx/internal/script"
+	"github.com/neural-chilli/aceryx/internal/search"
+	"github.com/neural-chilli/aceryx/internal/subworkflows"
 	"github.com/neural-chilli/aceryx/internal/tasks"
 	"github.com/neural-chilli/aceryx/internal/tenants"
+	"github.com/neural-chilli/aceryx/internal/toolpool"
 	"github.com/neural-chilli/aceryx/internal/triggers"
 	"github.com/neural-chilli/aceryx/internal/vault"
 	workflowsvc "github.com/neural-chilli/aceryx/internal/workflows"
@@ -80,6 +107,8 @@ func NewRouter() http.Handler {
 	mux := http.NewServeMux()
 	health := handlers.NewHealthHandlers(nil, nil, nil)
 	mux.HandleFunc("GET /health", health.Health)
+	mux.HandleFunc("GET /health/live", health.Liveness)
+	mux.HandleFunc("GET /health/ready", health.Readiness)
 	mux.HandleFunc("GET /healthz", health.Liveness)
 	mux.HandleFunc("GET /readyz", health.Readiness)
 	mux.Handle("GET /metrics", health.Metrics())
@@ -91,38 +120,114 @@ func NewRouterWithServices(db *sql.DB, eng *engine.Engine) http.Handler {
 }
 
 func NewRouterWithServicesContext(bgCtx context.Context, db *sql.DB, eng *engine.Engine) http.Handler {
+	return NewRouterWithOptions(bgCtx, db, eng, RouterOptions{})
+}
+
+// RouterOptions carries what the serving process resolves before the router
+// is built.
+type RouterOptions struct {
+	// ScriptWorker is the command that runs each script step in a child
+	// process; see script.Runner.SetWorker. Empty runs scripts in process.
+	ScriptWorker []string
+}
+
+func NewRouterWithOptions(bgCtx context.Context, db *sql.DB, eng *engine.Engine, opts RouterOptions) http.Handler {
 	mux := http.NewServeMux()
 
 	ctSvc := cases.NewCaseTypeService(db)
 	caseSvc := cases.NewCaseService(db, eng)
+	caseSvc.SetMockProfilesEnabled(chaos.IsDevEnvironment(os.Getenv("ACERYX_ENVIRONMENT")))
 	reportSvc := cases.NewReportsService(db, 5*time.Minute)
 	caseHandlers := handlers.NewCaseHandlers(ctSvc, caseSvc, reportSvc)
+	caseQueue := cases.NewCreateQueue(db, caseSvc, cases.CreateQueueConfig{
+		Workers:  intFromEnv("ACERYX_CASE_QUEUE_WORKERS", 4),
+		Capacity: intFromEnv("ACERYX_CASE_QUEUE_CAPACITY", 1000),
+	})
+	caseQueue.Start(bgCtx)
+	caseHandlers.Queue = caseQueue
+	caseHandlers.Engine = eng
+	progressBroker := progress.NewBroker()
+	caseHandlers.Progress = progressBroker
+	deadLetterStore := deadletter.NewStore(db, eng)
+	if url := strings.TrimSpace(os.Getenv("ACERYX_FAILURE_WEBHOOK_URL")); url != "" {
+		deadLetterStore.SetNotifier(deadletter.NewWebhookNotifier(url, os.Getenv("ACERYX_FAILURE_WEBHOOK_SECRET")))
+	}
+	deadLetterHandlers := handlers.NewDeadLetterHandlers(deadLetterStore)
+	redactor, err := redaction.FromEnv()
+	if err != nil {
+		// aceryx serve rejects this at startup; other callers get the built-in rules.
+		slog.Error("redaction rules ignored", "error", err)
+		redactor = redaction.Default()
+	}
 	workflowService := workflowsvc.NewService(db)
+	workflowService.SetRedactor(redactor)
+	caseHandlers.Workflows = workflowService
 	workflowHandlers := handlers.NewWorkflowHandlers(workflowService)
+	tagHandlers := handlers.NewTagHandlers(workflowService)
 	reportingSvc := reports.NewService(db, agents.NewLLMClientFromEnv(120*time.Second))
 	reportsHandlers := handlers.NewReportsHandlers(reportingSvc)
 	auditSvc := audit.NewService(db)
 	auditHandlers := handlers.NewAuditHandlers(auditSvc)
 
 	authzSvc := rbac.NewService(db)
+	workflowHandlers.Authz = authzSvc
+	workflowService.SetPermissionChecker(authzSvc)
+	caseSvc.SetExecutionAuthorizer(workflowService)
 	jwtSecret := firstNonEmpty(os.Getenv("ACERYX_JWT_SECRET"), "test-secret")
 	authSvc := rbac.NewAuthService(db, jwtSecret, parseDurationOrDefault(os.Getenv("ACERYX_SESSION_TTL"), 24*time.Hour))
 	principalSvc := rbac.NewPrincipalService(db, authzSvc)
 	roleSvc := rbac.NewRoleService(db, authzSvc)
 	authHandlers := handlers.NewAuthHandlers(authSvc, principalSvc, roleSvc)
+	authHandlers.Tokens = rbac.NewTokenService(db, authzSvc)
+	caseHandlers.Tokens = authHandlers.Tokens
 	secretStore := connectors.NewChainedSecretStore(connectors.NewDBSecretStore(db), &connectors.EnvSecretStore{})
+	egressPolicy := egressPolicyFromEnv()
+	eventBus := events.NewBus(db, egressPolicy)
+	eventHandlers := handlers.NewEventHandlers(eventBus)
+	workflowService.SetEventBus(eventBus)
+	observability.OnToolAnomaly(eventBus.OnToolAnomaly)
 	connectorRegistry := connectors.NewRegistry()
-	connectorRegistry.Register(httpconn.New())
+	connectorRegistry.Register(httpconn.NewWithEgress(egressPolicy))
 	connectorRegistry.Register(webhookreceiver.New())
-	connectorRegistry.Register(webhooksender.New())
+	connectorRegistry.Register(webhooksender.NewWithEgress(egressPolicy))
 	connectorRegistry.Register(emailconn.New())
 	connectorRegistry.Register(slackconn.New())
+	connectorRegistry.Register(slackconn.NewMessageConnector(egressPolicy))
 	connectorRegistry.Register(teamsconn.New())
+	connectorRegistry.Register(teamsconn.NewMessageConnector(egressPolicy))
 	connectorRegistry.Register(gchatconn.New())
 	connectorRegistry.Register(jiraconn.New())
+	connectorRegistry.Register(jsonconn.New())
+	connectorRegistry.Register(jsonconn.NewSchemaValidator())
+	connectorRegistry.Register(jsonconn.NewDataMapper())
+	connectorRegistry.Register(chunkconn.New())
+	connectorRegistry.Register(xmlconn.New())
+	connectorRegistry.Register(soapconn.NewWithEgress(egressPolicy))
+	connectorRegistry.Register(objectstorageconn.New(splitAndTrim(os.Getenv("ACERYX_OBJECT_STORAGE_PROVIDERS")), egressPolicy))
 	connectorRegistry.Register(postgresconn.New())
 	connectorRegistry.Register(docgenconn.New(db, nil))
+	connectorRegistry.Register(templateconn.New(db, agents.NewPromptTemplateService(db)))
+	connectorUsage := connectors.NewUsageStore(db)
+	connectorRegistry.SetUsageRecorder(connectorUsage)
 	connectorHandlers := handlers.NewConnectorHandlers(connectorRegistry, secretStore)
+	connectorHandlers.Usage = connectorUsage
+	connectorHandlers.Redactor = redactor
+	executeGuard := connectors.NewExecutionGuard(executeGuardConfigFromEnv())
+	connectorHandlers.Guard = executeGuard
+	configReloader := config.NewReloader(os.Getenv("ACERYX_CONFIG_FILE"))
+	configReloader.OnReload(func() { observability.SetLogLevel(os.Getenv("ACERYX_LOG_LEVEL")) })
+	configReloader.OnReload(func() { executeGuard.Reconfigure(executeGuardConfigFromEnv()) })
+	chaos.Configure(chaosRulesFromEnv())
+	configReloader.OnReload(func() { chaos.Configure(chaosRulesFromEnv()) })
+	clusterNode := cluster.New(db, cluster.Config{
+		ReplicaID:         os.Getenv("ACERYX_REPLICA_ID"),
+		HeartbeatInterval: parseDurationOrDefault(os.Getenv("ACERYX_CLUSTER_HEARTBEAT_INTERVAL"), cluster.DefaultHeartbeatInterval),
+		LeaseTTL:          parseDurationOrDefault(os.Getenv("ACERYX_CLUSTER_LEASE_TTL"), cluster.DefaultLeaseTTL),
+	})
+	systemHandlers := handlers.NewSystemHandlers(configReloader)
+	systemHandlers.Cluster = clusterNode
+	systemHandlers.DB = db
+	systemHandlers.Engine = eng
 	driverRegistry := drivers.NewDriverRegistry()
 	driverRegistry.RegisterDB(postgres.New())
 	driverRegistry.RegisterDB(mysql.New())
@@ -177,6 +282,30 @@ func NewRouterWithServicesContext(bgCtx context.Context, db *sql.DB, eng *engine
 	aiComponentRegistry := ai.NewComponentRegistry(aiComponentStore)
 	_ = aiComponentRegistry.LoadFromDirectory(firstNonEmpty(os.Getenv("ACERYX_AI_COMPONENTS_DIR"), "./ai-components"))
 	workflowService.SetAIComponentCatalog(aiComponentRegistry)
+	workflowService.SetConnectorCatalog(connectorRegistry)
+	workflowService.SetEgressPolicy(egressPolicy)
+	workflowService.SetAppURL(os.Getenv("ACERYX_APP_URL"))
+	workflowService.SetCaseCreator(caseSvc)
+	workflowService.SetDraftHistoryLimit(intFromEnv("ACERYX_DESIGNER_HISTORY_LIMIT", workflowsvc.DefaultDraftHistoryLimit))
+	if lintRules, err := workflowsvc.ParseLintRules(os.Getenv("ACERYX_LINT_RULES")); err != nil {
+		// aceryx serve rejects this at startup; other callers get the default severities.
+		slog.Error("lint rules ignored", "error", err)
+	} else {
+		workflowService.SetLintRules(lintRules)
+	}
+	mockProfileStore := mocktools.NewStore(db)
+	mockProtocol := mocktools.NewMockProtocol(mockProfileStore)
+	integrationExecutor := connectors.NewExecutor(db, connectorRegistry, secretStore)
+	integrationExecutor.SetMockProtocol(mockProtocol)
+	connectorCache := connectors.NewResultCache(intFromEnv("ACERYX_CONNECTOR_CACHE_MAX_ENTRIES", connectors.DefaultCacheMaxEntries))
+	integrationExecutor.SetResultCache(connectorCache)
+	connectorCircuits := connectors.NewCircuits(
+		intFromEnv("ACERYX_CONNECTOR_CIRCUIT_FAILURES", connectors.DefaultCircuitFailures),
+		parseDurationOrDefault(os.Getenv("ACERYX_CONNECTOR_CIRCUIT_COOLDOWN"), connectors.DefaultCircuitCoolDown),
+	)
+	integrationExecutor.SetCircuits(connectorCircuits)
+	workflowService.SetStepTester(integrationExecutor)
+	workflowService.SetCredentialChecker(integrationExecutor)
 	aiComponentHandlers := handlers.NewAIComponentHandlers(aiComponentRegistry)
 	extractionService := extraction.NewService(extraction.NewRepository(db))
 	if eng != nil {
@@ -220,6 +349,15 @@ func NewRouterWithServicesContext(bgCtx context.Context, db *sql.DB, eng *engine
 	vaultSvc.SetAuditService(auditSvc)
 	vaultSvc.SetBackendStatus(vaultBackendStatus)
 	vaultHandlers := handlers.NewVaultHandlers(vaultSvc)
+	var artifactSvc *artifacts.Service
+	if artifactObjects, err := artifacts.NewObjectStoreFromEnv(bgCtx); err != nil {
+		// aceryx serve rejects a configured backend that fails here; only the
+		// default local directory is allowed to be missing.
+		slog.Warn("artifact store unavailable; steps that emit artifacts will fail", "error", err)
+	} else {
+		artifactSvc = artifacts.NewService(db, artifactObjects)
+	}
+	artifactHandlers := handlers.NewArtifactHandlers(artifactSvc, caseQueue)
 	ragKBStore := ragstore.NewKnowledgeBaseStore(db)
 	ragDocStore := ragstore.NewDocumentStore(db)
 	var ragVectorStore rag.VectorStore = rag.NewNoopVectorStore()
@@ -242,9 +380,10 @@ func NewRouterWithServicesContext(bgCtx context.Context, db *sql.DB, eng *engine
 	triggerManager := triggers.NewTriggerManager(pluginRuntime, nil, driverRegistry, channels.NewTriggerPipelineAdapter(channelPipeline), triggerStore, triggers.TriggerManagerConfig{})
 	triggerHandlers := triggers.NewAdminHandlers(triggerManager)
 	channelManager := channels.NewChannelManager(channelPipeline, channelStore, triggerManager, driverRegistry, secretStore)
-	channelWebhook := &webhookchannel.WebhookHandler{ChannelStore: channelStore, Pipeline: channelPipeline, SecretStore: secretStore}
+	channelWebhook := &webhookchannel.WebhookHandler{ChannelStore: channelStore, Pipeline: channelPipeline, SecretStore: secretStore, Outputs: caseSvc}
 	channelForm := formchannel.NewFormHandler(channelStore, channelPipeline, channels.NewCaseTypeSchemaStore(db))
 	channelAPI := channels.NewAPI(channelStore, channelManager)
+	channelAPI.Executions = workflowService
 	pluginsDir := firstNonEmpty(os.Getenv("ACERYX_PLUGINS_DIR"), "./testdata")
 	_ = pluginRuntime.LoadAll(pluginsDir, plugins.AllowAllLicence{})
 	_ = pluginRuntime.RegisterVirtual(&plugins.Plugin{
@@ -273,12 +412,44 @@ func NewRouterWithServicesContext(bgCtx context.Context, db *sql.DB, eng *engine
 					{Key: "auth_type", Label: "Authentication", Type: "select", Required: true, Default: "none", Options: []string{"none", "bearer", "api_key", "oauth2"}},
 					{Key: "auth_secret", Label: "Auth Secret", Type: "secret", Required: false, HelpText: "Secret reference for auth"},
 					{Key: "tool", Label: "Tool", Type: "text", Required: true, HelpText: "MCP tool name"},
+					{Key: "tool_version", Label: "Tool Version", Type: "text", Required: false, HelpText: "Pin the tool's input schema version; the step fails if the server changes it"},
 					{Key: "arguments", Label: "Arguments", Type: "json", Required: false, HelpText: "JSON object for tool arguments"},
 					{Key: "output_path", Label: "Output Path", Type: "text", Required: false, HelpText: "Target case.data path"},
 				},
 			},
 		},
 	})
+	_ = pluginRuntime.RegisterVirtual(&plugins.Plugin{
+		ID:           "grpc-client",
+		Name:         "gRPC Service Call",
+		Version:      "1.0.0",
+		Type:         plugins.StepPlugin,
+		Category:     "Integration",
+		LicenceTier:  "open_source",
+		MaturityTier: "core",
+		ToolCapable:  false,
+		Status:       plugins.PluginActive,
+		Manifest: plugins.PluginManifest{
+			ID:          "grpc-client",
+			Name:        "gRPC Service Call",
+			Version:     "1.0.0",
+			Type:        string(plugins.StepPlugin),
+			Category:    "Integration",
+			Tier:        "open_source",
+			Maturity:    "core",
+			ToolCapable: false,
+			UI: plugins.ManifestUI{
+				Description: "Call a unary method on a gRPC server that supports reflection.",
+				Properties: []plugins.PropertyDef{
+					{Key: "address", Label: "Server Address", Type: "text", Required: true, HelpText: "host:port of the gRPC server"},
+					{Key: "tls", Label: "Use TLS", Type: "boolean", Required: false, Default: false},
+					{Key: "method", Label: "Method", Type: "text", Required: true, HelpText: "Full method name, e.g. pkg.Service/Method"},
+					{Key: "arguments", Label: "Request", Type: "json", Required: false, HelpText: "Request message as JSON"},
+					{Key: "output_path", Label: "Output Path", Type: "text", Required: false, HelpText: "Target case.data path"},
+				},
+			},
+		},
+	})
 	webhookHandler := webhookreceiver.NewHandler(db, secretStore)
 	webhookHandler.SetEvaluator(eng)
 	wsHub := notify.NewHub(db, notify.DefaultTokenValidator(func(ctx context.Context, token string) (uuid.UUID, uuid.UUID, error) {
@@ -290,6 +461,7 @@ func NewRouterWithServicesContext(bgCtx context.Context, db *sql.DB, eng *engine
 	}))
 	notifySvc := notify.NewService(db, wsHub)
 	health := handlers.NewHealthHandlers(db, eng, wsHub)
+	health.ConnectorCache = connectorCache
 	activitySvc := activity.NewService(db, wsHub)
 	auditSvc.OnCommitted(activitySvc.OnAuditEvent)
 	activityHandlers := handlers.NewActivityHandlers(activitySvc)
@@ -299,9 +471,53 @@ func NewRouterWithServicesContext(bgCtx context.Context, db *sql.DB, eng *engine
 	caseSvc.SetAuditService(auditSvc)
 	taskHandlers := handlers.NewTaskHandlers(taskSvc)
 	mcpCache := mcp.NewToolCache(db, 24*time.Hour)
+	mcpCache.SetRetiredRetention(parseDurationOrDefault(os.Getenv("ACERYX_MCP_RETIRED_TOOL_RETENTION"), 0))
 	mcpManager := mcp.NewManager(mcpCache, secretStore, splitAndTrim(os.Getenv("ACERYX_MCP_SELF_URLS")), &http.Client{Timeout: 60 * time.Second})
+	mcpManager.SetEventBus(eventBus)
 	mcpAPI := mcp.NewAPI(mcpManager, mcpCache)
+	workflowService.SetToolVersionCatalog(mcpCache)
 	mcpHandlers := handlers.NewMCPHandlers(mcpAPI)
+	toolPool := toolpool.New(intFromEnv("ACERYX_TOOL_POOL_MAX_SIZE", toolpool.DefaultMaxSize), map[string]time.Duration{
+		grpcclient.ProtocolName: parseDurationOrDefault(os.Getenv("ACERYX_TOOL_POOL_GRPC_LIFETIME"), toolpool.DefaultLifetime),
+	})
+	grpcProtocol := grpcclient.NewGrpcProtocolWithEgress(egressPolicy)
+	grpcProtocol.SetPool(toolPool)
+	customToolStore := customtools.NewStore(db)
+	customToolProtocol := customtools.NewCustomHTTPProtocol(customToolStore, secretStore, egressPolicy)
+	customToolHandlers := handlers.NewCustomToolHandlers(customToolStore, customToolProtocol)
+	customToolProtocol.SetMockProtocol(mockProtocol)
+	mockProfileHandlers := handlers.NewMockProfileHandlers(mockProfileStore)
+	toolRegistryHandlers := handlers.NewToolRegistryHandlers(mcpAPI, customToolProtocol, health)
+	workflowService.SetToolDescriptionCatalogs(customToolProtocol, mcpCache)
+	requiredProtocols := map[string]bool{}
+	for _, name := range splitAndTrim(os.Getenv("ACERYX_REQUIRED_PROTOCOLS")) {
+		requiredProtocols[strings.ToLower(name)] = true
+	}
+	health.AddProtocolCheck("mcp", requiredProtocols["mcp"], func(context.Context) error {
+		if open := mcpManager.OpenCircuits(); len(open) > 0 {
+			return fmt.Errorf("circuit open for %s", strings.Join(open, ", "))
+		}
+		return nil
+	})
+	health.AddProtocolCheck("connectors", requiredProtocols["connectors"], func(context.Context) error {
+		if open := connectorCircuits.Open(); len(open) > 0 {
+			return fmt.Errorf("circuit open for %s", strings.Join(open, ", "))
+		}
+		return nil
+	})
+	health.AddProtocolCheck("triggers", requiredProtocols["triggers"], func(context.Context) error {
+		failed := 0
+		for _, item := range triggerManager.List() {
+			if item.Status == triggers.TriggerError {
+				failed++
+			}
+		}
+		if failed > 0 {
+			return fmt.Errorf("%d trigger(s) in error state", failed)
+		}
+		return nil
+	})
+	grpcHandlers := handlers.NewGrpcHandlers(grpcProtocol)
 	agenticTraceStore := agentic.NewPostgresTraceStore(db)
 	agenticAPI := agentic.NewAPI(db, agenticTraceStore)
 	agenticHandlers := handlers.NewAgenticHandlers(agenticAPI)
@@ -332,17 +548,110 @@ func NewRouterWithServicesContext(bgCtx context.Context, db *sql.DB, eng *engine
 	mcpServerAdminHandlers := handlers.NewMCPServerAdminHandlers(mcpKeyAPI)
 	promptTemplateSvc := agents.NewPromptTemplateService(db)
 	promptTemplateHandlers := handlers.NewPromptTemplateHandlers(promptTemplateSvc)
+	featureConfig, err := features.ConfigFromEnv()
+	if err != nil {
+		// aceryx serve rejects this at startup; other callers get the defaults.
+		slog.Error("feature flag configuration ignored", "error", err)
+	}
+	featureSvc := features.NewService(features.NewPostgresStore(db), featureConfig)
+	featureHandlers := handlers.NewFeatureHandlers(featureSvc)
+	quotaSvc := quotas.NewService(quotas.NewPostgresStore(db))
+	caseSvc.SetQuotaGate(quotaSvc)
+	usageHandlers := handlers.NewUsageHandlers(quotaSvc)
+	searchSvc := search.NewService()
+	searchSvc.Register(search.KindWorkflow, search.WorkflowSource(db))
+	searchSvc.Register(search.KindConnector, func(context.Context, uuid.UUID) ([]search.Document, error) {
+		return connectorRegistry.SearchDocuments(), nil
+	})
+	searchSvc.Register(search.KindPlugin, func(context.Context, uuid.UUID) ([]search.Document, error) {
+		var docs []search.Document
+		for _, p := range pluginRuntime.List() {
+			if p.IsLatest {
+				docs = append(docs, p.SearchDocument())
+			}
+		}
+		return docs, nil
+	})
+	searchSvc.Register(search.KindMCPTool, func(ctx context.Context, tenantID uuid.UUID) ([]search.Document, error) {
+		servers, err := mcpCache.ListServers(ctx, tenantID)
+		if err != nil {
+			return nil, err
+		}
+		var docs []search.Document
+		for _, server := range servers {
+			for _, tool := range server.Tools {
+				docs = append(docs, search.Document{ID: server.ServerURL + "#" + tool.Name, Name: tool.Name, Tags: []string{server.ServerURL}, Description: tool.Description})
+			}
+		}
+		return docs, nil
+	})
+	searchHandlers := handlers.NewSearchHandlers(searchSvc)
+	paletteSvc := designer.NewService()
+	paletteSvc.Register("steps", func(context.Context, uuid.UUID) ([]designer.Item, error) {
+		return designer.BuiltinItems(), nil
+	})
+	paletteSvc.Register("connectors", func(context.Context, uuid.UUID) ([]designer.Item, error) {
+		return designer.ConnectorItems(connectorRegistry.Describe()), nil
+	})
+	paletteSvc.Register("ai_components", func(ctx context.Context, tenantID uuid.UUID) ([]designer.Item, error) {
+		components, err := aiComponentRegistry.List(ctx, tenantID)
+		if err != nil {
+			return nil, err
+		}
+		return designer.AIComponentItems(components), nil
+	})
+	paletteSvc.Register("plugins", func(context.Context, uuid.UUID) ([]designer.Item, error) {
+		return designer.PluginItems(pluginRuntime.List()), nil
+	})
+	paletteSvc.Register("mcp_tools", func(ctx context.Context, tenantID uuid.UUID) ([]designer.Item, error) {
+		servers, err := mcpCache.ListServers(ctx, tenantID)
+		if err != nil {
+			return nil, err
+		}
+		return designer.MCPToolItems(servers), nil
+	})
+	paletteSvc.Register("sub_workflows", func(ctx context.Context, tenantID uuid.UUID) ([]designer.Item, error) {
+		published, err := workflowService.PublishedWorkflows(ctx, tenantID)
+		if err != nil {
+			return nil, err
+		}
+		return designer.SubWorkflowItems(published), nil
+	})
+	designerHandlers := handlers.NewDesignerHandlers(paletteSvc)
 	if eng != nil {
+		eng.SetOwner(clusterNode.InstanceID(), clusterNode.LeaseTTL())
+		eng.SetFeatureGate(featureSvc)
+		eng.SetUsageGate(quotaSvc)
+		eng.SetRedactor(redactor)
+		if artifactSvc != nil {
+			eng.SetArtifactStore(artifactSvc)
+		}
+		eng.AddStepEventHook(quotaSvc.OnStepEvent)
+		eng.AddStepEventHook(workflowService.OnStepEvent)
 		eng.RegisterExecutor("human_task", tasks.NewHumanTaskExecutor(taskSvc))
-		eng.RegisterExecutor("integration", connectors.NewExecutor(db, connectorRegistry, secretStore))
-		eng.RegisterExecutor("mcp-client", mcp.NewStepExecutor(db, mcpManager))
+		eng.RegisterExecutor("integration", integrationExecutor)
+		mcpStepExecutor := mcp.NewStepExecutor(db, mcpManager)
+		mcpStepExecutor.SetMockProtocol(mockProtocol)
+		eng.RegisterExecutor("mcp-client", mcpStepExecutor)
+		grpcStepExecutor := grpcclient.NewStepExecutor(db, grpcProtocol)
+		grpcStepExecutor.SetMockProtocol(mockProtocol)
+		eng.RegisterExecutor("grpc-client", grpcStepExecutor)
+		eng.RegisterExecutor("custom-tool", customtools.NewStepExecutor(db, customToolProtocol))
+		scriptRunner := script.NewRunner(script.Limits{
+			Timeout:       parseDurationOrDefault(os.Getenv("ACERYX_SCRIPT_MAX_TIMEOUT"), script.DefaultLimits().Timeout),
+			MaxAllocBytes: uint64(intFromEnv("ACERYX_SCRIPT_MAX_ALLOC_MB", 64)) << 20,
+		})
+		if len(opts.ScriptWorker) > 0 {
+			scriptRunner.SetWorker(opts.ScriptWorker...)
+		}
+		eng.RegisterExecutor("script", script.NewStepExecutor(db, scriptRunner))
 		eng.RegisterExecutor("agent", agents.NewAgentExecutor(agents.ExecutorConfig{
 			DB:           db,
 			TaskCreator:  taskSvc,
 			LLMClient:    agents.NewLLMClientFromEnv(120 * time.Second),
 			AuditService: auditSvc,
 		}))
-		eng.RegisterExecutor("agentic", agentic.NewStepExecutor(
+		agenticExecutor := agentic.NewStepExecutor(
 			db,
 			agentic.NewRunner(),
 			agenticTraceStore,
@@ -351,7 +660,10 @@ func NewRouterWithServicesContext(bgCtx context.Context, db *sql.DB, eng *engine
 			pluginRuntime,
 			mcpManager,
 			ragSearch,
-		))
+		)
+		agenticExecutor.SetToolAccess(agentToolAccessFromEnv())
+		configReloader.OnReload(func() { agenticExecutor.SetToolAccess(agentToolAccessFromEnv()) })
+		eng.RegisterExecutor("agentic", agenticExecutor)
 		eng.RegisterExecutor("ai_component", ai.NewStepExecutor(db, ai.NewComponentExecutor(
 			llmManager,
 			ai.NewPostgresCaseStore(db),
@@ -360,6 +672,18 @@ func NewRouterWithServicesContext(bgCtx context.Context, db *sql.DB, eng *engine
 		)))
 		eng.RegisterExecutor("extraction", extraction.NewStepExecutor(db, taskSvc))
 		eng.RegisterExecutor("plugin", plugins.NewStepExecutor(db, pluginRuntime))
+		eng.RegisterExecutor("aggregate", engine.NewAggregateExecutor(db))
+		timerExecutor := engine.NewTimerExecutor(db)
+		eng.RegisterExecutor("timer", timerExecutor)
+		eng.RegisterExecutor("delay", timerExecutor)
+		eng.RegisterExecutor("wait_until", timerExecutor)
+		subWorkflowExecutor := subworkflows.NewStepExecutor(db, caseSvc, eng, intFromEnv("ACERYX_SUB_WORKFLOW_MAX_DEPTH", subworkflows.DefaultMaxDepth))
+		eng.RegisterExecutor("sub_workflow", subWorkflowExecutor)
+		eng.AddSettledHook(subWorkflowExecutor.OnCaseSettled)
+		eng.AddFailureHook(deadLetterStore.OnStepFailed)
+		eng.AddFailureHook(eventBus.OnStepFailed)
+		eng.AddSettledHook(eventBus.OnCaseSettled)
+		eng.AddStepEventHook(progressBroker.Publish)
 		eng.SetEscalationCallback(taskSvc.HandleOverdue)
 	}
 	tenantSvc := tenants.NewTenantService(db)
@@ -369,10 +693,19 @@ func NewRouterWithServicesContext(bgCtx context.Context, db *sql.DB, eng *engine
 		bgCtx = context.Background()
 	}
 	if shouldStartBackgroundTickers() {
-		go vaultSvc.StartOrphanCleanupTicker(bgCtx)
-		go reportingSvc.StartViewRefreshTicker(bgCtx)
-		go reportingSvc.StartScheduleTicker(bgCtx)
+		// Singleton work runs only on the elected leader replica.
+		clusterNode.OnLeader("vault-orphan-cleanup", vaultSvc.StartOrphanCleanupTicker)
+		clusterNode.OnLeader("report-view-refresh", reportingSvc.StartViewRefreshTicker)
+		clusterNode.OnLeader("report-schedules", reportingSvc.StartScheduleTicker)
+		clusterNode.OnLeader("workflow-sla-monitor", workflowService.StartSLAMonitor)
+		if eng != nil {
+			clusterNode.OnLeader("step-recovery", eng.StartRecoveryMonitor)
+		}
+		go clusterNode.Run(bgCtx)
+		go eventBus.Run(bgCtx)
 		go ragWorker.Start(bgCtx)
+		go toolPool.Run(bgCtx)
+		go configReloader.Watch(bgCtx, parseDurationOrDefault(os.Getenv("ACERYX_CONFIG_WATCH_INTERVAL"), 10*time.Second))
 		go func() {
 			if err := channelManager.StartAll(bgCtx); err != nil {
 				_ = err
@@ -387,22 +720,33 @@ func NewRouterWithServicesContext(bgCtx context.Context, db *sql.DB, eng *engine
 
 	authMW := middleware.AuthMiddleware(authSvc)
 	withAuth := func(h http.HandlerFunc) http.Handler {
-		return authMW(http.HandlerFunc(h))
+		return authMW(middleware.DenyScopedTokens(http.HandlerFunc(h)))
 	}
 	withPerm := func(permission string, h http.HandlerFunc) http.Handler {
 		return authMW(middleware.RequirePermission(authzSvc, authSvc, permission)(http.HandlerFunc(h)))
 	}
+	// guarded puts an execute surface behind the per-principal execution
+	// guard shared with connector test calls.
+	guarded := func(target string, h http.HandlerFunc) http.HandlerFunc {
+		return handlers.GuardExecution(executeGuard, target, h).ServeHTTP
+	}
 
 	mux.HandleFunc("GET /health", health.Health)
+	mux.HandleFunc("GET /health/live", health.Liveness)
+	mux.HandleFunc("GET /health/ready", health.Readiness)
 	mux.HandleFunc("GET /healthz", health.Liveness)
 	mux.HandleFunc("GET /readyz", health.Readiness)
 	mux.Handle("GET /metrics", health.Metrics())
 
 	mux.HandleFunc("POST /auth/login", authHandlers.Login)
+	mux.HandleFunc("GET /auth/session", authHandlers.Session)
 	mux.Handle("POST /auth/logout", withAuth(authHandlers.Logout))
 	mux.Handle("POST /auth/password", withAuth(authHandlers.ChangePassword))
 	mux.Handle("GET /auth/preferences", withAuth(authHandlers.GetPreferences))
 	mux.Handle("PUT /auth/preferences", withAuth(authHandlers.PutPreferences))
+	mux.Handle("GET /api/v1/preferences", withAuth(authHandlers.GetDisplayPreferences))
+	mux.Handle("PUT /api/v1/preferences", withAuth(authHandlers.PutDisplayPreferences))
+	mux.Handle("PUT /api/v1/preferences/workspace", withPerm("admin:tenant", authHandlers.PutWorkspacePreferences))
 
 	mux.HandleFunc("GET /tenant/branding", tenantHandlers.GetBranding)
 	mux.Handle("PUT /tenant/branding", withPerm("admin:tenant", tenantHandlers.PutBranding))
@@ -422,24 +766,84 @@ func NewRouterWithServicesContext(bgCtx context.Context, db *sql.DB, eng *engine
 	mux.Handle("GET /admin/roles", withPerm("admin:roles", authHandlers.ListRoles))
 	mux.Handle("PUT /admin/roles/{id}/permissions", withPerm("admin:roles", authHandlers.UpdateRolePermissions))
 
+	mux.Handle("POST /tokens", withPerm("admin:tokens", authHandlers.CreateServiceToken))
+	mux.Handle("GET /tokens", withPerm("admin:tokens", authHandlers.ListServiceTokens))
+	mux.Handle("DELETE /tokens/{id}", withPerm("admin:tokens", authHandlers.RevokeServiceToken))
+
 	mux.Handle("POST /case-types", withPerm("cases:create", caseHandlers.RegisterCaseType))
 	mux.Handle("GET /case-types", withPerm("cases:read", caseHandlers.ListCaseTypes))
 	mux.Handle("GET /case-types/{id}", withPerm("cases:read", caseHandlers.GetCaseType))
 
-	mux.Handle("POST /cases", withPerm("cases:create", caseHandlers.CreateCase))
+	// Execute shares let principals without cases:create start cases of one
+	// workflow, so CreateCase checks permissions and token scopes itself.
+	mux.Handle("POST /cases", authMW(guarded("cases.create", caseHandlers.CreateCase)))
 	mux.Handle("GET /workflows", withPerm("workflows:view", workflowHandlers.List))
 	mux.Handle("POST /workflows", withPerm("workflows:edit", workflowHandlers.Create))
-	mux.Handle("GET /workflows/{id}/versions/draft", withPerm("workflows:view", workflowHandlers.GetDraft))
-	mux.Handle("PUT /workflows/{id}/versions/draft", withPerm("workflows:edit", workflowHandlers.PutDraft))
+	// Per-workflow routes reachable through ownership or shares check
+	// permissions in the handler instead of requiring a tenant-wide role.
+	mux.Handle("GET /workflows/{id}", withAuth(workflowHandlers.Get))
+	mux.Handle("DELETE /workflows/{id}", withPerm("workflows:edit", workflowHandlers.Delete))
+	mux.Handle("PUT /workflows/{id}/tags", withAuth(workflowHandlers.SetTags))
+	mux.Handle("GET /workflows/{id}/shares", withAuth(workflowHandlers.ListShares))
+	mux.Handle("PUT /workflows/{id}/shares", withAuth(workflowHandlers.PutShare))
+	mux.Handle("DELETE /workflows/{id}/shares/{grantee_type}/{grantee_id}", withAuth(workflowHandlers.DeleteShare))
+	mux.Handle("POST /workflows/{id}/transfer", withAuth(workflowHandlers.TransferOwnership))
+	mux.Handle("GET /api/v1/tags", withPerm("workflows:view", tagHandlers.List))
+	mux.Handle("POST /api/v1/tags/merge", withPerm("workflows:edit", tagHandlers.Merge))
+	mux.Handle("POST /api/v1/tags/prune", withPerm("workflows:edit", tagHandlers.Prune))
+	mux.Handle("POST /api/v1/tags/{name}/rename", withPerm("workflows:edit", tagHandlers.Rename))
+	mux.Handle("DELETE /api/v1/tags/{name}", withPerm("workflows:edit", tagHandlers.Delete))
+	mux.Handle("GET /workflows/{id}/versions/draft", withAuth(workflowHandlers.GetDraft))
+	mux.Handle("PUT /workflows/{id}/versions/draft", withAuth(workflowHandlers.PutDraft))
+	mux.Handle("DELETE /workflows/{id}/versions/draft", withAuth(workflowHandlers.DiscardDraft))
+	mux.Handle("PUT /workflows/{id}/versions/draft/graph", withAuth(workflowHandlers.PutDraftGraph))
+	mux.Handle("GET /workflows/{id}/versions/draft/status", withAuth(workflowHandlers.GetDraftStatus))
+	mux.Handle("GET /workflows/{id}/versions/draft/history", withAuth(workflowHandlers.GetDraftHistory))
+	mux.Handle("POST /workflows/{id}/versions/draft/undo", withAuth(workflowHandlers.UndoDraft))
+	mux.Handle("POST /workflows/{id}/versions/draft/redo", withAuth(workflowHandlers.RedoDraft))
 	mux.Handle("POST /workflows/{id}/publish", withPerm("workflows:deploy", workflowHandlers.Publish))
+	mux.Handle("GET /workflows/{id}/review", withAuth(workflowHandlers.GetReview))
+	mux.Handle("POST /workflows/{id}/review", withPerm("workflows:edit", workflowHandlers.SubmitReview))
+	mux.Handle("DELETE /workflows/{id}/review", withPerm("workflows:edit", workflowHandlers.WithdrawReview))
+	mux.Handle("POST /workflows/{id}/review/approve", withPerm("workflows:review", workflowHandlers.ApproveReview))
+	mux.Handle("POST /workflows/{id}/review/reject", withPerm("workflows:review", workflowHandlers.RejectReview))
+	mux.Handle("PUT /workflows/{id}/review-policy", withPerm("workflows:deploy", workflowHandlers.PutReviewPolicy))
+	mux.Handle("POST /workflows/{id}/archive", withPerm("workflows:deploy", workflowHandlers.Archive))
+	mux.Handle("POST /workflows/{id}/unarchive", withPerm("workflows:deploy", workflowHandlers.Unarchive))
+	mux.Handle("GET /workflows/{id}/canary", withPerm("workflows:view", workflowHandlers.GetCanary))
+	mux.Handle("POST /workflows/{id}/canary", withPerm("workflows:deploy", workflowHandlers.StartCanary))
+	mux.Handle("POST /workflows/{id}/canary/promote", withPerm("workflows:deploy", workflowHandlers.PromoteCanary))
+	mux.Handle("POST /workflows/{id}/canary/rollback", withPerm("workflows:deploy", workflowHandlers.RollbackCanary))
+	mux.Handle("GET /workflows/{id}/sla", withPerm("workflows:view", workflowHandlers.GetSLA))
+	mux.Handle("PUT /workflows/{id}/sla", withPerm("workflows:deploy", workflowHandlers.PutSLA))
+	mux.Handle("DELETE /workflows/{id}/sla", withPerm("workflows:deploy", workflowHandlers.DeleteSLA))
+	mux.Handle("GET /workflows/{id}/sla/breaches", withPerm("workflows:view", workflowHandlers.ListSLABreaches))
+	mux.Handle("POST /workflows/{id}/dry-run", withPerm("workflows:view", guarded("workflows.dry_run", workflowHandlers.DryRun)))
+	mux.Handle("POST /workflows/{id}/promote", withPerm("workflows:deploy", workflowHandlers.Promote))
+	mux.Handle("POST /workflows/{id}/steps/{step_id}/test", withPerm("workflows:edit", guarded("workflows.test_step", workflowHandlers.TestStep)))
+	mux.Handle("GET /workflows/{id}/docs", withAuth(workflowHandlers.Docs))
+	mux.Handle("GET /workflows/{id}/diagram", withAuth(workflowHandlers.Diagram))
+	mux.Handle("GET /workflows/{id}/dependencies", withAuth(workflowHandlers.Dependencies))
+	mux.Handle("GET /workflows/{id}/costs", withAuth(workflowHandlers.Costs))
 	mux.Handle("GET /workflows/{id}/yaml/latest", withPerm("workflows:view", workflowHandlers.ExportYAMLLatest))
 	mux.Handle("GET /workflows/{id}/yaml/{version}", withPerm("workflows:view", workflowHandlers.ExportYAMLVersion))
 	mux.Handle("PUT /workflows/{id}/yaml/draft", withPerm("workflows:edit", workflowHandlers.ImportYAMLDraft))
+	mux.Handle("GET /environments/{name}/variables", withPerm("workflows:view", workflowHandlers.GetEnvironmentVariables))
+	mux.Handle("PUT /environments/{name}/variables", withPerm("workflows:deploy", workflowHandlers.PutEnvironmentVariables))
 	mux.Handle("GET /cases/{id}", withPerm("cases:read", caseHandlers.GetCase))
+	mux.Handle("GET /api/v1/executions/{id}", withPerm("cases:read", caseHandlers.GetExecution))
+	mux.Handle("GET /api/v1/executions/{id}/events", withPerm("cases:read", caseHandlers.StreamExecutionEvents))
+	mux.Handle("POST /api/v1/executions/{id}/replay", withPerm("cases:create", guarded("executions.replay", caseHandlers.ReplayExecution)))
+	mux.Handle("POST /api/v1/executions/{id}/cancel", withPerm("cases:close", caseHandlers.CancelExecution))
+	mux.Handle("GET /api/v1/executions/{id}/artifacts", withPerm("cases:read", artifactHandlers.List))
+	mux.Handle("GET /api/v1/executions/{id}/artifacts/{artifact_id}", withPerm("cases:read", artifactHandlers.Download))
+	mux.Handle("GET /executions/dead-letter", withPerm("cases:read", deadLetterHandlers.List))
+	mux.Handle("POST /executions/dead-letter/{id}/requeue", withPerm("cases:update", deadLetterHandlers.Requeue))
 	mux.Handle("GET /cases", withPerm("cases:read", caseHandlers.ListCases))
 	mux.Handle("PATCH /cases/{id}/data", withPerm("cases:update", caseHandlers.PatchCaseData))
 	mux.Handle("POST /cases/{id}/close", withPerm("cases:close", caseHandlers.CloseCase))
 	mux.Handle("POST /cases/{id}/cancel", withPerm("cases:close", caseHandlers.CancelCase))
+	mux.Handle("POST /cases/{id}/resume", withPerm("cases:update", caseHandlers.ResumeCase))
 	mux.Handle("GET /cases/{id}/events", withPerm("cases:read", auditHandlers.ListCaseEvents))
 	mux.Handle("GET /activity", withAuth(activityHandlers.Feed))
 	mux.Handle("POST /cases/{id}/events/verify", withPerm("admin:audit", auditHandlers.VerifyCaseEvents))
@@ -505,6 +909,20 @@ func NewRouterWithServicesContext(bgCtx context.Context, db *sql.DB, eng *engine
 	mux.Handle("POST /v1/admin/plugins/{id}/disable", withPerm("admin:tenant", pluginHandlers.Disable))
 	mux.Handle("POST /admin/plugins/{id}/enable", withPerm("admin:tenant", pluginHandlers.Enable))
 	mux.Handle("POST /v1/admin/plugins/{id}/enable", withPerm("admin:tenant", pluginHandlers.Enable))
+	mux.Handle("GET /api/v1/search", withPerm("workflows:view", searchHandlers.Search))
+	mux.Handle("GET /api/v1/designer/palette", withPerm("workflows:view", designerHandlers.GetPalette))
+	mux.Handle("GET /api/v1/system/features", withAuth(featureHandlers.List))
+	mux.Handle("PUT /api/v1/system/features/{key}", withPerm("admin:tenant", featureHandlers.Update))
+	mux.Handle("GET /api/v1/usage", withAuth(usageHandlers.Get))
+	mux.Handle("GET /api/v1/usage/quotas", withPerm("admin:tenant", usageHandlers.ListQuotas))
+	mux.Handle("PUT /api/v1/usage/quotas", withPerm("admin:tenant", usageHandlers.PutQuota))
+	mux.Handle("POST /api/v1/system/reload", withPerm("admin:tenant", systemHandlers.Reload))
+	mux.Handle("POST /api/v1/event-subscriptions", withPerm("admin:tenant", eventHandlers.CreateSubscription))
+	mux.Handle("GET /api/v1/event-subscriptions", withPerm("admin:tenant", eventHandlers.ListSubscriptions))
+	mux.Handle("DELETE /api/v1/event-subscriptions/{id}", withPerm("admin:tenant", eventHandlers.DeleteSubscription))
+	mux.Handle("GET /api/v1/event-subscriptions/{id}/deliveries", withPerm("admin:tenant", eventHandlers.ListDeliveries))
+	mux.Handle("GET /api/v1/system/cluster", withPerm("admin:tenant", systemHandlers.ClusterStatus))
+	mux.Handle("GET /api/v1/system/runtime", withPerm("admin:tenant", systemHandlers.Runtime))
 	mux.Handle("GET /api/v1/admin/triggers", withPerm("admin:tenant", triggerHandlers.List))
 	mux.Handle("GET /v1/channels", withPerm("channels:manage", channelAPI.List))
 	mux.Handle("GET /api/v1/channels", withPerm("channels:manage", channelAPI.List))
@@ -522,6 +940,18 @@ func NewRouterWithServicesContext(bgCtx context.Context, db *sql.DB, eng *engine
 	mux.Handle("POST /api/v1/channels/{id}/disable", withPerm("channels:manage", channelAPI.Disable))
 	mux.Handle("GET /v1/channels/{id}/events", withPerm("channels:manage", channelAPI.Events))
 	mux.Handle("GET /api/v1/channels/{id}/events", withPerm("channels:manage", channelAPI.Events))
+	mux.Handle("GET /v1/channels/{id}/schedule", withPerm("channels:manage", channelAPI.Schedule))
+	mux.Handle("GET /api/v1/channels/{id}/schedule", withPerm("channels:manage", channelAPI.Schedule))
+	mux.Handle("GET /v1/schedule-calendars", withPerm("channels:manage", channelAPI.ListCalendars))
+	mux.Handle("GET /api/v1/schedule-calendars", withPerm("channels:manage", channelAPI.ListCalendars))
+	mux.Handle("POST /v1/schedule-calendars", withPerm("channels:manage", channelAPI.CreateCalendar))
+	mux.Handle("POST /api/v1/schedule-calendars", withPerm("channels:manage", channelAPI.CreateCalendar))
+	mux.Handle("GET /v1/schedule-calendars/{id}", withPerm("channels:manage", channelAPI.GetCalendar))
+	mux.Handle("GET /api/v1/schedule-calendars/{id}", withPerm("channels:manage", channelAPI.GetCalendar))
+	mux.Handle("PUT /v1/schedule-calendars/{id}", withPerm("channels:manage", channelAPI.UpdateCalendar))
+	mux.Handle("PUT /api/v1/schedule-calendars/{id}", withPerm("channels:manage", channelAPI.UpdateCalendar))
+	mux.Handle("DELETE /v1/schedule-calendars/{id}", withPerm("channels:manage", channelAPI.DeleteCalendar))
+	mux.Handle("DELETE /api/v1/schedule-calendars/{id}", withPerm("channels:manage", channelAPI.DeleteCalendar))
 	mux.Handle("POST /v1/channels/webhook/{channel_id}/receive", http.HandlerFunc(channelWebhook.ServeHTTP))
 	mux.Handle("POST /api/v1/channels/webhook/{channel_id}/receive", http.HandlerFunc(channelWebhook.ServeHTTP))
 	mux.Handle("GET /intake/{channel_id}", http.HandlerFunc(channelForm.ServeForm))
@@ -536,6 +966,26 @@ func NewRouterWithServicesContext(bgCtx context.Context, db *sql.DB, eng *engine
 	mux.Handle("DELETE /api/v1/mcp-servers", withPerm("admin:tenant", mcpHandlers.Delete))
 	mux.Handle("DELETE /api/v1/mcp-servers/{url}", withPerm("admin:tenant", mcpHandlers.Delete))
 	mux.Handle("POST /api/v1/mcp-servers/refresh", withPerm("admin:tenant", mcpHandlers.Refresh))
+	mux.Handle("GET /api/v1/mcp-servers/tool-versions", withPerm("admin:tenant", mcpHandlers.ToolVersions))
+	mux.Handle("GET /api/v1/tools/registry", withPerm("admin:tenant", toolRegistryHandlers.List))
+	mux.Handle("POST /api/v1/tools/registry/{protocol}/refresh", withPerm("admin:tenant", toolRegistryHandlers.Refresh))
+	mux.Handle("POST /api/v1/tools/custom", withPerm("admin:tenant", customToolHandlers.Create))
+	mux.Handle("GET /api/v1/tools/custom", withPerm("admin:tenant", customToolHandlers.List))
+	mux.Handle("GET /api/v1/tools/custom/{name}", withPerm("admin:tenant", customToolHandlers.Get))
+	mux.Handle("DELETE /api/v1/tools/custom/{name}", withPerm("admin:tenant", customToolHandlers.Delete))
+	mux.Handle("POST /api/v1/tools/custom/{name}/invoke", withPerm("admin:tenant", customToolHandlers.Invoke))
+	mux.Handle("GET /api/v1/tools/custom/{name}/form-schema", withPerm("admin:tenant", customToolHandlers.FormSchema))
+	mux.Handle("POST /api/v1/tools/custom/{name}/playground", withPerm("admin:tenant", guarded("tools.playground", customToolHandlers.Playground)))
+	mux.Handle("GET /api/v1/tools/custom/{name}/examples", withPerm("admin:tenant", customToolHandlers.ListExamples))
+	mux.Handle("POST /api/v1/tools/custom/{name}/examples", withPerm("admin:tenant", customToolHandlers.CreateExample))
+	mux.Handle("DELETE /api/v1/tools/custom/{name}/examples/{id}", withPerm("admin:tenant", customToolHandlers.DeleteExample))
+	mux.Handle("POST /api/v1/tools/custom/{name}/examples/{id}/run", withPerm("admin:tenant", customToolHandlers.RunExample))
+	mux.Handle("POST /api/v1/tools/mock-profiles", withPerm("admin:tenant", mockProfileHandlers.Create))
+	mux.Handle("GET /api/v1/tools/mock-profiles", withPerm("admin:tenant", mockProfileHandlers.List))
+	mux.Handle("GET /api/v1/tools/mock-profiles/{name}", withPerm("admin:tenant", mockProfileHandlers.Get))
+	mux.Handle("PUT /api/v1/tools/mock-profiles/{name}", withPerm("admin:tenant", mockProfileHandlers.Update))
+	mux.Handle("DELETE /api/v1/tools/mock-profiles/{name}", withPerm("admin:tenant", mockProfileHandlers.Delete))
+	mux.Handle("POST /api/v1/grpc-servers/discover", withPerm("admin:tenant", grpcHandlers.Discover))
 	mux.Handle("GET /api/v1/agentic-traces", withPerm("cases:read", agenticHandlers.ListTraces))
 	mux.Handle("GET /api/v1/agentic-traces/{id}", withPerm("cases:read", agenticHandlers.GetTrace))
 	mux.Handle("GET /api/v1/agentic-traces/{id}/events", withPerm("cases:read", agenticHandlers.ListEvents))
@@ -556,6 +1006,8 @@ func NewRouterWithServicesContext(bgCtx context.Context, db *sql.DB, eng *engine
 	mux.HandleFunc("GET /vault/signed/{doc_id}", vaultHandlers.SignedDownload)
 	mux.Handle("GET /connectors", withAuth(connectorHandlers.List))
 	mux.Handle("POST /connectors/{key}/actions/{action}/test", withPerm("workflows:edit", connectorHandlers.TestAction))
+	mux.Handle("GET /connectors/{key}/stats", withPerm("workflows:view", connectorHandlers.Stats))
+	mux.Handle("GET /admin/execution-abuse", withPerm("admin:tenant", connectorHandlers.AbuseReports))
 	mux.Handle("GET /admin/llm-providers", withPerm("admin:tenant", llmHandlers.ListProviders))
 	mux.Handle("GET /v1/admin/llm-providers", withPerm("admin:tenant", llmHandlers.ListProviders))
 	mux.Handle("POST /admin/llm-providers", withPerm("admin:tenant", llmHandlers.CreateProvider))
@@ -630,6 +1082,63 @@ func parseDurationOrDefault(raw string, fallback time.Duration) time.Duration {
 	return d
 }
 
+func executeGuardConfigFromEnv() connectors.ExecutionGuardConfig {
+	return connectors.ExecutionGuardConfig{
+		RequestsPerMinute: intFromEnv("ACERYX_EXECUTE_RPM", connectors.DefaultExecuteRequestsPerMinute),
+		Burst:             intFromEnv("ACERYX_EXECUTE_BURST", connectors.DefaultExecuteBurst),
+		MaxFailures:       intFromEnv("ACERYX_EXECUTE_MAX_FAILURES", connectors.DefaultExecuteMaxFailures),
+	}
+}
+
+// agentToolAccessFromEnv reads ACERYX_AGENT_TOOLS_ALLOW and
+// ACERYX_AGENT_TOOLS_DENY. A list that does not parse denies every tool, so a
+// typo cannot silently re-enable tools production meant to block.
+func agentToolAccessFromEnv() agentic.ToolAccessPolicy {
+	allow, allowErr := agentic.ParseToolAccessRules(os.Getenv("ACERYX_AGENT_TOOLS_ALLOW"))
+	deny, denyErr := agentic.ParseToolAccessRules(os.Getenv("ACERYX_AGENT_TOOLS_DENY"))
+	if err := errors.Join(allowErr, denyErr); err != nil {
+		slog.Error("invalid agent tool access rules; denying all agent tools", "error", err)
+		return agentic.ToolAccessPolicy{Deny: []agentic.ToolAccessRule{
+			{Source: agentic.ToolSourceConnector},
+			{Source: agentic.ToolSourceMCP},
+			{Source: agentic.ToolSourceRAG},
+			{Source: agentic.ToolSourceCaseData},
+		}}
+	}
+	return agentic.ToolAccessPolicy{Allow: allow, Deny: deny}
+}
+
+// chaosRulesFromEnv reads ACERYX_CHAOS_RULES, which are ignored unless
+// ACERYX_ENVIRONMENT names a development environment.
+func chaosRulesFromEnv() []chaos.Rule {
+	raw := strings.TrimSpace(os.Getenv("ACERYX_CHAOS_RULES"))
+	if raw == "" {
+		return nil
+	}
+	environment := os.Getenv("ACERYX_ENVIRONMENT")
+	if !chaos.IsDevEnvironment(environment) {
+		slog.Error("chaos rules ignored outside a development environment", "environment", environment)
+		return nil
+	}
+	rules, err := chaos.ParseRules(raw)
+	if err != nil {
+		slog.Error("invalid chaos rules; injecting no tool failures", "error", err)
+		return nil
+	}
+	slog.Warn("chaos mode enabled; matching tool calls fail or slow down on purpose", "rules", len(rules), "environment", environment)
+	return rules
+}
+
+// egressPolicyFromEnv reads ACERYX_EGRESS_ALLOWED_DOMAINS and
+// ACERYX_EGRESS_ALLOW_PRIVATE for the http connector.
+func egressPolicyFromEnv() connectors.EgressPolicy {
+	allowPrivate := strings.TrimSpace(os.Getenv("ACERYX_EGRESS_ALLOW_PRIVATE"))
+	return connectors.EgressPolicy{
+		AllowPrivate:   strings.EqualFold(allowPrivate, "true") || allowPrivate == "1",
+		AllowedDomains: splitAndTrim(os.Getenv("ACERYX_EGRESS_ALLOWED_DOMAINS")),
+	}
+}
+
 func intFromEnv(key string, fallback int) int {
 	raw := strings.TrimSpace(os.Getenv(key))
 	if raw == "" {
@@ -680,6 +1189,7 @@ func shouldStartBackgroundTickers() bool {
 
 func chainMiddlewares(next http.Handler) http.Handler {
 	h := next
+	h = middleware.BodyLimitMiddleware(middleware.BodyLimitsFromEnv())(h)
 	h = middleware.MaintenanceModeMiddleware(h)
 	h = middleware.CorrelationMiddleware(h)
 	h = middleware.MetricsMiddleware(h)
@@ -0,0 +1,518 @@
+package main
+
+import (
+	"bytes"
+	"embed"
+	"flag"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"path/filepath"
+	"regexp"
+	"sort"
+	"strings"
+	"text/template"
+	"unicode"
+
+	"gopkg.in/yaml.v3"
+)
+
+//go:embed templates/client-*/*.tmpl
+var clientTemplateFS embed.FS
+
+var (
+	pathParamPattern  = regexp.MustCompile(`\{([^}]+)\}`)
+	nonIdentCharacter = regexp.MustCompile(`[^A-Za-z0-9]+`)
+)
+
+// openAPIDocument is the subset of an OpenAPI 3 document the client generator reads.
+// JSON documents parse through the YAML decoder unchanged.
+type openAPIDocument struct {
+	Info struct {
+		Title   string `yaml:"title"`
+		Version string `yaml:"version"`
+	} `yaml:"info"`
+	Paths      map[string]openAPIPathItem `yaml:"paths"`
+	Components struct {
+		Schemas map[string]openAPISchema `yaml:"schemas"`
+	} `yaml:"components"`
+}
+
+type openAPIPathItem struct {
+	Parameters []openAPIParameter `yaml:"parameters"`
+	Get        *openAPIOperation  `yaml:"get"`
+	Post       *openAPIOperation  `yaml:"post"`
+	Put        *openAPIOperation  `yaml:"put"`
+	Patch      *openAPIOperation  `yaml:"patch"`
+	Delete     *openAPIOperation  `yaml:"delete"`
+}
+
+type openAPIOperation struct {
+	OperationID string                     `yaml:"operationId"`
+	Summary     string                     `yaml:"summary"`
+	Parameters  []openAPIParameter         `yaml:"parameters"`
+	RequestBody *openAPIRequestBody        `yaml:"requestBody"`
+	Responses   map[string]openAPIResponse `yaml:"responses"`
+}
+
+type openAPIParameter struct {
+	Name     string        `yaml:"name"`
+	In       string        `yaml:"in"`
+	Required bool          `yaml:"required"`
+	Schema   openAPISchema `yaml:"schema"`
+}
+
+type openAPIRequestBody struct {
+	Required bool                        `yaml:"required"`
+	Content  map[string]openAPIMediaType `yaml:"content"`
+}
+
+type openAPIResponse struct {
+	Content map[string]openAPIMediaType `yaml:"content"`
+}
+
+type openAPIMediaType struct {
+	Schema openAPISchema `yaml:"schema"`
+}
+
+type openAPISchema struct {
+	Ref        string                   `yaml:"$ref"`
+	Type       string                   `yaml:"type"`
+	Items      *openAPISchema           `yaml:"items"`
+	Properties map[string]openAPISchema `yaml:"properties"`
+	Required   []string                 `yaml:"required"`
+	Enum       []any                    `yaml:"enum"`
+}
+
+type clientParam struct {
+	Name     string
+	Ident    string
+	Required bool
+	TSType   string
+	PyType   string
+}
+
+type clientOperation struct {
+	TSName string
+	// TSParams is the TypeScript method's parameter list.
+	TSParams     string
+	PyName       string
+	Method       string
+	Path         string
+	Summary      string
+	PathParams   []clientParam
+	QueryParams  []clientParam
+	HasBody      bool
+	BodyTSType   string
+	BodyPyType   string
+	ResultTSType string
+	ResultPyType string
+}
+
+type clientField struct {
+	Name     string
+	Required bool
+	TSType   string
+	PyType   string
+}
+
+type clientModel struct {
+	Name   string
+	Fields []clientField
+}
+
+type clientTemplateData struct {
+	Title         string
+	APIVersion    string
+	ClientVersion string
+	Models        []clientModel
+	Operations    []clientOperation
+}
+
+func runClient(args []string) error {
+	if len(args) == 0 {
+		return fmt.Errorf("usage: aceryx client generate --lang ts|python --out <dir> --spec <file>")
+	}
+	switch args[0] {
+	case "generate":
+		return runClientGenerate(args[1:], os.Stdout)
+	default:
+		return fmt.Errorf("unknown client subcommand: %s", args[0])
+	}
+}
+
+func runClientGenerate(args []string, out io.Writer) error {
+	fs := flag.NewFlagSet("client generate", flag.ContinueOnError)
+	lang := fs.String("lang", "ts", "client language (ts|python)")
+	outDir := fs.String("out", "", "output directory")
+	specPath := fs.String("spec", "", "path to an OpenAPI document (JSON or YAML)")
+	if err := fs.Parse(args); err != nil {
+		return err
+	}
+	if strings.TrimSpace(*outDir) == "" {
+		return fmt.Errorf("--out is required")
+	}
+	key, err := clientTemplateKey(*lang)
+	if err != nil {
+		return err
+	}
+	if strings.TrimSpace(*specPath) == "" {
+		return fmt.Errorf("--spec is required")
+	}
+
+	raw, err := os.ReadFile(*specPath)
+	if err != nil {
+		return fmt.Errorf("read --spec: %w", err)
+	}
+	var doc openAPIDocument
+	if err := yaml.Unmarshal(raw, &doc); err != nil {
+		return fmt.Errorf("parse OpenAPI document: %w", err)
+	}
+	if len(doc.Paths) == 0 {
+		return fmt.Errorf("OpenAPI document has no paths")
+	}
+	data := buildClientTemplateData(doc)
+
+	dir := "templates/" + key
+	entries, err := clientTemplateFS.ReadDir(dir)
+	if err != nil {
+		return fmt.Errorf("read client templates %s: %w", key, err)
+	}
+	if err := os.MkdirAll(*outDir, 0o755); err != nil {
+		return fmt.Errorf("create output directory: %w", err)
+	}
+	for _, entry := range entries {
+		templatePath := dir + "/" + entry.Name()
+		rawTemplate, err := clientTemplateFS.ReadFile(templatePath)
+		if err != nil {
+			return fmt.Errorf("read template %s: %w", templatePath, err)
+		}
+		parsed, err := template.New(entry.Name()).Parse(string(rawTemplate))
+		if err != nil {
+			return fmt.Errorf("parse template %s: %w", templatePath, err)
+		}
+		var rendered bytes.Buffer
+		if err := parsed.Execute(&rendered, data); err != nil {
+			return fmt.Errorf("execute template %s: %w", templatePath, err)
+		}
+		target := filepath.Join(*outDir, strings.TrimSuffix(entry.Name(), ".tmpl"))
+		if err := os.WriteFile(target, rendered.Bytes(), 0o644); err != nil {
+			return fmt.Errorf("write %s: %w", target, err)
+		}
+	}
+
+	_, _ = fmt.Fprintf(out, "generated %s client (%d operations, API %s) in %s\n", *lang, len(data.Operations), data.APIVersion, *outDir)
+	return nil
+}
+
+func clientTemplateKey(lang string) (string, error) {
+	switch strings.ToLower(strings.TrimSpace(lang)) {
+	case "ts", "typescript":
+		return "client-ts", nil
+	case "python", "py":
+		return "client-python", nil
+	default:
+		return "", fmt.Errorf("unsupported --lang %q (expected ts|python)", lang)
+	}
+}
+
+func buildClientTemplateData(doc openAPIDocument) clientTemplateData {
+	data := clientTemplateData{
+		Title:         firstNonEmptyString(doc.Info.Title, "Aceryx API"),
+		APIVersion:    firstNonEmptyString(doc.Info.Version, aceryxHostVersion),
+		ClientVersion: aceryxHostVersion,
+	}
+
+	modelNames := make([]string, 0, len(doc.Components.Schemas))
+	for name := range doc.Components.Schemas {
+		modelNames = append(modelNames, name)
+	}
+	sort.Strings(modelNames)
+	for _, name := range modelNames {
+		schema := doc.Components.Schemas[name]
+		model := clientModel{Name: typeIdent(name)}
+		required := stringSet(schema.Required)
+		fieldNames := make([]string, 0, len(schema.Properties))
+		for field := range schema.Properties {
+			fieldNames = append(fieldNames, field)
+		}
+		sort.Strings(fieldNames)
+		for _, field := range fieldNames {
+			prop := schema.Properties[field]
+			model.Fields = append(model.Fields, clientField{
+				Name:     field,
+				Required: required[field],
+				TSType:   tsType(prop),
+				PyType:   pyType(prop),
+			})
+		}
+		data.Models = append(data.Models, model)
+	}
+
+	paths := make([]string, 0, len(doc.Paths))
+	for path := range doc.Paths {
+		paths = append(paths, path)
+	}
+	sort.Strings(paths)
+	usedNames := map[string]int{}
+	for _, path := range paths {
+		item := doc.Paths[path]
+		for _, entry := range []struct {
+			method string
+			op     *openAPIOperation
+		}{
+			{http.MethodGet, item.Get},
+			{http.MethodPost, item.Post},
+			{http.MethodPut, item.Put},
+			{http.MethodPatch, item.Patch},
+			{http.MethodDelete, item.Delete},
+		} {
+			if entry.op == nil {
+				continue
+			}
+			op := buildClientOperation(entry.method, path, item.Parameters, *entry.op)
+			base := op.TSName
+			if n := usedNames[base]; n > 0 {
+				op.TSName = fmt.Sprintf("%s%d", op.TSName, n+1)
+				op.PyName = fmt.Sprintf("%s_%d", op.PyName, n+1)
+			}
+			usedNames[base]++
+			data.Operations = append(data.Operations, op)
+		}
+	}
+	return data
+}
+
+func buildClientOperation(method string, path string, shared []openAPIParameter, op openAPIOperation) clientOperation {
+	name := op.OperationID
+	if strings.TrimSpace(name) == "" {
+		name = strings.ToLower(method) + " " + pathParamPattern.ReplaceAllString(path, "by $1")
+	}
+	out := clientOperation{
+		TSName:       camelIdent(name),
+		PyName:       snakeIdent(name),
+		Method:       method,
+		Path:         path,
+		Summary:      strings.TrimSpace(op.Summary),
+		ResultTSType: "unknown",
+		ResultPyType: "Any",
+	}
+
+	params := map[string]openAPIParameter{}
+	order := []string{}
+	for _, p := range append(append([]openAPIParameter{}, shared...), op.Parameters...) {
+		key := p.In + ":" + p.Name
+		if _, ok := params[key]; !ok {
+			order = append(order, key)
+		}
+		params[key] = p
+	}
+	for _, match := range pathParamPattern.FindAllStringSubmatch(path, -1) {
+		key := "path:" + match[1]
+		if _, ok := params[key]; !ok {
+			params[key] = openAPIParameter{Name: match[1], In: "path", Schema: openAPISchema{Type: "string"}}
+			order = append(order, key)
+		}
+	}
+	for _, key := range order {
+		p := params[key]
+		param := clientParam{Name: p.Name, Ident: camelIdent(p.Name), Required: p.Required || p.In == "path", TSType: tsType(p.Schema), PyType: pyType(p.Schema)}
+		switch p.In {
+		case "path":
+			param.Ident = snakeIdent(p.Name)
+			out.PathParams = append(out.PathParams, param)
+		case "query":
+			param.Ident = snakeIdent(p.Name)
+			out.QueryParams = append(out.QueryParams, param)
+		}
+	}
+
+	if op.RequestBody != nil {
+		out.HasBody = true
+		out.BodyTSType, out.BodyPyType = "unknown", "Any"
+		if media, ok := op.RequestBody.Content["application/json"]; ok {
+			out.BodyTSType, out.BodyPyType = tsType(media.Schema), pyType(media.Schema)
+		}
+	}
+	out.TSParams = tsParams(out)
+	for _, code := range []string{"200", "201", "202"} {
+		res, ok := op.Responses[code]
+		if !ok {
+			continue
+		}
+		if media, ok := res.Content["application/json"]; ok {
+			out.ResultTSType, out.ResultPyType = tsType(media.Schema), pyType(media.Schema)
+		}
+		break
+	}
+	return out
+}
+
+func tsParams(op clientOperation) string {
+	params := make([]string, 0, len(op.PathParams)+2)
+	for _, p := range op.PathParams {
+		params = append(params, p.Ident+": "+p.TSType)
+	}
+	if op.HasBody {
+		params = append(params, "body: "+op.BodyTSType)
+	}
+	if len(op.QueryParams) > 0 {
+		var b strings.Builder
+		b.WriteString("query: {")
+		for _, p := range op.QueryParams {
+			fmt.Fprintf(&b, " '%s'?: %s;", p.Name, p.TSType)
+		}
+		b.WriteString(" } = {}")
+		params = append(params, b.String())
+	}
+	return strings.Join(params, ", ")
+}
+
+func tsType(s openAPISchema) string {
+	if s.Ref != "" {
+		return typeIdent(refName(s.Ref))
+	}
+	switch s.Type {
+	case "string":
+		return "string"
+	case "integer", "number":
+		return "number"
+	case "boolean":
+		return "boolean"
+	case "array":
+		if s.Items == nil {
+			return "unknown[]"
+		}
+		return tsType(*s.Items) + "[]"
+	case "object":
+		return "Record<string, unknown>"
+	default:
+		return "unknown"
+	}
+}
+
+func pyType(s openAPISchema) string {
+	if s.Ref != "" {
+		return `"` + typeIdent(refName(s.Ref)) + `"`
+	}
+	switch s.Type {
+	case "string":
+		return "str"
+	case "integer":
+		return "int"
+	case "number":
+		return "float"
+	case "boolean":
+		return "bool"
+	case "array":
+		if s.Items == nil {
+			return "List[Any]"
+		}
+		return "List[" + pyType(*s.Items) + "]"
+	case "object":
+		return "Dict[str, Any]"
+	default:
+		return "Any"
+	}
+}
+
+func refName(ref string) string {
+	return ref[strings.LastIndex(ref, "/")+1:]
+}
+
+func identWords(raw string) []string {
+	var words []string
+	for _, part := range nonIdentCharacter.Split(raw, -1) {
+		if part == "" {
+			continue
+		}
+		// Split camelCase boundaries so "listCases" and "list_cases" agree.
+		start := 0
+		runes := []rune(part)
+		for i := 1; i < len(runes); i++ {
+			if unicode.IsUpper(runes[i]) && unicode.IsLower(runes[i-1]) {
+				words = append(words, string(runes[start:i]))
+				start = i
+			}
+		}
+		words = append(words, string(runes[start:]))
+	}
+	return words
+}
+
+func camelIdent(raw string) string {
+	words := identWords(raw)
+	if len(words) == 0 {
+		return "operation"
+	}
+	var b strings.Builder
+	for i, w := range words {
+		if i == 0 {
+			b.WriteString(strings.ToLower(w))
+			continue
+		}
+		b.WriteString(upperFirstRune(strings.ToLower(w)))
+	}
+	return safeIdent(b.String())
+}
+
+func snakeIdent(raw string) string {
+	words := identWords(raw)
+	if len(words) == 0 {
+		return "operation"
+	}
+	for i := range words {
+		words[i] = strings.ToLower(words[i])
+	}
+	return safeIdent(strings.Join(words, "_"))
+}
+
+func typeIdent(raw string) string {
+	words := identWords(raw)
+	var b strings.Builder
+	for _, w := range words {
+		b.WriteString(upperFirstRune(w))
+	}
+	if b.Len() == 0 {
+		return "Model"
+	}
+	return safeIdent(b.String())
+}
+
+func safeIdent(v string) string {
+	if v != "" && unicode.IsDigit([]rune(v)[0]) {
+		return "_" + v
+	}
+	return v
+}
+
+func upperFirstRune(v string) string {
+	if v == "" {
+		return v
+	}
+	runes := []rune(v)
+	runes[0] = unicode.ToUpper(runes[0])
+	return string(runes)
+}
+
+func stringSet(values []string) map[string]bool {
+	out := make(map[string]bool, len(values))
+	for _, v := range values {
+		out[v] = true
+	}
+	return out
+}
+
+func firstNonEmptyString(values ...string) string {
+	for _, v := range values {
+		if strings.TrimSpace(v) != "" {
+			return v
+		}
+	}
+	return ""
+}
+
+func firstNonEmptyEnv(key string, fallback string) string {
+	return firstNonEmptyString(os.Getenv(key), fallback)
+}
@@ -0,0 +1,130 @@
+package main
+
+import (
+	"bytes"
+	"flag"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+var updateGolden = flag.Bool("update", false, "rewrite golden files")
+
+const testOpenAPISpec = `{
+  "openapi": "3.0.3",
+  "info": {"title": "Aceryx API", "version": "0.0.1"},
+  "paths": {
+    "/api/v1/cases": {
+      "get": {
+        "operationId": "listCases",
+        "parameters": [{"name": "status", "in": "query", "schema": {"type": "string"}}],
+        "responses": {"200": {"content": {"application/json": {"schema": {"type": "array", "items": {"$ref": "#/components/schemas/Case"}}}}}}
+      },
+      "post": {
+        "operationId": "create_case",
+        "requestBody": {"content": {"application/json": {"schema": {"$ref": "#/components/schemas/Case"}}}}
+      }
+    },
+    "/api/v1/cases/{id}": {
+      "get": {"responses": {"200": {"content": {"application/json": {"schema": {"$ref": "#/components/schemas/Case"}}}}}}
+    }
+  },
+  "components": {
+    "schemas": {
+      "Case": {
+        "type": "object",
+        "required": ["id"],
+        "properties": {"id": {"type": "string"}, "priority": {"type": "integer"}}
+      }
+    }
+  }
+}`
+
+func TestClientGenerateTypeScript(t *testing.T) {
+	withTempCWD(t, func() {
+		if err := os.WriteFile("openapi.json", []byte(testOpenAPISpec), 0o644); err != nil {
+			t.Fatalf("write spec: %v", err)
+		}
+		var out bytes.Buffer
+		if err := runClientGenerate([]string{"--lang=ts", "--out=sdk-ts", "--spec=openapi.json"}, &out); err != nil {
+			t.Fatalf("runClientGenerate failed: %v", err)
+		}
+		src := mustRead(t, "sdk-ts/client.ts")
+		for _, want := range []string{
+			"export interface Case {",
+			"'id': string",
+			"'priority'?: number",
+			"async listCases(query: { 'status'?: string; } = {}): Promise<Case[]>",
+			"async createCase(body: Case): Promise<unknown>",
+			"async getApiV1CasesById(id: string): Promise<Case>",
+			"export const API_VERSION = '0.0.1'",
+		} {
+			if !strings.Contains(src, want) {
+				t.Fatalf("generated client missing %q:\n%s", want, src)
+			}
+		}
+	})
+}
+
+func TestClientGenerateTypeScriptGolden(t *testing.T) {
+	golden, err := filepath.Abs(filepath.Join("..", "..", "tests", "fixtures", "client_golden", "client.ts"))
+	if err != nil {
+		t.Fatalf("resolve golden file: %v", err)
+	}
+	withTempCWD(t, func() {
+		if err := os.WriteFile("openapi.json", []byte(testOpenAPISpec), 0o644); err != nil {
+			t.Fatalf("write spec: %v", err)
+		}
+		var out bytes.Buffer
+		if err := runClientGenerate([]string{"--lang=ts", "--out=sdk-ts", "--spec=openapi.json"}, &out); err != nil {
+			t.Fatalf("runClientGenerate failed: %v", err)
+		}
+		src := mustRead(t, "sdk-ts/client.ts")
+		if *updateGolden {
+			if err := os.WriteFile(golden, []byte(src), 0o644); err != nil {
+				t.Fatalf("update golden file: %v", err)
+			}
+		}
+		if want := mustRead(t, golden); src != want {
+			t.Fatalf("generated client differs from %s (run with -update to accept):\n%s", golden, src)
+		}
+	})
+}
+
+func TestClientGeneratePython(t *testing.T) {
+	withTempCWD(t, func() {
+		if err := os.WriteFile("openapi.json", []byte(testOpenAPISpec), 0o644); err != nil {
+			t.Fatalf("write spec: %v", err)
+		}
+		var out bytes.Buffer
+		if err := runClientGenerate([]string{"--lang=python", "--out=sdk-py", "--spec=openapi.json"}, &out); err != nil {
+			t.Fatalf("runClientGenerate failed: %v", err)
+		}
+		src := mustRead(t, "sdk-py/client.py")
+		for _, want := range []string{
+			"class Case(TypedDict, total=False):",
+			"def list_cases(self, status: Optional[str] = None) -> List[\"Case\"]:",
+			"def get_api_v1_cases_by_id(self, id: str) -> \"Case\":",
+			`return self._request("GET", path, query={"status": status}, body=None)`,
+		} {
+			if !strings.Contains(src, want) {
+				t.Fatalf("generated client missing %q:\n%s", want, src)
+			}
+		}
+	})
+}
+
+func TestClientGenerateRequiresSpec(t *testing.T) {
+	var out bytes.Buffer
+	if err := runClientGenerate([]string{"--lang=ts", "--out=x"}, &out); err == nil || !strings.Contains(err.Error(), "--spec is required") {
+		t.Fatalf("expected missing --spec error, got %v", err)
+	}
+}
+
+func TestClientGenerateRejectsUnknownLanguage(t *testing.T) {
+	var out bytes.Buffer
+	if err := runClientGenerate([]string{"--lang=java", "--out=x", "--spec=missing.json"}, &out); err == nil {
+		t.Fatal("expected unsupported language error")
+	}
+}
@@ -0,0 +1,65 @@
+package main
+
+import (
+	"flag"
+	"fmt"
+	"io"
+	"strings"
+
+	"github.com/neural-chilli/aceryx/internal/config"
+)
+
+const configUsage = "usage: aceryx config [genkey|encrypt --name <KEY> [--value <value>]]"
+
+// runConfig manages encrypted config values. genkey prints a new master key;
+// encrypt seals --value, or stdin when it is not given, for the config key
+// named by --name under the master key from ACERYX_CONFIG_KEY or
+// ACERYX_CONFIG_KEY_FILE.
+func runConfig(args []string, in io.Reader, out io.Writer) error {
+	if len(args) == 0 {
+		return fmt.Errorf("%s", configUsage)
+	}
+	switch args[0] {
+	case "genkey":
+		key, err := config.GenerateMasterKey()
+		if err != nil {
+			return err
+		}
+		_, err = fmt.Fprintln(out, key)
+		return err
+	case "encrypt":
+		fs := flag.NewFlagSet("config encrypt", flag.ContinueOnError)
+		fs.SetOutput(io.Discard)
+		name := fs.String("name", "", "config key the value will be stored under")
+		value := fs.String("value", "", "value to encrypt; read from stdin when empty")
+		if err := fs.Parse(args[1:]); err != nil {
+			return fmt.Errorf("%s: %w", configUsage, err)
+		}
+		if strings.TrimSpace(*name) == "" {
+			return fmt.Errorf("%s: --name is required", configUsage)
+		}
+		plaintext := *value
+		if plaintext == "" {
+			raw, err := io.ReadAll(in)
+			if err != nil {
+				return fmt.Errorf("read value: %w", err)
+			}
+			plaintext = strings.TrimRight(string(raw), "\r\n")
+		}
+		if plaintext == "" {
+			return fmt.Errorf("nothing to encrypt")
+		}
+		key, err := config.MasterKey()
+		if err != nil {
+			return err
+		}
+		sealed, err := config.EncryptValue(key, strings.TrimSpace(*name), plaintext)
+		if err != nil {
+			return err
+		}
+		_, err = fmt.Fprintln(out, sealed)
+		return err
+	default:
+		return fmt.Errorf("unknown config subcommand: %s", args[0])
+	}
+}
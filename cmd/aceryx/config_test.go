@@ -0,0 +1,33 @@
+package main
+
+import (
+	"bytes"
+	"strings"
+	"testing"
+
+	"github.com/neural-chilli/aceryx/internal/config"
+)
+
+func TestConfigEncryptUsesMasterKey(t *testing.T) {
+	var keyOut bytes.Buffer
+	if err := runConfig([]string{"genkey"}, nil, &keyOut); err != nil {
+		t.Fatalf("genkey: %v", err)
+	}
+	t.Setenv("ACERYX_CONFIG_KEY", strings.TrimSpace(keyOut.String()))
+	t.Setenv("ACERYX_CONFIG_KEY_FILE", "")
+
+	var sealed bytes.Buffer
+	if err := runConfig([]string{"encrypt", "--name", "ACERYX_LLM_API_KEY"}, strings.NewReader("hunter2\n"), &sealed); err != nil {
+		t.Fatalf("encrypt: %v", err)
+	}
+	key, err := config.MasterKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if got, err := config.DecryptValue(key, "ACERYX_LLM_API_KEY", strings.TrimSpace(sealed.String())); err != nil || got != "hunter2" {
+		t.Fatalf("decrypt: %q %v", got, err)
+	}
+	if err := runConfig([]string{"encrypt"}, strings.NewReader("hunter2\n"), &sealed); err == nil {
+		t.Fatal("expected encrypt without --name to fail")
+	}
+}
@@ -0,0 +1,116 @@
+package main
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"text/tabwriter"
+)
+
+const connectorsUsage = "usage: aceryx connectors [list|show] [--server <url>] [--api-key <key>|--token <token>] [--output table|json]"
+
+// cliConnector mirrors the server's connector descriptor.
+type cliConnector struct {
+	Meta struct {
+		Key         string `json:"key"`
+		Name        string `json:"name"`
+		Description string `json:"description"`
+		Version     string `json:"version"`
+	} `json:"meta"`
+	Auth struct {
+		Type string `json:"type"`
+	} `json:"auth"`
+	Actions []struct {
+		Key         string `json:"key"`
+		Name        string `json:"name"`
+		Description string `json:"description"`
+	} `json:"actions"`
+}
+
+func runConnectors(args []string, out io.Writer) error {
+	if len(args) == 0 {
+		return fmt.Errorf("%s", connectorsUsage)
+	}
+	ctx := context.Background()
+	switch args[0] {
+	case "list":
+		return runConnectorsList(ctx, args[1:], out)
+	case "show":
+		return runConnectorsShow(ctx, args[1:], out)
+	default:
+		return fmt.Errorf("unknown connectors subcommand: %s", args[0])
+	}
+}
+
+func runConnectorsList(ctx context.Context, args []string, out io.Writer) error {
+	cmd := newRemoteCommand("connectors", "list")
+	if _, err := cmd.parse(args); err != nil {
+		return err
+	}
+	raw, items, err := fetchConnectors(ctx, cmd.client())
+	if err != nil {
+		return err
+	}
+	if *cmd.output == "json" {
+		return writeIndentedJSON(out, raw)
+	}
+	tw := tabwriter.NewWriter(out, 0, 4, 2, ' ', 0)
+	_, _ = fmt.Fprintln(tw, "KEY\tNAME\tVERSION\tAUTH\tACTIONS")
+	for _, c := range items {
+		_, _ = fmt.Fprintf(tw, "%s\t%s\t%s\t%s\t%d\n", c.Meta.Key, c.Meta.Name, c.Meta.Version, c.Auth.Type, len(c.Actions))
+	}
+	return tw.Flush()
+}
+
+func runConnectorsShow(ctx context.Context, args []string, out io.Writer) error {
+	cmd := newRemoteCommand("connectors", "show")
+	positional, err := cmd.parse(args)
+	if err != nil {
+		return err
+	}
+	if len(positional) != 1 {
+		return fmt.Errorf("expected one connector key")
+	}
+	_, items, err := fetchConnectors(ctx, cmd.client())
+	if err != nil {
+		return err
+	}
+	for _, c := range items {
+		if c.Meta.Key != positional[0] {
+			continue
+		}
+		if *cmd.output == "json" {
+			raw, err := json.Marshal(c)
+			if err != nil {
+				return err
+			}
+			return writeIndentedJSON(out, raw)
+		}
+		_, _ = fmt.Fprintf(out, "%s (%s) %s\n%s\n\n", c.Meta.Name, c.Meta.Key, c.Meta.Version, c.Meta.Description)
+		tw := tabwriter.NewWriter(out, 0, 4, 2, ' ', 0)
+		_, _ = fmt.Fprintln(tw, "ACTION\tNAME\tDESCRIPTION")
+		for _, a := range c.Actions {
+			_, _ = fmt.Fprintf(tw, "%s\t%s\t%s\n", a.Key, a.Name, a.Description)
+		}
+		return tw.Flush()
+	}
+	return fmt.Errorf("connector %q not found", positional[0])
+}
+
+// fetchConnectors returns the raw list response and its decoded items. The
+// registry is small, so one page at the server's maximum limit covers it.
+func fetchConnectors(ctx context.Context, client *apiClient) ([]byte, []cliConnector, error) {
+	raw, err := client.do(ctx, http.MethodGet, "/connectors?limit=500", "", nil)
+	if err != nil {
+		return nil, nil, err
+	}
+	var page struct {
+		Items []cliConnector `json:"items"`
+	}
+	if err := json.Unmarshal(raw, &page); err != nil {
+		return nil, nil, fmt.Errorf("decode connectors: %w", err)
+	}
+	return raw, page.Items, nil
+}
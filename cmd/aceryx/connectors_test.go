@@ -0,0 +1,40 @@
+package main
+
+import (
+	"bytes"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+)
+
+func TestConnectorsListAndShow(t *testing.T) {
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Header.Get("Authorization") != "Bearer key-1" || r.URL.Path != "/connectors" {
+			w.WriteHeader(http.StatusUnauthorized)
+			return
+		}
+		_, _ = w.Write([]byte(`{"items":[{"meta":{"key":"http","name":"HTTP","version":"1.0.0"},"auth":{"type":"none"},"actions":[{"key":"request","name":"Request","description":"Send an HTTP request"}]}],"total":1}`))
+	}))
+	t.Cleanup(srv.Close)
+
+	var out bytes.Buffer
+	if err := runConnectors([]string{"list", "--server", srv.URL, "--api-key", "key-1"}, &out); err != nil {
+		t.Fatalf("list: %v", err)
+	}
+	if !strings.Contains(out.String(), "HTTP") || !strings.Contains(out.String(), "1.0.0") {
+		t.Fatalf("unexpected list output:\n%s", out.String())
+	}
+
+	out.Reset()
+	if err := runConnectors([]string{"show", "http", "--server", srv.URL, "--api-key", "key-1"}, &out); err != nil {
+		t.Fatalf("show: %v", err)
+	}
+	if !strings.Contains(out.String(), "Send an HTTP request") {
+		t.Fatalf("unexpected show output:\n%s", out.String())
+	}
+
+	if err := runConnectors([]string{"show", "missing", "--server", srv.URL, "--api-key", "key-1"}, &out); err == nil || !strings.Contains(err.Error(), "not found") {
+		t.Fatalf("expected not found error, got %v", err)
+	}
+}
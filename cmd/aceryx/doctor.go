@@ -0,0 +1,535 @@
+package main
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"flag"
+	"fmt"
+	"io"
+	"net"
+	"net/http"
+	"os"
+	"regexp"
+	"sort"
+	"strconv"
+	"strings"
+	"text/tabwriter"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/artifacts"
+	"github.com/neural-chilli/aceryx/internal/chaos"
+	"github.com/neural-chilli/aceryx/internal/config"
+	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/engine"
+	"github.com/neural-chilli/aceryx/internal/features"
+	"github.com/neural-chilli/aceryx/internal/mcpserver"
+	internalmigrations "github.com/neural-chilli/aceryx/internal/migrations"
+	"github.com/neural-chilli/aceryx/internal/redaction"
+	"github.com/neural-chilli/aceryx/internal/vault"
+	"github.com/neural-chilli/aceryx/internal/workflows"
+)
+
+const doctorUsage = "usage: aceryx doctor [--server <url>] [--timeout <duration>] [--output table|json]"
+
+const (
+	doctorOK   = "ok"
+	doctorWarn = "warn"
+	doctorFail = "fail"
+)
+
+// errDoctorFailed is returned when at least one check fails, so scripts can
+// rely on the exit code.
+var errDoctorFailed = errors.New("one or more doctor checks failed")
+
+var secretReferencePattern = regexp.MustCompile(`\{\{\s*secrets\.([^{}\s]+)\s*\}\}`)
+
+var (
+	doctorIntEnv = []string{
+		"ACERYX_DB_MAX_OPEN_CONNS", "ACERYX_DB_MAX_IDLE_CONNS",
+		"ACERYX_MAX_BODY_BYTES", "ACERYX_MAX_WORKFLOW_BODY_BYTES", "ACERYX_MAX_INGEST_BODY_BYTES", "ACERYX_MAX_JSON_DEPTH",
+		"ACERYX_EXECUTE_RPM", "ACERYX_EXECUTE_BURST", "ACERYX_EXECUTE_MAX_FAILURES",
+		"ACERYX_LOG_MAX_SIZE_MB", "ACERYX_LOG_MAX_BACKUPS", "ACERYX_SMTP_PORT",
+	}
+	doctorDurationEnv = []string{
+		"ACERYX_DB_CONN_MAX_LIFETIME", "ACERYX_DB_CONN_MAX_IDLE_TIME", "ACERYX_SHUTDOWN_DRAIN_TIMEOUT",
+		"ACERYX_SESSION_TTL", "ACERYX_VAULT_CLEANUP_INTERVAL", "ACERYX_CONFIG_WATCH_INTERVAL",
+	}
+)
+
+// doctorResult is one line of the report. Fix says what to change when the
+// check does not pass.
+type doctorResult struct {
+	Check  string `json:"check"`
+	Status string `json:"status"`
+	Detail string `json:"detail"`
+	Fix    string `json:"fix,omitempty"`
+}
+
+type doctorReport struct {
+	results []doctorResult
+}
+
+func (r *doctorReport) add(check, status, detail, fix string) {
+	r.results = append(r.results, doctorResult{Check: check, Status: status, Detail: detail, Fix: fix})
+}
+
+func (r *doctorReport) failed() bool {
+	for _, res := range r.results {
+		if res.Status == doctorFail {
+			return true
+		}
+	}
+	return false
+}
+
+// publishedWorkflow is the latest published version of a workflow, used by
+// the secret and feature checks.
+type publishedWorkflow struct {
+	TenantID uuid.UUID
+	Name     string
+	AST      string
+}
+
+func runDoctor(args []string, out io.Writer) error {
+	fs := flag.NewFlagSet("doctor", flag.ContinueOnError)
+	server := fs.String("server", os.Getenv("ACERYX_URL"), "running server to query for protocol health (defaults to ACERYX_URL, then ACERYX_HTTP_ADDR)")
+	timeout := fs.Duration("timeout", 5*time.Second, "timeout for each network check")
+	output := fs.String("output", "table", "output format (table|json)")
+	if err := fs.Parse(args); err != nil {
+		return err
+	}
+	if fs.NArg() > 0 {
+		return fmt.Errorf("%s", doctorUsage)
+	}
+	if *output != "table" && *output != "json" {
+		return fmt.Errorf("--output must be table or json")
+	}
+
+	ctx := context.Background()
+	report := &doctorReport{}
+	checkDoctorConfig(report)
+	db := checkDoctorDatabase(ctx, report, *timeout)
+	if db != nil {
+		defer func() { _ = db.Close() }()
+	}
+	checkDoctorStorage(ctx, report)
+	checkDoctorArtifacts(ctx, report)
+	workflows, err := loadPublishedWorkflows(ctx, db)
+	if err != nil {
+		report.add("workflows", doctorWarn, "could not read published workflows: "+err.Error(), "run `aceryx migrate` if the schema is behind")
+	}
+	checkDoctorSecrets(ctx, report, db, workflows)
+	checkDoctorPorts(report, *timeout)
+	checkDoctorProtocols(ctx, report, doctorServerURL(*server), *timeout)
+	checkDoctorFeatures(report, workflows)
+
+	if err := writeDoctorReport(out, report.results, *output); err != nil {
+		return err
+	}
+	if report.failed() {
+		return errDoctorFailed
+	}
+	return nil
+}
+
+func checkDoctorConfig(report *doctorReport) {
+	if path := strings.TrimSpace(os.Getenv("ACERYX_CONFIG_FILE")); path != "" {
+		if _, err := config.ReadEnvFile(path); err != nil {
+			report.add("config.file", doctorFail, err.Error(), "fix the file so every line is KEY=VALUE and every ${...} reference and enc:v1: value resolves, or unset ACERYX_CONFIG_FILE")
+		} else {
+			report.add("config.file", doctorOK, path, "")
+		}
+	}
+
+	if _, err := middleware.CORSConfigFromEnv(); err != nil {
+		report.add("config.cors", doctorFail, err.Error(), "fix the ACERYX_CORS_* settings; the server refuses to start until they are valid")
+	}
+	if _, err := redaction.FromEnv(); err != nil {
+		report.add("config.redaction", doctorFail, err.Error(), "fix ACERYX_REDACTION_RULES; the server refuses to start until they are valid")
+	}
+	if _, err := workflows.ParseLintRules(os.Getenv("ACERYX_LINT_RULES")); err != nil {
+		report.add("config.lint", doctorFail, err.Error(), "fix ACERYX_LINT_RULES; the server refuses to start until they are valid")
+	}
+
+	var problems []string
+	switch strings.ToLower(strings.TrimSpace(os.Getenv("ACERYX_LOG_LEVEL"))) {
+	case "", "debug", "info", "warn", "warning", "error":
+	default:
+		problems = append(problems, "ACERYX_LOG_LEVEL must be debug, info, warn or error")
+	}
+	switch strings.ToLower(strings.TrimSpace(os.Getenv("ACERYX_LOG_FORMAT"))) {
+	case "", "json", "text":
+	default:
+		problems = append(problems, "ACERYX_LOG_FORMAT must be json or text")
+	}
+	for _, key := range doctorIntEnv {
+		if raw := strings.TrimSpace(os.Getenv(key)); raw != "" {
+			if n, err := strconv.Atoi(raw); err != nil || n <= 0 {
+				problems = append(problems, key+" must be a positive integer")
+			}
+		}
+	}
+	for _, key := range doctorDurationEnv {
+		if raw := strings.TrimSpace(os.Getenv(key)); raw != "" {
+			if d, err := time.ParseDuration(raw); err != nil || d <= 0 {
+				problems = append(problems, key+" must be a positive duration such as 30s or 24h")
+			}
+		}
+	}
+	if addr := strings.TrimSpace(os.Getenv("ACERYX_HTTP_ADDR")); addr != "" {
+		if _, _, err := net.SplitHostPort(addr); err != nil {
+			problems = append(problems, "ACERYX_HTTP_ADDR must be host:port, e.g. :8080")
+		}
+	}
+	if raw := strings.TrimSpace(os.Getenv("ACERYX_CHAOS_RULES")); raw != "" {
+		if _, err := chaos.ParseRules(raw); err != nil {
+			problems = append(problems, "ACERYX_CHAOS_RULES: "+err.Error())
+		} else if !chaos.IsDevEnvironment(os.Getenv("ACERYX_ENVIRONMENT")) {
+			problems = append(problems, "ACERYX_CHAOS_RULES only applies when ACERYX_ENVIRONMENT is "+strings.Join(chaos.DevEnvironments, ", "))
+		}
+	}
+	if len(problems) > 0 {
+		// Invalid values are ignored at startup in favour of the defaults,
+		// which is easy to miss.
+		report.add("config.values", doctorWarn, strings.Join(problems, "; "), "correct the values; until then the server silently uses the defaults")
+		return
+	}
+	report.add("config.values", doctorOK, "environment settings are well-formed", "")
+}
+
+func checkDoctorDatabase(ctx context.Context, report *doctorReport, timeout time.Duration) *sql.DB {
+	databaseURL := resolveDatabaseURL()
+	if databaseURL == "" {
+		report.add("database", doctorFail, "no database URL configured", "set ACERYX_DB_URL to a Postgres connection string")
+		return nil
+	}
+	pingCtx, cancel := context.WithTimeout(ctx, timeout)
+	defer cancel()
+	db, err := openDatabaseFromURL(pingCtx, databaseURL)
+	if err != nil {
+		report.add("database", doctorFail, err.Error(), "check Postgres is running and the URL's host, port, credentials and database name")
+		return nil
+	}
+	report.add("database", doctorOK, "connected", "")
+
+	latest, err := internalmigrations.LatestVersion()
+	if err != nil {
+		report.add("database.migrations", doctorFail, err.Error(), "rebuild the binary; its embedded migrations are unreadable")
+		return db
+	}
+	var applied int
+	if err := db.QueryRowContext(pingCtx, `SELECT COALESCE(MAX(version), 0) FROM schema_migrations`).Scan(&applied); err != nil {
+		applied = 0
+	}
+	if applied < latest {
+		report.add("database.migrations", doctorFail, fmt.Sprintf("schema at version %d, binary expects %d", applied, latest), "run `aceryx migrate`")
+		return db
+	}
+	report.add("database.migrations", doctorOK, fmt.Sprintf("schema at version %d", applied), "")
+	return db
+}
+
+func checkDoctorStorage(ctx context.Context, report *doctorReport) {
+	backend := strings.ToLower(firstNonEmptyString(strings.TrimSpace(os.Getenv("ACERYX_VAULT_BACKEND")), "local"))
+	if backend != "local" {
+		_, status, err := vault.BuildVaultStoreFromEnv(ctx, os.Getenv("ACERYX_VAULT_SIGNING_KEY"))
+		if err != nil || !status.Healthy {
+			report.add("storage", doctorFail, fmt.Sprintf("%s backend: %s", backend, firstNonEmptyString(status.Error, fmt.Sprint(err))), "check the ACERYX_VAULT_"+strings.ToUpper(backend)+"_* settings and the credentials they reference")
+			return
+		}
+		report.add("storage", doctorOK, backend+" backend configured", "")
+		return
+	}
+	root := firstNonEmptyString(os.Getenv("ACERYX_VAULT_ROOT"), resolveVaultPath())
+	info, err := os.Stat(root)
+	if errors.Is(err, os.ErrNotExist) {
+		report.add("storage", doctorOK, fmt.Sprintf("local vault %s does not exist yet; it is created on first upload", root), "")
+		return
+	}
+	if err != nil || !info.IsDir() {
+		report.add("storage", doctorFail, fmt.Sprintf("local vault %s is not a directory", root), "set ACERYX_VAULT_ROOT to a writable directory")
+		return
+	}
+	probe, err := os.CreateTemp(root, ".aceryx-doctor-*")
+	if err != nil {
+		report.add("storage", doctorFail, fmt.Sprintf("local vault %s is not writable: %v", root, err), "grant the server user write access or set ACERYX_VAULT_ROOT")
+		return
+	}
+	_ = probe.Close()
+	_ = os.Remove(probe.Name())
+	report.add("storage", doctorOK, "local vault "+root+" is writable", "")
+}
+
+func checkDoctorArtifacts(ctx context.Context, report *doctorReport) {
+	if !artifacts.BackendConfigured() {
+		return
+	}
+	if _, err := artifacts.NewObjectStoreFromEnv(ctx); err != nil {
+		report.add("storage.artifacts", doctorFail, err.Error(), "fix the ACERYX_ARTIFACTS_* settings; the server refuses to start until they are valid")
+		return
+	}
+	report.add("storage.artifacts", doctorOK, "artifact store configured", "")
+}
+
+func checkDoctorSecrets(ctx context.Context, report *doctorReport, db *sql.DB, workflows []publishedWorkflow) {
+	switch secret := os.Getenv("ACERYX_JWT_SECRET"); {
+	case secret == "":
+		report.add("secrets.jwt", doctorFail, "ACERYX_JWT_SECRET is not set; sessions are signed with a development secret", "set ACERYX_JWT_SECRET to a random value of at least 32 characters")
+	case len(secret) < 32:
+		report.add("secrets.jwt", doctorWarn, "ACERYX_JWT_SECRET is shorter than 32 characters", "use a longer random value")
+	default:
+		report.add("secrets.jwt", doctorOK, "set", "")
+	}
+	if os.Getenv("ACERYX_LLM_ENDPOINT") != "" && os.Getenv("ACERYX_LLM_API_KEY") == "" {
+		report.add("secrets.llm", doctorWarn, "ACERYX_LLM_ENDPOINT is set without ACERYX_LLM_API_KEY", "set ACERYX_LLM_API_KEY unless the endpoint needs no key")
+	}
+	if os.Getenv("ACERYX_SMTP_USERNAME") != "" && os.Getenv("ACERYX_SMTP_PASSWORD") == "" {
+		report.add("secrets.smtp", doctorWarn, "ACERYX_SMTP_USERNAME is set without ACERYX_SMTP_PASSWORD", "set ACERYX_SMTP_PASSWORD")
+	}
+	if db == nil {
+		return
+	}
+
+	store := connectors.NewChainedSecretStore(connectors.NewDBSecretStore(db), &connectors.EnvSecretStore{})
+	var missing []string
+	checked := 0
+	for _, wf := range workflows {
+		seen := map[string]bool{}
+		for _, match := range secretReferencePattern.FindAllStringSubmatch(wf.AST, -1) {
+			key := match[1]
+			if seen[key] {
+				continue
+			}
+			seen[key] = true
+			checked++
+			if _, err := store.Get(ctx, wf.TenantID, key); err != nil {
+				missing = append(missing, fmt.Sprintf("%s (workflow %q)", key, wf.Name))
+			}
+		}
+	}
+	if len(missing) > 0 {
+		report.add("secrets.workflows", doctorFail, "unresolved: "+strings.Join(missing, ", "), "add each secret for the workflow's tenant, or set ACERYX_SECRET_<KEY> with dots replaced by underscores")
+		return
+	}
+	report.add("secrets.workflows", doctorOK, fmt.Sprintf("%d secret reference(s) resolve", checked), "")
+}
+
+func checkDoctorPorts(report *doctorReport, timeout time.Duration) {
+	checkDoctorPort(report, "port.http", firstNonEmptyString(strings.TrimSpace(os.Getenv("ACERYX_HTTP_ADDR")), ":8080"), "ACERYX_HTTP_ADDR", timeout)
+	enabled := strings.TrimSpace(os.Getenv("ACERYX_MCP_SERVER_ENABLED"))
+	if strings.EqualFold(enabled, "true") || enabled == "1" {
+		checkDoctorPort(report, "port.mcp", firstNonEmptyString(strings.TrimSpace(os.Getenv("ACERYX_MCP_SERVER_ADDR")), mcpserver.DefaultListenAddr), "ACERYX_MCP_SERVER_ADDR", timeout)
+	}
+}
+
+// checkDoctorPort reports whether addr can be bound. A port held by a running
+// Aceryx server is fine; anything else holding it will stop `aceryx serve`.
+func checkDoctorPort(report *doctorReport, check, addr, envKey string, timeout time.Duration) {
+	ln, err := net.Listen("tcp", addr)
+	if err == nil {
+		_ = ln.Close()
+		report.add(check, doctorOK, addr+" is available", "")
+		return
+	}
+	if aceryxServing(doctorServerURL(""), timeout) && check == "port.http" {
+		report.add(check, doctorOK, addr+" is in use by a running Aceryx server", "")
+		return
+	}
+	report.add(check, doctorFail, fmt.Sprintf("cannot listen on %s: %v", addr, err), "stop the process using the port or set "+envKey+" to a free address")
+}
+
+func aceryxServing(server string, timeout time.Duration) bool {
+	client := &http.Client{Timeout: timeout}
+	res, err := client.Get(server + "/health/live")
+	if err != nil {
+		return false
+	}
+	_ = res.Body.Close()
+	return res.StatusCode == http.StatusOK
+}
+
+func checkDoctorProtocols(ctx context.Context, report *doctorReport, server string, timeout time.Duration) {
+	reqCtx, cancel := context.WithTimeout(ctx, timeout)
+	defer cancel()
+	req, err := http.NewRequestWithContext(reqCtx, http.MethodGet, server+"/health/ready", nil)
+	if err != nil {
+		report.add("protocols", doctorWarn, err.Error(), "pass --server with the server's base URL")
+		return
+	}
+	res, err := http.DefaultClient.Do(req)
+	if err != nil {
+		report.add("protocols", doctorWarn, "no server reachable at "+server+"; protocol checks skipped", "start the server with `aceryx serve` or pass --server")
+		return
+	}
+	defer func() { _ = res.Body.Close() }()
+	var body struct {
+		Status    string `json:"status"`
+		Reason    string `json:"reason"`
+		Protocols map[string]struct {
+			Status   string `json:"status"`
+			Required bool   `json:"required"`
+			Error    string `json:"error"`
+		} `json:"protocols"`
+	}
+	if err := json.NewDecoder(io.LimitReader(res.Body, 1<<20)).Decode(&body); err != nil {
+		report.add("protocols", doctorWarn, fmt.Sprintf("%s/health/ready returned status %d", server, res.StatusCode), "check --server points at an Aceryx server")
+		return
+	}
+	if res.StatusCode != http.StatusOK && len(body.Protocols) == 0 {
+		report.add("protocols", doctorFail, "server not ready: "+firstNonEmptyString(body.Reason, body.Status), "fix the readiness reason reported above; see GET /health/ready")
+		return
+	}
+	names := make([]string, 0, len(body.Protocols))
+	for name := range body.Protocols {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	for _, name := range names {
+		p := body.Protocols[name]
+		switch {
+		case p.Status == "healthy":
+			report.add("protocol."+name, doctorOK, "healthy", "")
+		case p.Required:
+			report.add("protocol."+name, doctorFail, "required protocol unhealthy: "+p.Error, doctorProtocolFix(name))
+		default:
+			report.add("protocol."+name, doctorWarn, "unhealthy: "+p.Error, doctorProtocolFix(name))
+		}
+	}
+}
+
+func doctorProtocolFix(name string) string {
+	switch name {
+	case "mcp":
+		return "check the MCP servers named above are reachable; their circuit breakers reset once calls succeed"
+	case "triggers":
+		return "inspect failing triggers with GET /api/v1/admin/triggers and restart them after fixing their configuration"
+	default:
+		return "check the protocol's configuration"
+	}
+}
+
+func checkDoctorFeatures(report *doctorReport, workflows []publishedWorkflow) {
+	overrides, err := features.ParseConfig(os.Getenv("ACERYX_FEATURES"))
+	if err != nil {
+		report.add("features", doctorFail, err.Error(), "use comma-separated key=true|false pairs in ACERYX_FEATURES; the server refuses to start until they are valid")
+		return
+	}
+	known := map[string]features.Flag{}
+	for _, f := range features.Known {
+		known[f.Key] = f
+	}
+	var unknown []string
+	for key := range overrides {
+		if _, ok := known[key]; !ok {
+			unknown = append(unknown, key)
+		}
+	}
+	sort.Strings(unknown)
+	if len(unknown) > 0 {
+		report.add("features.config", doctorWarn, "unknown flag(s) in ACERYX_FEATURES: "+strings.Join(unknown, ", "), "remove or correct them; GET /api/v1/system/features lists the known flags")
+	}
+
+	var blocked []string
+	for _, wf := range workflows {
+		var ast engine.WorkflowAST
+		if err := json.Unmarshal([]byte(wf.AST), &ast); err != nil {
+			continue
+		}
+		seen := map[string]bool{}
+		for _, step := range ast.Steps {
+			key := features.StepFlag(step.Type)
+			f, isKnown := known[key]
+			if !isKnown || seen[key] {
+				continue
+			}
+			seen[key] = true
+			enabled, overridden := overrides[key]
+			if !overridden {
+				enabled = f.Default
+			}
+			if !enabled {
+				blocked = append(blocked, fmt.Sprintf("workflow %q needs %s", wf.Name, key))
+			}
+		}
+	}
+	if len(blocked) > 0 {
+		report.add("features.workflows", doctorFail, strings.Join(blocked, "; "), "enable the flags in ACERYX_FEATURES or remove those steps; tenant overrides may still enable them")
+		return
+	}
+	report.add("features.workflows", doctorOK, "published workflows only use enabled step types", "")
+}
+
+func loadPublishedWorkflows(ctx context.Context, db *sql.DB) ([]publishedWorkflow, error) {
+	if db == nil {
+		return nil, nil
+	}
+	rows, err := db.QueryContext(ctx, `
+SELECT DISTINCT ON (w.id) w.tenant_id, w.name, wv.ast::text
+FROM workflows w
+JOIN workflow_versions wv ON wv.workflow_id = w.id
+WHERE wv.status = 'published'
+ORDER BY w.id, wv.version DESC
+`)
+	if err != nil {
+		return nil, err
+	}
+	defer func() { _ = rows.Close() }()
+	var out []publishedWorkflow
+	for rows.Next() {
+		var wf publishedWorkflow
+		if err := rows.Scan(&wf.TenantID, &wf.Name, &wf.AST); err != nil {
+			return nil, err
+		}
+		out = append(out, wf)
+	}
+	return out, rows.Err()
+}
+
+// doctorServerURL picks the server to probe: --server, then a loopback URL
+// for ACERYX_HTTP_ADDR.
+func doctorServerURL(server string) string {
+	if server = strings.TrimRight(strings.TrimSpace(server), "/"); server != "" {
+		return server
+	}
+	addr := firstNonEmptyString(strings.TrimSpace(os.Getenv("ACERYX_HTTP_ADDR")), ":8080")
+	host, port, err := net.SplitHostPort(addr)
+	if err != nil {
+		return "http://localhost:8080"
+	}
+	if host == "" || host == "0.0.0.0" || host == "::" {
+		host = "localhost"
+	}
+	return "http://" + net.JoinHostPort(host, port)
+}
+
+func writeDoctorReport(out io.Writer, results []doctorResult, format string) error {
+	if format == "json" {
+		raw, err := json.Marshal(map[string]any{"checks": results})
+		if err != nil {
+			return err
+		}
+		return writeIndentedJSON(out, raw)
+	}
+	tw := tabwriter.NewWriter(out, 0, 4, 2, ' ', 0)
+	_, _ = fmt.Fprintln(tw, "STATUS\tCHECK\tDETAIL")
+	for _, res := range results {
+		_, _ = fmt.Fprintf(tw, "%s\t%s\t%s\n", strings.ToUpper(res.Status), res.Check, res.Detail)
+	}
+	if err := tw.Flush(); err != nil {
+		return err
+	}
+	var fixes []string
+	for _, res := range results {
+		if res.Fix != "" && res.Status != doctorOK {
+			fixes = append(fixes, fmt.Sprintf("  %s: %s", res.Check, res.Fix))
+		}
+	}
+	if len(fixes) > 0 {
+		_, _ = fmt.Fprintf(out, "\nSuggested fixes:\n%s\n", strings.Join(fixes, "\n"))
+	}
+	return nil
+}
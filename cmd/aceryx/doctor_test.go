@@ -0,0 +1,85 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+)
+
+func TestDoctorReportsActionableFailures(t *testing.T) {
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		_, _ = w.Write([]byte(`{"status":"degraded","protocols":{"mcp":{"status":"unhealthy","required":false,"error":"circuit open for https://tools.example.com/mcp"},"triggers":{"status":"healthy","required":false}}}`))
+	}))
+	t.Cleanup(srv.Close)
+	for _, key := range []string{"ACERYX_DB_URL", "ACERYX_DATABASE_URL", "DATABASE_URL", "ACERYX_CONFIG_FILE", "ACERYX_JWT_SECRET", "ACERYX_ARTIFACTS_ROOT"} {
+		t.Setenv(key, "")
+	}
+	t.Setenv("ACERYX_LOG_LEVEL", "verbose")
+	t.Setenv("ACERYX_HTTP_ADDR", "127.0.0.1:0")
+	t.Setenv("ACERYX_VAULT_ROOT", t.TempDir())
+	t.Setenv("ACERYX_FEATURES", "steps.agent=false,steps.typo")
+	t.Setenv("ACERYX_ARTIFACTS_BACKEND", "ftp")
+
+	var out bytes.Buffer
+	err := runDoctor([]string{"--server", srv.URL, "--output", "json"}, &out)
+	if !errors.Is(err, errDoctorFailed) {
+		t.Fatalf("expected errDoctorFailed, got %v", err)
+	}
+	var report struct {
+		Checks []doctorResult `json:"checks"`
+	}
+	if err := json.Unmarshal(out.Bytes(), &report); err != nil {
+		t.Fatalf("decode report: %v\n%s", err, out.String())
+	}
+	statuses := map[string]doctorResult{}
+	for _, c := range report.Checks {
+		statuses[c.Check] = c
+	}
+	want := map[string]string{
+		"database":          doctorFail,
+		"config.values":     doctorWarn,
+		"secrets.jwt":       doctorFail,
+		"storage":           doctorOK,
+		"storage.artifacts": doctorFail,
+		"port.http":         doctorOK,
+		"protocol.mcp":      doctorWarn,
+		"protocol.triggers": doctorOK,
+		"features.config":   doctorWarn,
+	}
+	for check, status := range want {
+		got, ok := statuses[check]
+		if !ok || got.Status != status {
+			t.Fatalf("%s: got %+v, want status %s", check, got, status)
+		}
+		if status != doctorOK && got.Fix == "" {
+			t.Fatalf("%s: expected a suggested fix", check)
+		}
+	}
+	if !strings.Contains(statuses["features.config"].Detail, "steps.typo") {
+		t.Fatalf("unexpected features detail %q", statuses["features.config"].Detail)
+	}
+}
+
+func TestDoctorFeaturesFlagsDisabledStepsInWorkflows(t *testing.T) {
+	t.Setenv("ACERYX_FEATURES", "steps.plugin=false")
+	report := &doctorReport{}
+	checkDoctorFeatures(report, []publishedWorkflow{{Name: "Intake", AST: `{"steps":[{"id":"a","type":"plugin"},{"id":"b","type":"human_task"}]}`}})
+	last := report.results[len(report.results)-1]
+	if last.Status != doctorFail || !strings.Contains(last.Detail, `workflow "Intake" needs steps.plugin`) {
+		t.Fatalf("unexpected result %+v", last)
+	}
+}
+
+func TestDoctorServerURL(t *testing.T) {
+	t.Setenv("ACERYX_HTTP_ADDR", ":9090")
+	if got := doctorServerURL(""); got != "http://localhost:9090" {
+		t.Fatalf("got %q", got)
+	}
+	if got := doctorServerURL("https://aceryx.example.com/"); got != "https://aceryx.example.com" {
+		t.Fatalf("got %q", got)
+	}
+}
@@ -16,17 +16,42 @@ import (
 
 	"github.com/google/uuid"
 	_ "github.com/jackc/pgx/v5/stdlib"
+	"github.com/neural-chilli/aceryx/api/middleware"
 	frontendassets "github.com/neural-chilli/aceryx/frontend"
+	"github.com/neural-chilli/aceryx/internal/artifacts"
 	"github.com/neural-chilli/aceryx/internal/backup"
+	"github.com/neural-chilli/aceryx/internal/config"
 	"github.com/neural-chilli/aceryx/internal/engine"
 	"github.com/neural-chilli/aceryx/internal/expressions"
+	"github.com/neural-chilli/aceryx/internal/features"
 	internalmigrations "github.com/neural-chilli/aceryx/internal/migrations"
 	"github.com/neural-chilli/aceryx/internal/observability"
+	"github.com/neural-chilli/aceryx/internal/redaction"
+	"github.com/neural-chilli/aceryx/internal/script"
 	"github.com/neural-chilli/aceryx/internal/server"
+	"github.com/neural-chilli/aceryx/internal/workflows"
 )
 
+// scriptWorkerCommand is the hidden subcommand aceryx serve runs each
+// script step under; see script.ServeWorker.
+const scriptWorkerCommand = "script-worker"
+
 func main() {
-	observability.SetupLoggerFromEnv(os.Stdout)
+	if len(os.Args) >= 2 && os.Args[1] == scriptWorkerCommand {
+		// Runs one script step for the serving process; stdout carries the
+		// result, so nothing else may write to it.
+		if err := script.ServeWorker(os.Stdin, os.Stdout); err != nil {
+			fmt.Fprintln(os.Stderr, err)
+			os.Exit(1)
+		}
+		return
+	}
+	configErr := config.ApplyEnvFile(os.Getenv("ACERYX_CONFIG_FILE"))
+	_, logFile := observability.SetupLoggerFromEnv(os.Stdout)
+	if configErr != nil {
+		slog.Error("config file not applied", "error", configErr)
+		os.Exit(1)
+	}
 
 	if len(os.Args) < 2 {
 		printUsage()
@@ -68,8 +93,13 @@ func main() {
 			os.Exit(1)
 		}
 	case "serve":
-		if err := runServe(); err != nil {
+		err := runServe()
+		if err != nil {
 			slog.Error("serve failed", "error", err)
+		}
+		// Finish pending log compressions before the process exits.
+		_ = logFile.Close()
+		if err != nil {
 			os.Exit(1)
 		}
 	case "plugin":
@@ -77,6 +107,42 @@ func main() {
 			slog.Error("plugin command failed", "error", err)
 			os.Exit(1)
 		}
+	case "client":
+		if err := runClient(os.Args[2:]); err != nil {
+			slog.Error("client command failed", "error", err)
+			os.Exit(1)
+		}
+	case "workflows", "flows":
+		if err := runWorkflows(os.Args[2:], os.Stdout); err != nil {
+			slog.Error("workflows command failed", "error", err)
+			os.Exit(1)
+		}
+	case "doctor":
+		if err := runDoctor(os.Args[2:], os.Stdout); err != nil {
+			if errors.Is(err, errDoctorFailed) {
+				os.Exit(1)
+			}
+			slog.Error("doctor failed", "error", err)
+			os.Exit(1)
+		}
+	case "validate":
+		if err := runValidate(os.Args[2:], os.Stdout); err != nil {
+			if errors.Is(err, errValidateFailed) {
+				os.Exit(1)
+			}
+			slog.Error("validate failed", "error", err)
+			os.Exit(1)
+		}
+	case "config":
+		if err := runConfig(os.Args[2:], os.Stdin, os.Stdout); err != nil {
+			slog.Error("config command failed", "error", err)
+			os.Exit(1)
+		}
+	case "connectors", "tools":
+		if err := runConnectors(os.Args[2:], os.Stdout); err != nil {
+			slog.Error("connectors command failed", "error", err)
+			os.Exit(1)
+		}
 	default:
 		printUsage()
 	}
@@ -260,8 +326,36 @@ func runRestore(args []string) error {
 }
 
 func runServe() error {
+	cors, err := middleware.CORSConfigFromEnv()
+	if err != nil {
+		return err
+	}
+	if _, err := features.ConfigFromEnv(); err != nil {
+		return err
+	}
+	if _, err := redaction.FromEnv(); err != nil {
+		return err
+	}
+	if _, err := workflows.ParseLintRules(os.Getenv("ACERYX_LINT_RULES")); err != nil {
+		return fmt.Errorf("ACERYX_LINT_RULES: %w", err)
+	}
 	serverCtx, stopSignals := signal.NotifyContext(context.Background(), os.Interrupt, syscall.SIGTERM)
 	defer stopSignals()
+	if artifacts.BackendConfigured() {
+		if _, err := artifacts.NewObjectStoreFromEnv(serverCtx); err != nil {
+			return fmt.Errorf("artifact store: %w", err)
+		}
+	}
+
+	shutdownTracing, err := observability.SetupTracingFromEnv(serverCtx)
+	if err != nil {
+		return err
+	}
+	defer func() {
+		flushCtx, cancel := context.WithTimeout(context.Background(), 5*time.Second)
+		defer cancel()
+		_ = shutdownTracing(flushCtx)
+	}()
 
 	dbCtx, cancel := context.WithTimeout(context.Background(), 30*time.Second)
 	defer cancel()
@@ -273,19 +367,36 @@ func runServe() error {
 	defer func() { _ = db.Close() }()
 
 	evaluator := expressions.NewEvaluator()
-	eng := engine.New(db, evaluator, engine.Config{})
-	handler := server.NewHandlerWithContext(serverCtx, db, eng, frontendassets.DistFS())
+	eng := engine.New(db, evaluator, engine.Config{
+		Environment:           os.Getenv("ACERYX_ENVIRONMENT"),
+		DisableInputRecording: os.Getenv("ACERYX_RECORD_STEP_INPUTS") == "false",
+		StepLogLines:          envInt("ACERYX_STEP_LOG_LINES", 0),
+		MaxConcurrentSteps:    envInt("ACERYX_MAX_CONCURRENT_STEPS", 0),
+		Lanes:                 laneConfigFromEnv(),
+	})
+	opts := server.Options{CORS: cors}
+	if exe, err := os.Executable(); err == nil {
+		opts.Router.ScriptWorker = []string{exe, scriptWorkerCommand}
+	} else {
+		slog.Warn("script steps run in process without a heap limit", "error", err)
+	}
+	handler := server.NewHandlerWithOptions(serverCtx, db, eng, frontendassets.DistFS(), opts)
+	// Resume steps left active by processes that have gone, including those
+	// aborted during a shutdown drain. Executors are registered by now; the
+	// cluster leader keeps sweeping for steps of replicas that die later.
+	if err := eng.Recover(serverCtx); err != nil {
+		return fmt.Errorf("recover steps: %w", err)
+	}
 	go eng.StartSLAMonitor(serverCtx)
+	go eng.StartTimerMonitor(serverCtx)
+	go eng.StartCancelWatcher(serverCtx)
 
 	addr := os.Getenv("ACERYX_HTTP_ADDR")
 	if addr == "" {
 		addr = ":8080"
 	}
 
-	srv := &http.Server{
-		Addr:    addr,
-		Handler: handler,
-	}
+	srv := newHTTPServer(addr, handler)
 
 	go func() {
 		<-serverCtx.Done()
@@ -299,27 +410,37 @@ func runServe() error {
 	if err != nil && !errors.Is(err, http.ErrServerClosed) {
 		return err
 	}
-	if waitErr := waitForEngineDrain(eng, 20*time.Second); waitErr != nil {
-		return waitErr
+	drainTimeout := envDuration("ACERYX_SHUTDOWN_DRAIN_TIMEOUT", 30*time.Second)
+	slog.Info("draining step executions", "in_flight", len(eng.InFlight()), "timeout", drainTimeout.String())
+	drainCtx, cancelDrain := context.WithTimeout(context.Background(), drainTimeout)
+	defer cancelDrain()
+	if err := eng.Shutdown(drainCtx); err != nil {
+		return err
 	}
 	return nil
 }
 
-func waitForEngineDrain(eng *engine.Engine, timeout time.Duration) error {
-	if eng == nil {
-		return nil
-	}
-	done := make(chan struct{})
-	go func() {
-		eng.Wait()
-		close(done)
-	}()
-	select {
-	case <-done:
-		return nil
-	case <-time.After(timeout):
-		return fmt.Errorf("timed out waiting for engine workers to drain")
-	}
+// newHTTPServer configures the API server's connection handling. HTTP/2 is
+// served without TLS (h2c) for clients and proxies that speak it; TLS is
+// expected to terminate in front of Aceryx.
+func newHTTPServer(addr string, handler http.Handler) *http.Server {
+	srv := &http.Server{
+		Addr:              addr,
+		Handler:           handler,
+		ReadHeaderTimeout: envDuration("ACERYX_HTTP_READ_HEADER_TIMEOUT", 10*time.Second),
+		IdleTimeout:       envDuration("ACERYX_HTTP_IDLE_TIMEOUT", 2*time.Minute),
+		MaxHeaderBytes:    envInt("ACERYX_HTTP_MAX_HEADER_BYTES", http.DefaultMaxHeaderBytes),
+		HTTP2: &http.HTTP2Config{
+			MaxConcurrentStreams: envInt("ACERYX_HTTP2_MAX_CONCURRENT_STREAMS", 250),
+			MaxReadFrameSize:     envInt("ACERYX_HTTP2_MAX_READ_FRAME_SIZE", 1<<20),
+			SendPingTimeout:      envDuration("ACERYX_HTTP2_PING_INTERVAL", 30*time.Second),
+			PingTimeout:          envDuration("ACERYX_HTTP2_PING_TIMEOUT", 15*time.Second),
+		},
+		Protocols: new(http.Protocols),
+	}
+	srv.Protocols.SetHTTP1(true)
+	srv.Protocols.SetUnencryptedHTTP2(os.Getenv("ACERYX_HTTP2") != "false")
+	return srv
 }
 
 func openDatabase(ctx context.Context) (*sql.DB, error) {
@@ -357,6 +478,19 @@ func configureDBPool(db *sql.DB) {
 	db.SetConnMaxIdleTime(envDuration("ACERYX_DB_CONN_MAX_IDLE_TIME", 15*time.Minute))
 }
 
+// laneConfigFromEnv leaves a lane map nil when its variable is unset, so the
+// engine applies its default split.
+func laneConfigFromEnv() engine.LaneConfig {
+	cfg := engine.LaneConfig{MaxWait: envDuration("ACERYX_LANE_MAX_WAIT", engine.DefaultLaneMaxWait)}
+	if n := envInt("ACERYX_LANE_INTERACTIVE_RESERVED", 0); n > 0 {
+		cfg.Reserved = map[engine.Lane]int{engine.LaneInteractive: n}
+	}
+	if n := envInt("ACERYX_LANE_BATCH_LIMIT", 0); n > 0 {
+		cfg.Limit = map[engine.Lane]int{engine.LaneBatch: n}
+	}
+	return cfg
+}
+
 func envInt(name string, fallback int) int {
 	value := os.Getenv(name)
 	if value == "" {
@@ -403,9 +537,15 @@ func resolveVaultPath() string {
 
 func printUsage() {
 	fmt.Println("aceryx - case orchestration engine")
-	fmt.Println("usage: aceryx [serve|migrate|seed|backup|restore|plugin|version]")
+	fmt.Println("usage: aceryx [serve|migrate|seed|backup|restore|plugin|client|workflows|validate|connectors|config|doctor|version]")
 	fmt.Println("backup usage: aceryx backup --output /path/to/backup.tar.gz [--tenant <tenant_id>] [--pause]")
 	fmt.Println("backup verify usage: aceryx backup verify --input /path/to/backup.tar.gz")
 	fmt.Println("restore usage: aceryx restore --input /path/to/backup.tar.gz [--target-db <connection_string>] --confirm")
 	fmt.Println("plugin usage: aceryx plugin [init|build|test|validate]")
+	fmt.Println("client usage: aceryx client generate --lang ts|python --out <dir> [--spec <openapi file>|--server <url>]")
+	fmt.Println("workflows usage: aceryx workflows [list|show|create|delete|execute|export|import|docs] [--server <url>] [--api-key <key>|--token <token>] [--output table|json]")
+	fmt.Println("validate usage: aceryx validate [--lint] [--output table|json] <workflow.yaml>")
+	fmt.Println("doctor usage: aceryx doctor [--server <url>] [--timeout <duration>] [--output table|json]")
+	fmt.Println("connectors usage: aceryx connectors [list|show] [--server <url>] [--api-key <key>|--token <token>] [--output table|json]")
+	fmt.Println("config usage: aceryx config [genkey|encrypt [--value <value>]]")
 }
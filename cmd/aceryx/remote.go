@@ -0,0 +1,127 @@
+package main
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"flag"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"strings"
+	"time"
+)
+
+// apiClient calls a running Aceryx server on behalf of CLI commands. The
+// credential is a session token or an API key; the server accepts either as a
+// bearer token.
+type apiClient struct {
+	server string
+	token  string
+	http   *http.Client
+}
+
+// remoteCommand holds the flags shared by every subcommand that talks to a
+// server over the REST API rather than to the local database.
+type remoteCommand struct {
+	fs     *flag.FlagSet
+	server *string
+	token  *string
+	apiKey *string
+	output *string
+}
+
+func newRemoteCommand(group, name string) *remoteCommand {
+	fs := flag.NewFlagSet(group+" "+name, flag.ContinueOnError)
+	return &remoteCommand{
+		fs:     fs,
+		server: fs.String("server", firstNonEmptyEnv("ACERYX_URL", "http://localhost:8080"), "Aceryx server URL"),
+		token:  fs.String("token", os.Getenv("ACERYX_TOKEN"), "bearer token (defaults to ACERYX_TOKEN)"),
+		apiKey: fs.String("api-key", os.Getenv("ACERYX_API_KEY"), "API key (defaults to ACERYX_API_KEY); takes precedence over --token"),
+		output: fs.String("output", "table", "output format (table|json)"),
+	}
+}
+
+// parse accepts flags before or after positional arguments and checks the
+// output format and server URL.
+func (c *remoteCommand) parse(args []string) ([]string, error) {
+	var positional []string
+	for {
+		if err := c.fs.Parse(args); err != nil {
+			return nil, err
+		}
+		args = c.fs.Args()
+		if len(args) == 0 {
+			break
+		}
+		positional = append(positional, args[0])
+		args = args[1:]
+	}
+	if *c.output != "table" && *c.output != "json" {
+		return nil, fmt.Errorf("--output must be table or json")
+	}
+	server := strings.TrimSpace(*c.server)
+	if !strings.HasPrefix(server, "http://") && !strings.HasPrefix(server, "https://") {
+		return nil, fmt.Errorf("--server must be an http or https URL")
+	}
+	return positional, nil
+}
+
+func (c *remoteCommand) client() *apiClient {
+	return &apiClient{
+		server: strings.TrimRight(strings.TrimSpace(*c.server), "/"),
+		token:  firstNonEmptyString(strings.TrimSpace(*c.apiKey), strings.TrimSpace(*c.token)),
+		http:   &http.Client{Timeout: 60 * time.Second},
+	}
+}
+
+func (c *apiClient) doJSON(ctx context.Context, method, path string, in any) ([]byte, error) {
+	body, err := json.Marshal(in)
+	if err != nil {
+		return nil, err
+	}
+	return c.do(ctx, method, path, "application/json", bytes.NewReader(body))
+}
+
+func (c *apiClient) do(ctx context.Context, method, path, contentType string, body io.Reader) ([]byte, error) {
+	req, err := http.NewRequestWithContext(ctx, method, c.server+path, body)
+	if err != nil {
+		return nil, fmt.Errorf("build request: %w", err)
+	}
+	if contentType != "" {
+		req.Header.Set("Content-Type", contentType)
+	}
+	if c.token != "" {
+		req.Header.Set("Authorization", "Bearer "+c.token)
+	}
+	res, err := c.http.Do(req)
+	if err != nil {
+		return nil, fmt.Errorf("%s %s: %w", method, path, err)
+	}
+	defer func() { _ = res.Body.Close() }()
+	raw, err := io.ReadAll(io.LimitReader(res.Body, 32<<20))
+	if err != nil {
+		return nil, fmt.Errorf("read response: %w", err)
+	}
+	if res.StatusCode >= http.StatusMultipleChoices {
+		var apiErr struct {
+			Error string `json:"error"`
+		}
+		if json.Unmarshal(raw, &apiErr) == nil && apiErr.Error != "" {
+			return nil, fmt.Errorf("%s %s: %s (status %d)", method, path, apiErr.Error, res.StatusCode)
+		}
+		return nil, fmt.Errorf("%s %s: status %d", method, path, res.StatusCode)
+	}
+	return raw, nil
+}
+
+func writeIndentedJSON(out io.Writer, raw []byte) error {
+	var buf bytes.Buffer
+	if err := json.Indent(&buf, raw, "", "  "); err != nil {
+		return fmt.Errorf("format json: %w", err)
+	}
+	buf.WriteByte('\n')
+	_, err := out.Write(buf.Bytes())
+	return err
+}
@@ -0,0 +1,100 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"flag"
+	"fmt"
+	"io"
+	"os"
+	"text/tabwriter"
+
+	"github.com/neural-chilli/aceryx/internal/engine"
+	"github.com/neural-chilli/aceryx/internal/workflows"
+)
+
+const validateUsage = "usage: aceryx validate [--lint] [--output table|json] <workflow.yaml>"
+
+// errValidateFailed is returned when the definition is invalid or, with
+// --lint, has an error-severity finding, so scripts can rely on the exit
+// code.
+var errValidateFailed = errors.New("workflow validation failed")
+
+type validateResult struct {
+	Valid bool                    `json:"valid"`
+	Error string                  `json:"error,omitempty"`
+	Lint  []workflows.LintFinding `json:"lint,omitempty"`
+}
+
+// runValidate checks a local YAML workflow definition the way saving a draft
+// does, and with --lint runs the lint rules configured by ACERYX_LINT_RULES.
+func runValidate(args []string, out io.Writer) error {
+	fs := flag.NewFlagSet("validate", flag.ContinueOnError)
+	lint := fs.Bool("lint", false, "also report lint findings")
+	output := fs.String("output", "table", "output format (table|json)")
+	if err := fs.Parse(args); err != nil {
+		return err
+	}
+	if fs.NArg() != 1 {
+		return fmt.Errorf("%s", validateUsage)
+	}
+	if *output != "table" && *output != "json" {
+		return fmt.Errorf("--output must be table or json")
+	}
+	source, err := os.ReadFile(fs.Arg(0))
+	if err != nil {
+		return fmt.Errorf("read workflow: %w", err)
+	}
+	astRaw, err := workflows.ParseYAML(string(source))
+	if err != nil {
+		return err
+	}
+
+	result := validateResult{Valid: true}
+	if err := workflows.ValidateDefinition(astRaw); err != nil {
+		result.Valid = false
+		result.Error = err.Error()
+	}
+	if *lint {
+		rules, err := workflows.ParseLintRules(os.Getenv("ACERYX_LINT_RULES"))
+		if err != nil {
+			return fmt.Errorf("ACERYX_LINT_RULES: %w", err)
+		}
+		var ast engine.WorkflowAST
+		if err := json.Unmarshal(astRaw, &ast); err == nil {
+			result.Lint = workflows.Lint(ast, string(source), rules)
+		}
+	}
+
+	if err := writeValidateResult(out, result, *output); err != nil {
+		return err
+	}
+	if !result.Valid || workflows.HasLintErrors(result.Lint) {
+		return errValidateFailed
+	}
+	return nil
+}
+
+func writeValidateResult(out io.Writer, result validateResult, output string) error {
+	if output == "json" {
+		raw, err := json.Marshal(result)
+		if err != nil {
+			return err
+		}
+		return writeIndentedJSON(out, raw)
+	}
+	if result.Valid {
+		_, _ = fmt.Fprintln(out, "valid")
+	} else {
+		_, _ = fmt.Fprintf(out, "invalid: %s\n", result.Error)
+	}
+	if len(result.Lint) == 0 {
+		return nil
+	}
+	tw := tabwriter.NewWriter(out, 0, 4, 2, ' ', 0)
+	_, _ = fmt.Fprintln(tw, "SEVERITY\tRULE\tSTEP\tMESSAGE")
+	for _, finding := range result.Lint {
+		_, _ = fmt.Fprintf(tw, "%s\t%s\t%s\t%s\n", finding.Severity, finding.Rule, finding.StepID, finding.Message)
+	}
+	return tw.Flush()
+}
@@ -0,0 +1,51 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestValidateLintFailsOnErrorFindings(t *testing.T) {
+	t.Setenv("ACERYX_LINT_RULES", "")
+	path := filepath.Join(t.TempDir(), "flow.yaml")
+	source := `steps:
+  - id: fetch
+    type: integration
+    config:
+      connector: http
+      action: request
+      input:
+        url: https://api.example.com
+        password: hunter2hunter2
+`
+	if err := os.WriteFile(path, []byte(source), 0o600); err != nil {
+		t.Fatalf("write fixture: %v", err)
+	}
+
+	var out bytes.Buffer
+	if err := runValidate([]string{path}, &out); err != nil {
+		t.Fatalf("validate without --lint: %v\n%s", err, out.String())
+	}
+
+	out.Reset()
+	err := runValidate([]string{"--lint", "--output", "json", path}, &out)
+	if !errors.Is(err, errValidateFailed) {
+		t.Fatalf("expected errValidateFailed, got %v", err)
+	}
+	var result validateResult
+	if err := json.Unmarshal(out.Bytes(), &result); err != nil {
+		t.Fatalf("decode result: %v\n%s", err, out.String())
+	}
+	if !result.Valid || len(result.Lint) != 2 || result.Lint[1].Field != "config.input.password" {
+		t.Fatalf("unexpected result %+v", result)
+	}
+
+	t.Setenv("ACERYX_LINT_RULES", "hardcoded-credential=warning")
+	if err := runValidate([]string{"--lint", path}, &out); err != nil {
+		t.Fatalf("expected warnings only to pass, got %v", err)
+	}
+}
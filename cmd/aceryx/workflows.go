@@ -0,0 +1,390 @@
+package main
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"io"
+	"mime/multipart"
+	"net/http"
+	"net/url"
+	"os"
+	"path/filepath"
+	"text/tabwriter"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+const workflowsUsage = "usage: aceryx workflows [list|show|create|delete|execute|export|import|docs] [--server <url>] [--api-key <key>|--token <token>] [--output table|json]"
+
+// cliWorkflow mirrors the server's workflow representation.
+type cliWorkflow struct {
+	ID                string    `json:"id"`
+	Name              string    `json:"name"`
+	CaseTypeID        string    `json:"case_type_id"`
+	CreatedAt         time.Time `json:"created_at"`
+	UpdatedAt         time.Time `json:"updated_at"`
+	PublishedVersions []struct {
+		Version     int        `json:"version"`
+		PublishedAt *time.Time `json:"published_at"`
+	} `json:"published_versions"`
+}
+
+func runWorkflows(args []string, out io.Writer) error {
+	if len(args) == 0 {
+		return fmt.Errorf("%s", workflowsUsage)
+	}
+	ctx := context.Background()
+	switch args[0] {
+	case "list":
+		return runWorkflowsList(ctx, args[1:], out)
+	case "show":
+		return runWorkflowsShow(ctx, args[1:], out)
+	case "create":
+		return runWorkflowsCreate(ctx, args[1:], out)
+	case "delete":
+		return runWorkflowsDelete(ctx, args[1:], out)
+	case "execute":
+		return runWorkflowsExecute(ctx, args[1:], out)
+	case "export":
+		return runWorkflowsExport(ctx, args[1:], out)
+	case "import":
+		return runWorkflowsImport(ctx, args[1:], out)
+	case "docs":
+		return runWorkflowsDocs(ctx, args[1:], out)
+	default:
+		return fmt.Errorf("unknown workflows subcommand: %s", args[0])
+	}
+}
+
+func runWorkflowsList(ctx context.Context, args []string, out io.Writer) error {
+	cmd := newRemoteCommand("workflows", "list")
+	if _, err := cmd.parse(args); err != nil {
+		return err
+	}
+	raw, err := cmd.client().do(ctx, http.MethodGet, "/workflows", "", nil)
+	if err != nil {
+		return err
+	}
+	if *cmd.output == "json" {
+		return writeIndentedJSON(out, raw)
+	}
+	var page struct {
+		Items []cliWorkflow `json:"items"`
+	}
+	if err := json.Unmarshal(raw, &page); err != nil {
+		return fmt.Errorf("decode workflows: %w", err)
+	}
+	tw := tabwriter.NewWriter(out, 0, 4, 2, ' ', 0)
+	_, _ = fmt.Fprintln(tw, "ID\tNAME\tCASE TYPE\tPUBLISHED\tUPDATED")
+	for _, wf := range page.Items {
+		_, _ = fmt.Fprintf(tw, "%s\t%s\t%s\t%s\t%s\n", wf.ID, wf.Name, wf.CaseTypeID, latestPublished(wf), wf.UpdatedAt.Format(time.RFC3339))
+	}
+	return tw.Flush()
+}
+
+func runWorkflowsShow(ctx context.Context, args []string, out io.Writer) error {
+	cmd := newRemoteCommand("workflows", "show")
+	positional, err := cmd.parse(args)
+	if err != nil {
+		return err
+	}
+	id, err := workflowIDArg(positional)
+	if err != nil {
+		return err
+	}
+	raw, err := cmd.client().do(ctx, http.MethodGet, "/workflows/"+id, "", nil)
+	if err != nil {
+		return err
+	}
+	if *cmd.output == "json" {
+		return writeIndentedJSON(out, raw)
+	}
+	var wf cliWorkflow
+	if err := json.Unmarshal(raw, &wf); err != nil {
+		return fmt.Errorf("decode workflow: %w", err)
+	}
+	tw := tabwriter.NewWriter(out, 0, 4, 2, ' ', 0)
+	_, _ = fmt.Fprintf(tw, "ID\t%s\n", wf.ID)
+	_, _ = fmt.Fprintf(tw, "Name\t%s\n", wf.Name)
+	_, _ = fmt.Fprintf(tw, "Case type\t%s\n", wf.CaseTypeID)
+	_, _ = fmt.Fprintf(tw, "Published\t%s\n", latestPublished(wf))
+	_, _ = fmt.Fprintf(tw, "Created\t%s\n", wf.CreatedAt.Format(time.RFC3339))
+	_, _ = fmt.Fprintf(tw, "Updated\t%s\n", wf.UpdatedAt.Format(time.RFC3339))
+	return tw.Flush()
+}
+
+func runWorkflowsCreate(ctx context.Context, args []string, out io.Writer) error {
+	cmd := newRemoteCommand("workflows", "create")
+	name := cmd.fs.String("name", "", "workflow name")
+	caseType := cmd.fs.String("case-type", "", "case type the workflow runs for")
+	file := cmd.fs.String("file", "", "optional YAML definition to import as the first draft")
+	if _, err := cmd.parse(args); err != nil {
+		return err
+	}
+	if *name == "" || *caseType == "" {
+		return fmt.Errorf("--name and --case-type are required")
+	}
+	client := cmd.client()
+	raw, err := client.doJSON(ctx, http.MethodPost, "/workflows", map[string]string{"name": *name, "case_type_id": *caseType})
+	if err != nil {
+		return err
+	}
+	var wf cliWorkflow
+	if err := json.Unmarshal(raw, &wf); err != nil {
+		return fmt.Errorf("decode workflow: %w", err)
+	}
+	if *file != "" {
+		if err := client.importYAML(ctx, wf.ID, *file); err != nil {
+			return fmt.Errorf("workflow %s created but import failed: %w", wf.ID, err)
+		}
+	}
+	if *cmd.output == "json" {
+		return writeIndentedJSON(out, raw)
+	}
+	_, err = fmt.Fprintf(out, "created workflow %s (%s)\n", wf.ID, wf.Name)
+	return err
+}
+
+func runWorkflowsDelete(ctx context.Context, args []string, out io.Writer) error {
+	cmd := newRemoteCommand("workflows", "delete")
+	positional, err := cmd.parse(args)
+	if err != nil {
+		return err
+	}
+	id, err := workflowIDArg(positional)
+	if err != nil {
+		return err
+	}
+	if _, err := cmd.client().do(ctx, http.MethodDelete, "/workflows/"+id, "", nil); err != nil {
+		return err
+	}
+	if *cmd.output == "json" {
+		return json.NewEncoder(out).Encode(map[string]string{"id": id, "status": "deleted"})
+	}
+	_, err = fmt.Fprintf(out, "deleted workflow %s\n", id)
+	return err
+}
+
+// runWorkflowsExecute starts a case of the workflow's case type, which runs
+// the latest published version of the workflow.
+func runWorkflowsExecute(ctx context.Context, args []string, out io.Writer) error {
+	cmd := newRemoteCommand("workflows", "execute")
+	data := cmd.fs.String("data", "{}", "case data as a JSON object")
+	dataFile := cmd.fs.String("data-file", "", "read case data from a JSON file")
+	priority := cmd.fs.Int("priority", 0, "case priority")
+	positional, err := cmd.parse(args)
+	if err != nil {
+		return err
+	}
+	id, err := workflowIDArg(positional)
+	if err != nil {
+		return err
+	}
+	rawData := []byte(*data)
+	if *dataFile != "" {
+		if rawData, err = os.ReadFile(*dataFile); err != nil {
+			return fmt.Errorf("read --data-file: %w", err)
+		}
+	}
+	var caseData map[string]any
+	if err := json.Unmarshal(rawData, &caseData); err != nil {
+		return fmt.Errorf("case data must be a JSON object: %w", err)
+	}
+
+	client := cmd.client()
+	raw, err := client.do(ctx, http.MethodGet, "/workflows/"+id, "", nil)
+	if err != nil {
+		return err
+	}
+	var wf cliWorkflow
+	if err := json.Unmarshal(raw, &wf); err != nil {
+		return fmt.Errorf("decode workflow: %w", err)
+	}
+	caseType, err := client.caseTypeName(ctx, wf.CaseTypeID)
+	if err != nil {
+		return err
+	}
+	raw, err = client.doJSON(ctx, http.MethodPost, "/cases", map[string]any{"case_type": caseType, "data": caseData, "priority": *priority})
+	if err != nil {
+		return err
+	}
+	if *cmd.output == "json" {
+		return writeIndentedJSON(out, raw)
+	}
+	var created struct {
+		ID         string `json:"id"`
+		CaseNumber string `json:"case_number"`
+		Status     string `json:"status"`
+	}
+	if err := json.Unmarshal(raw, &created); err != nil {
+		return fmt.Errorf("decode case: %w", err)
+	}
+	_, err = fmt.Fprintf(out, "started case %s (%s) status=%s\n", created.CaseNumber, created.ID, created.Status)
+	return err
+}
+
+func runWorkflowsExport(ctx context.Context, args []string, out io.Writer) error {
+	cmd := newRemoteCommand("workflows", "export")
+	version := cmd.fs.Int("version", 0, "version to export (default: latest published, else draft)")
+	diagram := cmd.fs.String("diagram", "", "export a mermaid or dot diagram of the steps instead of YAML")
+	outPath := cmd.fs.String("out", "", "write the export to this file instead of stdout")
+	positional, err := cmd.parse(args)
+	if err != nil {
+		return err
+	}
+	id, err := workflowIDArg(positional)
+	if err != nil {
+		return err
+	}
+	path := "/workflows/" + id + "/yaml/latest"
+	if *version > 0 {
+		path = fmt.Sprintf("/workflows/%s/yaml/%d", id, *version)
+	}
+	if *diagram != "" {
+		if *diagram != "mermaid" && *diagram != "dot" {
+			return fmt.Errorf("--diagram must be mermaid or dot")
+		}
+		query := url.Values{"format": {*diagram}}
+		if *version > 0 {
+			query.Set("version", fmt.Sprint(*version))
+		}
+		path = "/workflows/" + id + "/diagram?" + query.Encode()
+	}
+	raw, err := cmd.client().do(ctx, http.MethodGet, path, "", nil)
+	if err != nil {
+		return err
+	}
+	if *outPath != "" {
+		return os.WriteFile(*outPath, raw, 0o644)
+	}
+	_, err = out.Write(raw)
+	return err
+}
+
+func runWorkflowsImport(ctx context.Context, args []string, out io.Writer) error {
+	cmd := newRemoteCommand("workflows", "import")
+	file := cmd.fs.String("file", "", "YAML definition to import")
+	publish := cmd.fs.Bool("publish", false, "publish the draft after importing")
+	positional, err := cmd.parse(args)
+	if err != nil {
+		return err
+	}
+	id, err := workflowIDArg(positional)
+	if err != nil {
+		return err
+	}
+	if *file == "" {
+		return fmt.Errorf("--file is required")
+	}
+	client := cmd.client()
+	if err := client.importYAML(ctx, id, *file); err != nil {
+		return err
+	}
+	status := "imported"
+	if *publish {
+		if _, err := client.do(ctx, http.MethodPost, "/workflows/"+id+"/publish", "", nil); err != nil {
+			return fmt.Errorf("draft imported but publish failed: %w", err)
+		}
+		status = "published"
+	}
+	if *cmd.output == "json" {
+		return json.NewEncoder(out).Encode(map[string]string{"id": id, "status": status})
+	}
+	_, err = fmt.Fprintf(out, "%s workflow %s from %s\n", status, id, *file)
+	return err
+}
+
+func runWorkflowsDocs(ctx context.Context, args []string, out io.Writer) error {
+	cmd := newRemoteCommand("workflows", "docs")
+	format := cmd.fs.String("format", "markdown", "markdown or html")
+	version := cmd.fs.Int("version", 0, "version to document (default: latest published, else draft)")
+	outPath := cmd.fs.String("out", "", "write the docs to this file instead of stdout")
+	positional, err := cmd.parse(args)
+	if err != nil {
+		return err
+	}
+	id, err := workflowIDArg(positional)
+	if err != nil {
+		return err
+	}
+	if *format != "markdown" && *format != "html" {
+		return fmt.Errorf("--format must be markdown or html")
+	}
+	query := url.Values{"format": {*format}}
+	if *version > 0 {
+		query.Set("version", fmt.Sprint(*version))
+	}
+	raw, err := cmd.client().do(ctx, http.MethodGet, "/workflows/"+id+"/docs?"+query.Encode(), "", nil)
+	if err != nil {
+		return err
+	}
+	if *outPath != "" {
+		return os.WriteFile(*outPath, raw, 0o644)
+	}
+	_, err = out.Write(raw)
+	return err
+}
+
+func (c *apiClient) importYAML(ctx context.Context, workflowID, path string) error {
+	source, err := os.ReadFile(path)
+	if err != nil {
+		return fmt.Errorf("read %s: %w", path, err)
+	}
+	var body bytes.Buffer
+	form := multipart.NewWriter(&body)
+	part, err := form.CreateFormFile("file", filepath.Base(path))
+	if err != nil {
+		return err
+	}
+	if _, err := part.Write(source); err != nil {
+		return err
+	}
+	if err := form.Close(); err != nil {
+		return err
+	}
+	_, err = c.do(ctx, http.MethodPut, "/workflows/"+workflowID+"/yaml/draft", form.FormDataContentType(), &body)
+	return err
+}
+
+// caseTypeName resolves a workflow's case type reference, which is either a
+// case type name or its ID, to the name POST /cases expects.
+func (c *apiClient) caseTypeName(ctx context.Context, ref string) (string, error) {
+	if _, err := uuid.Parse(ref); err != nil {
+		return ref, nil
+	}
+	raw, err := c.do(ctx, http.MethodGet, "/case-types/"+url.PathEscape(ref), "", nil)
+	if err != nil {
+		return "", fmt.Errorf("resolve case type %s: %w", ref, err)
+	}
+	var ct struct {
+		Name string `json:"name"`
+	}
+	if err := json.Unmarshal(raw, &ct); err != nil {
+		return "", fmt.Errorf("decode case type: %w", err)
+	}
+	return ct.Name, nil
+}
+
+func workflowIDArg(positional []string) (string, error) {
+	if len(positional) != 1 {
+		return "", fmt.Errorf("expected one workflow id")
+	}
+	if _, err := uuid.Parse(positional[0]); err != nil {
+		return "", fmt.Errorf("invalid workflow id %q", positional[0])
+	}
+	return positional[0], nil
+}
+
+func latestPublished(wf cliWorkflow) string {
+	if len(wf.PublishedVersions) == 0 {
+		return "-"
+	}
+	latest := wf.PublishedVersions[0].Version
+	for _, v := range wf.PublishedVersions[1:] {
+		latest = max(latest, v.Version)
+	}
+	return fmt.Sprintf("v%d", latest)
+}
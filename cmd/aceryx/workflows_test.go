@@ -0,0 +1,149 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"io"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+)
+
+const testWorkflowID = "6f1c2a44-9d1e-4c55-8f0b-1a2b3c4d5e6f"
+
+func newWorkflowsTestServer(t *testing.T, requests *[]string) *httptest.Server {
+	t.Helper()
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Header.Get("Authorization") != "Bearer tok" {
+			w.WriteHeader(http.StatusUnauthorized)
+			_, _ = w.Write([]byte(`{"error":"unauthenticated"}`))
+			return
+		}
+		body, _ := io.ReadAll(r.Body)
+		*requests = append(*requests, r.Method+" "+r.URL.Path+" "+string(body))
+		w.Header().Set("Content-Type", "application/json")
+		switch r.Method + " " + r.URL.Path {
+		case "GET /workflows":
+			_, _ = w.Write([]byte(`{"items":[{"id":"` + testWorkflowID + `","name":"Onboarding","case_type_id":"onboarding","published_versions":[{"version":1},{"version":3}]}],"total":1}`))
+		case "GET /workflows/" + testWorkflowID:
+			_, _ = w.Write([]byte(`{"id":"` + testWorkflowID + `","name":"Onboarding","case_type_id":"onboarding"}`))
+		case "POST /cases":
+			w.WriteHeader(http.StatusCreated)
+			_, _ = w.Write([]byte(`{"id":"c1","case_number":"ONB-000001","status":"open"}`))
+		case "GET /workflows/" + testWorkflowID + "/docs":
+			w.Header().Set("Content-Type", "text/markdown")
+			_, _ = w.Write([]byte("# Onboarding (" + r.URL.Query().Get("format") + " v" + r.URL.Query().Get("version") + ")\n"))
+		case "GET /workflows/" + testWorkflowID + "/diagram":
+			_, _ = w.Write([]byte("digraph (" + r.URL.RawQuery + ")\n"))
+		case "DELETE /workflows/" + testWorkflowID:
+			w.WriteHeader(http.StatusConflict)
+			_, _ = w.Write([]byte(`{"error":"workflow_in_use"}`))
+		default:
+			w.WriteHeader(http.StatusNotFound)
+		}
+	}))
+	t.Cleanup(srv.Close)
+	return srv
+}
+
+func TestWorkflowsListTableAndJSON(t *testing.T) {
+	var requests []string
+	srv := newWorkflowsTestServer(t, &requests)
+
+	var out bytes.Buffer
+	if err := runWorkflows([]string{"list", "--server", srv.URL, "--token", "tok"}, &out); err != nil {
+		t.Fatalf("list: %v", err)
+	}
+	if !strings.Contains(out.String(), "Onboarding") || !strings.Contains(out.String(), "v3") {
+		t.Fatalf("unexpected table output:\n%s", out.String())
+	}
+
+	out.Reset()
+	if err := runWorkflows([]string{"list", "--output", "json", "--server", srv.URL, "--token", "tok"}, &out); err != nil {
+		t.Fatalf("list json: %v", err)
+	}
+	var decoded map[string]any
+	if err := json.Unmarshal(out.Bytes(), &decoded); err != nil || decoded["total"] != float64(1) {
+		t.Fatalf("expected server JSON, got %s (%v)", out.String(), err)
+	}
+}
+
+func TestWorkflowsExecuteCreatesCaseForCaseType(t *testing.T) {
+	var requests []string
+	srv := newWorkflowsTestServer(t, &requests)
+
+	var out bytes.Buffer
+	err := runWorkflows([]string{"execute", testWorkflowID, "--data", `{"amount":10}`, "--server", srv.URL, "--token", "tok"}, &out)
+	if err != nil {
+		t.Fatalf("execute: %v", err)
+	}
+	if !strings.Contains(out.String(), "ONB-000001") {
+		t.Fatalf("unexpected output %q", out.String())
+	}
+	last := requests[len(requests)-1]
+	if !strings.HasPrefix(last, "POST /cases ") || !strings.Contains(last, `"case_type":"onboarding"`) || !strings.Contains(last, `"amount":10`) {
+		t.Fatalf("unexpected create request %q", last)
+	}
+}
+
+func TestWorkflowsDocsPassesFormatAndVersion(t *testing.T) {
+	var requests []string
+	srv := newWorkflowsTestServer(t, &requests)
+
+	var out bytes.Buffer
+	if err := runWorkflows([]string{"docs", testWorkflowID, "--format", "html", "--version", "2", "--server", srv.URL, "--token", "tok"}, &out); err != nil {
+		t.Fatalf("docs: %v", err)
+	}
+	if out.String() != "# Onboarding (html v2)\n" {
+		t.Fatalf("unexpected docs output %q", out.String())
+	}
+	if err := runWorkflows([]string{"docs", testWorkflowID, "--format", "pdf", "--server", srv.URL, "--token", "tok"}, &out); err == nil {
+		t.Fatal("expected an unsupported format to be rejected")
+	}
+}
+
+func TestWorkflowsExportDiagram(t *testing.T) {
+	var requests []string
+	srv := newWorkflowsTestServer(t, &requests)
+
+	var out bytes.Buffer
+	if err := runWorkflows([]string{"export", testWorkflowID, "--diagram", "dot", "--version", "3", "--server", srv.URL, "--token", "tok"}, &out); err != nil {
+		t.Fatalf("export diagram: %v", err)
+	}
+	if out.String() != "digraph (format=dot&version=3)\n" {
+		t.Fatalf("unexpected diagram output %q", out.String())
+	}
+	if err := runWorkflows([]string{"export", testWorkflowID, "--diagram", "svg", "--server", srv.URL, "--token", "tok"}, &out); err == nil {
+		t.Fatal("expected an unsupported diagram format to be rejected")
+	}
+}
+
+func TestWorkflowsReportsServerErrors(t *testing.T) {
+	var requests []string
+	srv := newWorkflowsTestServer(t, &requests)
+
+	err := runWorkflows([]string{"delete", testWorkflowID, "--server", srv.URL, "--token", "tok"}, io.Discard)
+	if err == nil || !strings.Contains(err.Error(), "workflow_in_use") {
+		t.Fatalf("expected workflow_in_use error, got %v", err)
+	}
+	if err := runWorkflows([]string{"show", "not-a-uuid", "--server", srv.URL}, io.Discard); err == nil {
+		t.Fatal("expected invalid id error")
+	}
+	if err := runWorkflows([]string{"list", "--output", "xml", "--server", srv.URL}, io.Discard); err == nil {
+		t.Fatal("expected invalid output error")
+	}
+}
+
+func TestWorkflowsAPIKeyTakesPrecedenceOverToken(t *testing.T) {
+	var requests []string
+	srv := newWorkflowsTestServer(t, &requests)
+	t.Setenv("ACERYX_API_KEY", "tok")
+
+	if err := runWorkflows([]string{"list", "--server", srv.URL, "--token", "stale"}, io.Discard); err != nil {
+		t.Fatalf("list with api key: %v", err)
+	}
+	if err := runWorkflows([]string{"list", "--server", "localhost:8080"}, io.Discard); err == nil {
+		t.Fatal("expected error for server without scheme")
+	}
+}
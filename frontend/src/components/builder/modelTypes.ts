@@ -18,6 +18,7 @@ export type WorkflowStep = {
   condition?: string
   join?: 'all' | 'any' | string
   error_policy?: Record<string, unknown>
+  timeout_seconds?: number
   position?: { x: number; y: number }
   [key: string]: unknown
 }
@@ -2,7 +2,7 @@ import { computed, ref } from 'vue'
 import { useBranding } from './useBranding'
 import { useTerminology } from './useTerminology'
 import { useTheme } from './useTheme'
-import type { Branding, LoginResponse, Principal, Theme, UserPreferences } from '../types'
+import type { Branding, DisplayPreferences, DisplayPreferencesView, LoginResponse, Principal, Theme, UserPreferences } from '../types'
 
 const TOKEN_KEY = 'acx_session_token'
 const API_BASE = import.meta.env.MODE === 'test' ? '' : '/api'
@@ -11,7 +11,9 @@ const currentUser = ref<Principal | null>(null)
 const tenantBranding = ref<Branding | null>(null)
 const themes = ref<Theme[]>([])
 const preferences = ref<UserPreferences | null>(null)
+const display = ref<DisplayPreferences | null>(null)
 const sessionExpired = ref(false)
+let restoring: Promise<boolean> | null = null
 
 function normalizeTheme(raw: Theme): Theme {
   const overrides = (raw.overrides ?? {}) as unknown
@@ -27,6 +29,8 @@ function clearSession() {
   tenantBranding.value = null
   themes.value = []
   preferences.value = null
+  display.value = null
+  restoring = null
   sessionStorage.removeItem(TOKEN_KEY)
 }
 
@@ -42,7 +46,7 @@ function apiURL(input: string): string {
 
 export function useAuth() {
   const { apply: applyBranding } = useBranding()
-  const { apply: applyTheme } = useTheme()
+  const { apply: applyTheme, applyDisplay } = useTheme()
 
   const applyLoginContext = (resp: LoginResponse) => {
     const { setTerms } = useTerminology()
@@ -59,6 +63,8 @@ export function useAuth() {
     if (fallback) {
       applyTheme(fallback)
     }
+    display.value = resp.display ?? null
+    applyDisplay(resp.display)
   }
 
   const login = async (email: string, password: string, tenantSlug = ''): Promise<LoginResponse> => {
@@ -73,14 +79,17 @@ export function useAuth() {
       throw new Error('invalid credentials')
     }
     const payload = (await res.json()) as LoginResponse
-    token.value = payload.token
-    sessionStorage.setItem(TOKEN_KEY, payload.token)
+    if (payload.token) {
+      token.value = payload.token
+      sessionStorage.setItem(TOKEN_KEY, payload.token)
+    }
     applyLoginContext(payload)
     return payload
   }
 
   const authFetch = async (input: string, init: RequestInit = {}) => {
     const headers = new Headers(init.headers ?? {})
+    headers.set('X-Requested-With', 'XMLHttpRequest')
     if (token.value) {
       headers.set('Authorization', `Bearer ${token.value}`)
     }
@@ -94,7 +103,7 @@ export function useAuth() {
   }
 
   const logout = async () => {
-    if (token.value) {
+    if (token.value || currentUser.value) {
       await authFetch('/auth/logout', { method: 'POST' })
     }
     clearSession()
@@ -135,6 +144,31 @@ export function useAuth() {
     return pref
   }
 
+  const getDisplayPreferences = async (): Promise<DisplayPreferencesView> => {
+    const res = await authFetch('/api/v1/preferences')
+    if (!res.ok) {
+      throw new Error('failed to load display preferences')
+    }
+    const view = (await res.json()) as DisplayPreferencesView
+    display.value = view.effective
+    return view
+  }
+
+  const updateDisplayPreferences = async (payload: DisplayPreferences): Promise<DisplayPreferencesView> => {
+    const res = await authFetch('/api/v1/preferences', {
+      method: 'PUT',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify(payload),
+    })
+    if (!res.ok) {
+      throw new Error('failed to update display preferences')
+    }
+    const view = (await res.json()) as DisplayPreferencesView
+    display.value = view.effective
+    applyDisplay(view.effective)
+    return view
+  }
+
   const loadThemes = async () => {
     const res = await authFetch('/tenant/themes')
     if (!res.ok) {
@@ -144,14 +178,41 @@ export function useAuth() {
     return themes.value
   }
 
-  const initialize = async () => {
-    if (!token.value) {
-      return
+  // restoreSession picks up an existing session from the session cookie (or
+  // the token kept for this tab) once per page load, so reloads and new tabs
+  // keep the signed-in user.
+  const restoreSession = (): Promise<boolean> => {
+    if (!restoring) {
+      restoring = (async () => {
+        try {
+          const headers = new Headers()
+          if (token.value) {
+            headers.set('Authorization', `Bearer ${token.value}`)
+          }
+          const res = await fetch(apiURL('/auth/session'), { headers, credentials: 'include' })
+          if (!res.ok) {
+            return false
+          }
+          const payload = (await res.json()) as LoginResponse
+          // A session restored from the cookie carries no token; requests
+          // then authenticate with the cookie alone.
+          if (payload.token) {
+            token.value = payload.token
+            sessionStorage.setItem(TOKEN_KEY, payload.token)
+          }
+          applyLoginContext(payload)
+          return true
+        } catch {
+          return false
+        }
+      })()
     }
-    try {
-      await loadThemes()
-      await getPreferences()
-    } catch {
+    return restoring
+  }
+
+  const initialize = async () => {
+    const restored = await restoreSession()
+    if (!restored && token.value) {
       clearSession()
     }
   }
@@ -162,15 +223,19 @@ export function useAuth() {
     tenantBranding,
     themes,
     preferences,
+    display,
     sessionExpired,
-    isAuthenticated: computed(() => Boolean(token.value)),
+    isAuthenticated: computed(() => Boolean(token.value || currentUser.value)),
     authFetch,
     login,
     logout,
     initialize,
+    restoreSession,
     changePassword,
     getPreferences,
     updatePreferences,
+    getDisplayPreferences,
+    updateDisplayPreferences,
     loadThemes,
   }
 }
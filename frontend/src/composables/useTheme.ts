@@ -1,8 +1,26 @@
 import { ref } from 'vue'
-import type { Theme } from '../types'
+import type { DisplayPreferences, Theme } from '../types'
 
 const THEME_KEY = 'acx:theme-id'
 const currentTheme = ref<Theme | null>(null)
+const colorScheme = ref<NonNullable<DisplayPreferences['color_scheme']>>('theme')
+
+function prefersDark(): boolean {
+  return typeof window.matchMedia === 'function' && window.matchMedia('(prefers-color-scheme: dark)').matches
+}
+
+function isDark(theme: Theme | null): boolean {
+  switch (colorScheme.value) {
+    case 'dark':
+      return true
+    case 'light':
+      return false
+    case 'system':
+      return prefersDark()
+    default:
+      return theme?.mode === 'dark'
+  }
+}
 
 function clearThemeOverrides(previous: Theme | null) {
   if (!previous) {
@@ -18,14 +36,11 @@ export function useTheme() {
   const apply = (theme: Theme) => {
     const root = document.documentElement
     clearThemeOverrides(currentTheme.value)
-    root.classList.remove('p-dark')
-    if (theme.mode === 'dark') {
-      root.classList.add('p-dark')
-    }
+    currentTheme.value = theme
+    root.classList.toggle('p-dark', isDark(theme))
     for (const [prop, value] of Object.entries(theme.overrides ?? {})) {
       root.style.setProperty(prop, value)
     }
-    currentTheme.value = theme
     try {
       localStorage.setItem(THEME_KEY, theme.id)
     } catch {
@@ -33,6 +48,15 @@ export function useTheme() {
     }
   }
 
+  // applyDisplay applies the server-side display preferences: the color
+  // scheme, which can override the theme's mode, and the density.
+  const applyDisplay = (prefs: DisplayPreferences | undefined) => {
+    const root = document.documentElement
+    colorScheme.value = prefs?.color_scheme ?? 'theme'
+    root.classList.toggle('p-dark', isDark(currentTheme.value))
+    root.dataset.density = prefs?.density ?? 'comfortable'
+  }
+
   const savedThemeID = (): string | null => {
     try {
       return localStorage.getItem(THEME_KEY)
@@ -41,5 +65,5 @@ export function useTheme() {
     }
   }
 
-  return { currentTheme, apply, savedThemeID }
+  return { currentTheme, apply, applyDisplay, savedThemeID }
 }
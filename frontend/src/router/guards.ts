@@ -2,11 +2,15 @@ import type { Router } from 'vue-router'
 import { useAuth } from '../composables/useAuth'
 
 export function installAuthGuards(router: Router) {
-  router.beforeEach((to) => {
-    const { isAuthenticated } = useAuth()
-    if (to.meta.requiresAuth && !isAuthenticated.value) {
+  router.beforeEach(async (to) => {
+    const { isAuthenticated, restoreSession, display } = useAuth()
+    if (to.meta.requiresAuth && !isAuthenticated.value && !(await restoreSession())) {
       return { path: '/login', query: { redirect: to.fullPath } }
     }
+    if (to.meta.landing) {
+      const landing = display.value?.landing_page
+      return landing && landing !== '/' && landing.startsWith('/') && !landing.startsWith('//') ? landing : '/inbox'
+    }
     return true
   })
 
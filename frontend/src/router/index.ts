@@ -4,7 +4,7 @@ import { installAuthGuards } from './guards'
 const router = createRouter({
   history: createWebHistory(),
   routes: [
-    { path: '/', redirect: '/inbox' },
+    { path: '/', component: () => import('../views/Inbox.vue'), meta: { requiresAuth: true, landing: true } },
     { path: '/login', component: () => import('../views/Login.vue') },
     { path: '/inbox', component: () => import('../views/Inbox.vue'), meta: { requiresAuth: true } },
     { path: '/activity', component: () => import('../views/Activity.vue'), meta: { requiresAuth: true } },
@@ -12,6 +12,8 @@ const router = createRouter({
     { path: '/cases/:id', component: () => import('../views/CaseView.vue'), meta: { requiresAuth: true } },
     { path: '/builder', component: () => import('../views/Builder.vue'), meta: { requiresAuth: true } },
     { path: '/reports', component: () => import('../views/Reports.vue'), meta: { requiresAuth: true } },
+    { path: '/tools', component: () => import('../views/Tools.vue'), meta: { requiresAuth: true } },
+    { path: '/system', component: () => import('../views/System.vue'), meta: { requiresAuth: true } },
     { path: '/profile', component: () => import('../views/Profile.vue'), meta: { requiresAuth: true } },
     { path: '/auth/password', component: () => import('../views/PasswordChange.vue'), meta: { requiresAuth: true } },
   ],
@@ -47,8 +47,21 @@ export type UserPreferences = {
   preferences: Record<string, unknown>
 }
 
+export type DisplayPreferences = {
+  theme_id?: string
+  color_scheme?: 'theme' | 'system' | 'light' | 'dark'
+  density?: 'comfortable' | 'compact'
+  landing_page?: string
+}
+
+export type DisplayPreferencesView = {
+  effective: DisplayPreferences
+  user: DisplayPreferences
+  workspace: DisplayPreferences
+}
+
 export type LoginResponse = {
-  token: string
+  token?: string
   principal: Principal
   tenant: {
     id: string
@@ -59,6 +72,7 @@ export type LoginResponse = {
     settings: Record<string, unknown>
   }
   preferences: UserPreferences
+  display?: DisplayPreferences
   themes: Theme[]
   expires_at: string
 }
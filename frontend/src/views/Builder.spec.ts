@@ -67,7 +67,10 @@ describe('Builder view mobile gating', () => {
 
     const fetchMock = vi.fn(async (input: RequestInfo | URL, init?: RequestInit) => {
       const url = String(input)
-      if (url === '/workflows' || url === '/connectors' || url === '/prompt-templates') {
+      if (url === '/workflows?limit=200' || url === '/connectors?limit=500') {
+        return new Response(JSON.stringify({ items: [], total: 0, limit: 200, offset: 0 }), { status: 200 })
+      }
+      if (url === '/prompt-templates') {
         return new Response(JSON.stringify([]), { status: 200 })
       }
       if (url === '/api/v1/ai-components' || url === '/api/v1/extraction-schemas') {
@@ -0,0 +1,77 @@
+import { createPinia, setActivePinia } from 'pinia'
+import PrimeVue from 'primevue/config'
+import Aura from '@primevue/themes/aura'
+import { flushPromises, mount } from '@vue/test-utils'
+import { beforeEach, describe, expect, it, vi } from 'vitest'
+import { useAuth } from '../composables/useAuth'
+import SystemView from './System.vue'
+
+const runtime = {
+  started_at: '2026-10-18T08:00:00Z',
+  uptime_seconds: 93784,
+  go_version: 'go1.24.0',
+  memory: {
+    rss_bytes: 134217728,
+    heap_alloc_bytes: 41943040,
+    heap_inuse_bytes: 52428800,
+    heap_sys_bytes: 67108864,
+    sys_bytes: 100663296,
+    gc_cycles: 42,
+    last_gc_pause_ns: 120000,
+  },
+  scheduler: { goroutines: 87, gomaxprocs: 4, num_cpu: 8 },
+  worker_pool: {
+    active: 3,
+    capacity: 20,
+    lanes: {
+      batch: { running: 2, queued: 40, reserved: 0, limit: 10 },
+      interactive: { running: 1, queued: 0, reserved: 4 },
+      standard: { running: 0, queued: 0, reserved: 0 },
+    },
+  },
+  db_pool: { open: 6, in_use: 2, idle: 4, max_open: 25, wait_count: 0, wait_duration_ms: 0 },
+  storage_latency: {
+    case_write: { count: 120, p50_ms: 1.8, p95_ms: 6.4, p99_ms: 21 },
+  },
+  tool_anomalies: [
+    {
+      protocol: 'mcp',
+      tool_id: 'lookup',
+      kind: 'failure_rate',
+      message: 'mcp tool lookup failed 50% of its last 12 calls, against 2% over the past hour',
+      recent_calls: 12,
+      detected_at: '2026-10-18T09:55:00Z',
+    },
+  ],
+}
+
+describe('System view', () => {
+  beforeEach(() => {
+    vi.restoreAllMocks()
+    setActivePinia(createPinia())
+    const auth = useAuth()
+    auth.token.value = 'test-token'
+    auth.currentUser.value = { id: 'p1', tenant_id: 't1', type: 'human', name: 'Admin' }
+  })
+
+  it('renders runtime metrics from the API', async () => {
+    const fetchSpy = vi.fn(async () => new Response(JSON.stringify(runtime), { status: 200 }))
+    vi.stubGlobal('fetch', fetchSpy)
+
+    const wrapper = mount(SystemView, {
+      global: { plugins: [createPinia(), [PrimeVue, { theme: { preset: Aura } }]] },
+    })
+    await flushPromises()
+
+    expect(String(fetchSpy.mock.calls[0]?.[0])).toContain('/api/v1/system/runtime')
+    expect(wrapper.find('[data-card="process"]').text()).toContain('1d 2h 3m')
+    expect(wrapper.find('[data-card="memory"]').text()).toContain('128.0 MiB')
+    expect(wrapper.find('[data-card="scheduler"]').text()).toContain('87')
+    expect(wrapper.find('[data-lane="batch"]').text()).toBe('2 running, 40 queued')
+    expect(wrapper.find('[data-card="db-pool"]').text()).toContain('2 / 6')
+    expect(wrapper.find('table.latency').text()).toContain('case_write')
+    expect(wrapper.find('table.latency').text()).toContain('6.40 ms')
+    expect(wrapper.find('[data-card="tool-anomalies"]').text()).toContain('failed 50% of its last 12 calls')
+    wrapper.unmount()
+  })
+})
@@ -0,0 +1,104 @@
+import { createPinia, setActivePinia } from 'pinia'
+import PrimeVue from 'primevue/config'
+import Aura from '@primevue/themes/aura'
+import { flushPromises, mount } from '@vue/test-utils'
+import { beforeEach, describe, expect, it, vi } from 'vitest'
+import { useAuth } from '../composables/useAuth'
+import ToolsView from './Tools.vue'
+
+function registry(mcpStatus: string, serverError = '') {
+  return {
+    protocols: [
+      {
+        protocol: 'mcp',
+        status: mcpStatus,
+        last_refreshed: '2026-10-01T09:00:00Z',
+        tool_count: 2,
+        sources: [
+          {
+            name: 'https://a.example/mcp',
+            status: serverError ? 'error' : 'active',
+            error: serverError || undefined,
+            last_refreshed: '2026-10-01T09:00:00Z',
+            tools: [{ name: 'lookup' }],
+          },
+          {
+            name: 'https://b.example/mcp',
+            status: 'active',
+            last_refreshed: '2026-10-01T08:00:00Z',
+            tools: [{ name: 'search', version: '3' }],
+            anomalies: [
+              {
+                tool_id: 'search',
+                kind: 'latency',
+                message: 'mcp tool search p95 latency is 4200ms over its last 20 calls, against 180ms over the past hour',
+              },
+            ],
+          },
+        ],
+      },
+      { protocol: 'custom_http', status: 'healthy', tool_count: 0, sources: [] },
+    ],
+  }
+}
+
+function mountTools() {
+  return mount(ToolsView, {
+    global: {
+      plugins: [createPinia(), [PrimeVue, { theme: { preset: Aura } }]],
+    },
+  })
+}
+
+describe('Tools view', () => {
+  beforeEach(() => {
+    vi.restoreAllMocks()
+    setActivePinia(createPinia())
+    const auth = useAuth()
+    auth.token.value = 'test-token'
+    auth.currentUser.value = { id: 'p1', tenant_id: 't1', type: 'human', name: 'Admin' }
+  })
+
+  it('groups tools by protocol and drills down into sources', async () => {
+    vi.stubGlobal('fetch', vi.fn(async () => new Response(JSON.stringify(registry('healthy')), { status: 200 })))
+
+    const wrapper = mountTools()
+    await flushPromises()
+    const groups = wrapper.findAll('article.protocol')
+    expect(groups).toHaveLength(2)
+    expect(groups[0].text()).toContain('MCP servers')
+    expect(groups[0].text()).toContain('2 tools')
+    expect(groups[1].text()).toContain('Custom HTTP tools')
+    expect(wrapper.text()).not.toContain('lookup')
+
+    await groups[0].find('button.toggle').trigger('click')
+    expect(wrapper.text()).toContain('https://a.example/mcp')
+    expect(wrapper.text()).toContain('lookup')
+    expect(wrapper.text()).toContain('v3')
+    expect(wrapper.find('[data-anomaly="search"]').text()).toContain('p95 latency is 4200ms')
+  })
+
+  it('refreshes a single source and shows its error inline', async () => {
+    const fetchSpy = vi.fn(async (input: RequestInfo | URL) => {
+      const url = String(input)
+      if (url.endsWith('/api/v1/tools/registry/mcp/refresh')) {
+        return new Response(JSON.stringify(registry('degraded', 'connection refused').protocols[0]), { status: 200 })
+      }
+      return new Response(JSON.stringify(registry('healthy')), { status: 200 })
+    })
+    vi.stubGlobal('fetch', fetchSpy)
+
+    const wrapper = mountTools()
+    await flushPromises()
+    await wrapper.find('article[data-protocol="mcp"] button.toggle').trigger('click')
+    const sourceRefresh = wrapper.findAll('.source .p-button')[0]
+    await sourceRefresh.trigger('click')
+    await flushPromises()
+
+    const refreshCall = fetchSpy.mock.calls.find(([input]) => String(input).endsWith('/refresh'))
+    expect(refreshCall).toBeDefined()
+    expect(JSON.parse(String((refreshCall?.[1] as RequestInit).body))).toEqual({ source: 'https://a.example/mcp' })
+    expect(wrapper.text()).toContain('degraded')
+    expect(wrapper.text()).toContain('connection refused')
+  })
+})
@@ -4,6 +4,11 @@ import vue from '@vitejs/plugin-vue'
 // https://vite.dev/config/
 export default defineConfig({
   plugins: [vue()],
+  build: {
+    // dist/.vite/manifest.json maps sources to fingerprinted files; the
+    // server uses it for asset_url and immutable cache headers.
+    manifest: true,
+  },
   server: {
     proxy: {
       '/api': 'http://localhost:8080',
@@ -0,0 +1,117 @@
+package agentic
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/google/uuid"
+)
+
+// ToolAccessRule matches tools by source, plugin category and tool name,
+// optionally for a single tenant. Empty fields match anything.
+type ToolAccessRule struct {
+	Tenant   uuid.UUID
+	Source   ToolSource
+	Category string
+	Tool     string
+}
+
+// ToolAccessPolicy is the deployment-wide limit on which tools agentic steps
+// may be given, on top of each step's own tool_policy. A tool matching any
+// applicable deny rule is removed; if any allow rules apply to the tenant, a
+// tool must also match one of them.
+type ToolAccessPolicy struct {
+	Allow []ToolAccessRule
+	Deny  []ToolAccessRule
+}
+
+// ToolAccessSubject is what rules are matched against.
+type ToolAccessSubject struct {
+	Source   ToolSource
+	Category string
+	Tool     string
+}
+
+func (p ToolAccessPolicy) Permits(tenantID uuid.UUID, subject ToolAccessSubject) bool {
+	for _, rule := range p.Deny {
+		if rule.appliesTo(tenantID) && rule.matches(subject) {
+			return false
+		}
+	}
+	applicable := false
+	for _, rule := range p.Allow {
+		if !rule.appliesTo(tenantID) {
+			continue
+		}
+		applicable = true
+		if rule.matches(subject) {
+			return true
+		}
+	}
+	return !applicable
+}
+
+func (r ToolAccessRule) appliesTo(tenantID uuid.UUID) bool {
+	return r.Tenant == uuid.Nil || r.Tenant == tenantID
+}
+
+func (r ToolAccessRule) matches(subject ToolAccessSubject) bool {
+	if r.Source != "" && r.Source != subject.Source {
+		return false
+	}
+	if r.Category != "" && !strings.EqualFold(r.Category, subject.Category) {
+		return false
+	}
+	if r.Tool != "" && r.Tool != subject.Tool {
+		return false
+	}
+	return true
+}
+
+// ParseToolAccessRules parses comma-separated rules, each one or more
+// key:value conditions joined by "+", for example
+// "category:database,source:mcp+tenant:<uuid>". Keys are source, category,
+// tool and tenant.
+func ParseToolAccessRules(raw string) ([]ToolAccessRule, error) {
+	rules := make([]ToolAccessRule, 0)
+	for _, part := range strings.Split(raw, ",") {
+		part = strings.TrimSpace(part)
+		if part == "" {
+			continue
+		}
+		var rule ToolAccessRule
+		for _, cond := range strings.Split(part, "+") {
+			key, value, ok := strings.Cut(strings.TrimSpace(cond), ":")
+			value = strings.TrimSpace(value)
+			if !ok || value == "" {
+				return nil, fmt.Errorf("invalid tool access condition %q", cond)
+			}
+			switch strings.ToLower(strings.TrimSpace(key)) {
+			case "source":
+				switch source := ToolSource(value); source {
+				case ToolSourceConnector, ToolSourceMCP, ToolSourceRAG, ToolSourceCaseData:
+					rule.Source = source
+				default:
+					return nil, fmt.Errorf("unknown tool source %q", value)
+				}
+			case "category":
+				rule.Category = value
+			case "tool":
+				rule.Tool = value
+			case "tenant":
+				id, err := uuid.Parse(value)
+				if err != nil {
+					return nil, fmt.Errorf("invalid tenant in tool access rule %q: %w", part, err)
+				}
+				rule.Tenant = id
+			default:
+				return nil, fmt.Errorf("unknown tool access key %q", key)
+			}
+		}
+		if rule.Source == "" && rule.Category == "" && rule.Tool == "" {
+			return nil, fmt.Errorf("tool access rule %q matches every tool", part)
+		}
+		rules = append(rules, rule)
+	}
+	return rules, nil
+}
@@ -35,6 +35,18 @@ func NewMCPInvoker(mcpManager MCPManager, tenantID uuid.UUID, serverURL, toolNam
 	}
 }
 
+// Healthy reports false while the server's circuit breaker is refusing
+// calls, when the manager can tell.
+func (mi *MCPInvoker) Healthy() bool {
+	if mi == nil || mi.mcpManager == nil {
+		return false
+	}
+	breaker, ok := mi.mcpManager.(interface {
+		CircuitOpen(tenantID uuid.UUID, serverURL string) bool
+	})
+	return !ok || !breaker.CircuitOpen(mi.tenantID, mi.serverURL)
+}
+
 func (mi *MCPInvoker) Invoke(ctx context.Context, args json.RawMessage) (json.RawMessage, error) {
 	if mi == nil || mi.mcpManager == nil {
 		return nil, fmt.Errorf("mcp invoker not configured")
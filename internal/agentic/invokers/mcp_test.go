@@ -28,3 +28,16 @@ func TestMCPInvoker_Invoke(t *testing.T) {
 		t.Fatalf("expected result")
 	}
 }
+
+type openCircuitManager struct{ mcpManagerMock }
+
+func (openCircuitManager) CircuitOpen(uuid.UUID, string) bool { return true }
+
+func TestMCPInvoker_HealthyFollowsCircuit(t *testing.T) {
+	if !NewMCPInvoker(mcpManagerMock{}, uuid.New(), "https://example.com", "search", mcp.AuthConfig{Type: "none"}, 1).Healthy() {
+		t.Fatalf("expected healthy when manager reports no circuit state")
+	}
+	if NewMCPInvoker(openCircuitManager{}, uuid.New(), "https://example.com", "search", mcp.AuthConfig{Type: "none"}, 1).Healthy() {
+		t.Fatalf("expected unhealthy while circuit is open")
+	}
+}
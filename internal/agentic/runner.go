@@ -41,6 +41,7 @@ type RunResult struct {
 	TotalIterations int
 	TotalToolCalls  int
 	TotalTokens     int
+	TotalCost       float64
 	DurationMS      int
 	TraceID         uuid.UUID
 }
@@ -87,6 +88,7 @@ func (r *runner) Run(ctx context.Context, config RunConfig) (RunResult, error) {
 	iteration := 0
 	toolCallCount := 0
 	totalTokens := 0
+	totalCost := 0.0
 	invalidConclusionAttempts := 0
 	lastResponseContent := ""
 	lastResponseJSON := json.RawMessage(`{}`)
@@ -99,6 +101,7 @@ func (r *runner) Run(ctx context.Context, config RunConfig) (RunResult, error) {
 				TotalIterations: iteration,
 				TotalToolCalls:  toolCallCount,
 				TotalTokens:     totalTokens,
+				TotalCost:       totalCost,
 				DurationMS:      int(time.Since(started).Milliseconds()),
 				Conclusion:      lastResponseJSON,
 			})
@@ -135,6 +138,7 @@ func (r *runner) Run(ctx context.Context, config RunConfig) (RunResult, error) {
 				TotalIterations: iteration,
 				TotalToolCalls:  toolCallCount,
 				TotalTokens:     totalTokens,
+				TotalCost:       totalCost,
 				DurationMS:      int(time.Since(started).Milliseconds()),
 				Conclusion:      lastResponseJSON,
 			})
@@ -142,6 +146,7 @@ func (r *runner) Run(ctx context.Context, config RunConfig) (RunResult, error) {
 		lastResponseContent = resp.Content
 		lastResponseJSON = json.RawMessage(strings.TrimSpace(resp.Content))
 		totalTokens += resp.TotalTokens
+		totalCost += resp.CostUSD
 		ce.SetTokensUsed(totalTokens)
 
 		sequence++
@@ -168,6 +173,7 @@ func (r *runner) Run(ctx context.Context, config RunConfig) (RunResult, error) {
 				TotalIterations: iteration,
 				TotalToolCalls:  toolCallCount,
 				TotalTokens:     totalTokens,
+				TotalCost:       totalCost,
 				DurationMS:      int(time.Since(started).Milliseconds()),
 			})
 		} else if warning != "" {
@@ -294,6 +300,7 @@ func (r *runner) Run(ctx context.Context, config RunConfig) (RunResult, error) {
 					TotalIterations: iteration,
 					TotalToolCalls:  toolCallCount,
 					TotalTokens:     totalTokens,
+					TotalCost:       totalCost,
 					DurationMS:      int(time.Since(started).Milliseconds()),
 					Conclusion:      mustJSON(map[string]any{"raw_response": resp.Content}),
 				})
@@ -319,6 +326,7 @@ func (r *runner) Run(ctx context.Context, config RunConfig) (RunResult, error) {
 			TotalIterations: iteration,
 			TotalToolCalls:  toolCallCount,
 			TotalTokens:     totalTokens,
+			TotalCost:       totalCost,
 			DurationMS:      int(time.Since(started).Milliseconds()),
 		})
 	}
@@ -6,6 +6,7 @@ import (
 	"encoding/json"
 	"fmt"
 	"strings"
+	"sync"
 	"time"
 
 	"github.com/google/uuid"
@@ -25,6 +26,8 @@ type StepExecutor struct {
 	pluginRuntime invokers.PluginRuntime
 	mcpManager    invokers.MCPManager
 	ragSearch     *rag.SearchService
+	accessMu      sync.RWMutex
+	toolAccess    ToolAccessPolicy
 }
 
 func NewStepExecutor(
@@ -55,6 +58,14 @@ func NewStepExecutor(
 	}
 }
 
+// SetToolAccess limits the tools any agentic step may be given. It is safe
+// to call while steps run; steps already started keep their tools.
+func (s *StepExecutor) SetToolAccess(policy ToolAccessPolicy) {
+	s.accessMu.Lock()
+	defer s.accessMu.Unlock()
+	s.toolAccess = policy
+}
+
 func (s *StepExecutor) Execute(ctx context.Context, caseID uuid.UUID, stepID string, configRaw json.RawMessage) (*engine.StepResult, error) {
 	if s == nil || s.db == nil || s.runner == nil || s.llm == nil {
 		return nil, fmt.Errorf("agentic step executor not configured")
@@ -148,6 +159,8 @@ func (s *StepExecutor) Execute(ctx context.Context, caseID uuid.UUID, stepID str
 		WritesCaseData: true,
 		CaseDataPatch:  patch,
 		AuditEventType: "agentic.concluded",
+		Tokens:         runResult.TotalTokens,
+		Cost:           runResult.TotalCost,
 	}, nil
 }
 
@@ -171,23 +184,18 @@ WHERE c.id = $1
 }
 
 func (s *StepExecutor) buildManifest(ctx context.Context, tenantID, caseID uuid.UUID, cfg AgenticStepConfig) (*ToolManifest, error) {
-	assembler := NewToolAssembler(s.mcpManager, s.ragSearch)
+	s.accessMu.RLock()
+	access := s.toolAccess
+	s.accessMu.RUnlock()
+	assembler := NewToolAssembler(s.mcpManager, s.ragSearch).WithAccess(access, s.toolCategory)
 	return assembler.Assemble(ctx, tenantID, cfg.ToolPolicy, cfg.ToolNodes, func(node ToolNodeConfig, toolName string) (ToolInvoker, string, json.RawMessage, error) {
 		source := strings.TrimSpace(node.Source)
 		switch source {
 		case "", "connector":
 			safety := "read_only"
 			params := json.RawMessage(`{"type":"object","properties":{"arguments":{"type":"object"}}}`)
-			if s.pluginRuntime != nil {
-				if rt, ok := s.pluginRuntime.(interface {
-					Get(ref plugins.PluginRef) (*plugins.Plugin, error)
-				}); ok {
-					if plugin, err := rt.Get(plugins.PluginRef{ID: strings.TrimSpace(node.Connector)}); err == nil && plugin != nil {
-						if strings.TrimSpace(plugin.ToolSafety) != "" {
-							safety = strings.TrimSpace(plugin.ToolSafety)
-						}
-					}
-				}
+			if plugin := s.lookupPlugin(node.Connector); plugin != nil && strings.TrimSpace(plugin.ToolSafety) != "" {
+				safety = strings.TrimSpace(plugin.ToolSafety)
 			}
 			return invokers.NewConnectorInvoker(s.pluginRuntime, node.Connector, node.Config, cfg.Limits.Timeout), safety, params, nil
 		case "mcp":
@@ -226,6 +234,35 @@ func (s *StepExecutor) buildManifest(ctx context.Context, tenantID, caseID uuid.
 	})
 }
 
+func (s *StepExecutor) lookupPlugin(connector string) *plugins.Plugin {
+	if s.pluginRuntime == nil {
+		return nil
+	}
+	rt, ok := s.pluginRuntime.(interface {
+		Get(ref plugins.PluginRef) (*plugins.Plugin, error)
+	})
+	if !ok {
+		return nil
+	}
+	plugin, err := rt.Get(plugins.PluginRef{ID: strings.TrimSpace(connector)})
+	if err != nil {
+		return nil
+	}
+	return plugin
+}
+
+// toolCategory is the plugin category of connector tools; other sources have
+// none.
+func (s *StepExecutor) toolCategory(node ToolNodeConfig) string {
+	switch strings.TrimSpace(node.Source) {
+	case "", "connector":
+		if plugin := s.lookupPlugin(node.Connector); plugin != nil {
+			return strings.TrimSpace(plugin.Category)
+		}
+	}
+	return ""
+}
+
 type stepCaseStore struct {
 	db       *sql.DB
 	tenantID uuid.UUID
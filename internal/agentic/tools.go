@@ -3,7 +3,9 @@ package agentic
 import (
 	"context"
 	"encoding/json"
+	"errors"
 	"fmt"
+	"sort"
 	"strings"
 
 	"github.com/google/uuid"
@@ -84,6 +86,12 @@ type ToolInvoker interface {
 	Invoke(ctx context.Context, arguments json.RawMessage) (json.RawMessage, error)
 }
 
+// ToolHealth is implemented by invokers that can tell their backend is down,
+// such as an MCP server behind an open circuit breaker.
+type ToolHealth interface {
+	Healthy() bool
+}
+
 type MCPManager interface {
 	DiscoverTools(ctx context.Context, tenantID uuid.UUID, serverURL string, auth mcp.AuthConfig) ([]mcp.MCPTool, error)
 }
@@ -91,12 +99,22 @@ type MCPManager interface {
 type ToolAssembler struct {
 	mcpManager MCPManager
 	ragSearch  *rag.SearchService
+	access     ToolAccessPolicy
+	categoryOf func(node ToolNodeConfig) string
 }
 
 func NewToolAssembler(mcpManager MCPManager, ragSearch *rag.SearchService) *ToolAssembler {
 	return &ToolAssembler{mcpManager: mcpManager, ragSearch: ragSearch}
 }
 
+// WithAccess applies policy to assembled tools; categoryOf supplies each
+// tool's category for category rules and may be nil.
+func (ta *ToolAssembler) WithAccess(policy ToolAccessPolicy, categoryOf func(node ToolNodeConfig) string) *ToolAssembler {
+	ta.access = policy
+	ta.categoryOf = categoryOf
+	return ta
+}
+
 func (ta *ToolAssembler) Assemble(ctx context.Context, tenantID uuid.UUID, policy ToolPolicy, toolNodes []ToolNodeConfig, invokerFactory func(node ToolNodeConfig, toolName string) (ToolInvoker, string, json.RawMessage, error)) (*ToolManifest, error) {
 	allowedRefs := make(map[string]struct{}, len(policy.Tools))
 	for _, ref := range policy.Tools {
@@ -110,7 +128,7 @@ func (ta *ToolAssembler) Assemble(ctx context.Context, tenantID uuid.UUID, polic
 	}
 
 	mode := policy.ToolMode.Normalize()
-	tools := make([]ResolvedTool, 0, len(toolNodes))
+	tools := make([]toolCandidate, 0, len(toolNodes))
 	for _, node := range toolNodes {
 		if _, ok := allowedRefs[strings.TrimSpace(node.ID)]; !ok {
 			continue
@@ -127,7 +145,14 @@ func (ta *ToolAssembler) Assemble(ctx context.Context, tenantID uuid.UUID, polic
 		if source == "" {
 			source = ToolSourceConnector
 		}
-		tools = append(tools, ResolvedTool{
+		subject := ToolAccessSubject{Source: source, Tool: name}
+		if ta.categoryOf != nil {
+			subject.Category = ta.categoryOf(node)
+		}
+		if !ta.access.Permits(tenantID, subject) {
+			continue
+		}
+		tools = append(tools, toolCandidate{priority: node.Priority, tool: ResolvedTool{
 			ID:          node.ID,
 			Name:        name,
 			Description: strings.TrimSpace(node.Description),
@@ -135,12 +160,80 @@ func (ta *ToolAssembler) Assemble(ctx context.Context, tenantID uuid.UUID, polic
 			Source:      source,
 			ToolSafety:  safety,
 			Invoker:     invoker,
-		})
+		}})
 	}
-	return NewToolManifest(tools), nil
+	return NewToolManifest(mergeDuplicateTools(tools)), nil
+}
+
+type toolCandidate struct {
+	tool     ResolvedTool
+	priority int
+}
+
+// mergeDuplicateTools collapses candidates sharing a name into one tool owned
+// by the source with the lowest priority, which fails over to the others in
+// priority order. Tools keep the order in which their name first appears.
+func mergeDuplicateTools(candidates []toolCandidate) []ResolvedTool {
+	order := make([]string, 0, len(candidates))
+	byName := make(map[string][]toolCandidate, len(candidates))
+	for _, candidate := range candidates {
+		if _, seen := byName[candidate.tool.Name]; !seen {
+			order = append(order, candidate.tool.Name)
+		}
+		byName[candidate.tool.Name] = append(byName[candidate.tool.Name], candidate)
+	}
+	out := make([]ResolvedTool, 0, len(order))
+	for _, name := range order {
+		group := byName[name]
+		sort.SliceStable(group, func(i, j int) bool { return group[i].priority < group[j].priority })
+		tool := group[0].tool
+		if len(group) > 1 {
+			failover := failoverInvoker{invokers: make([]ToolInvoker, 0, len(group))}
+			for _, candidate := range group {
+				failover.invokers = append(failover.invokers, candidate.tool.Invoker)
+			}
+			tool.Invoker = failover
+		}
+		out = append(out, tool)
+	}
+	return out
+}
+
+// failoverInvoker calls the first healthy source in priority order; sources
+// reporting unhealthy are only tried once every healthy one has refused. A
+// call refused by an MCP circuit breaker never reached the server, so it moves
+// on to the next source. Any other error is returned as is, since the call
+// may already have had effects.
+type failoverInvoker struct {
+	invokers []ToolInvoker
+}
+
+func (fi failoverInvoker) Invoke(ctx context.Context, arguments json.RawMessage) (json.RawMessage, error) {
+	ordered := make([]ToolInvoker, 0, len(fi.invokers))
+	var unhealthy []ToolInvoker
+	for _, invoker := range fi.invokers {
+		if health, ok := invoker.(ToolHealth); ok && !health.Healthy() {
+			unhealthy = append(unhealthy, invoker)
+			continue
+		}
+		ordered = append(ordered, invoker)
+	}
+	ordered = append(ordered, unhealthy...)
+	var err error
+	for _, invoker := range ordered {
+		var out json.RawMessage
+		out, err = invoker.Invoke(ctx, arguments)
+		if !errors.Is(err, mcp.ErrCircuitOpen) {
+			return out, err
+		}
+	}
+	return nil, err
 }
 
 func sanitizeToolName(node ToolNodeConfig) string {
+	if strings.TrimSpace(node.Name) != "" {
+		return normalizeToolName(node.Name)
+	}
 	if strings.TrimSpace(node.MCPPrefix) != "" && strings.TrimSpace(node.MCPToolName) != "" {
 		return normalizeToolName(node.MCPPrefix + "_" + node.MCPToolName)
 	}
@@ -34,3 +34,108 @@ func TestToolAssemblerFiltersByMode(t *testing.T) {
 		t.Fatalf("expected 1 filtered tool, got %d", len(manifest.Tools()))
 	}
 }
+
+type sourceInvoker struct {
+	source  string
+	healthy map[string]bool
+}
+
+func (si sourceInvoker) Healthy() bool { return si.healthy[si.source] }
+
+func (si sourceInvoker) Invoke(context.Context, json.RawMessage) (json.RawMessage, error) {
+	return json.RawMessage(`"` + si.source + `"`), nil
+}
+
+func TestToolAssemblerFailsOverDuplicateTools(t *testing.T) {
+	healthy := map[string]bool{"mcp": false, "connector": true}
+	nodes := []ToolNodeConfig{
+		{ID: "native", Connector: "lookup", Name: "lookup", Priority: 1},
+		{ID: "remote", Source: "mcp", MCPPrefix: "crm", MCPToolName: "lookup", Name: "lookup"},
+	}
+	manifest, err := NewToolAssembler(nil, nil).Assemble(context.Background(), uuid.New(), ToolPolicy{
+		Tools: []ToolRef{{Ref: "native"}, {Ref: "remote"}},
+	}, nodes, func(node ToolNodeConfig, toolName string) (ToolInvoker, string, json.RawMessage, error) {
+		source := node.Source
+		if source == "" {
+			source = "connector"
+		}
+		return sourceInvoker{source: source, healthy: healthy}, "read_only", nil, nil
+	})
+	if err != nil {
+		t.Fatalf("Assemble error: %v", err)
+	}
+	tools := manifest.Tools()
+	if len(tools) != 1 || tools[0].Source != ToolSourceMCP {
+		t.Fatalf("expected one tool owned by the mcp source, got %+v", tools)
+	}
+	out, err := tools[0].Invoker.Invoke(context.Background(), nil)
+	if err != nil || string(out) != `"connector"` {
+		t.Fatalf("expected failover to connector, got %s (%v)", out, err)
+	}
+	healthy["mcp"] = true
+	if out, _ := tools[0].Invoker.Invoke(context.Background(), nil); string(out) != `"mcp"` {
+		t.Fatalf("expected healthy primary to serve the call, got %s", out)
+	}
+}
+
+func TestToolAssemblerAppliesAccessPolicy(t *testing.T) {
+	tenant := uuid.New()
+	categories := map[string]string{"postgres": "database", "slack": "messaging"}
+	ta := NewToolAssembler(nil, nil).WithAccess(ToolAccessPolicy{
+		Deny: []ToolAccessRule{{Category: "database"}, {Source: ToolSourceMCP, Tenant: tenant}},
+	}, func(node ToolNodeConfig) string { return categories[node.Connector] })
+	nodes := []ToolNodeConfig{
+		{ID: "db", Connector: "postgres"},
+		{ID: "chat", Connector: "slack"},
+		{ID: "remote", Source: "mcp", MCPPrefix: "crm", MCPToolName: "lookup"},
+	}
+	policy := ToolPolicy{Tools: []ToolRef{{Ref: "db"}, {Ref: "chat"}, {Ref: "remote"}}, ToolMode: ToolModeFull}
+	factory := func(ToolNodeConfig, string) (ToolInvoker, string, json.RawMessage, error) {
+		return testInvoker{}, "read_only", nil, nil
+	}
+
+	manifest, err := ta.Assemble(context.Background(), tenant, policy, nodes, factory)
+	if err != nil {
+		t.Fatalf("Assemble error: %v", err)
+	}
+	if tools := manifest.Tools(); len(tools) != 1 || tools[0].ID != "chat" {
+		t.Fatalf("expected only chat for restricted tenant, got %+v", tools)
+	}
+	manifest, err = ta.Assemble(context.Background(), uuid.New(), policy, nodes, factory)
+	if err != nil {
+		t.Fatalf("Assemble error: %v", err)
+	}
+	if len(manifest.Tools()) != 2 {
+		t.Fatalf("expected chat and mcp tool for other tenants, got %+v", manifest.Tools())
+	}
+}
+
+func TestToolAccessPolicyAllowList(t *testing.T) {
+	allow, err := ParseToolAccessRules("source:rag, category:messaging+source:connector")
+	if err != nil {
+		t.Fatalf("parse: %v", err)
+	}
+	policy := ToolAccessPolicy{Allow: allow}
+	tenant := uuid.New()
+	if !policy.Permits(tenant, ToolAccessSubject{Source: ToolSourceRAG, Tool: "kb"}) {
+		t.Fatal("expected rag tool to be allowed")
+	}
+	if !policy.Permits(tenant, ToolAccessSubject{Source: ToolSourceConnector, Category: "Messaging", Tool: "slack"}) {
+		t.Fatal("expected messaging connector to be allowed")
+	}
+	if policy.Permits(tenant, ToolAccessSubject{Source: ToolSourceConnector, Category: "file", Tool: "sftp"}) {
+		t.Fatal("expected file connector to be outside the allow list")
+	}
+}
+
+func TestParseToolAccessRulesRejectsInvalid(t *testing.T) {
+	for _, raw := range []string{"source:ftp", "colour:red", "tenant:nope+tool:x", "tool", "tenant:" + uuid.NewString()} {
+		if _, err := ParseToolAccessRules(raw); err == nil {
+			t.Fatalf("expected %q to be rejected", raw)
+		}
+	}
+	rules, err := ParseToolAccessRules("  ")
+	if err != nil || len(rules) != 0 {
+		t.Fatalf("expected empty rules, got %v, %v", rules, err)
+	}
+}
@@ -139,6 +139,12 @@ type ToolNodeConfig struct {
 	Config      json.RawMessage `json:"config" yaml:"config"`
 	Description string          `json:"description" yaml:"description"`
 
+	// Name overrides the tool name derived from the connector or MCP tool.
+	// Nodes sharing a name are exposed as one tool served by the source with
+	// the lowest Priority, failing over to the next when it is unhealthy.
+	Name     string `json:"name" yaml:"name"`
+	Priority int    `json:"priority" yaml:"priority"`
+
 	MCPServerURL string `json:"mcp_server_url" yaml:"mcp_server_url"`
 	MCPToolName  string `json:"mcp_tool_name" yaml:"mcp_tool_name"`
 	MCPPrefix    string `json:"mcp_prefix" yaml:"mcp_prefix"`
@@ -205,6 +205,7 @@ func (a *AgentExecutor) Execute(ctx context.Context, caseID uuid.UUID, stepID st
 		Output:         resultPayload,
 		ExecutionEvent: eventJSON,
 		AuditEventType: "agent.completed",
+		Tokens:         usage.InputTokens + usage.OutputTokens,
 	}
 	if cfg.WritesCaseData {
 		stepResult.WritesCaseData = true
@@ -94,9 +94,7 @@ func (c *LLMClient) ChatCompletion(ctx context.Context, messages []Message, resp
 	if c.apiKey != "" {
 		req.Header.Set("Authorization", "Bearer "+c.apiKey)
 	}
-	if cid := observability.CorrelationIDFromContext(ctx); cid != "" {
-		req.Header.Set(observability.CorrelationHeader, cid)
-	}
+	observability.InjectCorrelationHeaders(ctx, req.Header)
 
 	resp, err := c.client.Do(req)
 	if err != nil {
@@ -165,9 +163,7 @@ func (c *LLMClient) Embed(ctx context.Context, input string) ([]float32, error)
 	if c.apiKey != "" {
 		req.Header.Set("Authorization", "Bearer "+c.apiKey)
 	}
-	if cid := observability.CorrelationIDFromContext(ctx); cid != "" {
-		req.Header.Set(observability.CorrelationHeader, cid)
-	}
+	observability.InjectCorrelationHeaders(ctx, req.Header)
 
 	resp, err := c.client.Do(req)
 	if err != nil {
@@ -150,6 +150,12 @@ RETURNING id, tenant_id, name, version, template, COALESCE(output_schema, '{}'::
 	return item, nil
 }
 
+// Resolve loads a stored template by reference: "name" for the latest
+// version, "name_v2" or an explicit version for a specific one.
+func (s *PromptTemplateService) Resolve(ctx context.Context, tenantID uuid.UUID, ref string, version int) (PromptTemplate, error) {
+	return s.resolveTemplate(ctx, tenantID, ref, version)
+}
+
 func (s *PromptTemplateService) resolveTemplate(ctx context.Context, tenantID uuid.UUID, raw string, explicitVersion int) (PromptTemplate, error) {
 	name, version := parseTemplateReference(raw)
 	if explicitVersion > 0 {
@@ -9,6 +9,12 @@ import (
 	"time"
 )
 
+// RenderTemplate renders a template with the same engine and helpers
+// (toJSON, formatCurrency, formatDate) as agent prompts.
+func RenderTemplate(raw string, data map[string]any) (string, error) {
+	return renderPromptTemplate(raw, data)
+}
+
 func renderPromptTemplate(raw string, data map[string]any) (string, error) {
 	funcs := template.FuncMap{
 		"toJSON": func(v any) string {
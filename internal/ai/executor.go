@@ -75,6 +75,7 @@ type ComponentExecResult struct {
 	InputTokens       int             `json:"input_tokens"`
 	OutputTokens      int             `json:"output_tokens"`
 	TotalTokens       int             `json:"total_tokens"`
+	CostUSD           float64         `json:"cost_usd"`
 	ConfidenceWarning bool            `json:"confidence_warning,omitempty"`
 	Event             json.RawMessage `json:"event,omitempty"`
 }
@@ -122,6 +123,7 @@ func (ce *ComponentExecutor) Execute(ctx context.Context, req ComponentExecReque
 		response llm.ChatResponse
 		output   json.RawMessage
 		parsed   map[string]any
+		costUSD  float64
 	)
 
 	for attempt := 1; attempt <= maxValidationAttempts; attempt++ {
@@ -136,6 +138,7 @@ func (ce *ComponentExecutor) Execute(ctx context.Context, req ComponentExecReque
 		if err != nil {
 			return ComponentExecResult{}, fmt.Errorf("component llm chat attempt %d: %w", attempt, err)
 		}
+		costUSD += response.CostUSD
 		if strings.TrimSpace(response.Model) != "" {
 			model = response.Model
 		}
@@ -245,6 +248,7 @@ func (ce *ComponentExecutor) Execute(ctx context.Context, req ComponentExecReque
 		InputTokens:       response.InputTokens,
 		OutputTokens:      response.OutputTokens,
 		TotalTokens:       response.TotalTokens,
+		CostUSD:           costUSD,
 		ConfidenceWarning: warning,
 		Event:             eventRaw,
 	}
@@ -65,6 +65,8 @@ func (s *StepExecutor) Execute(ctx context.Context, caseID uuid.UUID, stepID str
 		CaseDataPatch:  out.MergePatch,
 		ExecutionEvent: out.Event,
 		AuditEventType: "ai_component.executed",
+		Tokens:         out.TotalTokens,
+		Cost:           out.CostUSD,
 	}, nil
 }
 
@@ -0,0 +1,135 @@
+// Package artifacts stores large step outputs — files, reports, LLM
+// transcripts — in an object store, keeping only a handle in the step result.
+package artifacts
+
+import (
+	"bytes"
+	"context"
+	"database/sql"
+	"errors"
+	"fmt"
+	"io"
+	"io/fs"
+	"net/http"
+	"strings"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
+	"github.com/neural-chilli/aceryx/internal/storage"
+)
+
+var ErrNotFound = errors.New("artifact not found")
+
+// Record is a stored artifact.
+type Record struct {
+	ID          uuid.UUID `json:"id"`
+	CaseID      uuid.UUID `json:"case_id"`
+	StepID      string    `json:"step_id"`
+	Name        string    `json:"name"`
+	ContentType string    `json:"content_type"`
+	SizeBytes   int64     `json:"size_bytes"`
+	Checksum    string    `json:"checksum"`
+	CreatedAt   time.Time `json:"created_at"`
+	storageKey  string
+}
+
+type Service struct {
+	db      *sql.DB
+	objects storage.ObjectStore
+}
+
+func NewService(db *sql.DB, objects storage.ObjectStore) *Service {
+	return &Service{db: db, objects: objects}
+}
+
+// SaveArtifact implements engine.ArtifactStore. Content type is sniffed from
+// the data when the executor did not set one.
+func (s *Service) SaveArtifact(ctx context.Context, caseID uuid.UUID, stepID string, artifact engine.Artifact) (engine.Artifact, error) {
+	var tenantID uuid.UUID
+	if err := s.db.QueryRowContext(ctx, `SELECT tenant_id FROM cases WHERE id = $1`, caseID).Scan(&tenantID); err != nil {
+		return engine.Artifact{}, fmt.Errorf("load case for artifact: %w", err)
+	}
+	id := uuid.New()
+	name := strings.TrimSpace(artifact.Name)
+	if name == "" {
+		name = id.String()
+	}
+	contentType := strings.TrimSpace(artifact.ContentType)
+	if contentType == "" {
+		contentType = http.DetectContentType(artifact.Data)
+	}
+	checksum := storage.SHA256Hex(artifact.Data)
+	key := storageKey(tenantID, caseID, id)
+	if err := s.objects.Put(ctx, key, bytes.NewReader(artifact.Data), storage.ObjectMetadata{
+		ContentType:   contentType,
+		ContentLength: int64(len(artifact.Data)),
+		Checksum:      checksum,
+	}); err != nil {
+		return engine.Artifact{}, fmt.Errorf("upload artifact: %w", err)
+	}
+	if _, err := s.db.ExecContext(ctx, `
+INSERT INTO execution_artifacts (id, tenant_id, case_id, step_id, name, content_type, size_bytes, checksum, storage_key)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+`, id, tenantID, caseID, stepID, name, contentType, len(artifact.Data), checksum, key); err != nil {
+		_ = s.objects.Delete(ctx, key)
+		return engine.Artifact{}, fmt.Errorf("insert artifact: %w", err)
+	}
+	return engine.Artifact{ID: id, Name: name, ContentType: contentType, SizeBytes: int64(len(artifact.Data))}, nil
+}
+
+// List returns a case's artifacts, oldest first.
+func (s *Service) List(ctx context.Context, tenantID, caseID uuid.UUID) ([]Record, error) {
+	rows, err := s.db.QueryContext(ctx, `
+SELECT id, case_id, step_id, name, content_type, size_bytes, checksum, created_at, storage_key
+FROM execution_artifacts
+WHERE tenant_id = $1 AND case_id = $2
+ORDER BY created_at, id
+`, tenantID, caseID)
+	if err != nil {
+		return nil, fmt.Errorf("list artifacts: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+
+	out := make([]Record, 0)
+	for rows.Next() {
+		var rec Record
+		if err := rows.Scan(&rec.ID, &rec.CaseID, &rec.StepID, &rec.Name, &rec.ContentType, &rec.SizeBytes, &rec.Checksum, &rec.CreatedAt, &rec.storageKey); err != nil {
+			return nil, fmt.Errorf("scan artifact: %w", err)
+		}
+		out = append(out, rec)
+	}
+	if err := rows.Err(); err != nil {
+		return nil, fmt.Errorf("iterate artifacts: %w", err)
+	}
+	return out, nil
+}
+
+// Open returns an artifact's record and content. The caller closes the
+// reader.
+func (s *Service) Open(ctx context.Context, tenantID, caseID, artifactID uuid.UUID) (Record, io.ReadCloser, error) {
+	var rec Record
+	err := s.db.QueryRowContext(ctx, `
+SELECT id, case_id, step_id, name, content_type, size_bytes, checksum, created_at, storage_key
+FROM execution_artifacts
+WHERE tenant_id = $1 AND case_id = $2 AND id = $3
+`, tenantID, caseID, artifactID).Scan(&rec.ID, &rec.CaseID, &rec.StepID, &rec.Name, &rec.ContentType, &rec.SizeBytes, &rec.Checksum, &rec.CreatedAt, &rec.storageKey)
+	if errors.Is(err, sql.ErrNoRows) {
+		return Record{}, nil, ErrNotFound
+	}
+	if err != nil {
+		return Record{}, nil, fmt.Errorf("load artifact: %w", err)
+	}
+	body, _, err := s.objects.Get(ctx, rec.storageKey)
+	if errors.Is(err, fs.ErrNotExist) {
+		return Record{}, nil, ErrNotFound
+	}
+	if err != nil {
+		return Record{}, nil, fmt.Errorf("download artifact: %w", err)
+	}
+	return rec, body, nil
+}
+
+func storageKey(tenantID, caseID, artifactID uuid.UUID) string {
+	return fmt.Sprintf("artifacts/%s/%s/%s", tenantID, caseID, artifactID)
+}
@@ -0,0 +1,63 @@
+package artifacts
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"strings"
+
+	"github.com/neural-chilli/aceryx/internal/storage"
+	storagelocal "github.com/neural-chilli/aceryx/internal/storage/local"
+	storages3 "github.com/neural-chilli/aceryx/internal/storage/s3"
+)
+
+// NewObjectStoreFromEnv builds the artifact store selected by
+// ACERYX_ARTIFACTS_BACKEND: "local" (the default) writes under
+// ACERYX_ARTIFACTS_ROOT, "s3" uses the ACERYX_ARTIFACTS_S3_* settings.
+func NewObjectStoreFromEnv(ctx context.Context) (storage.ObjectStore, error) {
+	backend := strings.ToLower(strings.TrimSpace(os.Getenv("ACERYX_ARTIFACTS_BACKEND")))
+	switch backend {
+	case "", "local":
+		root := strings.TrimSpace(os.Getenv("ACERYX_ARTIFACTS_ROOT"))
+		if root == "" {
+			root = "./data/artifacts"
+		}
+		store, err := storagelocal.New(root)
+		if err != nil {
+			return nil, err
+		}
+		return store, nil
+	case "s3":
+		sse := strings.TrimSpace(os.Getenv("ACERYX_ARTIFACTS_S3_SSE"))
+		if sse == "" {
+			sse = "sse-s3"
+		}
+		store, err := storages3.New(ctx, storages3.Config{
+			Bucket:          os.Getenv("ACERYX_ARTIFACTS_S3_BUCKET"),
+			Region:          os.Getenv("ACERYX_ARTIFACTS_S3_REGION"),
+			Prefix:          os.Getenv("ACERYX_ARTIFACTS_S3_PREFIX"),
+			Endpoint:        os.Getenv("ACERYX_ARTIFACTS_S3_ENDPOINT"),
+			AccessKeyID:     os.Getenv("ACERYX_ARTIFACTS_S3_ACCESS_KEY_ID"),
+			SecretAccessKey: os.Getenv("ACERYX_ARTIFACTS_S3_SECRET_ACCESS_KEY"),
+			UseIAMRole:      parseBool(os.Getenv("ACERYX_ARTIFACTS_S3_USE_IAM_ROLE")),
+			SSE:             sse,
+		})
+		if err != nil {
+			return nil, err
+		}
+		return store, nil
+	default:
+		return nil, fmt.Errorf("unsupported artifacts backend %q", backend)
+	}
+}
+
+// BackendConfigured reports whether the artifact store was configured
+// explicitly rather than left to the default local directory.
+func BackendConfigured() bool {
+	return strings.TrimSpace(os.Getenv("ACERYX_ARTIFACTS_BACKEND")) != "" || strings.TrimSpace(os.Getenv("ACERYX_ARTIFACTS_ROOT")) != ""
+}
+
+func parseBool(v string) bool {
+	v = strings.TrimSpace(strings.ToLower(v))
+	return v == "1" || v == "true" || v == "yes"
+}
@@ -19,7 +19,8 @@ SELECT id, step_id, state, started_at, completed_at,
        sla_deadline,
        retry_count,
        COALESCE(draft_data, '{}'::jsonb),
-       COALESCE(metadata, '{}'::jsonb)
+       COALESCE(metadata, '{}'::jsonb),
+       logs
 FROM case_steps
 WHERE case_id = $1
   AND case_id IN (SELECT id FROM cases WHERE id = $1 AND tenant_id = $2)
@@ -35,7 +36,7 @@ ORDER BY step_id
 		var step CaseStep
 		if err := rows.Scan(&step.ID, &step.StepID, &step.State, &step.StartedAt, &step.CompletedAt,
 			&step.Result, &step.Events, &step.Error, &step.AssignedTo, &step.SLADeadline,
-			&step.RetryCount, &step.DraftData, &step.Metadata); err != nil {
+			&step.RetryCount, &step.DraftData, &step.Metadata, &step.Logs); err != nil {
 			return nil, fmt.Errorf("scan case step: %w", err)
 		}
 		out = append(out, step)
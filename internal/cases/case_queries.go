@@ -2,6 +2,9 @@ package cases
 
 import (
 	"context"
+	"database/sql"
+	"encoding/json"
+	"errors"
 	"fmt"
 	"strconv"
 	"strings"
@@ -215,3 +218,62 @@ func normalizePage(page, perPage int) (int, int) {
 	}
 	return page, perPage
 }
+
+// CaseOutput reports the workflow output for a case: the step result when stepID
+// is set, otherwise the case data once the case has completed. done is false while
+// the output is still pending. A failed/skipped step or cancelled case is an error.
+func (s *CaseService) CaseOutput(ctx context.Context, tenantID, caseID uuid.UUID, stepID string) (map[string]any, bool, error) {
+	var (
+		status  string
+		rawData []byte
+	)
+	if err := s.db.QueryRowContext(ctx, `
+SELECT status, data
+FROM cases
+WHERE tenant_id = $1 AND id = $2
+`, tenantID, caseID).Scan(&status, &rawData); err != nil {
+		return nil, false, fmt.Errorf("load case output: %w", err)
+	}
+	if status == "cancelled" {
+		return nil, true, fmt.Errorf("case %s was cancelled", caseID)
+	}
+
+	if strings.TrimSpace(stepID) == "" {
+		if status != "completed" {
+			return nil, false, nil
+		}
+		out := map[string]any{}
+		if err := json.Unmarshal(rawData, &out); err != nil {
+			return nil, true, fmt.Errorf("decode case data: %w", err)
+		}
+		return out, true, nil
+	}
+
+	var (
+		state     string
+		rawResult []byte
+	)
+	err := s.db.QueryRowContext(ctx, `
+SELECT state, COALESCE(result, '{}'::jsonb)
+FROM case_steps
+WHERE case_id = $1 AND step_id = $2
+`, caseID, stepID).Scan(&state, &rawResult)
+	if errors.Is(err, sql.ErrNoRows) {
+		return nil, false, nil
+	}
+	if err != nil {
+		return nil, false, fmt.Errorf("load step output: %w", err)
+	}
+	switch state {
+	case "completed":
+		out := map[string]any{}
+		if err := json.Unmarshal(rawResult, &out); err != nil {
+			return nil, true, fmt.Errorf("decode step result: %w", err)
+		}
+		return out, true, nil
+	case "failed", "skipped":
+		return nil, true, fmt.Errorf("step %s %s", stepID, state)
+	default:
+		return nil, false, nil
+	}
+}
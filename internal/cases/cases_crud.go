@@ -17,10 +17,31 @@ import (
 )
 
 func (s *CaseService) CreateCase(ctx context.Context, tenantID, createdBy uuid.UUID, req CreateCaseRequest) (Case, []ValidationError, error) {
+	return s.CreateLinkedCase(ctx, tenantID, createdBy, req, nil)
+}
+
+// CaseLinkFunc records rows tied to a new case, such as the sub-workflow
+// call that started it, in the transaction creating the case.
+type CaseLinkFunc func(ctx context.Context, tx *sql.Tx, c Case) error
+
+// CreateLinkedCase creates a case as CreateCase does and runs link before
+// committing, so the case is never created without its link. An error from
+// link rolls the case back and is returned.
+func (s *CaseService) CreateLinkedCase(ctx context.Context, tenantID, createdBy uuid.UUID, req CreateCaseRequest, link CaseLinkFunc) (Case, []ValidationError, error) {
 	start := time.Now()
 	defer func() {
 		observability.DBQueryDurationSeconds.WithLabelValues("case_write").Observe(time.Since(start).Seconds())
 	}()
+	mockProfile := strings.TrimSpace(req.MockProfile)
+	if mockProfile == "" {
+		mockProfile = engine.MockProfileFromContext(ctx)
+	}
+	if mockProfile != "" && !s.mockProfiles {
+		return Case{}, nil, ErrMockProfilesDisabled
+	}
+	if err := s.AdmitExecution(ctx, tenantID, createdBy); err != nil {
+		return Case{}, nil, err
+	}
 	tx, err := s.db.BeginTx(ctx, nil)
 	if err != nil {
 		return Case{}, nil, fmt.Errorf("begin create case tx: %w", err)
@@ -35,18 +56,70 @@ func (s *CaseService) CreateCase(ctx context.Context, tenantID, createdBy uuid.U
 		return Case{}, nil, err
 	}
 
+	lane := req.Lane
+	if lane == "" {
+		lane = engine.LaneFromContext(ctx)
+	}
+	if _, ok := engine.ParseLane(string(lane)); !ok {
+		return Case{}, nil, fmt.Errorf("invalid lane: %s", lane)
+	}
+	if mockProfile != "" {
+		var exists int
+		err := tx.QueryRowContext(ctx, `SELECT 1 FROM mock_profiles WHERE tenant_id = $1 AND name = $2`, tenantID, mockProfile).Scan(&exists)
+		if errors.Is(err, sql.ErrNoRows) {
+			return Case{}, nil, fmt.Errorf("invalid mock_profile: %s", mockProfile)
+		}
+		if err != nil {
+			return Case{}, nil, fmt.Errorf("load mock profile: %w", err)
+		}
+	}
+
 	validation := ValidateCaseData(ct.Schema, req.Data)
 	if len(validation) > 0 {
 		return Case{}, validation, nil
 	}
 
-	workflowID, workflowVersion, astRaw, err := resolveLatestPublishedWorkflowTx(ctx, tx, tenantID, ct.Name, ct.ID)
+	var (
+		workflowID      uuid.UUID
+		workflowVersion int
+		astRaw          []byte
+	)
+	if req.WorkflowID != uuid.Nil {
+		workflowID, workflowVersion = req.WorkflowID, req.WorkflowVersion
+		err = tx.QueryRowContext(ctx, `
+SELECT wv.ast
+FROM workflows w
+JOIN workflow_versions wv ON wv.workflow_id = w.id
+WHERE w.id = $1 AND w.tenant_id = $2 AND wv.version = $3
+`, workflowID, tenantID, workflowVersion).Scan(&astRaw)
+		if errors.Is(err, sql.ErrNoRows) {
+			return Case{}, nil, fmt.Errorf("workflow version %d not found", workflowVersion)
+		}
+	} else {
+		workflowID, workflowVersion, astRaw, err = resolveLatestPublishedWorkflowTx(ctx, tx, tenantID, ct.Name, ct.ID)
+	}
 	if err != nil {
 		if errors.Is(err, sql.ErrNoRows) {
 			return Case{}, nil, fmt.Errorf("no published workflow for case type %s", ct.Name)
 		}
 		return Case{}, nil, err
 	}
+	if s.executions != nil {
+		if err := s.executions.AuthorizeExecution(ctx, tenantID, createdBy, workflowID); err != nil {
+			return Case{}, nil, err
+		}
+	}
+	validation, err = validateWorkflowInput(astRaw, req.Data)
+	if err != nil {
+		return Case{}, nil, err
+	}
+	variableErrs, err := validateVariables(astRaw, req.Variables)
+	if err != nil {
+		return Case{}, nil, err
+	}
+	if validation = append(validation, variableErrs...); len(validation) > 0 {
+		return Case{}, validation, nil
+	}
 
 	caseNumber, err := generateCaseNumberTx(ctx, tx, tenantID, ct.Name)
 	if err != nil {
@@ -57,14 +130,20 @@ func (s *CaseService) CreateCase(ctx context.Context, tenantID, createdBy uuid.U
 	if err != nil {
 		return Case{}, nil, fmt.Errorf("marshal case data: %w", err)
 	}
+	rawVariables := []byte("{}")
+	if len(req.Variables) > 0 {
+		if rawVariables, err = json.Marshal(req.Variables); err != nil {
+			return Case{}, nil, fmt.Errorf("marshal case variables: %w", err)
+		}
+	}
 
 	var c Case
 	err = tx.QueryRowContext(ctx, `
 INSERT INTO cases (
-    tenant_id, case_type_id, case_number, status, data, created_by, priority, workflow_id, workflow_version
-) VALUES ($1, $2, $3, 'open', $4::jsonb, $5, $6, $7, $8)
+    tenant_id, case_type_id, case_number, status, data, created_by, priority, workflow_id, workflow_version, correlation_id, variables, execution_lane, mock_profile
+) VALUES ($1, $2, $3, 'open', $4::jsonb, $5, $6, $7, $8, NULLIF($9, ''), $10::jsonb, $11, NULLIF($12, ''))
 RETURNING id, tenant_id, case_type_id, case_number, status, data, created_at, updated_at, created_by, assigned_to, due_at, priority, version, workflow_id, workflow_version
-`, tenantID, ct.ID, caseNumber, string(rawData), createdBy, req.Priority, workflowID, workflowVersion).Scan(
+`, tenantID, ct.ID, caseNumber, string(rawData), createdBy, req.Priority, workflowID, workflowVersion, observability.CorrelationIDFromContext(ctx), string(rawVariables), string(lane), mockProfile).Scan(
 		&c.ID, &c.TenantID, &c.CaseTypeID, &c.CaseNumber, &c.Status, &rawData, &c.CreatedAt, &c.UpdatedAt,
 		&c.CreatedBy, &c.AssignedTo, &c.DueAt, &c.Priority, &c.Version, &c.WorkflowID, &c.WorkflowVersion,
 	)
@@ -95,10 +174,20 @@ VALUES ($1, $2, 'pending', '{}'::jsonb, '[]'::jsonb, '{}'::jsonb, 0, '{}'::jsonb
 	}); err != nil {
 		return Case{}, nil, err
 	}
+	if link != nil {
+		if err := link(ctx, tx, c); err != nil {
+			return Case{}, nil, err
+		}
+	}
 
 	if err := s.audit.CommitTx(tx); err != nil {
 		return Case{}, nil, fmt.Errorf("commit create case tx: %w", err)
 	}
+	if s.quotas != nil {
+		if err := s.quotas.RecordExecution(ctx, tenantID, createdBy); err != nil {
+			slog.WarnContext(ctx, "record case execution usage failed", "case_id", c.ID.String(), "error", err)
+		}
+	}
 
 	if s.engine != nil {
 		_ = s.engine.EvaluateDAG(ctx, c.ID)
@@ -395,6 +484,34 @@ WHERE tenant_id = $1 AND id = $2
 	return nil
 }
 
+// ResumeCase re-runs the case's failed steps (or the listed ones) without
+// repeating steps that already completed.
+func (s *CaseService) ResumeCase(ctx context.Context, tenantID, caseID, actorID uuid.UUID, stepIDs []string) ([]string, error) {
+	if s.engine == nil {
+		return nil, fmt.Errorf("engine is not configured")
+	}
+	var exists int
+	if err := s.db.QueryRowContext(ctx, `SELECT COUNT(*) FROM cases WHERE tenant_id=$1 AND id=$2`, tenantID, caseID).Scan(&exists); err != nil {
+		return nil, err
+	}
+	if exists == 0 {
+		return nil, sql.ErrNoRows
+	}
+	resumed, err := s.engine.ResumeCase(ctx, caseID, actorID, stepIDs)
+	if err != nil {
+		return nil, err
+	}
+	slog.InfoContext(ctx, "case resumed",
+		append(observability.RequestAttrs(ctx),
+			"tenant_id", tenantID.String(),
+			"case_id", caseID.String(),
+			"actor_id", actorID.String(),
+			"steps", resumed,
+		)...,
+	)
+	return resumed, nil
+}
+
 func (s *CaseService) CancelCase(ctx context.Context, tenantID, caseID, actorID uuid.UUID, reason string) error {
 	if s.engine == nil {
 		return fmt.Errorf("engine is not configured")
@@ -0,0 +1,21 @@
+package cases
+
+import (
+	"context"
+	"errors"
+	"testing"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+func TestCreateCaseRejectsMockProfilesUnlessEnabled(t *testing.T) {
+	s := &CaseService{}
+	if _, _, err := s.CreateCase(context.Background(), uuid.New(), uuid.New(), CreateCaseRequest{CaseType: "loan", MockProfile: "ci"}); !errors.Is(err, ErrMockProfilesDisabled) {
+		t.Fatalf("expected a requested mock profile to be rejected, got %v", err)
+	}
+	ctx := engine.WithMockProfile(context.Background(), "ci")
+	if _, _, err := s.CreateCase(ctx, uuid.New(), uuid.New(), CreateCaseRequest{CaseType: "loan"}); !errors.Is(err, ErrMockProfilesDisabled) {
+		t.Fatalf("expected an inherited mock profile to be rejected, got %v", err)
+	}
+}
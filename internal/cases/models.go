@@ -2,9 +2,11 @@ package cases
 
 import (
 	"encoding/json"
+	"errors"
 	"time"
 
 	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
 )
 
 type ValidationError struct {
@@ -78,6 +80,7 @@ type CaseStep struct {
 	RetryCount  int             `json:"retry_count"`
 	DraftData   json.RawMessage `json:"draft_data,omitempty"`
 	Metadata    json.RawMessage `json:"metadata,omitempty"`
+	Logs        json.RawMessage `json:"logs,omitempty"`
 }
 
 type CaseEvent struct {
@@ -103,10 +106,34 @@ type CaseDocument struct {
 	DeletedAt  *time.Time `json:"deleted_at,omitempty"`
 }
 
+// ErrMockProfilesDisabled is returned by CreateCase for a case with a mock
+// profile outside a development environment.
+var ErrMockProfilesDisabled = errors.New("mock_profile is only accepted in development environments")
+
+// ErrExecutionForbidden is returned when a principal may not start cases of
+// a workflow.
+var ErrExecutionForbidden = errors.New("principal may not execute this workflow")
+
 type CreateCaseRequest struct {
 	CaseType string                 `json:"case_type"`
 	Data     map[string]interface{} `json:"data"`
 	Priority int                    `json:"priority"`
+	// Variables override the workflow's environment values for this case.
+	// Each must be declared by one of the workflow's overlays, with the same
+	// JSON type.
+	Variables map[string]interface{} `json:"variables,omitempty"`
+	// Lane is the execution lane the case's steps run in. Empty takes the
+	// lane of ctx; see engine.LaneFromContext.
+	Lane engine.Lane `json:"lane,omitempty"`
+	// MockProfile names the mock profile that answers the case's tool
+	// calls. Empty takes the profile of ctx, so sub-workflows inherit it;
+	// see engine.MockProfileFromContext.
+	MockProfile string `json:"mock_profile,omitempty"`
+
+	// WorkflowID and WorkflowVersion pin the case to one workflow version
+	// instead of the latest published one; replays use them.
+	WorkflowID      uuid.UUID `json:"-"`
+	WorkflowVersion int       `json:"-"`
 }
 
 type PatchResult struct {
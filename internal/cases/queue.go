@@ -0,0 +1,294 @@
+package cases
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"log/slog"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+var (
+	// ErrQueueFull is returned by CreateQueue.Enqueue when the queue is at capacity.
+	ErrQueueFull = errors.New("case creation queue full")
+	// ErrJobNotFound is returned by CreateQueue.Get for unknown jobs and jobs
+	// of other tenants.
+	ErrJobNotFound = errors.New("case creation job not found")
+)
+
+const (
+	JobQueued    = "queued"
+	JobRunning   = "running"
+	JobCompleted = "completed"
+	JobFailed    = "failed"
+
+	// createClaimTimeout is how long a job may stay running before another
+	// worker takes it over, assuming the worker that claimed it has died.
+	createClaimTimeout = 5 * time.Minute
+)
+
+// CreateJob is one asynchronous case creation. CaseID is set once the case
+// exists; the workflow then runs on the engine as for synchronous creation.
+type CreateJob struct {
+	ID         uuid.UUID         `json:"execution_id"`
+	TenantID   uuid.UUID         `json:"-"`
+	Status     string            `json:"status"`
+	Priority   int               `json:"priority"`
+	CaseType   string            `json:"case_type"`
+	CaseID     *uuid.UUID        `json:"case_id,omitempty"`
+	CaseNumber string            `json:"case_number,omitempty"`
+	Error      string            `json:"error,omitempty"`
+	Validation []ValidationError `json:"validation,omitempty"`
+	EnqueuedAt time.Time         `json:"enqueued_at"`
+	StartedAt  *time.Time        `json:"started_at,omitempty"`
+	FinishedAt *time.Time        `json:"finished_at,omitempty"`
+}
+
+type CreateQueueConfig struct {
+	Workers      int
+	Capacity     int
+	Retention    time.Duration
+	PollInterval time.Duration
+}
+
+type caseCreator interface {
+	CreateCase(ctx context.Context, tenantID, createdBy uuid.UUID, req CreateCaseRequest) (Case, []ValidationError, error)
+}
+
+// CreateQueue creates cases on a bounded worker pool. Jobs are kept in
+// case_create_jobs and claimed with SKIP LOCKED, so they survive restarts
+// and every replica can run workers. Higher-priority jobs run first, FIFO
+// within a priority. Finished jobs are kept for Retention so callers can
+// poll their outcome.
+type CreateQueue struct {
+	db           *sql.DB
+	creator      caseCreator
+	workers      int
+	capacity     int
+	retention    time.Duration
+	pollInterval time.Duration
+	wake         chan struct{}
+}
+
+func NewCreateQueue(db *sql.DB, creator caseCreator, cfg CreateQueueConfig) *CreateQueue {
+	if cfg.Workers <= 0 {
+		cfg.Workers = 4
+	}
+	if cfg.Capacity <= 0 {
+		cfg.Capacity = 1000
+	}
+	if cfg.Retention <= 0 {
+		cfg.Retention = time.Hour
+	}
+	if cfg.PollInterval <= 0 {
+		cfg.PollInterval = time.Second
+	}
+	return &CreateQueue{
+		db:           db,
+		creator:      creator,
+		workers:      cfg.Workers,
+		capacity:     cfg.Capacity,
+		retention:    cfg.Retention,
+		pollInterval: cfg.PollInterval,
+		wake:         make(chan struct{}, cfg.Workers),
+	}
+}
+
+// Start runs the workers until ctx is done.
+func (q *CreateQueue) Start(ctx context.Context) {
+	for i := 0; i < q.workers; i++ {
+		go q.work(ctx)
+	}
+}
+
+// Enqueue stores a job for req. Capacity is per tenant, so one tenant
+// filling the queue does not turn away the others, and counts the tenant's
+// queued jobs on every replica. The count and insert run under a
+// per-tenant advisory lock so concurrent calls cannot overshoot it.
+func (q *CreateQueue) Enqueue(ctx context.Context, tenantID, createdBy uuid.UUID, req CreateCaseRequest) (CreateJob, error) {
+	if _, err := q.db.ExecContext(ctx, `
+DELETE FROM case_create_jobs WHERE finished_at < now() - make_interval(secs => $1)
+`, q.retention.Seconds()); err != nil {
+		return CreateJob{}, fmt.Errorf("prune case creation jobs: %w", err)
+	}
+	raw, err := json.Marshal(req)
+	if err != nil {
+		return CreateJob{}, fmt.Errorf("encode case creation request: %w", err)
+	}
+	job := CreateJob{
+		ID:       uuid.New(),
+		TenantID: tenantID,
+		Status:   JobQueued,
+		Priority: req.Priority,
+		CaseType: req.CaseType,
+	}
+	tx, err := q.db.BeginTx(ctx, nil)
+	if err != nil {
+		return CreateJob{}, fmt.Errorf("begin case creation enqueue: %w", err)
+	}
+	defer func() { _ = tx.Rollback() }()
+	if _, err := tx.ExecContext(ctx, `SELECT pg_advisory_xact_lock(hashtextextended($1::text, 0))`, "aceryx.case_create_jobs:"+tenantID.String()); err != nil {
+		return CreateJob{}, fmt.Errorf("acquire case creation queue lock: %w", err)
+	}
+	var queued int
+	if err := tx.QueryRowContext(ctx, `SELECT count(*) FROM case_create_jobs WHERE tenant_id = $1 AND status = $2`, tenantID, JobQueued).Scan(&queued); err != nil {
+		return CreateJob{}, fmt.Errorf("count queued case creation jobs: %w", err)
+	}
+	if queued >= q.capacity {
+		return CreateJob{}, ErrQueueFull
+	}
+	if err := tx.QueryRowContext(ctx, `
+INSERT INTO case_create_jobs (id, tenant_id, created_by, status, priority, case_type, request)
+VALUES ($1, $2, $3, $4, $5, $6, $7)
+RETURNING enqueued_at
+`, job.ID, tenantID, createdBy, JobQueued, req.Priority, req.CaseType, raw).Scan(&job.EnqueuedAt); err != nil {
+		return CreateJob{}, fmt.Errorf("insert case creation job: %w", err)
+	}
+	if err := tx.Commit(); err != nil {
+		return CreateJob{}, fmt.Errorf("commit case creation job: %w", err)
+	}
+	select {
+	case q.wake <- struct{}{}:
+	default:
+	}
+	return job, nil
+}
+
+// Get returns a job owned by tenantID.
+func (q *CreateQueue) Get(ctx context.Context, tenantID, id uuid.UUID) (CreateJob, error) {
+	var (
+		job        CreateJob
+		caseID     uuid.NullUUID
+		caseNumber sql.NullString
+		errMsg     sql.NullString
+		validation []byte
+		startedAt  sql.NullTime
+		finishedAt sql.NullTime
+	)
+	err := q.db.QueryRowContext(ctx, `
+SELECT id, tenant_id, status, priority, case_type, case_id, case_number, error, validation, enqueued_at, started_at, finished_at
+FROM case_create_jobs
+WHERE tenant_id = $1 AND id = $2
+`, tenantID, id).Scan(&job.ID, &job.TenantID, &job.Status, &job.Priority, &job.CaseType, &caseID, &caseNumber, &errMsg, &validation, &job.EnqueuedAt, &startedAt, &finishedAt)
+	if errors.Is(err, sql.ErrNoRows) {
+		return CreateJob{}, ErrJobNotFound
+	}
+	if err != nil {
+		return CreateJob{}, fmt.Errorf("load case creation job: %w", err)
+	}
+	if caseID.Valid {
+		job.CaseID = &caseID.UUID
+	}
+	job.CaseNumber = caseNumber.String
+	job.Error = errMsg.String
+	if len(validation) > 0 {
+		if err := json.Unmarshal(validation, &job.Validation); err != nil {
+			return CreateJob{}, fmt.Errorf("decode case creation job validation: %w", err)
+		}
+	}
+	if startedAt.Valid {
+		job.StartedAt = &startedAt.Time
+	}
+	if finishedAt.Valid {
+		job.FinishedAt = &finishedAt.Time
+	}
+	return job, nil
+}
+
+func (q *CreateQueue) work(ctx context.Context) {
+	ticker := time.NewTicker(q.pollInterval)
+	defer ticker.Stop()
+	for {
+		for {
+			ran, err := q.runNext(ctx)
+			if err != nil && ctx.Err() == nil {
+				slog.WarnContext(ctx, "case creation job failed", "error", err)
+			}
+			if !ran {
+				break
+			}
+		}
+		select {
+		case <-ctx.Done():
+			return
+		case <-q.wake:
+		case <-ticker.C:
+		}
+	}
+}
+
+// runNext claims the next job and creates its case. Jobs left running by a
+// worker that died are claimed again after createClaimTimeout.
+func (q *CreateQueue) runNext(ctx context.Context) (bool, error) {
+	var (
+		id        uuid.UUID
+		tenantID  uuid.UUID
+		createdBy uuid.UUID
+		raw       []byte
+	)
+	err := q.db.QueryRowContext(ctx, `
+UPDATE case_create_jobs
+SET status = $1, started_at = now()
+WHERE id = (
+    SELECT id FROM case_create_jobs
+    WHERE status = $2 OR (status = $1 AND started_at < now() - make_interval(secs => $3))
+    ORDER BY priority DESC, seq
+    LIMIT 1
+    FOR UPDATE SKIP LOCKED
+)
+RETURNING id, tenant_id, created_by, request
+`, JobRunning, JobQueued, createClaimTimeout.Seconds()).Scan(&id, &tenantID, &createdBy, &raw)
+	if errors.Is(err, sql.ErrNoRows) {
+		return false, nil
+	}
+	if err != nil {
+		return false, fmt.Errorf("claim case creation job: %w", err)
+	}
+
+	var (
+		c          Case
+		validation []ValidationError
+		req        CreateCaseRequest
+	)
+	if err = json.Unmarshal(raw, &req); err == nil {
+		c, validation, err = q.creator.CreateCase(ctx, tenantID, createdBy, req)
+	}
+	if err != nil && ctx.Err() != nil {
+		// Shutting down: hand the job back rather than fail it.
+		if _, err := q.db.ExecContext(context.WithoutCancel(ctx), `
+UPDATE case_create_jobs SET status = $2, started_at = NULL WHERE id = $1
+`, id, JobQueued); err != nil {
+			return true, fmt.Errorf("release case creation job %s: %w", id, err)
+		}
+		return false, nil
+	}
+
+	status, errMsg := JobCompleted, ""
+	var caseID uuid.NullUUID
+	var caseNumber sql.NullString
+	var validationJSON []byte
+	switch {
+	case err != nil:
+		status, errMsg = JobFailed, err.Error()
+	case len(validation) > 0:
+		status, errMsg = JobFailed, "validation_failed"
+		if validationJSON, err = json.Marshal(validation); err != nil {
+			return true, fmt.Errorf("encode case creation job validation: %w", err)
+		}
+	default:
+		caseID = uuid.NullUUID{UUID: c.ID, Valid: true}
+		caseNumber = sql.NullString{String: c.CaseNumber, Valid: true}
+	}
+	if _, err := q.db.ExecContext(context.WithoutCancel(ctx), `
+UPDATE case_create_jobs
+SET status = $2, case_id = $3, case_number = $4, error = NULLIF($5, ''), validation = $6, finished_at = now()
+WHERE id = $1
+`, id, status, caseID, caseNumber, errMsg, validationJSON); err != nil {
+		return true, fmt.Errorf("finish case creation job %s: %w", id, err)
+	}
+	return true, nil
+}
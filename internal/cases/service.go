@@ -3,6 +3,9 @@ package cases
 import (
 	"context"
 	"database/sql"
+	"errors"
+	"fmt"
+	"strings"
 	"time"
 
 	"github.com/google/uuid"
@@ -13,6 +16,7 @@ import (
 type Engine interface {
 	EvaluateDAG(ctx context.Context, caseID uuid.UUID) error
 	CancelCase(ctx context.Context, caseID uuid.UUID, actorID uuid.UUID, reason string) error
+	ResumeCase(ctx context.Context, caseID, actorID uuid.UUID, stepIDs []string) ([]string, error)
 }
 
 type CaseTypeService struct {
@@ -24,12 +28,30 @@ type CaseService struct {
 	engine Engine
 	notify Notifier
 	audit  *audit.Service
+	quotas QuotaGate
+	// executions decides who may start cases of a workflow; nil allows all.
+	executions ExecutionAuthorizer
+	// mockProfiles allows cases to be created with a mock profile.
+	mockProfiles bool
 }
 
 type Notifier interface {
 	Notify(ctx context.Context, event notify.NotifyEvent) error
 }
 
+// QuotaGate admits case creation against usage quotas and counts the cases
+// that were created.
+type QuotaGate interface {
+	AdmitExecution(ctx context.Context, tenantID, principalID uuid.UUID) error
+	RecordExecution(ctx context.Context, tenantID, principalID uuid.UUID) error
+}
+
+// ExecutionAuthorizer decides whether principalID may start cases of
+// workflowID, returning ErrExecutionForbidden when it may not.
+type ExecutionAuthorizer interface {
+	AuthorizeExecution(ctx context.Context, tenantID, principalID, workflowID uuid.UUID) error
+}
+
 type ReportsService struct {
 	db              *sql.DB
 	refreshInterval time.Duration
@@ -47,6 +69,62 @@ func (s *CaseService) SetNotifier(n Notifier) {
 	s.notify = n
 }
 
+func (s *CaseService) SetQuotaGate(gate QuotaGate) {
+	s.quotas = gate
+}
+
+func (s *CaseService) SetExecutionAuthorizer(authorizer ExecutionAuthorizer) {
+	s.executions = authorizer
+}
+
+// AuthorizeExecution reports whether principalID may start a case of
+// caseType now, judged on the case type's published workflow. CreateCase
+// checks this itself; callers that defer creation check it up front.
+func (s *CaseService) AuthorizeExecution(ctx context.Context, tenantID, principalID uuid.UUID, caseType string) error {
+	if s.executions == nil {
+		return nil
+	}
+	var workflowID uuid.UUID
+	err := s.db.QueryRowContext(ctx, `
+SELECT w.id
+FROM workflows w
+JOIN workflow_versions wv ON wv.workflow_id = w.id
+WHERE w.tenant_id = $1
+  AND (w.case_type = $2 OR w.case_type IN (SELECT id::text FROM case_types WHERE tenant_id = $1 AND name = $2))
+  AND wv.status = 'published'
+ORDER BY wv.version DESC
+LIMIT 1
+`, tenantID, strings.TrimSpace(caseType)).Scan(&workflowID)
+	if errors.Is(err, sql.ErrNoRows) {
+		// CreateCase reports the missing case type or workflow.
+		return nil
+	}
+	if err != nil {
+		return fmt.Errorf("resolve workflow for execution check: %w", err)
+	}
+	return s.executions.AuthorizeExecution(ctx, tenantID, principalID, workflowID)
+}
+
+// SetMockProfilesEnabled allows cases to be created with a mock profile,
+// whose canned tool responses stand in for real systems. Only development
+// environments should enable it.
+func (s *CaseService) SetMockProfilesEnabled(enabled bool) {
+	s.mockProfiles = enabled
+}
+
+func (s *CaseService) MockProfilesEnabled() bool {
+	return s.mockProfiles
+}
+
+// AdmitExecution reports whether createdBy may start a case now. CreateCase
+// checks this itself; callers that defer creation check it up front.
+func (s *CaseService) AdmitExecution(ctx context.Context, tenantID, createdBy uuid.UUID) error {
+	if s.quotas == nil {
+		return nil
+	}
+	return s.quotas.AdmitExecution(ctx, tenantID, createdBy)
+}
+
 func (s *CaseService) SetAuditService(auditSvc *audit.Service) {
 	if auditSvc == nil {
 		return
@@ -1,9 +1,13 @@
 package cases
 
 import (
+	"encoding/json"
 	"fmt"
 	"reflect"
 	"regexp"
+	"strings"
+
+	"github.com/neural-chilli/aceryx/internal/engine"
 )
 
 func ValidateCaseData(schema CaseTypeSchema, data map[string]interface{}) []ValidationError {
@@ -21,6 +25,44 @@ func ValidateCaseData(schema CaseTypeSchema, data map[string]interface{}) []Vali
 	return errors
 }
 
+// validateWorkflowInput checks data against the input_schema of the workflow
+// the case will run, reporting violations with dotted field paths.
+func validateWorkflowInput(astRaw []byte, data map[string]interface{}) ([]ValidationError, error) {
+	var ast engine.WorkflowAST
+	if err := json.Unmarshal(astRaw, &ast); err != nil {
+		return nil, fmt.Errorf("decode workflow ast: %w", err)
+	}
+	violations, err := ast.ValidateInput(data)
+	if err != nil {
+		return nil, err
+	}
+	errs := make([]ValidationError, 0, len(violations))
+	for _, v := range violations {
+		field := strings.ReplaceAll(strings.TrimPrefix(v.Path, "/"), "/", ".")
+		errs = append(errs, ValidationError{Field: field, Rule: "input_schema", Message: v.Message})
+	}
+	return errs, nil
+}
+
+// validateVariables checks case variables against the environment values
+// the workflow declares.
+func validateVariables(astRaw []byte, variables map[string]interface{}) ([]ValidationError, error) {
+	if len(variables) == 0 {
+		return nil, nil
+	}
+	var ast engine.WorkflowAST
+	if err := json.Unmarshal(astRaw, &ast); err != nil {
+		return nil, fmt.Errorf("decode workflow ast: %w", err)
+	}
+	violations := ast.ValidateVariables(variables)
+	errs := make([]ValidationError, 0, len(violations))
+	for _, v := range violations {
+		name := strings.TrimPrefix(v.Path, "/")
+		errs = append(errs, ValidationError{Field: "variables." + name, Rule: v.Keyword, Message: v.Message, Value: variables[name]})
+	}
+	return errs, nil
+}
+
 func validateField(path string, def SchemaField, value interface{}) []ValidationError {
 	errs := make([]ValidationError, 0)
 
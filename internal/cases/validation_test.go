@@ -56,3 +56,20 @@ func TestValidateCaseData_Table(t *testing.T) {
 		})
 	}
 }
+
+func TestValidateWorkflowInput(t *testing.T) {
+	ast := []byte(`{"steps":[{"id":"a","type":"rule"}],"input_schema":{"type":"object","required":["applicant"],"properties":{"applicant":{"type":"object","properties":{"age":{"type":"integer","minimum":18}}}}}}`)
+	errs, err := validateWorkflowInput(ast, map[string]interface{}{"applicant": map[string]interface{}{"age": 16}})
+	if err != nil {
+		t.Fatalf("validate: %v", err)
+	}
+	if len(errs) != 1 || errs[0].Field != "applicant.age" || errs[0].Rule != "input_schema" {
+		t.Fatalf("unexpected errors %+v", errs)
+	}
+	if errs, err := validateWorkflowInput(ast, map[string]interface{}{}); err != nil || len(errs) != 1 {
+		t.Fatalf("expected missing applicant to be reported, got %+v (%v)", errs, err)
+	}
+	if errs, err := validateWorkflowInput([]byte(`{"steps":[]}`), map[string]interface{}{"anything": true}); err != nil || len(errs) != 0 {
+		t.Fatalf("workflows without an input schema accept any data, got %+v (%v)", errs, err)
+	}
+}
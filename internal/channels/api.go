@@ -2,17 +2,23 @@ package channels
 
 import (
 	"encoding/json"
+	"errors"
 	"net/http"
 	"strconv"
 	"strings"
+	"time"
 
 	"github.com/google/uuid"
 	"github.com/neural-chilli/aceryx/api/middleware"
+	"github.com/neural-chilli/aceryx/internal/cases"
 )
 
 type API struct {
 	Store   ChannelStore
 	Manager *ChannelManager
+	// Executions, when set, requires whoever binds a channel to a workflow
+	// to be allowed to execute it, since the channel starts its cases.
+	Executions cases.ExecutionAuthorizer
 }
 
 func NewAPI(store ChannelStore, manager *ChannelManager) *API {
@@ -48,6 +54,9 @@ func (a *API) Create(w http.ResponseWriter, r *http.Request) {
 		req.ID = uuid.New()
 	}
 	req.TenantID = principal.TenantID
+	if !validChannelConfig(w, &req) || !a.authorizeWorkflow(w, r, principal, req.WorkflowID) {
+		return
+	}
 	if err := a.Store.Create(r.Context(), &req); err != nil {
 		writeError(w, http.StatusBadRequest, "channel_create_failed")
 		return
@@ -55,6 +64,24 @@ func (a *API) Create(w http.ResponseWriter, r *http.Request) {
 	writeJSON(w, http.StatusCreated, req)
 }
 
+// authorizeWorkflow writes 403 when the principal may not execute the
+// channel's workflow.
+func (a *API) authorizeWorkflow(w http.ResponseWriter, r *http.Request, principal *middleware.Principal, workflowID *uuid.UUID) bool {
+	if a.Executions == nil || workflowID == nil {
+		return true
+	}
+	err := a.Executions.AuthorizeExecution(r.Context(), principal.TenantID, principal.ID, *workflowID)
+	switch {
+	case errors.Is(err, cases.ErrExecutionForbidden):
+		writeError(w, http.StatusForbidden, "forbidden")
+		return false
+	case err != nil:
+		writeError(w, http.StatusInternalServerError, "workflow_authorization_failed")
+		return false
+	}
+	return true
+}
+
 func (a *API) Get(w http.ResponseWriter, r *http.Request) {
 	principal := middleware.PrincipalFromContext(r.Context())
 	if principal == nil {
@@ -92,6 +119,9 @@ func (a *API) Update(w http.ResponseWriter, r *http.Request) {
 	}
 	req.ID = id
 	req.TenantID = principal.TenantID
+	if !validChannelConfig(w, &req) || !a.authorizeWorkflow(w, r, principal, req.WorkflowID) {
+		return
+	}
 	if err := a.Store.Update(r.Context(), &req); err != nil {
 		writeError(w, http.StatusBadRequest, "channel_update_failed")
 		return
@@ -162,6 +192,79 @@ func (a *API) Events(w http.ResponseWriter, r *http.Request) {
 	writeJSON(w, http.StatusOK, items)
 }
 
+// Schedule reports a schedule channel's next fire time and last outcome, and
+// its next count fire times (default 5) in the channel's timezone.
+func (a *API) Schedule(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	id, err := uuid.Parse(strings.TrimSpace(r.PathValue("id")))
+	if err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_id")
+		return
+	}
+	item, err := a.Store.Get(r.Context(), principal.TenantID, id)
+	if err != nil || item.Type != ChannelSchedule {
+		writeError(w, http.StatusNotFound, "channel_not_found")
+		return
+	}
+	store, ok := a.Store.(ScheduleStore)
+	if !ok {
+		writeError(w, http.StatusServiceUnavailable, "schedule_store_unavailable")
+		return
+	}
+	state, err := store.GetScheduleState(r.Context(), id)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "channel_schedule_failed")
+		return
+	}
+	if state == nil {
+		state = &ScheduleState{ChannelID: id}
+	}
+	count, _ := strconv.Atoi(r.URL.Query().Get("count"))
+	if count <= 0 {
+		count = 5
+	}
+	count = min(count, 50)
+	var cfg ScheduleConfig
+	_ = json.Unmarshal(item.Config, &cfg)
+	calendars, err := store.ScheduleCalendars(r.Context(), principal.TenantID, cfg.Calendars)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "channel_schedule_failed")
+		return
+	}
+	upcoming, err := cfg.NextRuns(time.Now(), count, calendars)
+	if err != nil {
+		upcoming = []time.Time{}
+	}
+	writeJSON(w, http.StatusOK, scheduleResponse{ScheduleState: state, Upcoming: upcoming})
+}
+
+type scheduleResponse struct {
+	*ScheduleState
+	Upcoming []time.Time `json:"upcoming"`
+}
+
+// validChannelConfig rejects schedule channels with a bad cron expression or
+// missed-run policy. Other channel types are checked when they start.
+func validChannelConfig(w http.ResponseWriter, ch *Channel) bool {
+	if ch.Type != ChannelSchedule {
+		return true
+	}
+	var cfg ScheduleConfig
+	if err := json.Unmarshal(ch.Config, &cfg); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_schedule")
+		return false
+	}
+	if err := cfg.Validate(); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_schedule")
+		return false
+	}
+	return true
+}
+
 func (a *API) parseManagedChannelID(w http.ResponseWriter, r *http.Request) (uuid.UUID, bool) {
 	principal := middleware.PrincipalFromContext(r.Context())
 	if principal == nil {
@@ -0,0 +1,250 @@
+package channels
+
+import (
+	"bufio"
+	"context"
+	"errors"
+	"fmt"
+	"strings"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+var (
+	ErrInvalidCalendar = errors.New("invalid schedule calendar")
+	ErrCalendarInUse   = errors.New("schedule calendar is in use")
+)
+
+// ScheduleCalendar is a named set of days, such as a country's public
+// holidays, on which schedule channels that list it do not run.
+type ScheduleCalendar struct {
+	ID        uuid.UUID      `json:"id"`
+	TenantID  uuid.UUID      `json:"tenant_id"`
+	Name      string         `json:"name"`
+	Dates     []CalendarDate `json:"dates"`
+	Weekdays  []string       `json:"weekdays"`
+	CreatedAt time.Time      `json:"created_at"`
+	UpdatedAt time.Time      `json:"updated_at"`
+}
+
+// CalendarDate is an excluded day. A yearly date repeats on the same month
+// and day every year.
+type CalendarDate struct {
+	Date   string `json:"date"`
+	Name   string `json:"name,omitempty"`
+	Yearly bool   `json:"yearly,omitempty"`
+}
+
+type CalendarStore interface {
+	ListCalendars(ctx context.Context, tenantID uuid.UUID) ([]*ScheduleCalendar, error)
+	GetCalendar(ctx context.Context, tenantID, calendarID uuid.UUID) (*ScheduleCalendar, error)
+	CreateCalendar(ctx context.Context, calendar *ScheduleCalendar) error
+	UpdateCalendar(ctx context.Context, calendar *ScheduleCalendar) error
+	// DeleteCalendar returns ErrCalendarInUse while a schedule channel lists
+	// the calendar.
+	DeleteCalendar(ctx context.Context, tenantID, calendarID uuid.UUID) error
+}
+
+func (c ScheduleCalendar) Validate() error {
+	if strings.TrimSpace(c.Name) == "" {
+		return fmt.Errorf("%w: name is required", ErrInvalidCalendar)
+	}
+	for _, d := range c.Dates {
+		if _, err := time.Parse(time.DateOnly, d.Date); err != nil {
+			return fmt.Errorf("%w: date %q is not YYYY-MM-DD", ErrInvalidCalendar, d.Date)
+		}
+	}
+	for _, day := range c.Weekdays {
+		if _, ok := parseWeekday(day); !ok {
+			return fmt.Errorf("%w: unknown weekday %q", ErrInvalidCalendar, day)
+		}
+	}
+	return nil
+}
+
+var weekdayNames = map[string]time.Weekday{
+	"sun": time.Sunday, "mon": time.Monday, "tue": time.Tuesday, "wed": time.Wednesday,
+	"thu": time.Thursday, "fri": time.Friday, "sat": time.Saturday,
+}
+
+// parseWeekday accepts full or three-letter English day names.
+func parseWeekday(raw string) (time.Weekday, bool) {
+	raw = strings.ToLower(strings.TrimSpace(raw))
+	if len(raw) < 3 {
+		return 0, false
+	}
+	day, ok := weekdayNames[raw[:3]]
+	if !ok || !strings.HasPrefix(strings.ToLower(day.String()), raw) {
+		return 0, false
+	}
+	return day, true
+}
+
+// scheduleExclusions are the days a schedule skips, judged by the local
+// date of each fire time in the schedule's timezone.
+type scheduleExclusions struct {
+	dates    map[string]bool
+	yearly   map[string]bool
+	weekdays map[time.Weekday]bool
+}
+
+func newScheduleExclusions(cfg ScheduleConfig, calendars []*ScheduleCalendar) scheduleExclusions {
+	x := scheduleExclusions{dates: map[string]bool{}, yearly: map[string]bool{}, weekdays: map[time.Weekday]bool{}}
+	add := func(dates []CalendarDate, weekdays []string) {
+		for _, d := range dates {
+			if d.Yearly && len(d.Date) == len(time.DateOnly) {
+				x.yearly[d.Date[5:]] = true
+			} else {
+				x.dates[d.Date] = true
+			}
+		}
+		for _, raw := range weekdays {
+			if day, ok := parseWeekday(raw); ok {
+				x.weekdays[day] = true
+			}
+		}
+	}
+	dates := make([]CalendarDate, 0, len(cfg.ExcludeDates))
+	for _, d := range cfg.ExcludeDates {
+		dates = append(dates, CalendarDate{Date: d})
+	}
+	add(dates, cfg.ExcludeWeekdays)
+	for _, c := range calendars {
+		if c != nil {
+			add(c.Dates, c.Weekdays)
+		}
+	}
+	return x
+}
+
+func (x scheduleExclusions) excludes(t time.Time) bool {
+	date := t.Format(time.DateOnly)
+	return x.weekdays[t.Weekday()] || x.dates[date] || x.yearly[date[5:]]
+}
+
+// maxExcludedRuns bounds the search for an allowed fire time, so a schedule
+// whose every run is excluded reports that it never fires.
+const maxExcludedRuns = 5000
+
+// nextAllowed returns the first fire time after after that is not excluded.
+func nextAllowed(cron engine.Cron, after time.Time, x scheduleExclusions) (time.Time, bool) {
+	t := after
+	for i := 0; i < maxExcludedRuns; i++ {
+		next, ok := cron.Next(t)
+		if !ok {
+			return time.Time{}, false
+		}
+		if !x.excludes(next) {
+			return next, true
+		}
+		t = next
+	}
+	return time.Time{}, false
+}
+
+// ParseICal reads the all-day events of an iCalendar (RFC 5545) file, such
+// as a published holiday calendar, as excluded dates. Multi-day events give
+// one date per day, and FREQ=YEARLY events become yearly dates; other
+// recurrence rules are rejected. The calendar's X-WR-CALNAME is returned as
+// its name.
+func ParseICal(raw string) (string, []CalendarDate, error) {
+	lines := unfoldICal(raw)
+	var (
+		name   string
+		dates  []CalendarDate
+		event  map[string]string
+		inside bool
+	)
+	for _, line := range lines {
+		key, value, ok := strings.Cut(line, ":")
+		if !ok {
+			continue
+		}
+		prop, _, _ := strings.Cut(key, ";")
+		prop = strings.ToUpper(prop)
+		switch {
+		case prop == "BEGIN" && strings.EqualFold(value, "VEVENT"):
+			event, inside = map[string]string{}, true
+		case prop == "END" && strings.EqualFold(value, "VEVENT"):
+			parsed, err := icalEventDates(event)
+			if err != nil {
+				return "", nil, err
+			}
+			dates = append(dates, parsed...)
+			inside = false
+		case inside:
+			event[prop] = value
+		case prop == "X-WR-CALNAME":
+			name = unescapeICal(value)
+		}
+	}
+	if len(dates) == 0 {
+		return "", nil, fmt.Errorf("%w: no events found", ErrInvalidCalendar)
+	}
+	return name, dates, nil
+}
+
+func icalEventDates(event map[string]string) ([]CalendarDate, error) {
+	start, err := icalDate(event["DTSTART"])
+	if err != nil {
+		return nil, err
+	}
+	summary := unescapeICal(event["SUMMARY"])
+	end := start.AddDate(0, 0, 1)
+	if raw := event["DTEND"]; raw != "" {
+		if end, err = icalDate(raw); err != nil {
+			return nil, err
+		}
+	}
+	yearly := false
+	if rule := strings.ToUpper(event["RRULE"]); rule != "" {
+		if !strings.Contains(rule, "FREQ=YEARLY") || strings.Contains(rule, "BYDAY") {
+			return nil, fmt.Errorf("%w: unsupported recurrence %q in %q", ErrInvalidCalendar, rule, summary)
+		}
+		yearly = true
+	}
+	var out []CalendarDate
+	for d := start; d.Before(end) && len(out) < 31; d = d.AddDate(0, 0, 1) {
+		out = append(out, CalendarDate{Date: d.Format(time.DateOnly), Name: summary, Yearly: yearly})
+	}
+	if len(out) == 0 {
+		out = append(out, CalendarDate{Date: start.Format(time.DateOnly), Name: summary, Yearly: yearly})
+	}
+	return out, nil
+}
+
+// icalDate reads a DATE (20261225) or the date part of a DATE-TIME
+// (20261225T000000Z).
+func icalDate(raw string) (time.Time, error) {
+	raw = strings.TrimSpace(raw)
+	if len(raw) < 8 {
+		return time.Time{}, fmt.Errorf("%w: missing or invalid DTSTART %q", ErrInvalidCalendar, raw)
+	}
+	d, err := time.Parse("20060102", raw[:8])
+	if err != nil {
+		return time.Time{}, fmt.Errorf("%w: invalid date %q", ErrInvalidCalendar, raw)
+	}
+	return d, nil
+}
+
+// unfoldICal joins continuation lines, which start with a space or tab.
+func unfoldICal(raw string) []string {
+	var lines []string
+	scanner := bufio.NewScanner(strings.NewReader(raw))
+	scanner.Buffer(make([]byte, 64*1024), 1024*1024)
+	for scanner.Scan() {
+		line := strings.TrimRight(scanner.Text(), "\r")
+		if (strings.HasPrefix(line, " ") || strings.HasPrefix(line, "\t")) && len(lines) > 0 {
+			lines[len(lines)-1] += line[1:]
+			continue
+		}
+		lines = append(lines, line)
+	}
+	return lines
+}
+
+func unescapeICal(s string) string {
+	return strings.NewReplacer(`\n`, " ", `\N`, " ", `\,`, ",", `\;`, ";", `\\`, `\`).Replace(strings.TrimSpace(s))
+}
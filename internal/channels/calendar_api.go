@@ -0,0 +1,197 @@
+package channels
+
+import (
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"io"
+	"mime"
+	"net/http"
+	"strings"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/api/middleware"
+)
+
+// maxICalBytes bounds an imported iCalendar file.
+const maxICalBytes = 2 << 20
+
+// calendarRequest is the body of a calendar create or update. Dates from an
+// inline iCal file are added to Dates.
+type calendarRequest struct {
+	Name     string         `json:"name"`
+	Dates    []CalendarDate `json:"dates"`
+	Weekdays []string       `json:"weekdays"`
+	ICal     string         `json:"ical"`
+}
+
+func (a *API) ListCalendars(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	store, ok := a.calendarStore(w)
+	if !ok {
+		return
+	}
+	items, err := store.ListCalendars(r.Context(), principal.TenantID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "calendars_list_failed")
+		return
+	}
+	writeJSON(w, http.StatusOK, items)
+}
+
+// CreateCalendar takes a JSON calendar, or a raw iCalendar file when the
+// Content-Type is text/calendar. An imported file is named by ?name= or its
+// X-WR-CALNAME.
+func (a *API) CreateCalendar(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	store, ok := a.calendarStore(w)
+	if !ok {
+		return
+	}
+	calendar, ok := readCalendar(w, r)
+	if !ok {
+		return
+	}
+	calendar.ID = uuid.New()
+	calendar.TenantID = principal.TenantID
+	if err := store.CreateCalendar(r.Context(), calendar); err != nil {
+		writeError(w, http.StatusInternalServerError, "calendar_create_failed")
+		return
+	}
+	writeJSON(w, http.StatusCreated, calendar)
+}
+
+func (a *API) GetCalendar(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	id, err := uuid.Parse(strings.TrimSpace(r.PathValue("id")))
+	if err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_id")
+		return
+	}
+	store, ok := a.calendarStore(w)
+	if !ok {
+		return
+	}
+	item, err := store.GetCalendar(r.Context(), principal.TenantID, id)
+	if err != nil {
+		writeError(w, http.StatusNotFound, "calendar_not_found")
+		return
+	}
+	writeJSON(w, http.StatusOK, item)
+}
+
+func (a *API) UpdateCalendar(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	id, err := uuid.Parse(strings.TrimSpace(r.PathValue("id")))
+	if err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_id")
+		return
+	}
+	store, ok := a.calendarStore(w)
+	if !ok {
+		return
+	}
+	calendar, ok := readCalendar(w, r)
+	if !ok {
+		return
+	}
+	calendar.ID = id
+	calendar.TenantID = principal.TenantID
+	if err := store.UpdateCalendar(r.Context(), calendar); err != nil {
+		if errors.Is(err, sql.ErrNoRows) {
+			writeError(w, http.StatusNotFound, "calendar_not_found")
+			return
+		}
+		writeError(w, http.StatusInternalServerError, "calendar_update_failed")
+		return
+	}
+	writeJSON(w, http.StatusOK, calendar)
+}
+
+func (a *API) DeleteCalendar(w http.ResponseWriter, r *http.Request) {
+	principal := middleware.PrincipalFromContext(r.Context())
+	if principal == nil {
+		writeError(w, http.StatusUnauthorized, "unauthenticated")
+		return
+	}
+	id, err := uuid.Parse(strings.TrimSpace(r.PathValue("id")))
+	if err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_id")
+		return
+	}
+	store, ok := a.calendarStore(w)
+	if !ok {
+		return
+	}
+	switch err := store.DeleteCalendar(r.Context(), principal.TenantID, id); {
+	case errors.Is(err, ErrCalendarInUse):
+		writeError(w, http.StatusConflict, "calendar_in_use")
+		return
+	case errors.Is(err, sql.ErrNoRows):
+		writeError(w, http.StatusNotFound, "calendar_not_found")
+		return
+	case err != nil:
+		writeError(w, http.StatusInternalServerError, "calendar_delete_failed")
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{"status": "deleted"})
+}
+
+func (a *API) calendarStore(w http.ResponseWriter) (CalendarStore, bool) {
+	store, ok := a.Store.(CalendarStore)
+	if !ok {
+		writeError(w, http.StatusServiceUnavailable, "calendar_store_unavailable")
+	}
+	return store, ok
+}
+
+// readCalendar decodes and validates a calendar from a JSON body or a raw
+// iCalendar file.
+func readCalendar(w http.ResponseWriter, r *http.Request) (*ScheduleCalendar, bool) {
+	var req calendarRequest
+	mediaType, _, _ := mime.ParseMediaType(r.Header.Get("Content-Type"))
+	if mediaType == "text/calendar" {
+		raw, err := io.ReadAll(io.LimitReader(r.Body, maxICalBytes))
+		if err != nil {
+			writeError(w, http.StatusBadRequest, "invalid_calendar")
+			return nil, false
+		}
+		req.Name, req.ICal = r.URL.Query().Get("name"), string(raw)
+	} else if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_json")
+		return nil, false
+	}
+	calendar := &ScheduleCalendar{Name: strings.TrimSpace(req.Name), Dates: req.Dates, Weekdays: req.Weekdays}
+	if strings.TrimSpace(req.ICal) != "" {
+		name, dates, err := ParseICal(req.ICal)
+		if err != nil {
+			writeError(w, http.StatusBadRequest, "invalid_calendar")
+			return nil, false
+		}
+		if calendar.Name == "" {
+			calendar.Name = name
+		}
+		calendar.Dates = append(calendar.Dates, dates...)
+	}
+	if err := calendar.Validate(); err != nil {
+		writeError(w, http.StatusBadRequest, "invalid_calendar")
+		return nil, false
+	}
+	return calendar, true
+}
@@ -0,0 +1,159 @@
+package channels
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"fmt"
+
+	"github.com/google/uuid"
+)
+
+const calendarColumns = `id, tenant_id, name, dates, weekdays, created_at, updated_at`
+
+func (s *PostgresStore) ListCalendars(ctx context.Context, tenantID uuid.UUID) ([]*ScheduleCalendar, error) {
+	rows, err := s.db.QueryContext(ctx, `
+SELECT `+calendarColumns+`
+FROM schedule_calendars
+WHERE tenant_id = $1
+ORDER BY name
+`, tenantID)
+	if err != nil {
+		return nil, fmt.Errorf("list schedule calendars: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	return scanCalendars(rows)
+}
+
+func (s *PostgresStore) GetCalendar(ctx context.Context, tenantID, calendarID uuid.UUID) (*ScheduleCalendar, error) {
+	rows, err := s.db.QueryContext(ctx, `
+SELECT `+calendarColumns+`
+FROM schedule_calendars
+WHERE tenant_id = $1 AND id = $2
+`, tenantID, calendarID)
+	if err != nil {
+		return nil, fmt.Errorf("load schedule calendar: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	items, err := scanCalendars(rows)
+	if err != nil {
+		return nil, err
+	}
+	if len(items) == 0 {
+		return nil, sql.ErrNoRows
+	}
+	return items[0], nil
+}
+
+func (s *PostgresStore) ScheduleCalendars(ctx context.Context, tenantID uuid.UUID, ids []uuid.UUID) ([]*ScheduleCalendar, error) {
+	if len(ids) == 0 {
+		return nil, nil
+	}
+	raw, _ := json.Marshal(ids)
+	rows, err := s.db.QueryContext(ctx, `
+SELECT `+calendarColumns+`
+FROM schedule_calendars
+WHERE tenant_id = $1 AND id IN (SELECT jsonb_array_elements_text($2::jsonb)::uuid)
+`, tenantID, string(raw))
+	if err != nil {
+		return nil, fmt.Errorf("load schedule calendars: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	return scanCalendars(rows)
+}
+
+func (s *PostgresStore) CreateCalendar(ctx context.Context, calendar *ScheduleCalendar) error {
+	if calendar == nil {
+		return fmt.Errorf("calendar is nil")
+	}
+	datesRaw, weekdaysRaw := calendarJSON(calendar)
+	err := s.db.QueryRowContext(ctx, `
+INSERT INTO schedule_calendars (id, tenant_id, name, dates, weekdays)
+VALUES ($1, $2, $3, $4::jsonb, $5::jsonb)
+RETURNING created_at, updated_at
+`, calendar.ID, calendar.TenantID, calendar.Name, datesRaw, weekdaysRaw).Scan(&calendar.CreatedAt, &calendar.UpdatedAt)
+	if err != nil {
+		return fmt.Errorf("create schedule calendar: %w", err)
+	}
+	return nil
+}
+
+func (s *PostgresStore) UpdateCalendar(ctx context.Context, calendar *ScheduleCalendar) error {
+	if calendar == nil {
+		return fmt.Errorf("calendar is nil")
+	}
+	datesRaw, weekdaysRaw := calendarJSON(calendar)
+	err := s.db.QueryRowContext(ctx, `
+UPDATE schedule_calendars
+SET name = $3, dates = $4::jsonb, weekdays = $5::jsonb, updated_at = now()
+WHERE tenant_id = $1 AND id = $2
+RETURNING created_at, updated_at
+`, calendar.TenantID, calendar.ID, calendar.Name, datesRaw, weekdaysRaw).Scan(&calendar.CreatedAt, &calendar.UpdatedAt)
+	if err != nil {
+		return fmt.Errorf("update schedule calendar: %w", err)
+	}
+	return nil
+}
+
+func (s *PostgresStore) DeleteCalendar(ctx context.Context, tenantID, calendarID uuid.UUID) error {
+	var inUse bool
+	err := s.db.QueryRowContext(ctx, `
+SELECT EXISTS (
+    SELECT 1 FROM channels
+    WHERE tenant_id = $1 AND type = 'schedule' AND deleted_at IS NULL
+      AND config->'calendars' ? $2
+)
+`, tenantID, calendarID.String()).Scan(&inUse)
+	if err != nil {
+		return fmt.Errorf("check schedule calendar use: %w", err)
+	}
+	if inUse {
+		return ErrCalendarInUse
+	}
+	res, err := s.db.ExecContext(ctx, `
+DELETE FROM schedule_calendars
+WHERE tenant_id = $1 AND id = $2
+`, tenantID, calendarID)
+	if err != nil {
+		return fmt.Errorf("delete schedule calendar: %w", err)
+	}
+	if n, _ := res.RowsAffected(); n == 0 {
+		return sql.ErrNoRows
+	}
+	return nil
+}
+
+func calendarJSON(calendar *ScheduleCalendar) (string, string) {
+	dates := calendar.Dates
+	if dates == nil {
+		dates = []CalendarDate{}
+	}
+	weekdays := calendar.Weekdays
+	if weekdays == nil {
+		weekdays = []string{}
+	}
+	datesRaw, _ := json.Marshal(dates)
+	weekdaysRaw, _ := json.Marshal(weekdays)
+	return string(datesRaw), string(weekdaysRaw)
+}
+
+func scanCalendars(rows *sql.Rows) ([]*ScheduleCalendar, error) {
+	out := []*ScheduleCalendar{}
+	for rows.Next() {
+		var (
+			item        ScheduleCalendar
+			datesRaw    []byte
+			weekdaysRaw []byte
+		)
+		if err := rows.Scan(&item.ID, &item.TenantID, &item.Name, &datesRaw, &weekdaysRaw, &item.CreatedAt, &item.UpdatedAt); err != nil {
+			return nil, fmt.Errorf("scan schedule calendar: %w", err)
+		}
+		_ = json.Unmarshal(datesRaw, &item.Dates)
+		_ = json.Unmarshal(weekdaysRaw, &item.Weekdays)
+		out = append(out, &item)
+	}
+	if err := rows.Err(); err != nil {
+		return nil, fmt.Errorf("iterate schedule calendars: %w", err)
+	}
+	return out, nil
+}
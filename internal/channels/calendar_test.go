@@ -0,0 +1,110 @@
+package channels
+
+import (
+	"errors"
+	"testing"
+	"time"
+
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+const holidayICal = "BEGIN:VCALENDAR\r\n" +
+	"VERSION:2.0\r\n" +
+	"X-WR-CALNAME:UK Bank Holidays\r\n" +
+	"BEGIN:VEVENT\r\n" +
+	"DTSTART;VALUE=DATE:20261225\r\n" +
+	"DTEND;VALUE=DATE:20261229\r\n" +
+	"SUMMARY:Christmas\\, Boxing Day and\r\n" +
+	"  substitutes\r\n" +
+	"END:VEVENT\r\n" +
+	"BEGIN:VEVENT\r\n" +
+	"DTSTART;VALUE=DATE:20270101\r\n" +
+	"RRULE:FREQ=YEARLY\r\n" +
+	"SUMMARY:New Year's Day\r\n" +
+	"END:VEVENT\r\n" +
+	"END:VCALENDAR\r\n"
+
+func TestParseICal(t *testing.T) {
+	t.Parallel()
+
+	name, dates, err := ParseICal(holidayICal)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if name != "UK Bank Holidays" {
+		t.Fatalf("expected calendar name, got %q", name)
+	}
+	if len(dates) != 5 {
+		t.Fatalf("expected four Christmas days and New Year, got %+v", dates)
+	}
+	if dates[0].Date != "2026-12-25" || dates[3].Date != "2026-12-28" || dates[0].Name != "Christmas, Boxing Day and substitutes" {
+		t.Fatalf("unexpected multi-day event dates %+v", dates[:4])
+	}
+	if last := dates[4]; last.Date != "2027-01-01" || !last.Yearly {
+		t.Fatalf("expected yearly New Year's Day, got %+v", last)
+	}
+
+	_, _, err = ParseICal("BEGIN:VEVENT\nDTSTART:20261102\nRRULE:FREQ=MONTHLY;BYDAY=1MO\nEND:VEVENT\n")
+	if !errors.Is(err, ErrInvalidCalendar) {
+		t.Fatalf("expected unsupported recurrence to be rejected, got %v", err)
+	}
+	if _, _, err := ParseICal("BEGIN:VCALENDAR\nEND:VCALENDAR\n"); !errors.Is(err, ErrInvalidCalendar) {
+		t.Fatalf("expected empty calendar to be rejected, got %v", err)
+	}
+}
+
+func TestNextAllowedSkipsExcludedDays(t *testing.T) {
+	t.Parallel()
+
+	cron, err := engine.ParseCron("0 9 * * *")
+	if err != nil {
+		t.Fatal(err)
+	}
+	holidays := &ScheduleCalendar{Name: "holidays", Dates: []CalendarDate{
+		{Date: "2026-12-25"},
+		{Date: "2020-01-01", Yearly: true},
+	}}
+	x := newScheduleExclusions(ScheduleConfig{ExcludeWeekdays: []string{"saturday", "Sun"}}, []*ScheduleCalendar{holidays})
+
+	cases := []struct {
+		after time.Time
+		want  time.Time
+	}{
+		// Christmas Day is a Friday, followed by a weekend.
+		{after: time.Date(2026, 12, 24, 10, 0, 0, 0, time.UTC), want: time.Date(2026, 12, 28, 9, 0, 0, 0, time.UTC)},
+		// New Year's Day repeats every year.
+		{after: time.Date(2026, 12, 31, 10, 0, 0, 0, time.UTC), want: time.Date(2027, 1, 4, 9, 0, 0, 0, time.UTC)},
+	}
+	for _, tc := range cases {
+		got, ok := nextAllowed(cron, tc.after, x)
+		if !ok || !got.Equal(tc.want) {
+			t.Fatalf("after %s: expected %s, got %s (%v)", tc.after, tc.want, got, ok)
+		}
+	}
+
+	everyDay := newScheduleExclusions(ScheduleConfig{ExcludeWeekdays: []string{"mon", "tue", "wed", "thu", "fri", "sat", "sun"}}, nil)
+	if _, ok := nextAllowed(cron, time.Now(), everyDay); ok {
+		t.Fatal("expected a schedule excluded on every day to never fire")
+	}
+}
+
+func TestPlanScheduleRunsLeavesOutExcludedDays(t *testing.T) {
+	t.Parallel()
+
+	cron, err := engine.ParseCron("0 9 * * *")
+	if err != nil {
+		t.Fatal(err)
+	}
+	x := newScheduleExclusions(ScheduleConfig{ExcludeDates: []string{"2026-10-19"}}, nil)
+	// Down from Sunday until Tuesday 09:00:30: Sunday was missed, Monday is
+	// excluded and Tuesday is on time.
+	due := time.Date(2026, 10, 18, 9, 0, 0, 0, time.UTC)
+	now := time.Date(2026, 10, 20, 9, 0, 30, 0, time.UTC)
+	plan, ok := planScheduleRuns(cron, x, due, now, MissedRunCatchUpAll)
+	if !ok || len(plan.runs) != 2 || plan.skipped != 0 {
+		t.Fatalf("expected Sunday and Tuesday runs only, got %+v", plan)
+	}
+	if plan.runs[1].Day() != 20 {
+		t.Fatalf("expected the excluded Monday to be left out, got %v", plan.runs)
+	}
+}
@@ -151,6 +151,11 @@ func (cm *ChannelManager) buildRunner(ch *Channel) (ChannelRunner, error) {
 		cfg := FileDropConfig{}
 		_ = json.Unmarshal(ch.Config, &cfg)
 		return &FileDropChannelRunner{ChannelID: ch.ID, TenantID: ch.TenantID, Config: cfg, Pipeline: cm.pipeline}, nil
+	case ChannelSchedule:
+		cfg := ScheduleConfig{}
+		_ = json.Unmarshal(ch.Config, &cfg)
+		store, _ := cm.store.(ScheduleStore)
+		return &ScheduleChannelRunner{ChannelID: ch.ID, TenantID: ch.TenantID, Config: cfg, Pipeline: cm.pipeline, Store: store}, nil
 	default:
 		return nil, nil
 	}
@@ -8,6 +8,7 @@ import (
 	"time"
 
 	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
 )
 
 var (
@@ -110,6 +111,7 @@ func (p *Pipeline) Process(ctx context.Context, req PipelineRequest) (PipelineRe
 				WorkflowID: ch.WorkflowID,
 				Data:       adapted,
 				ActorID:    req.ActorID,
+				Lane:       channelLane(ch.Type),
 			})
 			if err != nil {
 				return err
@@ -195,3 +197,12 @@ func truncateRawPayload(raw []byte) []byte {
 	}
 	return out
 }
+
+// channelLane runs cases from file drops and schedules, which arrive in bulk,
+// in the batch lane so they do not hold up interactive runs.
+func channelLane(t ChannelType) engine.Lane {
+	if t == ChannelFileDrop || t == ChannelSchedule {
+		return engine.LaneBatch
+	}
+	return engine.LaneStandard
+}
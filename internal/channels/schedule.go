@@ -0,0 +1,252 @@
+package channels
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"log/slog"
+	"sync"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+const (
+	scheduleCheckInterval = 15 * time.Second
+	// scheduleGrace is how late a fire time may be picked up and still count
+	// as on time rather than missed.
+	scheduleGrace = 2 * time.Minute
+	// maxCatchUpRuns bounds catch_up_all after a long outage.
+	maxCatchUpRuns = 100
+)
+
+// ScheduleState is a scheduled channel's persisted progress. NextRunAt is
+// the next fire time not yet claimed; it is advanced before runs start, so a
+// restart or a second replica never fires the same time twice.
+type ScheduleState struct {
+	ChannelID  uuid.UUID  `json:"channel_id"`
+	Cron       string     `json:"cron"`
+	Timezone   string     `json:"timezone,omitempty"`
+	NextRunAt  *time.Time `json:"next_run_at,omitempty"`
+	LastRunAt  *time.Time `json:"last_run_at,omitempty"`
+	LastStatus string     `json:"last_status,omitempty"`
+	LastError  string     `json:"last_error,omitempty"`
+	LastCaseID *uuid.UUID `json:"last_case_id,omitempty"`
+	MissedRuns int        `json:"missed_runs"`
+	UpdatedAt  time.Time  `json:"updated_at"`
+}
+
+// ScheduleRun is the outcome of one scheduled fire time. Skipped counts
+// missed fire times dropped by the channel's policy.
+type ScheduleRun struct {
+	ScheduledAt time.Time
+	Status      string
+	Error       string
+	CaseID      *uuid.UUID
+	Skipped     int
+}
+
+type ScheduleStore interface {
+	GetScheduleState(ctx context.Context, channelID uuid.UUID) (*ScheduleState, error)
+	// ClaimScheduleRun moves next_run_at from prev to next for cron in
+	// timezone, and reports false if another process moved it first.
+	ClaimScheduleRun(ctx context.Context, tenantID, channelID uuid.UUID, cron, timezone string, prev *time.Time, next time.Time) (bool, error)
+	RecordScheduleRun(ctx context.Context, channelID uuid.UUID, run ScheduleRun) error
+	// ScheduleCalendars loads the tenant's calendars among ids, skipping
+	// any that no longer exist.
+	ScheduleCalendars(ctx context.Context, tenantID uuid.UUID, ids []uuid.UUID) ([]*ScheduleCalendar, error)
+}
+
+// schedulePlan is what one check of a schedule does: the fire times to run,
+// how many missed ones the policy drops, and the next fire time to wait for.
+type schedulePlan struct {
+	runs    []time.Time
+	skipped int
+	next    time.Time
+}
+
+// planScheduleRuns collects the fire times from due up to now, both in the
+// schedule's timezone, leaving out excluded days. Those older than
+// scheduleGrace were missed, typically during downtime: skip drops them,
+// run_once runs the latest of them unless an on-time run follows, and
+// catch_up_all runs each, up to maxCatchUpRuns.
+func planScheduleRuns(cron engine.Cron, x scheduleExclusions, due, now time.Time, policy MissedRunPolicy) (schedulePlan, bool) {
+	var missed, onTime []time.Time
+	for t := due; !t.After(now) && len(missed)+len(onTime) < maxCatchUpRuns; {
+		switch {
+		case x.excludes(t):
+		case now.Sub(t) > scheduleGrace:
+			missed = append(missed, t)
+		default:
+			onTime = append(onTime, t)
+		}
+		nextT, ok := nextAllowed(cron, t, x)
+		if !ok {
+			break
+		}
+		t = nextT
+	}
+	next, ok := nextAllowed(cron, now, x)
+	if !ok {
+		return schedulePlan{}, false
+	}
+	plan := schedulePlan{next: next}
+	switch policy {
+	case MissedRunCatchUpAll:
+		plan.runs = append(missed, onTime...)
+	case MissedRunOnce:
+		plan.runs = onTime
+		if len(onTime) == 0 && len(missed) > 0 {
+			plan.runs = missed[len(missed)-1:]
+		}
+	default:
+		plan.runs = onTime
+	}
+	plan.skipped = len(missed) + len(onTime) - len(plan.runs)
+	return plan, true
+}
+
+// ScheduleChannelRunner starts a case at each fire time of a cron schedule.
+// Its progress lives in the ScheduleStore, so missed runs are found and
+// handled by MissedRuns after a restart.
+type ScheduleChannelRunner struct {
+	ChannelID uuid.UUID
+	TenantID  uuid.UUID
+	Config    ScheduleConfig
+	Pipeline  *Pipeline
+	Store     ScheduleStore
+
+	now    func() time.Time
+	mu     sync.Mutex
+	cancel context.CancelFunc
+}
+
+func (sr *ScheduleChannelRunner) Start(ctx context.Context) error {
+	sr.mu.Lock()
+	defer sr.mu.Unlock()
+	if sr.cancel != nil {
+		return nil
+	}
+	cfg := sr.Config.WithDefaults()
+	if err := cfg.Validate(); err != nil {
+		return fmt.Errorf("invalid schedule: %w", err)
+	}
+	if sr.Store == nil {
+		return fmt.Errorf("schedule store unavailable")
+	}
+	cron, _ := engine.ParseCron(cfg.Cron)
+	loc, _ := cfg.Location()
+	runCtx, cancel := context.WithCancel(ctx)
+	sr.cancel = cancel
+	go sr.loop(runCtx, cfg, cron, loc)
+	return nil
+}
+
+func (sr *ScheduleChannelRunner) Stop() error {
+	sr.mu.Lock()
+	defer sr.mu.Unlock()
+	if sr.cancel != nil {
+		sr.cancel()
+		sr.cancel = nil
+	}
+	return nil
+}
+
+func (sr *ScheduleChannelRunner) loop(ctx context.Context, cfg ScheduleConfig, cron engine.Cron, loc *time.Location) {
+	ticker := time.NewTicker(scheduleCheckInterval)
+	defer ticker.Stop()
+	for {
+		if err := sr.checkOnce(ctx, cfg, cron, loc); err != nil {
+			slog.Error("schedule channel check failed", "channel_id", sr.ChannelID, "error", err)
+		}
+		select {
+		case <-ctx.Done():
+			return
+		case <-ticker.C:
+		}
+	}
+}
+
+func (sr *ScheduleChannelRunner) checkOnce(ctx context.Context, cfg ScheduleConfig, cron engine.Cron, loc *time.Location) error {
+	now := time.Now().In(loc)
+	if sr.now != nil {
+		now = sr.now().In(loc)
+	}
+	state, err := sr.Store.GetScheduleState(ctx, sr.ChannelID)
+	if err != nil {
+		return err
+	}
+	var prev *time.Time
+	if state != nil {
+		prev = state.NextRunAt
+	}
+	calendars, err := sr.Store.ScheduleCalendars(ctx, sr.TenantID, cfg.Calendars)
+	if err != nil {
+		return err
+	}
+	exclusions := newScheduleExclusions(cfg, calendars)
+	// A new schedule, or one whose expression or timezone changed, starts
+	// from now.
+	if state == nil || state.NextRunAt == nil || state.Cron != cfg.Cron || state.Timezone != cfg.Timezone {
+		next, ok := nextAllowed(cron, now, exclusions)
+		if !ok {
+			return fmt.Errorf("schedule %q never fires", cfg.Cron)
+		}
+		_, err := sr.Store.ClaimScheduleRun(ctx, sr.TenantID, sr.ChannelID, cfg.Cron, cfg.Timezone, prev, next)
+		return err
+	}
+	if now.Before(*state.NextRunAt) {
+		return nil
+	}
+	plan, ok := planScheduleRuns(cron, exclusions, state.NextRunAt.In(loc), now, cfg.MissedRuns)
+	if !ok {
+		return fmt.Errorf("schedule %q never fires", cfg.Cron)
+	}
+	claimed, err := sr.Store.ClaimScheduleRun(ctx, sr.TenantID, sr.ChannelID, cfg.Cron, cfg.Timezone, prev, plan.next)
+	if err != nil || !claimed {
+		return err
+	}
+	if len(plan.runs) == 0 {
+		return sr.Store.RecordScheduleRun(ctx, sr.ChannelID, ScheduleRun{ScheduledAt: now, Status: "skipped", Skipped: plan.skipped})
+	}
+	for i, at := range plan.runs {
+		run := sr.fire(ctx, cfg, at, now)
+		if i == 0 {
+			run.Skipped = plan.skipped
+		}
+		if err := sr.Store.RecordScheduleRun(ctx, sr.ChannelID, run); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+func (sr *ScheduleChannelRunner) fire(ctx context.Context, cfg ScheduleConfig, at, now time.Time) ScheduleRun {
+	payload := map[string]any{}
+	for k, v := range cfg.Data {
+		payload[k] = v
+	}
+	payload["scheduled_at"] = at.Format(time.RFC3339)
+	payload["late"] = now.Sub(at) > scheduleGrace
+	raw, _ := json.Marshal(payload)
+	run := ScheduleRun{ScheduledAt: at, Status: "succeeded"}
+	if sr.Pipeline == nil {
+		run.Status, run.Error = "failed", "channel pipeline unavailable"
+		return run
+	}
+	result, err := sr.Pipeline.Process(ctx, PipelineRequest{
+		TenantID:   sr.TenantID,
+		ChannelID:  sr.ChannelID,
+		Data:       raw,
+		Source:     "schedule",
+		ReceivedAt: now,
+	})
+	if err != nil {
+		slog.Error("schedule channel pipeline failure", "channel_id", sr.ChannelID, "scheduled_at", at, "error", err)
+		run.Status, run.Error = "failed", err.Error()
+		return run
+	}
+	run.CaseID = &result.CaseID
+	return run
+}
@@ -0,0 +1,76 @@
+package channels
+
+import (
+	"context"
+	"database/sql"
+	"errors"
+	"fmt"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+func (s *PostgresStore) GetScheduleState(ctx context.Context, channelID uuid.UUID) (*ScheduleState, error) {
+	var (
+		state      ScheduleState
+		nextRunAt  sql.NullTime
+		lastRunAt  sql.NullTime
+		lastCaseID uuid.NullUUID
+	)
+	err := s.db.QueryRowContext(ctx, `
+SELECT channel_id, cron, timezone, next_run_at, last_run_at, COALESCE(last_status, ''), COALESCE(last_error, ''), last_case_id, missed_runs, updated_at
+FROM channel_schedules
+WHERE channel_id = $1
+`, channelID).Scan(&state.ChannelID, &state.Cron, &state.Timezone, &nextRunAt, &lastRunAt, &state.LastStatus, &state.LastError, &lastCaseID, &state.MissedRuns, &state.UpdatedAt)
+	if errors.Is(err, sql.ErrNoRows) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, fmt.Errorf("load channel schedule: %w", err)
+	}
+	if nextRunAt.Valid {
+		state.NextRunAt = &nextRunAt.Time
+	}
+	if lastRunAt.Valid {
+		state.LastRunAt = &lastRunAt.Time
+	}
+	if lastCaseID.Valid {
+		state.LastCaseID = &lastCaseID.UUID
+	}
+	return &state, nil
+}
+
+func (s *PostgresStore) ClaimScheduleRun(ctx context.Context, tenantID, channelID uuid.UUID, cron, timezone string, prev *time.Time, next time.Time) (bool, error) {
+	res, err := s.db.ExecContext(ctx, `
+INSERT INTO channel_schedules (channel_id, tenant_id, cron, timezone, next_run_at)
+VALUES ($1, $2, $3, $4, $6)
+ON CONFLICT (channel_id) DO UPDATE
+SET cron = EXCLUDED.cron, timezone = EXCLUDED.timezone, next_run_at = EXCLUDED.next_run_at, updated_at = now()
+WHERE channel_schedules.next_run_at IS NOT DISTINCT FROM $5
+`, channelID, tenantID, cron, timezone, prev, next)
+	if err != nil {
+		return false, fmt.Errorf("claim channel schedule run: %w", err)
+	}
+	n, err := res.RowsAffected()
+	if err != nil {
+		return false, fmt.Errorf("claim channel schedule run: %w", err)
+	}
+	return n == 1, nil
+}
+
+func (s *PostgresStore) RecordScheduleRun(ctx context.Context, channelID uuid.UUID, run ScheduleRun) error {
+	_, err := s.db.ExecContext(ctx, `
+UPDATE channel_schedules
+SET last_run_at = $2,
+    last_status = $3,
+    last_error = NULLIF($4, ''),
+    last_case_id = $5,
+    missed_runs = missed_runs + $6,
+    updated_at = now()
+WHERE channel_id = $1
+`, channelID, run.ScheduledAt, run.Status, run.Error, run.CaseID, run.Skipped)
+	if err != nil {
+		return fmt.Errorf("record channel schedule run: %w", err)
+	}
+	return nil
+}
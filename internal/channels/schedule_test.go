@@ -0,0 +1,180 @@
+package channels
+
+import (
+	"context"
+	"testing"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+type fakeScheduleStore struct {
+	state     *ScheduleState
+	runs      []ScheduleRun
+	calendars []*ScheduleCalendar
+}
+
+func (s *fakeScheduleStore) GetScheduleState(context.Context, uuid.UUID) (*ScheduleState, error) {
+	if s.state == nil {
+		return nil, nil
+	}
+	copied := *s.state
+	return &copied, nil
+}
+
+func (s *fakeScheduleStore) ClaimScheduleRun(_ context.Context, _, channelID uuid.UUID, cron, timezone string, prev *time.Time, next time.Time) (bool, error) {
+	if s.state == nil {
+		s.state = &ScheduleState{ChannelID: channelID}
+	} else if (prev == nil) != (s.state.NextRunAt == nil) || (prev != nil && !prev.Equal(*s.state.NextRunAt)) {
+		return false, nil
+	}
+	s.state.Cron = cron
+	s.state.Timezone = timezone
+	s.state.NextRunAt = &next
+	return true, nil
+}
+
+func (s *fakeScheduleStore) RecordScheduleRun(_ context.Context, _ uuid.UUID, run ScheduleRun) error {
+	s.runs = append(s.runs, run)
+	s.state.MissedRuns += run.Skipped
+	s.state.LastStatus = run.Status
+	return nil
+}
+
+func (s *fakeScheduleStore) ScheduleCalendars(context.Context, uuid.UUID, []uuid.UUID) ([]*ScheduleCalendar, error) {
+	return s.calendars, nil
+}
+
+func TestPlanScheduleRunsMissedPolicies(t *testing.T) {
+	t.Parallel()
+
+	cron, err := engine.ParseCron("0 * * * *")
+	if err != nil {
+		t.Fatal(err)
+	}
+	due := time.Date(2026, 10, 18, 1, 0, 0, 0, time.UTC)
+	// Down from 01:00 until 04:00:30: 01:00-03:00 were missed, 04:00 is on time.
+	now := time.Date(2026, 10, 18, 4, 0, 30, 0, time.UTC)
+
+	cases := []struct {
+		policy  MissedRunPolicy
+		runs    int
+		skipped int
+	}{
+		{policy: MissedRunSkip, runs: 1, skipped: 3},
+		{policy: MissedRunOnce, runs: 1, skipped: 3},
+		{policy: MissedRunCatchUpAll, runs: 4, skipped: 0},
+	}
+	for _, tc := range cases {
+		plan, ok := planScheduleRuns(cron, scheduleExclusions{}, due, now, tc.policy)
+		if !ok || len(plan.runs) != tc.runs || plan.skipped != tc.skipped {
+			t.Fatalf("%s: expected %d runs and %d skipped, got %+v", tc.policy, tc.runs, tc.skipped, plan)
+		}
+		if want := time.Date(2026, 10, 18, 5, 0, 0, 0, time.UTC); !plan.next.Equal(want) {
+			t.Fatalf("%s: expected next run %s, got %s", tc.policy, want, plan.next)
+		}
+	}
+
+	// Without an on-time run, run_once runs the latest missed time.
+	plan, _ := planScheduleRuns(cron, scheduleExclusions{}, due, time.Date(2026, 10, 18, 3, 30, 0, 0, time.UTC), MissedRunOnce)
+	if len(plan.runs) != 1 || plan.runs[0].Hour() != 3 || plan.skipped != 2 {
+		t.Fatalf("expected the 03:00 run only, got %+v", plan)
+	}
+	plan, _ = planScheduleRuns(cron, scheduleExclusions{}, due, time.Date(2026, 10, 18, 3, 30, 0, 0, time.UTC), MissedRunSkip)
+	if len(plan.runs) != 0 || plan.skipped != 3 {
+		t.Fatalf("expected every missed run skipped, got %+v", plan)
+	}
+}
+
+func TestScheduleRunnerPersistsAndCatchesUp(t *testing.T) {
+	t.Parallel()
+
+	channel := &Channel{ID: uuid.New(), TenantID: uuid.New(), Type: ChannelSchedule, Enabled: true, CaseTypeID: uuid.New()}
+	store := &fakeChannelStore{channel: channel, cases: map[uuid.UUID]fakeCaseRecord{}}
+	schedules := &fakeScheduleStore{}
+	now := time.Date(2026, 10, 18, 8, 59, 0, 0, time.UTC)
+	runner := &ScheduleChannelRunner{
+		ChannelID: channel.ID,
+		TenantID:  channel.TenantID,
+		Config:    ScheduleConfig{Cron: "*/30 * * * *", MissedRuns: MissedRunCatchUpAll, Data: map[string]any{"report": "daily"}},
+		Pipeline:  NewPipeline(noopWorkflowRunner{}, store, nil),
+		Store:     schedules,
+		now:       func() time.Time { return now },
+	}
+	cron, _ := engine.ParseCron(runner.Config.Cron)
+	ctx := context.Background()
+
+	if err := runner.checkOnce(ctx, runner.Config, cron, time.UTC); err != nil {
+		t.Fatal(err)
+	}
+	if schedules.state == nil || !schedules.state.NextRunAt.Equal(time.Date(2026, 10, 18, 9, 0, 0, 0, time.UTC)) {
+		t.Fatalf("expected first run persisted for 09:00, got %+v", schedules.state)
+	}
+	if len(store.cases) != 0 {
+		t.Fatal("expected no case before the first fire time")
+	}
+
+	// The process was down until 10:10, so 09:00, 09:30 and 10:00 are missed.
+	now = time.Date(2026, 10, 18, 10, 10, 0, 0, time.UTC)
+	if err := runner.checkOnce(ctx, runner.Config, cron, time.UTC); err != nil {
+		t.Fatal(err)
+	}
+	if len(store.cases) != 3 || len(schedules.runs) != 3 {
+		t.Fatalf("expected three catch-up cases, got %d cases and %d runs", len(store.cases), len(schedules.runs))
+	}
+	if !schedules.state.NextRunAt.Equal(time.Date(2026, 10, 18, 10, 30, 0, 0, time.UTC)) {
+		t.Fatalf("expected next run at 10:30, got %s", schedules.state.NextRunAt)
+	}
+
+	// A second check before the next fire time does nothing.
+	if err := runner.checkOnce(ctx, runner.Config, cron, time.UTC); err != nil {
+		t.Fatal(err)
+	}
+	if len(store.cases) != 3 {
+		t.Fatalf("expected no further cases, got %d", len(store.cases))
+	}
+}
+
+func TestScheduleConfigValidate(t *testing.T) {
+	t.Parallel()
+
+	if err := (ScheduleConfig{Cron: "0 9 * * mon-fri"}).Validate(); err != nil {
+		t.Fatalf("expected valid schedule, got %v", err)
+	}
+	if err := (ScheduleConfig{Cron: "0 9 * *"}).Validate(); err == nil {
+		t.Fatal("expected invalid cron to be rejected")
+	}
+	if err := (ScheduleConfig{Cron: "@daily", MissedRuns: "retry"}).Validate(); err == nil {
+		t.Fatal("expected unknown policy to be rejected")
+	}
+	if err := (ScheduleConfig{Cron: "@daily", Timezone: "Mars/Olympus"}).Validate(); err == nil {
+		t.Fatal("expected unknown timezone to be rejected")
+	}
+	if err := (ScheduleConfig{Cron: "@daily", ExcludeDates: []string{"25/12/2026"}}).Validate(); err == nil {
+		t.Fatal("expected malformed exclusion date to be rejected")
+	}
+	if err := (ScheduleConfig{Cron: "@daily", ExcludeWeekdays: []string{"funday"}}).Validate(); err == nil {
+		t.Fatal("expected unknown weekday to be rejected")
+	}
+}
+
+func TestScheduleConfigNextRunsAcrossDST(t *testing.T) {
+	t.Parallel()
+
+	cfg := ScheduleConfig{Cron: "0 9 * * MON", Timezone: "Europe/Berlin"}
+	loc, err := cfg.Location()
+	if err != nil {
+		t.Skipf("timezone data unavailable: %v", err)
+	}
+	// Berlin leaves summer time on 25 October 2026.
+	runs, err := cfg.NextRuns(time.Date(2026, 10, 18, 12, 0, 0, 0, time.UTC), 2, nil)
+	if err != nil || len(runs) != 2 {
+		t.Fatalf("expected two runs, got %v (%v)", runs, err)
+	}
+	for i, want := range []time.Time{time.Date(2026, 10, 19, 7, 0, 0, 0, time.UTC), time.Date(2026, 10, 26, 8, 0, 0, 0, time.UTC)} {
+		if !runs[i].Equal(want) || runs[i].In(loc).Hour() != 9 {
+			t.Fatalf("run %d: expected %s (09:00 local), got %s", i, want, runs[i])
+		}
+	}
+}
@@ -6,6 +6,7 @@ import (
 	"time"
 
 	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
 )
 
 type ChannelStore interface {
@@ -38,4 +39,5 @@ type CreateOrUpdateCaseInput struct {
 	WorkflowID *uuid.UUID
 	Data       map[string]any
 	ActorID    uuid.UUID
+	Lane       engine.Lane
 }
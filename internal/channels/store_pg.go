@@ -12,6 +12,7 @@ import (
 	"github.com/google/uuid"
 	"github.com/neural-chilli/aceryx/internal/audit"
 	"github.com/neural-chilli/aceryx/internal/cases"
+	"github.com/neural-chilli/aceryx/internal/observability"
 )
 
 type PostgresStore struct {
@@ -284,12 +285,15 @@ LIMIT 1
 	workflowID := in.WorkflowID
 	workflowVersion := 1
 	rawAST := []byte(`{"nodes":[]}`)
+	// A running canary version takes its percentage of new cases; otherwise
+	// the canary sorts last and the published version is used.
 	if workflowID != nil {
 		if err := s.tx.QueryRowContext(ctx, `
-SELECT version, ast
-FROM workflow_versions
-WHERE workflow_id = $1 AND status = 'published'
-ORDER BY version DESC
+SELECT wv.version, wv.ast
+FROM workflow_versions wv
+LEFT JOIN workflow_canaries wc ON wc.workflow_id = wv.workflow_id AND wc.version = wv.version AND wc.status = 'active'
+WHERE wv.workflow_id = $1 AND wv.status IN ('published', 'canary')
+ORDER BY wv.status = 'canary' AND random() * 100 >= COALESCE(wc.percent, 0), wv.version DESC
 LIMIT 1
 `, *workflowID).Scan(&workflowVersion, &rawAST); err != nil {
 			return uuid.Nil, fmt.Errorf("resolve workflow version: %w", err)
@@ -299,8 +303,9 @@ LIMIT 1
 SELECT w.id, wv.version, wv.ast
 FROM workflows w
 JOIN workflow_versions wv ON wv.workflow_id = w.id
-WHERE w.tenant_id = $1 AND w.case_type = $2 AND wv.status = 'published'
-ORDER BY wv.version DESC
+LEFT JOIN workflow_canaries wc ON wc.workflow_id = w.id AND wc.version = wv.version AND wc.status = 'active'
+WHERE w.tenant_id = $1 AND w.case_type = $2 AND wv.status IN ('published', 'canary')
+ORDER BY wv.status = 'canary' AND random() * 100 >= COALESCE(wc.percent, 0), wv.version DESC
 LIMIT 1
 `, in.TenantID, caseTypeName).Scan(&workflowID, &workflowVersion, &rawAST); err != nil {
 			return uuid.Nil, fmt.Errorf("resolve default workflow for channel case type: %w", err)
@@ -323,12 +328,12 @@ RETURNING last_number
 	var caseID uuid.UUID
 	if err := s.tx.QueryRowContext(ctx, `
 INSERT INTO cases (
-    tenant_id, case_type_id, case_number, status, data, created_by, priority, workflow_id, workflow_version
+    tenant_id, case_type_id, case_number, status, data, created_by, priority, workflow_id, workflow_version, correlation_id, execution_lane
 ) VALUES (
-    $1, $2, $3, 'open', $4::jsonb, $5, 0, $6, $7
+    $1, $2, $3, 'open', $4::jsonb, $5, 0, $6, $7, NULLIF($8, ''), COALESCE(NULLIF($9, ''), 'standard')
 )
 RETURNING id
-`, in.TenantID, in.CaseTypeID, caseNumber, string(rawData), in.ActorID, workflowID, workflowVersion).Scan(&caseID); err != nil {
+`, in.TenantID, in.CaseTypeID, caseNumber, string(rawData), in.ActorID, workflowID, workflowVersion, observability.CorrelationIDFromContext(ctx), string(in.Lane)).Scan(&caseID); err != nil {
 		return uuid.Nil, fmt.Errorf("insert channel-created case: %w", err)
 	}
 
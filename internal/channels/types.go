@@ -2,9 +2,11 @@ package channels
 
 import (
 	"encoding/json"
+	"fmt"
 	"time"
 
 	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
 )
 
 type ChannelType string
@@ -15,6 +17,7 @@ const (
 	ChannelForm     ChannelType = "form"
 	ChannelFileDrop ChannelType = "file_drop"
 	ChannelPlugin   ChannelType = "plugin"
+	ChannelSchedule ChannelType = "schedule"
 )
 
 type EventStatus string
@@ -116,10 +119,22 @@ type EmailConfig struct {
 }
 
 type WebhookConfig struct {
-	AuthType      string `json:"auth_type"`
-	AuthSecret    string `json:"auth_secret"`
-	AuthHeader    string `json:"auth_header"`
-	HMACAlgorithm string `json:"hmac_algorithm"`
+	AuthType      string                 `json:"auth_type"`
+	AuthSecret    string                 `json:"auth_secret"`
+	AuthHeader    string                 `json:"auth_header"`
+	HMACAlgorithm string                 `json:"hmac_algorithm"`
+	Response      *WebhookResponseConfig `json:"response,omitempty"`
+}
+
+// WebhookResponseConfig turns a webhook into a synchronous call: the reply is
+// rendered from the workflow output (final case data, or SourceStep's result)
+// once available, falling back to 202 Accepted after TimeoutSeconds.
+type WebhookResponseConfig struct {
+	StatusCode     int               `json:"status_code"`
+	Headers        map[string]string `json:"headers"`
+	Body           any               `json:"body"`
+	SourceStep     string            `json:"source_step"`
+	TimeoutSeconds int               `json:"timeout_seconds"`
 }
 
 type FormConfig struct {
@@ -137,6 +152,30 @@ type FileDropConfig struct {
 	PollIntervalSecs int      `json:"poll_interval_seconds"`
 }
 
+// MissedRunPolicy decides what a schedule does with fire times that passed
+// while no server was running it.
+type MissedRunPolicy string
+
+const (
+	MissedRunSkip       MissedRunPolicy = "skip"
+	MissedRunOnce       MissedRunPolicy = "run_once"
+	MissedRunCatchUpAll MissedRunPolicy = "catch_up_all"
+)
+
+// ScheduleConfig configures a schedule channel. Cron is evaluated in
+// Timezone, an IANA name such as "Europe/Berlin", or UTC when it is empty.
+// Fire times on ExcludeDates (YYYY-MM-DD), on ExcludeWeekdays or on a day
+// of one of the Calendars are left out.
+type ScheduleConfig struct {
+	Cron            string          `json:"cron"`
+	Timezone        string          `json:"timezone"`
+	MissedRuns      MissedRunPolicy `json:"missed_runs"`
+	ExcludeDates    []string        `json:"exclude_dates,omitempty"`
+	ExcludeWeekdays []string        `json:"exclude_weekdays,omitempty"`
+	Calendars       []uuid.UUID     `json:"calendars,omitempty"`
+	Data            map[string]any  `json:"data"`
+}
+
 func (c EmailConfig) WithDefaults() EmailConfig {
 	if c.Mailbox == "" {
 		c.Mailbox = "INBOX"
@@ -150,6 +189,19 @@ func (c EmailConfig) WithDefaults() EmailConfig {
 	return c
 }
 
+func (c WebhookResponseConfig) WithDefaults() WebhookResponseConfig {
+	if c.StatusCode <= 0 {
+		c.StatusCode = 200
+	}
+	if c.TimeoutSeconds <= 0 {
+		c.TimeoutSeconds = 10
+	}
+	if c.TimeoutSeconds > 120 {
+		c.TimeoutSeconds = 120
+	}
+	return c
+}
+
 func (c FormConfig) WithDefaults() FormConfig {
 	if c.RateLimitPerMinute <= 0 {
 		c.RateLimitPerMinute = 10
@@ -166,3 +218,69 @@ func (c FileDropConfig) WithDefaults() FileDropConfig {
 	}
 	return c
 }
+
+func (c ScheduleConfig) WithDefaults() ScheduleConfig {
+	if c.MissedRuns == "" {
+		c.MissedRuns = MissedRunOnce
+	}
+	return c
+}
+
+func (c ScheduleConfig) Location() (*time.Location, error) {
+	if c.Timezone == "" {
+		return time.UTC, nil
+	}
+	loc, err := time.LoadLocation(c.Timezone)
+	if err != nil {
+		return nil, fmt.Errorf("unknown timezone %q", c.Timezone)
+	}
+	return loc, nil
+}
+
+// NextRuns lists the next n fire times after after, in the schedule's
+// timezone, leaving out excluded days. calendars are the schedule's
+// Calendars.
+func (c ScheduleConfig) NextRuns(after time.Time, n int, calendars []*ScheduleCalendar) ([]time.Time, error) {
+	cron, err := engine.ParseCron(c.Cron)
+	if err != nil {
+		return nil, err
+	}
+	loc, err := c.Location()
+	if err != nil {
+		return nil, err
+	}
+	exclusions := newScheduleExclusions(c, calendars)
+	out := make([]time.Time, 0, n)
+	t := after.In(loc)
+	for len(out) < n {
+		next, ok := nextAllowed(cron, t, exclusions)
+		if !ok {
+			break
+		}
+		out = append(out, next)
+		t = next
+	}
+	return out, nil
+}
+
+func (c ScheduleConfig) Validate() error {
+	if _, err := engine.ParseCron(c.Cron); err != nil {
+		return err
+	}
+	if _, err := c.Location(); err != nil {
+		return err
+	}
+	exclusions := ScheduleCalendar{Name: "exclusions", Weekdays: c.ExcludeWeekdays}
+	for _, d := range c.ExcludeDates {
+		exclusions.Dates = append(exclusions.Dates, CalendarDate{Date: d})
+	}
+	if err := exclusions.Validate(); err != nil {
+		return err
+	}
+	switch c.WithDefaults().MissedRuns {
+	case MissedRunSkip, MissedRunOnce, MissedRunCatchUpAll:
+		return nil
+	default:
+		return fmt.Errorf("unknown missed_runs policy %q", c.MissedRuns)
+	}
+}
@@ -13,6 +13,7 @@ import (
 	"net/http"
 	"net/url"
 	"strings"
+	"time"
 
 	"github.com/google/uuid"
 	"github.com/neural-chilli/aceryx/internal/channels"
@@ -23,6 +24,10 @@ type WebhookHandler struct {
 	ChannelStore channels.ChannelStore
 	Pipeline     *channels.Pipeline
 	SecretStore  connectors.SecretStore
+	// Outputs is required for channels with response mapping; without it they
+	// reply with 202 Accepted.
+	Outputs      CaseOutputReader
+	PollInterval time.Duration
 }
 
 func (h *WebhookHandler) ServeHTTP(w http.ResponseWriter, r *http.Request) {
@@ -89,6 +94,10 @@ func (h *WebhookHandler) ServeHTTP(w http.ResponseWriter, r *http.Request) {
 		writeError(w, http.StatusInternalServerError, "pipeline_failed")
 		return
 	}
+	if cfg.Response != nil {
+		h.writeMappedResponse(w, r, channel, *cfg.Response, result, payload)
+		return
+	}
 	writeJSON(w, http.StatusOK, map[string]any{"case_id": result.CaseID.String(), "event_id": result.EventID.String()})
 }
 
@@ -192,3 +192,71 @@ func TestWebhookNoneAuthReturns200(t *testing.T) {
 		t.Fatalf("expected 200, got %d body=%s", rec.Code, rec.Body.String())
 	}
 }
+
+type fakeOutputs struct {
+	output map[string]any
+	done   bool
+	err    error
+}
+
+func (f *fakeOutputs) CaseOutput(context.Context, uuid.UUID, uuid.UUID, string) (map[string]any, bool, error) {
+	return f.output, f.done, f.err
+}
+
+func postWebhook(h *WebhookHandler, body string) *httptest.ResponseRecorder {
+	channelID := h.ChannelStore.(*fakeStore).channel.ID.String()
+	req := httptest.NewRequest(http.MethodPost, "/api/v1/channels/webhook/"+channelID, strings.NewReader(body))
+	req.SetPathValue("channel_id", channelID)
+	req.Header.Set("Content-Type", "application/json")
+	rec := httptest.NewRecorder()
+	h.ServeHTTP(rec, req)
+	return rec
+}
+
+func TestWebhookResponseMappingRendersWorkflowOutput(t *testing.T) {
+	t.Parallel()
+	h := newWebhookHandlerForTest(channels.WebhookConfig{
+		AuthType: "none",
+		Response: &channels.WebhookResponseConfig{
+			StatusCode: http.StatusCreated,
+			Headers:    map[string]string{"X-Decision": "{{output.decision}}"},
+			Body:       map[string]any{"decision": "{{output.decision}}", "reference": "{{request.reference}}"},
+			SourceStep: "score",
+		},
+	}, true)
+	h.Outputs = &fakeOutputs{output: map[string]any{"decision": "approved"}, done: true}
+	h.PollInterval = time.Millisecond
+
+	rec := postWebhook(h, `{"reference":"A-1"}`)
+	if rec.Code != http.StatusCreated {
+		t.Fatalf("expected 201, got %d body=%s", rec.Code, rec.Body.String())
+	}
+	if rec.Header().Get("X-Decision") != "approved" {
+		t.Fatalf("expected mapped header, got %q", rec.Header().Get("X-Decision"))
+	}
+	var body map[string]any
+	if err := json.Unmarshal(rec.Body.Bytes(), &body); err != nil {
+		t.Fatalf("decode body: %v", err)
+	}
+	if body["decision"] != "approved" || body["reference"] != "A-1" {
+		t.Fatalf("unexpected mapped body: %+v", body)
+	}
+}
+
+func TestWebhookResponseMappingTimeoutReturns202(t *testing.T) {
+	t.Parallel()
+	h := newWebhookHandlerForTest(channels.WebhookConfig{
+		AuthType: "none",
+		Response: &channels.WebhookResponseConfig{TimeoutSeconds: 1},
+	}, true)
+	h.Outputs = &fakeOutputs{done: false}
+	h.PollInterval = 10 * time.Millisecond
+
+	rec := postWebhook(h, `{"reference":"A-1"}`)
+	if rec.Code != http.StatusAccepted {
+		t.Fatalf("expected 202, got %d body=%s", rec.Code, rec.Body.String())
+	}
+	if !strings.Contains(rec.Body.String(), `"status":"accepted"`) {
+		t.Fatalf("expected accepted body, got %s", rec.Body.String())
+	}
+}
@@ -0,0 +1,109 @@
+package webhook
+
+import (
+	"context"
+	"encoding/json"
+	"log/slog"
+	"mime"
+	"net/http"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/channels"
+	"github.com/neural-chilli/aceryx/internal/connectors"
+)
+
+// CaseOutputReader reports the workflow output a mapped webhook reply is
+// rendered from. done is false while the output is still pending.
+type CaseOutputReader interface {
+	CaseOutput(ctx context.Context, tenantID, caseID uuid.UUID, stepID string) (output map[string]any, done bool, err error)
+}
+
+const defaultPollInterval = 200 * time.Millisecond
+
+func (h *WebhookHandler) writeMappedResponse(w http.ResponseWriter, r *http.Request, channel *channels.Channel, cfg channels.WebhookResponseConfig, result channels.PipelineResult, payload []byte) {
+	cfg = cfg.WithDefaults()
+	accepted := map[string]any{"status": "accepted", "case_id": result.CaseID.String(), "event_id": result.EventID.String()}
+	if h.Outputs == nil {
+		writeJSON(w, http.StatusAccepted, accepted)
+		return
+	}
+
+	ctx, cancel := context.WithTimeout(r.Context(), time.Duration(cfg.TimeoutSeconds)*time.Second)
+	defer cancel()
+	output, err := h.waitForOutput(ctx, channel.TenantID, result.CaseID, cfg.SourceStep)
+	if err != nil {
+		if ctx.Err() != nil {
+			writeJSON(w, http.StatusAccepted, accepted)
+			return
+		}
+		slog.Warn("webhook response mapping failed", "channel_id", channel.ID.String(), "case_id", result.CaseID.String(), "error", err)
+		writeError(w, http.StatusBadGateway, "workflow_failed")
+		return
+	}
+
+	request := map[string]any{}
+	_ = json.Unmarshal(payload, &request)
+	tplCtx := map[string]any{
+		"output":   output,
+		"request":  request,
+		"case_id":  result.CaseID.String(),
+		"event_id": result.EventID.String(),
+	}
+	for key, value := range cfg.Headers {
+		w.Header().Set(key, connectors.ResolveTemplateString(value, tplCtx))
+	}
+	if cfg.Body == nil {
+		if w.Header().Get("Content-Type") == "" {
+			w.Header().Set("Content-Type", "application/json")
+		}
+		w.WriteHeader(cfg.StatusCode)
+		_ = json.NewEncoder(w).Encode(output)
+		return
+	}
+	if text, ok := cfg.Body.(string); ok {
+		if w.Header().Get("Content-Type") == "" {
+			w.Header().Set("Content-Type", "text/plain; charset=utf-8")
+		}
+		resolve := connectors.ResolveTemplateString
+		if isHTML(w.Header().Get("Content-Type")) {
+			resolve = connectors.ResolveTemplateHTML
+		}
+		w.WriteHeader(cfg.StatusCode)
+		_, _ = w.Write([]byte(resolve(text, tplCtx)))
+		return
+	}
+	if w.Header().Get("Content-Type") == "" {
+		w.Header().Set("Content-Type", "application/json")
+	}
+	w.WriteHeader(cfg.StatusCode)
+	_ = json.NewEncoder(w).Encode(connectors.ResolveTemplateAny(cfg.Body, tplCtx))
+}
+
+func isHTML(contentType string) bool {
+	mediaType, _, err := mime.ParseMediaType(contentType)
+	return err == nil && (mediaType == "text/html" || mediaType == "application/xhtml+xml")
+}
+
+func (h *WebhookHandler) waitForOutput(ctx context.Context, tenantID, caseID uuid.UUID, stepID string) (map[string]any, error) {
+	interval := h.PollInterval
+	if interval <= 0 {
+		interval = defaultPollInterval
+	}
+	ticker := time.NewTicker(interval)
+	defer ticker.Stop()
+	for {
+		output, done, err := h.Outputs.CaseOutput(ctx, tenantID, caseID, stepID)
+		if err != nil {
+			return nil, err
+		}
+		if done {
+			return output, nil
+		}
+		select {
+		case <-ctx.Done():
+			return nil, ctx.Err()
+		case <-ticker.C:
+		}
+	}
+}
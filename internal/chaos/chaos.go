@@ -0,0 +1,185 @@
+// Package chaos makes tool calls fail or slow down on purpose, so that
+// workflow authors can check their retry policies, fallbacks and
+// dead-letter handling against the failures they were designed for. It only
+// runs in development environments.
+package chaos
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"math/rand/v2"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/observability"
+)
+
+// ErrInjected is wrapped by every failure chaos injects.
+var ErrInjected = errors.New("chaos: injected tool failure")
+
+// DevEnvironments are the ACERYX_ENVIRONMENT values chaos rules may be used
+// in.
+var DevEnvironments = []string{"dev", "development", "local", "test"}
+
+// Rule makes calls to the tools it matches fail with probability FailRate,
+// and wait Delay first with probability DelayRate. Empty Protocol and Tool
+// and a nil Tenant match anything.
+type Rule struct {
+	Tenant    uuid.UUID
+	Protocol  string
+	Tool      string
+	FailRate  float64
+	Delay     time.Duration
+	DelayRate float64
+}
+
+func (r Rule) matches(tenantID, protocol, toolID string) bool {
+	if r.Tenant != uuid.Nil && r.Tenant.String() != tenantID {
+		return false
+	}
+	if r.Protocol != "" && r.Protocol != protocol {
+		return false
+	}
+	return r.Tool == "" || r.Tool == toolID
+}
+
+// Injector applies rules to tool calls. The zero value injects nothing.
+type Injector struct {
+	mu    sync.RWMutex
+	rules []Rule
+	// roll returns a number in [0, 1); tests replace it.
+	roll func() float64
+}
+
+var defaultInjector = &Injector{}
+
+// Configure replaces the rules of the process-wide injector used by Inject.
+func Configure(rules []Rule) {
+	defaultInjector.SetRules(rules)
+}
+
+// Inject applies the process-wide rules to one call of toolID. Tool
+// executors call it just before calling the tool.
+func Inject(ctx context.Context, protocol, toolID string) error {
+	return defaultInjector.Inject(ctx, protocol, toolID)
+}
+
+func (i *Injector) SetRules(rules []Rule) {
+	i.mu.Lock()
+	defer i.mu.Unlock()
+	i.rules = rules
+}
+
+// Inject waits and fails as the first rule matching the call says. The
+// tenant is taken from ctx. A delay ends early when ctx is done.
+func (i *Injector) Inject(ctx context.Context, protocol, toolID string) error {
+	i.mu.RLock()
+	rules, roll := i.rules, i.roll
+	i.mu.RUnlock()
+	if len(rules) == 0 {
+		return nil
+	}
+	if roll == nil {
+		roll = rand.Float64
+	}
+	tenantID := observability.TenantIDFromContext(ctx)
+	for _, rule := range rules {
+		if !rule.matches(tenantID, protocol, toolID) {
+			continue
+		}
+		if rule.Delay > 0 && roll() < rule.DelayRate {
+			timer := time.NewTimer(rule.Delay)
+			select {
+			case <-timer.C:
+			case <-ctx.Done():
+				timer.Stop()
+				return ctx.Err()
+			}
+		}
+		if rule.FailRate > 0 && roll() < rule.FailRate {
+			return fmt.Errorf("%w: %s tool %s", ErrInjected, protocol, toolID)
+		}
+		return nil
+	}
+	return nil
+}
+
+// ParseRules parses comma-separated rules, each one or more key:value
+// conditions joined by "+", for example
+// "protocol:connector+tool:http/request+fail:0.3,protocol:mcp+delay:2s".
+// Keys are protocol, tool and tenant, which select calls, and fail, delay
+// and delay_rate. fail and delay_rate are probabilities from 0 to 1;
+// delay_rate defaults to 1 when a delay is given.
+func ParseRules(raw string) ([]Rule, error) {
+	rules := make([]Rule, 0)
+	for _, part := range strings.Split(raw, ",") {
+		part = strings.TrimSpace(part)
+		if part == "" {
+			continue
+		}
+		rule := Rule{DelayRate: -1}
+		for _, cond := range strings.Split(part, "+") {
+			key, value, ok := strings.Cut(strings.TrimSpace(cond), ":")
+			value = strings.TrimSpace(value)
+			if !ok || value == "" {
+				return nil, fmt.Errorf("invalid chaos condition %q", cond)
+			}
+			var err error
+			switch strings.ToLower(strings.TrimSpace(key)) {
+			case "protocol":
+				rule.Protocol = value
+			case "tool":
+				rule.Tool = value
+			case "tenant":
+				if rule.Tenant, err = uuid.Parse(value); err != nil {
+					return nil, fmt.Errorf("invalid tenant in chaos rule %q: %w", part, err)
+				}
+			case "fail":
+				if rule.FailRate, err = parseProbability(value); err != nil {
+					return nil, fmt.Errorf("invalid fail in chaos rule %q: %w", part, err)
+				}
+			case "delay":
+				if rule.Delay, err = time.ParseDuration(value); err != nil || rule.Delay <= 0 {
+					return nil, fmt.Errorf("invalid delay in chaos rule %q: must be a positive duration", part)
+				}
+			case "delay_rate":
+				if rule.DelayRate, err = parseProbability(value); err != nil {
+					return nil, fmt.Errorf("invalid delay_rate in chaos rule %q: %w", part, err)
+				}
+			default:
+				return nil, fmt.Errorf("unknown chaos key %q", key)
+			}
+		}
+		if rule.DelayRate < 0 {
+			rule.DelayRate = 1
+		}
+		if rule.FailRate == 0 && (rule.Delay == 0 || rule.DelayRate == 0) {
+			return nil, fmt.Errorf("chaos rule %q neither fails nor delays calls", part)
+		}
+		rules = append(rules, rule)
+	}
+	return rules, nil
+}
+
+func parseProbability(raw string) (float64, error) {
+	p, err := strconv.ParseFloat(raw, 64)
+	if err != nil || p < 0 || p > 1 {
+		return 0, fmt.Errorf("%q is not a probability between 0 and 1", raw)
+	}
+	return p, nil
+}
+
+// IsDevEnvironment reports whether chaos rules may be used in environment.
+func IsDevEnvironment(environment string) bool {
+	environment = strings.ToLower(strings.TrimSpace(environment))
+	for _, dev := range DevEnvironments {
+		if environment == dev {
+			return true
+		}
+	}
+	return false
+}
@@ -0,0 +1,84 @@
+package chaos
+
+import (
+	"context"
+	"errors"
+	"testing"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/observability"
+)
+
+func TestParseRules(t *testing.T) {
+	tenant := uuid.New()
+	rules, err := ParseRules("protocol:connector+tool:http/request+fail:0.3, protocol:mcp+delay:2s+tenant:" + tenant.String())
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(rules) != 2 {
+		t.Fatalf("expected two rules, got %+v", rules)
+	}
+	if got := rules[0]; got.Protocol != "connector" || got.Tool != "http/request" || got.FailRate != 0.3 || got.Delay != 0 {
+		t.Fatalf("unexpected failure rule %+v", got)
+	}
+	if got := rules[1]; got.Delay != 2*time.Second || got.DelayRate != 1 || got.Tenant != tenant {
+		t.Fatalf("expected delay_rate to default to 1, got %+v", got)
+	}
+
+	for _, bad := range []string{
+		"protocol:mcp",
+		"tool:lookup+fail:1.5",
+		"tool:lookup+delay:-1s",
+		"tool:lookup+delay:1s+delay_rate:0",
+		"colour:red+fail:1",
+		"tenant:nope+fail:1",
+	} {
+		if _, err := ParseRules(bad); err == nil {
+			t.Fatalf("expected %q to be rejected", bad)
+		}
+	}
+	if rules, err := ParseRules(""); err != nil || len(rules) != 0 {
+		t.Fatalf("expected no rules, got %+v, %v", rules, err)
+	}
+}
+
+func TestInjectorFailsMatchingCalls(t *testing.T) {
+	tenant := uuid.New()
+	i := &Injector{roll: func() float64 { return 0.5 }}
+	i.SetRules([]Rule{
+		{Protocol: "mcp", Tool: "search", FailRate: 0.4},
+		{Protocol: "mcp", Tenant: tenant, FailRate: 0.6},
+	})
+
+	ctx := observability.WithTenantID(context.Background(), tenant)
+	if err := i.Inject(ctx, "mcp", "search"); err != nil {
+		t.Fatalf("expected the first matching rule to decide, and 0.5 to miss 0.4, got %v", err)
+	}
+	if err := i.Inject(ctx, "mcp", "lookup"); !errors.Is(err, ErrInjected) {
+		t.Fatalf("expected an injected failure for the tenant's rule, got %v", err)
+	}
+	if err := i.Inject(context.Background(), "mcp", "lookup"); err != nil {
+		t.Fatalf("expected another tenant's calls to be left alone, got %v", err)
+	}
+	if err := i.Inject(ctx, "grpc", "Lookup"); err != nil {
+		t.Fatalf("expected other protocols to be left alone, got %v", err)
+	}
+}
+
+func TestInjectorDelayStopsWithContext(t *testing.T) {
+	i := &Injector{roll: func() float64 { return 0 }}
+	i.SetRules([]Rule{{Protocol: "connector", Delay: time.Hour, DelayRate: 1}})
+
+	ctx, cancel := context.WithTimeout(context.Background(), 10*time.Millisecond)
+	defer cancel()
+	if err := i.Inject(ctx, "connector", "http/request"); !errors.Is(err, context.DeadlineExceeded) {
+		t.Fatalf("expected the delay to end with the call's deadline, got %v", err)
+	}
+}
+
+func TestIsDevEnvironment(t *testing.T) {
+	if !IsDevEnvironment(" Dev ") || IsDevEnvironment("") || IsDevEnvironment("prod") {
+		t.Fatal("unexpected development environments")
+	}
+}
@@ -0,0 +1,265 @@
+// Package cluster tracks the server replicas sharing a database and elects
+// one of them leader, so singleton background work such as report schedules
+// runs once per cluster rather than once per replica.
+package cluster
+
+import (
+	"context"
+	"database/sql"
+	"errors"
+	"fmt"
+	"log/slog"
+	"os"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+const (
+	DefaultHeartbeatInterval = 10 * time.Second
+	DefaultLeaseTTL          = 30 * time.Second
+
+	RoleLeader   = "leader"
+	RoleFollower = "follower"
+
+	leaseName = "scheduler"
+)
+
+// Config identifies this replica. Lease expiry is judged by the database
+// clock, so replicas need not agree on the time.
+type Config struct {
+	ReplicaID         string
+	Hostname          string
+	HeartbeatInterval time.Duration
+	LeaseTTL          time.Duration
+}
+
+func (c *Config) normalize() {
+	if strings.TrimSpace(c.Hostname) == "" {
+		c.Hostname, _ = os.Hostname()
+	}
+	if strings.TrimSpace(c.ReplicaID) == "" {
+		c.ReplicaID = strings.TrimPrefix(c.Hostname+"-"+uuid.NewString()[:8], "-")
+	}
+	if c.HeartbeatInterval <= 0 {
+		c.HeartbeatInterval = DefaultHeartbeatInterval
+	}
+	if c.LeaseTTL <= c.HeartbeatInterval {
+		c.LeaseTTL = 3 * c.HeartbeatInterval
+	}
+}
+
+// Replica is one server process seen through its heartbeat. Stale replicas
+// have missed heartbeats for longer than the lease TTL.
+type Replica struct {
+	ID          string    `json:"id"`
+	Hostname    string    `json:"hostname"`
+	Role        string    `json:"role"`
+	Self        bool      `json:"self"`
+	Stale       bool      `json:"stale"`
+	StartedAt   time.Time `json:"started_at"`
+	HeartbeatAt time.Time `json:"heartbeat_at"`
+}
+
+// Status is the cluster as this replica sees it.
+type Status struct {
+	ReplicaID      string     `json:"replica_id"`
+	Leader         string     `json:"leader,omitempty"`
+	LeaseExpiresAt *time.Time `json:"lease_expires_at,omitempty"`
+	Replicas       []Replica  `json:"replicas"`
+}
+
+type leaderTask struct {
+	name string
+	run  func(ctx context.Context)
+}
+
+// Node is this replica's membership. Register leader-only work with OnLeader
+// before Run.
+type Node struct {
+	db         *sql.DB
+	cfg        Config
+	instanceID uuid.UUID
+
+	mu          sync.Mutex
+	leader      bool
+	tasks       []leaderTask
+	cancelTasks context.CancelFunc
+}
+
+func New(db *sql.DB, cfg Config) *Node {
+	cfg.normalize()
+	return &Node{db: db, cfg: cfg, instanceID: uuid.New()}
+}
+
+// ID returns this replica's ID.
+func (n *Node) ID() string {
+	return n.cfg.ReplicaID
+}
+
+// InstanceID identifies this process. Unlike ID it changes on every start,
+// so work owned by a previous run of the same replica can be told apart.
+func (n *Node) InstanceID() uuid.UUID {
+	return n.instanceID
+}
+
+// LeaseTTL returns how long a replica may miss heartbeats before it is
+// considered gone.
+func (n *Node) LeaseTTL() time.Duration {
+	return n.cfg.LeaseTTL
+}
+
+// IsLeader reports whether this replica held the lease at its last
+// heartbeat.
+func (n *Node) IsLeader() bool {
+	n.mu.Lock()
+	defer n.mu.Unlock()
+	return n.leader
+}
+
+// OnLeader runs run while this replica leads. Its context is cancelled when
+// leadership is lost, and run starts again if leadership comes back.
+func (n *Node) OnLeader(name string, run func(ctx context.Context)) {
+	n.mu.Lock()
+	defer n.mu.Unlock()
+	n.tasks = append(n.tasks, leaderTask{name: name, run: run})
+}
+
+// Run heartbeats until ctx is done, then releases the lease and deregisters
+// so another replica can take over without waiting for expiry.
+func (n *Node) Run(ctx context.Context) {
+	ticker := time.NewTicker(n.cfg.HeartbeatInterval)
+	defer ticker.Stop()
+	for {
+		n.heartbeat(ctx)
+		select {
+		case <-ctx.Done():
+			n.resign()
+			return
+		case <-ticker.C:
+		}
+	}
+}
+
+func (n *Node) heartbeat(ctx context.Context) {
+	leader, err := n.beat(ctx)
+	if err != nil && ctx.Err() == nil {
+		// Without the database the lease cannot be renewed, so step down
+		// rather than risk two leaders once it expires.
+		slog.WarnContext(ctx, "cluster heartbeat failed", "replica", n.cfg.ReplicaID, "error", err)
+	}
+	n.setLeader(ctx, leader)
+}
+
+func (n *Node) beat(ctx context.Context) (bool, error) {
+	if _, err := n.db.ExecContext(ctx, `
+INSERT INTO cluster_replicas (id, hostname, instance_id)
+VALUES ($1, $2, $3)
+ON CONFLICT (id) DO UPDATE SET hostname = EXCLUDED.hostname, instance_id = EXCLUDED.instance_id, heartbeat_at = now()
+`, n.cfg.ReplicaID, n.cfg.Hostname, n.instanceID); err != nil {
+		return false, fmt.Errorf("record heartbeat: %w", err)
+	}
+	var holder string
+	err := n.db.QueryRowContext(ctx, `
+INSERT INTO cluster_leases (name, replica_id, expires_at)
+VALUES ($1, $2, now() + make_interval(secs => $3))
+ON CONFLICT (name) DO UPDATE
+SET replica_id = EXCLUDED.replica_id,
+    acquired_at = CASE WHEN cluster_leases.replica_id = EXCLUDED.replica_id THEN cluster_leases.acquired_at ELSE now() END,
+    expires_at = EXCLUDED.expires_at
+WHERE cluster_leases.replica_id = EXCLUDED.replica_id OR cluster_leases.expires_at < now()
+RETURNING replica_id
+`, leaseName, n.cfg.ReplicaID, n.cfg.LeaseTTL.Seconds()).Scan(&holder)
+	if errors.Is(err, sql.ErrNoRows) {
+		return false, nil
+	}
+	if err != nil {
+		return false, fmt.Errorf("renew leader lease: %w", err)
+	}
+	if _, err := n.db.ExecContext(ctx, `
+DELETE FROM cluster_replicas WHERE heartbeat_at < now() - make_interval(secs => $1)
+`, (10 * n.cfg.LeaseTTL).Seconds()); err != nil {
+		return true, fmt.Errorf("prune replicas: %w", err)
+	}
+	return true, nil
+}
+
+// setLeader starts the leader tasks on election and cancels them on
+// demotion.
+func (n *Node) setLeader(ctx context.Context, leader bool) {
+	n.mu.Lock()
+	defer n.mu.Unlock()
+	if leader == n.leader {
+		return
+	}
+	n.leader = leader
+	if !leader {
+		slog.Info("cluster leadership lost", "replica", n.cfg.ReplicaID)
+		if n.cancelTasks != nil {
+			n.cancelTasks()
+			n.cancelTasks = nil
+		}
+		return
+	}
+	slog.Info("cluster leadership acquired", "replica", n.cfg.ReplicaID)
+	leaderCtx, cancel := context.WithCancel(ctx)
+	n.cancelTasks = cancel
+	for _, task := range n.tasks {
+		go task.run(leaderCtx)
+	}
+}
+
+func (n *Node) resign() {
+	n.setLeader(context.Background(), false)
+	ctx, cancel := context.WithTimeout(context.Background(), 5*time.Second)
+	defer cancel()
+	if _, err := n.db.ExecContext(ctx, `DELETE FROM cluster_leases WHERE name = $1 AND replica_id = $2`, leaseName, n.cfg.ReplicaID); err != nil {
+		slog.Warn("release leader lease failed", "replica", n.cfg.ReplicaID, "error", err)
+	}
+	if _, err := n.db.ExecContext(ctx, `DELETE FROM cluster_replicas WHERE id = $1`, n.cfg.ReplicaID); err != nil {
+		slog.Warn("deregister replica failed", "replica", n.cfg.ReplicaID, "error", err)
+	}
+}
+
+// Status lists known replicas, oldest first, with the current leader.
+func (n *Node) Status(ctx context.Context) (Status, error) {
+	out := Status{ReplicaID: n.cfg.ReplicaID, Replicas: []Replica{}}
+	var expires time.Time
+	err := n.db.QueryRowContext(ctx, `
+SELECT replica_id, expires_at FROM cluster_leases WHERE name = $1 AND expires_at > now()
+`, leaseName).Scan(&out.Leader, &expires)
+	switch {
+	case err == nil:
+		expires = expires.UTC()
+		out.LeaseExpiresAt = &expires
+	case !errors.Is(err, sql.ErrNoRows):
+		return Status{}, fmt.Errorf("load leader lease: %w", err)
+	}
+
+	rows, err := n.db.QueryContext(ctx, `
+SELECT id, hostname, started_at, heartbeat_at, heartbeat_at < now() - make_interval(secs => $1)
+FROM cluster_replicas
+ORDER BY started_at, id
+`, n.cfg.LeaseTTL.Seconds())
+	if err != nil {
+		return Status{}, fmt.Errorf("list replicas: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	for rows.Next() {
+		var replica Replica
+		if err := rows.Scan(&replica.ID, &replica.Hostname, &replica.StartedAt, &replica.HeartbeatAt, &replica.Stale); err != nil {
+			return Status{}, fmt.Errorf("scan replica: %w", err)
+		}
+		replica.Role = RoleFollower
+		if replica.ID == out.Leader {
+			replica.Role = RoleLeader
+		}
+		replica.Self = replica.ID == n.cfg.ReplicaID
+		replica.StartedAt = replica.StartedAt.UTC()
+		replica.HeartbeatAt = replica.HeartbeatAt.UTC()
+		out.Replicas = append(out.Replicas, replica)
+	}
+	return out, rows.Err()
+}
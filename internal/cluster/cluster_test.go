@@ -0,0 +1,45 @@
+package cluster
+
+import (
+	"context"
+	"testing"
+	"time"
+)
+
+func TestConfigNormalize(t *testing.T) {
+	cfg := Config{Hostname: "web-1", HeartbeatInterval: 20 * time.Second, LeaseTTL: 5 * time.Second}
+	cfg.normalize()
+	if cfg.ReplicaID == "" || cfg.ReplicaID[:6] != "web-1-" {
+		t.Fatalf("expected a generated replica ID, got %q", cfg.ReplicaID)
+	}
+	if cfg.LeaseTTL != 60*time.Second {
+		t.Fatalf("expected the lease TTL to outlast three heartbeats, got %s", cfg.LeaseTTL)
+	}
+}
+
+func TestLeaderTasksFollowLeadership(t *testing.T) {
+	n := New(nil, Config{ReplicaID: "a"})
+	started := make(chan context.Context, 2)
+	n.OnLeader("test", func(ctx context.Context) { started <- ctx })
+
+	n.setLeader(context.Background(), true)
+	taskCtx := <-started
+	n.setLeader(context.Background(), true)
+	select {
+	case <-started:
+		t.Fatal("task started twice while leading")
+	case <-time.After(20 * time.Millisecond):
+	}
+
+	n.setLeader(context.Background(), false)
+	select {
+	case <-taskCtx.Done():
+	case <-time.After(time.Second):
+		t.Fatal("task context not cancelled on demotion")
+	}
+	if n.IsLeader() {
+		t.Fatal("still leader after demotion")
+	}
+	n.setLeader(context.Background(), true)
+	<-started
+}
@@ -0,0 +1,104 @@
+package config
+
+import (
+	"crypto/aes"
+	"crypto/cipher"
+	"crypto/rand"
+	"encoding/base64"
+	"errors"
+	"fmt"
+	"os"
+	"strings"
+)
+
+// EncryptedPrefix marks a config value encrypted with the master key.
+const EncryptedPrefix = "enc:v1:"
+
+// ErrNoMasterKey is returned when an encrypted value is read without
+// ACERYX_CONFIG_KEY or ACERYX_CONFIG_KEY_FILE set.
+var ErrNoMasterKey = errors.New("encrypted config value needs ACERYX_CONFIG_KEY or ACERYX_CONFIG_KEY_FILE")
+
+// GenerateMasterKey returns a new base64-encoded 256-bit master key.
+func GenerateMasterKey() (string, error) {
+	key := make([]byte, 32)
+	if _, err := rand.Read(key); err != nil {
+		return "", fmt.Errorf("generate master key: %w", err)
+	}
+	return base64.StdEncoding.EncodeToString(key), nil
+}
+
+// MasterKey loads the master key from ACERYX_CONFIG_KEY, or from the file
+// named by ACERYX_CONFIG_KEY_FILE. The key is base64 and must decode to 32
+// bytes.
+func MasterKey() ([]byte, error) {
+	encoded := strings.TrimSpace(os.Getenv("ACERYX_CONFIG_KEY"))
+	if encoded == "" {
+		if path := strings.TrimSpace(os.Getenv("ACERYX_CONFIG_KEY_FILE")); path != "" {
+			raw, err := os.ReadFile(path)
+			if err != nil {
+				return nil, fmt.Errorf("read config key file: %w", err)
+			}
+			encoded = strings.TrimSpace(string(raw))
+		}
+	}
+	if encoded == "" {
+		return nil, ErrNoMasterKey
+	}
+	key, err := base64.StdEncoding.DecodeString(encoded)
+	if err != nil {
+		return nil, fmt.Errorf("decode config key: %w", err)
+	}
+	if len(key) != 32 {
+		return nil, fmt.Errorf("config key must be 32 bytes, got %d", len(key))
+	}
+	return key, nil
+}
+
+// EncryptValue seals plaintext with AES-256-GCM under key and returns it as
+// an enc:v1: value for a config file. name is the config key the value is
+// stored under; it is bound as additional data, so the value cannot be moved
+// to another key and still decrypt.
+func EncryptValue(key []byte, name, plaintext string) (string, error) {
+	gcm, err := newGCM(key)
+	if err != nil {
+		return "", err
+	}
+	nonce := make([]byte, gcm.NonceSize())
+	if _, err := rand.Read(nonce); err != nil {
+		return "", fmt.Errorf("generate nonce: %w", err)
+	}
+	sealed := gcm.Seal(nonce, nonce, []byte(plaintext), []byte(name))
+	return EncryptedPrefix + base64.StdEncoding.EncodeToString(sealed), nil
+}
+
+// DecryptValue opens an enc:v1: value sealed by EncryptValue for name.
+func DecryptValue(key []byte, name, value string) (string, error) {
+	encoded, ok := strings.CutPrefix(strings.TrimSpace(value), EncryptedPrefix)
+	if !ok {
+		return "", fmt.Errorf("value is not %s encrypted", EncryptedPrefix)
+	}
+	sealed, err := base64.StdEncoding.DecodeString(encoded)
+	if err != nil {
+		return "", fmt.Errorf("decode encrypted value: %w", err)
+	}
+	gcm, err := newGCM(key)
+	if err != nil {
+		return "", err
+	}
+	if len(sealed) < gcm.NonceSize() {
+		return "", fmt.Errorf("encrypted value is truncated")
+	}
+	plaintext, err := gcm.Open(nil, sealed[:gcm.NonceSize()], sealed[gcm.NonceSize():], []byte(name))
+	if err != nil {
+		return "", fmt.Errorf("decrypt value: wrong key, wrong config key name or corrupted value")
+	}
+	return string(plaintext), nil
+}
+
+func newGCM(key []byte) (cipher.AEAD, error) {
+	block, err := aes.NewCipher(key)
+	if err != nil {
+		return nil, fmt.Errorf("config key: %w", err)
+	}
+	return cipher.NewGCM(block)
+}
@@ -0,0 +1,68 @@
+package config
+
+import (
+	"fmt"
+	"os"
+	"strings"
+)
+
+// Interpolate expands ${NAME} from lookup and ${file:/path} from the file's
+// contents, without its trailing newline, so a config file can refer to the
+// environment and to mounted secrets instead of holding them. "$$" is a
+// literal "$". An undefined variable or unreadable file is an error rather
+// than an empty value.
+func Interpolate(value string, lookup func(string) (string, bool)) (string, error) {
+	if !strings.Contains(value, "$") {
+		return value, nil
+	}
+	var b strings.Builder
+	for i := 0; i < len(value); i++ {
+		c := value[i]
+		if c != '$' || i+1 >= len(value) {
+			b.WriteByte(c)
+			continue
+		}
+		switch value[i+1] {
+		case '$':
+			b.WriteByte('$')
+			i++
+		case '{':
+			end := strings.IndexByte(value[i+2:], '}')
+			if end < 0 {
+				return "", fmt.Errorf("unterminated ${ in %q", value)
+			}
+			ref := strings.TrimSpace(value[i+2 : i+2+end])
+			resolved, err := resolveReference(ref, lookup)
+			if err != nil {
+				return "", err
+			}
+			b.WriteString(resolved)
+			i += end + 2
+		default:
+			b.WriteByte(c)
+		}
+	}
+	return b.String(), nil
+}
+
+func resolveReference(ref string, lookup func(string) (string, bool)) (string, error) {
+	if path, ok := strings.CutPrefix(ref, "file:"); ok {
+		path = strings.TrimSpace(path)
+		if path == "" {
+			return "", fmt.Errorf("${file:} needs a path")
+		}
+		raw, err := os.ReadFile(path)
+		if err != nil {
+			return "", fmt.Errorf("read ${file:%s}: %w", path, err)
+		}
+		return strings.TrimRight(string(raw), "\r\n"), nil
+	}
+	if ref == "" {
+		return "", fmt.Errorf("empty ${} reference")
+	}
+	value, ok := lookup(ref)
+	if !ok {
+		return "", fmt.Errorf("${%s} is not set", ref)
+	}
+	return value, nil
+}
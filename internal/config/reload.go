@@ -0,0 +1,223 @@
+// Package config loads settings from an optional env file and re-applies the
+// ones that are safe to change while the server runs.
+package config
+
+import (
+	"bufio"
+	"context"
+	"errors"
+	"fmt"
+	"log/slog"
+	"os"
+	"os/signal"
+	"sort"
+	"strings"
+	"sync"
+	"syscall"
+	"time"
+)
+
+// ErrNoConfigFile is returned by Reload when ACERYX_CONFIG_FILE is not set.
+var ErrNoConfigFile = errors.New("no config file configured")
+
+// ReloadableKeys are applied live on reload. Any other key that changes in
+// the file only takes effect after a restart.
+var ReloadableKeys = map[string]bool{
+	"ACERYX_LOG_LEVEL":            true,
+	"ACERYX_EXECUTE_RPM":          true,
+	"ACERYX_EXECUTE_BURST":        true,
+	"ACERYX_EXECUTE_MAX_FAILURES": true,
+	"ACERYX_AGENT_TOOLS_ALLOW":    true,
+	"ACERYX_AGENT_TOOLS_DENY":     true,
+	"ACERYX_CHAOS_RULES":          true,
+}
+
+type ReloadResult struct {
+	Applied         []string  `json:"applied"`
+	RestartRequired []string  `json:"restart_required"`
+	ReloadedAt      time.Time `json:"reloaded_at"`
+}
+
+// Reloader re-reads the config file and runs the registered appliers, which
+// read their settings back from the environment.
+type Reloader struct {
+	mu       sync.Mutex
+	path     string
+	appliers []func()
+	modTime  time.Time
+}
+
+func NewReloader(path string) *Reloader {
+	return &Reloader{path: strings.TrimSpace(path)}
+}
+
+// OnReload registers a function run after every successful reload.
+func (r *Reloader) OnReload(apply func()) {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	r.appliers = append(r.appliers, apply)
+}
+
+func (r *Reloader) Reload() (ReloadResult, error) {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	result := ReloadResult{Applied: []string{}, RestartRequired: []string{}, ReloadedAt: time.Now().UTC()}
+	if r.path == "" {
+		return result, ErrNoConfigFile
+	}
+	if info, err := os.Stat(r.path); err == nil {
+		r.modTime = info.ModTime()
+	}
+	values, err := ReadEnvFile(r.path)
+	if err != nil {
+		return result, err
+	}
+	keys := make([]string, 0, len(values))
+	for key := range values {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+	for _, key := range keys {
+		if current, ok := os.LookupEnv(key); ok && current == values[key] {
+			continue
+		}
+		if !ReloadableKeys[key] {
+			result.RestartRequired = append(result.RestartRequired, key)
+			continue
+		}
+		if err := os.Setenv(key, values[key]); err != nil {
+			return result, fmt.Errorf("set %s: %w", key, err)
+		}
+		result.Applied = append(result.Applied, key)
+	}
+	for _, apply := range r.appliers {
+		apply()
+	}
+	return result, nil
+}
+
+// Watch reloads on SIGHUP and whenever the file's modification time changes,
+// checking every interval, until ctx is done.
+func (r *Reloader) Watch(ctx context.Context, interval time.Duration) {
+	if r.path == "" {
+		return
+	}
+	hup := make(chan os.Signal, 1)
+	signal.Notify(hup, syscall.SIGHUP)
+	defer signal.Stop(hup)
+	ticker := time.NewTicker(interval)
+	defer ticker.Stop()
+	for {
+		select {
+		case <-ctx.Done():
+			return
+		case <-hup:
+			r.reloadAndLog("signal")
+		case <-ticker.C:
+			if r.changed() {
+				r.reloadAndLog("file_changed")
+			}
+		}
+	}
+}
+
+func (r *Reloader) changed() bool {
+	info, err := os.Stat(r.path)
+	if err != nil {
+		return false
+	}
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	return !info.ModTime().Equal(r.modTime)
+}
+
+func (r *Reloader) reloadAndLog(trigger string) {
+	result, err := r.Reload()
+	if err != nil {
+		slog.Error("config reload failed", "trigger", trigger, "error", err)
+		return
+	}
+	slog.Info("config reloaded", "trigger", trigger, "applied", result.Applied, "restart_required", result.RestartRequired)
+}
+
+// ApplyEnvFile sets every key in the file as an environment variable. Values
+// in the file override the process environment so a reload and a restart
+// agree on the result.
+func ApplyEnvFile(path string) error {
+	path = strings.TrimSpace(path)
+	if path == "" {
+		return nil
+	}
+	values, err := ReadEnvFile(path)
+	if err != nil {
+		return err
+	}
+	for key, value := range values {
+		if err := os.Setenv(key, value); err != nil {
+			return fmt.Errorf("set %s: %w", key, err)
+		}
+	}
+	return nil
+}
+
+// ReadEnvFile parses KEY=VALUE lines. Blank lines, # comments and a leading
+// "export " are ignored; matching surrounding quotes are stripped. Values are
+// then resolved: enc:v1: values are decrypted with the master key for their
+// key name, and ${NAME} and ${file:/path} references are expanded, with NAME
+// looked up in earlier lines of the file before the environment. "$${" is a
+// literal "${". Single-quoted values are taken literally.
+func ReadEnvFile(path string) (map[string]string, error) {
+	f, err := os.Open(path)
+	if err != nil {
+		return nil, fmt.Errorf("open config file: %w", err)
+	}
+	defer func() { _ = f.Close() }()
+
+	values := map[string]string{}
+	lookup := func(name string) (string, bool) {
+		if v, ok := values[name]; ok {
+			return v, true
+		}
+		return os.LookupEnv(name)
+	}
+	var masterKey []byte
+	scanner := bufio.NewScanner(f)
+	for lineNo := 1; scanner.Scan(); lineNo++ {
+		line := strings.TrimSpace(scanner.Text())
+		if line == "" || strings.HasPrefix(line, "#") {
+			continue
+		}
+		line = strings.TrimPrefix(line, "export ")
+		key, value, ok := strings.Cut(line, "=")
+		key = strings.TrimSpace(key)
+		if !ok || key == "" {
+			return nil, fmt.Errorf("config file %s line %d: expected KEY=VALUE", path, lineNo)
+		}
+		value = strings.TrimSpace(value)
+		literal := false
+		if len(value) >= 2 && (value[0] == '"' || value[0] == '\'') && value[len(value)-1] == value[0] {
+			literal = value[0] == '\''
+			value = value[1 : len(value)-1]
+		}
+		switch {
+		case strings.HasPrefix(value, EncryptedPrefix):
+			if masterKey == nil {
+				if masterKey, err = MasterKey(); err != nil {
+					return nil, fmt.Errorf("config file %s line %d: %w", path, lineNo, err)
+				}
+			}
+			if value, err = DecryptValue(masterKey, key, value); err != nil {
+				return nil, fmt.Errorf("config file %s line %d: %s: %w", path, lineNo, key, err)
+			}
+		case !literal:
+			if value, err = Interpolate(value, lookup); err != nil {
+				return nil, fmt.Errorf("config file %s line %d: %s: %w", path, lineNo, key, err)
+			}
+		}
+		values[key] = value
+	}
+	if err := scanner.Err(); err != nil {
+		return nil, fmt.Errorf("read config file: %w", err)
+	}
+	return values, nil
+}
@@ -0,0 +1,72 @@
+package config
+
+import (
+	"errors"
+	"os"
+	"path/filepath"
+	"slices"
+	"testing"
+)
+
+func TestReadEnvFile(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "aceryx.env")
+	content := "# comment\n\nexport ACERYX_LOG_LEVEL=debug\nACERYX_AGENT_TOOLS_DENY=\"category:database\"\nACERYX_HTTP_ADDR = ':9000'\n"
+	if err := os.WriteFile(path, []byte(content), 0o600); err != nil {
+		t.Fatal(err)
+	}
+	got, err := ReadEnvFile(path)
+	if err != nil {
+		t.Fatalf("read: %v", err)
+	}
+	want := map[string]string{"ACERYX_LOG_LEVEL": "debug", "ACERYX_AGENT_TOOLS_DENY": "category:database", "ACERYX_HTTP_ADDR": ":9000"}
+	if len(got) != len(want) {
+		t.Fatalf("got %v", got)
+	}
+	for k, v := range want {
+		if got[k] != v {
+			t.Fatalf("%s = %q, want %q", k, got[k], v)
+		}
+	}
+
+	if err := os.WriteFile(path, []byte("NOT A SETTING\n"), 0o600); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := ReadEnvFile(path); err == nil {
+		t.Fatal("expected error for malformed line")
+	}
+}
+
+func TestReloaderAppliesOnlyReloadableKeys(t *testing.T) {
+	t.Setenv("ACERYX_LOG_LEVEL", "info")
+	t.Setenv("ACERYX_HTTP_ADDR", ":8080")
+	path := filepath.Join(t.TempDir(), "aceryx.env")
+	if err := os.WriteFile(path, []byte("ACERYX_LOG_LEVEL=debug\nACERYX_HTTP_ADDR=:9000\n"), 0o600); err != nil {
+		t.Fatal(err)
+	}
+
+	r := NewReloader(path)
+	applied := 0
+	r.OnReload(func() { applied++ })
+	result, err := r.Reload()
+	if err != nil {
+		t.Fatalf("reload: %v", err)
+	}
+	if !slices.Equal(result.Applied, []string{"ACERYX_LOG_LEVEL"}) || !slices.Equal(result.RestartRequired, []string{"ACERYX_HTTP_ADDR"}) {
+		t.Fatalf("unexpected result %+v", result)
+	}
+	if os.Getenv("ACERYX_LOG_LEVEL") != "debug" || os.Getenv("ACERYX_HTTP_ADDR") != ":8080" {
+		t.Fatalf("env not updated as expected: level=%s addr=%s", os.Getenv("ACERYX_LOG_LEVEL"), os.Getenv("ACERYX_HTTP_ADDR"))
+	}
+	if applied != 1 {
+		t.Fatalf("expected appliers to run once, ran %d", applied)
+	}
+	if r.changed() {
+		t.Fatal("file should not count as changed right after a reload")
+	}
+}
+
+func TestReloaderWithoutFile(t *testing.T) {
+	if _, err := NewReloader("").Reload(); !errors.Is(err, ErrNoConfigFile) {
+		t.Fatalf("expected ErrNoConfigFile, got %v", err)
+	}
+}
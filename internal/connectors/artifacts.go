@@ -0,0 +1,67 @@
+package connectors
+
+import (
+	"encoding/json"
+	"fmt"
+	"sort"
+	"strings"
+
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+// StepArtifact moves an action output field into an artifact. Name defaults
+// to the field name. ContentType defaults to text/plain for strings and
+// application/json for anything else.
+type StepArtifact struct {
+	Name        string `json:"name"`
+	ContentType string `json:"content_type"`
+}
+
+// extractArtifacts returns a copy of result with each configured field
+// replaced by {"artifact": name}, and the artifacts holding their values.
+// Missing fields are skipped.
+func extractArtifacts(result map[string]any, specs map[string]StepArtifact) (map[string]any, []engine.Artifact, error) {
+	if len(specs) == 0 {
+		return result, nil, nil
+	}
+	out := make(map[string]any, len(result))
+	for k, v := range result {
+		out[k] = v
+	}
+	fields := make([]string, 0, len(specs))
+	for field := range specs {
+		fields = append(fields, field)
+	}
+	sort.Strings(fields)
+
+	artifacts := make([]engine.Artifact, 0, len(fields))
+	for _, field := range fields {
+		value, ok := out[field]
+		if !ok || value == nil {
+			continue
+		}
+		spec := specs[field]
+		artifact := engine.Artifact{Name: strings.TrimSpace(spec.Name), ContentType: strings.TrimSpace(spec.ContentType)}
+		if artifact.Name == "" {
+			artifact.Name = field
+		}
+		if s, isString := value.(string); isString {
+			artifact.Data = []byte(s)
+			if artifact.ContentType == "" {
+				artifact.ContentType = "text/plain; charset=utf-8"
+			}
+		} else {
+			raw, err := json.Marshal(value)
+			if err != nil {
+				return nil, nil, fmt.Errorf("encode artifact %q: %w", field, err)
+			}
+			artifact.Data = raw
+			if artifact.ContentType == "" {
+				artifact.ContentType = "application/json"
+			}
+		}
+		out[field] = map[string]any{"artifact": artifact.Name}
+		artifacts = append(artifacts, artifact)
+	}
+	return out, artifacts, nil
+}
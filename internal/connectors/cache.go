@@ -0,0 +1,195 @@
+package connectors
+
+import (
+	"container/list"
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"fmt"
+	"slices"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+const DefaultCacheMaxEntries = 1000
+
+// DeterministicCacheTTL is the default cache TTL for actions whose output
+// depends only on their input.
+const DeterministicCacheTTL = 5 * time.Minute
+
+// ResultCache keeps connector action results for identical calls, so that
+// deterministic actions such as JSON transforms and HTTP GETs are not
+// re-executed while a workflow is iterated on. Entries are evicted when they
+// expire or, least recently used first, beyond the size limit.
+type ResultCache struct {
+	maxEntries int
+	now        func() time.Time
+
+	mu      sync.Mutex
+	entries map[string]*list.Element
+	lru     *list.List
+	stats   map[string]*ActionCacheStats
+}
+
+type cacheEntry struct {
+	key       string
+	value     []byte
+	expiresAt time.Time
+}
+
+type ActionCacheStats struct {
+	Connector string `json:"connector"`
+	Action    string `json:"action"`
+	Hits      int64  `json:"hits"`
+	Misses    int64  `json:"misses"`
+}
+
+type CacheStats struct {
+	Entries int                `json:"entries"`
+	Hits    int64              `json:"hits"`
+	Misses  int64              `json:"misses"`
+	Actions []ActionCacheStats `json:"actions"`
+}
+
+func NewResultCache(maxEntries int) *ResultCache {
+	if maxEntries <= 0 {
+		maxEntries = DefaultCacheMaxEntries
+	}
+	return &ResultCache{
+		maxEntries: maxEntries,
+		now:        time.Now,
+		entries:    map[string]*list.Element{},
+		lru:        list.New(),
+		stats:      map[string]*ActionCacheStats{},
+	}
+}
+
+// CacheKey identifies a call by tenant, action, auth and canonicalised input.
+// The case and step ids the executor adds to the input are left out, so the
+// same call from another case or step shares the entry.
+func CacheKey(tenantID uuid.UUID, connector, action string, auth map[string]string, input map[string]any) (string, error) {
+	canonical := make(map[string]any, len(input))
+	for k, v := range input {
+		if k == "_case_id" || k == "_step_id" {
+			continue
+		}
+		canonical[k] = v
+	}
+	// encoding/json writes map keys in sorted order, which makes the
+	// encoding canonical.
+	raw, err := json.Marshal(struct {
+		TenantID  uuid.UUID         `json:"tenant_id"`
+		Connector string            `json:"connector"`
+		Action    string            `json:"action"`
+		Auth      map[string]string `json:"auth"`
+		Input     map[string]any    `json:"input"`
+	}{tenantID, connector, action, auth, canonical})
+	if err != nil {
+		return "", fmt.Errorf("encode cache key: %w", err)
+	}
+	sum := sha256.Sum256(raw)
+	return hex.EncodeToString(sum[:]), nil
+}
+
+// Get returns a copy of the cached result for key and counts a hit or miss
+// against the action.
+func (c *ResultCache) Get(connector, action, key string) (map[string]any, bool) {
+	c.mu.Lock()
+	stats := c.actionStats(connector, action)
+	el, ok := c.entries[key]
+	if ok && !c.now().Before(el.Value.(*cacheEntry).expiresAt) {
+		c.removeElement(el)
+		ok = false
+	}
+	if !ok {
+		stats.Misses++
+		c.mu.Unlock()
+		return nil, false
+	}
+	stats.Hits++
+	c.lru.MoveToFront(el)
+	raw := el.Value.(*cacheEntry).value
+	c.mu.Unlock()
+
+	var out map[string]any
+	if err := json.Unmarshal(raw, &out); err != nil {
+		return nil, false
+	}
+	return out, true
+}
+
+// Set caches value under key for ttl. Values that cannot be encoded are not
+// cached.
+func (c *ResultCache) Set(key string, value map[string]any, ttl time.Duration) {
+	if ttl <= 0 {
+		return
+	}
+	raw, err := json.Marshal(value)
+	if err != nil {
+		return
+	}
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	entry := &cacheEntry{key: key, value: raw, expiresAt: c.now().Add(ttl)}
+	if el, ok := c.entries[key]; ok {
+		el.Value = entry
+		c.lru.MoveToFront(el)
+		return
+	}
+	c.entries[key] = c.lru.PushFront(entry)
+	for c.lru.Len() > c.maxEntries {
+		c.removeElement(c.lru.Back())
+	}
+}
+
+func (c *ResultCache) Stats() CacheStats {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	out := CacheStats{Entries: c.lru.Len(), Actions: make([]ActionCacheStats, 0, len(c.stats))}
+	for _, s := range c.stats {
+		out.Hits += s.Hits
+		out.Misses += s.Misses
+		out.Actions = append(out.Actions, *s)
+	}
+	slices.SortFunc(out.Actions, func(a, b ActionCacheStats) int {
+		if a.Connector != b.Connector {
+			return strings.Compare(a.Connector, b.Connector)
+		}
+		return strings.Compare(a.Action, b.Action)
+	})
+	return out
+}
+
+func (c *ResultCache) actionStats(connector, action string) *ActionCacheStats {
+	id := connector + "/" + action
+	s, ok := c.stats[id]
+	if !ok {
+		s = &ActionCacheStats{Connector: connector, Action: action}
+		c.stats[id] = s
+	}
+	return s
+}
+
+func (c *ResultCache) removeElement(el *list.Element) {
+	c.lru.Remove(el)
+	delete(c.entries, el.Value.(*cacheEntry).key)
+}
+
+// cacheTTL is how long a call's result may be cached: the step's
+// cache_ttl_seconds when set, otherwise the action's default. Actions without
+// a default are never cached, and a negative step TTL disables caching.
+func cacheTTL(cfg StepConfig, action ActionSpec, input map[string]any) time.Duration {
+	if action.CacheTTL <= 0 || cfg.CacheTTLSeconds < 0 {
+		return 0
+	}
+	if action.Cacheable != nil && !action.Cacheable(input) {
+		return 0
+	}
+	if cfg.CacheTTLSeconds > 0 {
+		return time.Duration(cfg.CacheTTLSeconds) * time.Second
+	}
+	return action.CacheTTL
+}
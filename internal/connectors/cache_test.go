@@ -0,0 +1,131 @@
+package connectors
+
+import (
+	"context"
+	"encoding/json"
+	"testing"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+type countingConnector struct {
+	testConnector
+	calls int
+}
+
+func (c *countingConnector) Meta() ConnectorMeta { return ConnectorMeta{Key: "counting", Name: "Counting"} }
+
+func (c *countingConnector) Actions() []ActionSpec {
+	execute := func(_ context.Context, _ map[string]string, input map[string]any) (map[string]any, error) {
+		c.calls++
+		return map[string]any{"call": c.calls, "value": input["value"]}, nil
+	}
+	return []ActionSpec{
+		{Key: "pure", Execute: execute, CacheTTL: time.Minute},
+		{Key: "get_only", Execute: execute, CacheTTL: time.Minute, Cacheable: func(input map[string]any) bool { return input["method"] == "GET" }},
+		{Key: "impure", Execute: execute},
+	}
+}
+
+func TestCacheKeyIgnoresCaseAndStepIDs(t *testing.T) {
+	tenantID := uuid.New()
+	a, err := CacheKey(tenantID, "http", "request", nil, map[string]any{"url": "https://x.test", "_case_id": "1", "_step_id": "a"})
+	if err != nil {
+		t.Fatalf("cache key: %v", err)
+	}
+	b, _ := CacheKey(tenantID, "http", "request", nil, map[string]any{"_step_id": "b", "url": "https://x.test", "_case_id": "2"})
+	if a != b {
+		t.Fatal("expected the same key for calls differing only in case and step")
+	}
+	otherTenant, _ := CacheKey(uuid.New(), "http", "request", nil, map[string]any{"url": "https://x.test"})
+	otherAuth, _ := CacheKey(tenantID, "http", "request", map[string]string{"token": "t"}, map[string]any{"url": "https://x.test"})
+	otherInput, _ := CacheKey(tenantID, "http", "request", nil, map[string]any{"url": "https://y.test"})
+	for _, other := range []string{otherTenant, otherAuth, otherInput} {
+		if other == a {
+			t.Fatal("expected a different key when tenant, auth or input differ")
+		}
+	}
+}
+
+func TestResultCacheExpiresAndEvicts(t *testing.T) {
+	now := time.Date(2026, 4, 1, 9, 0, 0, 0, time.UTC)
+	cache := NewResultCache(2)
+	cache.now = func() time.Time { return now }
+
+	cache.Set("a", map[string]any{"v": 1}, time.Minute)
+	cache.Set("b", map[string]any{"v": 2}, time.Hour)
+	if got, ok := cache.Get("c", "x", "a"); !ok || got["v"] != float64(1) {
+		t.Fatalf("expected a cached, got %v %v", got, ok)
+	}
+	got, _ := cache.Get("c", "x", "a")
+	got["v"] = "mutated"
+	if again, _ := cache.Get("c", "x", "a"); again["v"] != float64(1) {
+		t.Fatalf("cached values must not be shared with callers, got %v", again)
+	}
+
+	cache.Set("c", map[string]any{"v": 3}, time.Hour)
+	if _, ok := cache.Get("c", "x", "b"); ok {
+		t.Fatal("expected the least recently used entry to be evicted")
+	}
+	now = now.Add(2 * time.Minute)
+	if _, ok := cache.Get("c", "x", "a"); ok {
+		t.Fatal("expected a to have expired")
+	}
+
+	stats := cache.Stats()
+	if stats.Entries != 1 || stats.Hits != 3 || stats.Misses != 2 || len(stats.Actions) != 1 || stats.Actions[0].Connector != "c" {
+		t.Fatalf("unexpected stats %+v", stats)
+	}
+}
+
+func TestExecutorCachesCacheableActions(t *testing.T) {
+	conn := &countingConnector{}
+	reg := NewRegistry()
+	reg.Register(conn)
+	exec := NewExecutor(nil, reg, nil)
+	cache := NewResultCache(10)
+	exec.SetResultCache(cache)
+	tenantID := uuid.New()
+	run := func(raw string) map[string]any {
+		t.Helper()
+		out, err := exec.TestStep(context.Background(), tenantID, "step", json.RawMessage(raw), map[string]any{})
+		if err != nil || out.Error != "" {
+			t.Fatalf("test step: %v %s", err, out.Error)
+		}
+		return out.Output
+	}
+
+	first := run(`{"connector":"counting","action":"pure","input":{"value":1}}`)
+	second := run(`{"connector":"counting","action":"pure","input":{"value":1}}`)
+	if conn.calls != 1 || first["call"] != 1 || second["call"] != float64(1) {
+		t.Fatalf("expected the second call to be served from cache, calls=%d", conn.calls)
+	}
+	run(`{"connector":"counting","action":"pure","input":{"value":2}}`)
+	if conn.calls != 2 {
+		t.Fatalf("expected a different input to miss, calls=%d", conn.calls)
+	}
+
+	refreshed := run(`{"connector":"counting","action":"pure","no_cache":true,"input":{"value":1}}`)
+	if conn.calls != 3 || refreshed["call"] != 3 {
+		t.Fatalf("expected no_cache to force a call, calls=%d", conn.calls)
+	}
+	if cached := run(`{"connector":"counting","action":"pure","input":{"value":1}}`); cached["call"] != float64(3) {
+		t.Fatalf("expected the forced call to refresh the cache, got %v", cached)
+	}
+
+	run(`{"connector":"counting","action":"pure","cache_ttl_seconds":-1,"input":{"value":9}}`)
+	run(`{"connector":"counting","action":"pure","cache_ttl_seconds":-1,"input":{"value":9}}`)
+	run(`{"connector":"counting","action":"impure","input":{"value":1}}`)
+	run(`{"connector":"counting","action":"impure","input":{"value":1}}`)
+	run(`{"connector":"counting","action":"get_only","input":{"method":"POST"}}`)
+	run(`{"connector":"counting","action":"get_only","input":{"method":"POST"}}`)
+	if conn.calls != 9 {
+		t.Fatalf("expected uncacheable calls to run every time, calls=%d", conn.calls)
+	}
+	run(`{"connector":"counting","action":"get_only","input":{"method":"GET"}}`)
+	run(`{"connector":"counting","action":"get_only","input":{"method":"GET"}}`)
+	if conn.calls != 10 {
+		t.Fatalf("expected a GET to be cached, calls=%d", conn.calls)
+	}
+}
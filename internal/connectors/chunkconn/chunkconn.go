@@ -0,0 +1,247 @@
+package chunkconn
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"strings"
+
+	"github.com/neural-chilli/aceryx/internal/connectors"
+)
+
+// Connector splits arrays into batches for APIs with payload limits and
+// merges per-batch results back into one array. The batches feed a for_each
+// step directly; merge also accepts a for_each step's output.
+type Connector struct{}
+
+func New() *Connector { return &Connector{} }
+
+func (c *Connector) Meta() connectors.ConnectorMeta {
+	return connectors.ConnectorMeta{Key: "chunk", Name: "Chunk", Description: "Split arrays into batches and merge batch results", Version: "v1", Icon: "pi pi-th-large"}
+}
+
+func (c *Connector) Auth() connectors.AuthSpec { return connectors.AuthSpec{Type: "none"} }
+
+func (c *Connector) Triggers() []connectors.TriggerSpec { return nil }
+
+func (c *Connector) Actions() []connectors.ActionSpec {
+	return []connectors.ActionSpec{
+		{
+			Key:         "split",
+			Name:        "Split",
+			Description: "Split an array into batches by item count and/or encoded size",
+			InputSchema: map[string]any{
+				"type":     "object",
+				"required": []string{"items"},
+				"properties": map[string]any{
+					"items":     map[string]any{"type": "array", "description": "The array to split, or a JSON string holding one"},
+					"size":      map[string]any{"type": "integer", "description": "Maximum items per batch"},
+					"max_bytes": map[string]any{"type": "integer", "description": "Maximum JSON-encoded size of a batch in bytes"},
+				},
+			},
+			OutputSchema: map[string]any{"type": "object"},
+			Execute:      c.split,
+			CacheTTL:     connectors.DeterministicCacheTTL,
+		},
+		{
+			Key:         "merge",
+			Name:        "Merge",
+			Description: "Concatenate per-batch results into one array",
+			InputSchema: map[string]any{
+				"type":     "object",
+				"required": []string{"batches"},
+				"properties": map[string]any{
+					"batches": map[string]any{"description": "An array of batch results, or a for_each step's output"},
+					"path":    map[string]any{"type": "string", "description": "Dot path to the array inside each batch result"},
+				},
+			},
+			OutputSchema: map[string]any{"type": "object"},
+			Execute:      c.merge,
+			CacheTTL:     connectors.DeterministicCacheTTL,
+		},
+	}
+}
+
+func (c *Connector) split(_ context.Context, _ map[string]string, input map[string]any) (map[string]any, error) {
+	items, err := readArray(input, "items")
+	if err != nil {
+		return nil, err
+	}
+	size := readInt(input, "size", 0)
+	maxBytes := readInt(input, "max_bytes", 0)
+	if size < 0 || maxBytes < 0 {
+		return nil, fmt.Errorf("size and max_bytes cannot be negative")
+	}
+	if size == 0 && maxBytes == 0 {
+		return nil, fmt.Errorf("size or max_bytes is required")
+	}
+	batches, err := Split(items, size, maxBytes)
+	if err != nil {
+		return nil, err
+	}
+	sizes := make([]int, len(batches))
+	out := make([]any, len(batches))
+	for i, batch := range batches {
+		sizes[i] = len(batch)
+		out[i] = batch
+	}
+	return map[string]any{"batches": out, "count": len(batches), "sizes": sizes, "total": len(items)}, nil
+}
+
+// Split groups items in order. A batch closes when it holds size items or
+// when adding the next item would take its JSON encoding past maxBytes; a
+// zero limit is ignored. An item that alone exceeds maxBytes is an error,
+// since no batch could carry it.
+func Split(items []any, size, maxBytes int) ([][]any, error) {
+	var batches [][]any
+	var current []any
+	currentBytes := 2 // the enclosing brackets
+	for i, item := range items {
+		itemBytes := 0
+		if maxBytes > 0 {
+			raw, err := json.Marshal(item)
+			if err != nil {
+				return nil, fmt.Errorf("encode item %d: %w", i, err)
+			}
+			itemBytes = len(raw)
+			if itemBytes+2 > maxBytes {
+				return nil, fmt.Errorf("item %d is %d bytes, larger than max_bytes %d", i, itemBytes, maxBytes)
+			}
+		}
+		added := itemBytes
+		if len(current) > 0 {
+			added++ // the separating comma
+		}
+		full := (size > 0 && len(current) >= size) || (maxBytes > 0 && currentBytes+added > maxBytes)
+		if full && len(current) > 0 {
+			batches = append(batches, current)
+			current, currentBytes, added = nil, 2, itemBytes
+		}
+		current = append(current, item)
+		currentBytes += added
+	}
+	if len(current) > 0 {
+		batches = append(batches, current)
+	}
+	return batches, nil
+}
+
+// merge flattens one level: each batch result (or the array at path inside
+// it) contributes its elements, and non-array results are kept as single
+// elements. for_each output is unwrapped to its item outputs first.
+func (c *Connector) merge(_ context.Context, _ map[string]string, input map[string]any) (map[string]any, error) {
+	raw, ok := input["batches"]
+	if !ok || raw == nil {
+		return nil, fmt.Errorf("batches is required")
+	}
+	raw = decodeJSONString(raw)
+	if fanned, ok := raw.(map[string]any); ok {
+		items, isForEach := fanned["items"].([]any)
+		if !isForEach {
+			return nil, fmt.Errorf("batches must be an array or a for_each output")
+		}
+		outputs := make([]any, 0, len(items))
+		for _, item := range items {
+			if entry, ok := item.(map[string]any); ok {
+				if output, ok := entry["output"]; ok {
+					outputs = append(outputs, output)
+					continue
+				}
+			}
+			outputs = append(outputs, item)
+		}
+		raw = outputs
+	}
+	batches, ok := raw.([]any)
+	if !ok {
+		return nil, fmt.Errorf("batches must be an array or a for_each output")
+	}
+	path := strings.TrimSpace(readString(input, "path", ""))
+	merged := make([]any, 0, len(batches))
+	for i, batch := range batches {
+		batch = decodeJSONString(batch)
+		if path != "" {
+			value, found := lookupPath(batch, path)
+			if !found {
+				return nil, fmt.Errorf("batch %d has no value at %q", i, path)
+			}
+			batch = decodeJSONString(value)
+		}
+		if list, ok := batch.([]any); ok {
+			merged = append(merged, list...)
+			continue
+		}
+		if batch != nil {
+			merged = append(merged, batch)
+		}
+	}
+	return map[string]any{"items": merged, "count": len(merged)}, nil
+}
+
+func lookupPath(value any, path string) (any, bool) {
+	current := value
+	for _, part := range strings.Split(path, ".") {
+		obj, ok := current.(map[string]any)
+		if !ok {
+			return nil, false
+		}
+		if current, ok = obj[part]; !ok {
+			return nil, false
+		}
+	}
+	return current, true
+}
+
+// decodeJSONString decodes templated values that arrive as JSON text.
+func decodeJSONString(value any) any {
+	s, ok := value.(string)
+	if !ok {
+		return value
+	}
+	trimmed := strings.TrimSpace(s)
+	if !strings.HasPrefix(trimmed, "[") && !strings.HasPrefix(trimmed, "{") {
+		return value
+	}
+	var decoded any
+	if err := json.Unmarshal([]byte(trimmed), &decoded); err != nil {
+		return value
+	}
+	return decoded
+}
+
+func readArray(input map[string]any, key string) ([]any, error) {
+	raw, ok := input[key]
+	if !ok || raw == nil {
+		return nil, fmt.Errorf("%s is required", key)
+	}
+	items, ok := decodeJSONString(raw).([]any)
+	if !ok {
+		return nil, fmt.Errorf("%s must be an array", key)
+	}
+	return items, nil
+}
+
+func readString(input map[string]any, key string, fallback string) string {
+	raw, ok := input[key]
+	if !ok || raw == nil {
+		return fallback
+	}
+	if s, ok := raw.(string); ok {
+		return s
+	}
+	return fallback
+}
+
+func readInt(input map[string]any, key string, fallback int) int {
+	raw, ok := input[key]
+	if !ok || raw == nil {
+		return fallback
+	}
+	switch v := raw.(type) {
+	case int:
+		return v
+	case float64:
+		return int(v)
+	}
+	return fallback
+}
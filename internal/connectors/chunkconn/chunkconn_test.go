@@ -0,0 +1,82 @@
+package chunkconn
+
+import (
+	"context"
+	"encoding/json"
+	"reflect"
+	"testing"
+)
+
+func TestSplitByCountAndBytes(t *testing.T) {
+	items := []any{1.0, 2.0, 3.0, 4.0, 5.0}
+	batches, err := Split(items, 2, 0)
+	if err != nil {
+		t.Fatalf("split: %v", err)
+	}
+	if want := [][]any{{1.0, 2.0}, {3.0, 4.0}, {5.0}}; !reflect.DeepEqual(batches, want) {
+		t.Fatalf("batches = %v, want %v", batches, want)
+	}
+
+	// "[1,2]" is 5 bytes, so each batch holds two single-digit numbers.
+	batches, err = Split(items, 0, 5)
+	if err != nil {
+		t.Fatalf("split by bytes: %v", err)
+	}
+	for i, batch := range batches {
+		raw, _ := json.Marshal(batch)
+		if len(raw) > 5 {
+			t.Fatalf("batch %d is %d bytes: %s", i, len(raw), raw)
+		}
+	}
+	if len(batches) != 3 {
+		t.Fatalf("expected 3 batches, got %v", batches)
+	}
+
+	batches, err = Split(items, 4, 5)
+	if err != nil || len(batches) != 3 {
+		t.Fatalf("the tighter limit should win, got %v (%v)", batches, err)
+	}
+	if _, err := Split([]any{"far too long for the limit"}, 0, 10); err == nil {
+		t.Fatal("expected an oversized item to be rejected")
+	}
+}
+
+func TestSplitAction(t *testing.T) {
+	c := New()
+	out, err := c.split(context.Background(), nil, map[string]any{"items": `[{"id":1},{"id":2},{"id":3}]`, "size": 2.0})
+	if err != nil {
+		t.Fatalf("split: %v", err)
+	}
+	if out["count"] != 2 || out["total"] != 3 || !reflect.DeepEqual(out["sizes"], []int{2, 1}) {
+		t.Fatalf("unexpected split output %#v", out)
+	}
+	if _, err := c.split(context.Background(), nil, map[string]any{"items": []any{1.0}}); err == nil {
+		t.Fatal("expected a missing limit to be rejected")
+	}
+	if _, err := c.split(context.Background(), nil, map[string]any{"items": "nope", "size": 1.0}); err == nil {
+		t.Fatal("expected non-array items to be rejected")
+	}
+}
+
+func TestMergeAcceptsArraysAndForEachOutput(t *testing.T) {
+	c := New()
+	out, err := c.merge(context.Background(), nil, map[string]any{"batches": []any{[]any{1.0, 2.0}, []any{3.0}, "x"}})
+	if err != nil {
+		t.Fatalf("merge: %v", err)
+	}
+	if !reflect.DeepEqual(out["items"], []any{1.0, 2.0, 3.0, "x"}) {
+		t.Fatalf("unexpected merge output %#v", out)
+	}
+
+	forEach := `{"items":[{"index":0,"output":{"body":{"results":[{"ok":true}]}}},{"index":1,"output":{"body":{"results":[{"ok":false},{"ok":true}]}}}]}`
+	out, err = c.merge(context.Background(), nil, map[string]any{"batches": forEach, "path": "body.results"})
+	if err != nil {
+		t.Fatalf("merge for_each: %v", err)
+	}
+	if out["count"] != 3 {
+		t.Fatalf("expected 3 merged results, got %#v", out)
+	}
+	if _, err := c.merge(context.Background(), nil, map[string]any{"batches": forEach, "path": "body.missing"}); err == nil {
+		t.Fatal("expected a missing path to be rejected")
+	}
+}
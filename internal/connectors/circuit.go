@@ -0,0 +1,127 @@
+package connectors
+
+import (
+	"context"
+	"errors"
+	"sort"
+	"sync"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+const (
+	// DefaultCircuitFailures is how many consecutive failed calls open a
+	// connector's circuit.
+	DefaultCircuitFailures = 5
+	// DefaultCircuitCoolDown is how long an open circuit refuses calls before
+	// it lets a probe call through.
+	DefaultCircuitCoolDown = time.Minute
+)
+
+// ErrCircuitOpen is returned for calls refused without reaching the
+// downstream system because its recent calls failed.
+var ErrCircuitOpen = errors.New("circuit open — recent calls failed")
+
+// Circuits keeps a circuit breaker per tenant and connector, so a
+// misbehaving downstream system fails its steps fast instead of holding each
+// one for the full timeout. A circuit opens after a number of consecutive
+// failed calls. Once the cool-down has passed it is half-open: one probe call
+// is let through, and its result closes the circuit or opens it again.
+type Circuits struct {
+	failures int
+	coolDown time.Duration
+	now      func() time.Time
+
+	mu       sync.Mutex
+	circuits map[circuitKey]*circuit
+}
+
+type circuitKey struct {
+	tenantID  uuid.UUID
+	connector string
+}
+
+type circuit struct {
+	failures int
+	openedAt time.Time
+	probing  bool
+}
+
+func NewCircuits(failures int, coolDown time.Duration) *Circuits {
+	if failures <= 0 {
+		failures = DefaultCircuitFailures
+	}
+	if coolDown <= 0 {
+		coolDown = DefaultCircuitCoolDown
+	}
+	return &Circuits{failures: failures, coolDown: coolDown, now: time.Now, circuits: map[circuitKey]*circuit{}}
+}
+
+// allow returns ErrCircuitOpen when a call to connector should be refused.
+func (c *Circuits) allow(tenantID uuid.UUID, connector string) error {
+	if c == nil {
+		return nil
+	}
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	state := c.circuits[circuitKey{tenantID, connector}]
+	if state == nil || state.failures < c.failures {
+		return nil
+	}
+	if state.probing || c.now().Sub(state.openedAt) < c.coolDown {
+		return ErrCircuitOpen
+	}
+	state.probing = true
+	return nil
+}
+
+// record updates the circuit with the result of a call allow let through.
+// Calls cancelled by their caller say nothing about the downstream system.
+func (c *Circuits) record(tenantID uuid.UUID, connector string, err error) {
+	if c == nil {
+		return
+	}
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	key := circuitKey{tenantID, connector}
+	state := c.circuits[key]
+	switch {
+	case err == nil:
+		delete(c.circuits, key)
+	case errors.Is(err, context.Canceled):
+		if state != nil {
+			state.probing = false
+		}
+	default:
+		if state == nil {
+			state = &circuit{}
+			c.circuits[key] = state
+		}
+		state.failures++
+		state.probing = false
+		if state.failures >= c.failures {
+			state.openedAt = c.now()
+		}
+	}
+}
+
+// Open lists the connectors whose circuit is open for at least one tenant.
+func (c *Circuits) Open() []string {
+	if c == nil {
+		return nil
+	}
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	seen := map[string]bool{}
+	out := make([]string, 0)
+	for key, state := range c.circuits {
+		if state.failures < c.failures || seen[key.connector] {
+			continue
+		}
+		seen[key.connector] = true
+		out = append(out, key.connector)
+	}
+	sort.Strings(out)
+	return out
+}
@@ -0,0 +1,66 @@
+package connectors
+
+import (
+	"context"
+	"errors"
+	"testing"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+func TestCircuits_OpenAndProbe(t *testing.T) {
+	now := time.Now()
+	circuits := NewCircuits(2, time.Minute)
+	circuits.now = func() time.Time { return now }
+	tenantID := uuid.New()
+	downstream := errors.New("503")
+
+	circuits.record(tenantID, "crm", downstream)
+	if err := circuits.allow(tenantID, "crm"); err != nil {
+		t.Fatalf("circuit opened after one failure: %v", err)
+	}
+	circuits.record(tenantID, "crm", downstream)
+	if err := circuits.allow(tenantID, "crm"); !errors.Is(err, ErrCircuitOpen) {
+		t.Fatalf("expected open circuit, got %v", err)
+	}
+	if err := circuits.allow(uuid.New(), "crm"); err != nil {
+		t.Fatalf("another tenant's circuit is open: %v", err)
+	}
+	if open := circuits.Open(); len(open) != 1 || open[0] != "crm" {
+		t.Fatalf("unexpected open circuits %v", open)
+	}
+
+	now = now.Add(time.Minute)
+	if err := circuits.allow(tenantID, "crm"); err != nil {
+		t.Fatalf("expected a probe after the cool-down, got %v", err)
+	}
+	if err := circuits.allow(tenantID, "crm"); !errors.Is(err, ErrCircuitOpen) {
+		t.Fatalf("expected a single probe, got %v", err)
+	}
+	circuits.record(tenantID, "crm", downstream)
+	if err := circuits.allow(tenantID, "crm"); !errors.Is(err, ErrCircuitOpen) {
+		t.Fatalf("expected a failed probe to reopen the circuit, got %v", err)
+	}
+
+	now = now.Add(time.Minute)
+	if err := circuits.allow(tenantID, "crm"); err != nil {
+		t.Fatalf("expected a probe, got %v", err)
+	}
+	circuits.record(tenantID, "crm", nil)
+	if err := circuits.allow(tenantID, "crm"); err != nil {
+		t.Fatalf("expected a successful probe to close the circuit, got %v", err)
+	}
+	if open := circuits.Open(); len(open) != 0 {
+		t.Fatalf("unexpected open circuits %v", open)
+	}
+}
+
+func TestCircuits_IgnoresCancelledCalls(t *testing.T) {
+	circuits := NewCircuits(1, time.Minute)
+	tenantID := uuid.New()
+	circuits.record(tenantID, "crm", context.Canceled)
+	if err := circuits.allow(tenantID, "crm"); err != nil {
+		t.Fatalf("cancelled call opened the circuit: %v", err)
+	}
+}
@@ -0,0 +1,147 @@
+package connectors
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"net"
+	"net/http"
+	"net/netip"
+	"net/url"
+	"strings"
+	"syscall"
+	"time"
+)
+
+// ErrEgressBlocked is returned when an outbound request targets a destination
+// the egress policy does not permit.
+var ErrEgressBlocked = errors.New("egress blocked")
+
+var extraBlockedRanges = []netip.Prefix{
+	netip.MustParsePrefix("0.0.0.0/8"),
+	netip.MustParsePrefix("100.64.0.0/10"),
+}
+
+// EgressPolicy restricts where connectors may send requests. By default
+// loopback, private, link-local (including cloud metadata endpoints) and
+// multicast addresses are refused. AllowedDomains, when set, limits requests
+// to those hosts and their subdomains. AllowPrivate lifts the address check
+// for trusted deployments that call internal services.
+type EgressPolicy struct {
+	AllowPrivate   bool
+	AllowedDomains []string
+}
+
+// CheckURL validates a URL without resolving it: the scheme must be http or
+// https, the host must be allow-listed when a list is set, and a literal IP
+// host must not be in a blocked range. Hostnames are checked again once
+// resolved, at connect time.
+func (p EgressPolicy) CheckURL(rawURL string) error {
+	parsed, err := url.Parse(rawURL)
+	if err != nil {
+		return fmt.Errorf("invalid url: %w", err)
+	}
+	if scheme := strings.ToLower(parsed.Scheme); scheme != "http" && scheme != "https" {
+		return fmt.Errorf("%w: unsupported scheme %q", ErrEgressBlocked, parsed.Scheme)
+	}
+	if parsed.Hostname() == "" {
+		return fmt.Errorf("invalid url: missing host")
+	}
+	return p.CheckHost(parsed.Hostname())
+}
+
+// CheckHost validates a host name or literal IP the way CheckURL validates
+// a URL's host.
+func (p EgressPolicy) CheckHost(host string) error {
+	host = strings.ToLower(strings.TrimSuffix(host, "."))
+	if !p.domainAllowed(host) {
+		return fmt.Errorf("%w: host %s is not in the allowed domains", ErrEgressBlocked, host)
+	}
+	if addr, err := netip.ParseAddr(host); err == nil {
+		return p.CheckAddr(addr)
+	}
+	return nil
+}
+
+// CheckAddr reports whether a resolved address may be connected to.
+func (p EgressPolicy) CheckAddr(addr netip.Addr) error {
+	if p.AllowPrivate || !isBlockedAddr(addr) {
+		return nil
+	}
+	return fmt.Errorf("%w: %s is a private or reserved address", ErrEgressBlocked, addr)
+}
+
+func (p EgressPolicy) domainAllowed(host string) bool {
+	if len(p.AllowedDomains) == 0 {
+		return true
+	}
+	for _, domain := range p.AllowedDomains {
+		domain = strings.ToLower(strings.TrimPrefix(strings.TrimSpace(domain), "*."))
+		if domain == "" {
+			continue
+		}
+		if host == domain || strings.HasSuffix(host, "."+domain) {
+			return true
+		}
+	}
+	return false
+}
+
+// Client returns an HTTP client that enforces the policy on every
+// connection. Callers streaming bodies should still CheckURL first.
+func (p EgressPolicy) Client(timeout time.Duration) *http.Client {
+	return &http.Client{Timeout: timeout, Transport: p.transport()}
+}
+
+// DialContext connects the way the policy's HTTP clients do, checking the
+// address each socket actually connects to. Clients that are not HTTP, such
+// as gRPC, dial through it; they should CheckHost their target first.
+func (p EgressPolicy) DialContext(ctx context.Context, network, address string) (net.Conn, error) {
+	return p.dialer().DialContext(ctx, network, address)
+}
+
+// transport dials through a Control hook so every connection, including
+// redirects and DNS answers that change between validation and dial, is
+// checked against the address the socket actually connects to.
+func (p EgressPolicy) transport() *http.Transport {
+	transport := http.DefaultTransport.(*http.Transport).Clone()
+	transport.DialContext = p.dialer().DialContext
+	// Through a proxy from HTTP_PROXY and friends only the proxy's address
+	// would be dialled and checked, never the destination's.
+	transport.Proxy = nil
+	// Each request builds its own transport; don't leave idle connections behind.
+	transport.DisableKeepAlives = true
+	return transport
+}
+
+func (p EgressPolicy) dialer() *net.Dialer {
+	return &net.Dialer{
+		Timeout:   30 * time.Second,
+		KeepAlive: 30 * time.Second,
+		Control: func(_, address string, _ syscall.RawConn) error {
+			host, _, err := net.SplitHostPort(address)
+			if err != nil {
+				return err
+			}
+			addr, err := netip.ParseAddr(host)
+			if err != nil {
+				return fmt.Errorf("%w: unresolved address %q", ErrEgressBlocked, host)
+			}
+			return p.CheckAddr(addr)
+		},
+	}
+}
+
+func isBlockedAddr(addr netip.Addr) bool {
+	addr = addr.Unmap()
+	if addr.IsLoopback() || addr.IsPrivate() || addr.IsLinkLocalUnicast() || addr.IsLinkLocalMulticast() ||
+		addr.IsInterfaceLocalMulticast() || addr.IsMulticast() || addr.IsUnspecified() {
+		return true
+	}
+	for _, prefix := range extraBlockedRanges {
+		if prefix.Contains(addr) {
+			return true
+		}
+	}
+	return false
+}
@@ -0,0 +1,124 @@
+package connectors
+
+import (
+	"context"
+	"errors"
+	"net/http"
+	"net/http/httptest"
+	"net/netip"
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestEgressPolicyCheckURL(t *testing.T) {
+	policy := EgressPolicy{}
+	for _, raw := range []string{
+		"http://169.254.169.254/latest/meta-data/",
+		"http://127.0.0.1:8080/",
+		"http://10.1.2.3/",
+		"http://[::1]/",
+		"http://[fd00::1]/",
+		"http://[::ffff:192.168.1.1]/",
+		"http://100.64.0.1/",
+		"file:///etc/passwd",
+	} {
+		if err := policy.CheckURL(raw); !errors.Is(err, ErrEgressBlocked) {
+			t.Errorf("CheckURL(%q) = %v, want ErrEgressBlocked", raw, err)
+		}
+	}
+	for _, raw := range []string{"https://api.example.com/v1", "http://8.8.8.8/"} {
+		if err := policy.CheckURL(raw); err != nil {
+			t.Errorf("CheckURL(%q) = %v", raw, err)
+		}
+	}
+	if err := (EgressPolicy{AllowPrivate: true}).CheckURL("http://10.1.2.3/"); err != nil {
+		t.Fatalf("allow private: %v", err)
+	}
+}
+
+func TestEgressPolicyAllowedDomains(t *testing.T) {
+	policy := EgressPolicy{AllowedDomains: []string{"example.com", "*.partner.io"}}
+	for _, raw := range []string{"https://example.com/", "https://api.example.com/", "https://x.partner.io/"} {
+		if err := policy.CheckURL(raw); err != nil {
+			t.Errorf("CheckURL(%q) = %v", raw, err)
+		}
+	}
+	for _, raw := range []string{"https://notexample.com/", "https://example.com.evil.net/", "https://8.8.8.8/"} {
+		if err := policy.CheckURL(raw); !errors.Is(err, ErrEgressBlocked) {
+			t.Errorf("CheckURL(%q) = %v, want ErrEgressBlocked", raw, err)
+		}
+	}
+}
+
+func TestEgressPolicyCheckAddr(t *testing.T) {
+	if err := (EgressPolicy{}).CheckAddr(netip.MustParseAddr("93.184.216.34")); err != nil {
+		t.Fatalf("public address: %v", err)
+	}
+	if err := (EgressPolicy{}).CheckAddr(netip.MustParseAddr("fe80::1")); err == nil {
+		t.Fatal("expected link-local address to be blocked")
+	}
+}
+
+func TestDoJSONRequestEgressBlockedAtConnect(t *testing.T) {
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+		_, _ = w.Write([]byte(`{}`))
+	}))
+	defer srv.Close()
+
+	// A hostname passes validation; the loopback address it resolves to is
+	// caught when dialing.
+	target := strings.Replace(srv.URL, "127.0.0.1", "localhost", 1)
+	_, _, _, err := DoJSONRequestWithOptions(context.Background(), http.MethodGet, target, nil, nil, RequestOptions{
+		MaxRetries: 2,
+		Egress:     &EgressPolicy{},
+	})
+	if !errors.Is(err, ErrEgressBlocked) {
+		t.Fatalf("expected ErrEgressBlocked, got %v", err)
+	}
+
+	status, _, _, err := DoJSONRequestWithOptions(context.Background(), http.MethodGet, target, nil, nil, RequestOptions{
+		Egress: &EgressPolicy{AllowPrivate: true},
+	})
+	if err != nil || status != http.StatusOK {
+		t.Fatalf("allow private: status=%d err=%v", status, err)
+	}
+}
+
+func TestDoJSONRequestEgressChecksRedirects(t *testing.T) {
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		http.Redirect(w, r, "http://169.254.169.254/latest/meta-data/", http.StatusFound)
+	}))
+	defer srv.Close()
+
+	_, _, _, err := DoJSONRequestWithOptions(context.Background(), http.MethodGet, srv.URL, nil, nil, RequestOptions{
+		Egress: &EgressPolicy{AllowedDomains: []string{"127.0.0.1"}, AllowPrivate: true},
+	})
+	if !errors.Is(err, ErrEgressBlocked) {
+		t.Fatalf("expected redirect off the allow-list to be blocked, got %v", err)
+	}
+}
+
+func TestEgressClientIgnoresProxyEnvironment(t *testing.T) {
+	proxied := false
+	proxy := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+		proxied = true
+		_, _ = w.Write([]byte(`{}`))
+	}))
+	defer proxy.Close()
+	t.Setenv("HTTP_PROXY", proxy.URL)
+	t.Setenv("HTTPS_PROXY", proxy.URL)
+
+	policy := EgressPolicy{AllowPrivate: true}
+	if policy.transport().Proxy != nil {
+		t.Fatal("egress transport must not use a proxy")
+	}
+	resp, err := policy.Client(5*time.Second).Get("http://internal.invalid/")
+	if err == nil {
+		_ = resp.Body.Close()
+		t.Fatal("expected the request to dial the destination and fail")
+	}
+	if proxied {
+		t.Fatal("request went through the proxy, bypassing the egress check")
+	}
+}
@@ -5,16 +5,19 @@ import (
 	"context"
 	"embed"
 	"fmt"
+	"html"
 	"html/template"
+	"mime"
 	"mime/multipart"
 	"net/smtp"
 	"net/textproto"
 	"strings"
 
 	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/notify"
 )
 
-//go:embed templates/*.html
+//go:embed templates/*.html templates/*/*.html
 var templateFS embed.FS
 
 type Connector struct{}
@@ -90,10 +93,21 @@ func (c *Connector) send(ctx context.Context, auth map[string]string, input map[
 		data["Body"] = asString(input["body"])
 	}
 
-	htmlBody, err := renderTemplate(templateName, data)
+	locale := asString(input["locale"])
+	if locale == "" {
+		locale = asString(input["default_locale"])
+	}
+	tpl, err := parseTemplate(templateName, locale)
+	if err != nil {
+		return nil, err
+	}
+	htmlBody, err := executeTemplate(tpl, templateName, data)
 	if err != nil {
 		return nil, err
 	}
+	if subject == "" {
+		subject = renderSubject(tpl, data)
+	}
 	plain := stripHTML(htmlBody)
 	message, contentType, err := buildMultipartMessage(auth["from"], to, subject, plain, htmlBody)
 	if err != nil {
@@ -113,15 +127,31 @@ func (c *Connector) send(ctx context.Context, auth map[string]string, input map[
 	return map[string]any{"to": to, "subject": subject, "content_type": contentType}, nil
 }
 
-func renderTemplate(name string, data map[string]any) (string, error) {
-	files := []string{
-		"templates/_base.html",
-		"templates/" + name + ".html",
-	}
-	tpl, err := template.ParseFS(templateFS, files...)
+var templates = notify.NewEmailTemplates(templateFS, "templates")
+
+// parseTemplate resolves the layout and content templates along the locale
+// fallback chain ("fr-CA" -> "fr" -> default), each independently.
+func parseTemplate(name string, locale string) (*template.Template, error) {
+	tpl, err := templates.Parse(name, locale)
 	if err != nil {
-		return "", fmt.Errorf("parse email template %s: %w", name, err)
+		return nil, fmt.Errorf("parse email template %s: %w", name, err)
 	}
+	return tpl, nil
+}
+
+func renderSubject(tpl *template.Template, data map[string]any) string {
+	subjectTpl := tpl.Lookup("subject")
+	if subjectTpl == nil {
+		return ""
+	}
+	var out bytes.Buffer
+	if err := subjectTpl.Execute(&out, data); err != nil {
+		return ""
+	}
+	return strings.TrimSpace(html.UnescapeString(out.String()))
+}
+
+func executeTemplate(tpl *template.Template, name string, data map[string]any) (string, error) {
 	var out bytes.Buffer
 	if err := tpl.Execute(&out, data); err != nil {
 		return "", fmt.Errorf("execute email template %s: %w", name, err)
@@ -133,7 +163,7 @@ func buildMultipartMessage(from string, to string, subject string, plain string,
 	var body bytes.Buffer
 	writer := multipart.NewWriter(&body)
 	boundary := writer.Boundary()
-	headers := fmt.Sprintf("From: %s\r\nTo: %s\r\nSubject: %s\r\nMIME-Version: 1.0\r\nContent-Type: multipart/alternative; boundary=%s\r\n\r\n", from, to, subject, boundary)
+	headers := fmt.Sprintf("From: %s\r\nTo: %s\r\nSubject: %s\r\nMIME-Version: 1.0\r\nContent-Type: multipart/alternative; boundary=%s\r\n\r\n", from, to, mime.QEncoding.Encode("utf-8", subject), boundary)
 
 	partText, err := writer.CreatePart(mapToMIMEHeader(map[string]string{"Content-Type": "text/plain; charset=UTF-8"}))
 	if err != nil {
@@ -7,67 +7,221 @@ import (
 	"errors"
 	"fmt"
 	"log/slog"
+	"strings"
 	"time"
 
 	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/chaos"
 	"github.com/neural-chilli/aceryx/internal/engine"
+	"github.com/neural-chilli/aceryx/internal/mocktools"
 	"github.com/neural-chilli/aceryx/internal/observability"
+	"github.com/neural-chilli/aceryx/internal/redaction"
 )
 
 type Executor struct {
 	db       *sql.DB
 	registry *Registry
 	secrets  SecretStore
+	cache    *ResultCache
+	circuits *Circuits
+	mocks    *mocktools.MockProtocol
 }
 
+// StepConfig is an integration step's config. CacheTTLSeconds overrides the
+// action's cache TTL, and a negative value disables caching for the step.
+// NoCache forces a fresh call whose result replaces any cached one.
+// Artifacts names output fields to store as artifacts instead of inline.
+// SensitiveOutput keeps the output out of the step record, as the action's
+// own flag does. Credentials names the secret profile auth fields the step
+// leaves unset are read from; see CredentialSecretKey.
 type StepConfig struct {
-	Connector      string            `json:"connector"`
-	Action         string            `json:"action"`
-	Auth           map[string]string `json:"auth"`
-	Input          map[string]any    `json:"input"`
-	TimeoutSeconds int               `json:"timeout_seconds"`
+	Connector       string                  `json:"connector"`
+	Action          string                  `json:"action"`
+	Auth            map[string]string       `json:"auth"`
+	Credentials     string                  `json:"credentials"`
+	Input           map[string]any          `json:"input"`
+	TimeoutSeconds  int                     `json:"timeout_seconds"`
+	CacheTTLSeconds int                     `json:"cache_ttl_seconds"`
+	NoCache         bool                    `json:"no_cache"`
+	Artifacts       map[string]StepArtifact `json:"artifacts"`
+	SensitiveOutput bool                    `json:"sensitive_output"`
 }
 
 func NewExecutor(db *sql.DB, registry *Registry, secrets SecretStore) *Executor {
 	return &Executor{db: db, registry: registry, secrets: secrets}
 }
 
+// SetResultCache enables result caching for cacheable actions.
+func (e *Executor) SetResultCache(cache *ResultCache) {
+	e.cache = cache
+}
+
+// SetCircuits enables a circuit breaker per tenant and connector.
+func (e *Executor) SetCircuits(circuits *Circuits) {
+	e.circuits = circuits
+}
+
+// SetMockProtocol answers calls made under a mock profile from the profile
+// instead of the connector.
+func (e *Executor) SetMockProtocol(mocks *mocktools.MockProtocol) {
+	e.mocks = mocks
+}
+
+// TestResult is the outcome of running a single integration step outside a
+// case: the action input after template resolution and the action's output.
+type TestResult struct {
+	Input      map[string]any `json:"input"`
+	Output     map[string]any `json:"output,omitempty"`
+	Error      string         `json:"error,omitempty"`
+	DurationMS int64          `json:"duration_ms"`
+}
+
 func (e *Executor) Execute(ctx context.Context, caseID uuid.UUID, stepID string, raw json.RawMessage) (*engine.StepResult, error) {
+	cfg, action, err := e.parseStep(raw)
+	if err != nil {
+		return nil, err
+	}
+
+	caseCtx, tenantID, err := e.loadCaseContext(ctx, caseID)
+	if err != nil {
+		return nil, err
+	}
+	engine.AddForEachItem(ctx, caseCtx)
+
+	resolvedAuth, resolvedInput, err := e.resolveInput(ctx, tenantID, cfg, caseCtx)
+	if err != nil {
+		return nil, err
+	}
+	resolvedInput["_case_id"] = caseID.String()
+	resolvedInput["_step_id"] = stepID
+	resolvedInput["_tenant_id"] = tenantID.String()
+	if include, _ := resolvedInput["include_summary"].(bool); include {
+		resolvedInput["_execution_summary"] = caseCtx["execution"]
+	}
+
+	result, err := e.invoke(ctx, tenantID, caseID, stepID, cfg, action, resolvedAuth, resolvedInput)
+	if err != nil {
+		return nil, err
+	}
+	result, artifacts, err := extractArtifacts(result, cfg.Artifacts)
+	if err != nil {
+		return nil, err
+	}
+	payload, err := json.Marshal(result)
+	if err != nil {
+		return nil, fmt.Errorf("marshal connector action result: %w", err)
+	}
+	return &engine.StepResult{Output: payload, Artifacts: artifacts, Sensitive: cfg.SensitiveOutput || action.SensitiveOutput}, nil
+}
+
+// TestStep runs an integration step against caller-supplied template context
+// instead of a stored case. It powers the designer's "test this step" action,
+// so the connector is really called; action failures are reported in the
+// result rather than returned as an error.
+func (e *Executor) TestStep(ctx context.Context, tenantID uuid.UUID, stepID string, raw json.RawMessage, caseCtx map[string]any) (TestResult, error) {
+	cfg, action, err := e.parseStep(raw)
+	if err != nil {
+		return TestResult{}, err
+	}
+	if e.secrets != nil {
+		caseCtx["__secret_resolver"] = func(key string) string {
+			v, gerr := e.secrets.Get(ctx, tenantID, key)
+			if gerr != nil {
+				return ""
+			}
+			return v
+		}
+	}
+
+	resolvedAuth, resolvedInput, err := e.resolveInput(ctx, tenantID, cfg, caseCtx)
+	out := TestResult{Input: e.reportedInput(cfg, caseCtx)}
+	if err != nil {
+		out.Error = err.Error()
+		return out, nil
+	}
+	resolvedInput["_step_id"] = stepID
+	resolvedInput["_tenant_id"] = tenantID.String()
+
 	start := time.Now()
+	result, err := e.invoke(ctx, tenantID, uuid.Nil, stepID, cfg, action, resolvedAuth, resolvedInput)
+	out.DurationMS = time.Since(start).Milliseconds()
+	if err != nil {
+		out.Error = err.Error()
+		return out, nil
+	}
+	out.Output = result
+	return out, nil
+}
+
+// reportedInput renders the step's input for a TestResult. Secret
+// references are masked rather than resolved, since the result is returned
+// to the caller.
+func (e *Executor) reportedInput(cfg StepConfig, caseCtx map[string]any) map[string]any {
+	masked := make(map[string]any, len(caseCtx)+1)
+	for k, v := range caseCtx {
+		masked[k] = v
+	}
+	masked["__secret_resolver"] = func(string) string { return redaction.Mask }
+	input, _ := ResolveTemplateAny(cfg.Input, masked).(map[string]any)
+	if input == nil {
+		input = map[string]any{}
+	}
+	if action, ok := e.registry.GetAction(cfg.Connector, cfg.Action); ok {
+		for _, key := range action.RawInputKeys {
+			if raw, exists := cfg.Input[key]; exists {
+				input[key] = raw
+			}
+		}
+	}
+	return input
+}
+
+func (e *Executor) parseStep(raw json.RawMessage) (StepConfig, ActionSpec, error) {
 	cfg := StepConfig{}
 	if err := json.Unmarshal(raw, &cfg); err != nil {
-		return nil, fmt.Errorf("parse integration step config: %w", err)
+		return StepConfig{}, ActionSpec{}, fmt.Errorf("parse integration step config: %w", err)
 	}
 	if cfg.Connector == "" || cfg.Action == "" {
-		return nil, errors.New("connector and action are required")
+		return StepConfig{}, ActionSpec{}, errors.New("connector and action are required")
 	}
 
 	action, ok := e.registry.GetAction(cfg.Connector, cfg.Action)
 	if !ok {
-		return nil, fmt.Errorf("connector action not found: %s/%s", cfg.Connector, cfg.Action)
-	}
-
-	caseCtx, tenantID, err := e.loadCaseContext(ctx, caseID)
-	if err != nil {
-		return nil, err
+		return StepConfig{}, ActionSpec{}, fmt.Errorf("connector action not found: %s/%s", cfg.Connector, cfg.Action)
 	}
+	return cfg, action, nil
+}
 
+// resolveInput renders the step's auth and input templates. Auth fields the
+// step leaves unset fall back to tenant secrets: the step's credential
+// profile when it names one, else the secret named after the field.
+func (e *Executor) resolveInput(ctx context.Context, tenantID uuid.UUID, cfg StepConfig, caseCtx map[string]any) (map[string]string, map[string]any, error) {
 	resolvedAuth := make(map[string]string, len(cfg.Auth))
 	for k, v := range cfg.Auth {
 		resolvedAuth[k] = ResolveTemplateString(v, caseCtx)
 	}
 
+	profile := strings.TrimSpace(ResolveTemplateString(cfg.Credentials, caseCtx))
 	if connector, ok := e.registry.Get(cfg.Connector); ok {
 		for _, field := range connector.Auth().Fields {
 			if _, exists := resolvedAuth[field.Key]; exists && resolvedAuth[field.Key] != "" {
 				continue
 			}
-			if e.secrets == nil {
-				continue
+			key := field.Key
+			if profile != "" {
+				key = CredentialSecretKey(profile, field.Key)
 			}
-			value, gerr := e.secrets.Get(ctx, tenantID, field.Key)
-			if gerr == nil && value != "" {
+			var value string
+			if e.secrets != nil {
+				var gerr error
+				if value, gerr = e.secrets.Get(ctx, tenantID, key); gerr != nil && profile != "" && !errors.Is(gerr, ErrSecretNotFound) {
+					return nil, nil, gerr
+				}
+			}
+			if value != "" {
 				resolvedAuth[field.Key] = value
+			} else if profile != "" && field.Required {
+				return nil, nil, fmt.Errorf("%w: profile %q has no secret %q", ErrCredentialsMissing, profile, key)
 			}
 		}
 	}
@@ -77,10 +231,86 @@ func (e *Executor) Execute(ctx context.Context, caseID uuid.UUID, stepID string,
 	if resolvedInput == nil {
 		resolvedInput = map[string]any{}
 	}
-	resolvedInput["_case_id"] = caseID.String()
-	resolvedInput["_step_id"] = stepID
-	resolvedInput["_tenant_id"] = tenantID.String()
+	if action, ok := e.registry.GetAction(cfg.Connector, cfg.Action); ok {
+		for _, key := range action.RawInputKeys {
+			if raw, exists := cfg.Input[key]; exists {
+				resolvedInput[key] = raw
+			}
+		}
+	}
+	return resolvedAuth, resolvedInput, nil
+}
 
+// MissingCredentials lists the secrets a credential profile lacks for the
+// connector's required auth fields, skipping fields set in auth. Unknown
+// connectors need none.
+func (e *Executor) MissingCredentials(ctx context.Context, tenantID uuid.UUID, connectorKey, profile string, auth map[string]string) ([]string, error) {
+	connector, ok := e.registry.Get(connectorKey)
+	if !ok {
+		return nil, nil
+	}
+	var missing []string
+	for _, field := range connector.Auth().Fields {
+		if !field.Required || strings.TrimSpace(auth[field.Key]) != "" {
+			continue
+		}
+		key := CredentialSecretKey(profile, field.Key)
+		if e.secrets == nil {
+			missing = append(missing, key)
+			continue
+		}
+		value, err := e.secrets.Get(ctx, tenantID, key)
+		if err != nil && !errors.Is(err, ErrSecretNotFound) {
+			return nil, err
+		}
+		if value == "" {
+			missing = append(missing, key)
+		}
+	}
+	return missing, nil
+}
+
+func (e *Executor) invoke(ctx context.Context, tenantID, caseID uuid.UUID, stepID string, cfg StepConfig, action ActionSpec, auth map[string]string, input map[string]any) (map[string]any, error) {
+	toolID := cfg.Connector + "/" + cfg.Action
+	if out, mocked, err := e.mocks.Intercept(ctx, "connector", toolID, input); mocked {
+		if err != nil {
+			return nil, err
+		}
+		result := map[string]any{}
+		if err := json.Unmarshal(out, &result); err != nil {
+			return nil, fmt.Errorf("mock output for connector %s must be a JSON object", toolID)
+		}
+		return result, nil
+	}
+	var cacheKey string
+	ttl := cacheTTL(cfg, action, input)
+	if e.cache != nil && ttl > 0 {
+		key, err := CacheKey(tenantID, cfg.Connector, cfg.Action, auth, input)
+		if err == nil {
+			cacheKey = key
+		}
+	}
+	if cacheKey != "" && !cfg.NoCache {
+		if cached, ok := e.cache.Get(cfg.Connector, cfg.Action, cacheKey); ok {
+			observability.ConnectorCacheTotal.WithLabelValues(tenantID.String(), cfg.Connector, cfg.Action, "hit").Inc()
+			slog.DebugContext(ctx, "connector result served from cache",
+				append(observability.RequestAttrs(ctx),
+					"case_id", caseID.String(),
+					"step_id", stepID,
+					"connector", cfg.Connector,
+					"action", cfg.Action,
+				)...,
+			)
+			return cached, nil
+		}
+		observability.ConnectorCacheTotal.WithLabelValues(tenantID.String(), cfg.Connector, cfg.Action, "miss").Inc()
+	}
+
+	if err := e.circuits.allow(tenantID, cfg.Connector); err != nil {
+		observability.ConnectorCallsTotal.WithLabelValues(tenantID.String(), cfg.Connector, cfg.Action, "circuit_open").Inc()
+		return nil, fmt.Errorf("connector %s: %w", cfg.Connector, err)
+	}
+	start := time.Now()
 	timeout := 30 * time.Second
 	if cfg.TimeoutSeconds > 0 {
 		timeout = time.Duration(cfg.TimeoutSeconds) * time.Second
@@ -88,7 +318,25 @@ func (e *Executor) Execute(ctx context.Context, caseID uuid.UUID, stepID string,
 	actx, cancel := context.WithTimeout(ctx, timeout)
 	defer cancel()
 
-	result, err := action.Execute(actx, resolvedAuth, resolvedInput)
+	actx, endSpan := observability.StartToolSpan(actx, toolID, "connector")
+	var result map[string]any
+	err := chaos.Inject(actx, "connector", toolID)
+	if err == nil {
+		result, err = action.Execute(actx, auth, input)
+	}
+	endSpan(err)
+	e.circuits.record(tenantID, cfg.Connector, err)
+	principalID, _ := uuid.Parse(observability.PrincipalIDFromContext(ctx))
+	e.registry.RecordUsage(ctx, UsageRecord{
+		TenantID:     tenantID,
+		ConnectorKey: cfg.Connector,
+		ActionKey:    cfg.Action,
+		CaseID:       caseID,
+		PrincipalID:  principalID,
+		Duration:     time.Since(start),
+		Success:      err == nil,
+		CalledAt:     start,
+	})
 	if err != nil {
 		status := "error"
 		if errors.Is(err, context.DeadlineExceeded) {
@@ -107,6 +355,9 @@ func (e *Executor) Execute(ctx context.Context, caseID uuid.UUID, stepID string,
 		)
 		return nil, err
 	}
+	if cacheKey != "" {
+		e.cache.Set(cacheKey, result, ttl)
+	}
 	observability.ConnectorCallsTotal.WithLabelValues(tenantID.String(), cfg.Connector, cfg.Action, "success").Inc()
 	observability.ConnectorDurationSeconds.WithLabelValues(tenantID.String(), cfg.Connector).Observe(time.Since(start).Seconds())
 	slog.InfoContext(ctx, "connector call completed",
@@ -118,11 +369,7 @@ func (e *Executor) Execute(ctx context.Context, caseID uuid.UUID, stepID string,
 			"duration_ms", time.Since(start).Milliseconds(),
 		)...,
 	)
-	payload, err := json.Marshal(result)
-	if err != nil {
-		return nil, fmt.Errorf("marshal connector action result: %w", err)
-	}
-	return &engine.StepResult{Output: payload}, nil
+	return result, nil
 }
 
 func (e *Executor) loadCaseContext(ctx context.Context, caseID uuid.UUID) (map[string]any, uuid.UUID, error) {
@@ -156,28 +403,34 @@ WHERE c.id = $1
 	}
 
 	steps := map[string]any{}
+	summary := []any{}
 	rows, err := e.db.QueryContext(ctx, `
-SELECT cs.step_id, COALESCE(cs.result, '{}'::jsonb)
+SELECT cs.step_id, cs.state, cs.started_at, cs.completed_at, COALESCE(cs.result, '{}'::jsonb), COALESCE(cs.error->>'message', '')
 FROM case_steps cs
 JOIN cases c ON c.id = cs.case_id
 WHERE cs.case_id = $1
   AND c.tenant_id = $2
+ORDER BY cs.started_at NULLS LAST, cs.step_id
 `, caseID, tenantID)
 	if err != nil {
 		return nil, uuid.Nil, fmt.Errorf("load step results for template context: %w", err)
 	}
 	defer func() { _ = rows.Close() }()
 	for rows.Next() {
-		var stepID string
-		var raw []byte
-		if err := rows.Scan(&stepID, &raw); err != nil {
+		var (
+			stepID, state, stepError string
+			startedAt, completedAt   sql.NullTime
+			raw                      []byte
+		)
+		if err := rows.Scan(&stepID, &state, &startedAt, &completedAt, &raw, &stepError); err != nil {
 			return nil, uuid.Nil, fmt.Errorf("scan step context row: %w", err)
 		}
 		result := map[string]any{}
 		if err := json.Unmarshal(raw, &result); err != nil {
 			return nil, uuid.Nil, fmt.Errorf("decode step context result for %s: %w", stepID, err)
 		}
-		steps[stepID] = map[string]any{"result": result}
+		steps[stepID] = map[string]any{"result": result, "state": state}
+		summary = append(summary, stepSummary(stepID, state, startedAt, completedAt, stepError))
 	}
 	if err := rows.Err(); err != nil {
 		return nil, uuid.Nil, fmt.Errorf("iterate step context rows: %w", err)
@@ -198,6 +451,12 @@ WHERE cs.case_id = $1
 		"case":   caseMap,
 		"tenant": map[string]any{"branding": branding},
 		"now":    time.Now().UTC().Format(time.RFC3339),
+		"execution": map[string]any{
+			"case_id":     caseID.String(),
+			"case_number": caseNumber,
+			"status":      caseStatus,
+			"steps":       summary,
+		},
 	}
 	if e.secrets != nil {
 		templateContext["__secret_resolver"] = func(key string) string {
@@ -210,3 +469,16 @@ WHERE cs.case_id = $1
 	}
 	return templateContext, tenantID, nil
 }
+
+// stepSummary is one row of the execution summary that messaging connectors
+// attach when a step sets include_summary.
+func stepSummary(stepID, state string, startedAt, completedAt sql.NullTime, stepError string) map[string]any {
+	out := map[string]any{"step_id": stepID, "state": state}
+	if startedAt.Valid && completedAt.Valid {
+		out["duration_ms"] = completedAt.Time.Sub(startedAt.Time).Milliseconds()
+	}
+	if stepError != "" {
+		out["error"] = stepError
+	}
+	return out
+}
@@ -0,0 +1,168 @@
+package connectors
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"testing"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/redaction"
+)
+
+type echoConnector struct{ testConnector }
+
+func (c *echoConnector) Meta() ConnectorMeta { return ConnectorMeta{Key: "echo", Name: "Echo"} }
+
+func (c *echoConnector) Actions() []ActionSpec {
+	return []ActionSpec{
+		{Key: "send", Execute: func(_ context.Context, auth map[string]string, input map[string]any) (map[string]any, error) {
+			return map[string]any{"token": auth["token"], "to": input["to"]}, nil
+		}},
+		{Key: "template", RawInputKeys: []string{"body"}, Execute: func(_ context.Context, _ map[string]string, input map[string]any) (map[string]any, error) {
+			return map[string]any{"body": input["body"], "to": input["to"]}, nil
+		}},
+		{Key: "fail", Execute: func(_ context.Context, _ map[string]string, _ map[string]any) (map[string]any, error) {
+			return nil, errors.New("upstream refused")
+		}},
+	}
+}
+
+func TestExecutor_TestStepResolvesTemplatesFromSuppliedContext(t *testing.T) {
+	reg := NewRegistry()
+	reg.Register(&echoConnector{})
+	recorder := &captureRecorder{}
+	reg.SetUsageRecorder(recorder)
+	exec := NewExecutor(nil, reg, nil)
+	caseCtx := map[string]any{"case": map[string]any{
+		"data":  map[string]any{"email": "a@example.test"},
+		"steps": map[string]any{"login": map[string]any{"result": map[string]any{"token": "t-1"}}},
+	}}
+
+	raw := json.RawMessage(`{"connector":"echo","action":"send","auth":{"token":"{{case.steps.login.result.token}}"},"input":{"to":"{{case.data.email}}"}}`)
+	out, err := exec.TestStep(context.Background(), uuid.New(), "notify", raw, caseCtx)
+	if err != nil {
+		t.Fatalf("test step: %v", err)
+	}
+	if out.Input["to"] != "a@example.test" || out.Output["to"] != "a@example.test" || out.Output["token"] != "t-1" {
+		t.Fatalf("unexpected test result %+v", out)
+	}
+	if _, leaked := out.Input["_tenant_id"]; leaked {
+		t.Fatalf("internal input keys should not be reported: %v", out.Input)
+	}
+
+	out, err = exec.TestStep(context.Background(), uuid.New(), "notify", json.RawMessage(`{"connector":"echo","action":"fail"}`), caseCtx)
+	if err != nil {
+		t.Fatalf("action failures should be reported in the result, got %v", err)
+	}
+	if out.Error != "upstream refused" || out.Output != nil {
+		t.Fatalf("unexpected failure result %+v", out)
+	}
+	if len(recorder.records) != 2 || !recorder.records[0].Success || recorder.records[1].Success || recorder.records[1].ActionKey != "fail" {
+		t.Fatalf("expected a success and a failure to be recorded, got %+v", recorder.records)
+	}
+}
+
+func TestExecutor_TestStepRedactsSecretsInReportedInput(t *testing.T) {
+	reg := NewRegistry()
+	reg.Register(&echoConnector{})
+	exec := NewExecutor(nil, reg, mapSecretStore{"db_password": "hunter2"})
+
+	raw := json.RawMessage(`{"connector":"echo","action":"send","input":{"to":"{{secrets.db_password}}","note":"pw={{secrets.db_password}}"}}`)
+	out, err := exec.TestStep(context.Background(), uuid.New(), "notify", raw, map[string]any{})
+	if err != nil {
+		t.Fatalf("test step: %v", err)
+	}
+	if out.Output["to"] != "hunter2" {
+		t.Fatalf("expected the secret to reach the action, got %+v", out.Output)
+	}
+	if out.Input["to"] != redaction.Mask || out.Input["note"] != "pw="+redaction.Mask {
+		t.Fatalf("expected secrets to be redacted in the reported input, got %+v", out.Input)
+	}
+}
+
+func TestExecutor_RawInputKeysSkipTemplateResolution(t *testing.T) {
+	reg := NewRegistry()
+	reg.Register(&echoConnector{})
+	exec := NewExecutor(nil, reg, nil)
+	caseCtx := map[string]any{"case": map[string]any{"data": map[string]any{"email": "a@example.test"}}}
+
+	raw := json.RawMessage(`{"connector":"echo","action":"template","input":{"body":"Hi {{ .name }}","to":"{{case.data.email}}"}}`)
+	out, err := exec.TestStep(context.Background(), uuid.New(), "notify", raw, caseCtx)
+	if err != nil {
+		t.Fatalf("test step: %v", err)
+	}
+	if out.Output["body"] != "Hi {{ .name }}" || out.Output["to"] != "a@example.test" {
+		t.Fatalf("unexpected output %+v", out.Output)
+	}
+}
+
+func TestExtractArtifactsReplacesFieldsWithHandles(t *testing.T) {
+	result := map[string]any{"status": 200, "body": "<html>report</html>", "rows": []any{1.0, 2.0}}
+	out, artifacts, err := extractArtifacts(result, map[string]StepArtifact{
+		"body":    {Name: "report.html", ContentType: "text/html"},
+		"rows":    {},
+		"missing": {},
+	})
+	if err != nil {
+		t.Fatalf("extract: %v", err)
+	}
+	if len(artifacts) != 2 {
+		t.Fatalf("expected 2 artifacts, got %#v", artifacts)
+	}
+	if artifacts[0].Name != "report.html" || artifacts[0].ContentType != "text/html" || string(artifacts[0].Data) != "<html>report</html>" {
+		t.Fatalf("unexpected body artifact: %#v", artifacts[0])
+	}
+	if artifacts[1].Name != "rows" || artifacts[1].ContentType != "application/json" || string(artifacts[1].Data) != "[1,2]" {
+		t.Fatalf("unexpected rows artifact: %#v", artifacts[1])
+	}
+	if handle, _ := out["body"].(map[string]any); handle["artifact"] != "report.html" || out["status"] != 200 {
+		t.Fatalf("unexpected output: %#v", out)
+	}
+	if result["body"] != "<html>report</html>" {
+		t.Fatal("extractArtifacts must not modify the action result, which may be cached")
+	}
+}
+
+type tokenConnector struct{ echoConnector }
+
+func (c *tokenConnector) Auth() AuthSpec {
+	return AuthSpec{Type: "api_key", Fields: []AuthField{{Key: "token", Required: true}}}
+}
+
+type mapSecretStore map[string]string
+
+func (m mapSecretStore) Get(_ context.Context, _ uuid.UUID, key string) (string, error) {
+	if value, ok := m[key]; ok {
+		return value, nil
+	}
+	return "", ErrSecretNotFound
+}
+
+func TestExecutor_CredentialProfile(t *testing.T) {
+	reg := NewRegistry()
+	reg.Register(&tokenConnector{})
+	exec := NewExecutor(nil, reg, mapSecretStore{"token": "global", "crm-prod.token": "prod"})
+	tenantID := uuid.New()
+
+	out, err := exec.TestStep(context.Background(), tenantID, "notify", json.RawMessage(`{"connector":"echo","action":"send","credentials":"crm-prod"}`), map[string]any{})
+	if err != nil || out.Output["token"] != "prod" {
+		t.Fatalf("expected the profile's token, got %+v (%v)", out, err)
+	}
+	out, err = exec.TestStep(context.Background(), tenantID, "notify", json.RawMessage(`{"connector":"echo","action":"send"}`), map[string]any{})
+	if err != nil || out.Output["token"] != "global" {
+		t.Fatalf("expected the tenant-wide token, got %+v (%v)", out, err)
+	}
+	out, err = exec.TestStep(context.Background(), tenantID, "notify", json.RawMessage(`{"connector":"echo","action":"send","credentials":"crm-dev"}`), map[string]any{})
+	if err != nil || out.Output != nil || out.Error == "" {
+		t.Fatalf("expected a missing credentials error, got %+v (%v)", out, err)
+	}
+
+	missing, err := exec.MissingCredentials(context.Background(), tenantID, "echo", "crm-dev", nil)
+	if err != nil || len(missing) != 1 || missing[0] != "crm-dev.token" {
+		t.Fatalf("missing = %v (%v)", missing, err)
+	}
+	if missing, _ := exec.MissingCredentials(context.Background(), tenantID, "echo", "crm-dev", map[string]string{"token": "inline"}); len(missing) != 0 {
+		t.Fatalf("auth set on the step should not be required from the profile, got %v", missing)
+	}
+}
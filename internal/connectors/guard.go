@@ -0,0 +1,343 @@
+package connectors
+
+import (
+	"errors"
+	"fmt"
+	"sync"
+	"time"
+
+	"github.com/google/uuid"
+	"golang.org/x/time/rate"
+)
+
+const (
+	DefaultExecuteRequestsPerMinute = 60
+	DefaultExecuteBurst             = 10
+	DefaultExecuteBurstWindow       = 10 * time.Second
+	DefaultExecuteBurstThreshold    = 20
+	DefaultExecuteMaxFailures       = 5
+	DefaultExecuteLockoutBase       = time.Minute
+	DefaultExecuteLockoutMax        = time.Hour
+	defaultMaxAbuseReports          = 200
+	lockoutDecay                    = 24 * time.Hour
+	guardPruneInterval              = time.Minute
+)
+
+var (
+	ErrExecutionRateLimited = errors.New("execution rate limit exceeded")
+	ErrExecutionLocked      = errors.New("execution locked after repeated failures")
+)
+
+const (
+	AbuseKindRateLimited = "rate_limited"
+	AbuseKindBurst       = "burst"
+	AbuseKindLockout     = "lockout"
+)
+
+// ExecutionGuardConfig bounds how hard a single principal can drive the
+// connector execute endpoints. Zero values select the package defaults.
+type ExecutionGuardConfig struct {
+	RequestsPerMinute int
+	Burst             int
+	BurstWindow       time.Duration
+	BurstThreshold    int
+	MaxFailures       int
+	LockoutBase       time.Duration
+	LockoutMax        time.Duration
+	MaxReports        int
+}
+
+// AbuseReport records a guard intervention for tenant admins.
+type AbuseReport struct {
+	TenantID    uuid.UUID  `json:"tenant_id"`
+	PrincipalID uuid.UUID  `json:"principal_id"`
+	Kind        string     `json:"kind"`
+	Target      string     `json:"target"`
+	Detail      string     `json:"detail"`
+	LockedUntil *time.Time `json:"locked_until,omitempty"`
+	At          time.Time  `json:"at"`
+}
+
+// GuardError carries how long the caller should wait before retrying.
+type GuardError struct {
+	Err        error
+	RetryAfter time.Duration
+}
+
+func (e *GuardError) Error() string { return e.Err.Error() }
+
+func (e *GuardError) Unwrap() error { return e.Err }
+
+type principalGuardState struct {
+	limiter         *rate.Limiter
+	recent          []time.Time
+	burstReportedAt time.Time
+	limitReportedAt time.Time
+	failures        int
+	lockouts        int
+	lockedUntil     time.Time
+	lastLockoutAt   time.Time
+}
+
+// ExecutionGuard applies per-principal rate limits, burst detection and
+// escalating lockouts to connector executions. It is independent of any
+// global HTTP rate limiting and keeps state in memory per process.
+type ExecutionGuard struct {
+	mu       sync.Mutex
+	config   ExecutionGuardConfig
+	states   map[uuid.UUID]*principalGuardState
+	reports  []AbuseReport
+	now      func() time.Time
+	prunedAt time.Time
+}
+
+func NewExecutionGuard(config ExecutionGuardConfig) *ExecutionGuard {
+	return &ExecutionGuard{
+		config: config.withDefaults(),
+		states: map[uuid.UUID]*principalGuardState{},
+		now:    time.Now,
+	}
+}
+
+// Reconfigure applies new limits without losing lockouts or failure counts.
+// Existing per-principal limiters adopt the new rate and burst.
+func (g *ExecutionGuard) Reconfigure(config ExecutionGuardConfig) {
+	if g == nil {
+		return
+	}
+	g.mu.Lock()
+	defer g.mu.Unlock()
+	g.config = config.withDefaults()
+	now := g.now()
+	for _, state := range g.states {
+		state.limiter.SetLimitAt(now, g.config.limit())
+		state.limiter.SetBurstAt(now, g.config.Burst)
+	}
+}
+
+func (c ExecutionGuardConfig) withDefaults() ExecutionGuardConfig {
+	if c.RequestsPerMinute <= 0 {
+		c.RequestsPerMinute = DefaultExecuteRequestsPerMinute
+	}
+	if c.Burst <= 0 {
+		c.Burst = DefaultExecuteBurst
+	}
+	if c.BurstWindow <= 0 {
+		c.BurstWindow = DefaultExecuteBurstWindow
+	}
+	if c.BurstThreshold <= 0 {
+		c.BurstThreshold = DefaultExecuteBurstThreshold
+	}
+	if c.MaxFailures <= 0 {
+		c.MaxFailures = DefaultExecuteMaxFailures
+	}
+	if c.LockoutBase <= 0 {
+		c.LockoutBase = DefaultExecuteLockoutBase
+	}
+	if c.LockoutMax <= 0 {
+		c.LockoutMax = DefaultExecuteLockoutMax
+	}
+	if c.MaxReports <= 0 {
+		c.MaxReports = defaultMaxAbuseReports
+	}
+	return c
+}
+
+func (c ExecutionGuardConfig) limit() rate.Limit {
+	return rate.Limit(float64(c.RequestsPerMinute) / float64(time.Minute/time.Second))
+}
+
+// Allow admits one execution of target by the principal, or returns a
+// *GuardError wrapping ErrExecutionLocked or ErrExecutionRateLimited.
+func (g *ExecutionGuard) Allow(tenantID, principalID uuid.UUID, target string) error {
+	if g == nil {
+		return nil
+	}
+	g.mu.Lock()
+	defer g.mu.Unlock()
+
+	now := g.now()
+	if now.Sub(g.prunedAt) >= guardPruneInterval {
+		g.prune(now)
+	}
+	state := g.state(principalID)
+	if now.Before(state.lockedUntil) {
+		return &GuardError{Err: ErrExecutionLocked, RetryAfter: state.lockedUntil.Sub(now)}
+	}
+
+	// Attempts are counted before the limiter so hammering a throttled
+	// endpoint still registers as a burst.
+	cutoff := now.Add(-g.config.BurstWindow)
+	kept := state.recent[:0]
+	for _, at := range state.recent {
+		if at.After(cutoff) {
+			kept = append(kept, at)
+		}
+	}
+	state.recent = append(kept, now)
+	if len(state.recent) > g.config.BurstThreshold && now.Sub(state.burstReportedAt) >= g.config.BurstWindow {
+		state.burstReportedAt = now
+		g.report(AbuseReport{
+			TenantID:    tenantID,
+			PrincipalID: principalID,
+			Kind:        AbuseKindBurst,
+			Target:      target,
+			Detail:      fmt.Sprintf("%d executions within %s", len(state.recent), g.config.BurstWindow),
+			At:          now,
+		})
+	}
+
+	reservation := state.limiter.ReserveN(now, 1)
+	if delay := reservation.DelayFrom(now); delay > 0 {
+		reservation.CancelAt(now)
+		if now.Sub(state.limitReportedAt) >= time.Minute {
+			state.limitReportedAt = now
+			g.report(AbuseReport{
+				TenantID:    tenantID,
+				PrincipalID: principalID,
+				Kind:        AbuseKindRateLimited,
+				Target:      target,
+				Detail:      fmt.Sprintf("exceeded %d executions per minute", g.config.RequestsPerMinute),
+				At:          now,
+			})
+		}
+		return &GuardError{Err: ErrExecutionRateLimited, RetryAfter: delay}
+	}
+	return nil
+}
+
+// RecordFailure counts a rejected execution (invalid payload, input or
+// credentials; see CallerFault). After MaxFailures consecutive failures the
+// principal is locked out; each further lockout within 24 hours doubles the
+// cool-down up to LockoutMax.
+func (g *ExecutionGuard) RecordFailure(tenantID, principalID uuid.UUID, target string) {
+	if g == nil {
+		return
+	}
+	g.mu.Lock()
+	defer g.mu.Unlock()
+
+	now := g.now()
+	state := g.state(principalID)
+	state.failures++
+	if state.failures < g.config.MaxFailures {
+		return
+	}
+	if !state.lastLockoutAt.IsZero() && now.Sub(state.lastLockoutAt) > lockoutDecay {
+		state.lockouts = 0
+	}
+	state.lockouts++
+	state.failures = 0
+	state.lastLockoutAt = now
+	cooldown := g.config.LockoutBase << (state.lockouts - 1)
+	if cooldown <= 0 || cooldown > g.config.LockoutMax {
+		cooldown = g.config.LockoutMax
+	}
+	state.lockedUntil = now.Add(cooldown)
+	lockedUntil := state.lockedUntil
+	g.report(AbuseReport{
+		TenantID:    tenantID,
+		PrincipalID: principalID,
+		Kind:        AbuseKindLockout,
+		Target:      target,
+		Detail:      fmt.Sprintf("%d consecutive failures; lockout #%d for %s", g.config.MaxFailures, state.lockouts, cooldown),
+		LockedUntil: &lockedUntil,
+		At:          now,
+	})
+}
+
+// RecordSuccess clears the consecutive failure count.
+func (g *ExecutionGuard) RecordSuccess(principalID uuid.UUID) {
+	if g == nil {
+		return
+	}
+	g.mu.Lock()
+	defer g.mu.Unlock()
+	if state, ok := g.states[principalID]; ok {
+		state.failures = 0
+	}
+}
+
+// CallerFault reports whether a failed call of action was the caller's
+// fault rather than the tool's: auth leaves a required auth field empty,
+// input lacks a key the action's input schema requires, or err says
+// credentials are missing. Only these failures should be passed to
+// RecordFailure; a tool that is down says nothing about who called it.
+func CallerFault(spec AuthSpec, action ActionSpec, auth map[string]string, input map[string]any, err error) bool {
+	if errors.Is(err, ErrCredentialsMissing) {
+		return true
+	}
+	for _, field := range spec.Fields {
+		if field.Required && auth[field.Key] == "" {
+			return true
+		}
+	}
+	var required []string
+	switch keys := action.InputSchema["required"].(type) {
+	case []string:
+		required = keys
+	case []any:
+		for _, key := range keys {
+			if s, ok := key.(string); ok {
+				required = append(required, s)
+			}
+		}
+	}
+	for _, key := range required {
+		if _, ok := input[key]; !ok {
+			return true
+		}
+	}
+	return false
+}
+
+// Reports returns the tenant's abuse reports, newest first.
+func (g *ExecutionGuard) Reports(tenantID uuid.UUID) []AbuseReport {
+	if g == nil {
+		return []AbuseReport{}
+	}
+	g.mu.Lock()
+	defer g.mu.Unlock()
+	out := make([]AbuseReport, 0)
+	for i := len(g.reports) - 1; i >= 0; i-- {
+		if g.reports[i].TenantID == tenantID {
+			out = append(out, g.reports[i])
+		}
+	}
+	return out
+}
+
+func (g *ExecutionGuard) state(principalID uuid.UUID) *principalGuardState {
+	state, ok := g.states[principalID]
+	if !ok {
+		state = &principalGuardState{limiter: rate.NewLimiter(g.config.limit(), g.config.Burst)}
+		g.states[principalID] = state
+	}
+	return state
+}
+
+// prune drops principals the guard holds nothing against: a full limiter,
+// no failures towards a lockout and no lockout recent enough to escalate
+// the next one. Such a principal starts afresh on its next call anyway.
+func (g *ExecutionGuard) prune(now time.Time) {
+	g.prunedAt = now
+	for id, state := range g.states {
+		if state.failures > 0 || now.Before(state.lockedUntil) {
+			continue
+		}
+		if !state.lastLockoutAt.IsZero() && now.Sub(state.lastLockoutAt) <= lockoutDecay {
+			continue
+		}
+		if state.limiter.TokensAt(now) < float64(state.limiter.Burst()) {
+			continue
+		}
+		delete(g.states, id)
+	}
+}
+
+func (g *ExecutionGuard) report(report AbuseReport) {
+	g.reports = append(g.reports, report)
+	if overflow := len(g.reports) - g.config.MaxReports; overflow > 0 {
+		g.reports = append([]AbuseReport(nil), g.reports[overflow:]...)
+	}
+}
@@ -0,0 +1,164 @@
+package connectors
+
+import (
+	"errors"
+	"testing"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+func newTestGuard(cfg ExecutionGuardConfig) (*ExecutionGuard, *time.Time) {
+	g := NewExecutionGuard(cfg)
+	now := time.Date(2026, 1, 1, 12, 0, 0, 0, time.UTC)
+	g.now = func() time.Time { return now }
+	return g, &now
+}
+
+func TestExecutionGuardRateLimitsPerPrincipal(t *testing.T) {
+	g, now := newTestGuard(ExecutionGuardConfig{RequestsPerMinute: 60, Burst: 2})
+	tenant, alice, bob := uuid.New(), uuid.New(), uuid.New()
+
+	for i := 0; i < 2; i++ {
+		if err := g.Allow(tenant, alice, "http.request"); err != nil {
+			t.Fatalf("attempt %d: %v", i, err)
+		}
+	}
+	err := g.Allow(tenant, alice, "http.request")
+	var guardErr *GuardError
+	if !errors.Is(err, ErrExecutionRateLimited) || !errors.As(err, &guardErr) || guardErr.RetryAfter <= 0 {
+		t.Fatalf("expected rate limit with retry-after, got %v", err)
+	}
+	if err := g.Allow(tenant, bob, "http.request"); err != nil {
+		t.Fatalf("other principals must not share the limit: %v", err)
+	}
+
+	*now = now.Add(time.Second)
+	if err := g.Allow(tenant, alice, "http.request"); err != nil {
+		t.Fatalf("expected token refill after a second: %v", err)
+	}
+	reports := g.Reports(tenant)
+	if len(reports) != 1 || reports[0].Kind != AbuseKindRateLimited || reports[0].PrincipalID != alice {
+		t.Fatalf("unexpected reports: %#v", reports)
+	}
+}
+
+func TestExecutionGuardDetectsBursts(t *testing.T) {
+	g, _ := newTestGuard(ExecutionGuardConfig{RequestsPerMinute: 6000, Burst: 100, BurstThreshold: 3})
+	tenant, principal := uuid.New(), uuid.New()
+	for i := 0; i < 6; i++ {
+		_ = g.Allow(tenant, principal, "jira.create_issue")
+	}
+	reports := g.Reports(tenant)
+	if len(reports) != 1 || reports[0].Kind != AbuseKindBurst {
+		t.Fatalf("expected a single burst report, got %#v", reports)
+	}
+}
+
+func TestExecutionGuardEscalatesLockouts(t *testing.T) {
+	g, now := newTestGuard(ExecutionGuardConfig{MaxFailures: 2, LockoutBase: time.Minute, LockoutMax: 3 * time.Minute})
+	tenant, principal := uuid.New(), uuid.New()
+
+	lockout := func() time.Duration {
+		g.RecordFailure(tenant, principal, "http.request")
+		g.RecordFailure(tenant, principal, "http.request")
+		var guardErr *GuardError
+		if err := g.Allow(tenant, principal, "http.request"); !errors.As(err, &guardErr) || !errors.Is(err, ErrExecutionLocked) {
+			t.Fatalf("expected lockout, got %v", err)
+		}
+		return guardErr.RetryAfter
+	}
+
+	for _, want := range []time.Duration{time.Minute, 2 * time.Minute, 3 * time.Minute} {
+		if got := lockout(); got != want {
+			t.Fatalf("expected cool-down %s, got %s", want, got)
+		}
+		*now = now.Add(want)
+	}
+
+	g.RecordFailure(tenant, principal, "http.request")
+	g.RecordSuccess(principal)
+	g.RecordFailure(tenant, principal, "http.request")
+	if err := g.Allow(tenant, principal, "http.request"); err != nil {
+		t.Fatalf("success should reset the failure count: %v", err)
+	}
+	if reports := g.Reports(uuid.New()); len(reports) != 0 {
+		t.Fatalf("reports leaked across tenants: %#v", reports)
+	}
+	if reports := g.Reports(tenant); len(reports) != 3 || reports[0].LockedUntil == nil {
+		t.Fatalf("expected three lockout reports, got %#v", reports)
+	}
+}
+
+func TestExecutionGuardReconfigureAppliesToExistingPrincipals(t *testing.T) {
+	g, now := newTestGuard(ExecutionGuardConfig{RequestsPerMinute: 60, Burst: 1})
+	tenant, alice := uuid.New(), uuid.New()
+	if err := g.Allow(tenant, alice, "http.request"); err != nil {
+		t.Fatalf("first attempt: %v", err)
+	}
+
+	g.Reconfigure(ExecutionGuardConfig{RequestsPerMinute: 1, Burst: 1})
+	*now = now.Add(time.Second)
+	if err := g.Allow(tenant, alice, "http.request"); !errors.Is(err, ErrExecutionRateLimited) {
+		t.Fatalf("expected the lowered rate to apply, got %v", err)
+	}
+	*now = now.Add(time.Minute)
+	if err := g.Allow(tenant, alice, "http.request"); err != nil {
+		t.Fatalf("expected a token after a minute: %v", err)
+	}
+}
+
+func TestCallerFaultCountsOnlyInvalidCalls(t *testing.T) {
+	spec := AuthSpec{Fields: []AuthField{{Key: "token", Required: true}, {Key: "region"}}}
+	action := ActionSpec{InputSchema: map[string]any{"required": []any{"url"}}}
+	auth := map[string]string{"token": "t"}
+	input := map[string]any{"url": "https://example.com"}
+	upstream := errors.New("upstream returned status 503")
+
+	if CallerFault(spec, action, auth, input, upstream) {
+		t.Fatal("a failing upstream must not count against the caller")
+	}
+	if !CallerFault(spec, action, map[string]string{}, input, upstream) {
+		t.Fatal("expected a missing required auth field to count")
+	}
+	if !CallerFault(spec, action, auth, map[string]any{}, upstream) {
+		t.Fatal("expected a missing required input to count")
+	}
+	if !CallerFault(spec, action, auth, input, ErrCredentialsMissing) {
+		t.Fatal("expected missing credentials to count")
+	}
+
+	g, _ := newTestGuard(ExecutionGuardConfig{MaxFailures: 2})
+	tenant, principal := uuid.New(), uuid.New()
+	for i := 0; i < 3; i++ {
+		if CallerFault(spec, action, auth, input, upstream) {
+			g.RecordFailure(tenant, principal, "http.request")
+		}
+	}
+	if err := g.Allow(tenant, principal, "http.request"); err != nil {
+		t.Fatalf("upstream failures must not lock the caller out: %v", err)
+	}
+}
+
+func TestExecutionGuardPrunesIdlePrincipals(t *testing.T) {
+	g, now := newTestGuard(ExecutionGuardConfig{RequestsPerMinute: 60, Burst: 2, MaxFailures: 2})
+	tenant, idle, failing := uuid.New(), uuid.New(), uuid.New()
+	if err := g.Allow(tenant, idle, "http.request"); err != nil {
+		t.Fatalf("allow: %v", err)
+	}
+	if err := g.Allow(tenant, failing, "http.request"); err != nil {
+		t.Fatalf("allow: %v", err)
+	}
+	g.RecordFailure(tenant, failing, "http.request")
+
+	*now = now.Add(2 * guardPruneInterval)
+	if err := g.Allow(tenant, uuid.New(), "http.request"); err != nil {
+		t.Fatalf("allow: %v", err)
+	}
+	if _, ok := g.states[idle]; ok {
+		t.Fatal("expected the idle principal with a full limiter to be pruned")
+	}
+	if _, ok := g.states[failing]; !ok {
+		t.Fatal("expected a principal with failures towards a lockout to be kept")
+	}
+}
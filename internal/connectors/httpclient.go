@@ -4,18 +4,56 @@ import (
 	"bytes"
 	"context"
 	"encoding/json"
+	"errors"
 	"fmt"
 	"io"
+	"log/slog"
 	"net/http"
 	"time"
 
 	"github.com/neural-chilli/aceryx/internal/observability"
+	"github.com/neural-chilli/aceryx/internal/redaction"
 )
 
+const (
+	DefaultMaxResponseBytes = 10 << 20
+	DefaultMaxRedirects     = 10
+	DefaultRetryBackoff     = 500 * time.Millisecond
+	maxRetryBackoff         = 30 * time.Second
+
+	// maxLoggedBodyBytes caps each body in debug exchange logs.
+	maxLoggedBodyBytes = 4096
+)
+
+// ErrResponseTooLarge is returned when a response body exceeds RequestOptions.MaxResponseBytes.
+var ErrResponseTooLarge = errors.New("response body exceeds size limit")
+
+var (
+	errTooManyRedirects = errors.New("too many redirects")
+	errBuildRequest     = errors.New("build request")
+)
+
+// RequestOptions controls transport policy for outbound connector requests.
+// Zero values select the defaults: follow up to DefaultMaxRedirects redirects,
+// no retries, a DefaultMaxResponseBytes body cap and no egress restriction.
+type RequestOptions struct {
+	Timeout          time.Duration
+	DisableRedirects bool
+	MaxRedirects     int
+	MaxRetries       int
+	RetryBackoff     time.Duration
+	MaxResponseBytes int64
+	Egress           *EgressPolicy
+}
+
 func DoJSONRequest(ctx context.Context, method string, url string, headers map[string]string, body any, timeout time.Duration) (int, http.Header, []byte, error) {
-	if timeout <= 0 {
-		timeout = 30 * time.Second
-	}
+	return DoJSONRequestWithOptions(ctx, method, url, headers, body, RequestOptions{Timeout: timeout})
+}
+
+// DoJSONRequestWithOptions performs a JSON request, retrying connection errors
+// and 5xx responses with exponential backoff up to opts.MaxRetries times.
+func DoJSONRequestWithOptions(ctx context.Context, method string, url string, headers map[string]string, body any, opts RequestOptions) (int, http.Header, []byte, error) {
+	opts = applyRequestDefaults(opts)
 	requestBody := []byte{}
 	if body != nil {
 		raw, err := json.Marshal(body)
@@ -24,10 +62,42 @@ func DoJSONRequest(ctx context.Context, method string, url string, headers map[s
 		}
 		requestBody = raw
 	}
+	return DoRequestWithOptions(ctx, method, url, headers, requestBody, opts)
+}
+
+// DoRequestWithOptions sends a pre-encoded body under the same transport
+// policy as DoJSONRequestWithOptions. Callers sending anything other than
+// JSON should set Content-Type in headers.
+func DoRequestWithOptions(ctx context.Context, method string, url string, headers map[string]string, requestBody []byte, opts RequestOptions) (int, http.Header, []byte, error) {
+	opts = applyRequestDefaults(opts)
+	client := &http.Client{Timeout: opts.Timeout, CheckRedirect: redirectPolicy(opts)}
+	if opts.Egress != nil {
+		if err := opts.Egress.CheckURL(url); err != nil {
+			return 0, nil, nil, err
+		}
+		client.Transport = opts.Egress.transport()
+	}
+	var (
+		status          int
+		responseHeaders http.Header
+		payload         []byte
+		err             error
+	)
+	for attempt := 0; ; attempt++ {
+		status, responseHeaders, payload, err = doJSONAttempt(ctx, client, method, url, headers, requestBody, opts.MaxResponseBytes)
+		if attempt >= opts.MaxRetries || !shouldRetry(status, err) {
+			return status, responseHeaders, payload, err
+		}
+		if waitBackoff(ctx, retryDelay(opts.RetryBackoff, attempt)) != nil {
+			return status, responseHeaders, payload, err
+		}
+	}
+}
 
+func doJSONAttempt(ctx context.Context, client *http.Client, method string, url string, headers map[string]string, requestBody []byte, maxBytes int64) (int, http.Header, []byte, error) {
 	req, err := http.NewRequestWithContext(ctx, method, url, bytes.NewReader(requestBody))
 	if err != nil {
-		return 0, nil, nil, fmt.Errorf("build request: %w", err)
+		return 0, nil, nil, fmt.Errorf("%w: %w", errBuildRequest, err)
 	}
 	for key, value := range headers {
 		req.Header.Set(key, value)
@@ -35,19 +105,118 @@ func DoJSONRequest(ctx context.Context, method string, url string, headers map[s
 	if req.Header.Get("Content-Type") == "" {
 		req.Header.Set("Content-Type", "application/json")
 	}
-	if cid := observability.CorrelationIDFromContext(ctx); cid != "" {
-		req.Header.Set(observability.CorrelationHeader, cid)
-	}
+	observability.InjectCorrelationHeaders(ctx, req.Header)
+	observability.InjectTraceHeaders(ctx, req.Header)
 
-	client := &http.Client{Timeout: timeout}
 	res, err := client.Do(req)
 	if err != nil {
 		return 0, nil, nil, err
 	}
 	defer func() { _ = res.Body.Close() }()
-	payload, err := io.ReadAll(res.Body)
+	if res.ContentLength > maxBytes {
+		return res.StatusCode, res.Header, nil, fmt.Errorf("%w: %d bytes declared, limit %d", ErrResponseTooLarge, res.ContentLength, maxBytes)
+	}
+	payload, err := io.ReadAll(io.LimitReader(res.Body, maxBytes+1))
 	if err != nil {
 		return res.StatusCode, res.Header, nil, fmt.Errorf("read response body: %w", err)
 	}
+	if int64(len(payload)) > maxBytes {
+		return res.StatusCode, res.Header, nil, fmt.Errorf("%w: limit %d bytes", ErrResponseTooLarge, maxBytes)
+	}
+	logExchange(ctx, req, requestBody, res, payload)
 	return res.StatusCode, res.Header, payload, nil
 }
+
+// logExchange writes a request and its response at debug level, with
+// headers and bodies passed through the redaction rules.
+func logExchange(ctx context.Context, req *http.Request, requestBody []byte, res *http.Response, responseBody []byte) {
+	if !slog.Default().Enabled(ctx, slog.LevelDebug) {
+		return
+	}
+	redactor := redaction.FromContext(ctx)
+	slog.DebugContext(ctx, "connector http exchange",
+		append(observability.RequestAttrs(ctx),
+			"method", req.Method,
+			"url", redactor.RedactString(req.URL.String()),
+			"status", res.StatusCode,
+			"request_headers", redactor.RedactHeaders(req.Header),
+			"request_body", truncateLogged(redactor.RedactBody(requestBody)),
+			"response_headers", redactor.RedactHeaders(res.Header),
+			"response_body", truncateLogged(redactor.RedactBody(responseBody)),
+		)...,
+	)
+}
+
+func truncateLogged(body string) string {
+	if len(body) <= maxLoggedBodyBytes {
+		return body
+	}
+	return body[:maxLoggedBodyBytes] + "...(truncated)"
+}
+
+func applyRequestDefaults(opts RequestOptions) RequestOptions {
+	if opts.Timeout <= 0 {
+		opts.Timeout = 30 * time.Second
+	}
+	if opts.MaxRedirects <= 0 {
+		opts.MaxRedirects = DefaultMaxRedirects
+	}
+	if opts.MaxRetries < 0 {
+		opts.MaxRetries = 0
+	}
+	if opts.RetryBackoff <= 0 {
+		opts.RetryBackoff = DefaultRetryBackoff
+	}
+	if opts.MaxResponseBytes <= 0 {
+		opts.MaxResponseBytes = DefaultMaxResponseBytes
+	}
+	return opts
+}
+
+func redirectPolicy(opts RequestOptions) func(*http.Request, []*http.Request) error {
+	return func(req *http.Request, via []*http.Request) error {
+		if opts.DisableRedirects {
+			// Hand the 3xx response back to the caller untouched.
+			return http.ErrUseLastResponse
+		}
+		if len(via) >= opts.MaxRedirects {
+			return fmt.Errorf("%w: stopped after %d", errTooManyRedirects, opts.MaxRedirects)
+		}
+		if opts.Egress != nil {
+			return opts.Egress.CheckURL(req.URL.String())
+		}
+		return nil
+	}
+}
+
+func shouldRetry(status int, err error) bool {
+	if err != nil {
+		// These failures are deterministic and will not improve on retry.
+		for _, permanent := range []error{ErrResponseTooLarge, ErrEgressBlocked, errTooManyRedirects, errBuildRequest, context.Canceled, context.DeadlineExceeded} {
+			if errors.Is(err, permanent) {
+				return false
+			}
+		}
+		return true
+	}
+	return status >= http.StatusInternalServerError
+}
+
+func retryDelay(base time.Duration, attempt int) time.Duration {
+	delay := base << attempt
+	if delay <= 0 || delay > maxRetryBackoff {
+		return maxRetryBackoff
+	}
+	return delay
+}
+
+func waitBackoff(ctx context.Context, delay time.Duration) error {
+	timer := time.NewTimer(delay)
+	defer timer.Stop()
+	select {
+	case <-ctx.Done():
+		return ctx.Err()
+	case <-timer.C:
+		return nil
+	}
+}
@@ -0,0 +1,101 @@
+package connectors
+
+import (
+	"context"
+	"errors"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"sync/atomic"
+	"testing"
+	"time"
+)
+
+func TestDoJSONRequestRetriesServerErrors(t *testing.T) {
+	var calls atomic.Int32
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+		if calls.Add(1) < 3 {
+			w.WriteHeader(http.StatusBadGateway)
+			return
+		}
+		_, _ = w.Write([]byte(`{"ok":true}`))
+	}))
+	defer srv.Close()
+
+	status, _, body, err := DoJSONRequestWithOptions(context.Background(), http.MethodGet, srv.URL, nil, nil, RequestOptions{
+		MaxRetries:   2,
+		RetryBackoff: time.Millisecond,
+	})
+	if err != nil {
+		t.Fatalf("request: %v", err)
+	}
+	if status != http.StatusOK || string(body) != `{"ok":true}` {
+		t.Fatalf("unexpected response status=%d body=%s", status, body)
+	}
+	if calls.Load() != 3 {
+		t.Fatalf("expected 3 attempts, got %d", calls.Load())
+	}
+}
+
+func TestDoJSONRequestDoesNotRetryClientErrors(t *testing.T) {
+	var calls atomic.Int32
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+		calls.Add(1)
+		w.WriteHeader(http.StatusBadRequest)
+	}))
+	defer srv.Close()
+
+	status, _, _, err := DoJSONRequestWithOptions(context.Background(), http.MethodGet, srv.URL, nil, nil, RequestOptions{
+		MaxRetries:   3,
+		RetryBackoff: time.Millisecond,
+	})
+	if err != nil || status != http.StatusBadRequest {
+		t.Fatalf("expected 400 without error, got status=%d err=%v", status, err)
+	}
+	if calls.Load() != 1 {
+		t.Fatalf("expected a single attempt, got %d", calls.Load())
+	}
+}
+
+func TestDoJSONRequestRedirectPolicy(t *testing.T) {
+	target := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+		_, _ = w.Write([]byte(`"landed"`))
+	}))
+	defer target.Close()
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		http.Redirect(w, r, target.URL, http.StatusFound)
+	}))
+	defer srv.Close()
+
+	status, headers, _, err := DoJSONRequestWithOptions(context.Background(), http.MethodGet, srv.URL, nil, nil, RequestOptions{DisableRedirects: true})
+	if err != nil {
+		t.Fatalf("request without redirects: %v", err)
+	}
+	if status != http.StatusFound || headers.Get("Location") != target.URL {
+		t.Fatalf("expected untouched 302, got status=%d location=%q", status, headers.Get("Location"))
+	}
+
+	status, _, body, err := DoJSONRequestWithOptions(context.Background(), http.MethodGet, srv.URL, nil, nil, RequestOptions{})
+	if err != nil {
+		t.Fatalf("request with redirects: %v", err)
+	}
+	if status != http.StatusOK || string(body) != `"landed"` {
+		t.Fatalf("expected redirect to be followed, got status=%d body=%s", status, body)
+	}
+}
+
+func TestDoJSONRequestEnforcesMaxResponseSize(t *testing.T) {
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+		_, _ = w.Write([]byte(strings.Repeat("x", 64)))
+	}))
+	defer srv.Close()
+
+	_, _, _, err := DoJSONRequestWithOptions(context.Background(), http.MethodGet, srv.URL, nil, nil, RequestOptions{
+		MaxResponseBytes: 16,
+		MaxRetries:       2,
+		RetryBackoff:     time.Millisecond,
+	})
+	if !errors.Is(err, ErrResponseTooLarge) {
+		t.Fatalf("expected ErrResponseTooLarge, got %v", err)
+	}
+}
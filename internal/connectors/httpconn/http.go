@@ -11,10 +11,18 @@ import (
 	"github.com/neural-chilli/aceryx/internal/connectors"
 )
 
-type Connector struct{}
+type Connector struct {
+	egress connectors.EgressPolicy
+}
 
+// New returns a connector that refuses private and link-local destinations.
 func New() *Connector { return &Connector{} }
 
+// NewWithEgress returns a connector restricted by the given egress policy.
+func NewWithEgress(policy connectors.EgressPolicy) *Connector {
+	return &Connector{egress: policy}
+}
+
 func (c *Connector) Meta() connectors.ConnectorMeta {
 	return connectors.ConnectorMeta{Key: "http", Name: "HTTP/REST", Description: "Generic HTTP connector", Version: "v1", Icon: "pi pi-globe"}
 }
@@ -32,10 +40,21 @@ func (c *Connector) Actions() []connectors.ActionSpec {
 			InputSchema:  map[string]any{"type": "object"},
 			OutputSchema: map[string]any{"type": "object"},
 			Execute:      c.request,
+			CacheTTL:     connectors.DeterministicCacheTTL,
+			Cacheable:    safeMethod,
 		},
 	}
 }
 
+// safeMethod limits caching to requests without side effects.
+func safeMethod(input map[string]any) bool {
+	switch strings.ToUpper(readString(input, "method", "GET")) {
+	case http.MethodGet, http.MethodHead:
+		return true
+	}
+	return false
+}
+
 func (c *Connector) request(ctx context.Context, _ map[string]string, input map[string]any) (map[string]any, error) {
 	method := strings.ToUpper(readString(input, "method", "GET"))
 	url := readString(input, "url", "")
@@ -44,12 +63,22 @@ func (c *Connector) request(ctx context.Context, _ map[string]string, input map[
 	}
 
 	headers := readStringMap(input["headers"])
-	timeout := time.Duration(readInt(input, "timeout_seconds", 30)) * time.Second
-	status, responseHeaders, body, err := connectors.DoJSONRequest(ctx, method, url, headers, input["body"], timeout)
+	opts := connectors.RequestOptions{
+		Timeout:          time.Duration(readInt(input, "timeout_seconds", 30)) * time.Second,
+		DisableRedirects: !readBool(input, "follow_redirects", true),
+		MaxRedirects:     readInt(input, "max_redirects", connectors.DefaultMaxRedirects),
+		MaxRetries:       readInt(input, "max_retries", 0),
+		RetryBackoff:     time.Duration(readInt(input, "retry_backoff_ms", 0)) * time.Millisecond,
+		MaxResponseBytes: int64(readInt(input, "max_response_bytes", connectors.DefaultMaxResponseBytes)),
+		Egress:           &c.egress,
+	}
+	status, responseHeaders, body, err := connectors.DoJSONRequestWithOptions(ctx, method, url, headers, input["body"], opts)
 	if err != nil {
 		return nil, err
 	}
-	if status < http.StatusOK || status >= http.StatusMultipleChoices {
+	// With redirects disabled the 3xx response is the result; callers read Location themselves.
+	redirect := opts.DisableRedirects && status >= http.StatusMultipleChoices && status < http.StatusBadRequest
+	if !redirect && (status < http.StatusOK || status >= http.StatusMultipleChoices) {
 		return nil, fmt.Errorf("http request failed with status %d: %s", status, string(body))
 	}
 
@@ -92,6 +121,17 @@ func readInt(input map[string]any, key string, fallback int) int {
 	return fallback
 }
 
+func readBool(input map[string]any, key string, fallback bool) bool {
+	raw, ok := input[key]
+	if !ok || raw == nil {
+		return fallback
+	}
+	if b, ok := raw.(bool); ok {
+		return b
+	}
+	return fallback
+}
+
 func readStringMap(raw any) map[string]string {
 	out := map[string]string{}
 	switch typed := raw.(type) {
@@ -0,0 +1,492 @@
+package jsonconn
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"math"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/neural-chilli/aceryx/internal/connectors"
+)
+
+// Casts supported by data_map field mappings.
+var mappingCasts = []string{"string", "integer", "number", "boolean", "date", "datetime", "json"}
+
+// DataMapper is the data_map connector: it transforms records with a
+// declarative list of field mappings. Its input schema describes each
+// mapping in full so the designer can render a field mapping editor.
+type DataMapper struct{}
+
+func NewDataMapper() *DataMapper { return &DataMapper{} }
+
+func (c *DataMapper) Meta() connectors.ConnectorMeta {
+	return connectors.ConnectorMeta{Key: "data_map", Name: "Data Mapper", Description: "Map record fields to a new shape with casts, formats and defaults", Version: "v1", Icon: "pi pi-sitemap"}
+}
+
+func (c *DataMapper) Auth() connectors.AuthSpec { return connectors.AuthSpec{Type: "none"} }
+
+func (c *DataMapper) Triggers() []connectors.TriggerSpec { return nil }
+
+func (c *DataMapper) Actions() []connectors.ActionSpec {
+	spec := action("map", "Map Fields", "Transform a record or every record in an array with field mappings", mapFields)
+	spec.InputSchema = DataMapInputSchema()
+	spec.OutputSchema = map[string]any{
+		"type": "object",
+		"properties": map[string]any{
+			"result": map[string]any{"description": "The mapped record, or an array of mapped records"},
+			"count":  map[string]any{"type": "integer", "description": "Number of records mapped"},
+			"errors": map[string]any{
+				"type":        "array",
+				"description": "Fields that could not be mapped when on_error is skip",
+				"items": map[string]any{
+					"type": "object",
+					"properties": map[string]any{
+						"index":  map[string]any{"type": "integer"},
+						"target": map[string]any{"type": "string"},
+						"error":  map[string]any{"type": "string"},
+					},
+				},
+			},
+		},
+	}
+	return []connectors.ActionSpec{spec}
+}
+
+// DataMapInputSchema is the JSON Schema for the map action's input.
+func DataMapInputSchema() map[string]any {
+	return map[string]any{
+		"type":     "object",
+		"required": []string{"data", "mappings"},
+		"properties": map[string]any{
+			"data": map[string]any{
+				"title":       "Data",
+				"description": "A record, an array of records or a JSON string",
+			},
+			"path": map[string]any{
+				"type":        "string",
+				"title":       "Records path",
+				"description": "JSONPath selecting the array of records inside data; omit to map data itself",
+			},
+			"mappings": map[string]any{
+				"type":        "array",
+				"title":       "Field mappings",
+				"description": "Applied in order; later mappings may overwrite earlier targets",
+				"items": map[string]any{
+					"type":     "object",
+					"required": []string{"target"},
+					"properties": map[string]any{
+						"source": map[string]any{
+							"type":        "string",
+							"title":       "Source",
+							"description": "JSONPath or dotted path in the source record, such as customer.name",
+						},
+						"target": map[string]any{
+							"type":        "string",
+							"title":       "Target",
+							"description": "Dotted path in the output record, such as applicant.full_name",
+						},
+						"cast": map[string]any{
+							"type":        "string",
+							"title":       "Cast",
+							"description": "Convert the value to this type",
+							"enum":        mappingCasts,
+						},
+						"format": map[string]any{
+							"type":        "string",
+							"title":       "Format",
+							"description": "Output layout for date and datetime (a Go layout such as 02/01/2006, or rfc3339 or unix), or a printf verb such as %.2f for string",
+						},
+						"input_format": map[string]any{
+							"type":        "string",
+							"title":       "Input format",
+							"description": "Layout of the source value for date and datetime when it is not RFC 3339, YYYY-MM-DD or a Unix timestamp",
+						},
+						"default": map[string]any{
+							"title":       "Default",
+							"description": "Used when the source is missing, null or empty",
+						},
+						"required": map[string]any{
+							"type":        "boolean",
+							"title":       "Required",
+							"description": "Fail when the source is missing and there is no default",
+						},
+					},
+				},
+			},
+			"keep_unmapped": map[string]any{
+				"type":        "boolean",
+				"title":       "Keep unmapped fields",
+				"description": "Start each output record from a copy of the source record",
+			},
+			"on_error": map[string]any{
+				"type":        "string",
+				"title":       "On error",
+				"description": "fail stops the step; skip leaves the field out and reports it in errors",
+				"enum":        []string{"fail", "skip"},
+			},
+		},
+	}
+}
+
+type fieldMapping struct {
+	source      *jsonPath
+	sourceRaw   string
+	target      []string
+	targetRaw   string
+	cast        string
+	format      string
+	inputFormat string
+	fallback    any
+	hasFallback bool
+	required    bool
+}
+
+func mapFields(_ context.Context, _ map[string]string, input map[string]any) (map[string]any, error) {
+	data, err := readData(input)
+	if err != nil {
+		return nil, err
+	}
+	mappings, err := compileMappings(input["mappings"])
+	if err != nil {
+		return nil, err
+	}
+	onError := readString(input, "on_error", "fail")
+	if onError != "fail" && onError != "skip" {
+		return nil, fmt.Errorf("on_error must be fail or skip")
+	}
+	keep := readBool(input, "keep_unmapped", false)
+
+	records, single := []any{data}, true
+	if raw := readString(input, "path", ""); raw != "" {
+		if records, err = selectArray(data, raw); err != nil {
+			return nil, err
+		}
+		single = false
+	} else if arr, ok := data.([]any); ok {
+		records, single = arr, false
+	}
+
+	failures := []any{}
+	out := make([]any, 0, len(records))
+	for i, record := range records {
+		mapped, errs := mapRecord(record, mappings, keep)
+		for _, fieldErr := range errs {
+			if onError == "fail" {
+				if single {
+					return nil, fieldErr
+				}
+				return nil, fmt.Errorf("record %d: %w", i, fieldErr)
+			}
+			failures = append(failures, map[string]any{"index": i, "target": fieldErr.target, "error": fieldErr.Error()})
+		}
+		out = append(out, mapped)
+	}
+	var result any = out
+	if single {
+		result = out[0]
+	}
+	return map[string]any{"result": result, "count": len(out), "errors": failures}, nil
+}
+
+func compileMappings(raw any) ([]fieldMapping, error) {
+	if s, ok := raw.(string); ok {
+		var decoded any
+		if err := json.Unmarshal([]byte(s), &decoded); err != nil {
+			return nil, fmt.Errorf("mappings is not valid JSON: %w", err)
+		}
+		raw = decoded
+	}
+	items, ok := raw.([]any)
+	if !ok || len(items) == 0 {
+		return nil, fmt.Errorf("mappings must be a non-empty array")
+	}
+	out := make([]fieldMapping, 0, len(items))
+	for i, item := range items {
+		spec, ok := item.(map[string]any)
+		if !ok {
+			return nil, fmt.Errorf("mapping %d must be an object", i)
+		}
+		m := fieldMapping{
+			sourceRaw:   strings.TrimSpace(readString(spec, "source", "")),
+			targetRaw:   strings.TrimSpace(readString(spec, "target", "")),
+			cast:        readString(spec, "cast", ""),
+			format:      readString(spec, "format", ""),
+			inputFormat: readString(spec, "input_format", ""),
+			required:    readBool(spec, "required", false),
+		}
+		m.fallback, m.hasFallback = spec["default"]
+		if m.targetRaw == "" {
+			return nil, fmt.Errorf("mapping %d: target is required", i)
+		}
+		m.target = strings.Split(m.targetRaw, ".")
+		for _, part := range m.target {
+			if part == "" {
+				return nil, fmt.Errorf("mapping %d: invalid target %q", i, m.targetRaw)
+			}
+		}
+		if m.sourceRaw == "" && !m.hasFallback {
+			return nil, fmt.Errorf("mapping %d: source or default is required", i)
+		}
+		if m.sourceRaw != "" {
+			path, err := compileJSONPath(m.sourceRaw)
+			if err != nil {
+				return nil, fmt.Errorf("mapping %d: %w", i, err)
+			}
+			m.source = path
+		}
+		if m.cast != "" && !containsString(mappingCasts, m.cast) {
+			return nil, fmt.Errorf("mapping %d: unsupported cast %q", i, m.cast)
+		}
+		out = append(out, m)
+	}
+	return out, nil
+}
+
+type mappingError struct {
+	target string
+	err    error
+}
+
+func (e mappingError) Error() string { return fmt.Sprintf("%s: %v", e.target, e.err) }
+
+func (e mappingError) Unwrap() error { return e.err }
+
+func mapRecord(record any, mappings []fieldMapping, keep bool) (map[string]any, []mappingError) {
+	out := map[string]any{}
+	if src, ok := record.(map[string]any); keep && ok {
+		out = deepCopy(src).(map[string]any)
+	}
+	var errs []mappingError
+	for _, m := range mappings {
+		value, found := m.lookup(record)
+		if !found {
+			switch {
+			case m.hasFallback:
+				value = m.fallback
+			case m.required:
+				errs = append(errs, mappingError{target: m.targetRaw, err: fmt.Errorf("source %q is missing", m.sourceRaw)})
+				continue
+			default:
+				continue
+			}
+		}
+		if value != nil && m.cast != "" {
+			cast, err := castValue(value, m.cast, m.format, m.inputFormat)
+			if err != nil {
+				errs = append(errs, mappingError{target: m.targetRaw, err: err})
+				continue
+			}
+			value = cast
+		}
+		if err := setPath(out, m.target, value); err != nil {
+			errs = append(errs, mappingError{target: m.targetRaw, err: err})
+		}
+	}
+	return out, errs
+}
+
+// lookup treats a missing, null or empty string source as not found. A path
+// matching several nodes yields them as an array.
+func (m fieldMapping) lookup(record any) (any, bool) {
+	if m.source == nil {
+		return nil, false
+	}
+	matches := m.source.Query(record)
+	var value any
+	switch len(matches) {
+	case 0:
+		return nil, false
+	case 1:
+		value = matches[0]
+	default:
+		value = matches
+	}
+	if value == nil {
+		return nil, false
+	}
+	if s, ok := value.(string); ok && strings.TrimSpace(s) == "" {
+		return nil, false
+	}
+	return value, true
+}
+
+func setPath(out map[string]any, path []string, value any) error {
+	current := out
+	for _, part := range path[:len(path)-1] {
+		next, exists := current[part]
+		if !exists || next == nil {
+			child := map[string]any{}
+			current[part] = child
+			current = child
+			continue
+		}
+		child, ok := next.(map[string]any)
+		if !ok {
+			return fmt.Errorf("%q is already set to a non-object value", part)
+		}
+		current = child
+	}
+	current[path[len(path)-1]] = value
+	return nil
+}
+
+func castValue(value any, cast, format, inputFormat string) (any, error) {
+	switch cast {
+	case "string":
+		if format != "" {
+			return fmt.Sprintf(format, value), nil
+		}
+		return stringValue(value), nil
+	case "integer":
+		f, err := numberValue(value)
+		if err != nil {
+			return nil, err
+		}
+		if f != math.Trunc(f) {
+			return nil, fmt.Errorf("%v is not a whole number", value)
+		}
+		return int64(f), nil
+	case "number":
+		return numberValue(value)
+	case "boolean":
+		return boolValue(value)
+	case "date", "datetime":
+		t, err := timeValue(value, inputFormat)
+		if err != nil {
+			return nil, err
+		}
+		return formatTime(t, cast, format), nil
+	case "json":
+		if s, ok := value.(string); ok {
+			var decoded any
+			if err := json.Unmarshal([]byte(s), &decoded); err != nil {
+				return nil, fmt.Errorf("value is not valid JSON: %w", err)
+			}
+			return decoded, nil
+		}
+		return value, nil
+	}
+	return value, nil
+}
+
+func stringValue(value any) string {
+	switch v := value.(type) {
+	case string:
+		return v
+	case float64:
+		return strconv.FormatFloat(v, 'f', -1, 64)
+	case bool:
+		return strconv.FormatBool(v)
+	}
+	if f, ok := toFloat(value); ok {
+		return strconv.FormatFloat(f, 'f', -1, 64)
+	}
+	raw, _ := json.Marshal(value)
+	return string(raw)
+}
+
+func numberValue(value any) (float64, error) {
+	switch v := value.(type) {
+	case bool:
+		if v {
+			return 1, nil
+		}
+		return 0, nil
+	case string:
+		f, err := strconv.ParseFloat(strings.ReplaceAll(strings.TrimSpace(v), ",", ""), 64)
+		if err != nil {
+			return 0, fmt.Errorf("%q is not a number", v)
+		}
+		return f, nil
+	}
+	if f, ok := toFloat(value); ok {
+		return f, nil
+	}
+	return 0, fmt.Errorf("%v is not a number", value)
+}
+
+func boolValue(value any) (bool, error) {
+	switch v := value.(type) {
+	case bool:
+		return v, nil
+	case string:
+		switch strings.ToLower(strings.TrimSpace(v)) {
+		case "true", "yes", "y", "1", "on":
+			return true, nil
+		case "false", "no", "n", "0", "off":
+			return false, nil
+		}
+		return false, fmt.Errorf("%q is not a boolean", v)
+	}
+	if f, ok := toFloat(value); ok {
+		return f != 0, nil
+	}
+	return false, fmt.Errorf("%v is not a boolean", value)
+}
+
+// timeValue parses RFC 3339, YYYY-MM-DD, Unix seconds or inputFormat.
+func timeValue(value any, inputFormat string) (time.Time, error) {
+	if f, ok := toFloat(value); ok {
+		return time.Unix(int64(f), 0).UTC(), nil
+	}
+	s, ok := value.(string)
+	if !ok {
+		return time.Time{}, fmt.Errorf("%v is not a date", value)
+	}
+	s = strings.TrimSpace(s)
+	layouts := []string{time.RFC3339Nano, "2006-01-02"}
+	if inputFormat != "" {
+		layouts = []string{inputFormat}
+	}
+	for _, layout := range layouts {
+		if t, err := time.Parse(layout, s); err == nil {
+			return t, nil
+		}
+	}
+	return time.Time{}, fmt.Errorf("%q is not a date", s)
+}
+
+func formatTime(t time.Time, cast, format string) any {
+	switch strings.ToLower(format) {
+	case "":
+		if cast == "date" {
+			return t.Format("2006-01-02")
+		}
+		return t.Format(time.RFC3339)
+	case "rfc3339":
+		return t.Format(time.RFC3339)
+	case "unix":
+		return t.Unix()
+	}
+	return t.Format(format)
+}
+
+func deepCopy(value any) any {
+	switch v := value.(type) {
+	case map[string]any:
+		out := make(map[string]any, len(v))
+		for key, child := range v {
+			out[key] = deepCopy(child)
+		}
+		return out
+	case []any:
+		out := make([]any, len(v))
+		for i, child := range v {
+			out[i] = deepCopy(child)
+		}
+		return out
+	}
+	return value
+}
+
+func containsString(list []string, value string) bool {
+	for _, item := range list {
+		if item == value {
+			return true
+		}
+	}
+	return false
+}
@@ -0,0 +1,390 @@
+package jsonconn
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"sort"
+	"strings"
+
+	"github.com/neural-chilli/aceryx/internal/connectors"
+)
+
+type Connector struct{}
+
+func New() *Connector { return &Connector{} }
+
+func (c *Connector) Meta() connectors.ConnectorMeta {
+	return connectors.ConnectorMeta{Key: "json_transform", Name: "JSON Transform", Description: "Query and reshape JSON with JSONPath", Version: "v1", Icon: "pi pi-code"}
+}
+
+func (c *Connector) Auth() connectors.AuthSpec { return connectors.AuthSpec{Type: "none"} }
+
+func (c *Connector) Triggers() []connectors.TriggerSpec { return nil }
+
+func (c *Connector) Actions() []connectors.ActionSpec {
+	return []connectors.ActionSpec{
+		action("query", "Query", "Select values with a JSONPath expression", c.query),
+		action("map", "Map", "Reshape every element of an array with a template", c.mapItems),
+		action("flatten", "Flatten", "Flatten nested arrays", c.flatten),
+		action("sort_by", "Sort By", "Sort an array by a JSONPath key", c.sortBy),
+		action("group_by", "Group By", "Group array elements by a JSONPath key", c.groupBy),
+		action("reshape", "Reshape", "Build a new document from a template", c.reshape),
+		action("validate", "Validate", "Validate data against a JSON Schema", validate),
+	}
+}
+
+func action(key, name, description string, execute func(context.Context, map[string]string, map[string]any) (map[string]any, error)) connectors.ActionSpec {
+	return connectors.ActionSpec{
+		Key:          key,
+		Name:         name,
+		Description:  description,
+		InputSchema:  map[string]any{"type": "object"},
+		OutputSchema: map[string]any{"type": "object"},
+		Execute:      execute,
+		CacheTTL:     connectors.DeterministicCacheTTL,
+	}
+}
+
+// query returns every match in "result" and the first match in "value".
+func (c *Connector) query(_ context.Context, _ map[string]string, input map[string]any) (map[string]any, error) {
+	data, err := readData(input)
+	if err != nil {
+		return nil, err
+	}
+	raw := readString(input, "path", "")
+	if raw == "" {
+		return nil, fmt.Errorf("path is required")
+	}
+	path, err := compileJSONPath(raw)
+	if err != nil {
+		return nil, err
+	}
+	matches := path.Query(data)
+	var first any
+	if len(matches) > 0 {
+		first = matches[0]
+	}
+	return map[string]any{"result": matches, "value": first, "count": len(matches)}, nil
+}
+
+func (c *Connector) mapItems(_ context.Context, _ map[string]string, input map[string]any) (map[string]any, error) {
+	data, err := readData(input)
+	if err != nil {
+		return nil, err
+	}
+	items, err := selectArray(data, readString(input, "path", "$"))
+	if err != nil {
+		return nil, err
+	}
+	template, ok := input["template"]
+	if !ok || template == nil {
+		return nil, fmt.Errorf("template is required")
+	}
+	out := make([]any, 0, len(items))
+	for i, item := range items {
+		value, err := renderTemplate(template, item, map[string]any{"item": item, "index": i, "data": data})
+		if err != nil {
+			return nil, err
+		}
+		out = append(out, value)
+	}
+	return map[string]any{"result": out}, nil
+}
+
+func (c *Connector) flatten(_ context.Context, _ map[string]string, input map[string]any) (map[string]any, error) {
+	data, err := readData(input)
+	if err != nil {
+		return nil, err
+	}
+	items, err := selectArray(data, readString(input, "path", "$"))
+	if err != nil {
+		return nil, err
+	}
+	depth := readInt(input, "depth", 1)
+	if depth < 0 {
+		// Negative depth flattens completely.
+		depth = int(^uint(0) >> 1)
+	}
+	return map[string]any{"result": flattenArray(items, depth)}, nil
+}
+
+func (c *Connector) sortBy(_ context.Context, _ map[string]string, input map[string]any) (map[string]any, error) {
+	data, err := readData(input)
+	if err != nil {
+		return nil, err
+	}
+	items, err := selectArray(data, readString(input, "path", "$"))
+	if err != nil {
+		return nil, err
+	}
+	key, err := compileJSONPath(readString(input, "key", "$"))
+	if err != nil {
+		return nil, err
+	}
+	descending := readBool(input, "descending", false)
+
+	type keyed struct {
+		item any
+		key  filterValue
+	}
+	rows := make([]keyed, len(items))
+	for i, item := range items {
+		rows[i] = keyed{item: item, key: firstMatch(key, item)}
+	}
+	sort.SliceStable(rows, func(i, j int) bool {
+		if descending {
+			return lessKey(rows[j].key, rows[i].key)
+		}
+		return lessKey(rows[i].key, rows[j].key)
+	})
+	out := make([]any, len(rows))
+	for i, row := range rows {
+		out[i] = row.item
+	}
+	return map[string]any{"result": out}, nil
+}
+
+// groupBy keys groups by the stringified key value. Elements without the key
+// land under "" so nothing is silently dropped.
+func (c *Connector) groupBy(_ context.Context, _ map[string]string, input map[string]any) (map[string]any, error) {
+	data, err := readData(input)
+	if err != nil {
+		return nil, err
+	}
+	items, err := selectArray(data, readString(input, "path", "$"))
+	if err != nil {
+		return nil, err
+	}
+	raw := readString(input, "key", "")
+	if raw == "" {
+		return nil, fmt.Errorf("key is required")
+	}
+	key, err := compileJSONPath(raw)
+	if err != nil {
+		return nil, err
+	}
+	groups := map[string]any{}
+	order := []string{}
+	for _, item := range items {
+		name := groupName(firstMatch(key, item))
+		existing, ok := groups[name].([]any)
+		if !ok {
+			order = append(order, name)
+		}
+		groups[name] = append(existing, item)
+	}
+	return map[string]any{"result": groups, "keys": order}, nil
+}
+
+func (c *Connector) reshape(_ context.Context, _ map[string]string, input map[string]any) (map[string]any, error) {
+	data, err := readData(input)
+	if err != nil {
+		return nil, err
+	}
+	template, ok := input["template"]
+	if !ok || template == nil {
+		return nil, fmt.Errorf("template is required")
+	}
+	value, err := renderTemplate(template, data, map[string]any{"data": data})
+	if err != nil {
+		return nil, err
+	}
+	return map[string]any{"result": value}, nil
+}
+
+// renderTemplate walks a template. String leaves starting with "$" are
+// JSONPath expressions evaluated against node: one match yields the value,
+// several yield an array, none yield null. Other strings use the standard
+// {{...}} connector templating over tplCtx.
+func renderTemplate(template any, node any, tplCtx map[string]any) (any, error) {
+	switch typed := template.(type) {
+	case string:
+		if !strings.HasPrefix(typed, "$") {
+			return connectors.ResolveTemplateString(typed, tplCtx), nil
+		}
+		path, err := compileJSONPath(typed)
+		if err != nil {
+			return nil, err
+		}
+		matches := path.Query(node)
+		switch len(matches) {
+		case 0:
+			return nil, nil
+		case 1:
+			return matches[0], nil
+		default:
+			return matches, nil
+		}
+	case map[string]any:
+		out := make(map[string]any, len(typed))
+		for key, child := range typed {
+			value, err := renderTemplate(child, node, tplCtx)
+			if err != nil {
+				return nil, err
+			}
+			out[key] = value
+		}
+		return out, nil
+	case []any:
+		out := make([]any, len(typed))
+		for i, child := range typed {
+			value, err := renderTemplate(child, node, tplCtx)
+			if err != nil {
+				return nil, err
+			}
+			out[i] = value
+		}
+		return out, nil
+	default:
+		return template, nil
+	}
+}
+
+func flattenArray(items []any, depth int) []any {
+	out := make([]any, 0, len(items))
+	for _, item := range items {
+		if nested, ok := item.([]any); ok && depth > 0 {
+			out = append(out, flattenArray(nested, depth-1)...)
+			continue
+		}
+		out = append(out, item)
+	}
+	return out
+}
+
+func firstMatch(path *jsonPath, node any) filterValue {
+	matches := path.Query(node)
+	if len(matches) == 0 {
+		return filterValue{}
+	}
+	return filterValue{value: matches[0], exists: true}
+}
+
+// lessKey orders missing keys last, then null, booleans, numbers and strings;
+// anything else compares by its JSON encoding.
+func lessKey(a, b filterValue) bool {
+	if a.exists != b.exists {
+		return a.exists
+	}
+	ra, rb := rankKey(a.value), rankKey(b.value)
+	if ra != rb {
+		return ra < rb
+	}
+	switch ra {
+	case 1:
+		return !a.value.(bool) && b.value.(bool)
+	case 2:
+		fa, _ := toFloat(a.value)
+		fb, _ := toFloat(b.value)
+		return fa < fb
+	case 3:
+		return a.value.(string) < b.value.(string)
+	case 4:
+		ja, _ := json.Marshal(a.value)
+		jb, _ := json.Marshal(b.value)
+		return string(ja) < string(jb)
+	}
+	return false
+}
+
+func rankKey(v any) int {
+	if v == nil {
+		return 0
+	}
+	if _, ok := v.(bool); ok {
+		return 1
+	}
+	if _, ok := toFloat(v); ok {
+		return 2
+	}
+	if _, ok := v.(string); ok {
+		return 3
+	}
+	return 4
+}
+
+func groupName(key filterValue) string {
+	if !key.exists || key.value == nil {
+		return ""
+	}
+	switch v := key.value.(type) {
+	case string:
+		return v
+	case bool:
+		return fmt.Sprintf("%t", v)
+	}
+	if f, ok := toFloat(key.value); ok {
+		return fmt.Sprintf("%v", f)
+	}
+	raw, _ := json.Marshal(key.value)
+	return string(raw)
+}
+
+// readData accepts the document as a decoded value or as a JSON string.
+func readData(input map[string]any) (any, error) {
+	data, ok := input["data"]
+	if !ok {
+		return nil, fmt.Errorf("data is required")
+	}
+	if s, ok := data.(string); ok {
+		var decoded any
+		if err := json.Unmarshal([]byte(s), &decoded); err == nil {
+			return decoded, nil
+		}
+	}
+	return data, nil
+}
+
+func selectArray(data any, raw string) ([]any, error) {
+	path, err := compileJSONPath(raw)
+	if err != nil {
+		return nil, err
+	}
+	matches := path.Query(data)
+	if len(matches) == 1 {
+		arr, ok := matches[0].([]any)
+		if !ok {
+			return nil, fmt.Errorf("path %q does not select an array", raw)
+		}
+		return arr, nil
+	}
+	// A path matching several nodes (e.g. "$.orders[*].lines") yields those nodes.
+	return matches, nil
+}
+
+func readString(input map[string]any, key string, fallback string) string {
+	raw, ok := input[key]
+	if !ok || raw == nil {
+		return fallback
+	}
+	if s, ok := raw.(string); ok {
+		return s
+	}
+	return fallback
+}
+
+func readInt(input map[string]any, key string, fallback int) int {
+	raw, ok := input[key]
+	if !ok || raw == nil {
+		return fallback
+	}
+	switch v := raw.(type) {
+	case int:
+		return v
+	case float64:
+		return int(v)
+	}
+	return fallback
+}
+
+func readBool(input map[string]any, key string, fallback bool) bool {
+	raw, ok := input[key]
+	if !ok || raw == nil {
+		return fallback
+	}
+	if b, ok := raw.(bool); ok {
+		return b
+	}
+	return fallback
+}
@@ -0,0 +1,245 @@
+package jsonconn
+
+import (
+	"context"
+	"encoding/json"
+	"reflect"
+	"testing"
+)
+
+const storeJSON = `{
+	"store": {
+		"book": [
+			{"category": "reference", "author": "Rees", "title": "Sayings", "price": 8.95},
+			{"category": "fiction", "author": "Waugh", "title": "Sword", "price": 12.99},
+			{"category": "fiction", "author": "Melville", "title": "Moby Dick", "isbn": "0-553", "price": 8.99},
+			{"category": "fiction", "author": "Tolkien", "title": "The Lord", "isbn": "0-395", "price": 22.99}
+		],
+		"bicycle": {"color": "red", "price": 19.95}
+	}
+}`
+
+func mustDecode(t *testing.T, raw string) any {
+	t.Helper()
+	var out any
+	if err := json.Unmarshal([]byte(raw), &out); err != nil {
+		t.Fatalf("decode: %v", err)
+	}
+	return out
+}
+
+func TestJSONPathQueries(t *testing.T) {
+	doc := mustDecode(t, storeJSON)
+	cases := []struct {
+		path string
+		want []any
+	}{
+		{"$.store.book[0].author", []any{"Rees"}},
+		{"$['store']['bicycle']['color']", []any{"red"}},
+		{"$.store.book[*].author", []any{"Rees", "Waugh", "Melville", "Tolkien"}},
+		{"$..author", []any{"Rees", "Waugh", "Melville", "Tolkien"}},
+		{"$.store.book[-1].author", []any{"Tolkien"}},
+		{"$.store.book[0,2].author", []any{"Rees", "Melville"}},
+		{"$.store.book[1:3].author", []any{"Waugh", "Melville"}},
+		{"$.store.book[::-2].author", []any{"Tolkien", "Waugh"}},
+		{"$.store.book[?(@.isbn)].title", []any{"Moby Dick", "The Lord"}},
+		{"$.store.book[?(@.price < 10)].title", []any{"Sayings", "Moby Dick"}},
+		{"$.store.book[?(@.category == 'fiction' && !(@.price > 20))].author", []any{"Waugh", "Melville"}},
+		{"$.store.book[?(@.author =~ /^m/i || @.price >= 22)].author", []any{"Melville", "Tolkien"}},
+		{"$.store.book[?(@.price > $.store.bicycle.price)].title", []any{"The Lord"}},
+		{"store.bicycle.color", []any{"red"}},
+	}
+	for _, tc := range cases {
+		path, err := compileJSONPath(tc.path)
+		if err != nil {
+			t.Fatalf("compile %s: %v", tc.path, err)
+		}
+		if got := path.Query(doc); !reflect.DeepEqual(got, tc.want) {
+			t.Fatalf("%s: expected %v, got %v", tc.path, tc.want, got)
+		}
+	}
+}
+
+func TestJSONPathRejectsMalformedExpressions(t *testing.T) {
+	for _, raw := range []string{"$.store[", "$.store.book[?(@.price <)]", "$.a[1:2:3:4]", "$.a[?(@.b =~ /[/)]"} {
+		if _, err := compileJSONPath(raw); err == nil {
+			t.Fatalf("expected %q to be rejected", raw)
+		}
+	}
+}
+
+func TestTransformOperations(t *testing.T) {
+	c := New()
+	ctx := context.Background()
+	books := mustDecode(t, storeJSON).(map[string]any)["store"].(map[string]any)["book"]
+
+	out, err := c.mapItems(ctx, nil, map[string]any{
+		"data":     books,
+		"template": map[string]any{"name": "$.title", "label": "{{index}}: {{item.author}}"},
+	})
+	if err != nil {
+		t.Fatalf("map: %v", err)
+	}
+	first := out["result"].([]any)[0].(map[string]any)
+	if first["name"] != "Sayings" || first["label"] != "0: Rees" {
+		t.Fatalf("unexpected mapped item: %#v", first)
+	}
+
+	out, err = c.sortBy(ctx, nil, map[string]any{"data": books, "key": "$.price", "descending": true})
+	if err != nil {
+		t.Fatalf("sort_by: %v", err)
+	}
+	if top := out["result"].([]any)[0].(map[string]any); top["author"] != "Tolkien" {
+		t.Fatalf("expected most expensive first, got %#v", top)
+	}
+
+	out, err = c.groupBy(ctx, nil, map[string]any{"data": books, "key": "category"})
+	if err != nil {
+		t.Fatalf("group_by: %v", err)
+	}
+	groups := out["result"].(map[string]any)
+	if len(groups["fiction"].([]any)) != 3 || len(groups["reference"].([]any)) != 1 {
+		t.Fatalf("unexpected groups: %#v", groups)
+	}
+
+	out, err = c.flatten(ctx, nil, map[string]any{"data": `[1,[2,[3,[4]]]]`, "depth": -1})
+	if err != nil {
+		t.Fatalf("flatten: %v", err)
+	}
+	if got := out["result"]; !reflect.DeepEqual(got, []any{1.0, 2.0, 3.0, 4.0}) {
+		t.Fatalf("unexpected flatten result: %v", got)
+	}
+
+	out, err = c.reshape(ctx, nil, map[string]any{
+		"data":     storeJSON,
+		"template": map[string]any{"cheap": "$.store.book[?(@.price < 9)].title", "bike": "$.store.bicycle.color"},
+	})
+	if err != nil {
+		t.Fatalf("reshape: %v", err)
+	}
+	shaped := out["result"].(map[string]any)
+	if shaped["bike"] != "red" || !reflect.DeepEqual(shaped["cheap"], []any{"Sayings", "Moby Dick"}) {
+		t.Fatalf("unexpected reshape result: %#v", shaped)
+	}
+}
+
+func TestValidateReportsStructuredErrors(t *testing.T) {
+	schema := map[string]any{
+		"type":     "object",
+		"required": []any{"name"},
+		"properties": map[string]any{
+			"age":  map[string]any{"type": "integer", "minimum": 0},
+			"tags": map[string]any{"type": "array", "items": map[string]any{"type": "string"}},
+		},
+	}
+	out, err := validate(context.Background(), nil, map[string]any{
+		"data":   map[string]any{"age": -1, "tags": []any{"ok", 3}},
+		"schema": schema,
+	})
+	if err != nil {
+		t.Fatalf("validate: %v", err)
+	}
+	if out["valid"] != false {
+		t.Fatalf("expected invalid result, got %#v", out)
+	}
+	paths := map[string]bool{}
+	for _, raw := range out["errors"].([]any) {
+		entry := raw.(map[string]any)
+		if entry["message"] == "" || entry["keyword"] == "" {
+			t.Fatalf("expected message and keyword, got %#v", entry)
+		}
+		paths[entry["path"].(string)] = true
+	}
+	for _, want := range []string{"/", "/age", "/tags/1"} {
+		if !paths[want] {
+			t.Fatalf("expected error at %s, got %v", want, paths)
+		}
+	}
+
+	out, err = validate(context.Background(), nil, map[string]any{"data": `{"name":"x"}`, "schema": schema})
+	if err != nil || out["valid"] != true {
+		t.Fatalf("expected valid document, got out=%#v err=%v", out, err)
+	}
+}
+
+func TestSchemaValidateCanFailStep(t *testing.T) {
+	_, err := validate(context.Background(), nil, map[string]any{
+		"data":            map[string]any{},
+		"schema":          `{"type":"object","required":["id"]}`,
+		"fail_on_invalid": true,
+	})
+	if err == nil {
+		t.Fatal("expected invalid data to fail the step")
+	}
+	if _, err := validate(context.Background(), nil, map[string]any{"data": 1, "schema": `{"type": 12}`}); err == nil {
+		t.Fatal("expected malformed schema to be rejected")
+	}
+}
+
+func TestDataMapCastsFormatsAndDefaults(t *testing.T) {
+	mappings := []any{
+		map[string]any{"source": "customer.name", "target": "applicant.full_name"},
+		map[string]any{"source": "$.amount", "target": "loan.amount"},
+		map[string]any{"source": "balance", "target": "loan.balance", "cast": "number"},
+		map[string]any{"source": "amount", "target": "loan.label", "cast": "string", "format": "£%.2f"},
+		map[string]any{"source": "term", "target": "loan.months", "cast": "integer", "default": "12"},
+		map[string]any{"source": "opened", "target": "opened_on", "cast": "date", "input_format": "02/01/2006", "format": "2 Jan 2006"},
+		map[string]any{"source": "active", "target": "active", "cast": "boolean"},
+		map[string]any{"target": "source_system", "default": "crm"},
+	}
+	out, err := mapFields(context.Background(), nil, map[string]any{
+		"data":     `[{"customer":{"name":"Ada"},"amount":1250.5,"balance":"1,250.5","opened":"05/03/2026","active":"yes"},{"customer":{"name":"Bo"},"amount":99,"term":"","active":0,"opened":"01/01/2026"}]`,
+		"mappings": mappings,
+	})
+	if err != nil {
+		t.Fatalf("map: %v", err)
+	}
+	records := out["result"].([]any)
+	want := map[string]any{
+		"applicant":     map[string]any{"full_name": "Ada"},
+		"loan":          map[string]any{"amount": 1250.5, "balance": 1250.5, "label": "£1250.50", "months": int64(12)},
+		"opened_on":     "5 Mar 2026",
+		"active":        true,
+		"source_system": "crm",
+	}
+	if out["count"] != 2 || !reflect.DeepEqual(records[0], want) {
+		t.Fatalf("unexpected first record: %#v", records[0])
+	}
+	if second := records[1].(map[string]any); second["active"] != false || second["loan"].(map[string]any)["months"] != int64(12) {
+		t.Fatalf("unexpected second record: %#v", second)
+	}
+}
+
+func TestDataMapErrors(t *testing.T) {
+	input := map[string]any{
+		"data": map[string]any{"id": "A1", "age": "old", "extra": "kept"},
+		"mappings": []any{
+			map[string]any{"source": "age", "target": "age", "cast": "integer"},
+			map[string]any{"source": "email", "target": "email", "required": true},
+			map[string]any{"source": "id", "target": "id"},
+		},
+	}
+	if _, err := mapFields(context.Background(), nil, input); err == nil {
+		t.Fatal("expected a failed cast to fail the step")
+	}
+
+	input["on_error"] = "skip"
+	input["keep_unmapped"] = true
+	out, err := mapFields(context.Background(), nil, input)
+	if err != nil {
+		t.Fatalf("map with skip: %v", err)
+	}
+	record := out["result"].(map[string]any)
+	if record["extra"] != "kept" || record["id"] != "A1" || record["age"] != "old" {
+		t.Fatalf("unexpected record: %#v", record)
+	}
+	if errs := out["errors"].([]any); len(errs) != 2 || errs[0].(map[string]any)["target"] != "age" {
+		t.Fatalf("unexpected errors: %#v", errs)
+	}
+
+	for _, bad := range []any{nil, []any{}, []any{map[string]any{"source": "a"}}, []any{map[string]any{"target": "a"}}, []any{map[string]any{"source": "a", "target": "b", "cast": "money"}}} {
+		if _, err := mapFields(context.Background(), nil, map[string]any{"data": map[string]any{}, "mappings": bad}); err == nil {
+			t.Fatalf("expected mappings %#v to be rejected", bad)
+		}
+	}
+}
@@ -0,0 +1,744 @@
+package jsonconn
+
+import (
+	"encoding/json"
+	"fmt"
+	"regexp"
+	"sort"
+	"strconv"
+	"strings"
+	"unicode"
+)
+
+// jsonPath is a compiled JSONPath expression. Supported syntax:
+//
+//	$ / @                  root / current node (inside filters)
+//	.name ['name'] ["n"]   child member
+//	.* [*]                 wildcard
+//	..name ..* ..[...]     recursive descent
+//	[0] [-1] [0,2]         index and union
+//	[start:end:step]       array slice
+//	[?(@.a > 1 && @.b)]    filter with == != < <= > >= =~ && || ! and parentheses
+type jsonPath struct {
+	segments []pathSegment
+}
+
+type pathSegment struct {
+	recursive bool
+	selectors []pathSelector
+}
+
+type pathSelector interface {
+	apply(root any, node any, out []any) []any
+}
+
+type nameSelector struct{ name string }
+
+type wildcardSelector struct{}
+
+type indexSelector struct{ index int }
+
+type sliceSelector struct {
+	start, end *int
+	step       int
+}
+
+type filterSelector struct{ expr filterExpr }
+
+// compileJSONPath parses a JSONPath expression rooted at "$". A bare dotted
+// path without the "$" prefix ("items[0].name") is accepted for convenience.
+func compileJSONPath(raw string) (*jsonPath, error) {
+	expr := strings.TrimSpace(raw)
+	if expr == "" {
+		return nil, fmt.Errorf("jsonpath is empty")
+	}
+	if !strings.HasPrefix(expr, "$") {
+		if strings.HasPrefix(expr, "[") {
+			expr = "$" + expr
+		} else {
+			expr = "$." + expr
+		}
+	}
+	p := &pathParser{src: expr}
+	path, err := p.parsePath('$')
+	if err != nil {
+		return nil, fmt.Errorf("jsonpath %q: %w", raw, err)
+	}
+	p.skipSpace()
+	if !p.eof() {
+		return nil, fmt.Errorf("jsonpath %q: unexpected %q at offset %d", raw, p.src[p.pos:], p.pos)
+	}
+	return path, nil
+}
+
+// Query returns every node matched by the path, in document order.
+func (p *jsonPath) Query(root any) []any {
+	return p.eval(root, root)
+}
+
+func (p *jsonPath) eval(root any, start any) []any {
+	nodes := []any{start}
+	for _, seg := range p.segments {
+		next := make([]any, 0, len(nodes))
+		for _, node := range nodes {
+			if seg.recursive {
+				for _, d := range descendants(node, nil) {
+					for _, sel := range seg.selectors {
+						next = sel.apply(root, d, next)
+					}
+				}
+				continue
+			}
+			for _, sel := range seg.selectors {
+				next = sel.apply(root, node, next)
+			}
+		}
+		nodes = next
+	}
+	return nodes
+}
+
+func (s nameSelector) apply(_ any, node any, out []any) []any {
+	if obj, ok := node.(map[string]any); ok {
+		if v, ok := obj[s.name]; ok {
+			out = append(out, v)
+		}
+	}
+	return out
+}
+
+func (wildcardSelector) apply(_ any, node any, out []any) []any {
+	return append(out, children(node)...)
+}
+
+func (s indexSelector) apply(_ any, node any, out []any) []any {
+	arr, ok := node.([]any)
+	if !ok {
+		return out
+	}
+	idx := s.index
+	if idx < 0 {
+		idx += len(arr)
+	}
+	if idx >= 0 && idx < len(arr) {
+		out = append(out, arr[idx])
+	}
+	return out
+}
+
+func (s sliceSelector) apply(_ any, node any, out []any) []any {
+	arr, ok := node.([]any)
+	if !ok || s.step == 0 {
+		return out
+	}
+	n := len(arr)
+	normalize := func(v int) int {
+		if v < 0 {
+			return v + n
+		}
+		return v
+	}
+	if s.step > 0 {
+		lower, upper := 0, n
+		if s.start != nil {
+			lower = clampInt(normalize(*s.start), 0, n)
+		}
+		if s.end != nil {
+			upper = clampInt(normalize(*s.end), 0, n)
+		}
+		for i := lower; i < upper; i += s.step {
+			out = append(out, arr[i])
+		}
+		return out
+	}
+	upper, lower := n-1, -1
+	if s.start != nil {
+		upper = clampInt(normalize(*s.start), -1, n-1)
+	}
+	if s.end != nil {
+		lower = clampInt(normalize(*s.end), -1, n-1)
+	}
+	for i := upper; i > lower; i += s.step {
+		out = append(out, arr[i])
+	}
+	return out
+}
+
+func (s filterSelector) apply(root any, node any, out []any) []any {
+	for _, child := range children(node) {
+		if truthy(s.expr.eval(root, child)) {
+			out = append(out, child)
+		}
+	}
+	return out
+}
+
+// children returns array elements, or object values ordered by key so results
+// are deterministic.
+func children(node any) []any {
+	switch v := node.(type) {
+	case []any:
+		return v
+	case map[string]any:
+		keys := make([]string, 0, len(v))
+		for k := range v {
+			keys = append(keys, k)
+		}
+		sort.Strings(keys)
+		out := make([]any, 0, len(keys))
+		for _, k := range keys {
+			out = append(out, v[k])
+		}
+		return out
+	default:
+		return nil
+	}
+}
+
+func descendants(node any, out []any) []any {
+	out = append(out, node)
+	for _, child := range children(node) {
+		out = descendants(child, out)
+	}
+	return out
+}
+
+func clampInt(v, lo, hi int) int {
+	if v < lo {
+		return lo
+	}
+	if v > hi {
+		return hi
+	}
+	return v
+}
+
+// Filter expressions.
+
+type filterExpr interface {
+	eval(root any, current any) filterValue
+}
+
+// filterValue carries a comparison operand; exists is false for paths that
+// matched nothing, which only compare equal to another missing value.
+type filterValue struct {
+	value  any
+	exists bool
+}
+
+type literalExpr struct{ value any }
+
+type pathExpr struct {
+	fromRoot bool
+	path     *jsonPath
+}
+
+type notExpr struct{ inner filterExpr }
+
+type logicalExpr struct {
+	op          string
+	left, right filterExpr
+}
+
+type compareExpr struct {
+	op          string
+	left, right filterExpr
+	pattern     *regexp.Regexp
+}
+
+func (e literalExpr) eval(any, any) filterValue { return filterValue{value: e.value, exists: true} }
+
+func (e pathExpr) eval(root any, current any) filterValue {
+	start := current
+	if e.fromRoot {
+		start = root
+	}
+	matches := e.path.eval(root, start)
+	if len(matches) == 0 {
+		return filterValue{}
+	}
+	return filterValue{value: matches[0], exists: true}
+}
+
+func (e notExpr) eval(root any, current any) filterValue {
+	return filterValue{value: !truthy(e.inner.eval(root, current)), exists: true}
+}
+
+func (e logicalExpr) eval(root any, current any) filterValue {
+	left := truthy(e.left.eval(root, current))
+	if e.op == "&&" {
+		return filterValue{value: left && truthy(e.right.eval(root, current)), exists: true}
+	}
+	return filterValue{value: left || truthy(e.right.eval(root, current)), exists: true}
+}
+
+func (e compareExpr) eval(root any, current any) filterValue {
+	left := e.left.eval(root, current)
+	if e.op == "=~" {
+		s, ok := left.value.(string)
+		return filterValue{value: left.exists && ok && e.pattern.MatchString(s), exists: true}
+	}
+	right := e.right.eval(root, current)
+	return filterValue{value: compareValues(e.op, left, right), exists: true}
+}
+
+func truthy(v filterValue) bool {
+	if !v.exists {
+		return false
+	}
+	if b, ok := v.value.(bool); ok {
+		return b
+	}
+	// A bare path in a filter is an existence test; matched booleans count as themselves.
+	return true
+}
+
+func compareValues(op string, left, right filterValue) bool {
+	if !left.exists || !right.exists {
+		if op == "!=" {
+			return left.exists != right.exists
+		}
+		if op == "==" {
+			return !left.exists && !right.exists
+		}
+		return false
+	}
+	if lf, ok := toFloat(left.value); ok {
+		if rf, ok := toFloat(right.value); ok {
+			switch op {
+			case "==":
+				return lf == rf
+			case "!=":
+				return lf != rf
+			case "<":
+				return lf < rf
+			case "<=":
+				return lf <= rf
+			case ">":
+				return lf > rf
+			case ">=":
+				return lf >= rf
+			}
+		}
+	}
+	if ls, ok := left.value.(string); ok {
+		if rs, ok := right.value.(string); ok {
+			switch op {
+			case "==":
+				return ls == rs
+			case "!=":
+				return ls != rs
+			case "<":
+				return ls < rs
+			case "<=":
+				return ls <= rs
+			case ">":
+				return ls > rs
+			case ">=":
+				return ls >= rs
+			}
+		}
+	}
+	switch op {
+	case "==":
+		return deepEqual(left.value, right.value)
+	case "!=":
+		return !deepEqual(left.value, right.value)
+	default:
+		return false
+	}
+}
+
+func deepEqual(a, b any) bool {
+	ra, errA := json.Marshal(a)
+	rb, errB := json.Marshal(b)
+	return errA == nil && errB == nil && string(ra) == string(rb)
+}
+
+func toFloat(v any) (float64, bool) {
+	switch n := v.(type) {
+	case float64:
+		return n, true
+	case float32:
+		return float64(n), true
+	case int:
+		return float64(n), true
+	case int64:
+		return float64(n), true
+	case int32:
+		return float64(n), true
+	case json.Number:
+		f, err := n.Float64()
+		return f, err == nil
+	default:
+		return 0, false
+	}
+}
+
+// Parser.
+
+type pathParser struct {
+	src string
+	pos int
+}
+
+func (p *pathParser) eof() bool { return p.pos >= len(p.src) }
+
+func (p *pathParser) peek() byte {
+	if p.eof() {
+		return 0
+	}
+	return p.src[p.pos]
+}
+
+func (p *pathParser) skipSpace() {
+	for !p.eof() && (p.src[p.pos] == ' ' || p.src[p.pos] == '\t' || p.src[p.pos] == '\n') {
+		p.pos++
+	}
+}
+
+func (p *pathParser) consume(s string) bool {
+	if strings.HasPrefix(p.src[p.pos:], s) {
+		p.pos += len(s)
+		return true
+	}
+	return false
+}
+
+func (p *pathParser) parsePath(rootChar byte) (*jsonPath, error) {
+	if p.peek() != rootChar {
+		return nil, fmt.Errorf("expected %q at offset %d", rootChar, p.pos)
+	}
+	p.pos++
+	path := &jsonPath{}
+	for !p.eof() {
+		switch {
+		case p.consume(".."):
+			seg, err := p.parseAfterDot()
+			if err != nil {
+				return nil, err
+			}
+			seg.recursive = true
+			path.segments = append(path.segments, seg)
+		case p.consume("."):
+			seg, err := p.parseAfterDot()
+			if err != nil {
+				return nil, err
+			}
+			path.segments = append(path.segments, seg)
+		case p.peek() == '[':
+			seg, err := p.parseBracket()
+			if err != nil {
+				return nil, err
+			}
+			path.segments = append(path.segments, seg)
+		default:
+			return path, nil
+		}
+	}
+	return path, nil
+}
+
+func (p *pathParser) parseAfterDot() (pathSegment, error) {
+	if p.peek() == '[' {
+		return p.parseBracket()
+	}
+	if p.consume("*") {
+		return pathSegment{selectors: []pathSelector{wildcardSelector{}}}, nil
+	}
+	start := p.pos
+	for !p.eof() {
+		r := rune(p.src[p.pos])
+		if r == '_' || r == '-' || r == '$' || unicode.IsLetter(r) || unicode.IsDigit(r) || r >= 0x80 {
+			p.pos++
+			continue
+		}
+		break
+	}
+	if start == p.pos {
+		return pathSegment{}, fmt.Errorf("expected member name at offset %d", p.pos)
+	}
+	return pathSegment{selectors: []pathSelector{nameSelector{name: p.src[start:p.pos]}}}, nil
+}
+
+func (p *pathParser) parseBracket() (pathSegment, error) {
+	p.pos++ // '['
+	seg := pathSegment{}
+	for {
+		p.skipSpace()
+		sel, err := p.parseBracketSelector()
+		if err != nil {
+			return pathSegment{}, err
+		}
+		seg.selectors = append(seg.selectors, sel)
+		p.skipSpace()
+		if p.consume(",") {
+			continue
+		}
+		if p.consume("]") {
+			return seg, nil
+		}
+		return pathSegment{}, fmt.Errorf("expected ',' or ']' at offset %d", p.pos)
+	}
+}
+
+func (p *pathParser) parseBracketSelector() (pathSelector, error) {
+	switch c := p.peek(); {
+	case c == '*':
+		p.pos++
+		return wildcardSelector{}, nil
+	case c == '\'' || c == '"':
+		name, err := p.parseQuoted()
+		if err != nil {
+			return nil, err
+		}
+		return nameSelector{name: name}, nil
+	case c == '?':
+		p.pos++
+		p.skipSpace()
+		expr, err := p.parseOr()
+		if err != nil {
+			return nil, err
+		}
+		return filterSelector{expr: expr}, nil
+	case c == '-' || c == ':' || (c >= '0' && c <= '9'):
+		return p.parseIndexOrSlice()
+	default:
+		return nil, fmt.Errorf("unexpected %q at offset %d", string(c), p.pos)
+	}
+}
+
+func (p *pathParser) parseIndexOrSlice() (pathSelector, error) {
+	var parts [3]*int
+	part := 0
+	for {
+		p.skipSpace()
+		if c := p.peek(); c == '-' || (c >= '0' && c <= '9') {
+			n, err := p.parseInt()
+			if err != nil {
+				return nil, err
+			}
+			parts[part] = &n
+		}
+		p.skipSpace()
+		if p.peek() != ':' {
+			break
+		}
+		if part == 2 {
+			return nil, fmt.Errorf("too many ':' in slice at offset %d", p.pos)
+		}
+		p.pos++
+		part++
+	}
+	if part == 0 {
+		if parts[0] == nil {
+			return nil, fmt.Errorf("expected index at offset %d", p.pos)
+		}
+		return indexSelector{index: *parts[0]}, nil
+	}
+	step := 1
+	if parts[2] != nil {
+		step = *parts[2]
+	}
+	return sliceSelector{start: parts[0], end: parts[1], step: step}, nil
+}
+
+func (p *pathParser) parseInt() (int, error) {
+	start := p.pos
+	if p.peek() == '-' {
+		p.pos++
+	}
+	for !p.eof() && p.src[p.pos] >= '0' && p.src[p.pos] <= '9' {
+		p.pos++
+	}
+	n, err := strconv.Atoi(p.src[start:p.pos])
+	if err != nil {
+		return 0, fmt.Errorf("invalid integer at offset %d", start)
+	}
+	return n, nil
+}
+
+func (p *pathParser) parseQuoted() (string, error) {
+	quote := p.src[p.pos]
+	p.pos++
+	var b strings.Builder
+	for !p.eof() {
+		c := p.src[p.pos]
+		switch {
+		case c == '\\' && p.pos+1 < len(p.src):
+			b.WriteByte(p.src[p.pos+1])
+			p.pos += 2
+		case c == quote:
+			p.pos++
+			return b.String(), nil
+		default:
+			b.WriteByte(c)
+			p.pos++
+		}
+	}
+	return "", fmt.Errorf("unterminated string")
+}
+
+func (p *pathParser) parseOr() (filterExpr, error) {
+	left, err := p.parseAnd()
+	if err != nil {
+		return nil, err
+	}
+	for {
+		p.skipSpace()
+		if !p.consume("||") {
+			return left, nil
+		}
+		right, err := p.parseAnd()
+		if err != nil {
+			return nil, err
+		}
+		left = logicalExpr{op: "||", left: left, right: right}
+	}
+}
+
+func (p *pathParser) parseAnd() (filterExpr, error) {
+	left, err := p.parseUnary()
+	if err != nil {
+		return nil, err
+	}
+	for {
+		p.skipSpace()
+		if !p.consume("&&") {
+			return left, nil
+		}
+		right, err := p.parseUnary()
+		if err != nil {
+			return nil, err
+		}
+		left = logicalExpr{op: "&&", left: left, right: right}
+	}
+}
+
+func (p *pathParser) parseUnary() (filterExpr, error) {
+	p.skipSpace()
+	if p.peek() == '!' && !strings.HasPrefix(p.src[p.pos:], "!=") {
+		p.pos++
+		inner, err := p.parseUnary()
+		if err != nil {
+			return nil, err
+		}
+		return notExpr{inner: inner}, nil
+	}
+	if p.consume("(") {
+		inner, err := p.parseOr()
+		if err != nil {
+			return nil, err
+		}
+		p.skipSpace()
+		if !p.consume(")") {
+			return nil, fmt.Errorf("expected ')' at offset %d", p.pos)
+		}
+		return p.parseComparisonTail(inner)
+	}
+	operand, err := p.parseOperand()
+	if err != nil {
+		return nil, err
+	}
+	return p.parseComparisonTail(operand)
+}
+
+func (p *pathParser) parseComparisonTail(left filterExpr) (filterExpr, error) {
+	p.skipSpace()
+	for _, op := range []string{"==", "!=", "<=", ">=", "=~", "<", ">"} {
+		if !p.consume(op) {
+			continue
+		}
+		p.skipSpace()
+		if op == "=~" {
+			pattern, err := p.parseRegex()
+			if err != nil {
+				return nil, err
+			}
+			return compareExpr{op: op, left: left, pattern: pattern}, nil
+		}
+		right, err := p.parseOperand()
+		if err != nil {
+			return nil, err
+		}
+		return compareExpr{op: op, left: left, right: right}, nil
+	}
+	return left, nil
+}
+
+func (p *pathParser) parseRegex() (*regexp.Regexp, error) {
+	var raw string
+	switch p.peek() {
+	case '/':
+		end := p.pos + 1
+		for end < len(p.src) && p.src[end] != '/' {
+			if p.src[end] == '\\' {
+				end++
+			}
+			end++
+		}
+		if end >= len(p.src) {
+			return nil, fmt.Errorf("unterminated regex at offset %d", p.pos)
+		}
+		raw = p.src[p.pos+1 : end]
+		p.pos = end + 1
+		flags := ""
+		for p.peek() == 'i' || p.peek() == 's' || p.peek() == 'm' {
+			flags += string(p.peek())
+			p.pos++
+		}
+		if flags != "" {
+			raw = "(?" + flags + ")" + raw
+		}
+	case '\'', '"':
+		s, err := p.parseQuoted()
+		if err != nil {
+			return nil, err
+		}
+		raw = s
+	default:
+		return nil, fmt.Errorf("expected regex at offset %d", p.pos)
+	}
+	re, err := regexp.Compile(raw)
+	if err != nil {
+		return nil, fmt.Errorf("invalid regex: %w", err)
+	}
+	return re, nil
+}
+
+func (p *pathParser) parseOperand() (filterExpr, error) {
+	p.skipSpace()
+	switch c := p.peek(); {
+	case c == '@' || c == '$':
+		path, err := p.parsePath(c)
+		if err != nil {
+			return nil, err
+		}
+		return pathExpr{fromRoot: c == '$', path: path}, nil
+	case c == '\'' || c == '"':
+		s, err := p.parseQuoted()
+		if err != nil {
+			return nil, err
+		}
+		return literalExpr{value: s}, nil
+	case c == '-' || (c >= '0' && c <= '9'):
+		start := p.pos
+		p.pos++
+		for !p.eof() && strings.IndexByte("0123456789.eE+-", p.src[p.pos]) >= 0 {
+			p.pos++
+		}
+		f, err := strconv.ParseFloat(p.src[start:p.pos], 64)
+		if err != nil {
+			return nil, fmt.Errorf("invalid number at offset %d", start)
+		}
+		return literalExpr{value: f}, nil
+	case p.consume("true"):
+		return literalExpr{value: true}, nil
+	case p.consume("false"):
+		return literalExpr{value: false}, nil
+	case p.consume("null"):
+		return literalExpr{value: nil}, nil
+	default:
+		return nil, fmt.Errorf("unexpected %q in filter at offset %d", string(c), p.pos)
+	}
+}
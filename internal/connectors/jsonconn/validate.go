@@ -0,0 +1,132 @@
+package jsonconn
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"sort"
+
+	"github.com/neural-chilli/aceryx/internal/connectors"
+	jsonschema "github.com/santhosh-tekuri/jsonschema/v5"
+)
+
+// SchemaValidator is the standalone schema_validate connector. It shares the
+// validation logic behind json_transform's validate action.
+type SchemaValidator struct{}
+
+func NewSchemaValidator() *SchemaValidator { return &SchemaValidator{} }
+
+func (c *SchemaValidator) Meta() connectors.ConnectorMeta {
+	return connectors.ConnectorMeta{Key: "schema_validate", Name: "Schema Validate", Description: "Validate data against a JSON Schema", Version: "v1", Icon: "pi pi-check-square"}
+}
+
+func (c *SchemaValidator) Auth() connectors.AuthSpec { return connectors.AuthSpec{Type: "none"} }
+
+func (c *SchemaValidator) Triggers() []connectors.TriggerSpec { return nil }
+
+func (c *SchemaValidator) Actions() []connectors.ActionSpec {
+	return []connectors.ActionSpec{
+		action("validate", "Validate", "Validate data against a JSON Schema, optionally failing the step", validate),
+	}
+}
+
+// validate reports {valid, errors}. With fail_on_invalid set, invalid data
+// fails the step so downstream steps never see it.
+func validate(_ context.Context, _ map[string]string, input map[string]any) (map[string]any, error) {
+	data, err := readData(input)
+	if err != nil {
+		return nil, err
+	}
+	schema, ok := input["schema"]
+	if !ok || schema == nil {
+		return nil, fmt.Errorf("schema is required")
+	}
+	violations, err := validateDocument(data, schema)
+	if err != nil {
+		return nil, err
+	}
+	if len(violations) > 0 && readBool(input, "fail_on_invalid", false) {
+		first := violations[0].(map[string]any)
+		return nil, fmt.Errorf("schema validation failed with %d error(s): %s: %s", len(violations), first["path"], first["message"])
+	}
+	return map[string]any{"valid": len(violations) == 0, "errors": violations}, nil
+}
+
+// validateDocument compiles schema (a decoded value or JSON string) and
+// returns one entry per failing leaf: the JSON Pointer of the offending value,
+// the schema keyword location and a message.
+func validateDocument(data any, schema any) ([]any, error) {
+	rawSchema, err := schemaBytes(schema)
+	if err != nil {
+		return nil, err
+	}
+	compiler := jsonschema.NewCompiler()
+	if err := compiler.AddResource("input.schema.json", bytes.NewReader(rawSchema)); err != nil {
+		return nil, fmt.Errorf("invalid schema: %w", err)
+	}
+	compiled, err := compiler.Compile("input.schema.json")
+	if err != nil {
+		return nil, fmt.Errorf("invalid schema: %w", err)
+	}
+
+	// Round-trip through JSON so Go-native values (ints, typed maps) validate
+	// the same way decoded payloads do.
+	rawData, err := json.Marshal(data)
+	if err != nil {
+		return nil, fmt.Errorf("data is not valid json: %w", err)
+	}
+	var doc any
+	if err := json.Unmarshal(rawData, &doc); err != nil {
+		return nil, fmt.Errorf("data is not valid json: %w", err)
+	}
+
+	err = compiled.Validate(doc)
+	if err == nil {
+		return []any{}, nil
+	}
+	var verr *jsonschema.ValidationError
+	if !errors.As(err, &verr) {
+		return nil, err
+	}
+	leaves := collectLeafErrors(verr, nil)
+	sort.SliceStable(leaves, func(i, j int) bool { return leaves[i].InstanceLocation < leaves[j].InstanceLocation })
+	out := make([]any, 0, len(leaves))
+	for _, leaf := range leaves {
+		path := leaf.InstanceLocation
+		if path == "" {
+			path = "/"
+		}
+		out = append(out, map[string]any{
+			"path":    path,
+			"keyword": leaf.KeywordLocation,
+			"message": leaf.Message,
+		})
+	}
+	return out, nil
+}
+
+func collectLeafErrors(err *jsonschema.ValidationError, out []*jsonschema.ValidationError) []*jsonschema.ValidationError {
+	if len(err.Causes) == 0 {
+		return append(out, err)
+	}
+	for _, cause := range err.Causes {
+		out = collectLeafErrors(cause, out)
+	}
+	return out
+}
+
+func schemaBytes(schema any) ([]byte, error) {
+	if s, ok := schema.(string); ok {
+		if !json.Valid([]byte(s)) {
+			return nil, fmt.Errorf("schema must be valid json")
+		}
+		return []byte(s), nil
+	}
+	raw, err := json.Marshal(schema)
+	if err != nil {
+		return nil, fmt.Errorf("invalid schema: %w", err)
+	}
+	return raw, nil
+}
@@ -0,0 +1,382 @@
+package objectstorageconn
+
+import (
+	"context"
+	"encoding/base64"
+	"fmt"
+	"io"
+	"net/http"
+	"strings"
+	"time"
+	"unicode/utf8"
+
+	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/storage"
+	"github.com/neural-chilli/aceryx/internal/storage/azure"
+	"github.com/neural-chilli/aceryx/internal/storage/gcs"
+	"github.com/neural-chilli/aceryx/internal/storage/s3"
+)
+
+const (
+	ProviderS3    = "s3"
+	ProviderGCS   = "gcs"
+	ProviderAzure = "azure"
+
+	// DefaultMaxInlineBytes caps objects returned in the step output. Larger
+	// objects must be streamed to a destination_url.
+	DefaultMaxInlineBytes = 10 << 20
+)
+
+// Opener builds a store for one call from the resolved auth and input.
+type Opener func(ctx context.Context, provider string, auth map[string]string, input map[string]any) (storage.ObjectStore, error)
+
+type Connector struct {
+	providers map[string]bool
+	egress    connectors.EgressPolicy
+	open      Opener
+}
+
+// New returns a connector for the given providers. S3 and S3-compatible
+// stores are always available; GCS and Azure Blob only when listed.
+// source_url and destination_url transfers are restricted by egress.
+func New(providers []string, egress connectors.EgressPolicy) *Connector {
+	enabled := map[string]bool{ProviderS3: true}
+	for _, provider := range providers {
+		if provider = strings.ToLower(strings.TrimSpace(provider)); provider != "" {
+			enabled[provider] = true
+		}
+	}
+	return &Connector{providers: enabled, egress: egress, open: openStore}
+}
+
+func (c *Connector) Meta() connectors.ConnectorMeta {
+	return connectors.ConnectorMeta{Key: "object_storage", Name: "Object Storage", Description: "Read and write objects in S3-compatible stores, GCS and Azure Blob", Version: "v1", Icon: "pi pi-box"}
+}
+
+func (c *Connector) Auth() connectors.AuthSpec {
+	return connectors.AuthSpec{
+		Type: "api_key",
+		Fields: []connectors.AuthField{
+			{Key: "access_key_id", Label: "S3 Access Key ID", Type: "string", Required: false},
+			{Key: "secret_access_key", Label: "S3 Secret Access Key", Type: "password", Required: false},
+			{Key: "gcs_credentials_json", Label: "GCS Service Account JSON", Type: "password", Required: false},
+			{Key: "azure_account_key", Label: "Azure Storage Account Key", Type: "password", Required: false},
+		},
+	}
+}
+
+func (c *Connector) Triggers() []connectors.TriggerSpec { return nil }
+
+func (c *Connector) Actions() []connectors.ActionSpec {
+	return []connectors.ActionSpec{
+		action("get", "Get Object", "Read an object inline or stream it to a destination URL", c.get),
+		action("put", "Put Object", "Write an object from inline content or stream it from a source URL", c.put),
+		action("list", "List Objects", "List objects under a prefix", c.list),
+		action("delete", "Delete Object", "Delete an object", c.delete),
+	}
+}
+
+func action(key, name, description string, execute func(context.Context, map[string]string, map[string]any) (map[string]any, error)) connectors.ActionSpec {
+	return connectors.ActionSpec{
+		Key:          key,
+		Name:         name,
+		Description:  description,
+		InputSchema:  map[string]any{"type": "object"},
+		OutputSchema: map[string]any{"type": "object"},
+		Execute:      execute,
+	}
+}
+
+func (c *Connector) store(ctx context.Context, auth map[string]string, input map[string]any) (storage.ObjectStore, error) {
+	provider := strings.ToLower(readString(input, "provider", ProviderS3))
+	if !c.providers[provider] {
+		return nil, fmt.Errorf("object storage provider %q is not enabled", provider)
+	}
+	return c.open(ctx, provider, auth, input)
+}
+
+func openStore(ctx context.Context, provider string, auth map[string]string, input map[string]any) (storage.ObjectStore, error) {
+	switch provider {
+	case ProviderS3:
+		return s3.New(ctx, s3.Config{
+			Bucket:          readString(input, "bucket", ""),
+			Region:          readString(input, "region", ""),
+			Endpoint:        readString(input, "endpoint", ""),
+			AccessKeyID:     auth["access_key_id"],
+			SecretAccessKey: auth["secret_access_key"],
+		})
+	case ProviderGCS:
+		return gcs.New(ctx, gcs.Config{
+			Bucket:          readString(input, "bucket", ""),
+			CredentialsJSON: auth["gcs_credentials_json"],
+		})
+	case ProviderAzure:
+		return azure.New(ctx, azure.Config{
+			Container:   readString(input, "container", readString(input, "bucket", "")),
+			AccountName: readString(input, "account_name", ""),
+			AccountKey:  auth["azure_account_key"],
+		})
+	}
+	return nil, fmt.Errorf("unknown object storage provider %q", provider)
+}
+
+// get returns the object in "content", or streams it to destination_url
+// with an HTTP PUT so large objects never sit in memory.
+func (c *Connector) get(ctx context.Context, auth map[string]string, input map[string]any) (map[string]any, error) {
+	key, err := requireKey(input)
+	if err != nil {
+		return nil, err
+	}
+	store, err := c.store(ctx, auth, input)
+	if err != nil {
+		return nil, err
+	}
+	rc, meta, err := store.Get(ctx, key)
+	if err != nil {
+		return nil, fmt.Errorf("get object %s: %w", key, err)
+	}
+	defer func() { _ = rc.Close() }()
+	out := map[string]any{"key": key, "content_type": meta.ContentType}
+
+	if destination := readString(input, "destination_url", ""); destination != "" {
+		status, written, err := c.upload(ctx, destination, rc, meta, readStringMap(input["destination_headers"]))
+		if err != nil {
+			return nil, err
+		}
+		out["size"], out["destination_status"] = written, status
+		return out, nil
+	}
+
+	limit := int64(readInt(input, "max_bytes", DefaultMaxInlineBytes))
+	data, err := io.ReadAll(io.LimitReader(rc, limit+1))
+	if err != nil {
+		return nil, fmt.Errorf("read object %s: %w", key, err)
+	}
+	if int64(len(data)) > limit {
+		return nil, fmt.Errorf("object %s exceeds %d bytes; use destination_url to stream it", key, limit)
+	}
+	encoding := readString(input, "encoding", "")
+	if encoding == "" {
+		encoding = "text"
+		if !utf8.Valid(data) {
+			encoding = "base64"
+		}
+	}
+	switch encoding {
+	case "text":
+		out["content"] = string(data)
+	case "base64":
+		out["content"] = base64.StdEncoding.EncodeToString(data)
+	default:
+		return nil, fmt.Errorf("encoding must be text or base64")
+	}
+	out["encoding"], out["size"] = encoding, len(data)
+	return out, nil
+}
+
+// put writes inline content, or streams the body of a GET to source_url
+// straight into the store.
+func (c *Connector) put(ctx context.Context, auth map[string]string, input map[string]any) (map[string]any, error) {
+	key, err := requireKey(input)
+	if err != nil {
+		return nil, err
+	}
+	meta := storage.ObjectMetadata{
+		ContentType: readString(input, "content_type", ""),
+		Custom:      readStringMap(input["metadata"]),
+	}
+	var body io.Reader
+	if source := readString(input, "source_url", ""); source != "" {
+		res, err := c.download(ctx, source, readStringMap(input["source_headers"]))
+		if err != nil {
+			return nil, err
+		}
+		defer func() { _ = res.Body.Close() }()
+		if meta.ContentType == "" {
+			meta.ContentType = res.Header.Get("Content-Type")
+		}
+		if res.ContentLength > 0 {
+			meta.ContentLength = res.ContentLength
+		}
+		body = res.Body
+	} else {
+		content, ok := input["content"].(string)
+		if !ok {
+			return nil, fmt.Errorf("content or source_url is required")
+		}
+		switch readString(input, "encoding", "text") {
+		case "text":
+			body = strings.NewReader(content)
+		case "base64":
+			body = base64.NewDecoder(base64.StdEncoding, strings.NewReader(content))
+		default:
+			return nil, fmt.Errorf("encoding must be text or base64")
+		}
+	}
+	if meta.ContentType == "" {
+		meta.ContentType = "application/octet-stream"
+	}
+	store, err := c.store(ctx, auth, input)
+	if err != nil {
+		return nil, err
+	}
+	counter := &countingReader{r: body}
+	if err := store.Put(ctx, key, counter, meta); err != nil {
+		return nil, fmt.Errorf("put object %s: %w", key, err)
+	}
+	return map[string]any{"key": key, "size": counter.n, "content_type": meta.ContentType}, nil
+}
+
+func (c *Connector) list(ctx context.Context, auth map[string]string, input map[string]any) (map[string]any, error) {
+	store, err := c.store(ctx, auth, input)
+	if err != nil {
+		return nil, err
+	}
+	infos, err := store.List(ctx, readString(input, "prefix", ""), storage.ListOpts{
+		MaxResults: readInt(input, "max_results", 1000),
+		Cursor:     readString(input, "cursor", ""),
+		Delimiter:  readString(input, "delimiter", ""),
+	})
+	if err != nil {
+		return nil, fmt.Errorf("list objects: %w", err)
+	}
+	objects := make([]any, 0, len(infos))
+	for _, info := range infos {
+		item := map[string]any{"key": info.Key, "size": info.Size}
+		if !info.LastModified.IsZero() {
+			item["last_modified"] = info.LastModified.UTC().Format(time.RFC3339)
+		}
+		if info.ContentType != "" {
+			item["content_type"] = info.ContentType
+		}
+		objects = append(objects, item)
+	}
+	return map[string]any{"objects": objects, "count": len(objects)}, nil
+}
+
+func (c *Connector) delete(ctx context.Context, auth map[string]string, input map[string]any) (map[string]any, error) {
+	key, err := requireKey(input)
+	if err != nil {
+		return nil, err
+	}
+	store, err := c.store(ctx, auth, input)
+	if err != nil {
+		return nil, err
+	}
+	if err := store.Delete(ctx, key); err != nil {
+		return nil, fmt.Errorf("delete object %s: %w", key, err)
+	}
+	return map[string]any{"key": key, "deleted": true}, nil
+}
+
+func (c *Connector) download(ctx context.Context, url string, headers map[string]string) (*http.Response, error) {
+	if err := c.egress.CheckURL(url); err != nil {
+		return nil, err
+	}
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, url, nil)
+	if err != nil {
+		return nil, fmt.Errorf("build source request: %w", err)
+	}
+	for key, value := range headers {
+		req.Header.Set(key, value)
+	}
+	res, err := c.egress.Client(0).Do(req)
+	if err != nil {
+		return nil, fmt.Errorf("fetch source_url: %w", err)
+	}
+	if res.StatusCode < http.StatusOK || res.StatusCode >= http.StatusMultipleChoices {
+		_ = res.Body.Close()
+		return nil, fmt.Errorf("fetch source_url failed with status %d", res.StatusCode)
+	}
+	return res, nil
+}
+
+func (c *Connector) upload(ctx context.Context, url string, body io.Reader, meta storage.ObjectMetadata, headers map[string]string) (int, int64, error) {
+	if err := c.egress.CheckURL(url); err != nil {
+		return 0, 0, err
+	}
+	counter := &countingReader{r: body}
+	req, err := http.NewRequestWithContext(ctx, http.MethodPut, url, counter)
+	if err != nil {
+		return 0, 0, fmt.Errorf("build destination request: %w", err)
+	}
+	if meta.ContentLength > 0 {
+		req.ContentLength = meta.ContentLength
+	}
+	if meta.ContentType != "" {
+		req.Header.Set("Content-Type", meta.ContentType)
+	}
+	for key, value := range headers {
+		req.Header.Set(key, value)
+	}
+	res, err := c.egress.Client(0).Do(req)
+	if err != nil {
+		return 0, 0, fmt.Errorf("stream to destination_url: %w", err)
+	}
+	_ = res.Body.Close()
+	if res.StatusCode < http.StatusOK || res.StatusCode >= http.StatusMultipleChoices {
+		return res.StatusCode, counter.n, fmt.Errorf("stream to destination_url failed with status %d", res.StatusCode)
+	}
+	return res.StatusCode, counter.n, nil
+}
+
+type countingReader struct {
+	r io.Reader
+	n int64
+}
+
+func (c *countingReader) Read(p []byte) (int, error) {
+	n, err := c.r.Read(p)
+	c.n += int64(n)
+	return n, err
+}
+
+func requireKey(input map[string]any) (string, error) {
+	key := storage.NormalizeKey(readString(input, "key", ""))
+	if key == "" {
+		return "", fmt.Errorf("key is required")
+	}
+	return key, nil
+}
+
+func readString(input map[string]any, key string, fallback string) string {
+	raw, ok := input[key]
+	if !ok || raw == nil {
+		return fallback
+	}
+	if s, ok := raw.(string); ok {
+		return strings.TrimSpace(s)
+	}
+	return fallback
+}
+
+func readInt(input map[string]any, key string, fallback int) int {
+	raw, ok := input[key]
+	if !ok || raw == nil {
+		return fallback
+	}
+	switch v := raw.(type) {
+	case int:
+		return v
+	case float64:
+		return int(v)
+	}
+	return fallback
+}
+
+func readStringMap(raw any) map[string]string {
+	out := map[string]string{}
+	switch typed := raw.(type) {
+	case map[string]string:
+		for k, v := range typed {
+			out[k] = v
+		}
+	case map[string]any:
+		for k, v := range typed {
+			if s, ok := v.(string); ok {
+				out[k] = s
+			}
+		}
+	}
+	return out
+}
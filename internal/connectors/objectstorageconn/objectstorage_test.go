@@ -0,0 +1,103 @@
+package objectstorageconn
+
+import (
+	"context"
+	"io"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+
+	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/storage"
+	"github.com/neural-chilli/aceryx/internal/storage/local"
+)
+
+func newTestConnector(t *testing.T) *Connector {
+	t.Helper()
+	store, err := local.New(t.TempDir())
+	if err != nil {
+		t.Fatalf("local store: %v", err)
+	}
+	conn := New(nil, connectors.EgressPolicy{AllowPrivate: true})
+	conn.open = func(context.Context, string, map[string]string, map[string]any) (storage.ObjectStore, error) {
+		return store, nil
+	}
+	return conn
+}
+
+func TestPutGetListDelete(t *testing.T) {
+	ctx := context.Background()
+	conn := newTestConnector(t)
+	if _, err := conn.put(ctx, nil, map[string]any{"key": "reports/a.txt", "content": "hello", "content_type": "text/plain"}); err != nil {
+		t.Fatalf("put: %v", err)
+	}
+	if _, err := conn.put(ctx, nil, map[string]any{"key": "reports/b.bin", "content": "AP8=", "encoding": "base64"}); err != nil {
+		t.Fatalf("put base64: %v", err)
+	}
+
+	out, err := conn.get(ctx, nil, map[string]any{"key": "reports/a.txt"})
+	if err != nil || out["content"] != "hello" || out["encoding"] != "text" {
+		t.Fatalf("unexpected get %#v, %v", out, err)
+	}
+	out, err = conn.get(ctx, nil, map[string]any{"key": "reports/b.bin"})
+	if err != nil || out["content"] != "AP8=" || out["encoding"] != "base64" {
+		t.Fatalf("expected binary object back as base64, got %#v, %v", out, err)
+	}
+	if _, err := conn.get(ctx, nil, map[string]any{"key": "reports/a.txt", "max_bytes": 2}); err == nil || !strings.Contains(err.Error(), "destination_url") {
+		t.Fatalf("expected inline size limit, got %v", err)
+	}
+
+	listed, err := conn.list(ctx, nil, map[string]any{"prefix": "reports/"})
+	if err != nil || listed["count"] != 2 {
+		t.Fatalf("unexpected list %#v, %v", listed, err)
+	}
+	if _, err := conn.delete(ctx, nil, map[string]any{"key": "reports/a.txt"}); err != nil {
+		t.Fatalf("delete: %v", err)
+	}
+	if _, err := conn.get(ctx, nil, map[string]any{"key": "reports/a.txt"}); err == nil {
+		t.Fatal("expected deleted object to be gone")
+	}
+}
+
+func TestStreamsFromSourceAndToDestination(t *testing.T) {
+	ctx := context.Background()
+	conn := newTestConnector(t)
+	payload := strings.Repeat("x", 1<<20)
+	var received string
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		switch r.Method {
+		case http.MethodGet:
+			w.Header().Set("Content-Type", "text/csv")
+			_, _ = io.WriteString(w, payload)
+		case http.MethodPut:
+			raw, _ := io.ReadAll(r.Body)
+			received = string(raw)
+			w.WriteHeader(http.StatusCreated)
+		}
+	}))
+	defer srv.Close()
+
+	out, err := conn.put(ctx, nil, map[string]any{"key": "big.csv", "source_url": srv.URL})
+	if err != nil || out["size"] != int64(len(payload)) || out["content_type"] != "text/csv" {
+		t.Fatalf("unexpected streamed put %#v, %v", out, err)
+	}
+	out, err = conn.get(ctx, nil, map[string]any{"key": "big.csv", "destination_url": srv.URL, "max_bytes": 16})
+	if err != nil || out["destination_status"] != http.StatusCreated || received != payload {
+		t.Fatalf("unexpected streamed get %#v, %v", out, err)
+	}
+}
+
+func TestProvidersAreGated(t *testing.T) {
+	conn := New([]string{"gcs"}, connectors.EgressPolicy{})
+	for provider, enabled := range map[string]bool{"s3": true, "gcs": true, "azure": false} {
+		_, err := conn.store(context.Background(), nil, map[string]any{"provider": provider})
+		gated := err != nil && strings.Contains(err.Error(), "not enabled")
+		if gated == enabled {
+			t.Errorf("provider %s: enabled=%v, err=%v", provider, enabled, err)
+		}
+	}
+	if _, err := conn.put(context.Background(), nil, map[string]any{"key": "a", "source_url": "http://169.254.169.254/"}); err == nil {
+		t.Fatal("expected metadata source_url to be refused")
+	}
+}
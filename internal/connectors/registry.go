@@ -1,13 +1,20 @@
 package connectors
 
 import (
+	"context"
+	"log/slog"
 	"sort"
+	"strings"
 	"sync"
+	"time"
+
+	"github.com/neural-chilli/aceryx/internal/search"
 )
 
 type Registry struct {
 	mu         sync.RWMutex
 	connectors map[string]Connector
+	usage      UsageRecorder
 }
 
 func NewRegistry() *Registry {
@@ -27,6 +34,32 @@ func (r *Registry) Register(c Connector) {
 	r.connectors[meta.Key] = c
 }
 
+// SetUsageRecorder sets where RecordUsage sends connector call metrics.
+func (r *Registry) SetUsageRecorder(recorder UsageRecorder) {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	r.usage = recorder
+}
+
+// RecordUsage records one connector call. It is a no-op without a recorder,
+// and recording failures are logged rather than returned so that metrics
+// never fail a call.
+func (r *Registry) RecordUsage(ctx context.Context, rec UsageRecord) {
+	r.mu.RLock()
+	recorder := r.usage
+	r.mu.RUnlock()
+	if recorder == nil {
+		return
+	}
+	if err := recorder.RecordUsage(ctx, rec); err != nil {
+		slog.WarnContext(ctx, "record connector usage failed",
+			"connector", rec.ConnectorKey,
+			"action", rec.ActionKey,
+			"error", err,
+		)
+	}
+}
+
 func (r *Registry) Get(key string) (Connector, bool) {
 	r.mu.RLock()
 	defer r.mu.RUnlock()
@@ -46,19 +79,67 @@ func (r *Registry) List() []ConnectorMeta {
 }
 
 func (r *Registry) Describe() []ConnectorDescriptor {
+	out, _ := r.DescribePage(0, 0, "", false)
+	return out
+}
+
+// SearchDocuments describes every connector for the search index, tagged
+// with its key and action keys.
+func (r *Registry) SearchDocuments() []search.Document {
+	descriptors := r.Describe()
+	out := make([]search.Document, 0, len(descriptors))
+	for _, d := range descriptors {
+		tags := []string{d.Meta.Key}
+		description := []string{d.Meta.Description}
+		for _, action := range d.Actions {
+			tags = append(tags, action.Key)
+			description = append(description, action.Name, action.Description)
+		}
+		out = append(out, search.Document{ID: d.Meta.Key, Name: d.Meta.Name, Tags: tags, Description: strings.Join(description, " ")})
+	}
+	return out
+}
+
+// DescribePage describes one page of connectors ordered by key, or by display
+// name when sortBy is "name", and returns the total connector count. Only the
+// connectors on the page are described. A zero limit returns every connector
+// from offset.
+func (r *Registry) DescribePage(offset, limit int, sortBy string, descending bool) ([]ConnectorDescriptor, int) {
 	r.mu.RLock()
 	defer r.mu.RUnlock()
-	out := make([]ConnectorDescriptor, 0, len(r.connectors))
+	all := make([]Connector, 0, len(r.connectors))
 	for _, c := range r.connectors {
+		all = append(all, c)
+	}
+	sort.Slice(all, func(i, j int) bool {
+		a, b := all[i].Meta(), all[j].Meta()
+		if descending {
+			a, b = b, a
+		}
+		if sortBy == "name" && a.Name != b.Name {
+			return a.Name < b.Name
+		}
+		return a.Key < b.Key
+	})
+	total := len(all)
+	start := min(max(offset, 0), total)
+	end := total
+	if limit > 0 {
+		end = min(start+limit, total)
+	}
+	out := make([]ConnectorDescriptor, 0, end-start)
+	for _, c := range all[start:end] {
 		actions := c.Actions()
 		actionSummaries := make([]ActionSummary, 0, len(actions))
 		for _, a := range actions {
 			actionSummaries = append(actionSummaries, ActionSummary{
-				Key:          a.Key,
-				Name:         a.Name,
-				Description:  a.Description,
-				InputSchema:  a.InputSchema,
-				OutputSchema: a.OutputSchema,
+				Key:             a.Key,
+				Name:            a.Name,
+				Description:     a.Description,
+				InputSchema:     a.InputSchema,
+				OutputSchema:    a.OutputSchema,
+				CacheTTLSeconds: int(a.CacheTTL / time.Second),
+				SensitiveOutput: a.SensitiveOutput,
 			})
 		}
 		out = append(out, ConnectorDescriptor{
@@ -68,8 +149,7 @@ func (r *Registry) Describe() []ConnectorDescriptor {
 			Actions:  actionSummaries,
 		})
 	}
-	sort.Slice(out, func(i, j int) bool { return out[i].Meta.Key < out[j].Meta.Key })
-	return out
+	return out, total
 }
 
 func (r *Registry) GetAction(connectorKey, actionKey string) (ActionSpec, bool) {
@@ -43,3 +43,30 @@ func TestRegistry_RegisterGetListAndAction(t *testing.T) {
 		t.Fatalf("expected ok=true, got %#v", got)
 	}
 }
+
+type namedConnector struct {
+	testConnector
+	key, name string
+}
+
+func (c *namedConnector) Meta() ConnectorMeta { return ConnectorMeta{Key: c.key, Name: c.name} }
+
+func TestRegistry_DescribePage(t *testing.T) {
+	reg := NewRegistry()
+	reg.Register(&namedConnector{key: "a", name: "Zeta"})
+	reg.Register(&namedConnector{key: "b", name: "Alpha"})
+	reg.Register(&namedConnector{key: "c", name: "Mid"})
+
+	page, total := reg.DescribePage(1, 1, "", false)
+	if total != 3 || len(page) != 1 || page[0].Meta.Key != "b" {
+		t.Fatalf("unexpected key page %+v total=%d", page, total)
+	}
+	page, _ = reg.DescribePage(0, 2, "name", true)
+	if len(page) != 2 || page[0].Meta.Name != "Zeta" || page[1].Meta.Name != "Mid" {
+		t.Fatalf("unexpected name desc page %+v", page)
+	}
+	page, total = reg.DescribePage(5, 2, "", false)
+	if total != 3 || len(page) != 0 {
+		t.Fatalf("expected empty page past the end, got %+v", page)
+	}
+}
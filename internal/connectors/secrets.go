@@ -13,6 +13,16 @@ import (
 
 var ErrSecretNotFound = errors.New("secret not found")
 
+// ErrCredentialsMissing is returned when a step's credential profile lacks a
+// secret for a required auth field.
+var ErrCredentialsMissing = errors.New("credentials missing")
+
+// CredentialSecretKey is the secret holding an auth field of a credential
+// profile, e.g. "crm-prod.api_key" for profile crm-prod.
+func CredentialSecretKey(profile, field string) string {
+	return profile + "." + field
+}
+
 type SecretStore interface {
 	Get(ctx context.Context, tenantID uuid.UUID, key string) (string, error)
 }
@@ -20,7 +30,7 @@ type SecretStore interface {
 type EnvSecretStore struct{}
 
 func (s *EnvSecretStore) Get(_ context.Context, _ uuid.UUID, key string) (string, error) {
-	envKey := "ACERYX_SECRET_" + strings.ToUpper(strings.ReplaceAll(key, ".", "_"))
+	envKey := "ACERYX_SECRET_" + strings.ToUpper(strings.NewReplacer(".", "_", "-", "_").Replace(key))
 	value := os.Getenv(envKey)
 	if value == "" {
 		return "", ErrSecretNotFound
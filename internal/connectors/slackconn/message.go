@@ -0,0 +1,217 @@
+package slackconn
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/neural-chilli/aceryx/internal/connectors"
+)
+
+// MessageConnector is the slack_message tool: one action that posts a
+// formatted message through a bot token or an incoming webhook.
+type MessageConnector struct {
+	egress connectors.EgressPolicy
+}
+
+// NewMessageConnector returns a slack_message connector restricted by the
+// given egress policy.
+func NewMessageConnector(policy connectors.EgressPolicy) *MessageConnector {
+	return &MessageConnector{egress: policy}
+}
+
+func (c *MessageConnector) Meta() connectors.ConnectorMeta {
+	return connectors.ConnectorMeta{Key: "slack_message", Name: "Slack Message", Description: "Post a formatted Slack message", Version: "v1", Icon: "pi pi-comments", Category: "Messaging"}
+}
+
+func (c *MessageConnector) Auth() connectors.AuthSpec {
+	return connectors.AuthSpec{Type: "api_key", Fields: []connectors.AuthField{
+		{Key: "bot_token", Label: "Bot Token", Type: "password", Required: false},
+		{Key: "slack_webhook_url", Label: "Incoming Webhook URL", Type: "url", Required: false},
+		{Key: "api_base_url", Label: "API Base URL", Type: "url", Required: false},
+	}}
+}
+
+func (c *MessageConnector) Triggers() []connectors.TriggerSpec { return nil }
+
+func (c *MessageConnector) Actions() []connectors.ActionSpec {
+	return []connectors.ActionSpec{
+		{
+			Key:         "send",
+			Name:        "Send",
+			Description: "Post a message with optional Block Kit blocks and an execution summary",
+			InputSchema: map[string]any{
+				"type": "object",
+				"properties": map[string]any{
+					"channel":         map[string]any{"type": "string", "description": "Channel or user ID; required with a bot token"},
+					"text":            map[string]any{"type": "string", "description": "Message text, also the notification fallback"},
+					"title":           map[string]any{"type": "string"},
+					"fields":          map[string]any{"type": "object", "description": "Label/value pairs shown under the text"},
+					"blocks":          map[string]any{"type": "array", "description": "Block Kit blocks, replacing the generated ones"},
+					"thread_ts":       map[string]any{"type": "string"},
+					"include_summary": map[string]any{"type": "boolean", "description": "Append the case's step states"},
+				},
+			},
+			OutputSchema: map[string]any{"type": "object"},
+			Execute:      c.send,
+		},
+	}
+}
+
+func (c *MessageConnector) send(ctx context.Context, auth map[string]string, input map[string]any) (map[string]any, error) {
+	text := strings.TrimSpace(asString(input["text"]))
+	blocks := slackBlocks(input)
+	if text == "" && len(blocks) == 0 {
+		return nil, fmt.Errorf("text or blocks is required")
+	}
+	if text == "" {
+		text = asString(input["title"])
+	}
+	payload := map[string]any{"text": text}
+	if len(blocks) > 0 {
+		payload["blocks"] = blocks
+	}
+	if ts := asString(input["thread_ts"]); ts != "" {
+		payload["thread_ts"] = ts
+	}
+	opts := connectors.RequestOptions{Timeout: 30 * time.Second, Egress: &c.egress}
+
+	if token := auth["bot_token"]; token != "" {
+		channel := asString(input["channel"])
+		if channel == "" {
+			return nil, fmt.Errorf("channel is required when posting with a bot token")
+		}
+		payload["channel"] = channel
+		base := auth["api_base_url"]
+		if base == "" {
+			base = "https://slack.com/api"
+		}
+		headers := map[string]string{"Authorization": "Bearer " + token}
+		status, _, body, err := connectors.DoJSONRequestWithOptions(ctx, http.MethodPost, strings.TrimRight(base, "/")+"/chat.postMessage", headers, payload, opts)
+		if err != nil {
+			return nil, err
+		}
+		if status < 200 || status >= 300 {
+			return nil, fmt.Errorf("slack api status %d: %s", status, string(body))
+		}
+		out := map[string]any{}
+		if err := json.Unmarshal(body, &out); err != nil {
+			return nil, fmt.Errorf("decode slack response: %w", err)
+		}
+		if ok, _ := out["ok"].(bool); !ok {
+			return nil, fmt.Errorf("slack api error: %v", out["error"])
+		}
+		return map[string]any{"delivered_via": "bot", "channel": out["channel"], "ts": out["ts"]}, nil
+	}
+
+	webhook := auth["slack_webhook_url"]
+	if webhook == "" {
+		return nil, fmt.Errorf("bot_token or slack_webhook_url is required")
+	}
+	status, _, body, err := connectors.DoJSONRequestWithOptions(ctx, http.MethodPost, webhook, nil, payload, opts)
+	if err != nil {
+		return nil, err
+	}
+	if status < 200 || status >= 300 {
+		return nil, fmt.Errorf("slack webhook status %d: %s", status, string(body))
+	}
+	return map[string]any{"delivered_via": "webhook", "status": status}, nil
+}
+
+// slackBlocks returns caller-supplied blocks as is, or builds header, text
+// and field sections. The execution summary is appended in both cases. A
+// plain text message gets no blocks.
+func slackBlocks(input map[string]any) []any {
+	blocks, _ := input["blocks"].([]any)
+	if raw, ok := input["blocks"].(string); ok && strings.TrimSpace(raw) != "" {
+		_ = json.Unmarshal([]byte(raw), &blocks)
+	}
+	summary, hasSummary := input["_execution_summary"].(map[string]any)
+	if len(blocks) == 0 {
+		title, fields := asString(input["title"]), fieldList(input["fields"])
+		if title == "" && len(fields) == 0 && !hasSummary {
+			return nil
+		}
+		if title != "" {
+			blocks = append(blocks, map[string]any{"type": "header", "text": plainText(title)})
+		}
+		if text := asString(input["text"]); text != "" {
+			blocks = append(blocks, section(text))
+		}
+		if len(fields) > 0 {
+			blocks = append(blocks, map[string]any{"type": "section", "fields": fields})
+		}
+	}
+	if hasSummary {
+		blocks = append(blocks, map[string]any{"type": "divider"}, section(summaryMarkdown(summary)))
+	}
+	return blocks
+}
+
+func summaryMarkdown(summary map[string]any) string {
+	var b strings.Builder
+	fmt.Fprintf(&b, "*Case %s* · %s", asString(summary["case_number"]), asString(summary["status"]))
+	steps, _ := summary["steps"].([]any)
+	for _, raw := range steps {
+		step, _ := raw.(map[string]any)
+		fmt.Fprintf(&b, "\n%s `%s` %s", stateIcon(asString(step["state"])), asString(step["step_id"]), asString(step["state"]))
+		if ms, ok := durationMS(step["duration_ms"]); ok {
+			fmt.Fprintf(&b, " (%s)", time.Duration(ms)*time.Millisecond)
+		}
+		if msg := asString(step["error"]); msg != "" {
+			fmt.Fprintf(&b, " — %s", msg)
+		}
+	}
+	return b.String()
+}
+
+func stateIcon(state string) string {
+	switch state {
+	case "completed":
+		return ":white_check_mark:"
+	case "failed":
+		return ":x:"
+	case "active":
+		return ":hourglass_flowing_sand:"
+	case "skipped":
+		return ":fast_forward:"
+	}
+	return ":white_circle:"
+}
+
+func fieldList(raw any) []any {
+	fields, _ := raw.(map[string]any)
+	keys := make([]string, 0, len(fields))
+	for key := range fields {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+	out := make([]any, 0, len(keys))
+	for _, key := range keys {
+		out = append(out, map[string]any{"type": "mrkdwn", "text": fmt.Sprintf("*%s*\n%v", key, fields[key])})
+	}
+	return out
+}
+
+// durationMS accepts the summary's int64 as well as a JSON-decoded number.
+func durationMS(raw any) (int64, bool) {
+	switch v := raw.(type) {
+	case int64:
+		return v, true
+	case float64:
+		return int64(v), true
+	}
+	return 0, false
+}
+
+func section(text string) map[string]any {
+	return map[string]any{"type": "section", "text": map[string]any{"type": "mrkdwn", "text": text}}
+}
+
+func plainText(text string) map[string]any {
+	return map[string]any{"type": "plain_text", "text": text}
+}
@@ -0,0 +1,69 @@
+package slackconn
+
+import (
+	"context"
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+
+	"github.com/neural-chilli/aceryx/internal/connectors"
+)
+
+func TestMessageSendsBlocksWithSummary(t *testing.T) {
+	var got map[string]any
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		_ = json.NewDecoder(r.Body).Decode(&got)
+		if r.URL.Path == "/chat.postMessage" {
+			if r.Header.Get("Authorization") != "Bearer xoxb-1" {
+				t.Errorf("missing bot token")
+			}
+			_, _ = w.Write([]byte(`{"ok":true,"channel":"C1","ts":"1.2"}`))
+			return
+		}
+		_, _ = w.Write([]byte("ok"))
+	}))
+	defer srv.Close()
+	conn := NewMessageConnector(connectors.EgressPolicy{AllowPrivate: true})
+	input := map[string]any{
+		"channel": "C1",
+		"title":   "Claim approved",
+		"text":    "Payout scheduled",
+		"fields":  map[string]any{"Amount": 1200},
+		"_execution_summary": map[string]any{
+			"case_number": "CLM-7",
+			"status":      "completed",
+			"steps": []any{
+				map[string]any{"step_id": "review", "state": "completed", "duration_ms": int64(1500)},
+				map[string]any{"step_id": "notify", "state": "failed", "error": "timeout"},
+			},
+		},
+	}
+
+	out, err := conn.send(context.Background(), map[string]string{"bot_token": "xoxb-1", "api_base_url": srv.URL}, input)
+	if err != nil || out["ts"] != "1.2" || out["delivered_via"] != "bot" {
+		t.Fatalf("unexpected bot result %#v, %v", out, err)
+	}
+	blocks, _ := got["blocks"].([]any)
+	if len(blocks) != 5 || got["channel"] != "C1" || got["text"] != "Payout scheduled" {
+		t.Fatalf("unexpected payload %#v", got)
+	}
+	summary, _ := json.Marshal(blocks[4])
+	for _, want := range []string{"Case CLM-7", "`review` completed (1.5s)", "`notify` failed — timeout"} {
+		if !strings.Contains(string(summary), want) {
+			t.Fatalf("summary %s missing %q", summary, want)
+		}
+	}
+
+	out, err = conn.send(context.Background(), map[string]string{"slack_webhook_url": srv.URL + "/hook"}, map[string]any{"text": "plain"})
+	if err != nil || out["delivered_via"] != "webhook" {
+		t.Fatalf("unexpected webhook result %#v, %v", out, err)
+	}
+	if _, ok := got["blocks"]; ok || got["text"] != "plain" {
+		t.Fatalf("expected plain text webhook payload, got %#v", got)
+	}
+	if _, err := conn.send(context.Background(), nil, map[string]any{"text": "x"}); err == nil {
+		t.Fatal("expected missing credentials to fail")
+	}
+}
@@ -0,0 +1,242 @@
+package soapconn
+
+import (
+	"context"
+	"fmt"
+	"net/http"
+	"strings"
+	"time"
+
+	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/connectors/xmlconn"
+)
+
+const (
+	soap11Namespace = "http://schemas.xmlsoap.org/soap/envelope/"
+	soap12Namespace = "http://www.w3.org/2003/05/soap-envelope"
+)
+
+type Connector struct {
+	egress connectors.EgressPolicy
+}
+
+// New returns a connector that refuses private and link-local destinations.
+func New() *Connector { return &Connector{} }
+
+// NewWithEgress returns a connector restricted by the given egress policy.
+func NewWithEgress(policy connectors.EgressPolicy) *Connector {
+	return &Connector{egress: policy}
+}
+
+func (c *Connector) Meta() connectors.ConnectorMeta {
+	return connectors.ConnectorMeta{Key: "soap", Name: "SOAP", Description: "Call SOAP 1.1 and 1.2 services", Version: "v1", Icon: "pi pi-server"}
+}
+
+func (c *Connector) Auth() connectors.AuthSpec { return connectors.AuthSpec{Type: "none"} }
+
+func (c *Connector) Triggers() []connectors.TriggerSpec { return nil }
+
+func (c *Connector) Actions() []connectors.ActionSpec {
+	return []connectors.ActionSpec{
+		{
+			Key:          "request",
+			Name:         "Request",
+			Description:  "Send a SOAP envelope and return the response body as JSON",
+			InputSchema:  map[string]any{"type": "object"},
+			OutputSchema: map[string]any{"type": "object"},
+			Execute:      c.request,
+		},
+	}
+}
+
+func (c *Connector) request(ctx context.Context, _ map[string]string, input map[string]any) (map[string]any, error) {
+	url := readString(input, "url", "")
+	if url == "" {
+		return nil, fmt.Errorf("url is required")
+	}
+	version := readString(input, "version", "1.1")
+	if version != "1.1" && version != "1.2" {
+		return nil, fmt.Errorf("version must be 1.1 or 1.2")
+	}
+	envelope, err := BuildEnvelope(version, readString(input, "operation", ""), readString(input, "namespace", ""), input["header"], input["body"])
+	if err != nil {
+		return nil, err
+	}
+
+	headers := readStringMap(input["http_headers"])
+	soapAction := readString(input, "soap_action", "")
+	if version == "1.1" {
+		headers["Content-Type"] = "text/xml; charset=utf-8"
+		headers["SOAPAction"] = `"` + soapAction + `"`
+	} else {
+		contentType := "application/soap+xml; charset=utf-8"
+		if soapAction != "" {
+			contentType += `; action="` + soapAction + `"`
+		}
+		headers["Content-Type"] = contentType
+	}
+	opts := connectors.RequestOptions{
+		Timeout:          time.Duration(readInt(input, "timeout_seconds", 30)) * time.Second,
+		MaxRetries:       readInt(input, "max_retries", 0),
+		MaxResponseBytes: int64(readInt(input, "max_response_bytes", connectors.DefaultMaxResponseBytes)),
+		Egress:           &c.egress,
+	}
+	status, _, payload, err := connectors.DoRequestWithOptions(ctx, http.MethodPost, url, headers, []byte(envelope), opts)
+	if err != nil {
+		return nil, err
+	}
+
+	// Faults usually arrive as 500s, so read the envelope before the status.
+	parsed, perr := xmlconn.Parse(string(payload), nil)
+	if perr != nil {
+		if status < http.StatusOK || status >= http.StatusMultipleChoices {
+			return nil, fmt.Errorf("soap request failed with status %d: %s", status, string(payload))
+		}
+		return nil, fmt.Errorf("soap response: %w", perr)
+	}
+	body := responseBody(parsed)
+	if fault, ok := body["Fault"]; ok {
+		return nil, fmt.Errorf("soap fault (status %d): %s", status, describeFault(fault))
+	}
+	if status < http.StatusOK || status >= http.StatusMultipleChoices {
+		return nil, fmt.Errorf("soap request failed with status %d: %s", status, string(payload))
+	}
+	return map[string]any{
+		"status": status,
+		"body":   body,
+		"raw":    string(payload),
+	}, nil
+}
+
+// BuildEnvelope wraps body in a SOAP envelope. With an operation, body is
+// rendered inside an operation element in namespace; without one it is
+// placed in soap:Body as is. body and header may be JSON in the xml_transform
+// shape or a string of XML copied verbatim.
+func BuildEnvelope(version, operation, namespace string, header, body any) (string, error) {
+	envNamespace := soap11Namespace
+	if version == "1.2" {
+		envNamespace = soap12Namespace
+	}
+	content := xmlContent(body)
+	if operation != "" {
+		name := operation
+		fields := map[string]any{}
+		if namespace != "" {
+			name = "m:" + operation
+			fields["@xmlns:m"] = namespace
+		}
+		if m, ok := content.(map[string]any); ok {
+			for key, value := range m {
+				fields[key] = value
+			}
+		}
+		content = map[string]any{name: fields}
+	}
+	envelope := map[string]any{
+		"@xmlns:soap": envNamespace,
+		"#order":      []any{"soap:Header", "soap:Body"},
+		"soap:Body":   content,
+	}
+	if header != nil {
+		envelope["soap:Header"] = xmlContent(header)
+	}
+	return xmlconn.Render("soap:Envelope", envelope, false)
+}
+
+func xmlContent(value any) any {
+	switch typed := value.(type) {
+	case nil:
+		return map[string]any{}
+	case string:
+		return map[string]any{"#xml": typed}
+	default:
+		return typed
+	}
+}
+
+func responseBody(parsed map[string]any) map[string]any {
+	envelope, _ := parsed["Envelope"].(map[string]any)
+	body, _ := envelope["Body"].(map[string]any)
+	if body == nil {
+		return map[string]any{}
+	}
+	return body
+}
+
+// describeFault reads faultcode/faultstring (1.1) or Code/Reason (1.2).
+func describeFault(fault any) string {
+	m, ok := fault.(map[string]any)
+	if !ok {
+		return fmt.Sprint(fault)
+	}
+	code, reason := text(m["faultcode"]), text(m["faultstring"])
+	if code == "" {
+		code = text(m["Code"])
+	}
+	if reason == "" {
+		reason = text(m["Reason"])
+	}
+	return strings.TrimSpace(strings.Trim(code+": "+reason, ": "))
+}
+
+// text digs the first string out of a parsed element, so Code/Value and
+// Reason/Text resolve to their content.
+func text(value any) string {
+	switch typed := value.(type) {
+	case string:
+		return typed
+	case []any:
+		if len(typed) > 0 {
+			return text(typed[0])
+		}
+	case map[string]any:
+		for _, key := range []string{"#text", "Value", "Text"} {
+			if s := text(typed[key]); s != "" {
+				return s
+			}
+		}
+	}
+	return ""
+}
+
+func readString(input map[string]any, key string, fallback string) string {
+	raw, ok := input[key]
+	if !ok || raw == nil {
+		return fallback
+	}
+	if s, ok := raw.(string); ok {
+		return s
+	}
+	return fallback
+}
+
+func readInt(input map[string]any, key string, fallback int) int {
+	raw, ok := input[key]
+	if !ok || raw == nil {
+		return fallback
+	}
+	switch v := raw.(type) {
+	case int:
+		return v
+	case float64:
+		return int(v)
+	}
+	return fallback
+}
+
+func readStringMap(raw any) map[string]string {
+	out := map[string]string{}
+	switch typed := raw.(type) {
+	case map[string]string:
+		for k, v := range typed {
+			out[k] = v
+		}
+	case map[string]any:
+		for k, v := range typed {
+			if s, ok := v.(string); ok {
+				out[k] = s
+			}
+		}
+	}
+	return out
+}
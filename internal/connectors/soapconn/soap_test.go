@@ -0,0 +1,70 @@
+package soapconn
+
+import (
+	"context"
+	"io"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+
+	"github.com/neural-chilli/aceryx/internal/connectors"
+)
+
+func TestBuildEnvelope(t *testing.T) {
+	got, err := BuildEnvelope("1.1", "GetQuote", "urn:quotes", nil, map[string]any{"symbol": "ACX"})
+	if err != nil {
+		t.Fatalf("build: %v", err)
+	}
+	want := `<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/"><soap:Body><m:GetQuote xmlns:m="urn:quotes"><symbol>ACX</symbol></m:GetQuote></soap:Body></soap:Envelope>`
+	if got != want {
+		t.Fatalf("unexpected envelope\n got %s\nwant %s", got, want)
+	}
+
+	got, err = BuildEnvelope("1.2", "", "", `<auth token="t"/>`, `<Ping/>`)
+	if err != nil {
+		t.Fatalf("build raw: %v", err)
+	}
+	if !strings.Contains(got, soap12Namespace) || !strings.Contains(got, `<soap:Header><auth token="t"/></soap:Header><soap:Body><Ping/></soap:Body>`) {
+		t.Fatalf("unexpected raw envelope %s", got)
+	}
+}
+
+func TestRequestReturnsBodyAndSurfacesFaults(t *testing.T) {
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		raw, _ := io.ReadAll(r.Body)
+		if r.Header.Get("SOAPAction") != `"urn:quotes#GetQuote"` || !strings.HasPrefix(r.Header.Get("Content-Type"), "text/xml") {
+			t.Errorf("unexpected headers %v", r.Header)
+		}
+		if strings.Contains(string(raw), "BAD") {
+			w.WriteHeader(http.StatusInternalServerError)
+			_, _ = io.WriteString(w, `<s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/"><s:Body><s:Fault><faultcode>s:Client</faultcode><faultstring>unknown symbol</faultstring></s:Fault></s:Body></s:Envelope>`)
+			return
+		}
+		_, _ = io.WriteString(w, `<s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/"><s:Body><GetQuoteResponse><price>12.5</price></GetQuoteResponse></s:Body></s:Envelope>`)
+	}))
+	defer srv.Close()
+
+	conn := NewWithEgress(connectors.EgressPolicy{AllowPrivate: true})
+	input := map[string]any{
+		"url":         srv.URL,
+		"soap_action": "urn:quotes#GetQuote",
+		"operation":   "GetQuote",
+		"namespace":   "urn:quotes",
+		"body":        map[string]any{"symbol": "ACX"},
+	}
+	out, err := conn.request(context.Background(), nil, input)
+	if err != nil {
+		t.Fatalf("request: %v", err)
+	}
+	body, _ := out["body"].(map[string]any)
+	quote, _ := body["GetQuoteResponse"].(map[string]any)
+	if quote["price"] != "12.5" || out["status"] != http.StatusOK {
+		t.Fatalf("unexpected output %#v", out)
+	}
+
+	input["body"] = map[string]any{"symbol": "BAD"}
+	if _, err := conn.request(context.Background(), nil, input); err == nil || !strings.Contains(err.Error(), "s:Client: unknown symbol") {
+		t.Fatalf("expected soap fault, got %v", err)
+	}
+}
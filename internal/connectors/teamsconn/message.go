@@ -0,0 +1,205 @@
+package teamsconn
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"net/url"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/neural-chilli/aceryx/internal/connectors"
+)
+
+const adaptiveCardType = "application/vnd.microsoft.card.adaptive"
+
+// MessageConnector is the teams_message tool: one action that posts an
+// Adaptive Card through a webhook or, with a Graph token, to a channel.
+type MessageConnector struct {
+	egress connectors.EgressPolicy
+}
+
+// NewMessageConnector returns a teams_message connector restricted by the
+// given egress policy.
+func NewMessageConnector(policy connectors.EgressPolicy) *MessageConnector {
+	return &MessageConnector{egress: policy}
+}
+
+func (c *MessageConnector) Meta() connectors.ConnectorMeta {
+	return connectors.ConnectorMeta{Key: "teams_message", Name: "Teams Message", Description: "Post an Adaptive Card to Microsoft Teams", Version: "v1", Icon: "pi pi-microsoft", Category: "Messaging"}
+}
+
+func (c *MessageConnector) Auth() connectors.AuthSpec {
+	return connectors.AuthSpec{Type: "api_key", Fields: []connectors.AuthField{
+		{Key: "teams_webhook_url", Label: "Workflow Webhook URL", Type: "url", Required: false},
+		{Key: "graph_token", Label: "Microsoft Graph Token", Type: "password", Required: false},
+		{Key: "graph_base_url", Label: "Graph Base URL", Type: "url", Required: false},
+	}}
+}
+
+func (c *MessageConnector) Triggers() []connectors.TriggerSpec { return nil }
+
+func (c *MessageConnector) Actions() []connectors.ActionSpec {
+	return []connectors.ActionSpec{
+		{
+			Key:         "send",
+			Name:        "Send",
+			Description: "Post an Adaptive Card with optional facts and an execution summary",
+			InputSchema: map[string]any{
+				"type": "object",
+				"properties": map[string]any{
+					"team_id":         map[string]any{"type": "string", "description": "Required with a Graph token"},
+					"channel_id":      map[string]any{"type": "string", "description": "Required with a Graph token"},
+					"title":           map[string]any{"type": "string"},
+					"text":            map[string]any{"type": "string"},
+					"fields":          map[string]any{"type": "object", "description": "Label/value pairs shown as facts"},
+					"card":            map[string]any{"type": "object", "description": "Adaptive Card, replacing the generated one"},
+					"include_summary": map[string]any{"type": "boolean", "description": "Append the case's step states"},
+				},
+			},
+			OutputSchema: map[string]any{"type": "object"},
+			Execute:      c.send,
+		},
+	}
+}
+
+func (c *MessageConnector) send(ctx context.Context, auth map[string]string, input map[string]any) (map[string]any, error) {
+	card, err := adaptiveCard(input)
+	if err != nil {
+		return nil, err
+	}
+	opts := connectors.RequestOptions{Timeout: 30 * time.Second, Egress: &c.egress}
+
+	if token := auth["graph_token"]; token != "" {
+		teamID, channelID := asString(input["team_id"]), asString(input["channel_id"])
+		if teamID == "" || channelID == "" {
+			return nil, fmt.Errorf("team_id and channel_id are required when posting with a Graph token")
+		}
+		content, err := json.Marshal(card)
+		if err != nil {
+			return nil, fmt.Errorf("marshal adaptive card: %w", err)
+		}
+		base := auth["graph_base_url"]
+		if base == "" {
+			base = "https://graph.microsoft.com/v1.0"
+		}
+		endpoint := fmt.Sprintf("%s/teams/%s/channels/%s/messages", strings.TrimRight(base, "/"), url.PathEscape(teamID), url.PathEscape(channelID))
+		payload := map[string]any{
+			"body":        map[string]any{"contentType": "html", "content": `<attachment id="card"></attachment>`},
+			"attachments": []any{map[string]any{"id": "card", "contentType": adaptiveCardType, "content": string(content)}},
+		}
+		status, _, body, err := connectors.DoJSONRequestWithOptions(ctx, http.MethodPost, endpoint, map[string]string{"Authorization": "Bearer " + token}, payload, opts)
+		if err != nil {
+			return nil, err
+		}
+		if status < 200 || status >= 300 {
+			return nil, fmt.Errorf("teams graph status %d: %s", status, string(body))
+		}
+		out := map[string]any{}
+		_ = json.Unmarshal(body, &out)
+		return map[string]any{"delivered_via": "graph", "message_id": out["id"]}, nil
+	}
+
+	webhook := auth["teams_webhook_url"]
+	if webhook == "" {
+		return nil, fmt.Errorf("teams_webhook_url or graph_token is required")
+	}
+	payload := map[string]any{
+		"type":        "message",
+		"attachments": []any{map[string]any{"contentType": adaptiveCardType, "content": card}},
+	}
+	status, _, body, err := connectors.DoJSONRequestWithOptions(ctx, http.MethodPost, webhook, nil, payload, opts)
+	if err != nil {
+		return nil, err
+	}
+	if status < 200 || status >= 300 {
+		return nil, fmt.Errorf("teams webhook status %d: %s", status, string(body))
+	}
+	return map[string]any{"delivered_via": "webhook", "status": status}, nil
+}
+
+// adaptiveCard returns the caller's card, or builds one from title, text and
+// fields. The execution summary is appended to the body in both cases.
+func adaptiveCard(input map[string]any) (map[string]any, error) {
+	card, _ := input["card"].(map[string]any)
+	if raw, ok := input["card"].(string); ok && strings.TrimSpace(raw) != "" {
+		if err := json.Unmarshal([]byte(raw), &card); err != nil {
+			return nil, fmt.Errorf("card is not valid JSON: %w", err)
+		}
+	}
+	if card == nil {
+		title, text := asString(input["title"]), asString(input["text"])
+		facts := factList(input["fields"])
+		if title == "" && text == "" && len(facts) == 0 {
+			return nil, fmt.Errorf("title, text, fields or card is required")
+		}
+		var body []any
+		if title != "" {
+			body = append(body, map[string]any{"type": "TextBlock", "text": title, "size": "Medium", "weight": "Bolder", "wrap": true})
+		}
+		if text != "" {
+			body = append(body, map[string]any{"type": "TextBlock", "text": text, "wrap": true})
+		}
+		if len(facts) > 0 {
+			body = append(body, map[string]any{"type": "FactSet", "facts": facts})
+		}
+		card = map[string]any{
+			"type":    "AdaptiveCard",
+			"$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+			"version": "1.4",
+			"body":    body,
+		}
+	}
+	if summary, ok := input["_execution_summary"].(map[string]any); ok {
+		body, _ := card["body"].([]any)
+		card["body"] = append(body, summaryBlocks(summary)...)
+	}
+	return card, nil
+}
+
+func summaryBlocks(summary map[string]any) []any {
+	steps, _ := summary["steps"].([]any)
+	facts := make([]any, 0, len(steps))
+	for _, raw := range steps {
+		step, _ := raw.(map[string]any)
+		value := asString(step["state"])
+		if ms, ok := durationMS(step["duration_ms"]); ok {
+			value += fmt.Sprintf(" (%s)", time.Duration(ms)*time.Millisecond)
+		}
+		if msg := asString(step["error"]); msg != "" {
+			value += " — " + msg
+		}
+		facts = append(facts, map[string]any{"title": asString(step["step_id"]), "value": value})
+	}
+	return []any{
+		map[string]any{"type": "TextBlock", "text": fmt.Sprintf("Case %s · %s", asString(summary["case_number"]), asString(summary["status"])), "weight": "Bolder", "separator": true, "wrap": true},
+		map[string]any{"type": "FactSet", "facts": facts},
+	}
+}
+
+func factList(raw any) []any {
+	fields, _ := raw.(map[string]any)
+	keys := make([]string, 0, len(fields))
+	for key := range fields {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+	out := make([]any, 0, len(keys))
+	for _, key := range keys {
+		out = append(out, map[string]any{"title": key, "value": fmt.Sprint(fields[key])})
+	}
+	return out
+}
+
+// durationMS accepts the summary's int64 as well as a JSON-decoded number.
+func durationMS(raw any) (int64, bool) {
+	switch v := raw.(type) {
+	case int64:
+		return v, true
+	case float64:
+		return int64(v), true
+	}
+	return 0, false
+}
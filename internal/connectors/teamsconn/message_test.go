@@ -0,0 +1,57 @@
+package teamsconn
+
+import (
+	"context"
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+
+	"github.com/neural-chilli/aceryx/internal/connectors"
+)
+
+func TestMessageSendsAdaptiveCard(t *testing.T) {
+	var got map[string]any
+	var path string
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		path = r.URL.Path
+		_ = json.NewDecoder(r.Body).Decode(&got)
+		w.WriteHeader(http.StatusCreated)
+		_, _ = w.Write([]byte(`{"id":"m1"}`))
+	}))
+	defer srv.Close()
+	conn := NewMessageConnector(connectors.EgressPolicy{AllowPrivate: true})
+	input := map[string]any{
+		"title":  "Claim approved",
+		"fields": map[string]any{"Amount": "1200"},
+		"_execution_summary": map[string]any{
+			"case_number": "CLM-7",
+			"status":      "completed",
+			"steps":       []any{map[string]any{"step_id": "review", "state": "completed"}},
+		},
+	}
+
+	out, err := conn.send(context.Background(), map[string]string{"teams_webhook_url": srv.URL}, input)
+	if err != nil || out["delivered_via"] != "webhook" {
+		t.Fatalf("unexpected webhook result %#v, %v", out, err)
+	}
+	attachments, _ := got["attachments"].([]any)
+	attachment, _ := attachments[0].(map[string]any)
+	card, _ := attachment["content"].(map[string]any)
+	body, _ := card["body"].([]any)
+	if got["type"] != "message" || attachment["contentType"] != adaptiveCardType || len(body) != 4 {
+		t.Fatalf("unexpected webhook payload %#v", got)
+	}
+
+	input["team_id"], input["channel_id"] = "T 1", "19:abc"
+	out, err = conn.send(context.Background(), map[string]string{"graph_token": "tok", "graph_base_url": srv.URL}, input)
+	if err != nil || out["message_id"] != "m1" {
+		t.Fatalf("unexpected graph result %#v, %v", out, err)
+	}
+	if path != "/teams/T 1/channels/19:abc/messages" {
+		t.Fatalf("unexpected graph path %q", path)
+	}
+	if _, err := conn.send(context.Background(), map[string]string{"graph_token": "tok"}, map[string]any{}); err == nil {
+		t.Fatal("expected empty message to fail")
+	}
+}
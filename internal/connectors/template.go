@@ -2,6 +2,8 @@ package connectors
 
 import (
 	"fmt"
+	"html"
+	"net/url"
 	"regexp"
 	"strconv"
 	"strings"
@@ -11,6 +13,22 @@ import (
 var templatePattern = regexp.MustCompile(`\{\{\s*([^{}]+?)\s*\}\}`)
 
 func ResolveTemplateString(raw string, ctx map[string]any) string {
+	return resolveTemplate(raw, ctx, func(s string) string { return s })
+}
+
+// ResolveTemplateHTML is ResolveTemplateString for HTML output: substituted
+// values are escaped, the template text itself is not.
+func ResolveTemplateHTML(raw string, ctx map[string]any) string {
+	return resolveTemplate(raw, ctx, html.EscapeString)
+}
+
+// ResolveTemplateURL is ResolveTemplateString for URLs: substituted values
+// are path-escaped so they cannot change the URL's structure.
+func ResolveTemplateURL(raw string, ctx map[string]any) string {
+	return resolveTemplate(raw, ctx, url.PathEscape)
+}
+
+func resolveTemplate(raw string, ctx map[string]any, escape func(string) string) string {
 	if raw == "" {
 		return ""
 	}
@@ -26,7 +44,7 @@ func ResolveTemplateString(raw string, ctx map[string]any) string {
 		if strings.HasPrefix(path, "secrets.") {
 			key := strings.TrimPrefix(path, "secrets.")
 			if resolver, ok := ctx["__secret_resolver"].(func(string) string); ok {
-				return resolver(key)
+				return escape(resolver(key))
 			}
 			return ""
 		}
@@ -34,7 +52,7 @@ func ResolveTemplateString(raw string, ctx map[string]any) string {
 		if !ok || value == nil {
 			return ""
 		}
-		return stringifyTemplateValue(value)
+		return escape(stringifyTemplateValue(value))
 	})
 }
 
@@ -38,3 +38,12 @@ func TestResolveTemplateStringAndFunctions(t *testing.T) {
 		t.Fatalf("expected tenant branding company name, got %q", got)
 	}
 }
+
+func TestResolveTemplateHTMLEscapesValues(t *testing.T) {
+	ctx := map[string]any{"request": map[string]any{"name": `<script>alert("x")</script>`}}
+	got := ResolveTemplateHTML("<p>Hello {{request.name}}</p>", ctx)
+	want := "<p>Hello &lt;script&gt;alert(&#34;x&#34;)&lt;/script&gt;</p>"
+	if got != want {
+		t.Fatalf("expected %q, got %q", want, got)
+	}
+}
@@ -0,0 +1,139 @@
+package templateconn
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"fmt"
+	"strings"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/agents"
+	"github.com/neural-chilli/aceryx/internal/connectors"
+)
+
+// TemplateStore resolves stored prompt templates.
+type TemplateStore interface {
+	Resolve(ctx context.Context, tenantID uuid.UUID, ref string, version int) (agents.PromptTemplate, error)
+}
+
+type Connector struct {
+	db        *sql.DB
+	templates TemplateStore
+}
+
+func New(db *sql.DB, templates TemplateStore) *Connector {
+	return &Connector{db: db, templates: templates}
+}
+
+func (c *Connector) Meta() connectors.ConnectorMeta {
+	return connectors.ConnectorMeta{Key: "render_template", Name: "Render Template", Description: "Render text from a template and case data", Version: "v1", Icon: "pi pi-file-edit"}
+}
+
+func (c *Connector) Auth() connectors.AuthSpec { return connectors.AuthSpec{Type: "none"} }
+
+func (c *Connector) Triggers() []connectors.TriggerSpec { return nil }
+
+func (c *Connector) Actions() []connectors.ActionSpec {
+	return []connectors.ActionSpec{
+		{
+			Key:         "render",
+			Name:        "Render",
+			Description: "Render an inline or stored template to text",
+			InputSchema: map[string]any{
+				"type": "object",
+				"properties": map[string]any{
+					"template":    map[string]any{"type": "string", "description": "Template source; Go template syntax"},
+					"template_id": map[string]any{"type": "string", "description": "Stored prompt template name, optionally name_vN"},
+					"version":     map[string]any{"type": "integer"},
+					"data":        map[string]any{"type": "object", "description": "Values available at the template root"},
+				},
+			},
+			OutputSchema: map[string]any{"type": "object"},
+			Execute:      c.render,
+			RawInputKeys: []string{"template"},
+		},
+	}
+}
+
+// render exposes data at the template root, alongside "case" (the running
+// case, when there is one) and "now". Keys in data win.
+func (c *Connector) render(ctx context.Context, _ map[string]string, input map[string]any) (map[string]any, error) {
+	source, _ := input["template"].(string)
+	ref := strings.TrimSpace(asString(input["template_id"]))
+	if strings.TrimSpace(source) == "" && ref == "" {
+		return nil, fmt.Errorf("template or template_id is required")
+	}
+	out := map[string]any{}
+	if ref != "" {
+		if c.templates == nil {
+			return nil, fmt.Errorf("stored templates are not configured")
+		}
+		tenantID, err := uuid.Parse(asString(input["_tenant_id"]))
+		if err != nil {
+			return nil, fmt.Errorf("template_id requires a tenant")
+		}
+		tpl, err := c.templates.Resolve(ctx, tenantID, ref, asInt(input["version"]))
+		if err != nil {
+			return nil, err
+		}
+		source = tpl.Template
+		out["template_id"], out["version"] = tpl.Name, tpl.Version
+	}
+
+	data := map[string]any{}
+	if caseCtx, err := c.loadCase(ctx, input); err != nil {
+		return nil, err
+	} else if caseCtx != nil {
+		data["case"] = caseCtx
+	}
+	data["now"] = time.Now().UTC().Format(time.RFC3339)
+	if extra, ok := input["data"].(map[string]any); ok {
+		for key, value := range extra {
+			data[key] = value
+		}
+	}
+	text, err := agents.RenderTemplate(source, data)
+	if err != nil {
+		return nil, err
+	}
+	out["text"] = text
+	return out, nil
+}
+
+func (c *Connector) loadCase(ctx context.Context, input map[string]any) (map[string]any, error) {
+	caseID, err := uuid.Parse(asString(input["_case_id"]))
+	if err != nil || c.db == nil {
+		return nil, nil
+	}
+	var (
+		caseNumber, status string
+		raw                []byte
+	)
+	if err := c.db.QueryRowContext(ctx, `SELECT case_number, status, data FROM cases WHERE id = $1`, caseID).Scan(&caseNumber, &status, &raw); err != nil {
+		return nil, fmt.Errorf("load case for template: %w", err)
+	}
+	caseData := map[string]any{}
+	if len(raw) > 0 {
+		if err := json.Unmarshal(raw, &caseData); err != nil {
+			return nil, fmt.Errorf("decode case data for template: %w", err)
+		}
+	}
+	return map[string]any{"id": caseID.String(), "case_number": caseNumber, "status": status, "data": caseData}, nil
+}
+
+func asString(v any) string {
+	s, _ := v.(string)
+	return s
+}
+
+func asInt(v any) int {
+	switch n := v.(type) {
+	case int:
+		return n
+	case float64:
+		return int(n)
+	}
+	return 0
+}
@@ -0,0 +1,49 @@
+package templateconn
+
+import (
+	"context"
+	"errors"
+	"testing"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/agents"
+)
+
+type stubTemplates map[string]agents.PromptTemplate
+
+func (s stubTemplates) Resolve(_ context.Context, _ uuid.UUID, ref string, _ int) (agents.PromptTemplate, error) {
+	tpl, ok := s[ref]
+	if !ok {
+		return agents.PromptTemplate{}, errors.New("not found")
+	}
+	return tpl, nil
+}
+
+func TestRenderInlineAndStoredTemplates(t *testing.T) {
+	conn := New(nil, stubTemplates{"welcome": {Name: "welcome", Version: 3, Template: "Dear {{.name}}, total {{formatCurrency .total}}"}})
+	ctx := context.Background()
+
+	out, err := conn.render(ctx, nil, map[string]any{
+		"template": "{{range .items}}- {{.}}\n{{end}}{{toJSON .meta}}",
+		"data":     map[string]any{"items": []any{"a", "b"}, "meta": map[string]any{"n": 2}},
+	})
+	if err != nil || out["text"] != "- a\n- b\n{\"n\":2}" {
+		t.Fatalf("unexpected inline render %#v, %v", out, err)
+	}
+
+	out, err = conn.render(ctx, nil, map[string]any{
+		"template_id": "welcome",
+		"_tenant_id":  uuid.NewString(),
+		"data":        map[string]any{"name": "Ada", "total": 1234.5},
+	})
+	if err != nil || out["text"] != "Dear Ada, total £1,234.50" || out["version"] != 3 {
+		t.Fatalf("unexpected stored render %#v, %v", out, err)
+	}
+
+	if _, err := conn.render(ctx, nil, map[string]any{"template": "{{.broken"}); err == nil {
+		t.Fatal("expected a parse error")
+	}
+	if _, err := conn.render(ctx, nil, map[string]any{}); err == nil {
+		t.Fatal("expected a missing template error")
+	}
+}
@@ -2,6 +2,7 @@ package connectors
 
 import (
 	"context"
+	"time"
 )
 
 // Connector defines the interface that all connectors implement.
@@ -21,6 +22,9 @@ type ConnectorMeta struct {
 	Description string `json:"description"`
 	Version     string `json:"version"`
 	Icon        string `json:"icon"`
+	// Category groups the connector's actions in the designer palette;
+	// empty means "Integrations".
+	Category string `json:"category,omitempty"`
 }
 
 type AuthSpec struct {
@@ -50,14 +54,27 @@ type ActionSpec struct {
 	InputSchema  map[string]any `json:"input_schema"`
 	OutputSchema map[string]any `json:"output_schema"`
 	Execute      ActionFunc     `json:"-"`
+	// CacheTTL is how long results may be reused for identical calls. Zero
+	// means the action is never cached. Cacheable, when set, limits caching
+	// to the inputs it accepts, such as HTTP GETs.
+	CacheTTL  time.Duration                   `json:"-"`
+	Cacheable func(input map[string]any) bool `json:"-"`
+	// RawInputKeys are passed to the action as written, without resolving
+	// {{...}} expressions, for inputs that are themselves templates.
+	RawInputKeys []string `json:"-"`
+	// SensitiveOutput keeps the action's output out of step records, for
+	// actions that return credentials or personal data.
+	SensitiveOutput bool `json:"-"`
 }
 
 type ActionSummary struct {
-	Key          string         `json:"key"`
-	Name         string         `json:"name"`
-	Description  string         `json:"description"`
-	InputSchema  map[string]any `json:"input_schema"`
-	OutputSchema map[string]any `json:"output_schema"`
+	Key             string         `json:"key"`
+	Name            string         `json:"name"`
+	Description     string         `json:"description"`
+	InputSchema     map[string]any `json:"input_schema"`
+	OutputSchema    map[string]any `json:"output_schema"`
+	CacheTTLSeconds int            `json:"cache_ttl_seconds,omitempty"`
+	SensitiveOutput bool           `json:"sensitive_output,omitempty"`
 }
 
 type ConnectorDescriptor struct {
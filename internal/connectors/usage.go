@@ -0,0 +1,242 @@
+package connectors
+
+import (
+	"context"
+	"database/sql"
+	"fmt"
+	"sort"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+// UsageRecord describes one connector action invocation. CaseID and
+// PrincipalID are uuid.Nil when the call was not made for a case or a user.
+type UsageRecord struct {
+	TenantID     uuid.UUID
+	ConnectorKey string
+	ActionKey    string
+	CaseID       uuid.UUID
+	PrincipalID  uuid.UUID
+	Duration     time.Duration
+	Success      bool
+	CalledAt     time.Time
+}
+
+type UsageRecorder interface {
+	RecordUsage(ctx context.Context, rec UsageRecord) error
+}
+
+type UsageTotals struct {
+	Calls         int64   `json:"calls"`
+	Failures      int64   `json:"failures"`
+	SuccessRate   float64 `json:"success_rate"`
+	AvgDurationMS float64 `json:"avg_duration_ms"`
+	MaxDurationMS int64   `json:"max_duration_ms"`
+}
+
+type DailyUsage struct {
+	Day string `json:"day"`
+	UsageTotals
+}
+
+type ActionUsage struct {
+	Action string `json:"action"`
+	UsageTotals
+}
+
+type WorkflowUsage struct {
+	WorkflowID uuid.UUID `json:"workflow_id"`
+	Calls      int64     `json:"calls"`
+	Failures   int64     `json:"failures"`
+}
+
+// UsageStats summarises a connector's calls over the trailing Days days,
+// including today.
+type UsageStats struct {
+	Connector string          `json:"connector"`
+	Days      int             `json:"days"`
+	Totals    UsageTotals     `json:"totals"`
+	Daily     []DailyUsage    `json:"daily"`
+	Actions   []ActionUsage   `json:"actions"`
+	Workflows []WorkflowUsage `json:"workflows"`
+}
+
+// UsageStore persists connector calls and keeps the daily rollup in step.
+type UsageStore struct {
+	db *sql.DB
+}
+
+func NewUsageStore(db *sql.DB) *UsageStore {
+	return &UsageStore{db: db}
+}
+
+func (s *UsageStore) RecordUsage(ctx context.Context, rec UsageRecord) error {
+	calledAt := rec.CalledAt
+	if calledAt.IsZero() {
+		calledAt = time.Now()
+	}
+	calledAt = calledAt.UTC()
+	durationMS := rec.Duration.Milliseconds()
+	failures := 0
+	if !rec.Success {
+		failures = 1
+	}
+
+	tx, err := s.db.BeginTx(ctx, nil)
+	if err != nil {
+		return fmt.Errorf("begin connector usage tx: %w", err)
+	}
+	defer func() { _ = tx.Rollback() }()
+
+	if _, err := tx.ExecContext(ctx, `
+INSERT INTO connector_calls (tenant_id, connector_key, action_key, case_id, workflow_id, principal_id, duration_ms, success, called_at)
+VALUES ($1, $2, $3, $4, (SELECT workflow_id FROM cases WHERE id = $4 AND tenant_id = $1), $5, $6, $7, $8)
+`, rec.TenantID, rec.ConnectorKey, rec.ActionKey, nullableUUID(rec.CaseID), nullableUUID(rec.PrincipalID), durationMS, rec.Success, calledAt); err != nil {
+		return fmt.Errorf("insert connector call: %w", err)
+	}
+	if _, err := tx.ExecContext(ctx, `
+INSERT INTO connector_usage_daily (tenant_id, connector_key, action_key, day, calls, failures, total_duration_ms, max_duration_ms)
+VALUES ($1, $2, $3, $4::date, 1, $5, $6, $6)
+ON CONFLICT (tenant_id, connector_key, action_key, day) DO UPDATE
+SET calls = connector_usage_daily.calls + 1,
+    failures = connector_usage_daily.failures + EXCLUDED.failures,
+    total_duration_ms = connector_usage_daily.total_duration_ms + EXCLUDED.total_duration_ms,
+    max_duration_ms = GREATEST(connector_usage_daily.max_duration_ms, EXCLUDED.max_duration_ms)
+`, rec.TenantID, rec.ConnectorKey, rec.ActionKey, calledAt.Format(time.DateOnly), failures, durationMS); err != nil {
+		return fmt.Errorf("update connector usage rollup: %w", err)
+	}
+	return tx.Commit()
+}
+
+// Stats reads a connector's usage for the trailing days days. Totals, daily
+// and per-action figures come from the rollup; the per-workflow breakdown is
+// computed from raw calls.
+func (s *UsageStore) Stats(ctx context.Context, tenantID uuid.UUID, connectorKey string, days int) (UsageStats, error) {
+	since := time.Now().UTC().AddDate(0, 0, -(days - 1)).Format(time.DateOnly)
+	stats := UsageStats{
+		Connector: connectorKey,
+		Days:      days,
+		Daily:     make([]DailyUsage, 0),
+		Actions:   make([]ActionUsage, 0),
+		Workflows: make([]WorkflowUsage, 0),
+	}
+
+	rows, err := s.db.QueryContext(ctx, `
+SELECT day, action_key, calls, failures, total_duration_ms, max_duration_ms
+FROM connector_usage_daily
+WHERE tenant_id = $1 AND connector_key = $2 AND day >= $3::date
+ORDER BY day, action_key
+`, tenantID, connectorKey, since)
+	if err != nil {
+		return UsageStats{}, fmt.Errorf("query connector usage rollup: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+
+	var rollup []usageRow
+	for rows.Next() {
+		var row usageRow
+		var day time.Time
+		if err := rows.Scan(&day, &row.action, &row.calls, &row.failures, &row.totalMS, &row.maxMS); err != nil {
+			return UsageStats{}, fmt.Errorf("scan connector usage row: %w", err)
+		}
+		row.day = day.Format(time.DateOnly)
+		rollup = append(rollup, row)
+	}
+	if err := rows.Err(); err != nil {
+		return UsageStats{}, fmt.Errorf("iterate connector usage rows: %w", err)
+	}
+	stats.Totals, stats.Daily, stats.Actions = summariseUsage(rollup)
+
+	wfRows, err := s.db.QueryContext(ctx, `
+SELECT workflow_id, COUNT(*), COUNT(*) FILTER (WHERE NOT success)
+FROM connector_calls
+WHERE tenant_id = $1 AND connector_key = $2 AND called_at >= $3::date AND workflow_id IS NOT NULL
+GROUP BY workflow_id
+ORDER BY COUNT(*) DESC, workflow_id
+`, tenantID, connectorKey, since)
+	if err != nil {
+		return UsageStats{}, fmt.Errorf("query connector usage by workflow: %w", err)
+	}
+	defer func() { _ = wfRows.Close() }()
+	for wfRows.Next() {
+		var wf WorkflowUsage
+		if err := wfRows.Scan(&wf.WorkflowID, &wf.Calls, &wf.Failures); err != nil {
+			return UsageStats{}, fmt.Errorf("scan connector workflow usage: %w", err)
+		}
+		stats.Workflows = append(stats.Workflows, wf)
+	}
+	if err := wfRows.Err(); err != nil {
+		return UsageStats{}, fmt.Errorf("iterate connector workflow usage: %w", err)
+	}
+	return stats, nil
+}
+
+type usageRow struct {
+	day      string
+	action   string
+	calls    int64
+	failures int64
+	totalMS  int64
+	maxMS    int64
+}
+
+// summariseUsage folds rollup rows, ordered by day, into overall, per-day and
+// per-action totals.
+func summariseUsage(rows []usageRow) (UsageTotals, []DailyUsage, []ActionUsage) {
+	var total usageAccumulator
+	dayAcc := map[string]*usageAccumulator{}
+	actionAcc := map[string]*usageAccumulator{}
+	actionOrder := make([]string, 0)
+	dayOrder := make([]string, 0)
+	for _, row := range rows {
+		total.add(row)
+		if _, ok := dayAcc[row.day]; !ok {
+			dayAcc[row.day] = &usageAccumulator{}
+			dayOrder = append(dayOrder, row.day)
+		}
+		dayAcc[row.day].add(row)
+		if _, ok := actionAcc[row.action]; !ok {
+			actionAcc[row.action] = &usageAccumulator{}
+			actionOrder = append(actionOrder, row.action)
+		}
+		actionAcc[row.action].add(row)
+	}
+	daily := make([]DailyUsage, 0, len(dayOrder))
+	for _, day := range dayOrder {
+		daily = append(daily, DailyUsage{Day: day, UsageTotals: dayAcc[day].totals()})
+	}
+	sort.Strings(actionOrder)
+	actions := make([]ActionUsage, 0, len(actionOrder))
+	for _, action := range actionOrder {
+		actions = append(actions, ActionUsage{Action: action, UsageTotals: actionAcc[action].totals()})
+	}
+	return total.totals(), daily, actions
+}
+
+type usageAccumulator struct {
+	calls, failures, totalMS, maxMS int64
+}
+
+func (a *usageAccumulator) add(row usageRow) {
+	a.calls += row.calls
+	a.failures += row.failures
+	a.totalMS += row.totalMS
+	a.maxMS = max(a.maxMS, row.maxMS)
+}
+
+func (a *usageAccumulator) totals() UsageTotals {
+	out := UsageTotals{Calls: a.calls, Failures: a.failures, MaxDurationMS: a.maxMS}
+	if a.calls > 0 {
+		out.SuccessRate = float64(a.calls-a.failures) / float64(a.calls)
+		out.AvgDurationMS = float64(a.totalMS) / float64(a.calls)
+	}
+	return out
+}
+
+func nullableUUID(id uuid.UUID) any {
+	if id == uuid.Nil {
+		return nil
+	}
+	return id
+}
@@ -0,0 +1,51 @@
+package connectors
+
+import (
+	"context"
+	"errors"
+	"testing"
+)
+
+func TestSummariseUsage_FoldsRollupRows(t *testing.T) {
+	total, daily, actions := summariseUsage([]usageRow{
+		{day: "2026-01-01", action: "send", calls: 3, failures: 1, totalMS: 300, maxMS: 200},
+		{day: "2026-01-01", action: "lookup", calls: 1, totalMS: 50, maxMS: 50},
+		{day: "2026-01-02", action: "send", calls: 4, totalMS: 100, maxMS: 40},
+	})
+	if total.Calls != 8 || total.Failures != 1 || total.MaxDurationMS != 200 || total.AvgDurationMS != 56.25 || total.SuccessRate != 0.875 {
+		t.Fatalf("unexpected totals %+v", total)
+	}
+	if len(daily) != 2 || daily[0].Day != "2026-01-01" || daily[0].Calls != 4 || daily[1].Calls != 4 {
+		t.Fatalf("unexpected daily %+v", daily)
+	}
+	if len(actions) != 2 || actions[0].Action != "lookup" || actions[1].Action != "send" || actions[1].Calls != 7 {
+		t.Fatalf("unexpected actions %+v", actions)
+	}
+
+	empty, daily, actions := summariseUsage(nil)
+	if empty.Calls != 0 || empty.SuccessRate != 0 || len(daily) != 0 || len(actions) != 0 {
+		t.Fatalf("expected zero usage, got %+v %+v %+v", empty, daily, actions)
+	}
+}
+
+type captureRecorder struct {
+	records []UsageRecord
+	err     error
+}
+
+func (c *captureRecorder) RecordUsage(_ context.Context, rec UsageRecord) error {
+	c.records = append(c.records, rec)
+	return c.err
+}
+
+func TestRegistry_RecordUsageIgnoresRecorderErrors(t *testing.T) {
+	reg := NewRegistry()
+	reg.RecordUsage(context.Background(), UsageRecord{ConnectorKey: "t"})
+
+	recorder := &captureRecorder{err: errors.New("db down")}
+	reg.SetUsageRecorder(recorder)
+	reg.RecordUsage(context.Background(), UsageRecord{ConnectorKey: "t", ActionKey: "ping", Success: true})
+	if len(recorder.records) != 1 || recorder.records[0].ActionKey != "ping" {
+		t.Fatalf("expected one forwarded record, got %+v", recorder.records)
+	}
+}
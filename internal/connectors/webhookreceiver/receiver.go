@@ -14,6 +14,7 @@ import (
 
 	"github.com/google/uuid"
 	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/observability"
 )
 
 type Connector struct{}
@@ -228,12 +229,14 @@ SELECT id FROM case_types WHERE tenant_id = $1 AND name = $2 AND status = 'activ
 	var workflowID uuid.UUID
 	var workflowVersion int
 	var workflowAST []byte
+	// A running canary version takes its percentage of new cases.
 	if err := h.db.QueryRowContext(ctx, `
 SELECT w.id, wv.version, wv.ast
 FROM workflows w
 JOIN workflow_versions wv ON wv.workflow_id = w.id
-WHERE w.tenant_id = $1 AND w.case_type = $2 AND wv.status = 'published'
-ORDER BY wv.version DESC
+LEFT JOIN workflow_canaries wc ON wc.workflow_id = w.id AND wc.version = wv.version AND wc.status = 'active'
+WHERE w.tenant_id = $1 AND w.case_type = $2 AND wv.status IN ('published', 'canary')
+ORDER BY wv.status = 'canary' AND random() * 100 >= COALESCE(wc.percent, 0), wv.version DESC
 LIMIT 1
 `, cfg.TenantID, cfg.CaseType).Scan(&workflowID, &workflowVersion, &workflowAST); err != nil {
 		return uuid.Nil, fmt.Errorf("resolve workflow for webhook: %w", err)
@@ -256,10 +259,10 @@ LIMIT 1
 
 	var caseID uuid.UUID
 	err = tx.QueryRowContext(ctx, `
-INSERT INTO cases (tenant_id, case_type_id, case_number, status, data, created_by, workflow_id, workflow_version)
-VALUES ($1, $2, $3, 'open', $4::jsonb, $5, $6, $7)
+INSERT INTO cases (tenant_id, case_type_id, case_number, status, data, created_by, workflow_id, workflow_version, correlation_id)
+VALUES ($1, $2, $3, 'open', $4::jsonb, $5, $6, $7, NULLIF($8, ''))
 RETURNING id
-`, cfg.TenantID, caseTypeID, caseNumber, string(rawData), cfg.CreatedBy, workflowID, workflowVersion).Scan(&caseID)
+`, cfg.TenantID, caseTypeID, caseNumber, string(rawData), cfg.CreatedBy, workflowID, workflowVersion, observability.CorrelationIDFromContext(ctx)).Scan(&caseID)
 	if err != nil {
 		return uuid.Nil, fmt.Errorf("create case from webhook: %w", err)
 	}
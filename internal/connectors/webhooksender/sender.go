@@ -9,10 +9,17 @@ import (
 	"github.com/neural-chilli/aceryx/internal/connectors"
 )
 
-type Connector struct{}
+type Connector struct {
+	egress connectors.EgressPolicy
+}
 
 func New() *Connector { return &Connector{} }
 
+// NewWithEgress returns a connector restricted by the given egress policy.
+func NewWithEgress(policy connectors.EgressPolicy) *Connector {
+	return &Connector{egress: policy}
+}
+
 func (c *Connector) Meta() connectors.ConnectorMeta {
 	return connectors.ConnectorMeta{Key: "webhook_sender", Name: "Webhook Sender", Description: "Outbound webhook delivery", Version: "v1", Icon: "pi pi-send"}
 }
@@ -51,7 +58,10 @@ func (c *Connector) send(ctx context.Context, _ map[string]string, input map[str
 	if raw, ok := input["timeout_seconds"].(float64); ok && int(raw) > 0 {
 		timeout = time.Duration(int(raw)) * time.Second
 	}
-	status, _, body, err := connectors.DoJSONRequest(ctx, http.MethodPost, url, headers, input["body"], timeout)
+	status, _, body, err := connectors.DoJSONRequestWithOptions(ctx, http.MethodPost, url, headers, input["body"], connectors.RequestOptions{
+		Timeout: timeout,
+		Egress:  &c.egress,
+	})
 	if err != nil {
 		return nil, err
 	}
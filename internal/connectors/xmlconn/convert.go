@@ -0,0 +1,250 @@
+package xmlconn
+
+import (
+	"bytes"
+	"encoding/xml"
+	"errors"
+	"fmt"
+	"io"
+	"sort"
+	"strconv"
+	"strings"
+)
+
+// Keys with special meaning when XML is mapped to JSON and back.
+const (
+	attrPrefix = "@"
+	textKey    = "#text"
+	orderKey   = "#order"
+	rawXMLKey  = "#xml"
+)
+
+type node struct {
+	name     string
+	attrs    []xml.Attr
+	children []*node
+	text     strings.Builder
+}
+
+// parseTree reads an XML document into a tree. Namespace prefixes are
+// dropped from element and attribute names, and namespace declarations are
+// not kept.
+func parseTree(data string) (*node, error) {
+	dec := xml.NewDecoder(strings.NewReader(data))
+	doc := &node{}
+	stack := []*node{doc}
+	for {
+		tok, err := dec.Token()
+		if errors.Is(err, io.EOF) {
+			break
+		}
+		if err != nil {
+			return nil, fmt.Errorf("parse xml: %w", err)
+		}
+		top := stack[len(stack)-1]
+		switch t := tok.(type) {
+		case xml.StartElement:
+			child := &node{name: t.Name.Local}
+			for _, attr := range t.Attr {
+				if attr.Name.Space == "xmlns" || attr.Name.Local == "xmlns" {
+					continue
+				}
+				child.attrs = append(child.attrs, attr)
+			}
+			top.children = append(top.children, child)
+			stack = append(stack, child)
+		case xml.EndElement:
+			stack = stack[:len(stack)-1]
+		case xml.CharData:
+			top.text.Write(t)
+		}
+	}
+	if len(doc.children) != 1 {
+		return nil, fmt.Errorf("parse xml: expected one root element, found %d", len(doc.children))
+	}
+	return doc, nil
+}
+
+// toJSON maps an element to JSON. A leaf element becomes its text;
+// otherwise attributes become "@name" keys, text becomes "#text" and child
+// elements become keys, with repeated or forced names collected into arrays.
+func (n *node) toJSON(forceArray map[string]bool) any {
+	text := strings.TrimSpace(n.text.String())
+	if len(n.attrs) == 0 && len(n.children) == 0 {
+		return text
+	}
+	out := map[string]any{}
+	for _, attr := range n.attrs {
+		out[attrPrefix+attr.Name.Local] = attr.Value
+	}
+	for _, child := range n.children {
+		value := child.toJSON(forceArray)
+		existing, seen := out[child.name]
+		switch {
+		case !seen && forceArray[child.name]:
+			out[child.name] = []any{value}
+		case !seen:
+			out[child.name] = value
+		default:
+			if list, ok := existing.([]any); ok {
+				out[child.name] = append(list, value)
+			} else {
+				out[child.name] = []any{existing, value}
+			}
+		}
+	}
+	if text != "" {
+		out[textKey] = text
+	}
+	return out
+}
+
+// Parse converts an XML document to JSON keyed by its root element name.
+func Parse(data string, forceArray []string) (map[string]any, error) {
+	doc, err := parseTree(data)
+	if err != nil {
+		return nil, err
+	}
+	force := map[string]bool{}
+	for _, name := range forceArray {
+		force[name] = true
+	}
+	root := doc.children[0]
+	return map[string]any{root.name: root.toJSON(force)}, nil
+}
+
+// Render converts JSON to an XML document with root as the document
+// element. It reverses Parse: "@name" keys become attributes, "#text" the
+// element text and arrays repeated elements. Child elements are written in
+// key order unless "#order" lists the keys to write first; "#xml" is copied
+// into the element verbatim. Names may carry a prefix such as "soap:Body".
+func Render(root string, value any, indent bool) (string, error) {
+	if strings.TrimSpace(root) == "" {
+		return "", fmt.Errorf("root element name is required")
+	}
+	var buf bytes.Buffer
+	enc := xml.NewEncoder(&buf)
+	if indent {
+		enc.Indent("", "  ")
+	}
+	if err := encodeElement(enc, &buf, root, value); err != nil {
+		return "", err
+	}
+	if err := enc.Flush(); err != nil {
+		return "", fmt.Errorf("render xml: %w", err)
+	}
+	return buf.String(), nil
+}
+
+func encodeElement(enc *xml.Encoder, buf *bytes.Buffer, name string, value any) error {
+	if !validName(name) {
+		return fmt.Errorf("render xml: invalid element name %q", name)
+	}
+	if list, ok := value.([]any); ok {
+		for _, item := range list {
+			if err := encodeElement(enc, buf, name, item); err != nil {
+				return err
+			}
+		}
+		return nil
+	}
+	start := xml.StartElement{Name: xml.Name{Local: name}}
+	fields, _ := value.(map[string]any)
+	for _, key := range sortedKeys(fields) {
+		if attr, ok := strings.CutPrefix(key, attrPrefix); ok {
+			if !validName(attr) {
+				return fmt.Errorf("render xml: invalid attribute name %q", attr)
+			}
+			start.Attr = append(start.Attr, xml.Attr{Name: xml.Name{Local: attr}, Value: scalarText(fields[key])})
+		}
+	}
+	if err := enc.EncodeToken(start); err != nil {
+		return fmt.Errorf("render xml: %w", err)
+	}
+	if fields == nil {
+		if value != nil {
+			if err := enc.EncodeToken(xml.CharData(scalarText(value))); err != nil {
+				return fmt.Errorf("render xml: %w", err)
+			}
+		}
+	} else {
+		if text, ok := fields[textKey]; ok {
+			if err := enc.EncodeToken(xml.CharData(scalarText(text))); err != nil {
+				return fmt.Errorf("render xml: %w", err)
+			}
+		}
+		if raw, ok := fields[rawXMLKey].(string); ok {
+			if err := enc.Flush(); err != nil {
+				return fmt.Errorf("render xml: %w", err)
+			}
+			buf.WriteString(raw)
+		}
+		for _, key := range childOrder(fields) {
+			if err := encodeElement(enc, buf, key, fields[key]); err != nil {
+				return err
+			}
+		}
+	}
+	if err := enc.EncodeToken(start.End()); err != nil {
+		return fmt.Errorf("render xml: %w", err)
+	}
+	return nil
+}
+
+// childOrder lists child element keys: those named in "#order" first, then
+// the rest alphabetically.
+func childOrder(fields map[string]any) []string {
+	seen := map[string]bool{}
+	var out []string
+	if order, ok := fields[orderKey].([]any); ok {
+		for _, item := range order {
+			key, _ := item.(string)
+			if _, exists := fields[key]; exists && !seen[key] && isChildKey(key) {
+				out = append(out, key)
+				seen[key] = true
+			}
+		}
+	}
+	for _, key := range sortedKeys(fields) {
+		if !seen[key] && isChildKey(key) {
+			out = append(out, key)
+		}
+	}
+	return out
+}
+
+func isChildKey(key string) bool {
+	return !strings.HasPrefix(key, attrPrefix) && !strings.HasPrefix(key, "#")
+}
+
+func sortedKeys(m map[string]any) []string {
+	keys := make([]string, 0, len(m))
+	for key := range m {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+	return keys
+}
+
+func scalarText(value any) string {
+	switch v := value.(type) {
+	case nil:
+		return ""
+	case string:
+		return v
+	case float64:
+		return strconv.FormatFloat(v, 'f', -1, 64)
+	case bool:
+		return strconv.FormatBool(v)
+	default:
+		return fmt.Sprint(v)
+	}
+}
+
+func validName(name string) bool {
+	if name == "" || strings.ContainsAny(name, " \t\r\n<>&\"'/=") {
+		return false
+	}
+	first := name[0]
+	return first != '-' && first != '.' && (first < '0' || first > '9')
+}
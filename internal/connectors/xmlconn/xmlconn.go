@@ -0,0 +1,142 @@
+package xmlconn
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/neural-chilli/aceryx/internal/connectors"
+)
+
+type Connector struct{}
+
+func New() *Connector { return &Connector{} }
+
+func (c *Connector) Meta() connectors.ConnectorMeta {
+	return connectors.ConnectorMeta{Key: "xml_transform", Name: "XML Transform", Description: "Convert between XML and JSON and extract values with XPath", Version: "v1", Icon: "pi pi-code"}
+}
+
+func (c *Connector) Auth() connectors.AuthSpec { return connectors.AuthSpec{Type: "none"} }
+
+func (c *Connector) Triggers() []connectors.TriggerSpec { return nil }
+
+func (c *Connector) Actions() []connectors.ActionSpec {
+	return []connectors.ActionSpec{
+		action("parse", "Parse", "Convert an XML document to JSON", c.parse),
+		action("query", "Query", "Select values from an XML document with XPath", c.query),
+		action("render", "Render", "Convert JSON to an XML document", c.render),
+	}
+}
+
+func action(key, name, description string, execute func(context.Context, map[string]string, map[string]any) (map[string]any, error)) connectors.ActionSpec {
+	return connectors.ActionSpec{
+		Key:          key,
+		Name:         name,
+		Description:  description,
+		InputSchema:  map[string]any{"type": "object"},
+		OutputSchema: map[string]any{"type": "object"},
+		Execute:      execute,
+		CacheTTL:     connectors.DeterministicCacheTTL,
+	}
+}
+
+func (c *Connector) parse(_ context.Context, _ map[string]string, input map[string]any) (map[string]any, error) {
+	xmlText := readString(input, "xml", "")
+	if xmlText == "" {
+		return nil, fmt.Errorf("xml is required")
+	}
+	result, err := Parse(xmlText, readStrings(input, "force_array"))
+	if err != nil {
+		return nil, err
+	}
+	return map[string]any{"result": result}, nil
+}
+
+// query returns every match in "result" and the first match in "value".
+func (c *Connector) query(_ context.Context, _ map[string]string, input map[string]any) (map[string]any, error) {
+	xmlText := readString(input, "xml", "")
+	if xmlText == "" {
+		return nil, fmt.Errorf("xml is required")
+	}
+	steps, err := compileXPath(readString(input, "xpath", ""))
+	if err != nil {
+		return nil, err
+	}
+	doc, err := parseTree(xmlText)
+	if err != nil {
+		return nil, err
+	}
+	force := map[string]bool{}
+	for _, name := range readStrings(input, "force_array") {
+		force[name] = true
+	}
+	matches := evaluate(doc, steps, force)
+	var first any
+	if len(matches) > 0 {
+		first = matches[0]
+	}
+	return map[string]any{"result": matches, "value": first, "count": len(matches)}, nil
+}
+
+// render accepts either "root" and "data", or "data" with a single key
+// naming the root element.
+func (c *Connector) render(_ context.Context, _ map[string]string, input map[string]any) (map[string]any, error) {
+	data, ok := input["data"]
+	if !ok {
+		return nil, fmt.Errorf("data is required")
+	}
+	root := readString(input, "root", "")
+	if root == "" {
+		wrapped, ok := data.(map[string]any)
+		if !ok || len(wrapped) != 1 {
+			return nil, fmt.Errorf("root is required unless data has a single key")
+		}
+		for key, value := range wrapped {
+			root, data = key, value
+		}
+	}
+	xmlText, err := Render(root, data, readBool(input, "indent", false))
+	if err != nil {
+		return nil, err
+	}
+	if readBool(input, "declaration", true) {
+		xmlText = `<?xml version="1.0" encoding="UTF-8"?>` + "\n" + xmlText
+	}
+	return map[string]any{"xml": xmlText}, nil
+}
+
+func readString(input map[string]any, key string, fallback string) string {
+	raw, ok := input[key]
+	if !ok || raw == nil {
+		return fallback
+	}
+	if s, ok := raw.(string); ok {
+		return s
+	}
+	return fallback
+}
+
+func readBool(input map[string]any, key string, fallback bool) bool {
+	raw, ok := input[key]
+	if !ok || raw == nil {
+		return fallback
+	}
+	if b, ok := raw.(bool); ok {
+		return b
+	}
+	return fallback
+}
+
+func readStrings(input map[string]any, key string) []string {
+	var out []string
+	switch typed := input[key].(type) {
+	case []string:
+		return typed
+	case []any:
+		for _, item := range typed {
+			if s, ok := item.(string); ok {
+				out = append(out, s)
+			}
+		}
+	}
+	return out
+}
@@ -0,0 +1,108 @@
+package xmlconn
+
+import (
+	"context"
+	"reflect"
+	"strings"
+	"testing"
+)
+
+const ordersXML = `<?xml version="1.0"?>
+<ns:orders xmlns:ns="urn:orders" region="eu">
+	<ns:order id="1" status="open"><customer>Ada</customer><total>12.50</total></ns:order>
+	<ns:order id="2" status="closed"><customer>Brian</customer><total>8</total></ns:order>
+	<note>  quarterly  </note>
+</ns:orders>`
+
+func TestParseMapsElementsAttributesAndRepeats(t *testing.T) {
+	got, err := Parse(ordersXML, nil)
+	if err != nil {
+		t.Fatalf("parse: %v", err)
+	}
+	want := map[string]any{"orders": map[string]any{
+		"@region": "eu",
+		"order": []any{
+			map[string]any{"@id": "1", "@status": "open", "customer": "Ada", "total": "12.50"},
+			map[string]any{"@id": "2", "@status": "closed", "customer": "Brian", "total": "8"},
+		},
+		"note": "quarterly",
+	}}
+	if !reflect.DeepEqual(got, want) {
+		t.Fatalf("unexpected parse result:\n got %#v\nwant %#v", got, want)
+	}
+
+	forced, err := Parse(`<a><b>1</b></a>`, []string{"b"})
+	if err != nil || !reflect.DeepEqual(forced, map[string]any{"a": map[string]any{"b": []any{"1"}}}) {
+		t.Fatalf("expected force_array to wrap b, got %#v, %v", forced, err)
+	}
+	if _, err := Parse(`<a><b></a>`, nil); err == nil {
+		t.Fatal("expected malformed xml to fail")
+	}
+}
+
+func TestXPathQueries(t *testing.T) {
+	cases := []struct {
+		path string
+		want []any
+	}{
+		{"/orders/order/customer", []any{"Ada", "Brian"}},
+		{"//customer", []any{"Ada", "Brian"}},
+		{"/ns:orders/ns:order[2]/customer", []any{"Brian"}},
+		{"//order[@status='open']/@id", []any{"1"}},
+		{"//order[customer='Brian']/total/text()", []any{"8"}},
+		{"/orders/@region", []any{"eu"}},
+		{"//order[1]/@*", []any{"1", "open"}},
+		{"/orders/note", []any{"quarterly"}},
+		{"/orders/missing", []any{}},
+	}
+	conn := New()
+	for _, tc := range cases {
+		out, err := conn.query(context.Background(), nil, map[string]any{"xml": ordersXML, "xpath": tc.path})
+		if err != nil {
+			t.Fatalf("%s: %v", tc.path, err)
+		}
+		got, _ := out["result"].([]any)
+		if len(got) == 0 && len(tc.want) == 0 {
+			continue
+		}
+		if !reflect.DeepEqual(got, tc.want) {
+			t.Errorf("%s: got %#v, want %#v", tc.path, got, tc.want)
+		}
+	}
+	for _, bad := range []string{"", "/orders/", "//order[0]", "/@id/order", "//order[@id=1]"} {
+		if _, err := compileXPath(bad); err == nil {
+			t.Errorf("expected %q to be rejected", bad)
+		}
+	}
+}
+
+func TestRenderRoundTripsParse(t *testing.T) {
+	parsed, err := Parse(ordersXML, nil)
+	if err != nil {
+		t.Fatalf("parse: %v", err)
+	}
+	out, err := New().render(context.Background(), nil, map[string]any{"data": parsed, "declaration": false})
+	if err != nil {
+		t.Fatalf("render: %v", err)
+	}
+	xmlText, _ := out["xml"].(string)
+	again, err := Parse(xmlText, nil)
+	if err != nil || !reflect.DeepEqual(again, parsed) {
+		t.Fatalf("round trip mismatch: %s (%v)", xmlText, err)
+	}
+
+	ordered, err := Render("env", map[string]any{
+		"#order": []any{"z", "a"},
+		"a":      "<&>",
+		"z":      map[string]any{"#xml": "<raw/>"},
+	}, false)
+	if err != nil {
+		t.Fatalf("render ordered: %v", err)
+	}
+	if ordered != `<env><z><raw/></z><a>&lt;&amp;&gt;</a></env>` {
+		t.Fatalf("unexpected render %s", ordered)
+	}
+	if _, err := Render("bad name", "x", false); err == nil || !strings.Contains(err.Error(), "invalid element name") {
+		t.Fatalf("expected invalid name error, got %v", err)
+	}
+}
@@ -0,0 +1,284 @@
+package xmlconn
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+)
+
+type stepKind int
+
+const (
+	elementStep stepKind = iota
+	attributeStep
+	textStep
+	selfStep
+)
+
+// xpathStep is one location step. Names match local names, so prefixes in
+// the expression are ignored.
+type xpathStep struct {
+	descendant bool
+	kind       stepKind
+	name       string
+	predicates []xpathPredicate
+}
+
+// xpathPredicate is [n], [@attr], [@attr='v'], [child] or [child='v'].
+type xpathPredicate struct {
+	index int
+	attr  bool
+	name  string
+	value *string
+}
+
+// compileXPath parses the XPath subset used for extraction: absolute and
+// relative paths, "//", "*", ".", "@attr", "@*", "text()" and the
+// predicates above.
+func compileXPath(expr string) ([]xpathStep, error) {
+	expr = strings.TrimSpace(expr)
+	if expr == "" {
+		return nil, fmt.Errorf("xpath is required")
+	}
+	var steps []xpathStep
+	i := 0
+	descendant := false
+	switch {
+	case strings.HasPrefix(expr, "//"):
+		descendant, i = true, 2
+	case strings.HasPrefix(expr, "/"):
+		i = 1
+	}
+	for i < len(expr) {
+		end := stepEnd(expr, i)
+		step, err := parseStep(expr[i:end])
+		if err != nil {
+			return nil, fmt.Errorf("xpath %q: %w", expr, err)
+		}
+		step.descendant = descendant
+		steps = append(steps, step)
+		descendant = false
+		i = end
+		if i < len(expr) {
+			i++
+			if i < len(expr) && expr[i] == '/' {
+				descendant = true
+				i++
+			}
+			if i >= len(expr) {
+				return nil, fmt.Errorf("xpath %q: trailing /", expr)
+			}
+		}
+	}
+	if len(steps) == 0 {
+		steps = append(steps, xpathStep{kind: selfStep})
+	}
+	for idx, step := range steps[:len(steps)-1] {
+		if step.kind == attributeStep || step.kind == textStep {
+			return nil, fmt.Errorf("xpath %q: step %d must be last", expr, idx+1)
+		}
+	}
+	return steps, nil
+}
+
+// stepEnd returns the index of the "/" ending the step that starts at i,
+// ignoring slashes inside predicates and quotes.
+func stepEnd(expr string, i int) int {
+	depth := 0
+	var quote byte
+	for ; i < len(expr); i++ {
+		c := expr[i]
+		switch {
+		case quote != 0:
+			if c == quote {
+				quote = 0
+			}
+		case c == '\'' || c == '"':
+			quote = c
+		case c == '[':
+			depth++
+		case c == ']':
+			depth--
+		case c == '/' && depth == 0:
+			return i
+		}
+	}
+	return i
+}
+
+func parseStep(raw string) (xpathStep, error) {
+	raw = strings.TrimSpace(raw)
+	name, rest, _ := strings.Cut(raw, "[")
+	step := xpathStep{}
+	switch name = strings.TrimSpace(name); {
+	case name == "":
+		return step, fmt.Errorf("empty step")
+	case name == ".":
+		step.kind = selfStep
+	case name == "text()":
+		step.kind = textStep
+	case strings.HasPrefix(name, "@"):
+		step.kind, step.name = attributeStep, localName(name[1:])
+	default:
+		step.kind, step.name = elementStep, localName(name)
+	}
+	if rest == "" {
+		return step, nil
+	}
+	for _, part := range strings.Split("["+rest, "[")[1:] {
+		body, ok := strings.CutSuffix(strings.TrimSpace(part), "]")
+		if !ok {
+			return step, fmt.Errorf("unterminated predicate in %q", raw)
+		}
+		pred, err := parsePredicate(strings.TrimSpace(body))
+		if err != nil {
+			return step, err
+		}
+		step.predicates = append(step.predicates, pred)
+	}
+	return step, nil
+}
+
+func parsePredicate(body string) (xpathPredicate, error) {
+	if n, err := strconv.Atoi(body); err == nil {
+		if n < 1 {
+			return xpathPredicate{}, fmt.Errorf("position must be at least 1")
+		}
+		return xpathPredicate{index: n}, nil
+	}
+	key, value, hasValue := strings.Cut(body, "=")
+	key = strings.TrimSpace(key)
+	pred := xpathPredicate{}
+	if attr, ok := strings.CutPrefix(key, "@"); ok {
+		pred.attr, key = true, attr
+	}
+	if key == "" {
+		return pred, fmt.Errorf("invalid predicate [%s]", body)
+	}
+	pred.name = localName(key)
+	if hasValue {
+		value = strings.TrimSpace(value)
+		if len(value) < 2 || (value[0] != '\'' && value[0] != '"') || value[len(value)-1] != value[0] {
+			return pred, fmt.Errorf("predicate value must be quoted in [%s]", body)
+		}
+		unquoted := value[1 : len(value)-1]
+		pred.value = &unquoted
+	}
+	return pred, nil
+}
+
+func localName(name string) string {
+	if i := strings.LastIndexByte(name, ':'); i >= 0 {
+		return name[i+1:]
+	}
+	return name
+}
+
+// evaluate applies steps to a document node. Elements come back as JSON
+// (see toJSON); attributes and text as strings.
+func evaluate(doc *node, steps []xpathStep, forceArray map[string]bool) []any {
+	current := []*node{doc}
+	for _, step := range steps {
+		if step.descendant && step.kind != elementStep {
+			var expanded []*node
+			for _, n := range current {
+				expanded = append(expanded, descendantsOrSelf(n)...)
+			}
+			current = expanded
+		}
+		switch step.kind {
+		case selfStep:
+			continue
+		case attributeStep:
+			var out []any
+			for _, n := range current {
+				for _, attr := range n.attrs {
+					if step.name == "*" || attr.Name.Local == step.name {
+						out = append(out, attr.Value)
+					}
+				}
+			}
+			return out
+		case textStep:
+			var out []any
+			for _, n := range current {
+				if text := strings.TrimSpace(n.text.String()); text != "" {
+					out = append(out, text)
+				}
+			}
+			return out
+		}
+		var next []*node
+		for _, n := range current {
+			var candidates []*node
+			pool := n.children
+			if step.descendant {
+				pool = descendantsOrSelf(n)[1:]
+			}
+			for _, child := range pool {
+				if step.name == "*" || child.name == step.name {
+					candidates = append(candidates, child)
+				}
+			}
+			next = append(next, applyPredicates(candidates, step.predicates)...)
+		}
+		current = next
+	}
+	out := make([]any, 0, len(current))
+	for _, n := range current {
+		if n.name == "" {
+			// The document node itself, as selected by "." or "/".
+			for _, child := range n.children {
+				out = append(out, map[string]any{child.name: child.toJSON(forceArray)})
+			}
+			continue
+		}
+		out = append(out, n.toJSON(forceArray))
+	}
+	return out
+}
+
+func applyPredicates(nodes []*node, predicates []xpathPredicate) []*node {
+	for _, pred := range predicates {
+		if pred.index > 0 {
+			if pred.index > len(nodes) {
+				return nil
+			}
+			nodes = nodes[pred.index-1 : pred.index]
+			continue
+		}
+		kept := nodes[:0:0]
+		for _, n := range nodes {
+			if pred.matches(n) {
+				kept = append(kept, n)
+			}
+		}
+		nodes = kept
+	}
+	return nodes
+}
+
+func (p xpathPredicate) matches(n *node) bool {
+	if p.attr {
+		for _, attr := range n.attrs {
+			if attr.Name.Local == p.name && (p.value == nil || attr.Value == *p.value) {
+				return true
+			}
+		}
+		return false
+	}
+	for _, child := range n.children {
+		if child.name == p.name && (p.value == nil || strings.TrimSpace(child.text.String()) == *p.value) {
+			return true
+		}
+	}
+	return false
+}
+
+func descendantsOrSelf(n *node) []*node {
+	out := []*node{n}
+	for _, child := range n.children {
+		out = append(out, descendantsOrSelf(child)...)
+	}
+	return out
+}
@@ -0,0 +1,231 @@
+package customtools
+
+import (
+	"bytes"
+	"context"
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"reflect"
+	"strings"
+
+	"github.com/google/uuid"
+)
+
+const maxExampleNameLength = 100
+
+// AddExample saves an example call for the named tool.
+func (s *Store) AddExample(ctx context.Context, tenantID, createdBy uuid.UUID, toolName string, req ExampleRequest) (Example, error) {
+	tool, err := s.Get(ctx, tenantID, toolName)
+	if err != nil {
+		return Example{}, err
+	}
+	example, err := normalizeExample(tool, req)
+	if err != nil {
+		return Example{}, err
+	}
+	return s.insertExample(ctx, tool, createdBy, example)
+}
+
+func (s *Store) insertExample(ctx context.Context, tool Tool, createdBy uuid.UUID, example Example) (Example, error) {
+	var expected any
+	if len(example.ExpectedOutput) > 0 {
+		expected = string(example.ExpectedOutput)
+	}
+	example.CreatedBy = &createdBy
+	err := s.db.QueryRowContext(ctx, `
+INSERT INTO custom_tool_examples (tool_id, name, description, input, expected_output, created_by)
+VALUES ($1, $2, $3, $4::jsonb, $5::jsonb, $6)
+ON CONFLICT (tool_id, name) DO NOTHING
+RETURNING id, created_at
+`, tool.ID, example.Name, example.Description, string(example.Input), expected, createdBy).Scan(&example.ID, &example.CreatedAt)
+	if errors.Is(err, sql.ErrNoRows) {
+		return Example{}, ErrExampleExists
+	}
+	if err != nil {
+		return Example{}, fmt.Errorf("insert custom tool example: %w", err)
+	}
+	return example, nil
+}
+
+// Examples lists the named tool's examples in the order they were saved.
+func (s *Store) Examples(ctx context.Context, tenantID uuid.UUID, toolName string) ([]Example, error) {
+	tool, err := s.Get(ctx, tenantID, toolName)
+	if err != nil {
+		return nil, err
+	}
+	rows, err := s.db.QueryContext(ctx, `
+SELECT id, name, description, input, expected_output, created_by, created_at
+FROM custom_tool_examples
+WHERE tool_id = $1
+ORDER BY created_at, name
+`, tool.ID)
+	if err != nil {
+		return nil, fmt.Errorf("list custom tool examples: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	out := make([]Example, 0)
+	for rows.Next() {
+		example, err := scanExample(rows)
+		if err != nil {
+			return nil, err
+		}
+		out = append(out, example)
+	}
+	if err := rows.Err(); err != nil {
+		return nil, fmt.Errorf("iterate custom tool examples: %w", err)
+	}
+	return out, nil
+}
+
+func (s *Store) GetExample(ctx context.Context, tenantID uuid.UUID, toolName string, id uuid.UUID) (Example, error) {
+	row := s.db.QueryRowContext(ctx, `
+SELECT e.id, e.name, e.description, e.input, e.expected_output, e.created_by, e.created_at
+FROM custom_tool_examples e
+JOIN custom_tools t ON t.id = e.tool_id
+WHERE t.tenant_id = $1 AND t.name = $2 AND e.id = $3
+`, tenantID, strings.TrimSpace(toolName), id)
+	example, err := scanExample(row)
+	if errors.Is(err, sql.ErrNoRows) {
+		return Example{}, ErrExampleNotFound
+	}
+	return example, err
+}
+
+func (s *Store) DeleteExample(ctx context.Context, tenantID uuid.UUID, toolName string, id uuid.UUID) error {
+	res, err := s.db.ExecContext(ctx, `
+DELETE FROM custom_tool_examples e
+USING custom_tools t
+WHERE t.id = e.tool_id AND t.tenant_id = $1 AND t.name = $2 AND e.id = $3
+`, tenantID, strings.TrimSpace(toolName), id)
+	if err != nil {
+		return fmt.Errorf("delete custom tool example: %w", err)
+	}
+	if n, _ := res.RowsAffected(); n == 0 {
+		return ErrExampleNotFound
+	}
+	return nil
+}
+
+// RunExample invokes the named tool with a saved example's input.
+func (p *CustomHTTPProtocol) RunExample(ctx context.Context, tenantID uuid.UUID, toolName string, id uuid.UUID) (ExampleRun, error) {
+	tool, err := p.Lookup(ctx, tenantID, toolName)
+	if err != nil {
+		return ExampleRun{}, err
+	}
+	example, err := p.store.GetExample(ctx, tenantID, toolName, id)
+	if err != nil {
+		return ExampleRun{}, err
+	}
+	output, err := p.InvokeTool(ctx, tenantID, tool, example.Input)
+	if err != nil {
+		return ExampleRun{}, err
+	}
+	run := ExampleRun{Example: example, Output: output}
+	if len(example.ExpectedOutput) > 0 {
+		matches := jsonEqual(example.ExpectedOutput, output)
+		run.Matches = &matches
+	}
+	return run, nil
+}
+
+// Playground invokes the named tool and, when asked, records the call as an
+// example. The example is checked before the call, so an invalid name does
+// not cost a request to the endpoint.
+func (p *CustomHTTPProtocol) Playground(ctx context.Context, tenantID, actorID uuid.UUID, toolName string, req PlaygroundRequest) (PlaygroundRun, error) {
+	tool, err := p.Lookup(ctx, tenantID, toolName)
+	if err != nil {
+		return PlaygroundRun{}, err
+	}
+	var example Example
+	save := strings.TrimSpace(req.SaveAs) != ""
+	if save {
+		example, err = normalizeExample(tool, ExampleRequest{Name: req.SaveAs, Description: req.Description, Input: req.Input})
+		if err != nil {
+			return PlaygroundRun{}, fmt.Errorf("custom tool %s: %w: %w", tool.Name, ErrInvalidArguments, err)
+		}
+	}
+	output, err := p.InvokeTool(ctx, tenantID, tool, req.Input)
+	if err != nil {
+		return PlaygroundRun{}, err
+	}
+	run := PlaygroundRun{Output: output}
+	if !save {
+		return run, nil
+	}
+	// Sensitive output is never stored, so the example keeps no expected
+	// output for those tools.
+	var buf bytes.Buffer
+	if !tool.SensitiveOutput && json.Compact(&buf, output) == nil {
+		example.ExpectedOutput = buf.Bytes()
+	}
+	saved, err := p.store.insertExample(ctx, tool, actorID, example)
+	if err != nil {
+		return run, err
+	}
+	run.Example = &saved
+	return run, nil
+}
+
+func scanExample(row rowScanner) (Example, error) {
+	var (
+		example  Example
+		input    []byte
+		expected []byte
+	)
+	if err := row.Scan(&example.ID, &example.Name, &example.Description, &input, &expected, &example.CreatedBy, &example.CreatedAt); err != nil {
+		if errors.Is(err, sql.ErrNoRows) {
+			return Example{}, err
+		}
+		return Example{}, fmt.Errorf("scan custom tool example: %w", err)
+	}
+	example.Input = input
+	example.ExpectedOutput = expected
+	return example, nil
+}
+
+// normalizeExample checks an example against the tool's input schema, so a
+// saved example can always be run while the tool is unchanged.
+func normalizeExample(tool Tool, req ExampleRequest) (Example, error) {
+	example := Example{
+		Name:        strings.TrimSpace(req.Name),
+		Description: strings.TrimSpace(req.Description),
+	}
+	if example.Name == "" || len(example.Name) > maxExampleNameLength {
+		return Example{}, fmt.Errorf("name is required and must be at most %d characters", maxExampleNameLength)
+	}
+	input := bytes.TrimSpace(req.Input)
+	if len(input) == 0 || string(input) == "null" {
+		input = []byte(`{}`)
+	}
+	var args map[string]any
+	if err := json.Unmarshal(input, &args); err != nil {
+		return Example{}, fmt.Errorf("input must be a JSON object")
+	}
+	if err := validateAgainst(tool.InputSchema, args); err != nil {
+		return Example{}, fmt.Errorf("input does not match the tool's input schema: %w", err)
+	}
+	var buf bytes.Buffer
+	if err := json.Compact(&buf, input); err != nil {
+		return Example{}, fmt.Errorf("input must be a JSON object")
+	}
+	example.Input = buf.Bytes()
+	if expected := bytes.TrimSpace(req.ExpectedOutput); len(expected) > 0 && string(expected) != "null" {
+		var out bytes.Buffer
+		if err := json.Compact(&out, expected); err != nil {
+			return Example{}, fmt.Errorf("invalid expected_output: %w", err)
+		}
+		example.ExpectedOutput = out.Bytes()
+	}
+	return example, nil
+}
+
+// jsonEqual compares two JSON documents ignoring key order and whitespace.
+func jsonEqual(a, b json.RawMessage) bool {
+	var left, right any
+	if json.Unmarshal(a, &left) != nil || json.Unmarshal(b, &right) != nil {
+		return false
+	}
+	return reflect.DeepEqual(left, right)
+}
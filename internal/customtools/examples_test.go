@@ -0,0 +1,46 @@
+package customtools
+
+import (
+	"encoding/json"
+	"strings"
+	"testing"
+)
+
+func TestNormalizeExampleChecksInputSchema(t *testing.T) {
+	tool := Tool{Name: "lookup_customer", InputSchema: json.RawMessage(`{"type":"object","required":["id"]}`)}
+	example, err := normalizeExample(tool, ExampleRequest{
+		Name:           " Existing customer ",
+		Input:          json.RawMessage(`{ "id": "c-1" }`),
+		ExpectedOutput: json.RawMessage(`{ "name": "Ada" }`),
+	})
+	if err != nil {
+		t.Fatalf("normalizeExample: %v", err)
+	}
+	if example.Name != "Existing customer" || string(example.Input) != `{"id":"c-1"}` || string(example.ExpectedOutput) != `{"name":"Ada"}` {
+		t.Fatalf("unexpected example %+v", example)
+	}
+
+	cases := []struct {
+		name string
+		req  ExampleRequest
+		want string
+	}{
+		{"missing name", ExampleRequest{Input: json.RawMessage(`{"id":"c-1"}`)}, "name is required"},
+		{"not an object", ExampleRequest{Name: "list", Input: json.RawMessage(`["c-1"]`)}, "must be a JSON object"},
+		{"schema mismatch", ExampleRequest{Name: "empty", Input: json.RawMessage(`{}`)}, "input schema"},
+	}
+	for _, tc := range cases {
+		if _, err := normalizeExample(tool, tc.req); err == nil || !strings.Contains(err.Error(), tc.want) {
+			t.Fatalf("%s: expected error containing %q, got %v", tc.name, tc.want, err)
+		}
+	}
+}
+
+func TestJSONEqualIgnoresFormatting(t *testing.T) {
+	if !jsonEqual(json.RawMessage(`{"a":1,"b":[1,2]}`), json.RawMessage(`{ "b": [1, 2], "a": 1 }`)) {
+		t.Fatal("expected key order and whitespace to be ignored")
+	}
+	if jsonEqual(json.RawMessage(`{"a":1}`), json.RawMessage(`{"a":2}`)) {
+		t.Fatal("expected different values to differ")
+	}
+}
@@ -0,0 +1,232 @@
+package customtools
+
+import (
+	"bytes"
+	"encoding/json"
+	"strings"
+)
+
+// Form describes an input form for a tool, derived from its input schema so
+// a playground can render fields without interpreting JSON Schema itself.
+type Form struct {
+	Title       string      `json:"title"`
+	Description string      `json:"description,omitempty"`
+	Fields      []FormField `json:"fields"`
+}
+
+// FormField is one property of the input schema. Type is string, select,
+// integer, number, boolean, date, datetime, email, url, object, array or
+// json; json is used for anything the form cannot model, and the field takes
+// raw JSON. Object fields carry their properties in Fields, and array fields
+// describe their items in Items.
+type FormField struct {
+	ID          string      `json:"id"`
+	Label       string      `json:"label"`
+	Description string      `json:"description,omitempty"`
+	Type        string      `json:"type"`
+	Required    bool        `json:"required,omitempty"`
+	Default     any         `json:"default,omitempty"`
+	Options     []any       `json:"options,omitempty"`
+	MinLength   *int        `json:"min_length,omitempty"`
+	MaxLength   *int        `json:"max_length,omitempty"`
+	Min         *float64    `json:"min,omitempty"`
+	Max         *float64    `json:"max,omitempty"`
+	Fields      []FormField `json:"fields,omitempty"`
+	Items       *FormField  `json:"items,omitempty"`
+}
+
+type schemaNode struct {
+	Type        json.RawMessage `json:"type"`
+	Title       string          `json:"title"`
+	Description string          `json:"description"`
+	Format      string          `json:"format"`
+	Default     any             `json:"default"`
+	Enum        []any           `json:"enum"`
+	Const       any             `json:"const"`
+	Properties  json.RawMessage `json:"properties"`
+	Required    []string        `json:"required"`
+	Items       json.RawMessage `json:"items"`
+	MinLength   *int            `json:"minLength"`
+	MaxLength   *int            `json:"maxLength"`
+	Minimum     *float64        `json:"minimum"`
+	Maximum     *float64        `json:"maximum"`
+}
+
+// maxFormDepth bounds nesting so recursive-looking schemas still produce a
+// finite form; deeper objects fall back to raw JSON.
+const maxFormDepth = 5
+
+// FormFor builds the input form for a tool. Fields keep the order of the
+// schema's properties.
+func FormFor(tool Tool) Form {
+	form := Form{Title: tool.Name, Description: tool.Description, Fields: []FormField{}}
+	var root schemaNode
+	if json.Unmarshal(tool.InputSchema, &root) != nil {
+		return form
+	}
+	if root.Title != "" {
+		form.Title = root.Title
+	}
+	if fields := objectFields(root, 0); fields != nil {
+		form.Fields = fields
+	}
+	return form
+}
+
+func objectFields(node schemaNode, depth int) []FormField {
+	keys := orderedKeys(node.Properties)
+	if len(keys) == 0 {
+		return nil
+	}
+	var props map[string]json.RawMessage
+	if json.Unmarshal(node.Properties, &props) != nil {
+		return nil
+	}
+	required := make(map[string]bool, len(node.Required))
+	for _, key := range node.Required {
+		required[key] = true
+	}
+	fields := make([]FormField, 0, len(keys))
+	for _, key := range keys {
+		field := formField(key, props[key], depth)
+		field.Required = required[key]
+		fields = append(fields, field)
+	}
+	return fields
+}
+
+func formField(id string, raw json.RawMessage, depth int) FormField {
+	field := FormField{ID: id, Label: labelFor(id), Type: "json"}
+	var node schemaNode
+	if json.Unmarshal(raw, &node) != nil {
+		return field
+	}
+	if node.Title != "" {
+		field.Label = node.Title
+	}
+	field.Description = node.Description
+	field.Default = node.Default
+	field.MinLength, field.MaxLength = node.MinLength, node.MaxLength
+	field.Min, field.Max = node.Minimum, node.Maximum
+
+	if len(node.Enum) > 0 || node.Const != nil {
+		field.Type = "select"
+		field.Options = node.Enum
+		if len(field.Options) == 0 {
+			field.Options = []any{node.Const}
+		}
+		return field
+	}
+	switch schemaType(node.Type) {
+	case "string":
+		field.Type = stringFieldType(node)
+	case "integer", "number", "boolean":
+		field.Type = schemaType(node.Type)
+	case "object":
+		if depth+1 < maxFormDepth {
+			if fields := objectFields(node, depth+1); fields != nil {
+				field.Type = "object"
+				field.Fields = fields
+			}
+		}
+	case "array":
+		if depth+1 < maxFormDepth && len(bytes.TrimSpace(node.Items)) > 0 && node.Items[0] == '{' {
+			items := formField("items", node.Items, depth+1)
+			if items.Type != "json" {
+				field.Type = "array"
+				items.Label = ""
+				field.Items = &items
+			}
+		}
+	}
+	return field
+}
+
+// schemaType returns the schema's single non-null type, so optional fields
+// declared as ["string", "null"] still get a typed input.
+func schemaType(raw json.RawMessage) string {
+	var single string
+	if json.Unmarshal(raw, &single) == nil {
+		return single
+	}
+	var many []string
+	if json.Unmarshal(raw, &many) != nil {
+		return ""
+	}
+	out := ""
+	for _, t := range many {
+		if t == "null" {
+			continue
+		}
+		if out != "" {
+			return ""
+		}
+		out = t
+	}
+	return out
+}
+
+func stringFieldType(node schemaNode) string {
+	switch node.Format {
+	case "date":
+		return "date"
+	case "date-time":
+		return "datetime"
+	case "email":
+		return "email"
+	case "uri", "url":
+		return "url"
+	}
+	return "string"
+}
+
+// orderedKeys returns an object's keys in document order.
+func orderedKeys(raw json.RawMessage) []string {
+	dec := json.NewDecoder(bytes.NewReader(raw))
+	if tok, err := dec.Token(); err != nil || tok != json.Delim('{') {
+		return nil
+	}
+	var keys []string
+	for dec.More() {
+		tok, err := dec.Token()
+		if err != nil {
+			return nil
+		}
+		key, ok := tok.(string)
+		if !ok {
+			return nil
+		}
+		var skip json.RawMessage
+		if err := dec.Decode(&skip); err != nil {
+			return nil
+		}
+		keys = append(keys, key)
+	}
+	return keys
+}
+
+// labelFor turns a property name into a label: customer_id becomes
+// "Customer id" and customerID becomes "Customer ID".
+func labelFor(id string) string {
+	var (
+		b    strings.Builder
+		prev rune
+	)
+	for _, r := range id {
+		switch {
+		case r == '_' || r == '-':
+			b.WriteRune(' ')
+		case r >= 'A' && r <= 'Z' && (prev >= 'a' && prev <= 'z' || prev >= '0' && prev <= '9'):
+			b.WriteRune(' ')
+			b.WriteRune(r)
+		default:
+			b.WriteRune(r)
+		}
+		prev = r
+	}
+	label := strings.TrimSpace(b.String())
+	if label == "" {
+		return id
+	}
+	return strings.ToUpper(label[:1]) + label[1:]
+}
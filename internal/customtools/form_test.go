@@ -0,0 +1,66 @@
+package customtools
+
+import (
+	"encoding/json"
+	"testing"
+)
+
+func TestFormForFollowsInputSchema(t *testing.T) {
+	tool := Tool{Name: "create_ticket", Description: "Open a support ticket", InputSchema: json.RawMessage(`{
+		"type": "object",
+		"required": ["subject", "priority"],
+		"properties": {
+			"subject": {"type": "string", "maxLength": 120},
+			"priority": {"type": "string", "enum": ["low", "high"], "default": "low"},
+			"due": {"type": "string", "format": "date"},
+			"customerID": {"type": ["integer", "null"], "minimum": 1},
+			"contact": {"type": "object", "properties": {"email": {"type": "string", "format": "email"}}},
+			"tags": {"type": "array", "items": {"type": "string"}},
+			"extra": {"anyOf": [{"type": "string"}, {"type": "number"}]}
+		}
+	}`)}
+	form := FormFor(tool)
+	if form.Title != "create_ticket" || form.Description != "Open a support ticket" {
+		t.Fatalf("unexpected form header %+v", form)
+	}
+	want := []struct {
+		id, label, typ string
+		required       bool
+	}{
+		{"subject", "Subject", "string", true},
+		{"priority", "Priority", "select", true},
+		{"due", "Due", "date", false},
+		{"customerID", "Customer ID", "integer", false},
+		{"contact", "Contact", "object", false},
+		{"tags", "Tags", "array", false},
+		{"extra", "Extra", "json", false},
+	}
+	if len(form.Fields) != len(want) {
+		t.Fatalf("expected %d fields, got %+v", len(want), form.Fields)
+	}
+	for i, w := range want {
+		f := form.Fields[i]
+		if f.ID != w.id || f.Label != w.label || f.Type != w.typ || f.Required != w.required {
+			t.Fatalf("field %d: expected %+v, got %+v", i, w, f)
+		}
+	}
+	if priority := form.Fields[1]; len(priority.Options) != 2 || priority.Default != "low" {
+		t.Fatalf("unexpected select field %+v", priority)
+	}
+	if subject := form.Fields[0]; subject.MaxLength == nil || *subject.MaxLength != 120 {
+		t.Fatalf("expected max length on subject, got %+v", subject)
+	}
+	if contact := form.Fields[4]; len(contact.Fields) != 1 || contact.Fields[0].Type != "email" {
+		t.Fatalf("unexpected nested fields %+v", contact.Fields)
+	}
+	if tags := form.Fields[5]; tags.Items == nil || tags.Items.Type != "string" {
+		t.Fatalf("unexpected array items %+v", tags.Items)
+	}
+}
+
+func TestFormForWithoutProperties(t *testing.T) {
+	form := FormFor(Tool{Name: "ping", InputSchema: json.RawMessage(`{"type":"object"}`)})
+	if form.Fields == nil || len(form.Fields) != 0 {
+		t.Fatalf("expected an empty field list, got %+v", form.Fields)
+	}
+}
@@ -0,0 +1,222 @@
+package customtools
+
+import (
+	"context"
+	"encoding/base64"
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"strings"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/chaos"
+	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/mocktools"
+	"github.com/neural-chilli/aceryx/internal/observability"
+)
+
+// CustomHTTPProtocol executes user-defined tools by calling their HTTP
+// endpoint. Requests go through the connector egress policy.
+type CustomHTTPProtocol struct {
+	store   *Store
+	secrets connectors.SecretStore
+	egress  connectors.EgressPolicy
+	mocks   *mocktools.MockProtocol
+}
+
+func NewCustomHTTPProtocol(store *Store, secrets connectors.SecretStore, egress connectors.EgressPolicy) *CustomHTTPProtocol {
+	return &CustomHTTPProtocol{store: store, secrets: secrets, egress: egress}
+}
+
+// SetMockProtocol answers calls made under a mock profile from the profile
+// instead of the tool's endpoint.
+func (p *CustomHTTPProtocol) SetMockProtocol(mocks *mocktools.MockProtocol) {
+	p.mocks = mocks
+}
+
+// Tools lists the tenant's custom tools.
+func (p *CustomHTTPProtocol) Tools(ctx context.Context, tenantID uuid.UUID) ([]Tool, error) {
+	if p == nil || p.store == nil {
+		return nil, fmt.Errorf("custom tools not configured")
+	}
+	return p.store.List(ctx, tenantID)
+}
+
+// Lookup returns the named tool's definition.
+func (p *CustomHTTPProtocol) Lookup(ctx context.Context, tenantID uuid.UUID, name string) (Tool, error) {
+	if p == nil || p.store == nil {
+		return Tool{}, fmt.Errorf("custom tools not configured")
+	}
+	return p.store.Get(ctx, tenantID, name)
+}
+
+// Invoke calls the named tool with args, which must satisfy its input schema.
+func (p *CustomHTTPProtocol) Invoke(ctx context.Context, tenantID uuid.UUID, name string, args json.RawMessage) (json.RawMessage, error) {
+	tool, err := p.Lookup(ctx, tenantID, name)
+	if err != nil {
+		return nil, err
+	}
+	return p.InvokeTool(ctx, tenantID, tool, args)
+}
+
+// InvokeTool calls a tool already loaded with Lookup, or answers from the
+// execution's mock profile when it has one.
+func (p *CustomHTTPProtocol) InvokeTool(ctx context.Context, tenantID uuid.UUID, tool Tool, args json.RawMessage) (json.RawMessage, error) {
+	if out, mocked, err := p.mocks.Intercept(ctx, ProtocolName, tool.Name, args); mocked {
+		return out, err
+	}
+	toolCtx, endSpan := observability.StartToolSpan(ctx, tool.Name, ProtocolName)
+	var out json.RawMessage
+	err := chaos.Inject(toolCtx, ProtocolName, tool.Name)
+	if err == nil {
+		out, err = p.call(toolCtx, tenantID, tool, args)
+	}
+	endSpan(err)
+	return out, err
+}
+
+func (p *CustomHTTPProtocol) call(ctx context.Context, tenantID uuid.UUID, tool Tool, args json.RawMessage) (json.RawMessage, error) {
+	if len(strings.TrimSpace(string(args))) == 0 {
+		args = json.RawMessage(`{}`)
+	}
+	var argValues map[string]any
+	if err := json.Unmarshal(args, &argValues); err != nil {
+		return nil, fmt.Errorf("custom tool %s: %w: arguments must be a JSON object", tool.Name, ErrInvalidArguments)
+	}
+	if err := validateAgainst(tool.InputSchema, argValues); err != nil {
+		return nil, fmt.Errorf("custom tool %s: %w: %w", tool.Name, ErrInvalidArguments, err)
+	}
+
+	tplCtx := map[string]any{
+		"args": argValues,
+		"__secret_resolver": func(key string) string {
+			value, _ := p.secret(ctx, tenantID, key)
+			return value
+		},
+	}
+	ep := tool.Endpoint
+	target := connectors.ResolveTemplateURL(ep.URL, tplCtx)
+	headers := make(map[string]string, len(ep.Headers)+1)
+	for k, v := range ep.Headers {
+		headers[k] = connectors.ResolveTemplateString(v, tplCtx)
+	}
+	if err := p.applyAuth(ctx, tenantID, ep.Auth, headers); err != nil {
+		return nil, fmt.Errorf("custom tool %s: %w", tool.Name, err)
+	}
+	var body any
+	if ep.Method != http.MethodGet && ep.Method != http.MethodDelete {
+		body = argValues
+	}
+	timeout := defaultInvokeTimeout
+	if ep.TimeoutMS > 0 {
+		timeout = time.Duration(ep.TimeoutMS) * time.Millisecond
+	}
+	egress := p.egress
+	status, _, payload, err := connectors.DoJSONRequestWithOptions(ctx, ep.Method, target, headers, body, connectors.RequestOptions{
+		Timeout: timeout,
+		Egress:  &egress,
+	})
+	if err != nil {
+		return nil, fmt.Errorf("custom tool %s: %w", tool.Name, err)
+	}
+	if status >= 400 {
+		detail := strings.TrimSpace(string(payload))
+		if len(detail) > maxErrorBodyBytes {
+			detail = detail[:maxErrorBodyBytes]
+		}
+		return nil, fmt.Errorf("custom tool %s: endpoint returned %d: %s", tool.Name, status, detail)
+	}
+
+	out := json.RawMessage(strings.TrimSpace(string(payload)))
+	switch {
+	case len(out) == 0:
+		out = json.RawMessage(`{}`)
+	case !json.Valid(out):
+		// Plain-text responses are wrapped so tool output is always JSON.
+		out, _ = json.Marshal(map[string]any{"body": string(payload)})
+	}
+	if len(tool.OutputSchema) > 0 {
+		var value any
+		_ = json.Unmarshal(out, &value)
+		if err := validateAgainst(tool.OutputSchema, value); err != nil {
+			return nil, fmt.Errorf("custom tool %s: response does not match output schema: %w", tool.Name, err)
+		}
+	}
+	return out, nil
+}
+
+func (p *CustomHTTPProtocol) applyAuth(ctx context.Context, tenantID uuid.UUID, auth AuthProfile, headers map[string]string) error {
+	switch auth.Type {
+	case "", "none":
+		return nil
+	case "bearer":
+		token, err := p.secret(ctx, tenantID, auth.SecretRef)
+		if err != nil {
+			return err
+		}
+		headers["Authorization"] = "Bearer " + token
+	case "api_key":
+		key, err := p.secret(ctx, tenantID, auth.SecretRef)
+		if err != nil {
+			return err
+		}
+		header := strings.TrimSpace(auth.HeaderName)
+		if header == "" {
+			header = "X-API-Key"
+		}
+		headers[header] = key
+	case "basic":
+		user, err := p.secret(ctx, tenantID, auth.UsernameRef)
+		if err != nil {
+			return err
+		}
+		pass, err := p.secret(ctx, tenantID, auth.PasswordRef)
+		if err != nil {
+			return err
+		}
+		headers["Authorization"] = "Basic " + base64.StdEncoding.EncodeToString([]byte(user+":"+pass))
+	default:
+		return fmt.Errorf("unsupported auth type %q", auth.Type)
+	}
+	return nil
+}
+
+func (p *CustomHTTPProtocol) secret(ctx context.Context, tenantID uuid.UUID, key string) (string, error) {
+	if p.secrets == nil {
+		return "", fmt.Errorf("secret store not configured")
+	}
+	value, err := p.secrets.Get(ctx, tenantID, strings.TrimSpace(key))
+	if err != nil {
+		return "", fmt.Errorf("resolve secret %s: %w", key, err)
+	}
+	return value, nil
+}
+
+func validateAgainst(schema json.RawMessage, value any) error {
+	if len(schema) == 0 {
+		return nil
+	}
+	compiled, err := compileSchema(schema)
+	if err != nil {
+		return err
+	}
+	return compiled.Validate(value)
+}
+
+// ToolDescription describes the tool a custom-tool step calls, for generated
+// workflow docs. Other step types get "".
+func (p *CustomHTTPProtocol) ToolDescription(ctx context.Context, tenantID uuid.UUID, stepType string, config json.RawMessage) string {
+	if stepType != "custom-tool" {
+		return ""
+	}
+	var cfg StepConfig
+	if err := json.Unmarshal(config, &cfg); err != nil || strings.TrimSpace(cfg.Tool) == "" {
+		return ""
+	}
+	tool, err := p.Lookup(ctx, tenantID, cfg.Tool)
+	if err != nil {
+		return ""
+	}
+	return tool.Description
+}
@@ -0,0 +1,111 @@
+package customtools
+
+import (
+	"context"
+	"encoding/json"
+	"io"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/connectors"
+)
+
+type stubSecrets map[string]string
+
+func (s stubSecrets) Get(_ context.Context, _ uuid.UUID, key string) (string, error) {
+	return s[key], nil
+}
+
+func TestNormalizeValidatesDefinitions(t *testing.T) {
+	valid := CreateRequest{
+		Name:        "lookup_customer",
+		InputSchema: json.RawMessage(`{"type":"object","required":["id"]}`),
+		Endpoint:    Endpoint{URL: "https://crm.example.com/customers/{{args.id}}", Method: "get"},
+	}
+	tool, err := normalize(valid)
+	if err != nil {
+		t.Fatalf("normalize: %v", err)
+	}
+	if tool.Endpoint.Method != "GET" {
+		t.Fatalf("expected method to be upper-cased, got %q", tool.Endpoint.Method)
+	}
+
+	cases := []struct {
+		name   string
+		mutate func(*CreateRequest)
+		want   string
+	}{
+		{"bad name", func(r *CreateRequest) { r.Name = "Lookup Customer" }, "name must be"},
+		{"bad schema", func(r *CreateRequest) { r.InputSchema = json.RawMessage(`{"type":"nope"}`) }, "invalid input_schema"},
+		{"relative url", func(r *CreateRequest) { r.Endpoint.URL = "/customers" }, "absolute http"},
+		{"templated host", func(r *CreateRequest) { r.Endpoint.URL = "https://{{args.host}}/customers" }, "host cannot contain"},
+		{"bad method", func(r *CreateRequest) { r.Endpoint.Method = "TRACE" }, "unsupported method"},
+		{"missing secret", func(r *CreateRequest) { r.Endpoint.Auth = AuthProfile{Type: "bearer"} }, "secret_ref is required"},
+	}
+	for _, tc := range cases {
+		req := valid
+		tc.mutate(&req)
+		if _, err := normalize(req); err == nil || !strings.Contains(err.Error(), tc.want) {
+			t.Fatalf("%s: expected error containing %q, got %v", tc.name, tc.want, err)
+		}
+	}
+}
+
+func TestCallMapsArgumentsOntoRequest(t *testing.T) {
+	var gotPath, gotAuth, gotBody string
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		gotPath = r.URL.EscapedPath()
+		gotAuth = r.Header.Get("Authorization")
+		raw, _ := io.ReadAll(r.Body)
+		gotBody = string(raw)
+		_, _ = w.Write([]byte(`{"status":"ok"}`))
+	}))
+	defer srv.Close()
+
+	tool, err := normalize(CreateRequest{
+		Name:         "update_customer",
+		InputSchema:  json.RawMessage(`{"type":"object","required":["id"],"properties":{"id":{"type":"string"}}}`),
+		OutputSchema: json.RawMessage(`{"type":"object","required":["status"]}`),
+		Endpoint: Endpoint{
+			URL:    srv.URL + "/customers/{{args.id}}",
+			Method: "PUT",
+			Auth:   AuthProfile{Type: "bearer", SecretRef: "crm_token"},
+		},
+	})
+	if err != nil {
+		t.Fatalf("normalize: %v", err)
+	}
+	protocol := NewCustomHTTPProtocol(nil, stubSecrets{"crm_token": "s3cret"}, connectors.EgressPolicy{AllowPrivate: true})
+
+	out, err := protocol.call(context.Background(), uuid.New(), tool, json.RawMessage(`{"id":"a/b","tier":"gold"}`))
+	if err != nil {
+		t.Fatalf("call: %v", err)
+	}
+	if string(out) != `{"status":"ok"}` {
+		t.Fatalf("unexpected output %s", out)
+	}
+	if gotPath != "/customers/a%2Fb" || gotAuth != "Bearer s3cret" || !strings.Contains(gotBody, `"tier":"gold"`) {
+		t.Fatalf("unexpected request path=%q auth=%q body=%q", gotPath, gotAuth, gotBody)
+	}
+
+	if _, err := protocol.call(context.Background(), uuid.New(), tool, json.RawMessage(`{"tier":"gold"}`)); err == nil || !strings.Contains(err.Error(), "invalid arguments") {
+		t.Fatalf("expected missing argument to be rejected, got %v", err)
+	}
+}
+
+func TestCallReportsEndpointErrors(t *testing.T) {
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+		http.Error(w, "no such customer", http.StatusNotFound)
+	}))
+	defer srv.Close()
+
+	tool := Tool{Name: "lookup", Endpoint: Endpoint{URL: srv.URL, Method: "GET"}}
+	protocol := NewCustomHTTPProtocol(nil, nil, connectors.EgressPolicy{AllowPrivate: true})
+	_, err := protocol.call(context.Background(), uuid.New(), tool, nil)
+	if err == nil || !strings.Contains(err.Error(), "returned 404: no such customer") {
+		t.Fatalf("unexpected error %v", err)
+	}
+}
@@ -0,0 +1,128 @@
+package customtools
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"fmt"
+	"strings"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+type StepExecutor struct {
+	db       *sql.DB
+	protocol *CustomHTTPProtocol
+}
+
+type StepConfig struct {
+	Tool       string         `json:"tool"`
+	Arguments  map[string]any `json:"arguments"`
+	OutputPath string         `json:"output_path"`
+}
+
+func NewStepExecutor(db *sql.DB, protocol *CustomHTTPProtocol) *StepExecutor {
+	return &StepExecutor{db: db, protocol: protocol}
+}
+
+func (e *StepExecutor) Execute(ctx context.Context, caseID uuid.UUID, _ string, raw json.RawMessage) (*engine.StepResult, error) {
+	if e == nil || e.protocol == nil {
+		return nil, fmt.Errorf("custom tool step executor not configured")
+	}
+	cfg := StepConfig{}
+	if err := json.Unmarshal(raw, &cfg); err != nil {
+		return nil, fmt.Errorf("parse custom-tool step config: %w", err)
+	}
+	if strings.TrimSpace(cfg.Tool) == "" {
+		return nil, fmt.Errorf("tool is required")
+	}
+	tplCtx, tenantID, err := e.loadCaseContext(ctx, caseID)
+	if err != nil {
+		return nil, err
+	}
+	engine.AddForEachItem(ctx, tplCtx)
+	args, err := json.Marshal(connectors.ResolveTemplateAny(cfg.Arguments, tplCtx))
+	if err != nil {
+		return nil, fmt.Errorf("marshal custom tool arguments: %w", err)
+	}
+	if string(args) == "null" {
+		args = []byte(`{}`)
+	}
+	tool, err := e.protocol.Lookup(ctx, tenantID, cfg.Tool)
+	if err != nil {
+		return nil, err
+	}
+	output, err := e.protocol.InvokeTool(ctx, tenantID, tool, args)
+	if err != nil {
+		return nil, err
+	}
+	patch, err := buildCasePatch(cfg.OutputPath, output)
+	if err != nil {
+		return nil, err
+	}
+	return &engine.StepResult{
+		Outcome:        "ok",
+		Output:         output,
+		WritesCaseData: len(patch) > 0,
+		CaseDataPatch:  patch,
+		AuditEventType: "custom_tool.invoked",
+		Sensitive:      tool.SensitiveOutput,
+	}, nil
+}
+
+func (e *StepExecutor) loadCaseContext(ctx context.Context, caseID uuid.UUID) (map[string]any, uuid.UUID, error) {
+	if e.db == nil {
+		return nil, uuid.Nil, fmt.Errorf("custom tool step executor db not configured")
+	}
+	var (
+		tenantID uuid.UUID
+		caseData []byte
+	)
+	if err := e.db.QueryRowContext(ctx, `SELECT tenant_id, data FROM cases WHERE id = $1`, caseID).Scan(&tenantID, &caseData); err != nil {
+		return nil, uuid.Nil, fmt.Errorf("load case context for custom tool step: %w", err)
+	}
+	caseMap := map[string]any{}
+	if len(caseData) > 0 {
+		if err := json.Unmarshal(caseData, &caseMap); err != nil {
+			return nil, uuid.Nil, fmt.Errorf("decode case data for custom tool step: %w", err)
+		}
+	}
+	return map[string]any{
+		"case": map[string]any{"data": caseMap},
+		"now":  time.Now().UTC().Format(time.RFC3339),
+	}, tenantID, nil
+}
+
+func buildCasePatch(outputPath string, output json.RawMessage) (json.RawMessage, error) {
+	path := strings.TrimSpace(outputPath)
+	path = strings.TrimPrefix(path, "case.data.")
+	path = strings.TrimPrefix(path, "data.")
+	path = strings.Trim(path, ".")
+	if path == "" {
+		return nil, nil
+	}
+	var value any
+	if err := json.Unmarshal(output, &value); err != nil {
+		return nil, fmt.Errorf("decode custom tool output: %w", err)
+	}
+	parts := strings.Split(path, ".")
+	root := map[string]any{}
+	cur := root
+	for i, part := range parts {
+		if i == len(parts)-1 {
+			cur[part] = value
+			break
+		}
+		next := map[string]any{}
+		cur[part] = next
+		cur = next
+	}
+	raw, err := json.Marshal(root)
+	if err != nil {
+		return nil, fmt.Errorf("marshal custom tool case patch: %w", err)
+	}
+	return raw, nil
+}
@@ -0,0 +1,220 @@
+package customtools
+
+import (
+	"bytes"
+	"context"
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"net/url"
+	"regexp"
+	"strings"
+
+	"github.com/google/uuid"
+	jsonschema "github.com/santhosh-tekuri/jsonschema/v5"
+)
+
+var (
+	toolNamePattern    = regexp.MustCompile(`^[a-z][a-z0-9_]{0,63}$`)
+	urlTemplatePattern = regexp.MustCompile(`\{\{\s*[^{}]+?\s*\}\}`)
+	supportedMethods   = map[string]bool{"GET": true, "POST": true, "PUT": true, "PATCH": true, "DELETE": true}
+	supportedAuthTypes = map[string]bool{"": true, "none": true, "bearer": true, "api_key": true, "basic": true}
+)
+
+type Store struct {
+	db *sql.DB
+}
+
+func NewStore(db *sql.DB) *Store {
+	return &Store{db: db}
+}
+
+func (s *Store) Create(ctx context.Context, tenantID, createdBy uuid.UUID, req CreateRequest) (Tool, error) {
+	tool, err := normalize(req)
+	if err != nil {
+		return Tool{}, err
+	}
+	endpoint, _ := json.Marshal(tool.Endpoint)
+	var outputSchema any
+	if len(tool.OutputSchema) > 0 {
+		outputSchema = string(tool.OutputSchema)
+	}
+	tool.TenantID = tenantID
+	tool.CreatedBy = &createdBy
+	err = s.db.QueryRowContext(ctx, `
+INSERT INTO custom_tools (tenant_id, name, description, input_schema, output_schema, endpoint, sensitive_output, created_by)
+VALUES ($1, $2, $3, $4::jsonb, $5::jsonb, $6::jsonb, $7, $8)
+ON CONFLICT (tenant_id, name) DO NOTHING
+RETURNING id, created_at, updated_at
+`, tenantID, tool.Name, tool.Description, string(tool.InputSchema), outputSchema, string(endpoint), tool.SensitiveOutput, createdBy).Scan(&tool.ID, &tool.CreatedAt, &tool.UpdatedAt)
+	if errors.Is(err, sql.ErrNoRows) {
+		return Tool{}, ErrToolExists
+	}
+	if err != nil {
+		return Tool{}, fmt.Errorf("insert custom tool: %w", err)
+	}
+	return tool, nil
+}
+
+func (s *Store) List(ctx context.Context, tenantID uuid.UUID) ([]Tool, error) {
+	rows, err := s.db.QueryContext(ctx, `
+SELECT id, tenant_id, name, description, input_schema, output_schema, endpoint, sensitive_output, created_by, created_at, updated_at
+FROM custom_tools
+WHERE tenant_id = $1
+ORDER BY name
+`, tenantID)
+	if err != nil {
+		return nil, fmt.Errorf("list custom tools: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	out := make([]Tool, 0)
+	for rows.Next() {
+		tool, err := scanTool(rows)
+		if err != nil {
+			return nil, err
+		}
+		out = append(out, tool)
+	}
+	if err := rows.Err(); err != nil {
+		return nil, fmt.Errorf("iterate custom tools: %w", err)
+	}
+	return out, nil
+}
+
+func (s *Store) Get(ctx context.Context, tenantID uuid.UUID, name string) (Tool, error) {
+	row := s.db.QueryRowContext(ctx, `
+SELECT id, tenant_id, name, description, input_schema, output_schema, endpoint, sensitive_output, created_by, created_at, updated_at
+FROM custom_tools
+WHERE tenant_id = $1 AND name = $2
+`, tenantID, strings.TrimSpace(name))
+	tool, err := scanTool(row)
+	if errors.Is(err, sql.ErrNoRows) {
+		return Tool{}, ErrToolNotFound
+	}
+	return tool, err
+}
+
+func (s *Store) Delete(ctx context.Context, tenantID uuid.UUID, name string) error {
+	res, err := s.db.ExecContext(ctx, `DELETE FROM custom_tools WHERE tenant_id = $1 AND name = $2`, tenantID, strings.TrimSpace(name))
+	if err != nil {
+		return fmt.Errorf("delete custom tool: %w", err)
+	}
+	if n, _ := res.RowsAffected(); n == 0 {
+		return ErrToolNotFound
+	}
+	return nil
+}
+
+type rowScanner interface {
+	Scan(dest ...any) error
+}
+
+func scanTool(row rowScanner) (Tool, error) {
+	var (
+		tool         Tool
+		inputSchema  []byte
+		outputSchema []byte
+		endpoint     []byte
+	)
+	if err := row.Scan(&tool.ID, &tool.TenantID, &tool.Name, &tool.Description, &inputSchema, &outputSchema, &endpoint, &tool.SensitiveOutput, &tool.CreatedBy, &tool.CreatedAt, &tool.UpdatedAt); err != nil {
+		if errors.Is(err, sql.ErrNoRows) {
+			return Tool{}, err
+		}
+		return Tool{}, fmt.Errorf("scan custom tool: %w", err)
+	}
+	tool.InputSchema = inputSchema
+	tool.OutputSchema = outputSchema
+	if err := json.Unmarshal(endpoint, &tool.Endpoint); err != nil {
+		return Tool{}, fmt.Errorf("decode custom tool endpoint: %w", err)
+	}
+	return tool, nil
+}
+
+// normalize validates a tool definition and returns it with defaults applied.
+func normalize(req CreateRequest) (Tool, error) {
+	tool := Tool{
+		Name:            strings.TrimSpace(req.Name),
+		Description:     strings.TrimSpace(req.Description),
+		Endpoint:        req.Endpoint,
+		SensitiveOutput: req.SensitiveOutput,
+	}
+	if !toolNamePattern.MatchString(tool.Name) {
+		return Tool{}, fmt.Errorf("name must be lowercase letters, digits and underscores, starting with a letter")
+	}
+	inputSchema, err := compactSchema("input_schema", req.InputSchema)
+	if err != nil {
+		return Tool{}, err
+	}
+	if inputSchema == nil {
+		inputSchema = json.RawMessage(`{"type":"object"}`)
+	}
+	tool.InputSchema = inputSchema
+	if tool.OutputSchema, err = compactSchema("output_schema", req.OutputSchema); err != nil {
+		return Tool{}, err
+	}
+
+	ep := &tool.Endpoint
+	ep.URL = strings.TrimSpace(ep.URL)
+	ep.Method = strings.ToUpper(strings.TrimSpace(ep.Method))
+	if ep.Method == "" {
+		ep.Method = "POST"
+	}
+	if !supportedMethods[ep.Method] {
+		return Tool{}, fmt.Errorf("unsupported method %q", ep.Method)
+	}
+	parsed, err := url.Parse(urlTemplatePattern.ReplaceAllString(ep.URL, "x"))
+	if err != nil || (parsed.Scheme != "http" && parsed.Scheme != "https") || parsed.Host == "" {
+		return Tool{}, fmt.Errorf("endpoint url must be an absolute http or https url")
+	}
+	// Placeholders may only appear after the host, where they cannot send
+	// the request somewhere else.
+	_, authority, _ := strings.Cut(ep.URL, "://")
+	if end := strings.IndexAny(authority, "/?#"); end >= 0 {
+		authority = authority[:end]
+	}
+	if strings.Contains(authority, "{{") {
+		return Tool{}, fmt.Errorf("endpoint url host cannot contain placeholders")
+	}
+	if ep.TimeoutMS < 0 || ep.TimeoutMS > int(maxInvokeTimeout.Milliseconds()) {
+		return Tool{}, fmt.Errorf("timeout_ms must be between 0 and %d", maxInvokeTimeout.Milliseconds())
+	}
+	ep.Auth.Type = strings.ToLower(strings.TrimSpace(ep.Auth.Type))
+	if !supportedAuthTypes[ep.Auth.Type] {
+		return Tool{}, fmt.Errorf("unsupported auth type %q", ep.Auth.Type)
+	}
+	switch ep.Auth.Type {
+	case "bearer", "api_key":
+		if strings.TrimSpace(ep.Auth.SecretRef) == "" {
+			return Tool{}, fmt.Errorf("auth secret_ref is required for %s", ep.Auth.Type)
+		}
+	case "basic":
+		if strings.TrimSpace(ep.Auth.UsernameRef) == "" || strings.TrimSpace(ep.Auth.PasswordRef) == "" {
+			return Tool{}, fmt.Errorf("auth username_ref and password_ref are required for basic")
+		}
+	}
+	return tool, nil
+}
+
+func compactSchema(field string, raw json.RawMessage) (json.RawMessage, error) {
+	raw = bytes.TrimSpace(raw)
+	if len(raw) == 0 || string(raw) == "null" {
+		return nil, nil
+	}
+	if _, err := compileSchema(raw); err != nil {
+		return nil, fmt.Errorf("invalid %s: %w", field, err)
+	}
+	var buf bytes.Buffer
+	if err := json.Compact(&buf, raw); err != nil {
+		return nil, fmt.Errorf("invalid %s: %w", field, err)
+	}
+	return buf.Bytes(), nil
+}
+
+func compileSchema(raw json.RawMessage) (*jsonschema.Schema, error) {
+	compiler := jsonschema.NewCompiler()
+	if err := compiler.AddResource("schema.json", bytes.NewReader(raw)); err != nil {
+		return nil, err
+	}
+	return compiler.Compile("schema.json")
+}
@@ -0,0 +1,112 @@
+package customtools
+
+import (
+	"encoding/json"
+	"errors"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+const (
+	ProtocolName         = "custom_http"
+	defaultInvokeTimeout = 30 * time.Second
+	maxInvokeTimeout     = 5 * time.Minute
+	maxErrorBodyBytes    = 512
+)
+
+var (
+	ErrToolNotFound     = errors.New("custom tool not found")
+	ErrToolExists       = errors.New("custom tool already exists")
+	ErrInvalidArguments = errors.New("invalid arguments")
+	ErrExampleNotFound  = errors.New("custom tool example not found")
+	ErrExampleExists    = errors.New("custom tool example already exists")
+)
+
+// Tool is a user-defined tool backed by an HTTP endpoint. SensitiveOutput
+// keeps the tool's responses out of step records.
+type Tool struct {
+	ID              uuid.UUID       `json:"id"`
+	TenantID        uuid.UUID       `json:"tenant_id"`
+	Name            string          `json:"name"`
+	Description     string          `json:"description"`
+	InputSchema     json.RawMessage `json:"input_schema"`
+	OutputSchema    json.RawMessage `json:"output_schema,omitempty"`
+	Endpoint        Endpoint        `json:"endpoint"`
+	SensitiveOutput bool            `json:"sensitive_output"`
+	CreatedBy       *uuid.UUID      `json:"created_by,omitempty"`
+	CreatedAt       time.Time       `json:"created_at"`
+	UpdatedAt       time.Time       `json:"updated_at"`
+}
+
+// Endpoint maps a tool call onto an HTTP request. URL and header values are
+// templates over the call arguments ({{args.id}}) and secrets
+// ({{secrets.key}}); values substituted into the URL are path-escaped. For
+// methods with a body the arguments are sent as the JSON body.
+type Endpoint struct {
+	URL       string            `json:"url"`
+	Method    string            `json:"method"`
+	Headers   map[string]string `json:"headers,omitempty"`
+	Auth      AuthProfile       `json:"auth"`
+	TimeoutMS int               `json:"timeout_ms,omitempty"`
+}
+
+// AuthProfile names the secrets used to authenticate to the endpoint. Type is
+// none, bearer, api_key or basic.
+type AuthProfile struct {
+	Type        string `json:"type"`
+	SecretRef   string `json:"secret_ref,omitempty"`
+	HeaderName  string `json:"header_name,omitempty"`
+	UsernameRef string `json:"username_ref,omitempty"`
+	PasswordRef string `json:"password_ref,omitempty"`
+}
+
+type CreateRequest struct {
+	Name            string          `json:"name"`
+	Description     string          `json:"description"`
+	InputSchema     json.RawMessage `json:"input_schema"`
+	OutputSchema    json.RawMessage `json:"output_schema,omitempty"`
+	Endpoint        Endpoint        `json:"endpoint"`
+	SensitiveOutput bool            `json:"sensitive_output,omitempty"`
+}
+
+// Example is a saved call to a tool. Input satisfies the tool's input schema
+// when the example is saved; ExpectedOutput, when set, is compared with the
+// output of a run.
+type Example struct {
+	ID             uuid.UUID       `json:"id"`
+	Name           string          `json:"name"`
+	Description    string          `json:"description"`
+	Input          json.RawMessage `json:"input"`
+	ExpectedOutput json.RawMessage `json:"expected_output,omitempty"`
+	CreatedBy      *uuid.UUID      `json:"created_by,omitempty"`
+	CreatedAt      time.Time       `json:"created_at"`
+}
+
+type ExampleRequest struct {
+	Name           string          `json:"name"`
+	Description    string          `json:"description"`
+	Input          json.RawMessage `json:"input"`
+	ExpectedOutput json.RawMessage `json:"expected_output,omitempty"`
+}
+
+// ExampleRun is the result of running an example. Matches is set only when
+// the example has an expected output.
+type ExampleRun struct {
+	Example Example         `json:"example"`
+	Output  json.RawMessage `json:"output"`
+	Matches *bool           `json:"matches,omitempty"`
+}
+
+// PlaygroundRequest runs a tool with Input. With SaveAs, a successful run is
+// saved as an example named SaveAs, with its output as the expected output.
+type PlaygroundRequest struct {
+	Input       json.RawMessage `json:"input"`
+	SaveAs      string          `json:"save_as,omitempty"`
+	Description string          `json:"description,omitempty"`
+}
+
+type PlaygroundRun struct {
+	Output  json.RawMessage `json:"output"`
+	Example *Example        `json:"example,omitempty"`
+}
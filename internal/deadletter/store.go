@@ -0,0 +1,288 @@
+// Package deadletter keeps steps that failed after exhausting their retries,
+// with the input and case context they failed on, so operators can inspect
+// and requeue them.
+package deadletter
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"log/slog"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+const (
+	StatusOpen     = "open"
+	StatusRequeued = "requeued"
+
+	DefaultListLimit = 50
+	MaxListLimit     = 500
+)
+
+var (
+	ErrNotFound        = errors.New("dead letter not found")
+	ErrAlreadyRequeued = errors.New("dead letter already requeued")
+)
+
+type Entry struct {
+	ID         uuid.UUID       `json:"id"`
+	TenantID   uuid.UUID       `json:"tenant_id"`
+	CaseID     uuid.UUID       `json:"case_id"`
+	CaseNumber string          `json:"case_number"`
+	StepID     string          `json:"step_id"`
+	StepType   string          `json:"step_type"`
+	Attempts   int             `json:"attempts"`
+	Error      string          `json:"error"`
+	Input      json.RawMessage `json:"input"`
+	Context    json.RawMessage `json:"context"`
+	Status     string          `json:"status"`
+	CreatedAt  time.Time       `json:"created_at"`
+	RequeuedAt *time.Time      `json:"requeued_at,omitempty"`
+	RequeuedBy *uuid.UUID      `json:"requeued_by,omitempty"`
+}
+
+// Resumer re-runs failed steps of a case; *engine.Engine implements it.
+type Resumer interface {
+	ResumeCase(ctx context.Context, caseID, actorID uuid.UUID, stepIDs []string) ([]string, error)
+}
+
+// Notifier is told about every new dead letter.
+type Notifier interface {
+	Notify(ctx context.Context, entry Entry) error
+}
+
+type Store struct {
+	db       *sql.DB
+	resumer  Resumer
+	notifier Notifier
+}
+
+func NewStore(db *sql.DB, resumer Resumer) *Store {
+	return &Store{db: db, resumer: resumer}
+}
+
+func (s *Store) SetNotifier(notifier Notifier) {
+	s.notifier = notifier
+}
+
+// OnStepFailed is an engine.FailureHook. Notification happens in the
+// background so a slow receiver does not hold the step worker.
+func (s *Store) OnStepFailed(ctx context.Context, failure engine.StepFailure) {
+	entry, err := s.Record(ctx, failure)
+	if err != nil {
+		slog.ErrorContext(ctx, "record dead letter failed", "case_id", failure.CaseID.String(), "step_id", failure.StepID, "error", err)
+		return
+	}
+	if s.notifier == nil {
+		return
+	}
+	go func() {
+		notifyCtx, cancel := context.WithTimeout(context.WithoutCancel(ctx), 30*time.Second)
+		defer cancel()
+		if err := s.notifier.Notify(notifyCtx, entry); err != nil {
+			slog.WarnContext(notifyCtx, "dead letter notification failed", "dead_letter_id", entry.ID.String(), "error", err)
+		}
+	}()
+}
+
+// Record stores failure with the step's configuration as input and the case
+// data and step results as context.
+func (s *Store) Record(ctx context.Context, failure engine.StepFailure) (Entry, error) {
+	entry := Entry{
+		CaseID:   failure.CaseID,
+		StepID:   failure.StepID,
+		StepType: failure.StepType,
+		Attempts: failure.Attempts,
+		Error:    failure.Error,
+		Status:   StatusOpen,
+	}
+	var (
+		dataRaw []byte
+		astRaw  []byte
+	)
+	err := s.db.QueryRowContext(ctx, `
+SELECT c.tenant_id, c.case_number, c.data, wv.ast
+FROM cases c
+JOIN workflow_versions wv ON wv.workflow_id = c.workflow_id AND wv.version = c.workflow_version
+WHERE c.id = $1
+`, failure.CaseID).Scan(&entry.TenantID, &entry.CaseNumber, &dataRaw, &astRaw)
+	if err != nil {
+		return Entry{}, fmt.Errorf("load failed case: %w", err)
+	}
+	entry.Input = stepConfig(astRaw, failure.StepID)
+
+	steps := map[string]json.RawMessage{}
+	rows, err := s.db.QueryContext(ctx, `
+SELECT step_id, COALESCE(result, '{}'::jsonb)
+FROM case_steps
+WHERE case_id = $1
+`, failure.CaseID)
+	if err != nil {
+		return Entry{}, fmt.Errorf("load step results for dead letter: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	for rows.Next() {
+		var stepID string
+		var raw []byte
+		if err := rows.Scan(&stepID, &raw); err != nil {
+			return Entry{}, fmt.Errorf("scan step result for dead letter: %w", err)
+		}
+		steps[stepID] = raw
+	}
+	if err := rows.Err(); err != nil {
+		return Entry{}, fmt.Errorf("iterate step results for dead letter: %w", err)
+	}
+	entry.Context, err = json.Marshal(map[string]any{"case": json.RawMessage(dataRaw), "steps": steps})
+	if err != nil {
+		return Entry{}, fmt.Errorf("marshal dead letter context: %w", err)
+	}
+
+	err = s.db.QueryRowContext(ctx, `
+INSERT INTO dead_letters (tenant_id, case_id, step_id, step_type, attempts, error, input, context)
+VALUES ($1, $2, $3, $4, $5, $6, $7::jsonb, $8::jsonb)
+RETURNING id, created_at
+`, entry.TenantID, entry.CaseID, entry.StepID, entry.StepType, entry.Attempts, entry.Error,
+		string(entry.Input), string(entry.Context)).Scan(&entry.ID, &entry.CreatedAt)
+	if err != nil {
+		return Entry{}, fmt.Errorf("insert dead letter: %w", err)
+	}
+	return entry, nil
+}
+
+// stepConfig returns the config of stepID in a workflow AST, or an empty
+// object when it cannot be found.
+func stepConfig(astRaw []byte, stepID string) json.RawMessage {
+	var ast engine.WorkflowAST
+	if err := json.Unmarshal(astRaw, &ast); err != nil {
+		return json.RawMessage(`{}`)
+	}
+	for _, step := range ast.Steps {
+		if step.ID == stepID && len(step.Config) > 0 {
+			return step.Config
+		}
+	}
+	return json.RawMessage(`{}`)
+}
+
+// List returns the tenant's dead letters, newest first. An empty status
+// returns all of them.
+func (s *Store) List(ctx context.Context, tenantID uuid.UUID, status string, limit int) ([]Entry, error) {
+	if limit <= 0 {
+		limit = DefaultListLimit
+	}
+	if limit > MaxListLimit {
+		limit = MaxListLimit
+	}
+	rows, err := s.db.QueryContext(ctx, `
+SELECT d.id, d.tenant_id, d.case_id, c.case_number, d.step_id, d.step_type, d.attempts, d.error,
+       d.input, d.context, d.status, d.created_at, d.requeued_at, d.requeued_by
+FROM dead_letters d
+JOIN cases c ON c.id = d.case_id
+WHERE d.tenant_id = $1
+  AND ($2 = '' OR d.status = $2)
+ORDER BY d.created_at DESC
+LIMIT $3
+`, tenantID, status, limit)
+	if err != nil {
+		return nil, fmt.Errorf("list dead letters: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	out := make([]Entry, 0)
+	for rows.Next() {
+		entry, err := scanEntry(rows)
+		if err != nil {
+			return nil, err
+		}
+		out = append(out, entry)
+	}
+	if err := rows.Err(); err != nil {
+		return nil, fmt.Errorf("iterate dead letters: %w", err)
+	}
+	return out, nil
+}
+
+// Requeue marks the entry requeued, then resumes the dead-lettered step.
+// Marking first means two requeues of one entry never both resume it; if
+// resuming fails the entry is reopened. engine.ErrNothingToResume is
+// returned when the step is no longer failed.
+func (s *Store) Requeue(ctx context.Context, tenantID, id, actorID uuid.UUID) (Entry, error) {
+	// Truncated to the database's precision so the reopen below matches it.
+	now := time.Now().UTC().Truncate(time.Microsecond)
+	res, err := s.db.ExecContext(ctx, `
+UPDATE dead_letters
+SET status = 'requeued', requeued_at = $3, requeued_by = $4
+WHERE id = $1 AND tenant_id = $2 AND status = 'open'
+`, id, tenantID, now, actorID)
+	if err != nil {
+		return Entry{}, fmt.Errorf("mark dead letter requeued: %w", err)
+	}
+	n, err := res.RowsAffected()
+	if err != nil {
+		return Entry{}, fmt.Errorf("mark dead letter requeued: %w", err)
+	}
+	if n == 0 {
+		if _, err := s.get(ctx, tenantID, id); err != nil {
+			return Entry{}, err
+		}
+		return Entry{}, ErrAlreadyRequeued
+	}
+
+	entry, err := s.get(ctx, tenantID, id)
+	if err == nil {
+		_, err = s.resumer.ResumeCase(ctx, entry.CaseID, actorID, []string{entry.StepID})
+	}
+	if err != nil {
+		if _, reopenErr := s.db.ExecContext(context.WithoutCancel(ctx), `
+UPDATE dead_letters
+SET status = 'open', requeued_at = NULL, requeued_by = NULL
+WHERE id = $1 AND requeued_at = $2
+`, id, now); reopenErr != nil {
+			slog.ErrorContext(ctx, "reopen dead letter failed", "dead_letter_id", id.String(), "error", reopenErr)
+		}
+		return Entry{}, err
+	}
+	return entry, nil
+}
+
+func (s *Store) get(ctx context.Context, tenantID, id uuid.UUID) (Entry, error) {
+	entry, err := scanEntry(s.db.QueryRowContext(ctx, `
+SELECT d.id, d.tenant_id, d.case_id, c.case_number, d.step_id, d.step_type, d.attempts, d.error,
+       d.input, d.context, d.status, d.created_at, d.requeued_at, d.requeued_by
+FROM dead_letters d
+JOIN cases c ON c.id = d.case_id
+WHERE d.id = $1 AND d.tenant_id = $2
+`, id, tenantID))
+	if errors.Is(err, sql.ErrNoRows) {
+		return Entry{}, ErrNotFound
+	}
+	return entry, err
+}
+
+type rowScanner interface {
+	Scan(dest ...any) error
+}
+
+func scanEntry(row rowScanner) (Entry, error) {
+	var (
+		entry   Entry
+		input   []byte
+		caseCtx []byte
+	)
+	if err := row.Scan(
+		&entry.ID, &entry.TenantID, &entry.CaseID, &entry.CaseNumber, &entry.StepID, &entry.StepType, &entry.Attempts, &entry.Error,
+		&input, &caseCtx, &entry.Status, &entry.CreatedAt, &entry.RequeuedAt, &entry.RequeuedBy,
+	); err != nil {
+		if errors.Is(err, sql.ErrNoRows) {
+			return Entry{}, err
+		}
+		return Entry{}, fmt.Errorf("scan dead letter: %w", err)
+	}
+	entry.Input = input
+	entry.Context = caseCtx
+	return entry, nil
+}
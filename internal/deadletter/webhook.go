@@ -0,0 +1,63 @@
+package deadletter
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"time"
+
+	"github.com/neural-chilli/aceryx/internal/events"
+)
+
+// EventDeadLettered is the event type of failure webhooks.
+const EventDeadLettered = "step.dead_lettered"
+
+// WebhookNotifier POSTs a JSON summary of each dead letter to a URL. The
+// case context is left out; receivers can fetch it from the API. Requests
+// carry the same headers and signature as event subscription deliveries, with
+// the dead letter ID as the delivery ID.
+type WebhookNotifier struct {
+	url    string
+	secret string
+	client *http.Client
+}
+
+func NewWebhookNotifier(url, secret string) *WebhookNotifier {
+	return &WebhookNotifier{url: url, secret: secret, client: &http.Client{Timeout: 10 * time.Second}}
+}
+
+func (n *WebhookNotifier) Notify(ctx context.Context, entry Entry) error {
+	body, err := json.Marshal(map[string]any{
+		"event":          EventDeadLettered,
+		"dead_letter_id": entry.ID,
+		"tenant_id":      entry.TenantID,
+		"case_id":        entry.CaseID,
+		"case_number":    entry.CaseNumber,
+		"step_id":        entry.StepID,
+		"step_type":      entry.StepType,
+		"attempts":       entry.Attempts,
+		"error":          entry.Error,
+		"created_at":     entry.CreatedAt,
+	})
+	if err != nil {
+		return fmt.Errorf("marshal dead letter webhook: %w", err)
+	}
+	req, err := http.NewRequestWithContext(ctx, http.MethodPost, n.url, bytes.NewReader(body))
+	if err != nil {
+		return fmt.Errorf("build dead letter webhook request: %w", err)
+	}
+	events.SetWebhookHeaders(req.Header, EventDeadLettered, entry.ID.String(), n.secret, body)
+	resp, err := n.client.Do(req)
+	if err != nil {
+		return fmt.Errorf("post dead letter webhook: %w", err)
+	}
+	defer func() { _ = resp.Body.Close() }()
+	_, _ = io.Copy(io.Discard, resp.Body)
+	if resp.StatusCode >= 300 {
+		return fmt.Errorf("dead letter webhook returned status %d", resp.StatusCode)
+	}
+	return nil
+}
@@ -0,0 +1,68 @@
+package deadletter
+
+import (
+	"context"
+	"encoding/json"
+	"io"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+
+	"github.com/google/uuid"
+
+	"github.com/neural-chilli/aceryx/internal/events"
+)
+
+func TestWebhookNotifier_PostsSignedSummary(t *testing.T) {
+	var (
+		body   []byte
+		header http.Header
+	)
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		body, _ = io.ReadAll(r.Body)
+		header = r.Header.Clone()
+		w.WriteHeader(http.StatusNoContent)
+	}))
+	defer srv.Close()
+
+	entry := Entry{ID: uuid.New(), CaseID: uuid.New(), StepID: "charge", StepType: "integration", Attempts: 3, Error: "timeout", Context: json.RawMessage(`{"case":{"ssn":"secret"}}`)}
+	if err := NewWebhookNotifier(srv.URL, "s3cret").Notify(context.Background(), entry); err != nil {
+		t.Fatalf("notify: %v", err)
+	}
+	if header.Get(events.SignatureHeader) != events.Sign("s3cret", body) {
+		t.Fatalf("signature %q does not match body", header.Get(events.SignatureHeader))
+	}
+	if header.Get(events.EventHeader) != EventDeadLettered || header.Get(events.DeliveryHeader) != entry.ID.String() {
+		t.Fatalf("unexpected headers %v", header)
+	}
+	var got map[string]any
+	if err := json.Unmarshal(body, &got); err != nil {
+		t.Fatalf("decode body: %v", err)
+	}
+	if got["event"] != "step.dead_lettered" || got["step_id"] != "charge" || got["error"] != "timeout" {
+		t.Fatalf("unexpected payload %v", got)
+	}
+	if _, ok := got["context"]; ok {
+		t.Fatal("case context must not be sent to the webhook")
+	}
+}
+
+func TestWebhookNotifier_ErrorStatus(t *testing.T) {
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+		w.WriteHeader(http.StatusBadGateway)
+	}))
+	defer srv.Close()
+	if err := NewWebhookNotifier(srv.URL, "").Notify(context.Background(), Entry{}); err == nil {
+		t.Fatal("expected error for non-2xx response")
+	}
+}
+
+func TestStepConfig(t *testing.T) {
+	ast := []byte(`{"steps":[{"id":"a","type":"rule"},{"id":"b","type":"integration","config":{"connector":"http"}}]}`)
+	if got := string(stepConfig(ast, "b")); got != `{"connector":"http"}` {
+		t.Fatalf("stepConfig(b) = %s", got)
+	}
+	if got := string(stepConfig(ast, "a")); got != `{}` {
+		t.Fatalf("stepConfig(a) = %s", got)
+	}
+}
@@ -0,0 +1,281 @@
+// Package designer serves metadata for the workflow designer. The palette
+// groups every step the designer can add, including connector actions,
+// AI components, plugins, MCP tools and published workflows, with the UI
+// metadata and required config fields needed to render and pre-fill them.
+package designer
+
+import (
+	"context"
+	"log/slog"
+	"slices"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+	"unicode/utf8"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/search"
+)
+
+const (
+	KindStep        = "step"
+	KindConnector   = "connector"
+	KindAIComponent = "ai_component"
+	KindPlugin      = "plugin"
+	KindMCPTool     = "mcp_tool"
+	KindSubWorkflow = "sub_workflow"
+
+	maxLabelLength  = 28
+	defaultCacheTTL = 30 * time.Second
+)
+
+// Field is a required config field, derived from the tool's input schema or
+// declared config fields. Path is where the value goes in the step config.
+type Field struct {
+	Name        string   `json:"name"`
+	Path        string   `json:"path"`
+	Type        string   `json:"type,omitempty"`
+	Label       string   `json:"label,omitempty"`
+	Description string   `json:"description,omitempty"`
+	Options     []string `json:"options,omitempty"`
+}
+
+// Item is one palette entry. Adding it to a workflow creates a step of
+// StepType with Config as its initial config.
+type Item struct {
+	ID             string         `json:"id"`
+	Kind           string         `json:"kind"`
+	StepType       string         `json:"step_type"`
+	Label          string         `json:"label"`
+	Name           string         `json:"name"`
+	Description    string         `json:"description,omitempty"`
+	Category       string         `json:"category"`
+	Icon           string         `json:"icon,omitempty"`
+	IconSVG        string         `json:"icon_svg,omitempty"`
+	Color          string         `json:"color"`
+	RequiredFields []Field        `json:"required_fields"`
+	Config         map[string]any `json:"config,omitempty"`
+}
+
+type Category struct {
+	Name  string `json:"name"`
+	Items []Item `json:"items"`
+}
+
+// Query filters the palette. Search ranks items by relevance; without it
+// categories keep their natural order and items are sorted by label.
+type Query struct {
+	Search     string
+	Kinds      []string
+	Categories []string
+	Limit      int
+	Offset     int
+}
+
+type Result struct {
+	Categories []Category `json:"categories"`
+	Total      int        `json:"total"`
+}
+
+// Source lists the palette items of one kind available to a tenant.
+type Source func(ctx context.Context, tenantID uuid.UUID) ([]Item, error)
+
+type cachedPalette struct {
+	items     []Item
+	byID      map[string]Item
+	index     *search.Index
+	expiresAt time.Time
+}
+
+// Service builds each tenant's palette from its registered sources and keeps
+// it, with a search index, for a short TTL.
+type Service struct {
+	ttl time.Duration
+	now func() time.Time
+
+	mu      sync.Mutex
+	names   []string
+	sources map[string]Source
+	cache   map[uuid.UUID]cachedPalette
+}
+
+func NewService() *Service {
+	return &Service{
+		ttl:     defaultCacheTTL,
+		now:     time.Now,
+		sources: map[string]Source{},
+		cache:   map[uuid.UUID]cachedPalette{},
+	}
+}
+
+// Register adds a named source. Register before serving requests.
+func (s *Service) Register(name string, source Source) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if _, ok := s.sources[name]; !ok {
+		s.names = append(s.names, name)
+	}
+	s.sources[name] = source
+	s.cache = map[uuid.UUID]cachedPalette{}
+}
+
+// Invalidate drops a tenant's cached palette so the next request rebuilds it.
+func (s *Service) Invalidate(tenantID uuid.UUID) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	delete(s.cache, tenantID)
+}
+
+func (s *Service) Palette(ctx context.Context, tenantID uuid.UUID, q Query) (Result, error) {
+	palette, err := s.palette(ctx, tenantID)
+	if err != nil {
+		return Result{}, err
+	}
+
+	var ordered []Item
+	if strings.TrimSpace(q.Search) != "" {
+		for _, hit := range palette.index.Search(q.Search) {
+			ordered = append(ordered, palette.byID[hit.ID])
+		}
+	} else {
+		ordered = palette.items
+	}
+	matched := make([]Item, 0, len(ordered))
+	for _, item := range ordered {
+		if len(q.Kinds) > 0 && !slices.Contains(q.Kinds, item.Kind) {
+			continue
+		}
+		if len(q.Categories) > 0 && !containsFold(q.Categories, item.Category) {
+			continue
+		}
+		matched = append(matched, item)
+	}
+
+	total := len(matched)
+	start := min(max(q.Offset, 0), total)
+	end := total
+	if q.Limit > 0 {
+		end = min(start+q.Limit, total)
+	}
+	return Result{Categories: group(matched[start:end]), Total: total}, nil
+}
+
+func (s *Service) palette(ctx context.Context, tenantID uuid.UUID) (cachedPalette, error) {
+	s.mu.Lock()
+	if cached, ok := s.cache[tenantID]; ok && s.now().Before(cached.expiresAt) {
+		s.mu.Unlock()
+		return cached, nil
+	}
+	names := append([]string(nil), s.names...)
+	sources := make([]Source, len(names))
+	for i, name := range names {
+		sources[i] = s.sources[name]
+	}
+	s.mu.Unlock()
+
+	var items []Item
+	for i, source := range sources {
+		found, err := source(ctx, tenantID)
+		if err != nil {
+			if ctx.Err() != nil {
+				return cachedPalette{}, ctx.Err()
+			}
+			// A broken source should not leave the designer without a palette.
+			slog.WarnContext(ctx, "palette source unavailable", "source", names[i], "tenant_id", tenantID.String(), "error", err)
+			continue
+		}
+		items = append(items, found...)
+	}
+	sortItems(items)
+
+	palette := cachedPalette{byID: make(map[string]Item, len(items)), expiresAt: s.now().Add(s.ttl)}
+	docs := make([]search.Document, 0, len(items))
+	for _, item := range items {
+		if _, dup := palette.byID[item.ID]; dup {
+			continue
+		}
+		palette.byID[item.ID] = item
+		palette.items = append(palette.items, item)
+		docs = append(docs, search.Document{
+			Kind:        item.Kind,
+			ID:          item.ID,
+			Name:        item.Name,
+			Tags:        []string{item.Label, item.Category, item.StepType},
+			Description: item.Description,
+		})
+	}
+	palette.index = search.NewIndex(docs)
+
+	s.mu.Lock()
+	s.cache[tenantID] = palette
+	s.mu.Unlock()
+	return palette, nil
+}
+
+// categoryOrder puts the built-in categories first, in the order the
+// designer has always shown them.
+var categoryOrder = []string{"Human", "Automation", "Logic", "Communication", "Messaging", "Integrations"}
+
+func categoryRank(name string) int {
+	if i := slices.Index(categoryOrder, name); i >= 0 {
+		return i
+	}
+	return len(categoryOrder)
+}
+
+func sortItems(items []Item) {
+	sort.SliceStable(items, func(i, j int) bool {
+		a, b := items[i], items[j]
+		if ra, rb := categoryRank(a.Category), categoryRank(b.Category); ra != rb {
+			return ra < rb
+		}
+		if a.Category != b.Category {
+			return a.Category < b.Category
+		}
+		// Built-in steps keep their declared order within a category.
+		if a.Kind == KindStep || b.Kind == KindStep {
+			return a.Kind == KindStep && b.Kind != KindStep
+		}
+		if a.Label != b.Label {
+			return a.Label < b.Label
+		}
+		return a.ID < b.ID
+	})
+}
+
+// group splits ordered items into categories, keeping the order in which
+// each category first appears.
+func group(items []Item) []Category {
+	out := make([]Category, 0)
+	index := map[string]int{}
+	for _, item := range items {
+		i, ok := index[item.Category]
+		if !ok {
+			i = len(out)
+			index[item.Category] = i
+			out = append(out, Category{Name: item.Category})
+		}
+		out[i].Items = append(out[i].Items, item)
+	}
+	return out
+}
+
+// shortLabel trims a display name to fit a palette button.
+func shortLabel(name string) string {
+	name = strings.TrimSpace(name)
+	if utf8.RuneCountInString(name) <= maxLabelLength {
+		return name
+	}
+	runes := []rune(name)
+	return strings.TrimSpace(string(runes[:maxLabelLength-1])) + "…"
+}
+
+func containsFold(values []string, v string) bool {
+	for _, candidate := range values {
+		if strings.EqualFold(candidate, v) {
+			return true
+		}
+	}
+	return false
+}
@@ -0,0 +1,105 @@
+package designer
+
+import (
+	"context"
+	"errors"
+	"testing"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/connectors"
+)
+
+func testService() *Service {
+	svc := NewService()
+	svc.Register("steps", func(context.Context, uuid.UUID) ([]Item, error) {
+		return BuiltinItems(), nil
+	})
+	svc.Register("connectors", func(context.Context, uuid.UUID) ([]Item, error) {
+		return ConnectorItems([]connectors.ConnectorDescriptor{{
+			Meta: connectors.ConnectorMeta{Key: "slack", Name: "Slack"},
+			Actions: []connectors.ActionSummary{
+				{Key: "send_message", Name: "Send message", InputSchema: map[string]any{
+					"type":     "object",
+					"required": []string{"channel", "text"},
+					"properties": map[string]any{
+						"channel": map[string]any{"type": "string", "description": "Channel id"},
+						"text":    map[string]any{"type": []any{"null", "string"}},
+					},
+				}},
+				{Key: "list_channels", Name: "List channels"},
+			},
+		}}), nil
+	})
+	svc.Register("broken", func(context.Context, uuid.UUID) ([]Item, error) {
+		return nil, errors.New("unavailable")
+	})
+	return svc
+}
+
+func TestPaletteGroupsByCategory(t *testing.T) {
+	result, err := testService().Palette(context.Background(), uuid.New(), Query{})
+	if err != nil {
+		t.Fatalf("palette: %v", err)
+	}
+	if result.Total != 9 {
+		t.Fatalf("expected 9 items, got %d", result.Total)
+	}
+	var names []string
+	for _, c := range result.Categories {
+		names = append(names, c.Name)
+	}
+	want := []string{"Human", "Automation", "Logic", "Communication", "Integrations"}
+	if len(names) != len(want) {
+		t.Fatalf("unexpected categories %v", names)
+	}
+	for i := range want {
+		if names[i] != want[i] {
+			t.Fatalf("unexpected categories %v", names)
+		}
+	}
+	automation := result.Categories[1].Items
+	if automation[0].StepType != "agent" || automation[2].StepType != "integration" {
+		t.Fatalf("built-in steps should keep their order, got %+v", automation)
+	}
+	integrations := result.Categories[4].Items
+	if integrations[0].Label != "Slack: List channels" || integrations[1].Label != "Slack: Send message" {
+		t.Fatalf("unexpected integration order %+v", integrations)
+	}
+	fields := integrations[1].RequiredFields
+	if len(fields) != 2 || fields[0].Path != "input.channel" || fields[0].Description != "Channel id" || fields[1].Type != "string" {
+		t.Fatalf("unexpected required fields %+v", fields)
+	}
+}
+
+func TestPaletteSearchFilterAndPage(t *testing.T) {
+	svc := testService()
+	tenantID := uuid.New()
+
+	result, err := svc.Palette(context.Background(), tenantID, Query{Search: "slack mesage"})
+	if err != nil {
+		t.Fatalf("palette: %v", err)
+	}
+	if result.Total != 1 || result.Categories[0].Items[0].ID != "connector:slack.send_message" {
+		t.Fatalf("unexpected search result %+v", result)
+	}
+
+	result, _ = svc.Palette(context.Background(), tenantID, Query{Kinds: []string{KindStep}, Categories: []string{"logic"}})
+	if result.Total != 2 || len(result.Categories) != 1 {
+		t.Fatalf("unexpected filtered result %+v", result)
+	}
+
+	result, _ = svc.Palette(context.Background(), tenantID, Query{Limit: 2, Offset: 1})
+	if result.Total != 9 || len(result.Categories) != 1 || result.Categories[0].Name != "Automation" || len(result.Categories[0].Items) != 2 {
+		t.Fatalf("unexpected page %+v", result)
+	}
+}
+
+func TestShortLabel(t *testing.T) {
+	if got := shortLabel("  Send message "); got != "Send message" {
+		t.Fatalf("got %q", got)
+	}
+	got := shortLabel("Salesforce: Create or update opportunity")
+	if []rune(got)[len([]rune(got))-1] != '…' || len([]rune(got)) > maxLabelLength {
+		t.Fatalf("label not shortened: %q", got)
+	}
+}
@@ -0,0 +1,292 @@
+package designer
+
+import (
+	"encoding/json"
+	"fmt"
+	"regexp"
+	"sort"
+	"strings"
+
+	"github.com/neural-chilli/aceryx/internal/ai"
+	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/mcp"
+	"github.com/neural-chilli/aceryx/internal/plugins"
+	"github.com/neural-chilli/aceryx/internal/workflows"
+)
+
+// Colours match the step node tints on the designer canvas.
+var stepColors = map[string]string{
+	"human_task":   "#2563eb",
+	"agent":        "#7c3aed",
+	"extraction":   "#0ea5e9",
+	"integration":  "#16a34a",
+	"rule":         "#ea580c",
+	"timer":        "#64748b",
+	"notification": "#0f766e",
+	"ai_component": "#7c3aed",
+	"plugin":       "#db2777",
+	"mcp-client":   "#9333ea",
+	"sub_workflow": "#4f46e5",
+}
+
+// BuiltinItems returns the core step types.
+func BuiltinItems() []Item {
+	step := func(stepType, name, category, icon, description string, config map[string]any) Item {
+		return Item{
+			ID:             KindStep + ":" + stepType,
+			Kind:           KindStep,
+			StepType:       stepType,
+			Label:          name,
+			Name:           name,
+			Description:    description,
+			Category:       category,
+			Icon:           icon,
+			Color:          stepColors[stepType],
+			RequiredFields: []Field{},
+			Config:         config,
+		}
+	}
+	return []Item{
+		step("human_task", "Human Task", "Human", "👤", "Assign a task to a person or role", nil),
+		step("agent", "Agent", "Automation", "🧠", "Ask an LLM agent to decide or draft", nil),
+		step("extraction", "Extraction", "Automation", "📄", "Extract structured data from a document", map[string]any{
+			"document_path":         "case.data.attachments[0].vault_id",
+			"schema":                "",
+			"model":                 "gpt-5.4",
+			"auto_accept_threshold": 0.85,
+			"review_threshold":      0.3,
+			"output_path":           "case.data.extracted",
+			"on_review": map[string]any{
+				"task_type":     "extraction_review",
+				"assignee_role": "underwriter",
+				"sla_hours":     4,
+			},
+			"on_reject": map[string]any{"goto": ""},
+		}),
+		step("integration", "Integration", "Automation", "🔌", "Call a connector action", nil),
+		step("rule", "Rule", "Logic", "◆", "Branch on an expression", nil),
+		step("timer", "Timer", "Logic", "⏰", "Wait for a duration or deadline", nil),
+		step("notification", "Notification", "Communication", "🔔", "Send an email or message", nil),
+	}
+}
+
+// ConnectorItems returns one item per connector action.
+func ConnectorItems(descriptors []connectors.ConnectorDescriptor) []Item {
+	var out []Item
+	for _, d := range descriptors {
+		connectorName := firstNonEmpty(d.Meta.Name, d.Meta.Key)
+		for _, action := range d.Actions {
+			actionName := firstNonEmpty(action.Name, action.Key)
+			out = append(out, Item{
+				ID:             fmt.Sprintf("%s:%s.%s", KindConnector, d.Meta.Key, action.Key),
+				Kind:           KindConnector,
+				StepType:       "integration",
+				Label:          shortLabel(connectorName + ": " + actionName),
+				Name:           connectorName + ": " + actionName,
+				Description:    firstNonEmpty(action.Description, d.Meta.Description),
+				Category:       firstNonEmpty(d.Meta.Category, "Integrations"),
+				Icon:           firstNonEmpty(d.Meta.Icon, "🔌"),
+				Color:          stepColors["integration"],
+				RequiredFields: SchemaFields(action.InputSchema, "input"),
+				Config:         map[string]any{"connector": d.Meta.Key, "action": action.Key},
+			})
+		}
+	}
+	return out
+}
+
+// AIComponentItems returns one item per AI component. Required fields cover
+// both declared config fields and required inputs.
+func AIComponentItems(components []*ai.AIComponentDef) []Item {
+	var out []Item
+	for _, comp := range components {
+		if comp == nil {
+			continue
+		}
+		name := firstNonEmpty(comp.DisplayLabel, comp.ID)
+		defaults := map[string]string{}
+		required := []Field{}
+		for _, f := range comp.ConfigFields {
+			if f.Default != nil {
+				defaults[f.Name] = fmt.Sprint(f.Default)
+			}
+			if f.Required {
+				required = append(required, Field{Name: f.Name, Path: "config_values." + f.Name, Type: f.Type, Label: f.Label, Options: f.Options})
+			}
+		}
+		var inputSchema map[string]any
+		_ = json.Unmarshal(comp.InputSchema, &inputSchema)
+		required = append(required, SchemaFields(inputSchema, "input_paths")...)
+		out = append(out, Item{
+			ID:             KindAIComponent + ":" + comp.ID,
+			Kind:           KindAIComponent,
+			StepType:       "ai_component",
+			Label:          shortLabel(name),
+			Name:           name,
+			Description:    comp.Description,
+			Category:       firstNonEmpty(comp.Category, "AI: Components"),
+			Icon:           firstNonEmpty(comp.Icon, "✨"),
+			Color:          stepColors["ai_component"],
+			RequiredFields: required,
+			Config: map[string]any{
+				"label":         name,
+				"component":     comp.ID,
+				"output_path":   "case.data.ai." + pathSegment(comp.ID),
+				"input_paths":   map[string]any{},
+				"config_values": defaults,
+			},
+		})
+	}
+	return out
+}
+
+// PluginItems returns one item per step plugin, latest versions only.
+func PluginItems(list []*plugins.Plugin) []Item {
+	var out []Item
+	for _, p := range list {
+		if p == nil || !p.IsLatest || p.Type != plugins.StepPlugin {
+			continue
+		}
+		name := firstNonEmpty(p.Name, p.ID)
+		required := []Field{}
+		for _, prop := range p.Manifest.UI.Properties {
+			if prop.Required {
+				required = append(required, Field{Name: prop.Key, Path: "input." + prop.Key, Type: prop.Type, Label: prop.Label, Description: prop.HelpText, Options: prop.Options})
+			}
+		}
+		out = append(out, Item{
+			ID:             KindPlugin + ":" + p.ID,
+			Kind:           KindPlugin,
+			StepType:       "plugin",
+			Label:          shortLabel(name),
+			Name:           name,
+			Description:    p.Manifest.UI.Description,
+			Category:       firstNonEmpty(p.Category, "Plugins"),
+			Icon:           "🧩",
+			IconSVG:        p.Manifest.UI.IconSVG,
+			Color:          stepColors["plugin"],
+			RequiredFields: required,
+			Config:         map[string]any{"plugin": p.ID, "input": map[string]any{}},
+		})
+	}
+	return out
+}
+
+// MCPToolItems returns one item per tool discovered on the tenant's MCP
+// servers.
+func MCPToolItems(servers []mcp.CachedServer) []Item {
+	var out []Item
+	for _, server := range servers {
+		for _, tool := range server.Tools {
+			var inputSchema map[string]any
+			_ = json.Unmarshal(tool.InputSchema, &inputSchema)
+			out = append(out, Item{
+				ID:             KindMCPTool + ":" + server.ServerURL + "#" + tool.Name,
+				Kind:           KindMCPTool,
+				StepType:       "mcp-client",
+				Label:          shortLabel(tool.Name),
+				Name:           tool.Name,
+				Description:    tool.Description,
+				Category:       "MCP Tools",
+				Icon:           "🛰",
+				Color:          stepColors["mcp-client"],
+				RequiredFields: SchemaFields(inputSchema, "arguments"),
+				Config:         map[string]any{"server_url": server.ServerURL, "tool": tool.Name, "arguments": map[string]any{}},
+			})
+		}
+	}
+	return out
+}
+
+// SubWorkflowItems returns one item per published workflow, pre-filled to
+// call it from a sub_workflow step. Required fields come from its input
+// schema.
+func SubWorkflowItems(list []workflows.PublishedWorkflow) []Item {
+	var out []Item
+	for _, wf := range list {
+		name := firstNonEmpty(wf.Name, wf.CaseType)
+		var inputSchema map[string]any
+		_ = json.Unmarshal(wf.Contract.InputSchema, &inputSchema)
+		description, _ := inputSchema["description"].(string)
+		outputs := make([]string, 0, len(wf.Contract.Outputs))
+		for output := range wf.Contract.Outputs {
+			outputs = append(outputs, output)
+		}
+		sort.Strings(outputs)
+		out = append(out, Item{
+			ID:             KindSubWorkflow + ":" + wf.WorkflowID.String(),
+			Kind:           KindSubWorkflow,
+			StepType:       "sub_workflow",
+			Label:          shortLabel(name),
+			Name:           name,
+			Description:    firstNonEmpty(description, "Start a "+wf.CaseType+" case and wait for it"),
+			Category:       "Workflows",
+			Icon:           "↳",
+			Color:          stepColors["sub_workflow"],
+			RequiredFields: SchemaFields(inputSchema, "input"),
+			Config:         map[string]any{"case_type": wf.CaseType, "input": map[string]any{}, "outputs": outputs},
+		})
+	}
+	return out
+}
+
+// SchemaFields lists the required properties of a JSON object schema in the
+// order the schema declares them, placing each under prefix in the step
+// config. Schemas built in Go may use []string where decoded JSON has []any.
+func SchemaFields(schema map[string]any, prefix string) []Field {
+	out := []Field{}
+	properties, _ := schema["properties"].(map[string]any)
+	for _, name := range stringList(schema["required"]) {
+		if name == "" {
+			continue
+		}
+		field := Field{Name: name, Path: prefix + "." + name}
+		if prop, ok := properties[name].(map[string]any); ok {
+			for _, t := range stringList(prop["type"]) {
+				// A list such as ["string", "null"] takes its first non-null type.
+				if t != "null" {
+					field.Type = t
+					break
+				}
+			}
+			field.Label, _ = prop["title"].(string)
+			field.Description, _ = prop["description"].(string)
+			field.Options = stringList(prop["enum"])
+		}
+		out = append(out, field)
+	}
+	return out
+}
+
+func stringList(raw any) []string {
+	switch v := raw.(type) {
+	case string:
+		return []string{v}
+	case []string:
+		return v
+	case []any:
+		out := make([]string, 0, len(v))
+		for _, item := range v {
+			out = append(out, fmt.Sprint(item))
+		}
+		return out
+	}
+	return nil
+}
+
+var nonPathChars = regexp.MustCompile(`[^a-z0-9_]+`)
+
+// pathSegment turns a component id into a case data key.
+func pathSegment(id string) string {
+	segment := strings.Trim(nonPathChars.ReplaceAllString(strings.ToLower(id), "_"), "_")
+	return firstNonEmpty(segment, "result")
+}
+
+func firstNonEmpty(values ...string) string {
+	for _, v := range values {
+		if s := strings.TrimSpace(v); s != "" {
+			return s
+		}
+	}
+	return ""
+}
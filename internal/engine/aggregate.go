@@ -0,0 +1,105 @@
+package engine
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"fmt"
+
+	"github.com/google/uuid"
+)
+
+type aggregateConfig struct {
+	From []string `json:"from"`
+}
+
+// AggregateExecutor is the fan-in step: it collects the outputs of the steps
+// named in config.from. The result has "results", each source's output by
+// step ID, and "items", every for_each item output followed by plain outputs,
+// in source order. Skipped or unfinished sources are left out.
+type AggregateExecutor struct {
+	db *sql.DB
+}
+
+func NewAggregateExecutor(db *sql.DB) *AggregateExecutor {
+	return &AggregateExecutor{db: db}
+}
+
+func (a *AggregateExecutor) Execute(ctx context.Context, caseID uuid.UUID, stepID string, config json.RawMessage) (*StepResult, error) {
+	cfg := aggregateConfig{}
+	if len(config) > 0 {
+		if err := json.Unmarshal(config, &cfg); err != nil {
+			return nil, fmt.Errorf("decode aggregate step config: %w", err)
+		}
+	}
+	if len(cfg.From) == 0 {
+		return nil, fmt.Errorf("aggregate step %s config missing from", stepID)
+	}
+
+	outputs := make(map[string]json.RawMessage, len(cfg.From))
+	for _, source := range cfg.From {
+		var raw []byte
+		err := a.db.QueryRowContext(ctx, `
+SELECT result
+FROM case_steps
+WHERE case_id = $1 AND step_id = $2 AND state = 'completed'
+`, caseID, source).Scan(&raw)
+		if errors.Is(err, sql.ErrNoRows) {
+			continue
+		}
+		if err != nil {
+			return nil, fmt.Errorf("load aggregate source %s: %w", source, err)
+		}
+		var result StepResult
+		if err := json.Unmarshal(raw, &result); err != nil {
+			return nil, fmt.Errorf("decode aggregate source %s: %w", source, err)
+		}
+		outputs[source] = result.Output
+	}
+
+	payload, err := json.Marshal(aggregateOutputs(cfg.From, outputs))
+	if err != nil {
+		return nil, fmt.Errorf("marshal aggregate result: %w", err)
+	}
+	return &StepResult{Output: payload}, nil
+}
+
+func aggregateOutputs(order []string, outputs map[string]json.RawMessage) map[string]any {
+	results := make(map[string]json.RawMessage, len(outputs))
+	items := make([]json.RawMessage, 0, len(outputs))
+	for _, source := range order {
+		out, ok := outputs[source]
+		if !ok {
+			continue
+		}
+		results[source] = out
+		if fanned, ok := forEachOutputs(out); ok {
+			items = append(items, fanned...)
+			continue
+		}
+		items = append(items, out)
+	}
+	return map[string]any{"results": results, "items": items}
+}
+
+// forEachOutputs unpacks the per-item outputs of a for_each step result.
+func forEachOutputs(out json.RawMessage) ([]json.RawMessage, bool) {
+	var fanned struct {
+		Items []struct {
+			Index  *int            `json:"index"`
+			Output json.RawMessage `json:"output"`
+		} `json:"items"`
+	}
+	if err := json.Unmarshal(out, &fanned); err != nil || fanned.Items == nil {
+		return nil, false
+	}
+	items := make([]json.RawMessage, 0, len(fanned.Items))
+	for _, item := range fanned.Items {
+		if item.Index == nil {
+			return nil, false
+		}
+		items = append(items, item.Output)
+	}
+	return items, true
+}
@@ -0,0 +1,61 @@
+package engine
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/google/uuid"
+)
+
+// Artifact is a file, report or transcript a step produced. Executors set
+// Name, ContentType and Data; the ArtifactStore assigns ID and SizeBytes.
+// Data never reaches the step result.
+type Artifact struct {
+	ID          uuid.UUID `json:"id"`
+	Name        string    `json:"name"`
+	ContentType string    `json:"content_type"`
+	SizeBytes   int64     `json:"size_bytes"`
+	Data        []byte    `json:"-"`
+}
+
+// ArtifactStore persists step artifacts and returns their handles.
+type ArtifactStore interface {
+	SaveArtifact(ctx context.Context, caseID uuid.UUID, stepID string, artifact Artifact) (Artifact, error)
+}
+
+func (e *Engine) SetArtifactStore(store ArtifactStore) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+	e.artifacts = store
+}
+
+func (e *Engine) artifactStore() ArtifactStore {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+	return e.artifacts
+}
+
+// storeArtifacts saves the artifacts attached to a step result and replaces
+// them with their handles. Artifacts that already have an ID, such as ones
+// passed through from an earlier step, are kept as they are.
+func (e *Engine) storeArtifacts(ctx context.Context, caseID uuid.UUID, stepID string, result *StepResult) error {
+	if len(result.Artifacts) == 0 {
+		return nil
+	}
+	store := e.artifactStore()
+	if store == nil {
+		return fmt.Errorf("step %s produced artifacts but no artifact store is configured", stepID)
+	}
+	for i, artifact := range result.Artifacts {
+		if artifact.ID != uuid.Nil && artifact.Data == nil {
+			continue
+		}
+		saved, err := store.SaveArtifact(ctx, caseID, stepID, artifact)
+		if err != nil {
+			return fmt.Errorf("store artifact %q: %w", artifact.Name, err)
+		}
+		saved.Data = nil
+		result.Artifacts[i] = saved
+	}
+	return nil
+}
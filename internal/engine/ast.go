@@ -36,6 +36,12 @@ func ValidateAST(ast WorkflowAST) error {
 		if join != "all" && join != "any" {
 			return fmt.Errorf("step %s: %w %q", step.ID, ErrInvalidJoinStrategy, step.Join)
 		}
+		if step.TimeoutSeconds < 0 {
+			return fmt.Errorf("step %s: timeout_seconds cannot be negative", step.ID)
+		}
+		if err := validateForEach(step); err != nil {
+			return err
+		}
 		byID[step.ID] = step
 	}
 
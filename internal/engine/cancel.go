@@ -4,11 +4,22 @@ import (
 	"context"
 	"fmt"
 	"log/slog"
+	"strings"
+	"time"
 
 	"github.com/google/uuid"
 	"github.com/neural-chilli/aceryx/internal/observability"
 )
 
+// CancelPollInterval is how often StartCancelWatcher checks whether cases
+// with running steps were cancelled on another replica.
+const CancelPollInterval = 2 * time.Second
+
+// CancelCase cancels a case: steps that have not finished are skipped and
+// running step executions are aborted, so HTTP calls and LLM requests in
+// flight are cancelled. Executions on this process are aborted at once; those
+// on other replicas when their StartCancelWatcher next polls. It returns
+// ErrCaseCancelled when the case already is.
 func (e *Engine) CancelCase(ctx context.Context, caseID uuid.UUID, actorID uuid.UUID, reason string) error {
 	return e.cancelCase(ctx, caseID, actorID, reason)
 }
@@ -20,13 +31,17 @@ func (e *Engine) cancelCase(ctx context.Context, caseID uuid.UUID, actorID uuid.
 	}
 	defer func() { _ = e.auditSvc.RollbackTx(tx) }()
 
-	ast, err := loadWorkflowASTTx(ctx, tx, caseID)
+	ast, err := e.loadWorkflowASTTx(ctx, tx, caseID)
 	if err != nil {
 		return err
 	}
-	if _, err := tx.ExecContext(ctx, `SELECT id FROM cases WHERE id = $1 FOR UPDATE`, caseID); err != nil {
+	var caseStatus string
+	if err := tx.QueryRowContext(ctx, `SELECT status FROM cases WHERE id = $1 FOR UPDATE`, caseID).Scan(&caseStatus); err != nil {
 		return fmt.Errorf("lock case for cancellation: %w", err)
 	}
+	if caseStatus == "cancelled" {
+		return ErrCaseCancelled
+	}
 
 	if _, err := tx.ExecContext(ctx, `
 UPDATE cases
@@ -58,6 +73,17 @@ WHERE case_id = $1 AND state = 'active' AND step_id = $2
 			return fmt.Errorf("cancel active human task %s: %w", step.ID, err)
 		}
 	}
+	if _, err := tx.ExecContext(ctx, `
+UPDATE case_steps
+SET
+    state = 'skipped',
+    completed_at = now(),
+    metadata = COALESCE(metadata, '{}'::jsonb) || jsonb_build_object('cancelled', true),
+    events = COALESCE(events, '[]'::jsonb) || jsonb_build_array(jsonb_build_object('type', 'cancelled', 'at', now()))
+WHERE case_id = $1 AND state = 'active'
+`, caseID); err != nil {
+		return fmt.Errorf("cancel active steps: %w", err)
+	}
 
 	if err := e.auditSvc.RecordCaseEventTx(ctx, tx, caseID, "", "case", actorID, "human", "cancelled", map[string]any{"reason": reason}); err != nil {
 		return err
@@ -65,10 +91,12 @@ WHERE case_id = $1 AND state = 'active' AND step_id = $2
 	if err := e.auditSvc.CommitTx(tx); err != nil {
 		return fmt.Errorf("commit cancel case: %w", err)
 	}
+	e.abortCaseExecutions(caseID)
 	tenantID, terr := e.lookupTenantID(ctx, caseID)
 	if terr == nil {
 		e.updateCaseStepStateMetrics(ctx, tenantID)
 	}
+	e.notifySettled(ctx, caseID, true)
 	slog.InfoContext(ctx, "case cancelled in engine",
 		append(observability.RequestAttrs(ctx),
 			"case_id", caseID.String(),
@@ -77,3 +105,75 @@ WHERE case_id = $1 AND state = 'active' AND step_id = $2
 	)
 	return nil
 }
+
+// abortCaseExecutions cancels the contexts of the case's running step
+// executions with ErrCaseCancelled as the cause.
+func (e *Engine) abortCaseExecutions(caseID uuid.UUID) {
+	e.inflightMu.Lock()
+	defer e.inflightMu.Unlock()
+	for key, cancel := range e.inflightCancels {
+		if key.caseID == caseID {
+			cancel(ErrCaseCancelled)
+		}
+	}
+}
+
+// StartCancelWatcher aborts running step executions whose case was cancelled
+// through another replica, polling every CancelPollInterval until ctx is
+// done. Every replica runs it.
+func (e *Engine) StartCancelWatcher(ctx context.Context) {
+	ticker := time.NewTicker(CancelPollInterval)
+	defer ticker.Stop()
+	for {
+		select {
+		case <-ticker.C:
+			if err := e.abortCancelledExecutions(ctx); err != nil && ctx.Err() == nil {
+				slog.WarnContext(ctx, "check for cancelled cases failed", "error", err)
+			}
+		case <-ctx.Done():
+			return
+		}
+	}
+}
+
+// abortCancelledExecutions aborts the running step executions of cases that
+// are cancelled in the database.
+func (e *Engine) abortCancelledExecutions(ctx context.Context) error {
+	e.inflightMu.Lock()
+	seen := map[uuid.UUID]bool{}
+	ids := make([]string, 0, len(e.inflightCancels))
+	for key := range e.inflightCancels {
+		if !seen[key.caseID] {
+			seen[key.caseID] = true
+			ids = append(ids, key.caseID.String())
+		}
+	}
+	e.inflightMu.Unlock()
+	if len(ids) == 0 {
+		return nil
+	}
+
+	rows, err := e.db.QueryContext(ctx, `
+SELECT id FROM cases
+WHERE id = ANY(string_to_array($1, ',')::uuid[]) AND status = 'cancelled'
+`, strings.Join(ids, ","))
+	if err != nil {
+		return fmt.Errorf("load cancelled cases: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	var cancelled []uuid.UUID
+	for rows.Next() {
+		var id uuid.UUID
+		if err := rows.Scan(&id); err != nil {
+			return fmt.Errorf("scan cancelled case: %w", err)
+		}
+		cancelled = append(cancelled, id)
+	}
+	if err := rows.Err(); err != nil {
+		return fmt.Errorf("iterate cancelled cases: %w", err)
+	}
+	for _, id := range cancelled {
+		e.abortCaseExecutions(id)
+	}
+	return nil
+}
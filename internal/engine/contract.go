@@ -0,0 +1,130 @@
+package engine
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"sort"
+	"strings"
+
+	jsonschema "github.com/santhosh-tekuri/jsonschema/v5"
+)
+
+// InputViolation is one way case data fails a workflow's input schema. Path
+// is a JSON Pointer to the offending value and Keyword the failing rule.
+type InputViolation struct {
+	Path    string `json:"path"`
+	Keyword string `json:"keyword"`
+	Message string `json:"message"`
+}
+
+func compileInputSchema(raw json.RawMessage) (*jsonschema.Schema, error) {
+	compiler := jsonschema.NewCompiler()
+	if err := compiler.AddResource("workflow-input.schema.json", bytes.NewReader(raw)); err != nil {
+		return nil, fmt.Errorf("invalid input_schema: %w", err)
+	}
+	schema, err := compiler.Compile("workflow-input.schema.json")
+	if err != nil {
+		return nil, fmt.Errorf("invalid input_schema: %w", err)
+	}
+	return schema, nil
+}
+
+// HasInputSchema reports whether the workflow declares an input contract.
+func (ast WorkflowAST) HasInputSchema() bool {
+	trimmed := bytes.TrimSpace(ast.InputSchema)
+	return len(trimmed) > 0 && !bytes.Equal(trimmed, []byte("null"))
+}
+
+// ValidateContract checks that the input schema compiles and that every
+// output is a dot path rooted at "case".
+func (ast WorkflowAST) ValidateContract() error {
+	if ast.HasInputSchema() {
+		if _, err := compileInputSchema(ast.InputSchema); err != nil {
+			return err
+		}
+	}
+	for name, path := range ast.Outputs {
+		if strings.TrimSpace(name) == "" {
+			return fmt.Errorf("workflow output names cannot be empty")
+		}
+		if path = strings.TrimSpace(path); path != "case" && !strings.HasPrefix(path, "case.") {
+			return fmt.Errorf("workflow output %q path %q must start with case.", name, path)
+		}
+	}
+	return nil
+}
+
+// ValidateInput checks case data against the input schema. Workflows without
+// one accept any data.
+func (ast WorkflowAST) ValidateInput(data map[string]any) ([]InputViolation, error) {
+	if !ast.HasInputSchema() {
+		return nil, nil
+	}
+	schema, err := compileInputSchema(ast.InputSchema)
+	if err != nil {
+		return nil, err
+	}
+	// Round-trip so Go-native values validate like decoded JSON.
+	raw, err := json.Marshal(data)
+	if err != nil {
+		return nil, fmt.Errorf("encode case data: %w", err)
+	}
+	var doc any
+	if err := json.Unmarshal(raw, &doc); err != nil {
+		return nil, fmt.Errorf("decode case data: %w", err)
+	}
+	err = schema.Validate(doc)
+	if err == nil {
+		return nil, nil
+	}
+	var verr *jsonschema.ValidationError
+	if !errors.As(err, &verr) {
+		return nil, err
+	}
+	var out []InputViolation
+	var collect func(*jsonschema.ValidationError)
+	collect = func(e *jsonschema.ValidationError) {
+		if len(e.Causes) == 0 {
+			path := e.InstanceLocation
+			if path == "" {
+				path = "/"
+			}
+			out = append(out, InputViolation{Path: path, Keyword: e.KeywordLocation, Message: e.Message})
+			return
+		}
+		for _, cause := range e.Causes {
+			collect(cause)
+		}
+	}
+	collect(verr)
+	sort.SliceStable(out, func(i, j int) bool { return out[i].Path < out[j].Path })
+	return out, nil
+}
+
+// ResolveOutputs evaluates Outputs against root, which has the same shape as
+// for_each items: {"case": {...case data, "steps": {id: {"result": ...}}}}.
+// Paths that resolve to nothing yield null.
+func (ast WorkflowAST) ResolveOutputs(root map[string]any) map[string]any {
+	out := make(map[string]any, len(ast.Outputs))
+	for name, path := range ast.Outputs {
+		value, _ := lookupDotPath(root, path)
+		out[name] = value
+	}
+	return out
+}
+
+func lookupDotPath(root map[string]any, path string) (any, bool) {
+	var current any = root
+	for _, part := range strings.Split(strings.TrimSpace(path), ".") {
+		obj, ok := current.(map[string]any)
+		if !ok {
+			return nil, false
+		}
+		if current, ok = obj[part]; !ok {
+			return nil, false
+		}
+	}
+	return current, true
+}
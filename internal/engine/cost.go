@@ -0,0 +1,53 @@
+package engine
+
+import (
+	"context"
+	"encoding/json"
+
+	"github.com/google/uuid"
+)
+
+// costedExecutor adds the costs an executor does not know about to each
+// successful call: the step config's cost_per_call, for priced APIs, and the
+// cost_units a tool reports under _meta in its output.
+type costedExecutor struct {
+	inner StepExecutor
+}
+
+func (c costedExecutor) Execute(ctx context.Context, caseID uuid.UUID, stepID string, config json.RawMessage) (*StepResult, error) {
+	result, err := c.inner.Execute(ctx, caseID, stepID, config)
+	if err != nil {
+		return result, err
+	}
+	cost := callCost(config)
+	if result == nil {
+		if cost == 0 {
+			return nil, nil
+		}
+		result = &StepResult{}
+	}
+	result.Cost += cost + reportedCost(result.Output)
+	return result, nil
+}
+
+func callCost(config json.RawMessage) float64 {
+	var cfg struct {
+		CostPerCall float64 `json:"cost_per_call"`
+	}
+	if len(config) == 0 || json.Unmarshal(config, &cfg) != nil {
+		return 0
+	}
+	return max(cfg.CostPerCall, 0)
+}
+
+func reportedCost(output json.RawMessage) float64 {
+	var out struct {
+		Meta struct {
+			CostUnits float64 `json:"cost_units"`
+		} `json:"_meta"`
+	}
+	if len(output) == 0 || json.Unmarshal(output, &out) != nil {
+		return 0
+	}
+	return max(out.Meta.CostUnits, 0)
+}
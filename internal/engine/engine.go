@@ -11,16 +11,22 @@ import (
 
 	"github.com/google/uuid"
 	"github.com/neural-chilli/aceryx/internal/observability"
+	"go.opentelemetry.io/otel/attribute"
+	"go.opentelemetry.io/otel/trace"
 )
 
 func (e *Engine) EvaluateDAG(ctx context.Context, caseID uuid.UUID) error {
 	return e.evaluateDAG(ctx, caseID)
 }
 
-func (e *Engine) evaluateDAG(ctx context.Context, caseID uuid.UUID) error {
+func (e *Engine) evaluateDAG(ctx context.Context, caseID uuid.UUID) (err error) {
 	start := time.Now()
+	ctx, span := observability.Tracer().Start(ctx, "workflow.evaluate", trace.WithAttributes(attribute.String("case_id", caseID.String())))
 	defer func() {
 		observability.DBQueryDurationSeconds.WithLabelValues("dag_eval").Observe(time.Since(start).Seconds())
+		span.SetAttributes(attribute.Int64("duration_ms", time.Since(start).Milliseconds()))
+		observability.SpanError(span, err)
+		span.End()
 	}()
 	tx, err := e.db.BeginTx(ctx, nil)
 	if err != nil {
@@ -30,19 +36,34 @@ func (e *Engine) evaluateDAG(ctx context.Context, caseID uuid.UUID) error {
 
 	var caseStatus string
 	var caseData []byte
-	var tenantID uuid.UUID
+	var tenantID, workflowID, createdBy uuid.UUID
+	var correlationID sql.NullString
+	var lane Lane
+	var mockProfile string
 	err = tx.QueryRowContext(ctx, `
-SELECT status, data, tenant_id
+SELECT status, data, tenant_id, workflow_id, created_by, correlation_id, execution_lane, COALESCE(mock_profile, '')
 FROM cases
 WHERE id = $1
 FOR UPDATE
-`, caseID).Scan(&caseStatus, &caseData, &tenantID)
+`, caseID).Scan(&caseStatus, &caseData, &tenantID, &workflowID, &createdBy, &correlationID, &lane, &mockProfile)
 	if err != nil {
 		if errors.Is(err, sql.ErrNoRows) {
 			return ErrNotFound
 		}
 		return fmt.Errorf("lock case row: %w", err)
 	}
+	if correlationID.Valid && observability.CorrelationIDFromContext(ctx) == "" {
+		ctx = observability.WithCorrelationID(ctx, correlationID.String)
+	}
+	ctx = WithLane(ctx, lane)
+	ctx = WithMockProfile(ctx, mockProfile)
+	ctx = observability.WithTenantID(ctx, tenantID)
+	ctx = observability.WithLogAttrs(ctx,
+		slog.String("workflow_id", workflowID.String()),
+		slog.String("case_id", caseID.String()),
+		slog.String("principal_id", createdBy.String()),
+	)
+	span.SetAttributes(attribute.String("workflow_id", workflowID.String()), attribute.String("principal_id", createdBy.String()))
 	if caseStatus == "cancelled" {
 		observability.DAGEvaluationsTotal.WithLabelValues(tenantID.String()).Inc()
 		observability.DAGEvaluationDurationSeconds.WithLabelValues(tenantID.String()).Observe(time.Since(start).Seconds())
@@ -54,7 +75,7 @@ FOR UPDATE
 		return err
 	}
 
-	ast, err := loadWorkflowASTTx(ctx, tx, caseID)
+	ast, err := e.loadWorkflowASTTx(ctx, tx, caseID)
 	if err != nil {
 		return err
 	}
@@ -74,13 +95,23 @@ FOR UPDATE
 
 	stepsByID := stepMap(ast)
 	toDispatch := make([]WorkflowStep, 0)
+	var featureSnapshot map[string]bool
+	owner, _ := e.stepOwner()
 	for _, tr := range transitions {
-		if err := applyTransitionTx(ctx, tx, caseID, tr); err != nil {
+		if err := applyTransitionTx(ctx, tx, caseID, tr, owner); err != nil {
 			return err
 		}
 		switch tr.Type {
 		case TransitionToActive:
-			if err := e.auditSvc.RecordCaseEventTx(ctx, tx, caseID, tr.StepID, "step", e.systemActor(), "system", "activated", map[string]any{"from": tr.From, "to": tr.To, "reason": tr.Reason, "outcome": tr.Outcome}); err != nil {
+			data := map[string]any{"from": tr.From, "to": tr.To, "reason": tr.Reason, "outcome": tr.Outcome}
+			if gate := e.featureGate(); gate != nil {
+				// Record the flags in force so behavioural differences between runs can be traced.
+				if featureSnapshot == nil {
+					featureSnapshot = gate.Snapshot(ctx, tenantID)
+				}
+				data["features"] = featureSnapshot
+			}
+			if err := e.auditSvc.RecordCaseEventTx(ctx, tx, caseID, tr.StepID, "step", e.systemActor(), "system", "activated", data); err != nil {
 				return err
 			}
 		case TransitionToSkipped:
@@ -102,6 +133,11 @@ FOR UPDATE
 	if err := e.auditSvc.CommitTx(tx); err != nil {
 		return fmt.Errorf("commit dag evaluation: %w", err)
 	}
+	span.SetAttributes(
+		attribute.String("tenant_id", tenantID.String()),
+		attribute.Int("transitions", len(transitions)),
+		attribute.Int("dispatched", len(toDispatch)),
+	)
 	e.updateCaseStepStateMetrics(ctx, tenantID)
 	observability.DAGEvaluationsTotal.WithLabelValues(tenantID.String()).Inc()
 	observability.DAGEvaluationDurationSeconds.WithLabelValues(tenantID.String()).Observe(time.Since(start).Seconds())
@@ -118,13 +154,26 @@ FOR UPDATE
 		)...,
 	)
 
+	for _, tr := range transitions {
+		switch tr.Type {
+		case TransitionToActive:
+			e.emitStepEvent(ctx, StepEvent{CaseID: caseID, StepID: tr.StepID, Status: StepStatusQueued})
+		case TransitionToSkipped:
+			e.emitStepEvent(ctx, StepEvent{CaseID: caseID, StepID: tr.StepID, Status: StepStatusSkipped})
+		}
+	}
 	for _, step := range toDispatch {
-		e.dispatchStep(caseID, step)
+		e.dispatchStep(ctx, caseID, step)
+	}
+	if settled, failed := settledState(states, transitions); settled {
+		e.notifySettled(ctx, caseID, failed)
 	}
 	return nil
 }
 
-func applyTransitionTx(ctx context.Context, tx *sql.Tx, caseID uuid.UUID, tr Transition) error {
+// applyTransitionTx applies tr. Steps made active are stamped with owner,
+// the process that will run them; uuid.Nil leaves them unowned.
+func applyTransitionTx(ctx context.Context, tx *sql.Tx, caseID uuid.UUID, tr Transition, owner uuid.UUID) error {
 	switch tr.Type {
 	case TransitionToReady:
 		if _, err := tx.ExecContext(ctx, `
@@ -148,13 +197,15 @@ UPDATE case_steps
 SET
     state = 'active',
     started_at = COALESCE(started_at, now()),
+    owner_instance = $4,
+    owned_at = now(),
     metadata = CASE
         WHEN metadata IS NULL THEN jsonb_build_object('request_id', gen_random_uuid()::text)
         WHEN metadata ? 'request_id' THEN metadata
         ELSE jsonb_set(metadata, '{request_id}', to_jsonb(gen_random_uuid()::text), true)
     END
 WHERE case_id = $1 AND step_id = $2 AND state = $3
-`, caseID, tr.StepID, StateReady); err != nil {
+`, caseID, tr.StepID, StateReady, uuid.NullUUID{UUID: owner, Valid: owner != uuid.Nil}); err != nil {
 			return fmt.Errorf("apply active transition for %s: %w", tr.StepID, err)
 		}
 	default:
@@ -201,14 +252,21 @@ WHERE case_id = $1
 	return states, nil
 }
 
-func loadWorkflowASTTx(ctx context.Context, tx *sql.Tx, caseID uuid.UUID) (WorkflowAST, error) {
-	var raw []byte
+// loadWorkflowASTTx loads the case's workflow version with its environment
+// values applied; see environmentValuesTx. Publishing checks that every
+// environment reference resolves, so unresolved ones are only logged here.
+func (e *Engine) loadWorkflowASTTx(ctx context.Context, tx *sql.Tx, caseID uuid.UUID) (WorkflowAST, error) {
+	var (
+		raw       []byte
+		tenantID  uuid.UUID
+		variables []byte
+	)
 	err := tx.QueryRowContext(ctx, `
-SELECT wv.ast
+SELECT wv.ast, c.tenant_id, c.variables
 FROM cases c
 JOIN workflow_versions wv ON wv.workflow_id = c.workflow_id AND wv.version = c.workflow_version
 WHERE c.id = $1
-`, caseID).Scan(&raw)
+`, caseID).Scan(&raw, &tenantID, &variables)
 	if err != nil {
 		if errors.Is(err, sql.ErrNoRows) {
 			return WorkflowAST{}, ErrNotFound
@@ -219,6 +277,20 @@ WHERE c.id = $1
 	if err != nil {
 		return WorkflowAST{}, err
 	}
+	if len(ast.EnvironmentRefs()) == 0 {
+		return ast, nil
+	}
+	values, err := e.environmentValuesTx(ctx, tx, tenantID, ast, variables)
+	if err != nil {
+		return WorkflowAST{}, err
+	}
+	ast, missing, err := ast.WithEnvironmentValues(values)
+	if err != nil {
+		return WorkflowAST{}, err
+	}
+	if len(missing) > 0 {
+		slog.WarnContext(ctx, "workflow environment values missing", "case_id", caseID.String(), "environment", e.environment, "missing", missing)
+	}
 	return ast, nil
 }
 
@@ -228,13 +300,37 @@ func (e *Engine) triggerEvaluation(caseID uuid.UUID) {
 	})
 }
 
-func (e *Engine) dispatchStep(caseID uuid.UUID, step WorkflowStep) {
+// dispatchStep runs step detached from the evaluation's cancellation but
+// keeps its span context, so step spans are children of the evaluation span.
+// While draining, pooled steps are not started; they stay active and are
+// marked aborted so Recover runs them on the next start.
+func (e *Engine) dispatchStep(ctx context.Context, caseID uuid.UUID, step WorkflowStep) {
+	stepCtx := trace.ContextWithSpanContext(e.execCtx, trace.SpanContextFromContext(ctx))
+	if cid := observability.CorrelationIDFromContext(ctx); cid != "" {
+		stepCtx = observability.WithCorrelationID(stepCtx, cid)
+	}
+	if tenantID, err := uuid.Parse(observability.TenantIDFromContext(ctx)); err == nil {
+		stepCtx = observability.WithTenantID(stepCtx, tenantID)
+	}
+	stepCtx = observability.WithLogAttrs(stepCtx, observability.LogAttrsFromContext(ctx)...)
+	lane := LaneFromContext(ctx)
+	stepCtx = WithLane(stepCtx, lane)
+	stepCtx = WithMockProfile(stepCtx, MockProfileFromContext(ctx))
 	if step.Type == "human_task" {
-		_ = e.executeWithRetry(context.Background(), caseID, step)
+		_ = e.executeWithRetry(stepCtx, caseID, step)
+		return
+	}
+	if e.draining.Load() {
+		if err := e.recordAborted(ctx, caseID, step.ID, false); err != nil {
+			slog.ErrorContext(ctx, "record deferred step failed", "case_id", caseID.String(), "step_id", step.ID, "error", err)
+		}
 		return
 	}
-	e.executions.Submit(func() {
-		_ = e.executeWithRetry(context.Background(), caseID, step)
+	e.executions.Submit(lane, func() {
+		runCtx, cancel := context.WithCancelCause(stepCtx)
+		defer cancel(nil)
+		defer e.trackExecution(caseID, step, cancel)()
+		_ = e.executeWithRetry(runCtx, caseID, step)
 	})
 }
 
@@ -1,7 +1,17 @@
 package engine
 
 import (
+	"context"
+	"encoding/json"
+	"errors"
+	"reflect"
+	"strings"
+	"sync/atomic"
 	"testing"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/redaction"
 )
 
 type fixedEval struct {
@@ -186,3 +196,572 @@ func TestValidateAST_CycleDetection(t *testing.T) {
 		t.Fatal("expected cycle validation error")
 	}
 }
+
+type caseFlagEval struct{}
+
+func (caseFlagEval) EvaluateBool(expr string, ctx map[string]interface{}) (bool, error) {
+	data, _ := ctx["case"].(map[string]any)
+	v, _ := data[expr].(bool)
+	return v, nil
+}
+
+func TestSimulate_RoutesOnMockOutcomesAndCaseData(t *testing.T) {
+	ast := WorkflowAST{Steps: []WorkflowStep{
+		{ID: "check", Type: "rule", Outcomes: map[string][]string{"approve": {"approve"}, "reject": {"reject"}}},
+		{ID: "approve", Type: "integration", DependsOn: []string{"check"}},
+		{ID: "reject", Type: "integration", DependsOn: []string{"check"}},
+		{ID: "notify", Type: "notification", DependsOn: []string{"approve"}, Condition: "vip"},
+		{ID: "audit", Type: "rule"},
+	}}
+	calls := []string{}
+	steps, err := Simulate(ast, caseFlagEval{}, nil, func(step WorkflowStep, _ int, caseData map[string]any) (string, map[string]any) {
+		calls = append(calls, step.ID)
+		if step.ID == "check" {
+			return "approve", map[string]any{"vip": false}
+		}
+		return "", caseData
+	})
+	if err != nil {
+		t.Fatalf("Simulate: %v", err)
+	}
+	byID := map[string]SimulatedStep{}
+	for _, s := range steps {
+		byID[s.StepID] = s
+	}
+	if byID["check"].Wave != 1 || byID["audit"].Wave != 1 || byID["approve"].Wave != 2 {
+		t.Fatalf("unexpected waves %+v", steps)
+	}
+	if byID["reject"].State != StateSkipped || byID["reject"].Reason != string(ReasonOutcomeRouting) {
+		t.Fatalf("reject should be skipped by outcome routing, got %+v", byID["reject"])
+	}
+	if byID["notify"].State != StateSkipped || byID["notify"].Reason != string(ReasonGuardFalse) {
+		t.Fatalf("notify should be skipped by its guard, got %+v", byID["notify"])
+	}
+	if len(calls) != 3 {
+		t.Fatalf("expected 3 simulated executions, got %v", calls)
+	}
+}
+
+func TestAbortCaseExecutions(t *testing.T) {
+	eng := New(nil, nil, Config{})
+	cancelled, other := uuid.New(), uuid.New()
+	ctxA, cancelA := context.WithCancelCause(context.Background())
+	ctxB, cancelB := context.WithCancelCause(context.Background())
+	defer eng.trackExecution(cancelled, WorkflowStep{ID: "call"}, cancelA)()
+	defer eng.trackExecution(other, WorkflowStep{ID: "call"}, cancelB)()
+
+	eng.abortCaseExecutions(cancelled)
+	if !errors.Is(context.Cause(ctxA), ErrCaseCancelled) {
+		t.Fatalf("expected the case's execution to be cancelled, got %v", context.Cause(ctxA))
+	}
+	if ctxB.Err() != nil {
+		t.Fatal("another case's execution was cancelled")
+	}
+}
+
+func TestShutdown_WaitsForInFlightExecutions(t *testing.T) {
+	eng := New(nil, nil, Config{})
+	caseID := uuid.New()
+	started := make(chan struct{})
+	release := make(chan struct{})
+	eng.executions.Submit(LaneStandard, func() {
+		defer eng.trackExecution(caseID, WorkflowStep{ID: "call", Type: "integration"}, func(error) {})()
+		close(started)
+		<-release
+	})
+	<-started
+
+	done := make(chan error, 1)
+	go func() { done <- eng.Shutdown(context.Background()) }()
+	for !eng.Draining() {
+		time.Sleep(time.Millisecond)
+	}
+	if inflight := eng.InFlight(); len(inflight) != 1 || inflight[0].CaseID != caseID || inflight[0].StepID != "call" {
+		t.Fatalf("unexpected in-flight executions %+v", inflight)
+	}
+	select {
+	case err := <-done:
+		t.Fatalf("shutdown returned before execution finished: %v", err)
+	case <-time.After(20 * time.Millisecond):
+	}
+
+	close(release)
+	if err := <-done; err != nil {
+		t.Fatalf("shutdown: %v", err)
+	}
+	if inflight := eng.InFlight(); len(inflight) != 0 {
+		t.Fatalf("expected no in-flight executions, got %+v", inflight)
+	}
+}
+
+func TestAbortedBeforeStart(t *testing.T) {
+	cases := []struct {
+		raw  string
+		want bool
+	}{
+		{raw: ``},
+		{raw: `null`},
+		{raw: `{"type":"aborted","reason":"shutdown","started":false}`, want: true},
+		{raw: `{"type":"aborted","reason":"shutdown","started":true}`},
+		{raw: `{"type":"retry_attempt","attempt":1}`},
+	}
+	for _, tc := range cases {
+		if got := abortedBeforeStart([]byte(tc.raw)); got != tc.want {
+			t.Fatalf("abortedBeforeStart(%s) = %v, want %v", tc.raw, got, tc.want)
+		}
+	}
+}
+
+func TestSettledState(t *testing.T) {
+	cases := []struct {
+		name        string
+		states      map[string]StepState
+		transitions []Transition
+		settled     bool
+		failed      bool
+	}{
+		{
+			name:    "all completed",
+			states:  map[string]StepState{"a": {State: StateCompleted}, "b": {State: StateSkipped}},
+			settled: true,
+		},
+		{
+			name:   "step active",
+			states: map[string]StepState{"a": {State: StateCompleted}, "b": {State: StateActive}},
+		},
+		{
+			name:        "pending step activated",
+			states:      map[string]StepState{"a": {State: StateCompleted}, "b": {State: StatePending}},
+			transitions: []Transition{{StepID: "b", From: StatePending, To: StateActive, Type: TransitionToActive}},
+		},
+		{
+			name:        "pending step skipped",
+			states:      map[string]StepState{"a": {State: StateCompleted}, "b": {State: StatePending}},
+			transitions: []Transition{{StepID: "b", From: StatePending, To: StateSkipped, Type: TransitionToSkipped}},
+			settled:     true,
+		},
+		{
+			name:    "blocked behind failure",
+			states:  map[string]StepState{"a": {State: StateFailed}, "b": {State: StatePending}},
+			settled: true,
+			failed:  true,
+		},
+	}
+	for _, tc := range cases {
+		settled, failed := settledState(tc.states, tc.transitions)
+		if settled != tc.settled || failed != tc.failed {
+			t.Fatalf("%s: settledState = (%v, %v), want (%v, %v)", tc.name, settled, failed, tc.settled, tc.failed)
+		}
+	}
+}
+
+func TestForEachItems(t *testing.T) {
+	root := map[string]any{"case": map[string]any{
+		"applicants": []any{"ann", "bob"},
+		"name":       "x",
+		"steps":      map[string]any{"fetch": map[string]any{"result": map[string]any{"output": map[string]any{"rows": []any{1.0}}}}},
+	}}
+	items, err := forEachItems(root, "case.applicants", 0)
+	if err != nil || !reflect.DeepEqual(items, []any{"ann", "bob"}) {
+		t.Fatalf("case data items = %v, %v", items, err)
+	}
+	items, err = forEachItems(root, "case.steps.fetch.result.output.rows", 0)
+	if err != nil || len(items) != 1 {
+		t.Fatalf("step output items = %v, %v", items, err)
+	}
+	if items, err := forEachItems(root, "case.missing.rows", 0); err != nil || len(items) != 0 {
+		t.Fatalf("missing path should be empty, got %v, %v", items, err)
+	}
+	if _, err := forEachItems(root, "case.name", 0); err == nil {
+		t.Fatal("expected error for non-array items")
+	}
+
+	root["case"].(map[string]any)["applicants"] = []any{"ann", "bob", "cat"}
+	items, err = forEachItems(root, "case.applicants", 2)
+	if err != nil || !reflect.DeepEqual(items, []any{[]any{"ann", "bob"}, []any{"cat"}}) {
+		t.Fatalf("batched items = %v, %v", items, err)
+	}
+}
+
+func TestRunForEach_BoundsParallelismAndKeepsOrder(t *testing.T) {
+	var running, peak int32
+	items := []any{"a", "b", "c", "d", "e"}
+	outputs, err := runForEach(context.Background(), items, 2, func(_ context.Context, item any, index int) (json.RawMessage, error) {
+		n := atomic.AddInt32(&running, 1)
+		for {
+			p := atomic.LoadInt32(&peak)
+			if n <= p || atomic.CompareAndSwapInt32(&peak, p, n) {
+				break
+			}
+		}
+		time.Sleep(5 * time.Millisecond)
+		atomic.AddInt32(&running, -1)
+		return json.Marshal(map[string]any{"item": item, "index": index})
+	})
+	if err != nil {
+		t.Fatalf("run for_each: %v", err)
+	}
+	if peak > 2 {
+		t.Fatalf("expected at most 2 concurrent items, saw %d", peak)
+	}
+	for i, out := range outputs {
+		var got map[string]any
+		if err := json.Unmarshal(out, &got); err != nil || got["item"] != items[i] {
+			t.Fatalf("output %d out of order: %s", i, out)
+		}
+	}
+
+	boom := errors.New("boom")
+	_, err = runForEach(context.Background(), items, 1, func(_ context.Context, _ any, index int) (json.RawMessage, error) {
+		if index == 1 {
+			return nil, boom
+		}
+		return json.RawMessage(`{}`), nil
+	})
+	if !errors.Is(err, boom) {
+		t.Fatalf("expected item failure, got %v", err)
+	}
+}
+
+func TestAggregateOutputs(t *testing.T) {
+	outputs := map[string]json.RawMessage{
+		"score":  json.RawMessage(`{"items":[{"index":0,"output":{"s":1}},{"index":1,"output":{"s":2}}]}`),
+		"manual": json.RawMessage(`{"items":["not","fanned"]}`),
+	}
+	got := aggregateOutputs([]string{"score", "skipped", "manual"}, outputs)
+	raw, err := json.Marshal(got["items"])
+	if err != nil {
+		t.Fatalf("marshal items: %v", err)
+	}
+	if want := `[{"s":1},{"s":2},{"items":["not","fanned"]}]`; string(raw) != want {
+		t.Fatalf("items = %s, want %s", raw, want)
+	}
+	if results := got["results"].(map[string]json.RawMessage); len(results) != 2 {
+		t.Fatalf("expected results for completed sources only, got %v", results)
+	}
+}
+
+func TestValidateAST_ForEach(t *testing.T) {
+	ast := WorkflowAST{Steps: []WorkflowStep{{ID: "review", Type: "human_task", ForEach: &ForEach{Items: "case.rows"}}}}
+	if err := ValidateAST(ast); err == nil {
+		t.Fatal("expected for_each on human_task to be rejected")
+	}
+	ast.Steps[0] = WorkflowStep{ID: "draft", Type: "agent", ForEach: &ForEach{Items: "case.rows"}}
+	if err := ValidateAST(ast); err == nil {
+		t.Fatal("expected for_each on a step type that ignores the item to be rejected")
+	}
+	ast.Steps[0] = WorkflowStep{ID: "score", Type: "integration", ForEach: &ForEach{}}
+	if err := ValidateAST(ast); err == nil {
+		t.Fatal("expected for_each without items to be rejected")
+	}
+	ast.Steps[0].ForEach.Items = "case.rows"
+	ast.Steps[0].ForEach.Parallelism = MaxForEachParallelism + 1
+	if err := ValidateAST(ast); err == nil {
+		t.Fatal("expected for_each parallelism above the limit to be rejected")
+	}
+	ast.Steps[0].ForEach.Parallelism = MaxForEachParallelism
+	if err := ValidateAST(ast); err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+}
+
+func TestAddForEachItem(t *testing.T) {
+	tplCtx := map[string]any{"case": map[string]any{}}
+	AddForEachItem(context.Background(), tplCtx)
+	if _, ok := tplCtx["item"]; ok {
+		t.Fatalf("expected no item outside a for_each step, got %v", tplCtx)
+	}
+	AddForEachItem(WithForEachItem(context.Background(), map[string]any{"id": "a"}, 2), tplCtx)
+	if item, _ := tplCtx["item"].(map[string]any); item["id"] != "a" || tplCtx["item_index"] != 2 {
+		t.Fatalf("unexpected template context %v", tplCtx)
+	}
+	if forEachItemHash(map[string]any{"id": "a"}) == forEachItemHash(map[string]any{"id": "b"}) {
+		t.Fatal("expected different items to hash differently")
+	}
+}
+
+func TestAttemptContext_Timeout(t *testing.T) {
+	ctx, cancel := attemptContext(context.Background(), WorkflowStep{ID: "call", TimeoutSeconds: 1})
+	defer cancel()
+	<-ctx.Done()
+	if !errors.Is(context.Cause(ctx), ErrStepTimedOut) {
+		t.Fatalf("expected step timeout, got %v", context.Cause(ctx))
+	}
+
+	ctx, cancel = attemptContext(context.Background(), WorkflowStep{ID: "call"})
+	defer cancel()
+	if _, ok := ctx.Deadline(); ok {
+		t.Fatal("expected no deadline without timeout_seconds")
+	}
+	ast := WorkflowAST{Steps: []WorkflowStep{{ID: "call", Type: "integration", TimeoutSeconds: -1}}}
+	if err := ValidateAST(ast); err == nil {
+		t.Fatal("expected a negative timeout to be rejected")
+	}
+}
+
+func TestCronScheduleNext(t *testing.T) {
+	london, err := time.LoadLocation("Europe/London")
+	if err != nil {
+		t.Skipf("timezone data unavailable: %v", err)
+	}
+	tests := []struct {
+		expr  string
+		after time.Time
+		want  time.Time
+	}{
+		{"*/15 * * * *", time.Date(2026, 3, 2, 10, 7, 30, 0, time.UTC), time.Date(2026, 3, 2, 10, 15, 0, 0, time.UTC)},
+		{"0 9 * * mon-fri", time.Date(2026, 3, 6, 9, 0, 0, 0, time.UTC), time.Date(2026, 3, 9, 9, 0, 0, 0, time.UTC)},
+		{"30 8 1 * *", time.Date(2026, 12, 15, 0, 0, 0, 0, time.UTC), time.Date(2027, 1, 1, 8, 30, 0, 0, time.UTC)},
+		{"0 0 13 * 5", time.Date(2026, 3, 1, 0, 0, 0, 0, time.UTC), time.Date(2026, 3, 6, 0, 0, 0, 0, time.UTC)},
+		{"@daily", time.Date(2026, 3, 1, 0, 0, 0, 0, time.UTC), time.Date(2026, 3, 2, 0, 0, 0, 0, time.UTC)},
+		// 01:30 does not exist in London on the night the clocks go forward.
+		{"30 1 * * *", time.Date(2026, 3, 28, 12, 0, 0, 0, london), time.Date(2026, 3, 30, 1, 30, 0, 0, london)},
+		{"0 9 * * 7", time.Date(2026, 3, 2, 0, 0, 0, 0, time.UTC), time.Date(2026, 3, 8, 9, 0, 0, 0, time.UTC)},
+		// 01:00-02:00 happens twice in London when the clocks go back. A fixed
+		// 01:30 fires once; an hourly schedule fires in both.
+		{"30 1 * * *", time.Date(2026, 10, 25, 0, 30, 0, 0, time.UTC).In(london), time.Date(2026, 10, 26, 1, 30, 0, 0, london)},
+		{"30 * * * *", time.Date(2026, 10, 25, 0, 30, 0, 0, time.UTC).In(london), time.Date(2026, 10, 25, 1, 30, 0, 0, time.UTC)},
+	}
+	for _, tc := range tests {
+		schedule, err := parseCron(tc.expr)
+		if err != nil {
+			t.Fatalf("parse %q: %v", tc.expr, err)
+		}
+		got, ok := schedule.next(tc.after)
+		if !ok || !got.Equal(tc.want) {
+			t.Fatalf("%q after %s = %s, want %s", tc.expr, tc.after, got, tc.want)
+		}
+	}
+
+	for _, expr := range []string{"* * * *", "60 * * * *", "* * * foo *", "*/0 * * * *", "5-1 * * * *"} {
+		if _, err := parseCron(expr); err == nil {
+			t.Fatalf("expected %q to be rejected", expr)
+		}
+	}
+	impossible, _ := parseCron("0 0 31 2 *")
+	if _, ok := impossible.next(time.Now()); ok {
+		t.Fatal("expected February 31st to never fire")
+	}
+}
+
+func TestTimerResumeAt(t *testing.T) {
+	now := time.Date(2026, 3, 2, 10, 0, 0, 0, time.UTC)
+	seconds, minutes := 30.0, 2.0
+	data := map[string]any{"follow_up": map[string]any{"at": "2026-03-05T09:00:00+01:00"}}
+
+	tests := []struct {
+		name string
+		cfg  timerConfig
+		want time.Time
+	}{
+		{"duration", timerConfig{Duration: "1h30m"}, now.Add(90 * time.Minute)},
+		{"days", timerConfig{Duration: "2d"}, now.Add(48 * time.Hour)},
+		{"seconds and minutes", timerConfig{Seconds: &seconds, Minutes: &minutes}, now.Add(150 * time.Second)},
+		{"until", timerConfig{Until: "2026-03-03T00:00:00Z"}, time.Date(2026, 3, 3, 0, 0, 0, 0, time.UTC)},
+		{"until path", timerConfig{UntilPath: "case.data.follow_up.at"}, time.Date(2026, 3, 5, 8, 0, 0, 0, time.UTC)},
+		{"schedule", timerConfig{Schedule: "0 9 * * *", Timezone: "America/New_York"}, time.Date(2026, 3, 2, 14, 0, 0, 0, time.UTC)},
+	}
+	for _, tc := range tests {
+		got, err := timerResumeAt(tc.cfg, now, data)
+		if err != nil {
+			t.Fatalf("%s: %v", tc.name, err)
+		}
+		if !got.Equal(tc.want) {
+			t.Fatalf("%s: resume at %s, want %s", tc.name, got, tc.want)
+		}
+	}
+
+	for name, cfg := range map[string]timerConfig{
+		"empty":        {},
+		"two kinds":    {Duration: "1h", Until: "2026-03-03T00:00:00Z"},
+		"bad duration": {Duration: "soon"},
+		"missing path": {UntilPath: "case.data.missing"},
+		"bad timezone": {Schedule: "@hourly", Timezone: "Mars/Olympus"},
+	} {
+		if _, err := timerResumeAt(cfg, now, data); err == nil {
+			t.Fatalf("%s: expected an error", name)
+		}
+	}
+}
+
+func TestWorkflowContract(t *testing.T) {
+	ast := WorkflowAST{
+		InputSchema: json.RawMessage(`{"type":"object","required":["customer"],"properties":{"customer":{"type":"object","required":["id"],"properties":{"id":{"type":"string"}}},"amount":{"type":"number"}}}`),
+		Outputs:     map[string]string{"decision": "case.decision", "score": "case.steps.score.result.output.value", "missing": "case.nope"},
+	}
+	if err := ast.ValidateContract(); err != nil {
+		t.Fatalf("validate contract: %v", err)
+	}
+
+	violations, err := ast.ValidateInput(map[string]any{"customer": map[string]any{"id": "c-1"}, "amount": 10})
+	if err != nil || len(violations) != 0 {
+		t.Fatalf("expected valid input, got %v (%v)", violations, err)
+	}
+	violations, err = ast.ValidateInput(map[string]any{"customer": map[string]any{}, "amount": "ten"})
+	if err != nil {
+		t.Fatalf("validate input: %v", err)
+	}
+	if len(violations) != 2 || violations[0].Path != "/amount" || violations[1].Path != "/customer" {
+		t.Fatalf("unexpected violations %#v", violations)
+	}
+
+	outputs := ast.ResolveOutputs(map[string]any{"case": map[string]any{
+		"decision": "approve",
+		"steps":    map[string]any{"score": map[string]any{"result": map[string]any{"output": map[string]any{"value": 0.9}}}},
+	}})
+	want := map[string]any{"decision": "approve", "score": 0.9, "missing": nil}
+	if !reflect.DeepEqual(outputs, want) {
+		t.Fatalf("outputs = %#v, want %#v", outputs, want)
+	}
+
+	if err := (WorkflowAST{Outputs: map[string]string{"x": "data.x"}}).ValidateContract(); err == nil {
+		t.Fatal("expected an output path outside case to be rejected")
+	}
+}
+
+func TestForEnvironment(t *testing.T) {
+	ast := WorkflowAST{
+		Steps: []WorkflowStep{
+			{ID: "crm", Type: "integration", Config: json.RawMessage(`{"connector":"http","input":{"url":"{{env.crm_url}}/customers","timeout":"{{ env.timeout }}","profile":"{{env.profile}}"}}`)},
+			{ID: "review", Type: "human_task", Config: json.RawMessage(`{"assign_to_role":"underwriter"}`)},
+		},
+		Environments: map[string]map[string]any{
+			"default": {"timeout": 30.0, "profile": "sandbox"},
+			"prod":    {"crm_url": "https://crm.example.com", "profile": "crm-prod"},
+		},
+	}
+	if refs := ast.EnvironmentRefs(); !reflect.DeepEqual(refs, []string{"crm_url", "profile", "timeout"}) {
+		t.Fatalf("refs = %v", refs)
+	}
+
+	prod, missing, err := ast.ForEnvironment("prod")
+	if err != nil || len(missing) != 0 {
+		t.Fatalf("resolve prod: %v (missing %v)", err, missing)
+	}
+	var cfg map[string]any
+	if err := json.Unmarshal(prod.Steps[0].Config, &cfg); err != nil {
+		t.Fatalf("decode: %v", err)
+	}
+	want := map[string]any{"url": "https://crm.example.com/customers", "timeout": 30.0, "profile": "crm-prod"}
+	if !reflect.DeepEqual(cfg["input"], want) {
+		t.Fatalf("input = %#v, want %#v", cfg["input"], want)
+	}
+	if string(prod.Steps[1].Config) != `{"assign_to_role":"underwriter"}` {
+		t.Fatalf("config without references changed: %s", prod.Steps[1].Config)
+	}
+	if string(ast.Steps[0].Config) == string(prod.Steps[0].Config) {
+		t.Fatal("ForEnvironment modified the original workflow")
+	}
+
+	dev, missing, err := ast.ForEnvironment("dev")
+	if err != nil {
+		t.Fatalf("resolve dev: %v", err)
+	}
+	if !reflect.DeepEqual(missing, []string{"crm_url"}) {
+		t.Fatalf("missing = %v", missing)
+	}
+	if err := json.Unmarshal(dev.Steps[0].Config, &cfg); err != nil {
+		t.Fatalf("decode: %v", err)
+	}
+	if url := cfg["input"].(map[string]any)["url"]; url != "{{env.crm_url}}/customers" {
+		t.Fatalf("unresolved reference should be kept, got %v", url)
+	}
+}
+
+func TestValidateVariables(t *testing.T) {
+	ast := WorkflowAST{Environments: map[string]map[string]any{
+		"default": {"timeout": 30.0, "profile": "sandbox"},
+		"prod":    {"crm_url": "https://crm.example.com"},
+	}}
+	if violations := ast.ValidateVariables(map[string]any{"timeout": 5.0, "crm_url": "http://localhost"}); len(violations) != 0 {
+		t.Fatalf("expected no violations, got %#v", violations)
+	}
+	violations := ast.ValidateVariables(map[string]any{"timeout": "5", "region": "eu"})
+	want := []InputViolation{
+		{Path: "/region", Keyword: "declared", Message: "is not a declared variable"},
+		{Path: "/timeout", Keyword: "type", Message: "must be type number, got string"},
+	}
+	if !reflect.DeepEqual(violations, want) {
+		t.Fatalf("violations = %#v", violations)
+	}
+}
+
+type memoryArtifacts struct{ saved []Artifact }
+
+func (m *memoryArtifacts) SaveArtifact(_ context.Context, _ uuid.UUID, _ string, artifact Artifact) (Artifact, error) {
+	m.saved = append(m.saved, artifact)
+	return Artifact{ID: uuid.New(), Name: artifact.Name, ContentType: artifact.ContentType, SizeBytes: int64(len(artifact.Data)), Data: artifact.Data}, nil
+}
+
+func TestStoreArtifactsKeepsHandlesOnly(t *testing.T) {
+	e := &Engine{}
+	existing := Artifact{ID: uuid.New(), Name: "earlier.txt"}
+	result := &StepResult{Artifacts: []Artifact{{Name: "report.pdf", ContentType: "application/pdf", Data: []byte("%PDF")}, existing}}
+	if err := e.storeArtifacts(context.Background(), uuid.New(), "render", result); err == nil {
+		t.Fatal("expected an error without an artifact store")
+	}
+
+	store := &memoryArtifacts{}
+	e.SetArtifactStore(store)
+	if err := e.storeArtifacts(context.Background(), uuid.New(), "render", result); err != nil {
+		t.Fatalf("store artifacts: %v", err)
+	}
+	if len(store.saved) != 1 || string(store.saved[0].Data) != "%PDF" {
+		t.Fatalf("expected only the new artifact to be saved, got %#v", store.saved)
+	}
+	stored := result.Artifacts[0]
+	if stored.ID == uuid.Nil || stored.SizeBytes != 4 || stored.Data != nil || result.Artifacts[1].ID != existing.ID {
+		t.Fatalf("unexpected artifacts: %#v", result.Artifacts)
+	}
+	raw, _ := json.Marshal(result)
+	if strings.Contains(string(raw), "%PDF") {
+		t.Fatalf("artifact data leaked into the step result: %s", raw)
+	}
+}
+
+func TestRedactResultMasksStoredOutput(t *testing.T) {
+	e := &Engine{}
+	redactor, err := redaction.Compile(redaction.Rules{Paths: []string{"$.customer.ssn"}})
+	if err != nil {
+		t.Fatalf("compile: %v", err)
+	}
+	e.SetRedactor(redactor)
+
+	result := &StepResult{Output: json.RawMessage(`{"customer":{"ssn":"123-45-6789","name":"Ada"}}`)}
+	stored := e.redactResult(result)
+	if string(stored.Output) != `{"customer":{"name":"Ada","ssn":"[REDACTED]"}}` {
+		t.Fatalf("unexpected stored output: %s", stored.Output)
+	}
+	if !strings.Contains(string(result.Output), "123-45-6789") {
+		t.Fatal("redactResult must not modify the executor's result")
+	}
+
+	sensitive := e.redactResult(&StepResult{Output: json.RawMessage(`{"token":"abc"}`), Sensitive: true})
+	if string(sensitive.Output) != `"[REDACTED]"` {
+		t.Fatalf("sensitive output was stored: %s", sensitive.Output)
+	}
+}
+
+func TestCostedExecutorAddsCallAndReportedCosts(t *testing.T) {
+	exec := costedExecutor{inner: NewMockExecutor(map[string][]MockExecution{
+		"score":  {{Result: &StepResult{Output: json.RawMessage(`{"_meta":{"cost_units":0.25},"score":7}`), Cost: 0.5}}},
+		"notify": {{Result: nil}},
+		"fail":   {{Err: errors.New("boom")}},
+	})}
+	config := json.RawMessage(`{"cost_per_call":0.1}`)
+
+	res, err := exec.Execute(context.Background(), uuid.New(), "score", config)
+	if err != nil {
+		t.Fatalf("execute: %v", err)
+	}
+	if diff := res.Cost - 0.85; diff > 1e-9 || diff < -1e-9 {
+		t.Fatalf("expected executor, per-call and reported costs to add up to 0.85, got %v", res.Cost)
+	}
+	res, err = exec.Execute(context.Background(), uuid.New(), "notify", config)
+	if err != nil || res == nil || res.Cost != 0.1 {
+		t.Fatalf("expected the per-call cost without a result, got %#v, %v", res, err)
+	}
+	if res, err := exec.Execute(context.Background(), uuid.New(), "fail", config); err == nil || res != nil {
+		t.Fatalf("expected the failure to pass through, got %#v, %v", res, err)
+	}
+}
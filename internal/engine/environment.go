@@ -0,0 +1,226 @@
+package engine
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"fmt"
+	"regexp"
+	"sort"
+	"strings"
+
+	"github.com/google/uuid"
+)
+
+// DefaultEnvironment is the overlay every environment inherits from.
+const DefaultEnvironment = "default"
+
+var envRefPattern = regexp.MustCompile(`\{\{\s*env\.([A-Za-z0-9_.-]+)\s*\}\}`)
+
+// EnvironmentValues returns the values an environment sees: the default
+// overlay with the named overlay on top.
+func (ast WorkflowAST) EnvironmentValues(name string) map[string]any {
+	out := map[string]any{}
+	for k, v := range ast.Environments[DefaultEnvironment] {
+		out[k] = v
+	}
+	if name = strings.TrimSpace(name); name != "" && name != DefaultEnvironment {
+		for k, v := range ast.Environments[name] {
+			out[k] = v
+		}
+	}
+	return out
+}
+
+// EnvironmentRefs lists the {{env.*}} names used in step configs, sorted.
+func (ast WorkflowAST) EnvironmentRefs() []string {
+	seen := map[string]bool{}
+	for _, step := range ast.Steps {
+		for _, match := range envRefPattern.FindAllSubmatch(step.Config, -1) {
+			seen[string(match[1])] = true
+		}
+	}
+	out := make([]string, 0, len(seen))
+	for name := range seen {
+		out = append(out, name)
+	}
+	sort.Strings(out)
+	return out
+}
+
+// ForEnvironment returns a copy of the workflow with {{env.*}} references in
+// step configs replaced by the environment's values. A string that is only a
+// reference takes the value as is, so overlays can hold numbers and objects;
+// embedded references are spliced in as text. References without a value are
+// left in place and reported in missing.
+func (ast WorkflowAST) ForEnvironment(name string) (WorkflowAST, []string, error) {
+	return ast.WithEnvironmentValues(ast.EnvironmentValues(name))
+}
+
+// WithEnvironmentValues resolves {{env.*}} references as ForEnvironment
+// does, from values instead of the workflow's own overlays.
+func (ast WorkflowAST) WithEnvironmentValues(values map[string]any) (WorkflowAST, []string, error) {
+	refs := ast.EnvironmentRefs()
+	if len(refs) == 0 {
+		return ast, nil, nil
+	}
+	var missing []string
+	for _, ref := range refs {
+		if _, ok := values[ref]; !ok {
+			missing = append(missing, ref)
+		}
+	}
+
+	out := ast
+	out.Steps = make([]WorkflowStep, len(ast.Steps))
+	for i, step := range ast.Steps {
+		out.Steps[i] = step
+		if !envRefPattern.Match(step.Config) {
+			continue
+		}
+		var cfg any
+		if err := json.Unmarshal(step.Config, &cfg); err != nil {
+			return WorkflowAST{}, nil, fmt.Errorf("decode step %s config: %w", step.ID, err)
+		}
+		raw, err := json.Marshal(ResolveEnvRefs(cfg, values))
+		if err != nil {
+			return WorkflowAST{}, nil, fmt.Errorf("encode step %s config: %w", step.ID, err)
+		}
+		out.Steps[i].Config = raw
+	}
+	return out, missing, nil
+}
+
+// ResolveEnvRefs replaces {{env.*}} references in a decoded JSON value, as
+// ForEnvironment does for step configs.
+func ResolveEnvRefs(value any, values map[string]any) any {
+	switch v := value.(type) {
+	case map[string]any:
+		out := make(map[string]any, len(v))
+		for k, item := range v {
+			out[k] = ResolveEnvRefs(item, values)
+		}
+		return out
+	case []any:
+		out := make([]any, len(v))
+		for i, item := range v {
+			out[i] = ResolveEnvRefs(item, values)
+		}
+		return out
+	case string:
+		if match := envRefPattern.FindStringSubmatch(v); match != nil && match[0] == strings.TrimSpace(v) {
+			if resolved, ok := values[match[1]]; ok {
+				return resolved
+			}
+			return v
+		}
+		return envRefPattern.ReplaceAllStringFunc(v, func(ref string) string {
+			resolved, ok := values[envRefPattern.FindStringSubmatch(ref)[1]]
+			if !ok {
+				return ref
+			}
+			if s, isString := resolved.(string); isString {
+				return s
+			}
+			raw, _ := json.Marshal(resolved)
+			return string(raw)
+		})
+	}
+	return value
+}
+
+// environmentValuesTx returns the values a case's {{env.*}} references
+// resolve to. From lowest to highest precedence: the workflow's default
+// overlay, its overlay for the engine's environment, the tenant's default
+// variable set, the tenant's set for the engine's environment, and the
+// variables the case was started with.
+func (e *Engine) environmentValuesTx(ctx context.Context, tx *sql.Tx, tenantID uuid.UUID, ast WorkflowAST, caseVariables []byte) (map[string]any, error) {
+	values := ast.EnvironmentValues(e.environment)
+	rows, err := tx.QueryContext(ctx, `
+SELECT name, value
+FROM environment_variables
+WHERE tenant_id = $1 AND environment IN ($2, $3)
+ORDER BY environment = $2 DESC
+`, tenantID, DefaultEnvironment, strings.TrimSpace(e.environment))
+	if err != nil {
+		return nil, fmt.Errorf("load environment variables: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	for rows.Next() {
+		var (
+			name  string
+			raw   []byte
+			value any
+		)
+		if err := rows.Scan(&name, &raw); err != nil {
+			return nil, fmt.Errorf("scan environment variable: %w", err)
+		}
+		if err := json.Unmarshal(raw, &value); err != nil {
+			return nil, fmt.Errorf("decode environment variable %s: %w", name, err)
+		}
+		values[name] = value
+	}
+	if err := rows.Err(); err != nil {
+		return nil, fmt.Errorf("iterate environment variables: %w", err)
+	}
+	if len(caseVariables) > 0 {
+		var overrides map[string]any
+		if err := json.Unmarshal(caseVariables, &overrides); err != nil {
+			return nil, fmt.Errorf("decode case variables: %w", err)
+		}
+		for name, value := range overrides {
+			values[name] = value
+		}
+	}
+	return values, nil
+}
+
+// ValidateVariables checks execution-time overrides of environment values.
+// A variable is declared by a value in any of the workflow's overlays, and
+// an override must have the same JSON type as one of its declared values.
+func (ast WorkflowAST) ValidateVariables(overrides map[string]any) []InputViolation {
+	names := make([]string, 0, len(overrides))
+	for name := range overrides {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	var violations []InputViolation
+	for _, name := range names {
+		types := map[string]bool{}
+		for _, overlay := range ast.Environments {
+			if value, ok := overlay[name]; ok {
+				types[jsonType(value)] = true
+			}
+		}
+		switch got := jsonType(overrides[name]); {
+		case len(types) == 0:
+			violations = append(violations, InputViolation{Path: "/" + name, Keyword: "declared", Message: "is not a declared variable"})
+		case !types[got]:
+			want := make([]string, 0, len(types))
+			for t := range types {
+				want = append(want, t)
+			}
+			sort.Strings(want)
+			violations = append(violations, InputViolation{Path: "/" + name, Keyword: "type", Message: fmt.Sprintf("must be type %s, got %s", strings.Join(want, " or "), got)})
+		}
+	}
+	return violations
+}
+
+func jsonType(value any) string {
+	switch value.(type) {
+	case nil:
+		return "null"
+	case string:
+		return "string"
+	case bool:
+		return "boolean"
+	case float64, float32, int, int64, json.Number:
+		return "number"
+	case map[string]any:
+		return "object"
+	case []any:
+		return "array"
+	}
+	return fmt.Sprintf("%T", value)
+}
@@ -3,6 +3,7 @@ package engine
 import (
 	"context"
 	"database/sql"
+	"encoding/json"
 	"errors"
 	"fmt"
 	"log/slog"
@@ -10,38 +11,116 @@ import (
 	"time"
 
 	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/features"
 	"github.com/neural-chilli/aceryx/internal/observability"
+	"github.com/neural-chilli/aceryx/internal/redaction"
+	"go.opentelemetry.io/otel/attribute"
+	"go.opentelemetry.io/otel/trace"
 )
 
 func (e *Engine) executeWithRetry(ctx context.Context, caseID uuid.UUID, step WorkflowStep) error {
 	start := time.Now()
+	ctx = observability.WithLogAttrs(ctx,
+		slog.String("case_id", caseID.String()),
+		slog.String("step_id", step.ID),
+		slog.String("step_type", step.Type),
+	)
+	ctx = redaction.WithRedactor(ctx, e.activeRedactor())
+	ctx, span := observability.Tracer().Start(ctx, "step.execute", trace.WithAttributes(observability.ExecutionSpanAttrs(ctx)...))
+	defer func() {
+		span.SetAttributes(attribute.Int64("duration_ms", time.Since(start).Milliseconds()))
+		span.End()
+	}()
+	if e.stepLogLines > 0 {
+		var capture *observability.LogCapture
+		ctx, capture = observability.WithLogCapture(ctx, e.stepLogLines)
+		defer e.storeStepLogs(ctx, caseID, step.ID, capture)
+	}
 	exec, err := e.executorFor(step.Type)
 	if err != nil {
+		observability.SpanError(span, err)
 		return err
 	}
+	if gate := e.featureGate(); gate != nil {
+		tenantID, err := e.lookupTenantID(ctx, caseID)
+		if err != nil {
+			return err
+		}
+		if flag := features.StepFlag(step.Type); !gate.Enabled(ctx, tenantID, flag) {
+			// Disabled capabilities fail fast; retrying cannot succeed until an operator re-enables them.
+			disabledErr := fmt.Errorf("%w: %s", ErrFeatureDisabled, flag)
+			observability.SpanError(span, disabledErr)
+			return e.failStep(ctx, caseID, step.ID, disabledErr)
+		}
+	}
+	exec = costedExecutor{inner: exec}
+	if step.ForEach != nil {
+		exec = &forEachExecutor{engine: e, inner: exec, spec: *step.ForEach}
+	}
+	if gate := e.usageGate(); gate != nil {
+		exec = usageGatedExecutor{engine: e, gate: gate, inner: exec}
+	}
 	policy := defaultErrorPolicyForStep(step.Type, step.ErrorPolicy)
 
 	attempt := 0
+	var runtime time.Duration
 	for {
 		attempt++
-		result, execErr := exec.Execute(ctx, caseID, step.ID, step.Config)
+		e.emitStepEvent(ctx, StepEvent{CaseID: caseID, StepID: step.ID, Status: StepStatusRunning, Attempt: attempt})
+		e.recordStepInputs(ctx, caseID, step, attempt)
+		execStart := time.Now()
+		attemptCtx, cancelAttempt := attemptContext(ctx, step)
+		result, execErr := exec.Execute(attemptCtx, caseID, step.ID, step.Config)
+		if execErr != nil && errors.Is(context.Cause(attemptCtx), ErrStepTimedOut) {
+			execErr = fmt.Errorf("%w after %ds: %w", ErrStepTimedOut, step.TimeoutSeconds, execErr)
+		}
+		cancelAttempt()
+		runtime += time.Since(execStart)
 		if errors.Is(execErr, ErrStepAwaitingReview) {
 			return nil
 		}
+		if execErr == nil && result != nil {
+			// A failed artifact upload is retried like a failed attempt.
+			execErr = e.storeArtifacts(ctx, caseID, step.ID, result)
+		}
 		if execErr == nil {
 			if result == nil {
 				result = &StepResult{}
 			}
 			result.Attempts = attempt
+			result.RuntimeMS = runtime.Milliseconds()
+			span.SetAttributes(attribute.Int("attempts", attempt), attribute.String("outcome", result.Outcome))
 			err := e.completeStep(ctx, caseID, step.ID, result)
 			e.observeStepExecution(ctx, caseID, step.Type, start)
 			return err
 		}
 
+		if errors.Is(context.Cause(ctx), ErrCaseCancelled) {
+			// CancelCase has already skipped the step.
+			observability.SpanError(span, ErrCaseCancelled)
+			return ErrCaseCancelled
+		}
+		if e.draining.Load() && ctx.Err() != nil {
+			// Cancelled by Shutdown, which records the abort; the step
+			// stays active for Recover.
+			observability.SpanError(span, execErr)
+			return execErr
+		}
+		if errors.Is(execErr, ErrUsageExceeded) {
+			// Budgets only free up when their window resets; retrying cannot help.
+			observability.SpanError(span, execErr)
+			return e.failStepAttempts(ctx, caseID, step.ID, execErr, attempt, runtime.Milliseconds())
+		}
+		span.AddEvent("attempt failed", trace.WithAttributes(attribute.Int("attempt", attempt), attribute.String("error", execErr.Error())))
 		retryCount, updateErr := e.incrementRetryCount(ctx, caseID, step.ID, attempt, execErr)
 		if updateErr != nil {
 			return updateErr
 		}
+		if retryCount < policy.MaxAttempts && e.draining.Load() {
+			// Don't wait out a backoff during shutdown; Recover retries later.
+			observability.SpanError(span, execErr)
+			return e.recordAborted(ctx, caseID, step.ID, true)
+		}
 		if retryCount < policy.MaxAttempts {
 			slog.WarnContext(ctx, "step retry scheduled",
 				append(observability.RequestAttrs(ctx),
@@ -52,15 +131,81 @@ func (e *Engine) executeWithRetry(ctx context.Context, caseID uuid.UUID, step Wo
 					"max_attempts", policy.MaxAttempts,
 				)...,
 			)
-			time.Sleep(calculateBackoff(policy, retryCount))
+			select {
+			case <-time.After(calculateBackoff(policy, retryCount)):
+			case <-ctx.Done():
+				if errors.Is(context.Cause(ctx), ErrCaseCancelled) {
+					return ErrCaseCancelled
+				}
+			}
 			continue
 		}
-		err := e.onExhausted(ctx, caseID, step, attempt, execErr)
+		span.SetAttributes(attribute.Int("attempts", attempt))
+		observability.SpanError(span, execErr)
+		err := e.onExhausted(ctx, caseID, step, attempt, runtime.Milliseconds(), execErr)
 		e.observeStepExecution(ctx, caseID, step.Type, start)
 		return err
 	}
 }
 
+// attemptContext bounds one attempt of step by its timeout, if it has one.
+func attemptContext(ctx context.Context, step WorkflowStep) (context.Context, context.CancelFunc) {
+	if step.TimeoutSeconds <= 0 {
+		return ctx, func() {}
+	}
+	return context.WithTimeoutCause(ctx, time.Duration(step.TimeoutSeconds)*time.Second, ErrStepTimedOut)
+}
+
+// recordStepInputs stores what an attempt runs with: the step config and the
+// case data and completed step results its templates resolve against, so the
+// step can be replayed later. Recording is best effort.
+func (e *Engine) recordStepInputs(ctx context.Context, caseID uuid.UUID, step WorkflowStep, attempt int) {
+	if !e.recordInputs {
+		return
+	}
+	config := string(step.Config)
+	if strings.TrimSpace(config) == "" {
+		config = "{}"
+	}
+	if _, err := e.db.ExecContext(ctx, `
+UPDATE case_steps cs
+SET inputs = jsonb_build_object(
+    'attempt', $3::int,
+    'config', $4::jsonb,
+    'case', c.data,
+    'steps', COALESCE((
+        SELECT jsonb_object_agg(up.step_id, up.result)
+        FROM case_steps up
+        WHERE up.case_id = c.id AND up.state = 'completed' AND up.result IS NOT NULL
+    ), '{}'::jsonb),
+    'recorded_at', now()
+)
+FROM cases c
+WHERE c.id = cs.case_id AND cs.case_id = $1 AND cs.step_id = $2
+`, caseID, step.ID, attempt, config); err != nil {
+		slog.WarnContext(ctx, "record step inputs failed", "case_id", caseID.String(), "step_id", step.ID, "error", err)
+	}
+}
+
+// storeStepLogs keeps the log lines captured while a step ran on its
+// case_steps row, replacing those of an earlier run.
+func (e *Engine) storeStepLogs(ctx context.Context, caseID uuid.UUID, stepID string, capture *observability.LogCapture) {
+	lines := capture.Lines()
+	if len(lines) == 0 {
+		return
+	}
+	raw, err := json.Marshal(map[string]any{"lines": lines, "dropped": capture.Dropped()})
+	if err != nil {
+		slog.WarnContext(ctx, "encode step logs failed", "error", err)
+		return
+	}
+	if _, err := e.db.ExecContext(context.WithoutCancel(ctx), `
+UPDATE case_steps SET logs = $3::jsonb WHERE case_id = $1 AND step_id = $2
+`, caseID, stepID, string(raw)); err != nil {
+		slog.WarnContext(ctx, "store step logs failed", "error", err)
+	}
+}
+
 func (e *Engine) observeStepExecution(ctx context.Context, caseID uuid.UUID, stepType string, start time.Time) {
 	tenantID, err := e.lookupTenantID(ctx, caseID)
 	if err != nil {
@@ -94,7 +239,7 @@ func (e *Engine) incrementRetryCount(ctx context.Context, caseID uuid.UUID, step
 	    )
 WHERE case_id = $1 AND step_id = $2 AND state = 'active'
 RETURNING retry_count
-`, caseID, stepID, attempt, execErr.Error()).Scan(&retryCount)
+`, caseID, stepID, attempt, e.activeRedactor().RedactString(execErr.Error())).Scan(&retryCount)
 	if err != nil {
 		if errors.Is(err, sql.ErrNoRows) {
 			return 0, fmt.Errorf("step %s is no longer active", stepID)
@@ -111,29 +256,34 @@ RETURNING retry_count
 	return retryCount, nil
 }
 
-func (e *Engine) onExhausted(ctx context.Context, caseID uuid.UUID, step WorkflowStep, attempts int, execErr error) error {
+func (e *Engine) onExhausted(ctx context.Context, caseID uuid.UUID, step WorkflowStep, attempts int, runtimeMS int64, execErr error) error {
 	policy := defaultErrorPolicyForStep(step.Type, step.ErrorPolicy)
 	action := policy.OnExhausted
 
 	switch {
 	case action == "skip":
-		if err := e.skipStepTerminal(ctx, caseID, step.ID, attempts, execErr); err != nil {
+		if err := e.skipStepTerminal(ctx, caseID, step.ID, attempts, runtimeMS, execErr); err != nil {
 			return err
 		}
 		e.triggerEvaluation(caseID)
 		return nil
 	case strings.HasPrefix(action, "goto:"):
 		target := strings.TrimPrefix(action, "goto:")
-		if err := e.failStep(ctx, caseID, step.ID, execErr); err != nil {
+		if err := e.failStepAttempts(ctx, caseID, step.ID, execErr, attempts, runtimeMS); err != nil {
 			return err
 		}
+		e.notifyFailure(ctx, StepFailure{CaseID: caseID, StepID: step.ID, StepType: step.Type, Attempts: attempts, Error: execErr.Error()})
 		if err := e.activateFallbackStep(ctx, caseID, target); err != nil {
 			return err
 		}
 		e.triggerEvaluation(caseID)
 		return nil
 	default:
-		return e.failStep(ctx, caseID, step.ID, execErr)
+		if err := e.failStepAttempts(ctx, caseID, step.ID, execErr, attempts, runtimeMS); err != nil {
+			return err
+		}
+		e.notifyFailure(ctx, StepFailure{CaseID: caseID, StepID: step.ID, StepType: step.Type, Attempts: attempts, Error: execErr.Error()})
+		return nil
 	}
 }
 
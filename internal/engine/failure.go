@@ -0,0 +1,38 @@
+package engine
+
+import (
+	"context"
+
+	"github.com/google/uuid"
+)
+
+// StepFailure describes a step that failed after exhausting its retries.
+type StepFailure struct {
+	CaseID   uuid.UUID
+	StepID   string
+	StepType string
+	Attempts int
+	Error    string
+}
+
+// FailureHook is told about steps that failed after exhausting their retries.
+// It runs on the step's worker, so slow work should be handed off.
+type FailureHook func(ctx context.Context, failure StepFailure)
+
+func (e *Engine) AddFailureHook(hook FailureHook) {
+	if hook == nil {
+		return
+	}
+	e.mu.Lock()
+	defer e.mu.Unlock()
+	e.failureHooks = append(e.failureHooks, hook)
+}
+
+func (e *Engine) notifyFailure(ctx context.Context, failure StepFailure) {
+	e.mu.RLock()
+	hooks := append([]FailureHook(nil), e.failureHooks...)
+	e.mu.RUnlock()
+	for _, hook := range hooks {
+		hook(ctx, failure)
+	}
+}
@@ -0,0 +1,376 @@
+package engine
+
+import (
+	"context"
+	"crypto/sha256"
+	"database/sql"
+	"encoding/hex"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"log/slog"
+	"strings"
+	"sync"
+	"sync/atomic"
+
+	"github.com/google/uuid"
+)
+
+// MaxForEachItems bounds how many items one for_each step may fan out over.
+const MaxForEachItems = 1000
+
+// MaxForEachParallelism bounds how many items of one for_each step run at
+// once. Items run inside the step's single execution slot, so this is the
+// most one step can add to the load MaxConcurrentSteps admits.
+const MaxForEachParallelism = 16
+
+// ForEach runs a step once per element of the array at Items, a dot path into
+// {"case": {...case data, "steps": {id: {"result": ...}}}}. With BatchSize
+// set, the step runs once per batch of up to that many elements instead, and
+// each item is the batch array. At most Parallelism items run at once
+// (default 1). The step completes with {"items": [{"index", "output"}...]} in
+// item order. Completed items are kept in case_step_items until the step
+// completes, so a retry only runs the items that did not.
+type ForEach struct {
+	Items       string `json:"items"`
+	Parallelism int    `json:"parallelism,omitempty"`
+	BatchSize   int    `json:"batch_size,omitempty"`
+}
+
+type forEachItemKey struct{}
+
+type forEachItem struct {
+	value any
+	index int
+}
+
+// WithForEachItem marks ctx as executing the index-th item of a for_each step.
+func WithForEachItem(ctx context.Context, item any, index int) context.Context {
+	return context.WithValue(ctx, forEachItemKey{}, forEachItem{value: item, index: index})
+}
+
+// ForEachItemFromContext returns the current for_each item, if any. Executors
+// that resolve templates expose it as {{item}} and {{item_index}}.
+func ForEachItemFromContext(ctx context.Context) (any, int, bool) {
+	item, ok := ctx.Value(forEachItemKey{}).(forEachItem)
+	if !ok {
+		return nil, 0, false
+	}
+	return item.value, item.index, true
+}
+
+// AddForEachItem sets item and item_index in a template context when ctx is
+// executing a for_each item.
+func AddForEachItem(ctx context.Context, tplCtx map[string]any) {
+	if item, index, ok := ForEachItemFromContext(ctx); ok {
+		tplCtx["item"] = item
+		tplCtx["item_index"] = index
+	}
+}
+
+// forEachUnsupported lists the built-in step types whose executors do not
+// read the current item; for_each on them would run the same call per item.
+var forEachUnsupported = map[string]bool{
+	"human_task":   true,
+	"agent":        true,
+	"agentic":      true,
+	"ai_component": true,
+	"extraction":   true,
+	"plugin":       true,
+	"aggregate":    true,
+	"timer":        true,
+	"delay":        true,
+	"wait_until":   true,
+	"sub_workflow": true,
+}
+
+func validateForEach(step WorkflowStep) error {
+	if step.ForEach == nil {
+		return nil
+	}
+	if strings.TrimSpace(step.ForEach.Items) == "" {
+		return fmt.Errorf("step %s: for_each requires items", step.ID)
+	}
+	if step.ForEach.Parallelism < 0 {
+		return fmt.Errorf("step %s: for_each parallelism cannot be negative", step.ID)
+	}
+	if step.ForEach.Parallelism > MaxForEachParallelism {
+		return fmt.Errorf("step %s: for_each parallelism cannot exceed %d", step.ID, MaxForEachParallelism)
+	}
+	if step.ForEach.BatchSize < 0 {
+		return fmt.Errorf("step %s: for_each batch_size cannot be negative", step.ID)
+	}
+	if forEachUnsupported[step.Type] {
+		return fmt.Errorf("step %s: for_each is not supported on %s steps", step.ID, step.Type)
+	}
+	return nil
+}
+
+type forEachExecutor struct {
+	engine *Engine
+	inner  StepExecutor
+	spec   ForEach
+}
+
+func (f *forEachExecutor) Execute(ctx context.Context, caseID uuid.UUID, stepID string, config json.RawMessage) (*StepResult, error) {
+	items, err := f.engine.loadForEachItems(ctx, caseID, f.spec.Items, f.spec.BatchSize)
+	if err != nil {
+		return nil, err
+	}
+	done, err := f.engine.loadForEachCheckpoints(ctx, caseID, stepID)
+	if err != nil {
+		return nil, err
+	}
+	var tokens atomic.Int64
+	var sensitive atomic.Bool
+	var costMu sync.Mutex
+	var cost float64
+	var artifactsMu sync.Mutex
+	var artifacts []Artifact
+	outputs, err := runForEach(ctx, items, f.spec.Parallelism, func(ctx context.Context, item any, index int) (json.RawMessage, error) {
+		hash := forEachItemHash(item)
+		if cp, ok := done[index]; ok && hash != "" && cp.hash == hash {
+			tokens.Add(int64(cp.tokens))
+			costMu.Lock()
+			cost += cp.cost
+			costMu.Unlock()
+			return cp.output, nil
+		}
+		res, err := f.inner.Execute(WithForEachItem(ctx, item, index), caseID, stepID, config)
+		if errors.Is(err, ErrStepAwaitingReview) {
+			return nil, fmt.Errorf("item %d: for_each steps cannot wait for review", index)
+		}
+		if err != nil {
+			return nil, fmt.Errorf("item %d: %w", index, err)
+		}
+		if res == nil {
+			res = &StepResult{}
+		}
+		tokens.Add(int64(res.Tokens))
+		costMu.Lock()
+		cost += res.Cost
+		costMu.Unlock()
+		if res.Sensitive {
+			sensitive.Store(true)
+		}
+		if len(res.Artifacts) > 0 {
+			artifactsMu.Lock()
+			artifacts = append(artifacts, res.Artifacts...)
+			artifactsMu.Unlock()
+		} else if hash != "" && !res.Sensitive {
+			// Artifacts are only stored once the whole step completes, so
+			// items that emit them run again on retry.
+			cp := forEachCheckpoint{hash: hash, output: res.Output, tokens: res.Tokens, cost: res.Cost}
+			if err := f.engine.saveForEachCheckpoint(ctx, caseID, stepID, index, cp); err != nil {
+				slog.WarnContext(ctx, "for_each item not checkpointed", "case_id", caseID.String(), "step_id", stepID, "item_index", index, "error", err)
+			}
+		}
+		return res.Output, nil
+	})
+	if err != nil {
+		return nil, err
+	}
+	results := make([]map[string]any, len(outputs))
+	for i, out := range outputs {
+		results[i] = map[string]any{"index": i, "output": out}
+	}
+	payload, err := json.Marshal(map[string]any{"items": results})
+	if err != nil {
+		return nil, fmt.Errorf("marshal for_each results: %w", err)
+	}
+	return &StepResult{Output: payload, Tokens: int(tokens.Load()), Cost: cost, Artifacts: artifacts, Sensitive: sensitive.Load()}, nil
+}
+
+type forEachCheckpoint struct {
+	hash   string
+	output json.RawMessage
+	tokens int
+	cost   float64
+}
+
+// forEachItemHash identifies an item's value, so a retry only reuses a
+// checkpoint when the item it ran for is unchanged. Items that cannot be
+// encoded get "" and are never checkpointed.
+func forEachItemHash(item any) string {
+	raw, err := json.Marshal(item)
+	if err != nil {
+		return ""
+	}
+	sum := sha256.Sum256(raw)
+	return hex.EncodeToString(sum[:])
+}
+
+func (e *Engine) loadForEachCheckpoints(ctx context.Context, caseID uuid.UUID, stepID string) (map[int]forEachCheckpoint, error) {
+	rows, err := e.db.QueryContext(ctx, `
+SELECT item_index, item_hash, output, tokens, cost::float8
+FROM case_step_items
+WHERE case_id = $1 AND step_id = $2
+`, caseID, stepID)
+	if err != nil {
+		return nil, fmt.Errorf("load for_each checkpoints: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	done := map[int]forEachCheckpoint{}
+	for rows.Next() {
+		var (
+			index  int
+			cp     forEachCheckpoint
+			output []byte
+		)
+		if err := rows.Scan(&index, &cp.hash, &output, &cp.tokens, &cp.cost); err != nil {
+			return nil, fmt.Errorf("scan for_each checkpoint: %w", err)
+		}
+		if len(output) > 0 {
+			cp.output = output
+		}
+		done[index] = cp
+	}
+	if err := rows.Err(); err != nil {
+		return nil, fmt.Errorf("iterate for_each checkpoints: %w", err)
+	}
+	return done, nil
+}
+
+// saveForEachCheckpoint records a completed item. The output is redacted as
+// it would be in the step result.
+func (e *Engine) saveForEachCheckpoint(ctx context.Context, caseID uuid.UUID, stepID string, index int, cp forEachCheckpoint) error {
+	var output sql.NullString
+	if len(cp.output) > 0 {
+		output = sql.NullString{String: string(e.activeRedactor().RedactJSON(cp.output)), Valid: true}
+	}
+	_, err := e.db.ExecContext(ctx, `
+INSERT INTO case_step_items (case_id, step_id, item_index, item_hash, output, tokens, cost)
+VALUES ($1, $2, $3, $4, $5::jsonb, $6, $7)
+ON CONFLICT (case_id, step_id, item_index) DO UPDATE
+SET item_hash = EXCLUDED.item_hash, output = EXCLUDED.output, tokens = EXCLUDED.tokens, cost = EXCLUDED.cost
+`, caseID, stepID, index, cp.hash, output, cp.tokens, cp.cost)
+	return err
+}
+
+// runForEach calls run for every item with at most parallelism in flight and
+// returns outputs in item order. The first failure cancels the remaining
+// items and is returned.
+func runForEach(ctx context.Context, items []any, parallelism int, run func(context.Context, any, int) (json.RawMessage, error)) ([]json.RawMessage, error) {
+	if parallelism <= 0 {
+		parallelism = 1
+	}
+	// Versions published before the limit may ask for more.
+	parallelism = min(parallelism, MaxForEachParallelism)
+	ctx, cancel := context.WithCancel(ctx)
+	defer cancel()
+
+	outputs := make([]json.RawMessage, len(items))
+	sem := make(chan struct{}, parallelism)
+	var (
+		wg       sync.WaitGroup
+		errOnce  sync.Once
+		firstErr error
+	)
+	for i, item := range items {
+		select {
+		case sem <- struct{}{}:
+		case <-ctx.Done():
+		}
+		if ctx.Err() != nil {
+			break
+		}
+		wg.Add(1)
+		go func(i int, item any) {
+			defer wg.Done()
+			defer func() { <-sem }()
+			out, err := run(ctx, item, i)
+			if err != nil {
+				errOnce.Do(func() {
+					firstErr = err
+					cancel()
+				})
+				return
+			}
+			outputs[i] = out
+		}(i, item)
+	}
+	wg.Wait()
+	if firstErr != nil {
+		return nil, firstErr
+	}
+	if err := ctx.Err(); err != nil {
+		return nil, err
+	}
+	return outputs, nil
+}
+
+func (e *Engine) loadForEachItems(ctx context.Context, caseID uuid.UUID, path string, batchSize int) ([]any, error) {
+	var dataRaw []byte
+	if err := e.db.QueryRowContext(ctx, `SELECT data FROM cases WHERE id = $1`, caseID).Scan(&dataRaw); err != nil {
+		return nil, fmt.Errorf("load case data for for_each: %w", err)
+	}
+	caseMap := map[string]any{}
+	if len(dataRaw) > 0 {
+		if err := json.Unmarshal(dataRaw, &caseMap); err != nil {
+			return nil, fmt.Errorf("decode case data for for_each: %w", err)
+		}
+	}
+
+	steps := map[string]any{}
+	rows, err := e.db.QueryContext(ctx, `
+SELECT step_id, COALESCE(result, '{}'::jsonb)
+FROM case_steps
+WHERE case_id = $1
+`, caseID)
+	if err != nil {
+		return nil, fmt.Errorf("load step results for for_each: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	for rows.Next() {
+		var stepID string
+		var raw []byte
+		if err := rows.Scan(&stepID, &raw); err != nil {
+			return nil, fmt.Errorf("scan step result for for_each: %w", err)
+		}
+		var result any
+		if err := json.Unmarshal(raw, &result); err != nil {
+			return nil, fmt.Errorf("decode step result %s for for_each: %w", stepID, err)
+		}
+		steps[stepID] = map[string]any{"result": result}
+	}
+	if err := rows.Err(); err != nil {
+		return nil, fmt.Errorf("iterate step results for for_each: %w", err)
+	}
+	caseMap["steps"] = steps
+
+	return forEachItems(map[string]any{"case": caseMap}, path, batchSize)
+}
+
+// forEachItems resolves path against root and requires an array. A missing
+// value is an empty array. With batchSize > 0 the elements are grouped into
+// batches. At most MaxForEachItems elements, or batches, are allowed.
+func forEachItems(root map[string]any, path string, batchSize int) ([]any, error) {
+	current, ok := lookupDotPath(root, path)
+	if !ok || current == nil {
+		return []any{}, nil
+	}
+	items, ok := current.([]any)
+	if !ok {
+		return nil, fmt.Errorf("for_each items %q is not an array", path)
+	}
+	if batchSize > 0 {
+		items = batchItems(items, batchSize)
+		if len(items) > MaxForEachItems {
+			return nil, fmt.Errorf("for_each items %q makes %d batches of %d, limit is %d", path, len(items), batchSize, MaxForEachItems)
+		}
+		return items, nil
+	}
+	if len(items) > MaxForEachItems {
+		return nil, fmt.Errorf("for_each items %q has %d elements, limit is %d", path, len(items), MaxForEachItems)
+	}
+	return items, nil
+}
+
+func batchItems(items []any, size int) []any {
+	batches := make([]any, 0, (len(items)+size-1)/size)
+	for start := 0; start < len(items); start += size {
+		end := min(start+size, len(items))
+		batches = append(batches, items[start:end:end])
+	}
+	return batches
+}
@@ -0,0 +1,249 @@
+package engine
+
+import (
+	"context"
+	"sort"
+	"sync"
+	"sync/atomic"
+	"time"
+
+	"github.com/neural-chilli/aceryx/internal/observability"
+)
+
+// Lane is an execution priority. A case's lane is fixed when it is created
+// and every step of the case runs in it.
+type Lane string
+
+const (
+	// LaneInteractive is for runs someone is waiting on, such as cases
+	// started from the UI and designer replays.
+	LaneInteractive Lane = "interactive"
+	// LaneStandard is for cases started by webhooks, channels and API
+	// clients.
+	LaneStandard Lane = "standard"
+	// LaneBatch is for bulk imports and scheduled backfills.
+	LaneBatch Lane = "batch"
+)
+
+// Lanes lists the lanes from highest to lowest priority.
+var Lanes = []Lane{LaneInteractive, LaneStandard, LaneBatch}
+
+const DefaultLaneMaxWait = 30 * time.Second
+
+// ParseLane accepts a lane name; empty is LaneStandard.
+func ParseLane(raw string) (Lane, bool) {
+	switch Lane(raw) {
+	case "", LaneStandard:
+		return LaneStandard, true
+	case LaneInteractive, LaneBatch:
+		return Lane(raw), true
+	}
+	return "", false
+}
+
+type laneKey struct{}
+
+// WithLane sets the lane for cases created and steps dispatched with ctx.
+func WithLane(ctx context.Context, lane Lane) context.Context {
+	return context.WithValue(ctx, laneKey{}, lane)
+}
+
+// LaneFromContext returns the lane set by WithLane, or LaneStandard.
+func LaneFromContext(ctx context.Context) Lane {
+	if lane, ok := ctx.Value(laneKey{}).(Lane); ok && lane != "" {
+		return lane
+	}
+	return LaneStandard
+}
+
+// LaneConfig splits the step workers between lanes. Reserved workers only
+// run their own lane; the rest are shared and taken by the highest-priority
+// lane with work queued. Limit caps a lane's workers, zero meaning no cap.
+// A queued execution that has waited longer than MaxWait runs before
+// higher-priority work, so a busy lane cannot starve the others.
+type LaneConfig struct {
+	Reserved map[Lane]int
+	Limit    map[Lane]int
+	MaxWait  time.Duration
+}
+
+// defaultLaneConfig reserves a fifth of the workers for interactive runs and
+// lets batch work use at most half.
+func defaultLaneConfig(cfg LaneConfig, capacity int) LaneConfig {
+	out := LaneConfig{Reserved: map[Lane]int{}, Limit: map[Lane]int{}, MaxWait: cfg.MaxWait}
+	if cfg.Reserved == nil {
+		cfg.Reserved = map[Lane]int{LaneInteractive: max(1, capacity/5)}
+	}
+	if cfg.Limit == nil {
+		cfg.Limit = map[Lane]int{LaneBatch: max(1, capacity/2)}
+	}
+	remaining := capacity
+	for _, lane := range Lanes {
+		reserved := min(max(cfg.Reserved[lane], 0), remaining)
+		remaining -= reserved
+		out.Reserved[lane] = reserved
+		if limit := cfg.Limit[lane]; limit > 0 {
+			out.Limit[lane] = max(limit, reserved)
+		}
+	}
+	if out.MaxWait <= 0 {
+		out.MaxWait = DefaultLaneMaxWait
+	}
+	return out
+}
+
+// LaneStats is one lane's share of the step workers.
+type LaneStats struct {
+	Running  int `json:"running"`
+	Queued   int `json:"queued"`
+	Reserved int `json:"reserved"`
+	Limit    int `json:"limit,omitempty"`
+}
+
+// LanePool runs step executions on a bounded set of workers shared between
+// lanes; see LaneConfig. Submit never blocks: executions wait in their
+// lane's queue until a worker is free.
+type LanePool struct {
+	mu       sync.Mutex
+	capacity int
+	cfg      LaneConfig
+	running  map[Lane]int
+	queues   map[Lane][]laneTask
+	now      func() time.Time
+	wg       sync.WaitGroup
+	n        atomic.Int64
+}
+
+type laneTask struct {
+	fn       func()
+	enqueued time.Time
+}
+
+func NewLanePool(capacity int, cfg LaneConfig) *LanePool {
+	if capacity <= 0 {
+		capacity = 10
+	}
+	return &LanePool{
+		capacity: capacity,
+		cfg:      defaultLaneConfig(cfg, capacity),
+		running:  map[Lane]int{},
+		queues:   map[Lane][]laneTask{},
+		now:      time.Now,
+	}
+}
+
+func (p *LanePool) Submit(lane Lane, fn func()) {
+	if _, ok := ParseLane(string(lane)); !ok {
+		lane = LaneStandard
+	}
+	p.wg.Add(1)
+	p.mu.Lock()
+	p.queues[lane] = append(p.queues[lane], laneTask{fn: fn, enqueued: p.now()})
+	p.dispatchLocked()
+	p.mu.Unlock()
+}
+
+// dispatchLocked starts queued executions while workers are free.
+func (p *LanePool) dispatchLocked() {
+	for {
+		lane, ok := p.nextLocked()
+		if !ok {
+			break
+		}
+		task := p.queues[lane][0]
+		p.queues[lane] = p.queues[lane][1:]
+		p.running[lane]++
+		p.n.Add(1)
+		go p.run(lane, task.fn)
+	}
+	for _, lane := range Lanes {
+		observability.ExecutionLaneQueued.WithLabelValues(string(lane)).Set(float64(len(p.queues[lane])))
+		observability.ExecutionLaneRunning.WithLabelValues(string(lane)).Set(float64(p.running[lane]))
+	}
+}
+
+func (p *LanePool) run(lane Lane, fn func()) {
+	defer func() {
+		p.n.Add(-1)
+		p.mu.Lock()
+		p.running[lane]--
+		p.dispatchLocked()
+		p.mu.Unlock()
+		p.wg.Done()
+	}()
+	fn()
+}
+
+// nextLocked picks the lane whose queued execution starts next: the longest
+// waiting one past MaxWait, otherwise the highest-priority lane that may
+// start one.
+func (p *LanePool) nextLocked() (Lane, bool) {
+	candidates := make([]Lane, 0, len(Lanes))
+	for _, lane := range Lanes {
+		if len(p.queues[lane]) > 0 && p.canStartLocked(lane) {
+			candidates = append(candidates, lane)
+		}
+	}
+	if len(candidates) == 0 {
+		return "", false
+	}
+	now := p.now()
+	starved := make([]Lane, 0, len(candidates))
+	for _, lane := range candidates {
+		if now.Sub(p.queues[lane][0].enqueued) > p.cfg.MaxWait {
+			starved = append(starved, lane)
+		}
+	}
+	if len(starved) > 0 {
+		sort.SliceStable(starved, func(i, j int) bool {
+			return p.queues[starved[i]][0].enqueued.Before(p.queues[starved[j]][0].enqueued)
+		})
+		return starved[0], true
+	}
+	return candidates[0], true
+}
+
+func (p *LanePool) canStartLocked(lane Lane) bool {
+	if limit := p.cfg.Limit[lane]; limit > 0 && p.running[lane] >= limit {
+		return false
+	}
+	if p.running[lane] < p.cfg.Reserved[lane] {
+		return true
+	}
+	shared := p.capacity
+	sharedUsed := 0
+	for _, l := range Lanes {
+		shared -= p.cfg.Reserved[l]
+		sharedUsed += max(p.running[l]-p.cfg.Reserved[l], 0)
+	}
+	return sharedUsed < shared
+}
+
+// Stats reports each lane's running and queued executions.
+func (p *LanePool) Stats() map[Lane]LaneStats {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	out := make(map[Lane]LaneStats, len(Lanes))
+	for _, lane := range Lanes {
+		out[lane] = LaneStats{
+			Running:  p.running[lane],
+			Queued:   len(p.queues[lane]),
+			Reserved: p.cfg.Reserved[lane],
+			Limit:    p.cfg.Limit[lane],
+		}
+	}
+	return out
+}
+
+// Wait blocks until every submitted execution, queued or running, is done.
+func (p *LanePool) Wait() {
+	p.wg.Wait()
+}
+
+func (p *LanePool) Active() int {
+	return int(p.n.Load())
+}
+
+func (p *LanePool) Capacity() int {
+	return p.capacity
+}
@@ -0,0 +1,165 @@
+package engine
+
+import (
+	"context"
+	"sync/atomic"
+	"testing"
+	"time"
+)
+
+// laneRecorder submits executions that report their lane when they start and
+// then hold their worker until release is closed.
+type laneRecorder struct {
+	started chan Lane
+	release chan struct{}
+}
+
+func newLaneRecorder() *laneRecorder {
+	return &laneRecorder{started: make(chan Lane, 16), release: make(chan struct{})}
+}
+
+func (r *laneRecorder) submit(p *LanePool, lane Lane) {
+	p.Submit(lane, func() {
+		r.started <- lane
+		<-r.release
+	})
+}
+
+func (r *laneRecorder) next(t *testing.T) Lane {
+	t.Helper()
+	select {
+	case lane := <-r.started:
+		return lane
+	case <-time.After(2 * time.Second):
+		t.Fatal("no execution started")
+		return ""
+	}
+}
+
+func (r *laneRecorder) none(t *testing.T) {
+	t.Helper()
+	select {
+	case lane := <-r.started:
+		t.Fatalf("unexpected %s execution started", lane)
+	case <-time.After(50 * time.Millisecond):
+	}
+}
+
+func noLaneDefaults() LaneConfig {
+	return LaneConfig{Reserved: map[Lane]int{}, Limit: map[Lane]int{}}
+}
+
+func TestLanePoolRunsHigherPriorityFirst(t *testing.T) {
+	p := NewLanePool(1, noLaneDefaults())
+	block := newLaneRecorder()
+	block.submit(p, LaneStandard)
+	block.next(t)
+
+	rec := newLaneRecorder()
+	close(rec.release)
+	rec.submit(p, LaneBatch)
+	rec.submit(p, LaneStandard)
+	rec.submit(p, LaneInteractive)
+	close(block.release)
+
+	for _, want := range []Lane{LaneInteractive, LaneStandard, LaneBatch} {
+		if got := rec.next(t); got != want {
+			t.Fatalf("expected %s next, got %s", want, got)
+		}
+	}
+	p.Wait()
+}
+
+func TestLanePoolReservedWorkersStayFree(t *testing.T) {
+	p := NewLanePool(2, LaneConfig{Reserved: map[Lane]int{LaneInteractive: 1}, Limit: map[Lane]int{}})
+	rec := newLaneRecorder()
+	for i := 0; i < 3; i++ {
+		rec.submit(p, LaneStandard)
+	}
+	rec.next(t)
+	rec.none(t)
+	if stats := p.Stats()[LaneStandard]; stats.Running != 1 || stats.Queued != 2 {
+		t.Fatalf("expected 1 running and 2 queued standard executions, got %+v", stats)
+	}
+
+	rec.submit(p, LaneInteractive)
+	if got := rec.next(t); got != LaneInteractive {
+		t.Fatalf("expected interactive execution on the reserved worker, got %s", got)
+	}
+	close(rec.release)
+	p.Wait()
+}
+
+func TestLanePoolBatchLimit(t *testing.T) {
+	p := NewLanePool(4, LaneConfig{Reserved: map[Lane]int{}, Limit: map[Lane]int{LaneBatch: 1}})
+	rec := newLaneRecorder()
+	rec.submit(p, LaneBatch)
+	rec.submit(p, LaneBatch)
+	rec.next(t)
+	rec.none(t)
+
+	rec.submit(p, LaneStandard)
+	if got := rec.next(t); got != LaneStandard {
+		t.Fatalf("expected standard execution beside capped batch, got %s", got)
+	}
+	if stats := p.Stats()[LaneBatch]; stats.Running != 1 || stats.Queued != 1 || stats.Limit != 1 {
+		t.Fatalf("unexpected batch stats %+v", stats)
+	}
+	close(rec.release)
+	p.Wait()
+}
+
+func TestLanePoolAgesStarvedExecutions(t *testing.T) {
+	p := NewLanePool(1, LaneConfig{Reserved: map[Lane]int{}, Limit: map[Lane]int{}, MaxWait: time.Second})
+	var clock atomic.Int64
+	clock.Store(time.Unix(1_700_000_000, 0).UnixNano())
+	p.now = func() time.Time { return time.Unix(0, clock.Load()) }
+
+	block := newLaneRecorder()
+	block.submit(p, LaneStandard)
+	block.next(t)
+
+	rec := newLaneRecorder()
+	close(rec.release)
+	rec.submit(p, LaneBatch)
+	clock.Add(int64(2 * time.Second))
+	rec.submit(p, LaneInteractive)
+	close(block.release)
+
+	if got := rec.next(t); got != LaneBatch {
+		t.Fatalf("expected starved batch execution first, got %s", got)
+	}
+	if got := rec.next(t); got != LaneInteractive {
+		t.Fatalf("expected interactive execution second, got %s", got)
+	}
+	p.Wait()
+}
+
+func TestDefaultLaneConfig(t *testing.T) {
+	cfg := defaultLaneConfig(LaneConfig{}, 10)
+	if cfg.Reserved[LaneInteractive] != 2 || cfg.Limit[LaneBatch] != 5 || cfg.MaxWait != DefaultLaneMaxWait {
+		t.Fatalf("unexpected defaults %+v", cfg)
+	}
+	cfg = defaultLaneConfig(LaneConfig{Reserved: map[Lane]int{LaneInteractive: 8, LaneStandard: 8}}, 10)
+	if cfg.Reserved[LaneInteractive] != 8 || cfg.Reserved[LaneStandard] != 2 {
+		t.Fatalf("expected reservations clamped to capacity, got %+v", cfg.Reserved)
+	}
+}
+
+func TestParseLaneAndContext(t *testing.T) {
+	cases := map[string]Lane{"": LaneStandard, "standard": LaneStandard, "interactive": LaneInteractive, "batch": LaneBatch}
+	for raw, want := range cases {
+		if got, ok := ParseLane(raw); !ok || got != want {
+			t.Fatalf("ParseLane(%q) = %q, %v", raw, got, ok)
+		}
+	}
+	if _, ok := ParseLane("urgent"); ok {
+		t.Fatal("expected unknown lane to be rejected")
+	}
+	if got := LaneFromContext(context.Background()); got != LaneStandard {
+		t.Fatalf("expected standard by default, got %s", got)
+	}
+	if got := LaneFromContext(WithLane(context.Background(), LaneBatch)); got != LaneBatch {
+		t.Fatalf("expected batch from context, got %s", got)
+	}
+}
@@ -0,0 +1,18 @@
+package engine
+
+import "context"
+
+type mockProfileKey struct{}
+
+// WithMockProfile sets the mock profile for cases created and steps
+// dispatched with ctx. Tool calls made under a mock profile are answered
+// from it instead of the real tools.
+func WithMockProfile(ctx context.Context, profile string) context.Context {
+	return context.WithValue(ctx, mockProfileKey{}, profile)
+}
+
+// MockProfileFromContext returns the profile set by WithMockProfile, or "".
+func MockProfileFromContext(ctx context.Context) string {
+	profile, _ := ctx.Value(mockProfileKey{}).(string)
+	return profile
+}
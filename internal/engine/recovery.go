@@ -4,37 +4,99 @@ import (
 	"context"
 	"errors"
 	"fmt"
+	"log/slog"
+	"time"
 
 	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/observability"
 )
 
+// DefaultRecoveryInterval is how often the cluster leader looks for steps
+// left active by replicas that have gone, when SetOwner gives no staleness.
+const DefaultRecoveryInterval = 30 * time.Second
+
+// Recover takes over active steps whose owning process has gone: steps
+// activated before owners were recorded, by a replica that shut down, or by
+// one that has missed heartbeats for the staleness given to SetOwner. Steps
+// are claimed one case at a time, so replicas recovering together never run
+// a step twice, and an advisory lock keeps them from scanning at once; when
+// another replica holds it, Recover returns without doing anything.
 func (e *Engine) Recover(ctx context.Context) error {
+	conn, err := e.db.Conn(ctx)
+	if err != nil {
+		return fmt.Errorf("open recovery connection: %w", err)
+	}
+	defer func() { _ = conn.Close() }()
+	var locked bool
+	if err := conn.QueryRowContext(ctx, `SELECT pg_try_advisory_lock(hashtextextended('aceryx.step_recovery', 0))`).Scan(&locked); err != nil {
+		return fmt.Errorf("take recovery lock: %w", err)
+	}
+	if !locked {
+		return nil
+	}
+	defer func() {
+		_, _ = conn.ExecContext(context.WithoutCancel(ctx), `SELECT pg_advisory_unlock(hashtextextended('aceryx.step_recovery', 0))`)
+	}()
+
+	owner, staleAfter := e.stepOwner()
 	rows, err := e.db.QueryContext(ctx, `
 SELECT DISTINCT cs.case_id
 FROM case_steps cs
 JOIN cases c ON c.id = cs.case_id
 WHERE cs.state = 'active' AND c.status != 'cancelled'
-`)
+  AND ($1::uuid IS NULL OR cs.owner_instance IS DISTINCT FROM $1)
+  AND (cs.owned_at IS NULL OR cs.owned_at < now() - make_interval(secs => $2))
+  AND NOT EXISTS (
+      SELECT 1 FROM cluster_replicas r
+      WHERE r.instance_id = cs.owner_instance AND r.heartbeat_at >= now() - make_interval(secs => $2)
+  )
+`, uuid.NullUUID{UUID: owner, Valid: owner != uuid.Nil}, staleAfter.Seconds())
 	if err != nil {
 		return fmt.Errorf("query active cases for recovery: %w", err)
 	}
 	defer func() { _ = rows.Close() }()
 
+	var caseIDs []uuid.UUID
 	for rows.Next() {
 		var caseID uuid.UUID
 		if err := rows.Scan(&caseID); err != nil {
 			return fmt.Errorf("scan recovery case id: %w", err)
 		}
-		if err := e.recoverCase(ctx, caseID); err != nil {
-			return err
-		}
+		caseIDs = append(caseIDs, caseID)
 	}
 	if err := rows.Err(); err != nil {
 		return fmt.Errorf("iterate recovery cases: %w", err)
 	}
+	for _, caseID := range caseIDs {
+		if err := e.recoverCase(ctx, caseID); err != nil {
+			return err
+		}
+	}
 	return nil
 }
 
+// StartRecoveryMonitor runs Recover periodically until ctx is done, taking
+// over the steps of replicas that stop heartbeating. It is meant to run on
+// the cluster leader only.
+func (e *Engine) StartRecoveryMonitor(ctx context.Context) {
+	_, interval := e.stepOwner()
+	if interval <= 0 {
+		interval = DefaultRecoveryInterval
+	}
+	ticker := time.NewTicker(interval)
+	defer ticker.Stop()
+	for {
+		select {
+		case <-ticker.C:
+			if err := e.Recover(ctx); err != nil && ctx.Err() == nil {
+				slog.WarnContext(ctx, "step recovery sweep failed", "error", err)
+			}
+		case <-ctx.Done():
+			return
+		}
+	}
+}
+
 func (e *Engine) recoverCase(ctx context.Context, caseID uuid.UUID) error {
 	tx, err := e.db.BeginTx(ctx, nil)
 	if err != nil {
@@ -42,34 +104,57 @@ func (e *Engine) recoverCase(ctx context.Context, caseID uuid.UUID) error {
 	}
 	defer func() { _ = tx.Rollback() }()
 
-	ast, err := loadWorkflowASTTx(ctx, tx, caseID)
+	ast, err := e.loadWorkflowASTTx(ctx, tx, caseID)
 	if err != nil {
 		return err
 	}
 	stepsByID := stepMap(ast)
+	var lane Lane
+	var tenantID uuid.UUID
+	var mockProfile string
+	if err := tx.QueryRowContext(ctx, `SELECT execution_lane, tenant_id, COALESCE(mock_profile, '') FROM cases WHERE id = $1`, caseID).Scan(&lane, &tenantID, &mockProfile); err != nil {
+		return fmt.Errorf("load case lane for recovery: %w", err)
+	}
+	ctx = WithLane(ctx, lane)
+	ctx = WithMockProfile(ctx, mockProfile)
+	ctx = observability.WithTenantID(ctx, tenantID)
 
+	// Claim the orphaned steps for this process, so no other replica
+	// recovers them too.
+	owner, staleAfter := e.stepOwner()
 	rows, err := tx.QueryContext(ctx, `
-SELECT step_id, result
-FROM case_steps
-WHERE case_id = $1 AND state = 'active'
-`, caseID)
+UPDATE case_steps cs
+SET owner_instance = $2, owned_at = now()
+WHERE cs.case_id = $1 AND cs.state = 'active'
+  AND ($2::uuid IS NULL OR cs.owner_instance IS DISTINCT FROM $2)
+  AND (cs.owned_at IS NULL OR cs.owned_at < now() - make_interval(secs => $3))
+  AND NOT EXISTS (
+      SELECT 1 FROM cluster_replicas r
+      WHERE r.instance_id = cs.owner_instance AND r.heartbeat_at >= now() - make_interval(secs => $3)
+  )
+RETURNING cs.step_id, cs.result, cs.events -> -1
+`, caseID, uuid.NullUUID{UUID: owner, Valid: owner != uuid.Nil}, staleAfter.Seconds())
 	if err != nil {
 		return fmt.Errorf("query active steps for recovery: %w", err)
 	}
 	defer func() { _ = rows.Close() }()
 
 	type recStep struct {
-		stepID string
-		result []byte
+		stepID    string
+		result    []byte
+		lastEvent []byte
 	}
 	active := make([]recStep, 0)
 	for rows.Next() {
 		var s recStep
-		if err := rows.Scan(&s.stepID, &s.result); err != nil {
+		if err := rows.Scan(&s.stepID, &s.result, &s.lastEvent); err != nil {
 			return fmt.Errorf("scan active step for recovery: %w", err)
 		}
 		active = append(active, s)
 	}
+	if err := rows.Err(); err != nil {
+		return fmt.Errorf("iterate active steps for recovery: %w", err)
+	}
 
 	if err := tx.Commit(); err != nil {
 		return fmt.Errorf("commit recover case preload: %w", err)
@@ -80,10 +165,15 @@ WHERE case_id = $1 AND state = 'active'
 		if !ok {
 			continue
 		}
+		if step.Type != "human_task" && abortedBeforeStart(s.lastEvent) {
+			// Activated during a shutdown drain and never run; safe to start.
+			e.dispatchStep(ctx, caseID, step)
+			continue
+		}
 		switch step.Type {
 		case "integration":
 			if idempotentStep(step) {
-				e.dispatchStep(caseID, step)
+				e.dispatchStep(ctx, caseID, step)
 			} else {
 				_ = e.failStep(ctx, caseID, step.ID, errors.New("recovery failed non-idempotent integration step"))
 			}
@@ -91,16 +181,16 @@ WHERE case_id = $1 AND state = 'active'
 			if len(s.result) > 0 {
 				_ = e.completeStep(ctx, caseID, step.ID, &StepResult{Output: s.result})
 			} else {
-				e.dispatchStep(caseID, step)
+				e.dispatchStep(ctx, caseID, step)
 			}
 		case "human_task":
 			// no action, remains active
 		case "rule":
-			e.dispatchStep(caseID, step)
+			e.dispatchStep(ctx, caseID, step)
 		case "timer":
-			e.dispatchStep(caseID, step)
+			e.dispatchStep(ctx, caseID, step)
 		default:
-			e.dispatchStep(caseID, step)
+			e.dispatchStep(ctx, caseID, step)
 		}
 	}
 
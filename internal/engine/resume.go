@@ -0,0 +1,97 @@
+package engine
+
+import (
+	"context"
+	"errors"
+	"fmt"
+
+	"github.com/google/uuid"
+)
+
+var (
+	ErrNothingToResume = errors.New("engine: no failed steps to resume")
+	ErrCaseCancelled   = errors.New("engine: case is cancelled")
+)
+
+// ResumeCase re-runs a case's failed steps, or only those in stepIDs when
+// given. Completed steps keep their results and are not executed again, so
+// the workflow continues from the last successful step. It returns the IDs of
+// the steps that were reset.
+func (e *Engine) ResumeCase(ctx context.Context, caseID, actorID uuid.UUID, stepIDs []string) ([]string, error) {
+	tx, err := e.db.BeginTx(ctx, nil)
+	if err != nil {
+		return nil, fmt.Errorf("begin resume case tx: %w", err)
+	}
+	defer func() { _ = e.auditSvc.RollbackTx(tx) }()
+
+	var caseStatus string
+	if err := tx.QueryRowContext(ctx, `SELECT status FROM cases WHERE id = $1 FOR UPDATE`, caseID).Scan(&caseStatus); err != nil {
+		return nil, fmt.Errorf("lock case for resume: %w", err)
+	}
+	if caseStatus == "cancelled" {
+		return nil, ErrCaseCancelled
+	}
+
+	rows, err := tx.QueryContext(ctx, `
+SELECT step_id
+FROM case_steps
+WHERE case_id = $1 AND state = 'failed'
+ORDER BY step_id
+FOR UPDATE
+`, caseID)
+	if err != nil {
+		return nil, fmt.Errorf("query failed steps: %w", err)
+	}
+	wanted := make(map[string]bool, len(stepIDs))
+	for _, id := range stepIDs {
+		wanted[id] = true
+	}
+	resumed := make([]string, 0)
+	for rows.Next() {
+		var stepID string
+		if err := rows.Scan(&stepID); err != nil {
+			_ = rows.Close()
+			return nil, fmt.Errorf("scan failed step: %w", err)
+		}
+		if len(wanted) == 0 || wanted[stepID] {
+			resumed = append(resumed, stepID)
+		}
+	}
+	if err := rows.Close(); err != nil {
+		return nil, fmt.Errorf("close failed steps: %w", err)
+	}
+
+	for _, stepID := range resumed {
+		if _, err := tx.ExecContext(ctx, `
+UPDATE case_steps
+SET
+    state = 'pending',
+    started_at = NULL,
+    completed_at = NULL,
+    result = NULL,
+    error = NULL,
+    retry_count = 0,
+    events = COALESCE(events, '[]'::jsonb) || jsonb_build_array(
+        jsonb_build_object('type', 'resumed', 'previous_error', error, 'by', $3::text, 'at', now())
+    )
+WHERE case_id = $1 AND step_id = $2 AND state = 'failed'
+`, caseID, stepID, actorID.String()); err != nil {
+			return nil, fmt.Errorf("reset failed step %s: %w", stepID, err)
+		}
+	}
+	if len(resumed) == 0 {
+		return nil, ErrNothingToResume
+	}
+
+	if _, err := tx.ExecContext(ctx, `UPDATE cases SET updated_at = now() WHERE id = $1`, caseID); err != nil {
+		return nil, fmt.Errorf("touch case for resume: %w", err)
+	}
+	if err := e.auditSvc.RecordCaseEventTx(ctx, tx, caseID, "", "case", actorID, "human", "resumed", map[string]any{"steps": resumed}); err != nil {
+		return nil, err
+	}
+	if err := e.auditSvc.CommitTx(tx); err != nil {
+		return nil, fmt.Errorf("commit resume case: %w", err)
+	}
+	e.triggerEvaluation(caseID)
+	return resumed, nil
+}
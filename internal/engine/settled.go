@@ -0,0 +1,53 @@
+package engine
+
+import (
+	"context"
+
+	"github.com/google/uuid"
+)
+
+// SettledHook is told when a case's workflow can make no further progress on
+// its own: no step is ready or active after an evaluation, or the case was
+// cancelled. failed reports whether any step failed or the case was
+// cancelled. Hooks may be called more than once for the same case and must
+// be idempotent.
+type SettledHook func(ctx context.Context, caseID uuid.UUID, failed bool)
+
+func (e *Engine) AddSettledHook(hook SettledHook) {
+	if hook == nil {
+		return
+	}
+	e.mu.Lock()
+	defer e.mu.Unlock()
+	e.settledHooks = append(e.settledHooks, hook)
+}
+
+func (e *Engine) notifySettled(ctx context.Context, caseID uuid.UUID, failed bool) {
+	e.mu.RLock()
+	hooks := append([]SettledHook(nil), e.settledHooks...)
+	e.mu.RUnlock()
+	for _, hook := range hooks {
+		hook(ctx, caseID, failed)
+	}
+}
+
+// settledState reports whether states, after transitions are applied, leave
+// nothing ready or active, and whether any step ended in failure.
+func settledState(states map[string]StepState, transitions []Transition) (settled bool, failed bool) {
+	current := make(map[string]string, len(states))
+	for stepID, st := range states {
+		current[stepID] = st.State
+	}
+	for _, tr := range transitions {
+		current[tr.StepID] = tr.To
+	}
+	for _, state := range current {
+		switch state {
+		case StateReady, StateActive:
+			return false, false
+		case StateFailed:
+			failed = true
+		}
+	}
+	return true, failed
+}
@@ -0,0 +1,149 @@
+package engine
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"log/slog"
+	"sort"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+// ErrDrainTimeout is returned by Shutdown when step executions were still
+// running at the drain deadline and had to be aborted.
+var ErrDrainTimeout = errors.New("engine drain timed out")
+
+// InFlightExecution is a step execution currently running on the worker pool.
+type InFlightExecution struct {
+	CaseID    uuid.UUID `json:"case_id"`
+	StepID    string    `json:"step_id"`
+	StepType  string    `json:"step_type"`
+	StartedAt time.Time `json:"started_at"`
+}
+
+type inflightKey struct {
+	caseID uuid.UUID
+	stepID string
+}
+
+// Draining reports whether Shutdown has been called. A draining engine does
+// not start new step executions.
+func (e *Engine) Draining() bool {
+	return e.draining.Load()
+}
+
+// InFlight lists running step executions, oldest first.
+func (e *Engine) InFlight() []InFlightExecution {
+	e.inflightMu.Lock()
+	out := make([]InFlightExecution, 0, len(e.inflight))
+	for _, exec := range e.inflight {
+		out = append(out, exec)
+	}
+	e.inflightMu.Unlock()
+	sort.Slice(out, func(i, j int) bool { return out[i].StartedAt.Before(out[j].StartedAt) })
+	return out
+}
+
+// trackExecution registers a running step execution until the returned
+// func is called. cancel aborts it when its case is cancelled.
+func (e *Engine) trackExecution(caseID uuid.UUID, step WorkflowStep, cancel context.CancelCauseFunc) func() {
+	key := inflightKey{caseID: caseID, stepID: step.ID}
+	e.inflightMu.Lock()
+	e.inflight[key] = InFlightExecution{CaseID: caseID, StepID: step.ID, StepType: step.Type, StartedAt: time.Now().UTC()}
+	e.inflightCancels[key] = cancel
+	e.inflightMu.Unlock()
+	return func() {
+		e.inflightMu.Lock()
+		delete(e.inflight, key)
+		delete(e.inflightCancels, key)
+		e.inflightMu.Unlock()
+	}
+}
+
+// Shutdown stops the engine from starting new step executions and waits for
+// running ones until ctx is done. Executions still running then are
+// cancelled and recorded as aborted. Aborted and never-started steps stay
+// active, so Recover picks them up on the next start.
+func (e *Engine) Shutdown(ctx context.Context) error {
+	if e == nil {
+		return nil
+	}
+	e.draining.Store(true)
+	done := make(chan struct{})
+	go func() {
+		e.Wait()
+		close(done)
+	}()
+	select {
+	case <-done:
+		return nil
+	case <-ctx.Done():
+	}
+
+	remaining := e.InFlight()
+	e.cancelExecutions()
+	recordCtx, cancel := context.WithTimeout(context.Background(), 5*time.Second)
+	defer cancel()
+	for _, exec := range remaining {
+		if err := e.recordAborted(recordCtx, exec.CaseID, exec.StepID, true); err != nil {
+			slog.Error("record aborted step execution failed", "case_id", exec.CaseID.String(), "step_id", exec.StepID, "error", err)
+		}
+	}
+	select {
+	case <-done:
+	case <-recordCtx.Done():
+	}
+	return fmt.Errorf("%w: %d step execution(s) aborted", ErrDrainTimeout, len(remaining))
+}
+
+// recordAborted notes on an active step that its execution was interrupted by
+// shutdown. started is false for steps that were activated but never ran.
+func (e *Engine) recordAborted(ctx context.Context, caseID uuid.UUID, stepID string, started bool) error {
+	tx, err := e.db.BeginTx(ctx, nil)
+	if err != nil {
+		return fmt.Errorf("begin abort step tx: %w", err)
+	}
+	defer func() { _ = tx.Rollback() }()
+
+	event, err := json.Marshal(map[string]any{"type": "aborted", "reason": "shutdown", "started": started})
+	if err != nil {
+		return fmt.Errorf("marshal abort event: %w", err)
+	}
+	res, err := tx.ExecContext(ctx, `
+UPDATE case_steps
+SET events = COALESCE(events, '[]'::jsonb) || jsonb_build_array($3::jsonb || jsonb_build_object('at', now()))
+WHERE case_id = $1 AND step_id = $2 AND state = 'active'
+`, caseID, stepID, string(event))
+	if err != nil {
+		return fmt.Errorf("record step abort: %w", err)
+	}
+	if n, _ := res.RowsAffected(); n == 0 {
+		return nil
+	}
+	if err := e.auditSvc.RecordCaseEventTx(ctx, tx, caseID, stepID, "step", e.systemActor(), "system", "aborted", map[string]any{"reason": "shutdown", "started": started}); err != nil {
+		return fmt.Errorf("record step abort event: %w", err)
+	}
+	if err := tx.Commit(); err != nil {
+		return fmt.Errorf("commit step abort: %w", err)
+	}
+	return nil
+}
+
+// abortedBeforeStart reports whether a step's last event says it was
+// activated during shutdown and never executed.
+func abortedBeforeStart(lastEvent []byte) bool {
+	if len(lastEvent) == 0 {
+		return false
+	}
+	var ev struct {
+		Type    string `json:"type"`
+		Started bool   `json:"started"`
+	}
+	if err := json.Unmarshal(lastEvent, &ev); err != nil {
+		return false
+	}
+	return ev.Type == "aborted" && !ev.Started
+}
@@ -0,0 +1,85 @@
+package engine
+
+import (
+	"encoding/json"
+	"fmt"
+)
+
+// SimulatedStep is the planned fate of one step in a dry run. Wave is the
+// 1-based round in which the step would be activated; steps activated in the
+// same wave run concurrently. Pending steps were never reached.
+type SimulatedStep struct {
+	StepID  string `json:"step_id"`
+	Type    string `json:"type"`
+	State   string `json:"state"`
+	Wave    int    `json:"wave,omitempty"`
+	Reason  string `json:"reason,omitempty"`
+	Outcome string `json:"outcome,omitempty"`
+}
+
+// SimulateFunc stands in for a step's executor during Simulate. It returns
+// the outcome to route on and the case data that later guards should see.
+type SimulateFunc func(step WorkflowStep, wave int, caseData map[string]any) (outcome string, next map[string]any)
+
+// Simulate walks ast with the same transition rules as a live case, but calls
+// run instead of executing activated steps. Results are in activation order,
+// followed by skipped and unreached steps in AST order.
+func Simulate(ast WorkflowAST, evaluator ExpressionEvaluator, caseData map[string]any, run SimulateFunc) ([]SimulatedStep, error) {
+	if caseData == nil {
+		caseData = map[string]any{}
+	}
+	states := make(map[string]StepState, len(ast.Steps))
+	planned := make(map[string]*SimulatedStep, len(ast.Steps))
+	order := make([]string, 0, len(ast.Steps))
+	for _, step := range ast.Steps {
+		planned[step.ID] = &SimulatedStep{StepID: step.ID, Type: step.Type, State: StatePending}
+	}
+	stepsByID := stepMap(ast)
+
+	for wave := 1; wave <= len(ast.Steps); wave++ {
+		transitions, err := computeTransitions(ast, states, evaluator, map[string]interface{}{"case": caseData})
+		if err != nil {
+			return nil, err
+		}
+		active := make([]string, 0)
+		for _, tr := range transitions {
+			states[tr.StepID] = StepState{StepID: tr.StepID, State: tr.To}
+			switch tr.Type {
+			case TransitionToSkipped:
+				planned[tr.StepID].State = StateSkipped
+				planned[tr.StepID].Reason = string(tr.Reason)
+			case TransitionToActive:
+				active = append(active, tr.StepID)
+			}
+		}
+		if len(active) == 0 {
+			break
+		}
+		for _, stepID := range active {
+			outcome, next := run(stepsByID[stepID], wave, caseData)
+			if next != nil {
+				caseData = next
+			}
+			result, err := json.Marshal(map[string]string{"outcome": outcome})
+			if err != nil {
+				return nil, fmt.Errorf("marshal simulated result for %s: %w", stepID, err)
+			}
+			states[stepID] = StepState{StepID: stepID, State: StateCompleted, Result: result}
+			planned[stepID].State = StateCompleted
+			planned[stepID].Wave = wave
+			planned[stepID].Outcome = outcome
+			order = append(order, stepID)
+		}
+	}
+
+	out := make([]SimulatedStep, 0, len(ast.Steps))
+	for _, stepID := range order {
+		out = append(out, *planned[stepID])
+	}
+	for _, step := range ast.Steps {
+		if planned[step.ID].State != StateCompleted {
+			out = append(out, *planned[step.ID])
+		}
+	}
+	return out, nil
+}
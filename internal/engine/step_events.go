@@ -0,0 +1,167 @@
+package engine
+
+import (
+	"context"
+	"database/sql"
+	"errors"
+	"fmt"
+	"log/slog"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+// Step lifecycle statuses reported to StepEventHooks. A step is queued when
+// activated, running while its executor is called, and ends succeeded,
+// failed or skipped.
+const (
+	StepStatusPending   = "pending"
+	StepStatusQueued    = "queued"
+	StepStatusRunning   = "running"
+	StepStatusSucceeded = "succeeded"
+	StepStatusFailed    = "failed"
+	StepStatusSkipped   = "skipped"
+)
+
+// StepEvent reports a step's lifecycle change. DurationMS is set on terminal
+// events for steps that were started. Succeeded events also carry the
+// step's executor RuntimeMS, LLM Tokens and Cost; events of steps that
+// failed or were skipped after running carry the RuntimeMS of their
+// attempts.
+type StepEvent struct {
+	CaseID     uuid.UUID `json:"case_id"`
+	TenantID   uuid.UUID `json:"tenant_id"`
+	StepID     string    `json:"step_id"`
+	Status     string    `json:"status"`
+	Outcome    string    `json:"outcome,omitempty"`
+	Error      string    `json:"error,omitempty"`
+	Attempt    int       `json:"attempt,omitempty"`
+	DurationMS *int64    `json:"duration_ms,omitempty"`
+	RuntimeMS  int64     `json:"runtime_ms,omitempty"`
+	Tokens     int       `json:"tokens,omitempty"`
+	Cost       float64   `json:"cost,omitempty"`
+	At         time.Time `json:"at"`
+}
+
+// StepEventHook is told about every step lifecycle change after it is
+// committed. It runs on the engine's goroutines, so it must not block.
+type StepEventHook func(ctx context.Context, event StepEvent)
+
+func (e *Engine) AddStepEventHook(hook StepEventHook) {
+	if hook == nil {
+		return
+	}
+	e.mu.Lock()
+	defer e.mu.Unlock()
+	e.stepEventHooks = append(e.stepEventHooks, hook)
+}
+
+// emitStepEvent fills in the tenant and, for terminal events, the step's
+// duration before calling the hooks. Nothing is loaded without hooks.
+func (e *Engine) emitStepEvent(ctx context.Context, event StepEvent) {
+	e.mu.RLock()
+	hooks := append([]StepEventHook(nil), e.stepEventHooks...)
+	e.mu.RUnlock()
+	if len(hooks) == 0 {
+		return
+	}
+
+	var startedAt, completedAt sql.NullTime
+	err := e.db.QueryRowContext(ctx, `
+SELECT c.tenant_id, cs.started_at, cs.completed_at
+FROM cases c
+LEFT JOIN case_steps cs ON cs.case_id = c.id AND cs.step_id = $2
+WHERE c.id = $1
+`, event.CaseID, event.StepID).Scan(&event.TenantID, &startedAt, &completedAt)
+	if err != nil {
+		slog.WarnContext(ctx, "load step event context failed", "case_id", event.CaseID.String(), "step_id", event.StepID, "error", err)
+		return
+	}
+	if event.At.IsZero() {
+		event.At = time.Now().UTC()
+	}
+	if isTerminalStepStatus(event.Status) && startedAt.Valid && completedAt.Valid {
+		ms := completedAt.Time.Sub(startedAt.Time).Milliseconds()
+		event.DurationMS = &ms
+	}
+	for _, hook := range hooks {
+		hook(ctx, event)
+	}
+}
+
+func isTerminalStepStatus(status string) bool {
+	switch status {
+	case StepStatusSucceeded, StepStatusFailed, StepStatusSkipped:
+		return true
+	}
+	return false
+}
+
+// StepProgress returns each step's current status, so a client can render a
+// case before following its StepEvents. Active steps are reported as
+// running, as the stored state does not tell queued and running apart.
+func (e *Engine) StepProgress(ctx context.Context, tenantID, caseID uuid.UUID) ([]StepEvent, error) {
+	if e == nil || e.db == nil {
+		return nil, fmt.Errorf("engine not configured")
+	}
+	var exists bool
+	if err := e.db.QueryRowContext(ctx, `SELECT true FROM cases WHERE tenant_id = $1 AND id = $2`, tenantID, caseID).Scan(&exists); err != nil {
+		if errors.Is(err, sql.ErrNoRows) {
+			return nil, ErrNotFound
+		}
+		return nil, fmt.Errorf("load case for step progress: %w", err)
+	}
+
+	rows, err := e.db.QueryContext(ctx, `
+SELECT step_id, state, COALESCE(error->>'message', ''), COALESCE(result->>'outcome', ''),
+       started_at, completed_at, COALESCE(completed_at, started_at, now())
+FROM case_steps
+WHERE case_id = $1
+ORDER BY started_at NULLS LAST, step_id
+`, caseID)
+	if err != nil {
+		return nil, fmt.Errorf("load step progress: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+
+	out := make([]StepEvent, 0)
+	for rows.Next() {
+		var (
+			event                  StepEvent
+			state                  string
+			startedAt, completedAt sql.NullTime
+		)
+		if err := rows.Scan(&event.StepID, &state, &event.Error, &event.Outcome, &startedAt, &completedAt, &event.At); err != nil {
+			return nil, fmt.Errorf("scan step progress: %w", err)
+		}
+		event.CaseID = caseID
+		event.TenantID = tenantID
+		event.Status = stepStatusForState(state)
+		if isTerminalStepStatus(event.Status) && startedAt.Valid && completedAt.Valid {
+			ms := completedAt.Time.Sub(startedAt.Time).Milliseconds()
+			event.DurationMS = &ms
+		}
+		out = append(out, event)
+	}
+	if err := rows.Err(); err != nil {
+		return nil, fmt.Errorf("iterate step progress: %w", err)
+	}
+	return out, nil
+}
+
+func stepStatusForState(state string) string {
+	switch state {
+	case StateReady:
+		return StepStatusQueued
+	case StateActive:
+		return StepStatusRunning
+	case StateCompleted:
+		return StepStatusSucceeded
+	case StateFailed:
+		return StepStatusFailed
+	case StateSkipped:
+		return StepStatusSkipped
+	default:
+		return StepStatusPending
+	}
+}
@@ -5,10 +5,12 @@ import (
 	"encoding/json"
 	"fmt"
 	"log/slog"
+	"strconv"
 	"strings"
 
 	"github.com/google/uuid"
 	"github.com/neural-chilli/aceryx/internal/observability"
+	"github.com/neural-chilli/aceryx/internal/redaction"
 )
 
 func (e *Engine) CompleteStep(ctx context.Context, caseID uuid.UUID, stepID string, result *StepResult) error {
@@ -31,7 +33,8 @@ func (e *Engine) completeStep(ctx context.Context, caseID uuid.UUID, stepID stri
 		return fmt.Errorf("lock case for complete step: %w", err)
 	}
 
-	resultJSON, err := json.Marshal(result)
+	stored := e.redactResult(result)
+	resultJSON, err := json.Marshal(stored)
 	if err != nil {
 		return fmt.Errorf("marshal step result: %w", err)
 	}
@@ -42,6 +45,7 @@ func (e *Engine) completeStep(ctx context.Context, caseID uuid.UUID, stepID stri
 	    state = 'completed',
     completed_at = now(),
     result = $3::jsonb,
+    cost = $6,
     error = NULL,
     events = COALESCE(events, '[]'::jsonb)
       || CASE
@@ -52,7 +56,7 @@ func (e *Engine) completeStep(ctx context.Context, caseID uuid.UUID, stepID stri
         jsonb_build_object('type', 'completed', 'attempts', $4::int, 'at', now())
 	    )
 	WHERE case_id = $1 AND step_id = $2 AND state = 'active'
-	`, caseID, stepID, string(resultJSON), result.Attempts, string(result.ExecutionEvent))
+	`, caseID, stepID, string(resultJSON), result.Attempts, string(stored.ExecutionEvent), result.Cost)
 	if err != nil {
 		return fmt.Errorf("update completed step state: %w", err)
 	}
@@ -60,6 +64,9 @@ func (e *Engine) completeStep(ctx context.Context, caseID uuid.UUID, stepID stri
 	if affected == 0 {
 		return ErrStepNotActive
 	}
+	if _, err := tx.ExecContext(ctx, `DELETE FROM case_step_items WHERE case_id = $1 AND step_id = $2`, caseID, stepID); err != nil {
+		return fmt.Errorf("clear for_each checkpoints: %w", err)
+	}
 
 	if result.WritesCaseData && len(result.CaseDataPatch) > 0 {
 		if _, err := tx.ExecContext(ctx, `
@@ -67,13 +74,14 @@ UPDATE cases
 SET
     data = COALESCE(data, '{}'::jsonb) || $2::jsonb,
     version = version + 1,
+    cost = cost + $3,
     updated_at = now()
 WHERE id = $1
-`, caseID, string(result.CaseDataPatch)); err != nil {
+`, caseID, string(result.CaseDataPatch), result.Cost); err != nil {
 			return fmt.Errorf("write step output to case data: %w", err)
 		}
 	} else {
-		if _, err := tx.ExecContext(ctx, `UPDATE cases SET updated_at = now() WHERE id = $1`, caseID); err != nil {
+		if _, err := tx.ExecContext(ctx, `UPDATE cases SET cost = cost + $2, updated_at = now() WHERE id = $1`, caseID, result.Cost); err != nil {
 			return fmt.Errorf("touch case for complete step: %w", err)
 		}
 	}
@@ -105,6 +113,7 @@ WHERE id = $1
 	if terr == nil {
 		e.updateCaseStepStateMetrics(ctx, tenantID)
 	}
+	e.emitStepEvent(ctx, StepEvent{CaseID: caseID, StepID: stepID, Status: StepStatusSucceeded, Outcome: result.Outcome, Attempt: result.Attempts, RuntimeMS: result.RuntimeMS, Tokens: result.Tokens, Cost: result.Cost})
 	slog.InfoContext(ctx, "step completed",
 		append(observability.RequestAttrs(ctx),
 			"case_id", caseID.String(),
@@ -123,6 +132,13 @@ func (e *Engine) FailStep(ctx context.Context, caseID uuid.UUID, stepID string,
 }
 
 func (e *Engine) failStep(ctx context.Context, caseID uuid.UUID, stepID string, failErr error) error {
+	return e.failStepAttempts(ctx, caseID, stepID, failErr, 0, 0)
+}
+
+// failStepAttempts fails a step whose executor ran attempts times for
+// runtimeMS in total, and reports both on the failed step event.
+func (e *Engine) failStepAttempts(ctx context.Context, caseID uuid.UUID, stepID string, failErr error, attempts int, runtimeMS int64) error {
+	message := e.activeRedactor().RedactString(failErr.Error())
 	tx, err := e.db.BeginTx(ctx, nil)
 	if err != nil {
 		return fmt.Errorf("begin fail step tx: %w", err)
@@ -144,14 +160,14 @@ SET
         jsonb_build_object('type', 'failed', 'error', $3, 'at', now())
     )
 WHERE case_id = $1 AND step_id = $2 AND state = 'active'
-`, caseID, stepID, failErr.Error()); err != nil {
+`, caseID, stepID, message); err != nil {
 		return fmt.Errorf("update failed step state: %w", err)
 	}
 
 	if _, err := tx.ExecContext(ctx, `UPDATE cases SET updated_at = now() WHERE id = $1`, caseID); err != nil {
 		return fmt.Errorf("touch case for fail step: %w", err)
 	}
-	if err := e.auditSvc.RecordCaseEventTx(ctx, tx, caseID, stepID, "step", e.systemActor(), "system", "failed", map[string]any{"error": failErr.Error()}); err != nil {
+	if err := e.auditSvc.RecordCaseEventTx(ctx, tx, caseID, stepID, "step", e.systemActor(), "system", "failed", map[string]any{"error": message}); err != nil {
 		return err
 	}
 	if err := e.auditSvc.CommitTx(tx); err != nil {
@@ -161,11 +177,12 @@ WHERE case_id = $1 AND step_id = $2 AND state = 'active'
 	if terr == nil {
 		e.updateCaseStepStateMetrics(ctx, tenantID)
 	}
+	e.emitStepEvent(ctx, StepEvent{CaseID: caseID, StepID: stepID, Status: StepStatusFailed, Error: message, Attempt: attempts, RuntimeMS: runtimeMS})
 	slog.ErrorContext(ctx, "step failed",
 		append(observability.RequestAttrs(ctx),
 			"case_id", caseID.String(),
 			"step_id", stepID,
-			"error", failErr,
+			"error", message,
 		)...,
 	)
 
@@ -175,7 +192,7 @@ WHERE case_id = $1 AND step_id = $2 AND state = 'active'
 	return nil
 }
 
-func (e *Engine) skipStepTerminal(ctx context.Context, caseID uuid.UUID, stepID string, attempts int, cause error) error {
+func (e *Engine) skipStepTerminal(ctx context.Context, caseID uuid.UUID, stepID string, attempts int, runtimeMS int64, cause error) error {
 	tx, err := e.db.BeginTx(ctx, nil)
 	if err != nil {
 		return fmt.Errorf("begin skip step tx: %w", err)
@@ -208,6 +225,7 @@ WHERE case_id = $1 AND step_id = $2 AND state = 'active'
 	if terr == nil {
 		e.updateCaseStepStateMetrics(ctx, tenantID)
 	}
+	e.emitStepEvent(ctx, StepEvent{CaseID: caseID, StepID: stepID, Status: StepStatusSkipped, Error: cause.Error(), Attempt: attempts, RuntimeMS: runtimeMS})
 	return nil
 }
 
@@ -232,3 +250,16 @@ WHERE id = $1 AND version = $2
 	}
 	return nil
 }
+
+// redactResult returns the result as it is stored: a sensitive output is
+// replaced by redaction.Mask and the redaction rules are applied to the rest.
+func (e *Engine) redactResult(result *StepResult) *StepResult {
+	stored := *result
+	if stored.Sensitive && len(stored.Output) > 0 {
+		stored.Output = json.RawMessage(strconv.Quote(redaction.Mask))
+	}
+	redactor := e.activeRedactor()
+	stored.Output = redactor.RedactJSON(stored.Output)
+	stored.ExecutionEvent = redactor.RedactJSON(stored.ExecutionEvent)
+	return &stored
+}
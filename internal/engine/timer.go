@@ -0,0 +1,318 @@
+package engine
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"log/slog"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+type timerConfig struct {
+	Duration  string   `json:"duration"`
+	Seconds   *float64 `json:"seconds"`
+	Minutes   *float64 `json:"minutes"`
+	Until     string   `json:"until"`
+	UntilPath string   `json:"until_path"`
+	Schedule  string   `json:"schedule"`
+	Timezone  string   `json:"timezone"`
+}
+
+// TimerExecutor runs timer, delay and wait_until steps. Rather than sleeping
+// it records the due time as resume_at in the step's metadata and leaves the
+// step active; StartTimerMonitor completes it once that time passes. Waits
+// therefore hold no worker and survive restarts: a recovered step keeps the
+// resume_at it was parked with.
+type TimerExecutor struct {
+	db  *sql.DB
+	now func() time.Time
+}
+
+func NewTimerExecutor(db *sql.DB) *TimerExecutor {
+	return &TimerExecutor{db: db, now: time.Now}
+}
+
+func (t *TimerExecutor) Execute(ctx context.Context, caseID uuid.UUID, stepID string, config json.RawMessage) (*StepResult, error) {
+	cfg := timerConfig{}
+	if len(config) > 0 {
+		if err := json.Unmarshal(config, &cfg); err != nil {
+			return nil, fmt.Errorf("decode timer step config: %w", err)
+		}
+	}
+	now := t.now()
+
+	var stored sql.NullString
+	err := t.db.QueryRowContext(ctx, `
+SELECT metadata ->> 'resume_at'
+FROM case_steps
+WHERE case_id = $1 AND step_id = $2
+`, caseID, stepID).Scan(&stored)
+	if err != nil && !errors.Is(err, sql.ErrNoRows) {
+		return nil, fmt.Errorf("load timer step %s: %w", stepID, err)
+	}
+
+	var resumeAt time.Time
+	if stored.Valid {
+		if resumeAt, err = time.Parse(time.RFC3339Nano, stored.String); err != nil {
+			return nil, fmt.Errorf("timer step %s has invalid resume_at %q", stepID, stored.String)
+		}
+	} else {
+		var data map[string]any
+		if cfg.UntilPath != "" {
+			if data, err = t.loadCaseData(ctx, caseID); err != nil {
+				return nil, err
+			}
+		}
+		if resumeAt, err = timerResumeAt(cfg, now, data); err != nil {
+			return nil, fmt.Errorf("timer step %s: %w", stepID, err)
+		}
+	}
+
+	if !resumeAt.After(now) {
+		if stored.Valid {
+			if _, err := t.db.ExecContext(ctx, `
+UPDATE case_steps
+SET metadata = metadata - 'resume_at'
+WHERE case_id = $1 AND step_id = $2
+`, caseID, stepID); err != nil {
+				return nil, fmt.Errorf("clear timer step %s: %w", stepID, err)
+			}
+		}
+		return timerResult(resumeAt, now)
+	}
+	if !stored.Valid {
+		if _, err := t.db.ExecContext(ctx, `
+UPDATE case_steps
+SET metadata = COALESCE(metadata, '{}'::jsonb) || jsonb_build_object('resume_at', $3::text)
+WHERE case_id = $1 AND step_id = $2 AND state = 'active'
+`, caseID, stepID, resumeAt.UTC().Format(time.RFC3339Nano)); err != nil {
+			return nil, fmt.Errorf("park timer step %s: %w", stepID, err)
+		}
+	}
+	return nil, ErrStepAwaitingReview
+}
+
+func (t *TimerExecutor) loadCaseData(ctx context.Context, caseID uuid.UUID) (map[string]any, error) {
+	var raw []byte
+	if err := t.db.QueryRowContext(ctx, `SELECT data FROM cases WHERE id = $1`, caseID).Scan(&raw); err != nil {
+		return nil, fmt.Errorf("load case data for timer: %w", err)
+	}
+	data := map[string]any{}
+	if len(raw) > 0 {
+		if err := json.Unmarshal(raw, &data); err != nil {
+			return nil, fmt.Errorf("decode case data for timer: %w", err)
+		}
+	}
+	return data, nil
+}
+
+// timerResumeAt works out when a timer is due. Exactly one of a duration
+// (duration, seconds and minutes, which add up), an absolute time (until, or
+// until_path naming an RFC 3339 field such as "case.data.follow_up_at") or a
+// cron schedule evaluated in timezone must be set.
+func timerResumeAt(cfg timerConfig, now time.Time, caseData map[string]any) (time.Time, error) {
+	hasDuration := cfg.Duration != "" || cfg.Seconds != nil || cfg.Minutes != nil
+	hasUntil := cfg.Until != "" || cfg.UntilPath != ""
+	hasSchedule := cfg.Schedule != ""
+	set := 0
+	for _, ok := range []bool{hasDuration, hasUntil, hasSchedule} {
+		if ok {
+			set++
+		}
+	}
+	if set != 1 {
+		return time.Time{}, fmt.Errorf("set exactly one of a duration, until or schedule")
+	}
+
+	switch {
+	case hasDuration:
+		var wait time.Duration
+		if cfg.Duration != "" {
+			d, err := parseTimerDuration(cfg.Duration)
+			if err != nil {
+				return time.Time{}, err
+			}
+			wait += d
+		}
+		if cfg.Seconds != nil {
+			wait += time.Duration(*cfg.Seconds * float64(time.Second))
+		}
+		if cfg.Minutes != nil {
+			wait += time.Duration(*cfg.Minutes * float64(time.Minute))
+		}
+		if wait < 0 {
+			return time.Time{}, fmt.Errorf("duration must not be negative")
+		}
+		return now.Add(wait), nil
+	case hasUntil:
+		raw := cfg.Until
+		if cfg.UntilPath != "" {
+			value, ok := caseDataPath(caseData, cfg.UntilPath)
+			if !ok {
+				return time.Time{}, fmt.Errorf("until_path %q is not set", cfg.UntilPath)
+			}
+			raw = value
+		}
+		at, err := time.Parse(time.RFC3339, strings.TrimSpace(raw))
+		if err != nil {
+			return time.Time{}, fmt.Errorf("until %q is not an RFC 3339 timestamp", raw)
+		}
+		return at, nil
+	default:
+		schedule, err := parseCron(cfg.Schedule)
+		if err != nil {
+			return time.Time{}, err
+		}
+		loc := time.UTC
+		if cfg.Timezone != "" {
+			if loc, err = time.LoadLocation(cfg.Timezone); err != nil {
+				return time.Time{}, fmt.Errorf("unknown timezone %q", cfg.Timezone)
+			}
+		}
+		at, ok := schedule.next(now.In(loc))
+		if !ok {
+			return time.Time{}, fmt.Errorf("schedule %q never fires", cfg.Schedule)
+		}
+		return at, nil
+	}
+}
+
+// parseTimerDuration accepts Go durations ("90s", "1h30m") and whole days
+// ("2d").
+func parseTimerDuration(raw string) (time.Duration, error) {
+	raw = strings.TrimSpace(raw)
+	if days, ok := strings.CutSuffix(raw, "d"); ok {
+		n, err := strconv.Atoi(days)
+		if err != nil {
+			return 0, fmt.Errorf("invalid duration %q", raw)
+		}
+		return time.Duration(n) * 24 * time.Hour, nil
+	}
+	d, err := time.ParseDuration(raw)
+	if err != nil {
+		return 0, fmt.Errorf("invalid duration %q", raw)
+	}
+	return d, nil
+}
+
+// caseDataPath resolves a dotted path against case data. The "case.data."
+// prefix is optional; the value must be a string.
+func caseDataPath(data map[string]any, path string) (string, bool) {
+	path = strings.TrimPrefix(strings.TrimSpace(path), "case.")
+	path = strings.TrimPrefix(path, "data.")
+	var current any = data
+	for _, part := range strings.Split(path, ".") {
+		obj, ok := current.(map[string]any)
+		if !ok {
+			return "", false
+		}
+		if current, ok = obj[part]; !ok {
+			return "", false
+		}
+	}
+	s, ok := current.(string)
+	return s, ok && s != ""
+}
+
+func timerResult(resumeAt, now time.Time) (*StepResult, error) {
+	payload, err := json.Marshal(map[string]any{
+		"resume_at":  resumeAt.UTC().Format(time.RFC3339),
+		"resumed_at": now.UTC().Format(time.RFC3339),
+	})
+	if err != nil {
+		return nil, fmt.Errorf("marshal timer result: %w", err)
+	}
+	return &StepResult{Output: payload}, nil
+}
+
+// StartTimerMonitor completes parked timer steps whose resume_at has passed.
+// Each due step is claimed by removing resume_at in the same statement that
+// selects it, so several replicas can run the monitor without completing a
+// step twice.
+func (e *Engine) StartTimerMonitor(ctx context.Context) {
+	ticker := time.NewTicker(e.timerInterval)
+	defer ticker.Stop()
+
+	for {
+		select {
+		case <-ticker.C:
+			for {
+				count, err := e.resumeDueTimers(ctx)
+				if err != nil {
+					slog.Warn("resume due timers failed", "error", err)
+					break
+				}
+				if count < 100 {
+					break
+				}
+			}
+		case <-ctx.Done():
+			return
+		}
+	}
+}
+
+func (e *Engine) resumeDueTimers(ctx context.Context) (int, error) {
+	rows, err := e.db.QueryContext(ctx, `
+UPDATE case_steps cs
+SET metadata = cs.metadata - 'resume_at'
+FROM (
+    SELECT s.id, s.metadata ->> 'resume_at' AS resume_at
+    FROM case_steps s
+    JOIN cases c ON c.id = s.case_id
+    WHERE s.state = 'active'
+      AND s.metadata ? 'resume_at'
+      AND (s.metadata ->> 'resume_at')::timestamptz <= now()
+      AND c.status != 'cancelled'
+    ORDER BY (s.metadata ->> 'resume_at')::timestamptz
+    LIMIT 100
+    FOR UPDATE OF s SKIP LOCKED
+) due
+WHERE cs.id = due.id
+RETURNING cs.case_id, cs.step_id, due.resume_at
+`)
+	if err != nil {
+		return 0, fmt.Errorf("claim due timers: %w", err)
+	}
+	type dueTimer struct {
+		caseID   uuid.UUID
+		stepID   string
+		resumeAt string
+	}
+	due := make([]dueTimer, 0)
+	for rows.Next() {
+		var d dueTimer
+		if err := rows.Scan(&d.caseID, &d.stepID, &d.resumeAt); err != nil {
+			_ = rows.Close()
+			return 0, fmt.Errorf("scan due timer: %w", err)
+		}
+		due = append(due, d)
+	}
+	if err := rows.Err(); err != nil {
+		_ = rows.Close()
+		return 0, fmt.Errorf("iterate due timers: %w", err)
+	}
+	_ = rows.Close()
+
+	now := time.Now()
+	for _, d := range due {
+		resumeAt, err := time.Parse(time.RFC3339Nano, d.resumeAt)
+		if err != nil {
+			resumeAt = now
+		}
+		result, err := timerResult(resumeAt, now)
+		if err != nil {
+			return 0, err
+		}
+		if err := e.completeStep(ctx, d.caseID, d.stepID, result); err != nil {
+			slog.Warn("complete timer step failed", "case_id", d.caseID, "step_id", d.stepID, "error", err)
+		}
+	}
+	return len(due), nil
+}
@@ -7,10 +7,12 @@ import (
 	"errors"
 	"fmt"
 	"sync"
+	"sync/atomic"
 	"time"
 
 	"github.com/google/uuid"
 	"github.com/neural-chilli/aceryx/internal/audit"
+	"github.com/neural-chilli/aceryx/internal/redaction"
 )
 
 const (
@@ -31,6 +33,9 @@ var (
 	ErrExpressionTimedOut  = errors.New("engine: expression evaluation timeout")
 	ErrCycleDetectedInAST  = errors.New("engine: cycle detected in workflow AST")
 	ErrInvalidJoinStrategy = errors.New("engine: invalid join strategy")
+	ErrFeatureDisabled     = errors.New("engine: feature disabled")
+	ErrStepTimedOut        = errors.New("engine: step timed out")
+	ErrUsageExceeded       = errors.New("engine: usage budget exceeded")
 )
 
 type TransitionType string
@@ -60,20 +65,33 @@ type Transition struct {
 	Outcome string
 }
 
+// WorkflowAST is a workflow definition. InputSchema and Outputs form its
+// contract: case data must satisfy the JSON Schema when a case starts, and
+// Outputs maps result names to dot paths (as for for_each items) returned to
+// a calling sub_workflow step. Environments holds per-environment values
+// that step configs reference as {{env.name}}; see ForEnvironment.
 type WorkflowAST struct {
-	Steps []WorkflowStep `json:"steps"`
+	Steps        []WorkflowStep            `json:"steps"`
+	InputSchema  json.RawMessage           `json:"input_schema,omitempty"`
+	Outputs      map[string]string         `json:"outputs,omitempty"`
+	Environments map[string]map[string]any `json:"environments,omitempty"`
 }
 
+// WorkflowStep is one node of a workflow. TimeoutSeconds bounds each attempt
+// of the step; an attempt that overruns fails with ErrStepTimedOut and is
+// retried under the step's error policy.
 type WorkflowStep struct {
-	ID          string                 `json:"id"`
-	Type        string                 `json:"type"`
-	DependsOn   []string               `json:"depends_on"`
-	Join        string                 `json:"join,omitempty"`
-	Condition   string                 `json:"condition,omitempty"`
-	Outcomes    map[string][]string    `json:"outcomes,omitempty"`
-	Config      json.RawMessage        `json:"config,omitempty"`
-	ErrorPolicy ErrorPolicy            `json:"error_policy,omitempty"`
-	Metadata    map[string]interface{} `json:"metadata,omitempty"`
+	ID             string                 `json:"id"`
+	Type           string                 `json:"type"`
+	DependsOn      []string               `json:"depends_on"`
+	Join           string                 `json:"join,omitempty"`
+	Condition      string                 `json:"condition,omitempty"`
+	Outcomes       map[string][]string    `json:"outcomes,omitempty"`
+	Config         json.RawMessage        `json:"config,omitempty"`
+	ErrorPolicy    ErrorPolicy            `json:"error_policy,omitempty"`
+	TimeoutSeconds int                    `json:"timeout_seconds,omitempty"`
+	ForEach        *ForEach               `json:"for_each,omitempty"`
+	Metadata       map[string]interface{} `json:"metadata,omitempty"`
 }
 
 type StepState struct {
@@ -93,6 +111,23 @@ type StepResult struct {
 	ExecutionEvent json.RawMessage `json:"execution_event,omitempty"`
 	AuditEventType string          `json:"audit_event_type,omitempty"`
 	Attempts       int             `json:"attempts,omitempty"`
+
+	// Tokens is the LLM tokens the step consumed, set by AI executors.
+	// Cost is what the step cost in cost units, set by executors that know
+	// a price; the engine adds the step's cost_per_call and any cost a tool
+	// reports in its output. RuntimeMS is filled in by the engine: time
+	// spent in the executor across attempts, excluding waits for review.
+	Tokens    int     `json:"tokens,omitempty"`
+	Cost      float64 `json:"cost,omitempty"`
+	RuntimeMS int64   `json:"runtime_ms,omitempty"`
+
+	// Artifacts are large outputs kept out of the result. The engine stores
+	// them before completing the step and records only their handles.
+	Artifacts []Artifact `json:"artifacts,omitempty"`
+
+	// Sensitive marks an output that must not be stored: the step record
+	// keeps only a redaction marker.
+	Sensitive bool `json:"sensitive,omitempty"`
 }
 
 // StepExecutor executes one active step.
@@ -112,6 +147,23 @@ type Config struct {
 	MaxConcurrentSteps       int
 	MaxConcurrentEvaluations int
 	SLAInterval              time.Duration
+	TimerInterval            time.Duration
+
+	// Environment selects the workflow environment overlay applied to step
+	// configs, e.g. "staging". Empty uses only the default overlay.
+	Environment string
+
+	// DisableInputRecording stops the engine storing each step attempt's
+	// config, case data and upstream results for replay.
+	DisableInputRecording bool
+
+	// StepLogLines keeps the last StepLogLines log lines written while a
+	// step runs on its case_steps row, for debugging. Zero disables capture.
+	StepLogLines int
+
+	// Lanes splits MaxConcurrentSteps between execution lanes. Nil maps
+	// take the defaults; see defaultLaneConfig.
+	Lanes LaneConfig
 }
 
 type EscalationCallback func(ctx context.Context, task OverdueTask) error
@@ -137,7 +189,7 @@ type WorkflowStatus struct {
 type Engine struct {
 	db            *sql.DB
 	evaluations   *WorkerPool
-	executions    *WorkerPool
+	executions    *LanePool
 	evaluators    ExpressionEvaluator
 	executors     map[string]StepExecutor
 	escalation    EscalationCallback
@@ -145,7 +197,46 @@ type Engine struct {
 	mu            sync.RWMutex
 	defaultPolicy ErrorPolicy
 	slaInterval   time.Duration
+	timerInterval time.Duration
+	environment   string
+	recordInputs  bool
+	stepLogLines  int
 	auditSvc      *audit.Service
+	features      FeatureGate
+	usage         UsageGate
+	artifacts     ArtifactStore
+	redactor      *redaction.Redactor
+	settledHooks  []SettledHook
+	failureHooks  []FailureHook
+
+	stepEventHooks []StepEventHook
+
+	// owner stamps the steps this process activates; see SetOwner.
+	owner           uuid.UUID
+	ownerStaleAfter time.Duration
+
+	// execCtx is the parent of every step execution; cancelExecutions aborts
+	// whatever is still running when Shutdown's drain deadline passes.
+	execCtx          context.Context
+	cancelExecutions context.CancelFunc
+	draining         atomic.Bool
+	inflightMu       sync.Mutex
+	inflight         map[inflightKey]InFlightExecution
+	inflightCancels  map[inflightKey]context.CancelCauseFunc
+}
+
+// FeatureGate reports per-tenant feature flags. Step types are gated by the
+// flag features.StepFlag(stepType).
+type FeatureGate interface {
+	Enabled(ctx context.Context, tenantID uuid.UUID, key string) bool
+	Snapshot(ctx context.Context, tenantID uuid.UUID) map[string]bool
+}
+
+// UsageGate enforces usage budgets before each attempt of a step. An error
+// wrapping ErrUsageExceeded fails the step without retries; any other error
+// is retried like a failed attempt.
+type UsageGate interface {
+	CheckStep(ctx context.Context, tenantID, caseID uuid.UUID) error
 }
 
 type ExpressionEvaluator interface {
@@ -162,9 +253,18 @@ func defaultConfig(cfg Config) Config {
 	if cfg.SLAInterval <= 0 {
 		cfg.SLAInterval = 60 * time.Second
 	}
+	if cfg.TimerInterval <= 0 {
+		cfg.TimerInterval = 5 * time.Second
+	}
 	return cfg
 }
 
+// EffectiveMaxAttempts is how many times the engine runs step before its
+// error policy is exhausted, applying the per-type default when unset.
+func EffectiveMaxAttempts(step WorkflowStep) int {
+	return defaultErrorPolicyForStep(step.Type, step.ErrorPolicy).MaxAttempts
+}
+
 func defaultErrorPolicyForStep(stepType string, policy ErrorPolicy) ErrorPolicy {
 	if policy.MaxAttempts <= 0 {
 		switch stepType {
@@ -191,16 +291,25 @@ func defaultErrorPolicyForStep(stepType string, policy ErrorPolicy) ErrorPolicy
 
 func New(db *sql.DB, evaluator ExpressionEvaluator, cfg Config) *Engine {
 	cfg = defaultConfig(cfg)
+	execCtx, cancelExecutions := context.WithCancel(context.Background())
 	return &Engine{
-		db:            db,
-		evaluators:    evaluator,
-		executors:     make(map[string]StepExecutor),
-		executions:    NewWorkerPool(cfg.MaxConcurrentSteps),
-		evaluations:   NewWorkerPool(cfg.MaxConcurrentEvaluations),
-		systemActorID: uuid.Nil,
-		slaInterval:   cfg.SLAInterval,
-		defaultPolicy: ErrorPolicy{MaxAttempts: 1, Backoff: "none", InitialDelay: 5 * time.Second, MaxDelay: 60 * time.Second, OnExhausted: "fail"},
-		auditSvc:      audit.NewService(db),
+		db:               db,
+		evaluators:       evaluator,
+		executors:        make(map[string]StepExecutor),
+		executions:       NewLanePool(cfg.MaxConcurrentSteps, cfg.Lanes),
+		evaluations:      NewWorkerPool(cfg.MaxConcurrentEvaluations),
+		systemActorID:    uuid.Nil,
+		slaInterval:      cfg.SLAInterval,
+		timerInterval:    cfg.TimerInterval,
+		environment:      cfg.Environment,
+		recordInputs:     !cfg.DisableInputRecording,
+		stepLogLines:     cfg.StepLogLines,
+		defaultPolicy:    ErrorPolicy{MaxAttempts: 1, Backoff: "none", InitialDelay: 5 * time.Second, MaxDelay: 60 * time.Second, OnExhausted: "fail"},
+		auditSvc:         audit.NewService(db),
+		execCtx:          execCtx,
+		cancelExecutions: cancelExecutions,
+		inflight:         make(map[inflightKey]InFlightExecution),
+		inflightCancels:  make(map[inflightKey]context.CancelCauseFunc),
 	}
 }
 
@@ -231,6 +340,65 @@ func (e *Engine) SetAuditService(svc *audit.Service) {
 	e.auditSvc = svc
 }
 
+// SetOwner stamps the steps this process activates with instanceID, the
+// cluster_replicas instance_id of this process. Recovery only takes over
+// active steps whose owner is no longer a replica heartbeating within
+// staleAfter, so steps running on live replicas are left alone.
+func (e *Engine) SetOwner(instanceID uuid.UUID, staleAfter time.Duration) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+	e.owner = instanceID
+	e.ownerStaleAfter = staleAfter
+}
+
+func (e *Engine) stepOwner() (uuid.UUID, time.Duration) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+	return e.owner, e.ownerStaleAfter
+}
+
+func (e *Engine) SetFeatureGate(gate FeatureGate) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+	e.features = gate
+}
+
+func (e *Engine) featureGate() FeatureGate {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+	return e.features
+}
+
+func (e *Engine) SetUsageGate(gate UsageGate) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+	e.usage = gate
+}
+
+func (e *Engine) usageGate() UsageGate {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+	return e.usage
+}
+
+// SetRedactor sets the rules applied to step outputs and errors before they
+// are stored, and passed to executors through their context. Without one,
+// redaction.Default() applies.
+func (e *Engine) SetRedactor(r *redaction.Redactor) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+	e.redactor = r
+}
+
+func (e *Engine) activeRedactor() *redaction.Redactor {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+	if e.redactor == nil {
+		return redaction.Default()
+	}
+	return e.redactor
+}
+
 func (e *Engine) executorFor(stepType string) (StepExecutor, error) {
 	e.mu.RLock()
 	defer e.mu.RUnlock()
@@ -258,6 +426,14 @@ func (e *Engine) WorkerPoolStats() (active int, capacity int) {
 	return active, capacity
 }
 
+// LaneStats reports how the step workers are shared between lanes.
+func (e *Engine) LaneStats() map[Lane]LaneStats {
+	if e == nil || e.executions == nil {
+		return nil
+	}
+	return e.executions.Stats()
+}
+
 func (e *Engine) Wait() {
 	if e == nil {
 		return
@@ -0,0 +1,29 @@
+package engine
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+
+	"github.com/google/uuid"
+)
+
+// usageGatedExecutor checks the case's usage budgets before each attempt,
+// so a gate error goes through the step's retry policy like any other
+// failed attempt.
+type usageGatedExecutor struct {
+	engine *Engine
+	gate   UsageGate
+	inner  StepExecutor
+}
+
+func (u usageGatedExecutor) Execute(ctx context.Context, caseID uuid.UUID, stepID string, config json.RawMessage) (*StepResult, error) {
+	tenantID, err := u.engine.lookupTenantID(ctx, caseID)
+	if err != nil {
+		return nil, fmt.Errorf("load case tenant for usage check: %w", err)
+	}
+	if err := u.gate.CheckStep(ctx, tenantID, caseID); err != nil {
+		return nil, err
+	}
+	return u.inner.Execute(ctx, caseID, stepID, config)
+}
@@ -0,0 +1,179 @@
+package events
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"io"
+	"log/slog"
+	"net/http"
+	"time"
+)
+
+const (
+	// SignatureHeader carries the hex HMAC-SHA256 of the body, keyed with the
+	// subscription's secret. Failure webhooks use the same headers.
+	SignatureHeader = "X-Aceryx-Signature"
+	EventHeader     = "X-Aceryx-Event"
+	DeliveryHeader  = "X-Aceryx-Delivery"
+
+	DefaultMaxAttempts  = 8
+	DefaultPollInterval = 2 * time.Second
+
+	deliveryBatch   = 20
+	deliveryTimeout = 10 * time.Second
+	initialBackoff  = 30 * time.Second
+	maxBackoff      = time.Hour
+
+	// deliveryLease is how long a claimed delivery is hidden from other
+	// replicas. The batch is sent one delivery at a time, so the last one
+	// may wait for every request before it to time out.
+	deliveryLease = deliveryBatch*deliveryTimeout + 30*time.Second
+)
+
+// Run delivers due webhooks until ctx is done. Deliveries are claimed with
+// SKIP LOCKED, so every replica can run it.
+func (b *Bus) Run(ctx context.Context) {
+	client := b.egress.Client(deliveryTimeout)
+	ticker := time.NewTicker(DefaultPollInterval)
+	defer ticker.Stop()
+	for {
+		for {
+			n, err := b.deliverDue(ctx, client)
+			if err != nil && ctx.Err() == nil {
+				slog.WarnContext(ctx, "event delivery tick failed", "error", err)
+			}
+			if n < deliveryBatch {
+				break
+			}
+		}
+		select {
+		case <-ctx.Done():
+			return
+		case <-ticker.C:
+		}
+	}
+}
+
+type pendingDelivery struct {
+	id       int64
+	attempts int
+	url      string
+	secret   string
+	event    Event
+}
+
+func (b *Bus) deliverDue(ctx context.Context, client *http.Client) (int, error) {
+	// Claiming pushes next_attempt_at past deliveryLease so another replica
+	// does not pick the delivery up while the batch is in flight.
+	rows, err := b.db.QueryContext(ctx, `
+UPDATE event_deliveries d
+SET next_attempt_at = now() + make_interval(secs => $2)
+FROM event_subscriptions s, events e
+WHERE d.id IN (
+    SELECT id FROM event_deliveries
+    WHERE status = 'pending' AND next_attempt_at <= now()
+    ORDER BY next_attempt_at
+    LIMIT $1
+    FOR UPDATE SKIP LOCKED
+)
+  AND s.id = d.subscription_id
+  AND e.id = d.event_id
+RETURNING d.id, d.attempts, s.url, s.secret, e.id, e.type, e.tenant_id, e.occurred_at, e.data
+`, deliveryBatch, deliveryLease.Seconds())
+	if err != nil {
+		return 0, fmt.Errorf("claim event deliveries: %w", err)
+	}
+	var due []pendingDelivery
+	for rows.Next() {
+		var (
+			d    pendingDelivery
+			data []byte
+		)
+		if err := rows.Scan(&d.id, &d.attempts, &d.url, &d.secret, &d.event.ID, &d.event.Type, &d.event.TenantID, &d.event.OccurredAt, &data); err != nil {
+			_ = rows.Close()
+			return 0, fmt.Errorf("scan event delivery: %w", err)
+		}
+		d.event.OccurredAt = d.event.OccurredAt.UTC()
+		d.event.Data = data
+		due = append(due, d)
+	}
+	if err := rows.Close(); err != nil {
+		return 0, fmt.Errorf("claim event deliveries: %w", err)
+	}
+	for _, d := range due {
+		status, sendErr := send(ctx, client, d)
+		if err := b.recordAttempt(ctx, d, status, sendErr); err != nil {
+			return len(due), err
+		}
+	}
+	return len(due), nil
+}
+
+func send(ctx context.Context, client *http.Client, d pendingDelivery) (int, error) {
+	body, err := json.Marshal(d.event)
+	if err != nil {
+		return 0, fmt.Errorf("marshal event: %w", err)
+	}
+	req, err := http.NewRequestWithContext(ctx, http.MethodPost, d.url, bytes.NewReader(body))
+	if err != nil {
+		return 0, fmt.Errorf("build event webhook request: %w", err)
+	}
+	SetWebhookHeaders(req.Header, d.event.Type, fmt.Sprint(d.id), d.secret, body)
+	resp, err := client.Do(req)
+	if err != nil {
+		return 0, fmt.Errorf("post event webhook: %w", err)
+	}
+	defer func() { _ = resp.Body.Close() }()
+	_, _ = io.Copy(io.Discard, io.LimitReader(resp.Body, 64<<10))
+	if resp.StatusCode >= 300 {
+		return resp.StatusCode, fmt.Errorf("event webhook returned status %d", resp.StatusCode)
+	}
+	return resp.StatusCode, nil
+}
+
+func (b *Bus) recordAttempt(ctx context.Context, d pendingDelivery, status int, sendErr error) error {
+	var responseStatus *int
+	if status > 0 {
+		responseStatus = &status
+	}
+	attempts := d.attempts + 1
+	var err error
+	switch {
+	case sendErr == nil:
+		_, err = b.db.ExecContext(ctx, `
+UPDATE event_deliveries
+SET status = 'delivered', attempts = $2, response_status = $3, last_error = '', delivered_at = now()
+WHERE id = $1
+`, d.id, attempts, responseStatus)
+	case attempts >= DefaultMaxAttempts:
+		_, err = b.db.ExecContext(ctx, `
+UPDATE event_deliveries
+SET status = 'failed', attempts = $2, response_status = $3, last_error = $4
+WHERE id = $1
+`, d.id, attempts, responseStatus, sendErr.Error())
+	default:
+		_, err = b.db.ExecContext(ctx, `
+UPDATE event_deliveries
+SET attempts = $2, response_status = $3, last_error = $4, next_attempt_at = now() + make_interval(secs => $5)
+WHERE id = $1
+`, d.id, attempts, responseStatus, sendErr.Error(), backoff(attempts).Seconds())
+	}
+	if err != nil {
+		return fmt.Errorf("record event delivery %d: %w", d.id, err)
+	}
+	return nil
+}
+
+// backoff doubles from 30 seconds after each failed attempt, up to an hour.
+func backoff(attempts int) time.Duration {
+	delay := initialBackoff
+	for i := 1; i < attempts && delay < maxBackoff; i++ {
+		delay *= 2
+	}
+	if delay > maxBackoff {
+		delay = maxBackoff
+	}
+	return delay
+}
@@ -0,0 +1,368 @@
+// Package events publishes lifecycle events such as workflow creation and
+// execution failures, to in-process listeners and to tenants' webhook
+// subscriptions. Webhook deliveries are stored with the event and retried
+// with backoff by Run until the receiver accepts them.
+package events
+
+import (
+	"context"
+	"crypto/rand"
+	"database/sql"
+	"encoding/hex"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"log/slog"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/engine"
+	"github.com/neural-chilli/aceryx/internal/observability"
+)
+
+// Event types.
+const (
+	WorkflowCreated     = "workflow.created"
+	WorkflowExecuted    = "workflow.executed"
+	WorkflowSLABreached = "workflow.sla_breached"
+	ExecutionFailed     = "execution.failed"
+	ToolRefreshed       = "tool.refreshed"
+	ToolAnomalyDetected = "tool.anomaly_detected"
+)
+
+// Types lists every event type a subscription may select.
+var Types = []string{WorkflowCreated, WorkflowExecuted, WorkflowSLABreached, ExecutionFailed, ToolRefreshed, ToolAnomalyDetected}
+
+var (
+	ErrInvalidSubscription  = errors.New("invalid subscription")
+	ErrSubscriptionNotFound = errors.New("subscription not found")
+)
+
+// Event is one published occurrence. Data is specific to Type.
+type Event struct {
+	ID         uuid.UUID       `json:"id"`
+	Type       string          `json:"type"`
+	TenantID   uuid.UUID       `json:"tenant_id"`
+	OccurredAt time.Time       `json:"occurred_at"`
+	Data       json.RawMessage `json:"data"`
+}
+
+// Listener receives events in-process, after they are stored. It runs on the
+// publisher's goroutine, so slow work should be handed off.
+type Listener func(ctx context.Context, event Event)
+
+// Subscription sends events of EventTypes to URL. Secret signs each
+// delivery and is only returned when the subscription is created.
+type Subscription struct {
+	ID         uuid.UUID `json:"id"`
+	URL        string    `json:"url"`
+	EventTypes []string  `json:"event_types"`
+	Secret     string    `json:"secret,omitempty"`
+	CreatedBy  uuid.UUID `json:"created_by"`
+	CreatedAt  time.Time `json:"created_at"`
+}
+
+type SubscriptionRequest struct {
+	URL        string   `json:"url"`
+	EventTypes []string `json:"event_types"`
+	Secret     string   `json:"secret,omitempty"`
+}
+
+// Delivery is one attempt history for an event sent to a subscription.
+type Delivery struct {
+	ID             int64      `json:"id"`
+	EventID        uuid.UUID  `json:"event_id"`
+	EventType      string     `json:"event_type"`
+	Status         string     `json:"status"`
+	Attempts       int        `json:"attempts"`
+	ResponseStatus *int       `json:"response_status,omitempty"`
+	LastError      string     `json:"last_error,omitempty"`
+	NextAttemptAt  *time.Time `json:"next_attempt_at,omitempty"`
+	DeliveredAt    *time.Time `json:"delivered_at,omitempty"`
+	CreatedAt      time.Time  `json:"created_at"`
+}
+
+type Bus struct {
+	db     *sql.DB
+	egress connectors.EgressPolicy
+
+	mu        sync.RWMutex
+	listeners []Listener
+}
+
+func NewBus(db *sql.DB, egress connectors.EgressPolicy) *Bus {
+	return &Bus{db: db, egress: egress}
+}
+
+// Listen adds an in-process listener for every event type.
+func (b *Bus) Listen(listener Listener) {
+	if listener == nil {
+		return
+	}
+	b.mu.Lock()
+	defer b.mu.Unlock()
+	b.listeners = append(b.listeners, listener)
+}
+
+// Publish records an event and queues a delivery to each matching
+// subscription. Failures are logged rather than returned so that publishing
+// never fails the operation that raised the event.
+func (b *Bus) Publish(ctx context.Context, tenantID uuid.UUID, eventType string, data any) {
+	b.publish(ctx, tenantID, eventType, "", data)
+}
+
+// publish skips the event when dedupeKey is set and an event of the same type
+// and key was already published for the tenant.
+func (b *Bus) publish(ctx context.Context, tenantID uuid.UUID, eventType, dedupeKey string, data any) {
+	if b == nil || tenantID == uuid.Nil {
+		return
+	}
+	raw, err := json.Marshal(data)
+	if err != nil {
+		slog.WarnContext(ctx, "encode event failed", "type", eventType, "error", err)
+		return
+	}
+	event := Event{Type: eventType, TenantID: tenantID, Data: raw}
+	var key sql.NullString
+	if dedupeKey != "" {
+		key = sql.NullString{String: dedupeKey, Valid: true}
+	}
+	err = b.db.QueryRowContext(ctx, `
+WITH ev AS (
+    INSERT INTO events (tenant_id, type, dedupe_key, data)
+    VALUES ($1, $2, $3, $4::jsonb)
+    ON CONFLICT (tenant_id, type, dedupe_key) WHERE dedupe_key IS NOT NULL DO NOTHING
+    RETURNING id, occurred_at
+), queued AS (
+    INSERT INTO event_deliveries (subscription_id, event_id)
+    SELECT s.id, ev.id
+    FROM ev
+    JOIN event_subscriptions s ON s.tenant_id = $1 AND s.event_types ? $2
+)
+SELECT id, occurred_at FROM ev
+`, tenantID, eventType, key, string(raw)).Scan(&event.ID, &event.OccurredAt)
+	if errors.Is(err, sql.ErrNoRows) {
+		return
+	}
+	if err != nil {
+		slog.WarnContext(ctx, "publish event failed", "type", eventType, "tenant_id", tenantID, "error", err)
+		return
+	}
+	event.OccurredAt = event.OccurredAt.UTC()
+	b.mu.RLock()
+	listeners := append([]Listener(nil), b.listeners...)
+	b.mu.RUnlock()
+	for _, listener := range listeners {
+		listener(ctx, event)
+	}
+}
+
+// OnCaseSettled is an engine.SettledHook publishing workflow.executed once
+// per case and outcome.
+func (b *Bus) OnCaseSettled(ctx context.Context, caseID uuid.UUID, failed bool) {
+	var (
+		tenantID, workflowID uuid.UUID
+		caseNumber           string
+		version              int
+	)
+	err := b.db.QueryRowContext(ctx, `
+SELECT tenant_id, case_number, workflow_id, workflow_version FROM cases WHERE id = $1
+`, caseID).Scan(&tenantID, &caseNumber, &workflowID, &version)
+	if err != nil {
+		slog.WarnContext(ctx, "load settled case for event failed", "case_id", caseID, "error", err)
+		return
+	}
+	status := "completed"
+	if failed {
+		status = "failed"
+	}
+	b.publish(ctx, tenantID, WorkflowExecuted, caseID.String()+":"+status, map[string]any{
+		"case_id":          caseID,
+		"case_number":      caseNumber,
+		"workflow_id":      workflowID,
+		"workflow_version": version,
+		"status":           status,
+	})
+}
+
+// OnStepFailed is an engine.FailureHook publishing execution.failed.
+func (b *Bus) OnStepFailed(ctx context.Context, failure engine.StepFailure) {
+	var tenantID uuid.UUID
+	if err := b.db.QueryRowContext(ctx, `SELECT tenant_id FROM cases WHERE id = $1`, failure.CaseID).Scan(&tenantID); err != nil {
+		slog.WarnContext(ctx, "load failed case for event failed", "case_id", failure.CaseID, "error", err)
+		return
+	}
+	b.Publish(ctx, tenantID, ExecutionFailed, map[string]any{
+		"case_id":   failure.CaseID,
+		"step_id":   failure.StepID,
+		"step_type": failure.StepType,
+		"attempts":  failure.Attempts,
+		"error":     failure.Error,
+	})
+}
+
+// OnToolAnomaly is an observability.ToolAnomalyListener publishing
+// tool.anomaly_detected. Calls made outside a tenant are not published.
+func (b *Bus) OnToolAnomaly(ctx context.Context, anomaly observability.ToolAnomaly) {
+	tenantID, err := uuid.Parse(anomaly.TenantID)
+	if err != nil {
+		return
+	}
+	b.Publish(context.WithoutCancel(ctx), tenantID, ToolAnomalyDetected, anomaly)
+}
+
+// Subscribe registers a webhook. A secret is generated when none is given.
+func (b *Bus) Subscribe(ctx context.Context, tenantID, actorID uuid.UUID, req SubscriptionRequest) (Subscription, error) {
+	url := strings.TrimSpace(req.URL)
+	if url == "" {
+		return Subscription{}, fmt.Errorf("%w: url is required", ErrInvalidSubscription)
+	}
+	if err := b.egress.CheckURL(url); err != nil {
+		return Subscription{}, fmt.Errorf("%w: %v", ErrInvalidSubscription, err)
+	}
+	types, err := normalizeTypes(req.EventTypes)
+	if err != nil {
+		return Subscription{}, err
+	}
+	secret := strings.TrimSpace(req.Secret)
+	if secret == "" {
+		buf := make([]byte, 32)
+		if _, err := rand.Read(buf); err != nil {
+			return Subscription{}, fmt.Errorf("generate subscription secret: %w", err)
+		}
+		secret = hex.EncodeToString(buf)
+	}
+	typesRaw, _ := json.Marshal(types)
+	out := Subscription{URL: url, EventTypes: types, Secret: secret, CreatedBy: actorID}
+	err = b.db.QueryRowContext(ctx, `
+INSERT INTO event_subscriptions (tenant_id, url, event_types, secret, created_by)
+VALUES ($1, $2, $3::jsonb, $4, $5)
+RETURNING id, created_at
+`, tenantID, url, string(typesRaw), secret, actorID).Scan(&out.ID, &out.CreatedAt)
+	if err != nil {
+		return Subscription{}, fmt.Errorf("create event subscription: %w", err)
+	}
+	out.CreatedAt = out.CreatedAt.UTC()
+	return out, nil
+}
+
+func normalizeTypes(in []string) ([]string, error) {
+	out := make([]string, 0, len(in))
+	seen := map[string]bool{}
+	for _, eventType := range in {
+		eventType = strings.TrimSpace(eventType)
+		known := false
+		for _, t := range Types {
+			known = known || t == eventType
+		}
+		if !known {
+			return nil, fmt.Errorf("%w: unknown event type %q", ErrInvalidSubscription, eventType)
+		}
+		if !seen[eventType] {
+			seen[eventType] = true
+			out = append(out, eventType)
+		}
+	}
+	if len(out) == 0 {
+		return nil, fmt.Errorf("%w: event_types is required", ErrInvalidSubscription)
+	}
+	return out, nil
+}
+
+// Subscriptions lists the tenant's webhooks, newest first, without secrets.
+func (b *Bus) Subscriptions(ctx context.Context, tenantID uuid.UUID) ([]Subscription, error) {
+	rows, err := b.db.QueryContext(ctx, `
+SELECT id, url, event_types, created_by, created_at
+FROM event_subscriptions
+WHERE tenant_id = $1
+ORDER BY created_at DESC
+`, tenantID)
+	if err != nil {
+		return nil, fmt.Errorf("list event subscriptions: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	out := make([]Subscription, 0)
+	for rows.Next() {
+		var (
+			sub      Subscription
+			typesRaw []byte
+		)
+		if err := rows.Scan(&sub.ID, &sub.URL, &typesRaw, &sub.CreatedBy, &sub.CreatedAt); err != nil {
+			return nil, fmt.Errorf("scan event subscription: %w", err)
+		}
+		_ = json.Unmarshal(typesRaw, &sub.EventTypes)
+		sub.CreatedAt = sub.CreatedAt.UTC()
+		out = append(out, sub)
+	}
+	return out, rows.Err()
+}
+
+// Unsubscribe deletes a webhook and its pending deliveries.
+func (b *Bus) Unsubscribe(ctx context.Context, tenantID, id uuid.UUID) error {
+	res, err := b.db.ExecContext(ctx, `DELETE FROM event_subscriptions WHERE id = $1 AND tenant_id = $2`, id, tenantID)
+	if err != nil {
+		return fmt.Errorf("delete event subscription: %w", err)
+	}
+	if affected, _ := res.RowsAffected(); affected == 0 {
+		return ErrSubscriptionNotFound
+	}
+	return nil
+}
+
+// Deliveries lists a subscription's most recent deliveries, newest first.
+func (b *Bus) Deliveries(ctx context.Context, tenantID, subscriptionID uuid.UUID, limit int) ([]Delivery, error) {
+	if limit <= 0 || limit > 200 {
+		limit = 50
+	}
+	var exists bool
+	if err := b.db.QueryRowContext(ctx, `
+SELECT EXISTS (SELECT 1 FROM event_subscriptions WHERE id = $1 AND tenant_id = $2)
+`, subscriptionID, tenantID).Scan(&exists); err != nil {
+		return nil, fmt.Errorf("load event subscription: %w", err)
+	}
+	if !exists {
+		return nil, ErrSubscriptionNotFound
+	}
+	rows, err := b.db.QueryContext(ctx, `
+SELECT d.id, d.event_id, e.type, d.status, d.attempts, d.response_status, d.last_error,
+       CASE WHEN d.status = 'pending' THEN d.next_attempt_at END, d.delivered_at, d.created_at
+FROM event_deliveries d
+JOIN events e ON e.id = d.event_id
+WHERE d.subscription_id = $1
+ORDER BY d.id DESC
+LIMIT $2
+`, subscriptionID, limit)
+	if err != nil {
+		return nil, fmt.Errorf("list event deliveries: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	out := make([]Delivery, 0)
+	for rows.Next() {
+		var (
+			d              Delivery
+			responseStatus sql.NullInt64
+			next, done     sql.NullTime
+		)
+		if err := rows.Scan(&d.ID, &d.EventID, &d.EventType, &d.Status, &d.Attempts, &responseStatus, &d.LastError, &next, &done, &d.CreatedAt); err != nil {
+			return nil, fmt.Errorf("scan event delivery: %w", err)
+		}
+		if responseStatus.Valid {
+			code := int(responseStatus.Int64)
+			d.ResponseStatus = &code
+		}
+		if next.Valid {
+			t := next.Time.UTC()
+			d.NextAttemptAt = &t
+		}
+		if done.Valid {
+			t := done.Time.UTC()
+			d.DeliveredAt = &t
+		}
+		d.CreatedAt = d.CreatedAt.UTC()
+		out = append(out, d)
+	}
+	return out, rows.Err()
+}
@@ -0,0 +1,75 @@
+package events
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"io"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+func TestNormalizeTypes(t *testing.T) {
+	got, err := normalizeTypes([]string{" workflow.created", "execution.failed", "workflow.created"})
+	if err != nil || len(got) != 2 || got[0] != WorkflowCreated || got[1] != ExecutionFailed {
+		t.Fatalf("normalizeTypes = %v, %v", got, err)
+	}
+	for _, bad := range [][]string{nil, {"flow.created"}} {
+		if _, err := normalizeTypes(bad); !errors.Is(err, ErrInvalidSubscription) {
+			t.Fatalf("expected ErrInvalidSubscription for %v, got %v", bad, err)
+		}
+	}
+}
+
+func TestBackoff(t *testing.T) {
+	for attempts, want := range map[int]time.Duration{1: 30 * time.Second, 2: time.Minute, 4: 4 * time.Minute, 20: time.Hour} {
+		if got := backoff(attempts); got != want {
+			t.Fatalf("backoff(%d) = %s, want %s", attempts, got, want)
+		}
+	}
+}
+
+func TestDeliveryLeaseCoversBatch(t *testing.T) {
+	if deliveryLease < deliveryBatch*deliveryTimeout {
+		t.Fatalf("delivery lease %s is shorter than a batch of timed-out requests (%s)", deliveryLease, deliveryBatch*deliveryTimeout)
+	}
+}
+
+func TestSendSignsEvent(t *testing.T) {
+	var (
+		body   []byte
+		header http.Header
+	)
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		body, _ = io.ReadAll(r.Body)
+		header = r.Header.Clone()
+		w.WriteHeader(http.StatusAccepted)
+	}))
+	defer srv.Close()
+
+	d := pendingDelivery{id: 7, url: srv.URL, secret: "s3cret", event: Event{ID: uuid.New(), Type: ExecutionFailed, Data: json.RawMessage(`{"step_id":"charge"}`)}}
+	status, err := send(context.Background(), srv.Client(), d)
+	if err != nil || status != http.StatusAccepted {
+		t.Fatalf("send = %d, %v", status, err)
+	}
+	if header.Get(SignatureHeader) != Sign("s3cret", body) || header.Get(EventHeader) != ExecutionFailed || header.Get(DeliveryHeader) != "7" {
+		t.Fatalf("unexpected headers %v", header)
+	}
+	var got Event
+	if err := json.Unmarshal(body, &got); err != nil || got.ID != d.event.ID || string(got.Data) != `{"step_id":"charge"}` {
+		t.Fatalf("unexpected body %s", body)
+	}
+
+	failing := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+		w.WriteHeader(http.StatusServiceUnavailable)
+	}))
+	defer failing.Close()
+	d.url = failing.URL
+	if status, err := send(context.Background(), failing.Client(), d); err == nil || status != http.StatusServiceUnavailable {
+		t.Fatalf("expected a retryable failure, got %d, %v", status, err)
+	}
+}
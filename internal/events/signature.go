@@ -0,0 +1,28 @@
+package events
+
+import (
+	"crypto/hmac"
+	"crypto/sha256"
+	"encoding/hex"
+	"net/http"
+)
+
+// Sign returns the hex HMAC-SHA256 of body keyed with secret, the value sent
+// in SignatureHeader.
+func Sign(secret string, body []byte) string {
+	mac := hmac.New(sha256.New, []byte(secret))
+	mac.Write(body)
+	return hex.EncodeToString(mac.Sum(nil))
+}
+
+// SetWebhookHeaders sets the headers every outgoing Aceryx webhook carries:
+// the event type, a delivery ID receivers can deduplicate on, and, when
+// secret is set, the body signature.
+func SetWebhookHeaders(h http.Header, eventType, deliveryID, secret string, body []byte) {
+	h.Set("Content-Type", "application/json")
+	h.Set(EventHeader, eventType)
+	h.Set(DeliveryHeader, deliveryID)
+	if secret != "" {
+		h.Set(SignatureHeader, Sign(secret, body))
+	}
+}
@@ -0,0 +1,249 @@
+package features
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"log/slog"
+	"os"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+const (
+	SourceDefault = "default"
+	SourceConfig  = "config"
+	SourceTenant  = "tenant"
+
+	defaultCacheTTL = 5 * time.Second
+)
+
+var ErrUnknownFlag = errors.New("unknown feature flag")
+
+// Flag describes a toggleable capability. Step flags are keyed
+// "steps.<step type>" and checked by the engine before dispatch.
+type Flag struct {
+	Key         string `json:"key"`
+	Description string `json:"description"`
+	Default     bool   `json:"default"`
+}
+
+// State is a flag's effective value for one tenant and where it came from.
+type State struct {
+	Key         string `json:"key"`
+	Description string `json:"description"`
+	Enabled     bool   `json:"enabled"`
+	Default     bool   `json:"default"`
+	Source      string `json:"source"`
+}
+
+// Known lists every flag the server understands, in display order.
+var Known = []Flag{
+	{Key: StepFlag("agent"), Description: "Single-shot LLM agent steps", Default: true},
+	{Key: StepFlag("agentic"), Description: "Multi-iteration agentic reasoning steps", Default: true},
+	{Key: StepFlag("ai_component"), Description: "Registered AI component steps", Default: true},
+	{Key: StepFlag("custom-tool"), Description: "Steps calling user-defined HTTP tools", Default: true},
+	{Key: StepFlag("extraction"), Description: "Document extraction steps", Default: true},
+	{Key: StepFlag("grpc-client"), Description: "Steps calling gRPC services via reflection", Default: true},
+	{Key: StepFlag("integration"), Description: "Connector integration steps", Default: true},
+	{Key: StepFlag("mcp-client"), Description: "Steps calling external MCP servers", Default: true},
+	{Key: StepFlag("plugin"), Description: "WASM plugin steps", Default: true},
+	{Key: StepFlag("script"), Description: "Sandboxed JavaScript glue steps", Default: false},
+	{Key: StepFlag("sub_workflow"), Description: "Steps running another case type's workflow", Default: true},
+}
+
+// StepFlag returns the flag key gating a workflow step type.
+func StepFlag(stepType string) string { return "steps." + stepType }
+
+// Store persists per-tenant overrides and records each change in the
+// tenant's audit log.
+type Store interface {
+	TenantOverrides(ctx context.Context, tenantID uuid.UUID) (map[string]bool, error)
+	SetOverride(ctx context.Context, tenantID uuid.UUID, key string, enabled bool, actorID uuid.UUID) error
+	ClearOverride(ctx context.Context, tenantID uuid.UUID, key string, actorID uuid.UUID) error
+}
+
+type cachedOverrides struct {
+	values    map[string]bool
+	expiresAt time.Time
+}
+
+// Service resolves flags: tenant override, then server config, then the
+// flag default. Keys not in Known are always enabled so unflagged
+// subsystems are never blocked.
+type Service struct {
+	store  Store
+	flags  map[string]Flag
+	config map[string]bool
+	ttl    time.Duration
+	now    func() time.Time
+
+	mu    sync.Mutex
+	cache map[uuid.UUID]cachedOverrides
+}
+
+func NewService(store Store, config map[string]bool) *Service {
+	flags := make(map[string]Flag, len(Known))
+	for _, flag := range Known {
+		flags[flag.Key] = flag
+	}
+	if config == nil {
+		config = map[string]bool{}
+	}
+	return &Service{
+		store:  store,
+		flags:  flags,
+		config: config,
+		ttl:    defaultCacheTTL,
+		now:    time.Now,
+		cache:  map[uuid.UUID]cachedOverrides{},
+	}
+}
+
+// ParseConfig reads a comma-separated list such as
+// "steps.plugin=off,steps.agentic". A bare key means on.
+func ParseConfig(raw string) (map[string]bool, error) {
+	out := map[string]bool{}
+	for _, item := range strings.Split(raw, ",") {
+		item = strings.TrimSpace(item)
+		if item == "" {
+			continue
+		}
+		key, value, hasValue := strings.Cut(item, "=")
+		key = strings.TrimSpace(key)
+		enabled := true
+		if hasValue {
+			switch strings.ToLower(strings.TrimSpace(value)) {
+			case "1", "true", "on", "yes":
+				enabled = true
+			case "0", "false", "off", "no":
+				enabled = false
+			default:
+				return nil, fmt.Errorf("feature %s: invalid value %q", key, value)
+			}
+		}
+		out[key] = enabled
+	}
+	return out, nil
+}
+
+// ConfigFromEnv reads ACERYX_FEATURES.
+func ConfigFromEnv() (map[string]bool, error) {
+	cfg, err := ParseConfig(os.Getenv("ACERYX_FEATURES"))
+	if err != nil {
+		return nil, fmt.Errorf("ACERYX_FEATURES: %w", err)
+	}
+	return cfg, nil
+}
+
+func (s *Service) Enabled(ctx context.Context, tenantID uuid.UUID, key string) bool {
+	if s == nil {
+		return true
+	}
+	flag, ok := s.flags[key]
+	if !ok {
+		return true
+	}
+	return s.resolve(flag, s.overrides(ctx, tenantID)).Enabled
+}
+
+// Snapshot returns the effective value of every known flag, for recording
+// alongside run metadata.
+func (s *Service) Snapshot(ctx context.Context, tenantID uuid.UUID) map[string]bool {
+	if s == nil {
+		return nil
+	}
+	overrides := s.overrides(ctx, tenantID)
+	out := make(map[string]bool, len(Known))
+	for _, flag := range Known {
+		out[flag.Key] = s.resolve(flag, overrides).Enabled
+	}
+	return out
+}
+
+func (s *Service) List(ctx context.Context, tenantID uuid.UUID) ([]State, error) {
+	overrides, err := s.loadOverrides(ctx, tenantID)
+	if err != nil {
+		return nil, err
+	}
+	out := make([]State, 0, len(Known))
+	for _, flag := range Known {
+		out = append(out, s.resolve(flag, overrides))
+	}
+	return out, nil
+}
+
+// Set stores a tenant override; a nil enabled clears it so the tenant follows
+// server configuration again.
+func (s *Service) Set(ctx context.Context, tenantID uuid.UUID, key string, enabled *bool, actorID uuid.UUID) (State, error) {
+	flag, ok := s.flags[key]
+	if !ok {
+		return State{}, fmt.Errorf("%w: %s", ErrUnknownFlag, key)
+	}
+	if s.store == nil {
+		return State{}, fmt.Errorf("feature flag storage not configured")
+	}
+	var err error
+	if enabled == nil {
+		err = s.store.ClearOverride(ctx, tenantID, key, actorID)
+	} else {
+		err = s.store.SetOverride(ctx, tenantID, key, *enabled, actorID)
+	}
+	if err != nil {
+		return State{}, err
+	}
+	s.mu.Lock()
+	delete(s.cache, tenantID)
+	s.mu.Unlock()
+	overrides, err := s.loadOverrides(ctx, tenantID)
+	if err != nil {
+		return State{}, err
+	}
+	return s.resolve(flag, overrides), nil
+}
+
+func (s *Service) resolve(flag Flag, overrides map[string]bool) State {
+	state := State{Key: flag.Key, Description: flag.Description, Enabled: flag.Default, Default: flag.Default, Source: SourceDefault}
+	if v, ok := s.config[flag.Key]; ok {
+		state.Enabled, state.Source = v, SourceConfig
+	}
+	if v, ok := overrides[flag.Key]; ok {
+		state.Enabled, state.Source = v, SourceTenant
+	}
+	return state
+}
+
+// overrides never fails: a storage error falls back to server configuration
+// rather than blocking the caller.
+func (s *Service) overrides(ctx context.Context, tenantID uuid.UUID) map[string]bool {
+	overrides, err := s.loadOverrides(ctx, tenantID)
+	if err != nil {
+		slog.WarnContext(ctx, "feature flag overrides unavailable", "tenant_id", tenantID.String(), "error", err)
+		return nil
+	}
+	return overrides
+}
+
+func (s *Service) loadOverrides(ctx context.Context, tenantID uuid.UUID) (map[string]bool, error) {
+	if s.store == nil {
+		return nil, nil
+	}
+	now := s.now()
+	s.mu.Lock()
+	cached, ok := s.cache[tenantID]
+	s.mu.Unlock()
+	if ok && now.Before(cached.expiresAt) {
+		return cached.values, nil
+	}
+	values, err := s.store.TenantOverrides(ctx, tenantID)
+	if err != nil {
+		return nil, err
+	}
+	s.mu.Lock()
+	s.cache[tenantID] = cachedOverrides{values: values, expiresAt: now.Add(s.ttl)}
+	s.mu.Unlock()
+	return values, nil
+}
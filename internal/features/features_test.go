@@ -0,0 +1,123 @@
+package features
+
+import (
+	"context"
+	"errors"
+	"testing"
+
+	"github.com/google/uuid"
+)
+
+type memoryStore struct {
+	overrides map[uuid.UUID]map[string]bool
+	reads     int
+	err       error
+}
+
+func (m *memoryStore) TenantOverrides(_ context.Context, tenantID uuid.UUID) (map[string]bool, error) {
+	m.reads++
+	if m.err != nil {
+		return nil, m.err
+	}
+	out := map[string]bool{}
+	for k, v := range m.overrides[tenantID] {
+		out[k] = v
+	}
+	return out, nil
+}
+
+func (m *memoryStore) SetOverride(_ context.Context, tenantID uuid.UUID, key string, enabled bool, _ uuid.UUID) error {
+	if m.overrides[tenantID] == nil {
+		m.overrides[tenantID] = map[string]bool{}
+	}
+	m.overrides[tenantID][key] = enabled
+	return nil
+}
+
+func (m *memoryStore) ClearOverride(_ context.Context, tenantID uuid.UUID, key string, _ uuid.UUID) error {
+	delete(m.overrides[tenantID], key)
+	return nil
+}
+
+func TestParseConfig(t *testing.T) {
+	cfg, err := ParseConfig(" steps.plugin=off, steps.agentic ,steps.agent=TRUE")
+	if err != nil {
+		t.Fatalf("parse: %v", err)
+	}
+	if cfg["steps.plugin"] || !cfg["steps.agentic"] || !cfg["steps.agent"] {
+		t.Fatalf("unexpected config: %#v", cfg)
+	}
+	if _, err := ParseConfig("steps.plugin=maybe"); err == nil {
+		t.Fatal("expected invalid value to be rejected")
+	}
+}
+
+func TestConfigFromEnv(t *testing.T) {
+	t.Setenv("ACERYX_FEATURES", "steps.script=on")
+	if cfg, err := ConfigFromEnv(); err != nil || !cfg["steps.script"] {
+		t.Fatalf("unexpected config %#v, %v", cfg, err)
+	}
+	t.Setenv("ACERYX_FEATURES", "steps.script=maybe")
+	if _, err := ConfigFromEnv(); err == nil {
+		t.Fatal("expected an invalid value error")
+	}
+}
+
+func TestServiceResolvesTenantOverConfigOverDefault(t *testing.T) {
+	store := &memoryStore{overrides: map[uuid.UUID]map[string]bool{}}
+	svc := NewService(store, map[string]bool{StepFlag("plugin"): false})
+	ctx := context.Background()
+	tenant, other := uuid.New(), uuid.New()
+
+	if svc.Enabled(ctx, tenant, StepFlag("plugin")) {
+		t.Fatal("expected config to disable plugin steps")
+	}
+	if !svc.Enabled(ctx, tenant, "steps.unknown") {
+		t.Fatal("unknown flags must default to enabled")
+	}
+
+	on := true
+	state, err := svc.Set(ctx, tenant, StepFlag("plugin"), &on, uuid.New())
+	if err != nil {
+		t.Fatalf("set: %v", err)
+	}
+	if !state.Enabled || state.Source != SourceTenant {
+		t.Fatalf("unexpected state after override: %#v", state)
+	}
+	if !svc.Enabled(ctx, tenant, StepFlag("plugin")) || svc.Enabled(ctx, other, StepFlag("plugin")) {
+		t.Fatal("override must apply only to its tenant")
+	}
+	if snapshot := svc.Snapshot(ctx, tenant); len(snapshot) != len(Known) || !snapshot[StepFlag("plugin")] {
+		t.Fatalf("unexpected snapshot: %#v", snapshot)
+	}
+
+	state, err = svc.Set(ctx, tenant, StepFlag("plugin"), nil, uuid.New())
+	if err != nil || state.Enabled || state.Source != SourceConfig {
+		t.Fatalf("expected cleared override to fall back to config, got %#v err=%v", state, err)
+	}
+	if _, err := svc.Set(ctx, tenant, "nope", &on, uuid.New()); !errors.Is(err, ErrUnknownFlag) {
+		t.Fatalf("expected ErrUnknownFlag, got %v", err)
+	}
+}
+
+func TestServiceCachesAndFailsOpen(t *testing.T) {
+	store := &memoryStore{overrides: map[uuid.UUID]map[string]bool{}}
+	svc := NewService(store, nil)
+	ctx := context.Background()
+	tenant := uuid.New()
+
+	for i := 0; i < 3; i++ {
+		svc.Enabled(ctx, tenant, StepFlag("agentic"))
+	}
+	if store.reads != 1 {
+		t.Fatalf("expected cached overrides, got %d reads", store.reads)
+	}
+
+	store.err = errors.New("db down")
+	if !svc.Enabled(ctx, uuid.New(), StepFlag("agentic")) {
+		t.Fatal("storage errors must fall back to defaults")
+	}
+	if _, err := svc.List(ctx, uuid.New()); err == nil {
+		t.Fatal("expected List to surface storage errors")
+	}
+}
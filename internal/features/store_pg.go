@@ -0,0 +1,88 @@
+package features
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"fmt"
+
+	"github.com/google/uuid"
+)
+
+type PostgresStore struct {
+	db *sql.DB
+}
+
+func NewPostgresStore(db *sql.DB) *PostgresStore {
+	return &PostgresStore{db: db}
+}
+
+func (s *PostgresStore) TenantOverrides(ctx context.Context, tenantID uuid.UUID) (map[string]bool, error) {
+	rows, err := s.db.QueryContext(ctx, `SELECT key, enabled FROM tenant_feature_flags WHERE tenant_id = $1`, tenantID)
+	if err != nil {
+		return nil, fmt.Errorf("list feature flag overrides: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	out := map[string]bool{}
+	for rows.Next() {
+		var key string
+		var enabled bool
+		if err := rows.Scan(&key, &enabled); err != nil {
+			return nil, fmt.Errorf("scan feature flag override: %w", err)
+		}
+		out[key] = enabled
+	}
+	if err := rows.Err(); err != nil {
+		return nil, fmt.Errorf("iterate feature flag overrides: %w", err)
+	}
+	return out, nil
+}
+
+// SetOverride stores the tenant's override and records the change in
+// auth_events, the tenant's administrative audit log, in one transaction.
+func (s *PostgresStore) SetOverride(ctx context.Context, tenantID uuid.UUID, key string, enabled bool, actorID uuid.UUID) error {
+	return s.change(ctx, tenantID, key, &enabled, actorID, `
+INSERT INTO tenant_feature_flags (tenant_id, key, enabled, updated_by, updated_at)
+VALUES ($1, $2, $3, $4, now())
+ON CONFLICT (tenant_id, key)
+DO UPDATE SET enabled = EXCLUDED.enabled, updated_by = EXCLUDED.updated_by, updated_at = now()
+`, tenantID, key, enabled, nullableActor(actorID))
+}
+
+// ClearOverride removes the tenant's override, recording the change like
+// SetOverride.
+func (s *PostgresStore) ClearOverride(ctx context.Context, tenantID uuid.UUID, key string, actorID uuid.UUID) error {
+	return s.change(ctx, tenantID, key, nil, actorID, `DELETE FROM tenant_feature_flags WHERE tenant_id = $1 AND key = $2`, tenantID, key)
+}
+
+func (s *PostgresStore) change(ctx context.Context, tenantID uuid.UUID, key string, enabled *bool, actorID uuid.UUID, query string, args ...any) error {
+	data, err := json.Marshal(map[string]any{"key": key, "enabled": enabled})
+	if err != nil {
+		return fmt.Errorf("marshal feature flag event: %w", err)
+	}
+	tx, err := s.db.BeginTx(ctx, nil)
+	if err != nil {
+		return fmt.Errorf("begin feature flag tx: %w", err)
+	}
+	defer func() { _ = tx.Rollback() }()
+	if _, err := tx.ExecContext(ctx, query, args...); err != nil {
+		return fmt.Errorf("update feature flag override: %w", err)
+	}
+	if _, err := tx.ExecContext(ctx, `
+INSERT INTO auth_events (tenant_id, principal_id, event_type, success, data)
+VALUES ($1, $2, 'feature_flag_changed', true, $3::jsonb)
+`, tenantID, nullableActor(actorID), string(data)); err != nil {
+		return fmt.Errorf("record feature flag event: %w", err)
+	}
+	if err := tx.Commit(); err != nil {
+		return fmt.Errorf("commit feature flag override: %w", err)
+	}
+	return nil
+}
+
+func nullableActor(actorID uuid.UUID) any {
+	if actorID == uuid.Nil {
+		return nil
+	}
+	return actorID
+}
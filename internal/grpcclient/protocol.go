@@ -0,0 +1,401 @@
+package grpcclient
+
+import (
+	"context"
+	"crypto/tls"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net"
+	"net/http"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/observability"
+	"github.com/neural-chilli/aceryx/internal/toolpool"
+	"google.golang.org/grpc"
+	"google.golang.org/grpc/codes"
+	"google.golang.org/grpc/credentials"
+	"google.golang.org/grpc/credentials/insecure"
+	"google.golang.org/grpc/metadata"
+	reflectionpb "google.golang.org/grpc/reflection/grpc_reflection_v1"
+	"google.golang.org/grpc/status"
+	"google.golang.org/protobuf/encoding/protojson"
+	"google.golang.org/protobuf/proto"
+	"google.golang.org/protobuf/reflect/protodesc"
+	"google.golang.org/protobuf/reflect/protoreflect"
+	"google.golang.org/protobuf/reflect/protoregistry"
+	"google.golang.org/protobuf/types/descriptorpb"
+	"google.golang.org/protobuf/types/dynamicpb"
+)
+
+// ProtocolName identifies gRPC instances in the tool pool.
+const ProtocolName = "grpc"
+
+// grpcInstance is a pooled connection to a gRPC server with the descriptors
+// reflected over it.
+type grpcInstance struct {
+	conn     *grpc.ClientConn
+	files    *protoregistry.Files
+	services []string
+}
+
+func (i *grpcInstance) Close() error {
+	return i.conn.Close()
+}
+
+// GrpcProtocol discovers unary methods on servers that expose the gRPC
+// server reflection service (grpc.reflection.v1) and invokes them with JSON
+// arguments, transcoding to and from protobuf on the fly. Connections and
+// descriptors are kept in a tool pool and reused until they expire. Every
+// connection is subject to the egress policy.
+type GrpcProtocol struct {
+	pool   *toolpool.Pool
+	egress connectors.EgressPolicy
+}
+
+func NewGrpcProtocol() *GrpcProtocol {
+	return NewGrpcProtocolWithEgress(connectors.EgressPolicy{})
+}
+
+// NewGrpcProtocolWithEgress returns a protocol restricted by the given
+// egress policy.
+func NewGrpcProtocolWithEgress(policy connectors.EgressPolicy) *GrpcProtocol {
+	return &GrpcProtocol{
+		pool:   toolpool.New(toolpool.DefaultMaxSize, map[string]time.Duration{ProtocolName: defaultDescriptorTTL}),
+		egress: policy,
+	}
+}
+
+// SetPool shares pool with other protocols in place of the protocol's own.
+func (p *GrpcProtocol) SetPool(pool *toolpool.Pool) {
+	if pool != nil {
+		p.pool = pool
+	}
+}
+
+// Discover lists the server's unary methods as tools. Streaming methods and
+// the reflection service itself are omitted.
+func (p *GrpcProtocol) Discover(ctx context.Context, target Target) ([]Tool, error) {
+	desc, release, err := p.instance(ctx, target, true)
+	if err != nil {
+		return nil, err
+	}
+	defer release()
+	tools := make([]Tool, 0)
+	for _, serviceName := range desc.services {
+		found, err := desc.files.FindDescriptorByName(protoreflect.FullName(serviceName))
+		if err != nil {
+			return nil, fmt.Errorf("resolve service %s: %w", serviceName, err)
+		}
+		service, ok := found.(protoreflect.ServiceDescriptor)
+		if !ok {
+			continue
+		}
+		methods := service.Methods()
+		for i := 0; i < methods.Len(); i++ {
+			method := methods.Get(i)
+			if method.IsStreamingClient() || method.IsStreamingServer() {
+				continue
+			}
+			input, err := json.Marshal(MessageSchema(method.Input()))
+			if err != nil {
+				return nil, fmt.Errorf("marshal input schema for %s: %w", method.FullName(), err)
+			}
+			output, err := json.Marshal(MessageSchema(method.Output()))
+			if err != nil {
+				return nil, fmt.Errorf("marshal output schema for %s: %w", method.FullName(), err)
+			}
+			tools = append(tools, Tool{
+				Name:         methodName(method),
+				Service:      string(service.FullName()),
+				Method:       string(method.Name()),
+				Description:  fmt.Sprintf("%s.%s(%s) returns %s", service.Name(), method.Name(), method.Input().FullName(), method.Output().FullName()),
+				InputSchema:  input,
+				OutputSchema: output,
+			})
+		}
+	}
+	return tools, nil
+}
+
+// Invoke calls a unary method. Arguments are protojson (proto or JSON field
+// names); the response is protojson with proto field names and defaults
+// populated.
+func (p *GrpcProtocol) Invoke(ctx context.Context, req InvokeRequest) (json.RawMessage, error) {
+	desc, release, err := p.instance(ctx, req.Target, false)
+	if err != nil {
+		return nil, err
+	}
+	defer release()
+	method, err := findMethod(desc.files, req.Method)
+	if err != nil {
+		return nil, err
+	}
+	if method.IsStreamingClient() || method.IsStreamingServer() {
+		return nil, fmt.Errorf("method %s is streaming; only unary methods are supported", req.Method)
+	}
+
+	in := dynamicpb.NewMessage(method.Input())
+	args := req.Arguments
+	if len(strings.TrimSpace(string(args))) == 0 {
+		args = json.RawMessage(`{}`)
+	}
+	if err := (protojson.UnmarshalOptions{Resolver: dynamicResolver(desc.files)}).Unmarshal(args, in); err != nil {
+		return nil, fmt.Errorf("encode %s request: %w", method.Input().FullName(), err)
+	}
+
+	timeout := req.Timeout
+	if timeout <= 0 {
+		timeout = defaultInvokeTimeout
+	}
+	ctx, cancel := context.WithTimeout(ctx, timeout)
+	defer cancel()
+
+	out := dynamicpb.NewMessage(method.Output())
+	if err := desc.conn.Invoke(outgoingContext(ctx, req.Target), "/"+methodName(method), in, out); err != nil {
+		if status.Code(err) == codes.Unavailable {
+			p.pool.Evict(ProtocolName, poolKey(req.Target))
+		}
+		return nil, fmt.Errorf("invoke %s: %w", methodName(method), err)
+	}
+	raw, err := (protojson.MarshalOptions{UseProtoNames: true, EmitUnpopulated: true, Resolver: dynamicResolver(desc.files)}).Marshal(out)
+	if err != nil {
+		return nil, fmt.Errorf("decode %s response: %w", method.Output().FullName(), err)
+	}
+	return raw, nil
+}
+
+// instance acquires the pooled connection for target, opening it and
+// fetching descriptors when there is none. refresh replaces a pooled
+// instance so that discovery always sees the server's current services.
+func (p *GrpcProtocol) instance(ctx context.Context, target Target, refresh bool) (*grpcInstance, func(), error) {
+	if strings.TrimSpace(target.Address) == "" {
+		return nil, nil, fmt.Errorf("address is required")
+	}
+	key := poolKey(target)
+	if refresh {
+		p.pool.Evict(ProtocolName, key)
+	}
+	instance, release, err := p.pool.Acquire(ProtocolName, key, func() (toolpool.Instance, error) {
+		conn, err := p.dial(target)
+		if err != nil {
+			return nil, err
+		}
+		files, services, err := fetchDescriptors(ctx, conn, target)
+		if err != nil {
+			_ = conn.Close()
+			return nil, err
+		}
+		return &grpcInstance{conn: conn, files: files, services: services}, nil
+	})
+	if err != nil {
+		return nil, nil, err
+	}
+	return instance.(*grpcInstance), release, nil
+}
+
+func poolKey(target Target) string {
+	return fmt.Sprintf("%s|tls=%t", strings.TrimSpace(target.Address), target.TLS)
+}
+
+// fetchDescriptors walks the reflection service: list services, fetch the
+// file defining each, then pull any dependencies not yet seen.
+func fetchDescriptors(ctx context.Context, conn *grpc.ClientConn, target Target) (*protoregistry.Files, []string, error) {
+	ctx, cancel := context.WithTimeout(ctx, defaultDialTimeout)
+	defer cancel()
+
+	stream, err := reflectionpb.NewServerReflectionClient(conn).ServerReflectionInfo(outgoingContext(ctx, target))
+	if err != nil {
+		return nil, nil, fmt.Errorf("open reflection stream: %w", err)
+	}
+	defer func() { _ = stream.CloseSend() }()
+
+	resp, err := reflectionCall(stream, &reflectionpb.ServerReflectionRequest{
+		MessageRequest: &reflectionpb.ServerReflectionRequest_ListServices{ListServices: "*"},
+	})
+	if err != nil {
+		return nil, nil, fmt.Errorf("list services: %w", err)
+	}
+	services := make([]string, 0)
+	for _, svc := range resp.GetListServicesResponse().GetService() {
+		if strings.HasPrefix(svc.GetName(), reflectionServicePrefix) {
+			continue
+		}
+		services = append(services, svc.GetName())
+	}
+	sort.Strings(services)
+
+	seen := map[string]*descriptorpb.FileDescriptorProto{}
+	pending := []string{}
+	collect := func(resp *reflectionpb.ServerReflectionResponse) error {
+		for _, raw := range resp.GetFileDescriptorResponse().GetFileDescriptorProto() {
+			fd := &descriptorpb.FileDescriptorProto{}
+			if err := proto.Unmarshal(raw, fd); err != nil {
+				return fmt.Errorf("decode file descriptor: %w", err)
+			}
+			if _, ok := seen[fd.GetName()]; ok {
+				continue
+			}
+			seen[fd.GetName()] = fd
+			pending = append(pending, fd.GetDependency()...)
+		}
+		return nil
+	}
+	for _, svc := range services {
+		resp, err := reflectionCall(stream, &reflectionpb.ServerReflectionRequest{
+			MessageRequest: &reflectionpb.ServerReflectionRequest_FileContainingSymbol{FileContainingSymbol: svc},
+		})
+		if err != nil {
+			return nil, nil, fmt.Errorf("describe service %s: %w", svc, err)
+		}
+		if err := collect(resp); err != nil {
+			return nil, nil, err
+		}
+	}
+	for len(pending) > 0 {
+		name := pending[0]
+		pending = pending[1:]
+		if _, ok := seen[name]; ok {
+			continue
+		}
+		resp, err := reflectionCall(stream, &reflectionpb.ServerReflectionRequest{
+			MessageRequest: &reflectionpb.ServerReflectionRequest_FileByFilename{FileByFilename: name},
+		})
+		if err != nil {
+			return nil, nil, fmt.Errorf("fetch file %s: %w", name, err)
+		}
+		if err := collect(resp); err != nil {
+			return nil, nil, err
+		}
+	}
+
+	set := &descriptorpb.FileDescriptorSet{}
+	for _, fd := range seen {
+		set.File = append(set.File, fd)
+	}
+	files, err := protodesc.NewFiles(set)
+	if err != nil {
+		return nil, nil, fmt.Errorf("build descriptor registry: %w", err)
+	}
+	return files, services, nil
+}
+
+func reflectionCall(stream reflectionpb.ServerReflection_ServerReflectionInfoClient, req *reflectionpb.ServerReflectionRequest) (*reflectionpb.ServerReflectionResponse, error) {
+	if err := stream.Send(req); err != nil {
+		return nil, err
+	}
+	resp, err := stream.Recv()
+	if err == io.EOF {
+		return nil, fmt.Errorf("reflection stream closed")
+	}
+	if err != nil {
+		return nil, err
+	}
+	if errResp := resp.GetErrorResponse(); errResp != nil {
+		return nil, fmt.Errorf("reflection error %d: %s", errResp.GetErrorCode(), errResp.GetErrorMessage())
+	}
+	return resp, nil
+}
+
+func findMethod(files *protoregistry.Files, name string) (protoreflect.MethodDescriptor, error) {
+	name = strings.TrimPrefix(strings.TrimSpace(name), "/")
+	serviceName, methodShort, ok := strings.Cut(name, "/")
+	if !ok {
+		// Accept "package.Service.Method" as well as "package.Service/Method".
+		idx := strings.LastIndex(name, ".")
+		if idx < 0 {
+			return nil, fmt.Errorf("method %q must be package.Service/Method", name)
+		}
+		serviceName, methodShort = name[:idx], name[idx+1:]
+	}
+	found, err := files.FindDescriptorByName(protoreflect.FullName(serviceName))
+	if err != nil {
+		return nil, fmt.Errorf("service %s not found", serviceName)
+	}
+	service, ok := found.(protoreflect.ServiceDescriptor)
+	if !ok {
+		return nil, fmt.Errorf("%s is not a service", serviceName)
+	}
+	method := service.Methods().ByName(protoreflect.Name(methodShort))
+	if method == nil {
+		return nil, fmt.Errorf("method %s not found on %s", methodShort, serviceName)
+	}
+	return method, nil
+}
+
+func methodName(method protoreflect.MethodDescriptor) string {
+	return string(method.Parent().FullName()) + "/" + string(method.Name())
+}
+
+// dial checks the target's host against the egress policy, then connects
+// through the policy's dialer so every address the name resolves to is
+// checked as well.
+func (p *GrpcProtocol) dial(target Target) (*grpc.ClientConn, error) {
+	address := strings.TrimSpace(target.Address)
+	if err := p.egress.CheckHost(targetHost(address)); err != nil {
+		return nil, err
+	}
+	creds := insecure.NewCredentials()
+	if target.TLS {
+		creds = credentials.NewTLS(&tls.Config{MinVersion: tls.VersionTLS12})
+	}
+	dialer := func(ctx context.Context, addr string) (net.Conn, error) {
+		return p.egress.DialContext(ctx, "tcp", addr)
+	}
+	conn, err := grpc.NewClient(address, grpc.WithTransportCredentials(creds), grpc.WithContextDialer(dialer))
+	if err != nil {
+		return nil, fmt.Errorf("dial %s: %w", target.Address, err)
+	}
+	return conn, nil
+}
+
+// targetHost returns the host of a gRPC target such as host:port or
+// dns:///host:port.
+func targetHost(address string) string {
+	if i := strings.LastIndex(address, "/"); i >= 0 {
+		address = address[i+1:]
+	}
+	if host, _, err := net.SplitHostPort(address); err == nil {
+		return host
+	}
+	return address
+}
+
+func outgoingContext(ctx context.Context, target Target) context.Context {
+	headers := http.Header{}
+	for k, v := range target.Metadata {
+		headers.Set(k, v)
+	}
+	observability.InjectCorrelationHeaders(ctx, headers)
+	observability.InjectTraceHeaders(ctx, headers)
+	pairs := make([]string, 0, len(headers)*2)
+	for k := range headers {
+		pairs = append(pairs, strings.ToLower(k), headers.Get(k))
+	}
+	if len(pairs) == 0 {
+		return ctx
+	}
+	return metadata.AppendToOutgoingContext(ctx, pairs...)
+}
+
+// dynamicResolver resolves google.protobuf.Any payloads against the
+// server's descriptors rather than the types linked into this binary.
+func dynamicResolver(files *protoregistry.Files) *protoregistry.Types {
+	registry := new(protoregistry.Types)
+	files.RangeFiles(func(fd protoreflect.FileDescriptor) bool {
+		registerMessages(registry, fd.Messages())
+		return true
+	})
+	return registry
+}
+
+func registerMessages(registry *protoregistry.Types, messages protoreflect.MessageDescriptors) {
+	for i := 0; i < messages.Len(); i++ {
+		md := messages.Get(i)
+		_ = registry.RegisterMessage(dynamicpb.NewMessageType(md))
+		registerMessages(registry, md.Messages())
+	}
+}
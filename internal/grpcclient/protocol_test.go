@@ -0,0 +1,112 @@
+package grpcclient
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"net"
+	"testing"
+
+	"github.com/neural-chilli/aceryx/internal/connectors"
+	"google.golang.org/grpc"
+	"google.golang.org/grpc/health"
+	healthpb "google.golang.org/grpc/health/grpc_health_v1"
+	"google.golang.org/grpc/reflection"
+)
+
+func startReflectionServer(t *testing.T) string {
+	t.Helper()
+	lis, err := net.Listen("tcp", "127.0.0.1:0")
+	if err != nil {
+		t.Fatalf("listen: %v", err)
+	}
+	srv := grpc.NewServer()
+	healthpb.RegisterHealthServer(srv, health.NewServer())
+	reflection.Register(srv)
+	go func() { _ = srv.Serve(lis) }()
+	t.Cleanup(srv.Stop)
+	return lis.Addr().String()
+}
+
+// newLocalProtocol may reach the loopback servers these tests start.
+func newLocalProtocol() *GrpcProtocol {
+	return NewGrpcProtocolWithEgress(connectors.EgressPolicy{AllowPrivate: true})
+}
+
+func TestDialAppliesEgressPolicy(t *testing.T) {
+	addr := startReflectionServer(t)
+	_, err := NewGrpcProtocol().Discover(context.Background(), Target{Address: addr})
+	if !errors.Is(err, connectors.ErrEgressBlocked) {
+		t.Fatalf("expected a loopback address to be blocked, got %v", err)
+	}
+	_, port, _ := net.SplitHostPort(addr)
+	if _, err := NewGrpcProtocol().Discover(context.Background(), Target{Address: "localhost:" + port}); err == nil {
+		t.Fatal("expected a name resolving to loopback to be blocked")
+	}
+	allowList := NewGrpcProtocolWithEgress(connectors.EgressPolicy{AllowPrivate: true, AllowedDomains: []string{"example.com"}})
+	if _, err := allowList.Discover(context.Background(), Target{Address: addr}); !errors.Is(err, connectors.ErrEgressBlocked) {
+		t.Fatalf("expected a host outside the allowed domains to be blocked, got %v", err)
+	}
+}
+
+func TestDiscoverListsUnaryMethods(t *testing.T) {
+	addr := startReflectionServer(t)
+	tools, err := newLocalProtocol().Discover(context.Background(), Target{Address: addr})
+	if err != nil {
+		t.Fatalf("Discover: %v", err)
+	}
+	byName := map[string]Tool{}
+	for _, tool := range tools {
+		byName[tool.Name] = tool
+	}
+	check, ok := byName["grpc.health.v1.Health/Check"]
+	if !ok {
+		t.Fatalf("expected Health/Check in %v", tools)
+	}
+	if _, ok := byName["grpc.health.v1.Health/Watch"]; ok {
+		t.Fatalf("streaming Watch method should be skipped")
+	}
+	for name := range byName {
+		if name == "grpc.reflection.v1.ServerReflection/ServerReflectionInfo" {
+			t.Fatalf("reflection service should be hidden")
+		}
+	}
+	var schema map[string]any
+	if err := json.Unmarshal(check.InputSchema, &schema); err != nil {
+		t.Fatalf("decode input schema: %v", err)
+	}
+	props, _ := schema["properties"].(map[string]any)
+	if _, ok := props["service"]; !ok {
+		t.Fatalf("expected service property in %v", schema)
+	}
+}
+
+func TestInvokeTranscodesJSON(t *testing.T) {
+	addr := startReflectionServer(t)
+	out, err := newLocalProtocol().Invoke(context.Background(), InvokeRequest{
+		Target:    Target{Address: addr},
+		Method:    "grpc.health.v1.Health/Check",
+		Arguments: json.RawMessage(`{"service":""}`),
+	})
+	if err != nil {
+		t.Fatalf("Invoke: %v", err)
+	}
+	var resp map[string]any
+	if err := json.Unmarshal(out, &resp); err != nil {
+		t.Fatalf("decode response: %v", err)
+	}
+	if resp["status"] != "SERVING" {
+		t.Fatalf("unexpected response %s", out)
+	}
+}
+
+func TestInvokeRejectsUnknownMethod(t *testing.T) {
+	addr := startReflectionServer(t)
+	_, err := newLocalProtocol().Invoke(context.Background(), InvokeRequest{
+		Target: Target{Address: addr},
+		Method: "grpc.health.v1.Health/Missing",
+	})
+	if err == nil {
+		t.Fatal("expected error for unknown method")
+	}
+}
@@ -0,0 +1,117 @@
+package grpcclient
+
+import "google.golang.org/protobuf/reflect/protoreflect"
+
+// MessageSchema converts a protobuf message into the JSON Schema of its
+// protojson encoding, so the designer can render forms for gRPC methods.
+// Recursive message references collapse to a plain object.
+func MessageSchema(md protoreflect.MessageDescriptor) map[string]any {
+	return messageSchema(md, map[protoreflect.FullName]bool{})
+}
+
+func messageSchema(md protoreflect.MessageDescriptor, visiting map[protoreflect.FullName]bool) map[string]any {
+	if schema, ok := wellKnownSchema(md); ok {
+		return schema
+	}
+	if visiting[md.FullName()] {
+		return map[string]any{"type": "object", "title": string(md.FullName())}
+	}
+	visiting[md.FullName()] = true
+	defer delete(visiting, md.FullName())
+
+	properties := map[string]any{}
+	required := []any{}
+	fields := md.Fields()
+	for i := 0; i < fields.Len(); i++ {
+		fd := fields.Get(i)
+		properties[string(fd.Name())] = fieldSchema(fd, visiting)
+		if fd.Cardinality() == protoreflect.Required {
+			required = append(required, string(fd.Name()))
+		}
+	}
+	schema := map[string]any{
+		"type":       "object",
+		"title":      string(md.FullName()),
+		"properties": properties,
+	}
+	if len(required) > 0 {
+		schema["required"] = required
+	}
+	return schema
+}
+
+func fieldSchema(fd protoreflect.FieldDescriptor, visiting map[protoreflect.FullName]bool) map[string]any {
+	if fd.IsMap() {
+		return map[string]any{
+			"type":                 "object",
+			"additionalProperties": singularSchema(fd.MapValue(), visiting),
+		}
+	}
+	if fd.IsList() {
+		return map[string]any{"type": "array", "items": singularSchema(fd, visiting)}
+	}
+	return singularSchema(fd, visiting)
+}
+
+func singularSchema(fd protoreflect.FieldDescriptor, visiting map[protoreflect.FullName]bool) map[string]any {
+	switch fd.Kind() {
+	case protoreflect.BoolKind:
+		return map[string]any{"type": "boolean"}
+	case protoreflect.Int32Kind, protoreflect.Sint32Kind, protoreflect.Sfixed32Kind:
+		return map[string]any{"type": "integer", "minimum": -2147483648, "maximum": 2147483647}
+	case protoreflect.Uint32Kind, protoreflect.Fixed32Kind:
+		return map[string]any{"type": "integer", "minimum": 0, "maximum": 4294967295}
+	case protoreflect.Int64Kind, protoreflect.Sint64Kind, protoreflect.Sfixed64Kind, protoreflect.Uint64Kind, protoreflect.Fixed64Kind:
+		// protojson writes 64-bit integers as strings to avoid precision loss.
+		return map[string]any{"type": []any{"integer", "string"}, "pattern": "^-?[0-9]+$"}
+	case protoreflect.FloatKind, protoreflect.DoubleKind:
+		return map[string]any{"type": "number"}
+	case protoreflect.StringKind:
+		return map[string]any{"type": "string"}
+	case protoreflect.BytesKind:
+		return map[string]any{"type": "string", "contentEncoding": "base64"}
+	case protoreflect.EnumKind:
+		values := fd.Enum().Values()
+		names := make([]any, 0, values.Len())
+		for i := 0; i < values.Len(); i++ {
+			names = append(names, string(values.Get(i).Name()))
+		}
+		return map[string]any{"type": "string", "enum": names}
+	case protoreflect.MessageKind, protoreflect.GroupKind:
+		return messageSchema(fd.Message(), visiting)
+	default:
+		return map[string]any{}
+	}
+}
+
+func wellKnownSchema(md protoreflect.MessageDescriptor) (map[string]any, bool) {
+	switch md.FullName() {
+	case "google.protobuf.Timestamp":
+		return map[string]any{"type": "string", "format": "date-time"}, true
+	case "google.protobuf.Duration":
+		return map[string]any{"type": "string", "pattern": "^-?[0-9]+(\\.[0-9]+)?s$"}, true
+	case "google.protobuf.Struct":
+		return map[string]any{"type": "object"}, true
+	case "google.protobuf.Value":
+		return map[string]any{}, true
+	case "google.protobuf.ListValue":
+		return map[string]any{"type": "array"}, true
+	case "google.protobuf.Empty":
+		return map[string]any{"type": "object"}, true
+	case "google.protobuf.Any":
+		return map[string]any{"type": "object", "required": []any{"@type"}, "properties": map[string]any{"@type": map[string]any{"type": "string"}}}, true
+	case "google.protobuf.FieldMask":
+		return map[string]any{"type": "string"}, true
+	case "google.protobuf.StringValue", "google.protobuf.BytesValue":
+		return map[string]any{"type": []any{"string", "null"}}, true
+	case "google.protobuf.BoolValue":
+		return map[string]any{"type": []any{"boolean", "null"}}, true
+	case "google.protobuf.Int32Value", "google.protobuf.UInt32Value":
+		return map[string]any{"type": []any{"integer", "null"}}, true
+	case "google.protobuf.Int64Value", "google.protobuf.UInt64Value":
+		return map[string]any{"type": []any{"integer", "string", "null"}}, true
+	case "google.protobuf.FloatValue", "google.protobuf.DoubleValue":
+		return map[string]any{"type": []any{"number", "null"}}, true
+	}
+	return nil, false
+}
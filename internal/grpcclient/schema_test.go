@@ -0,0 +1,59 @@
+package grpcclient
+
+import (
+	"testing"
+
+	"google.golang.org/protobuf/proto"
+	"google.golang.org/protobuf/reflect/protodesc"
+	"google.golang.org/protobuf/reflect/protoregistry"
+	"google.golang.org/protobuf/types/descriptorpb"
+)
+
+func TestMessageSchemaMapsProtobufTypes(t *testing.T) {
+	optional := descriptorpb.FieldDescriptorProto_LABEL_OPTIONAL.Enum()
+	repeated := descriptorpb.FieldDescriptorProto_LABEL_REPEATED.Enum()
+	fdp := &descriptorpb.FileDescriptorProto{
+		Name:    proto.String("test/order.proto"),
+		Package: proto.String("test"),
+		Syntax:  proto.String("proto3"),
+		EnumType: []*descriptorpb.EnumDescriptorProto{{
+			Name: proto.String("Status"),
+			Value: []*descriptorpb.EnumValueDescriptorProto{
+				{Name: proto.String("STATUS_UNKNOWN"), Number: proto.Int32(0)},
+				{Name: proto.String("STATUS_OPEN"), Number: proto.Int32(1)},
+			},
+		}},
+		MessageType: []*descriptorpb.DescriptorProto{{
+			Name: proto.String("Order"),
+			Field: []*descriptorpb.FieldDescriptorProto{
+				{Name: proto.String("id"), Number: proto.Int32(1), Label: optional, Type: descriptorpb.FieldDescriptorProto_TYPE_INT64.Enum(), JsonName: proto.String("id")},
+				{Name: proto.String("status"), Number: proto.Int32(2), Label: optional, Type: descriptorpb.FieldDescriptorProto_TYPE_ENUM.Enum(), TypeName: proto.String(".test.Status"), JsonName: proto.String("status")},
+				{Name: proto.String("tags"), Number: proto.Int32(3), Label: repeated, Type: descriptorpb.FieldDescriptorProto_TYPE_STRING.Enum(), JsonName: proto.String("tags")},
+				{Name: proto.String("children"), Number: proto.Int32(4), Label: repeated, Type: descriptorpb.FieldDescriptorProto_TYPE_MESSAGE.Enum(), TypeName: proto.String(".test.Order"), JsonName: proto.String("children")},
+			},
+		}},
+	}
+	fd, err := protodesc.NewFile(fdp, protoregistry.GlobalFiles)
+	if err != nil {
+		t.Fatalf("NewFile: %v", err)
+	}
+	schema := MessageSchema(fd.Messages().ByName("Order"))
+	props := schema["properties"].(map[string]any)
+
+	id := props["id"].(map[string]any)
+	if types, ok := id["type"].([]any); !ok || len(types) != 2 {
+		t.Fatalf("int64 should accept integer or string, got %v", id)
+	}
+	status := props["status"].(map[string]any)
+	if enum := status["enum"].([]any); len(enum) != 2 || enum[1] != "STATUS_OPEN" {
+		t.Fatalf("unexpected enum schema %v", status)
+	}
+	tags := props["tags"].(map[string]any)
+	if tags["type"] != "array" || tags["items"].(map[string]any)["type"] != "string" {
+		t.Fatalf("unexpected repeated schema %v", tags)
+	}
+	children := props["children"].(map[string]any)["items"].(map[string]any)
+	if children["type"] != "object" || children["properties"] != nil {
+		t.Fatalf("recursive reference should collapse to object, got %v", children)
+	}
+}
@@ -0,0 +1,151 @@
+package grpcclient
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"fmt"
+	"strings"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/chaos"
+	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/engine"
+	"github.com/neural-chilli/aceryx/internal/mocktools"
+	"github.com/neural-chilli/aceryx/internal/observability"
+)
+
+type StepExecutor struct {
+	db       *sql.DB
+	protocol *GrpcProtocol
+	mocks    *mocktools.MockProtocol
+}
+
+type StepConfig struct {
+	Address    string            `json:"address"`
+	TLS        bool              `json:"tls"`
+	Metadata   map[string]string `json:"metadata"`
+	Method     string            `json:"method"`
+	Arguments  map[string]any    `json:"arguments"`
+	OutputPath string            `json:"output_path"`
+	TimeoutMS  int               `json:"timeout_ms"`
+}
+
+func NewStepExecutor(db *sql.DB, protocol *GrpcProtocol) *StepExecutor {
+	return &StepExecutor{db: db, protocol: protocol}
+}
+
+// SetMockProtocol answers calls made under a mock profile from the profile
+// instead of the service.
+func (e *StepExecutor) SetMockProtocol(mocks *mocktools.MockProtocol) {
+	e.mocks = mocks
+}
+
+func (e *StepExecutor) Execute(ctx context.Context, caseID uuid.UUID, _ string, raw json.RawMessage) (*engine.StepResult, error) {
+	if e == nil || e.protocol == nil {
+		return nil, fmt.Errorf("grpc step executor not configured")
+	}
+	cfg := StepConfig{}
+	if err := json.Unmarshal(raw, &cfg); err != nil {
+		return nil, fmt.Errorf("parse grpc-client step config: %w", err)
+	}
+	if strings.TrimSpace(cfg.Address) == "" || strings.TrimSpace(cfg.Method) == "" {
+		return nil, fmt.Errorf("address and method are required")
+	}
+	tplCtx, err := e.loadCaseContext(ctx, caseID)
+	if err != nil {
+		return nil, err
+	}
+	engine.AddForEachItem(ctx, tplCtx)
+	args, err := json.Marshal(connectors.ResolveTemplateAny(cfg.Arguments, tplCtx))
+	if err != nil {
+		return nil, fmt.Errorf("marshal grpc arguments: %w", err)
+	}
+	if string(args) == "null" {
+		args = []byte(`{}`)
+	}
+	md := map[string]string{}
+	for k, v := range cfg.Metadata {
+		md[k] = connectors.ResolveTemplateString(v, tplCtx)
+	}
+	output, mocked, err := e.mocks.Intercept(ctx, "grpc", cfg.Method, json.RawMessage(args))
+	if !mocked {
+		toolCtx, endSpan := observability.StartToolSpan(ctx, cfg.Method, "grpc")
+		err = chaos.Inject(toolCtx, "grpc", cfg.Method)
+		if err == nil {
+			output, err = e.protocol.Invoke(toolCtx, InvokeRequest{
+				Target:    Target{Address: cfg.Address, TLS: cfg.TLS, Metadata: md},
+				Method:    cfg.Method,
+				Arguments: args,
+				Timeout:   time.Duration(cfg.TimeoutMS) * time.Millisecond,
+			})
+		}
+		endSpan(err)
+	}
+	if err != nil {
+		return nil, err
+	}
+	patch, err := buildCasePatch(cfg.OutputPath, output)
+	if err != nil {
+		return nil, err
+	}
+	return &engine.StepResult{
+		Outcome:        "ok",
+		Output:         output,
+		WritesCaseData: len(patch) > 0,
+		CaseDataPatch:  patch,
+		AuditEventType: "grpc.invoked",
+	}, nil
+}
+
+func (e *StepExecutor) loadCaseContext(ctx context.Context, caseID uuid.UUID) (map[string]any, error) {
+	if e.db == nil {
+		return nil, fmt.Errorf("grpc step executor db not configured")
+	}
+	var caseData []byte
+	if err := e.db.QueryRowContext(ctx, `SELECT data FROM cases WHERE id = $1`, caseID).Scan(&caseData); err != nil {
+		return nil, fmt.Errorf("load case context for grpc step: %w", err)
+	}
+	caseMap := map[string]any{}
+	if len(caseData) > 0 {
+		if err := json.Unmarshal(caseData, &caseMap); err != nil {
+			return nil, fmt.Errorf("decode case data for grpc step: %w", err)
+		}
+	}
+	return map[string]any{
+		"case": map[string]any{"data": caseMap},
+		"now":  time.Now().UTC().Format(time.RFC3339),
+	}, nil
+}
+
+func buildCasePatch(outputPath string, output json.RawMessage) (json.RawMessage, error) {
+	path := strings.TrimSpace(outputPath)
+	path = strings.TrimPrefix(path, "case.data.")
+	path = strings.TrimPrefix(path, "data.")
+	path = strings.Trim(path, ".")
+	if path == "" {
+		return nil, nil
+	}
+	var value any
+	if err := json.Unmarshal(output, &value); err != nil {
+		return nil, fmt.Errorf("decode grpc output: %w", err)
+	}
+	parts := strings.Split(path, ".")
+	root := map[string]any{}
+	cur := root
+	for i, part := range parts {
+		if i == len(parts)-1 {
+			cur[part] = value
+			break
+		}
+		next := map[string]any{}
+		cur[part] = next
+		cur = next
+	}
+	raw, err := json.Marshal(root)
+	if err != nil {
+		return nil, fmt.Errorf("marshal grpc case patch: %w", err)
+	}
+	return raw, nil
+}
@@ -0,0 +1,38 @@
+package grpcclient
+
+import (
+	"encoding/json"
+	"time"
+)
+
+const (
+	defaultDialTimeout      = 10 * time.Second
+	defaultInvokeTimeout    = 30 * time.Second
+	defaultDescriptorTTL    = 5 * time.Minute
+	reflectionServicePrefix = "grpc.reflection."
+)
+
+// Tool is one unary gRPC method exposed as a tool. Name is the full method
+// name ("package.Service/Method").
+type Tool struct {
+	Name         string          `json:"name"`
+	Service      string          `json:"service"`
+	Method       string          `json:"method"`
+	Description  string          `json:"description"`
+	InputSchema  json.RawMessage `json:"inputSchema"`
+	OutputSchema json.RawMessage `json:"outputSchema"`
+}
+
+// Target identifies a gRPC server and how to reach it.
+type Target struct {
+	Address  string            `json:"address"`
+	TLS      bool              `json:"tls"`
+	Metadata map[string]string `json:"metadata,omitempty"`
+}
+
+type InvokeRequest struct {
+	Target    Target
+	Method    string
+	Arguments json.RawMessage
+	Timeout   time.Duration
+}
@@ -11,6 +11,8 @@ import (
 	"strconv"
 	"strings"
 	"time"
+
+	"github.com/neural-chilli/aceryx/internal/observability"
 )
 
 // ClientManager manages shared HTTP transport and request policy.
@@ -108,6 +110,7 @@ func (cm *ClientManager) Execute(ctx context.Context, req PluginHTTPRequest) (Pl
 	for key, value := range req.Headers {
 		httpReq.Header.Set(key, value)
 	}
+	observability.InjectCorrelationHeaders(callCtx, httpReq.Header)
 	if err := cm.authManager.InjectAuth(callCtx, req.TenantID, httpReq, req.AuthConfig); err != nil {
 		return PluginHTTPResponse{}, err
 	}
@@ -280,6 +280,7 @@ func (m *AdapterManager) callChat(ctx context.Context, managed *managedAdapter,
 	inv.TotalTokens = resp.TotalTokens
 	inv.Model = coalesce(resp.Model, inv.Model)
 	inv.CostUSD = estimateCostUSD(managed.config, inv.Model, inv.InputTokens, inv.OutputTokens)
+	resp.CostUSD = inv.CostUSD
 	return resp, inv, nil
 }
 
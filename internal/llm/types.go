@@ -35,6 +35,7 @@ type ChatResponse struct {
 	Model        string
 	FinishReason string
 	ToolCalls    []ToolCall
+	CostUSD      float64
 }
 
 type ToolDef struct {
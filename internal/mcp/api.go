@@ -2,6 +2,7 @@ package mcp
 
 import (
 	"context"
+	"errors"
 	"fmt"
 	"strings"
 
@@ -62,17 +63,51 @@ func (a *API) Delete(ctx context.Context, tenantID uuid.UUID, serverURL string)
 	return a.cache.Delete(ctx, tenantID, serverURL)
 }
 
-func (a *API) Refresh(ctx context.Context, tenantID uuid.UUID, req RefreshRequest) ([]MCPTool, error) {
+// Refresh re-discovers the tools of req.ServerURL, or of every server the
+// tenant has cached when it is empty. Tools are returned for a single server
+// only. A single server that cannot be reached is an error; when refreshing
+// them all, failures are only reported.
+func (a *API) Refresh(ctx context.Context, tenantID uuid.UUID, req RefreshRequest) ([]MCPTool, RefreshReport, error) {
+	report := RefreshReport{Servers: []ServerRefresh{}}
 	if a == nil || a.manager == nil || a.cache == nil {
+		return nil, report, fmt.Errorf("mcp api not configured")
+	}
+	servers, err := a.cache.ListServers(ctx, tenantID)
+	if err != nil {
+		return nil, report, err
+	}
+	serverURL := strings.TrimSpace(req.ServerURL)
+	if serverURL != "" {
+		var previous []MCPTool
+		for _, server := range servers {
+			if server.ServerURL == serverURL {
+				previous = server.Tools
+			}
+		}
+		tools, server := a.manager.RefreshTools(ctx, tenantID, serverURL, AuthConfig{
+			Type:       req.AuthType,
+			SecretRef:  req.AuthSecret,
+			HeaderName: req.HeaderName,
+		}, previous)
+		report.add(server)
+		if server.Error != "" {
+			return nil, report, errors.New(server.Error)
+		}
+		return tools, report, nil
+	}
+	for _, server := range servers {
+		_, refreshed := a.manager.RefreshTools(ctx, tenantID, server.ServerURL, AuthConfig{Type: "none"}, server.Tools)
+		report.add(refreshed)
+	}
+	return nil, report, nil
+}
+
+func (a *API) ToolVersions(ctx context.Context, tenantID uuid.UUID, serverURL, toolName string) ([]ToolVersionRecord, error) {
+	if a == nil || a.cache == nil {
 		return nil, fmt.Errorf("mcp api not configured")
 	}
-	if strings.TrimSpace(req.ServerURL) == "" {
-		return nil, fmt.Errorf("server_url is required")
+	if strings.TrimSpace(serverURL) == "" || strings.TrimSpace(toolName) == "" {
+		return nil, fmt.Errorf("server_url and tool are required")
 	}
-	_ = a.cache.MarkStale(ctx, tenantID, req.ServerURL)
-	return a.manager.DiscoverTools(ctx, tenantID, req.ServerURL, AuthConfig{
-		Type:       req.AuthType,
-		SecretRef:  req.AuthSecret,
-		HeaderName: req.HeaderName,
-	})
+	return a.cache.ListToolVersions(ctx, tenantID, serverURL, toolName)
 }
@@ -12,8 +12,9 @@ import (
 )
 
 type ToolCache struct {
-	db              *sql.DB
-	refreshInterval time.Duration
+	db               *sql.DB
+	refreshInterval  time.Duration
+	retiredRetention time.Duration
 }
 
 func NewToolCache(db *sql.DB, refreshInterval time.Duration) *ToolCache {
@@ -23,6 +24,12 @@ func NewToolCache(db *sql.DB, refreshInterval time.Duration) *ToolCache {
 	return &ToolCache{db: db, refreshInterval: refreshInterval}
 }
 
+// SetRetiredRetention deletes tool versions once they have been retired for
+// longer than d, at the next discovery of their server. Zero keeps them.
+func (tc *ToolCache) SetRetiredRetention(d time.Duration) {
+	tc.retiredRetention = d
+}
+
 func (tc *ToolCache) GetTools(ctx context.Context, tenantID uuid.UUID, serverURL string) ([]MCPTool, error) {
 	if tc == nil || tc.db == nil {
 		return nil, nil
@@ -52,6 +59,7 @@ func (tc *ToolCache) SetTools(ctx context.Context, tenantID uuid.UUID, serverURL
 	if tc == nil || tc.db == nil {
 		return nil
 	}
+	tools = withVersions(tools)
 	raw, err := json.Marshal(tools)
 	if err != nil {
 		return fmt.Errorf("marshal mcp tools: %w", err)
@@ -69,7 +77,7 @@ DO UPDATE SET
 	if err != nil {
 		return fmt.Errorf("upsert mcp tool cache: %w", err)
 	}
-	return nil
+	return tc.recordVersions(ctx, tenantID, strings.TrimSpace(serverURL), tools)
 }
 
 func (tc *ToolCache) SetError(ctx context.Context, tenantID uuid.UUID, serverURL string, err error) error {
@@ -185,5 +193,27 @@ func decodeTools(raw []byte) ([]MCPTool, error) {
 	if err := json.Unmarshal(raw, &tools); err != nil {
 		return nil, fmt.Errorf("decode mcp tools cache: %w", err)
 	}
-	return tools, nil
+	return withVersions(tools), nil
+}
+
+// ToolDescription describes the cached tool an mcp-client step calls, for
+// generated workflow docs. Other step types and uncached tools get "".
+func (tc *ToolCache) ToolDescription(ctx context.Context, tenantID uuid.UUID, stepType string, config json.RawMessage) string {
+	if stepType != "mcp-client" {
+		return ""
+	}
+	var cfg StepConfig
+	if err := json.Unmarshal(config, &cfg); err != nil {
+		return ""
+	}
+	tools, err := tc.GetTools(ctx, tenantID, cfg.ServerURL)
+	if err != nil {
+		return ""
+	}
+	for _, tool := range tools {
+		if tool.Name == strings.TrimSpace(cfg.Tool) {
+			return tool.Description
+		}
+	}
+	return ""
 }
@@ -1,11 +1,15 @@
 package mcp
 
 import (
-	"fmt"
+	"errors"
 	"sync"
 	"time"
 )
 
+// ErrCircuitOpen is returned when a call is refused without reaching the
+// server because of recent failures.
+var ErrCircuitOpen = errors.New("circuit breaker open — recent failures")
+
 type CircuitState string
 
 const (
@@ -57,12 +61,12 @@ func (cb *CircuitBreaker) Allow() error {
 			cb.state = CircuitHalfOpen
 			cb.halfOpenProbe = false
 		} else {
-			return fmt.Errorf("circuit breaker open — recent failures")
+			return ErrCircuitOpen
 		}
 	}
 	if cb.state == CircuitHalfOpen {
 		if cb.halfOpenProbe {
-			return fmt.Errorf("circuit breaker open — recent failures")
+			return ErrCircuitOpen
 		}
 		cb.halfOpenProbe = true
 		cb.lastAttempt = now
@@ -105,3 +109,17 @@ func (cb *CircuitBreaker) State() CircuitState {
 	defer cb.mu.Unlock()
 	return cb.state
 }
+
+// Refusing reports whether Allow would currently refuse a call. Once the
+// cool-down has passed an open circuit admits a probe, so it is not refusing.
+func (cb *CircuitBreaker) Refusing() bool {
+	cb.mu.Lock()
+	defer cb.mu.Unlock()
+	switch cb.state {
+	case CircuitOpen:
+		return time.Now().UTC().Sub(cb.lastFailure) < cb.coolDown
+	case CircuitHalfOpen:
+		return cb.halfOpenProbe
+	}
+	return false
+}
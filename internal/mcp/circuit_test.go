@@ -15,10 +15,13 @@ func TestCircuitBreakerLifecycle(t *testing.T) {
 	if cb.State() != CircuitOpen {
 		t.Fatalf("expected open, got %s", cb.State())
 	}
-	if err := cb.Allow(); err == nil {
+	if err := cb.Allow(); err == nil || !cb.Refusing() {
 		t.Fatalf("expected immediate reject while open")
 	}
 	time.Sleep(25 * time.Millisecond)
+	if cb.Refusing() {
+		t.Fatalf("expected open circuit to admit a probe after cool-down")
+	}
 	if err := cb.Allow(); err != nil {
 		t.Fatalf("expected half-open probe allowed: %v", err)
 	}
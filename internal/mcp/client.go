@@ -12,6 +12,8 @@ import (
 	"net/http"
 	"strings"
 	"time"
+
+	"github.com/neural-chilli/aceryx/internal/observability"
 )
 
 const (
@@ -92,6 +94,8 @@ func (c *Client) sendRPC(ctx context.Context, payload jsonRPCRequest) (jsonRPCRe
 		setTimeoutHeader(req, time.Until(deadline))
 	}
 	c.applyAuth(req)
+	observability.InjectCorrelationHeaders(ctx, req.Header)
+	observability.InjectTraceHeaders(ctx, req.Header)
 
 	resp, err := c.httpClient.Do(req)
 	if err != nil {
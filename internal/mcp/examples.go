@@ -0,0 +1,45 @@
+package mcp
+
+import (
+	"bytes"
+	"encoding/json"
+)
+
+const maxToolExamples = 10
+
+// schemaExamples returns the example arguments a server publishes through
+// the JSON Schema "examples" keyword on a tool's input schema. Only object
+// examples are kept, since tool arguments are always an object.
+func schemaExamples(schema json.RawMessage) []json.RawMessage {
+	var parsed struct {
+		Examples []json.RawMessage `json:"examples"`
+	}
+	if len(bytes.TrimSpace(schema)) == 0 || json.Unmarshal(schema, &parsed) != nil {
+		return nil
+	}
+	var out []json.RawMessage
+	for _, example := range parsed.Examples {
+		example = bytes.TrimSpace(example)
+		if len(example) == 0 || example[0] != '{' {
+			continue
+		}
+		var buf bytes.Buffer
+		if json.Compact(&buf, example) != nil {
+			continue
+		}
+		out = append(out, buf.Bytes())
+		if len(out) == maxToolExamples {
+			break
+		}
+	}
+	return out
+}
+
+func withExamples(tools []MCPTool) []MCPTool {
+	for i := range tools {
+		if len(tools[i].Examples) == 0 {
+			tools[i].Examples = schemaExamples(tools[i].InputSchema)
+		}
+	}
+	return tools
+}
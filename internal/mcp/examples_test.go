@@ -0,0 +1,17 @@
+package mcp
+
+import (
+	"encoding/json"
+	"testing"
+)
+
+func TestSchemaExamplesKeepsObjects(t *testing.T) {
+	schema := json.RawMessage(`{"type":"object","examples":[{ "q": "invoices" }, "not an object", {"q":"refunds","limit":5}]}`)
+	got := schemaExamples(schema)
+	if len(got) != 2 || string(got[0]) != `{"q":"invoices"}` || string(got[1]) != `{"q":"refunds","limit":5}` {
+		t.Fatalf("unexpected examples %s", got)
+	}
+	if got := schemaExamples(json.RawMessage(`{"type":"object"}`)); got != nil {
+		t.Fatalf("expected no examples, got %s", got)
+	}
+}
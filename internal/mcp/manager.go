@@ -5,12 +5,14 @@ import (
 	"encoding/json"
 	"fmt"
 	"net/http"
+	"sort"
 	"strings"
 	"sync"
 	"time"
 
 	"github.com/google/uuid"
 	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/events"
 	"github.com/neural-chilli/aceryx/internal/llm"
 )
 
@@ -26,6 +28,7 @@ type Manager struct {
 	secretStore     connectors.SecretStore
 	selfURLs        []string
 	maxDepth        int
+	bus             *events.Bus
 	mu              sync.RWMutex
 }
 
@@ -46,7 +49,46 @@ func NewManager(cache *ToolCache, secretStore connectors.SecretStore, selfURLs [
 	}
 }
 
+// SetEventBus publishes tool.refreshed with each server's refresh report.
+func (m *Manager) SetEventBus(bus *events.Bus) {
+	m.bus = bus
+}
+
 func (m *Manager) DiscoverTools(ctx context.Context, tenantID uuid.UUID, serverURL string, auth AuthConfig) ([]MCPTool, error) {
+	return m.discover(ctx, tenantID, serverURL, auth, true)
+}
+
+// RefreshTools re-discovers serverURL's tools without reading the cache and
+// reports what changed against previous, the tools cached before. When
+// discovery fails the cached tools are kept and only this server is marked
+// stale.
+func (m *Manager) RefreshTools(ctx context.Context, tenantID uuid.UUID, serverURL string, auth AuthConfig, previous []MCPTool) ([]MCPTool, ServerRefresh) {
+	report := ServerRefresh{ServerURL: strings.TrimSpace(serverURL), Discovered: []string{}, Updated: []string{}, Removed: []string{}, Failed: []string{}}
+	tools, err := m.discover(ctx, tenantID, serverURL, auth, false)
+	if err != nil {
+		if m != nil && m.cache != nil {
+			_ = m.cache.MarkStale(ctx, tenantID, serverURL)
+		}
+		report.Failed = toolNames(previous)
+		report.Error = err.Error()
+		m.publishRefresh(ctx, tenantID, report)
+		return nil, report
+	}
+	report.Discovered = toolNames(tools)
+	report.Updated = changedTools(previous, tools)
+	report.Removed = removedTools(previous, tools)
+	m.publishRefresh(ctx, tenantID, report)
+	return tools, report
+}
+
+func (m *Manager) publishRefresh(ctx context.Context, tenantID uuid.UUID, report ServerRefresh) {
+	if m == nil {
+		return
+	}
+	m.bus.Publish(ctx, tenantID, events.ToolRefreshed, report)
+}
+
+func (m *Manager) discover(ctx context.Context, tenantID uuid.UUID, serverURL string, auth AuthConfig, useCache bool) ([]MCPTool, error) {
 	if m == nil {
 		return nil, fmt.Errorf("mcp manager not configured")
 	}
@@ -57,7 +99,7 @@ func (m *Manager) DiscoverTools(ctx context.Context, tenantID uuid.UUID, serverU
 	if err := m.CheckSelfInvocation(serverURL); err != nil {
 		return nil, err
 	}
-	if m.cache != nil {
+	if m.cache != nil && useCache {
 		cached, err := m.cache.GetTools(ctx, tenantID, serverURL)
 		if err != nil {
 			return nil, err
@@ -71,6 +113,7 @@ func (m *Manager) DiscoverTools(ctx context.Context, tenantID uuid.UUID, serverU
 		return nil, err
 	}
 	tools, err := m.clientFactory(serverURL, resolvedAuth).Discover(ctx)
+	tools = withExamples(withVersions(tools))
 	if err != nil {
 		if m.cache != nil {
 			_ = m.cache.SetError(ctx, tenantID, serverURL, err)
@@ -101,7 +144,7 @@ func (m *Manager) InvokeTool(ctx context.Context, req InvokeRequest) (MCPToolRes
 	}
 	cb := m.getCircuitBreaker(req.TenantID, req.ServerURL)
 	if err := cb.Allow(); err != nil {
-		return MCPToolResult{}, fmt.Errorf("MCP server %s circuit breaker open — recent failures", req.ServerURL)
+		return MCPToolResult{}, fmt.Errorf("MCP server %s %w", req.ServerURL, err)
 	}
 	resolvedAuth, err := m.resolveAuth(ctx, req.TenantID, req.Auth)
 	if err != nil {
@@ -130,6 +173,36 @@ func (m *Manager) ToolsForAgent(ctx context.Context, tenantID uuid.UUID, serverU
 	return ToLLMToolDefs(tools, prefix, toolFilter), nil
 }
 
+// OpenCircuits lists the MCP server URLs whose circuit breaker is currently
+// open for at least one tenant.
+func (m *Manager) OpenCircuits() []string {
+	m.mu.RLock()
+	defer m.mu.RUnlock()
+	seen := map[string]bool{}
+	out := make([]string, 0)
+	for key, cb := range m.circuitBreakers {
+		if cb.State() != CircuitOpen || seen[key.ServerURL] {
+			continue
+		}
+		seen[key.ServerURL] = true
+		out = append(out, key.ServerURL)
+	}
+	sort.Strings(out)
+	return out
+}
+
+// CircuitOpen reports whether calls from tenantID to serverURL are currently
+// being refused.
+func (m *Manager) CircuitOpen(tenantID uuid.UUID, serverURL string) bool {
+	if m == nil {
+		return false
+	}
+	m.mu.RLock()
+	cb, ok := m.circuitBreakers[cbKey{TenantID: tenantID.String(), ServerURL: strings.TrimSpace(serverURL)}]
+	m.mu.RUnlock()
+	return ok && cb.Refusing()
+}
+
 func (m *Manager) getCircuitBreaker(tenantID uuid.UUID, serverURL string) *CircuitBreaker {
 	key := cbKey{TenantID: tenantID.String(), ServerURL: strings.TrimSpace(serverURL)}
 	m.mu.RLock()
@@ -47,5 +47,38 @@ func TestManagerInvokeAndCircuit(t *testing.T) {
 		if err == nil || !strings.Contains(err.Error(), "circuit breaker open") {
 			t.Fatalf("expected circuit open error, got %v", err)
 		}
+		if open := m.OpenCircuits(); len(open) != 1 || open[0] != srv.URL {
+			t.Fatalf("expected %s to be reported open, got %v", srv.URL, open)
+		}
 	})
 }
+
+func TestManagerRefreshToolsReportsChanges(t *testing.T) {
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+		w.Header().Set("Content-Type", "application/json")
+		_, _ = w.Write([]byte(`{"jsonrpc":"2.0","id":1,"result":{"tools":[{"name":"a","inputSchema":{"type":"object"}},{"name":"b","inputSchema":{"type":"object","required":["q"]}},{"name":"c","inputSchema":{"type":"object"}}]}}`))
+	}))
+	defer srv.Close()
+	previous := []MCPTool{
+		{Name: "a", InputSchema: []byte(`{"type":"object"}`)},
+		{Name: "b", InputSchema: []byte(`{"type":"object"}`)},
+		{Name: "d", InputSchema: []byte(`{"type":"object"}`)},
+	}
+	m := NewManager(nil, fakeSecrets{}, nil, nil)
+	tools, report := m.RefreshTools(context.Background(), uuid.New(), srv.URL, AuthConfig{Type: "none"}, previous)
+	if report.Error != "" || len(tools) != 3 {
+		t.Fatalf("expected 3 tools, got %d (%s)", len(tools), report.Error)
+	}
+	if strings.Join(report.Discovered, ",") != "a,b,c" || strings.Join(report.Updated, ",") != "b,c" || strings.Join(report.Removed, ",") != "d" {
+		t.Fatalf("unexpected report %+v", report)
+	}
+
+	down := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+		w.WriteHeader(http.StatusBadGateway)
+	}))
+	defer down.Close()
+	_, report = m.RefreshTools(context.Background(), uuid.New(), down.URL, AuthConfig{Type: "none"}, previous)
+	if report.Error == "" || strings.Join(report.Failed, ",") != "a,b,d" || len(report.Discovered) != 0 {
+		t.Fatalf("expected cached tools reported as failed, got %+v", report)
+	}
+}
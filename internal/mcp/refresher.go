@@ -85,17 +85,80 @@ func (r *Refresher) refreshOnce(ctx context.Context) {
 		if strings.TrimSpace(server.ServerURL) == "" || server.TenantID == uuid.Nil {
 			continue
 		}
-		tools, err := r.manager.DiscoverTools(ctx, server.TenantID, server.ServerURL, AuthConfig{Type: "none"})
-		if err != nil {
-			_ = r.cache.MarkStale(ctx, server.TenantID, server.ServerURL)
-			slog.Warn("mcp cache refresh server failed", "tenant_id", server.TenantID.String(), "server_url", server.ServerURL, "error", err)
-			if time.Since(server.LastDiscovered) > r.staleMax {
-				slog.Warn("mcp cache stale for too long", "tenant_id", server.TenantID.String(), "server_url", server.ServerURL, "stale_hours", int(time.Since(server.LastDiscovered).Hours()))
-			}
+		_, report := r.manager.RefreshTools(ctx, server.TenantID, server.ServerURL, AuthConfig{Type: "none"}, server.Tools)
+		if report.Error == "" {
 			continue
 		}
-		if err := r.cache.SetTools(ctx, server.TenantID, server.ServerURL, tools); err != nil {
-			slog.Warn("mcp cache refresh failed to persist", "tenant_id", server.TenantID.String(), "server_url", server.ServerURL, "error", err)
+		slog.Warn("mcp cache refresh server failed", "tenant_id", server.TenantID.String(), "server_url", server.ServerURL, "error", report.Error)
+		if time.Since(server.LastDiscovered) > r.staleMax {
+			slog.Warn("mcp cache stale for too long", "tenant_id", server.TenantID.String(), "server_url", server.ServerURL, "stale_hours", int(time.Since(server.LastDiscovered).Hours()))
+		}
+	}
+}
+
+// ServerRefresh is the outcome of re-discovering one server's tools.
+// Discovered lists every tool the server now offers, Updated those that are
+// new or whose version changed, and Removed the cached tools it dropped,
+// whose versions are now retired. When discovery fails, Failed lists the
+// cached tools that were kept as they were and Error says why.
+type ServerRefresh struct {
+	ServerURL  string   `json:"server_url"`
+	Discovered []string `json:"discovered"`
+	Updated    []string `json:"updated"`
+	Removed    []string `json:"removed"`
+	Failed     []string `json:"failed"`
+	Error      string   `json:"error,omitempty"`
+}
+
+// RefreshReport covers a refresh of one server or of every server a tenant
+// has cached. A failed server does not stop the others.
+type RefreshReport struct {
+	Servers []ServerRefresh `json:"servers"`
+	Failed  int             `json:"failed"`
+}
+
+func (r *RefreshReport) add(server ServerRefresh) {
+	r.Servers = append(r.Servers, server)
+	if server.Error != "" {
+		r.Failed++
+	}
+}
+
+func toolNames(tools []MCPTool) []string {
+	out := make([]string, 0, len(tools))
+	for _, tool := range tools {
+		out = append(out, tool.Name)
+	}
+	return out
+}
+
+// removedTools lists the tools in previous that current no longer offers.
+func removedTools(previous, current []MCPTool) []string {
+	offered := make(map[string]bool, len(current))
+	for _, tool := range current {
+		offered[tool.Name] = true
+	}
+	out := make([]string, 0)
+	for _, tool := range previous {
+		if !offered[tool.Name] {
+			out = append(out, tool.Name)
+		}
+	}
+	return out
+}
+
+// changedTools lists the tools in current that are missing from previous or
+// carry a different version.
+func changedTools(previous, current []MCPTool) []string {
+	versions := make(map[string]string, len(previous))
+	for _, tool := range withVersions(append([]MCPTool(nil), previous...)) {
+		versions[tool.Name] = tool.Version
+	}
+	out := make([]string, 0)
+	for _, tool := range current {
+		if version, ok := versions[tool.Name]; !ok || version != tool.Version {
+			out = append(out, tool.Name)
 		}
 	}
+	return out
 }
@@ -10,13 +10,17 @@ import (
 	"time"
 
 	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/chaos"
 	"github.com/neural-chilli/aceryx/internal/connectors"
 	"github.com/neural-chilli/aceryx/internal/engine"
+	"github.com/neural-chilli/aceryx/internal/mocktools"
+	"github.com/neural-chilli/aceryx/internal/observability"
 )
 
 type StepExecutor struct {
 	db      *sql.DB
 	manager *Manager
+	mocks   *mocktools.MockProtocol
 }
 
 type StepConfig struct {
@@ -25,6 +29,7 @@ type StepConfig struct {
 	AuthSecret    string         `json:"auth_secret"`
 	AuthHeader    string         `json:"auth_header"`
 	Tool          string         `json:"tool"`
+	ToolVersion   string         `json:"tool_version"`
 	Arguments     map[string]any `json:"arguments"`
 	OutputPath    string         `json:"output_path"`
 	TimeoutMS     int            `json:"timeout_ms"`
@@ -36,6 +41,12 @@ func NewStepExecutor(db *sql.DB, manager *Manager) *StepExecutor {
 	return &StepExecutor{db: db, manager: manager}
 }
 
+// SetMockProtocol answers calls made under a mock profile from the profile
+// instead of the server.
+func (e *StepExecutor) SetMockProtocol(mocks *mocktools.MockProtocol) {
+	e.mocks = mocks
+}
+
 func (e *StepExecutor) Execute(ctx context.Context, caseID uuid.UUID, _ string, raw json.RawMessage) (*engine.StepResult, error) {
 	if e == nil || e.manager == nil {
 		return nil, fmt.Errorf("mcp step executor not configured")
@@ -51,6 +62,7 @@ func (e *StepExecutor) Execute(ctx context.Context, caseID uuid.UUID, _ string,
 	if err != nil {
 		return nil, err
 	}
+	engine.AddForEachItem(ctx, ctxData)
 	resolvedArgsAny := connectors.ResolveTemplateAny(cfg.Arguments, ctxData)
 	resolvedArgs, _ := resolvedArgsAny.(map[string]any)
 	if resolvedArgs == nil {
@@ -60,28 +72,14 @@ func (e *StepExecutor) Execute(ctx context.Context, caseID uuid.UUID, _ string,
 	if err != nil {
 		return nil, fmt.Errorf("marshal mcp arguments: %w", err)
 	}
-	timeoutMS := cfg.TimeoutMS
-	if timeoutMS <= 0 && cfg.TimeoutSecond > 0 {
-		timeoutMS = int((time.Duration(cfg.TimeoutSecond) * time.Second) / time.Millisecond)
-	}
-	invokeResult, err := e.manager.InvokeTool(ctx, InvokeRequest{
-		TenantID:  tenantID,
-		ServerURL: cfg.ServerURL,
-		Auth: AuthConfig{
-			Type:       cfg.AuthType,
-			SecretRef:  cfg.AuthSecret,
-			HeaderName: cfg.AuthHeader,
-		},
-		ToolName:  cfg.Tool,
-		Arguments: argsRaw,
-		Depth:     cfg.Depth,
-		TimeoutMS: timeoutMS,
-	})
+	invokeResult, mocked, err := e.mockToolResult(ctx, cfg.Tool, argsRaw)
 	if err != nil {
 		return nil, err
 	}
-	if invokeResult.IsError {
-		return nil, errors.New(ToolErrorMessage(invokeResult))
+	if !mocked {
+		if invokeResult, err = e.invoke(ctx, tenantID, cfg, argsRaw); err != nil {
+			return nil, err
+		}
 	}
 	outputRaw, err := json.Marshal(invokeResult)
 	if err != nil {
@@ -100,6 +98,81 @@ func (e *StepExecutor) Execute(ctx context.Context, caseID uuid.UUID, _ string,
 	}, nil
 }
 
+// invoke calls the step's tool on its server. A result flagged as an error
+// is returned as one.
+func (e *StepExecutor) invoke(ctx context.Context, tenantID uuid.UUID, cfg StepConfig, argsRaw json.RawMessage) (MCPToolResult, error) {
+	auth := AuthConfig{
+		Type:       cfg.AuthType,
+		SecretRef:  cfg.AuthSecret,
+		HeaderName: cfg.AuthHeader,
+	}
+	if pinned := strings.TrimSpace(cfg.ToolVersion); pinned != "" {
+		if err := e.checkPinnedVersion(ctx, tenantID, cfg.ServerURL, auth, cfg.Tool, pinned); err != nil {
+			return MCPToolResult{}, err
+		}
+	}
+	timeoutMS := cfg.TimeoutMS
+	if timeoutMS <= 0 && cfg.TimeoutSecond > 0 {
+		timeoutMS = int((time.Duration(cfg.TimeoutSecond) * time.Second) / time.Millisecond)
+	}
+	toolCtx, endSpan := observability.StartToolSpan(ctx, cfg.Tool, "mcp")
+	if err := chaos.Inject(toolCtx, "mcp", cfg.Tool); err != nil {
+		endSpan(err)
+		return MCPToolResult{}, err
+	}
+	invokeResult, err := e.manager.InvokeTool(toolCtx, InvokeRequest{
+		TenantID:  tenantID,
+		ServerURL: cfg.ServerURL,
+		Auth:      auth,
+		ToolName:  cfg.Tool,
+		Arguments: argsRaw,
+		Depth:     cfg.Depth,
+		TimeoutMS: timeoutMS,
+	})
+	if err == nil && invokeResult.IsError {
+		err = errors.New(ToolErrorMessage(invokeResult))
+	}
+	endSpan(err)
+	return invokeResult, err
+}
+
+// mockToolResult answers the call from the execution's mock profile, if it
+// has one. Canned output that is not an MCP tool result is returned as its
+// text content.
+func (e *StepExecutor) mockToolResult(ctx context.Context, tool string, argsRaw json.RawMessage) (MCPToolResult, bool, error) {
+	out, mocked, err := e.mocks.Intercept(ctx, "mcp", tool, argsRaw)
+	if !mocked || err != nil {
+		return MCPToolResult{}, mocked, err
+	}
+	var result MCPToolResult
+	if err := json.Unmarshal(out, &result); err != nil || len(result.Content) == 0 {
+		result = MCPToolResult{Content: []MCPContent{{Type: "text", Text: string(out)}}}
+	}
+	if result.IsError {
+		return MCPToolResult{}, true, errors.New(ToolErrorMessage(result))
+	}
+	return result, true, nil
+}
+
+// checkPinnedVersion refuses to call a tool whose input schema has changed
+// since the step was pinned to it.
+func (e *StepExecutor) checkPinnedVersion(ctx context.Context, tenantID uuid.UUID, serverURL string, auth AuthConfig, toolName, pinned string) error {
+	tools, err := e.manager.DiscoverTools(ctx, tenantID, serverURL, auth)
+	if err != nil {
+		return fmt.Errorf("check pinned tool version: %w", err)
+	}
+	for _, tool := range tools {
+		if tool.Name != toolName {
+			continue
+		}
+		if tool.Version != pinned {
+			return fmt.Errorf("tool %q is pinned to version %s but the server offers %s", toolName, pinned, tool.Version)
+		}
+		return nil
+	}
+	return fmt.Errorf("tool %q is pinned to version %s but the server no longer offers it", toolName, pinned)
+}
+
 func (e *StepExecutor) loadCaseContext(ctx context.Context, caseID uuid.UUID) (map[string]any, uuid.UUID, error) {
 	if e == nil || e.db == nil {
 		return nil, uuid.Nil, fmt.Errorf("mcp step executor db not configured")
@@ -17,11 +17,17 @@ type MCPTool struct {
 	Name        string          `json:"name"`
 	Description string          `json:"description"`
 	InputSchema json.RawMessage `json:"inputSchema"`
+	// Version is set by Aceryx from the input schema; see ToolVersion.
+	Version     string          `json:"version,omitempty"`
+	// Examples are example arguments taken from the input schema's
+	// "examples" keyword at discovery; see schemaExamples.
+	Examples []json.RawMessage `json:"examples,omitempty"`
 }
 
 type MCPToolResult struct {
-	Content []MCPContent `json:"content"`
-	IsError bool         `json:"isError"`
+	Content []MCPContent   `json:"content"`
+	IsError bool           `json:"isError"`
+	Meta    map[string]any `json:"_meta,omitempty"`
 }
 
 type MCPContent struct {
@@ -0,0 +1,175 @@
+package mcp
+
+import (
+	"bytes"
+	"context"
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"fmt"
+	"strings"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+// ToolVersionRecord is one input schema seen for a tool. Retired versions
+// are no longer offered by the server but are kept so pinned steps can be
+// diagnosed, until the cache's retired retention has passed.
+type ToolVersionRecord struct {
+	ToolName    string          `json:"tool_name"`
+	Version     string          `json:"version"`
+	Description string          `json:"description"`
+	InputSchema json.RawMessage `json:"inputSchema"`
+	FirstSeen   time.Time       `json:"first_seen"`
+	LastSeen    time.Time       `json:"last_seen"`
+	RetiredAt   *time.Time      `json:"retired_at,omitempty"`
+}
+
+// ToolVersion identifies a tool's contract: a short hash of its name and
+// input schema, so any schema change yields a new version while description
+// edits do not.
+func ToolVersion(tool MCPTool) string {
+	schema := bytes.TrimSpace(tool.InputSchema)
+	// Re-encode through a generic value so key order and whitespace do not
+	// change the version.
+	var v any
+	if err := json.Unmarshal(schema, &v); err == nil {
+		if canonical, err := json.Marshal(v); err == nil {
+			schema = canonical
+		}
+	}
+	sum := sha256.Sum256([]byte(strings.TrimSpace(tool.Name) + "\n" + string(schema)))
+	return hex.EncodeToString(sum[:6])
+}
+
+func withVersions(tools []MCPTool) []MCPTool {
+	for i := range tools {
+		if tools[i].Version == "" {
+			tools[i].Version = ToolVersion(tools[i])
+		}
+	}
+	return tools
+}
+
+// recordVersions adds the discovered versions to the history and retires
+// versions the server no longer offers.
+func (tc *ToolCache) recordVersions(ctx context.Context, tenantID uuid.UUID, serverURL string, tools []MCPTool) error {
+	tx, err := tc.db.BeginTx(ctx, nil)
+	if err != nil {
+		return fmt.Errorf("begin mcp tool versions tx: %w", err)
+	}
+	defer func() { _ = tx.Rollback() }()
+
+	current := make([]string, 0, len(tools))
+	for _, tool := range tools {
+		schema := string(tool.InputSchema)
+		if strings.TrimSpace(schema) == "" {
+			schema = "{}"
+		}
+		if _, err := tx.ExecContext(ctx, `
+INSERT INTO mcp_tool_versions (tenant_id, server_url, tool_name, version, description, input_schema)
+VALUES ($1, $2, $3, $4, $5, $6::jsonb)
+ON CONFLICT (tenant_id, server_url, tool_name, version)
+DO UPDATE SET
+	description = EXCLUDED.description,
+	last_seen = now(),
+	retired_at = NULL
+`, tenantID, serverURL, tool.Name, tool.Version, tool.Description, schema); err != nil {
+			return fmt.Errorf("record mcp tool version: %w", err)
+		}
+		current = append(current, tool.Name+"@"+tool.Version)
+	}
+	currentRaw, _ := json.Marshal(current)
+	if _, err := tx.ExecContext(ctx, `
+UPDATE mcp_tool_versions
+SET retired_at = now()
+WHERE tenant_id = $1
+  AND server_url = $2
+  AND retired_at IS NULL
+  AND tool_name || '@' || version NOT IN (SELECT jsonb_array_elements_text($3::jsonb))
+`, tenantID, serverURL, string(currentRaw)); err != nil {
+		return fmt.Errorf("retire mcp tool versions: %w", err)
+	}
+	if tc.retiredRetention > 0 {
+		if _, err := tx.ExecContext(ctx, `
+DELETE FROM mcp_tool_versions
+WHERE tenant_id = $1 AND server_url = $2 AND retired_at < $3
+`, tenantID, serverURL, time.Now().Add(-tc.retiredRetention)); err != nil {
+			return fmt.Errorf("delete retired mcp tool versions: %w", err)
+		}
+	}
+	if err := tx.Commit(); err != nil {
+		return fmt.Errorf("commit mcp tool versions tx: %w", err)
+	}
+	return nil
+}
+
+// ListToolVersions returns every version seen for a tool, newest first.
+func (tc *ToolCache) ListToolVersions(ctx context.Context, tenantID uuid.UUID, serverURL, toolName string) ([]ToolVersionRecord, error) {
+	if tc == nil || tc.db == nil {
+		return []ToolVersionRecord{}, nil
+	}
+	rows, err := tc.db.QueryContext(ctx, `
+SELECT tool_name, version, description, input_schema, first_seen, last_seen, retired_at
+FROM mcp_tool_versions
+WHERE tenant_id = $1 AND server_url = $2 AND tool_name = $3
+ORDER BY first_seen DESC
+`, tenantID, strings.TrimSpace(serverURL), strings.TrimSpace(toolName))
+	if err != nil {
+		return nil, fmt.Errorf("list mcp tool versions: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	out := []ToolVersionRecord{}
+	for rows.Next() {
+		var (
+			item   ToolVersionRecord
+			schema []byte
+		)
+		if err := rows.Scan(&item.ToolName, &item.Version, &item.Description, &schema, &item.FirstSeen, &item.LastSeen, &item.RetiredAt); err != nil {
+			return nil, fmt.Errorf("scan mcp tool version: %w", err)
+		}
+		item.InputSchema = schema
+		out = append(out, item)
+	}
+	if err := rows.Err(); err != nil {
+		return nil, fmt.Errorf("iterate mcp tool versions: %w", err)
+	}
+	return out, nil
+}
+
+// PinnedToolWarning explains why a step pinned to version cannot rely on
+// it, or returns "" when the version is the one the server offers. With no
+// version it only reports a tool the server no longer offers. Servers that
+// have not been discovered yet are not reported.
+func (tc *ToolCache) PinnedToolWarning(ctx context.Context, tenantID uuid.UUID, serverURL, toolName, version string) (string, error) {
+	versions, err := tc.ListToolVersions(ctx, tenantID, serverURL, toolName)
+	if err != nil || len(versions) == 0 {
+		return "", err
+	}
+	return pinnedVersionWarning(toolName, version, versions), nil
+}
+
+func pinnedVersionWarning(toolName, version string, versions []ToolVersionRecord) string {
+	current := ""
+	pinnedKnown := false
+	for _, v := range versions {
+		if v.RetiredAt == nil && current == "" {
+			current = v.Version
+		}
+		if v.Version == version || version == "" {
+			if v.RetiredAt == nil {
+				return ""
+			}
+			pinnedKnown = true
+		}
+	}
+	switch {
+	case current == "":
+		return fmt.Sprintf("tool %q is no longer offered by the server", toolName)
+	case pinnedKnown:
+		return fmt.Sprintf("tool %q version %s has been replaced by %s; review the input schema and update the pin", toolName, version, current)
+	default:
+		return fmt.Sprintf("tool %q has no version %s; the server offers %s", toolName, version, current)
+	}
+}
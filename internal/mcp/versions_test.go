@@ -0,0 +1,50 @@
+package mcp
+
+import (
+	"encoding/json"
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestToolVersionTracksSchemaOnly(t *testing.T) {
+	base := MCPTool{Name: "search", Description: "Search", InputSchema: json.RawMessage(`{"type":"object","properties":{"q":{"type":"string"}}}`)}
+	reordered := MCPTool{Name: "search", Description: "Find things", InputSchema: json.RawMessage(`{ "properties": {"q": {"type": "string"}}, "type": "object" }`)}
+	if ToolVersion(base) != ToolVersion(reordered) {
+		t.Fatal("expected key order, whitespace and description to leave the version unchanged")
+	}
+	changed := base
+	changed.InputSchema = json.RawMessage(`{"type":"object","properties":{"q":{"type":"integer"}}}`)
+	if ToolVersion(base) == ToolVersion(changed) {
+		t.Fatal("expected a schema change to produce a new version")
+	}
+	if len(ToolVersion(base)) != 12 {
+		t.Fatalf("unexpected version %q", ToolVersion(base))
+	}
+}
+
+func TestPinnedVersionWarning(t *testing.T) {
+	retired := time.Now()
+	versions := []ToolVersionRecord{
+		{ToolName: "search", Version: "new"},
+		{ToolName: "search", Version: "old", RetiredAt: &retired},
+	}
+	if got := pinnedVersionWarning("search", "new", versions); got != "" {
+		t.Fatalf("expected no warning for the current version, got %q", got)
+	}
+	if got := pinnedVersionWarning("search", "old", versions); !strings.Contains(got, "replaced by new") {
+		t.Fatalf("unexpected warning for a retired version: %q", got)
+	}
+	if got := pinnedVersionWarning("search", "typo", versions); !strings.Contains(got, "has no version typo") {
+		t.Fatalf("unexpected warning for an unknown version: %q", got)
+	}
+	if got := pinnedVersionWarning("search", "", versions); got != "" {
+		t.Fatalf("expected no warning for an unpinned tool still offered, got %q", got)
+	}
+	gone := []ToolVersionRecord{{ToolName: "search", Version: "old", RetiredAt: &retired}}
+	for _, version := range []string{"old", ""} {
+		if got := pinnedVersionWarning("search", version, gone); !strings.Contains(got, "no longer offered") {
+			t.Fatalf("unexpected warning for a removed tool pinned to %q: %q", version, got)
+		}
+	}
+}
@@ -6,7 +6,7 @@ import (
 	"encoding/json"
 	"fmt"
 
-	"golang.org/x/crypto/bcrypt"
+	"github.com/neural-chilli/aceryx/internal/rbac"
 )
 
 type roleSeed struct {
@@ -26,7 +26,9 @@ var allPermissions = []string{
 	"tasks:escalate",
 	"workflows:view",
 	"workflows:deploy",
+	"workflows:review",
 	"workflows:edit",
+	"workflows:share",
 	"vault:upload",
 	"vault:download",
 	"vault:delete",
@@ -34,6 +36,7 @@ var allPermissions = []string{
 	"admin:roles",
 	"admin:tenant",
 	"admin:audit",
+	"admin:tokens",
 	"reports:query",
 }
 
@@ -58,7 +61,7 @@ var defaultRoleSeeds = map[string]roleSeed{
 
 // SeedDefaultData seeds a default tenant, admin principal, roles, and assignments.
 func SeedDefaultData(ctx context.Context, db *sql.DB) error {
-	passwordHashBytes, err := bcrypt.GenerateFromPassword([]byte("admin"), 12)
+	passwordHash, err := rbac.HashPassword("admin")
 	if err != nil {
 		return fmt.Errorf("hash admin password: %w", err)
 	}
@@ -133,7 +136,7 @@ SET
     password_hash = EXCLUDED.password_hash,
     status = EXCLUDED.status
 RETURNING id
-`, tenantID, "Administrator", "admin@localhost", string(passwordHashBytes)).Scan(&principalID)
+`, tenantID, "Administrator", "admin@localhost", passwordHash).Scan(&principalID)
 	if err != nil {
 		_ = tx.Rollback()
 		return fmt.Errorf("upsert admin principal: %w", err)
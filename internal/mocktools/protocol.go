@@ -0,0 +1,162 @@
+package mocktools
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"reflect"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
+	"github.com/neural-chilli/aceryx/internal/observability"
+)
+
+// ProfileLoader loads a tenant's mock profile by name; Store implements it.
+type ProfileLoader interface {
+	Get(ctx context.Context, tenantID uuid.UUID, name string) (Profile, error)
+}
+
+// MockProtocol answers tool calls from the mock profile of the execution
+// making them.
+type MockProtocol struct {
+	profiles ProfileLoader
+}
+
+func NewMockProtocol(profiles ProfileLoader) *MockProtocol {
+	return &MockProtocol{profiles: profiles}
+}
+
+// Intercept reports whether a call of toolID is mocked and, if so, its
+// output or error. Tool executors call it before calling the tool and skip
+// the tool when the call is mocked. Calls made without a mock profile in
+// ctx, or through a nil MockProtocol, are not mocked. The profile's tenant
+// is taken from ctx.
+func (p *MockProtocol) Intercept(ctx context.Context, protocol, toolID string, input any) (json.RawMessage, bool, error) {
+	name := engine.MockProfileFromContext(ctx)
+	if p == nil || name == "" {
+		return nil, false, nil
+	}
+	tenantID, err := uuid.Parse(observability.TenantIDFromContext(ctx))
+	if err != nil {
+		return nil, true, fmt.Errorf("mock profile %s: execution has no tenant", name)
+	}
+	profile, err := p.profiles.Get(ctx, tenantID, name)
+	if err != nil {
+		return nil, true, fmt.Errorf("load mock profile %s: %w", name, err)
+	}
+	tool, ok := profile.lookup(protocol, toolID)
+	if !ok {
+		if profile.Passthrough {
+			return nil, false, nil
+		}
+		return nil, true, fmt.Errorf("%w: profile %s does not mock %s tool %s", ErrNoMockResponse, name, protocol, toolID)
+	}
+	args, err := genericJSON(input)
+	if err != nil {
+		return nil, true, fmt.Errorf("mock %s tool %s: %w", protocol, toolID, err)
+	}
+	for _, resp := range tool.Responses {
+		if !resp.matches(args) {
+			continue
+		}
+		if resp.DelayMS > 0 {
+			timer := time.NewTimer(time.Duration(resp.DelayMS) * time.Millisecond)
+			select {
+			case <-timer.C:
+			case <-ctx.Done():
+				timer.Stop()
+				return nil, true, ctx.Err()
+			}
+		}
+		if resp.Error != "" {
+			return nil, true, errors.New(resp.Error)
+		}
+		if len(resp.Output) == 0 {
+			return json.RawMessage(`{}`), true, nil
+		}
+		return resp.Output, true, nil
+	}
+	return nil, true, fmt.Errorf("%w: no response of profile %s matches the input of %s tool %s", ErrNoMockResponse, name, protocol, toolID)
+}
+
+// lookup finds the mock for a tool, preferring one for the call's protocol
+// over one for any protocol.
+func (p Profile) lookup(protocol, toolID string) (Tool, bool) {
+	var fallback *Tool
+	for i := range p.Tools {
+		tool := &p.Tools[i]
+		if tool.Name != toolID {
+			continue
+		}
+		if tool.Protocol == protocol {
+			return *tool, true
+		}
+		if tool.Protocol == "" && fallback == nil {
+			fallback = tool
+		}
+	}
+	if fallback == nil {
+		return Tool{}, false
+	}
+	return *fallback, true
+}
+
+func (r Response) matches(args any) bool {
+	if len(r.Match) == 0 {
+		return true
+	}
+	var match any
+	if err := json.Unmarshal(r.Match, &match); err != nil {
+		return false
+	}
+	return contains(args, match)
+}
+
+// contains reports whether every key of want is in got with a contained
+// value. Values other than objects must be equal.
+func contains(got, want any) bool {
+	wantObj, ok := want.(map[string]any)
+	if !ok {
+		return reflect.DeepEqual(got, want)
+	}
+	gotObj, ok := got.(map[string]any)
+	if !ok {
+		return false
+	}
+	for key, value := range wantObj {
+		actual, ok := gotObj[key]
+		if !ok || !contains(actual, value) {
+			return false
+		}
+	}
+	return true
+}
+
+// genericJSON converts input to the values encoding/json decodes into, so
+// it compares equal to decoded match objects.
+func genericJSON(input any) (any, error) {
+	var raw []byte
+	switch v := input.(type) {
+	case nil:
+		return map[string]any{}, nil
+	case json.RawMessage:
+		raw = v
+	case []byte:
+		raw = v
+	default:
+		var err error
+		if raw, err = json.Marshal(v); err != nil {
+			return nil, fmt.Errorf("encode input: %w", err)
+		}
+	}
+	if len(raw) == 0 {
+		return map[string]any{}, nil
+	}
+	var out any
+	if err := json.Unmarshal(raw, &out); err != nil {
+		return nil, fmt.Errorf("decode input: %w", err)
+	}
+	return out, nil
+}
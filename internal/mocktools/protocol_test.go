@@ -0,0 +1,117 @@
+package mocktools
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"testing"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
+	"github.com/neural-chilli/aceryx/internal/observability"
+)
+
+type fakeLoader map[string]Profile
+
+func (f fakeLoader) Get(_ context.Context, tenantID uuid.UUID, name string) (Profile, error) {
+	profile, ok := f[name]
+	if !ok || profile.TenantID != tenantID {
+		return Profile{}, ErrProfileNotFound
+	}
+	return profile, nil
+}
+
+func mockContext(tenantID uuid.UUID, profile string) context.Context {
+	ctx := observability.WithTenantID(context.Background(), tenantID)
+	return engine.WithMockProfile(ctx, profile)
+}
+
+func TestInterceptAnswersMatchingResponse(t *testing.T) {
+	tenant := uuid.New()
+	p := NewMockProtocol(fakeLoader{"ci": {
+		TenantID: tenant,
+		Name:     "ci",
+		Tools: []Tool{
+			{Name: "lookup", Responses: []Response{{Output: json.RawMessage(`{"any":true}`)}}},
+			{Protocol: "mcp", Name: "lookup", Responses: []Response{
+				{Match: json.RawMessage(`{"customer":{"tier":"gold"}}`), Output: json.RawMessage(`{"limit":5000}`)},
+				{Match: json.RawMessage(`{"customer":{"tier":"blocked"}}`), Error: "customer is blocked"},
+			}},
+		},
+	}})
+	ctx := mockContext(tenant, "ci")
+
+	out, mocked, err := p.Intercept(ctx, "mcp", "lookup", map[string]any{"customer": map[string]any{"tier": "gold", "id": 7}})
+	if err != nil || !mocked || string(out) != `{"limit":5000}` {
+		t.Fatalf("expected the matching response, got %s, %v, %v", out, mocked, err)
+	}
+	if _, mocked, err := p.Intercept(ctx, "mcp", "lookup", json.RawMessage(`{"customer":{"tier":"blocked"}}`)); !mocked || err == nil || err.Error() != "customer is blocked" {
+		t.Fatalf("expected the mocked error, got %v, %v", mocked, err)
+	}
+	if _, _, err := p.Intercept(ctx, "mcp", "lookup", map[string]any{"customer": map[string]any{"tier": "silver"}}); !errors.Is(err, ErrNoMockResponse) {
+		t.Fatalf("expected no matching response, got %v", err)
+	}
+	out, mocked, err = p.Intercept(ctx, "connector", "lookup", nil)
+	if err != nil || !mocked || string(out) != `{"any":true}` {
+		t.Fatalf("expected the any-protocol mock for other protocols, got %s, %v, %v", out, mocked, err)
+	}
+}
+
+func TestInterceptPassthroughAndUnmockedCalls(t *testing.T) {
+	tenant := uuid.New()
+	p := NewMockProtocol(fakeLoader{
+		"strict":  {TenantID: tenant, Name: "strict"},
+		"partial": {TenantID: tenant, Name: "partial", Passthrough: true},
+	})
+
+	if _, mocked, err := p.Intercept(observability.WithTenantID(context.Background(), tenant), "grpc", "Lookup", nil); mocked || err != nil {
+		t.Fatalf("expected calls without a profile to reach the tool, got %v, %v", mocked, err)
+	}
+	var unset *MockProtocol
+	if _, mocked, err := unset.Intercept(mockContext(tenant, "strict"), "grpc", "Lookup", nil); mocked || err != nil {
+		t.Fatalf("expected executors without a mock protocol to call the tool, got %v, %v", mocked, err)
+	}
+	if _, mocked, err := p.Intercept(mockContext(tenant, "partial"), "grpc", "Lookup", nil); mocked || err != nil {
+		t.Fatalf("expected a passthrough profile to let unmocked calls through, got %v, %v", mocked, err)
+	}
+	if _, mocked, err := p.Intercept(mockContext(tenant, "strict"), "grpc", "Lookup", nil); !mocked || !errors.Is(err, ErrNoMockResponse) {
+		t.Fatalf("expected unmocked calls to fail, got %v, %v", mocked, err)
+	}
+	if _, mocked, err := p.Intercept(mockContext(uuid.New(), "strict"), "grpc", "Lookup", nil); !mocked || !errors.Is(err, ErrProfileNotFound) {
+		t.Fatalf("expected another tenant's profile to be unavailable, got %v, %v", mocked, err)
+	}
+}
+
+func TestInterceptDelayStopsWithContext(t *testing.T) {
+	tenant := uuid.New()
+	p := NewMockProtocol(fakeLoader{"slow": {
+		TenantID: tenant,
+		Name:     "slow",
+		Tools:    []Tool{{Name: "lookup", Responses: []Response{{DelayMS: int(time.Hour / time.Millisecond)}}}},
+	}})
+	ctx, cancel := context.WithTimeout(mockContext(tenant, "slow"), 10*time.Millisecond)
+	defer cancel()
+	if _, _, err := p.Intercept(ctx, "mcp", "lookup", nil); !errors.Is(err, context.DeadlineExceeded) {
+		t.Fatalf("expected the delay to end with the call's deadline, got %v", err)
+	}
+}
+
+func TestNormalizeRejectsInvalidProfiles(t *testing.T) {
+	for name, req := range map[string]ProfileRequest{
+		"name":      {Name: "Bad Name"},
+		"protocol":  {Name: "ci", Tools: []Tool{{Protocol: "smtp", Name: "send", Responses: []Response{{}}}}},
+		"duplicate": {Name: "ci", Tools: []Tool{{Name: "a", Responses: []Response{{}}}, {Name: "a", Responses: []Response{{}}}}},
+		"responses": {Name: "ci", Tools: []Tool{{Name: "a"}}},
+		"match":     {Name: "ci", Tools: []Tool{{Name: "a", Responses: []Response{{Match: json.RawMessage(`[1]`)}}}}},
+		"both":      {Name: "ci", Tools: []Tool{{Name: "a", Responses: []Response{{Output: json.RawMessage(`{}`), Error: "x"}}}}},
+	} {
+		if _, err := normalize(req); !errors.Is(err, ErrInvalidProfile) {
+			t.Fatalf("%s: expected an invalid profile, got %v", name, err)
+		}
+	}
+	profile, err := normalize(ProfileRequest{Name: " ci ", Tools: []Tool{{Protocol: "mcp", Name: " lookup ", Responses: []Response{{}}}}})
+	if err != nil || profile.Name != "ci" || profile.Tools[0].Name != "lookup" {
+		t.Fatalf("unexpected profile %+v, %v", profile, err)
+	}
+}
@@ -0,0 +1,193 @@
+package mocktools
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"regexp"
+	"slices"
+	"strings"
+
+	"github.com/google/uuid"
+)
+
+var profileNamePattern = regexp.MustCompile(`^[a-z0-9][a-z0-9_-]{0,63}$`)
+
+type Store struct {
+	db *sql.DB
+}
+
+func NewStore(db *sql.DB) *Store {
+	return &Store{db: db}
+}
+
+func (s *Store) Create(ctx context.Context, tenantID, createdBy uuid.UUID, req ProfileRequest) (Profile, error) {
+	profile, err := normalize(req)
+	if err != nil {
+		return Profile{}, err
+	}
+	tools, _ := json.Marshal(profile.Tools)
+	profile.TenantID = tenantID
+	profile.CreatedBy = &createdBy
+	err = s.db.QueryRowContext(ctx, `
+INSERT INTO mock_profiles (tenant_id, name, description, passthrough, tools, created_by)
+VALUES ($1, $2, $3, $4, $5::jsonb, $6)
+ON CONFLICT (tenant_id, name) DO NOTHING
+RETURNING id, created_at, updated_at
+`, tenantID, profile.Name, profile.Description, profile.Passthrough, string(tools), createdBy).Scan(&profile.ID, &profile.CreatedAt, &profile.UpdatedAt)
+	if errors.Is(err, sql.ErrNoRows) {
+		return Profile{}, ErrProfileExists
+	}
+	if err != nil {
+		return Profile{}, fmt.Errorf("insert mock profile: %w", err)
+	}
+	return profile, nil
+}
+
+// Update replaces the named profile's description and tools. The name in req
+// is ignored.
+func (s *Store) Update(ctx context.Context, tenantID uuid.UUID, name string, req ProfileRequest) (Profile, error) {
+	req.Name = strings.TrimSpace(name)
+	profile, err := normalize(req)
+	if err != nil {
+		return Profile{}, err
+	}
+	tools, _ := json.Marshal(profile.Tools)
+	row := s.db.QueryRowContext(ctx, `
+UPDATE mock_profiles
+SET description = $3, passthrough = $4, tools = $5::jsonb, updated_at = now()
+WHERE tenant_id = $1 AND name = $2
+RETURNING id, tenant_id, name, description, passthrough, tools, created_by, created_at, updated_at
+`, tenantID, profile.Name, profile.Description, profile.Passthrough, string(tools))
+	out, err := scanProfile(row)
+	if errors.Is(err, sql.ErrNoRows) {
+		return Profile{}, ErrProfileNotFound
+	}
+	return out, err
+}
+
+func (s *Store) List(ctx context.Context, tenantID uuid.UUID) ([]Profile, error) {
+	rows, err := s.db.QueryContext(ctx, `
+SELECT id, tenant_id, name, description, passthrough, tools, created_by, created_at, updated_at
+FROM mock_profiles
+WHERE tenant_id = $1
+ORDER BY name
+`, tenantID)
+	if err != nil {
+		return nil, fmt.Errorf("list mock profiles: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	out := make([]Profile, 0)
+	for rows.Next() {
+		profile, err := scanProfile(rows)
+		if err != nil {
+			return nil, err
+		}
+		out = append(out, profile)
+	}
+	if err := rows.Err(); err != nil {
+		return nil, fmt.Errorf("iterate mock profiles: %w", err)
+	}
+	return out, nil
+}
+
+func (s *Store) Get(ctx context.Context, tenantID uuid.UUID, name string) (Profile, error) {
+	row := s.db.QueryRowContext(ctx, `
+SELECT id, tenant_id, name, description, passthrough, tools, created_by, created_at, updated_at
+FROM mock_profiles
+WHERE tenant_id = $1 AND name = $2
+`, tenantID, strings.TrimSpace(name))
+	profile, err := scanProfile(row)
+	if errors.Is(err, sql.ErrNoRows) {
+		return Profile{}, ErrProfileNotFound
+	}
+	return profile, err
+}
+
+func (s *Store) Delete(ctx context.Context, tenantID uuid.UUID, name string) error {
+	res, err := s.db.ExecContext(ctx, `DELETE FROM mock_profiles WHERE tenant_id = $1 AND name = $2`, tenantID, strings.TrimSpace(name))
+	if err != nil {
+		return fmt.Errorf("delete mock profile: %w", err)
+	}
+	if n, _ := res.RowsAffected(); n == 0 {
+		return ErrProfileNotFound
+	}
+	return nil
+}
+
+type rowScanner interface {
+	Scan(dest ...any) error
+}
+
+func scanProfile(row rowScanner) (Profile, error) {
+	var (
+		profile Profile
+		tools   []byte
+	)
+	if err := row.Scan(&profile.ID, &profile.TenantID, &profile.Name, &profile.Description, &profile.Passthrough, &tools, &profile.CreatedBy, &profile.CreatedAt, &profile.UpdatedAt); err != nil {
+		return Profile{}, err
+	}
+	if err := json.Unmarshal(tools, &profile.Tools); err != nil {
+		return Profile{}, fmt.Errorf("decode mock profile tools: %w", err)
+	}
+	return profile, nil
+}
+
+func normalize(req ProfileRequest) (Profile, error) {
+	profile := Profile{
+		Name:        strings.TrimSpace(req.Name),
+		Description: strings.TrimSpace(req.Description),
+		Passthrough: req.Passthrough,
+		Tools:       make([]Tool, 0, len(req.Tools)),
+	}
+	if !profileNamePattern.MatchString(profile.Name) {
+		return Profile{}, fmt.Errorf("%w: name must be lowercase letters, digits, '-' or '_', up to 64 characters", ErrInvalidProfile)
+	}
+	seen := map[string]bool{}
+	for i, tool := range req.Tools {
+		tool.Protocol = strings.TrimSpace(tool.Protocol)
+		tool.Name = strings.TrimSpace(tool.Name)
+		if tool.Name == "" {
+			return Profile{}, fmt.Errorf("%w: tools[%d] needs a name", ErrInvalidProfile, i)
+		}
+		if tool.Protocol != "" && !slices.Contains(Protocols, tool.Protocol) {
+			return Profile{}, fmt.Errorf("%w: tools[%d] has unknown protocol %q", ErrInvalidProfile, i, tool.Protocol)
+		}
+		key := tool.Protocol + "\x00" + tool.Name
+		if seen[key] {
+			return Profile{}, fmt.Errorf("%w: tool %s is mocked twice", ErrInvalidProfile, tool.Name)
+		}
+		seen[key] = true
+		if len(tool.Responses) == 0 {
+			return Profile{}, fmt.Errorf("%w: tool %s needs at least one response", ErrInvalidProfile, tool.Name)
+		}
+		for j, resp := range tool.Responses {
+			if err := resp.validate(); err != nil {
+				return Profile{}, fmt.Errorf("%w: tool %s responses[%d]: %v", ErrInvalidProfile, tool.Name, j, err)
+			}
+		}
+		profile.Tools = append(profile.Tools, tool)
+	}
+	return profile, nil
+}
+
+func (r Response) validate() error {
+	if len(r.Match) > 0 {
+		var match map[string]any
+		if err := json.Unmarshal(r.Match, &match); err != nil {
+			return errors.New("match must be a JSON object")
+		}
+	}
+	if len(r.Output) > 0 && !json.Valid(r.Output) {
+		return errors.New("output must be valid JSON")
+	}
+	if r.Error != "" && len(r.Output) > 0 {
+		return errors.New("set output or error, not both")
+	}
+	if r.DelayMS < 0 {
+		return errors.New("delay_ms cannot be negative")
+	}
+	return nil
+}
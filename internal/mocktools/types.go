@@ -0,0 +1,70 @@
+// Package mocktools answers tool calls from canned responses instead of the
+// real tools. A case created with a mock profile has every connector, MCP,
+// custom HTTP and gRPC call it makes answered from that profile, so CI can
+// run workflows without touching real systems.
+package mocktools
+
+import (
+	"encoding/json"
+	"errors"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+const ProtocolName = "mock"
+
+var (
+	ErrProfileNotFound = errors.New("mock profile not found")
+	ErrProfileExists   = errors.New("mock profile already exists")
+	ErrInvalidProfile  = errors.New("invalid mock profile")
+	// ErrNoMockResponse is returned for a call the execution's profile has
+	// no response for.
+	ErrNoMockResponse = errors.New("no mock response")
+)
+
+// Protocols a mock tool may stand in for. An empty protocol matches a tool
+// of that name in any of them.
+var Protocols = []string{"connector", "mcp", "custom_http", "grpc"}
+
+// Profile is a named set of mock tools. Unless Passthrough is set, calls to
+// tools the profile does not mock fail instead of reaching the real tool.
+type Profile struct {
+	ID          uuid.UUID  `json:"id"`
+	TenantID    uuid.UUID  `json:"tenant_id"`
+	Name        string     `json:"name"`
+	Description string     `json:"description"`
+	Passthrough bool       `json:"passthrough"`
+	Tools       []Tool     `json:"tools"`
+	CreatedBy   *uuid.UUID `json:"created_by,omitempty"`
+	CreatedAt   time.Time  `json:"created_at"`
+	UpdatedAt   time.Time  `json:"updated_at"`
+}
+
+// Tool mocks one tool. Name is the connector's "connector/action", or the
+// MCP tool, custom tool or gRPC method name. The first response whose Match
+// fits the call's input answers it.
+type Tool struct {
+	Protocol  string     `json:"protocol,omitempty"`
+	Name      string     `json:"name"`
+	Responses []Response `json:"responses"`
+}
+
+// Response is a canned answer. Match is a JSON object that the input must
+// contain: every key must be present with an equal value, and nested objects
+// are matched the same way. An empty Match answers any input. A response
+// with Error fails the call with that message; otherwise the call returns
+// Output after DelayMS.
+type Response struct {
+	Match   json.RawMessage `json:"match,omitempty"`
+	Output  json.RawMessage `json:"output,omitempty"`
+	Error   string          `json:"error,omitempty"`
+	DelayMS int             `json:"delay_ms,omitempty"`
+}
+
+type ProfileRequest struct {
+	Name        string `json:"name"`
+	Description string `json:"description"`
+	Passthrough bool   `json:"passthrough"`
+	Tools       []Tool `json:"tools"`
+}
@@ -0,0 +1,157 @@
+package notify
+
+import (
+	"bytes"
+	"context"
+	"html"
+	"html/template"
+	"io/fs"
+	"strings"
+	"sync"
+
+	"github.com/google/uuid"
+)
+
+const defaultLocale = "en"
+
+// resolveLocale picks the locale for an event: an explicit event locale wins,
+// then a "locale" value carried in the event data (typically a case variable),
+// then the tenant's default_locale setting, then English.
+func (s *Service) resolveLocale(ctx context.Context, event NotifyEvent) string {
+	if v := normalizeLocale(event.Locale); v != "" {
+		return v
+	}
+	if v, ok := event.Data["locale"].(string); ok {
+		if v = normalizeLocale(v); v != "" {
+			return v
+		}
+	}
+	localeFn := s.localeFn
+	if localeFn == nil {
+		if s.db == nil {
+			return defaultLocale
+		}
+		localeFn = s.loadTenantLocale
+	}
+	tenantLocale, err := localeFn(ctx, event.TenantID)
+	if err != nil {
+		s.logger.Printf("notify: load tenant locale failed tenant=%s event=%s err=%v", event.TenantID, event.Type, err)
+		return defaultLocale
+	}
+	if v := normalizeLocale(tenantLocale); v != "" {
+		return v
+	}
+	return defaultLocale
+}
+
+func (s *Service) loadTenantLocale(ctx context.Context, tenantID uuid.UUID) (string, error) {
+	var locale string
+	if err := s.db.QueryRowContext(ctx, `
+SELECT COALESCE(settings->>'default_locale', '')
+FROM tenants
+WHERE id = $1
+`, tenantID).Scan(&locale); err != nil {
+		return "", err
+	}
+	return locale, nil
+}
+
+// LocaleChain returns the lookup order for a locale, most specific first:
+// "pt-BR" yields ["pt-br", "pt"]. The unlocalised templates are the final
+// fallback and are not part of the chain.
+func LocaleChain(locale string) []string {
+	locale = normalizeLocale(locale)
+	if locale == "" {
+		return nil
+	}
+	chain := []string{locale}
+	for {
+		idx := strings.LastIndex(locale, "-")
+		if idx <= 0 {
+			break
+		}
+		locale = locale[:idx]
+		chain = append(chain, locale)
+	}
+	return chain
+}
+
+func normalizeLocale(locale string) string {
+	return strings.ToLower(strings.ReplaceAll(strings.TrimSpace(locale), "_", "-"))
+}
+
+// LocalizedTemplatePath returns dir/<locale>/name.html for the first locale
+// of LocaleChain(locale) that fsys has, or dir/name.html.
+func LocalizedTemplatePath(fsys fs.FS, dir, name, locale string) string {
+	for _, candidate := range LocaleChain(locale) {
+		path := dir + "/" + candidate + "/" + name + ".html"
+		if _, err := fs.Stat(fsys, path); err == nil {
+			return path
+		}
+	}
+	return dir + "/" + name + ".html"
+}
+
+// EmailTemplates parses localized email templates from dir in fsys: a
+// "_base" layout plus one content template per name, each resolved
+// independently along the locale chain, so a locale may override just the
+// content and still inherit the default layout. Parsed templates are kept
+// per resolved file pair, so each is parsed once.
+type EmailTemplates struct {
+	fsys   fs.FS
+	dir    string
+	mu     sync.Mutex
+	parsed map[[2]string]*template.Template
+}
+
+func NewEmailTemplates(fsys fs.FS, dir string) *EmailTemplates {
+	return &EmailTemplates{fsys: fsys, dir: dir, parsed: map[[2]string]*template.Template{}}
+}
+
+// Parse returns the template for name in locale. Templates are safe to
+// execute concurrently.
+func (t *EmailTemplates) Parse(name string, locale string) (*template.Template, error) {
+	key := [2]string{
+		LocalizedTemplatePath(t.fsys, t.dir, "_base", locale),
+		LocalizedTemplatePath(t.fsys, t.dir, name, locale),
+	}
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	if tpl, ok := t.parsed[key]; ok {
+		return tpl, nil
+	}
+	tpl, err := template.ParseFS(t.fsys, key[0], key[1])
+	if err != nil {
+		return nil, err
+	}
+	t.parsed[key] = tpl
+	return tpl, nil
+}
+
+var emailTemplates = NewEmailTemplates(emailTemplateFS, "templates/email")
+
+func parseEmailTemplate(name string, locale string) (*template.Template, error) {
+	return emailTemplates.Parse(name, locale)
+}
+
+// renderEmailSubject renders the optional "subject" block of a template. Only
+// localized templates need to define it; otherwise the fallback is returned.
+func renderEmailSubject(name string, data EmailData, fallback string) string {
+	tpl, err := parseEmailTemplate(name, data.Locale)
+	if err != nil {
+		return fallback
+	}
+	subjectTpl := tpl.Lookup("subject")
+	if subjectTpl == nil {
+		return fallback
+	}
+	var out bytes.Buffer
+	if err := subjectTpl.Execute(&out, data); err != nil {
+		return fallback
+	}
+	// html/template escapes the block for HTML output; headers want plain text.
+	if subject := strings.TrimSpace(html.UnescapeString(out.String())); subject != "" {
+		return subject
+	}
+	return fallback
+}
@@ -9,6 +9,7 @@ import (
 	"fmt"
 	"html/template"
 	"log"
+	"mime"
 	"mime/multipart"
 	"net/smtp"
 	"net/textproto"
@@ -19,7 +20,7 @@ import (
 	"github.com/google/uuid"
 )
 
-//go:embed templates/email/*.html
+//go:embed templates/email/*.html templates/email/*/*.html
 var emailTemplateFS embed.FS
 
 type Recipient struct {
@@ -35,6 +36,7 @@ type NotifyEvent struct {
 	CaseNumber string         `json:"case_number"`
 	StepID     string         `json:"step_id"`
 	StepLabel  string         `json:"step_label"`
+	Locale     string         `json:"locale,omitempty"`
 	Recipients []Recipient    `json:"recipients"`
 	Data       map[string]any `json:"data"`
 }
@@ -65,6 +67,7 @@ type EmailData struct {
 	Terminology TenantTerms    `json:"terminology"`
 	CaseURL     string         `json:"case_url"`
 	AppURL      string         `json:"app_url"`
+	Locale      string         `json:"locale"`
 	Custom      map[string]any `json:"custom"`
 }
 
@@ -109,6 +112,7 @@ type Service struct {
 	sendFunc func(context.Context, uuid.UUID, map[string]any) error
 	activeFn func(context.Context, uuid.UUID) (bool, error)
 	ctxFn    func(context.Context, uuid.UUID) (TenantBranding, TenantTerms, error)
+	localeFn func(context.Context, uuid.UUID) (string, error)
 	async    bool
 }
 
@@ -132,6 +136,7 @@ func NewService(db *sql.DB, hub *Hub) *Service {
 	}
 	s.activeFn = s.isPrincipalActive
 	s.ctxFn = s.loadTenantContext
+	s.localeFn = s.loadTenantLocale
 	return s
 }
 
@@ -155,6 +160,7 @@ func (s *Service) Notify(ctx context.Context, event NotifyEvent) error {
 		branding = defaultBranding()
 		terms = defaultTerminology()
 	}
+	locale := s.resolveLocale(ctx, event)
 
 	seen := map[uuid.UUID]map[string]struct{}{}
 	for _, rec := range event.Recipients {
@@ -212,6 +218,7 @@ func (s *Service) Notify(ctx context.Context, event NotifyEvent) error {
 					Terminology: terms,
 					CaseURL:     strings.TrimRight(s.appURL, "/") + "/cases/" + event.CaseID.String(),
 					AppURL:      s.appURL,
+					Locale:      locale,
 					Custom:      event.Data,
 				}
 				if v, ok := event.Data["sla_deadline"].(string); ok {
@@ -225,6 +232,7 @@ func (s *Service) Notify(ctx context.Context, event NotifyEvent) error {
 					s.logger.Printf("notify: render email failed template=%s event=%s err=%v", templateName, event.Type, rerr)
 					continue
 				}
+				subject = renderEmailSubject(templateName, emailData, subject)
 				sendEmail := func(to string, sub string, plain string, html string) {
 					sendCtx, cancel := context.WithTimeout(context.Background(), 15*time.Second)
 					defer cancel()
@@ -314,7 +322,7 @@ func emailTemplateForEvent(eventType string, terms TenantTerms) (subject string,
 }
 
 func renderEmailTemplate(name string, data EmailData) (string, string, error) {
-	tpl, err := template.ParseFS(emailTemplateFS, "templates/email/_base.html", "templates/email/"+name+".html")
+	tpl, err := parseEmailTemplate(name, data.Locale)
 	if err != nil {
 		return "", "", fmt.Errorf("parse email template %s: %w", name, err)
 	}
@@ -330,7 +338,7 @@ func buildMultipartMessage(from string, to string, subject string, plain string,
 	var body bytes.Buffer
 	writer := multipart.NewWriter(&body)
 	boundary := writer.Boundary()
-	headers := fmt.Sprintf("From: %s\r\nTo: %s\r\nSubject: %s\r\nMIME-Version: 1.0\r\nContent-Type: multipart/alternative; boundary=%s\r\n\r\n", from, to, subject, boundary)
+	headers := fmt.Sprintf("From: %s\r\nTo: %s\r\nSubject: %s\r\nMIME-Version: 1.0\r\nContent-Type: multipart/alternative; boundary=%s\r\n\r\n", from, to, mime.QEncoding.Encode("utf-8", subject), boundary)
 
 	partText, err := writer.CreatePart(mapToMIMEHeader(map[string]string{"Content-Type": "text/plain; charset=UTF-8"}))
 	if err != nil {
@@ -201,3 +201,101 @@ func TestMultipartEmailHasPlainAndHTMLParts(t *testing.T) {
 		t.Fatalf("expected html part in MIME message: %s", msg)
 	}
 }
+
+func TestLocaleChainFallsBackToLanguage(t *testing.T) {
+	got := LocaleChain("pt_BR")
+	if len(got) != 2 || got[0] != "pt-br" || got[1] != "pt" {
+		t.Fatalf("unexpected locale chain: %v", got)
+	}
+	if chain := LocaleChain(""); chain != nil {
+		t.Fatalf("expected empty chain for empty locale, got %v", chain)
+	}
+}
+
+func TestEmailTemplatesParseEachFilePairOnce(t *testing.T) {
+	first, err := parseEmailTemplate("task_assigned", "fr-CA")
+	if err != nil {
+		t.Fatalf("parse localized template: %v", err)
+	}
+	again, err := parseEmailTemplate("task_assigned", "fr")
+	if err != nil || again != first {
+		t.Fatalf("expected the parsed fr template to be reused, got %p vs %p, %v", again, first, err)
+	}
+	if LocalizedTemplatePath(emailTemplateFS, "templates/email", "task_assigned", "de") != "templates/email/task_assigned.html" {
+		t.Fatal("expected unknown locales to fall back to the default template")
+	}
+}
+
+func TestEmailTemplateRenderingUsesLocaleVariantWithFallback(t *testing.T) {
+	data := EmailData{
+		CaseNumber:  "CASE-321",
+		StepLabel:   "Revue",
+		Branding:    defaultBranding(),
+		Terminology: defaultTerminology(),
+		CaseURL:     "https://app.example.com/cases/1",
+		Locale:      "fr-CA",
+	}
+	html, _, err := renderEmailTemplate("task_assigned", data)
+	if err != nil {
+		t.Fatalf("render localized email template: %v", err)
+	}
+	if !strings.Contains(html, "Nouvelle task à traiter") || !strings.Contains(html, "Voir dans Aceryx") {
+		t.Fatalf("expected french content and layout: %s", html)
+	}
+	if subject := renderEmailSubject("task_assigned", data, "fallback"); subject != "Nouvelle task à traiter" {
+		t.Fatalf("expected localized subject, got %q", subject)
+	}
+
+	data.Locale = "de"
+	html, _, err = renderEmailTemplate("task_assigned", data)
+	if err != nil {
+		t.Fatalf("render fallback email template: %v", err)
+	}
+	if !strings.Contains(html, "You have a new task") {
+		t.Fatalf("expected default template for unknown locale: %s", html)
+	}
+	if subject := renderEmailSubject("task_assigned", data, "fallback"); subject != "fallback" {
+		t.Fatalf("expected fallback subject, got %q", subject)
+	}
+}
+
+func TestNotifyResolvesLocaleFromEventThenTenant(t *testing.T) {
+	email := &emailSpy{}
+	svc := &Service{
+		email:    email,
+		logger:   testLogger(),
+		appURL:   "http://app.local",
+		now:      fixedNow,
+		sendFunc: func(_ context.Context, _ uuid.UUID, _ map[string]any) error { return nil },
+		activeFn: func(_ context.Context, _ uuid.UUID) (bool, error) { return true, nil },
+		ctxFn: func(_ context.Context, _ uuid.UUID) (TenantBranding, TenantTerms, error) {
+			return defaultBranding(), defaultTerminology(), nil
+		},
+		localeFn: func(_ context.Context, _ uuid.UUID) (string, error) { return "fr", nil },
+		async:    false,
+	}
+	event := NotifyEvent{
+		Type:       "case_completed",
+		TenantID:   uuid.New(),
+		CaseID:     uuid.New(),
+		CaseNumber: "CASE-005",
+		Recipients: []Recipient{{PrincipalID: uuid.New(), Email: "user@example.com", Channels: []string{"email"}}},
+		Data:       map[string]any{},
+	}
+	if err := svc.Notify(context.Background(), event); err != nil {
+		t.Fatalf("notify returned error: %v", err)
+	}
+	event.Data = map[string]any{"locale": "en"}
+	if err := svc.Notify(context.Background(), event); err != nil {
+		t.Fatalf("notify returned error: %v", err)
+	}
+	if len(email.calls) != 2 {
+		t.Fatalf("expected two email dispatches, got %d", len(email.calls))
+	}
+	if email.calls[0].subject != "Case terminé" {
+		t.Fatalf("expected tenant locale subject, got %q", email.calls[0].subject)
+	}
+	if email.calls[1].subject != "Case completed" {
+		t.Fatalf("expected event locale to override tenant, got %q", email.calls[1].subject)
+	}
+}
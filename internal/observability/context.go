@@ -2,11 +2,18 @@ package observability
 
 import (
 	"context"
+	"net/http"
 
 	"github.com/google/uuid"
 )
 
-const CorrelationHeader = "X-Correlation-ID"
+// CorrelationHeader carries the ID tying a request to the logs, tool calls
+// and errors it causes. RequestIDHeader is accepted as an alternative and
+// sent alongside it, for services that use that convention.
+const (
+	CorrelationHeader = "X-Correlation-ID"
+	RequestIDHeader   = "X-Request-Id"
+)
 
 type correlationIDKey struct{}
 type tenantIDKey struct{}
@@ -21,6 +28,20 @@ func CorrelationIDFromContext(ctx context.Context) string {
 	return v
 }
 
+// InjectCorrelationHeaders sets the correlation headers on an outgoing
+// request from ctx, leaving headers the caller configured alone.
+func InjectCorrelationHeaders(ctx context.Context, header http.Header) {
+	id := CorrelationIDFromContext(ctx)
+	if id == "" {
+		return
+	}
+	for _, name := range []string{CorrelationHeader, RequestIDHeader} {
+		if header.Get(name) == "" {
+			header.Set(name, id)
+		}
+	}
+}
+
 func WithTenantID(ctx context.Context, tenantID uuid.UUID) context.Context {
 	return context.WithValue(ctx, tenantIDKey{}, tenantID)
 }
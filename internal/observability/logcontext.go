@@ -0,0 +1,175 @@
+package observability
+
+import (
+	"context"
+	"log/slog"
+	"sync"
+	"time"
+
+	"go.opentelemetry.io/otel/attribute"
+)
+
+type logAttrsKey struct{}
+type logCaptureKey struct{}
+
+// WithLogAttrs returns a context whose log records carry attrs, e.g. the
+// workflow, case and step an execution belongs to. An attr replaces one of
+// the same key already on ctx.
+func WithLogAttrs(ctx context.Context, attrs ...slog.Attr) context.Context {
+	if len(attrs) == 0 {
+		return ctx
+	}
+	existing := LogAttrsFromContext(ctx)
+	merged := make([]slog.Attr, 0, len(existing)+len(attrs))
+	for _, attr := range existing {
+		replaced := false
+		for _, next := range attrs {
+			replaced = replaced || next.Key == attr.Key
+		}
+		if !replaced {
+			merged = append(merged, attr)
+		}
+	}
+	return context.WithValue(ctx, logAttrsKey{}, append(merged, attrs...))
+}
+
+func LogAttrsFromContext(ctx context.Context) []slog.Attr {
+	attrs, _ := ctx.Value(logAttrsKey{}).([]slog.Attr)
+	return attrs
+}
+
+// ExecutionSpanAttrs returns the context's log attrs as span attributes, so
+// spans and log lines of one execution can be filtered on the same keys.
+func ExecutionSpanAttrs(ctx context.Context) []attribute.KeyValue {
+	attrs := LogAttrsFromContext(ctx)
+	out := make([]attribute.KeyValue, 0, len(attrs))
+	for _, attr := range attrs {
+		out = append(out, attribute.String(attr.Key, attr.Value.String()))
+	}
+	return out
+}
+
+// contextHandler adds the context's correlation ID and log attrs to records
+// that do not already carry them, and copies records into the context's log
+// capture, if any.
+type contextHandler struct {
+	slog.Handler
+	// keys are the attrs added with Logger.With, which take precedence.
+	keys map[string]bool
+}
+
+func (h contextHandler) Handle(ctx context.Context, record slog.Record) error {
+	if ctx == nil {
+		return h.Handler.Handle(ctx, record)
+	}
+	if capture, _ := ctx.Value(logCaptureKey{}).(*LogCapture); capture != nil {
+		capture.add(record)
+	}
+	attrs := LogAttrsFromContext(ctx)
+	if id := CorrelationIDFromContext(ctx); id != "" {
+		attrs = append([]slog.Attr{slog.String("correlation_id", id)}, attrs...)
+	}
+	if len(attrs) == 0 {
+		return h.Handler.Handle(ctx, record)
+	}
+	present := map[string]bool{}
+	record.Attrs(func(attr slog.Attr) bool {
+		present[attr.Key] = true
+		return true
+	})
+	record = record.Clone()
+	for _, attr := range attrs {
+		if !present[attr.Key] && !h.keys[attr.Key] {
+			record.AddAttrs(attr)
+		}
+	}
+	return h.Handler.Handle(ctx, record)
+}
+
+func (h contextHandler) WithAttrs(attrs []slog.Attr) slog.Handler {
+	keys := make(map[string]bool, len(h.keys)+len(attrs))
+	for key := range h.keys {
+		keys[key] = true
+	}
+	for _, attr := range attrs {
+		keys[attr.Key] = true
+	}
+	return contextHandler{Handler: h.Handler.WithAttrs(attrs), keys: keys}
+}
+
+func (h contextHandler) WithGroup(name string) slog.Handler {
+	return contextHandler{Handler: h.Handler.WithGroup(name), keys: h.keys}
+}
+
+// LogLine is one captured log record.
+type LogLine struct {
+	Time    time.Time      `json:"time"`
+	Level   string         `json:"level"`
+	Message string         `json:"message"`
+	Attrs   map[string]any `json:"attrs,omitempty"`
+}
+
+// LogCapture keeps the most recent log lines written under a context, so an
+// execution's logs can be stored with it for debugging.
+type LogCapture struct {
+	mu      sync.Mutex
+	limit   int
+	lines   []LogLine
+	dropped int
+}
+
+// WithLogCapture returns a context whose log records, at the levels the
+// logger writes, are also kept in the returned capture, up to limit lines.
+func WithLogCapture(ctx context.Context, limit int) (context.Context, *LogCapture) {
+	capture := &LogCapture{limit: max(limit, 1)}
+	return context.WithValue(ctx, logCaptureKey{}, capture), capture
+}
+
+// Lines returns the captured lines, oldest first.
+func (c *LogCapture) Lines() []LogLine {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	return append([]LogLine(nil), c.lines...)
+}
+
+// Dropped is how many older lines were discarded to stay within the limit.
+func (c *LogCapture) Dropped() int {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	return c.dropped
+}
+
+func (c *LogCapture) add(record slog.Record) {
+	line := LogLine{Time: record.Time.UTC(), Level: record.Level.String(), Message: record.Message}
+	if record.NumAttrs() > 0 {
+		line.Attrs = make(map[string]any, record.NumAttrs())
+		record.Attrs(func(attr slog.Attr) bool {
+			line.Attrs[attr.Key] = logValue(attr.Value)
+			return true
+		})
+	}
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	if len(c.lines) >= c.limit {
+		c.lines = append(c.lines[:0], c.lines[1:]...)
+		c.dropped++
+	}
+	c.lines = append(c.lines, line)
+}
+
+func logValue(value slog.Value) any {
+	value = value.Resolve()
+	switch value.Kind() {
+	case slog.KindGroup:
+		group := map[string]any{}
+		for _, attr := range value.Group() {
+			group[attr.Key] = logValue(attr.Value)
+		}
+		return group
+	case slog.KindAny:
+		if err, ok := value.Any().(error); ok {
+			return err.Error()
+		}
+	}
+	return value.Any()
+}
@@ -0,0 +1,297 @@
+package observability
+
+import (
+	"compress/gzip"
+	"fmt"
+	"io"
+	"log/slog"
+	"os"
+	"path/filepath"
+	"sort"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+)
+
+const (
+	DefaultLogMaxSizeMB  = 100
+	DefaultLogMaxBackups = 7
+	backupTimeFormat     = "2006-01-02T15-04-05.000"
+)
+
+// RotatingFileConfig controls log file rotation. A file is rotated when the
+// day changes or when a write would take it past MaxBytes. Rotated files are
+// renamed with a timestamp suffix, optionally gzipped, and only the newest
+// MaxBackups are kept.
+type RotatingFileConfig struct {
+	Path       string
+	MaxBytes   int64
+	MaxBackups int
+	Compress   bool
+}
+
+// RotatingFile is an io.WriteCloser that appends to Path and rotates it.
+// While Path cannot be reopened after a rotation, writes go to stderr and
+// each write tries to reopen it.
+type RotatingFile struct {
+	mu      sync.Mutex
+	config  RotatingFileConfig
+	file    *os.File
+	size    int64
+	day     string
+	now     func() time.Time
+	wg      sync.WaitGroup
+	closed  bool
+	failing bool
+	stderr  io.Writer
+}
+
+func OpenRotatingFile(config RotatingFileConfig) (*RotatingFile, error) {
+	if config.Path == "" {
+		return nil, fmt.Errorf("log file path is required")
+	}
+	if config.MaxBytes <= 0 {
+		config.MaxBytes = DefaultLogMaxSizeMB << 20
+	}
+	if config.MaxBackups <= 0 {
+		config.MaxBackups = DefaultLogMaxBackups
+	}
+	if err := os.MkdirAll(filepath.Dir(config.Path), 0o755); err != nil {
+		return nil, fmt.Errorf("create log directory: %w", err)
+	}
+	f := &RotatingFile{config: config, now: time.Now, stderr: os.Stderr}
+	if err := f.open(); err != nil {
+		return nil, err
+	}
+	return f, nil
+}
+
+func (f *RotatingFile) Write(p []byte) (int, error) {
+	f.mu.Lock()
+	defer f.mu.Unlock()
+	if f.closed {
+		return 0, os.ErrClosed
+	}
+	if f.file == nil {
+		if err := f.open(); err != nil {
+			return f.fallback(p, err)
+		}
+	}
+	now := f.now()
+	if now.Format(time.DateOnly) != f.day || (f.size > 0 && f.size+int64(len(p)) > f.config.MaxBytes) {
+		if err := f.rotate(now); err != nil {
+			if f.file == nil {
+				return f.fallback(p, err)
+			}
+			_, _ = fmt.Fprintln(f.stderr, err)
+		}
+	}
+	if f.failing {
+		f.failing = false
+		_, _ = fmt.Fprintf(f.stderr, "log file %s reopened\n", f.config.Path)
+	}
+	n, err := f.file.Write(p)
+	f.size += int64(n)
+	return n, err
+}
+
+// fallback writes p to stderr while the log file cannot be opened,
+// reporting the cause once.
+func (f *RotatingFile) fallback(p []byte, err error) (int, error) {
+	if !f.failing {
+		f.failing = true
+		_, _ = fmt.Fprintf(f.stderr, "log file unavailable, writing to stderr: %v\n", err)
+	}
+	return f.stderr.Write(p)
+}
+
+// Close closes the current file and waits for pending compressions.
+func (f *RotatingFile) Close() error {
+	f.mu.Lock()
+	var err error
+	f.closed = true
+	if f.file != nil {
+		err = f.file.Close()
+		f.file = nil
+	}
+	f.mu.Unlock()
+	f.wg.Wait()
+	return err
+}
+
+func (f *RotatingFile) open() error {
+	file, err := os.OpenFile(f.config.Path, os.O_CREATE|os.O_WRONLY|os.O_APPEND, 0o644)
+	if err != nil {
+		return fmt.Errorf("open log file: %w", err)
+	}
+	info, err := file.Stat()
+	if err != nil {
+		_ = file.Close()
+		return fmt.Errorf("stat log file: %w", err)
+	}
+	f.file = file
+	f.size = info.Size()
+	f.day = f.now().Format(time.DateOnly)
+	if info.Size() > 0 {
+		// An existing file belongs to the day it was last written.
+		f.day = info.ModTime().Format(time.DateOnly)
+	}
+	return nil
+}
+
+// rotate moves Path aside and reopens it. When the move fails Path is
+// reopened as it is; f.file is only left nil when reopening fails.
+func (f *RotatingFile) rotate(now time.Time) error {
+	closeErr := f.file.Close()
+	f.file = nil
+	backup := f.backupName(now)
+	if err := os.Rename(f.config.Path, backup); err != nil && !os.IsNotExist(err) {
+		if openErr := f.open(); openErr != nil {
+			return openErr
+		}
+		return fmt.Errorf("rotate log file: %w", err)
+	}
+	if err := f.open(); err != nil {
+		return err
+	}
+	f.day = now.Format(time.DateOnly)
+	if closeErr != nil {
+		_, _ = fmt.Fprintf(f.stderr, "close rotated log %s: %v\n", backup, closeErr)
+	}
+	if f.config.Compress {
+		f.wg.Add(1)
+		go func() {
+			defer f.wg.Done()
+			if err := compressFile(backup); err != nil {
+				_, _ = fmt.Fprintf(f.stderr, "compress rotated log %s: %v\n", backup, err)
+			}
+			f.prune()
+		}()
+		return nil
+	}
+	f.prune()
+	return nil
+}
+
+// backupName returns Path with now as a suffix, plus a sequence number
+// when rotations within the same millisecond would otherwise collide.
+func (f *RotatingFile) backupName(now time.Time) string {
+	base := f.config.Path + "." + now.Format(backupTimeFormat)
+	name := base
+	for seq := 1; backupExists(name); seq++ {
+		name = fmt.Sprintf("%s.%d", base, seq)
+	}
+	return name
+}
+
+func backupExists(name string) bool {
+	for _, candidate := range []string{name, name + ".gz", name + ".gz.tmp"} {
+		if _, err := os.Lstat(candidate); err == nil {
+			return true
+		}
+	}
+	return false
+}
+
+// prune removes the oldest rotated files beyond MaxBackups. Backup names,
+// less any .gz suffix, sort chronologically because of their timestamp and
+// sequence suffix.
+func (f *RotatingFile) prune() {
+	matches, err := filepath.Glob(f.config.Path + ".*")
+	if err != nil {
+		return
+	}
+	backups := matches[:0]
+	for _, m := range matches {
+		if !strings.HasSuffix(m, ".tmp") {
+			backups = append(backups, m)
+		}
+	}
+	sort.Slice(backups, func(i, j int) bool {
+		return backupSortKey(f.config.Path, backups[i]) < backupSortKey(f.config.Path, backups[j])
+	})
+	for len(backups) > f.config.MaxBackups {
+		_ = os.Remove(backups[0])
+		backups = backups[1:]
+	}
+}
+
+// backupSortKey is the timestamp of a backup of path followed by its
+// sequence number, padded so that .10 sorts after .9.
+func backupSortKey(path, name string) string {
+	suffix := strings.TrimSuffix(strings.TrimPrefix(name, path+"."), ".gz")
+	if len(suffix) < len(backupTimeFormat) {
+		return suffix
+	}
+	stamp, rest := suffix[:len(backupTimeFormat)], suffix[len(backupTimeFormat):]
+	seq, _ := strconv.Atoi(strings.TrimPrefix(rest, "."))
+	return fmt.Sprintf("%s.%06d", stamp, seq)
+}
+
+func compressFile(path string) error {
+	src, err := os.Open(path)
+	if err != nil {
+		return err
+	}
+	defer func() { _ = src.Close() }()
+	tmp := path + ".gz.tmp"
+	dst, err := os.OpenFile(tmp, os.O_CREATE|os.O_WRONLY|os.O_TRUNC, 0o644)
+	if err != nil {
+		return err
+	}
+	gz := gzip.NewWriter(dst)
+	if _, err := io.Copy(gz, src); err != nil {
+		_ = dst.Close()
+		_ = os.Remove(tmp)
+		return err
+	}
+	if err := gz.Close(); err != nil {
+		_ = dst.Close()
+		_ = os.Remove(tmp)
+		return err
+	}
+	if err := dst.Close(); err != nil {
+		_ = os.Remove(tmp)
+		return err
+	}
+	if err := os.Rename(tmp, path+".gz"); err != nil {
+		return err
+	}
+	return os.Remove(path)
+}
+
+// logOutputFromEnv adds ACERYX_LOG_FILE, when set, alongside w. Setting
+// ACERYX_LOG_STDOUT=false sends logs to the file only. The closer closes
+// the log file, if any.
+func logOutputFromEnv(w io.Writer) (io.Writer, io.Closer, error) {
+	path := strings.TrimSpace(os.Getenv("ACERYX_LOG_FILE"))
+	if path == "" {
+		return w, noLogFile{}, nil
+	}
+	file, err := OpenRotatingFile(RotatingFileConfig{
+		Path:       path,
+		MaxBytes:   int64(positiveEnvInt("ACERYX_LOG_MAX_SIZE_MB", DefaultLogMaxSizeMB)) << 20,
+		MaxBackups: positiveEnvInt("ACERYX_LOG_MAX_BACKUPS", DefaultLogMaxBackups),
+		Compress:   envBool("ACERYX_LOG_COMPRESS"),
+	})
+	if err != nil {
+		return w, noLogFile{}, err
+	}
+	if raw := strings.TrimSpace(os.Getenv("ACERYX_LOG_STDOUT")); strings.EqualFold(raw, "false") || raw == "0" {
+		return file, file, nil
+	}
+	return io.MultiWriter(w, file), file, nil
+}
+
+type noLogFile struct{}
+
+func (noLogFile) Close() error { return nil }
+
+func newHandler(w io.Writer, level slog.Leveler, format string) slog.Handler {
+	opts := &slog.HandlerOptions{Level: level, AddSource: false}
+	if strings.EqualFold(strings.TrimSpace(format), "text") {
+		return contextHandler{Handler: slog.NewTextHandler(w, opts)}
+	}
+	return contextHandler{Handler: slog.NewJSONHandler(w, opts)}
+}
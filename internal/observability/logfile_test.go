@@ -0,0 +1,162 @@
+package observability
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestRotatingFileRotatesBySizeAndPrunes(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "aceryx.log")
+	f, err := OpenRotatingFile(RotatingFileConfig{Path: path, MaxBytes: 10, MaxBackups: 2})
+	if err != nil {
+		t.Fatalf("open: %v", err)
+	}
+	clock := time.Date(2026, 10, 18, 9, 0, 0, 0, time.UTC)
+	f.now = func() time.Time {
+		clock = clock.Add(time.Second)
+		return clock
+	}
+	f.day = clock.Format(time.DateOnly)
+	for i := 0; i < 4; i++ {
+		if _, err := f.Write([]byte("0123456789")); err != nil {
+			t.Fatalf("write %d: %v", i, err)
+		}
+	}
+	if err := f.Close(); err != nil {
+		t.Fatalf("close: %v", err)
+	}
+
+	backups, _ := filepath.Glob(path + ".*")
+	if len(backups) != 2 {
+		t.Fatalf("expected 2 backups after pruning, got %v", backups)
+	}
+	current, _ := os.ReadFile(path)
+	if string(current) != "0123456789" {
+		t.Fatalf("current file holds %q", current)
+	}
+}
+
+func TestRotatingFileRotatesDailyAndCompresses(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "aceryx.log")
+	f, err := OpenRotatingFile(RotatingFileConfig{Path: path, MaxBytes: 1 << 20, Compress: true})
+	if err != nil {
+		t.Fatalf("open: %v", err)
+	}
+	day := time.Date(2026, 10, 18, 23, 59, 0, 0, time.UTC)
+	f.now = func() time.Time { return day }
+	f.day = day.Format(time.DateOnly)
+	_, _ = f.Write([]byte("first\n"))
+	day = day.Add(2 * time.Minute)
+	_, _ = f.Write([]byte("second\n"))
+	if err := f.Close(); err != nil {
+		t.Fatalf("close: %v", err)
+	}
+
+	backups, _ := filepath.Glob(path + ".*")
+	if len(backups) != 1 || !strings.HasSuffix(backups[0], ".gz") {
+		t.Fatalf("expected one compressed backup, got %v", backups)
+	}
+	current, _ := os.ReadFile(path)
+	if string(current) != "second\n" {
+		t.Fatalf("current file holds %q", current)
+	}
+}
+
+func TestRotatingFileNamesBackupsWithinTheSameMillisecondApart(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "aceryx.log")
+	f, err := OpenRotatingFile(RotatingFileConfig{Path: path, MaxBytes: 4, MaxBackups: 5})
+	if err != nil {
+		t.Fatalf("open: %v", err)
+	}
+	clock := time.Date(2026, 10, 18, 9, 0, 0, 0, time.UTC)
+	f.now = func() time.Time { return clock }
+	f.day = clock.Format(time.DateOnly)
+	for _, line := range []string{"aaaa", "bbbb", "cccc", "dddd"} {
+		if _, err := f.Write([]byte(line)); err != nil {
+			t.Fatalf("write %s: %v", line, err)
+		}
+	}
+	if err := f.Close(); err != nil {
+		t.Fatalf("close: %v", err)
+	}
+
+	backups, _ := filepath.Glob(path + ".*")
+	if len(backups) != 3 {
+		t.Fatalf("expected every rotation to keep its own backup, got %v", backups)
+	}
+	var kept []string
+	for _, backup := range backups {
+		raw, _ := os.ReadFile(backup)
+		kept = append(kept, string(raw))
+	}
+	if strings.Join(kept, ",") != "aaaa,bbbb,cccc" {
+		t.Fatalf("backups hold %v", kept)
+	}
+}
+
+func TestRotatingFileFallsBackToStderrUntilReopened(t *testing.T) {
+	dir := filepath.Join(t.TempDir(), "logs")
+	path := filepath.Join(dir, "aceryx.log")
+	f, err := OpenRotatingFile(RotatingFileConfig{Path: path, MaxBytes: 4})
+	if err != nil {
+		t.Fatalf("open: %v", err)
+	}
+	var stderr strings.Builder
+	f.stderr = &stderr
+	if _, err := f.Write([]byte("one\n")); err != nil {
+		t.Fatalf("write: %v", err)
+	}
+
+	// Rotation cannot reopen the file once its directory is gone.
+	if err := os.RemoveAll(dir); err != nil {
+		t.Fatalf("remove log directory: %v", err)
+	}
+	if _, err := f.Write([]byte("two\n")); err != nil {
+		t.Fatalf("write during outage: %v", err)
+	}
+	if !strings.Contains(stderr.String(), "two\n") {
+		t.Fatalf("expected the write to reach stderr, got %q", stderr.String())
+	}
+
+	if err := os.MkdirAll(dir, 0o755); err != nil {
+		t.Fatalf("recreate log directory: %v", err)
+	}
+	if _, err := f.Write([]byte("six\n")); err != nil {
+		t.Fatalf("write after recovery: %v", err)
+	}
+	if err := f.Close(); err != nil {
+		t.Fatalf("close: %v", err)
+	}
+	current, _ := os.ReadFile(path)
+	if string(current) != "six\n" {
+		t.Fatalf("expected the file to be reopened, holds %q", current)
+	}
+}
+
+func TestSetupLoggerFromEnvWritesFile(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "logs", "aceryx.log")
+	t.Setenv("ACERYX_LOG_FILE", path)
+	t.Setenv("ACERYX_LOG_STDOUT", "false")
+	t.Setenv("ACERYX_LOG_FORMAT", "text")
+
+	var stdout strings.Builder
+	logger, logFile := SetupLoggerFromEnv(&stdout)
+	logger.Info("hello", "k", "v")
+	if err := logFile.Close(); err != nil {
+		t.Fatalf("close log file: %v", err)
+	}
+
+	got, err := os.ReadFile(path)
+	if err != nil {
+		t.Fatalf("read log file: %v", err)
+	}
+	if !strings.Contains(string(got), "msg=hello k=v") {
+		t.Fatalf("expected text log line, got %q", got)
+	}
+	if stdout.Len() != 0 {
+		t.Fatalf("stdout should be unused, got %q", stdout.String())
+	}
+}
@@ -5,26 +5,45 @@ import (
 	"io"
 	"log/slog"
 	"os"
+	"strconv"
 	"strings"
 )
 
-func SetupLoggerFromEnv(w io.Writer) *slog.Logger {
-	level := parseLevel(strings.TrimSpace(os.Getenv("ACERYX_LOG_LEVEL")))
-	return SetupLogger(w, level)
+var logLevel = new(slog.LevelVar)
+
+// SetupLoggerFromEnv installs the default logger. Output goes to w and, when
+// ACERYX_LOG_FILE is set, to a rotating log file; ACERYX_LOG_FORMAT selects
+// json (default) or text. Closing the returned closer flushes and closes the
+// log file; it is a no-op without one.
+func SetupLoggerFromEnv(w io.Writer) (*slog.Logger, io.Closer) {
+	if w == nil {
+		w = os.Stdout
+	}
+	SetLogLevel(os.Getenv("ACERYX_LOG_LEVEL"))
+	out, closer, err := logOutputFromEnv(w)
+	logger := slog.New(newHandler(out, logLevel, os.Getenv("ACERYX_LOG_FORMAT")))
+	slog.SetDefault(logger)
+	if err != nil {
+		logger.Error("log file unavailable; logging to stdout only", "error", err)
+	}
+	return logger, closer
 }
 
 func SetupLogger(w io.Writer, level slog.Level) *slog.Logger {
 	if w == nil {
 		w = os.Stdout
 	}
-	logger := slog.New(slog.NewJSONHandler(w, &slog.HandlerOptions{
-		Level:     level,
-		AddSource: false,
-	}))
+	logger := slog.New(newHandler(w, level, "json"))
 	slog.SetDefault(logger)
 	return logger
 }
 
+// SetLogLevel changes the level of the logger installed by
+// SetupLoggerFromEnv without replacing it.
+func SetLogLevel(raw string) {
+	logLevel.Set(parseLevel(raw))
+}
+
 func parseLevel(raw string) slog.Level {
 	switch strings.ToLower(strings.TrimSpace(raw)) {
 	case "debug":
@@ -38,6 +57,19 @@ func parseLevel(raw string) slog.Level {
 	}
 }
 
+func positiveEnvInt(key string, fallback int) int {
+	v, err := strconv.Atoi(strings.TrimSpace(os.Getenv(key)))
+	if err != nil || v <= 0 {
+		return fallback
+	}
+	return v
+}
+
+func envBool(key string) bool {
+	raw := strings.TrimSpace(os.Getenv(key))
+	return strings.EqualFold(raw, "true") || raw == "1"
+}
+
 func RequestAttrs(ctx context.Context) []any {
 	return []any{
 		"correlation_id", CorrelationIDFromContext(ctx),
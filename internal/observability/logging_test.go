@@ -0,0 +1,81 @@
+package observability
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"log/slog"
+	"net/http"
+	"strings"
+	"testing"
+)
+
+func TestInjectCorrelationHeadersKeepsConfiguredHeaders(t *testing.T) {
+	ctx := WithCorrelationID(context.Background(), "req-1")
+	header := http.Header{}
+	header.Set(RequestIDHeader, "configured")
+	InjectCorrelationHeaders(ctx, header)
+	if header.Get(CorrelationHeader) != "req-1" || header.Get(RequestIDHeader) != "configured" {
+		t.Fatalf("unexpected headers %v", header)
+	}
+
+	empty := http.Header{}
+	InjectCorrelationHeaders(context.Background(), empty)
+	if len(empty) != 0 {
+		t.Fatalf("expected no headers without a correlation id, got %v", empty)
+	}
+}
+
+func TestLoggerAddsCorrelationIDFromContext(t *testing.T) {
+	var buf bytes.Buffer
+	logger := slog.New(newHandler(&buf, slog.LevelInfo, "json"))
+	ctx := WithCorrelationID(context.Background(), "req-2")
+
+	logger.InfoContext(ctx, "step started")
+	logger.InfoContext(ctx, "request failed", RequestAttrs(ctx)...)
+	logger.With("correlation_id", "explicit").InfoContext(ctx, "scoped")
+
+	lines := strings.Split(strings.TrimSpace(buf.String()), "\n")
+	want := []string{"req-2", "req-2", "explicit"}
+	for i, line := range lines {
+		if n := strings.Count(line, `"correlation_id"`); n != 1 {
+			t.Fatalf("line %d: expected one correlation_id, got %d: %s", i, n, line)
+		}
+		var record map[string]any
+		if err := json.Unmarshal([]byte(line), &record); err != nil {
+			t.Fatalf("decode log line: %v", err)
+		}
+		if record["correlation_id"] != want[i] {
+			t.Fatalf("line %d: expected correlation_id %q, got %v", i, want[i], record["correlation_id"])
+		}
+	}
+}
+
+func TestLoggerAddsExecutionAttrsAndCaptures(t *testing.T) {
+	var buf bytes.Buffer
+	logger := slog.New(newHandler(&buf, slog.LevelInfo, "json"))
+	ctx := WithLogAttrs(context.Background(), slog.String("case_id", "case-1"), slog.String("step_id", "a"))
+	ctx = WithLogAttrs(ctx, slog.String("step_id", "b"))
+	ctx, capture := WithLogCapture(ctx, 2)
+
+	logger.DebugContext(ctx, "hidden")
+	for _, msg := range []string{"one", "two", "three"} {
+		logger.InfoContext(ctx, msg, "attempt", 1)
+	}
+
+	var record map[string]any
+	last := strings.TrimSpace(buf.String())
+	if err := json.Unmarshal([]byte(last[strings.LastIndex(last, "\n")+1:]), &record); err != nil {
+		t.Fatalf("decode log line: %v", err)
+	}
+	if record["case_id"] != "case-1" || record["step_id"] != "b" {
+		t.Fatalf("expected execution attrs, got %v", record)
+	}
+	lines := capture.Lines()
+	if len(lines) != 2 || lines[0].Message != "two" || lines[1].Message != "three" || capture.Dropped() != 1 {
+		t.Fatalf("expected the last two lines, got %+v (dropped %d)", lines, capture.Dropped())
+	}
+	if lines[1].Attrs["attempt"] != int64(1) || lines[1].Level != "INFO" {
+		t.Fatalf("unexpected captured line %+v", lines[1])
+	}
+}
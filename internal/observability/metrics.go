@@ -93,6 +93,18 @@ var (
 		prometheus.HistogramOpts{Name: "aceryx_connector_duration_seconds", Help: "Connector call duration"},
 		[]string{"tenant_id", "connector"},
 	)
+	ConnectorCacheTotal = promauto.NewCounterVec(
+		prometheus.CounterOpts{Name: "aceryx_connector_cache_total", Help: "Connector result cache lookups by result"},
+		[]string{"tenant_id", "connector", "action", "result"},
+	)
+	ToolPoolInstances = promauto.NewGaugeVec(
+		prometheus.GaugeOpts{Name: "aceryx_tool_pool_instances", Help: "Pooled tool instances by protocol"},
+		[]string{"protocol"},
+	)
+	ToolPoolEvictionsTotal = promauto.NewCounterVec(
+		prometheus.CounterOpts{Name: "aceryx_tool_pool_evictions_total", Help: "Tool instances evicted from the pool by protocol"},
+		[]string{"protocol"},
+	)
 
 	DBPoolSize = promauto.NewGaugeVec(
 		prometheus.GaugeOpts{Name: "aceryx_db_pool_size", Help: "DB pool size by state"},
@@ -109,6 +121,14 @@ var (
 	WorkerPoolUtilisation = promauto.NewGauge(
 		prometheus.GaugeOpts{Name: "aceryx_worker_pool_utilisation", Help: "Worker pool utilisation (0-1)"},
 	)
+	ExecutionLaneRunning = promauto.NewGaugeVec(
+		prometheus.GaugeOpts{Name: "aceryx_execution_lane_running", Help: "Step executions running by lane"},
+		[]string{"lane"},
+	)
+	ExecutionLaneQueued = promauto.NewGaugeVec(
+		prometheus.GaugeOpts{Name: "aceryx_execution_lane_queued", Help: "Step executions waiting for a worker by lane"},
+		[]string{"lane"},
+	)
 )
 
 func ObserveHTTPRequest(method, path string, statusCode int, seconds float64) {
@@ -0,0 +1,175 @@
+package observability
+
+import (
+	"bytes"
+	"math"
+	"os"
+	"runtime"
+	"sort"
+	"strconv"
+	"time"
+
+	"github.com/prometheus/client_golang/prometheus"
+)
+
+var processStartedAt = time.Now().UTC()
+
+// ProcessStartedAt is when this process initialised the observability package,
+// which happens before main runs.
+func ProcessStartedAt() time.Time {
+	return processStartedAt
+}
+
+// RuntimeStats is a snapshot of the process: memory, scheduler and storage
+// latency.
+type RuntimeStats struct {
+	StartedAt      time.Time                     `json:"started_at"`
+	UptimeSeconds  int64                         `json:"uptime_seconds"`
+	GoVersion      string                        `json:"go_version"`
+	Memory         MemoryStats                   `json:"memory"`
+	Scheduler      SchedulerStats                `json:"scheduler"`
+	StorageLatency map[string]LatencyPercentiles `json:"storage_latency"`
+}
+
+// MemoryStats reports resident memory as the kernel sees it alongside the Go
+// heap. RSSBytes is 0 where /proc is unavailable.
+type MemoryStats struct {
+	RSSBytes       uint64 `json:"rss_bytes"`
+	HeapAllocBytes uint64 `json:"heap_alloc_bytes"`
+	HeapInuseBytes uint64 `json:"heap_inuse_bytes"`
+	HeapSysBytes   uint64 `json:"heap_sys_bytes"`
+	SysBytes       uint64 `json:"sys_bytes"`
+	GCCycles       uint32 `json:"gc_cycles"`
+	LastGCPauseNs  uint64 `json:"last_gc_pause_ns"`
+}
+
+type SchedulerStats struct {
+	Goroutines int `json:"goroutines"`
+	GOMAXPROCS int `json:"gomaxprocs"`
+	NumCPU     int `json:"num_cpu"`
+}
+
+// LatencyPercentiles are estimated from histogram buckets the way PromQL's
+// histogram_quantile does, so they are only as precise as the buckets.
+type LatencyPercentiles struct {
+	Count uint64  `json:"count"`
+	P50Ms float64 `json:"p50_ms"`
+	P95Ms float64 `json:"p95_ms"`
+	P99Ms float64 `json:"p99_ms"`
+}
+
+// ReadRuntimeStats takes a snapshot. It stops the world briefly to read the
+// Go memory statistics.
+func ReadRuntimeStats() RuntimeStats {
+	var mem runtime.MemStats
+	runtime.ReadMemStats(&mem)
+	stats := RuntimeStats{
+		StartedAt:     processStartedAt,
+		UptimeSeconds: int64(time.Since(processStartedAt).Seconds()),
+		GoVersion:     runtime.Version(),
+		Memory: MemoryStats{
+			RSSBytes:       residentMemory(),
+			HeapAllocBytes: mem.HeapAlloc,
+			HeapInuseBytes: mem.HeapInuse,
+			HeapSysBytes:   mem.HeapSys,
+			SysBytes:       mem.Sys,
+			GCCycles:       mem.NumGC,
+		},
+		Scheduler: SchedulerStats{
+			Goroutines: runtime.NumGoroutine(),
+			GOMAXPROCS: runtime.GOMAXPROCS(0),
+			NumCPU:     runtime.NumCPU(),
+		},
+		StorageLatency: storageLatency(),
+	}
+	if mem.NumGC > 0 {
+		stats.Memory.LastGCPauseNs = mem.PauseNs[(mem.NumGC+255)%256]
+	}
+	return stats
+}
+
+// residentMemory reads the resident set size from /proc/self/statm.
+func residentMemory() uint64 {
+	raw, err := os.ReadFile("/proc/self/statm")
+	if err != nil {
+		return 0
+	}
+	fields := bytes.Fields(raw)
+	if len(fields) < 2 {
+		return 0
+	}
+	pages, err := strconv.ParseUint(string(fields[1]), 10, 64)
+	if err != nil {
+		return 0
+	}
+	return pages * uint64(os.Getpagesize())
+}
+
+// storageLatency summarises aceryx_db_query_duration_seconds by query type.
+func storageLatency() map[string]LatencyPercentiles {
+	out := map[string]LatencyPercentiles{}
+	families, err := prometheus.DefaultGatherer.Gather()
+	if err != nil {
+		return out
+	}
+	for _, family := range families {
+		if family.GetName() != "aceryx_db_query_duration_seconds" {
+			continue
+		}
+		for _, metric := range family.GetMetric() {
+			histogram := metric.GetHistogram()
+			if histogram == nil || histogram.GetSampleCount() == 0 {
+				continue
+			}
+			label := ""
+			for _, pair := range metric.GetLabel() {
+				if pair.GetName() == "query_type" {
+					label = pair.GetValue()
+				}
+			}
+			buckets := make([]latencyBucket, 0, len(histogram.GetBucket()))
+			for _, bucket := range histogram.GetBucket() {
+				buckets = append(buckets, latencyBucket{upper: bucket.GetUpperBound(), count: bucket.GetCumulativeCount()})
+			}
+			count := histogram.GetSampleCount()
+			out[label] = LatencyPercentiles{
+				Count: count,
+				P50Ms: bucketQuantile(0.50, buckets, count) * 1000,
+				P95Ms: bucketQuantile(0.95, buckets, count) * 1000,
+				P99Ms: bucketQuantile(0.99, buckets, count) * 1000,
+			}
+		}
+	}
+	return out
+}
+
+type latencyBucket struct {
+	upper float64
+	count uint64
+}
+
+// bucketQuantile interpolates linearly within the bucket holding the
+// quantile. Observations beyond the last finite bound are reported at that
+// bound.
+func bucketQuantile(q float64, buckets []latencyBucket, total uint64) float64 {
+	if total == 0 || len(buckets) == 0 {
+		return 0
+	}
+	sort.Slice(buckets, func(i, j int) bool { return buckets[i].upper < buckets[j].upper })
+	rank := q * float64(total)
+	lower, below := 0.0, uint64(0)
+	for _, bucket := range buckets {
+		if math.IsInf(bucket.upper, 1) {
+			break
+		}
+		if float64(bucket.count) >= rank {
+			inBucket := bucket.count - below
+			if inBucket == 0 {
+				return bucket.upper
+			}
+			return lower + (bucket.upper-lower)*(rank-float64(below))/float64(inBucket)
+		}
+		lower, below = bucket.upper, bucket.count
+	}
+	return lower
+}
@@ -0,0 +1,51 @@
+package observability
+
+import (
+	"math"
+	"testing"
+)
+
+func TestBucketQuantile(t *testing.T) {
+	buckets := []latencyBucket{
+		{upper: 0.01, count: 50},
+		{upper: 0.1, count: 90},
+		{upper: 1, count: 100},
+		{upper: math.Inf(1), count: 100},
+	}
+	cases := []struct {
+		q    float64
+		want float64
+	}{
+		{q: 0.5, want: 0.01},
+		{q: 0.7, want: 0.055},
+		{q: 0.95, want: 0.55},
+	}
+	for _, tc := range cases {
+		if got := bucketQuantile(tc.q, buckets, 100); math.Abs(got-tc.want) > 1e-9 {
+			t.Fatalf("q%.2f: expected %v, got %v", tc.q, tc.want, got)
+		}
+	}
+
+	overflow := []latencyBucket{{upper: 0.1, count: 1}, {upper: math.Inf(1), count: 10}}
+	if got := bucketQuantile(0.99, overflow, 10); got != 0.1 {
+		t.Fatalf("expected overflow to report the last finite bound, got %v", got)
+	}
+	if got := bucketQuantile(0.5, nil, 0); got != 0 {
+		t.Fatalf("expected 0 without observations, got %v", got)
+	}
+}
+
+func TestReadRuntimeStats(t *testing.T) {
+	DBQueryDurationSeconds.WithLabelValues("runtime_test").Observe(0.002)
+	stats := ReadRuntimeStats()
+	if stats.StartedAt.IsZero() || stats.UptimeSeconds < 0 {
+		t.Fatalf("unexpected start time %v, uptime %d", stats.StartedAt, stats.UptimeSeconds)
+	}
+	if stats.Memory.HeapAllocBytes == 0 || stats.Scheduler.Goroutines == 0 || stats.Scheduler.GOMAXPROCS == 0 {
+		t.Fatalf("expected runtime figures, got %+v", stats)
+	}
+	latency, ok := stats.StorageLatency["runtime_test"]
+	if !ok || latency.Count != 1 || latency.P50Ms <= 0 {
+		t.Fatalf("expected latency for runtime_test, got %+v", stats.StorageLatency)
+	}
+}
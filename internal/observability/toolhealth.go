@@ -0,0 +1,278 @@
+package observability
+
+import (
+	"context"
+	"fmt"
+	"sort"
+	"sync"
+	"time"
+)
+
+// Tool anomaly kinds.
+const (
+	ToolAnomalyFailureRate = "failure_rate"
+	ToolAnomalyLatency     = "latency"
+)
+
+// Tool health compares each tool's calls over the last toolRecentWindow with
+// the rest of the last toolBaselineWindow. A tool is anomalous when its recent
+// failure rate is at least toolFailureRateDelta above, and double, its
+// baseline, or its recent p95 latency is toolLatencyFactor times its baseline
+// and at least toolLatencyFloor slower. Tools without enough calls in either
+// window are not judged, so a tool that has always failed is left to its
+// circuit breaker.
+const (
+	toolRecentWindow     = 5 * time.Minute
+	toolBaselineWindow   = time.Hour
+	toolMinRecentCalls   = 10
+	toolMinBaselineCalls = 30
+	toolFailureRateDelta = 0.2
+	toolLatencyFactor    = 3
+	toolLatencyFloor     = 250 * time.Millisecond
+)
+
+// toolLatencyBoundsMs are the upper bounds of the per-minute latency
+// histograms p95 is estimated from.
+var toolLatencyBoundsMs = []float64{5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000, 30000, 60000}
+
+// ToolAnomaly is a tool whose recent calls deviate from its baseline on this
+// replica.
+type ToolAnomaly struct {
+	TenantID            string    `json:"tenant_id,omitempty"`
+	Protocol            string    `json:"protocol"`
+	ToolID              string    `json:"tool_id"`
+	Kind                string    `json:"kind"`
+	Message             string    `json:"message"`
+	RecentCalls         uint64    `json:"recent_calls"`
+	RecentFailureRate   float64   `json:"recent_failure_rate"`
+	BaselineFailureRate float64   `json:"baseline_failure_rate"`
+	RecentP95Ms         float64   `json:"recent_p95_ms"`
+	BaselineP95Ms       float64   `json:"baseline_p95_ms"`
+	DetectedAt          time.Time `json:"detected_at"`
+}
+
+// ToolAnomalyListener is told when a tool becomes anomalous. It runs on the
+// goroutine that finished the tool call.
+type ToolAnomalyListener func(ctx context.Context, anomaly ToolAnomaly)
+
+type toolKey struct {
+	tenantID, protocol, toolID string
+}
+
+// toolMinute holds one minute of a tool's calls. latency counts calls per
+// toolLatencyBoundsMs bucket, with a last bucket for slower calls.
+type toolMinute struct {
+	minute   int64
+	calls    uint64
+	failures uint64
+	latency  []uint64
+}
+
+type toolSeries struct {
+	minutes []toolMinute
+	// active maps an anomaly kind to when it was first detected.
+	active map[string]time.Time
+}
+
+// ToolHealth tracks rolling failure rates and latency per tool.
+type ToolHealth struct {
+	mu        sync.Mutex
+	series    map[toolKey]*toolSeries
+	listeners []ToolAnomalyListener
+}
+
+func NewToolHealth() *ToolHealth {
+	return &ToolHealth{series: map[toolKey]*toolSeries{}}
+}
+
+var defaultToolHealth = NewToolHealth()
+
+// OnToolAnomaly registers listener with the process-wide tool health tracker
+// fed by StartToolSpan.
+func OnToolAnomaly(listener ToolAnomalyListener) {
+	defaultToolHealth.OnAnomaly(listener)
+}
+
+// ToolAnomalies lists the tools currently anomalous for tenantID on this
+// replica, or for every tenant when tenantID is empty.
+func ToolAnomalies(tenantID string) []ToolAnomaly {
+	return defaultToolHealth.Anomalies(tenantID, time.Now())
+}
+
+func (h *ToolHealth) OnAnomaly(listener ToolAnomalyListener) {
+	h.mu.Lock()
+	defer h.mu.Unlock()
+	h.listeners = append(h.listeners, listener)
+}
+
+// Record adds one finished call and tells the listeners about anomalies it
+// raised.
+func (h *ToolHealth) Record(ctx context.Context, tenantID, protocol, toolID string, duration time.Duration, failed bool, at time.Time) {
+	key := toolKey{tenantID: tenantID, protocol: protocol, toolID: toolID}
+	h.mu.Lock()
+	series := h.series[key]
+	if series == nil {
+		series = &toolSeries{minutes: make([]toolMinute, int(toolBaselineWindow/time.Minute)), active: map[string]time.Time{}}
+		h.series[key] = series
+	}
+	series.add(at, duration, failed)
+	wasActive := make(map[string]bool, len(series.active))
+	for kind := range series.active {
+		wasActive[kind] = true
+	}
+	var raised []ToolAnomaly
+	for _, anomaly := range series.refresh(key, at) {
+		if !wasActive[anomaly.Kind] {
+			anomaly.DetectedAt = series.active[anomaly.Kind]
+			raised = append(raised, anomaly)
+		}
+	}
+	listeners := h.listeners
+	h.mu.Unlock()
+
+	for _, anomaly := range raised {
+		for _, listener := range listeners {
+			listener(ctx, anomaly)
+		}
+	}
+}
+
+// Anomalies lists the tools anomalous at now, for tenantID or for every
+// tenant when it is empty.
+func (h *ToolHealth) Anomalies(tenantID string, now time.Time) []ToolAnomaly {
+	h.mu.Lock()
+	defer h.mu.Unlock()
+	out := []ToolAnomaly{}
+	for key, series := range h.series {
+		if tenantID != "" && key.tenantID != tenantID {
+			continue
+		}
+		for _, anomaly := range series.refresh(key, now) {
+			anomaly.DetectedAt = series.active[anomaly.Kind]
+			out = append(out, anomaly)
+		}
+	}
+	sort.Slice(out, func(i, j int) bool {
+		if out[i].Protocol != out[j].Protocol {
+			return out[i].Protocol < out[j].Protocol
+		}
+		if out[i].ToolID != out[j].ToolID {
+			return out[i].ToolID < out[j].ToolID
+		}
+		return out[i].Kind < out[j].Kind
+	})
+	return out
+}
+
+func (s *toolSeries) add(at time.Time, duration time.Duration, failed bool) {
+	minute := at.Unix() / 60
+	slot := &s.minutes[minute%int64(len(s.minutes))]
+	if slot.minute != minute {
+		*slot = toolMinute{minute: minute, latency: make([]uint64, len(toolLatencyBoundsMs)+1)}
+	}
+	slot.calls++
+	if failed {
+		slot.failures++
+	}
+	ms := float64(duration) / float64(time.Millisecond)
+	bucket := sort.SearchFloat64s(toolLatencyBoundsMs, ms)
+	slot.latency[bucket]++
+}
+
+// refresh re-evaluates the series at now, dropping anomalies that have
+// cleared and stamping new ones, and returns those that hold.
+func (s *toolSeries) refresh(key toolKey, now time.Time) []ToolAnomaly {
+	current := s.evaluate(key, now)
+	holding := map[string]bool{}
+	for _, anomaly := range current {
+		holding[anomaly.Kind] = true
+		if _, ok := s.active[anomaly.Kind]; !ok {
+			s.active[anomaly.Kind] = now.UTC()
+		}
+	}
+	for kind := range s.active {
+		if !holding[kind] {
+			delete(s.active, kind)
+		}
+	}
+	return current
+}
+
+// toolWindow aggregates the minutes of one window.
+type toolWindow struct {
+	calls    uint64
+	failures uint64
+	latency  []uint64
+}
+
+func (w toolWindow) failureRate() float64 {
+	if w.calls == 0 {
+		return 0
+	}
+	return float64(w.failures) / float64(w.calls)
+}
+
+func (w toolWindow) p95Ms() float64 {
+	buckets := make([]latencyBucket, 0, len(w.latency))
+	var cumulative uint64
+	for i, count := range w.latency {
+		cumulative += count
+		if i < len(toolLatencyBoundsMs) {
+			buckets = append(buckets, latencyBucket{upper: toolLatencyBoundsMs[i], count: cumulative})
+		}
+	}
+	return bucketQuantile(0.95, buckets, w.calls)
+}
+
+func (s *toolSeries) evaluate(key toolKey, now time.Time) []ToolAnomaly {
+	current := now.Unix() / 60
+	recentFrom := current - int64(toolRecentWindow/time.Minute) + 1
+	baselineFrom := current - int64(len(s.minutes)) + 1
+	recent := toolWindow{latency: make([]uint64, len(toolLatencyBoundsMs)+1)}
+	baseline := toolWindow{latency: make([]uint64, len(toolLatencyBoundsMs)+1)}
+	for _, slot := range s.minutes {
+		if slot.calls == 0 || slot.minute < baselineFrom || slot.minute > current {
+			continue
+		}
+		window := &baseline
+		if slot.minute >= recentFrom {
+			window = &recent
+		}
+		window.calls += slot.calls
+		window.failures += slot.failures
+		for i, count := range slot.latency {
+			window.latency[i] += count
+		}
+	}
+	if recent.calls < toolMinRecentCalls || baseline.calls < toolMinBaselineCalls {
+		return nil
+	}
+
+	base := ToolAnomaly{
+		TenantID:            key.tenantID,
+		Protocol:            key.protocol,
+		ToolID:              key.toolID,
+		RecentCalls:         recent.calls,
+		RecentFailureRate:   recent.failureRate(),
+		BaselineFailureRate: baseline.failureRate(),
+		RecentP95Ms:         recent.p95Ms(),
+		BaselineP95Ms:       baseline.p95Ms(),
+	}
+	var out []ToolAnomaly
+	if base.RecentFailureRate-base.BaselineFailureRate >= toolFailureRateDelta && base.RecentFailureRate >= 2*base.BaselineFailureRate {
+		anomaly := base
+		anomaly.Kind = ToolAnomalyFailureRate
+		anomaly.Message = fmt.Sprintf("%s tool %s failed %.0f%% of its last %d calls, against %.0f%% over the past hour",
+			key.protocol, key.toolID, 100*base.RecentFailureRate, base.RecentCalls, 100*base.BaselineFailureRate)
+		out = append(out, anomaly)
+	}
+	floorMs := float64(toolLatencyFloor / time.Millisecond)
+	if base.RecentP95Ms >= toolLatencyFactor*base.BaselineP95Ms && base.RecentP95Ms-base.BaselineP95Ms >= floorMs {
+		anomaly := base
+		anomaly.Kind = ToolAnomalyLatency
+		anomaly.Message = fmt.Sprintf("%s tool %s p95 latency is %.0fms over its last %d calls, against %.0fms over the past hour",
+			key.protocol, key.toolID, base.RecentP95Ms, base.RecentCalls, base.BaselineP95Ms)
+		out = append(out, anomaly)
+	}
+	return out
+}
@@ -0,0 +1,73 @@
+package observability
+
+import (
+	"context"
+	"testing"
+	"time"
+)
+
+func TestToolHealthRaisesFailureRateAnomalyOnce(t *testing.T) {
+	h := NewToolHealth()
+	var raised []ToolAnomaly
+	h.OnAnomaly(func(_ context.Context, anomaly ToolAnomaly) { raised = append(raised, anomaly) })
+
+	ctx := context.Background()
+	now := time.Date(2026, 10, 18, 12, 0, 0, 0, time.UTC)
+	for i := 0; i < 40; i++ {
+		h.Record(ctx, "tenant-a", "mcp", "lookup", 20*time.Millisecond, i == 0, now.Add(-50*time.Minute+time.Duration(i)*time.Minute))
+	}
+	for i := 0; i < 10; i++ {
+		h.Record(ctx, "tenant-a", "mcp", "lookup", 20*time.Millisecond, i%2 == 0, now.Add(time.Duration(i)*time.Second))
+	}
+
+	if len(raised) != 1 || raised[0].Kind != ToolAnomalyFailureRate || raised[0].DetectedAt.IsZero() {
+		t.Fatalf("expected one failure rate anomaly, got %+v", raised)
+	}
+	if got := raised[0]; got.RecentCalls != 10 || got.RecentFailureRate != 0.5 || got.BaselineFailureRate != 0.025 {
+		t.Fatalf("unexpected anomaly figures %+v", got)
+	}
+	if got := h.Anomalies("tenant-a", now.Add(time.Minute)); len(got) != 1 || got[0].ToolID != "lookup" {
+		t.Fatalf("expected the anomaly to be listed, got %+v", got)
+	}
+	if got := h.Anomalies("tenant-b", now.Add(time.Minute)); len(got) != 0 {
+		t.Fatalf("expected no anomalies for another tenant, got %+v", got)
+	}
+	if got := h.Anomalies("", now.Add(10*time.Minute)); len(got) != 0 {
+		t.Fatalf("expected the anomaly to clear once its calls leave the recent window, got %+v", got)
+	}
+}
+
+func TestToolHealthLatencyAnomaly(t *testing.T) {
+	h := NewToolHealth()
+	ctx := context.Background()
+	now := time.Date(2026, 10, 18, 12, 0, 0, 0, time.UTC)
+	for i := 0; i < 40; i++ {
+		h.Record(ctx, "", "connector", "http/request", 40*time.Millisecond, false, now.Add(-40*time.Minute+time.Duration(i)*time.Minute))
+	}
+	for i := 0; i < 10; i++ {
+		h.Record(ctx, "", "connector", "http/request", 50*time.Millisecond, false, now.Add(time.Duration(i)*time.Second))
+	}
+	if got := h.Anomalies("", now); len(got) != 0 {
+		t.Fatalf("expected a small slowdown to be ignored, got %+v", got)
+	}
+
+	for i := 0; i < 10; i++ {
+		h.Record(ctx, "", "connector", "http/request", 4*time.Second, false, now.Add(time.Minute+time.Duration(i)*time.Second))
+	}
+	got := h.Anomalies("", now.Add(2*time.Minute))
+	if len(got) != 1 || got[0].Kind != ToolAnomalyLatency || got[0].RecentP95Ms <= got[0].BaselineP95Ms {
+		t.Fatalf("expected a latency anomaly, got %+v", got)
+	}
+}
+
+func TestToolHealthNeedsEnoughCalls(t *testing.T) {
+	h := NewToolHealth()
+	ctx := context.Background()
+	now := time.Date(2026, 10, 18, 12, 0, 0, 0, time.UTC)
+	for i := 0; i < 10; i++ {
+		h.Record(ctx, "", "grpc", "Lookup", time.Millisecond, true, now.Add(time.Duration(i)*time.Second))
+	}
+	if got := h.Anomalies("", now); len(got) != 0 {
+		t.Fatalf("expected no anomaly without a baseline, got %+v", got)
+	}
+}
@@ -0,0 +1,110 @@
+package observability
+
+import (
+	"context"
+	"fmt"
+	"net/http"
+	"os"
+	"strconv"
+	"strings"
+	"time"
+
+	"go.opentelemetry.io/otel"
+	"go.opentelemetry.io/otel/attribute"
+	"go.opentelemetry.io/otel/codes"
+	"go.opentelemetry.io/otel/exporters/otlp/otlptrace/otlptracehttp"
+	"go.opentelemetry.io/otel/propagation"
+	"go.opentelemetry.io/otel/sdk/resource"
+	sdktrace "go.opentelemetry.io/otel/sdk/trace"
+	"go.opentelemetry.io/otel/trace"
+)
+
+const tracerName = "github.com/neural-chilli/aceryx"
+
+// SetupTracingFromEnv installs an OTLP/HTTP trace exporter when
+// ACERYX_OTLP_ENDPOINT (or the standard OTEL_EXPORTER_OTLP_* variables) is
+// set. Otherwise the global no-op provider stays in place and spans are free.
+// The returned func flushes and stops the exporter.
+func SetupTracingFromEnv(ctx context.Context) (func(context.Context) error, error) {
+	otel.SetTextMapPropagator(propagation.NewCompositeTextMapPropagator(propagation.TraceContext{}, propagation.Baggage{}))
+
+	endpoint := strings.TrimSpace(os.Getenv("ACERYX_OTLP_ENDPOINT"))
+	if endpoint == "" && strings.TrimSpace(os.Getenv("OTEL_EXPORTER_OTLP_ENDPOINT")) == "" && strings.TrimSpace(os.Getenv("OTEL_EXPORTER_OTLP_TRACES_ENDPOINT")) == "" {
+		return func(context.Context) error { return nil }, nil
+	}
+	opts := []otlptracehttp.Option{}
+	if endpoint != "" {
+		opts = append(opts, otlptracehttp.WithEndpointURL(endpoint))
+	}
+	exporter, err := otlptracehttp.New(ctx, opts...)
+	if err != nil {
+		return nil, fmt.Errorf("create otlp trace exporter: %w", err)
+	}
+
+	serviceName := strings.TrimSpace(os.Getenv("OTEL_SERVICE_NAME"))
+	if serviceName == "" {
+		serviceName = "aceryx"
+	}
+	res, err := resource.Merge(resource.Default(), resource.NewSchemaless(attribute.String("service.name", serviceName)))
+	if err != nil {
+		return nil, fmt.Errorf("build trace resource: %w", err)
+	}
+
+	provider := sdktrace.NewTracerProvider(
+		sdktrace.WithBatcher(exporter),
+		sdktrace.WithResource(res),
+		sdktrace.WithSampler(sdktrace.ParentBased(sdktrace.TraceIDRatioBased(parseSampleRatio(os.Getenv("ACERYX_OTLP_SAMPLE_RATIO"))))),
+	)
+	otel.SetTracerProvider(provider)
+	return provider.Shutdown, nil
+}
+
+func parseSampleRatio(raw string) float64 {
+	ratio, err := strconv.ParseFloat(strings.TrimSpace(raw), 64)
+	if err != nil || ratio < 0 || ratio > 1 {
+		return 1
+	}
+	return ratio
+}
+
+func Tracer() trace.Tracer {
+	return otel.Tracer(tracerName)
+}
+
+// InjectTraceHeaders writes traceparent/tracestate for the span in ctx so
+// downstream services join the same trace.
+func InjectTraceHeaders(ctx context.Context, header http.Header) {
+	otel.GetTextMapPropagator().Inject(ctx, propagation.HeaderCarrier(header))
+}
+
+// StartToolSpan starts a client span for one tool call, tagged with the
+// execution attrs on ctx. The returned func ends the span, recording its
+// duration and err, and feeds the call to tool health anomaly detection.
+func StartToolSpan(ctx context.Context, toolID, protocol string) (context.Context, func(error)) {
+	start := time.Now()
+	ctx, span := Tracer().Start(ctx, "tool.call",
+		trace.WithSpanKind(trace.SpanKindClient),
+		trace.WithAttributes(
+			attribute.String("tool_id", toolID),
+			attribute.String("protocol", protocol),
+		),
+		trace.WithAttributes(ExecutionSpanAttrs(ctx)...),
+	)
+	return ctx, func(err error) {
+		duration := time.Since(start)
+		span.SetAttributes(attribute.Int64("duration_ms", duration.Milliseconds()))
+		SpanError(span, err)
+		span.End()
+		defaultToolHealth.Record(ctx, TenantIDFromContext(ctx), protocol, toolID, duration, err != nil, time.Now())
+	}
+}
+
+// SpanError marks span as failed with err. A nil err is a no-op.
+func SpanError(span trace.Span, err error) {
+	if err == nil {
+		return
+	}
+	span.RecordError(err)
+	span.SetStatus(codes.Error, err.Error())
+	span.SetAttributes(attribute.String("error", err.Error()))
+}
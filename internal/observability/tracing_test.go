@@ -0,0 +1,80 @@
+package observability
+
+import (
+	"context"
+	"errors"
+	"net/http"
+	"strings"
+	"testing"
+
+	"go.opentelemetry.io/otel"
+	"go.opentelemetry.io/otel/codes"
+	"go.opentelemetry.io/otel/propagation"
+	sdktrace "go.opentelemetry.io/otel/sdk/trace"
+	"go.opentelemetry.io/otel/sdk/trace/tracetest"
+)
+
+func installRecorder(t *testing.T) *tracetest.SpanRecorder {
+	t.Helper()
+	recorder := tracetest.NewSpanRecorder()
+	provider := sdktrace.NewTracerProvider(sdktrace.WithSpanProcessor(recorder))
+	prevProvider, prevPropagator := otel.GetTracerProvider(), otel.GetTextMapPropagator()
+	otel.SetTracerProvider(provider)
+	otel.SetTextMapPropagator(propagation.TraceContext{})
+	t.Cleanup(func() {
+		otel.SetTracerProvider(prevProvider)
+		otel.SetTextMapPropagator(prevPropagator)
+	})
+	return recorder
+}
+
+func TestToolSpanRecordsAttributesAndError(t *testing.T) {
+	recorder := installRecorder(t)
+	ctx, parent := Tracer().Start(context.Background(), "step.execute")
+	_, end := StartToolSpan(ctx, "http/request", "connector")
+	end(errors.New("boom"))
+	parent.End()
+
+	spans := recorder.Ended()
+	if len(spans) != 2 {
+		t.Fatalf("expected 2 spans, got %d", len(spans))
+	}
+	tool := spans[0]
+	if tool.Name() != "tool.call" || tool.Parent().SpanID() != parent.SpanContext().SpanID() {
+		t.Fatalf("tool span should be a child of the step span")
+	}
+	attrs := map[string]string{}
+	for _, kv := range tool.Attributes() {
+		attrs[string(kv.Key)] = kv.Value.Emit()
+	}
+	if attrs["tool_id"] != "http/request" || attrs["protocol"] != "connector" || attrs["error"] != "boom" {
+		t.Fatalf("unexpected attributes %v", attrs)
+	}
+	if _, ok := attrs["duration_ms"]; !ok {
+		t.Fatalf("expected duration_ms attribute")
+	}
+	if tool.Status().Code != codes.Error {
+		t.Fatalf("expected error status, got %v", tool.Status())
+	}
+}
+
+func TestInjectTraceHeadersWritesTraceparent(t *testing.T) {
+	installRecorder(t)
+	ctx, span := Tracer().Start(context.Background(), "workflow.evaluate")
+	defer span.End()
+	header := http.Header{}
+	InjectTraceHeaders(ctx, header)
+	traceparent := header.Get("traceparent")
+	if !strings.Contains(traceparent, span.SpanContext().TraceID().String()) {
+		t.Fatalf("traceparent %q missing trace id", traceparent)
+	}
+}
+
+func TestParseSampleRatio(t *testing.T) {
+	cases := map[string]float64{"": 1, "0.25": 0.25, "2": 1, "nope": 1, "0": 0}
+	for raw, want := range cases {
+		if got := parseSampleRatio(raw); got != want {
+			t.Fatalf("parseSampleRatio(%q) = %v, want %v", raw, got, want)
+		}
+	}
+}
@@ -3,8 +3,11 @@ package plugins
 import (
 	"fmt"
 	"sort"
+	"strconv"
 	"strings"
 	"sync"
+
+	"github.com/neural-chilli/aceryx/internal/search"
 )
 
 type PluginRegistry struct {
@@ -134,16 +137,35 @@ func (r *PluginRegistry) ToolCapable() []*Plugin {
 	return r.filter(func(p *Plugin) bool { return p.Manifest.ToolCapable })
 }
 
+// Search ranks plugins by relevance to query, matching the name ahead of
+// the category and id, and those ahead of the descriptions.
 func (r *PluginRegistry) Search(query string) []*Plugin {
-	needle := strings.ToLower(strings.TrimSpace(query))
-	if needle == "" {
-		return r.All()
+	all := r.All()
+	if strings.TrimSpace(query) == "" {
+		return all
+	}
+	docs := make([]search.Document, len(all))
+	for i, p := range all {
+		docs[i] = p.SearchDocument()
+		docs[i].ID = strconv.Itoa(i)
+	}
+	hits := search.NewIndex(docs).Search(query)
+	out := make([]*Plugin, 0, len(hits))
+	for _, hit := range hits {
+		i, _ := strconv.Atoi(hit.ID)
+		out = append(out, all[i])
+	}
+	return out
+}
+
+// SearchDocument describes the plugin for the search index.
+func (p *Plugin) SearchDocument() search.Document {
+	return search.Document{
+		ID:          p.ID,
+		Name:        p.Name,
+		Tags:        []string{p.Category, p.ID},
+		Description: p.Manifest.UI.Description + " " + p.Manifest.ToolDescription,
 	}
-	return r.filter(func(p *Plugin) bool {
-		return strings.Contains(strings.ToLower(p.Name), needle) ||
-			strings.Contains(strings.ToLower(p.Manifest.UI.Description), needle) ||
-			strings.Contains(strings.ToLower(p.Category), needle)
-	})
 }
 
 func (r *PluginRegistry) StepPalette() []PaletteCategory {
@@ -0,0 +1,69 @@
+// Package progress fans engine step events out to clients following a case,
+// such as the workflow designer lighting up steps as they run.
+package progress
+
+import (
+	"context"
+	"sync"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+// subscriberBuffer is how many events a slow subscriber may fall behind
+// before further events are dropped for it.
+const subscriberBuffer = 64
+
+// Broker delivers step events to the subscribers of each case. Publishing
+// never blocks the engine.
+type Broker struct {
+	mu   sync.Mutex
+	subs map[uuid.UUID]map[chan engine.StepEvent]struct{}
+}
+
+func NewBroker() *Broker {
+	return &Broker{subs: map[uuid.UUID]map[chan engine.StepEvent]struct{}{}}
+}
+
+// Publish is an engine.StepEventHook.
+func (b *Broker) Publish(_ context.Context, event engine.StepEvent) {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+	for ch := range b.subs[event.CaseID] {
+		select {
+		case ch <- event:
+		default:
+		}
+	}
+}
+
+// Subscribe follows caseID's step events until cancel is called.
+func (b *Broker) Subscribe(caseID uuid.UUID) (events <-chan engine.StepEvent, cancel func()) {
+	ch := make(chan engine.StepEvent, subscriberBuffer)
+	b.mu.Lock()
+	if b.subs[caseID] == nil {
+		b.subs[caseID] = map[chan engine.StepEvent]struct{}{}
+	}
+	b.subs[caseID][ch] = struct{}{}
+	b.mu.Unlock()
+
+	var once sync.Once
+	return ch, func() {
+		once.Do(func() {
+			b.mu.Lock()
+			defer b.mu.Unlock()
+			delete(b.subs[caseID], ch)
+			if len(b.subs[caseID]) == 0 {
+				delete(b.subs, caseID)
+			}
+			close(ch)
+		})
+	}
+}
+
+// Subscribers reports how many clients follow caseID.
+func (b *Broker) Subscribers(caseID uuid.UUID) int {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+	return len(b.subs[caseID])
+}
@@ -0,0 +1,52 @@
+package progress
+
+import (
+	"context"
+	"testing"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+func TestBrokerDeliversEventsToCaseSubscribers(t *testing.T) {
+	b := NewBroker()
+	caseID, otherID := uuid.New(), uuid.New()
+	events, cancel := b.Subscribe(caseID)
+	defer cancel()
+
+	b.Publish(context.Background(), engine.StepEvent{CaseID: otherID, StepID: "ignored", Status: engine.StepStatusRunning})
+	b.Publish(context.Background(), engine.StepEvent{CaseID: caseID, StepID: "review", Status: engine.StepStatusRunning})
+
+	select {
+	case got := <-events:
+		if got.StepID != "review" || got.Status != engine.StepStatusRunning {
+			t.Fatalf("unexpected event %+v", got)
+		}
+	default:
+		t.Fatal("expected an event for the subscribed case")
+	}
+	select {
+	case got := <-events:
+		t.Fatalf("unexpected extra event %+v", got)
+	default:
+	}
+}
+
+func TestBrokerDropsEventsForSlowSubscribers(t *testing.T) {
+	b := NewBroker()
+	caseID := uuid.New()
+	events, cancel := b.Subscribe(caseID)
+
+	for range subscriberBuffer + 10 {
+		b.Publish(context.Background(), engine.StepEvent{CaseID: caseID, StepID: "loop", Status: engine.StepStatusQueued})
+	}
+	if got := len(events); got != subscriberBuffer {
+		t.Fatalf("expected %d buffered events, got %d", subscriberBuffer, got)
+	}
+
+	cancel()
+	cancel()
+	if got := b.Subscribers(caseID); got != 0 {
+		t.Fatalf("expected no subscribers after cancel, got %d", got)
+	}
+}
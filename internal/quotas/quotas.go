@@ -0,0 +1,252 @@
+// Package quotas limits how much each workspace and user may run: cases
+// started per hour, step executor runtime and LLM tokens per month. Usage is
+// attributed to the principal that started the case.
+package quotas
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"log/slog"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+const (
+	MetricExecutions  = "executions_per_hour"
+	MetricToolRuntime = "tool_runtime_ms"
+	MetricLLMTokens   = "llm_tokens"
+
+	ScopeWorkspace = "workspace"
+	ScopeUser      = "user"
+)
+
+var (
+	ErrQuotaExceeded = errors.New("quota exceeded")
+	ErrInvalidLimits = errors.New("invalid quota limits")
+)
+
+// Limits caps usage; zero means unlimited. ExecutionsPerHour counts cases
+// started in the current UTC hour, the others accumulate over the current
+// UTC month.
+type Limits struct {
+	ExecutionsPerHour int64 `json:"executions_per_hour"`
+	ToolRuntimeMS     int64 `json:"tool_runtime_ms"`
+	LLMTokens         int64 `json:"llm_tokens"`
+}
+
+// Counters is consumption: Executions in the current hour, ToolRuntimeMS
+// and LLMTokens in the current month.
+type Counters struct {
+	Executions    int64 `json:"executions"`
+	ToolRuntimeMS int64 `json:"tool_runtime_ms"`
+	LLMTokens     int64 `json:"llm_tokens"`
+}
+
+// Quota is the stored limits for a workspace, or for one user when
+// PrincipalID is set.
+type Quota struct {
+	PrincipalID *uuid.UUID `json:"principal_id,omitempty"`
+	Limits
+	UpdatedAt time.Time `json:"updated_at"`
+}
+
+// ExceededError reports the first limit a request would break. ResetAt is
+// when the window restarts.
+type ExceededError struct {
+	Scope   string
+	Metric  string
+	Limit   int64
+	Used    int64
+	ResetAt time.Time
+}
+
+func (e *ExceededError) Error() string {
+	return fmt.Sprintf("%s %s quota exceeded: used %d of %d", e.Scope, e.Metric, e.Used, e.Limit)
+}
+
+// Unwrap lets the engine fail a step over budget without retrying it.
+func (e *ExceededError) Unwrap() []error {
+	return []error{ErrQuotaExceeded, engine.ErrUsageExceeded}
+}
+
+// Budget reports whether a monthly budget ran out, as opposed to the hourly
+// execution rate.
+func (e *ExceededError) Budget() bool { return e.Metric != MetricExecutions }
+
+// Store persists limits and hourly usage. A nil principal addresses the
+// workspace: its limits, or usage summed over every user.
+type Store interface {
+	GetLimits(ctx context.Context, tenantID uuid.UUID, principalID *uuid.UUID) (Limits, error)
+	ListQuotas(ctx context.Context, tenantID uuid.UUID) ([]Quota, error)
+	SetLimits(ctx context.Context, tenantID uuid.UUID, principalID *uuid.UUID, limits Limits, actorID uuid.UUID) error
+	Usage(ctx context.Context, tenantID uuid.UUID, principalID *uuid.UUID, hour, month time.Time) (Counters, error)
+	Add(ctx context.Context, tenantID, principalID uuid.UUID, hour time.Time, delta Counters) error
+	CaseOwner(ctx context.Context, caseID uuid.UUID) (tenantID, principalID uuid.UUID, err error)
+}
+
+type Service struct {
+	store Store
+	now   func() time.Time
+}
+
+func NewService(store Store) *Service {
+	return &Service{store: store, now: time.Now}
+}
+
+// Windows returns the start of the current UTC hour and month.
+func (s *Service) Windows() (hour, month time.Time) {
+	now := s.now().UTC()
+	return now.Truncate(time.Hour), time.Date(now.Year(), now.Month(), 1, 0, 0, 0, 0, time.UTC)
+}
+
+// AdmitExecution checks that principalID may start a case: neither the
+// workspace nor the user is at its hourly execution limit or out of a
+// monthly budget.
+func (s *Service) AdmitExecution(ctx context.Context, tenantID, principalID uuid.UUID) error {
+	return s.check(ctx, tenantID, principalID, MetricExecutions, MetricToolRuntime, MetricLLMTokens)
+}
+
+// RecordExecution counts a started case against principalID.
+func (s *Service) RecordExecution(ctx context.Context, tenantID, principalID uuid.UUID) error {
+	hour, _ := s.Windows()
+	return s.store.Add(ctx, tenantID, principalID, hour, Counters{Executions: 1})
+}
+
+// CheckStep is the engine.UsageGate: a running case stops at its next step
+// once the workspace or the user who started it is out of budget. Lookup
+// errors are returned as they are, so the engine retries the step.
+func (s *Service) CheckStep(ctx context.Context, tenantID, caseID uuid.UUID) error {
+	_, principalID, err := s.store.CaseOwner(ctx, caseID)
+	if err != nil {
+		return fmt.Errorf("load case owner: %w", err)
+	}
+	return s.check(ctx, tenantID, principalID, MetricToolRuntime, MetricLLMTokens)
+}
+
+// OnStepEvent is an engine.StepEventHook recording the runtime and tokens
+// of each finished step, including the attempts of steps that failed.
+func (s *Service) OnStepEvent(ctx context.Context, event engine.StepEvent) {
+	switch event.Status {
+	case engine.StepStatusSucceeded, engine.StepStatusFailed, engine.StepStatusSkipped:
+	default:
+		return
+	}
+	if event.RuntimeMS <= 0 && event.Tokens <= 0 {
+		return
+	}
+	tenantID, principalID, err := s.store.CaseOwner(ctx, event.CaseID)
+	if err == nil {
+		hour, _ := s.Windows()
+		err = s.store.Add(ctx, tenantID, principalID, hour, Counters{ToolRuntimeMS: max(event.RuntimeMS, 0), LLMTokens: int64(max(event.Tokens, 0))})
+	}
+	if err != nil {
+		slog.WarnContext(ctx, "record step usage failed", "case_id", event.CaseID.String(), "step_id", event.StepID, "error", err)
+	}
+}
+
+func (s *Service) check(ctx context.Context, tenantID, principalID uuid.UUID, metrics ...string) error {
+	hour, month := s.Windows()
+	scopes := []struct {
+		name      string
+		principal *uuid.UUID
+	}{
+		{ScopeWorkspace, nil},
+		{ScopeUser, &principalID},
+	}
+	for _, scope := range scopes {
+		limits, err := s.store.GetLimits(ctx, tenantID, scope.principal)
+		if err != nil {
+			return err
+		}
+		if limits == (Limits{}) {
+			continue
+		}
+		used, err := s.store.Usage(ctx, tenantID, scope.principal, hour, month)
+		if err != nil {
+			return err
+		}
+		if exceeded := firstExceeded(limits, used, metrics); exceeded != nil {
+			exceeded.Scope = scope.name
+			exceeded.ResetAt = month.AddDate(0, 1, 0)
+			if exceeded.Metric == MetricExecutions {
+				exceeded.ResetAt = hour.Add(time.Hour)
+			}
+			return exceeded
+		}
+	}
+	return nil
+}
+
+// firstExceeded returns the first metric at or over its limit. Executions
+// are checked before admitting one more, so reaching the limit is enough.
+func firstExceeded(limits Limits, used Counters, metrics []string) *ExceededError {
+	for _, metric := range metrics {
+		var limit, value int64
+		switch metric {
+		case MetricExecutions:
+			limit, value = limits.ExecutionsPerHour, used.Executions
+		case MetricToolRuntime:
+			limit, value = limits.ToolRuntimeMS, used.ToolRuntimeMS
+		case MetricLLMTokens:
+			limit, value = limits.LLMTokens, used.LLMTokens
+		}
+		if limit > 0 && value >= limit {
+			return &ExceededError{Metric: metric, Limit: limit, Used: value}
+		}
+	}
+	return nil
+}
+
+// ScopeReport is one scope's limits and consumption in the current windows.
+type ScopeReport struct {
+	Limits Limits   `json:"limits"`
+	Used   Counters `json:"used"`
+}
+
+// Report is what GET /api/v1/usage returns.
+type Report struct {
+	HourStart  time.Time   `json:"hour_start"`
+	MonthStart time.Time   `json:"month_start"`
+	Workspace  ScopeReport `json:"workspace"`
+	User       ScopeReport `json:"user"`
+}
+
+// Report returns workspace and user consumption against their limits.
+func (s *Service) Report(ctx context.Context, tenantID, principalID uuid.UUID) (Report, error) {
+	hour, month := s.Windows()
+	out := Report{HourStart: hour, MonthStart: month}
+	for _, scope := range []struct {
+		principal *uuid.UUID
+		into      *ScopeReport
+	}{
+		{nil, &out.Workspace},
+		{&principalID, &out.User},
+	} {
+		limits, err := s.store.GetLimits(ctx, tenantID, scope.principal)
+		if err != nil {
+			return Report{}, err
+		}
+		used, err := s.store.Usage(ctx, tenantID, scope.principal, hour, month)
+		if err != nil {
+			return Report{}, err
+		}
+		*scope.into = ScopeReport{Limits: limits, Used: used}
+	}
+	return out, nil
+}
+
+func (s *Service) ListQuotas(ctx context.Context, tenantID uuid.UUID) ([]Quota, error) {
+	return s.store.ListQuotas(ctx, tenantID)
+}
+
+// SetLimits replaces the workspace limits, or a user's when principalID is
+// set. Negative values are rejected.
+func (s *Service) SetLimits(ctx context.Context, tenantID uuid.UUID, principalID *uuid.UUID, limits Limits, actorID uuid.UUID) error {
+	if limits.ExecutionsPerHour < 0 || limits.ToolRuntimeMS < 0 || limits.LLMTokens < 0 {
+		return fmt.Errorf("%w: limits cannot be negative", ErrInvalidLimits)
+	}
+	return s.store.SetLimits(ctx, tenantID, principalID, limits, actorID)
+}
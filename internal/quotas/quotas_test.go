@@ -0,0 +1,179 @@
+package quotas
+
+import (
+	"context"
+	"errors"
+	"testing"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+type usageRow struct {
+	tenantID, principalID uuid.UUID
+	hour                  time.Time
+	delta                 Counters
+}
+
+type memoryStore struct {
+	limits map[uuid.UUID]Limits
+	rows   []usageRow
+	owners map[uuid.UUID][2]uuid.UUID
+}
+
+func newMemoryStore() *memoryStore {
+	return &memoryStore{limits: map[uuid.UUID]Limits{}, owners: map[uuid.UUID][2]uuid.UUID{}}
+}
+
+func scopeKey(principalID *uuid.UUID) uuid.UUID {
+	if principalID == nil {
+		return uuid.Nil
+	}
+	return *principalID
+}
+
+func (m *memoryStore) GetLimits(_ context.Context, _ uuid.UUID, principalID *uuid.UUID) (Limits, error) {
+	return m.limits[scopeKey(principalID)], nil
+}
+
+func (m *memoryStore) ListQuotas(context.Context, uuid.UUID) ([]Quota, error) {
+	return nil, nil
+}
+
+func (m *memoryStore) SetLimits(_ context.Context, _ uuid.UUID, principalID *uuid.UUID, limits Limits, _ uuid.UUID) error {
+	m.limits[scopeKey(principalID)] = limits
+	return nil
+}
+
+func (m *memoryStore) Usage(_ context.Context, tenantID uuid.UUID, principalID *uuid.UUID, hour, month time.Time) (Counters, error) {
+	var out Counters
+	for _, row := range m.rows {
+		if row.tenantID != tenantID || (principalID != nil && row.principalID != *principalID) {
+			continue
+		}
+		if row.hour.Equal(hour) {
+			out.Executions += row.delta.Executions
+		}
+		if !row.hour.Before(month) {
+			out.ToolRuntimeMS += row.delta.ToolRuntimeMS
+			out.LLMTokens += row.delta.LLMTokens
+		}
+	}
+	return out, nil
+}
+
+func (m *memoryStore) Add(_ context.Context, tenantID, principalID uuid.UUID, hour time.Time, delta Counters) error {
+	m.rows = append(m.rows, usageRow{tenantID: tenantID, principalID: principalID, hour: hour, delta: delta})
+	return nil
+}
+
+func (m *memoryStore) CaseOwner(_ context.Context, caseID uuid.UUID) (uuid.UUID, uuid.UUID, error) {
+	owner, ok := m.owners[caseID]
+	if !ok {
+		return uuid.Nil, uuid.Nil, errors.New("case not found")
+	}
+	return owner[0], owner[1], nil
+}
+
+func newTestService(store Store, now time.Time) *Service {
+	svc := NewService(store)
+	svc.now = func() time.Time { return now }
+	return svc
+}
+
+func TestAdmitExecutionEnforcesHourlyLimit(t *testing.T) {
+	store := newMemoryStore()
+	now := time.Date(2026, 3, 10, 14, 25, 0, 0, time.UTC)
+	svc := newTestService(store, now)
+	ctx := context.Background()
+	tenant, alice, bob := uuid.New(), uuid.New(), uuid.New()
+	if err := svc.SetLimits(ctx, tenant, &alice, Limits{ExecutionsPerHour: 2}, uuid.New()); err != nil {
+		t.Fatalf("set limits: %v", err)
+	}
+
+	for i := 0; i < 2; i++ {
+		if err := svc.AdmitExecution(ctx, tenant, alice); err != nil {
+			t.Fatalf("execution %d: %v", i, err)
+		}
+		_ = svc.RecordExecution(ctx, tenant, alice)
+	}
+	err := svc.AdmitExecution(ctx, tenant, alice)
+	var exceeded *ExceededError
+	if !errors.As(err, &exceeded) || !errors.Is(err, ErrQuotaExceeded) {
+		t.Fatalf("expected quota error, got %v", err)
+	}
+	if exceeded.Scope != ScopeUser || exceeded.Metric != MetricExecutions || exceeded.Budget() {
+		t.Fatalf("unexpected error: %+v", exceeded)
+	}
+	if want := time.Date(2026, 3, 10, 15, 0, 0, 0, time.UTC); !exceeded.ResetAt.Equal(want) {
+		t.Fatalf("reset at %s, want %s", exceeded.ResetAt, want)
+	}
+	if err := svc.AdmitExecution(ctx, tenant, bob); err != nil {
+		t.Fatalf("other user should not be limited: %v", err)
+	}
+
+	svc.now = func() time.Time { return now.Add(time.Hour) }
+	if err := svc.AdmitExecution(ctx, tenant, alice); err != nil {
+		t.Fatalf("limit should reset next hour: %v", err)
+	}
+}
+
+func TestStepUsageCountsAgainstWorkspaceBudget(t *testing.T) {
+	store := newMemoryStore()
+	now := time.Date(2026, 3, 10, 14, 25, 0, 0, time.UTC)
+	svc := newTestService(store, now)
+	ctx := context.Background()
+	tenant, alice, bob, caseID := uuid.New(), uuid.New(), uuid.New(), uuid.New()
+	store.owners[caseID] = [2]uuid.UUID{tenant, alice}
+	if err := svc.SetLimits(ctx, tenant, nil, Limits{LLMTokens: 1000}, uuid.New()); err != nil {
+		t.Fatalf("set limits: %v", err)
+	}
+
+	svc.OnStepEvent(ctx, engine.StepEvent{CaseID: caseID, StepID: "draft", Status: engine.StepStatusSucceeded, RuntimeMS: 1200, Tokens: 600})
+	svc.OnStepEvent(ctx, engine.StepEvent{CaseID: caseID, StepID: "fetch", Status: engine.StepStatusFailed, RuntimeMS: 300, Tokens: 100})
+	svc.OnStepEvent(ctx, engine.StepEvent{CaseID: caseID, StepID: "draft", Status: engine.StepStatusRunning, Tokens: 900})
+	if err := svc.CheckStep(ctx, tenant, caseID); err != nil {
+		t.Fatalf("under budget: %v", err)
+	}
+	svc.OnStepEvent(ctx, engine.StepEvent{CaseID: caseID, StepID: "review", Status: engine.StepStatusSucceeded, Tokens: 400})
+
+	err := svc.CheckStep(ctx, tenant, caseID)
+	var exceeded *ExceededError
+	if !errors.As(err, &exceeded) || exceeded.Scope != ScopeWorkspace || exceeded.Metric != MetricLLMTokens || !exceeded.Budget() {
+		t.Fatalf("expected workspace token budget error, got %v", err)
+	}
+	if !errors.Is(err, engine.ErrUsageExceeded) {
+		t.Fatalf("expected the engine to fail the step without retries, got %v", err)
+	}
+	if want := time.Date(2026, 4, 1, 0, 0, 0, 0, time.UTC); !exceeded.ResetAt.Equal(want) {
+		t.Fatalf("reset at %s, want %s", exceeded.ResetAt, want)
+	}
+	if err := svc.AdmitExecution(ctx, tenant, bob); !errors.Is(err, ErrQuotaExceeded) {
+		t.Fatalf("workspace budget should stop every user, got %v", err)
+	}
+
+	report, err := svc.Report(ctx, tenant, alice)
+	if err != nil {
+		t.Fatalf("report: %v", err)
+	}
+	if report.Workspace.Used.LLMTokens != 1100 || report.User.Used.ToolRuntimeMS != 1500 || report.Workspace.Limits.LLMTokens != 1000 {
+		t.Fatalf("unexpected report: %+v", report)
+	}
+}
+
+func TestCheckStepLookupErrorsAreRetried(t *testing.T) {
+	svc := newTestService(newMemoryStore(), time.Now())
+	err := svc.CheckStep(context.Background(), uuid.New(), uuid.New())
+	if err == nil || errors.Is(err, engine.ErrUsageExceeded) {
+		t.Fatalf("expected a retryable lookup error, got %v", err)
+	}
+}
+
+func TestSetLimitsRejectsNegativeValues(t *testing.T) {
+	svc := NewService(newMemoryStore())
+	err := svc.SetLimits(context.Background(), uuid.New(), nil, Limits{ToolRuntimeMS: -1}, uuid.New())
+	if !errors.Is(err, ErrInvalidLimits) {
+		t.Fatalf("expected invalid limits, got %v", err)
+	}
+}
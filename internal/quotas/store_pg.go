@@ -0,0 +1,131 @@
+package quotas
+
+import (
+	"context"
+	"database/sql"
+	"errors"
+	"fmt"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+type PostgresStore struct {
+	db *sql.DB
+}
+
+func NewPostgresStore(db *sql.DB) *PostgresStore {
+	return &PostgresStore{db: db}
+}
+
+func (s *PostgresStore) GetLimits(ctx context.Context, tenantID uuid.UUID, principalID *uuid.UUID) (Limits, error) {
+	var out Limits
+	err := s.db.QueryRowContext(ctx, `
+SELECT executions_per_hour, tool_runtime_ms, llm_tokens
+FROM usage_quotas
+WHERE tenant_id = $1 AND principal_id IS NOT DISTINCT FROM $2
+`, tenantID, nullablePrincipal(principalID)).Scan(&out.ExecutionsPerHour, &out.ToolRuntimeMS, &out.LLMTokens)
+	if errors.Is(err, sql.ErrNoRows) {
+		return Limits{}, nil
+	}
+	if err != nil {
+		return Limits{}, fmt.Errorf("load usage quota: %w", err)
+	}
+	return out, nil
+}
+
+func (s *PostgresStore) ListQuotas(ctx context.Context, tenantID uuid.UUID) ([]Quota, error) {
+	rows, err := s.db.QueryContext(ctx, `
+SELECT principal_id, executions_per_hour, tool_runtime_ms, llm_tokens, updated_at
+FROM usage_quotas
+WHERE tenant_id = $1
+ORDER BY principal_id NULLS FIRST
+`, tenantID)
+	if err != nil {
+		return nil, fmt.Errorf("list usage quotas: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	out := make([]Quota, 0)
+	for rows.Next() {
+		var q Quota
+		var principalID uuid.NullUUID
+		if err := rows.Scan(&principalID, &q.ExecutionsPerHour, &q.ToolRuntimeMS, &q.LLMTokens, &q.UpdatedAt); err != nil {
+			return nil, fmt.Errorf("scan usage quota: %w", err)
+		}
+		if principalID.Valid {
+			q.PrincipalID = &principalID.UUID
+		}
+		q.UpdatedAt = q.UpdatedAt.UTC()
+		out = append(out, q)
+	}
+	if err := rows.Err(); err != nil {
+		return nil, fmt.Errorf("iterate usage quotas: %w", err)
+	}
+	return out, nil
+}
+
+func (s *PostgresStore) SetLimits(ctx context.Context, tenantID uuid.UUID, principalID *uuid.UUID, limits Limits, actorID uuid.UUID) error {
+	var updatedBy any
+	if actorID != uuid.Nil {
+		updatedBy = actorID
+	}
+	if _, err := s.db.ExecContext(ctx, `
+INSERT INTO usage_quotas (tenant_id, principal_id, executions_per_hour, tool_runtime_ms, llm_tokens, updated_by, updated_at)
+VALUES ($1, $2, $3, $4, $5, $6, now())
+ON CONFLICT (tenant_id, COALESCE(principal_id, '00000000-0000-0000-0000-000000000000'::uuid))
+DO UPDATE SET executions_per_hour = EXCLUDED.executions_per_hour,
+              tool_runtime_ms = EXCLUDED.tool_runtime_ms,
+              llm_tokens = EXCLUDED.llm_tokens,
+              updated_by = EXCLUDED.updated_by,
+              updated_at = now()
+`, tenantID, nullablePrincipal(principalID), limits.ExecutionsPerHour, limits.ToolRuntimeMS, limits.LLMTokens, updatedBy); err != nil {
+		return fmt.Errorf("set usage quota: %w", err)
+	}
+	return nil
+}
+
+func (s *PostgresStore) Usage(ctx context.Context, tenantID uuid.UUID, principalID *uuid.UUID, hour, month time.Time) (Counters, error) {
+	var out Counters
+	err := s.db.QueryRowContext(ctx, `
+SELECT COALESCE(SUM(executions) FILTER (WHERE hour = $3), 0),
+       COALESCE(SUM(tool_runtime_ms), 0),
+       COALESCE(SUM(llm_tokens), 0)
+FROM usage_hourly
+WHERE tenant_id = $1
+  AND ($2::uuid IS NULL OR principal_id = $2)
+  AND hour >= $4
+`, tenantID, nullablePrincipal(principalID), hour, month).Scan(&out.Executions, &out.ToolRuntimeMS, &out.LLMTokens)
+	if err != nil {
+		return Counters{}, fmt.Errorf("load usage: %w", err)
+	}
+	return out, nil
+}
+
+func (s *PostgresStore) Add(ctx context.Context, tenantID, principalID uuid.UUID, hour time.Time, delta Counters) error {
+	if _, err := s.db.ExecContext(ctx, `
+INSERT INTO usage_hourly (tenant_id, principal_id, hour, executions, tool_runtime_ms, llm_tokens)
+VALUES ($1, $2, $3, $4, $5, $6)
+ON CONFLICT (tenant_id, principal_id, hour)
+DO UPDATE SET executions = usage_hourly.executions + EXCLUDED.executions,
+              tool_runtime_ms = usage_hourly.tool_runtime_ms + EXCLUDED.tool_runtime_ms,
+              llm_tokens = usage_hourly.llm_tokens + EXCLUDED.llm_tokens
+`, tenantID, principalID, hour, delta.Executions, delta.ToolRuntimeMS, delta.LLMTokens); err != nil {
+		return fmt.Errorf("record usage: %w", err)
+	}
+	return nil
+}
+
+func (s *PostgresStore) CaseOwner(ctx context.Context, caseID uuid.UUID) (uuid.UUID, uuid.UUID, error) {
+	var tenantID, principalID uuid.UUID
+	if err := s.db.QueryRowContext(ctx, `SELECT tenant_id, created_by FROM cases WHERE id = $1`, caseID).Scan(&tenantID, &principalID); err != nil {
+		return uuid.Nil, uuid.Nil, fmt.Errorf("load case owner: %w", err)
+	}
+	return tenantID, principalID, nil
+}
+
+func nullablePrincipal(principalID *uuid.UUID) any {
+	if principalID == nil {
+		return nil
+	}
+	return *principalID
+}
@@ -13,7 +13,6 @@ import (
 	"time"
 
 	"github.com/google/uuid"
-	"golang.org/x/crypto/bcrypt"
 )
 
 type AuthService struct {
@@ -55,10 +54,17 @@ func (a *AuthService) Login(ctx context.Context, req LoginRequest) (*LoginRespon
 		_ = recordAuthEvent(ctx, a.db, authEvent{TenantID: &tenant.ID, PrincipalID: &principal.ID, EventType: "login", Success: false, IPAddress: req.IPAddress, UserAgent: req.UserAgent, Data: map[string]interface{}{"reason": "disabled"}})
 		return nil, ErrInvalidCredential
 	}
-	if err := bcrypt.CompareHashAndPassword([]byte(passwordHash), []byte(req.Password)); err != nil {
+	if !VerifyPassword(passwordHash, req.Password) {
 		_ = recordAuthEvent(ctx, a.db, authEvent{TenantID: &tenant.ID, PrincipalID: &principal.ID, EventType: "login", Success: false, IPAddress: req.IPAddress, UserAgent: req.UserAgent})
 		return nil, ErrInvalidCredential
 	}
+	if passwordNeedsRehash(passwordHash) {
+		// Upgrade bcrypt hashes now that the plaintext is at hand. A failure
+		// leaves the old hash, which still verifies.
+		if hash, err := HashPassword(req.Password); err == nil {
+			_, _ = a.db.ExecContext(ctx, `UPDATE principals SET password_hash = $3 WHERE id = $1 AND tenant_id = $2 AND password_hash = $4`, principal.ID, tenant.ID, hash, passwordHash)
+		}
+	}
 
 	sessionToken, tokenHash, err := generateSessionToken()
 	if err != nil {
@@ -97,6 +103,10 @@ RETURNING id
 	if err != nil {
 		return nil, fmt.Errorf("load user preferences: %w", err)
 	}
+	display, err := a.displayView(ctx, tenant.ID, prefs)
+	if err != nil {
+		return nil, fmt.Errorf("load display preferences: %w", err)
+	}
 
 	_ = recordAuthEvent(ctx, a.db, authEvent{TenantID: &tenant.ID, PrincipalID: &principal.ID, EventType: "login", Success: true, IPAddress: req.IPAddress, UserAgent: req.UserAgent})
 
@@ -105,6 +115,7 @@ RETURNING id
 		Principal:   principal,
 		Tenant:      tenant,
 		Preferences: prefs,
+		Display:     display.Effective,
 		Themes:      themes,
 		ExpiresAt:   expiresAt,
 	}, nil
@@ -118,6 +129,9 @@ func (a *AuthService) AuthenticateBearer(ctx context.Context, bearerToken string
 	if strings.Count(token, ".") == 2 {
 		return a.authenticateJWT(ctx, token)
 	}
+	if strings.HasPrefix(token, serviceTokenPrefix) {
+		return a.authenticateServiceToken(ctx, token)
+	}
 	return a.authenticateAPIKey(ctx, token)
 }
 
@@ -174,6 +188,60 @@ WHERE api_key_hash = $1
 	return &principal, nil
 }
 
+// Session restores the login context for a session token, so a client
+// holding only the session cookie can pick up where its login left off. The
+// token is returned as is; the session keeps its original expiry.
+func (a *AuthService) Session(ctx context.Context, token string) (*LoginResponse, error) {
+	token = strings.TrimSpace(token)
+	if strings.Count(token, ".") != 2 {
+		return nil, ErrInvalidToken
+	}
+	ap, err := a.authenticateJWT(ctx, token)
+	if err != nil {
+		return nil, err
+	}
+	claims, err := parseAndVerifyJWT(a.jwtSecret, token, time.Now().UTC())
+	if err != nil {
+		return nil, err
+	}
+	tenant, err := a.resolveTenant(ctx, &ap.TenantID, "")
+	if err != nil {
+		return nil, fmt.Errorf("load session tenant: %w", err)
+	}
+	var principal Principal
+	err = a.db.QueryRowContext(ctx, `
+SELECT id, tenant_id, type, name, COALESCE(email, ''), status, COALESCE(metadata, '{}'::jsonb), created_at
+FROM principals
+WHERE id = $1 AND tenant_id = $2
+`, ap.ID, ap.TenantID).Scan(&principal.ID, &principal.TenantID, &principal.Type, &principal.Name, &principal.Email, &principal.Status, &principal.Metadata, &principal.CreatedAt)
+	if err != nil {
+		return nil, fmt.Errorf("load session principal: %w", err)
+	}
+	principal.Roles = ap.Roles
+
+	themes, err := a.listThemesForTenant(ctx, tenant.ID)
+	if err != nil {
+		return nil, fmt.Errorf("list tenant themes: %w", err)
+	}
+	prefs, err := a.GetPreferences(ctx, tenant.ID, principal.ID)
+	if err != nil {
+		return nil, fmt.Errorf("load user preferences: %w", err)
+	}
+	display, err := a.displayView(ctx, tenant.ID, prefs)
+	if err != nil {
+		return nil, fmt.Errorf("load display preferences: %w", err)
+	}
+	return &LoginResponse{
+		Token:       token,
+		Principal:   principal,
+		Tenant:      tenant,
+		Preferences: prefs,
+		Display:     display.Effective,
+		Themes:      themes,
+		ExpiresAt:   time.Unix(claims.ExpiresAt, 0).UTC(),
+	}, nil
+}
+
 func (a *AuthService) Logout(ctx context.Context, tenantID, principalID uuid.UUID, sessionID uuid.UUID) error {
 	_, err := a.db.ExecContext(ctx, `
 DELETE FROM sessions s
@@ -213,11 +281,11 @@ WHERE id = $1 AND tenant_id = $2 AND type = 'human' AND status = 'active'
 		}
 		return fmt.Errorf("load password hash: %w", err)
 	}
-	if err := bcrypt.CompareHashAndPassword([]byte(currentHash), []byte(req.CurrentPassword)); err != nil {
+	if !VerifyPassword(currentHash, req.CurrentPassword) {
 		return ErrInvalidCredential
 	}
 
-	hash, err := bcrypt.GenerateFromPassword([]byte(req.NewPassword), 12)
+	hash, err := HashPassword(req.NewPassword)
 	if err != nil {
 		return fmt.Errorf("hash new password: %w", err)
 	}
@@ -226,7 +294,7 @@ WHERE id = $1 AND tenant_id = $2 AND type = 'human' AND status = 'active'
 UPDATE principals
 SET password_hash = $3
 WHERE id = $1 AND tenant_id = $2
-`, principalID, tenantID, string(hash)); err != nil {
+`, principalID, tenantID, hash); err != nil {
 		return fmt.Errorf("update password hash: %w", err)
 	}
 
@@ -399,7 +467,7 @@ func (a *AuthService) ensureThemeBelongsToTenant(ctx context.Context, tenantID,
 		return fmt.Errorf("validate theme id: %w", err)
 	}
 	if !exists {
-		return fmt.Errorf("theme not found")
+		return ErrThemeNotFound
 	}
 	return nil
 }
@@ -6,6 +6,7 @@ import (
 	"time"
 
 	"github.com/google/uuid"
+	"golang.org/x/crypto/bcrypt"
 )
 
 func TestValidatePasswordRules(t *testing.T) {
@@ -75,3 +76,49 @@ func TestSessionTokenGenerationEntropy(t *testing.T) {
 		t.Fatal("expected hash to differ from plaintext token")
 	}
 }
+
+func TestDisplayPreferencesValidate(t *testing.T) {
+	tests := []struct {
+		name    string
+		in      DisplayPreferences
+		wantErr bool
+	}{
+		{name: "empty", in: DisplayPreferences{}},
+		{name: "all set", in: DisplayPreferences{ColorScheme: ColorSchemeDark, Density: DensityCompact, LandingPage: "/cases"}},
+		{name: "unknown scheme", in: DisplayPreferences{ColorScheme: "sepia"}, wantErr: true},
+		{name: "unknown density", in: DisplayPreferences{Density: "cosy"}, wantErr: true},
+		{name: "relative landing page", in: DisplayPreferences{LandingPage: "cases"}, wantErr: true},
+		{name: "external landing page", in: DisplayPreferences{LandingPage: "//evil.example"}, wantErr: true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if err := tt.in.Validate(); (err != nil) != tt.wantErr {
+				t.Fatalf("Validate() error = %v, wantErr %v", err, tt.wantErr)
+			}
+		})
+	}
+}
+
+func TestPasswordHashingUsesArgon2idAndAcceptsBcrypt(t *testing.T) {
+	hash, err := HashPassword("Passw0rd")
+	if err != nil {
+		t.Fatalf("hash: %v", err)
+	}
+	if !strings.HasPrefix(hash, "$argon2id$") || passwordNeedsRehash(hash) {
+		t.Fatalf("expected a current argon2id hash, got %q", hash)
+	}
+	if !VerifyPassword(hash, "Passw0rd") || VerifyPassword(hash, "Passw0rd!") {
+		t.Fatal("argon2id hash did not verify only its own password")
+	}
+
+	legacy, err := bcrypt.GenerateFromPassword([]byte("Passw0rd"), bcrypt.MinCost)
+	if err != nil {
+		t.Fatalf("bcrypt: %v", err)
+	}
+	if !VerifyPassword(string(legacy), "Passw0rd") || VerifyPassword(string(legacy), "wrong") {
+		t.Fatal("bcrypt hash did not verify only its own password")
+	}
+	if !passwordNeedsRehash(string(legacy)) {
+		t.Fatal("expected a bcrypt hash to need rehashing")
+	}
+}
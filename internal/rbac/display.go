@@ -0,0 +1,194 @@
+package rbac
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"strings"
+
+	"github.com/google/uuid"
+)
+
+const (
+	ColorSchemeTheme  = "theme"
+	ColorSchemeSystem = "system"
+	ColorSchemeLight  = "light"
+	ColorSchemeDark   = "dark"
+
+	DensityComfortable = "comfortable"
+	DensityCompact     = "compact"
+
+	DefaultLandingPage = "/inbox"
+)
+
+// ErrInvalidPreference is returned for display preferences with values the
+// UI does not support.
+var ErrInvalidPreference = errors.New("invalid preference")
+
+// ErrThemeNotFound is returned when a preference names a theme the tenant
+// does not have.
+var ErrThemeNotFound = errors.New("theme not found")
+
+// DisplayPreferences are the UI settings kept on the server so they follow a
+// user across sessions and devices. ColorScheme "system" follows the
+// device's light or dark setting, and "theme", the default, uses the mode of
+// the selected theme. LandingPage is the path opened after sign-in, e.g.
+// "/cases". Empty fields are not set.
+type DisplayPreferences struct {
+	ThemeID     *uuid.UUID `json:"theme_id,omitempty"`
+	ColorScheme string     `json:"color_scheme,omitempty"`
+	Density     string     `json:"density,omitempty"`
+	LandingPage string     `json:"landing_page,omitempty"`
+}
+
+// DisplayPreferencesView has a user's own display preferences, the
+// workspace defaults, and the effective preferences the UI applies: the
+// user's, falling back to the workspace's, then to the built-in defaults.
+type DisplayPreferencesView struct {
+	Effective DisplayPreferences `json:"effective"`
+	User      DisplayPreferences `json:"user"`
+	Workspace DisplayPreferences `json:"workspace"`
+}
+
+func (p DisplayPreferences) Validate() error {
+	switch p.ColorScheme {
+	case "", ColorSchemeTheme, ColorSchemeSystem, ColorSchemeLight, ColorSchemeDark:
+	default:
+		return fmt.Errorf("%w: color_scheme must be theme, system, light or dark", ErrInvalidPreference)
+	}
+	switch p.Density {
+	case "", DensityComfortable, DensityCompact:
+	default:
+		return fmt.Errorf("%w: density must be comfortable or compact", ErrInvalidPreference)
+	}
+	if p.LandingPage != "" && (!strings.HasPrefix(p.LandingPage, "/") || strings.HasPrefix(p.LandingPage, "//") || len(p.LandingPage) > 200) {
+		return fmt.Errorf("%w: landing_page must be a path such as /inbox", ErrInvalidPreference)
+	}
+	return nil
+}
+
+func (p DisplayPreferences) values() map[string]string {
+	return map[string]string{"color_scheme": p.ColorScheme, "density": p.Density, "landing_page": p.LandingPage}
+}
+
+func displayFromJSON(raw []byte) DisplayPreferences {
+	var stored map[string]any
+	_ = json.Unmarshal(raw, &stored)
+	text := func(key string) string {
+		value, _ := stored[key].(string)
+		return value
+	}
+	return DisplayPreferences{ColorScheme: text("color_scheme"), Density: text("density"), LandingPage: text("landing_page")}
+}
+
+// DisplayPreferences returns the principal's display preferences.
+func (a *AuthService) DisplayPreferences(ctx context.Context, tenantID, principalID uuid.UUID) (DisplayPreferencesView, error) {
+	pref, err := a.GetPreferences(ctx, tenantID, principalID)
+	if err != nil {
+		return DisplayPreferencesView{}, err
+	}
+	return a.displayView(ctx, tenantID, pref)
+}
+
+func (a *AuthService) displayView(ctx context.Context, tenantID uuid.UUID, pref UserPreferences) (DisplayPreferencesView, error) {
+	workspace, err := a.WorkspacePreferences(ctx, tenantID)
+	if err != nil {
+		return DisplayPreferencesView{}, err
+	}
+	user := displayFromJSON(pref.Preferences)
+	user.ThemeID = pref.ThemeID
+	return DisplayPreferencesView{
+		Effective: DisplayPreferences{
+			ThemeID:     pref.ThemeID,
+			ColorScheme: firstNonEmpty(user.ColorScheme, workspace.ColorScheme, ColorSchemeTheme),
+			Density:     firstNonEmpty(user.Density, workspace.Density, DensityComfortable),
+			LandingPage: firstNonEmpty(user.LandingPage, workspace.LandingPage, DefaultLandingPage),
+		},
+		User:      user,
+		Workspace: workspace,
+	}, nil
+}
+
+// UpdateDisplayPreferences replaces the principal's display preferences,
+// keeping the other keys of their stored preferences. Fields left empty
+// fall back to the workspace defaults; a nil ThemeID keeps the theme.
+func (a *AuthService) UpdateDisplayPreferences(ctx context.Context, tenantID, principalID uuid.UUID, prefs DisplayPreferences) (DisplayPreferencesView, error) {
+	if err := prefs.Validate(); err != nil {
+		return DisplayPreferencesView{}, err
+	}
+	existing, err := a.GetPreferences(ctx, tenantID, principalID)
+	if err != nil {
+		return DisplayPreferencesView{}, err
+	}
+	stored := map[string]any{}
+	if err := json.Unmarshal(existing.Preferences, &stored); err != nil || stored == nil {
+		stored = map[string]any{}
+	}
+	for key, value := range prefs.values() {
+		if value == "" {
+			delete(stored, key)
+		} else {
+			stored[key] = value
+		}
+	}
+	raw, err := json.Marshal(stored)
+	if err != nil {
+		return DisplayPreferencesView{}, fmt.Errorf("marshal preferences: %w", err)
+	}
+	if _, err := a.UpdatePreferences(ctx, tenantID, principalID, UpdatePreferencesRequest{ThemeID: prefs.ThemeID, Preferences: raw}); err != nil {
+		return DisplayPreferencesView{}, err
+	}
+	return a.DisplayPreferences(ctx, tenantID, principalID)
+}
+
+// WorkspacePreferences returns the tenant's default display preferences.
+// The default theme is the tenant's default theme.
+func (a *AuthService) WorkspacePreferences(ctx context.Context, tenantID uuid.UUID) (DisplayPreferences, error) {
+	var raw []byte
+	if err := a.db.QueryRowContext(ctx, `SELECT COALESCE(settings->'preferences', '{}'::jsonb) FROM tenants WHERE id = $1`, tenantID).Scan(&raw); err != nil {
+		return DisplayPreferences{}, fmt.Errorf("load workspace preferences: %w", err)
+	}
+	prefs := displayFromJSON(raw)
+	themeID, err := a.getTenantDefaultThemeID(ctx, tenantID)
+	if err != nil {
+		return DisplayPreferences{}, fmt.Errorf("load tenant default theme: %w", err)
+	}
+	prefs.ThemeID = themeID
+	return prefs, nil
+}
+
+// UpdateWorkspacePreferences replaces the tenant's default display
+// preferences. ThemeID is ignored; the default theme is set on the theme.
+func (a *AuthService) UpdateWorkspacePreferences(ctx context.Context, tenantID uuid.UUID, prefs DisplayPreferences) (DisplayPreferences, error) {
+	if err := prefs.Validate(); err != nil {
+		return DisplayPreferences{}, err
+	}
+	stored := map[string]string{}
+	for key, value := range prefs.values() {
+		if value != "" {
+			stored[key] = value
+		}
+	}
+	raw, err := json.Marshal(stored)
+	if err != nil {
+		return DisplayPreferences{}, fmt.Errorf("marshal workspace preferences: %w", err)
+	}
+	if _, err := a.db.ExecContext(ctx, `
+UPDATE tenants
+SET settings = jsonb_set(COALESCE(settings, '{}'::jsonb), '{preferences}', $2::jsonb)
+WHERE id = $1
+`, tenantID, string(raw)); err != nil {
+		return DisplayPreferences{}, fmt.Errorf("update workspace preferences: %w", err)
+	}
+	return a.WorkspacePreferences(ctx, tenantID)
+}
+
+func firstNonEmpty(values ...string) string {
+	for _, value := range values {
+		if value != "" {
+			return value
+		}
+	}
+	return ""
+}
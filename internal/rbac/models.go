@@ -79,12 +79,13 @@ type LoginRequest struct {
 }
 
 type LoginResponse struct {
-	Token       string          `json:"token"`
-	Principal   Principal       `json:"principal"`
-	Tenant      TenantContext   `json:"tenant"`
-	Preferences UserPreferences `json:"preferences"`
-	Themes      []ThemeOption   `json:"themes"`
-	ExpiresAt   time.Time       `json:"expires_at"`
+	Token       string             `json:"token,omitempty"`
+	Principal   Principal          `json:"principal"`
+	Tenant      TenantContext      `json:"tenant"`
+	Preferences UserPreferences    `json:"preferences"`
+	Display     DisplayPreferences `json:"display"`
+	Themes      []ThemeOption      `json:"themes"`
+	ExpiresAt   time.Time          `json:"expires_at"`
 }
 
 type ChangePasswordRequest struct {
@@ -127,4 +128,6 @@ type AuthPrincipal struct {
 	Name      string
 	Email     string
 	Roles     []string
+	// Scope is set when the principal authenticated with a service token.
+	Scope     *TokenScope
 }
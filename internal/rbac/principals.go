@@ -8,7 +8,6 @@ import (
 	"strings"
 
 	"github.com/google/uuid"
-	"golang.org/x/crypto/bcrypt"
 )
 
 type PrincipalService struct {
@@ -45,12 +44,11 @@ func (s *PrincipalService) CreatePrincipal(ctx context.Context, tenantID uuid.UU
 		if req.Email == "" || req.Password == "" {
 			return Principal{}, "", fmt.Errorf("email and password are required for human principals")
 		}
-		hash, err := bcrypt.GenerateFromPassword([]byte(req.Password), 12)
+		hash, err := HashPassword(req.Password)
 		if err != nil {
 			return Principal{}, "", fmt.Errorf("hash principal password: %w", err)
 		}
-		h := string(hash)
-		passwordHash = &h
+		passwordHash = &hash
 	} else {
 		key, hash, err := GenerateAPIKey()
 		if err != nil {
@@ -0,0 +1,272 @@
+package rbac
+
+import (
+	"context"
+	"crypto/rand"
+	"database/sql"
+	"encoding/hex"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"strings"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+const (
+	serviceTokenPrefix     = "acx_st_"
+	DefaultServiceTokenTTL = 30 * 24 * time.Hour
+	MaxServiceTokenTTL     = 365 * 24 * time.Hour
+)
+
+var ErrServiceTokenNotFound = errors.New("service token not found")
+
+// TokenScope limits what a service token may do on behalf of its creator:
+// only the listed permissions and, when WorkflowIDs is not empty, only
+// starting cases of those workflows.
+type TokenScope struct {
+	TokenID     uuid.UUID   `json:"token_id"`
+	Permissions []string    `json:"permissions"`
+	WorkflowIDs []uuid.UUID `json:"workflow_ids,omitempty"`
+}
+
+// Allows reports whether the scope grants permission. Wildcards in the scope
+// match as they do for role permissions.
+func (s *TokenScope) Allows(permission string) bool {
+	if s == nil {
+		return true
+	}
+	perms := make(map[string]bool, len(s.Permissions))
+	for _, p := range s.Permissions {
+		perms[p] = true
+	}
+	return hasPermission(perms, permission)
+}
+
+type ServiceToken struct {
+	ID          uuid.UUID   `json:"id"`
+	TenantID    uuid.UUID   `json:"tenant_id"`
+	Name        string      `json:"name"`
+	Permissions []string    `json:"permissions"`
+	WorkflowIDs []uuid.UUID `json:"workflow_ids"`
+	CreatedBy   uuid.UUID   `json:"created_by"`
+	CreatedAt   time.Time   `json:"created_at"`
+	ExpiresAt   time.Time   `json:"expires_at"`
+	RevokedAt   *time.Time  `json:"revoked_at,omitempty"`
+	LastUsedAt  *time.Time  `json:"last_used_at,omitempty"`
+}
+
+type CreateServiceTokenRequest struct {
+	Name        string      `json:"name"`
+	Permissions []string    `json:"permissions"`
+	WorkflowIDs []uuid.UUID `json:"workflow_ids"`
+	TTLSeconds  int64       `json:"ttl_seconds"`
+}
+
+// CreatedServiceToken carries the plaintext token, which is only ever
+// returned when the token is minted.
+type CreatedServiceToken struct {
+	ServiceToken
+	Token string `json:"token"`
+}
+
+type TokenService struct {
+	db    *sql.DB
+	authz *Service
+}
+
+func NewTokenService(db *sql.DB, authz *Service) *TokenService {
+	return &TokenService{db: db, authz: authz}
+}
+
+// Create mints a service token for creatorID. The creator must hold every
+// permission it grants, and every workflow must belong to the tenant.
+func (s *TokenService) Create(ctx context.Context, tenantID, creatorID uuid.UUID, req CreateServiceTokenRequest) (CreatedServiceToken, error) {
+	name := strings.TrimSpace(req.Name)
+	if name == "" {
+		return CreatedServiceToken{}, fmt.Errorf("name is required")
+	}
+	permissions := make([]string, 0, len(req.Permissions))
+	for _, p := range req.Permissions {
+		p = strings.TrimSpace(p)
+		if p == "" {
+			continue
+		}
+		if !strings.Contains(p, ":") && p != "*" {
+			return CreatedServiceToken{}, fmt.Errorf("invalid permission %q", p)
+		}
+		if err := s.authz.Authorize(ctx, creatorID, p); err != nil {
+			if errors.Is(err, ErrForbidden) {
+				return CreatedServiceToken{}, fmt.Errorf("cannot grant permission %q you do not hold", p)
+			}
+			return CreatedServiceToken{}, err
+		}
+		permissions = append(permissions, p)
+	}
+	if len(permissions) == 0 {
+		return CreatedServiceToken{}, fmt.Errorf("at least one permission is required")
+	}
+	ttl := DefaultServiceTokenTTL
+	if req.TTLSeconds < 0 {
+		return CreatedServiceToken{}, fmt.Errorf("ttl_seconds must be positive")
+	}
+	if req.TTLSeconds > 0 {
+		ttl = time.Duration(req.TTLSeconds) * time.Second
+	}
+	if ttl > MaxServiceTokenTTL {
+		return CreatedServiceToken{}, fmt.Errorf("ttl_seconds must be at most %d", int64(MaxServiceTokenTTL/time.Second))
+	}
+	workflowIDs := req.WorkflowIDs
+	if workflowIDs == nil {
+		workflowIDs = []uuid.UUID{}
+	}
+	for _, id := range workflowIDs {
+		var exists bool
+		if err := s.db.QueryRowContext(ctx, `SELECT EXISTS (SELECT 1 FROM workflows WHERE id = $1 AND tenant_id = $2)`, id, tenantID).Scan(&exists); err != nil {
+			return CreatedServiceToken{}, fmt.Errorf("check token workflow: %w", err)
+		}
+		if !exists {
+			return CreatedServiceToken{}, fmt.Errorf("workflow %s not found", id)
+		}
+	}
+
+	plaintext, hash, err := generateServiceToken()
+	if err != nil {
+		return CreatedServiceToken{}, fmt.Errorf("generate service token: %w", err)
+	}
+	permissionsJSON, _ := json.Marshal(permissions)
+	workflowsJSON, _ := json.Marshal(workflowIDs)
+	out := CreatedServiceToken{Token: plaintext}
+	out.ServiceToken = ServiceToken{TenantID: tenantID, Name: name, Permissions: permissions, WorkflowIDs: workflowIDs, CreatedBy: creatorID}
+	err = s.db.QueryRowContext(ctx, `
+INSERT INTO service_tokens (tenant_id, name, token_hash, permissions, workflow_ids, created_by, expires_at)
+VALUES ($1, $2, $3, $4::jsonb, $5::jsonb, $6, $7)
+RETURNING id, created_at, expires_at
+`, tenantID, name, hash, string(permissionsJSON), string(workflowsJSON), creatorID, time.Now().UTC().Add(ttl)).Scan(&out.ID, &out.CreatedAt, &out.ExpiresAt)
+	if err != nil {
+		return CreatedServiceToken{}, fmt.Errorf("insert service token: %w", err)
+	}
+	_ = recordAuthEvent(ctx, s.db, authEvent{TenantID: &tenantID, PrincipalID: &creatorID, EventType: "service_token_created", Success: true, Data: map[string]interface{}{"token_id": out.ID.String(), "name": name}})
+	return out, nil
+}
+
+func (s *TokenService) List(ctx context.Context, tenantID uuid.UUID) ([]ServiceToken, error) {
+	rows, err := s.db.QueryContext(ctx, `
+SELECT id, tenant_id, name, permissions, workflow_ids, created_by, created_at, expires_at, revoked_at, last_used_at
+FROM service_tokens
+WHERE tenant_id = $1
+ORDER BY created_at DESC
+`, tenantID)
+	if err != nil {
+		return nil, fmt.Errorf("list service tokens: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+
+	out := make([]ServiceToken, 0)
+	for rows.Next() {
+		var (
+			t               ServiceToken
+			permissionsJSON []byte
+			workflowsJSON   []byte
+		)
+		if err := rows.Scan(&t.ID, &t.TenantID, &t.Name, &permissionsJSON, &workflowsJSON, &t.CreatedBy, &t.CreatedAt, &t.ExpiresAt, &t.RevokedAt, &t.LastUsedAt); err != nil {
+			return nil, fmt.Errorf("scan service token: %w", err)
+		}
+		if err := json.Unmarshal(permissionsJSON, &t.Permissions); err != nil {
+			return nil, fmt.Errorf("decode service token permissions: %w", err)
+		}
+		if err := json.Unmarshal(workflowsJSON, &t.WorkflowIDs); err != nil {
+			return nil, fmt.Errorf("decode service token workflows: %w", err)
+		}
+		out = append(out, t)
+	}
+	if err := rows.Err(); err != nil {
+		return nil, fmt.Errorf("iterate service tokens: %w", err)
+	}
+	return out, nil
+}
+
+// Revoke stops a token from authenticating. Revoking twice is not an error.
+func (s *TokenService) Revoke(ctx context.Context, tenantID, principalID, tokenID uuid.UUID) error {
+	res, err := s.db.ExecContext(ctx, `
+UPDATE service_tokens
+SET revoked_at = COALESCE(revoked_at, now())
+WHERE id = $1 AND tenant_id = $2
+`, tokenID, tenantID)
+	if err != nil {
+		return fmt.Errorf("revoke service token: %w", err)
+	}
+	if n, _ := res.RowsAffected(); n == 0 {
+		return ErrServiceTokenNotFound
+	}
+	_ = recordAuthEvent(ctx, s.db, authEvent{TenantID: &tenantID, PrincipalID: &principalID, EventType: "service_token_revoked", Success: true, Data: map[string]interface{}{"token_id": tokenID.String()}})
+	return nil
+}
+
+// AllowsCaseType reports whether scope may start a case of caseType, which
+// requires one of its workflows to handle that case type.
+func (s *TokenService) AllowsCaseType(ctx context.Context, tenantID uuid.UUID, scope *TokenScope, caseType string) (bool, error) {
+	if scope == nil || len(scope.WorkflowIDs) == 0 {
+		return true, nil
+	}
+	workflowsJSON, _ := json.Marshal(scope.WorkflowIDs)
+	var allowed bool
+	err := s.db.QueryRowContext(ctx, `
+SELECT EXISTS (
+    SELECT 1
+    FROM workflows
+    WHERE tenant_id = $1
+      AND case_type = $2
+      AND id::text IN (SELECT jsonb_array_elements_text($3::jsonb))
+)
+`, tenantID, strings.TrimSpace(caseType), string(workflowsJSON)).Scan(&allowed)
+	if err != nil {
+		return false, fmt.Errorf("check token workflow scope: %w", err)
+	}
+	return allowed, nil
+}
+
+func (a *AuthService) authenticateServiceToken(ctx context.Context, rawToken string) (*AuthPrincipal, error) {
+	var (
+		principal       AuthPrincipal
+		scope           TokenScope
+		permissionsJSON []byte
+		workflowsJSON   []byte
+	)
+	err := a.db.QueryRowContext(ctx, `
+UPDATE service_tokens t
+SET last_used_at = now()
+FROM principals p
+WHERE t.token_hash = $1
+  AND t.revoked_at IS NULL
+  AND t.expires_at > now()
+  AND p.id = t.created_by
+  AND p.tenant_id = t.tenant_id
+  AND p.status = 'active'
+RETURNING p.id, p.tenant_id, p.type, p.name, COALESCE(p.email, ''), t.id, t.permissions, t.workflow_ids
+`, hashSecret(rawToken)).Scan(&principal.ID, &principal.TenantID, &principal.Type, &principal.Name, &principal.Email, &scope.TokenID, &permissionsJSON, &workflowsJSON)
+	if err != nil {
+		if errors.Is(err, sql.ErrNoRows) {
+			return nil, ErrInvalidToken
+		}
+		return nil, fmt.Errorf("lookup service token: %w", err)
+	}
+	if err := json.Unmarshal(permissionsJSON, &scope.Permissions); err != nil {
+		return nil, fmt.Errorf("decode service token permissions: %w", err)
+	}
+	if err := json.Unmarshal(workflowsJSON, &scope.WorkflowIDs); err != nil {
+		return nil, fmt.Errorf("decode service token workflows: %w", err)
+	}
+	principal.Scope = &scope
+	return &principal, nil
+}
+
+func generateServiceToken() (string, string, error) {
+	raw := make([]byte, 32)
+	if _, err := rand.Read(raw); err != nil {
+		return "", "", err
+	}
+	plaintext := serviceTokenPrefix + hex.EncodeToString(raw)
+	return plaintext, hashSecret(plaintext), nil
+}
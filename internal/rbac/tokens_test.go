@@ -0,0 +1,65 @@
+package rbac
+
+import (
+	"context"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+func TestTokenScopeAllows(t *testing.T) {
+	var unscoped *TokenScope
+	if !unscoped.Allows("admin:users") {
+		t.Fatal("expected a principal without a token scope to be unrestricted")
+	}
+	scope := &TokenScope{Permissions: []string{"cases:create", "workflows:*"}}
+	for _, permission := range []string{"cases:create", "workflows:view"} {
+		if !scope.Allows(permission) {
+			t.Fatalf("expected scope to allow %s", permission)
+		}
+	}
+	for _, permission := range []string{"cases:read", "admin:tokens"} {
+		if scope.Allows(permission) {
+			t.Fatalf("expected scope to deny %s", permission)
+		}
+	}
+}
+
+func TestCreateServiceTokenValidatesScope(t *testing.T) {
+	authz := &Service{cache: NewPermissionCache(time.Minute)}
+	authz.loader = func(_ context.Context, _ uuid.UUID) (map[string]bool, error) {
+		return map[string]bool{"cases:create": true}, nil
+	}
+	tokens := NewTokenService(nil, authz)
+	tenantID, creatorID := uuid.New(), uuid.New()
+
+	cases := []struct {
+		name string
+		req  CreateServiceTokenRequest
+		want string
+	}{
+		{"missing name", CreateServiceTokenRequest{Permissions: []string{"cases:create"}}, "name is required"},
+		{"no permissions", CreateServiceTokenRequest{Name: "ci"}, "at least one permission"},
+		{"ungrantable", CreateServiceTokenRequest{Name: "ci", Permissions: []string{"admin:users"}}, "do not hold"},
+		{"malformed", CreateServiceTokenRequest{Name: "ci", Permissions: []string{"cases"}}, "invalid permission"},
+		{"too long", CreateServiceTokenRequest{Name: "ci", Permissions: []string{"cases:create"}, TTLSeconds: int64(2 * MaxServiceTokenTTL / time.Second)}, "at most"},
+	}
+	for _, tc := range cases {
+		_, err := tokens.Create(context.Background(), tenantID, creatorID, tc.req)
+		if err == nil || !strings.Contains(err.Error(), tc.want) {
+			t.Fatalf("%s: expected error containing %q, got %v", tc.name, tc.want, err)
+		}
+	}
+}
+
+func TestGenerateServiceTokenIsPrefixedAndHashed(t *testing.T) {
+	plaintext, hash, err := generateServiceToken()
+	if err != nil {
+		t.Fatalf("generate: %v", err)
+	}
+	if !strings.HasPrefix(plaintext, serviceTokenPrefix) || hash != hashSecret(plaintext) || strings.Contains(hash, plaintext) {
+		t.Fatalf("unexpected token %q hash %q", plaintext, hash)
+	}
+}
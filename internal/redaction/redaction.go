@@ -0,0 +1,316 @@
+// Package redaction masks personal data and secrets in payloads before they
+// are stored in step records or written to logs.
+package redaction
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"os"
+	"regexp"
+	"strconv"
+	"strings"
+)
+
+// Mask replaces every redacted value.
+const Mask = "[REDACTED]"
+
+// DefaultHeaders are always redacted, whatever the configured rules.
+var DefaultHeaders = []string{"Authorization", "Proxy-Authorization", "Cookie", "Set-Cookie", "X-Api-Key"}
+
+// Rules configures a Redactor. Paths are JSONPath expressions ($.a.b,
+// $.items[*].card, $..password) whose values are masked. Patterns are
+// regular expressions masked wherever they match inside a string. Headers
+// are header names, matched case-insensitively, masked in logged headers and
+// as object keys anywhere in a payload.
+type Rules struct {
+	Paths    []string `json:"paths"`
+	Patterns []string `json:"patterns"`
+	Headers  []string `json:"headers"`
+}
+
+// Redactor applies compiled Rules. A nil Redactor leaves values unchanged.
+type Redactor struct {
+	paths    [][]segment
+	patterns []*regexp.Regexp
+	headers  map[string]bool
+}
+
+type segment struct {
+	key      string
+	index    int
+	wildcard bool
+	descend  bool
+}
+
+// Compile validates rules and adds DefaultHeaders.
+func Compile(rules Rules) (*Redactor, error) {
+	r := &Redactor{headers: map[string]bool{}}
+	for _, raw := range rules.Paths {
+		segs, err := parsePath(raw)
+		if err != nil {
+			return nil, err
+		}
+		r.paths = append(r.paths, segs)
+	}
+	for _, raw := range rules.Patterns {
+		re, err := regexp.Compile(raw)
+		if err != nil {
+			return nil, fmt.Errorf("invalid redaction pattern %q: %w", raw, err)
+		}
+		r.patterns = append(r.patterns, re)
+	}
+	for _, name := range append(append([]string{}, DefaultHeaders...), rules.Headers...) {
+		if name = strings.TrimSpace(name); name != "" {
+			r.headers[strings.ToLower(name)] = true
+		}
+	}
+	return r, nil
+}
+
+// FromEnv compiles the rules in ACERYX_REDACTION_RULES, a JSON object with
+// paths, patterns and headers.
+func FromEnv() (*Redactor, error) {
+	var rules Rules
+	if raw := strings.TrimSpace(os.Getenv("ACERYX_REDACTION_RULES")); raw != "" {
+		if err := json.Unmarshal([]byte(raw), &rules); err != nil {
+			return nil, fmt.Errorf("parse ACERYX_REDACTION_RULES: %w", err)
+		}
+	}
+	return Compile(rules)
+}
+
+var builtin, _ = Compile(Rules{})
+
+// Default returns a redactor with only the built-in rules, which mask
+// DefaultHeaders. It stands in wherever no configured redactor was given.
+func Default() *Redactor {
+	return builtin
+}
+
+type redactorKey struct{}
+
+// WithRedactor sets the redactor that code running with ctx, such as
+// connector request logging, applies.
+func WithRedactor(ctx context.Context, r *Redactor) context.Context {
+	if r == nil {
+		return ctx
+	}
+	return context.WithValue(ctx, redactorKey{}, r)
+}
+
+// FromContext returns the redactor set by WithRedactor, or Default().
+func FromContext(ctx context.Context) *Redactor {
+	if r, ok := ctx.Value(redactorKey{}).(*Redactor); ok {
+		return r
+	}
+	return builtin
+}
+
+// RedactJSON returns raw with the rules applied. Values that are not valid
+// JSON are returned unchanged.
+func (r *Redactor) RedactJSON(raw json.RawMessage) json.RawMessage {
+	if r == nil || len(bytes.TrimSpace(raw)) == 0 {
+		return raw
+	}
+	dec := json.NewDecoder(bytes.NewReader(raw))
+	dec.UseNumber()
+	var value any
+	if err := dec.Decode(&value); err != nil {
+		return raw
+	}
+	out, err := json.Marshal(r.RedactValue(value))
+	if err != nil {
+		return raw
+	}
+	return out
+}
+
+// RedactValue returns a redacted copy of a decoded JSON value.
+func (r *Redactor) RedactValue(value any) any {
+	if r == nil {
+		return value
+	}
+	value = r.walk(deepCopy(value))
+	for _, path := range r.paths {
+		value = redactPath(value, path)
+	}
+	return value
+}
+
+// RedactString masks pattern matches in s.
+func (r *Redactor) RedactString(s string) string {
+	if r == nil {
+		return s
+	}
+	for _, re := range r.patterns {
+		s = re.ReplaceAllString(s, Mask)
+	}
+	return s
+}
+
+// RedactBody redacts a request or response body for logging: JSON bodies
+// get every rule, anything else only the patterns.
+func (r *Redactor) RedactBody(body []byte) string {
+	if r == nil {
+		return string(body)
+	}
+	if json.Valid(body) {
+		return string(r.RedactJSON(body))
+	}
+	return r.RedactString(string(body))
+}
+
+// RedactHeaders flattens headers for logging with sensitive ones masked.
+func (r *Redactor) RedactHeaders(headers http.Header) map[string]string {
+	out := make(map[string]string, len(headers))
+	for name, values := range headers {
+		if r != nil && r.headers[strings.ToLower(name)] {
+			out[name] = Mask
+			continue
+		}
+		out[name] = r.RedactString(strings.Join(values, ", "))
+	}
+	return out
+}
+
+// walk masks header-named keys and pattern matches throughout value, which
+// it may modify.
+func (r *Redactor) walk(value any) any {
+	switch v := value.(type) {
+	case map[string]any:
+		for k, child := range v {
+			if r.headers[strings.ToLower(k)] {
+				v[k] = Mask
+				continue
+			}
+			v[k] = r.walk(child)
+		}
+	case []any:
+		for i, child := range v {
+			v[i] = r.walk(child)
+		}
+	case string:
+		return r.RedactString(v)
+	}
+	return value
+}
+
+func redactPath(node any, segs []segment) any {
+	if len(segs) == 0 {
+		return Mask
+	}
+	seg, rest := segs[0], segs[1:]
+	if seg.descend {
+		here := seg
+		here.descend = false
+		node = redactPath(node, append([]segment{here}, rest...))
+		switch v := node.(type) {
+		case map[string]any:
+			for k, child := range v {
+				v[k] = redactPath(child, segs)
+			}
+		case []any:
+			for i, child := range v {
+				v[i] = redactPath(child, segs)
+			}
+		}
+		return node
+	}
+	switch v := node.(type) {
+	case map[string]any:
+		for k, child := range v {
+			if seg.wildcard || (seg.index < 0 && k == seg.key) {
+				v[k] = redactPath(child, rest)
+			}
+		}
+	case []any:
+		for i, child := range v {
+			if seg.wildcard || seg.index == i {
+				v[i] = redactPath(child, rest)
+			}
+		}
+	}
+	return node
+}
+
+// parsePath parses the JSONPath subset Rules accepts: dot and bracket
+// member access, [n], [*], .* and .. recursive descent.
+func parsePath(raw string) ([]segment, error) {
+	path := strings.TrimSpace(raw)
+	if !strings.HasPrefix(path, "$") {
+		return nil, fmt.Errorf("invalid redaction path %q: must start with $", raw)
+	}
+	path = path[1:]
+	var segs []segment
+	for path != "" {
+		descend := false
+		switch {
+		case strings.HasPrefix(path, ".."):
+			descend = true
+			path = path[2:]
+		case strings.HasPrefix(path, "."):
+			path = path[1:]
+		}
+		seg := segment{index: -1, descend: descend}
+		switch {
+		case strings.HasPrefix(path, "["):
+			end := strings.Index(path, "]")
+			if end < 0 {
+				return nil, fmt.Errorf("invalid redaction path %q: unclosed [", raw)
+			}
+			inner := strings.TrimSpace(path[1:end])
+			path = path[end+1:]
+			switch {
+			case inner == "*":
+				seg.wildcard = true
+			case len(inner) >= 2 && (inner[0] == '\'' || inner[0] == '"') && inner[len(inner)-1] == inner[0]:
+				seg.key = inner[1 : len(inner)-1]
+			default:
+				n, err := strconv.Atoi(inner)
+				if err != nil || n < 0 {
+					return nil, fmt.Errorf("invalid redaction path %q: bad index %q", raw, inner)
+				}
+				seg.index = n
+			}
+		case strings.HasPrefix(path, "*"):
+			seg.wildcard = true
+			path = path[1:]
+		default:
+			end := strings.IndexAny(path, ".[")
+			if end < 0 {
+				end = len(path)
+			}
+			seg.key = path[:end]
+			path = path[end:]
+			if seg.key == "" {
+				return nil, fmt.Errorf("invalid redaction path %q: empty member name", raw)
+			}
+		}
+		segs = append(segs, seg)
+	}
+	if len(segs) == 0 {
+		return nil, fmt.Errorf("invalid redaction path %q: redacting the whole document is not supported", raw)
+	}
+	return segs, nil
+}
+
+func deepCopy(value any) any {
+	switch v := value.(type) {
+	case map[string]any:
+		out := make(map[string]any, len(v))
+		for k, child := range v {
+			out[k] = deepCopy(child)
+		}
+		return out
+	case []any:
+		out := make([]any, len(v))
+		for i, child := range v {
+			out[i] = deepCopy(child)
+		}
+		return out
+	}
+	return value
+}
@@ -0,0 +1,100 @@
+package redaction
+
+import (
+	"context"
+	"encoding/json"
+	"net/http"
+	"testing"
+)
+
+func TestRedactJSONAppliesPathsPatternsAndHeaders(t *testing.T) {
+	r, err := Compile(Rules{
+		Paths:    []string{"$.customer.ssn", "$.cards[*].number", "$..password", "$['x-trace'][1]"},
+		Patterns: []string{`\b[\w.+-]+@[\w-]+\.[\w.]+\b`},
+		Headers:  []string{"X-Session"},
+	})
+	if err != nil {
+		t.Fatalf("compile: %v", err)
+	}
+	in := json.RawMessage(`{
+		"customer": {"ssn": "123-45-6789", "name": "Ada", "note": "mail ada@example.com today"},
+		"cards": [{"number": "4111111111111111", "brand": "visa"}, {"number": "5500000000000004"}],
+		"auth": {"nested": {"password": "hunter2"}},
+		"headers": {"Authorization": "Bearer abc", "x-session": "s1", "Accept": "json"},
+		"x-trace": ["a", "b"],
+		"amount": 12345678901234567890
+	}`)
+	var got map[string]any
+	if err := json.Unmarshal(r.RedactJSON(in), &got); err != nil {
+		t.Fatalf("decode: %v", err)
+	}
+	customer := got["customer"].(map[string]any)
+	if customer["ssn"] != Mask || customer["name"] != "Ada" || customer["note"] != "mail "+Mask+" today" {
+		t.Fatalf("unexpected customer: %#v", customer)
+	}
+	cards := got["cards"].([]any)
+	if cards[0].(map[string]any)["number"] != Mask || cards[1].(map[string]any)["number"] != Mask || cards[0].(map[string]any)["brand"] != "visa" {
+		t.Fatalf("unexpected cards: %#v", cards)
+	}
+	if got["auth"].(map[string]any)["nested"].(map[string]any)["password"] != Mask {
+		t.Fatalf("recursive path not applied: %#v", got["auth"])
+	}
+	headers := got["headers"].(map[string]any)
+	if headers["Authorization"] != Mask || headers["x-session"] != Mask || headers["Accept"] != "json" {
+		t.Fatalf("unexpected headers: %#v", headers)
+	}
+	if trace := got["x-trace"].([]any); trace[0] != "a" || trace[1] != Mask {
+		t.Fatalf("unexpected x-trace: %#v", trace)
+	}
+	if string(r.RedactJSON(json.RawMessage(`{"amount":12345678901234567890}`))) != `{"amount":12345678901234567890}` {
+		t.Fatal("numbers must survive redaction unchanged")
+	}
+}
+
+func TestRedactHeadersAndBody(t *testing.T) {
+	r, err := Compile(Rules{Patterns: []string{`secret-\w+`}})
+	if err != nil {
+		t.Fatalf("compile: %v", err)
+	}
+	headers := r.RedactHeaders(http.Header{"Authorization": {"Bearer abc"}, "Content-Type": {"text/plain"}})
+	if headers["Authorization"] != Mask || headers["Content-Type"] != "text/plain" {
+		t.Fatalf("unexpected headers: %#v", headers)
+	}
+	if got := r.RedactBody([]byte("token=secret-abc&x=1")); got != "token="+Mask+"&x=1" {
+		t.Fatalf("unexpected body: %q", got)
+	}
+	var nilRedactor *Redactor
+	if got := nilRedactor.RedactBody([]byte("secret-abc")); got != "secret-abc" {
+		t.Fatalf("nil redactor changed the body: %q", got)
+	}
+}
+
+func TestCompileRejectsInvalidRules(t *testing.T) {
+	for _, rules := range []Rules{
+		{Paths: []string{"customer.ssn"}},
+		{Paths: []string{"$.items[x]"}},
+		{Paths: []string{"$"}},
+		{Patterns: []string{"("}},
+	} {
+		if _, err := Compile(rules); err == nil {
+			t.Fatalf("expected %+v to be rejected", rules)
+		}
+	}
+}
+
+func TestFromContextFallsBackToBuiltinRules(t *testing.T) {
+	r, err := Compile(Rules{Patterns: []string{`secret-\w+`}})
+	if err != nil {
+		t.Fatalf("compile: %v", err)
+	}
+	if got := FromContext(WithRedactor(context.Background(), r)).RedactBody([]byte("secret-abc")); got != Mask {
+		t.Fatalf("expected the configured redactor, got %q", got)
+	}
+	fallback := FromContext(WithRedactor(context.Background(), nil))
+	if got := fallback.RedactBody([]byte("secret-abc")); got != "secret-abc" {
+		t.Fatalf("expected only the built-in rules, got %q", got)
+	}
+	if headers := fallback.RedactHeaders(http.Header{"Authorization": {"Bearer abc"}}); headers["Authorization"] != Mask {
+		t.Fatalf("expected the built-in header rules, got %#v", headers)
+	}
+}
@@ -0,0 +1,140 @@
+package script
+
+import (
+	"math"
+
+	"github.com/dop251/goja"
+)
+
+// arrayElementBytes is what one array element is charged, roughly the size
+// of a goja value slot.
+const arrayElementBytes = 16
+
+// allocGuard charges the strings and arrays a run builds against
+// Limits.MaxAllocBytes. goja has no per-runtime heap accounting, so the
+// built-ins that can grow a string or array are wrapped instead: each is
+// charged what it is about to build before it runs, and the rest of its
+// result afterwards. Charges only grow, like allocations, so a loop that
+// builds and drops strings is stopped as well as one that keeps them.
+type allocGuard struct {
+	vm       *goja.Runtime
+	limit    uint64
+	used     uint64
+	exceeded bool
+}
+
+func newAllocGuard(vm *goja.Runtime, limit uint64) *allocGuard {
+	g := &allocGuard{vm: vm, limit: limit}
+
+	str := g.prototype("String")
+	g.wrap(str, "repeat", func(call goja.FunctionCall) uint64 {
+		return mulSat(g.length(call.This), nonNegative(call.Argument(0)))
+	}, 1)
+	pad := func(call goja.FunctionCall) uint64 {
+		target, current := nonNegative(call.Argument(0)), g.length(call.This)
+		if target <= current {
+			return 0
+		}
+		return target - current
+	}
+	g.wrap(str, "padStart", pad, 1)
+	g.wrap(str, "padEnd", pad, 1)
+	for _, name := range []string{"concat", "replace", "replaceAll"} {
+		g.wrap(str, name, nil, 1)
+	}
+
+	arr := g.prototype("Array")
+	g.wrap(arr, "join", func(call goja.FunctionCall) uint64 {
+		sep := uint64(1)
+		if arg := call.Argument(0); !goja.IsUndefined(arg) {
+			sep = g.length(arg.ToString())
+		}
+		return mulSat(g.length(call.This), sep)
+	}, 1)
+	grow := func(call goja.FunctionCall) uint64 {
+		return mulSat(uint64(len(call.Arguments)), arrayElementBytes)
+	}
+	g.wrap(arr, "push", grow, 0)
+	g.wrap(arr, "unshift", grow, 0)
+	whole := func(call goja.FunctionCall) uint64 {
+		return mulSat(g.length(call.This), arrayElementBytes)
+	}
+	g.wrap(arr, "fill", whole, 0)
+	g.wrap(arr, "map", whole, 0)
+	g.wrap(arr, "concat", nil, arrayElementBytes)
+	g.wrap(g.vm.Get("Array").ToObject(g.vm), "from", func(call goja.FunctionCall) uint64 {
+		return mulSat(g.length(call.Argument(0)), arrayElementBytes)
+	}, arrayElementBytes)
+	g.wrap(g.vm.Get("JSON").ToObject(g.vm), "stringify", nil, 1)
+	return g
+}
+
+func (g *allocGuard) prototype(constructor string) *goja.Object {
+	return g.vm.Get(constructor).ToObject(g.vm).Get("prototype").ToObject(g.vm)
+}
+
+// wrap replaces obj[name] with a version that charges pre(call) before
+// calling the built-in, then unit bytes per element of its result less what
+// was charged up front.
+func (g *allocGuard) wrap(obj *goja.Object, name string, pre func(goja.FunctionCall) uint64, unit uint64) {
+	builtin, ok := goja.AssertFunction(obj.Get(name))
+	if !ok {
+		return
+	}
+	_ = obj.Set(name, func(call goja.FunctionCall) goja.Value {
+		var charged uint64
+		if pre != nil {
+			charged = pre(call)
+			g.charge(charged)
+		}
+		result, err := builtin(call.This, call.Arguments...)
+		if err != nil {
+			panic(err)
+		}
+		if size := mulSat(g.length(result), unit); size > charged {
+			g.charge(size - charged)
+		}
+		return result
+	})
+}
+
+// charge adds n bytes to the run's total. Past the limit it interrupts the
+// runtime, so a script that catches the thrown error still stops.
+func (g *allocGuard) charge(n uint64) {
+	if n > g.limit-g.used {
+		g.exceeded = true
+		g.vm.Interrupt(ErrMemoryLimit)
+		panic(g.vm.NewGoError(ErrMemoryLimit))
+	}
+	g.used += n
+}
+
+// length returns v's length property, or 0 for values without one.
+func (g *allocGuard) length(v goja.Value) uint64 {
+	if v == nil || goja.IsUndefined(v) || goja.IsNull(v) {
+		return 0
+	}
+	n := v.ToObject(g.vm).Get("length")
+	if n == nil {
+		return 0
+	}
+	return nonNegative(n)
+}
+
+func nonNegative(v goja.Value) uint64 {
+	if v == nil || goja.IsUndefined(v) {
+		return 0
+	}
+	n := v.ToInteger()
+	if n < 0 {
+		return 0
+	}
+	return uint64(n)
+}
+
+func mulSat(a, b uint64) uint64 {
+	if a != 0 && b > math.MaxUint64/a {
+		return math.MaxUint64
+	}
+	return a * b
+}
@@ -0,0 +1,220 @@
+package script
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"strings"
+	"time"
+
+	"github.com/dop251/goja"
+	"github.com/dop251/goja/ast"
+)
+
+var (
+	ErrSourceTooLarge = errors.New("script: source exceeds size limit")
+	ErrInvalidSource  = errors.New("script: source must be a single function body")
+	ErrTimeout        = errors.New("script: time limit exceeded")
+	ErrMemoryLimit    = errors.New("script: allocation limit exceeded")
+	ErrOutputTooLarge = errors.New("script: output exceeds size limit")
+)
+
+// scriptFunction names the function the source is parsed as the body of.
+const scriptFunction = "__aceryx_script"
+
+// heapPollInterval is how often a worker samples its heap against
+// MaxAllocBytes.
+const heapPollInterval = 2 * time.Millisecond
+
+// Limits bound what a single script run may use. Timeout covers CPU as well
+// as wall time since a run never waits on I/O. In a worker process (see
+// Runner.SetWorker) MaxAllocBytes bounds how far the heap may grow during
+// the run. Run in process, only the strings and arrays built through the
+// built-ins are charged; see allocGuard.
+type Limits struct {
+	Timeout        time.Duration
+	MaxSourceBytes int
+	MaxStackDepth  int
+	MaxAllocBytes  uint64
+	MaxOutputBytes int
+}
+
+func DefaultLimits() Limits {
+	return Limits{
+		Timeout:        time.Second,
+		MaxSourceBytes: 64 * 1024,
+		MaxStackDepth:  256,
+		MaxAllocBytes:  64 << 20,
+		MaxOutputBytes: 1 << 20,
+	}
+}
+
+// Runner runs JavaScript snippets in a fresh runtime per call. The runtime
+// has only the ECMAScript built-ins: no modules, timers, console, network or
+// filesystem access.
+type Runner struct {
+	limits    Limits
+	worker    []string
+	watchHeap bool
+}
+
+func NewRunner(limits Limits) *Runner {
+	defaults := DefaultLimits()
+	if limits.Timeout <= 0 {
+		limits.Timeout = defaults.Timeout
+	}
+	if limits.MaxSourceBytes <= 0 {
+		limits.MaxSourceBytes = defaults.MaxSourceBytes
+	}
+	if limits.MaxStackDepth <= 0 {
+		limits.MaxStackDepth = defaults.MaxStackDepth
+	}
+	if limits.MaxAllocBytes == 0 {
+		limits.MaxAllocBytes = defaults.MaxAllocBytes
+	}
+	if limits.MaxOutputBytes <= 0 {
+		limits.MaxOutputBytes = defaults.MaxOutputBytes
+	}
+	return &Runner{limits: limits}
+}
+
+func (r *Runner) Limits() Limits {
+	return r.limits
+}
+
+// SetWorker runs each script in a child process started with command, so
+// MaxAllocBytes can be enforced on the whole heap and a run that exhausts
+// memory only takes down the child. The command must call ServeWorker; its
+// environment holds only WorkerEnv.
+func (r *Runner) SetWorker(command ...string) {
+	r.worker = command
+}
+
+// Run executes source as the body of a function with input bound as
+// `input`, and returns the function's return value as JSON. timeout, when
+// positive and below the runner's limit, shortens the run.
+func (r *Runner) Run(source string, input json.RawMessage, timeout time.Duration) (json.RawMessage, error) {
+	if len(source) > r.limits.MaxSourceBytes {
+		return nil, ErrSourceTooLarge
+	}
+	if timeout <= 0 || timeout > r.limits.Timeout {
+		timeout = r.limits.Timeout
+	}
+	if len(strings.TrimSpace(string(input))) == 0 {
+		input = json.RawMessage(`null`)
+	}
+	if len(r.worker) > 0 {
+		return r.runWorker(source, input, timeout)
+	}
+	return r.run(source, input, timeout)
+}
+
+// compile parses source as the body of function(input). Source that closes
+// the function early to run code outside it parses as more than one
+// statement and is rejected.
+func compile(source string) (*goja.Program, error) {
+	prg, err := goja.Parse("script", "function "+scriptFunction+"(input) {\n"+source+"\n}")
+	if err != nil {
+		return nil, fmt.Errorf("script: compile: %w", err)
+	}
+	if len(prg.Body) != 1 {
+		return nil, ErrInvalidSource
+	}
+	if _, ok := prg.Body[0].(*ast.FunctionDeclaration); !ok {
+		return nil, ErrInvalidSource
+	}
+	program, err := goja.CompileAST(prg, false)
+	if err != nil {
+		return nil, fmt.Errorf("script: compile: %w", err)
+	}
+	return program, nil
+}
+
+func (r *Runner) run(source string, input json.RawMessage, timeout time.Duration) (json.RawMessage, error) {
+	program, err := compile(source)
+	if err != nil {
+		return nil, err
+	}
+
+	vm := goja.New()
+	vm.SetMaxCallStackSize(r.limits.MaxStackDepth)
+	guard := newAllocGuard(vm, r.limits.MaxAllocBytes)
+	done := make(chan struct{})
+	defer close(done)
+	go r.watch(vm, timeout, done)
+
+	if _, err := vm.RunProgram(program); err != nil {
+		return nil, fmt.Errorf("script: compile: %w", err)
+	}
+	call, ok := goja.AssertFunction(vm.Get(scriptFunction))
+	if !ok {
+		return nil, ErrInvalidSource
+	}
+	parse, ok := goja.AssertFunction(vm.Get("JSON").ToObject(vm).Get("parse"))
+	if !ok {
+		return nil, fmt.Errorf("script: bind input: JSON.parse is not a function")
+	}
+	inputValue, err := parse(goja.Undefined(), vm.ToValue(string(input)))
+	if err != nil {
+		return nil, fmt.Errorf("script: bind input: %w", err)
+	}
+
+	result, err := call(goja.Undefined(), inputValue)
+	if guard.exceeded {
+		return nil, ErrMemoryLimit
+	}
+	if err != nil {
+		var interrupted *goja.InterruptedError
+		if errors.As(err, &interrupted) {
+			if limitErr, ok := interrupted.Value().(error); ok {
+				return nil, limitErr
+			}
+		}
+		var stackOverflow *goja.StackOverflowError
+		if errors.As(err, &stackOverflow) {
+			return nil, fmt.Errorf("script: call stack limit of %d exceeded", r.limits.MaxStackDepth)
+		}
+		return nil, fmt.Errorf("script: %w", err)
+	}
+	var exported any
+	if result != nil && !goja.IsUndefined(result) {
+		exported = result.Export()
+	}
+	out, err := json.Marshal(exported)
+	if err != nil {
+		return nil, fmt.Errorf("script: result is not JSON serialisable: %w", err)
+	}
+	if len(out) > r.limits.MaxOutputBytes {
+		return nil, ErrOutputTooLarge
+	}
+	return out, nil
+}
+
+// watch interrupts vm when the run exceeds its time budget, or in a worker
+// when the heap grows by more than MaxAllocBytes.
+func (r *Runner) watch(vm *goja.Runtime, timeout time.Duration, done <-chan struct{}) {
+	deadline := time.NewTimer(timeout)
+	defer deadline.Stop()
+	var poll <-chan time.Time
+	var base uint64
+	if r.watchHeap {
+		base = heapObjectBytes()
+		ticker := time.NewTicker(heapPollInterval)
+		defer ticker.Stop()
+		poll = ticker.C
+	}
+	for {
+		select {
+		case <-done:
+			return
+		case <-deadline.C:
+			vm.Interrupt(ErrTimeout)
+			return
+		case <-poll:
+			if used := heapObjectBytes(); used > base && used-base > r.limits.MaxAllocBytes {
+				vm.Interrupt(ErrMemoryLimit)
+				return
+			}
+		}
+	}
+}
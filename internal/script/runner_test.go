@@ -0,0 +1,144 @@
+package script
+
+import (
+	"encoding/json"
+	"errors"
+	"os"
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestMain(m *testing.M) {
+	if os.Getenv(WorkerEnv) != "" {
+		if err := ServeWorker(os.Stdin, os.Stdout); err != nil {
+			os.Exit(1)
+		}
+		os.Exit(0)
+	}
+	os.Exit(m.Run())
+}
+
+func TestRunBindsInputAndReturnsJSON(t *testing.T) {
+	runner := NewRunner(DefaultLimits())
+	out, err := runner.Run(`
+const total = input.items.reduce((sum, item) => sum + item.amount, 0);
+return { total, large: total > 100 };
+`, json.RawMessage(`{"items":[{"amount":60},{"amount":70}]}`), 0)
+	if err != nil {
+		t.Fatalf("run: %v", err)
+	}
+	if string(out) != `{"large":true,"total":130}` {
+		t.Fatalf("unexpected output %s", out)
+	}
+
+	out, err = runner.Run(`input.seen = true;`, json.RawMessage(`{}`), 0)
+	if err != nil || string(out) != "null" {
+		t.Fatalf("expected a script without return to yield null, got %s, %v", out, err)
+	}
+}
+
+func TestRunHasNoAmbientIO(t *testing.T) {
+	runner := NewRunner(DefaultLimits())
+	for _, name := range []string{"require", "console", "fetch", "setTimeout", "process"} {
+		out, err := runner.Run(`return typeof `+name+`;`, nil, 0)
+		if err != nil || string(out) != `"undefined"` {
+			t.Fatalf("expected %s to be undefined, got %s, %v", name, out, err)
+		}
+	}
+}
+
+func TestRunEnforcesLimits(t *testing.T) {
+	runner := NewRunner(Limits{Timeout: 50 * time.Millisecond, MaxSourceBytes: 64, MaxStackDepth: 32, MaxOutputBytes: 16})
+
+	if _, err := runner.Run(`while (true) {}`, nil, 0); !errors.Is(err, ErrTimeout) {
+		t.Fatalf("expected timeout, got %v", err)
+	}
+	if _, err := runner.Run(strings.Repeat(" ", 65), nil, 0); !errors.Is(err, ErrSourceTooLarge) {
+		t.Fatalf("expected source limit, got %v", err)
+	}
+	if _, err := runner.Run(`const f = n => f(n + 1); f(0);`, nil, 0); err == nil || !strings.Contains(err.Error(), "call stack") {
+		t.Fatalf("expected stack limit, got %v", err)
+	}
+	if _, err := runner.Run(`return "x".repeat(32);`, nil, 0); !errors.Is(err, ErrOutputTooLarge) {
+		t.Fatalf("expected output limit, got %v", err)
+	}
+}
+
+func TestRunEnforcesAllocationLimit(t *testing.T) {
+	runner := NewRunner(Limits{Timeout: 5 * time.Second, MaxAllocBytes: 8 << 20})
+	_, err := runner.Run(`const parts = []; while (true) { parts.push("x".repeat(1024)); }`, nil, 0)
+	if !errors.Is(err, ErrMemoryLimit) {
+		t.Fatalf("expected allocation limit, got %v", err)
+	}
+}
+
+func TestRunChargesBuildersBeforeTheyAllocate(t *testing.T) {
+	runner := NewRunner(Limits{Timeout: 5 * time.Second, MaxAllocBytes: 1 << 20})
+	for _, source := range []string{
+		`return "x".repeat(1 << 30).length;`,
+		`return Array(1 << 24).fill(0).length;`,
+		`try { "x".padEnd(1 << 30); } catch (e) {} while (true) {}`,
+	} {
+		if _, err := runner.Run(source, nil, 0); !errors.Is(err, ErrMemoryLimit) {
+			t.Fatalf("%s: expected allocation limit, got %v", source, err)
+		}
+	}
+	out, err := runner.Run(`return "ab".repeat(4) + [1, 2].concat([3]).join("-");`, nil, 0)
+	if err != nil || string(out) != `"abababab1-2-3"` {
+		t.Fatalf("expected a run within budget to succeed, got %s, %v", out, err)
+	}
+}
+
+func TestRunAllocationLimitIgnoresOtherGoroutines(t *testing.T) {
+	runner := NewRunner(Limits{Timeout: 5 * time.Second, MaxAllocBytes: 1 << 20})
+	stop := make(chan struct{})
+	defer close(stop)
+	go func() {
+		var sink [][]byte
+		for {
+			select {
+			case <-stop:
+				return
+			default:
+				sink = append(sink[:0], make([]byte, 1<<20))
+			}
+		}
+	}()
+	out, err := runner.Run(`let total = 0; for (let i = 0; i < 200000; i++) { total += i; } return total;`, nil, 0)
+	if err != nil || string(out) != "19999900000" {
+		t.Fatalf("expected allocations outside the run not to count, got %s, %v", out, err)
+	}
+}
+
+func TestRunRejectsSourceThatEscapesTheFunction(t *testing.T) {
+	runner := NewRunner(Limits{Timeout: 50 * time.Millisecond})
+	for _, source := range []string{
+		`}); while (true) {}; (function() {`,
+		"}\nwhile (true) {}\nfunction again(input) {",
+	} {
+		if _, err := runner.Run(source, nil, 0); !errors.Is(err, ErrInvalidSource) && (err == nil || !strings.Contains(err.Error(), "compile")) {
+			t.Fatalf("%q: expected the source to be rejected, got %v", source, err)
+		}
+	}
+}
+
+func TestWorkerEnforcesHeapLimit(t *testing.T) {
+	runner := NewRunner(Limits{Timeout: 10 * time.Second, MaxAllocBytes: 16 << 20})
+	runner.SetWorker(os.Args[0])
+	for _, source := range []string{
+		`let s = "x"; while (true) { s += s; }`,
+		`const rows = []; for (let i = 0; ; i++) { rows[i] = { i, label: "row " + i }; }`,
+	} {
+		if _, err := runner.Run(source, nil, 0); !errors.Is(err, ErrMemoryLimit) {
+			t.Fatalf("%s: expected the memory limit, got %v", source, err)
+		}
+	}
+	out, err := runner.Run(`return input.n * 2;`, json.RawMessage(`{"n":21}`), 0)
+	if err != nil || string(out) != "42" {
+		t.Fatalf("expected a run within budget to succeed, got %s, %v", out, err)
+	}
+	if _, err := runner.Run(`while (true) {}`, nil, 50*time.Millisecond); !errors.Is(err, ErrTimeout) {
+		t.Fatalf("expected the worker to time out, got %v", err)
+	}
+}
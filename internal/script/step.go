@@ -0,0 +1,128 @@
+package script
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"fmt"
+	"strings"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/engine"
+	"github.com/neural-chilli/aceryx/internal/observability"
+)
+
+type StepExecutor struct {
+	db     *sql.DB
+	runner *Runner
+}
+
+// StepConfig configures a script step. Input is resolved against the case
+// like other step configs and bound as `input`; without it the script gets
+// the case data.
+type StepConfig struct {
+	Source     string `json:"source"`
+	Input      any    `json:"input"`
+	OutputPath string `json:"output_path"`
+	TimeoutMS  int    `json:"timeout_ms"`
+}
+
+func NewStepExecutor(db *sql.DB, runner *Runner) *StepExecutor {
+	return &StepExecutor{db: db, runner: runner}
+}
+
+func (e *StepExecutor) Execute(ctx context.Context, caseID uuid.UUID, _ string, raw json.RawMessage) (*engine.StepResult, error) {
+	if e == nil || e.runner == nil {
+		return nil, fmt.Errorf("script step executor not configured")
+	}
+	cfg := StepConfig{}
+	if err := json.Unmarshal(raw, &cfg); err != nil {
+		return nil, fmt.Errorf("parse script step config: %w", err)
+	}
+	if strings.TrimSpace(cfg.Source) == "" {
+		return nil, fmt.Errorf("source is required")
+	}
+	tplCtx, caseData, err := e.loadCaseContext(ctx, caseID)
+	if err != nil {
+		return nil, err
+	}
+	engine.AddForEachItem(ctx, tplCtx)
+	input := any(caseData)
+	if cfg.Input != nil {
+		input = connectors.ResolveTemplateAny(cfg.Input, tplCtx)
+	}
+	inputRaw, err := json.Marshal(input)
+	if err != nil {
+		return nil, fmt.Errorf("marshal script input: %w", err)
+	}
+	_, endSpan := observability.StartToolSpan(ctx, "script", "script")
+	output, err := e.runner.Run(cfg.Source, inputRaw, time.Duration(cfg.TimeoutMS)*time.Millisecond)
+	endSpan(err)
+	if err != nil {
+		return nil, err
+	}
+	patch, err := buildCasePatch(cfg.OutputPath, output)
+	if err != nil {
+		return nil, err
+	}
+	return &engine.StepResult{
+		Outcome:        "ok",
+		Output:         output,
+		WritesCaseData: len(patch) > 0,
+		CaseDataPatch:  patch,
+		AuditEventType: "script.executed",
+	}, nil
+}
+
+func (e *StepExecutor) loadCaseContext(ctx context.Context, caseID uuid.UUID) (map[string]any, map[string]any, error) {
+	if e.db == nil {
+		return nil, nil, fmt.Errorf("script step executor db not configured")
+	}
+	var caseData []byte
+	if err := e.db.QueryRowContext(ctx, `SELECT data FROM cases WHERE id = $1`, caseID).Scan(&caseData); err != nil {
+		return nil, nil, fmt.Errorf("load case context for script step: %w", err)
+	}
+	caseMap := map[string]any{}
+	if len(caseData) > 0 {
+		if err := json.Unmarshal(caseData, &caseMap); err != nil {
+			return nil, nil, fmt.Errorf("decode case data for script step: %w", err)
+		}
+	}
+	return map[string]any{
+		"case": map[string]any{"data": caseMap},
+		"now":  time.Now().UTC().Format(time.RFC3339),
+	}, caseMap, nil
+}
+
+func buildCasePatch(outputPath string, output json.RawMessage) (json.RawMessage, error) {
+	path := strings.TrimSpace(outputPath)
+	path = strings.TrimPrefix(path, "case.data.")
+	path = strings.TrimPrefix(path, "data.")
+	path = strings.Trim(path, ".")
+	if path == "" {
+		return nil, nil
+	}
+	var value any
+	if err := json.Unmarshal(output, &value); err != nil {
+		return nil, fmt.Errorf("decode script output: %w", err)
+	}
+	parts := strings.Split(path, ".")
+	root := map[string]any{}
+	cur := root
+	for i, part := range parts {
+		if i == len(parts)-1 {
+			cur[part] = value
+			break
+		}
+		next := map[string]any{}
+		cur[part] = next
+		cur = next
+	}
+	raw, err := json.Marshal(root)
+	if err != nil {
+		return nil, fmt.Errorf("marshal script case patch: %w", err)
+	}
+	return raw, nil
+}
@@ -0,0 +1,115 @@
+package script
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"math"
+	"os/exec"
+	"runtime/debug"
+	"runtime/metrics"
+	"strings"
+	"time"
+)
+
+// WorkerEnv is set in the environment of worker processes, so a binary
+// that is not the aceryx command (a test binary) can recognise one.
+const WorkerEnv = "ACERYX_SCRIPT_WORKER"
+
+// workerGrace is how long a worker may take beyond the run's timeout to
+// start and report before it is killed.
+const workerGrace = 2 * time.Second
+
+type workerRequest struct {
+	Source  string          `json:"source"`
+	Input   json.RawMessage `json:"input"`
+	Timeout time.Duration   `json:"timeout"`
+	Limits  Limits          `json:"limits"`
+}
+
+type workerResponse struct {
+	Output json.RawMessage `json:"output,omitempty"`
+	Error  string          `json:"error,omitempty"`
+}
+
+// limitErrors are the errors a worker reports by message and the parent
+// returns as themselves, so callers can match them with errors.Is.
+var limitErrors = []error{ErrSourceTooLarge, ErrInvalidSource, ErrTimeout, ErrMemoryLimit, ErrOutputTooLarge}
+
+// ServeWorker runs the one script read from in and writes its outcome to
+// out. The process's heap is watched against MaxAllocBytes and, where the
+// platform allows, its address space is capped, so a run that allocates
+// without going through the built-ins is still stopped.
+func ServeWorker(in io.Reader, out io.Writer) error {
+	var req workerRequest
+	if err := json.NewDecoder(in).Decode(&req); err != nil {
+		return fmt.Errorf("script worker: decode request: %w", err)
+	}
+	r := NewRunner(req.Limits)
+	r.watchHeap = true
+	limit := r.limits.MaxAllocBytes
+	if base := heapObjectBytes(); limit < math.MaxInt64-base {
+		// Collect garbage before it counts as growth.
+		debug.SetMemoryLimit(int64(base + limit))
+	}
+	limitAddressSpace(limit)
+
+	output, err := r.run(req.Source, req.Input, req.Timeout)
+	resp := workerResponse{Output: output}
+	if err != nil {
+		resp.Error = err.Error()
+	}
+	return json.NewEncoder(out).Encode(resp)
+}
+
+func (r *Runner) runWorker(source string, input json.RawMessage, timeout time.Duration) (json.RawMessage, error) {
+	req, err := json.Marshal(workerRequest{Source: source, Input: input, Timeout: timeout, Limits: r.limits})
+	if err != nil {
+		return nil, fmt.Errorf("script: encode worker request: %w", err)
+	}
+	ctx, cancel := context.WithTimeout(context.Background(), timeout+workerGrace)
+	defer cancel()
+	cmd := exec.CommandContext(ctx, r.worker[0], r.worker[1:]...)
+	cmd.Env = []string{WorkerEnv + "=1"}
+	cmd.Stdin = bytes.NewReader(req)
+	var stdout, stderr bytes.Buffer
+	cmd.Stdout = &stdout
+	cmd.Stderr = &stderr
+	runErr := cmd.Run()
+	if ctx.Err() != nil {
+		return nil, ErrTimeout
+	}
+
+	var resp workerResponse
+	if err := json.Unmarshal(stdout.Bytes(), &resp); err != nil {
+		if strings.Contains(stderr.String(), "out of memory") {
+			return nil, ErrMemoryLimit
+		}
+		if runErr != nil {
+			return nil, fmt.Errorf("script: worker: %w", runErr)
+		}
+		return nil, fmt.Errorf("script: decode worker response: %w", err)
+	}
+	if resp.Error != "" {
+		for _, limitErr := range limitErrors {
+			if resp.Error == limitErr.Error() {
+				return nil, limitErr
+			}
+		}
+		return nil, errors.New(resp.Error)
+	}
+	return resp.Output, nil
+}
+
+// heapObjectBytes returns the bytes of heap objects, live or not yet swept.
+func heapObjectBytes() uint64 {
+	sample := []metrics.Sample{{Name: "/memory/classes/heap/objects:bytes"}}
+	metrics.Read(sample)
+	if sample[0].Value.Kind() != metrics.KindUint64 {
+		return 0
+	}
+	return sample[0].Value.Uint64()
+}
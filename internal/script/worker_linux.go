@@ -0,0 +1,36 @@
+package script
+
+import (
+	"os"
+	"strconv"
+	"strings"
+	"syscall"
+)
+
+// addressSpaceSlack is the address space a worker may map beyond its
+// current size and twice its allocation budget, for the runtime's own use.
+const addressSpaceSlack = 512 << 20
+
+// limitAddressSpace caps the worker's address space, so an allocation the
+// heap watch cannot see in time fails in the worker instead of exhausting
+// the host. The Go runtime exits with "out of memory" when it hits the cap.
+func limitAddressSpace(limit uint64) {
+	statm, err := os.ReadFile("/proc/self/statm")
+	if err != nil {
+		return
+	}
+	fields := strings.Fields(string(statm))
+	if len(fields) == 0 {
+		return
+	}
+	pages, err := strconv.ParseUint(fields[0], 10, 64)
+	if err != nil {
+		return
+	}
+	size := pages * uint64(os.Getpagesize())
+	if limit > (1<<62-size-addressSpaceSlack)/2 {
+		return
+	}
+	capBytes := size + 2*limit + addressSpaceSlack
+	_ = syscall.Setrlimit(syscall.RLIMIT_AS, &syscall.Rlimit{Cur: capBytes, Max: capBytes})
+}
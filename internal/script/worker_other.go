@@ -0,0 +1,7 @@
+//go:build !linux
+
+package script
+
+// limitAddressSpace is a no-op where the address space cannot be capped;
+// the heap watch alone bounds the run.
+func limitAddressSpace(uint64) {}
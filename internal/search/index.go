@@ -0,0 +1,234 @@
+package search
+
+import (
+	"math"
+	"sort"
+	"strings"
+	"unicode"
+)
+
+// Field identifies where a term matched. Matches in the name outrank tags,
+// which outrank the description.
+type Field int
+
+const (
+	FieldName Field = iota
+	FieldTags
+	FieldDescription
+)
+
+var fieldBoosts = map[Field]float64{
+	FieldName:        3,
+	FieldTags:        2,
+	FieldDescription: 1,
+}
+
+const (
+	exactMatchScore  = 1.0
+	prefixMatchScore = 0.75
+	fuzzyMatchScore  = 0.5
+
+	// minPrefixLen and minFuzzyLen stop very short query terms matching
+	// most of the vocabulary.
+	minPrefixLen = 2
+	minFuzzyLen  = 4
+)
+
+// Document is one searchable record.
+type Document struct {
+	Kind        string   `json:"kind"`
+	ID          string   `json:"id"`
+	Name        string   `json:"name"`
+	Tags        []string `json:"tags,omitempty"`
+	Description string   `json:"description,omitempty"`
+}
+
+// Hit is a matching document with its relevance score.
+type Hit struct {
+	Document
+	Score float64 `json:"score"`
+}
+
+type posting struct {
+	doc   int
+	field Field
+}
+
+// Index is an immutable inverted index over a set of documents.
+type Index struct {
+	docs     []Document
+	postings map[string][]posting
+	vocab    []string
+}
+
+func NewIndex(docs []Document) *Index {
+	idx := &Index{docs: docs, postings: map[string][]posting{}}
+	for i, doc := range docs {
+		idx.add(i, FieldName, doc.Name)
+		for _, tag := range doc.Tags {
+			idx.add(i, FieldTags, tag)
+		}
+		idx.add(i, FieldDescription, doc.Description)
+	}
+	idx.vocab = make([]string, 0, len(idx.postings))
+	for term := range idx.postings {
+		idx.vocab = append(idx.vocab, term)
+	}
+	sort.Strings(idx.vocab)
+	return idx
+}
+
+func (idx *Index) add(doc int, field Field, text string) {
+	for _, term := range Tokenize(text) {
+		idx.postings[term] = append(idx.postings[term], posting{doc: doc, field: field})
+	}
+}
+
+// Len returns the number of indexed documents.
+func (idx *Index) Len() int { return len(idx.docs) }
+
+// Search returns documents matching every query term, best first. Each term
+// matches exactly, as a prefix of an indexed word, or within a small edit
+// distance. When kinds is non-empty only documents of those kinds are returned.
+func (idx *Index) Search(query string, kinds ...string) []Hit {
+	terms := Tokenize(query)
+	if len(terms) == 0 {
+		return []Hit{}
+	}
+	allowed := map[string]bool{}
+	for _, kind := range kinds {
+		allowed[kind] = true
+	}
+
+	scores := map[int]float64{}
+	for i, term := range terms {
+		termScores := idx.scoreTerm(term)
+		// Rarer terms say more about relevance.
+		weight := math.Log(1 + float64(len(idx.docs))/float64(max(len(termScores), 1)))
+		next := map[int]float64{}
+		for doc, s := range termScores {
+			if i > 0 {
+				if _, ok := scores[doc]; !ok {
+					continue
+				}
+			}
+			next[doc] = scores[doc] + s*weight
+		}
+		scores = next
+		if len(scores) == 0 {
+			return []Hit{}
+		}
+	}
+
+	needle := strings.ToLower(strings.TrimSpace(query))
+	hits := make([]Hit, 0, len(scores))
+	for doc, score := range scores {
+		d := idx.docs[doc]
+		if len(allowed) > 0 && !allowed[d.Kind] {
+			continue
+		}
+		name := strings.ToLower(d.Name)
+		switch {
+		case name == needle:
+			score *= 2
+		case strings.HasPrefix(name, needle):
+			score *= 1.5
+		}
+		hits = append(hits, Hit{Document: d, Score: math.Round(score*1000) / 1000})
+	}
+	sort.Slice(hits, func(i, j int) bool {
+		if hits[i].Score != hits[j].Score {
+			return hits[i].Score > hits[j].Score
+		}
+		if hits[i].Name != hits[j].Name {
+			return hits[i].Name < hits[j].Name
+		}
+		return hits[i].ID < hits[j].ID
+	})
+	return hits
+}
+
+// scoreTerm returns, per document, the best boosted match for one term.
+func (idx *Index) scoreTerm(term string) map[int]float64 {
+	out := map[int]float64{}
+	apply := func(indexed string, quality float64) {
+		for _, p := range idx.postings[indexed] {
+			if s := quality * fieldBoosts[p.field]; s > out[p.doc] {
+				out[p.doc] = s
+			}
+		}
+	}
+	apply(term, exactMatchScore)
+	if len([]rune(term)) >= minPrefixLen {
+		start := sort.SearchStrings(idx.vocab, term)
+		for _, indexed := range idx.vocab[start:] {
+			if !strings.HasPrefix(indexed, term) {
+				break
+			}
+			if indexed != term {
+				apply(indexed, prefixMatchScore)
+			}
+		}
+	}
+	if n := len([]rune(term)); n >= minFuzzyLen {
+		maxEdits := 1
+		if n >= 8 {
+			maxEdits = 2
+		}
+		for _, indexed := range idx.vocab {
+			if indexed == term || strings.HasPrefix(indexed, term) {
+				continue
+			}
+			if abs(len([]rune(indexed))-n) > maxEdits {
+				continue
+			}
+			if editDistance(term, indexed, maxEdits) <= maxEdits {
+				apply(indexed, fuzzyMatchScore)
+			}
+		}
+	}
+	return out
+}
+
+// Tokenize lowercases text and splits it into words on anything that is not a
+// letter or digit, so "credit_check" and "Credit Check" index the same way.
+func Tokenize(text string) []string {
+	return strings.FieldsFunc(strings.ToLower(text), func(r rune) bool {
+		return !unicode.IsLetter(r) && !unicode.IsDigit(r)
+	})
+}
+
+// editDistance is the Levenshtein distance between a and b, stopping early
+// once every candidate exceeds limit.
+func editDistance(a, b string, limit int) int {
+	ra, rb := []rune(a), []rune(b)
+	prev := make([]int, len(rb)+1)
+	curr := make([]int, len(rb)+1)
+	for j := range prev {
+		prev[j] = j
+	}
+	for i := 1; i <= len(ra); i++ {
+		curr[0] = i
+		rowMin := curr[0]
+		for j := 1; j <= len(rb); j++ {
+			cost := 1
+			if ra[i-1] == rb[j-1] {
+				cost = 0
+			}
+			curr[j] = min(prev[j]+1, curr[j-1]+1, prev[j-1]+cost)
+			rowMin = min(rowMin, curr[j])
+		}
+		if rowMin > limit {
+			return limit + 1
+		}
+		prev, curr = curr, prev
+	}
+	return prev[len(rb)]
+}
+
+func abs(n int) int {
+	if n < 0 {
+		return -n
+	}
+	return n
+}
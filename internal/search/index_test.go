@@ -0,0 +1,90 @@
+package search
+
+import (
+	"context"
+	"errors"
+	"testing"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+func testDocs() []Document {
+	return []Document{
+		{Kind: KindWorkflow, ID: "w1", Name: "Loan Origination", Tags: []string{"loan_application"}, Description: "credit_check agent review human_task"},
+		{Kind: KindConnector, ID: "slack", Name: "Slack", Tags: []string{"slack", "send_message"}, Description: "Post messages to channels"},
+		{Kind: KindConnector, ID: "http", Name: "HTTP", Tags: []string{"http", "request"}, Description: "Call any REST API, for example a loan servicing system"},
+		{Kind: KindPlugin, ID: "companies-house", Name: "Companies House", Tags: []string{"Financial Services"}, Description: "Company search"},
+	}
+}
+
+func TestIndexFieldBoostsRankNameFirst(t *testing.T) {
+	hits := NewIndex(testDocs()).Search("loan")
+	if len(hits) != 2 || hits[0].ID != "w1" || hits[1].ID != "http" {
+		t.Fatalf("expected name match before description match, got %+v", hits)
+	}
+	if hits[0].Score <= hits[1].Score {
+		t.Fatalf("expected higher score for name match: %+v", hits)
+	}
+}
+
+func TestIndexPrefixAndFuzzyMatching(t *testing.T) {
+	idx := NewIndex(testDocs())
+	if hits := idx.Search("orig"); len(hits) != 1 || hits[0].ID != "w1" {
+		t.Fatalf("prefix: got %+v", hits)
+	}
+	if hits := idx.Search("originaton"); len(hits) != 1 || hits[0].ID != "w1" {
+		t.Fatalf("fuzzy: got %+v", hits)
+	}
+	if hits := idx.Search("sl"); len(hits) != 1 || hits[0].ID != "slack" {
+		t.Fatalf("short prefix: got %+v", hits)
+	}
+	if hits := idx.Search("x"); len(hits) != 0 {
+		t.Fatalf("single unmatched letter should not match: %+v", hits)
+	}
+}
+
+func TestIndexRequiresEveryTermAndFiltersKinds(t *testing.T) {
+	idx := NewIndex(testDocs())
+	if hits := idx.Search("loan credit"); len(hits) != 1 || hits[0].ID != "w1" {
+		t.Fatalf("expected only documents matching both terms, got %+v", hits)
+	}
+	if hits := idx.Search("loan", KindConnector); len(hits) != 1 || hits[0].ID != "http" {
+		t.Fatalf("kind filter: got %+v", hits)
+	}
+	if hits := idx.Search("   "); len(hits) != 0 {
+		t.Fatalf("blank query: got %+v", hits)
+	}
+}
+
+func TestServiceCachesIndexAndSkipsFailingSources(t *testing.T) {
+	svc := NewService()
+	now := time.Date(2026, 10, 18, 9, 0, 0, 0, time.UTC)
+	svc.now = func() time.Time { return now }
+	loads := 0
+	svc.Register(KindWorkflow, func(context.Context, uuid.UUID) ([]Document, error) {
+		loads++
+		return []Document{{ID: "w1", Name: "Claims Intake"}}, nil
+	})
+	svc.Register(KindMCPTool, func(context.Context, uuid.UUID) ([]Document, error) {
+		return nil, errors.New("cache unavailable")
+	})
+
+	tenant := uuid.New()
+	for range 2 {
+		hits, err := svc.Search(context.Background(), tenant, "claims")
+		if err != nil || len(hits) != 1 || hits[0].Kind != KindWorkflow {
+			t.Fatalf("search: %+v, %v", hits, err)
+		}
+	}
+	if loads != 1 {
+		t.Fatalf("expected one source load within the TTL, got %d", loads)
+	}
+	now = now.Add(defaultIndexTTL + time.Second)
+	_, _ = svc.Search(context.Background(), tenant, "claims")
+	svc.Invalidate(tenant)
+	_, _ = svc.Search(context.Background(), tenant, "claims")
+	if loads != 3 {
+		t.Fatalf("expected reloads after expiry and invalidation, got %d", loads)
+	}
+}
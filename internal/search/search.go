@@ -1,3 +1,4 @@
-// Package search implements full-text search across case data
-// using Postgres tsvector/tsquery.
+// Package search ranks workflows and tools by relevance. Each tenant gets an
+// in-process inverted index built from registered sources; case data search
+// uses Postgres tsvector/tsquery in the cases package instead.
 package search
@@ -0,0 +1,185 @@
+package search
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"fmt"
+	"log/slog"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+const (
+	KindWorkflow  = "workflow"
+	KindConnector = "connector"
+	KindPlugin    = "plugin"
+	KindMCPTool   = "mcp_tool"
+
+	defaultIndexTTL = 30 * time.Second
+)
+
+// Source lists the documents of one kind visible to a tenant.
+type Source func(ctx context.Context, tenantID uuid.UUID) ([]Document, error)
+
+type cachedIndex struct {
+	index     *Index
+	expiresAt time.Time
+}
+
+// Service builds a per-tenant index from its registered sources and keeps it
+// for a short TTL, so repeated queries do not reload every record.
+type Service struct {
+	kinds   []string
+	sources map[string]Source
+	ttl     time.Duration
+	now     func() time.Time
+
+	mu    sync.Mutex
+	cache map[uuid.UUID]cachedIndex
+}
+
+func NewService() *Service {
+	return &Service{
+		sources: map[string]Source{},
+		ttl:     defaultIndexTTL,
+		now:     time.Now,
+		cache:   map[uuid.UUID]cachedIndex{},
+	}
+}
+
+// Register adds a source for kind. Register before serving queries.
+func (s *Service) Register(kind string, source Source) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if _, ok := s.sources[kind]; !ok {
+		s.kinds = append(s.kinds, kind)
+	}
+	s.sources[kind] = source
+	s.cache = map[uuid.UUID]cachedIndex{}
+}
+
+// Kinds returns the registered document kinds in registration order.
+func (s *Service) Kinds() []string {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	return append([]string(nil), s.kinds...)
+}
+
+// Invalidate drops a tenant's cached index so the next query rebuilds it.
+func (s *Service) Invalidate(tenantID uuid.UUID) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	delete(s.cache, tenantID)
+}
+
+func (s *Service) Search(ctx context.Context, tenantID uuid.UUID, query string, kinds ...string) ([]Hit, error) {
+	idx, err := s.index(ctx, tenantID)
+	if err != nil {
+		return nil, err
+	}
+	return idx.Search(query, kinds...), nil
+}
+
+func (s *Service) index(ctx context.Context, tenantID uuid.UUID) (*Index, error) {
+	s.mu.Lock()
+	if cached, ok := s.cache[tenantID]; ok && s.now().Before(cached.expiresAt) {
+		s.mu.Unlock()
+		return cached.index, nil
+	}
+	kinds := append([]string(nil), s.kinds...)
+	sources := make([]Source, len(kinds))
+	for i, kind := range kinds {
+		sources[i] = s.sources[kind]
+	}
+	s.mu.Unlock()
+
+	var docs []Document
+	for i, source := range sources {
+		items, err := source(ctx, tenantID)
+		if err != nil {
+			if ctx.Err() != nil {
+				return nil, ctx.Err()
+			}
+			// One unavailable source should not take search down with it.
+			slog.WarnContext(ctx, "search source unavailable", "kind", kinds[i], "tenant_id", tenantID.String(), "error", err)
+			continue
+		}
+		for _, item := range items {
+			item.Kind = kinds[i]
+			docs = append(docs, item)
+		}
+	}
+	idx := NewIndex(docs)
+
+	s.mu.Lock()
+	s.cache[tenantID] = cachedIndex{index: idx, expiresAt: s.now().Add(s.ttl)}
+	s.mu.Unlock()
+	return idx, nil
+}
+
+// WorkflowSource indexes a tenant's workflows by name, with the case type and
+// workflow tags as tags and the latest version's step ids and types as the
+// description.
+func WorkflowSource(db *sql.DB) Source {
+	return func(ctx context.Context, tenantID uuid.UUID) ([]Document, error) {
+		rows, err := db.QueryContext(ctx, `
+SELECT w.id, w.name, w.case_type, COALESCE(v.ast::text, '{}'),
+       (SELECT COALESCE(json_agg(t.name), '[]'::json)::text
+        FROM workflow_tags wt JOIN tags t ON t.id = wt.tag_id
+        WHERE wt.workflow_id = w.id)
+FROM workflows w
+LEFT JOIN LATERAL (
+    SELECT ast FROM workflow_versions WHERE workflow_id = w.id ORDER BY version DESC LIMIT 1
+) v ON true
+WHERE w.tenant_id = $1
+`, tenantID)
+		if err != nil {
+			return nil, fmt.Errorf("query workflows: %w", err)
+		}
+		defer func() { _ = rows.Close() }()
+
+		var out []Document
+		for rows.Next() {
+			var (
+				id       uuid.UUID
+				doc      Document
+				caseType string
+				rawAST   string
+				rawTags  string
+			)
+			if err := rows.Scan(&id, &doc.Name, &caseType, &rawAST, &rawTags); err != nil {
+				return nil, fmt.Errorf("scan workflow: %w", err)
+			}
+			doc.ID = id.String()
+			doc.Tags = []string{caseType}
+			var tags []string
+			if json.Unmarshal([]byte(rawTags), &tags) == nil {
+				doc.Tags = append(doc.Tags, tags...)
+			}
+			doc.Description = stepSummary(rawAST)
+			out = append(out, doc)
+		}
+		return out, rows.Err()
+	}
+}
+
+func stepSummary(rawAST string) string {
+	var ast struct {
+		Steps []struct {
+			ID   string `json:"id"`
+			Type string `json:"type"`
+		} `json:"steps"`
+	}
+	if err := json.Unmarshal([]byte(rawAST), &ast); err != nil {
+		return ""
+	}
+	parts := make([]string, 0, 2*len(ast.Steps))
+	for _, step := range ast.Steps {
+		parts = append(parts, step.ID, step.Type)
+	}
+	return strings.Join(parts, " ")
+}
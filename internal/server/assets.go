@@ -0,0 +1,84 @@
+package server
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"html/template"
+	"io/fs"
+	"strings"
+)
+
+// assetManifestPath is where the frontend build writes its asset manifest.
+const assetManifestPath = ".vite/manifest.json"
+
+const (
+	immutableCacheControl  = "public, max-age=31536000, immutable"
+	revalidateCacheControl = "no-cache"
+)
+
+// Assets maps frontend source files to the fingerprinted files the build
+// emitted for them, so pages always reference the current version. A build
+// without a manifest maps every name to itself.
+type Assets struct {
+	urls        map[string]string
+	fingerprint map[string]bool
+}
+
+type manifestChunk struct {
+	File   string   `json:"file"`
+	CSS    []string `json:"css"`
+	Assets []string `json:"assets"`
+}
+
+// LoadAssets reads the asset manifest from the frontend bundle.
+func LoadAssets(uiFS fs.FS) (*Assets, error) {
+	assets := &Assets{urls: map[string]string{}, fingerprint: map[string]bool{}}
+	raw, err := fs.ReadFile(uiFS, assetManifestPath)
+	if errors.Is(err, fs.ErrNotExist) {
+		return assets, nil
+	}
+	if err != nil {
+		return assets, fmt.Errorf("read asset manifest: %w", err)
+	}
+	var chunks map[string]manifestChunk
+	if err := json.Unmarshal(raw, &chunks); err != nil {
+		return assets, fmt.Errorf("parse asset manifest: %w", err)
+	}
+	for name, chunk := range chunks {
+		if chunk.File == "" {
+			continue
+		}
+		assets.urls[name] = "/" + chunk.File
+		assets.fingerprint[chunk.File] = true
+		for _, file := range append(chunk.CSS, chunk.Assets...) {
+			assets.fingerprint[file] = true
+		}
+	}
+	return assets, nil
+}
+
+// URL returns the URL of the file built from name, e.g. "src/main.ts".
+// Names the manifest does not list are served as they are.
+func (a *Assets) URL(name string) string {
+	name = strings.TrimPrefix(name, "/")
+	if url, ok := a.urls[name]; ok {
+		return url
+	}
+	return "/" + name
+}
+
+// FuncMap provides asset_url to server-rendered templates.
+func (a *Assets) FuncMap() template.FuncMap {
+	return template.FuncMap{"asset_url": a.URL}
+}
+
+// cacheControl is the Cache-Control header for a file in the bundle.
+// Fingerprinted files never change, so they are cached for good; anything
+// else is revalidated so a deploy is picked up straight away.
+func (a *Assets) cacheControl(file string) string {
+	if a.fingerprint[file] {
+		return immutableCacheControl
+	}
+	return revalidateCacheControl
+}
@@ -5,11 +5,13 @@ import (
 	"database/sql"
 	"encoding/json"
 	"io/fs"
+	"log/slog"
 	"net/http"
 	"path"
 	"strings"
 
 	"github.com/neural-chilli/aceryx/api"
+	"github.com/neural-chilli/aceryx/api/middleware"
 	"github.com/neural-chilli/aceryx/internal/engine"
 )
 
@@ -34,7 +36,18 @@ func NewHandler(db *sql.DB, eng *engine.Engine, uiFS fs.FS) http.Handler {
 }
 
 func NewHandlerWithContext(ctx context.Context, db *sql.DB, eng *engine.Engine, uiFS fs.FS) http.Handler {
-	apiHandler := api.NewRouterWithServicesContext(ctx, db, eng)
+	return NewHandlerWithOptions(ctx, db, eng, uiFS, Options{})
+}
+
+// Options carries what aceryx serve resolves before the handler is built.
+// The zero value leaves CORS off.
+type Options struct {
+	Router api.RouterOptions
+	CORS   middleware.CORSConfig
+}
+
+func NewHandlerWithOptions(ctx context.Context, db *sql.DB, eng *engine.Engine, uiFS fs.FS, opts Options) http.Handler {
+	apiHandler := api.NewRouterWithOptions(ctx, db, eng, opts.Router)
 	spa := newSPAHandler(uiFS)
 
 	mux := http.NewServeMux()
@@ -51,7 +64,8 @@ func NewHandlerWithContext(ctx context.Context, db *sql.DB, eng *engine.Engine,
 	mux.HandleFunc("GET /manifest.json", manifestHandler(db))
 	mux.Handle("/", spa)
 
-	return mux
+	handler := middleware.CompressionMiddleware(middleware.CompressionConfigFromEnv())(mux)
+	return middleware.CORSMiddleware(opts.CORS)(handler)
 }
 
 func forwardTo(handler http.Handler, targetPath string) http.HandlerFunc {
@@ -105,6 +119,10 @@ WHERE slug = $1
 }
 
 func newSPAHandler(uiFS fs.FS) http.Handler {
+	assets, err := LoadAssets(uiFS)
+	if err != nil {
+		slog.Warn("serving frontend without asset manifest", "error", err)
+	}
 	fileServer := http.FileServer(http.FS(uiFS))
 	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
 		if r.Method != http.MethodGet && r.Method != http.MethodHead {
@@ -118,12 +136,17 @@ func newSPAHandler(uiFS fs.FS) http.Handler {
 			requested = "index.html"
 		}
 
-		if f, err := uiFS.Open(requested); err == nil {
-			_ = f.Close()
-			fileServer.ServeHTTP(w, r)
-			return
+		// Build metadata such as the asset manifest is not served.
+		if !strings.HasPrefix(requested, ".") {
+			if f, err := uiFS.Open(requested); err == nil {
+				_ = f.Close()
+				w.Header().Set("Cache-Control", assets.cacheControl(requested))
+				fileServer.ServeHTTP(w, r)
+				return
+			}
 		}
 
+		w.Header().Set("Cache-Control", revalidateCacheControl)
 		clone := r.Clone(r.Context())
 		clone.URL.Path = "/"
 		fileServer.ServeHTTP(w, clone)
@@ -45,3 +45,51 @@ func TestSPAHandlerServesStaticAndFallsBackToIndex(t *testing.T) {
 		}
 	})
 }
+
+func TestSPAHandlerFingerprintedAssets(t *testing.T) {
+	ui := fstest.MapFS{
+		"index.html":             {Data: []byte("<html><body>app</body></html>")},
+		"assets/index-4f2a9c.js": {Data: []byte("console.log('ok')")},
+		"favicon.svg":            {Data: []byte("<svg></svg>")},
+		".vite/manifest.json": {Data: []byte(`{
+  "index.html": {"file": "assets/index-4f2a9c.js", "isEntry": true, "css": ["assets/index-7d1e0b.css"]}
+}`)},
+	}
+	assets, err := LoadAssets(ui)
+	if err != nil {
+		t.Fatalf("load assets: %v", err)
+	}
+	if got := assets.URL("index.html"); got != "/assets/index-4f2a9c.js" {
+		t.Fatalf("unexpected asset url %q", got)
+	}
+	if got := assets.URL("/favicon.svg"); got != "/favicon.svg" {
+		t.Fatalf("unexpected url for unlisted file %q", got)
+	}
+
+	h := newSPAHandler(ui)
+	tests := []struct {
+		path  string
+		cache string
+	}{
+		{"/assets/index-4f2a9c.js", immutableCacheControl},
+		{"/favicon.svg", revalidateCacheControl},
+		{"/", revalidateCacheControl},
+		{"/cases/123", revalidateCacheControl},
+	}
+	for _, tt := range tests {
+		rr := httptest.NewRecorder()
+		h.ServeHTTP(rr, httptest.NewRequest(http.MethodGet, tt.path, nil))
+		if rr.Code != http.StatusOK {
+			t.Fatalf("%s: expected 200, got %d", tt.path, rr.Code)
+		}
+		if got := rr.Header().Get("Cache-Control"); got != tt.cache {
+			t.Fatalf("%s: expected Cache-Control %q, got %q", tt.path, tt.cache, got)
+		}
+	}
+
+	rr := httptest.NewRecorder()
+	h.ServeHTTP(rr, httptest.NewRequest(http.MethodGet, "/.vite/manifest.json", nil))
+	if strings.Contains(rr.Body.String(), "isEntry") {
+		t.Fatal("asset manifest should not be served")
+	}
+}
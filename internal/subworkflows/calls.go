@@ -0,0 +1,149 @@
+package subworkflows
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"log/slog"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+// OnCaseSettled is an engine.SettledHook. When caseID was started by a
+// sub_workflow step, it completes or fails the waiting parent step; the
+// conditional update makes repeated notifications a no-op.
+func (s *StepExecutor) OnCaseSettled(ctx context.Context, caseID uuid.UUID, failed bool) {
+	if err := s.settle(ctx, caseID, failed); err != nil {
+		slog.ErrorContext(ctx, "resolve sub-workflow parent step failed", "child_case_id", caseID.String(), "error", err)
+	}
+}
+
+func (s *StepExecutor) settle(ctx context.Context, childID uuid.UUID, failed bool) error {
+	status := "completed"
+	if failed {
+		status = "failed"
+	}
+	var (
+		parentID   uuid.UUID
+		stepID     string
+		outputsRaw []byte
+	)
+	err := s.db.QueryRowContext(ctx, `
+UPDATE sub_workflow_calls
+SET status = $2, completed_at = now()
+WHERE child_case_id = $1 AND status = 'running'
+RETURNING parent_case_id, parent_step_id, outputs
+`, childID, status).Scan(&parentID, &stepID, &outputsRaw)
+	if errors.Is(err, sql.ErrNoRows) {
+		return nil
+	}
+	if err != nil {
+		return fmt.Errorf("mark sub-workflow call %s: %w", status, err)
+	}
+	if failed {
+		return s.engine.FailStep(ctx, parentID, stepID, fmt.Errorf("sub-workflow case %s failed or was cancelled", childID))
+	}
+
+	var outputs []string
+	if err := json.Unmarshal(outputsRaw, &outputs); err != nil {
+		return fmt.Errorf("decode sub-workflow outputs: %w", err)
+	}
+	var caseNumber string
+	var dataRaw []byte
+	if err := s.db.QueryRowContext(ctx, `SELECT case_number, data FROM cases WHERE id = $1`, childID).Scan(&caseNumber, &dataRaw); err != nil {
+		return fmt.Errorf("load sub-workflow case: %w", err)
+	}
+	data := map[string]any{}
+	if err := json.Unmarshal(dataRaw, &data); err != nil {
+		return fmt.Errorf("decode sub-workflow case data: %w", err)
+	}
+	result, err := s.childOutputs(ctx, childID, data)
+	if err != nil {
+		return err
+	}
+	payload, err := json.Marshal(map[string]any{
+		"case_id":     childID.String(),
+		"case_number": caseNumber,
+		"outputs":     selectOutputs(result, outputs),
+	})
+	if err != nil {
+		return fmt.Errorf("marshal sub-workflow result: %w", err)
+	}
+	return s.engine.CompleteStep(ctx, parentID, stepID, &engine.StepResult{Output: payload})
+}
+
+// childOutputs returns what the child workflow's outputs contract resolves
+// to, or its final case data when the workflow declares no outputs.
+func (s *StepExecutor) childOutputs(ctx context.Context, childID uuid.UUID, data map[string]any) (map[string]any, error) {
+	var astRaw []byte
+	err := s.db.QueryRowContext(ctx, `
+SELECT wv.ast
+FROM cases c
+JOIN workflow_versions wv ON wv.workflow_id = c.workflow_id AND wv.version = c.workflow_version
+WHERE c.id = $1
+`, childID).Scan(&astRaw)
+	if errors.Is(err, sql.ErrNoRows) {
+		return data, nil
+	}
+	if err != nil {
+		return nil, fmt.Errorf("load sub-workflow definition: %w", err)
+	}
+	var ast engine.WorkflowAST
+	if err := json.Unmarshal(astRaw, &ast); err != nil {
+		return nil, fmt.Errorf("decode sub-workflow definition: %w", err)
+	}
+	if len(ast.Outputs) == 0 {
+		return data, nil
+	}
+
+	caseMap := make(map[string]any, len(data)+1)
+	for k, v := range data {
+		caseMap[k] = v
+	}
+	steps := map[string]any{}
+	rows, err := s.db.QueryContext(ctx, `
+SELECT step_id, COALESCE(result, '{}'::jsonb)
+FROM case_steps
+WHERE case_id = $1
+`, childID)
+	if err != nil {
+		return nil, fmt.Errorf("load sub-workflow step results: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	for rows.Next() {
+		var stepID string
+		var raw []byte
+		if err := rows.Scan(&stepID, &raw); err != nil {
+			return nil, fmt.Errorf("scan sub-workflow step result: %w", err)
+		}
+		var stepResult any
+		if err := json.Unmarshal(raw, &stepResult); err != nil {
+			return nil, fmt.Errorf("decode sub-workflow step result %s: %w", stepID, err)
+		}
+		steps[stepID] = map[string]any{"result": stepResult}
+	}
+	if err := rows.Err(); err != nil {
+		return nil, fmt.Errorf("iterate sub-workflow step results: %w", err)
+	}
+	caseMap["steps"] = steps
+	return ast.ResolveOutputs(map[string]any{"case": caseMap}), nil
+}
+
+// selectOutputs picks the named fields from the child's outputs (its declared
+// outputs, or its final case data); with no names configured everything is
+// returned.
+func selectOutputs(data map[string]any, names []string) map[string]any {
+	if len(names) == 0 {
+		return data
+	}
+	out := make(map[string]any, len(names))
+	for _, name := range names {
+		if v, ok := data[name]; ok {
+			out[name] = v
+		}
+	}
+	return out
+}
@@ -0,0 +1,246 @@
+// Package subworkflows runs another case type's published workflow as a step:
+// the step starts a child case, waits for it to settle, and completes with the
+// child's outputs.
+package subworkflows
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"slices"
+	"strings"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/cases"
+	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+const DefaultMaxDepth = 5
+
+var (
+	ErrDepthExceeded = errors.New("sub-workflow depth limit exceeded")
+	ErrCycle         = errors.New("sub-workflow cycle detected")
+)
+
+type stepConfig struct {
+	CaseType string         `json:"case_type"`
+	Input    map[string]any `json:"input"`
+	Outputs  []string       `json:"outputs"`
+	Priority int            `json:"priority"`
+}
+
+// CaseCreator starts child cases, recording the call in the transaction
+// creating the child.
+type CaseCreator interface {
+	CreateLinkedCase(ctx context.Context, tenantID, createdBy uuid.UUID, req cases.CreateCaseRequest, link cases.CaseLinkFunc) (cases.Case, []cases.ValidationError, error)
+}
+
+// StepCompleter resolves the parent step once its child case settles.
+type StepCompleter interface {
+	CompleteStep(ctx context.Context, caseID uuid.UUID, stepID string, result *engine.StepResult) error
+	FailStep(ctx context.Context, caseID uuid.UUID, stepID string, failErr error) error
+}
+
+type StepExecutor struct {
+	db       *sql.DB
+	cases    CaseCreator
+	engine   StepCompleter
+	maxDepth int
+}
+
+func NewStepExecutor(db *sql.DB, creator CaseCreator, eng StepCompleter, maxDepth int) *StepExecutor {
+	if maxDepth <= 0 {
+		maxDepth = DefaultMaxDepth
+	}
+	return &StepExecutor{db: db, cases: creator, engine: eng, maxDepth: maxDepth}
+}
+
+type parentCase struct {
+	tenantID  uuid.UUID
+	createdBy uuid.UUID
+	caseType  string
+	context   map[string]any
+}
+
+func (s *StepExecutor) Execute(ctx context.Context, caseID uuid.UUID, stepID string, config json.RawMessage) (*engine.StepResult, error) {
+	if s == nil || s.cases == nil || s.engine == nil {
+		return nil, fmt.Errorf("sub-workflow step executor not configured")
+	}
+	cfg := stepConfig{}
+	if len(config) > 0 {
+		if err := json.Unmarshal(config, &cfg); err != nil {
+			return nil, fmt.Errorf("decode sub_workflow step config: %w", err)
+		}
+	}
+	cfg.CaseType = strings.TrimSpace(cfg.CaseType)
+	if cfg.CaseType == "" {
+		return nil, fmt.Errorf("sub_workflow step config missing case_type")
+	}
+
+	// A recovered step whose child is still running just keeps waiting.
+	var running bool
+	if err := s.db.QueryRowContext(ctx, `
+SELECT EXISTS (
+    SELECT 1 FROM sub_workflow_calls
+    WHERE parent_case_id = $1 AND parent_step_id = $2 AND status = 'running'
+)
+`, caseID, stepID).Scan(&running); err != nil {
+		return nil, fmt.Errorf("check running sub-workflow call: %w", err)
+	}
+	if running {
+		return nil, engine.ErrStepAwaitingReview
+	}
+
+	parent, err := s.loadParent(ctx, caseID)
+	if err != nil {
+		return nil, err
+	}
+	depth, chain, err := s.callerChain(ctx, caseID, parent.caseType)
+	if err != nil {
+		return nil, err
+	}
+	if err := checkCall(depth+1, s.maxDepth, chain, cfg.CaseType); err != nil {
+		return nil, err
+	}
+
+	input := map[string]any{}
+	if resolved, ok := connectors.ResolveTemplateAny(cfg.Input, parent.context).(map[string]any); ok && resolved != nil {
+		input = resolved
+	}
+	chainJSON, err := json.Marshal(append(chain, cfg.CaseType))
+	if err != nil {
+		return nil, fmt.Errorf("marshal sub-workflow call chain: %w", err)
+	}
+	outputs := cfg.Outputs
+	if outputs == nil {
+		outputs = []string{}
+	}
+	outputsJSON, err := json.Marshal(outputs)
+	if err != nil {
+		return nil, fmt.Errorf("marshal sub-workflow outputs: %w", err)
+	}
+	// The call is recorded with the child, so a retry after a failure never
+	// finds a child without its link, and the unique running call per parent
+	// step rolls back a second child started concurrently.
+	_, validation, err := s.cases.CreateLinkedCase(ctx, parent.tenantID, parent.createdBy, cases.CreateCaseRequest{
+		CaseType: cfg.CaseType,
+		Data:     input,
+		Priority: cfg.Priority,
+	}, func(ctx context.Context, tx *sql.Tx, child cases.Case) error {
+		if _, err := tx.ExecContext(ctx, `
+INSERT INTO sub_workflow_calls (child_case_id, tenant_id, parent_case_id, parent_step_id, depth, call_chain, outputs)
+VALUES ($1, $2, $3, $4, $5, $6::jsonb, $7::jsonb)
+`, child.ID, parent.tenantID, caseID, stepID, depth+1, string(chainJSON), string(outputsJSON)); err != nil {
+			return fmt.Errorf("record sub-workflow call: %w", err)
+		}
+		return nil
+	})
+	if err != nil {
+		return nil, fmt.Errorf("start sub-workflow case: %w", err)
+	}
+	if len(validation) > 0 {
+		messages := make([]string, 0, len(validation))
+		for _, v := range validation {
+			messages = append(messages, fmt.Sprintf("%s: %s", v.Field, v.Message))
+		}
+		return nil, fmt.Errorf("sub-workflow input rejected by %s schema: %s", cfg.CaseType, strings.Join(messages, "; "))
+	}
+	return nil, engine.ErrStepAwaitingReview
+}
+
+// checkCall rejects a call that would exceed maxDepth or re-enter a case type
+// already on the call chain.
+func checkCall(depth, maxDepth int, chain []string, caseType string) error {
+	if depth > maxDepth {
+		return fmt.Errorf("%w: depth %d exceeds %d", ErrDepthExceeded, depth, maxDepth)
+	}
+	if slices.Contains(chain, caseType) {
+		return fmt.Errorf("%w: %s -> %s", ErrCycle, strings.Join(chain, " -> "), caseType)
+	}
+	return nil
+}
+
+// callerChain returns the depth of caseID and the case types from the root
+// case down to it. A case not started by a sub_workflow step is the root.
+func (s *StepExecutor) callerChain(ctx context.Context, caseID uuid.UUID, caseType string) (int, []string, error) {
+	var depth int
+	var raw []byte
+	err := s.db.QueryRowContext(ctx, `
+SELECT depth, call_chain
+FROM sub_workflow_calls
+WHERE child_case_id = $1
+`, caseID).Scan(&depth, &raw)
+	if errors.Is(err, sql.ErrNoRows) {
+		return 0, []string{caseType}, nil
+	}
+	if err != nil {
+		return 0, nil, fmt.Errorf("load sub-workflow call chain: %w", err)
+	}
+	var chain []string
+	if err := json.Unmarshal(raw, &chain); err != nil {
+		return 0, nil, fmt.Errorf("decode sub-workflow call chain: %w", err)
+	}
+	return depth, chain, nil
+}
+
+func (s *StepExecutor) loadParent(ctx context.Context, caseID uuid.UUID) (parentCase, error) {
+	var (
+		parent     parentCase
+		caseNumber string
+		dataRaw    []byte
+	)
+	err := s.db.QueryRowContext(ctx, `
+SELECT c.tenant_id, c.created_by, ct.name, c.case_number, c.data
+FROM cases c
+JOIN case_types ct ON ct.id = c.case_type_id
+WHERE c.id = $1
+`, caseID).Scan(&parent.tenantID, &parent.createdBy, &parent.caseType, &caseNumber, &dataRaw)
+	if err != nil {
+		return parentCase{}, fmt.Errorf("load parent case: %w", err)
+	}
+	data := map[string]any{}
+	if err := json.Unmarshal(dataRaw, &data); err != nil {
+		return parentCase{}, fmt.Errorf("decode parent case data: %w", err)
+	}
+
+	steps := map[string]any{}
+	rows, err := s.db.QueryContext(ctx, `
+SELECT step_id, COALESCE(result, '{}'::jsonb)
+FROM case_steps
+WHERE case_id = $1
+`, caseID)
+	if err != nil {
+		return parentCase{}, fmt.Errorf("load parent step results: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	for rows.Next() {
+		var stepID string
+		var raw []byte
+		if err := rows.Scan(&stepID, &raw); err != nil {
+			return parentCase{}, fmt.Errorf("scan parent step result: %w", err)
+		}
+		result := map[string]any{}
+		if err := json.Unmarshal(raw, &result); err != nil {
+			return parentCase{}, fmt.Errorf("decode parent step result for %s: %w", stepID, err)
+		}
+		steps[stepID] = map[string]any{"result": result}
+	}
+	if err := rows.Err(); err != nil {
+		return parentCase{}, fmt.Errorf("iterate parent step results: %w", err)
+	}
+
+	caseMap := map[string]any{
+		"id":          caseID.String(),
+		"case_number": caseNumber,
+		"data":        data,
+		"steps":       steps,
+	}
+	for k, v := range data {
+		caseMap[k] = v
+	}
+	parent.context = map[string]any{"case": caseMap}
+	return parent, nil
+}
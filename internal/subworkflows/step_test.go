@@ -0,0 +1,43 @@
+package subworkflows
+
+import (
+	"errors"
+	"reflect"
+	"testing"
+)
+
+func TestCheckCall(t *testing.T) {
+	cases := []struct {
+		name     string
+		depth    int
+		chain    []string
+		caseType string
+		want     error
+	}{
+		{name: "first level", depth: 1, chain: []string{"loan"}, caseType: "kyc"},
+		{name: "at limit", depth: 3, chain: []string{"loan", "kyc", "aml"}, caseType: "sanctions"},
+		{name: "over limit", depth: 4, chain: []string{"loan", "kyc", "aml", "sanctions"}, caseType: "pep", want: ErrDepthExceeded},
+		{name: "self call", depth: 1, chain: []string{"loan"}, caseType: "loan", want: ErrCycle},
+		{name: "indirect cycle", depth: 2, chain: []string{"loan", "kyc"}, caseType: "loan", want: ErrCycle},
+	}
+	for _, tc := range cases {
+		err := checkCall(tc.depth, 3, tc.chain, tc.caseType)
+		if tc.want == nil && err != nil {
+			t.Fatalf("%s: unexpected error %v", tc.name, err)
+		}
+		if tc.want != nil && !errors.Is(err, tc.want) {
+			t.Fatalf("%s: expected %v, got %v", tc.name, tc.want, err)
+		}
+	}
+}
+
+func TestSelectOutputs(t *testing.T) {
+	data := map[string]any{"score": 720, "decision": "approve", "notes": "ok"}
+	if got := selectOutputs(data, nil); !reflect.DeepEqual(got, data) {
+		t.Fatalf("expected all data without output names, got %v", got)
+	}
+	got := selectOutputs(data, []string{"decision", "missing"})
+	if want := map[string]any{"decision": "approve"}; !reflect.DeepEqual(got, want) {
+		t.Fatalf("expected %v, got %v", want, got)
+	}
+}
@@ -0,0 +1,274 @@
+package toolpool
+
+import (
+	"container/list"
+	"context"
+	"log/slog"
+	"slices"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/neural-chilli/aceryx/internal/observability"
+)
+
+const (
+	DefaultMaxSize  = 64
+	DefaultLifetime = 5 * time.Minute
+	pruneInterval   = 30 * time.Second
+)
+
+// Instance is a pooled tool instance, such as a connection to a tool server
+// and what was discovered over it. Close releases it when it leaves the pool.
+type Instance interface {
+	Close() error
+}
+
+// Pool keeps tool instances per protocol and key so that connections are
+// reused across calls. Instances expire after their protocol's lifetime and,
+// least recently used first, are evicted beyond the size limit. An evicted
+// instance is closed once the last caller using it releases it.
+type Pool struct {
+	maxSize   int
+	lifetimes map[string]time.Duration
+	now       func() time.Time
+
+	mu      sync.Mutex
+	entries map[poolKey]*list.Element
+	lru     *list.List
+	closed  bool
+}
+
+type poolKey struct {
+	protocol string
+	key      string
+}
+
+type poolEntry struct {
+	id        poolKey
+	instance  Instance
+	expiresAt time.Time
+	refs      int
+	evicted   bool
+}
+
+type ProtocolStats struct {
+	Protocol  string `json:"protocol"`
+	Instances int    `json:"instances"`
+	InUse     int    `json:"in_use"`
+}
+
+type Stats struct {
+	Instances int             `json:"instances"`
+	MaxSize   int             `json:"max_size"`
+	Protocols []ProtocolStats `json:"protocols"`
+}
+
+// New creates a pool holding at most maxSize instances. lifetimes sets how
+// long instances of each protocol are reused; protocols without one use
+// DefaultLifetime.
+func New(maxSize int, lifetimes map[string]time.Duration) *Pool {
+	if maxSize <= 0 {
+		maxSize = DefaultMaxSize
+	}
+	byProtocol := make(map[string]time.Duration, len(lifetimes))
+	for protocol, lifetime := range lifetimes {
+		if lifetime > 0 {
+			byProtocol[protocol] = lifetime
+		}
+	}
+	return &Pool{
+		maxSize:   maxSize,
+		lifetimes: byProtocol,
+		now:       time.Now,
+		entries:   map[poolKey]*list.Element{},
+		lru:       list.New(),
+	}
+}
+
+// Acquire returns the pooled instance for protocol and key, opening one when
+// there is none or it has expired. release must be called once the caller is
+// done with the instance.
+func (p *Pool) Acquire(protocol, key string, open func() (Instance, error)) (Instance, func(), error) {
+	id := poolKey{protocol: protocol, key: key}
+	if instance, release, ok := p.acquireExisting(id); ok {
+		return instance, release, nil
+	}
+	instance, err := open()
+	if err != nil {
+		return nil, nil, err
+	}
+
+	p.mu.Lock()
+	if p.closed {
+		p.mu.Unlock()
+		return instance, func() { closeInstance(protocol, instance) }, nil
+	}
+	if el, ok := p.entries[id]; ok {
+		// Another caller opened the same instance meanwhile; keep theirs.
+		entry := el.Value.(*poolEntry)
+		if p.now().Before(entry.expiresAt) {
+			entry.refs++
+			p.lru.MoveToFront(el)
+			p.mu.Unlock()
+			closeInstance(protocol, instance)
+			return entry.instance, p.releaser(entry), nil
+		}
+		p.evict(el)
+	}
+	entry := &poolEntry{id: id, instance: instance, expiresAt: p.now().Add(p.lifetime(protocol)), refs: 1}
+	p.entries[id] = p.lru.PushFront(entry)
+	for p.lru.Len() > p.maxSize {
+		p.evict(p.lru.Back())
+	}
+	p.updateGauge(protocol)
+	p.mu.Unlock()
+	return instance, p.releaser(entry), nil
+}
+
+// Evict removes the instance for protocol and key, for example after it
+// failed, so that the next Acquire opens a fresh one.
+func (p *Pool) Evict(protocol, key string) {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	if el, ok := p.entries[poolKey{protocol: protocol, key: key}]; ok {
+		p.evict(el)
+	}
+}
+
+// Prune evicts expired instances and returns how many were removed.
+func (p *Pool) Prune() int {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	now := p.now()
+	removed := 0
+	for el := p.lru.Back(); el != nil; {
+		prev := el.Prev()
+		if !now.Before(el.Value.(*poolEntry).expiresAt) {
+			p.evict(el)
+			removed++
+		}
+		el = prev
+	}
+	return removed
+}
+
+// Run prunes expired instances until ctx is done, then closes the pool.
+func (p *Pool) Run(ctx context.Context) {
+	ticker := time.NewTicker(pruneInterval)
+	defer ticker.Stop()
+	for {
+		select {
+		case <-ticker.C:
+			p.Prune()
+		case <-ctx.Done():
+			p.Close()
+			return
+		}
+	}
+}
+
+// Close evicts every instance. Instances acquired afterwards are not pooled
+// and are closed on release.
+func (p *Pool) Close() {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	p.closed = true
+	for p.lru.Len() > 0 {
+		p.evict(p.lru.Back())
+	}
+}
+
+func (p *Pool) Stats() Stats {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	byProtocol := map[string]*ProtocolStats{}
+	for el := p.lru.Front(); el != nil; el = el.Next() {
+		entry := el.Value.(*poolEntry)
+		s, ok := byProtocol[entry.id.protocol]
+		if !ok {
+			s = &ProtocolStats{Protocol: entry.id.protocol}
+			byProtocol[entry.id.protocol] = s
+		}
+		s.Instances++
+		if entry.refs > 0 {
+			s.InUse++
+		}
+	}
+	out := Stats{Instances: p.lru.Len(), MaxSize: p.maxSize, Protocols: make([]ProtocolStats, 0, len(byProtocol))}
+	for _, s := range byProtocol {
+		out.Protocols = append(out.Protocols, *s)
+	}
+	slices.SortFunc(out.Protocols, func(a, b ProtocolStats) int { return strings.Compare(a.Protocol, b.Protocol) })
+	return out
+}
+
+func (p *Pool) acquireExisting(id poolKey) (Instance, func(), bool) {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	el, ok := p.entries[id]
+	if !ok {
+		return nil, nil, false
+	}
+	entry := el.Value.(*poolEntry)
+	if !p.now().Before(entry.expiresAt) {
+		p.evict(el)
+		return nil, nil, false
+	}
+	entry.refs++
+	p.lru.MoveToFront(el)
+	return entry.instance, p.releaser(entry), true
+}
+
+func (p *Pool) releaser(entry *poolEntry) func() {
+	var once sync.Once
+	return func() {
+		once.Do(func() {
+			p.mu.Lock()
+			entry.refs--
+			closeNow := entry.evicted && entry.refs == 0
+			p.mu.Unlock()
+			if closeNow {
+				closeInstance(entry.id.protocol, entry.instance)
+			}
+		})
+	}
+}
+
+// evict removes el from the pool, closing its instance unless a caller still
+// holds it. Must be called with p.mu held.
+func (p *Pool) evict(el *list.Element) {
+	entry := el.Value.(*poolEntry)
+	p.lru.Remove(el)
+	delete(p.entries, entry.id)
+	entry.evicted = true
+	observability.ToolPoolEvictionsTotal.WithLabelValues(entry.id.protocol).Inc()
+	p.updateGauge(entry.id.protocol)
+	if entry.refs == 0 {
+		closeInstance(entry.id.protocol, entry.instance)
+	}
+}
+
+// updateGauge must be called with p.mu held.
+func (p *Pool) updateGauge(protocol string) {
+	count := 0
+	for id := range p.entries {
+		if id.protocol == protocol {
+			count++
+		}
+	}
+	observability.ToolPoolInstances.WithLabelValues(protocol).Set(float64(count))
+}
+
+func (p *Pool) lifetime(protocol string) time.Duration {
+	if lifetime, ok := p.lifetimes[protocol]; ok {
+		return lifetime
+	}
+	return DefaultLifetime
+}
+
+func closeInstance(protocol string, instance Instance) {
+	if err := instance.Close(); err != nil {
+		slog.Warn("close pooled tool instance failed", "protocol", protocol, "error", err)
+	}
+}
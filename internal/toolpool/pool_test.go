@@ -0,0 +1,125 @@
+package toolpool
+
+import (
+	"errors"
+	"testing"
+	"time"
+)
+
+type fakeInstance struct {
+	name   string
+	closed int
+}
+
+func (f *fakeInstance) Close() error {
+	f.closed++
+	return nil
+}
+
+func opener(name string, opened *int) func() (Instance, error) {
+	return func() (Instance, error) {
+		*opened++
+		return &fakeInstance{name: name}, nil
+	}
+}
+
+func TestPoolReusesAndEvictsLeastRecentlyUsed(t *testing.T) {
+	pool := New(2, nil)
+	opened := 0
+
+	a, releaseA, err := pool.Acquire("grpc", "a", opener("a", &opened))
+	if err != nil {
+		t.Fatalf("acquire a: %v", err)
+	}
+	releaseA()
+	again, releaseAgain, _ := pool.Acquire("grpc", "a", opener("a", &opened))
+	releaseAgain()
+	if again != a || opened != 1 {
+		t.Fatalf("expected a to be reused, opened=%d", opened)
+	}
+
+	_, releaseB, _ := pool.Acquire("grpc", "b", opener("b", &opened))
+	releaseB()
+	_, releaseA, _ = pool.Acquire("grpc", "a", opener("a", &opened))
+	releaseA()
+	_, releaseC, _ := pool.Acquire("mcp", "c", opener("c", &opened))
+	releaseC()
+
+	stats := pool.Stats()
+	if stats.Instances != 2 || len(stats.Protocols) != 2 || stats.Protocols[0].Protocol != "grpc" {
+		t.Fatalf("unexpected stats %+v", stats)
+	}
+	b, releaseB, _ := pool.Acquire("grpc", "b", opener("b", &opened))
+	releaseB()
+	if opened != 4 || b.(*fakeInstance).closed != 0 {
+		t.Fatalf("expected b to have been evicted and reopened, opened=%d", opened)
+	}
+	if a.(*fakeInstance).closed != 1 {
+		t.Fatalf("expected evicted a to be closed, closed=%d", a.(*fakeInstance).closed)
+	}
+}
+
+func TestPoolExpiresPerProtocolLifetime(t *testing.T) {
+	now := time.Date(2026, 4, 1, 9, 0, 0, 0, time.UTC)
+	pool := New(10, map[string]time.Duration{"grpc": time.Minute})
+	pool.now = func() time.Time { return now }
+	opened := 0
+
+	short, release, _ := pool.Acquire("grpc", "a", opener("a", &opened))
+	release()
+	long, release, _ := pool.Acquire("mcp", "a", opener("a", &opened))
+	release()
+
+	now = now.Add(2 * time.Minute)
+	if removed := pool.Prune(); removed != 1 {
+		t.Fatalf("expected only the grpc instance to expire, removed %d", removed)
+	}
+	if short.(*fakeInstance).closed != 1 || long.(*fakeInstance).closed != 0 {
+		t.Fatal("expected only the expired instance to be closed")
+	}
+
+	now = now.Add(DefaultLifetime)
+	if _, release, _ = pool.Acquire("mcp", "a", opener("a", &opened)); opened != 3 {
+		t.Fatalf("expected an expired instance to be reopened, opened=%d", opened)
+	}
+	release()
+	if long.(*fakeInstance).closed != 1 {
+		t.Fatal("expected the expired mcp instance to be closed")
+	}
+}
+
+func TestPoolClosesEvictedInstanceOnRelease(t *testing.T) {
+	pool := New(10, nil)
+	opened := 0
+	inst, release, _ := pool.Acquire("grpc", "a", opener("a", &opened))
+
+	pool.Evict("grpc", "a")
+	if inst.(*fakeInstance).closed != 0 {
+		t.Fatal("expected an instance in use not to be closed on eviction")
+	}
+	release()
+	release()
+	if inst.(*fakeInstance).closed != 1 {
+		t.Fatalf("expected the instance to be closed once on release, closed=%d", inst.(*fakeInstance).closed)
+	}
+
+	pool.Close()
+	after, release, _ := pool.Acquire("grpc", "a", opener("a", &opened))
+	if pool.Stats().Instances != 0 {
+		t.Fatal("expected a closed pool not to keep instances")
+	}
+	release()
+	if after.(*fakeInstance).closed != 1 {
+		t.Fatal("expected instances acquired after close to be closed on release")
+	}
+}
+
+func TestPoolDoesNotKeepFailedOpens(t *testing.T) {
+	pool := New(10, nil)
+	if _, _, err := pool.Acquire("grpc", "a", func() (Instance, error) { return nil, errors.New("refused") }); err == nil {
+		t.Fatal("expected open error")
+	}
+	if pool.Stats().Instances != 0 {
+		t.Fatal("expected nothing pooled after a failed open")
+	}
+}
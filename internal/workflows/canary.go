@@ -0,0 +1,306 @@
+package workflows
+
+import (
+	"context"
+	"database/sql"
+	"errors"
+	"fmt"
+	"log/slog"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+// Canary defaults applied when a request leaves them unset.
+const (
+	DefaultCanaryMaxFailureRate = 0.1
+	DefaultCanaryMinCases       = 20
+)
+
+// Canary statuses.
+const (
+	CanaryActive     = "active"
+	CanaryPromoted   = "promoted"
+	CanaryRolledBack = "rolled_back"
+)
+
+var (
+	// ErrInvalidCanary is returned for out-of-range canary settings, or when
+	// the workflow has no published version to split traffic with.
+	ErrInvalidCanary = errors.New("invalid canary")
+	// ErrCanaryActive is returned when starting a canary while one runs.
+	ErrCanaryActive = errors.New("canary already active")
+	// ErrNoCanary is returned when the workflow has no active canary.
+	ErrNoCanary = errors.New("no active canary")
+)
+
+// CanaryRequest starts the draft as a canary. Percent of the cases triggers
+// and webhooks start run the canary, the rest the published version. The
+// canary is rolled back once more than MaxFailureRate of at least MinCases
+// canary cases have a failed step.
+type CanaryRequest struct {
+	Percent        int     `json:"percent"`
+	MaxFailureRate float64 `json:"max_failure_rate,omitempty"`
+	MinCases       int     `json:"min_cases,omitempty"`
+}
+
+// CanaryStats counts the cases started on one version since the canary began.
+type CanaryStats struct {
+	Version     int     `json:"version"`
+	Cases       int     `json:"cases"`
+	FailedCases int     `json:"failed_cases"`
+	FailureRate float64 `json:"failure_rate"`
+}
+
+type Canary struct {
+	ID             uuid.UUID   `json:"id"`
+	WorkflowID     uuid.UUID   `json:"workflow_id"`
+	Version        int         `json:"version"`
+	Percent        int         `json:"percent"`
+	MaxFailureRate float64     `json:"max_failure_rate"`
+	MinCases       int         `json:"min_cases"`
+	Status         string      `json:"status"`
+	Reason         string      `json:"reason,omitempty"`
+	StartedAt      time.Time   `json:"started_at"`
+	EndedAt        *time.Time  `json:"ended_at,omitempty"`
+	Canary         CanaryStats `json:"canary"`
+	Stable         CanaryStats `json:"stable"`
+}
+
+func (r *CanaryRequest) normalize() error {
+	if r.MaxFailureRate == 0 {
+		r.MaxFailureRate = DefaultCanaryMaxFailureRate
+	}
+	if r.MinCases == 0 {
+		r.MinCases = DefaultCanaryMinCases
+	}
+	switch {
+	case r.Percent < 1 || r.Percent > 99:
+		return fmt.Errorf("%w: percent must be between 1 and 99", ErrInvalidCanary)
+	case r.MaxFailureRate < 0 || r.MaxFailureRate > 1:
+		return fmt.Errorf("%w: max_failure_rate must be between 0 and 1", ErrInvalidCanary)
+	case r.MinCases < 0:
+		return fmt.Errorf("%w: min_cases cannot be negative", ErrInvalidCanary)
+	}
+	return nil
+}
+
+// shouldRollBack reports whether an active canary has seen enough cases to
+// judge and fails more often than allowed.
+func (c Canary) shouldRollBack() bool {
+	return c.Status == CanaryActive && c.Canary.Cases >= c.MinCases && c.Canary.FailureRate > c.MaxFailureRate
+}
+
+// StartCanary validates the draft like PublishDraft, then releases it as a
+// canary next to the published version instead of replacing it.
+func (s *Service) StartCanary(ctx context.Context, tenantID, actorID, workflowID uuid.UUID, req CanaryRequest) (Canary, error) {
+	if err := req.normalize(); err != nil {
+		return Canary{}, err
+	}
+	if err := s.releaseDraft(ctx, tenantID, actorID, workflowID, &req); err != nil {
+		return Canary{}, err
+	}
+	return s.GetCanary(ctx, tenantID, workflowID)
+}
+
+func startCanaryTx(ctx context.Context, tx *sql.Tx, tenantID, actorID, workflowID, draftID uuid.UUID, draftVersion int, req CanaryRequest) error {
+	var published, active bool
+	if err := tx.QueryRowContext(ctx, `
+SELECT
+  EXISTS(SELECT 1 FROM workflow_versions WHERE workflow_id = $1 AND status = 'published'),
+  EXISTS(SELECT 1 FROM workflow_canaries WHERE workflow_id = $1 AND tenant_id = $2 AND status = 'active')
+`, workflowID, tenantID).Scan(&published, &active); err != nil {
+		return fmt.Errorf("check canary preconditions: %w", err)
+	}
+	if !published {
+		return fmt.Errorf("%w: publish a first version before starting a canary", ErrInvalidCanary)
+	}
+	if active {
+		return ErrCanaryActive
+	}
+	if _, err := tx.ExecContext(ctx, `
+UPDATE workflow_versions
+SET status = 'canary',
+    published_at = now()
+WHERE id = $1
+`, draftID); err != nil {
+		return fmt.Errorf("start canary version: %w", err)
+	}
+	if _, err := tx.ExecContext(ctx, `
+INSERT INTO workflow_canaries (tenant_id, workflow_id, version, percent, max_failure_rate, min_cases, created_by)
+VALUES ($1, $2, $3, $4, $5, $6, $7)
+`, tenantID, workflowID, draftVersion, req.Percent, req.MaxFailureRate, req.MinCases, actorID); err != nil {
+		return fmt.Errorf("record canary: %w", err)
+	}
+	return nil
+}
+
+// GetCanary returns the workflow's most recent canary with case counts for
+// it and the published version since it started.
+func (s *Service) GetCanary(ctx context.Context, tenantID, workflowID uuid.UUID) (Canary, error) {
+	var (
+		c       Canary
+		endedAt sql.NullTime
+	)
+	err := s.db.QueryRowContext(ctx, `
+SELECT id, workflow_id, version, percent, max_failure_rate, min_cases, status, reason, started_at, ended_at
+FROM workflow_canaries
+WHERE workflow_id = $1 AND tenant_id = $2
+ORDER BY started_at DESC
+LIMIT 1
+`, workflowID, tenantID).Scan(&c.ID, &c.WorkflowID, &c.Version, &c.Percent, &c.MaxFailureRate, &c.MinCases, &c.Status, &c.Reason, &c.StartedAt, &endedAt)
+	if errors.Is(err, sql.ErrNoRows) {
+		return Canary{}, ErrNoCanary
+	}
+	if err != nil {
+		return Canary{}, fmt.Errorf("load canary: %w", err)
+	}
+	if endedAt.Valid {
+		c.EndedAt = &endedAt.Time
+	}
+	if err := s.loadCanaryStats(ctx, &c); err != nil {
+		return Canary{}, err
+	}
+	return c, nil
+}
+
+// loadCanaryStats counts cases per version started since the canary began.
+// The stable version is the highest other version with cases in that window.
+func (s *Service) loadCanaryStats(ctx context.Context, c *Canary) error {
+	rows, err := s.db.QueryContext(ctx, `
+SELECT c.workflow_version,
+       COUNT(*),
+       COUNT(*) FILTER (WHERE EXISTS (
+         SELECT 1 FROM case_steps cs WHERE cs.case_id = c.id AND cs.state = 'failed'
+       ))
+FROM cases c
+WHERE c.workflow_id = $1 AND c.created_at >= $2
+GROUP BY c.workflow_version
+ORDER BY c.workflow_version DESC
+`, c.WorkflowID, c.StartedAt)
+	if err != nil {
+		return fmt.Errorf("count canary cases: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	c.Canary = CanaryStats{Version: c.Version}
+	for rows.Next() {
+		var stats CanaryStats
+		if err := rows.Scan(&stats.Version, &stats.Cases, &stats.FailedCases); err != nil {
+			return fmt.Errorf("scan canary cases: %w", err)
+		}
+		if stats.Cases > 0 {
+			stats.FailureRate = float64(stats.FailedCases) / float64(stats.Cases)
+		}
+		switch {
+		case stats.Version == c.Version:
+			c.Canary = stats
+		case c.Stable.Version == 0:
+			c.Stable = stats
+		}
+	}
+	return rows.Err()
+}
+
+// PromoteCanary makes the active canary the published version.
+func (s *Service) PromoteCanary(ctx context.Context, tenantID, workflowID uuid.UUID) (Canary, error) {
+	if err := s.endCanary(ctx, tenantID, workflowID, CanaryPromoted, ""); err != nil {
+		return Canary{}, err
+	}
+	return s.GetCanary(ctx, tenantID, workflowID)
+}
+
+// RollbackCanary withdraws the active canary version; new cases all run the
+// published version again. Cases already on the canary finish on it.
+func (s *Service) RollbackCanary(ctx context.Context, tenantID, workflowID uuid.UUID, reason string) (Canary, error) {
+	if reason == "" {
+		reason = "rolled back manually"
+	}
+	if err := s.endCanary(ctx, tenantID, workflowID, CanaryRolledBack, reason); err != nil {
+		return Canary{}, err
+	}
+	return s.GetCanary(ctx, tenantID, workflowID)
+}
+
+func (s *Service) endCanary(ctx context.Context, tenantID, workflowID uuid.UUID, status, reason string) error {
+	tx, err := s.db.BeginTx(ctx, nil)
+	if err != nil {
+		return fmt.Errorf("begin end canary tx: %w", err)
+	}
+	defer func() { _ = tx.Rollback() }()
+
+	var version int
+	err = tx.QueryRowContext(ctx, `
+UPDATE workflow_canaries
+SET status = $3, reason = $4, ended_at = now()
+WHERE workflow_id = $1 AND tenant_id = $2 AND status = 'active'
+RETURNING version
+`, workflowID, tenantID, status, reason).Scan(&version)
+	if errors.Is(err, sql.ErrNoRows) {
+		return ErrNoCanary
+	}
+	if err != nil {
+		return fmt.Errorf("end canary: %w", err)
+	}
+
+	if status == CanaryPromoted {
+		if _, err := tx.ExecContext(ctx, `
+UPDATE workflow_versions
+SET status = 'withdrawn'
+WHERE workflow_id = $1 AND status = 'published'
+`, workflowID); err != nil {
+			return fmt.Errorf("withdraw previous published versions: %w", err)
+		}
+		if _, err := tx.ExecContext(ctx, `
+UPDATE workflow_versions
+SET status = 'published',
+    published_at = now()
+WHERE workflow_id = $1 AND version = $2 AND status = 'canary'
+`, workflowID, version); err != nil {
+			return fmt.Errorf("publish canary version: %w", err)
+		}
+	} else if _, err := tx.ExecContext(ctx, `
+UPDATE workflow_versions
+SET status = 'withdrawn'
+WHERE workflow_id = $1 AND version = $2 AND status = 'canary'
+`, workflowID, version); err != nil {
+		return fmt.Errorf("withdraw canary version: %w", err)
+	}
+
+	if err := tx.Commit(); err != nil {
+		return fmt.Errorf("commit end canary tx: %w", err)
+	}
+	return nil
+}
+
+// OnStepEvent is an engine.StepEventHook that rolls a canary back once a
+// failed step pushes its failure rate over the threshold.
+func (s *Service) OnStepEvent(ctx context.Context, event engine.StepEvent) {
+	if event.Status != engine.StepStatusFailed {
+		return
+	}
+	var workflowID uuid.UUID
+	err := s.db.QueryRowContext(ctx, `
+SELECT wc.workflow_id
+FROM cases c
+JOIN workflow_canaries wc ON wc.workflow_id = c.workflow_id AND wc.version = c.workflow_version AND wc.status = 'active'
+WHERE c.id = $1
+`, event.CaseID).Scan(&workflowID)
+	if errors.Is(err, sql.ErrNoRows) {
+		return
+	}
+	if err == nil {
+		var canary Canary
+		canary, err = s.GetCanary(ctx, event.TenantID, workflowID)
+		if err == nil && canary.shouldRollBack() {
+			reason := fmt.Sprintf("failure rate %.1f%% over %d cases exceeded %.1f%%", canary.Canary.FailureRate*100, canary.Canary.Cases, canary.MaxFailureRate*100)
+			if _, err = s.RollbackCanary(ctx, event.TenantID, workflowID, reason); err == nil || errors.Is(err, ErrNoCanary) {
+				slog.WarnContext(ctx, "workflow canary rolled back", "workflow_id", workflowID.String(), "version", canary.Version, "reason", reason)
+				return
+			}
+		}
+	}
+	if err != nil {
+		slog.WarnContext(ctx, "check workflow canary failed", "case_id", event.CaseID.String(), "error", err)
+	}
+}
@@ -0,0 +1,46 @@
+package workflows
+
+import (
+	"errors"
+	"testing"
+)
+
+func TestCanaryRequestNormalize(t *testing.T) {
+	req := CanaryRequest{Percent: 10}
+	if err := req.normalize(); err != nil {
+		t.Fatalf("normalize: %v", err)
+	}
+	if req.MaxFailureRate != DefaultCanaryMaxFailureRate || req.MinCases != DefaultCanaryMinCases {
+		t.Fatalf("expected defaults, got %#v", req)
+	}
+	for _, bad := range []CanaryRequest{
+		{Percent: 0},
+		{Percent: 100},
+		{Percent: 10, MaxFailureRate: 1.5},
+		{Percent: 10, MinCases: -1},
+	} {
+		if err := bad.normalize(); !errors.Is(err, ErrInvalidCanary) {
+			t.Fatalf("expected ErrInvalidCanary for %#v, got %v", bad, err)
+		}
+	}
+}
+
+func TestCanaryShouldRollBack(t *testing.T) {
+	canary := Canary{Status: CanaryActive, MaxFailureRate: 0.1, MinCases: 20}
+	canary.Canary = CanaryStats{Cases: 10, FailedCases: 5, FailureRate: 0.5}
+	if canary.shouldRollBack() {
+		t.Fatal("rolled back before min_cases")
+	}
+	canary.Canary = CanaryStats{Cases: 20, FailedCases: 2, FailureRate: 0.1}
+	if canary.shouldRollBack() {
+		t.Fatal("rolled back at exactly the threshold")
+	}
+	canary.Canary = CanaryStats{Cases: 20, FailedCases: 3, FailureRate: 0.15}
+	if !canary.shouldRollBack() {
+		t.Fatal("expected rollback above the threshold")
+	}
+	canary.Status = CanaryRolledBack
+	if canary.shouldRollBack() {
+		t.Fatal("ended canary rolled back again")
+	}
+}
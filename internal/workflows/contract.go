@@ -0,0 +1,300 @@
+package workflows
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"sort"
+	"strings"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+// Contract is a workflow's declared input schema and outputs. See
+// engine.WorkflowAST.
+type Contract struct {
+	InputSchema json.RawMessage   `json:"input_schema,omitempty"`
+	Outputs     map[string]string `json:"outputs,omitempty"`
+}
+
+// PublishedWorkflow is a workflow with a published version, as offered to
+// sub_workflow steps. CaseType is the case type name callers pass.
+type PublishedWorkflow struct {
+	WorkflowID uuid.UUID `json:"workflow_id"`
+	Name       string    `json:"name"`
+	CaseType   string    `json:"case_type"`
+	Contract   Contract  `json:"contract"`
+}
+
+type contractCatalog interface {
+	PublishedContract(ctx context.Context, tenantID uuid.UUID, caseType string) (engine.WorkflowAST, bool, error)
+}
+
+// PublishedContract returns the latest published definition of the workflow
+// for caseType, which carries its contract. ok is false when none is
+// published.
+func (s *Service) PublishedContract(ctx context.Context, tenantID uuid.UUID, caseType string) (engine.WorkflowAST, bool, error) {
+	var astRaw []byte
+	err := s.db.QueryRowContext(ctx, `
+SELECT wv.ast
+FROM workflows w
+JOIN workflow_versions wv ON wv.workflow_id = w.id
+WHERE w.tenant_id = $1
+  AND (w.case_type = $2 OR w.case_type IN (SELECT id::text FROM case_types WHERE tenant_id = $1 AND name = $2))
+  AND wv.status = 'published'
+ORDER BY wv.version DESC
+LIMIT 1
+`, tenantID, caseType).Scan(&astRaw)
+	if errors.Is(err, sql.ErrNoRows) {
+		return engine.WorkflowAST{}, false, nil
+	}
+	if err != nil {
+		return engine.WorkflowAST{}, false, fmt.Errorf("load published workflow for %s: %w", caseType, err)
+	}
+	var ast engine.WorkflowAST
+	if err := json.Unmarshal(astRaw, &ast); err != nil {
+		return engine.WorkflowAST{}, false, fmt.Errorf("decode published workflow for %s: %w", caseType, err)
+	}
+	return ast, true, nil
+}
+
+// PublishedWorkflows lists the tenant's published workflows with the contract
+// of their latest published version.
+func (s *Service) PublishedWorkflows(ctx context.Context, tenantID uuid.UUID) ([]PublishedWorkflow, error) {
+	rows, err := s.db.QueryContext(ctx, `
+SELECT DISTINCT ON (w.id) w.id, w.name, COALESCE(ct.name, w.case_type), wv.ast
+FROM workflows w
+JOIN workflow_versions wv ON wv.workflow_id = w.id AND wv.status = 'published'
+LEFT JOIN case_types ct ON ct.tenant_id = w.tenant_id AND ct.id::text = w.case_type
+WHERE w.tenant_id = $1
+ORDER BY w.id, wv.version DESC
+`, tenantID)
+	if err != nil {
+		return nil, fmt.Errorf("list published workflows: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+
+	out := make([]PublishedWorkflow, 0)
+	for rows.Next() {
+		var item PublishedWorkflow
+		var astRaw []byte
+		if err := rows.Scan(&item.WorkflowID, &item.Name, &item.CaseType, &astRaw); err != nil {
+			return nil, fmt.Errorf("scan published workflow: %w", err)
+		}
+		var ast engine.WorkflowAST
+		if err := json.Unmarshal(astRaw, &ast); err != nil {
+			return nil, fmt.Errorf("decode published workflow %s: %w", item.WorkflowID, err)
+		}
+		item.Contract.Outputs = ast.Outputs
+		if ast.HasInputSchema() {
+			item.Contract.InputSchema = ast.InputSchema
+		}
+		out = append(out, item)
+	}
+	if err := rows.Err(); err != nil {
+		return nil, fmt.Errorf("iterate published workflows: %w", err)
+	}
+	sort.Slice(out, func(i, j int) bool { return out[i].Name < out[j].Name })
+	return out, nil
+}
+
+// loadContract returns the contract of the latest published version, or of
+// the draft when nothing is published yet.
+func (s *Service) loadContract(ctx context.Context, workflowID uuid.UUID) (*Contract, error) {
+	var astRaw []byte
+	err := s.db.QueryRowContext(ctx, `
+SELECT ast
+FROM workflow_versions
+WHERE workflow_id = $1 AND status IN ('published', 'draft')
+ORDER BY status = 'published' DESC, version DESC
+LIMIT 1
+`, workflowID).Scan(&astRaw)
+	if errors.Is(err, sql.ErrNoRows) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, fmt.Errorf("load workflow contract: %w", err)
+	}
+	var ast engine.WorkflowAST
+	if err := json.Unmarshal(astRaw, &ast); err != nil {
+		return nil, fmt.Errorf("decode workflow contract: %w", err)
+	}
+	if !ast.HasInputSchema() && len(ast.Outputs) == 0 {
+		return nil, nil
+	}
+	contract := &Contract{Outputs: ast.Outputs}
+	if ast.HasInputSchema() {
+		contract.InputSchema = ast.InputSchema
+	}
+	return contract, nil
+}
+
+func addContractErrors(validation *PublishValidationErrors, workflow engine.WorkflowAST) {
+	if err := workflow.ValidateContract(); err != nil {
+		validation.add(PublishValidationError{
+			Field:   "contract",
+			Code:    "INVALID_CONTRACT",
+			Message: err.Error(),
+		})
+	}
+}
+
+// validateSubWorkflowCalls type-checks sub_workflow steps against the called
+// workflow's published contract: the input must supply every required
+// property, may not add properties the schema forbids, and literal values
+// must have the declared type. Templated values are only checked when the
+// child case starts. Requested outputs must be declared ones.
+func validateSubWorkflowCalls(
+	ctx context.Context,
+	tenantID uuid.UUID,
+	workflow engine.WorkflowAST,
+	contracts contractCatalog,
+	validation *PublishValidationErrors,
+) error {
+	if contracts == nil {
+		return nil
+	}
+	for _, step := range workflow.Steps {
+		if strings.TrimSpace(step.Type) != "sub_workflow" {
+			continue
+		}
+		var cfg struct {
+			CaseType string         `json:"case_type"`
+			Input    map[string]any `json:"input"`
+			Outputs  []string       `json:"outputs"`
+		}
+		if len(step.Config) == 0 || json.Unmarshal(step.Config, &cfg) != nil || strings.TrimSpace(cfg.CaseType) == "" {
+			continue
+		}
+		child, ok, err := contracts.PublishedContract(ctx, tenantID, strings.TrimSpace(cfg.CaseType))
+		if err != nil {
+			return err
+		}
+		if !ok {
+			continue
+		}
+		stepID := strings.TrimSpace(step.ID)
+		for _, message := range inputMismatches(child.InputSchema, cfg.Input) {
+			validation.add(PublishValidationError{
+				StepID:  stepID,
+				Field:   "config.input",
+				Code:    "SUB_WORKFLOW_INPUT_MISMATCH",
+				Message: fmt.Sprintf("Step %q input for %s: %s", stepID, cfg.CaseType, message),
+			})
+		}
+		if len(child.Outputs) == 0 {
+			continue
+		}
+		for _, name := range cfg.Outputs {
+			if _, declared := child.Outputs[name]; !declared {
+				validation.add(PublishValidationError{
+					StepID:     stepID,
+					Field:      "config.outputs",
+					Code:       "SUB_WORKFLOW_OUTPUT_MISMATCH",
+					Message:    fmt.Sprintf("Step %q requests output %q, which %s does not declare", stepID, name, cfg.CaseType),
+					Suggestion: "Declared outputs: " + strings.Join(sortedKeys(child.Outputs), ", "),
+				})
+			}
+		}
+	}
+	return nil
+}
+
+// inputMismatches compares a sub_workflow input against the top level of
+// the child's input schema.
+func inputMismatches(rawSchema json.RawMessage, input map[string]any) []string {
+	var schema map[string]any
+	if len(rawSchema) == 0 || json.Unmarshal(rawSchema, &schema) != nil || schema == nil {
+		return nil
+	}
+	properties, _ := schema["properties"].(map[string]any)
+	var out []string
+	for _, name := range stringValues(schema["required"]) {
+		if _, ok := input[name]; !ok {
+			out = append(out, fmt.Sprintf("missing required property %q", name))
+		}
+	}
+	closed := schema["additionalProperties"] == false
+	for _, name := range sortedKeys(input) {
+		prop, known := properties[name].(map[string]any)
+		if !known {
+			if closed {
+				out = append(out, fmt.Sprintf("property %q is not accepted", name))
+			}
+			continue
+		}
+		value := input[name]
+		if s, ok := value.(string); ok && strings.Contains(s, "{{") {
+			continue
+		}
+		types := stringValues(prop["type"])
+		if len(types) > 0 && !literalMatchesAny(value, types) {
+			out = append(out, fmt.Sprintf("property %q must be %s", name, strings.Join(types, " or ")))
+		}
+	}
+	return out
+}
+
+func literalMatchesAny(value any, types []string) bool {
+	for _, t := range types {
+		switch t {
+		case "string":
+			if _, ok := value.(string); ok {
+				return true
+			}
+		case "number":
+			if _, ok := value.(float64); ok {
+				return true
+			}
+		case "integer":
+			if f, ok := value.(float64); ok && f == float64(int64(f)) {
+				return true
+			}
+		case "boolean":
+			if _, ok := value.(bool); ok {
+				return true
+			}
+		case "object":
+			if _, ok := value.(map[string]any); ok {
+				return true
+			}
+		case "array":
+			if _, ok := value.([]any); ok {
+				return true
+			}
+		case "null":
+			if value == nil {
+				return true
+			}
+		}
+	}
+	return false
+}
+
+func stringValues(raw any) []string {
+	switch v := raw.(type) {
+	case string:
+		return []string{v}
+	case []any:
+		out := make([]string, 0, len(v))
+		for _, item := range v {
+			if s, ok := item.(string); ok {
+				out = append(out, s)
+			}
+		}
+		return out
+	}
+	return nil
+}
+
+func sortedKeys[V any](m map[string]V) []string {
+	keys := make([]string, 0, len(m))
+	for key := range m {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+	return keys
+}
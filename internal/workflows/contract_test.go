@@ -0,0 +1,82 @@
+package workflows
+
+import (
+	"context"
+	"encoding/json"
+	"strings"
+	"testing"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+type fakeContracts map[string]engine.WorkflowAST
+
+func (f fakeContracts) PublishedContract(_ context.Context, _ uuid.UUID, caseType string) (engine.WorkflowAST, bool, error) {
+	ast, ok := f[caseType]
+	return ast, ok, nil
+}
+
+func TestValidateSubWorkflowCalls_ChecksChildContract(t *testing.T) {
+	contracts := fakeContracts{
+		"kyc_check": {
+			InputSchema: json.RawMessage(`{
+				"type": "object",
+				"required": ["customer_id", "country"],
+				"additionalProperties": false,
+				"properties": {
+					"customer_id": {"type": "string"},
+					"country": {"type": "string"},
+					"amount": {"type": "number"}
+				}
+			}`),
+			Outputs: map[string]string{"decision": "case.decision", "score": "case.steps.score.result.output.score"},
+		},
+	}
+	workflow := engine.WorkflowAST{Steps: []engine.WorkflowStep{
+		{ID: "ok", Type: "sub_workflow", Config: json.RawMessage(`{"case_type":"kyc_check","input":{"customer_id":"{{case.data.customer_id}}","country":"GB","amount":12.5},"outputs":["decision"]}`)},
+		{ID: "bad", Type: "sub_workflow", Config: json.RawMessage(`{"case_type":"kyc_check","input":{"customer_id":42,"extra":true},"outputs":["risk"]}`)},
+		{ID: "unknown", Type: "sub_workflow", Config: json.RawMessage(`{"case_type":"other","input":{}}`)},
+	}}
+
+	validation := &PublishValidationErrors{Errors: make([]PublishValidationError, 0)}
+	if err := validateSubWorkflowCalls(context.Background(), uuid.New(), workflow, contracts, validation); err != nil {
+		t.Fatalf("validate: %v", err)
+	}
+
+	var messages []string
+	for _, e := range validation.Errors {
+		if e.StepID != "bad" {
+			t.Fatalf("unexpected error for step %q: %#v", e.StepID, e)
+		}
+		messages = append(messages, e.Code+": "+e.Message)
+	}
+	joined := strings.Join(messages, "\n")
+	for _, want := range []string{
+		`SUB_WORKFLOW_INPUT_MISMATCH: Step "bad" input for kyc_check: missing required property "country"`,
+		`property "customer_id" must be string`,
+		`property "extra" is not accepted`,
+		`SUB_WORKFLOW_OUTPUT_MISMATCH: Step "bad" requests output "risk"`,
+	} {
+		if !strings.Contains(joined, want) {
+			t.Fatalf("missing %q in:\n%s", want, joined)
+		}
+	}
+	if len(messages) != 4 {
+		t.Fatalf("expected 4 errors, got:\n%s", joined)
+	}
+}
+
+func TestAddContractErrors_RejectsBadContract(t *testing.T) {
+	validation := &PublishValidationErrors{Errors: make([]PublishValidationError, 0)}
+	addContractErrors(validation, engine.WorkflowAST{Outputs: map[string]string{"decision": "steps.review.result"}})
+	if len(validation.Errors) != 1 || validation.Errors[0].Code != "INVALID_CONTRACT" {
+		t.Fatalf("expected an INVALID_CONTRACT error, got %#v", validation.Errors)
+	}
+
+	validation = &PublishValidationErrors{Errors: make([]PublishValidationError, 0)}
+	addContractErrors(validation, engine.WorkflowAST{InputSchema: json.RawMessage(`{"type": 7}`)})
+	if len(validation.Errors) != 1 {
+		t.Fatalf("expected an uncompilable schema to be rejected, got %#v", validation.Errors)
+	}
+}
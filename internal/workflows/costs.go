@@ -0,0 +1,101 @@
+package workflows
+
+import (
+	"context"
+	"fmt"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+// CostReport is a workflow's spend in cost units across the cases started
+// since a point in time.
+type CostReport struct {
+	WorkflowID  uuid.UUID   `json:"workflow_id"`
+	Since       time.Time   `json:"since"`
+	Executions  int         `json:"executions"`
+	TotalCost   float64     `json:"total_cost"`
+	AverageCost float64     `json:"average_cost"`
+	Steps       []StepCost  `json:"steps"`
+	Daily       []DailyCost `json:"daily"`
+}
+
+// StepCost is one step's spend across its completed runs.
+type StepCost struct {
+	StepID      string  `json:"step_id"`
+	Runs        int     `json:"runs"`
+	TotalCost   float64 `json:"total_cost"`
+	AverageCost float64 `json:"average_cost"`
+}
+
+// DailyCost is the spend of the cases started on one UTC day.
+type DailyCost struct {
+	Day        string  `json:"day"`
+	Executions int     `json:"executions"`
+	Cost       float64 `json:"cost"`
+}
+
+// Costs reports the historical spend of a workflow's cases started since the
+// given time, per step and per day, most expensive steps first.
+func (s *Service) Costs(ctx context.Context, tenantID, workflowID uuid.UUID, since time.Time) (CostReport, error) {
+	report := CostReport{WorkflowID: workflowID, Since: since, Steps: []StepCost{}, Daily: []DailyCost{}}
+	if err := s.db.QueryRowContext(ctx, `
+SELECT COUNT(*), COALESCE(SUM(cost), 0)
+FROM cases
+WHERE tenant_id = $1 AND workflow_id = $2 AND created_at >= $3
+`, tenantID, workflowID, since).Scan(&report.Executions, &report.TotalCost); err != nil {
+		return CostReport{}, fmt.Errorf("load workflow cost: %w", err)
+	}
+	if report.Executions > 0 {
+		report.AverageCost = report.TotalCost / float64(report.Executions)
+	}
+
+	rows, err := s.db.QueryContext(ctx, `
+SELECT cs.step_id, COUNT(*), COALESCE(SUM(cs.cost), 0) AS total
+FROM case_steps cs
+JOIN cases c ON c.id = cs.case_id
+WHERE c.tenant_id = $1 AND c.workflow_id = $2 AND c.created_at >= $3 AND cs.state = 'completed'
+GROUP BY cs.step_id
+ORDER BY total DESC, cs.step_id
+`, tenantID, workflowID, since)
+	if err != nil {
+		return CostReport{}, fmt.Errorf("load step costs: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	for rows.Next() {
+		var step StepCost
+		if err := rows.Scan(&step.StepID, &step.Runs, &step.TotalCost); err != nil {
+			return CostReport{}, fmt.Errorf("scan step cost: %w", err)
+		}
+		if step.Runs > 0 {
+			step.AverageCost = step.TotalCost / float64(step.Runs)
+		}
+		report.Steps = append(report.Steps, step)
+	}
+	if err := rows.Err(); err != nil {
+		return CostReport{}, fmt.Errorf("iterate step costs: %w", err)
+	}
+
+	daily, err := s.db.QueryContext(ctx, `
+SELECT to_char(created_at AT TIME ZONE 'UTC', 'YYYY-MM-DD') AS day, COUNT(*), COALESCE(SUM(cost), 0)
+FROM cases
+WHERE tenant_id = $1 AND workflow_id = $2 AND created_at >= $3
+GROUP BY day
+ORDER BY day
+`, tenantID, workflowID, since)
+	if err != nil {
+		return CostReport{}, fmt.Errorf("load daily costs: %w", err)
+	}
+	defer func() { _ = daily.Close() }()
+	for daily.Next() {
+		var day DailyCost
+		if err := daily.Scan(&day.Day, &day.Executions, &day.Cost); err != nil {
+			return CostReport{}, fmt.Errorf("scan daily cost: %w", err)
+		}
+		report.Daily = append(report.Daily, day)
+	}
+	if err := daily.Err(); err != nil {
+		return CostReport{}, fmt.Errorf("iterate daily costs: %w", err)
+	}
+	return report, nil
+}
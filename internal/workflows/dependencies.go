@@ -0,0 +1,245 @@
+package workflows
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"regexp"
+	"strings"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+// DependencyReport lists what a workflow version depends on outside itself,
+// for reviewing a flow before it is allowed into production.
+type DependencyReport struct {
+	WorkflowID uuid.UUID            `json:"workflow_id"`
+	Version    int                  `json:"version"`
+	Tools      []ToolDependency     `json:"tools"`
+	Protocols  []string             `json:"protocols"`
+	Secrets    []SecretDependency   `json:"secrets"`
+	Endpoints  []EndpointDependency `json:"endpoints"`
+}
+
+type ToolDependency struct {
+	StepID   string `json:"step_id"`
+	Protocol string `json:"protocol"`
+	Tool     string `json:"tool"`
+	Version  string `json:"version,omitempty"`
+	Server   string `json:"server,omitempty"`
+}
+
+type SecretDependency struct {
+	Name  string   `json:"name"`
+	Steps []string `json:"steps"`
+}
+
+// EndpointDependency is an external host a step contacts. Templated
+// endpoints are only known at run time and are reported as written.
+type EndpointDependency struct {
+	Endpoint  string   `json:"endpoint"`
+	Templated bool     `json:"templated"`
+	Steps     []string `json:"steps"`
+}
+
+var secretRefPattern = regexp.MustCompile(`\{\{\s*secrets\.([A-Za-z0-9_.-]+)\s*\}\}`)
+
+// Dependencies reports the tools, protocols, secrets and external endpoints
+// a workflow version uses, derived from its step configs. Version 0 selects
+// the latest published version, falling back to the draft.
+func (s *Service) Dependencies(ctx context.Context, tenantID, workflowID uuid.UUID, version int) (DependencyReport, error) {
+	resolved, astRaw, err := s.loadVersionAST(ctx, tenantID, workflowID, version)
+	if err != nil {
+		return DependencyReport{}, err
+	}
+	var ast engine.WorkflowAST
+	if err := json.Unmarshal(astRaw, &ast); err != nil {
+		return DependencyReport{}, fmt.Errorf("decode workflow ast: %w", err)
+	}
+	report := WorkflowDependencies(ast)
+	report.WorkflowID = workflowID
+	report.Version = resolved
+	return report, nil
+}
+
+// WorkflowDependencies builds the dependency report for a workflow AST.
+func WorkflowDependencies(ast engine.WorkflowAST) DependencyReport {
+	report := DependencyReport{
+		Tools:     []ToolDependency{},
+		Protocols: []string{},
+		Secrets:   []SecretDependency{},
+		Endpoints: []EndpointDependency{},
+	}
+	protocols := map[string]bool{}
+	secrets := map[string][]string{}
+	endpoints := map[string][]string{}
+	for _, step := range ast.Steps {
+		stepID := strings.TrimSpace(step.ID)
+		cfg, err := decodeStepConfig(step)
+		if err != nil {
+			continue
+		}
+		for _, tool := range stepTools(step, cfg) {
+			report.Tools = append(report.Tools, tool)
+			protocols[tool.Protocol] = true
+		}
+		for _, name := range configSecrets(cfg, "") {
+			secrets[name] = appendStep(secrets[name], stepID)
+		}
+		for _, endpoint := range configEndpoints(strings.TrimSpace(step.Type), cfg, "") {
+			endpoints[endpoint] = appendStep(endpoints[endpoint], stepID)
+		}
+	}
+	report.Protocols = sortedKeys(protocols)
+	for _, name := range sortedKeys(secrets) {
+		report.Secrets = append(report.Secrets, SecretDependency{Name: name, Steps: secrets[name]})
+	}
+	for _, endpoint := range sortedKeys(endpoints) {
+		report.Endpoints = append(report.Endpoints, EndpointDependency{
+			Endpoint:  endpoint,
+			Templated: strings.Contains(endpoint, "{{"),
+			Steps:     endpoints[endpoint],
+		})
+	}
+	return report
+}
+
+// stepTools lists the tools a step calls, keyed by the protocol used to
+// reach them.
+func stepTools(step engine.WorkflowStep, cfg map[string]any) []ToolDependency {
+	stepID := strings.TrimSpace(step.ID)
+	str := func(key string) string {
+		value, _ := cfg[key].(string)
+		return strings.TrimSpace(value)
+	}
+	tool := func(protocol, name string) []ToolDependency {
+		if name == "" {
+			return nil
+		}
+		return []ToolDependency{{StepID: stepID, Protocol: protocol, Tool: name}}
+	}
+	switch strings.TrimSpace(step.Type) {
+	case "integration":
+		name := str("connector")
+		if action := str("action"); name != "" && action != "" {
+			name += "/" + action
+		}
+		return tool("connector", name)
+	case "mcp-client":
+		if str("tool") == "" {
+			return nil
+		}
+		return []ToolDependency{{StepID: stepID, Protocol: "mcp", Tool: str("tool"), Version: str("tool_version"), Server: str("server_url")}}
+	case "grpc-client":
+		if str("method") == "" {
+			return nil
+		}
+		return []ToolDependency{{StepID: stepID, Protocol: "grpc", Tool: str("method"), Server: str("address")}}
+	case "custom-tool":
+		return tool("custom_http", str("tool"))
+	case "plugin":
+		return tool("plugin", str("plugin"))
+	case "ai_component":
+		return tool("ai_component", str("component"))
+	case "sub_workflow":
+		return tool("sub_workflow", str("case_type"))
+	case "agentic":
+		policy, _ := cfg["tool_policy"].(map[string]any)
+		refs, _ := policy["tools"].([]any)
+		var tools []ToolDependency
+		for _, raw := range refs {
+			ref, _ := raw.(map[string]any)
+			name, _ := ref["ref"].(string)
+			tools = append(tools, tool("agentic", strings.TrimSpace(name))...)
+		}
+		return tools
+	}
+	return nil
+}
+
+// configSecrets collects the secrets a config refers to, either through a
+// {{secrets.*}} template or by name in an auth_secret or secret_ref field.
+func configSecrets(value any, key string) []string {
+	var out []string
+	switch v := value.(type) {
+	case map[string]any:
+		for _, child := range sortedKeys(v) {
+			out = append(out, configSecrets(v[child], child)...)
+		}
+	case []any:
+		for _, item := range v {
+			out = append(out, configSecrets(item, key)...)
+		}
+	case string:
+		for _, match := range secretRefPattern.FindAllStringSubmatch(v, -1) {
+			out = append(out, match[1])
+		}
+		name := strings.TrimSpace(v)
+		if (key == "auth_secret" || strings.HasSuffix(key, "secret_ref")) && name != "" && !strings.Contains(name, "{{") {
+			out = append(out, name)
+		}
+	}
+	return out
+}
+
+// configEndpoints collects the external endpoints a config contacts: any
+// http(s) or ws(s) URL, any value of a url/endpoint field, and the address of
+// a grpc-client step. URLs are reduced to their scheme and host.
+func configEndpoints(stepType string, value any, key string) []string {
+	var out []string
+	switch v := value.(type) {
+	case map[string]any:
+		for _, child := range sortedKeys(v) {
+			out = append(out, configEndpoints(stepType, v[child], child)...)
+		}
+	case []any:
+		for _, item := range v {
+			out = append(out, configEndpoints(stepType, item, key)...)
+		}
+	case string:
+		raw := strings.TrimSpace(v)
+		if raw == "" {
+			break
+		}
+		if hasURLScheme(raw) || key == "url" || key == "endpoint" || strings.HasSuffix(key, "_url") || (stepType == "grpc-client" && key == "address") {
+			out = append(out, endpointHost(raw))
+		}
+	}
+	return out
+}
+
+func hasURLScheme(raw string) bool {
+	lower := strings.ToLower(raw)
+	for _, scheme := range []string{"http://", "https://", "ws://", "wss://"} {
+		if strings.HasPrefix(lower, scheme) {
+			return true
+		}
+	}
+	return false
+}
+
+// endpointHost trims a URL to its scheme and host, dropping any credentials,
+// path and query. Values without a scheme are cut at the first path segment.
+func endpointHost(raw string) string {
+	prefix, rest := "", raw
+	if i := strings.Index(raw, "://"); i >= 0 {
+		prefix, rest = strings.ToLower(raw[:i+3]), raw[i+3:]
+	}
+	if i := strings.IndexAny(rest, "/?#"); i >= 0 {
+		rest = rest[:i]
+	}
+	if i := strings.LastIndex(rest, "@"); i >= 0 {
+		rest = rest[i+1:]
+	}
+	return prefix + rest
+}
+
+func appendStep(steps []string, stepID string) []string {
+	for _, existing := range steps {
+		if existing == stepID {
+			return steps
+		}
+	}
+	return append(steps, stepID)
+}
@@ -0,0 +1,44 @@
+package workflows
+
+import (
+	"encoding/json"
+	"reflect"
+	"testing"
+
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+func TestWorkflowDependencies(t *testing.T) {
+	ast := engine.WorkflowAST{Steps: []engine.WorkflowStep{
+		{ID: "fetch", Type: "integration", Config: json.RawMessage(`{"connector":"http","action":"request","input":{"url":"https://user:pw@api.example.com/v1/loans?id=1","headers":{"Authorization":"Bearer {{secrets.crm_token}}"}}}`)},
+		{ID: "score", Type: "mcp-client", Config: json.RawMessage(`{"server_url":"https://mcp.example.com/rpc","auth_secret":"mcp_key","tool":"score","tool_version":"2"}`)},
+		{ID: "ledger", Type: "grpc-client", Config: json.RawMessage(`{"address":"ledger.internal:443","method":"ledger.Ledger/Post"}`)},
+		{ID: "notify", Type: "custom-tool", Config: json.RawMessage(`{"tool":"send_sms","arguments":{"callback_url":"{{env.callback}}/sms"}}`)},
+		{ID: "refetch", Type: "integration", Config: json.RawMessage(`{"connector":"http","action":"request","input":{"url":"https://api.example.com/v2","token":"{{ secrets.crm_token }}"}}`)},
+		{ID: "review", Type: "human_task"},
+	}}
+	got := WorkflowDependencies(ast)
+
+	if want := []string{"connector", "custom_http", "grpc", "mcp"}; !reflect.DeepEqual(got.Protocols, want) {
+		t.Fatalf("protocols = %v, want %v", got.Protocols, want)
+	}
+	if len(got.Tools) != 5 || got.Tools[1] != (ToolDependency{StepID: "score", Protocol: "mcp", Tool: "score", Version: "2", Server: "https://mcp.example.com/rpc"}) {
+		t.Fatalf("unexpected tools: %+v", got.Tools)
+	}
+	wantSecrets := []SecretDependency{
+		{Name: "crm_token", Steps: []string{"fetch", "refetch"}},
+		{Name: "mcp_key", Steps: []string{"score"}},
+	}
+	if !reflect.DeepEqual(got.Secrets, wantSecrets) {
+		t.Fatalf("secrets = %+v, want %+v", got.Secrets, wantSecrets)
+	}
+	wantEndpoints := []EndpointDependency{
+		{Endpoint: "https://api.example.com", Steps: []string{"fetch", "refetch"}},
+		{Endpoint: "https://mcp.example.com", Steps: []string{"score"}},
+		{Endpoint: "ledger.internal:443", Steps: []string{"ledger"}},
+		{Endpoint: "{{env.callback}}", Templated: true, Steps: []string{"notify"}},
+	}
+	if !reflect.DeepEqual(got.Endpoints, wantEndpoints) {
+		t.Fatalf("endpoints = %+v, want %+v", got.Endpoints, wantEndpoints)
+	}
+}
@@ -0,0 +1,147 @@
+package workflows
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"strings"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+const (
+	DiagramMermaid = "mermaid"
+	DiagramDOT     = "dot"
+)
+
+type diagramEdge struct {
+	from, to string
+	label    string
+}
+
+// diagramEdges lists the edges between steps. Outcome edges are labelled
+// with their outcome and replace the plain dependency edge between the same
+// steps. Dependency edges into a guarded step are labelled with its
+// condition.
+func diagramEdges(ast engine.WorkflowAST) []diagramEdge {
+	routed := map[[2]string]bool{}
+	var outcomeEdges []diagramEdge
+	for _, step := range ast.Steps {
+		for _, outcome := range sortedKeys(step.Outcomes) {
+			for _, target := range step.Outcomes[outcome] {
+				outcomeEdges = append(outcomeEdges, diagramEdge{from: step.ID, to: target, label: outcome})
+				routed[[2]string{step.ID, target}] = true
+			}
+		}
+	}
+	var edges []diagramEdge
+	for _, step := range ast.Steps {
+		for _, dep := range step.DependsOn {
+			if !routed[[2]string{dep, step.ID}] {
+				edges = append(edges, diagramEdge{from: dep, to: step.ID, label: step.Condition})
+			}
+		}
+	}
+	return append(edges, outcomeEdges...)
+}
+
+// rootCondition returns the guard of a step with no dependencies, which has
+// no edge to carry it and is shown on the step itself.
+func rootCondition(step engine.WorkflowStep) string {
+	if len(step.DependsOn) > 0 {
+		return ""
+	}
+	return step.Condition
+}
+
+// MermaidDiagram draws the step graph as a Mermaid flowchart.
+func MermaidDiagram(ast engine.WorkflowAST) string {
+	nodes := make(map[string]string, len(ast.Steps))
+	var b strings.Builder
+	b.WriteString("flowchart TD\n")
+	for i, step := range ast.Steps {
+		node := fmt.Sprintf("s%d", i+1)
+		nodes[step.ID] = node
+		label := mermaidText(step.ID) + "<br/><small>" + mermaidText(step.Type) + "</small>"
+		if cond := rootCondition(step); cond != "" {
+			label += "<br/><small>when " + mermaidText(cond) + "</small>"
+		}
+		fmt.Fprintf(&b, "    %s[\"%s\"]\n", node, label)
+	}
+	for _, edge := range diagramEdges(ast) {
+		from, ok := nodes[edge.from]
+		to, ok2 := nodes[edge.to]
+		if !ok || !ok2 {
+			continue
+		}
+		if edge.label == "" {
+			fmt.Fprintf(&b, "    %s --> %s\n", from, to)
+		} else {
+			fmt.Fprintf(&b, "    %s -- \"%s\" --> %s\n", from, mermaidText(edge.label), to)
+		}
+	}
+	return b.String()
+}
+
+// mermaidText escapes text for a quoted Mermaid label.
+func mermaidText(s string) string {
+	return strings.NewReplacer(`"`, "#quot;", "<", "#lt;", ">", "#gt;", "\n", " ").Replace(s)
+}
+
+// DOTDiagram draws the step graph in Graphviz DOT.
+func DOTDiagram(name string, ast engine.WorkflowAST) string {
+	known := make(map[string]bool, len(ast.Steps))
+	var b strings.Builder
+	fmt.Fprintf(&b, "digraph %s {\n", dotQuote(name))
+	b.WriteString("    rankdir=TB;\n    node [shape=box, style=rounded];\n")
+	for _, step := range ast.Steps {
+		known[step.ID] = true
+		label := step.ID + "\n" + step.Type
+		if cond := rootCondition(step); cond != "" {
+			label += "\nwhen " + cond
+		}
+		fmt.Fprintf(&b, "    %s [label=%s];\n", dotQuote(step.ID), dotQuote(label))
+	}
+	for _, edge := range diagramEdges(ast) {
+		if !known[edge.from] || !known[edge.to] {
+			continue
+		}
+		if edge.label == "" {
+			fmt.Fprintf(&b, "    %s -> %s;\n", dotQuote(edge.from), dotQuote(edge.to))
+		} else {
+			fmt.Fprintf(&b, "    %s -> %s [label=%s];\n", dotQuote(edge.from), dotQuote(edge.to), dotQuote(edge.label))
+		}
+	}
+	b.WriteString("}\n")
+	return b.String()
+}
+
+func dotQuote(s string) string {
+	return `"` + strings.NewReplacer(`\`, `\\`, `"`, `\"`, "\n", `\n`).Replace(s) + `"`
+}
+
+// Diagram renders a workflow version's steps as a Mermaid flowchart or a DOT
+// graph. Version 0 selects the latest published version, falling back to the
+// draft.
+func (s *Service) Diagram(ctx context.Context, tenantID, workflowID uuid.UUID, version int, format string) (string, error) {
+	if format != DiagramMermaid && format != DiagramDOT {
+		return "", fmt.Errorf("unsupported diagram format %q", format)
+	}
+	_, astRaw, err := s.loadVersionAST(ctx, tenantID, workflowID, version)
+	if err != nil {
+		return "", err
+	}
+	var ast engine.WorkflowAST
+	if err := json.Unmarshal(astRaw, &ast); err != nil {
+		return "", fmt.Errorf("decode workflow ast: %w", err)
+	}
+	if format == DiagramMermaid {
+		return MermaidDiagram(ast), nil
+	}
+	var name string
+	if err := s.db.QueryRowContext(ctx, `SELECT name FROM workflows WHERE id = $1 AND tenant_id = $2`, workflowID, tenantID).Scan(&name); err != nil {
+		return "", fmt.Errorf("load workflow name: %w", err)
+	}
+	return DOTDiagram(name, ast), nil
+}
@@ -0,0 +1,55 @@
+package workflows
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+func diagramTestAST() engine.WorkflowAST {
+	return engine.WorkflowAST{Steps: []engine.WorkflowStep{
+		{ID: "intake", Type: "rule", Condition: "case.data.amount > 0"},
+		{ID: "review", Type: "human_task", DependsOn: []string{"intake"}, Outcomes: map[string][]string{"approved": {"pay"}, "rejected": {"notify"}}},
+		{ID: "pay", Type: "integration", DependsOn: []string{"review"}},
+		{ID: "notify", Type: "notification", DependsOn: []string{"review"}, Condition: `case.data.email != ""`},
+		{ID: "archive", Type: "integration", DependsOn: []string{"pay"}, Condition: "case.data.keep == true"},
+	}}
+}
+
+func TestMermaidDiagram(t *testing.T) {
+	got := MermaidDiagram(diagramTestAST())
+	for _, want := range []string{
+		"flowchart TD\n",
+		`s1["intake<br/><small>rule</small><br/><small>when case.data.amount #gt; 0</small>"]`,
+		"s1 --> s2\n",
+		`s2 -- "approved" --> s3`,
+		`s2 -- "rejected" --> s4`,
+		`s3 -- "case.data.keep == true" --> s5`,
+	} {
+		if !strings.Contains(got, want) {
+			t.Fatalf("expected %q in diagram:\n%s", want, got)
+		}
+	}
+	if strings.Contains(got, "s2 --> s3") {
+		t.Fatalf("expected the outcome edge to replace the dependency edge:\n%s", got)
+	}
+}
+
+func TestDOTDiagram(t *testing.T) {
+	got := DOTDiagram(`Refunds "v2"`, diagramTestAST())
+	for _, want := range []string{
+		`digraph "Refunds \"v2\"" {`,
+		`"intake" [label="intake\nrule\nwhen case.data.amount > 0"];`,
+		`"intake" -> "review";`,
+		`"review" -> "pay" [label="approved"];`,
+		`"pay" -> "archive" [label="case.data.keep == true"];`,
+	} {
+		if !strings.Contains(got, want) {
+			t.Fatalf("expected %q in diagram:\n%s", want, got)
+		}
+	}
+	if !strings.HasSuffix(got, "}\n") {
+		t.Fatalf("expected a closed graph:\n%s", got)
+	}
+}
@@ -0,0 +1,388 @@
+package workflows
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"html/template"
+	"strings"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/channels"
+	"github.com/neural-chilli/aceryx/internal/engine"
+	"github.com/neural-chilli/aceryx/internal/redaction"
+)
+
+const (
+	DocsFormatMarkdown = "markdown"
+	DocsFormatHTML     = "html"
+	DocsFormatJSON     = "json"
+)
+
+// Docs is the generated documentation for one workflow version.
+type Docs struct {
+	WorkflowID  uuid.UUID    `json:"workflow_id"`
+	Name        string       `json:"name"`
+	CaseType    string       `json:"case_type"`
+	Description string       `json:"description,omitempty"`
+	Version     int          `json:"version"`
+	Status      string       `json:"status"`
+	Triggers    []DocTrigger `json:"triggers"`
+	Steps       []DocStep    `json:"steps"`
+	Contract    Contract     `json:"contract"`
+	Mermaid     string       `json:"mermaid"`
+}
+
+// DocTrigger is a channel that starts cases for the workflow. Schedule
+// channels also list their cron expression and next fire times.
+type DocTrigger struct {
+	Name     string      `json:"name"`
+	Type     string      `json:"type"`
+	Enabled  bool        `json:"enabled"`
+	Schedule string      `json:"schedule,omitempty"`
+	Timezone string      `json:"timezone,omitempty"`
+	NextRuns []time.Time `json:"next_runs,omitempty"`
+}
+
+// docScheduleRuns is how many upcoming fire times docs list per schedule.
+const docScheduleRuns = 5
+
+// DocStep describes a step. Description comes from the step's metadata, or
+// else from the tool or connector action it calls. Config is redacted.
+type DocStep struct {
+	ID          string              `json:"id"`
+	Type        string              `json:"type"`
+	Description string              `json:"description,omitempty"`
+	DependsOn   []string            `json:"depends_on,omitempty"`
+	Condition   string              `json:"condition,omitempty"`
+	Outcomes    map[string][]string `json:"outcomes,omitempty"`
+	Config      json.RawMessage     `json:"config,omitempty"`
+}
+
+type toolDescriptionCatalog interface {
+	ToolDescription(ctx context.Context, tenantID uuid.UUID, stepType string, config json.RawMessage) string
+}
+
+// SetToolDescriptionCatalogs lets generated docs describe tool steps. The
+// catalogs are asked in turn and the first description wins.
+func (s *Service) SetToolDescriptionCatalogs(catalogs ...toolDescriptionCatalog) {
+	s.toolDescriptions = catalogs
+}
+
+// Docs documents a workflow version. Version 0 selects the latest published
+// version, falling back to the draft.
+func (s *Service) Docs(ctx context.Context, tenantID, workflowID uuid.UUID, version int) (Docs, error) {
+	version, astRaw, err := s.loadVersionAST(ctx, tenantID, workflowID, version)
+	if err != nil {
+		return Docs{}, err
+	}
+	out := Docs{WorkflowID: workflowID, Version: version, Triggers: []DocTrigger{}, Steps: []DocStep{}}
+	err = s.db.QueryRowContext(ctx, `
+SELECT w.name, COALESCE(ct.name, w.case_type), wv.status
+FROM workflows w
+JOIN workflow_versions wv ON wv.workflow_id = w.id AND wv.version = $3
+LEFT JOIN case_types ct ON ct.tenant_id = w.tenant_id AND ct.id::text = w.case_type
+WHERE w.id = $1 AND w.tenant_id = $2
+`, workflowID, tenantID, version).Scan(&out.Name, &out.CaseType, &out.Status)
+	if err != nil {
+		return Docs{}, fmt.Errorf("load workflow for docs: %w", err)
+	}
+	if out.Triggers, err = s.docTriggers(ctx, tenantID, workflowID); err != nil {
+		return Docs{}, err
+	}
+
+	var ast engine.WorkflowAST
+	if err := json.Unmarshal(astRaw, &ast); err != nil {
+		return Docs{}, fmt.Errorf("decode workflow ast: %w", err)
+	}
+	var meta struct {
+		Description string `json:"description"`
+	}
+	_ = json.Unmarshal(astRaw, &meta)
+	out.Description = strings.TrimSpace(meta.Description)
+	out.Contract = Contract{InputSchema: ast.InputSchema, Outputs: ast.Outputs}
+	for _, step := range ast.Steps {
+		out.Steps = append(out.Steps, DocStep{
+			ID:          step.ID,
+			Type:        step.Type,
+			Description: s.stepDescription(ctx, tenantID, step),
+			DependsOn:   step.DependsOn,
+			Condition:   step.Condition,
+			Outcomes:    step.Outcomes,
+			Config:      redactDocConfig(s.redactor, step.Config),
+		})
+	}
+	out.Mermaid = MermaidDiagram(ast)
+	return out, nil
+}
+
+// docTriggers lists the channels bound to the workflow, and those bound only
+// to its case type.
+func (s *Service) docTriggers(ctx context.Context, tenantID, workflowID uuid.UUID) ([]DocTrigger, error) {
+	rows, err := s.db.QueryContext(ctx, `
+SELECT c.name, c.type, c.enabled, c.config
+FROM channels c
+JOIN workflows w ON w.id = $2 AND w.tenant_id = c.tenant_id
+JOIN case_types ct ON ct.id = c.case_type_id
+WHERE c.tenant_id = $1
+  AND c.deleted_at IS NULL
+  AND (c.workflow_id = w.id OR (c.workflow_id IS NULL AND (ct.id::text = w.case_type OR ct.name = w.case_type)))
+ORDER BY c.name
+`, tenantID, workflowID)
+	if err != nil {
+		return nil, fmt.Errorf("list workflow triggers: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	out := []DocTrigger{}
+	for rows.Next() {
+		var (
+			trigger DocTrigger
+			config  []byte
+		)
+		if err := rows.Scan(&trigger.Name, &trigger.Type, &trigger.Enabled, &config); err != nil {
+			return nil, fmt.Errorf("scan workflow trigger: %w", err)
+		}
+		if channels.ChannelType(trigger.Type) == channels.ChannelSchedule {
+			var cfg channels.ScheduleConfig
+			if err := json.Unmarshal(config, &cfg); err == nil {
+				trigger.Schedule, trigger.Timezone = cfg.Cron, cfg.Timezone
+				calendars, err := channels.NewPostgresStore(s.db).ScheduleCalendars(ctx, tenantID, cfg.Calendars)
+				if err != nil {
+					return nil, err
+				}
+				trigger.NextRuns, _ = cfg.NextRuns(time.Now(), docScheduleRuns, calendars)
+			}
+		}
+		out = append(out, trigger)
+	}
+	if err := rows.Err(); err != nil {
+		return nil, fmt.Errorf("iterate workflow triggers: %w", err)
+	}
+	return out, nil
+}
+
+func (s *Service) stepDescription(ctx context.Context, tenantID uuid.UUID, step engine.WorkflowStep) string {
+	if description, ok := step.Metadata["description"].(string); ok && strings.TrimSpace(description) != "" {
+		return strings.TrimSpace(description)
+	}
+	for _, catalog := range s.toolDescriptions {
+		if description := strings.TrimSpace(catalog.ToolDescription(ctx, tenantID, step.Type, step.Config)); description != "" {
+			return description
+		}
+	}
+	if s.connectors != nil && step.Type == "integration" {
+		if cfg, err := decodeStepConfig(step); err == nil {
+			connectorKey, _ := cfg["connector"].(string)
+			actionKey, _ := cfg["action"].(string)
+			if action, ok := s.connectors.GetAction(connectorKey, actionKey); ok {
+				return action.Description
+			}
+		}
+	}
+	return ""
+}
+
+var docSensitiveKeySuffixes = []string{"password", "secret", "token", "api_key", "apikey", "credentials", "private_key"}
+
+// redactDocConfig applies the redaction rules, the built-in ones when
+// redactor is nil, and also masks values under keys such as password or
+// api_key, since step configs can hold literal credentials. Keys ending in
+// _ref name a vault secret and are kept.
+func redactDocConfig(redactor *redaction.Redactor, raw json.RawMessage) json.RawMessage {
+	if redactor == nil {
+		redactor = redaction.Default()
+	}
+	raw = redactor.RedactJSON(raw)
+	if len(bytes.TrimSpace(raw)) == 0 {
+		return nil
+	}
+	var value any
+	if err := json.Unmarshal(raw, &value); err != nil {
+		return raw
+	}
+	out, err := json.Marshal(maskSensitiveKeys(value))
+	if err != nil {
+		return raw
+	}
+	return out
+}
+
+func maskSensitiveKeys(value any) any {
+	switch v := value.(type) {
+	case map[string]any:
+		for key, item := range v {
+			lower := strings.ToLower(key)
+			if !strings.HasSuffix(lower, "_ref") && isSensitiveKey(lower) {
+				if _, nested := item.(map[string]any); !nested {
+					v[key] = redaction.Mask
+					continue
+				}
+			}
+			v[key] = maskSensitiveKeys(item)
+		}
+	case []any:
+		for i := range v {
+			v[i] = maskSensitiveKeys(v[i])
+		}
+	}
+	return value
+}
+
+func isSensitiveKey(key string) bool {
+	for _, suffix := range docSensitiveKeySuffixes {
+		if strings.HasSuffix(key, suffix) {
+			return true
+		}
+	}
+	return false
+}
+
+// ScheduleZone names the timezone a schedule trigger runs in.
+func (t DocTrigger) ScheduleZone() string {
+	if t.Timezone == "" {
+		return "UTC"
+	}
+	return t.Timezone
+}
+
+// FormatNextRuns formats NextRuns in the schedule's timezone, with the zone
+// abbreviation so daylight-saving changes are visible.
+func (t DocTrigger) FormatNextRuns() []string {
+	out := make([]string, 0, len(t.NextRuns))
+	for _, at := range t.NextRuns {
+		out = append(out, at.Format("Mon 2006-01-02 15:04 MST"))
+	}
+	return out
+}
+
+// RenderDocsMarkdown renders docs as a Markdown document with a Mermaid
+// diagram.
+func RenderDocsMarkdown(d Docs) string {
+	var b strings.Builder
+	fmt.Fprintf(&b, "# %s\n\n", d.Name)
+	if d.Description != "" {
+		fmt.Fprintf(&b, "%s\n\n", d.Description)
+	}
+	fmt.Fprintf(&b, "- **Case type:** %s\n- **Version:** %d (%s)\n\n", markdownCell(d.CaseType), d.Version, d.Status)
+
+	b.WriteString("## Triggers\n\n")
+	if len(d.Triggers) == 0 {
+		b.WriteString("No channels start cases for this workflow.\n\n")
+	} else {
+		b.WriteString("| Channel | Type | Enabled |\n|---|---|---|\n")
+		for _, t := range d.Triggers {
+			fmt.Fprintf(&b, "| %s | %s | %t |\n", markdownCell(t.Name), t.Type, t.Enabled)
+		}
+		b.WriteString("\n")
+		for _, t := range d.Triggers {
+			if t.Schedule == "" {
+				continue
+			}
+			fmt.Fprintf(&b, "**%s** runs on `%s` (%s). Next runs: %s.\n\n", markdownCell(t.Name), t.Schedule, t.ScheduleZone(), strings.Join(t.FormatNextRuns(), ", "))
+		}
+	}
+
+	b.WriteString("## Diagram\n\n```mermaid\n")
+	b.WriteString(d.Mermaid)
+	b.WriteString("```\n\n")
+
+	b.WriteString("## Steps\n\n| Step | Type | Depends on | Description |\n|---|---|---|---|\n")
+	for _, step := range d.Steps {
+		fmt.Fprintf(&b, "| `%s` | %s | %s | %s |\n", step.ID, step.Type, markdownCell(strings.Join(step.DependsOn, ", ")), markdownCell(step.Description))
+	}
+	b.WriteString("\n")
+	for _, step := range d.Steps {
+		if step.Condition == "" && len(step.Config) == 0 {
+			continue
+		}
+		fmt.Fprintf(&b, "### %s\n\n", step.ID)
+		if step.Condition != "" {
+			fmt.Fprintf(&b, "Runs when `%s`.\n\n", step.Condition)
+		}
+		if len(step.Config) > 0 {
+			fmt.Fprintf(&b, "```json\n%s\n```\n\n", indentJSON(step.Config))
+		}
+	}
+
+	b.WriteString("## Contract\n\n### Input\n\n")
+	if len(d.Contract.InputSchema) == 0 {
+		b.WriteString("Any case data is accepted.\n\n")
+	} else {
+		fmt.Fprintf(&b, "```json\n%s\n```\n\n", indentJSON(d.Contract.InputSchema))
+	}
+	b.WriteString("### Outputs\n\n")
+	if len(d.Contract.Outputs) == 0 {
+		b.WriteString("No outputs are declared.\n")
+	} else {
+		b.WriteString("| Output | Source |\n|---|---|\n")
+		for _, name := range sortedKeys(d.Contract.Outputs) {
+			fmt.Fprintf(&b, "| %s | `%s` |\n", markdownCell(name), d.Contract.Outputs[name])
+		}
+	}
+	return b.String()
+}
+
+var docsHTML = template.Must(template.New("docs").Funcs(template.FuncMap{
+	"join":   strings.Join,
+	"indent": indentJSON,
+	"keys":   sortedKeys[string],
+}).Parse(`<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{{.Name}}</title>
+<script type="module">import mermaid from "https://cdn.jsdelivr.net/npm/mermaid@11/dist/mermaid.esm.min.mjs"; mermaid.initialize({ startOnLoad: true });</script>
+</head>
+<body>
+<h1>{{.Name}}</h1>
+{{with .Description}}<p>{{.}}</p>{{end}}
+<ul><li><strong>Case type:</strong> {{.CaseType}}</li><li><strong>Version:</strong> {{.Version}} ({{.Status}})</li></ul>
+<h2>Triggers</h2>
+{{if .Triggers}}<table><tr><th>Channel</th><th>Type</th><th>Enabled</th></tr>
+{{range .Triggers}}<tr><td>{{.Name}}</td><td>{{.Type}}</td><td>{{.Enabled}}</td></tr>
+{{end}}</table>
+{{range .Triggers}}{{if .Schedule}}<p><strong>{{.Name}}</strong> runs on <code>{{.Schedule}}</code> ({{.ScheduleZone}}). Next runs: {{join .FormatNextRuns ", "}}.</p>
+{{end}}{{end}}{{else}}<p>No channels start cases for this workflow.</p>{{end}}
+<h2>Diagram</h2>
+<pre class="mermaid">{{.Mermaid}}</pre>
+<h2>Steps</h2>
+<table><tr><th>Step</th><th>Type</th><th>Depends on</th><th>Description</th></tr>
+{{range .Steps}}<tr><td><code>{{.ID}}</code></td><td>{{.Type}}</td><td>{{join .DependsOn ", "}}</td><td>{{.Description}}</td></tr>
+{{end}}</table>
+{{range .Steps}}{{if or .Condition .Config}}<h3>{{.ID}}</h3>
+{{with .Condition}}<p>Runs when <code>{{.}}</code>.</p>{{end}}
+{{with .Config}}<pre><code>{{indent .}}</code></pre>{{end}}
+{{end}}{{end}}<h2>Contract</h2>
+<h3>Input</h3>
+{{with .Contract.InputSchema}}<pre><code>{{indent .}}</code></pre>{{else}}<p>Any case data is accepted.</p>{{end}}
+<h3>Outputs</h3>
+{{with .Contract.Outputs}}<table><tr><th>Output</th><th>Source</th></tr>
+{{range $name := keys .}}<tr><td>{{$name}}</td><td><code>{{index $.Contract.Outputs $name}}</code></td></tr>
+{{end}}</table>{{else}}<p>No outputs are declared.</p>{{end}}
+</body>
+</html>
+`))
+
+// RenderDocsHTML renders docs as a standalone HTML page. The diagram is
+// drawn by Mermaid loaded from a CDN, and shows as text without it.
+func RenderDocsHTML(d Docs) (string, error) {
+	var buf bytes.Buffer
+	if err := docsHTML.Execute(&buf, d); err != nil {
+		return "", fmt.Errorf("render workflow docs: %w", err)
+	}
+	return buf.String(), nil
+}
+
+func indentJSON(raw json.RawMessage) string {
+	var buf bytes.Buffer
+	if err := json.Indent(&buf, raw, "", "  "); err != nil {
+		return string(raw)
+	}
+	return buf.String()
+}
+
+func markdownCell(s string) string {
+	return strings.NewReplacer("|", `\|`, "\n", " ").Replace(s)
+}
@@ -0,0 +1,54 @@
+package workflows
+
+import (
+	"encoding/json"
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestRedactDocConfigMasksCredentials(t *testing.T) {
+	raw := json.RawMessage(`{"url":"https://api.example.com","api_key":"sk-live","auth":{"password":"hunter2","secret_ref":"crm"},"max_tokens":512}`)
+	var got map[string]any
+	if err := json.Unmarshal(redactDocConfig(nil, raw), &got); err != nil {
+		t.Fatalf("decode redacted config: %v", err)
+	}
+	auth := got["auth"].(map[string]any)
+	if got["api_key"] != "[REDACTED]" || auth["password"] != "[REDACTED]" {
+		t.Fatalf("expected credentials to be masked, got %v", got)
+	}
+	if auth["secret_ref"] != "crm" || got["max_tokens"] != float64(512) || got["url"] != "https://api.example.com" {
+		t.Fatalf("expected other values to be kept, got %v", got)
+	}
+}
+
+func TestRenderDocs(t *testing.T) {
+	docs := Docs{
+		Name:     "Refunds",
+		CaseType: "refund",
+		Version:  2,
+		Status:   "published",
+		Triggers: []DocTrigger{
+			{Name: "Refund form", Type: "form", Enabled: true},
+			{Name: "Nightly", Type: "schedule", Enabled: true, Schedule: "0 2 * * *", NextRuns: []time.Time{time.Date(2026, 10, 19, 2, 0, 0, 0, time.UTC)}},
+		},
+		Steps: []DocStep{
+			{ID: "check", Type: "rule", Description: "Checks <amount> | limit", Condition: "case.data.amount > 0"},
+		},
+		Contract: Contract{Outputs: map[string]string{"approved": "steps.check.output.ok"}},
+		Mermaid:  "flowchart TD\n    s1[\"check\"]\n",
+	}
+	md := RenderDocsMarkdown(docs)
+	for _, want := range []string{"# Refunds", "| Refund form | form | true |", "**Nightly** runs on `0 2 * * *` (UTC). Next runs: Mon 2026-10-19 02:00 UTC.", "```mermaid\nflowchart TD", `Checks <amount> \| limit`, "Runs when `case.data.amount > 0`.", "| approved | `steps.check.output.ok` |"} {
+		if !strings.Contains(md, want) {
+			t.Fatalf("expected %q in markdown:\n%s", want, md)
+		}
+	}
+	page, err := RenderDocsHTML(docs)
+	if err != nil {
+		t.Fatalf("render html: %v", err)
+	}
+	if !strings.Contains(page, `<pre class="mermaid">`) || !strings.Contains(page, "Checks &lt;amount&gt; | limit") || !strings.Contains(page, "Next runs: Mon 2026-10-19 02:00 UTC.") {
+		t.Fatalf("unexpected html:\n%s", page)
+	}
+}
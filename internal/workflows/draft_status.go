@@ -0,0 +1,97 @@
+package workflows
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"fmt"
+
+	"github.com/google/uuid"
+)
+
+// DraftEditDiscard is the source of the edit that resets the draft to the
+// published version.
+const DraftEditDiscard = "discard"
+
+// ErrNoPublishedVersion is returned when a draft is discarded before the
+// workflow has ever been published.
+var ErrNoPublishedVersion = errors.New("workflow has no published version")
+
+// DraftStatus compares a workflow's draft with the version its cases,
+// schedules and webhooks run. Changed is true when the draft has edits that
+// are not published; PublishedVersion is nil until the first publish. Edits
+// counts the draft's logged edits that have not been undone.
+type DraftStatus struct {
+	DraftVersion     int  `json:"draft_version"`
+	PublishedVersion *int `json:"published_version,omitempty"`
+	Changed          bool `json:"changed"`
+	Edits            int  `json:"edits"`
+}
+
+// DraftStatus reports whether the draft differs from the published version.
+func (s *Service) DraftStatus(ctx context.Context, tenantID, workflowID uuid.UUID) (DraftStatus, error) {
+	var (
+		status    DraftStatus
+		published sql.NullInt64
+	)
+	err := s.db.QueryRowContext(ctx, `
+SELECT d.version,
+       p.version,
+       p.version IS NULL OR d.ast IS DISTINCT FROM p.ast,
+       (SELECT COUNT(*) FROM workflow_draft_history h WHERE h.workflow_id = w.id AND h.draft_version = d.version AND NOT h.undone)
+FROM workflows w
+JOIN LATERAL (
+    SELECT version, ast FROM workflow_versions
+    WHERE workflow_id = w.id AND status = 'draft'
+    ORDER BY version DESC LIMIT 1
+) d ON true
+LEFT JOIN LATERAL (
+    SELECT version, ast FROM workflow_versions
+    WHERE workflow_id = w.id AND status = 'published'
+    ORDER BY version DESC LIMIT 1
+) p ON true
+WHERE w.id = $1 AND w.tenant_id = $2
+`, workflowID, tenantID).Scan(&status.DraftVersion, &published, &status.Changed, &status.Edits)
+	if err != nil {
+		return DraftStatus{}, err
+	}
+	if published.Valid {
+		version := int(published.Int64)
+		status.PublishedVersion = &version
+	}
+	return status, nil
+}
+
+// DiscardDraft resets the draft to the published version. The reset is
+// logged like any other edit, so it can be undone.
+func (s *Service) DiscardDraft(ctx context.Context, tenantID, actorID, workflowID uuid.UUID) (json.RawMessage, error) {
+	var (
+		astRaw     []byte
+		yamlSource string
+	)
+	err := s.db.QueryRowContext(ctx, `
+SELECT wv.ast, COALESCE(wv.yaml_source, '')
+FROM workflows w
+JOIN workflow_versions wv ON wv.workflow_id = w.id
+WHERE w.id = $1 AND w.tenant_id = $2 AND wv.status = 'published'
+ORDER BY wv.version DESC
+LIMIT 1
+`, workflowID, tenantID).Scan(&astRaw, &yamlSource)
+	if errors.Is(err, sql.ErrNoRows) {
+		var exists bool
+		if err := s.db.QueryRowContext(ctx, `SELECT EXISTS(SELECT 1 FROM workflows WHERE id = $1 AND tenant_id = $2)`, workflowID, tenantID).Scan(&exists); err != nil {
+			return nil, fmt.Errorf("load workflow: %w", err)
+		}
+		if !exists {
+			return nil, sql.ErrNoRows
+		}
+		return nil, ErrNoPublishedVersion
+	}
+	if err != nil {
+		return nil, fmt.Errorf("load published version: %w", err)
+	}
+	return s.updateDraft(ctx, tenantID, actorID, workflowID, DraftEditDiscard, func([]byte) (draftUpdate, error) {
+		return draftUpdate{ast: astRaw, yaml: &yamlSource}, nil
+	})
+}
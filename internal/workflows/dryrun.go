@@ -0,0 +1,298 @@
+package workflows
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/cases"
+	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/engine"
+	"github.com/neural-chilli/aceryx/internal/expressions"
+	"github.com/santhosh-tekuri/jsonschema/v5"
+)
+
+type connectorCatalog interface {
+	GetAction(connectorKey, actionKey string) (connectors.ActionSpec, bool)
+}
+
+// DryRunRequest configures a simulated execution. Version 0 selects the latest
+// published version, falling back to the draft. MockOutputs are keyed by step
+// ID and stand in for step results. Environment selects the overlay that
+// {{env.*}} references resolve against.
+type DryRunRequest struct {
+	Version     int                   `json:"version,omitempty"`
+	Environment string                `json:"environment,omitempty"`
+	CaseData    map[string]any        `json:"case_data"`
+	MockOutputs map[string]DryRunMock `json:"mock_outputs"`
+}
+
+type DryRunMock struct {
+	Outcome       string         `json:"outcome,omitempty"`
+	Output        any            `json:"output,omitempty"`
+	CaseDataPatch map[string]any `json:"case_data_patch,omitempty"`
+}
+
+type DryRunStep struct {
+	engine.SimulatedStep
+	ResolvedConfig json.RawMessage `json:"resolved_config,omitempty"`
+	Output         any             `json:"output,omitempty"`
+	Mocked         bool            `json:"mocked"`
+}
+
+type DryRunResult struct {
+	WorkflowID uuid.UUID                `json:"workflow_id"`
+	Version    int                      `json:"version"`
+	Valid      bool                     `json:"valid"`
+	Errors     []PublishValidationError `json:"errors"`
+	Warnings   []string                 `json:"warnings"`
+	Order      [][]string               `json:"order"`
+	Steps      []DryRunStep             `json:"steps"`
+	CaseData   map[string]any           `json:"case_data"`
+}
+
+func (s *Service) SetConnectorCatalog(catalog connectorCatalog) {
+	s.connectors = catalog
+}
+
+// DryRun walks a workflow version without executing any step: it validates the
+// graph and step configs, resolves templates against the supplied case data
+// and mock outputs, and reports the order in which steps would run.
+func (s *Service) DryRun(ctx context.Context, tenantID, workflowID uuid.UUID, req DryRunRequest) (DryRunResult, error) {
+	version, astRaw, err := s.loadVersionAST(ctx, tenantID, workflowID, req.Version)
+	if err != nil {
+		return DryRunResult{}, err
+	}
+	result := DryRunResult{
+		WorkflowID: workflowID,
+		Version:    version,
+		Errors:     make([]PublishValidationError, 0),
+		Warnings:   make([]string, 0),
+		Order:      make([][]string, 0),
+		Steps:      make([]DryRunStep, 0),
+	}
+
+	validation := &PublishValidationErrors{Errors: make([]PublishValidationError, 0)}
+	if err := validatePublishWorkflow(ctx, tenantID, astRaw, s.catalog, s.egress, s.credentials, s); err != nil {
+		var structured *PublishValidationErrors
+		if !errors.As(err, &structured) {
+			validation.add(PublishValidationError{Code: "INVALID_WORKFLOW", Message: err.Error()})
+		} else {
+			validation.Errors = append(validation.Errors, structured.Errors...)
+		}
+	}
+	var ast engine.WorkflowAST
+	if err := json.Unmarshal(astRaw, &ast); err != nil || engine.ValidateAST(ast) != nil {
+		// The graph itself cannot be walked; report validation errors only.
+		result.Errors = validation.Errors
+		return result, nil
+	}
+	for stepID := range req.MockOutputs {
+		if !containsStep(ast, stepID) {
+			result.Warnings = append(result.Warnings, fmt.Sprintf("mock output for unknown step %q ignored", stepID))
+		}
+	}
+	result.Warnings = append(result.Warnings, s.pinnedToolWarnings(ctx, tenantID, ast)...)
+	if resolved, _, err := ast.ForEnvironment(req.Environment); err == nil {
+		ast = resolved
+	}
+	if violations, err := ast.ValidateInput(req.CaseData); err == nil {
+		for _, violation := range violations {
+			validation.add(PublishValidationError{
+				Field:   "case_data" + strings.ReplaceAll(strings.TrimSuffix(violation.Path, "/"), "/", "."),
+				Code:    "INPUT_SCHEMA_VIOLATION",
+				Message: fmt.Sprintf("Case data %s: %s", violation.Path, violation.Message),
+			})
+		}
+	}
+
+	caseData := req.CaseData
+	if caseData == nil {
+		caseData = map[string]any{}
+	}
+	stepResults := map[string]any{}
+	byID := map[string]*DryRunStep{}
+	simulated, err := engine.Simulate(ast, expressions.NewEvaluator(), caseData, func(step engine.WorkflowStep, _ int, data map[string]any) (string, map[string]any) {
+		planned := &DryRunStep{}
+		byID[step.ID] = planned
+		resolved := s.resolveStepConfig(step, data, stepResults, validation)
+		planned.ResolvedConfig = resolved
+
+		mock, mocked := req.MockOutputs[step.ID]
+		planned.Mocked = mocked
+		planned.Output = mock.Output
+		outcome := mock.Outcome
+		if outcome == "" && len(step.Outcomes) > 0 {
+			outcome = defaultOutcome(step)
+			result.Warnings = append(result.Warnings, fmt.Sprintf("step %q has no mock outcome; assumed %q", step.ID, outcome))
+		}
+		stepResults[step.ID] = map[string]any{"result": map[string]any{"outcome": outcome, "output": mock.Output}}
+		if len(mock.CaseDataPatch) > 0 {
+			data = cases.DeepMerge(data, mock.CaseDataPatch)
+		}
+		caseData = data
+		return outcome, data
+	})
+	if err != nil {
+		validation.add(PublishValidationError{Code: "SIMULATION_FAILED", Message: err.Error()})
+	}
+
+	for _, step := range simulated {
+		planned := byID[step.StepID]
+		if planned == nil {
+			planned = &DryRunStep{}
+		}
+		planned.SimulatedStep = step
+		result.Steps = append(result.Steps, *planned)
+		if step.Wave > 0 {
+			for len(result.Order) < step.Wave {
+				result.Order = append(result.Order, []string{})
+			}
+			result.Order[step.Wave-1] = append(result.Order[step.Wave-1], step.StepID)
+		}
+	}
+	result.CaseData = caseData
+	result.Errors = validation.Errors
+	result.Valid = len(result.Errors) == 0
+	return result, nil
+}
+
+func (s *Service) loadVersionAST(ctx context.Context, tenantID, workflowID uuid.UUID, version int) (int, []byte, error) {
+	var astRaw []byte
+	var err error
+	if version > 0 {
+		err = s.db.QueryRowContext(ctx, `
+SELECT wv.version, wv.ast
+FROM workflows w
+JOIN workflow_versions wv ON wv.workflow_id = w.id
+WHERE w.id = $1 AND w.tenant_id = $2 AND wv.version = $3
+`, workflowID, tenantID, version).Scan(&version, &astRaw)
+	} else {
+		err = s.db.QueryRowContext(ctx, `
+SELECT wv.version, wv.ast
+FROM workflows w
+JOIN workflow_versions wv ON wv.workflow_id = w.id
+WHERE w.id = $1 AND w.tenant_id = $2 AND wv.status IN ('published', 'draft')
+ORDER BY CASE wv.status WHEN 'published' THEN 0 ELSE 1 END, wv.version DESC
+LIMIT 1
+`, workflowID, tenantID).Scan(&version, &astRaw)
+	}
+	if errors.Is(err, sql.ErrNoRows) {
+		return 0, nil, err
+	}
+	if err != nil {
+		return 0, nil, fmt.Errorf("load workflow version for dry run: %w", err)
+	}
+	return version, astRaw, nil
+}
+
+// resolveStepConfig renders a step's config templates the way the connector
+// executor would at run time, and checks integration inputs against the
+// action's input schema.
+func (s *Service) resolveStepConfig(step engine.WorkflowStep, caseData map[string]any, stepResults map[string]any, validation *PublishValidationErrors) json.RawMessage {
+	cfg, err := decodeStepConfig(step)
+	if err != nil {
+		return nil
+	}
+	caseMap := map[string]any{"data": caseData, "steps": stepResults}
+	for k, v := range caseData {
+		caseMap[k] = v
+	}
+	resolved, _ := connectors.ResolveTemplateAny(cfg, map[string]any{
+		"case": caseMap,
+		"now":  time.Now().UTC().Format(time.RFC3339),
+	}).(map[string]any)
+	raw, err := json.Marshal(resolved)
+	if err != nil {
+		return nil
+	}
+	if strings.TrimSpace(step.Type) == "integration" && s.connectors != nil {
+		validateIntegrationInput(s.connectors, step.ID, resolved, validation)
+	}
+	return raw
+}
+
+func validateIntegrationInput(catalog connectorCatalog, stepID string, cfg map[string]any, validation *PublishValidationErrors) {
+	connectorKey, _ := cfg["connector"].(string)
+	actionKey, _ := cfg["action"].(string)
+	if connectorKey == "" || actionKey == "" {
+		return
+	}
+	action, ok := catalog.GetAction(connectorKey, actionKey)
+	if !ok {
+		validation.add(PublishValidationError{
+			StepID:  stepID,
+			Field:   "config.action",
+			Code:    "UNKNOWN_CONNECTOR_ACTION",
+			Message: fmt.Sprintf("Step %q uses unknown connector action %s/%s", stepID, connectorKey, actionKey),
+		})
+		return
+	}
+	if len(action.InputSchema) == 0 {
+		return
+	}
+	schemaRaw, err := json.Marshal(action.InputSchema)
+	if err != nil {
+		return
+	}
+	compiler := jsonschema.NewCompiler()
+	if err := compiler.AddResource("input.json", strings.NewReader(string(schemaRaw))); err != nil {
+		return
+	}
+	schema, err := compiler.Compile("input.json")
+	if err != nil {
+		return
+	}
+	input, ok := cfg["input"].(map[string]any)
+	if !ok {
+		input = map[string]any{}
+	}
+	var verr *jsonschema.ValidationError
+	if err := schema.Validate(input); errors.As(err, &verr) {
+		validation.add(PublishValidationError{
+			StepID:  stepID,
+			Field:   "config.input",
+			Code:    "INPUT_SCHEMA_MISMATCH",
+			Message: fmt.Sprintf("Step %q input does not match %s/%s schema: %s", stepID, connectorKey, actionKey, strings.Join(schemaLeafMessages(verr), "; ")),
+		})
+	}
+}
+
+func schemaLeafMessages(verr *jsonschema.ValidationError) []string {
+	if len(verr.Causes) == 0 {
+		location := verr.InstanceLocation
+		if location == "" {
+			location = "/"
+		}
+		return []string{location + ": " + verr.Message}
+	}
+	out := make([]string, 0, len(verr.Causes))
+	for _, cause := range verr.Causes {
+		out = append(out, schemaLeafMessages(cause)...)
+	}
+	return out
+}
+
+func defaultOutcome(step engine.WorkflowStep) string {
+	keys := make([]string, 0, len(step.Outcomes))
+	for k := range step.Outcomes {
+		keys = append(keys, k)
+	}
+	sort.Strings(keys)
+	return keys[0]
+}
+
+func containsStep(ast engine.WorkflowAST, stepID string) bool {
+	for _, step := range ast.Steps {
+		if step.ID == stepID {
+			return true
+		}
+	}
+	return false
+}
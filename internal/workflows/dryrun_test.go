@@ -0,0 +1,106 @@
+package workflows
+
+import (
+	"context"
+	"encoding/json"
+	"strings"
+	"testing"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+type stubConnectorCatalog map[string]connectors.ActionSpec
+
+func (c stubConnectorCatalog) GetAction(connectorKey, actionKey string) (connectors.ActionSpec, bool) {
+	spec, ok := c[connectorKey+"/"+actionKey]
+	return spec, ok
+}
+
+func TestResolveStepConfig_RendersTemplatesAndChecksInputSchema(t *testing.T) {
+	svc := &Service{connectors: stubConnectorCatalog{
+		"http/request": {Key: "request", InputSchema: map[string]any{
+			"type":     "object",
+			"required": []any{"url"},
+			"properties": map[string]any{
+				"url": map[string]any{"type": "string", "minLength": 1},
+			},
+		}},
+	}}
+	step := engine.WorkflowStep{
+		ID:     "call",
+		Type:   "integration",
+		Config: json.RawMessage(`{"connector":"http","action":"request","input":{"url":"{{case.data.endpoint}}","note":"{{case.steps.score.result.outcome}}"}}`),
+	}
+	stepResults := map[string]any{"score": map[string]any{"result": map[string]any{"outcome": "high"}}}
+
+	validation := &PublishValidationErrors{}
+	raw := svc.resolveStepConfig(step, map[string]any{"endpoint": "https://example.test"}, stepResults, validation)
+	if len(validation.Errors) != 0 {
+		t.Fatalf("unexpected validation errors %+v", validation.Errors)
+	}
+	var cfg map[string]any
+	if err := json.Unmarshal(raw, &cfg); err != nil {
+		t.Fatalf("decode resolved config: %v", err)
+	}
+	input := cfg["input"].(map[string]any)
+	if input["url"] != "https://example.test" || input["note"] != "high" {
+		t.Fatalf("unexpected resolved input %v", input)
+	}
+
+	svc.resolveStepConfig(step, map[string]any{}, stepResults, validation)
+	if len(validation.Errors) != 1 || validation.Errors[0].Code != "INPUT_SCHEMA_MISMATCH" || !strings.Contains(validation.Errors[0].Message, "/url") {
+		t.Fatalf("expected schema mismatch on /url, got %+v", validation.Errors)
+	}
+}
+
+func TestResolveStepConfig_UnknownConnectorAction(t *testing.T) {
+	svc := &Service{connectors: stubConnectorCatalog{}}
+	step := engine.WorkflowStep{
+		ID:     "call",
+		Type:   "integration",
+		Config: json.RawMessage(`{"connector":"nope","action":"send"}`),
+	}
+	validation := &PublishValidationErrors{}
+	svc.resolveStepConfig(step, nil, map[string]any{}, validation)
+	if len(validation.Errors) != 1 || validation.Errors[0].Code != "UNKNOWN_CONNECTOR_ACTION" {
+		t.Fatalf("expected unknown action error, got %+v", validation.Errors)
+	}
+}
+
+func TestStepTestContext_WrapsBareStepResults(t *testing.T) {
+	ctx := stepTestContext(StepTestRequest{
+		CaseData: map[string]any{"amount": 10},
+		StepResults: map[string]any{
+			"bare":    map[string]any{"score": 1},
+			"wrapped": map[string]any{"result": map[string]any{"score": 2}},
+		},
+	})
+	if got := connectors.ResolveTemplateString("{{case.steps.bare.result.score}}/{{case.steps.wrapped.result.score}}/{{case.amount}}", ctx); got != "1/2/10" {
+		t.Fatalf("unexpected resolution %q", got)
+	}
+}
+
+type stubToolVersions map[string]string
+
+func (s stubToolVersions) PinnedToolWarning(_ context.Context, _ uuid.UUID, serverURL, toolName, version string) (string, error) {
+	return s[serverURL+"|"+toolName+"@"+version], nil
+}
+
+func TestPinnedToolWarnings(t *testing.T) {
+	svc := &Service{toolVersions: stubToolVersions{
+		"https://mcp.test|search@old": "tool \"search\" version old has been replaced by new",
+		"https://mcp.test|gone@":      "tool \"gone\" is no longer offered by the server",
+	}}
+	ast := engine.WorkflowAST{Steps: []engine.WorkflowStep{
+		{ID: "pinned", Type: "mcp-client", Config: json.RawMessage(`{"server_url":"https://mcp.test","tool":"search","tool_version":"old"}`)},
+		{ID: "current", Type: "mcp-client", Config: json.RawMessage(`{"server_url":"https://mcp.test","tool":"search","tool_version":"new"}`)},
+		{ID: "unpinned", Type: "mcp-client", Config: json.RawMessage(`{"server_url":"https://mcp.test","tool":"search"}`)},
+		{ID: "dropped", Type: "mcp-client", Config: json.RawMessage(`{"server_url":"https://mcp.test","tool":"gone"}`)},
+	}}
+	warnings := svc.pinnedToolWarnings(context.Background(), uuid.New(), ast)
+	if len(warnings) != 2 || !strings.Contains(warnings[0], `step "pinned"`) || !strings.Contains(warnings[1], `step "dropped"`) {
+		t.Fatalf("unexpected warnings %v", warnings)
+	}
+}
@@ -0,0 +1,151 @@
+package workflows
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"sort"
+	"strings"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+// BundleFormat identifies promotion bundles.
+const BundleFormat = "aceryx.workflow-bundle/v1"
+
+var (
+	// ErrUnknownEnvironment is returned when promoting to an environment the
+	// workflow does not declare.
+	ErrUnknownEnvironment = errors.New("unknown environment")
+	// ErrEnvironmentIncomplete is returned when a step config references an
+	// environment value the target environment does not define.
+	ErrEnvironmentIncomplete = errors.New("environment values missing")
+)
+
+// PromoteRequest selects the version to promote and the target environment.
+// Version 0 selects the latest published version, falling back to the draft.
+type PromoteRequest struct {
+	Version     int    `json:"version,omitempty"`
+	Environment string `json:"environment"`
+}
+
+// Bundle is a workflow version rewritten for one environment: every
+// {{env.*}} reference is replaced by the environment's value and the
+// overlays are dropped, so the target instance needs no overlay of its own.
+// YAML holds the same definition for PUT /workflows/{id}/yaml/draft.
+type Bundle struct {
+	Format      string          `json:"format"`
+	WorkflowID  uuid.UUID       `json:"workflow_id"`
+	Name        string          `json:"name"`
+	CaseType    string          `json:"case_type"`
+	Version     int             `json:"version"`
+	Environment string          `json:"environment"`
+	AST         json.RawMessage `json:"ast"`
+	YAML        string          `json:"yaml"`
+}
+
+// Promote exports a workflow version as a bundle for the target environment.
+func (s *Service) Promote(ctx context.Context, tenantID, workflowID uuid.UUID, req PromoteRequest) (Bundle, error) {
+	target := strings.TrimSpace(req.Environment)
+	if target == "" {
+		return Bundle{}, fmt.Errorf("%w: environment is required", ErrUnknownEnvironment)
+	}
+	version, astRaw, err := s.loadVersionAST(ctx, tenantID, workflowID, req.Version)
+	if err != nil {
+		return Bundle{}, err
+	}
+	var ast engine.WorkflowAST
+	if err := json.Unmarshal(astRaw, &ast); err != nil {
+		return Bundle{}, fmt.Errorf("decode workflow ast: %w", err)
+	}
+	if _, declared := ast.Environments[target]; !declared && target != engine.DefaultEnvironment && len(ast.Environments) > 0 {
+		return Bundle{}, fmt.Errorf("%w: %s (declared: %s)", ErrUnknownEnvironment, target, strings.Join(sortedKeys(ast.Environments), ", "))
+	}
+	if missing := missingEnvironmentValues(ast, target); len(missing) > 0 {
+		return Bundle{}, fmt.Errorf("%w in %s: %s", ErrEnvironmentIncomplete, target, strings.Join(missing, ", "))
+	}
+
+	// Rewrite the stored document rather than engine.WorkflowAST so fields
+	// the engine does not model survive the round trip.
+	var doc map[string]any
+	if err := json.Unmarshal(astRaw, &doc); err != nil {
+		return Bundle{}, fmt.Errorf("decode workflow ast: %w", err)
+	}
+	values := ast.EnvironmentValues(target)
+	if steps, ok := doc["steps"].([]any); ok {
+		for _, raw := range steps {
+			if step, ok := raw.(map[string]any); ok && step["config"] != nil {
+				step["config"] = engine.ResolveEnvRefs(step["config"], values)
+			}
+		}
+	}
+	delete(doc, "environments")
+	resolved, err := json.Marshal(doc)
+	if err != nil {
+		return Bundle{}, fmt.Errorf("encode promoted workflow: %w", err)
+	}
+	yamlSource, err := marshalYAMLFromAST(resolved)
+	if err != nil {
+		return Bundle{}, err
+	}
+
+	bundle := Bundle{
+		Format:      BundleFormat,
+		WorkflowID:  workflowID,
+		Version:     version,
+		Environment: target,
+		AST:         resolved,
+		YAML:        yamlSource,
+	}
+	err = s.db.QueryRowContext(ctx, `
+SELECT w.name, COALESCE(ct.name, w.case_type)
+FROM workflows w
+LEFT JOIN case_types ct ON ct.tenant_id = w.tenant_id AND ct.id::text = w.case_type
+WHERE w.id = $1 AND w.tenant_id = $2
+`, workflowID, tenantID).Scan(&bundle.Name, &bundle.CaseType)
+	if err != nil {
+		return Bundle{}, fmt.Errorf("load workflow for promotion: %w", err)
+	}
+	return bundle, nil
+}
+
+// missingEnvironmentValues lists the {{env.*}} references that have no value
+// in the named environment, including the default overlay.
+func missingEnvironmentValues(ast engine.WorkflowAST, name string) []string {
+	values := ast.EnvironmentValues(name)
+	var missing []string
+	for _, ref := range ast.EnvironmentRefs() {
+		if _, ok := values[ref]; !ok {
+			missing = append(missing, ref)
+		}
+	}
+	return missing
+}
+
+// addEnvironmentErrors checks that every environment the workflow declares
+// can resolve every {{env.*}} reference. Without named environments the
+// default overlay must resolve them all.
+func addEnvironmentErrors(validation *PublishValidationErrors, workflow engine.WorkflowAST) {
+	names := make([]string, 0, len(workflow.Environments))
+	for name := range workflow.Environments {
+		if name != engine.DefaultEnvironment {
+			names = append(names, name)
+		}
+	}
+	if len(names) == 0 {
+		names = append(names, engine.DefaultEnvironment)
+	}
+	sort.Strings(names)
+	for _, name := range names {
+		for _, ref := range missingEnvironmentValues(workflow, name) {
+			validation.add(PublishValidationError{
+				Field:      "environments." + name,
+				Code:       "ENVIRONMENT_VALUE_MISSING",
+				Message:    fmt.Sprintf("{{env.%s}} has no value in environment %q", ref, name),
+				Suggestion: fmt.Sprintf("Add %q to environments.%s or environments.%s", ref, name, engine.DefaultEnvironment),
+			})
+		}
+	}
+}
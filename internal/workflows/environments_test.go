@@ -0,0 +1,37 @@
+package workflows
+
+import (
+	"encoding/json"
+	"testing"
+
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+func TestAddEnvironmentErrors(t *testing.T) {
+	workflow := engine.WorkflowAST{
+		Steps: []engine.WorkflowStep{
+			{ID: "crm", Type: "integration", Config: json.RawMessage(`{"input":{"url":"{{env.crm_url}}","profile":"{{env.profile}}"}}`)},
+		},
+		Environments: map[string]map[string]any{
+			"default": {"profile": "sandbox"},
+			"staging": {"crm_url": "https://crm.staging.example.com"},
+			"prod":    {},
+		},
+	}
+	validation := &PublishValidationErrors{Errors: make([]PublishValidationError, 0)}
+	addEnvironmentErrors(validation, workflow)
+	if len(validation.Errors) != 1 {
+		t.Fatalf("expected one error, got %#v", validation.Errors)
+	}
+	if got := validation.Errors[0]; got.Code != "ENVIRONMENT_VALUE_MISSING" || got.Field != "environments.prod" {
+		t.Fatalf("unexpected error %#v", got)
+	}
+
+	// Without named environments the default overlay must cover everything.
+	workflow.Environments = nil
+	validation = &PublishValidationErrors{Errors: make([]PublishValidationError, 0)}
+	addEnvironmentErrors(validation, workflow)
+	if len(validation.Errors) != 2 || validation.Errors[0].Field != "environments.default" {
+		t.Fatalf("expected two default errors, got %#v", validation.Errors)
+	}
+}
@@ -0,0 +1,210 @@
+package workflows
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"slices"
+	"strings"
+
+	"github.com/google/uuid"
+)
+
+const (
+	EdgeDependency = "dependency"
+	EdgeOutcome    = "outcome"
+)
+
+var ErrInvalidGraph = errors.New("invalid graph")
+
+// Graph is the designer canvas in Vue Flow's shape: one node per step, with
+// dependency and outcome edges between them.
+type Graph struct {
+	Nodes []GraphNode `json:"nodes"`
+	Edges []GraphEdge `json:"edges"`
+}
+
+type GraphNode struct {
+	ID       string         `json:"id"`
+	Type     string         `json:"type"`
+	Position *GraphPosition `json:"position,omitempty"`
+	Data     GraphNodeData  `json:"data"`
+}
+
+type GraphPosition struct {
+	X float64 `json:"x"`
+	Y float64 `json:"y"`
+}
+
+// GraphNodeData carries the step type and config. The node type is only a
+// renderer key, so StepType wins when both are set.
+type GraphNodeData struct {
+	StepType string          `json:"stepType"`
+	Config   json.RawMessage `json:"config,omitempty"`
+}
+
+type GraphEdge struct {
+	ID     string        `json:"id"`
+	Source string        `json:"source"`
+	Target string        `json:"target"`
+	Label  string        `json:"label,omitempty"`
+	Data   GraphEdgeData `json:"data"`
+}
+
+type GraphEdgeData struct {
+	EdgeType string `json:"edgeType"`
+	Outcome  string `json:"outcome,omitempty"`
+}
+
+// GraphToAST rebuilds the steps of base from graph. Nodes become steps,
+// dependency edges become depends_on and outcome edges become outcomes.
+// Fields the canvas does not edit, such as guards and error policies, are
+// kept from the step with the same id in base, as are top-level fields.
+func GraphToAST(base json.RawMessage, graph Graph) (json.RawMessage, error) {
+	doc := map[string]any{}
+	if len(bytes.TrimSpace(base)) > 0 {
+		if err := json.Unmarshal(base, &doc); err != nil {
+			return nil, fmt.Errorf("decode workflow ast: %w", err)
+		}
+		if doc == nil {
+			doc = map[string]any{}
+		}
+	}
+	previous := map[string]map[string]any{}
+	if rawSteps, ok := doc["steps"].([]any); ok {
+		for _, raw := range rawSteps {
+			if step, ok := raw.(map[string]any); ok {
+				if id, _ := step["id"].(string); id != "" {
+					previous[id] = step
+				}
+			}
+		}
+	}
+
+	steps := make([]map[string]any, 0, len(graph.Nodes))
+	byID := make(map[string]map[string]any, len(graph.Nodes))
+	for i, node := range graph.Nodes {
+		id := strings.TrimSpace(node.ID)
+		if id == "" {
+			return nil, fmt.Errorf("%w: node %d has no id", ErrInvalidGraph, i)
+		}
+		if _, dup := byID[id]; dup {
+			return nil, fmt.Errorf("%w: duplicate node %q", ErrInvalidGraph, id)
+		}
+		stepType := nodeStepType(node)
+		if stepType == "" {
+			return nil, fmt.Errorf("%w: node %q has no step type", ErrInvalidGraph, id)
+		}
+
+		step := make(map[string]any, len(previous[id])+4)
+		for k, v := range previous[id] {
+			step[k] = v
+		}
+		step["id"] = id
+		step["type"] = stepType
+		step["depends_on"] = []string{}
+		delete(step, "outcomes")
+		delete(step, "config")
+		if config := bytes.TrimSpace(node.Data.Config); len(config) > 0 && !bytes.Equal(config, []byte("null")) {
+			var decoded map[string]any
+			if err := json.Unmarshal(config, &decoded); err != nil {
+				return nil, fmt.Errorf("%w: node %q config must be an object", ErrInvalidGraph, id)
+			}
+			step["config"] = decoded
+		}
+		if node.Position != nil {
+			step["position"] = map[string]any{"x": node.Position.X, "y": node.Position.Y}
+		}
+		steps = append(steps, step)
+		byID[id] = step
+	}
+
+	dependsOn := map[string][]string{}
+	outcomes := map[string]map[string][]string{}
+	for i, edge := range graph.Edges {
+		source, target := strings.TrimSpace(edge.Source), strings.TrimSpace(edge.Target)
+		if byID[source] == nil || byID[target] == nil {
+			return nil, fmt.Errorf("%w: edge %d connects unknown nodes %q and %q", ErrInvalidGraph, i, source, target)
+		}
+		switch edgeType(edge) {
+		case EdgeDependency:
+			if !slices.Contains(dependsOn[target], source) {
+				dependsOn[target] = append(dependsOn[target], source)
+			}
+		case EdgeOutcome:
+			outcome := strings.TrimSpace(edge.Data.Outcome)
+			if outcome == "" {
+				outcome = strings.TrimSpace(edge.Label)
+			}
+			if outcome == "" {
+				return nil, fmt.Errorf("%w: outcome edge %d from %q has no outcome", ErrInvalidGraph, i, source)
+			}
+			if outcomes[source] == nil {
+				outcomes[source] = map[string][]string{}
+			}
+			if !slices.Contains(outcomes[source][outcome], target) {
+				outcomes[source][outcome] = append(outcomes[source][outcome], target)
+			}
+		default:
+			return nil, fmt.Errorf("%w: edge %d has unknown type %q", ErrInvalidGraph, i, edge.Data.EdgeType)
+		}
+	}
+	for id, deps := range dependsOn {
+		byID[id]["depends_on"] = deps
+	}
+	for id, routes := range outcomes {
+		byID[id]["outcomes"] = routes
+	}
+
+	doc["steps"] = steps
+	out, err := json.Marshal(doc)
+	if err != nil {
+		return nil, fmt.Errorf("encode workflow ast: %w", err)
+	}
+	return out, nil
+}
+
+func nodeStepType(node GraphNode) string {
+	if stepType := strings.TrimSpace(node.Data.StepType); stepType != "" {
+		return stepType
+	}
+	// The canvas renders unrecognised step types as "unknown".
+	if nodeType := strings.TrimSpace(node.Type); nodeType != "unknown" {
+		return nodeType
+	}
+	return ""
+}
+
+// edgeType defaults to an outcome edge when the edge carries an outcome and
+// to a dependency otherwise, as freshly drawn canvas edges have no data.
+func edgeType(edge GraphEdge) string {
+	if t := strings.TrimSpace(edge.Data.EdgeType); t != "" {
+		return t
+	}
+	if strings.TrimSpace(edge.Data.Outcome) != "" || strings.TrimSpace(edge.Label) != "" {
+		return EdgeOutcome
+	}
+	return EdgeDependency
+}
+
+// SaveDraftGraph replaces the draft's steps with the designer graph and
+// regenerates its YAML source in the same transaction, so the stored AST,
+// the YAML export and the canvas always agree. It returns the saved AST.
+func (s *Service) SaveDraftGraph(ctx context.Context, tenantID, actorID, workflowID uuid.UUID, graph Graph) (json.RawMessage, error) {
+	return s.updateDraft(ctx, tenantID, actorID, workflowID, DraftEditGraph, func(current []byte) (draftUpdate, error) {
+		ast, err := GraphToAST(current, graph)
+		if err != nil {
+			return draftUpdate{}, err
+		}
+		if err := validateWorkflowAST(ast); err != nil {
+			return draftUpdate{}, err
+		}
+		yamlSource, err := marshalYAMLFromAST(ast)
+		if err != nil {
+			return draftUpdate{}, err
+		}
+		return draftUpdate{ast: ast, yaml: &yamlSource}, nil
+	})
+}
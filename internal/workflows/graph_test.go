@@ -0,0 +1,74 @@
+package workflows
+
+import (
+	"encoding/json"
+	"errors"
+	"reflect"
+	"testing"
+)
+
+func TestGraphToAST(t *testing.T) {
+	base := mustJSON(t, map[string]any{
+		"name": "loan",
+		"steps": []map[string]any{
+			{"id": "review", "type": "human_task", "condition": "case.data.amount > 100", "depends_on": []string{"stale"}},
+		},
+	})
+	graph := Graph{
+		Nodes: []GraphNode{
+			{ID: "score", Type: "rule", Position: &GraphPosition{X: 10, Y: 20}, Data: GraphNodeData{StepType: "rule", Config: json.RawMessage(`{"expression":"true"}`)}},
+			{ID: "review", Type: "human_task", Data: GraphNodeData{Config: json.RawMessage(`{"assign_to_role":"underwriter"}`)}},
+			{ID: "notify", Type: "unknown", Data: GraphNodeData{StepType: "notification"}},
+		},
+		Edges: []GraphEdge{
+			{Source: "score", Target: "review"},
+			{Source: "score", Target: "review", Data: GraphEdgeData{EdgeType: EdgeDependency}},
+			{Source: "score", Target: "notify", Label: "low"},
+			{Source: "review", Target: "notify", Data: GraphEdgeData{EdgeType: EdgeOutcome, Outcome: "approved"}},
+		},
+	}
+
+	raw, err := GraphToAST(base, graph)
+	if err != nil {
+		t.Fatalf("GraphToAST: %v", err)
+	}
+	var got map[string]any
+	if err := json.Unmarshal(raw, &got); err != nil {
+		t.Fatalf("decode ast: %v", err)
+	}
+	want := mustJSON(t, map[string]any{
+		"name": "loan",
+		"steps": []map[string]any{
+			{"id": "score", "type": "rule", "depends_on": []string{}, "config": map[string]any{"expression": "true"}, "position": map[string]any{"x": 10, "y": 20}, "outcomes": map[string]any{"low": []string{"notify"}}},
+			{"id": "review", "type": "human_task", "condition": "case.data.amount > 100", "depends_on": []string{"score"}, "config": map[string]any{"assign_to_role": "underwriter"}, "outcomes": map[string]any{"approved": []string{"notify"}}},
+			{"id": "notify", "type": "notification", "depends_on": []string{}},
+		},
+	})
+	var wantDoc map[string]any
+	if err := json.Unmarshal(want, &wantDoc); err != nil {
+		t.Fatalf("decode want: %v", err)
+	}
+	if !reflect.DeepEqual(got, wantDoc) {
+		t.Fatalf("unexpected ast:\n got %s\nwant %s", raw, want)
+	}
+}
+
+func TestGraphToASTRejectsInvalidGraphs(t *testing.T) {
+	node := func(id string) GraphNode { return GraphNode{ID: id, Type: "rule"} }
+	cases := map[string]Graph{
+		"missing id":        {Nodes: []GraphNode{node("")}},
+		"duplicate node":    {Nodes: []GraphNode{node("a"), node("a")}},
+		"unknown step type": {Nodes: []GraphNode{{ID: "a", Type: "unknown"}}},
+		"config not object": {Nodes: []GraphNode{{ID: "a", Type: "rule", Data: GraphNodeData{Config: json.RawMessage(`[1]`)}}}},
+		"dangling edge":     {Nodes: []GraphNode{node("a")}, Edges: []GraphEdge{{Source: "a", Target: "b"}}},
+		"outcome missing":   {Nodes: []GraphNode{node("a"), node("b")}, Edges: []GraphEdge{{Source: "a", Target: "b", Data: GraphEdgeData{EdgeType: EdgeOutcome}}}},
+		"unknown edge type": {Nodes: []GraphNode{node("a"), node("b")}, Edges: []GraphEdge{{Source: "a", Target: "b", Data: GraphEdgeData{EdgeType: "data"}}}},
+	}
+	for name, graph := range cases {
+		t.Run(name, func(t *testing.T) {
+			if _, err := GraphToAST(nil, graph); !errors.Is(err, ErrInvalidGraph) {
+				t.Fatalf("expected ErrInvalidGraph, got %v", err)
+			}
+		})
+	}
+}
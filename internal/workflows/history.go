@@ -0,0 +1,308 @@
+package workflows
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"sort"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+// DefaultDraftHistoryLimit is how many draft edits are kept per workflow.
+const DefaultDraftHistoryLimit = 100
+
+// Draft edit sources.
+const (
+	DraftEditAST   = "ast"
+	DraftEditGraph = "graph"
+	DraftEditYAML  = "yaml"
+)
+
+// ErrNothingToRevert is returned when undo or redo has no edits to apply.
+var ErrNothingToRevert = errors.New("nothing to revert")
+
+// DraftEdit is one logged draft save. Added, Removed and Changed list the
+// step IDs the edit touched. Undone edits can be redone until the next save.
+type DraftEdit struct {
+	ID        int64     `json:"id"`
+	Source    string    `json:"source"`
+	ActorID   uuid.UUID `json:"actor_id"`
+	Undone    bool      `json:"undone"`
+	Added     []string  `json:"added"`
+	Removed   []string  `json:"removed"`
+	Changed   []string  `json:"changed"`
+	CreatedAt time.Time `json:"created_at"`
+}
+
+// SetDraftHistoryLimit bounds the edit log kept per workflow.
+func (s *Service) SetDraftHistoryLimit(limit int) {
+	s.historyLimit = limit
+}
+
+func (s *Service) draftHistoryLimit() int {
+	if s.historyLimit <= 0 {
+		return DefaultDraftHistoryLimit
+	}
+	return s.historyLimit
+}
+
+// draftUpdate is what a save writes: the new AST, and the YAML source when it
+// changes too.
+type draftUpdate struct {
+	ast  json.RawMessage
+	yaml *string
+}
+
+// updateDraft replaces the latest draft under a row lock and logs the edit,
+// clearing any undone edits since they can no longer be redone.
+func (s *Service) updateDraft(ctx context.Context, tenantID, actorID, workflowID uuid.UUID, source string, build func(current []byte) (draftUpdate, error)) (json.RawMessage, error) {
+	tx, err := s.db.BeginTx(ctx, nil)
+	if err != nil {
+		return nil, fmt.Errorf("begin save workflow draft tx: %w", err)
+	}
+	defer func() { _ = tx.Rollback() }()
+
+	var (
+		draftID      uuid.UUID
+		draftVersion int
+		astRaw       []byte
+		yamlSource   string
+	)
+	err = tx.QueryRowContext(ctx, `
+SELECT wv.id, wv.version, wv.ast, COALESCE(wv.yaml_source, '')
+FROM workflows w
+JOIN workflow_versions wv ON wv.workflow_id = w.id
+WHERE w.id = $1 AND w.tenant_id = $2 AND wv.status = 'draft'
+ORDER BY wv.version DESC
+LIMIT 1
+FOR UPDATE OF wv
+`, workflowID, tenantID).Scan(&draftID, &draftVersion, &astRaw, &yamlSource)
+	if err != nil {
+		return nil, err
+	}
+	if err := checkDraftEditable(ctx, tx, workflowID); err != nil {
+		return nil, err
+	}
+	update, err := build(astRaw)
+	if err != nil {
+		return nil, err
+	}
+	afterYAML := yamlSource
+	if update.yaml != nil {
+		afterYAML = *update.yaml
+	}
+
+	if _, err := tx.ExecContext(ctx, `
+UPDATE workflow_versions
+SET ast = $2::jsonb,
+    yaml_source = $3
+WHERE id = $1
+`, draftID, string(update.ast), afterYAML); err != nil {
+		return nil, fmt.Errorf("save workflow draft: %w", err)
+	}
+	if _, err := tx.ExecContext(ctx, `
+DELETE FROM workflow_draft_history
+WHERE workflow_id = $1 AND undone
+`, workflowID); err != nil {
+		return nil, fmt.Errorf("clear undone draft edits: %w", err)
+	}
+	if _, err := tx.ExecContext(ctx, `
+INSERT INTO workflow_draft_history (tenant_id, workflow_id, draft_version, source, before_ast, before_yaml, after_ast, after_yaml, actor_id)
+VALUES ($1, $2, $3, $4, $5::jsonb, $6, $7::jsonb, $8, $9)
+`, tenantID, workflowID, draftVersion, source, string(astRaw), yamlSource, string(update.ast), afterYAML, actorID); err != nil {
+		return nil, fmt.Errorf("log draft edit: %w", err)
+	}
+	if _, err := tx.ExecContext(ctx, `
+DELETE FROM workflow_draft_history
+WHERE workflow_id = $1
+  AND id <= (
+    SELECT id FROM workflow_draft_history
+    WHERE workflow_id = $1
+    ORDER BY id DESC
+    OFFSET $2 LIMIT 1
+  )
+`, workflowID, s.draftHistoryLimit()); err != nil {
+		return nil, fmt.Errorf("trim draft history: %w", err)
+	}
+	if err := tx.Commit(); err != nil {
+		return nil, fmt.Errorf("commit save workflow draft tx: %w", err)
+	}
+	return update.ast, nil
+}
+
+// DraftHistory lists the latest draft's logged edits, newest first.
+func (s *Service) DraftHistory(ctx context.Context, tenantID, workflowID uuid.UUID, limit int) ([]DraftEdit, error) {
+	if limit <= 0 || limit > s.draftHistoryLimit() {
+		limit = s.draftHistoryLimit()
+	}
+	rows, err := s.db.QueryContext(ctx, `
+SELECT h.id, h.source, h.actor_id, h.undone, h.before_ast, h.after_ast, h.created_at
+FROM workflow_draft_history h
+JOIN workflows w ON w.id = h.workflow_id
+WHERE h.workflow_id = $1 AND w.tenant_id = $2
+  AND h.draft_version = (
+    SELECT MAX(version) FROM workflow_versions WHERE workflow_id = $1 AND status = 'draft'
+  )
+ORDER BY h.id DESC
+LIMIT $3
+`, workflowID, tenantID, limit)
+	if err != nil {
+		return nil, fmt.Errorf("list draft history: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	out := make([]DraftEdit, 0)
+	for rows.Next() {
+		var (
+			edit          DraftEdit
+			before, after []byte
+		)
+		if err := rows.Scan(&edit.ID, &edit.Source, &edit.ActorID, &edit.Undone, &before, &after, &edit.CreatedAt); err != nil {
+			return nil, fmt.Errorf("scan draft edit: %w", err)
+		}
+		edit.Added, edit.Removed, edit.Changed = diffSteps(before, after)
+		out = append(out, edit)
+	}
+	return out, rows.Err()
+}
+
+// UndoDraft reverts the latest draft's last count edits that are not yet
+// undone, restoring the AST and YAML from before the earliest of them. It
+// returns the restored AST and how many edits were undone.
+func (s *Service) UndoDraft(ctx context.Context, tenantID, workflowID uuid.UUID, count int) (json.RawMessage, int, error) {
+	return s.revertDraft(ctx, tenantID, workflowID, count, false)
+}
+
+// RedoDraft reapplies up to count undone edits, oldest first.
+func (s *Service) RedoDraft(ctx context.Context, tenantID, workflowID uuid.UUID, count int) (json.RawMessage, int, error) {
+	return s.revertDraft(ctx, tenantID, workflowID, count, true)
+}
+
+func (s *Service) revertDraft(ctx context.Context, tenantID, workflowID uuid.UUID, count int, redo bool) (json.RawMessage, int, error) {
+	if count <= 0 {
+		count = 1
+	}
+	tx, err := s.db.BeginTx(ctx, nil)
+	if err != nil {
+		return nil, 0, fmt.Errorf("begin revert workflow draft tx: %w", err)
+	}
+	defer func() { _ = tx.Rollback() }()
+
+	var (
+		draftID      uuid.UUID
+		draftVersion int
+	)
+	err = tx.QueryRowContext(ctx, `
+SELECT wv.id, wv.version
+FROM workflows w
+JOIN workflow_versions wv ON wv.workflow_id = w.id
+WHERE w.id = $1 AND w.tenant_id = $2 AND wv.status = 'draft'
+ORDER BY wv.version DESC
+LIMIT 1
+FOR UPDATE OF wv
+`, workflowID, tenantID).Scan(&draftID, &draftVersion)
+	if err != nil {
+		return nil, 0, err
+	}
+	if err := checkDraftEditable(ctx, tx, workflowID); err != nil {
+		return nil, 0, err
+	}
+
+	// Undo walks back from the newest live edit and restores the state
+	// before the last one taken; redo walks forward from the oldest undone
+	// edit and restores the state after the last one taken.
+	query := `
+SELECT id, before_ast, COALESCE(before_yaml, '')
+FROM workflow_draft_history
+WHERE workflow_id = $1 AND draft_version = $2 AND NOT undone
+ORDER BY id DESC
+LIMIT $3
+`
+	if redo {
+		query = `
+SELECT id, after_ast, COALESCE(after_yaml, '')
+FROM workflow_draft_history
+WHERE workflow_id = $1 AND draft_version = $2 AND undone
+ORDER BY id ASC
+LIMIT $3
+`
+	}
+	rows, err := tx.QueryContext(ctx, query, workflowID, draftVersion, count)
+	if err != nil {
+		return nil, 0, fmt.Errorf("load draft edits: %w", err)
+	}
+	var (
+		ids        []int64
+		astRaw     []byte
+		yamlSource string
+	)
+	for rows.Next() {
+		var id int64
+		if err := rows.Scan(&id, &astRaw, &yamlSource); err != nil {
+			_ = rows.Close()
+			return nil, 0, fmt.Errorf("scan draft edit: %w", err)
+		}
+		ids = append(ids, id)
+	}
+	if err := rows.Close(); err != nil {
+		return nil, 0, fmt.Errorf("load draft edits: %w", err)
+	}
+	if len(ids) == 0 {
+		return nil, 0, ErrNothingToRevert
+	}
+
+	if _, err := tx.ExecContext(ctx, `
+UPDATE workflow_versions
+SET ast = $2::jsonb,
+    yaml_source = $3
+WHERE id = $1
+`, draftID, string(astRaw), yamlSource); err != nil {
+		return nil, 0, fmt.Errorf("restore workflow draft: %w", err)
+	}
+	for _, id := range ids {
+		if _, err := tx.ExecContext(ctx, `UPDATE workflow_draft_history SET undone = $2 WHERE id = $1`, id, !redo); err != nil {
+			return nil, 0, fmt.Errorf("mark draft edit: %w", err)
+		}
+	}
+	if err := tx.Commit(); err != nil {
+		return nil, 0, fmt.Errorf("commit revert workflow draft tx: %w", err)
+	}
+	return json.RawMessage(astRaw), len(ids), nil
+}
+
+// diffSteps compares two ASTs by step ID.
+func diffSteps(beforeRaw, afterRaw []byte) (added, removed, changed []string) {
+	index := func(raw []byte) map[string]string {
+		var ast engine.WorkflowAST
+		_ = json.Unmarshal(raw, &ast)
+		out := make(map[string]string, len(ast.Steps))
+		for _, step := range ast.Steps {
+			encoded, _ := json.Marshal(step)
+			out[step.ID] = string(encoded)
+		}
+		return out
+	}
+	before, after := index(beforeRaw), index(afterRaw)
+	added, removed, changed = []string{}, []string{}, []string{}
+	for id, encoded := range after {
+		previous, ok := before[id]
+		switch {
+		case !ok:
+			added = append(added, id)
+		case previous != encoded:
+			changed = append(changed, id)
+		}
+	}
+	for id := range before {
+		if _, ok := after[id]; !ok {
+			removed = append(removed, id)
+		}
+	}
+	sort.Strings(added)
+	sort.Strings(removed)
+	sort.Strings(changed)
+	return added, removed, changed
+}
@@ -0,0 +1,19 @@
+package workflows
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestDiffSteps(t *testing.T) {
+	before := []byte(`{"steps":[{"id":"a","type":"rule"},{"id":"b","type":"rule"},{"id":"c","type":"rule"}]}`)
+	after := []byte(`{"steps":[{"id":"a","type":"rule"},{"id":"b","type":"human_task"},{"id":"d","type":"rule"}]}`)
+	added, removed, changed := diffSteps(before, after)
+	if !reflect.DeepEqual(added, []string{"d"}) || !reflect.DeepEqual(removed, []string{"c"}) || !reflect.DeepEqual(changed, []string{"b"}) {
+		t.Fatalf("unexpected diff: added=%v removed=%v changed=%v", added, removed, changed)
+	}
+	added, removed, changed = diffSteps(before, before)
+	if len(added)+len(removed)+len(changed) != 0 {
+		t.Fatalf("expected no diff, got added=%v removed=%v changed=%v", added, removed, changed)
+	}
+}
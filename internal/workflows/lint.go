@@ -0,0 +1,320 @@
+package workflows
+
+import (
+	"encoding/json"
+	"fmt"
+	"regexp"
+	"sort"
+	"strings"
+
+	"github.com/neural-chilli/aceryx/internal/engine"
+	"gopkg.in/yaml.v3"
+)
+
+// Lint severities. Findings never stop a workflow being saved or published;
+// aceryx validate --lint fails on error findings.
+const (
+	LintSeverityError   = "error"
+	LintSeverityWarning = "warning"
+	LintSeverityInfo    = "info"
+	lintSeverityOff     = "off"
+)
+
+// Lint rule IDs, as used in suppressions and ACERYX_LINT_RULES.
+const (
+	LintRuleUnusedOutput    = "unused-output"
+	LintRuleMissingRetry    = "missing-retry"
+	LintRuleHardcodedSecret = "hardcoded-credential"
+	LintRuleUnboundedLoop   = "unbounded-loop"
+)
+
+const (
+	lintSuppressAll            = "all"
+	lintSuppressionMetadataKey = "lint_ignore"
+)
+
+var defaultLintSeverity = map[string]string{
+	LintRuleUnusedOutput:    LintSeverityInfo,
+	LintRuleMissingRetry:    LintSeverityWarning,
+	LintRuleHardcodedSecret: LintSeverityError,
+	LintRuleUnboundedLoop:   LintSeverityWarning,
+}
+
+// LintFinding is one lint rule match. StepID is empty for workflow-wide
+// findings.
+type LintFinding struct {
+	Rule     string `json:"rule"`
+	Severity string `json:"severity"`
+	StepID   string `json:"stepId,omitempty"`
+	Field    string `json:"field,omitempty"`
+	Message  string `json:"message"`
+}
+
+// LintRules overrides rule severities; "off" disables a rule. Rules not
+// listed keep their default severity.
+type LintRules map[string]string
+
+// ParseLintRules reads a comma-separated list of rule=severity pairs, such
+// as "unused-output=off,missing-retry=error".
+func ParseLintRules(raw string) (LintRules, error) {
+	rules := LintRules{}
+	for _, item := range strings.Split(raw, ",") {
+		item = strings.TrimSpace(item)
+		if item == "" {
+			continue
+		}
+		rule, severity, ok := strings.Cut(item, "=")
+		rule, severity = strings.TrimSpace(rule), strings.ToLower(strings.TrimSpace(severity))
+		if !ok || rule == "" {
+			return nil, fmt.Errorf("lint rule %q must be rule=severity", item)
+		}
+		if _, known := defaultLintSeverity[rule]; !known {
+			return nil, fmt.Errorf("unknown lint rule %q", rule)
+		}
+		switch severity {
+		case LintSeverityError, LintSeverityWarning, LintSeverityInfo, lintSeverityOff:
+		default:
+			return nil, fmt.Errorf("lint rule %s: severity must be error, warning, info or off", rule)
+		}
+		rules[rule] = severity
+	}
+	return rules, nil
+}
+
+func (r LintRules) severity(rule string) string {
+	if severity, ok := r[rule]; ok {
+		return severity
+	}
+	return defaultLintSeverity[rule]
+}
+
+// SetLintRules replaces the rule severities used by Lint.
+func (s *Service) SetLintRules(rules LintRules) {
+	s.lintRules = rules
+}
+
+// Lint checks a workflow definition with the service's rules. yamlSource is
+// the YAML the definition was imported from, if any: it supplies suppression
+// comments, and the AST when astRaw is empty.
+func (s *Service) Lint(astRaw json.RawMessage, yamlSource string) []LintFinding {
+	if len(astRaw) == 0 && yamlSource != "" {
+		astRaw, _ = ParseYAML(yamlSource)
+	}
+	var ast engine.WorkflowAST
+	if err := json.Unmarshal(astRaw, &ast); err != nil {
+		return []LintFinding{}
+	}
+	return Lint(ast, yamlSource, s.lintRules)
+}
+
+// Lint reports likely mistakes that validation accepts:
+//
+//   - unused-output: an integration or ai_component step whose result no
+//     other step, condition or workflow output reads.
+//   - missing-retry: a step calling an external service that runs only once.
+//   - hardcoded-credential: a config value that looks like a password, token
+//     or key rather than a {{secrets.*}} reference.
+//   - unbounded-loop: a for_each over caller-supplied case data without
+//     batching, which can run the step up to engine.MaxForEachItems times.
+//
+// A step suppresses rules by listing them in metadata.lint_ignore, or with a
+// "# lint-ignore: rule, rule" comment on the step in YAML. A lint-ignore
+// comment at the top of the file applies to every step; "all" suppresses
+// every rule.
+func Lint(ast engine.WorkflowAST, yamlSource string, rules LintRules) []LintFinding {
+	workflowIgnored, stepIgnored := yamlLintSuppressions(yamlSource)
+	out := make([]LintFinding, 0)
+	add := func(rule string, step engine.WorkflowStep, field, message string) {
+		severity := rules.severity(rule)
+		if severity == lintSeverityOff || workflowIgnored[rule] || workflowIgnored[lintSuppressAll] {
+			return
+		}
+		stepID := strings.TrimSpace(step.ID)
+		ignored := metadataLintSuppressions(step)
+		for name := range stepIgnored[stepID] {
+			ignored[name] = true
+		}
+		if ignored[rule] || ignored[lintSuppressAll] {
+			return
+		}
+		out = append(out, LintFinding{Rule: rule, Severity: severity, StepID: stepID, Field: field, Message: message})
+	}
+
+	for _, step := range ast.Steps {
+		stepID := strings.TrimSpace(step.ID)
+		stepType := strings.TrimSpace(step.Type)
+		cfg, _ := decodeStepConfig(step)
+
+		if (stepType == "integration" || stepType == "ai_component") && len(step.Outcomes) == 0 && !hasStringValue(cfg, "output_path") && !stepOutputReferenced(ast, stepID) {
+			add(LintRuleUnusedOutput, step, "", fmt.Sprintf("Step %q produces a result that no other step, condition or workflow output reads", stepID))
+		}
+		if isNetworkStepType(stepType) && engine.EffectiveMaxAttempts(step) <= 1 {
+			add(LintRuleMissingRetry, step, "error_policy.max_attempts", fmt.Sprintf("Step %q calls an external service but is not retried; set error_policy.max_attempts above 1", stepID))
+		}
+		for _, field := range hardcodedCredentials(cfg, "config") {
+			add(LintRuleHardcodedSecret, step, field, fmt.Sprintf("Step %q has what looks like a credential in %s; use a {{secrets.*}} reference", stepID, field))
+		}
+		if step.ForEach != nil && step.ForEach.BatchSize <= 0 {
+			items := strings.TrimSpace(step.ForEach.Items)
+			if strings.HasPrefix(items, "case.") && !strings.HasPrefix(items, "case.steps.") {
+				add(LintRuleUnboundedLoop, step, "for_each.items", fmt.Sprintf("Step %q loops over caller-supplied %s and may run up to %d times; set for_each.batch_size or bound the input schema", stepID, items, engine.MaxForEachItems))
+			}
+		}
+	}
+	return out
+}
+
+// HasLintErrors reports whether any finding has error severity.
+func HasLintErrors(findings []LintFinding) bool {
+	for _, finding := range findings {
+		if finding.Severity == LintSeverityError {
+			return true
+		}
+	}
+	return false
+}
+
+func isNetworkStepType(stepType string) bool {
+	switch stepType {
+	case "integration", "agent", "ai_component", "mcp-client":
+		return true
+	}
+	return false
+}
+
+// stepOutputReferenced reports whether anything outside the step reads
+// steps.<id>.
+func stepOutputReferenced(ast engine.WorkflowAST, stepID string) bool {
+	ref := regexp.MustCompile(`(^|[^A-Za-z0-9_])steps\.` + regexp.QuoteMeta(stepID) + `($|[^A-Za-z0-9_-])`)
+	for _, expr := range ast.Outputs {
+		if ref.MatchString(expr) {
+			return true
+		}
+	}
+	for _, step := range ast.Steps {
+		if strings.TrimSpace(step.ID) == stepID {
+			continue
+		}
+		if ref.Match(step.Config) || ref.MatchString(step.Condition) {
+			return true
+		}
+		if step.ForEach != nil && ref.MatchString(step.ForEach.Items) {
+			return true
+		}
+	}
+	return false
+}
+
+var (
+	credentialKeyPattern   = regexp.MustCompile(`(?i)(^|[_-])(password|passwd|secret|token|api_?key|private_?key|authorization)$`)
+	credentialValuePattern = regexp.MustCompile(`AKIA[0-9A-Z]{16}|gh[pousr]_[A-Za-z0-9]{36}|xox[abpr]-[A-Za-z0-9-]{10,}|sk-[A-Za-z0-9_-]{20,}|-----BEGIN [A-Z ]*PRIVATE KEY-----|(?i)^bearer\s+[A-Za-z0-9._~+/-]{16,}=*$`)
+)
+
+// hardcodedCredentials lists the config fields holding a literal value that
+// looks like a credential, either by key name or by its shape. Templated
+// values are assumed to reference secrets.
+func hardcodedCredentials(value any, path string) []string {
+	var out []string
+	switch v := value.(type) {
+	case map[string]any:
+		keys := make([]string, 0, len(v))
+		for key := range v {
+			keys = append(keys, key)
+		}
+		sort.Strings(keys)
+		for _, key := range keys {
+			field := path + "." + key
+			if s, ok := v[key].(string); ok && credentialKeyPattern.MatchString(key) && strings.TrimSpace(s) != "" && !strings.Contains(s, "{{") {
+				out = append(out, field)
+				continue
+			}
+			out = append(out, hardcodedCredentials(v[key], field)...)
+		}
+	case []any:
+		for i, item := range v {
+			out = append(out, hardcodedCredentials(item, fmt.Sprintf("%s[%d]", path, i))...)
+		}
+	case string:
+		if !strings.Contains(v, "{{") && credentialValuePattern.MatchString(strings.TrimSpace(v)) {
+			out = append(out, path)
+		}
+	}
+	return out
+}
+
+func metadataLintSuppressions(step engine.WorkflowStep) map[string]bool {
+	out := map[string]bool{}
+	switch v := step.Metadata[lintSuppressionMetadataKey].(type) {
+	case string:
+		for _, rule := range strings.Split(v, ",") {
+			out[strings.TrimSpace(rule)] = true
+		}
+	case []any:
+		for _, item := range v {
+			if rule, ok := item.(string); ok {
+				out[strings.TrimSpace(rule)] = true
+			}
+		}
+	}
+	return out
+}
+
+var lintIgnoreComment = regexp.MustCompile(`lint-ignore:\s*([A-Za-z0-9_, -]+)`)
+
+// yamlLintSuppressions reads lint-ignore comments: those before the first
+// key apply to the whole workflow, those on a step's own lines to the step.
+func yamlLintSuppressions(source string) (map[string]bool, map[string]map[string]bool) {
+	workflow := map[string]bool{}
+	steps := map[string]map[string]bool{}
+	if strings.TrimSpace(source) == "" {
+		return workflow, steps
+	}
+	var doc yaml.Node
+	if err := yaml.Unmarshal([]byte(source), &doc); err != nil || len(doc.Content) == 0 {
+		return workflow, steps
+	}
+	root := doc.Content[0]
+	addLintIgnores(workflow, doc.HeadComment, root.HeadComment)
+	if root.Kind != yaml.MappingNode {
+		return workflow, steps
+	}
+	if len(root.Content) > 0 {
+		addLintIgnores(workflow, root.Content[0].HeadComment)
+	}
+	for i := 0; i+1 < len(root.Content); i += 2 {
+		if root.Content[i].Value != "steps" || root.Content[i+1].Kind != yaml.SequenceNode {
+			continue
+		}
+		for _, item := range root.Content[i+1].Content {
+			if item.Kind != yaml.MappingNode {
+				continue
+			}
+			ignored := map[string]bool{}
+			addLintIgnores(ignored, item.HeadComment, item.LineComment)
+			stepID := ""
+			for k := 0; k+1 < len(item.Content); k += 2 {
+				key, value := item.Content[k], item.Content[k+1]
+				addLintIgnores(ignored, key.HeadComment, key.LineComment, value.LineComment)
+				if key.Value == "id" {
+					stepID = strings.TrimSpace(value.Value)
+				}
+			}
+			if stepID != "" && len(ignored) > 0 {
+				steps[stepID] = ignored
+			}
+		}
+	}
+	return workflow, steps
+}
+
+func addLintIgnores(into map[string]bool, comments ...string) {
+	for _, comment := range comments {
+		for _, match := range lintIgnoreComment.FindAllStringSubmatch(comment, -1) {
+			for _, rule := range strings.Split(match[1], ",") {
+				if rule = strings.TrimSpace(rule); rule != "" {
+					into[rule] = true
+				}
+			}
+		}
+	}
+}
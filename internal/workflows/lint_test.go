@@ -0,0 +1,81 @@
+package workflows
+
+import (
+	"encoding/json"
+	"strings"
+	"testing"
+
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+const lintFixture = `
+steps:
+  - id: fetch
+    type: integration
+    config:
+      connector: http
+      action: request
+      auth:
+        api_key: sk-live0123456789abcdefghij
+      input:
+        url: https://api.example.com/{{case.id}}
+    error_policy:
+      max_attempts: 1
+  - id: notify # lint-ignore: unused-output
+    type: integration
+    depends_on: [fetch]
+    for_each:
+      items: case.recipients
+    config:
+      connector: slack
+      action: send_message
+      input:
+        text: "{{case.steps.fetch.result.output.status}}"
+        token: "{{secrets.slack_token}}"
+`
+
+func findingKeys(t *testing.T, findings []LintFinding) []string {
+	t.Helper()
+	out := make([]string, 0, len(findings))
+	for _, f := range findings {
+		out = append(out, f.StepID+":"+f.Rule+":"+f.Severity)
+	}
+	return out
+}
+
+func TestLintReportsRulesAndHonoursSuppressions(t *testing.T) {
+	astRaw, err := ParseYAML(lintFixture)
+	if err != nil {
+		t.Fatalf("ParseYAML: %v", err)
+	}
+	var ast engine.WorkflowAST
+	if err := json.Unmarshal(astRaw, &ast); err != nil {
+		t.Fatalf("decode ast: %v", err)
+	}
+	got := strings.Join(findingKeys(t, Lint(ast, lintFixture, nil)), ",")
+	want := "fetch:missing-retry:warning,fetch:hardcoded-credential:error,notify:unbounded-loop:warning"
+	if got != want {
+		t.Fatalf("findings = %s, want %s", got, want)
+	}
+
+	rules, err := ParseLintRules("missing-retry=off, hardcoded-credential=warning")
+	if err != nil {
+		t.Fatalf("ParseLintRules: %v", err)
+	}
+	ast.Steps[1].Metadata = map[string]any{"lint_ignore": []any{"unbounded-loop"}}
+	findings := Lint(ast, "# lint-ignore: unused-output\n"+lintFixture, rules)
+	if got := strings.Join(findingKeys(t, findings), ","); got != "fetch:hardcoded-credential:warning" {
+		t.Fatalf("findings with overrides = %s", got)
+	}
+	if HasLintErrors(findings) {
+		t.Fatalf("expected no error findings after downgrading")
+	}
+}
+
+func TestParseLintRulesRejectsUnknown(t *testing.T) {
+	for _, raw := range []string{"typo=off", "unused-output", "unused-output=loud"} {
+		if _, err := ParseLintRules(raw); err == nil {
+			t.Fatalf("expected %q to be rejected", raw)
+		}
+	}
+}
@@ -0,0 +1,64 @@
+package workflows
+
+import (
+	"encoding/base64"
+	"encoding/json"
+	"errors"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+// ErrInvalidCursor is returned for list cursors that are malformed or were
+// issued for a different sort.
+var ErrInvalidCursor = errors.New("invalid cursor")
+
+// cursorCasts are the SQL types of the sort columns, for comparing a
+// cursor's sort value with them.
+var cursorCasts = map[string]string{
+	SortName:      "text",
+	SortCreatedAt: "timestamptz",
+	SortUpdatedAt: "timestamptz",
+}
+
+// listCursor marks the last workflow of a page by its sort value and ID, so
+// the next page starts after it however many workflows were added or
+// removed before it. It records the sort it was issued for.
+type listCursor struct {
+	Sort       string    `json:"s"`
+	Descending bool      `json:"d,omitempty"`
+	Value      string    `json:"v"`
+	ID         uuid.UUID `json:"id"`
+}
+
+func encodeListCursor(sortKey string, descending bool, last Workflow) string {
+	value := last.Name
+	switch sortKey {
+	case SortCreatedAt:
+		value = last.CreatedAt.Format(time.RFC3339Nano)
+	case SortUpdatedAt:
+		value = last.UpdatedAt.Format(time.RFC3339Nano)
+	}
+	raw, _ := json.Marshal(listCursor{Sort: sortKey, Descending: descending, Value: value, ID: last.ID})
+	return base64.RawURLEncoding.EncodeToString(raw)
+}
+
+func decodeListCursor(raw, sortKey string, descending bool) (listCursor, error) {
+	decoded, err := base64.RawURLEncoding.DecodeString(raw)
+	if err != nil {
+		return listCursor{}, ErrInvalidCursor
+	}
+	var cursor listCursor
+	if err := json.Unmarshal(decoded, &cursor); err != nil || cursor.ID == uuid.Nil {
+		return listCursor{}, ErrInvalidCursor
+	}
+	if cursor.Sort != sortKey || cursor.Descending != descending {
+		return listCursor{}, ErrInvalidCursor
+	}
+	if cursorCasts[sortKey] == "timestamptz" {
+		if _, err := time.Parse(time.RFC3339Nano, cursor.Value); err != nil {
+			return listCursor{}, ErrInvalidCursor
+		}
+	}
+	return cursor, nil
+}
@@ -0,0 +1,32 @@
+package workflows
+
+import (
+	"errors"
+	"testing"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+func TestListCursorRoundTrip(t *testing.T) {
+	last := Workflow{ID: uuid.New(), Name: "Loan Origination", UpdatedAt: time.Date(2026, 3, 14, 16, 20, 0, 123456000, time.UTC)}
+
+	raw := encodeListCursor(SortUpdatedAt, true, last)
+	cursor, err := decodeListCursor(raw, SortUpdatedAt, true)
+	if err != nil {
+		t.Fatalf("decode cursor: %v", err)
+	}
+	if cursor.ID != last.ID || cursor.Value != "2026-03-14T16:20:00.123456Z" {
+		t.Fatalf("unexpected cursor %+v", cursor)
+	}
+
+	if _, err := decodeListCursor(raw, SortUpdatedAt, false); !errors.Is(err, ErrInvalidCursor) {
+		t.Fatalf("expected a cursor for another direction to be rejected, got %v", err)
+	}
+	if _, err := decodeListCursor(raw, SortName, true); !errors.Is(err, ErrInvalidCursor) {
+		t.Fatalf("expected a cursor for another sort to be rejected, got %v", err)
+	}
+	if _, err := decodeListCursor("not-a-cursor", SortName, false); !errors.Is(err, ErrInvalidCursor) {
+		t.Fatalf("expected a malformed cursor to be rejected, got %v", err)
+	}
+}
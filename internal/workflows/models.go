@@ -11,7 +11,43 @@ type Workflow struct {
 	ID                uuid.UUID          `json:"id"`
 	Name              string             `json:"name"`
 	CaseTypeID        string             `json:"case_type_id"`
+	OwnerID           uuid.UUID          `json:"owner_id"`
+	CreatedAt         time.Time          `json:"created_at"`
+	UpdatedAt         time.Time          `json:"updated_at"`
+	Tags              []string           `json:"tags"`
+	State             string             `json:"state"`
+	RequiredApprovals int                `json:"required_approvals"`
 	PublishedVersions []PublishedVersion `json:"published_versions,omitempty"`
+	Permissions       *Permissions       `json:"permissions,omitempty"`
+	Contract          *Contract          `json:"contract,omitempty"`
+}
+
+const (
+	SortName      = "name"
+	SortCreatedAt = "created_at"
+	SortUpdatedAt = "updated_at"
+)
+
+// ListOptions pages and orders workflow listings. A zero Limit means no
+// limit; an empty State lists workflows in every state.
+// ListOptions selects a page of workflows. Cursor, a NextCursor from an
+// earlier page with the same sort, continues after that page and takes the
+// place of Offset.
+type ListOptions struct {
+	Limit      int
+	Offset     int
+	Cursor     string
+	Sort       string
+	Descending bool
+	State      string
+}
+
+// ListPage is a page of workflows. NextCursor is set when more workflows
+// follow the page.
+type ListPage struct {
+	Items      []Workflow
+	Total      int
+	NextCursor string
 }
 
 type PublishedVersion struct {
@@ -11,6 +11,7 @@ import (
 
 	"github.com/google/uuid"
 	"github.com/neural-chilli/aceryx/internal/ai"
+	"github.com/neural-chilli/aceryx/internal/connectors"
 	"github.com/neural-chilli/aceryx/internal/engine"
 )
 
@@ -55,11 +56,20 @@ type aiComponentCatalog interface {
 	List(ctx context.Context, tenantID uuid.UUID) ([]*ai.AIComponentDef, error)
 }
 
+// credentialChecker reports the secrets a credential profile lacks for a
+// connector's required auth fields.
+type credentialChecker interface {
+	MissingCredentials(ctx context.Context, tenantID uuid.UUID, connectorKey, profile string, auth map[string]string) ([]string, error)
+}
+
 func validatePublishWorkflow(
 	ctx context.Context,
 	tenantID uuid.UUID,
 	astRaw []byte,
 	catalog aiComponentCatalog,
+	egress *connectors.EgressPolicy,
+	credentials credentialChecker,
+	contracts contractCatalog,
 ) error {
 	var workflow engine.WorkflowAST
 	if err := json.Unmarshal(astRaw, &workflow); err != nil {
@@ -105,6 +115,10 @@ func validatePublishWorkflow(
 			continue
 		}
 		addMissingRequiredConfigErrors(validation, step, cfg)
+		addEgressErrors(validation, step, cfg, egress)
+		if err := addCredentialErrors(ctx, tenantID, validation, step, cfg, credentials); err != nil {
+			return err
+		}
 
 		for _, dep := range step.DependsOn {
 			if _, ok := byID[dep]; !ok {
@@ -135,6 +149,11 @@ func validatePublishWorkflow(
 	if err := validateComponentRefs(ctx, tenantID, workflow, catalog, validation); err != nil {
 		return err
 	}
+	addContractErrors(validation, workflow)
+	addEnvironmentErrors(validation, workflow)
+	if err := validateSubWorkflowCalls(ctx, tenantID, workflow, contracts, validation); err != nil {
+		return err
+	}
 
 	if err := engine.ValidateAST(workflow); err != nil {
 		if errors.Is(err, engine.ErrCycleDetectedInAST) {
@@ -250,7 +269,7 @@ func suggestComponentIDs(missing string, known []string) []string {
 
 func isSupportedStepType(stepType string) bool {
 	switch strings.TrimSpace(stepType) {
-	case "human_task", "agent", "ai_component", "extraction", "integration", "rule", "timer", "notification":
+	case "human_task", "agent", "ai_component", "extraction", "integration", "rule", "timer", "delay", "wait_until", "notification", "sub_workflow", "aggregate":
 		return true
 	default:
 		return false
@@ -303,6 +322,24 @@ func addMissingRequiredConfigErrors(validation *PublishValidationErrors, step en
 				Message: fmt.Sprintf("Step %q ai_component requires component", stepID),
 			})
 		}
+	case "sub_workflow":
+		if !hasStringValue(cfg, "case_type") {
+			validation.add(PublishValidationError{
+				StepID:  stepID,
+				Field:   "config.case_type",
+				Code:    "MISSING_REQUIRED_CONFIG",
+				Message: fmt.Sprintf("Step %q sub_workflow requires case_type", stepID),
+			})
+		}
+	case "aggregate":
+		if !hasKey(cfg, "from") {
+			validation.add(PublishValidationError{
+				StepID:  stepID,
+				Field:   "config.from",
+				Code:    "MISSING_REQUIRED_CONFIG",
+				Message: fmt.Sprintf("Step %q aggregate requires from", stepID),
+			})
+		}
 	case "extraction":
 		hasSchema := hasStringValue(cfg, "schema_id") || hasStringValue(cfg, "schema_name") || hasStringValue(cfg, "schema")
 		if !hasSchema {
@@ -348,13 +385,17 @@ func addMissingRequiredConfigErrors(validation *PublishValidationErrors, step en
 				Message: fmt.Sprintf("Step %q rule requires outcomes", stepID),
 			})
 		}
-	case "timer":
-		if !hasStringValue(cfg, "duration") && !hasStringValue(cfg, "schedule") {
+	case "timer", "delay", "wait_until":
+		if requirement := missingTimerConfig(step.Type, cfg); requirement != "" {
+			field := "config.duration"
+			if strings.TrimSpace(step.Type) == "wait_until" {
+				field = "config.until"
+			}
 			validation.add(PublishValidationError{
 				StepID:  stepID,
-				Field:   "config.duration",
+				Field:   field,
 				Code:    "MISSING_REQUIRED_CONFIG",
-				Message: fmt.Sprintf("Step %q timer requires duration or schedule", stepID),
+				Message: fmt.Sprintf("Step %q %s requires %s", stepID, step.Type, requirement),
 			})
 		}
 	case "notification":
@@ -377,6 +418,57 @@ func addMissingRequiredConfigErrors(validation *PublishValidationErrors, step en
 	}
 }
 
+func addEgressErrors(validation *PublishValidationErrors, step engine.WorkflowStep, cfg map[string]any, egress *connectors.EgressPolicy) {
+	if egress == nil || strings.TrimSpace(step.Type) != "integration" || cfg["connector"] != "http" {
+		return
+	}
+	input, _ := cfg["input"].(map[string]any)
+	rawURL, _ := input["url"].(string)
+	if rawURL == "" || strings.Contains(rawURL, "{{") {
+		return
+	}
+	if err := egress.CheckURL(rawURL); err != nil {
+		validation.add(PublishValidationError{
+			StepID:  strings.TrimSpace(step.ID),
+			Field:   "config.input.url",
+			Code:    "EGRESS_BLOCKED",
+			Message: fmt.Sprintf("Step %q url is not permitted: %v", strings.TrimSpace(step.ID), err),
+		})
+	}
+}
+
+// addCredentialErrors checks that an integration step's credential profile
+// has a secret for each required auth field the step does not set itself.
+// Templated profiles are only checked when the step runs.
+func addCredentialErrors(ctx context.Context, tenantID uuid.UUID, validation *PublishValidationErrors, step engine.WorkflowStep, cfg map[string]any, credentials credentialChecker) error {
+	profile, _ := cfg["credentials"].(string)
+	profile = strings.TrimSpace(profile)
+	if credentials == nil || strings.TrimSpace(step.Type) != "integration" || profile == "" || strings.Contains(profile, "{{") {
+		return nil
+	}
+	connectorKey, _ := cfg["connector"].(string)
+	auth := map[string]string{}
+	if rawAuth, ok := cfg["auth"].(map[string]any); ok {
+		for key, value := range rawAuth {
+			auth[key], _ = value.(string)
+		}
+	}
+	missing, err := credentials.MissingCredentials(ctx, tenantID, connectorKey, profile, auth)
+	if err != nil {
+		return fmt.Errorf("check credentials: %w", err)
+	}
+	for _, key := range missing {
+		validation.add(PublishValidationError{
+			StepID:     strings.TrimSpace(step.ID),
+			Field:      "config.credentials",
+			Code:       "CREDENTIALS_MISSING",
+			Message:    fmt.Sprintf("Credential profile %q has no secret %q", profile, key),
+			Suggestion: fmt.Sprintf("Add the secret %q, or set it in the step's auth", key),
+		})
+	}
+	return nil
+}
+
 func addGraphErrors(
 	validation *PublishValidationErrors,
 	workflow engine.WorkflowAST,
@@ -4,6 +4,7 @@ import (
 	"strings"
 	"testing"
 
+	"github.com/neural-chilli/aceryx/internal/connectors"
 	"github.com/neural-chilli/aceryx/internal/engine"
 )
 
@@ -46,3 +47,44 @@ func TestAddMissingRequiredConfigErrors_RuleRequiresOutcomes(t *testing.T) {
 		t.Fatalf("expected missing outcomes error, got %#v", validation.Errors)
 	}
 }
+
+func TestAddEgressErrors_RejectsLiteralPrivateURL(t *testing.T) {
+	policy := &connectors.EgressPolicy{}
+	step := engine.WorkflowStep{ID: "fetch", Type: "integration"}
+
+	validation := &PublishValidationErrors{Errors: make([]PublishValidationError, 0)}
+	cfg := map[string]any{"connector": "http", "action": "request", "input": map[string]any{"url": "http://169.254.169.254/latest/meta-data/"}}
+	addEgressErrors(validation, step, cfg, policy)
+	if len(validation.Errors) != 1 || validation.Errors[0].Code != "EGRESS_BLOCKED" {
+		t.Fatalf("expected EGRESS_BLOCKED, got %#v", validation.Errors)
+	}
+
+	validation = &PublishValidationErrors{Errors: make([]PublishValidationError, 0)}
+	cfg["input"] = map[string]any{"url": "{{case.data.endpoint}}"}
+	addEgressErrors(validation, step, cfg, policy)
+	if len(validation.Errors) != 0 {
+		t.Fatalf("templated url should be left to run time, got %#v", validation.Errors)
+	}
+}
+
+func TestAddMissingRequiredConfigErrors_TimerSteps(t *testing.T) {
+	tests := []struct {
+		stepType string
+		cfg      map[string]any
+		wantErr  bool
+	}{
+		{"delay", map[string]any{"minutes": 5}, false},
+		{"delay", map[string]any{"until": "2026-03-03T00:00:00Z"}, true},
+		{"wait_until", map[string]any{"schedule": "0 9 * * mon"}, false},
+		{"wait_until", map[string]any{"duration": "1h"}, true},
+		{"timer", map[string]any{"until_path": "case.data.due"}, false},
+		{"timer", map[string]any{}, true},
+	}
+	for _, tc := range tests {
+		validation := &PublishValidationErrors{Errors: make([]PublishValidationError, 0)}
+		addMissingRequiredConfigErrors(validation, engine.WorkflowStep{ID: "wait", Type: tc.stepType}, tc.cfg)
+		if got := len(validation.Errors) > 0; got != tc.wantErr {
+			t.Fatalf("%s %v: errors %#v", tc.stepType, tc.cfg, validation.Errors)
+		}
+	}
+}
@@ -0,0 +1,245 @@
+package workflows
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"strings"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/cases"
+	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/engine"
+	"github.com/neural-chilli/aceryx/internal/redaction"
+)
+
+var (
+	// ErrNoRecordedInputs is returned when replaying a step that never ran,
+	// or a case whose steps ran before input recording was enabled.
+	ErrNoRecordedInputs = errors.New("no recorded inputs")
+	// ErrReplayRejected is returned when the recorded case data no longer
+	// passes the case type schema or workflow contract.
+	ErrReplayRejected = errors.New("replay rejected")
+)
+
+type caseCreator interface {
+	CreateCase(ctx context.Context, tenantID, createdBy uuid.UUID, req cases.CreateCaseRequest) (cases.Case, []cases.ValidationError, error)
+}
+
+// SetCaseCreator lets Replay start new cases.
+func (s *Service) SetCaseCreator(creator caseCreator) {
+	s.cases = creator
+}
+
+// ReplayRequest selects what to replay. Without StepID the whole workflow
+// version reruns as a new case from the inputs the case's first step ran
+// with; with StepID only that step runs, with its recorded inputs. DryRun
+// resolves templates and routing without invoking anything.
+type ReplayRequest struct {
+	StepID string `json:"step_id,omitempty"`
+	DryRun bool   `json:"dry_run,omitempty"`
+}
+
+// RecordedInputs is what the latest attempt of a step ran with: its config
+// and the case data and completed step results its templates resolved
+// against.
+type RecordedInputs struct {
+	Attempt    int             `json:"attempt"`
+	Config     json.RawMessage `json:"config"`
+	Case       map[string]any  `json:"case"`
+	Steps      map[string]any  `json:"steps"`
+	RecordedAt time.Time       `json:"recorded_at"`
+}
+
+// ReplayResult reports a replay. A step replay carries the recorded Inputs,
+// the ResolvedConfig and, unless dry run, the step's Result. A workflow
+// replay carries the Plan for a dry run, otherwise the new ReplayCaseID.
+type ReplayResult struct {
+	CaseID         uuid.UUID              `json:"case_id"`
+	WorkflowID     uuid.UUID              `json:"workflow_id"`
+	Version        int                    `json:"version"`
+	StepID         string                 `json:"step_id,omitempty"`
+	DryRun         bool                   `json:"dry_run"`
+	Inputs         *RecordedInputs        `json:"inputs,omitempty"`
+	ResolvedConfig json.RawMessage        `json:"resolved_config,omitempty"`
+	Result         *connectors.TestResult `json:"result,omitempty"`
+	Plan           *DryRunResult          `json:"plan,omitempty"`
+	ReplayCaseID   *uuid.UUID             `json:"replay_case_id,omitempty"`
+}
+
+type recordedStep struct {
+	stepID string
+	inputs RecordedInputs
+	result json.RawMessage
+}
+
+// Replay reruns a case's workflow version, or one of its steps, with the
+// inputs recorded when the case ran.
+func (s *Service) Replay(ctx context.Context, tenantID, actorID, caseID uuid.UUID, req ReplayRequest) (ReplayResult, error) {
+	out := ReplayResult{CaseID: caseID, StepID: strings.TrimSpace(req.StepID), DryRun: req.DryRun}
+	var (
+		caseType     string
+		variablesRaw []byte
+		mockProfile  string
+	)
+	err := s.db.QueryRowContext(ctx, `
+SELECT c.workflow_id, c.workflow_version, ct.name, c.variables, COALESCE(c.mock_profile, '')
+FROM cases c
+JOIN case_types ct ON ct.id = c.case_type_id
+WHERE c.id = $1 AND c.tenant_id = $2
+`, caseID, tenantID).Scan(&out.WorkflowID, &out.Version, &caseType, &variablesRaw, &mockProfile)
+	if err != nil {
+		return ReplayResult{}, err
+	}
+	recorded, err := s.recordedSteps(ctx, caseID)
+	if err != nil {
+		return ReplayResult{}, err
+	}
+	if out.StepID != "" {
+		return s.replayStep(ctx, tenantID, out, recorded)
+	}
+	if len(recorded) == 0 {
+		return ReplayResult{}, ErrNoRecordedInputs
+	}
+
+	caseData := recorded[0].inputs.Case
+	if req.DryRun {
+		mocks := make(map[string]DryRunMock, len(recorded))
+		for _, step := range recorded {
+			var result engine.StepResult
+			if err := json.Unmarshal(step.result, &result); err != nil || (result.Outcome == "" && len(result.Output) == 0) {
+				continue
+			}
+			mock := DryRunMock{Outcome: result.Outcome}
+			_ = json.Unmarshal(result.Output, &mock.Output)
+			mocks[step.stepID] = mock
+		}
+		plan, err := s.DryRun(ctx, tenantID, out.WorkflowID, DryRunRequest{Version: out.Version, CaseData: caseData, MockOutputs: mocks})
+		if err != nil {
+			return ReplayResult{}, err
+		}
+		out.Plan = &plan
+		return out, nil
+	}
+
+	if s.cases == nil {
+		return ReplayResult{}, fmt.Errorf("replay: case creation is not configured")
+	}
+	var variables map[string]any
+	if err := json.Unmarshal(variablesRaw, &variables); err != nil {
+		return ReplayResult{}, fmt.Errorf("decode case variables: %w", err)
+	}
+	created, validation, err := s.cases.CreateCase(ctx, tenantID, actorID, cases.CreateCaseRequest{
+		CaseType:        caseType,
+		Data:            caseData,
+		Variables:       variables,
+		WorkflowID:      out.WorkflowID,
+		WorkflowVersion: out.Version,
+		Lane:            engine.LaneInteractive,
+		MockProfile:     mockProfile,
+	})
+	if err != nil {
+		return ReplayResult{}, err
+	}
+	if len(validation) > 0 {
+		return ReplayResult{}, fmt.Errorf("%w: %s", ErrReplayRejected, validation[0].Message)
+	}
+	out.ReplayCaseID = &created.ID
+	return out, nil
+}
+
+func (s *Service) replayStep(ctx context.Context, tenantID uuid.UUID, out ReplayResult, recorded []recordedStep) (ReplayResult, error) {
+	var inputs *RecordedInputs
+	for i := range recorded {
+		if recorded[i].stepID == out.StepID {
+			inputs = &recorded[i].inputs
+			break
+		}
+	}
+	if inputs == nil {
+		return ReplayResult{}, fmt.Errorf("%w for step %s", ErrNoRecordedInputs, out.StepID)
+	}
+	out.Inputs = inputs
+
+	_, astRaw, err := s.loadVersionAST(ctx, tenantID, out.WorkflowID, out.Version)
+	if err != nil {
+		return ReplayResult{}, err
+	}
+	var ast engine.WorkflowAST
+	if err := json.Unmarshal(astRaw, &ast); err != nil {
+		return ReplayResult{}, fmt.Errorf("decode workflow ast: %w", err)
+	}
+	stepType := ""
+	for _, step := range ast.Steps {
+		if step.ID == out.StepID {
+			stepType = strings.TrimSpace(step.Type)
+		}
+	}
+	if stepType == "" {
+		return ReplayResult{}, ErrStepNotFound
+	}
+	return s.rerunStep(ctx, tenantID, out, inputs, stepType)
+}
+
+// rerunStep resolves, and unless out is a dry run runs, a step of type
+// stepType with its recorded inputs. Secret references are shown masked in
+// ResolvedConfig; the step tester masks them in its result.
+func (s *Service) rerunStep(ctx context.Context, tenantID uuid.UUID, out ReplayResult, inputs *RecordedInputs, stepType string) (ReplayResult, error) {
+	caseCtx := stepTestContext(StepTestRequest{CaseData: inputs.Case, StepResults: inputs.Steps})
+	var cfg map[string]any
+	if err := json.Unmarshal(inputs.Config, &cfg); err == nil {
+		masked := make(map[string]any, len(caseCtx)+1)
+		for k, v := range caseCtx {
+			masked[k] = v
+		}
+		masked["__secret_resolver"] = func(string) string { return redaction.Mask }
+		if resolved, err := json.Marshal(connectors.ResolveTemplateAny(cfg, masked)); err == nil {
+			out.ResolvedConfig = resolved
+		}
+	}
+	if out.DryRun {
+		return out, nil
+	}
+	if stepType != "integration" || s.tester == nil {
+		return ReplayResult{}, fmt.Errorf("%w: %s", ErrStepNotTestable, stepType)
+	}
+	result, err := s.tester.TestStep(ctx, tenantID, out.StepID, inputs.Config, caseCtx)
+	if err != nil {
+		return ReplayResult{}, err
+	}
+	out.Result = &result
+	return out, nil
+}
+
+// recordedSteps returns the case's steps with recorded inputs, in the order
+// they were recorded.
+func (s *Service) recordedSteps(ctx context.Context, caseID uuid.UUID) ([]recordedStep, error) {
+	rows, err := s.db.QueryContext(ctx, `
+SELECT step_id, inputs, COALESCE(result, '{}'::jsonb)
+FROM case_steps
+WHERE case_id = $1 AND inputs IS NOT NULL
+ORDER BY (inputs->>'recorded_at')::timestamptz, step_id
+`, caseID)
+	if err != nil {
+		return nil, fmt.Errorf("load recorded step inputs: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	out := make([]recordedStep, 0)
+	for rows.Next() {
+		var (
+			step                 recordedStep
+			inputsRaw, resultRaw []byte
+		)
+		if err := rows.Scan(&step.stepID, &inputsRaw, &resultRaw); err != nil {
+			return nil, fmt.Errorf("scan recorded step inputs: %w", err)
+		}
+		if err := json.Unmarshal(inputsRaw, &step.inputs); err != nil {
+			return nil, fmt.Errorf("decode recorded inputs for %s: %w", step.stepID, err)
+		}
+		step.result = resultRaw
+		out = append(out, step)
+	}
+	return out, rows.Err()
+}
@@ -0,0 +1,64 @@
+package workflows
+
+import (
+	"context"
+	"encoding/json"
+	"strings"
+	"testing"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/redaction"
+)
+
+type echoConnector struct{}
+
+func (c *echoConnector) Meta() connectors.ConnectorMeta {
+	return connectors.ConnectorMeta{Key: "echo", Name: "Echo"}
+}
+func (c *echoConnector) Auth() connectors.AuthSpec { return connectors.AuthSpec{Type: "none"} }
+func (c *echoConnector) Triggers() []connectors.TriggerSpec { return nil }
+func (c *echoConnector) Actions() []connectors.ActionSpec {
+	return []connectors.ActionSpec{{Key: "send", Execute: func(_ context.Context, _ map[string]string, input map[string]any) (map[string]any, error) {
+		return map[string]any{"sent": input["password"] != ""}, nil
+	}}}
+}
+
+type mapSecrets map[string]string
+
+func (m mapSecrets) Get(_ context.Context, _ uuid.UUID, key string) (string, error) {
+	if value, ok := m[key]; ok {
+		return value, nil
+	}
+	return "", connectors.ErrSecretNotFound
+}
+
+func TestReplayStepDoesNotReturnResolvedSecrets(t *testing.T) {
+	reg := connectors.NewRegistry()
+	reg.Register(&echoConnector{})
+	s := &Service{tester: connectors.NewExecutor(nil, reg, mapSecrets{"db_password": "hunter2"})}
+	inputs := &RecordedInputs{
+		Config: json.RawMessage(`{"connector":"echo","action":"send","input":{"password":"{{secrets.db_password}}","to":"{{case.data.email}}"}}`),
+		Case:   map[string]any{"email": "a@example.test"},
+	}
+
+	for _, dryRun := range []bool{true, false} {
+		out, err := s.rerunStep(context.Background(), uuid.New(), ReplayResult{StepID: "notify", DryRun: dryRun}, inputs, "integration")
+		if err != nil {
+			t.Fatalf("replay (dry run %v): %v", dryRun, err)
+		}
+		body, _ := json.Marshal(out)
+		if strings.Contains(string(body), "hunter2") {
+			t.Fatalf("replay (dry run %v) returned a secret: %s", dryRun, body)
+		}
+		if !strings.Contains(string(out.ResolvedConfig), `"password":"`+redaction.Mask+`"`) || !strings.Contains(string(out.ResolvedConfig), "a@example.test") {
+			t.Fatalf("unexpected resolved config %s", out.ResolvedConfig)
+		}
+		if dryRun {
+			continue
+		}
+		if out.Result == nil || out.Result.Output["sent"] != true || out.Result.Input["password"] != redaction.Mask {
+			t.Fatalf("expected the secret to reach the action but be masked in the result, got %+v", out.Result)
+		}
+	}
+}
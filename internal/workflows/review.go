@@ -0,0 +1,395 @@
+package workflows
+
+import (
+	"context"
+	"database/sql"
+	"errors"
+	"fmt"
+	"strings"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+// Workflow lifecycle states, as listed and filtered by List.
+const (
+	StateDraft     = "draft"
+	StateInReview  = "in_review"
+	StatePublished = "published"
+	StateArchived  = "archived"
+)
+
+// Review statuses.
+const (
+	ReviewOpen      = "open"
+	ReviewRejected  = "rejected"
+	ReviewWithdrawn = "withdrawn"
+	ReviewPublished = "published"
+)
+
+var (
+	// ErrUnderReview is returned when editing a draft that is in review.
+	ErrUnderReview = errors.New("draft is under review")
+	// ErrArchived is returned when editing or publishing an archived workflow.
+	ErrArchived = errors.New("workflow is archived")
+	// ErrReviewOpen is returned when submitting a draft that is already in review.
+	ErrReviewOpen = errors.New("review already open")
+	// ErrNoOpenReview is returned when deciding on or withdrawing a review
+	// that is not open.
+	ErrNoOpenReview = errors.New("no open review")
+	// ErrSelfReview is returned when the submitter reviews their own draft.
+	ErrSelfReview = errors.New("submitter cannot review their own draft")
+	// ErrApprovalsRequired is returned when publishing a draft without the
+	// approvals its workflow requires.
+	ErrApprovalsRequired = errors.New("approvals required")
+)
+
+// workflowStateColumn derives the lifecycle state of the workflow aliased w.
+const workflowStateColumn = `CASE
+    WHEN w.archived_at IS NOT NULL THEN 'archived'
+    WHEN EXISTS (SELECT 1 FROM workflow_reviews r WHERE r.workflow_id = w.id AND r.status = 'open') THEN 'in_review'
+    WHEN EXISTS (SELECT 1 FROM workflow_versions v WHERE v.workflow_id = w.id AND v.status IN ('published', 'canary')) THEN 'published'
+    ELSE 'draft'
+END`
+
+// ValidState reports whether state is a lifecycle state.
+func ValidState(state string) bool {
+	switch state {
+	case StateDraft, StateInReview, StatePublished, StateArchived:
+		return true
+	}
+	return false
+}
+
+// Review is a draft version submitted for approval. Approvals counts the
+// approve decisions; the draft can be published once it reaches
+// RequiredApprovals.
+type Review struct {
+	ID                uuid.UUID        `json:"id"`
+	WorkflowID        uuid.UUID        `json:"workflow_id"`
+	Version           int              `json:"version"`
+	Status            string           `json:"status"`
+	Note              string           `json:"note,omitempty"`
+	SubmittedBy       uuid.UUID        `json:"submitted_by"`
+	SubmittedAt       time.Time        `json:"submitted_at"`
+	ClosedAt          *time.Time       `json:"closed_at,omitempty"`
+	RequiredApprovals int              `json:"required_approvals"`
+	Approvals         int              `json:"approvals"`
+	Decisions         []ReviewDecision `json:"decisions"`
+}
+
+// ReviewDecision is one reviewer's approval or rejection.
+type ReviewDecision struct {
+	ReviewerID uuid.UUID `json:"reviewer_id"`
+	Decision   string    `json:"decision"`
+	Comment    string    `json:"comment,omitempty"`
+	DecidedAt  time.Time `json:"decided_at"`
+}
+
+// SubmitForReview opens a review of the draft. The draft must pass publish
+// validation, and cannot be edited until the review is withdrawn, rejected
+// or published.
+func (s *Service) SubmitForReview(ctx context.Context, tenantID, actorID, workflowID uuid.UUID, note string) (Review, error) {
+	tx, err := s.db.BeginTx(ctx, nil)
+	if err != nil {
+		return Review{}, fmt.Errorf("begin submit review tx: %w", err)
+	}
+	defer func() { _ = tx.Rollback() }()
+
+	var (
+		draftVersion int
+		astRaw       []byte
+		archived     bool
+	)
+	err = tx.QueryRowContext(ctx, `
+SELECT wv.version, wv.ast, w.archived_at IS NOT NULL
+FROM workflows w
+JOIN workflow_versions wv ON wv.workflow_id = w.id
+WHERE w.id = $1 AND w.tenant_id = $2 AND wv.status = 'draft'
+ORDER BY wv.version DESC
+LIMIT 1
+FOR UPDATE OF wv
+`, workflowID, tenantID).Scan(&draftVersion, &astRaw, &archived)
+	if err != nil {
+		return Review{}, err
+	}
+	if archived {
+		return Review{}, ErrArchived
+	}
+	if err := validatePublishWorkflow(ctx, tenantID, astRaw, s.catalog, s.egress, s.credentials, s); err != nil {
+		return Review{}, err
+	}
+	var open bool
+	if err := tx.QueryRowContext(ctx, `
+SELECT EXISTS (SELECT 1 FROM workflow_reviews WHERE workflow_id = $1 AND status = 'open')
+`, workflowID).Scan(&open); err != nil {
+		return Review{}, fmt.Errorf("check open review: %w", err)
+	}
+	if open {
+		return Review{}, ErrReviewOpen
+	}
+	if _, err := tx.ExecContext(ctx, `
+INSERT INTO workflow_reviews (tenant_id, workflow_id, version, note, submitted_by)
+VALUES ($1, $2, $3, $4, $5)
+`, tenantID, workflowID, draftVersion, strings.TrimSpace(note), actorID); err != nil {
+		return Review{}, fmt.Errorf("open review: %w", err)
+	}
+	if err := tx.Commit(); err != nil {
+		return Review{}, fmt.Errorf("commit submit review tx: %w", err)
+	}
+	return s.CurrentReview(ctx, tenantID, workflowID)
+}
+
+// WithdrawReview closes the open review so the draft can be edited again.
+func (s *Service) WithdrawReview(ctx context.Context, tenantID, workflowID uuid.UUID) error {
+	res, err := s.db.ExecContext(ctx, `
+UPDATE workflow_reviews
+SET status = 'withdrawn', closed_at = now()
+WHERE workflow_id = $1 AND tenant_id = $2 AND status = 'open'
+`, workflowID, tenantID)
+	if err != nil {
+		return fmt.Errorf("withdraw review: %w", err)
+	}
+	if n, _ := res.RowsAffected(); n == 0 {
+		return ErrNoOpenReview
+	}
+	return nil
+}
+
+// DecideReview records a reviewer's approval or rejection of the open
+// review. A rejection closes the review and unlocks the draft; a reviewer
+// may change an approval until then.
+func (s *Service) DecideReview(ctx context.Context, tenantID, reviewerID, workflowID uuid.UUID, approve bool, comment string) (Review, error) {
+	tx, err := s.db.BeginTx(ctx, nil)
+	if err != nil {
+		return Review{}, fmt.Errorf("begin review decision tx: %w", err)
+	}
+	defer func() { _ = tx.Rollback() }()
+
+	var reviewID, submittedBy uuid.UUID
+	err = tx.QueryRowContext(ctx, `
+SELECT id, submitted_by
+FROM workflow_reviews
+WHERE workflow_id = $1 AND tenant_id = $2 AND status = 'open'
+FOR UPDATE
+`, workflowID, tenantID).Scan(&reviewID, &submittedBy)
+	if errors.Is(err, sql.ErrNoRows) {
+		return Review{}, ErrNoOpenReview
+	}
+	if err != nil {
+		return Review{}, fmt.Errorf("load open review: %w", err)
+	}
+	if submittedBy == reviewerID {
+		return Review{}, ErrSelfReview
+	}
+	decision := "approved"
+	if !approve {
+		decision = "rejected"
+	}
+	if _, err := tx.ExecContext(ctx, `
+INSERT INTO workflow_review_decisions (review_id, reviewer_id, decision, comment)
+VALUES ($1, $2, $3, $4)
+ON CONFLICT (review_id, reviewer_id) DO UPDATE
+SET decision = EXCLUDED.decision, comment = EXCLUDED.comment, decided_at = now()
+`, reviewID, reviewerID, decision, strings.TrimSpace(comment)); err != nil {
+		return Review{}, fmt.Errorf("record review decision: %w", err)
+	}
+	if !approve {
+		if _, err := tx.ExecContext(ctx, `
+UPDATE workflow_reviews SET status = 'rejected', closed_at = now() WHERE id = $1
+`, reviewID); err != nil {
+			return Review{}, fmt.Errorf("reject review: %w", err)
+		}
+	}
+	if err := tx.Commit(); err != nil {
+		return Review{}, fmt.Errorf("commit review decision tx: %w", err)
+	}
+	return s.CurrentReview(ctx, tenantID, workflowID)
+}
+
+// CurrentReview returns the workflow's most recent review, open or closed.
+// It returns sql.ErrNoRows when the workflow has never been reviewed.
+func (s *Service) CurrentReview(ctx context.Context, tenantID, workflowID uuid.UUID) (Review, error) {
+	var (
+		review   Review
+		closedAt sql.NullTime
+	)
+	err := s.db.QueryRowContext(ctx, `
+SELECT r.id, r.workflow_id, r.version, r.status, r.note, r.submitted_by, r.submitted_at, r.closed_at, w.required_approvals
+FROM workflow_reviews r
+JOIN workflows w ON w.id = r.workflow_id
+WHERE r.workflow_id = $1 AND r.tenant_id = $2
+ORDER BY r.submitted_at DESC
+LIMIT 1
+`, workflowID, tenantID).Scan(&review.ID, &review.WorkflowID, &review.Version, &review.Status, &review.Note,
+		&review.SubmittedBy, &review.SubmittedAt, &closedAt, &review.RequiredApprovals)
+	if err != nil {
+		return Review{}, err
+	}
+	review.SubmittedAt = review.SubmittedAt.UTC()
+	if closedAt.Valid {
+		t := closedAt.Time.UTC()
+		review.ClosedAt = &t
+	}
+
+	rows, err := s.db.QueryContext(ctx, `
+SELECT reviewer_id, decision, comment, decided_at
+FROM workflow_review_decisions
+WHERE review_id = $1
+ORDER BY decided_at
+`, review.ID)
+	if err != nil {
+		return Review{}, fmt.Errorf("load review decisions: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	review.Decisions = []ReviewDecision{}
+	for rows.Next() {
+		var decision ReviewDecision
+		if err := rows.Scan(&decision.ReviewerID, &decision.Decision, &decision.Comment, &decision.DecidedAt); err != nil {
+			return Review{}, fmt.Errorf("scan review decision: %w", err)
+		}
+		decision.DecidedAt = decision.DecidedAt.UTC()
+		if decision.Decision == "approved" {
+			review.Approvals++
+		}
+		review.Decisions = append(review.Decisions, decision)
+	}
+	if err := rows.Err(); err != nil {
+		return Review{}, fmt.Errorf("iterate review decisions: %w", err)
+	}
+	return review, nil
+}
+
+// SetRequiredApprovals sets how many approvals a draft needs before it can
+// be published. Zero lets drafts be published without review.
+func (s *Service) SetRequiredApprovals(ctx context.Context, tenantID, workflowID uuid.UUID, required int) error {
+	if required < 0 {
+		return fmt.Errorf("required_approvals must not be negative")
+	}
+	res, err := s.db.ExecContext(ctx, `
+UPDATE workflows SET required_approvals = $3, updated_at = now() WHERE id = $1 AND tenant_id = $2
+`, workflowID, tenantID, required)
+	if err != nil {
+		return fmt.Errorf("set required approvals: %w", err)
+	}
+	if n, _ := res.RowsAffected(); n == 0 {
+		return sql.ErrNoRows
+	}
+	return nil
+}
+
+// Archive retires a workflow: its published and canary versions are
+// withdrawn, so no new cases start on it, and any open review is withdrawn.
+// Cases already running finish on their version.
+func (s *Service) Archive(ctx context.Context, tenantID, workflowID uuid.UUID) error {
+	tx, err := s.db.BeginTx(ctx, nil)
+	if err != nil {
+		return fmt.Errorf("begin archive workflow tx: %w", err)
+	}
+	defer func() { _ = tx.Rollback() }()
+
+	res, err := tx.ExecContext(ctx, `
+UPDATE workflows SET archived_at = now(), updated_at = now()
+WHERE id = $1 AND tenant_id = $2 AND archived_at IS NULL
+`, workflowID, tenantID)
+	if err != nil {
+		return fmt.Errorf("archive workflow: %w", err)
+	}
+	if n, _ := res.RowsAffected(); n == 0 {
+		var exists bool
+		if err := tx.QueryRowContext(ctx, `SELECT EXISTS (SELECT 1 FROM workflows WHERE id = $1 AND tenant_id = $2)`, workflowID, tenantID).Scan(&exists); err != nil {
+			return fmt.Errorf("load workflow: %w", err)
+		}
+		if !exists {
+			return sql.ErrNoRows
+		}
+		return ErrArchived
+	}
+	for _, stmt := range []string{
+		`UPDATE workflow_versions SET status = 'withdrawn' WHERE workflow_id = $1 AND status IN ('published', 'canary')`,
+		`UPDATE workflow_canaries SET status = 'rolled_back', reason = 'workflow archived', ended_at = now() WHERE workflow_id = $1 AND status = 'active'`,
+		`UPDATE workflow_reviews SET status = 'withdrawn', closed_at = now() WHERE workflow_id = $1 AND status = 'open'`,
+	} {
+		if _, err := tx.ExecContext(ctx, stmt, workflowID); err != nil {
+			return fmt.Errorf("archive workflow: %w", err)
+		}
+	}
+	if err := tx.Commit(); err != nil {
+		return fmt.Errorf("commit archive workflow tx: %w", err)
+	}
+	return nil
+}
+
+// Unarchive makes an archived workflow editable again. It stays
+// unpublished until its draft is published.
+func (s *Service) Unarchive(ctx context.Context, tenantID, workflowID uuid.UUID) error {
+	res, err := s.db.ExecContext(ctx, `
+UPDATE workflows SET archived_at = NULL, updated_at = now()
+WHERE id = $1 AND tenant_id = $2
+`, workflowID, tenantID)
+	if err != nil {
+		return fmt.Errorf("unarchive workflow: %w", err)
+	}
+	if n, _ := res.RowsAffected(); n == 0 {
+		return sql.ErrNoRows
+	}
+	return nil
+}
+
+// checkDraftEditable rejects edits to the draft of an archived workflow or
+// of one in review.
+func checkDraftEditable(ctx context.Context, tx *sql.Tx, workflowID uuid.UUID) error {
+	var archived, inReview bool
+	if err := tx.QueryRowContext(ctx, `
+SELECT w.archived_at IS NOT NULL,
+       EXISTS (SELECT 1 FROM workflow_reviews r WHERE r.workflow_id = w.id AND r.status = 'open')
+FROM workflows w
+WHERE w.id = $1
+`, workflowID).Scan(&archived, &inReview); err != nil {
+		return fmt.Errorf("check draft editable: %w", err)
+	}
+	switch {
+	case archived:
+		return ErrArchived
+	case inReview:
+		return ErrUnderReview
+	}
+	return nil
+}
+
+// checkPublishApproved gates releasing the draft version: the workflow must
+// not be archived and, when it requires approvals, the draft must be in an
+// open review with enough of them. The open review, if any, is closed as
+// published.
+func checkPublishApproved(ctx context.Context, tx *sql.Tx, workflowID uuid.UUID, draftVersion int) error {
+	var (
+		archived  bool
+		required  int
+		reviewID  uuid.NullUUID
+		version   sql.NullInt64
+		approvals int
+	)
+	if err := tx.QueryRowContext(ctx, `
+SELECT w.archived_at IS NOT NULL, w.required_approvals, r.id, r.version,
+       (SELECT COUNT(*) FROM workflow_review_decisions d WHERE d.review_id = r.id AND d.decision = 'approved')
+FROM workflows w
+LEFT JOIN workflow_reviews r ON r.workflow_id = w.id AND r.status = 'open'
+WHERE w.id = $1
+`, workflowID).Scan(&archived, &required, &reviewID, &version, &approvals); err != nil {
+		return fmt.Errorf("check publish approvals: %w", err)
+	}
+	if archived {
+		return ErrArchived
+	}
+	if required > 0 && (!reviewID.Valid || int(version.Int64) != draftVersion || approvals < required) {
+		return fmt.Errorf("%w: %d of %d", ErrApprovalsRequired, approvals, required)
+	}
+	if reviewID.Valid {
+		if _, err := tx.ExecContext(ctx, `
+UPDATE workflow_reviews SET status = 'published', closed_at = now() WHERE id = $1
+`, reviewID.UUID); err != nil {
+			return fmt.Errorf("close review: %w", err)
+		}
+	}
+	return nil
+}
@@ -0,0 +1,16 @@
+package workflows
+
+import "testing"
+
+func TestValidState(t *testing.T) {
+	for _, state := range []string{StateDraft, StateInReview, StatePublished, StateArchived} {
+		if !ValidState(state) {
+			t.Fatalf("expected %q to be valid", state)
+		}
+	}
+	for _, state := range []string{"", "canary", "Published"} {
+		if ValidState(state) {
+			t.Fatalf("expected %q to be invalid", state)
+		}
+	}
+}
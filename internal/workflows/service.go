@@ -10,36 +10,139 @@ import (
 	"time"
 
 	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/events"
+	"github.com/neural-chilli/aceryx/internal/redaction"
 	"gopkg.in/yaml.v3"
 )
 
 type Service struct {
-	db      *sql.DB
-	catalog aiComponentCatalog
+	db               *sql.DB
+	catalog          aiComponentCatalog
+	connectors       connectorCatalog
+	tester           stepTester
+	egress           *connectors.EgressPolicy
+	credentials      credentialChecker
+	toolVersions     toolVersionCatalog
+	toolDescriptions []toolDescriptionCatalog
+	lintRules        LintRules
+	cases            caseCreator
+	historyLimit     int
+	bus              *events.Bus
+	appURL           string
+	permissions      permissionChecker
+	redactor         *redaction.Redactor
 }
 
 func NewService(db *sql.DB) *Service {
 	return &Service{db: db}
 }
 
+// SetRedactor sets the rules applied to step configs in generated docs.
+func (s *Service) SetRedactor(r *redaction.Redactor) {
+	s.redactor = r
+}
+
+// SetEventBus publishes workflow.created when workflows are created.
+func (s *Service) SetEventBus(bus *events.Bus) {
+	s.bus = bus
+}
+
 func (s *Service) SetAIComponentCatalog(catalog aiComponentCatalog) {
 	s.catalog = catalog
 }
 
-func (s *Service) List(ctx context.Context, tenantID uuid.UUID) ([]Workflow, error) {
+// SetEgressPolicy makes publish reject http integration steps whose literal
+// URL the policy refuses. Templated URLs are only checked when they run.
+func (s *Service) SetEgressPolicy(policy connectors.EgressPolicy) {
+	s.egress = &policy
+}
+
+// SetCredentialChecker makes publish reject integration steps whose
+// credential profile lacks a secret the connector requires.
+func (s *Service) SetCredentialChecker(checker credentialChecker) {
+	s.credentials = checker
+}
+
+// ErrInvalidSort is returned for sort keys other than name, created_at and updated_at.
+var ErrInvalidSort = errors.New("invalid sort")
+
+// ErrWorkflowInUse is returned when deleting a workflow that cases or
+// channels still reference.
+var ErrWorkflowInUse = errors.New("workflow in use")
+
+var sortColumns = map[string]string{
+	SortName:      "w.name",
+	SortCreatedAt: "w.created_at",
+	SortUpdatedAt: "w.updated_at",
+}
+
+func (s *Service) List(ctx context.Context, tenantID uuid.UUID, opts ListOptions) (ListPage, error) {
+	sortKey := opts.Sort
+	if sortKey == "" {
+		sortKey = SortName
+	}
+	column, ok := sortColumns[sortKey]
+	if !ok {
+		return ListPage{}, fmt.Errorf("%w: %s", ErrInvalidSort, opts.Sort)
+	}
+	direction := "ASC"
+	if opts.Descending {
+		direction = "DESC"
+	}
+	// One extra row tells whether another page follows.
+	limit := sql.NullInt64{Int64: int64(opts.Limit) + 1, Valid: opts.Limit > 0}
+	offset := max(opts.Offset, 0)
+	var (
+		afterValue sql.NullString
+		afterID    uuid.NullUUID
+	)
+	if opts.Cursor != "" {
+		cursor, err := decodeListCursor(opts.Cursor, sortKey, opts.Descending)
+		if err != nil {
+			return ListPage{}, err
+		}
+		afterValue = sql.NullString{String: cursor.Value, Valid: true}
+		afterID = uuid.NullUUID{UUID: cursor.ID, Valid: true}
+		offset = 0
+	}
+	comparison := ">"
+	if opts.Descending {
+		comparison = "<"
+	}
+
+	var total int
+	stateFilter := `($2 = '' OR ` + workflowStateColumn + ` = $2)`
+	if err := s.db.QueryRowContext(ctx, `SELECT COUNT(*) FROM workflows w WHERE w.tenant_id = $1 AND `+stateFilter, tenantID, opts.State).Scan(&total); err != nil {
+		return ListPage{}, fmt.Errorf("count workflows: %w", err)
+	}
+
+	// Page over workflows first, then join published versions, so a workflow
+	// with many versions still counts once towards the limit.
+	orderBy := column + " " + direction + ", w.id " + direction
 	rows, err := s.db.QueryContext(ctx, `
-SELECT w.id, w.name, w.case_type,
+WITH page AS (
+    SELECT w.id, w.name, w.case_type, w.created_by, w.created_at, w.updated_at,
+           `+workflowStateColumn+` AS state, w.required_approvals
+    FROM workflows w
+    WHERE w.tenant_id = $1 AND ($4 = '' OR `+workflowStateColumn+` = $4)
+      AND ($6::uuid IS NULL OR (`+column+`, w.id) `+comparison+` ($5::`+cursorCasts[sortKey]+`, $6::uuid))
+    ORDER BY `+orderBy+`
+    LIMIT $2 OFFSET $3
+)
+SELECT w.id, w.name, w.case_type, w.created_by, w.created_at, w.updated_at,
+       w.state, w.required_approvals,
+       `+workflowTagsColumn+` AS tags,
        COALESCE(wv.version, 0) AS version,
        wv.published_at
-FROM workflows w
+FROM page w
 LEFT JOIN workflow_versions wv
   ON wv.workflow_id = w.id
  AND wv.status = 'published'
-WHERE w.tenant_id = $1
-ORDER BY w.name ASC, wv.version DESC
-`, tenantID)
+ORDER BY `+orderBy+`, wv.version DESC
+`, tenantID, limit, offset, opts.State, afterValue, afterID)
 	if err != nil {
-		return nil, fmt.Errorf("list workflows: %w", err)
+		return ListPage{}, fmt.Errorf("list workflows: %w", err)
 	}
 	defer func() { _ = rows.Close() }()
 
@@ -54,19 +157,31 @@ ORDER BY w.name ASC, wv.version DESC
 			id          uuid.UUID
 			name        string
 			caseTypeID  string
+			ownerID     uuid.UUID
+			createdAt   time.Time
+			updatedAt   time.Time
+			state       string
+			required    int
+			tags        string
 			version     int
 			publishedAt sql.NullTime
 		)
-		if err := rows.Scan(&id, &name, &caseTypeID, &version, &publishedAt); err != nil {
-			return nil, fmt.Errorf("scan workflow row: %w", err)
+		if err := rows.Scan(&id, &name, &caseTypeID, &ownerID, &createdAt, &updatedAt, &state, &required, &tags, &version, &publishedAt); err != nil {
+			return ListPage{}, fmt.Errorf("scan workflow row: %w", err)
 		}
 		k := key{id: id}
 		idx, ok := indexByID[k]
 		if !ok {
 			ordered = append(ordered, Workflow{
-				ID:         id,
-				Name:       name,
-				CaseTypeID: caseTypeID,
+				ID:                id,
+				Name:              name,
+				CaseTypeID:        caseTypeID,
+				OwnerID:           ownerID,
+				CreatedAt:         createdAt.UTC(),
+				UpdatedAt:         updatedAt.UTC(),
+				Tags:              decodeTags(tags),
+				State:             state,
+				RequiredApprovals: required,
 			})
 			idx = len(ordered) - 1
 			indexByID[k] = idx
@@ -84,10 +199,15 @@ ORDER BY w.name ASC, wv.version DESC
 		}
 	}
 	if err := rows.Err(); err != nil {
-		return nil, fmt.Errorf("iterate workflow rows: %w", err)
+		return ListPage{}, fmt.Errorf("iterate workflow rows: %w", err)
 	}
 
-	return ordered, nil
+	page := ListPage{Items: ordered, Total: total}
+	if opts.Limit > 0 && len(ordered) > opts.Limit {
+		page.Items = ordered[:opts.Limit]
+		page.NextCursor = encodeListCursor(sortKey, opts.Descending, page.Items[opts.Limit-1])
+	}
+	return page, nil
 }
 
 func (s *Service) Create(ctx context.Context, tenantID, actorID uuid.UUID, req CreateRequest) (Workflow, error) {
@@ -110,11 +230,13 @@ func (s *Service) Create(ctx context.Context, tenantID, actorID uuid.UUID, req C
 	err = tx.QueryRowContext(ctx, `
 INSERT INTO workflows (tenant_id, name, case_type, created_by)
 VALUES ($1, $2, $3, $4)
-RETURNING id, name, case_type
-`, tenantID, name, caseTypeID, actorID).Scan(&out.ID, &out.Name, &out.CaseTypeID)
+RETURNING id, name, case_type, created_by, created_at, updated_at
+`, tenantID, name, caseTypeID, actorID).Scan(&out.ID, &out.Name, &out.CaseTypeID, &out.OwnerID, &out.CreatedAt, &out.UpdatedAt)
 	if err != nil {
 		return Workflow{}, fmt.Errorf("create workflow: %w", err)
 	}
+	out.Tags = []string{}
+	out.State = StateDraft
 
 	initialAST := json.RawMessage(`{"steps":[]}`)
 	if _, err := tx.ExecContext(ctx, `
@@ -127,9 +249,109 @@ VALUES ($1, 1, 'draft', $2::jsonb, '', $3)
 	if err := tx.Commit(); err != nil {
 		return Workflow{}, fmt.Errorf("commit create workflow tx: %w", err)
 	}
+	s.bus.Publish(ctx, tenantID, events.WorkflowCreated, map[string]any{
+		"workflow_id": out.ID,
+		"name":        out.Name,
+		"case_type":   out.CaseTypeID,
+		"created_by":  actorID,
+	})
 	return out, nil
 }
 
+// Get returns one workflow with its published versions, newest first. It
+// returns sql.ErrNoRows when the workflow does not exist in the tenant.
+func (s *Service) Get(ctx context.Context, tenantID, workflowID uuid.UUID) (Workflow, error) {
+	var (
+		out  Workflow
+		tags string
+	)
+	err := s.db.QueryRowContext(ctx, `
+SELECT w.id, w.name, w.case_type, w.created_by, w.created_at, w.updated_at, `+workflowTagsColumn+`,
+       `+workflowStateColumn+`, w.required_approvals
+FROM workflows w
+WHERE w.id = $1 AND w.tenant_id = $2
+`, workflowID, tenantID).Scan(&out.ID, &out.Name, &out.CaseTypeID, &out.OwnerID, &out.CreatedAt, &out.UpdatedAt, &tags, &out.State, &out.RequiredApprovals)
+	if err != nil {
+		return Workflow{}, err
+	}
+	out.Tags = decodeTags(tags)
+	out.CreatedAt = out.CreatedAt.UTC()
+	out.UpdatedAt = out.UpdatedAt.UTC()
+
+	rows, err := s.db.QueryContext(ctx, `
+SELECT version, published_at
+FROM workflow_versions
+WHERE workflow_id = $1 AND status = 'published'
+ORDER BY version DESC
+`, workflowID)
+	if err != nil {
+		return Workflow{}, fmt.Errorf("list published versions: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	for rows.Next() {
+		var (
+			version     PublishedVersion
+			publishedAt sql.NullTime
+		)
+		if err := rows.Scan(&version.Version, &publishedAt); err != nil {
+			return Workflow{}, fmt.Errorf("scan published version: %w", err)
+		}
+		if publishedAt.Valid {
+			t := publishedAt.Time.UTC()
+			version.PublishedAt = &t
+		}
+		out.PublishedVersions = append(out.PublishedVersions, version)
+	}
+	if err := rows.Err(); err != nil {
+		return Workflow{}, fmt.Errorf("iterate published versions: %w", err)
+	}
+	if out.Contract, err = s.loadContract(ctx, workflowID); err != nil {
+		return Workflow{}, err
+	}
+	return out, nil
+}
+
+// Delete removes a workflow and all its versions. Workflows that cases or
+// channels were created against are kept and ErrWorkflowInUse is returned.
+func (s *Service) Delete(ctx context.Context, tenantID, workflowID uuid.UUID) error {
+	tx, err := s.db.BeginTx(ctx, nil)
+	if err != nil {
+		return fmt.Errorf("begin delete workflow tx: %w", err)
+	}
+	defer func() { _ = tx.Rollback() }()
+
+	var inUse bool
+	err = tx.QueryRowContext(ctx, `
+SELECT EXISTS (SELECT 1 FROM cases WHERE workflow_id = w.id)
+    OR EXISTS (SELECT 1 FROM channels WHERE workflow_id = w.id)
+FROM workflows w
+WHERE w.id = $1 AND w.tenant_id = $2
+FOR UPDATE OF w
+`, workflowID, tenantID).Scan(&inUse)
+	if err != nil {
+		return err
+	}
+	if inUse {
+		return ErrWorkflowInUse
+	}
+	for _, stmt := range []string{
+		`UPDATE connector_usage SET workflow_id = NULL WHERE workflow_id = $1`,
+		`DELETE FROM ai_assistant_diffs WHERE workflow_id = $1`,
+		`DELETE FROM workflow_sla_breaches WHERE workflow_id = $1`,
+		`DELETE FROM workflow_slas WHERE workflow_id = $1`,
+		`DELETE FROM workflow_versions WHERE workflow_id = $1`,
+		`DELETE FROM workflows WHERE id = $1`,
+	} {
+		if _, err := tx.ExecContext(ctx, stmt, workflowID); err != nil {
+			return fmt.Errorf("delete workflow: %w", err)
+		}
+	}
+	if err := tx.Commit(); err != nil {
+		return fmt.Errorf("commit delete workflow tx: %w", err)
+	}
+	return nil
+}
+
 func (s *Service) GetDraftAST(ctx context.Context, tenantID, workflowID uuid.UUID) (json.RawMessage, error) {
 	var raw []byte
 	err := s.db.QueryRowContext(ctx, `
@@ -146,7 +368,7 @@ LIMIT 1
 	return json.RawMessage(raw), nil
 }
 
-func (s *Service) SaveDraftAST(ctx context.Context, tenantID, workflowID uuid.UUID, ast json.RawMessage) error {
+func (s *Service) SaveDraftAST(ctx context.Context, tenantID, actorID, workflowID uuid.UUID, ast json.RawMessage) error {
 	if len(ast) == 0 {
 		return fmt.Errorf("ast is required")
 	}
@@ -157,27 +379,20 @@ func (s *Service) SaveDraftAST(ctx context.Context, tenantID, workflowID uuid.UU
 	if err := validateWorkflowAST(ast); err != nil {
 		return err
 	}
-
-	res, err := s.db.ExecContext(ctx, `
-UPDATE workflow_versions wv
-SET ast = $3::jsonb
-FROM workflows w
-WHERE w.id = $1
-  AND w.tenant_id = $2
-  AND wv.workflow_id = w.id
-  AND wv.status = 'draft'
-`, workflowID, tenantID, string(ast))
-	if err != nil {
-		return fmt.Errorf("save workflow draft ast: %w", err)
-	}
-	affected, _ := res.RowsAffected()
-	if affected == 0 {
-		return sql.ErrNoRows
-	}
-	return nil
+	_, err := s.updateDraft(ctx, tenantID, actorID, workflowID, DraftEditAST, func([]byte) (draftUpdate, error) {
+		return draftUpdate{ast: ast}, nil
+	})
+	return err
 }
 
 func (s *Service) PublishDraft(ctx context.Context, tenantID, actorID, workflowID uuid.UUID) error {
+	return s.releaseDraft(ctx, tenantID, actorID, workflowID, nil)
+}
+
+// releaseDraft validates the draft and publishes it, or starts it as a canary
+// alongside the published version when canary is set. Either way a new draft
+// copy is created for further edits.
+func (s *Service) releaseDraft(ctx context.Context, tenantID, actorID, workflowID uuid.UUID, canary *CanaryRequest) error {
 	tx, err := s.db.BeginTx(ctx, nil)
 	if err != nil {
 		return fmt.Errorf("begin publish workflow tx: %w", err)
@@ -200,7 +415,7 @@ ORDER BY wv.version DESC
 LIMIT 1
 `, workflowID, tenantID).Scan(&draftID, &draftVersion, &astRaw, &draftYAML, &draftCreatedBy)
 	if err != nil {
-		if errors.Is(err, sql.ErrNoRows) {
+		if errors.Is(err, sql.ErrNoRows) && canary == nil {
 			var publishedExists bool
 			publishedErr := tx.QueryRowContext(ctx, `
 SELECT EXISTS(
@@ -224,29 +439,46 @@ SELECT EXISTS(
 		}
 		return err
 	}
-	if err := validatePublishWorkflow(ctx, tenantID, astRaw, s.catalog); err != nil {
+	if err := validatePublishWorkflow(ctx, tenantID, astRaw, s.catalog, s.egress, s.credentials, s); err != nil {
+		return err
+	}
+	if err := checkPublishApproved(ctx, tx, workflowID, draftVersion); err != nil {
 		return err
 	}
 
-	if _, err := tx.ExecContext(ctx, `
+	if canary != nil {
+		if err := startCanaryTx(ctx, tx, tenantID, actorID, workflowID, draftID, draftVersion, *canary); err != nil {
+			return err
+		}
+	} else {
+		// Publishing outright supersedes any running canary.
+		if _, err := tx.ExecContext(ctx, `
 UPDATE workflow_versions wv
 SET status = 'withdrawn'
 FROM workflows w
 WHERE w.id = $1
   AND w.tenant_id = $2
   AND wv.workflow_id = w.id
-  AND wv.status = 'published'
+  AND wv.status IN ('published', 'canary')
 `, workflowID, tenantID); err != nil {
-		return fmt.Errorf("withdraw previous published versions: %w", err)
-	}
+			return fmt.Errorf("withdraw previous published versions: %w", err)
+		}
+		if _, err := tx.ExecContext(ctx, `
+UPDATE workflow_canaries
+SET status = 'rolled_back', reason = $3, ended_at = now()
+WHERE workflow_id = $1 AND tenant_id = $2 AND status = 'active'
+`, workflowID, tenantID, fmt.Sprintf("superseded by publishing version %d", draftVersion)); err != nil {
+			return fmt.Errorf("end canary on publish: %w", err)
+		}
 
-	if _, err := tx.ExecContext(ctx, `
+		if _, err := tx.ExecContext(ctx, `
 UPDATE workflow_versions
 SET status = 'published',
     published_at = now()
 WHERE id = $1
 `, draftID); err != nil {
-		return fmt.Errorf("publish draft version: %w", err)
+			return fmt.Errorf("publish draft version: %w", err)
+		}
 	}
 
 	nextVersion := draftVersion + 1
@@ -327,39 +559,33 @@ func marshalYAMLFromAST(astRaw []byte) (string, error) {
 	return string(out), nil
 }
 
-func (s *Service) ImportYAMLDraft(ctx context.Context, tenantID, workflowID uuid.UUID, yamlSource string) error {
-	yamlSource = strings.TrimSpace(yamlSource)
-	if yamlSource == "" {
-		return fmt.Errorf("yaml is required")
+// ParseYAML converts a YAML workflow definition to its AST JSON.
+func ParseYAML(yamlSource string) (json.RawMessage, error) {
+	if strings.TrimSpace(yamlSource) == "" {
+		return nil, fmt.Errorf("yaml is required")
 	}
 	var decoded any
 	if err := yaml.Unmarshal([]byte(yamlSource), &decoded); err != nil {
-		return fmt.Errorf("invalid yaml: %w", err)
+		return nil, fmt.Errorf("invalid yaml: %w", err)
 	}
 	astRaw, err := json.Marshal(decoded)
 	if err != nil {
-		return fmt.Errorf("convert yaml to ast json: %w", err)
-	}
-	if err := validateWorkflowAST(astRaw); err != nil {
-		return err
+		return nil, fmt.Errorf("convert yaml to ast json: %w", err)
 	}
+	return astRaw, nil
+}
 
-	res, err := s.db.ExecContext(ctx, `
-UPDATE workflow_versions wv
-SET ast = $3::jsonb,
-    yaml_source = $4
-FROM workflows w
-WHERE w.id = $1
-  AND w.tenant_id = $2
-  AND wv.workflow_id = w.id
-  AND wv.status = 'draft'
-`, workflowID, tenantID, string(astRaw), yamlSource)
+func (s *Service) ImportYAMLDraft(ctx context.Context, tenantID, actorID, workflowID uuid.UUID, yamlSource string) error {
+	yamlSource = strings.TrimSpace(yamlSource)
+	astRaw, err := ParseYAML(yamlSource)
 	if err != nil {
-		return fmt.Errorf("import workflow yaml into draft: %w", err)
+		return err
 	}
-	affected, _ := res.RowsAffected()
-	if affected == 0 {
-		return sql.ErrNoRows
+	if err := validateWorkflowAST(astRaw); err != nil {
+		return err
 	}
-	return nil
+	_, err = s.updateDraft(ctx, tenantID, actorID, workflowID, DraftEditYAML, func([]byte) (draftUpdate, error) {
+		return draftUpdate{ast: astRaw, yaml: &yamlSource}, nil
+	})
+	return err
 }
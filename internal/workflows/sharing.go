@@ -0,0 +1,341 @@
+package workflows
+
+import (
+	"context"
+	"database/sql"
+	"errors"
+	"fmt"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/cases"
+)
+
+const (
+	GranteePrincipal = "principal"
+	GranteeRole      = "role"
+
+	AccessRead    = "read"
+	AccessWrite   = "write"
+	AccessExecute = "execute"
+)
+
+var (
+	ErrInvalidShare    = errors.New("invalid share")
+	ErrShareNotFound   = errors.New("share not found")
+	ErrGranteeNotFound = errors.New("grantee not found")
+)
+
+// Share grants a principal, or every principal holding a role, access to one
+// workflow on top of what their roles allow tenant-wide.
+type Share struct {
+	GranteeType string    `json:"grantee_type"`
+	GranteeID   uuid.UUID `json:"grantee_id"`
+	GranteeName string    `json:"grantee_name"`
+	Access      []string  `json:"access"`
+	CreatedAt   time.Time `json:"created_at"`
+}
+
+type ShareRequest struct {
+	GranteeType string    `json:"grantee_type"`
+	GranteeID   uuid.UUID `json:"grantee_id"`
+	Access      []string  `json:"access"`
+}
+
+// Permissions are what the requesting principal may do with one workflow.
+type Permissions struct {
+	CanView    bool `json:"can_view"`
+	CanEdit    bool `json:"can_edit"`
+	CanExecute bool `json:"can_execute"`
+	CanShare   bool `json:"can_share"`
+}
+
+// TenantAccess is what a principal's roles allow on every workflow in the
+// tenant, before ownership and shares are taken into account.
+type TenantAccess struct {
+	View    bool
+	Edit    bool
+	Execute bool
+	Share   bool
+}
+
+// permissionChecker resolves a principal's tenant-wide role permissions;
+// *rbac.Service implements it.
+type permissionChecker interface {
+	Authorize(ctx context.Context, principalID uuid.UUID, permission string) error
+}
+
+// SetPermissionChecker lets AuthorizeExecution count the cases:create
+// permission. Without it only ownership and execute shares allow execution.
+func (s *Service) SetPermissionChecker(checker permissionChecker) {
+	s.permissions = checker
+}
+
+type shareGrant struct {
+	read, write, execute bool
+}
+
+// permissions combines tenant access, ownership and share grants. Write and
+// execute grants imply read; only owners and tenant-wide sharers may share.
+func (base TenantAccess) permissions(owner bool, grant shareGrant) Permissions {
+	return Permissions{
+		CanView:    base.View || owner || grant.read || grant.write || grant.execute,
+		CanEdit:    base.Edit || owner || grant.write,
+		CanExecute: base.Execute || owner || grant.execute,
+		CanShare:   base.Share || owner,
+	}
+}
+
+// ResolvePermissions sets Permissions on each workflow for principalID.
+func (s *Service) ResolvePermissions(ctx context.Context, tenantID, principalID uuid.UUID, base TenantAccess, items []Workflow) error {
+	if len(items) == 0 {
+		return nil
+	}
+	grants, err := s.sharesFor(ctx, tenantID, principalID, uuid.NullUUID{})
+	if err != nil {
+		return err
+	}
+	for i := range items {
+		p := base.permissions(items[i].OwnerID == principalID, grants[items[i].ID])
+		items[i].Permissions = &p
+	}
+	return nil
+}
+
+// WorkflowPermissions returns what principalID may do with one workflow. It
+// returns sql.ErrNoRows when the workflow is not in the tenant.
+func (s *Service) WorkflowPermissions(ctx context.Context, tenantID, workflowID, principalID uuid.UUID, base TenantAccess) (Permissions, error) {
+	var ownerID uuid.UUID
+	if err := s.db.QueryRowContext(ctx, `SELECT created_by FROM workflows WHERE id = $1 AND tenant_id = $2`, workflowID, tenantID).Scan(&ownerID); err != nil {
+		return Permissions{}, err
+	}
+	grants, err := s.sharesFor(ctx, tenantID, principalID, uuid.NullUUID{UUID: workflowID, Valid: true})
+	if err != nil {
+		return Permissions{}, err
+	}
+	return base.permissions(ownerID == principalID, grants[workflowID]), nil
+}
+
+// AuthorizeExecution implements cases.ExecutionAuthorizer: principalID may
+// start cases of workflowID with cases:create tenant-wide, as its owner, or
+// through an execute share.
+func (s *Service) AuthorizeExecution(ctx context.Context, tenantID, principalID, workflowID uuid.UUID) error {
+	if s.permissions != nil && s.permissions.Authorize(ctx, principalID, "cases:create") == nil {
+		return nil
+	}
+	perms, err := s.WorkflowPermissions(ctx, tenantID, workflowID, principalID, TenantAccess{})
+	if errors.Is(err, sql.ErrNoRows) {
+		return cases.ErrExecutionForbidden
+	}
+	if err != nil {
+		return err
+	}
+	if !perms.CanExecute {
+		return cases.ErrExecutionForbidden
+	}
+	return nil
+}
+
+// sharesFor merges the grants held by a principal directly and through its
+// roles, per workflow. A valid workflowID limits the lookup to that workflow.
+func (s *Service) sharesFor(ctx context.Context, tenantID, principalID uuid.UUID, workflowID uuid.NullUUID) (map[uuid.UUID]shareGrant, error) {
+	rows, err := s.db.QueryContext(ctx, `
+SELECT ws.workflow_id, bool_or(ws.can_read), bool_or(ws.can_write), bool_or(ws.can_execute)
+FROM workflow_shares ws
+JOIN workflows w ON w.id = ws.workflow_id
+WHERE w.tenant_id = $1
+  AND ($3::uuid IS NULL OR ws.workflow_id = $3)
+  AND (ws.principal_id = $2
+       OR ws.role_id IN (SELECT role_id FROM principal_roles WHERE principal_id = $2))
+GROUP BY ws.workflow_id
+`, tenantID, principalID, workflowID)
+	if err != nil {
+		return nil, fmt.Errorf("load workflow shares: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	out := map[uuid.UUID]shareGrant{}
+	for rows.Next() {
+		var (
+			id    uuid.UUID
+			grant shareGrant
+		)
+		if err := rows.Scan(&id, &grant.read, &grant.write, &grant.execute); err != nil {
+			return nil, fmt.Errorf("scan workflow share: %w", err)
+		}
+		out[id] = grant
+	}
+	return out, rows.Err()
+}
+
+// ListShares returns a workflow's shares, principals first. It returns
+// sql.ErrNoRows when the workflow is not in the tenant.
+func (s *Service) ListShares(ctx context.Context, tenantID, workflowID uuid.UUID) ([]Share, error) {
+	var id uuid.UUID
+	if err := s.db.QueryRowContext(ctx, `SELECT id FROM workflows WHERE id = $1 AND tenant_id = $2`, workflowID, tenantID).Scan(&id); err != nil {
+		return nil, err
+	}
+	rows, err := s.db.QueryContext(ctx, `
+SELECT CASE WHEN ws.principal_id IS NOT NULL THEN 'principal' ELSE 'role' END,
+       COALESCE(ws.principal_id, ws.role_id),
+       COALESCE(p.name, r.name, ''),
+       ws.can_read, ws.can_write, ws.can_execute, ws.created_at
+FROM workflow_shares ws
+LEFT JOIN principals p ON p.id = ws.principal_id
+LEFT JOIN roles r ON r.id = ws.role_id
+WHERE ws.workflow_id = $1
+ORDER BY 1, 3
+`, workflowID)
+	if err != nil {
+		return nil, fmt.Errorf("list workflow shares: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	out := make([]Share, 0)
+	for rows.Next() {
+		var (
+			share Share
+			grant shareGrant
+		)
+		if err := rows.Scan(&share.GranteeType, &share.GranteeID, &share.GranteeName, &grant.read, &grant.write, &grant.execute, &share.CreatedAt); err != nil {
+			return nil, fmt.Errorf("scan workflow share: %w", err)
+		}
+		share.Access = grant.access()
+		share.CreatedAt = share.CreatedAt.UTC()
+		out = append(out, share)
+	}
+	return out, rows.Err()
+}
+
+// ShareWorkflow grants or replaces a grantee's access to a workflow. It
+// returns sql.ErrNoRows when the workflow is not in the tenant.
+func (s *Service) ShareWorkflow(ctx context.Context, tenantID, workflowID, actorID uuid.UUID, req ShareRequest) (Share, error) {
+	grant, err := parseAccess(req.Access)
+	if err != nil {
+		return Share{}, err
+	}
+
+	tx, err := s.db.BeginTx(ctx, nil)
+	if err != nil {
+		return Share{}, fmt.Errorf("begin share workflow tx: %w", err)
+	}
+	defer func() { _ = tx.Rollback() }()
+
+	var id uuid.UUID
+	if err := tx.QueryRowContext(ctx, `SELECT id FROM workflows WHERE id = $1 AND tenant_id = $2 FOR UPDATE`, workflowID, tenantID).Scan(&id); err != nil {
+		return Share{}, err
+	}
+	share := Share{GranteeType: req.GranteeType, GranteeID: req.GranteeID, Access: grant.access()}
+	var upsert string
+	switch req.GranteeType {
+	case GranteePrincipal:
+		err = tx.QueryRowContext(ctx, `SELECT name FROM principals WHERE id = $1 AND tenant_id = $2 AND status = 'active'`, req.GranteeID, tenantID).Scan(&share.GranteeName)
+		upsert = `
+INSERT INTO workflow_shares (workflow_id, principal_id, can_read, can_write, can_execute, created_by)
+VALUES ($1, $2, $3, $4, $5, $6)
+ON CONFLICT (principal_id, workflow_id) WHERE principal_id IS NOT NULL
+DO UPDATE SET can_read = EXCLUDED.can_read, can_write = EXCLUDED.can_write, can_execute = EXCLUDED.can_execute
+RETURNING created_at`
+	case GranteeRole:
+		err = tx.QueryRowContext(ctx, `SELECT name FROM roles WHERE id = $1 AND tenant_id = $2`, req.GranteeID, tenantID).Scan(&share.GranteeName)
+		upsert = `
+INSERT INTO workflow_shares (workflow_id, role_id, can_read, can_write, can_execute, created_by)
+VALUES ($1, $2, $3, $4, $5, $6)
+ON CONFLICT (role_id, workflow_id) WHERE role_id IS NOT NULL
+DO UPDATE SET can_read = EXCLUDED.can_read, can_write = EXCLUDED.can_write, can_execute = EXCLUDED.can_execute
+RETURNING created_at`
+	default:
+		return Share{}, fmt.Errorf("%w: grantee_type must be principal or role", ErrInvalidShare)
+	}
+	if errors.Is(err, sql.ErrNoRows) {
+		return Share{}, ErrGranteeNotFound
+	}
+	if err != nil {
+		return Share{}, fmt.Errorf("load grantee: %w", err)
+	}
+	if err := tx.QueryRowContext(ctx, upsert, workflowID, req.GranteeID, grant.read, grant.write, grant.execute, actorID).Scan(&share.CreatedAt); err != nil {
+		return Share{}, fmt.Errorf("share workflow: %w", err)
+	}
+	if err := tx.Commit(); err != nil {
+		return Share{}, fmt.Errorf("commit share workflow tx: %w", err)
+	}
+	share.CreatedAt = share.CreatedAt.UTC()
+	return share, nil
+}
+
+// UnshareWorkflow removes a grantee's share. Access through tenant roles is
+// unaffected.
+func (s *Service) UnshareWorkflow(ctx context.Context, tenantID, workflowID uuid.UUID, granteeType string, granteeID uuid.UUID) error {
+	column := map[string]string{GranteePrincipal: "principal_id", GranteeRole: "role_id"}[granteeType]
+	if column == "" {
+		return fmt.Errorf("%w: grantee_type must be principal or role", ErrInvalidShare)
+	}
+	res, err := s.db.ExecContext(ctx, `
+DELETE FROM workflow_shares ws
+USING workflows w
+WHERE w.id = ws.workflow_id
+  AND w.id = $1 AND w.tenant_id = $2
+  AND ws.`+column+` = $3
+`, workflowID, tenantID, granteeID)
+	if err != nil {
+		return fmt.Errorf("unshare workflow: %w", err)
+	}
+	if affected, _ := res.RowsAffected(); affected == 0 {
+		return ErrShareNotFound
+	}
+	return nil
+}
+
+// TransferOwnership makes another active principal in the tenant the
+// workflow's owner. It returns sql.ErrNoRows when the workflow is not in the
+// tenant.
+func (s *Service) TransferOwnership(ctx context.Context, tenantID, workflowID, ownerID uuid.UUID) (Workflow, error) {
+	var active bool
+	err := s.db.QueryRowContext(ctx, `SELECT status = 'active' FROM principals WHERE id = $1 AND tenant_id = $2`, ownerID, tenantID).Scan(&active)
+	if errors.Is(err, sql.ErrNoRows) || (err == nil && !active) {
+		return Workflow{}, ErrGranteeNotFound
+	}
+	if err != nil {
+		return Workflow{}, fmt.Errorf("load new owner: %w", err)
+	}
+	res, err := s.db.ExecContext(ctx, `UPDATE workflows SET created_by = $3 WHERE id = $1 AND tenant_id = $2`, workflowID, tenantID, ownerID)
+	if err != nil {
+		return Workflow{}, fmt.Errorf("transfer workflow ownership: %w", err)
+	}
+	if affected, _ := res.RowsAffected(); affected == 0 {
+		return Workflow{}, sql.ErrNoRows
+	}
+	return s.Get(ctx, tenantID, workflowID)
+}
+
+func parseAccess(access []string) (shareGrant, error) {
+	var grant shareGrant
+	for _, a := range access {
+		switch a {
+		case AccessRead:
+			grant.read = true
+		case AccessWrite:
+			grant.write = true
+		case AccessExecute:
+			grant.execute = true
+		default:
+			return shareGrant{}, fmt.Errorf("%w: unknown access %q", ErrInvalidShare, a)
+		}
+	}
+	if grant == (shareGrant{}) {
+		return shareGrant{}, fmt.Errorf("%w: access must include read, write or execute", ErrInvalidShare)
+	}
+	return grant, nil
+}
+
+func (g shareGrant) access() []string {
+	out := make([]string, 0, 3)
+	if g.read {
+		out = append(out, AccessRead)
+	}
+	if g.write {
+		out = append(out, AccessWrite)
+	}
+	if g.execute {
+		out = append(out, AccessExecute)
+	}
+	return out
+}
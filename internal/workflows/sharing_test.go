@@ -0,0 +1,45 @@
+package workflows
+
+import (
+	"errors"
+	"slices"
+	"testing"
+)
+
+func TestTenantAccessPermissions(t *testing.T) {
+	tests := []struct {
+		name  string
+		base  TenantAccess
+		owner bool
+		grant shareGrant
+		want  Permissions
+	}{
+		{name: "none", want: Permissions{}},
+		{name: "owner", owner: true, want: Permissions{CanView: true, CanEdit: true, CanExecute: true, CanShare: true}},
+		{name: "tenant viewer", base: TenantAccess{View: true}, want: Permissions{CanView: true}},
+		{name: "write share implies view", grant: shareGrant{write: true}, want: Permissions{CanView: true, CanEdit: true}},
+		{name: "execute share", base: TenantAccess{Share: true}, grant: shareGrant{execute: true}, want: Permissions{CanView: true, CanExecute: true, CanShare: true}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := tt.base.permissions(tt.owner, tt.grant); got != tt.want {
+				t.Fatalf("got %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestParseAccess(t *testing.T) {
+	grant, err := parseAccess([]string{"execute", "read", "read"})
+	if err != nil {
+		t.Fatalf("parseAccess: %v", err)
+	}
+	if got := grant.access(); !slices.Equal(got, []string{"read", "execute"}) {
+		t.Fatalf("unexpected access %v", got)
+	}
+	for _, access := range [][]string{nil, {"admin"}} {
+		if _, err := parseAccess(access); !errors.Is(err, ErrInvalidShare) {
+			t.Fatalf("parseAccess(%v): expected ErrInvalidShare, got %v", access, err)
+		}
+	}
+}
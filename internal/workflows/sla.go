@@ -0,0 +1,435 @@
+package workflows
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"log/slog"
+	"strings"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/events"
+)
+
+// DefaultSLACheckInterval is how often StartSLAMonitor evaluates SLAs.
+const DefaultSLACheckInterval = time.Minute
+
+// SLA breach kinds.
+const (
+	SLABreachDuration = "duration"
+	SLABreachSchedule = "schedule"
+)
+
+var (
+	// ErrInvalidSLA is returned for negative limits, an SLA with nothing to
+	// check, or an alert that does not name a connector action.
+	ErrInvalidSLA = errors.New("invalid sla")
+	// ErrNoSLA is returned when the workflow declares no SLA.
+	ErrNoSLA = errors.New("no sla")
+)
+
+// SLA is what a workflow's executions are held to. A case breaches
+// MaxDurationSeconds when it runs longer than that from creation. A schedule
+// channel starting the workflow breaches ScheduleToleranceSeconds when a fire
+// time is that late, or later, in starting. Each alert is an integration step
+// config run for every breach, with the breach as {{breach.*}} and the
+// workflow as {{workflow.*}} in its templates.
+type SLA struct {
+	WorkflowID               uuid.UUID         `json:"workflow_id"`
+	MaxDurationSeconds       int               `json:"max_duration_seconds,omitempty"`
+	ScheduleToleranceSeconds int               `json:"schedule_tolerance_seconds,omitempty"`
+	Alerts                   []json.RawMessage `json:"alerts"`
+	CreatedAt                time.Time         `json:"created_at"`
+	UpdatedAt                time.Time         `json:"updated_at"`
+}
+
+// SLABreach is one recorded breach. CaseID is set for duration breaches and
+// ChannelID and ScheduledAt for schedule breaches. AlertErrors lists the
+// alerts that failed, keyed by their position in the SLA's alerts.
+type SLABreach struct {
+	ID            uuid.UUID      `json:"id"`
+	WorkflowID    uuid.UUID      `json:"workflow_id"`
+	Kind          string         `json:"kind"`
+	CaseID        *uuid.UUID     `json:"case_id,omitempty"`
+	CaseNumber    string         `json:"case_number,omitempty"`
+	ChannelID     *uuid.UUID     `json:"channel_id,omitempty"`
+	ChannelName   string         `json:"channel_name,omitempty"`
+	ScheduledAt   *time.Time     `json:"scheduled_at,omitempty"`
+	LimitSeconds  int            `json:"limit_seconds"`
+	ActualSeconds int            `json:"actual_seconds"`
+	Message       string         `json:"message"`
+	URL           string         `json:"url,omitempty"`
+	AlertErrors   map[int]string `json:"alert_errors,omitempty"`
+	DetectedAt    time.Time      `json:"detected_at"`
+}
+
+// SetAppURL sets the base URL SLA alerts link cases from.
+func (s *Service) SetAppURL(url string) {
+	s.appURL = strings.TrimRight(strings.TrimSpace(url), "/")
+}
+
+func (sla *SLA) validate() error {
+	switch {
+	case sla.MaxDurationSeconds < 0 || sla.ScheduleToleranceSeconds < 0:
+		return fmt.Errorf("%w: limits cannot be negative", ErrInvalidSLA)
+	case sla.MaxDurationSeconds == 0 && sla.ScheduleToleranceSeconds == 0:
+		return fmt.Errorf("%w: set max_duration_seconds or schedule_tolerance_seconds", ErrInvalidSLA)
+	}
+	for i, raw := range sla.Alerts {
+		var cfg connectors.StepConfig
+		if err := json.Unmarshal(raw, &cfg); err != nil || cfg.Connector == "" || cfg.Action == "" {
+			return fmt.Errorf("%w: alert %d needs a connector and action", ErrInvalidSLA, i)
+		}
+	}
+	if sla.Alerts == nil {
+		sla.Alerts = []json.RawMessage{}
+	}
+	return nil
+}
+
+// GetSLA returns the workflow's SLA.
+func (s *Service) GetSLA(ctx context.Context, tenantID, workflowID uuid.UUID) (SLA, error) {
+	var (
+		sla       SLA
+		alertsRaw []byte
+	)
+	err := s.db.QueryRowContext(ctx, `
+SELECT workflow_id, max_duration_seconds, schedule_tolerance_seconds, alerts, created_at, updated_at
+FROM workflow_slas
+WHERE workflow_id = $1 AND tenant_id = $2
+`, workflowID, tenantID).Scan(&sla.WorkflowID, &sla.MaxDurationSeconds, &sla.ScheduleToleranceSeconds, &alertsRaw, &sla.CreatedAt, &sla.UpdatedAt)
+	if errors.Is(err, sql.ErrNoRows) {
+		return SLA{}, ErrNoSLA
+	}
+	if err != nil {
+		return SLA{}, fmt.Errorf("load workflow sla: %w", err)
+	}
+	_ = json.Unmarshal(alertsRaw, &sla.Alerts)
+	return sla, nil
+}
+
+// PutSLA declares or replaces the workflow's SLA. Executions that started
+// before the SLA was first declared are not held to it.
+func (s *Service) PutSLA(ctx context.Context, tenantID, workflowID uuid.UUID, sla SLA) (SLA, error) {
+	if err := sla.validate(); err != nil {
+		return SLA{}, err
+	}
+	alertsRaw, _ := json.Marshal(sla.Alerts)
+	res, err := s.db.ExecContext(ctx, `
+INSERT INTO workflow_slas (workflow_id, tenant_id, max_duration_seconds, schedule_tolerance_seconds, alerts)
+SELECT w.id, w.tenant_id, $3, $4, $5::jsonb
+FROM workflows w
+WHERE w.id = $1 AND w.tenant_id = $2
+ON CONFLICT (workflow_id) DO UPDATE
+SET max_duration_seconds = EXCLUDED.max_duration_seconds,
+    schedule_tolerance_seconds = EXCLUDED.schedule_tolerance_seconds,
+    alerts = EXCLUDED.alerts,
+    updated_at = now()
+`, workflowID, tenantID, sla.MaxDurationSeconds, sla.ScheduleToleranceSeconds, string(alertsRaw))
+	if err != nil {
+		return SLA{}, fmt.Errorf("save workflow sla: %w", err)
+	}
+	if n, _ := res.RowsAffected(); n == 0 {
+		return SLA{}, sql.ErrNoRows
+	}
+	return s.GetSLA(ctx, tenantID, workflowID)
+}
+
+// DeleteSLA removes the workflow's SLA. Recorded breaches are kept.
+func (s *Service) DeleteSLA(ctx context.Context, tenantID, workflowID uuid.UUID) error {
+	res, err := s.db.ExecContext(ctx, `DELETE FROM workflow_slas WHERE workflow_id = $1 AND tenant_id = $2`, workflowID, tenantID)
+	if err != nil {
+		return fmt.Errorf("delete workflow sla: %w", err)
+	}
+	if n, _ := res.RowsAffected(); n == 0 {
+		return ErrNoSLA
+	}
+	return nil
+}
+
+// ListSLABreaches returns the workflow's most recent breaches, newest first.
+func (s *Service) ListSLABreaches(ctx context.Context, tenantID, workflowID uuid.UUID, limit int) ([]SLABreach, error) {
+	if limit <= 0 || limit > 200 {
+		limit = 50
+	}
+	rows, err := s.db.QueryContext(ctx, `
+SELECT id, workflow_id, kind, case_id, COALESCE(case_number, ''), channel_id, COALESCE(channel_name, ''), scheduled_at,
+       limit_seconds, actual_seconds, message, url, alert_errors, detected_at
+FROM workflow_sla_breaches
+WHERE workflow_id = $1 AND tenant_id = $2
+ORDER BY detected_at DESC
+LIMIT $3
+`, workflowID, tenantID, limit)
+	if err != nil {
+		return nil, fmt.Errorf("list workflow sla breaches: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	out := make([]SLABreach, 0)
+	for rows.Next() {
+		var (
+			b           SLABreach
+			caseID      uuid.NullUUID
+			channelID   uuid.NullUUID
+			scheduledAt sql.NullTime
+			errorsRaw   []byte
+		)
+		if err := rows.Scan(&b.ID, &b.WorkflowID, &b.Kind, &caseID, &b.CaseNumber, &channelID, &b.ChannelName, &scheduledAt,
+			&b.LimitSeconds, &b.ActualSeconds, &b.Message, &b.URL, &errorsRaw, &b.DetectedAt); err != nil {
+			return nil, fmt.Errorf("scan workflow sla breach: %w", err)
+		}
+		if caseID.Valid {
+			b.CaseID = &caseID.UUID
+		}
+		if channelID.Valid {
+			b.ChannelID = &channelID.UUID
+		}
+		if scheduledAt.Valid {
+			b.ScheduledAt = &scheduledAt.Time
+		}
+		_ = json.Unmarshal(errorsRaw, &b.AlertErrors)
+		out = append(out, b)
+	}
+	return out, rows.Err()
+}
+
+// StartSLAMonitor evaluates SLAs every DefaultSLACheckInterval until ctx is
+// done. It runs on the leader replica only.
+func (s *Service) StartSLAMonitor(ctx context.Context) {
+	ticker := time.NewTicker(DefaultSLACheckInterval)
+	defer ticker.Stop()
+	for {
+		select {
+		case <-ticker.C:
+			if err := s.CheckSLAs(ctx); err != nil {
+				slog.WarnContext(ctx, "check workflow slas failed", "error", err)
+			}
+		case <-ctx.Done():
+			return
+		}
+	}
+}
+
+// slaCandidate is an execution found over its SLA, before it is recorded.
+type slaCandidate struct {
+	tenantID     uuid.UUID
+	workflowName string
+	breach       SLABreach
+	alerts       []json.RawMessage
+}
+
+// CheckSLAs records a breach for each execution newly over its workflow's
+// SLA and fires the workflow's alerts for it. A breach is recorded once per
+// case, or per schedule channel and fire time.
+func (s *Service) CheckSLAs(ctx context.Context) error {
+	candidates, err := s.durationBreaches(ctx)
+	if err != nil {
+		return err
+	}
+	scheduled, err := s.scheduleBreaches(ctx)
+	if err != nil {
+		return err
+	}
+	for _, c := range append(candidates, scheduled...) {
+		recorded, err := s.recordSLABreach(ctx, &c)
+		if err != nil {
+			return err
+		}
+		if recorded {
+			s.alertSLABreach(ctx, c)
+		}
+	}
+	return nil
+}
+
+// durationBreaches finds cases running, or that ran, longer than their
+// workflow's max duration. Cancelled cases are not held to it, and finished
+// cases only for a day after they finish.
+func (s *Service) durationBreaches(ctx context.Context) ([]slaCandidate, error) {
+	rows, err := s.db.QueryContext(ctx, `
+SELECT sla.tenant_id, sla.workflow_id, w.name, sla.max_duration_seconds, sla.alerts,
+       c.id, c.case_number, EXTRACT(EPOCH FROM (CASE WHEN c.status = 'completed' THEN c.updated_at ELSE now() END) - c.created_at)::bigint
+FROM workflow_slas sla
+JOIN workflows w ON w.id = sla.workflow_id
+JOIN cases c ON c.workflow_id = sla.workflow_id
+WHERE sla.max_duration_seconds > 0
+  AND c.created_at >= sla.created_at
+  AND (c.status IN ('open', 'in_progress') OR (c.status = 'completed' AND c.updated_at > now() - interval '1 day'))
+  AND (CASE WHEN c.status = 'completed' THEN c.updated_at ELSE now() END) > c.created_at + make_interval(secs => sla.max_duration_seconds)
+  AND NOT EXISTS (
+    SELECT 1 FROM workflow_sla_breaches b WHERE b.case_id = c.id AND b.kind = 'duration'
+  )
+ORDER BY c.created_at
+LIMIT 500
+`)
+	if err != nil {
+		return nil, fmt.Errorf("find workflow duration breaches: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	out := make([]slaCandidate, 0)
+	for rows.Next() {
+		var (
+			c         slaCandidate
+			alertsRaw []byte
+			caseID    uuid.UUID
+		)
+		c.breach.Kind = SLABreachDuration
+		if err := rows.Scan(&c.tenantID, &c.breach.WorkflowID, &c.workflowName, &c.breach.LimitSeconds, &alertsRaw,
+			&caseID, &c.breach.CaseNumber, &c.breach.ActualSeconds); err != nil {
+			return nil, fmt.Errorf("scan workflow duration breach: %w", err)
+		}
+		_ = json.Unmarshal(alertsRaw, &c.alerts)
+		c.breach.CaseID = &caseID
+		c.breach.Message = fmt.Sprintf("Case %s of %s has run for %s, over its SLA of %s",
+			c.breach.CaseNumber, c.workflowName, formatSLASeconds(c.breach.ActualSeconds), formatSLASeconds(c.breach.LimitSeconds))
+		if s.appURL != "" {
+			c.breach.URL = s.appURL + "/cases/" + caseID.String()
+		}
+		out = append(out, c)
+	}
+	return out, rows.Err()
+}
+
+// scheduleBreaches finds schedule channels starting an SLA's workflow whose
+// next fire time is overdue by more than the tolerance, or whose last run
+// started that late, for example after downtime.
+func (s *Service) scheduleBreaches(ctx context.Context) ([]slaCandidate, error) {
+	rows, err := s.db.QueryContext(ctx, `
+WITH bound AS (
+    SELECT sla.tenant_id, sla.workflow_id, w.name AS workflow_name, sla.schedule_tolerance_seconds AS tolerance, sla.alerts,
+           ch.id AS channel_id, ch.name AS channel_name, cs.next_run_at, cs.last_run_at, cs.last_status, cs.updated_at
+    FROM workflow_slas sla
+    JOIN workflows w ON w.id = sla.workflow_id
+    JOIN channels ch ON ch.tenant_id = sla.tenant_id AND ch.type = 'schedule' AND ch.enabled AND ch.deleted_at IS NULL
+    JOIN case_types ct ON ct.id = ch.case_type_id
+    JOIN channel_schedules cs ON cs.channel_id = ch.id
+    WHERE sla.schedule_tolerance_seconds > 0
+      AND (ch.workflow_id = w.id OR (ch.workflow_id IS NULL AND (ct.id::text = w.case_type OR ct.name = w.case_type)))
+), late AS (
+    SELECT bound.*, next_run_at AS scheduled_at, EXTRACT(EPOCH FROM now() - next_run_at)::bigint AS actual
+    FROM bound
+    WHERE next_run_at < now() - make_interval(secs => tolerance)
+    UNION ALL
+    SELECT bound.*, last_run_at, EXTRACT(EPOCH FROM updated_at - last_run_at)::bigint
+    FROM bound
+    WHERE last_status IN ('succeeded', 'failed')
+      AND updated_at - last_run_at > make_interval(secs => tolerance)
+      AND updated_at > now() - interval '1 day'
+)
+SELECT tenant_id, workflow_id, workflow_name, tolerance, alerts, channel_id, channel_name, scheduled_at, actual
+FROM late
+WHERE NOT EXISTS (
+    SELECT 1 FROM workflow_sla_breaches b
+    WHERE b.channel_id = late.channel_id AND b.scheduled_at = late.scheduled_at AND b.workflow_id = late.workflow_id
+)
+LIMIT 500
+`)
+	if err != nil {
+		return nil, fmt.Errorf("find workflow schedule breaches: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	out := make([]slaCandidate, 0)
+	for rows.Next() {
+		var (
+			c           slaCandidate
+			alertsRaw   []byte
+			channelID   uuid.UUID
+			scheduledAt time.Time
+		)
+		c.breach.Kind = SLABreachSchedule
+		if err := rows.Scan(&c.tenantID, &c.breach.WorkflowID, &c.workflowName, &c.breach.LimitSeconds, &alertsRaw,
+			&channelID, &c.breach.ChannelName, &scheduledAt, &c.breach.ActualSeconds); err != nil {
+			return nil, fmt.Errorf("scan workflow schedule breach: %w", err)
+		}
+		_ = json.Unmarshal(alertsRaw, &c.alerts)
+		c.breach.ChannelID = &channelID
+		c.breach.ScheduledAt = &scheduledAt
+		c.breach.Message = fmt.Sprintf("Schedule %s of %s due at %s started %s late, over its tolerance of %s",
+			c.breach.ChannelName, c.workflowName, scheduledAt.UTC().Format(time.RFC3339), formatSLASeconds(c.breach.ActualSeconds), formatSLASeconds(c.breach.LimitSeconds))
+		out = append(out, c)
+	}
+	return out, rows.Err()
+}
+
+// recordSLABreach stores the breach and reports whether it is new.
+func (s *Service) recordSLABreach(ctx context.Context, c *slaCandidate) (bool, error) {
+	err := s.db.QueryRowContext(ctx, `
+INSERT INTO workflow_sla_breaches (tenant_id, workflow_id, kind, case_id, case_number, channel_id, channel_name, scheduled_at,
+                                   limit_seconds, actual_seconds, message, url)
+VALUES ($1, $2, $3, $4, NULLIF($5, ''), $6, NULLIF($7, ''), $8, $9, $10, $11, $12)
+ON CONFLICT DO NOTHING
+RETURNING id, detected_at
+`, c.tenantID, c.breach.WorkflowID, c.breach.Kind, c.breach.CaseID, c.breach.CaseNumber, c.breach.ChannelID, c.breach.ChannelName,
+		c.breach.ScheduledAt, c.breach.LimitSeconds, c.breach.ActualSeconds, c.breach.Message, c.breach.URL).Scan(&c.breach.ID, &c.breach.DetectedAt)
+	if errors.Is(err, sql.ErrNoRows) {
+		return false, nil
+	}
+	if err != nil {
+		return false, fmt.Errorf("record workflow sla breach: %w", err)
+	}
+	return true, nil
+}
+
+// alertSLABreach publishes workflow.sla_breached and runs the SLA's alerts.
+// Alert failures are stored on the breach and logged, never retried.
+func (s *Service) alertSLABreach(ctx context.Context, c slaCandidate) {
+	slog.WarnContext(ctx, "workflow sla breached", "workflow_id", c.breach.WorkflowID.String(), "kind", c.breach.Kind, "message", c.breach.Message)
+	s.bus.Publish(ctx, c.tenantID, events.WorkflowSLABreached, c.breach)
+	if s.tester == nil || len(c.alerts) == 0 {
+		return
+	}
+	vars := slaAlertVars(c)
+	failures := map[int]string{}
+	for i, raw := range c.alerts {
+		result, err := s.tester.TestStep(ctx, c.tenantID, fmt.Sprintf("sla-alert-%d", i), raw, vars)
+		if err == nil && result.Error != "" {
+			err = errors.New(result.Error)
+		}
+		if err != nil {
+			failures[i] = err.Error()
+			slog.WarnContext(ctx, "workflow sla alert failed", "workflow_id", c.breach.WorkflowID.String(), "alert", i, "error", err)
+		}
+	}
+	if len(failures) == 0 {
+		return
+	}
+	raw, _ := json.Marshal(failures)
+	if _, err := s.db.ExecContext(ctx, `UPDATE workflow_sla_breaches SET alert_errors = $2::jsonb WHERE id = $1`, c.breach.ID, string(raw)); err != nil {
+		slog.WarnContext(ctx, "record workflow sla alert failures failed", "breach_id", c.breach.ID.String(), "error", err)
+	}
+}
+
+// slaAlertVars is the template context alert steps are resolved against.
+func slaAlertVars(c slaCandidate) map[string]any {
+	breach := map[string]any{
+		"id":             c.breach.ID.String(),
+		"kind":           c.breach.Kind,
+		"message":        c.breach.Message,
+		"url":            c.breach.URL,
+		"limit_seconds":  c.breach.LimitSeconds,
+		"actual_seconds": c.breach.ActualSeconds,
+		"limit":          formatSLASeconds(c.breach.LimitSeconds),
+		"actual":         formatSLASeconds(c.breach.ActualSeconds),
+		"detected_at":    c.breach.DetectedAt.UTC().Format(time.RFC3339),
+	}
+	if c.breach.CaseID != nil {
+		breach["case_id"] = c.breach.CaseID.String()
+		breach["case_number"] = c.breach.CaseNumber
+	}
+	if c.breach.ChannelID != nil {
+		breach["channel_id"] = c.breach.ChannelID.String()
+		breach["channel_name"] = c.breach.ChannelName
+		breach["scheduled_at"] = c.breach.ScheduledAt.UTC().Format(time.RFC3339)
+	}
+	return map[string]any{
+		"breach":   breach,
+		"workflow": map[string]any{"id": c.breach.WorkflowID.String(), "name": c.workflowName},
+	}
+}
+
+func formatSLASeconds(seconds int) string {
+	return (time.Duration(seconds) * time.Second).String()
+}
@@ -0,0 +1,72 @@
+package workflows
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"testing"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/connectors"
+)
+
+type recordingStepTester struct {
+	steps []string
+	vars  []map[string]any
+}
+
+func (r *recordingStepTester) TestStep(_ context.Context, _ uuid.UUID, stepID string, _ json.RawMessage, caseCtx map[string]any) (connectors.TestResult, error) {
+	r.steps = append(r.steps, stepID)
+	r.vars = append(r.vars, caseCtx)
+	return connectors.TestResult{}, nil
+}
+
+func TestSLAValidate(t *testing.T) {
+	sla := SLA{MaxDurationSeconds: 3600}
+	if err := sla.validate(); err != nil {
+		t.Fatalf("validate: %v", err)
+	}
+	if sla.Alerts == nil {
+		t.Fatal("expected alerts to default to an empty list")
+	}
+	for _, bad := range []SLA{
+		{},
+		{MaxDurationSeconds: -1},
+		{ScheduleToleranceSeconds: 60, Alerts: []json.RawMessage{json.RawMessage(`{"connector":"slack_message"}`)}},
+	} {
+		if err := bad.validate(); !errors.Is(err, ErrInvalidSLA) {
+			t.Fatalf("expected ErrInvalidSLA for %#v, got %v", bad, err)
+		}
+	}
+}
+
+func TestAlertSLABreachRunsEachAlert(t *testing.T) {
+	tester := &recordingStepTester{}
+	s := &Service{tester: tester}
+	caseID := uuid.New()
+	c := slaCandidate{
+		tenantID:     uuid.New(),
+		workflowName: "Loan review",
+		breach: SLABreach{
+			ID: uuid.New(), WorkflowID: uuid.New(), Kind: SLABreachDuration,
+			CaseID: &caseID, CaseNumber: "LR-000042", LimitSeconds: 3600, ActualSeconds: 5400,
+			Message: "Case LR-000042 of Loan review has run for 1h30m0s, over its SLA of 1h0m0s",
+		},
+		alerts: []json.RawMessage{
+			json.RawMessage(`{"connector":"slack_message","action":"send","input":{"text":"{{breach.message}}"}}`),
+			json.RawMessage(`{"connector":"email","action":"send","input":{"subject":"SLA breach"}}`),
+		},
+	}
+	s.alertSLABreach(context.Background(), c)
+
+	if len(tester.steps) != 2 || tester.steps[1] != "sla-alert-1" {
+		t.Fatalf("expected both alerts to run, got %v", tester.steps)
+	}
+	breach, _ := tester.vars[0]["breach"].(map[string]any)
+	if breach["case_number"] != "LR-000042" || breach["actual"] != "1h30m0s" || breach["limit"] != "1h0m0s" {
+		t.Fatalf("unexpected breach template context %#v", breach)
+	}
+	if workflow, _ := tester.vars[0]["workflow"].(map[string]any); workflow["name"] != "Loan review" {
+		t.Fatalf("unexpected workflow template context %#v", workflow)
+	}
+}
@@ -0,0 +1,111 @@
+package workflows
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"strings"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/connectors"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+var (
+	// ErrStepNotFound is returned when the requested step is not in the workflow version.
+	ErrStepNotFound = errors.New("step not found")
+	// ErrStepNotTestable is returned for step types that cannot run outside a case.
+	ErrStepNotTestable = errors.New("step type cannot be tested in isolation")
+)
+
+type stepTester interface {
+	TestStep(ctx context.Context, tenantID uuid.UUID, stepID string, raw json.RawMessage, caseCtx map[string]any) (connectors.TestResult, error)
+}
+
+// StepTestRequest supplies the context a step would normally read from its
+// case: case data and the results of upstream steps, keyed by step ID.
+type StepTestRequest struct {
+	Version     int            `json:"version,omitempty"`
+	CaseData    map[string]any `json:"case_data"`
+	StepResults map[string]any `json:"step_results"`
+}
+
+type StepTestResult struct {
+	WorkflowID uuid.UUID `json:"workflow_id"`
+	Version    int       `json:"version"`
+	StepID     string    `json:"step_id"`
+	Type       string    `json:"type"`
+	connectors.TestResult
+}
+
+func (s *Service) SetStepTester(tester stepTester) {
+	s.tester = tester
+}
+
+// TestStep runs one step of a workflow version in isolation. Only integration
+// steps are supported; the connector action is really invoked.
+func (s *Service) TestStep(ctx context.Context, tenantID, workflowID uuid.UUID, stepID string, req StepTestRequest) (StepTestResult, error) {
+	version, astRaw, err := s.loadVersionAST(ctx, tenantID, workflowID, req.Version)
+	if err != nil {
+		return StepTestResult{}, err
+	}
+	var ast engine.WorkflowAST
+	if err := json.Unmarshal(astRaw, &ast); err != nil {
+		return StepTestResult{}, fmt.Errorf("decode workflow ast: %w", err)
+	}
+	var step *engine.WorkflowStep
+	for i := range ast.Steps {
+		if ast.Steps[i].ID == stepID {
+			step = &ast.Steps[i]
+			break
+		}
+	}
+	if step == nil {
+		return StepTestResult{}, ErrStepNotFound
+	}
+	if strings.TrimSpace(step.Type) != "integration" || s.tester == nil {
+		return StepTestResult{}, fmt.Errorf("%w: %s", ErrStepNotTestable, step.Type)
+	}
+
+	out, err := s.tester.TestStep(ctx, tenantID, step.ID, step.Config, stepTestContext(req))
+	if err != nil {
+		return StepTestResult{}, err
+	}
+	return StepTestResult{
+		WorkflowID: workflowID,
+		Version:    version,
+		StepID:     step.ID,
+		Type:       step.Type,
+		TestResult: out,
+	}, nil
+}
+
+// stepTestContext builds the template context the connector executor would
+// build from a stored case. Step results may be given either as the bare
+// result or wrapped as {"result": ...}.
+func stepTestContext(req StepTestRequest) map[string]any {
+	caseData := req.CaseData
+	if caseData == nil {
+		caseData = map[string]any{}
+	}
+	steps := make(map[string]any, len(req.StepResults))
+	for id, result := range req.StepResults {
+		if wrapped, ok := result.(map[string]any); ok {
+			if _, has := wrapped["result"]; has && len(wrapped) == 1 {
+				steps[id] = wrapped
+				continue
+			}
+		}
+		steps[id] = map[string]any{"result": result}
+	}
+	caseMap := map[string]any{"data": caseData, "steps": steps}
+	for k, v := range caseData {
+		caseMap[k] = v
+	}
+	return map[string]any{
+		"case": caseMap,
+		"now":  time.Now().UTC().Format(time.RFC3339),
+	}
+}
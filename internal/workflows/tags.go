@@ -0,0 +1,322 @@
+package workflows
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"strings"
+	"time"
+	"unicode"
+	"unicode/utf8"
+
+	"github.com/google/uuid"
+)
+
+const maxTagLength = 64
+
+// workflowTagsColumn selects the sorted tags of workflow alias w as a JSON
+// array, read back with decodeTags.
+const workflowTagsColumn = `(SELECT COALESCE(json_agg(t.name ORDER BY t.name), '[]'::json)::text
+        FROM workflow_tags wt JOIN tags t ON t.id = wt.tag_id
+        WHERE wt.workflow_id = w.id)`
+
+var (
+	ErrInvalidTag  = errors.New("invalid tag")
+	ErrTagNotFound = errors.New("tag not found")
+	// ErrTagExists is returned when renaming onto an existing tag; merge
+	// the two instead.
+	ErrTagExists = errors.New("tag already exists")
+	ErrTagInUse  = errors.New("tag in use")
+)
+
+type Tag struct {
+	Name          string    `json:"name"`
+	WorkflowCount int       `json:"workflow_count"`
+	CreatedAt     time.Time `json:"created_at"`
+}
+
+// NormalizeTag trims and lowercases a tag and collapses inner whitespace, so
+// "Loans " and "loans" are the same tag.
+func NormalizeTag(raw string) (string, error) {
+	tag := strings.ToLower(strings.Join(strings.Fields(raw), " "))
+	if tag == "" || utf8.RuneCountInString(tag) > maxTagLength {
+		return "", fmt.Errorf("%w: must be 1-%d characters", ErrInvalidTag, maxTagLength)
+	}
+	if strings.ContainsFunc(tag, unicode.IsControl) {
+		return "", fmt.Errorf("%w: control characters are not allowed", ErrInvalidTag)
+	}
+	return tag, nil
+}
+
+// ListTags returns every tag in the tenant with the number of workflows using
+// it, including unused tags.
+func (s *Service) ListTags(ctx context.Context, tenantID uuid.UUID) ([]Tag, error) {
+	rows, err := s.db.QueryContext(ctx, `
+SELECT t.name, t.created_at, COUNT(wt.workflow_id)
+FROM tags t
+LEFT JOIN workflow_tags wt ON wt.tag_id = t.id
+WHERE t.tenant_id = $1
+GROUP BY t.id
+ORDER BY t.name
+`, tenantID)
+	if err != nil {
+		return nil, fmt.Errorf("list tags: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	out := make([]Tag, 0)
+	for rows.Next() {
+		var tag Tag
+		if err := rows.Scan(&tag.Name, &tag.CreatedAt, &tag.WorkflowCount); err != nil {
+			return nil, fmt.Errorf("scan tag: %w", err)
+		}
+		tag.CreatedAt = tag.CreatedAt.UTC()
+		out = append(out, tag)
+	}
+	return out, rows.Err()
+}
+
+// SetWorkflowTags replaces a workflow's tags, creating tags that do not exist
+// yet. It returns sql.ErrNoRows when the workflow is not in the tenant.
+func (s *Service) SetWorkflowTags(ctx context.Context, tenantID, workflowID uuid.UUID, raw []string) ([]string, error) {
+	tags := make([]string, 0, len(raw))
+	seen := map[string]bool{}
+	for _, r := range raw {
+		tag, err := NormalizeTag(r)
+		if err != nil {
+			return nil, err
+		}
+		if !seen[tag] {
+			seen[tag] = true
+			tags = append(tags, tag)
+		}
+	}
+
+	tx, err := s.db.BeginTx(ctx, nil)
+	if err != nil {
+		return nil, fmt.Errorf("begin set tags tx: %w", err)
+	}
+	defer func() { _ = tx.Rollback() }()
+
+	var id uuid.UUID
+	if err := tx.QueryRowContext(ctx, `SELECT id FROM workflows WHERE id = $1 AND tenant_id = $2 FOR UPDATE`, workflowID, tenantID).Scan(&id); err != nil {
+		return nil, err
+	}
+	if _, err := tx.ExecContext(ctx, `DELETE FROM workflow_tags WHERE workflow_id = $1`, workflowID); err != nil {
+		return nil, fmt.Errorf("clear workflow tags: %w", err)
+	}
+	for _, tag := range tags {
+		tagID, err := upsertTag(ctx, tx, tenantID, tag)
+		if err != nil {
+			return nil, err
+		}
+		if _, err := tx.ExecContext(ctx, `INSERT INTO workflow_tags (workflow_id, tag_id) VALUES ($1, $2)`, workflowID, tagID); err != nil {
+			return nil, fmt.Errorf("tag workflow: %w", err)
+		}
+	}
+	if _, err := tx.ExecContext(ctx, `UPDATE workflows SET updated_at = now() WHERE id = $1`, workflowID); err != nil {
+		return nil, fmt.Errorf("touch workflow: %w", err)
+	}
+	if err := tx.Commit(); err != nil {
+		return nil, fmt.Errorf("commit set tags tx: %w", err)
+	}
+	return tags, nil
+}
+
+// RenameTag renames a tag on every workflow that uses it.
+func (s *Service) RenameTag(ctx context.Context, tenantID uuid.UUID, from, to string) (Tag, error) {
+	from, err := NormalizeTag(from)
+	if err != nil {
+		return Tag{}, err
+	}
+	to, err = NormalizeTag(to)
+	if err != nil {
+		return Tag{}, err
+	}
+
+	tx, err := s.db.BeginTx(ctx, nil)
+	if err != nil {
+		return Tag{}, fmt.Errorf("begin rename tag tx: %w", err)
+	}
+	defer func() { _ = tx.Rollback() }()
+
+	tagID, err := lockTag(ctx, tx, tenantID, from)
+	if err != nil {
+		return Tag{}, err
+	}
+	if from != to {
+		var exists bool
+		if err := tx.QueryRowContext(ctx, `SELECT EXISTS (SELECT 1 FROM tags WHERE tenant_id = $1 AND name = $2)`, tenantID, to).Scan(&exists); err != nil {
+			return Tag{}, fmt.Errorf("check tag: %w", err)
+		}
+		if exists {
+			return Tag{}, ErrTagExists
+		}
+		if _, err := tx.ExecContext(ctx, `UPDATE tags SET name = $2 WHERE id = $1`, tagID, to); err != nil {
+			return Tag{}, fmt.Errorf("rename tag: %w", err)
+		}
+	}
+	tag, err := describeTag(ctx, tx, tagID)
+	if err != nil {
+		return Tag{}, err
+	}
+	if err := tx.Commit(); err != nil {
+		return Tag{}, fmt.Errorf("commit rename tag tx: %w", err)
+	}
+	return tag, nil
+}
+
+// MergeTags moves every workflow tagged with any of sources onto target,
+// creating target if needed, and deletes the sources.
+func (s *Service) MergeTags(ctx context.Context, tenantID uuid.UUID, sources []string, target string) (Tag, error) {
+	target, err := NormalizeTag(target)
+	if err != nil {
+		return Tag{}, err
+	}
+	if len(sources) == 0 {
+		return Tag{}, fmt.Errorf("%w: at least one source tag is required", ErrInvalidTag)
+	}
+
+	tx, err := s.db.BeginTx(ctx, nil)
+	if err != nil {
+		return Tag{}, fmt.Errorf("begin merge tags tx: %w", err)
+	}
+	defer func() { _ = tx.Rollback() }()
+
+	targetID, err := upsertTag(ctx, tx, tenantID, target)
+	if err != nil {
+		return Tag{}, err
+	}
+	for _, raw := range sources {
+		source, err := NormalizeTag(raw)
+		if err != nil {
+			return Tag{}, err
+		}
+		if source == target {
+			continue
+		}
+		sourceID, err := lockTag(ctx, tx, tenantID, source)
+		if err != nil {
+			return Tag{}, fmt.Errorf("%w: %s", err, source)
+		}
+		if _, err := tx.ExecContext(ctx, `
+INSERT INTO workflow_tags (workflow_id, tag_id)
+SELECT workflow_id, $2 FROM workflow_tags WHERE tag_id = $1
+ON CONFLICT DO NOTHING
+`, sourceID, targetID); err != nil {
+			return Tag{}, fmt.Errorf("merge tag %s: %w", source, err)
+		}
+		if _, err := tx.ExecContext(ctx, `DELETE FROM tags WHERE id = $1`, sourceID); err != nil {
+			return Tag{}, fmt.Errorf("delete merged tag %s: %w", source, err)
+		}
+	}
+	tag, err := describeTag(ctx, tx, targetID)
+	if err != nil {
+		return Tag{}, err
+	}
+	if err := tx.Commit(); err != nil {
+		return Tag{}, fmt.Errorf("commit merge tags tx: %w", err)
+	}
+	return tag, nil
+}
+
+// DeleteTag deletes a tag no workflow uses.
+func (s *Service) DeleteTag(ctx context.Context, tenantID uuid.UUID, name string) error {
+	name, err := NormalizeTag(name)
+	if err != nil {
+		return err
+	}
+	tx, err := s.db.BeginTx(ctx, nil)
+	if err != nil {
+		return fmt.Errorf("begin delete tag tx: %w", err)
+	}
+	defer func() { _ = tx.Rollback() }()
+
+	tagID, err := lockTag(ctx, tx, tenantID, name)
+	if err != nil {
+		return err
+	}
+	var inUse bool
+	if err := tx.QueryRowContext(ctx, `SELECT EXISTS (SELECT 1 FROM workflow_tags WHERE tag_id = $1)`, tagID).Scan(&inUse); err != nil {
+		return fmt.Errorf("check tag usage: %w", err)
+	}
+	if inUse {
+		return ErrTagInUse
+	}
+	if _, err := tx.ExecContext(ctx, `DELETE FROM tags WHERE id = $1`, tagID); err != nil {
+		return fmt.Errorf("delete tag: %w", err)
+	}
+	if err := tx.Commit(); err != nil {
+		return fmt.Errorf("commit delete tag tx: %w", err)
+	}
+	return nil
+}
+
+// PruneTags deletes every unused tag in the tenant and returns their names.
+func (s *Service) PruneTags(ctx context.Context, tenantID uuid.UUID) ([]string, error) {
+	rows, err := s.db.QueryContext(ctx, `
+DELETE FROM tags t
+WHERE t.tenant_id = $1
+  AND NOT EXISTS (SELECT 1 FROM workflow_tags wt WHERE wt.tag_id = t.id)
+RETURNING t.name
+`, tenantID)
+	if err != nil {
+		return nil, fmt.Errorf("prune tags: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	deleted := make([]string, 0)
+	for rows.Next() {
+		var name string
+		if err := rows.Scan(&name); err != nil {
+			return nil, fmt.Errorf("scan pruned tag: %w", err)
+		}
+		deleted = append(deleted, name)
+	}
+	return deleted, rows.Err()
+}
+
+func decodeTags(raw string) []string {
+	tags := []string{}
+	_ = json.Unmarshal([]byte(raw), &tags)
+	return tags
+}
+
+func upsertTag(ctx context.Context, tx *sql.Tx, tenantID uuid.UUID, name string) (uuid.UUID, error) {
+	var id uuid.UUID
+	err := tx.QueryRowContext(ctx, `
+INSERT INTO tags (tenant_id, name) VALUES ($1, $2)
+ON CONFLICT (tenant_id, name) DO UPDATE SET name = EXCLUDED.name
+RETURNING id
+`, tenantID, name).Scan(&id)
+	if err != nil {
+		return uuid.Nil, fmt.Errorf("upsert tag: %w", err)
+	}
+	return id, nil
+}
+
+func lockTag(ctx context.Context, tx *sql.Tx, tenantID uuid.UUID, name string) (uuid.UUID, error) {
+	var id uuid.UUID
+	err := tx.QueryRowContext(ctx, `SELECT id FROM tags WHERE tenant_id = $1 AND name = $2 FOR UPDATE`, tenantID, name).Scan(&id)
+	if errors.Is(err, sql.ErrNoRows) {
+		return uuid.Nil, ErrTagNotFound
+	}
+	if err != nil {
+		return uuid.Nil, fmt.Errorf("load tag: %w", err)
+	}
+	return id, nil
+}
+
+func describeTag(ctx context.Context, tx *sql.Tx, tagID uuid.UUID) (Tag, error) {
+	var tag Tag
+	err := tx.QueryRowContext(ctx, `
+SELECT t.name, t.created_at, (SELECT COUNT(*) FROM workflow_tags WHERE tag_id = t.id)
+FROM tags t
+WHERE t.id = $1
+`, tagID).Scan(&tag.Name, &tag.CreatedAt, &tag.WorkflowCount)
+	if err != nil {
+		return Tag{}, fmt.Errorf("describe tag: %w", err)
+	}
+	tag.CreatedAt = tag.CreatedAt.UTC()
+	return tag, nil
+}
@@ -0,0 +1,25 @@
+package workflows
+
+import (
+	"errors"
+	"strings"
+	"testing"
+)
+
+func TestNormalizeTag(t *testing.T) {
+	got, err := NormalizeTag("  Customer   Onboarding ")
+	if err != nil || got != "customer onboarding" {
+		t.Fatalf("got %q, %v", got, err)
+	}
+	for _, raw := range []string{"", "   ", strings.Repeat("x", maxTagLength+1), "bad\ttag\x00"} {
+		if _, err := NormalizeTag(raw); !errors.Is(err, ErrInvalidTag) {
+			t.Fatalf("NormalizeTag(%q): expected ErrInvalidTag, got %v", raw, err)
+		}
+	}
+	if got := decodeTags(`["a","b"]`); len(got) != 2 {
+		t.Fatalf("decodeTags: %v", got)
+	}
+	if got := decodeTags(""); got == nil || len(got) != 0 {
+		t.Fatalf("decodeTags should return an empty slice, got %#v", got)
+	}
+}
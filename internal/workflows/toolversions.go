@@ -0,0 +1,52 @@
+package workflows
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"strings"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+type toolVersionCatalog interface {
+	PinnedToolWarning(ctx context.Context, tenantID uuid.UUID, serverURL, toolName, version string) (string, error)
+}
+
+func (s *Service) SetToolVersionCatalog(catalog toolVersionCatalog) {
+	s.toolVersions = catalog
+}
+
+// pinnedToolWarnings reports mcp-client steps pinned to a tool version the
+// server no longer offers, and steps calling a tool the server has dropped
+// altogether. A step keeps running against whatever the server exposes, so
+// these are warnings rather than validation errors.
+func (s *Service) pinnedToolWarnings(ctx context.Context, tenantID uuid.UUID, ast engine.WorkflowAST) []string {
+	if s.toolVersions == nil {
+		return nil
+	}
+	var warnings []string
+	for _, step := range ast.Steps {
+		if strings.TrimSpace(step.Type) != "mcp-client" || len(step.Config) == 0 {
+			continue
+		}
+		var cfg struct {
+			ServerURL   string `json:"server_url"`
+			Tool        string `json:"tool"`
+			ToolVersion string `json:"tool_version"`
+		}
+		if err := json.Unmarshal(step.Config, &cfg); err != nil || strings.TrimSpace(cfg.Tool) == "" {
+			continue
+		}
+		warning, err := s.toolVersions.PinnedToolWarning(ctx, tenantID, strings.TrimSpace(cfg.ServerURL), strings.TrimSpace(cfg.Tool), strings.TrimSpace(cfg.ToolVersion))
+		if err != nil {
+			warnings = append(warnings, fmt.Sprintf("step %q: could not check pinned tool version: %v", step.ID, err))
+			continue
+		}
+		if warning != "" {
+			warnings = append(warnings, fmt.Sprintf("step %q: %s", step.ID, warning))
+		}
+	}
+	return warnings
+}
@@ -14,6 +14,11 @@ const (
 	agentLowConfidenceEscalate = "escalate_to_human"
 )
 
+// ValidateDefinition checks a workflow AST the way saving a draft does.
+func ValidateDefinition(ast json.RawMessage) error {
+	return validateWorkflowAST(ast)
+}
+
 func validateWorkflowAST(ast json.RawMessage) error {
 	var workflow engine.WorkflowAST
 	if err := json.Unmarshal(ast, &workflow); err != nil {
@@ -100,9 +105,9 @@ func validateStepRequiredConfig(step engine.WorkflowStep) error {
 		if len(step.Outcomes) == 0 && !hasLegacyRuleOutcomes {
 			return fmt.Errorf("step %q rule requires at least one outcome route", step.ID)
 		}
-	case "timer":
-		if !hasStringValue(cfg, "duration") {
-			return fmt.Errorf("step %q timer requires duration", step.ID)
+	case "timer", "delay", "wait_until":
+		if requirement := missingTimerConfig(step.Type, cfg); requirement != "" {
+			return fmt.Errorf("step %q %s requires %s", step.ID, step.Type, requirement)
 		}
 	case "notification":
 		if !hasStringValue(cfg, "channel") {
@@ -112,6 +117,14 @@ func validateStepRequiredConfig(step engine.WorkflowStep) error {
 		if !hasStringValue(cfg, "component") {
 			return fmt.Errorf("step %q ai_component requires component", step.ID)
 		}
+	case "sub_workflow":
+		if !hasStringValue(cfg, "case_type") {
+			return fmt.Errorf("step %q sub_workflow requires case_type", step.ID)
+		}
+	case "aggregate":
+		if !hasKey(cfg, "from") {
+			return fmt.Errorf("step %q aggregate requires from", step.ID)
+		}
 	case "extraction":
 		if !hasStringValue(cfg, "document_path") && !hasStringValue(cfg, "document_ref") {
 			return fmt.Errorf("step %q extraction requires document_path or document_ref", step.ID)
@@ -247,6 +260,28 @@ func hasStringValue(cfg map[string]any, key string) bool {
 	return strings.TrimSpace(fmt.Sprint(raw)) != ""
 }
 
+// missingTimerConfig describes what a timer, delay or wait_until step is
+// missing, or returns "" when it can be scheduled.
+func missingTimerConfig(stepType string, cfg map[string]any) string {
+	hasDuration := hasStringValue(cfg, "duration") || hasStringValue(cfg, "seconds") || hasStringValue(cfg, "minutes")
+	hasDeadline := hasStringValue(cfg, "until") || hasStringValue(cfg, "until_path") || hasStringValue(cfg, "schedule")
+	switch strings.TrimSpace(stepType) {
+	case "delay":
+		if !hasDuration {
+			return "duration, seconds or minutes"
+		}
+	case "wait_until":
+		if !hasDeadline {
+			return "until, until_path or schedule"
+		}
+	default:
+		if !hasDuration && !hasDeadline {
+			return "duration, until or schedule"
+		}
+	}
+	return ""
+}
+
 func validateAgentStepConfig(stepID string, raw json.RawMessage) error {
 	if len(raw) == 0 {
 		return nil
@@ -0,0 +1,92 @@
+package workflows
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"regexp"
+
+	"github.com/google/uuid"
+)
+
+// ErrInvalidVariableSet is returned for an environment variable set with an
+// invalid environment or variable name.
+var ErrInvalidVariableSet = errors.New("invalid variable set")
+
+var variableNamePattern = regexp.MustCompile(`^[A-Za-z0-9_.-]{1,128}$`)
+
+// EnvironmentVariables returns the tenant's variable set for an
+// environment. Cases resolve {{env.*}} references against it on top of the
+// workflow's own overlays.
+func (s *Service) EnvironmentVariables(ctx context.Context, tenantID uuid.UUID, environment string) (map[string]any, error) {
+	if !variableNamePattern.MatchString(environment) {
+		return nil, fmt.Errorf("%w: environment %q", ErrInvalidVariableSet, environment)
+	}
+	rows, err := s.db.QueryContext(ctx, `
+SELECT name, value FROM environment_variables WHERE tenant_id = $1 AND environment = $2
+`, tenantID, environment)
+	if err != nil {
+		return nil, fmt.Errorf("list environment variables: %w", err)
+	}
+	defer func() { _ = rows.Close() }()
+	out := map[string]any{}
+	for rows.Next() {
+		var (
+			name  string
+			raw   []byte
+			value any
+		)
+		if err := rows.Scan(&name, &raw); err != nil {
+			return nil, fmt.Errorf("scan environment variable: %w", err)
+		}
+		if err := json.Unmarshal(raw, &value); err != nil {
+			return nil, fmt.Errorf("decode environment variable %s: %w", name, err)
+		}
+		out[name] = value
+	}
+	if err := rows.Err(); err != nil {
+		return nil, fmt.Errorf("iterate environment variables: %w", err)
+	}
+	return out, nil
+}
+
+// PutEnvironmentVariables replaces the tenant's variable set for an
+// environment. Running cases pick up the new values at their next step.
+func (s *Service) PutEnvironmentVariables(ctx context.Context, tenantID, actorID uuid.UUID, environment string, values map[string]any) error {
+	if !variableNamePattern.MatchString(environment) {
+		return fmt.Errorf("%w: environment %q", ErrInvalidVariableSet, environment)
+	}
+	for name := range values {
+		if !variableNamePattern.MatchString(name) {
+			return fmt.Errorf("%w: variable %q", ErrInvalidVariableSet, name)
+		}
+	}
+	tx, err := s.db.BeginTx(ctx, nil)
+	if err != nil {
+		return fmt.Errorf("begin put environment variables tx: %w", err)
+	}
+	defer func() { _ = tx.Rollback() }()
+
+	if _, err := tx.ExecContext(ctx, `
+DELETE FROM environment_variables WHERE tenant_id = $1 AND environment = $2
+`, tenantID, environment); err != nil {
+		return fmt.Errorf("clear environment variables: %w", err)
+	}
+	for name, value := range values {
+		raw, err := json.Marshal(value)
+		if err != nil {
+			return fmt.Errorf("encode environment variable %s: %w", name, err)
+		}
+		if _, err := tx.ExecContext(ctx, `
+INSERT INTO environment_variables (tenant_id, environment, name, value, updated_by)
+VALUES ($1, $2, $3, $4::jsonb, $5)
+`, tenantID, environment, name, string(raw), actorID); err != nil {
+			return fmt.Errorf("insert environment variable %s: %w", name, err)
+		}
+	}
+	if err := tx.Commit(); err != nil {
+		return fmt.Errorf("commit put environment variables tx: %w", err)
+	}
+	return nil
+}
@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS tenant_feature_flags (
+    tenant_id  UUID NOT NULL REFERENCES tenants(id),
+    key        TEXT NOT NULL,
+    enabled    BOOLEAN NOT NULL,
+    updated_by UUID REFERENCES principals(id),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (tenant_id, key)
+);
+
+COMMENT ON TABLE tenant_feature_flags IS
+    'Per-tenant overrides of feature flag defaults; absent rows fall back to server configuration.';
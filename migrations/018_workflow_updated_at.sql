@@ -0,0 +1,27 @@
+ALTER TABLE workflows ADD COLUMN IF NOT EXISTS updated_at TIMESTAMPTZ NOT NULL DEFAULT now();
+
+UPDATE workflows w
+SET updated_at = GREATEST(w.created_at, COALESCE((
+    SELECT MAX(COALESCE(wv.published_at, wv.created_at))
+    FROM workflow_versions wv
+    WHERE wv.workflow_id = w.id
+), w.created_at));
+
+CREATE OR REPLACE FUNCTION workflow_versions_touch_workflow()
+RETURNS TRIGGER
+LANGUAGE plpgsql
+AS $$
+BEGIN
+    UPDATE workflows SET updated_at = now() WHERE id = NEW.workflow_id;
+    RETURN NEW;
+END;
+$$;
+
+DROP TRIGGER IF EXISTS trg_workflow_versions_touch_workflow ON workflow_versions;
+CREATE TRIGGER trg_workflow_versions_touch_workflow
+AFTER INSERT OR UPDATE ON workflow_versions
+FOR EACH ROW
+EXECUTE FUNCTION workflow_versions_touch_workflow();
+
+CREATE INDEX IF NOT EXISTS idx_workflows_tenant_created ON workflows(tenant_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_workflows_tenant_updated ON workflows(tenant_id, updated_at);
@@ -0,0 +1,33 @@
+CREATE TABLE IF NOT EXISTS connector_calls (
+    id            UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id     UUID NOT NULL REFERENCES tenants(id),
+    connector_key TEXT NOT NULL,
+    action_key    TEXT NOT NULL,
+    case_id       UUID REFERENCES cases(id),
+    workflow_id   UUID REFERENCES workflows(id),
+    principal_id  UUID REFERENCES principals(id),
+    duration_ms   BIGINT NOT NULL,
+    success       BOOLEAN NOT NULL,
+    called_at     TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_connector_calls_tenant_connector_time
+    ON connector_calls(tenant_id, connector_key, called_at DESC);
+
+COMMENT ON TABLE connector_calls IS
+    'One row per connector action invocation, from workflow steps and interactive tests.';
+
+CREATE TABLE IF NOT EXISTS connector_usage_daily (
+    tenant_id         UUID NOT NULL REFERENCES tenants(id),
+    connector_key     TEXT NOT NULL,
+    action_key        TEXT NOT NULL,
+    day               DATE NOT NULL,
+    calls             BIGINT NOT NULL DEFAULT 0,
+    failures          BIGINT NOT NULL DEFAULT 0,
+    total_duration_ms BIGINT NOT NULL DEFAULT 0,
+    max_duration_ms   BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (tenant_id, connector_key, action_key, day)
+);
+
+COMMENT ON TABLE connector_usage_daily IS
+    'Per-day rollup of connector_calls, maintained on write; days are UTC.';
@@ -0,0 +1,22 @@
+CREATE TABLE IF NOT EXISTS sub_workflow_calls (
+    child_case_id  UUID PRIMARY KEY REFERENCES cases(id),
+    tenant_id      UUID NOT NULL REFERENCES tenants(id),
+    parent_case_id UUID NOT NULL REFERENCES cases(id),
+    parent_step_id TEXT NOT NULL,
+    depth          INTEGER NOT NULL,
+    call_chain     JSONB NOT NULL DEFAULT '[]',
+    outputs        JSONB NOT NULL DEFAULT '[]',
+    status         TEXT NOT NULL DEFAULT 'running'
+                   CHECK (status IN ('running', 'completed', 'failed')),
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT now(),
+    completed_at   TIMESTAMPTZ
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_sub_workflow_calls_parent_running
+    ON sub_workflow_calls(parent_case_id, parent_step_id)
+    WHERE status = 'running';
+
+COMMENT ON TABLE sub_workflow_calls IS
+    'Links a child case started by a sub_workflow step to the parent step waiting on it.';
+COMMENT ON COLUMN sub_workflow_calls.call_chain IS
+    'Case type names from the root case down to this child, used for cycle detection.';
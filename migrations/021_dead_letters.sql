@@ -0,0 +1,22 @@
+CREATE TABLE IF NOT EXISTS dead_letters (
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id    UUID NOT NULL REFERENCES tenants(id),
+    case_id      UUID NOT NULL REFERENCES cases(id),
+    step_id      TEXT NOT NULL,
+    step_type    TEXT NOT NULL,
+    attempts     INTEGER NOT NULL,
+    error        TEXT NOT NULL,
+    input        JSONB NOT NULL DEFAULT '{}',
+    context      JSONB NOT NULL DEFAULT '{}',
+    status       TEXT NOT NULL DEFAULT 'open'
+                 CHECK (status IN ('open', 'requeued')),
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT now(),
+    requeued_at  TIMESTAMPTZ,
+    requeued_by  UUID REFERENCES principals(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_dead_letters_tenant_status_time
+    ON dead_letters(tenant_id, status, created_at DESC);
+
+COMMENT ON TABLE dead_letters IS
+    'Steps that failed after exhausting retries, with the step config and case context at the time.';
@@ -0,0 +1,20 @@
+CREATE TABLE IF NOT EXISTS tags (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id   UUID NOT NULL REFERENCES tenants(id),
+    name        TEXT NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (tenant_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS workflow_tags (
+    workflow_id UUID NOT NULL REFERENCES workflows(id) ON DELETE CASCADE,
+    tag_id      UUID NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (workflow_id, tag_id)
+);
+
+-- Usage counts, rename and merge look workflows up by tag.
+CREATE INDEX IF NOT EXISTS idx_workflow_tags_tag
+    ON workflow_tags(tag_id);
+
+COMMENT ON TABLE tags IS
+    'Per-tenant workflow labels. Renames and merges update one row instead of every workflow.';
@@ -0,0 +1,31 @@
+CREATE TABLE IF NOT EXISTS workflow_shares (
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    workflow_id  UUID NOT NULL REFERENCES workflows(id) ON DELETE CASCADE,
+    principal_id UUID REFERENCES principals(id) ON DELETE CASCADE,
+    role_id      UUID REFERENCES roles(id) ON DELETE CASCADE,
+    can_read     BOOLEAN NOT NULL DEFAULT false,
+    can_write    BOOLEAN NOT NULL DEFAULT false,
+    can_execute  BOOLEAN NOT NULL DEFAULT false,
+    created_by   UUID REFERENCES principals(id),
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CHECK ((principal_id IS NULL) <> (role_id IS NULL)),
+    CHECK (can_read OR can_write OR can_execute)
+);
+
+-- One share per grantee and workflow. Leading with the grantee also serves
+-- permission checks, which look up every share held by a principal or its roles.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_workflow_shares_principal
+    ON workflow_shares(principal_id, workflow_id) WHERE principal_id IS NOT NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_workflow_shares_role
+    ON workflow_shares(role_id, workflow_id) WHERE role_id IS NOT NULL;
+
+-- Roles that could already edit every workflow keep managing ownership and sharing.
+INSERT INTO role_permissions (role_id, permission)
+SELECT role_id, 'workflows:share'
+FROM role_permissions
+WHERE permission = 'workflows:edit'
+ON CONFLICT DO NOTHING;
+
+COMMENT ON TABLE workflow_shares IS
+    'Per-workflow read, write and execute grants to a principal or to every holder of a role, on top of tenant-wide role permissions.';
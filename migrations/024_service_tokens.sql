@@ -0,0 +1,26 @@
+CREATE TABLE IF NOT EXISTS service_tokens (
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id    UUID NOT NULL REFERENCES tenants(id),
+    name         TEXT NOT NULL,
+    token_hash   TEXT NOT NULL UNIQUE,
+    permissions  JSONB NOT NULL DEFAULT '[]'::jsonb,
+    workflow_ids JSONB NOT NULL DEFAULT '[]'::jsonb,
+    created_by   UUID NOT NULL REFERENCES principals(id),
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT now(),
+    expires_at   TIMESTAMPTZ NOT NULL,
+    revoked_at   TIMESTAMPTZ,
+    last_used_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_service_tokens_tenant
+    ON service_tokens(tenant_id, created_at DESC);
+
+-- Roles that manage users also manage service tokens.
+INSERT INTO role_permissions (role_id, permission)
+SELECT role_id, 'admin:tokens'
+FROM role_permissions
+WHERE permission = 'admin:users'
+ON CONFLICT DO NOTHING;
+
+COMMENT ON TABLE service_tokens IS
+    'Hashed, expiring tokens that act as their creator limited to the listed permissions and, when workflow_ids is not empty, to starting cases of those workflows.';
@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS mcp_tool_versions (
+    tenant_id    UUID NOT NULL REFERENCES tenants(id),
+    server_url   TEXT NOT NULL,
+    tool_name    TEXT NOT NULL,
+    version      TEXT NOT NULL,
+    description  TEXT NOT NULL DEFAULT '',
+    input_schema JSONB NOT NULL DEFAULT '{}'::jsonb,
+    first_seen   TIMESTAMPTZ NOT NULL DEFAULT now(),
+    last_seen    TIMESTAMPTZ NOT NULL DEFAULT now(),
+    retired_at   TIMESTAMPTZ,
+    PRIMARY KEY (tenant_id, server_url, tool_name, version)
+);
+
+CREATE INDEX IF NOT EXISTS idx_mcp_tool_versions_current
+    ON mcp_tool_versions(tenant_id, server_url) WHERE retired_at IS NULL;
+
+COMMENT ON TABLE mcp_tool_versions IS
+    'Every input schema seen for an MCP tool. version is a hash of the schema; retired_at is set once discovery no longer returns that version.';
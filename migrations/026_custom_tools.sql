@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS custom_tools (
+    id            UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id     UUID NOT NULL REFERENCES tenants(id),
+    name          TEXT NOT NULL,
+    description   TEXT NOT NULL DEFAULT '',
+    input_schema  JSONB NOT NULL DEFAULT '{}'::jsonb,
+    output_schema JSONB,
+    endpoint      JSONB NOT NULL,
+    created_by    UUID REFERENCES principals(id),
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at    TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (tenant_id, name)
+);
+
+COMMENT ON TABLE custom_tools IS
+    'User-defined tools backed by an HTTP endpoint. endpoint holds the URL template, method, headers and auth profile.';
@@ -0,0 +1,31 @@
+CREATE TABLE IF NOT EXISTS usage_quotas (
+    tenant_id           UUID NOT NULL REFERENCES tenants(id),
+    principal_id        UUID REFERENCES principals(id),
+    executions_per_hour BIGINT NOT NULL DEFAULT 0,
+    tool_runtime_ms     BIGINT NOT NULL DEFAULT 0,
+    llm_tokens          BIGINT NOT NULL DEFAULT 0,
+    updated_by          UUID REFERENCES principals(id),
+    updated_at          TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_usage_quotas_scope
+    ON usage_quotas(tenant_id, COALESCE(principal_id, '00000000-0000-0000-0000-000000000000'::uuid));
+
+COMMENT ON TABLE usage_quotas IS
+    'Usage limits per workspace (principal_id NULL) or per user; 0 means unlimited. Executions count per UTC hour, tool runtime and LLM tokens per UTC month.';
+
+CREATE TABLE IF NOT EXISTS usage_hourly (
+    tenant_id       UUID NOT NULL REFERENCES tenants(id),
+    principal_id    UUID NOT NULL REFERENCES principals(id),
+    hour            TIMESTAMPTZ NOT NULL,
+    executions      BIGINT NOT NULL DEFAULT 0,
+    tool_runtime_ms BIGINT NOT NULL DEFAULT 0,
+    llm_tokens      BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (tenant_id, principal_id, hour)
+);
+
+CREATE INDEX IF NOT EXISTS idx_usage_hourly_tenant_hour
+    ON usage_hourly(tenant_id, hour);
+
+COMMENT ON TABLE usage_hourly IS
+    'Per-hour consumption by the principal that started each case, maintained on write. Runtime is time spent in step executors.';
@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS execution_artifacts (
+    id           UUID PRIMARY KEY,
+    tenant_id    UUID NOT NULL REFERENCES tenants(id),
+    case_id      UUID NOT NULL REFERENCES cases(id) ON DELETE CASCADE,
+    step_id      TEXT NOT NULL,
+    name         TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size_bytes   BIGINT NOT NULL,
+    checksum     TEXT NOT NULL,
+    storage_key  TEXT NOT NULL,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_execution_artifacts_case
+    ON execution_artifacts(tenant_id, case_id, created_at);
+
+COMMENT ON TABLE execution_artifacts IS
+    'Large step outputs (files, reports, transcripts) held in the artifact object store; step results keep only the artifact id.';
@@ -0,0 +1,5 @@
+ALTER TABLE custom_tools
+    ADD COLUMN IF NOT EXISTS sensitive_output BOOLEAN NOT NULL DEFAULT false;
+
+COMMENT ON COLUMN custom_tools.sensitive_output IS
+    'When true, step records keep a redaction marker instead of the tool response.';
@@ -0,0 +1,25 @@
+ALTER TABLE workflow_versions DROP CONSTRAINT IF EXISTS workflow_versions_status_check;
+ALTER TABLE workflow_versions ADD CONSTRAINT workflow_versions_status_check
+    CHECK (status IN ('draft', 'published', 'withdrawn', 'canary'));
+
+CREATE TABLE IF NOT EXISTS workflow_canaries (
+    id               UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id        UUID NOT NULL REFERENCES tenants(id),
+    workflow_id      UUID NOT NULL REFERENCES workflows(id),
+    version          INTEGER NOT NULL,
+    percent          INTEGER NOT NULL CHECK (percent BETWEEN 1 AND 99),
+    max_failure_rate DOUBLE PRECISION NOT NULL CHECK (max_failure_rate > 0 AND max_failure_rate <= 1),
+    min_cases        INTEGER NOT NULL DEFAULT 20 CHECK (min_cases > 0),
+    status           TEXT NOT NULL DEFAULT 'active'
+                     CHECK (status IN ('active', 'promoted', 'rolled_back')),
+    reason           TEXT NOT NULL DEFAULT '',
+    created_by       UUID NOT NULL REFERENCES principals(id),
+    started_at       TIMESTAMPTZ NOT NULL DEFAULT now(),
+    ended_at         TIMESTAMPTZ
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_workflow_canaries_active
+    ON workflow_canaries(workflow_id) WHERE status = 'active';
+
+COMMENT ON TABLE workflow_canaries IS
+    'Canary rollouts: cases started by triggers and webhooks run the canary version with the given percent chance, and the canary is rolled back once max_failure_rate of at least min_cases have a failed step.';
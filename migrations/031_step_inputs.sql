@@ -0,0 +1,5 @@
+ALTER TABLE case_steps
+    ADD COLUMN IF NOT EXISTS inputs JSONB;
+
+COMMENT ON COLUMN case_steps.inputs IS
+    '{"attempt":1,"config":{},"case":{},"steps":{},"recorded_at":"timestamp"} — what the latest attempt ran with, for replay.';
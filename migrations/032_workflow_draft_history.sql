@@ -0,0 +1,20 @@
+CREATE TABLE IF NOT EXISTS workflow_draft_history (
+    id            BIGSERIAL PRIMARY KEY,
+    tenant_id     UUID NOT NULL REFERENCES tenants(id),
+    workflow_id   UUID NOT NULL REFERENCES workflows(id),
+    draft_version INTEGER NOT NULL,
+    source        TEXT NOT NULL CHECK (source IN ('ast', 'graph', 'yaml')),
+    before_ast    JSONB NOT NULL,
+    before_yaml   TEXT,
+    after_ast     JSONB NOT NULL,
+    after_yaml    TEXT,
+    undone        BOOLEAN NOT NULL DEFAULT false,
+    actor_id      UUID NOT NULL REFERENCES principals(id),
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_workflow_draft_history_workflow
+    ON workflow_draft_history(workflow_id, id);
+
+COMMENT ON TABLE workflow_draft_history IS
+    'Bounded log of draft saves for designer undo/redo. Undone rows can be redone until the next save, which deletes them.';
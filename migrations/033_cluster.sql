@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS cluster_replicas (
+    id           TEXT PRIMARY KEY,
+    hostname     TEXT NOT NULL DEFAULT '',
+    started_at   TIMESTAMPTZ NOT NULL DEFAULT now(),
+    heartbeat_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TABLE IF NOT EXISTS cluster_leases (
+    name        TEXT PRIMARY KEY,
+    replica_id  TEXT NOT NULL,
+    acquired_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    expires_at  TIMESTAMPTZ NOT NULL
+);
+
+COMMENT ON TABLE cluster_replicas IS
+    'Server processes sharing this database, refreshed every heartbeat and removed on clean shutdown.';
+COMMENT ON TABLE cluster_leases IS
+    'Leader leases. The holder renews before expires_at; any replica may take over an expired lease.';
@@ -0,0 +1,46 @@
+CREATE TABLE IF NOT EXISTS events (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id   UUID NOT NULL REFERENCES tenants(id),
+    type        TEXT NOT NULL,
+    dedupe_key  TEXT,
+    data        JSONB NOT NULL DEFAULT '{}'::jsonb,
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_events_dedupe
+    ON events(tenant_id, type, dedupe_key) WHERE dedupe_key IS NOT NULL;
+
+CREATE TABLE IF NOT EXISTS event_subscriptions (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id   UUID NOT NULL REFERENCES tenants(id),
+    url         TEXT NOT NULL,
+    event_types JSONB NOT NULL,
+    secret      TEXT NOT NULL,
+    created_by  UUID NOT NULL REFERENCES principals(id),
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_subscriptions_tenant
+    ON event_subscriptions(tenant_id);
+
+CREATE TABLE IF NOT EXISTS event_deliveries (
+    id              BIGSERIAL PRIMARY KEY,
+    subscription_id UUID NOT NULL REFERENCES event_subscriptions(id) ON DELETE CASCADE,
+    event_id        UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    status          TEXT NOT NULL DEFAULT 'pending'
+                    CHECK (status IN ('pending', 'delivered', 'failed')),
+    attempts        INTEGER NOT NULL DEFAULT 0,
+    response_status INTEGER,
+    last_error      TEXT NOT NULL DEFAULT '',
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    delivered_at    TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_deliveries_due
+    ON event_deliveries(next_attempt_at) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_event_deliveries_subscription
+    ON event_deliveries(subscription_id, id DESC);
+
+COMMENT ON TABLE event_deliveries IS
+    'Outbound webhook deliveries, one per event and matching subscription. Pending rows are retried with backoff until delivered or out of attempts.';
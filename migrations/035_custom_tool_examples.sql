@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS custom_tool_examples (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tool_id         UUID NOT NULL REFERENCES custom_tools(id) ON DELETE CASCADE,
+    name            TEXT NOT NULL,
+    description     TEXT NOT NULL DEFAULT '',
+    input           JSONB NOT NULL DEFAULT '{}'::jsonb,
+    expected_output JSONB,
+    created_by      UUID REFERENCES principals(id),
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (tool_id, name)
+);
+
+COMMENT ON TABLE custom_tool_examples IS
+    'Saved example calls for custom tools. input must satisfy the tool input schema; expected_output, when set, is compared with the output of a run.';
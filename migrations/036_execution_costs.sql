@@ -0,0 +1,14 @@
+ALTER TABLE case_steps
+    ADD COLUMN IF NOT EXISTS cost NUMERIC(18,6) NOT NULL DEFAULT 0;
+
+COMMENT ON COLUMN case_steps.cost IS
+    'Cost units the completed step consumed: executor-reported cost (e.g. LLM token pricing), the step config cost_per_call and _meta.cost_units in tool output.';
+
+ALTER TABLE cases
+    ADD COLUMN IF NOT EXISTS cost NUMERIC(18,6) NOT NULL DEFAULT 0;
+
+COMMENT ON COLUMN cases.cost IS
+    'Total cost units of the case''s completed steps, maintained on write.';
+
+CREATE INDEX IF NOT EXISTS idx_cases_workflow_created
+    ON cases(workflow_id, created_at);
@@ -0,0 +1,5 @@
+ALTER TABLE cases
+    ADD COLUMN IF NOT EXISTS correlation_id TEXT;
+
+COMMENT ON COLUMN cases.correlation_id IS
+    'Correlation ID of the request that created the case; attached to the logs and outgoing tool calls of its steps.';
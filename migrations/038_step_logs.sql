@@ -0,0 +1,5 @@
+ALTER TABLE case_steps
+    ADD COLUMN IF NOT EXISTS logs JSONB;
+
+COMMENT ON COLUMN case_steps.logs IS
+    'Most recent log lines written while the step last ran ({"lines": [...], "dropped": n}), kept when ACERYX_STEP_LOG_LINES is set.';
@@ -0,0 +1,48 @@
+ALTER TABLE workflows
+    ADD COLUMN IF NOT EXISTS required_approvals INTEGER NOT NULL DEFAULT 0 CHECK (required_approvals >= 0),
+    ADD COLUMN IF NOT EXISTS archived_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN workflows.required_approvals IS
+    'Approvals a draft needs in review, from principals other than its submitter, before it can be published. Zero publishes without review.';
+COMMENT ON COLUMN workflows.archived_at IS
+    'When the workflow was archived. Archived workflows have no published version and cannot be edited until unarchived.';
+
+CREATE TABLE IF NOT EXISTS workflow_reviews (
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id    UUID NOT NULL REFERENCES tenants(id),
+    workflow_id  UUID NOT NULL REFERENCES workflows(id) ON DELETE CASCADE,
+    version      INTEGER NOT NULL,
+    status       TEXT NOT NULL DEFAULT 'open'
+                 CHECK (status IN ('open', 'rejected', 'withdrawn', 'published')),
+    note         TEXT NOT NULL DEFAULT '',
+    submitted_by UUID NOT NULL REFERENCES principals(id),
+    submitted_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    closed_at    TIMESTAMPTZ
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_workflow_reviews_open
+    ON workflow_reviews(workflow_id) WHERE status = 'open';
+
+CREATE INDEX IF NOT EXISTS idx_workflow_reviews_workflow
+    ON workflow_reviews(workflow_id, submitted_at DESC);
+
+CREATE TABLE IF NOT EXISTS workflow_review_decisions (
+    review_id   UUID NOT NULL REFERENCES workflow_reviews(id) ON DELETE CASCADE,
+    reviewer_id UUID NOT NULL REFERENCES principals(id),
+    decision    TEXT NOT NULL CHECK (decision IN ('approved', 'rejected')),
+    comment     TEXT NOT NULL DEFAULT '',
+    decided_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (review_id, reviewer_id)
+);
+
+-- Roles that could already publish workflows can review them.
+INSERT INTO role_permissions (role_id, permission)
+SELECT role_id, 'workflows:review'
+FROM role_permissions
+WHERE permission = 'workflows:deploy'
+ON CONFLICT DO NOTHING;
+
+COMMENT ON TABLE workflow_reviews IS
+    'Drafts submitted for review. While a review is open the draft cannot be edited; publishing closes it.';
+COMMENT ON TABLE workflow_review_decisions IS
+    'One approve or reject decision per reviewer and review. A rejection closes the review.';
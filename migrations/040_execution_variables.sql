@@ -0,0 +1,18 @@
+ALTER TABLE cases
+    ADD COLUMN IF NOT EXISTS variables JSONB NOT NULL DEFAULT '{}'::jsonb;
+
+COMMENT ON COLUMN cases.variables IS
+    'Environment value overrides the case was started with; they take precedence over workflow overlays and environment variable sets.';
+
+CREATE TABLE IF NOT EXISTS environment_variables (
+    tenant_id   UUID NOT NULL REFERENCES tenants(id),
+    environment TEXT NOT NULL,
+    name        TEXT NOT NULL,
+    value       JSONB NOT NULL,
+    updated_by  UUID REFERENCES principals(id),
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (tenant_id, environment, name)
+);
+
+COMMENT ON TABLE environment_variables IS
+    'Tenant-wide values for {{env.*}} references, per environment. The default set applies to every environment; a named set applies where ACERYX_ENVIRONMENT matches it.';
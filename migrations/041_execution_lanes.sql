@@ -0,0 +1,6 @@
+ALTER TABLE cases
+    ADD COLUMN IF NOT EXISTS execution_lane TEXT NOT NULL DEFAULT 'standard'
+        CHECK (execution_lane IN ('interactive', 'standard', 'batch'));
+
+COMMENT ON COLUMN cases.execution_lane IS
+    'Priority lane the case''s steps run in: interactive (UI and designer runs), standard (webhooks, channels, API) or batch (bulk imports and backfills).';
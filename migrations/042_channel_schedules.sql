@@ -0,0 +1,21 @@
+ALTER TABLE channels DROP CONSTRAINT IF EXISTS channels_type_check;
+ALTER TABLE channels ADD CONSTRAINT channels_type_check
+    CHECK (type IN ('email', 'webhook', 'form', 'file_drop', 'plugin', 'schedule'));
+
+CREATE TABLE IF NOT EXISTS channel_schedules (
+    channel_id   UUID PRIMARY KEY REFERENCES channels(id),
+    tenant_id    UUID NOT NULL REFERENCES tenants(id),
+    cron         TEXT NOT NULL,
+    next_run_at  TIMESTAMPTZ,
+    last_run_at  TIMESTAMPTZ,
+    last_status  TEXT CHECK (last_status IN ('succeeded', 'failed', 'skipped')),
+    last_error   TEXT,
+    last_case_id UUID REFERENCES cases(id),
+    missed_runs  INTEGER NOT NULL DEFAULT 0,
+    updated_at   TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+COMMENT ON TABLE channel_schedules IS
+    'Progress of schedule channels. next_run_at is claimed (advanced) before a run starts, so restarts and replicas never fire a time twice.';
+COMMENT ON COLUMN channel_schedules.missed_runs IS
+    'Fire times passed during downtime and dropped by the channel''s missed_runs policy.';
@@ -0,0 +1,5 @@
+ALTER TABLE channel_schedules
+    ADD COLUMN IF NOT EXISTS timezone TEXT NOT NULL DEFAULT '';
+
+COMMENT ON COLUMN channel_schedules.timezone IS
+    'IANA timezone the cron expression is evaluated in; empty means UTC. Changing it restarts the schedule from now.';
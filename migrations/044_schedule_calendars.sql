@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS schedule_calendars (
+    id         UUID PRIMARY KEY,
+    tenant_id  UUID NOT NULL REFERENCES tenants(id),
+    name       TEXT NOT NULL,
+    dates      JSONB NOT NULL DEFAULT '[]'::jsonb,
+    weekdays   JSONB NOT NULL DEFAULT '[]'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_schedule_calendars_tenant ON schedule_calendars (tenant_id, name);
+
+COMMENT ON TABLE schedule_calendars IS
+    'Named sets of excluded days, such as public holidays, that schedule channels list in config.calendars.';
+COMMENT ON COLUMN schedule_calendars.dates IS
+    'Excluded days as [{"date": "YYYY-MM-DD", "name": ..., "yearly": bool}]. Yearly dates repeat on the same month and day.';
@@ -0,0 +1,39 @@
+CREATE TABLE IF NOT EXISTS workflow_slas (
+    workflow_id                UUID PRIMARY KEY REFERENCES workflows(id),
+    tenant_id                  UUID NOT NULL REFERENCES tenants(id),
+    max_duration_seconds       INTEGER NOT NULL DEFAULT 0 CHECK (max_duration_seconds >= 0),
+    schedule_tolerance_seconds INTEGER NOT NULL DEFAULT 0 CHECK (schedule_tolerance_seconds >= 0),
+    alerts                     JSONB NOT NULL DEFAULT '[]'::jsonb,
+    created_at                 TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at                 TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TABLE IF NOT EXISTS workflow_sla_breaches (
+    id             UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id      UUID NOT NULL REFERENCES tenants(id),
+    workflow_id    UUID NOT NULL REFERENCES workflows(id),
+    kind           TEXT NOT NULL CHECK (kind IN ('duration', 'schedule')),
+    case_id        UUID REFERENCES cases(id),
+    case_number    TEXT,
+    channel_id     UUID REFERENCES channels(id),
+    channel_name   TEXT,
+    scheduled_at   TIMESTAMPTZ,
+    limit_seconds  INTEGER NOT NULL,
+    actual_seconds INTEGER NOT NULL,
+    message        TEXT NOT NULL,
+    url            TEXT NOT NULL DEFAULT '',
+    alert_errors   JSONB,
+    detected_at    TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_workflow_sla_breaches_case
+    ON workflow_sla_breaches(case_id, kind) WHERE case_id IS NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_workflow_sla_breaches_schedule
+    ON workflow_sla_breaches(workflow_id, channel_id, scheduled_at) WHERE channel_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_workflow_sla_breaches_workflow
+    ON workflow_sla_breaches(workflow_id, detected_at DESC);
+
+COMMENT ON TABLE workflow_slas IS
+    'Per-workflow SLAs: the longest a case may run, and how late a schedule channel starting the workflow may fire. alerts are integration step configs run for each breach.';
+COMMENT ON TABLE workflow_sla_breaches IS
+    'SLA breaches found by the leader''s SLA monitor, once per case or per schedule channel and fire time. alert_errors maps failed alerts, by position, to their error.';
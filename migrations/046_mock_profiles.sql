@@ -0,0 +1,20 @@
+CREATE TABLE IF NOT EXISTS mock_profiles (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id   UUID NOT NULL REFERENCES tenants(id),
+    name        TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    passthrough BOOLEAN NOT NULL DEFAULT false,
+    tools       JSONB NOT NULL DEFAULT '[]'::jsonb,
+    created_by  UUID REFERENCES principals(id),
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (tenant_id, name)
+);
+
+ALTER TABLE cases
+    ADD COLUMN IF NOT EXISTS mock_profile TEXT;
+
+COMMENT ON TABLE mock_profiles IS
+    'Named sets of canned tool responses. tools lists, per protocol and tool name, responses picked by matching the call input; passthrough lets unmocked tools reach the real tool.';
+COMMENT ON COLUMN cases.mock_profile IS
+    'Mock profile answering the case''s tool calls, or NULL to call the real tools.';
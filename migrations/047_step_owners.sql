@@ -0,0 +1,17 @@
+ALTER TABLE cluster_replicas
+    ADD COLUMN IF NOT EXISTS instance_id UUID;
+
+ALTER TABLE case_steps
+    ADD COLUMN IF NOT EXISTS owner_instance UUID,
+    ADD COLUMN IF NOT EXISTS owned_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_case_steps_active_owner
+    ON case_steps (owner_instance)
+    WHERE state = 'active';
+
+COMMENT ON COLUMN cluster_replicas.instance_id IS
+    'Identifies the running process; changes on restart even when the replica ID is fixed.';
+COMMENT ON COLUMN case_steps.owner_instance IS
+    'instance_id of the process that activated or recovered the step. Recovery takes over active steps whose owner no longer heartbeats.';
+COMMENT ON COLUMN case_steps.owned_at IS
+    'When owner_instance last took the step.';
@@ -0,0 +1,28 @@
+CREATE TABLE IF NOT EXISTS case_create_jobs (
+    id          UUID PRIMARY KEY,
+    seq         BIGSERIAL,
+    tenant_id   UUID NOT NULL REFERENCES tenants(id),
+    created_by  UUID NOT NULL,
+    status      TEXT NOT NULL,
+    priority    INTEGER NOT NULL DEFAULT 0,
+    case_type   TEXT NOT NULL,
+    request     JSONB NOT NULL,
+    case_id     UUID,
+    case_number TEXT,
+    error       TEXT,
+    validation  JSONB,
+    enqueued_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    started_at  TIMESTAMPTZ,
+    finished_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_case_create_jobs_pending
+    ON case_create_jobs (priority DESC, seq)
+    WHERE status IN ('queued', 'running');
+
+CREATE INDEX IF NOT EXISTS idx_case_create_jobs_finished
+    ON case_create_jobs (finished_at)
+    WHERE finished_at IS NOT NULL;
+
+COMMENT ON TABLE case_create_jobs IS
+    'Asynchronous case creations. Workers on any replica claim queued jobs with SKIP LOCKED; finished jobs are kept for polling, then pruned.';
@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS case_step_items (
+    case_id    UUID NOT NULL REFERENCES cases(id) ON DELETE CASCADE,
+    step_id    TEXT NOT NULL,
+    item_index INTEGER NOT NULL,
+    item_hash  TEXT NOT NULL,
+    output     JSONB,
+    tokens     INTEGER NOT NULL DEFAULT 0,
+    cost       NUMERIC(18,6) NOT NULL DEFAULT 0,
+    PRIMARY KEY (case_id, step_id, item_index)
+);
+
+COMMENT ON TABLE case_step_items IS
+    'Completed items of a for_each step that has not completed yet. Retries reuse an item whose item_hash still matches instead of running it again; the rows are deleted when the step completes.';
@@ -0,0 +1,177 @@
+// Package embedded runs the Aceryx execution engine inside another Go
+// service, without the HTTP API or web UI. State lives in the same Postgres
+// schema the server uses, so the tenant and actor IDs passed in must exist
+// (create them with `aceryx seed` or through the server's admin API).
+//
+//	rt, err := embedded.Open(ctx, embedded.Config{DB: db, Migrate: true})
+//	rt.RegisterExecutor("score", scorer)
+//	rt.Subscribe(func(ctx context.Context, ev embedded.StepEvent) { ... })
+//	if err := rt.Start(ctx); err != nil { ... }
+//	defer rt.Shutdown(context.Background())
+//	_, err = rt.LoadWorkflow(ctx, tenantID, actorID, "loan-review", "loan", yamlSource)
+//	caseID, err := rt.Execute(ctx, tenantID, actorID, "loan", map[string]any{"amount": 5000})
+package embedded
+
+import (
+	"context"
+	"database/sql"
+	"errors"
+	"fmt"
+	"strings"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/cases"
+	"github.com/neural-chilli/aceryx/internal/engine"
+	"github.com/neural-chilli/aceryx/internal/expressions"
+	"github.com/neural-chilli/aceryx/internal/migrations"
+	"github.com/neural-chilli/aceryx/internal/workflows"
+)
+
+type (
+	// StepExecutor runs one step type. Register executors before Start.
+	StepExecutor = engine.StepExecutor
+	// StepResult is what a StepExecutor returns.
+	StepResult = engine.StepResult
+	// StepEvent reports a step starting, retrying or finishing.
+	StepEvent = engine.StepEvent
+	// WorkflowStatus summarises a case's progress.
+	WorkflowStatus = engine.WorkflowStatus
+	// CaseTypeSchema describes the fields a case type accepts.
+	CaseTypeSchema = cases.CaseTypeSchema
+)
+
+// ErrValidation wraps case data or workflow definitions that were rejected.
+var ErrValidation = errors.New("validation failed")
+
+// Config configures a Runtime. DB is required and must reach a Postgres
+// database; set Migrate to apply the embedded migrations on Open.
+type Config struct {
+	DB                    *sql.DB
+	Migrate               bool
+	Environment           string
+	DisableInputRecording bool
+}
+
+// Runtime is an embedded engine with the case and workflow services it
+// needs to load and execute workflows.
+type Runtime struct {
+	engine    *engine.Engine
+	caseTypes *cases.CaseTypeService
+	cases     *cases.CaseService
+	workflows *workflows.Service
+}
+
+// Open builds a Runtime. No steps run until Start.
+func Open(ctx context.Context, cfg Config) (*Runtime, error) {
+	if cfg.DB == nil {
+		return nil, fmt.Errorf("embedded: DB is required")
+	}
+	if cfg.Migrate {
+		if err := migrations.NewRunner(cfg.DB).Apply(ctx); err != nil {
+			return nil, fmt.Errorf("embedded: apply migrations: %w", err)
+		}
+	}
+	eng := engine.New(cfg.DB, expressions.NewEvaluator(), engine.Config{
+		Environment:           cfg.Environment,
+		DisableInputRecording: cfg.DisableInputRecording,
+	})
+	rt := &Runtime{
+		engine:    eng,
+		caseTypes: cases.NewCaseTypeService(cfg.DB),
+		cases:     cases.NewCaseService(cfg.DB, eng),
+		workflows: workflows.NewService(cfg.DB),
+	}
+	rt.workflows.SetCaseCreator(rt.cases)
+	eng.RegisterExecutor("aggregate", engine.NewAggregateExecutor(cfg.DB))
+	timerExecutor := engine.NewTimerExecutor(cfg.DB)
+	for _, stepType := range []string{"timer", "delay", "wait_until"} {
+		eng.RegisterExecutor(stepType, timerExecutor)
+	}
+	return rt, nil
+}
+
+// RegisterExecutor makes a step type available to workflows. Aggregate and
+// timer steps are built in; integrations, human tasks, agents and the other
+// server-side step types need the embedder's own executors.
+func (r *Runtime) RegisterExecutor(stepType string, executor StepExecutor) {
+	r.engine.RegisterExecutor(stepType, executor)
+}
+
+// Subscribe calls hook for every step event. Hooks run synchronously on the
+// engine's workers, so they should hand slow work off.
+func (r *Runtime) Subscribe(hook func(ctx context.Context, event StepEvent)) {
+	r.engine.AddStepEventHook(hook)
+}
+
+// Start resumes steps left active by a previous process and starts the SLA
+// and timer monitors and the cancel watcher, which stop when ctx is done.
+func (r *Runtime) Start(ctx context.Context) error {
+	if err := r.engine.Recover(ctx); err != nil {
+		return fmt.Errorf("embedded: recover steps: %w", err)
+	}
+	go r.engine.StartSLAMonitor(ctx)
+	go r.engine.StartTimerMonitor(ctx)
+	go r.engine.StartCancelWatcher(ctx)
+	return nil
+}
+
+// Shutdown stops starting new steps and waits for running ones until ctx is
+// done. It does not close the DB.
+func (r *Runtime) Shutdown(ctx context.Context) error {
+	return r.engine.Shutdown(ctx)
+}
+
+// RegisterCaseType creates a case type, or a new version of an existing one.
+func (r *Runtime) RegisterCaseType(ctx context.Context, tenantID, actorID uuid.UUID, name string, schema CaseTypeSchema) error {
+	_, validation, err := r.caseTypes.RegisterCaseType(ctx, tenantID, actorID, name, schema)
+	if err != nil {
+		return err
+	}
+	if len(validation) > 0 {
+		return fmt.Errorf("%w: %s", ErrValidation, validation[0].Message)
+	}
+	return nil
+}
+
+// LoadWorkflow creates a workflow for caseType from YAML and publishes it,
+// so cases of that type run it. It returns the workflow ID.
+func (r *Runtime) LoadWorkflow(ctx context.Context, tenantID, actorID uuid.UUID, name, caseType, yamlSource string) (uuid.UUID, error) {
+	workflow, err := r.workflows.Create(ctx, tenantID, actorID, workflows.CreateRequest{Name: name, CaseTypeID: caseType})
+	if err != nil {
+		return uuid.Nil, err
+	}
+	if err := r.workflows.ImportYAMLDraft(ctx, tenantID, actorID, workflow.ID, yamlSource); err != nil {
+		return uuid.Nil, fmt.Errorf("%w: %v", ErrValidation, err)
+	}
+	if err := r.workflows.PublishDraft(ctx, tenantID, actorID, workflow.ID); err != nil {
+		var publishErrs *workflows.PublishValidationErrors
+		if errors.As(err, &publishErrs) || strings.HasPrefix(err.Error(), "invalid workflow ast:") {
+			return uuid.Nil, fmt.Errorf("%w: %v", ErrValidation, err)
+		}
+		return uuid.Nil, err
+	}
+	return workflow.ID, nil
+}
+
+// Execute starts a case of caseType with data and returns its ID. Steps run
+// in the background; follow them with Subscribe or Status.
+func (r *Runtime) Execute(ctx context.Context, tenantID, actorID uuid.UUID, caseType string, data map[string]any) (uuid.UUID, error) {
+	created, validation, err := r.cases.CreateCase(ctx, tenantID, actorID, cases.CreateCaseRequest{CaseType: caseType, Data: data})
+	if err != nil {
+		return uuid.Nil, err
+	}
+	if len(validation) > 0 {
+		return uuid.Nil, fmt.Errorf("%w: %s", ErrValidation, validation[0].Message)
+	}
+	return created.ID, nil
+}
+
+// Status reports a case's progress.
+func (r *Runtime) Status(ctx context.Context, tenantID, caseID uuid.UUID) (WorkflowStatus, error) {
+	return r.engine.GetStatus(ctx, tenantID, caseID)
+}
+
+// Cancel stops a case's remaining steps.
+func (r *Runtime) Cancel(ctx context.Context, caseID, actorID uuid.UUID, reason string) error {
+	return r.engine.CancelCase(ctx, caseID, actorID, reason)
+}
@@ -0,0 +1,12 @@
+package embedded
+
+import (
+	"context"
+	"testing"
+)
+
+func TestOpenRequiresDB(t *testing.T) {
+	if _, err := Open(context.Background(), Config{}); err == nil {
+		t.Fatal("expected an error without a DB")
+	}
+}
@@ -0,0 +1,70 @@
+// Code generated by `aceryx client generate`. DO NOT EDIT.
+// Aceryx API — API version 0.0.1, generator 0.0.1.
+
+export const API_VERSION = '0.0.1'
+
+export interface Case {
+  'id': string
+  'priority'?: number
+}
+
+export class ApiError extends Error {
+  constructor(
+    public readonly status: number,
+    public readonly body: unknown,
+  ) {
+    super(`request failed with status ${status}`)
+  }
+}
+
+export interface ClientOptions {
+  baseUrl: string
+  token?: string
+  fetch?: typeof fetch
+}
+
+export class AceryxClient {
+  private readonly baseUrl: string
+  private readonly token?: string
+  private readonly fetchImpl: typeof fetch
+
+  constructor(options: ClientOptions) {
+    this.baseUrl = options.baseUrl.replace(/\/+$/, '')
+    this.token = options.token
+    this.fetchImpl = options.fetch ?? fetch
+  }
+
+  private async request<T>(method: string, path: string, query?: Record<string, unknown>, body?: unknown): Promise<T> {
+    const url = new URL(this.baseUrl + path)
+    for (const [key, value] of Object.entries(query ?? {})) {
+      if (value !== undefined && value !== null) url.searchParams.set(key, String(value))
+    }
+    const headers: Record<string, string> = { Accept: 'application/json' }
+    if (body !== undefined) headers['Content-Type'] = 'application/json'
+    if (this.token) headers.Authorization = `Bearer ${this.token}`
+    const res = await this.fetchImpl(url.toString(), {
+      method,
+      headers,
+      body: body === undefined ? undefined : JSON.stringify(body),
+    })
+    const text = await res.text()
+    const parsed = text ? JSON.parse(text) : undefined
+    if (!res.ok) throw new ApiError(res.status, parsed)
+    return parsed as T
+  }
+
+  /** GET /api/v1/cases */
+  async listCases(query: { 'status'?: string; } = {}): Promise<Case[]> {
+    return this.request<Case[]>('GET', `/api/v1/cases`, query, undefined)
+  }
+
+  /** POST /api/v1/cases */
+  async createCase(body: Case): Promise<unknown> {
+    return this.request<unknown>('POST', `/api/v1/cases`, undefined, body)
+  }
+
+  /** GET /api/v1/cases/{id} */
+  async getApiV1CasesById(id: string): Promise<Case> {
+    return this.request<Case>('GET', `/api/v1/cases/{id}`.replace('{id}', encodeURIComponent(String(id))), undefined, undefined)
+  }
+}
@@ -0,0 +1,118 @@
+package integration
+
+import (
+	"context"
+	"errors"
+	"sync"
+	"testing"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/cases"
+)
+
+type recordingCaseCreator struct {
+	mu    sync.Mutex
+	order []string
+}
+
+func (c *recordingCaseCreator) CreateCase(_ context.Context, _, _ uuid.UUID, req cases.CreateCaseRequest) (cases.Case, []cases.ValidationError, error) {
+	c.mu.Lock()
+	c.order = append(c.order, req.CaseType)
+	c.mu.Unlock()
+	switch req.CaseType {
+	case "broken":
+		return cases.Case{}, nil, errors.New("boom")
+	case "invalid":
+		return cases.Case{}, []cases.ValidationError{{Field: "amount", Message: "required"}}, nil
+	}
+	return cases.Case{ID: uuid.New(), CaseNumber: "C-" + req.CaseType}, nil, nil
+}
+
+func waitForCreateJob(t *testing.T, ctx context.Context, q *cases.CreateQueue, tenantID, id uuid.UUID) cases.CreateJob {
+	t.Helper()
+	var job cases.CreateJob
+	waitForCondition(t, 5*time.Second, 20*time.Millisecond, func() bool {
+		var err error
+		job, err = q.Get(ctx, tenantID, id)
+		return err == nil && (job.Status == cases.JobCompleted || job.Status == cases.JobFailed)
+	}, "case creation job did not finish")
+	return job
+}
+
+func TestCreateQueueIntegration_PersistsJobsAndRunsByPriority(t *testing.T) {
+	ctx := context.Background()
+	db, cleanup := setupPostgresWithMigrations(t)
+	defer cleanup()
+	tenantID, principalID := seedTenantAndPrincipal(t, ctx, db, "case-queue")
+
+	// Jobs enqueued on one replica are run by the workers of another.
+	creator := &recordingCaseCreator{}
+	enqueuer := cases.NewCreateQueue(db, creator, cases.CreateQueueConfig{Capacity: 3})
+	var last cases.CreateJob
+	for _, req := range []cases.CreateCaseRequest{
+		{CaseType: "low", Priority: 0},
+		{CaseType: "high", Priority: 5},
+		{CaseType: "low2", Priority: 0},
+	} {
+		job, err := enqueuer.Enqueue(ctx, tenantID, principalID, req)
+		if err != nil {
+			t.Fatalf("enqueue %s: %v", req.CaseType, err)
+		}
+		last = job
+	}
+	if _, err := enqueuer.Enqueue(ctx, tenantID, principalID, cases.CreateCaseRequest{CaseType: "overflow"}); !errors.Is(err, cases.ErrQueueFull) {
+		t.Fatalf("expected ErrQueueFull, got %v", err)
+	}
+	otherTenantID, otherPrincipalID := seedTenantAndPrincipal(t, ctx, db, "case-queue-other")
+	if _, err := enqueuer.Enqueue(ctx, otherTenantID, otherPrincipalID, cases.CreateCaseRequest{CaseType: "other"}); err != nil {
+		t.Fatalf("a full queue for one tenant must not turn away another: %v", err)
+	}
+	if _, err := db.ExecContext(ctx, `DELETE FROM case_create_jobs WHERE tenant_id = $1`, otherTenantID); err != nil {
+		t.Fatalf("drop other tenant's job: %v", err)
+	}
+	if _, err := enqueuer.Get(ctx, uuid.New(), last.ID); !errors.Is(err, cases.ErrJobNotFound) {
+		t.Fatalf("job must not be visible to another tenant, got %v", err)
+	}
+
+	runCtx, cancel := context.WithCancel(ctx)
+	defer cancel()
+	worker := cases.NewCreateQueue(db, creator, cases.CreateQueueConfig{Workers: 1, PollInterval: 20 * time.Millisecond})
+	worker.Start(runCtx)
+	done := waitForCreateJob(t, ctx, worker, tenantID, last.ID)
+	if done.Status != cases.JobCompleted || done.CaseID == nil || done.CaseNumber != "C-low2" || done.FinishedAt == nil {
+		t.Fatalf("unexpected finished job %+v", done)
+	}
+	creator.mu.Lock()
+	defer creator.mu.Unlock()
+	if got := creator.order; len(got) != 3 || got[0] != "high" || got[1] != "low" || got[2] != "low2" {
+		t.Fatalf("unexpected execution order %v", got)
+	}
+}
+
+func TestCreateQueueIntegration_RecordsFailures(t *testing.T) {
+	ctx := context.Background()
+	db, cleanup := setupPostgresWithMigrations(t)
+	defer cleanup()
+	tenantID, principalID := seedTenantAndPrincipal(t, ctx, db, "case-queue-failures")
+
+	q := cases.NewCreateQueue(db, &recordingCaseCreator{}, cases.CreateQueueConfig{Workers: 2, PollInterval: 20 * time.Millisecond})
+	runCtx, cancel := context.WithCancel(ctx)
+	defer cancel()
+	q.Start(runCtx)
+
+	broken, err := q.Enqueue(ctx, tenantID, principalID, cases.CreateCaseRequest{CaseType: "broken"})
+	if err != nil {
+		t.Fatalf("enqueue broken: %v", err)
+	}
+	invalid, err := q.Enqueue(ctx, tenantID, principalID, cases.CreateCaseRequest{CaseType: "invalid"})
+	if err != nil {
+		t.Fatalf("enqueue invalid: %v", err)
+	}
+	if job := waitForCreateJob(t, ctx, q, tenantID, broken.ID); job.Status != cases.JobFailed || job.Error != "boom" {
+		t.Fatalf("unexpected broken job %+v", job)
+	}
+	if job := waitForCreateJob(t, ctx, q, tenantID, invalid.ID); job.Status != cases.JobFailed || len(job.Validation) != 1 {
+		t.Fatalf("unexpected invalid job %+v", job)
+	}
+}
@@ -173,4 +173,8 @@ func (s *stubCaseEngine) CancelCase(_ context.Context, _ uuid.UUID, _ uuid.UUID,
 	return nil
 }
 
+func (s *stubCaseEngine) ResumeCase(_ context.Context, _, _ uuid.UUID, stepIDs []string) ([]string, error) {
+	return stepIDs, nil
+}
+
 func intPtr(v int) *int { return &v }
@@ -3,10 +3,12 @@ package integration
 import (
 	"bytes"
 	"context"
+	"encoding/json"
 	"net/http"
 	"net/http/httptest"
 	"testing"
 
+	"github.com/google/uuid"
 	"github.com/neural-chilli/aceryx/api"
 	internalmigrations "github.com/neural-chilli/aceryx/internal/migrations"
 )
@@ -48,3 +50,55 @@ func TestCasesHTTPIntegration_CloseAndCancelRejectInvalidJSON(t *testing.T) {
 		}
 	})
 }
+
+func TestCasesHTTPIntegration_ReplayRespectsTokenWorkflowScope(t *testing.T) {
+	ctx := context.Background()
+	db, cleanup := setupPostgresWithMigrations(t)
+	defer cleanup()
+
+	if err := internalmigrations.SeedDefaultData(ctx, db); err != nil {
+		t.Fatalf("seed default data: %v", err)
+	}
+
+	tenantID, adminID, adminEmail := fetchDefaultAdmin(t, ctx, db)
+	allowedCaseID := seedVaultCase(t, ctx, db, tenantID, adminID, "replay_scope_allowed")
+	otherCaseID := seedVaultCase(t, ctx, db, tenantID, adminID, "replay_scope_other")
+	var allowedWorkflowID uuid.UUID
+	if err := db.QueryRowContext(ctx, `SELECT workflow_id FROM cases WHERE id = $1`, allowedCaseID).Scan(&allowedWorkflowID); err != nil {
+		t.Fatalf("load case workflow: %v", err)
+	}
+
+	router := api.NewRouterWithServices(db, nil)
+	login := loginViaAPI(t, router, tenantID, adminEmail, "admin")
+
+	body, _ := json.Marshal(map[string]any{"name": "replay-scope", "permissions": []string{"cases:create"}, "workflow_ids": []uuid.UUID{allowedWorkflowID}})
+	req := httptest.NewRequest(http.MethodPost, "/tokens", bytes.NewReader(body))
+	req.Header.Set("Authorization", "Bearer "+login.Token)
+	req.Header.Set("Content-Type", "application/json")
+	w := httptest.NewRecorder()
+	router.ServeHTTP(w, req)
+	if w.Code != http.StatusCreated {
+		t.Fatalf("mint service token status=%d body=%s", w.Code, w.Body.String())
+	}
+	var token struct {
+		Token string `json:"token"`
+	}
+	if err := json.Unmarshal(w.Body.Bytes(), &token); err != nil || token.Token == "" {
+		t.Fatalf("decode service token: %v body=%s", err, w.Body.String())
+	}
+
+	replay := func(caseID uuid.UUID) *httptest.ResponseRecorder {
+		req := httptest.NewRequest(http.MethodPost, "/api/v1/executions/"+caseID.String()+"/replay", bytes.NewBufferString(`{"dry_run":true}`))
+		req.Header.Set("Authorization", "Bearer "+token.Token)
+		req.Header.Set("Content-Type", "application/json")
+		w := httptest.NewRecorder()
+		router.ServeHTTP(w, req)
+		return w
+	}
+	if w := replay(otherCaseID); w.Code != http.StatusForbidden {
+		t.Fatalf("expected 403 replaying a case outside the token's workflows, got status=%d body=%s", w.Code, w.Body.String())
+	}
+	if w := replay(allowedCaseID); w.Code == http.StatusForbidden {
+		t.Fatalf("expected the token's own workflow to pass the scope check, got status=%d body=%s", w.Code, w.Body.String())
+	}
+}
@@ -32,7 +32,7 @@ import (
 )
 
 func TestConnectorsIntegration_HTTPConnector(t *testing.T) {
-	conn := httpconn.New()
+	conn := httpconn.NewWithEgress(connectors.EgressPolicy{AllowPrivate: true})
 	action, ok := findAction(conn.Actions(), "request")
 	if !ok {
 		t.Fatal("http connector request action not found")
@@ -220,6 +220,34 @@ func TestConnectorsIntegration_EmailSlackTeamsGChatJira(t *testing.T) {
 		}
 	})
 
+	t.Run("email selects locale template variant", func(t *testing.T) {
+		conn := emailconn.New()
+		action, _ := findAction(conn.Actions(), "send")
+		out, err := action.Execute(ctx, map[string]string{
+			"smtp_host": "mock",
+			"smtp_port": "25",
+			"from":      "noreply@example.com",
+			"username":  "u",
+			"password":  "p",
+		}, map[string]any{
+			"to":           "user@example.com",
+			"template":     "task_assigned",
+			"locale":       "fr-CA",
+			"company_name": "Acme Lending",
+			"case_number":  "LA-000002",
+		})
+		if err != nil {
+			t.Fatalf("email send: %v", err)
+		}
+		if subject, _ := out["subject"].(string); subject != "Nouvelle tâche pour le dossier LA-000002" {
+			t.Fatalf("expected localized subject, got %q", subject)
+		}
+		mimeBody, _ := out["mime"].(string)
+		if !strings.Contains(mimeBody, "nouvelle tâche") {
+			t.Fatalf("expected french content in email output, got %s", mimeBody)
+		}
+	})
+
 	t.Run("slack send message", func(t *testing.T) {
 		srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
 			_, _ = w.Write([]byte(`{"ok":true,"ts":"1.2"}`))
@@ -349,7 +377,7 @@ VALUES ($1, 'offer_letter_v2', 1, 'active', $2::jsonb, $3)
 	}
 
 	reg := connectors.NewRegistry()
-	reg.Register(httpconn.New())
+	reg.Register(httpconn.NewWithEgress(connectors.EgressPolicy{AllowPrivate: true}))
 	exec := connectors.NewExecutor(db, reg, connectors.NewChainedSecretStore(connectors.NewDBSecretStore(db)))
 	en := engine.New(db, expressions.NewEvaluator(), engine.Config{})
 	en.RegisterExecutor("integration", exec)
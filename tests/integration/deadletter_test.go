@@ -0,0 +1,103 @@
+package integration
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"testing"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/cases"
+	"github.com/neural-chilli/aceryx/internal/deadletter"
+	"github.com/neural-chilli/aceryx/internal/engine"
+)
+
+type deadLetterResumer struct {
+	calls int
+	err   error
+}
+
+func (r *deadLetterResumer) ResumeCase(_ context.Context, _, _ uuid.UUID, stepIDs []string) ([]string, error) {
+	r.calls++
+	if r.err != nil {
+		return nil, r.err
+	}
+	return stepIDs, nil
+}
+
+func TestDeadLetterIntegration_RecordListAndRequeue(t *testing.T) {
+	ctx := context.Background()
+	db, cleanup := setupPostgresWithMigrations(t)
+	defer cleanup()
+
+	fx := newFixtures(t, ctx, db).
+		WithTenant("dead-letters").
+		WithCaseType("loan_application", testCaseSchema()).
+		WithPublishedWorkflow(engine.WorkflowAST{Steps: []engine.WorkflowStep{
+			{ID: "score", Type: "integration", Config: json.RawMessage(`{"connector":"http","action":"request"}`)},
+		}}).
+		Build()
+	created, validation, err := cases.NewCaseService(db, &stubCaseEngine{}).CreateCase(ctx, fx.TenantID, fx.PrincipalID, cases.CreateCaseRequest{
+		CaseType: fx.CaseType,
+		Data: map[string]interface{}{
+			"applicant": map[string]interface{}{"company_name": "Acme Ltd"},
+			"loan":      map[string]interface{}{"amount": 50000.0},
+		},
+	})
+	if err != nil || len(validation) > 0 {
+		t.Fatalf("create case: %v %+v", err, validation)
+	}
+
+	resumer := &deadLetterResumer{}
+	store := deadletter.NewStore(db, resumer)
+	entry, err := store.Record(ctx, engine.StepFailure{CaseID: created.ID, StepID: "score", StepType: "integration", Attempts: 3, Error: "timeout"})
+	if err != nil {
+		t.Fatalf("record dead letter: %v", err)
+	}
+	var input map[string]any
+	if err := json.Unmarshal(entry.Input, &input); err != nil || input["connector"] != "http" {
+		t.Fatalf("expected the step config as input, got %s (%v)", entry.Input, err)
+	}
+	if entry.TenantID != fx.TenantID || entry.Status != deadletter.StatusOpen || entry.CaseNumber != created.CaseNumber {
+		t.Fatalf("unexpected recorded entry %+v", entry)
+	}
+	var recordedCtx map[string]any
+	if err := json.Unmarshal(entry.Context, &recordedCtx); err != nil || recordedCtx["case"] == nil || recordedCtx["steps"] == nil {
+		t.Fatalf("expected case data and step results in the context, got %s (%v)", entry.Context, err)
+	}
+
+	listed, err := store.List(ctx, fx.TenantID, deadletter.StatusOpen, 0)
+	if err != nil || len(listed) != 1 || listed[0].ID != entry.ID || listed[0].CaseNumber != created.CaseNumber {
+		t.Fatalf("unexpected open dead letters %+v (%v)", listed, err)
+	}
+	if other, err := store.List(ctx, uuid.New(), "", 0); err != nil || len(other) != 0 {
+		t.Fatalf("dead letters must not be visible to another tenant, got %+v (%v)", other, err)
+	}
+
+	// A failed resume leaves the entry open so it can be requeued again.
+	resumer.err = engine.ErrNothingToResume
+	if _, err := store.Requeue(ctx, fx.TenantID, entry.ID, fx.PrincipalID); !errors.Is(err, engine.ErrNothingToResume) {
+		t.Fatalf("expected ErrNothingToResume, got %v", err)
+	}
+	if listed, err := store.List(ctx, fx.TenantID, deadletter.StatusOpen, 0); err != nil || len(listed) != 1 {
+		t.Fatalf("expected the entry to be reopened, got %+v (%v)", listed, err)
+	}
+
+	resumer.err = nil
+	requeued, err := store.Requeue(ctx, fx.TenantID, entry.ID, fx.PrincipalID)
+	if err != nil {
+		t.Fatalf("requeue dead letter: %v", err)
+	}
+	if requeued.Status != deadletter.StatusRequeued || requeued.RequeuedBy == nil || *requeued.RequeuedBy != fx.PrincipalID {
+		t.Fatalf("unexpected requeued entry %+v", requeued)
+	}
+	if _, err := store.Requeue(ctx, fx.TenantID, entry.ID, fx.PrincipalID); !errors.Is(err, deadletter.ErrAlreadyRequeued) {
+		t.Fatalf("expected ErrAlreadyRequeued, got %v", err)
+	}
+	if _, err := store.Requeue(ctx, uuid.New(), entry.ID, fx.PrincipalID); !errors.Is(err, deadletter.ErrNotFound) {
+		t.Fatalf("expected ErrNotFound for another tenant, got %v", err)
+	}
+	if resumer.calls != 2 {
+		t.Fatalf("expected two resume attempts, got %d", resumer.calls)
+	}
+}
@@ -166,6 +166,55 @@ func TestEngineIntegration_CancellationStopsProgress(t *testing.T) {
 	}
 }
 
+type blockingExecutor struct {
+	started chan struct{}
+	aborted chan error
+}
+
+func (b blockingExecutor) Execute(ctx context.Context, _ uuid.UUID, _ string, _ json.RawMessage) (*engine.StepResult, error) {
+	close(b.started)
+	<-ctx.Done()
+	b.aborted <- context.Cause(ctx)
+	return nil, ctx.Err()
+}
+
+func TestEngineIntegration_CancellationAbortsStepsOnOtherReplicas(t *testing.T) {
+	ctx := context.Background()
+	db, cleanup := setupPostgresWithMigrations(t)
+	defer cleanup()
+
+	ast := engine.WorkflowAST{Steps: []engine.WorkflowStep{{ID: "slow", Type: "integration"}}}
+	caseID := seedEngineCase(t, ctx, db, ast)
+
+	runner := engine.New(db, expressions.NewEvaluator(), engine.Config{})
+	exec := blockingExecutor{started: make(chan struct{}), aborted: make(chan error, 1)}
+	runner.RegisterExecutor("integration", exec)
+	watchCtx, stopWatch := context.WithCancel(ctx)
+	defer stopWatch()
+	go runner.StartCancelWatcher(watchCtx)
+	if err := runner.EvaluateDAG(ctx, caseID); err != nil {
+		t.Fatalf("evaluate dag: %v", err)
+	}
+	select {
+	case <-exec.started:
+	case <-time.After(5 * time.Second):
+		t.Fatal("step did not start")
+	}
+
+	other := engine.New(db, expressions.NewEvaluator(), engine.Config{})
+	if err := other.CancelCase(ctx, caseID, uuid.New(), "user requested"); err != nil {
+		t.Fatalf("cancel case: %v", err)
+	}
+	select {
+	case cause := <-exec.aborted:
+		if !errors.Is(cause, engine.ErrCaseCancelled) {
+			t.Fatalf("expected the step to be aborted by the cancellation, got %v", cause)
+		}
+	case <-time.After(engine.CancelPollInterval + 5*time.Second):
+		t.Fatal("step on the other replica was not aborted")
+	}
+}
+
 func TestEngineIntegration_ErrorPolicyRetriesAndExhausts(t *testing.T) {
 	ctx := context.Background()
 	db, cleanup := setupPostgresWithMigrations(t)
@@ -197,6 +246,51 @@ func TestEngineIntegration_ErrorPolicyRetriesAndExhausts(t *testing.T) {
 	waitForStepState(t, ctx, db, caseID, "unstable", engine.StateSkipped)
 }
 
+func TestEngineIntegration_ResumeRerunsOnlyFailedSteps(t *testing.T) {
+	ctx := context.Background()
+	db, cleanup := setupPostgresWithMigrations(t)
+	defer cleanup()
+
+	ast := engine.WorkflowAST{Steps: []engine.WorkflowStep{
+		{ID: "charge", Type: "integration", ErrorPolicy: engine.ErrorPolicy{MaxAttempts: 1}},
+		{ID: "notify", Type: "integration", DependsOn: []string{"charge"}, ErrorPolicy: engine.ErrorPolicy{MaxAttempts: 1}},
+		{ID: "close", Type: "rule", DependsOn: []string{"notify"}},
+	}}
+	caseID := seedEngineCase(t, ctx, db, ast)
+	en := engine.New(db, expressions.NewEvaluator(), engine.Config{})
+	// charge has a single result: running it twice would fail the step.
+	en.RegisterExecutor("integration", engine.NewMockExecutor(map[string][]engine.MockExecution{
+		"charge": {{Result: &engine.StepResult{Output: json.RawMessage(`{"charged":true}`)}}},
+		"notify": {
+			{Err: errors.New("smtp down")},
+			{Result: &engine.StepResult{Output: json.RawMessage(`{"sent":true}`)}},
+		},
+	}))
+	en.RegisterExecutor("rule", engine.NewMockExecutor(map[string][]engine.MockExecution{
+		"close": {{Result: &engine.StepResult{Output: json.RawMessage(`{"ok":true}`)}}},
+	}))
+
+	if err := en.EvaluateDAG(ctx, caseID); err != nil {
+		t.Fatalf("evaluate dag: %v", err)
+	}
+	waitForStepState(t, ctx, db, caseID, "notify", engine.StateFailed)
+
+	resumed, err := en.ResumeCase(ctx, caseID, uuid.New(), nil)
+	if err != nil {
+		t.Fatalf("resume case: %v", err)
+	}
+	if len(resumed) != 1 || resumed[0] != "notify" {
+		t.Fatalf("expected only notify to be resumed, got %v", resumed)
+	}
+	waitForStepState(t, ctx, db, caseID, "notify", engine.StateCompleted)
+	waitForStepState(t, ctx, db, caseID, "close", engine.StateCompleted)
+	waitForStepState(t, ctx, db, caseID, "charge", engine.StateCompleted)
+
+	if _, err := en.ResumeCase(ctx, caseID, uuid.New(), nil); !errors.Is(err, engine.ErrNothingToResume) {
+		t.Fatalf("expected ErrNothingToResume, got %v", err)
+	}
+}
+
 func TestEngineIntegration_SLAEscalation(t *testing.T) {
 	ctx := context.Background()
 	db, cleanup := setupPostgresWithMigrations(t)
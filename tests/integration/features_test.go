@@ -0,0 +1,47 @@
+package integration
+
+import (
+	"context"
+	"testing"
+
+	"github.com/neural-chilli/aceryx/internal/features"
+)
+
+func TestFeatureOverridesAreAudited(t *testing.T) {
+	ctx := context.Background()
+	db, cleanup := setupPostgresWithMigrations(t)
+	defer cleanup()
+	tenantID, principalID := seedTenantAndPrincipal(t, ctx, db, "feature-audit")
+
+	svc := features.NewService(features.NewPostgresStore(db), nil)
+	off := false
+	key := features.StepFlag("plugin")
+	if _, err := svc.Set(ctx, tenantID, key, &off, principalID); err != nil {
+		t.Fatalf("disable feature: %v", err)
+	}
+	if _, err := svc.Set(ctx, tenantID, key, nil, principalID); err != nil {
+		t.Fatalf("clear feature override: %v", err)
+	}
+
+	rows, err := db.QueryContext(ctx, `
+SELECT data->>'key', COALESCE(data->>'enabled', 'null')
+FROM auth_events
+WHERE tenant_id = $1 AND principal_id = $2 AND event_type = 'feature_flag_changed'
+ORDER BY created_at, id
+`, tenantID, principalID)
+	if err != nil {
+		t.Fatalf("query feature flag events: %v", err)
+	}
+	defer func() { _ = rows.Close() }()
+	var got []string
+	for rows.Next() {
+		var eventKey, enabled string
+		if err := rows.Scan(&eventKey, &enabled); err != nil {
+			t.Fatalf("scan feature flag event: %v", err)
+		}
+		got = append(got, eventKey+"="+enabled)
+	}
+	if len(got) != 2 || got[0] != key+"=false" || got[1] != key+"=null" {
+		t.Fatalf("expected a disable and a clear event for %s, got %v", key, got)
+	}
+}
@@ -14,7 +14,6 @@ import (
 	"github.com/neural-chilli/aceryx/api"
 	internalmigrations "github.com/neural-chilli/aceryx/internal/migrations"
 	"github.com/neural-chilli/aceryx/internal/rbac"
-	"golang.org/x/crypto/bcrypt"
 )
 
 func TestRBACIntegration_PrincipalAndAuthFlows(t *testing.T) {
@@ -54,8 +53,8 @@ func TestRBACIntegration_PrincipalAndAuthFlows(t *testing.T) {
 	if passwordHash == "Passw0rd" {
 		t.Fatal("password hash should not store plaintext")
 	}
-	if err := bcrypt.CompareHashAndPassword([]byte(passwordHash), []byte("Passw0rd")); err != nil {
-		t.Fatalf("stored password hash does not match password: %v", err)
+	if !strings.HasPrefix(passwordHash, "$argon2id$") || !rbac.VerifyPassword(passwordHash, "Passw0rd") {
+		t.Fatalf("stored password hash is not an argon2id hash of the password: %q", passwordHash)
 	}
 
 	agent, apiKey, err := principalSvc.CreatePrincipal(ctx, tenantID, rbac.CreatePrincipalRequest{
@@ -0,0 +1,103 @@
+package integration
+
+import (
+	"context"
+	"errors"
+	"testing"
+
+	"github.com/google/uuid"
+	"github.com/neural-chilli/aceryx/internal/cases"
+	"github.com/neural-chilli/aceryx/internal/workflows"
+)
+
+func TestWorkflowSharingAndOwnershipTransfer(t *testing.T) {
+	ctx := context.Background()
+	db, cleanup := setupPostgresWithMigrations(t)
+	defer cleanup()
+
+	tenantID, ownerID := seedTenantAndPrincipal(t, ctx, db, "sharing")
+	_, workflowID := seedMinimalCaseTypeAndWorkflow(t, ctx, db, tenantID, ownerID)
+	var colleagueID, roleID uuid.UUID
+	mustQueryRow := func(dest *uuid.UUID, query string, args ...any) {
+		t.Helper()
+		if err := db.QueryRowContext(ctx, query, args...).Scan(dest); err != nil {
+			t.Fatalf("seed: %v", err)
+		}
+	}
+	mustQueryRow(&colleagueID, `INSERT INTO principals (tenant_id, type, name, email, status) VALUES ($1, 'human', 'Colleague', 'colleague@example.com', 'active') RETURNING id`, tenantID)
+	mustQueryRow(&roleID, `INSERT INTO roles (tenant_id, name) VALUES ($1, 'auditors') RETURNING id`, tenantID)
+	mustExec(t, ctx, db, `INSERT INTO principal_roles (principal_id, role_id) VALUES ($1, $2)`, colleagueID, roleID)
+
+	svc := workflows.NewService(db)
+	perms := func(principalID uuid.UUID) workflows.Permissions {
+		t.Helper()
+		p, err := svc.WorkflowPermissions(ctx, tenantID, workflowID, principalID, workflows.TenantAccess{})
+		if err != nil {
+			t.Fatalf("permissions: %v", err)
+		}
+		return p
+	}
+
+	if p := perms(ownerID); !p.CanView || !p.CanEdit || !p.CanExecute || !p.CanShare {
+		t.Fatalf("owner should have every permission, got %+v", p)
+	}
+	if p := perms(colleagueID); p.CanView {
+		t.Fatalf("colleague should not see an unshared workflow, got %+v", p)
+	}
+	if err := svc.AuthorizeExecution(ctx, tenantID, colleagueID, workflowID); !errors.Is(err, cases.ErrExecutionForbidden) {
+		t.Fatalf("expected ErrExecutionForbidden before sharing, got %v", err)
+	}
+
+	if _, err := svc.ShareWorkflow(ctx, tenantID, workflowID, ownerID, workflows.ShareRequest{GranteeType: workflows.GranteeRole, GranteeID: roleID, Access: []string{"read"}}); err != nil {
+		t.Fatalf("share with role: %v", err)
+	}
+	if _, err := svc.ShareWorkflow(ctx, tenantID, workflowID, ownerID, workflows.ShareRequest{GranteeType: workflows.GranteePrincipal, GranteeID: colleagueID, Access: []string{"execute"}}); err != nil {
+		t.Fatalf("share with principal: %v", err)
+	}
+	if p := perms(colleagueID); !p.CanView || p.CanEdit || !p.CanExecute || p.CanShare {
+		t.Fatalf("unexpected shared permissions %+v", p)
+	}
+	if err := svc.AuthorizeExecution(ctx, tenantID, colleagueID, workflowID); err != nil {
+		t.Fatalf("execute share should allow execution: %v", err)
+	}
+	shares, err := svc.ListShares(ctx, tenantID, workflowID)
+	if err != nil || len(shares) != 2 || shares[0].GranteeName != "Colleague" || shares[1].GranteeName != "auditors" {
+		t.Fatalf("unexpected shares %+v err=%v", shares, err)
+	}
+
+	if _, err := svc.ShareWorkflow(ctx, tenantID, workflowID, ownerID, workflows.ShareRequest{GranteeType: workflows.GranteePrincipal, GranteeID: colleagueID, Access: []string{"admin"}}); !errors.Is(err, workflows.ErrInvalidShare) {
+		t.Fatalf("expected ErrInvalidShare, got %v", err)
+	}
+	if _, err := svc.ShareWorkflow(ctx, tenantID, workflowID, ownerID, workflows.ShareRequest{GranteeType: workflows.GranteePrincipal, GranteeID: uuid.New(), Access: []string{"read"}}); !errors.Is(err, workflows.ErrGranteeNotFound) {
+		t.Fatalf("expected ErrGranteeNotFound, got %v", err)
+	}
+
+	wf, err := svc.TransferOwnership(ctx, tenantID, workflowID, colleagueID)
+	if err != nil {
+		t.Fatalf("transfer: %v", err)
+	}
+	if wf.OwnerID != colleagueID {
+		t.Fatalf("expected new owner %s, got %s", colleagueID, wf.OwnerID)
+	}
+	if p := perms(ownerID); p.CanView {
+		t.Fatalf("previous owner should lose access, got %+v", p)
+	}
+	if p := perms(colleagueID); !p.CanEdit || !p.CanShare {
+		t.Fatalf("new owner should have every permission, got %+v", p)
+	}
+
+	if err := svc.UnshareWorkflow(ctx, tenantID, workflowID, workflows.GranteeRole, roleID); err != nil {
+		t.Fatalf("unshare: %v", err)
+	}
+	if err := svc.UnshareWorkflow(ctx, tenantID, workflowID, workflows.GranteeRole, roleID); !errors.Is(err, workflows.ErrShareNotFound) {
+		t.Fatalf("expected ErrShareNotFound, got %v", err)
+	}
+
+	items := []workflows.Workflow{wf}
+	if err := svc.ResolvePermissions(ctx, tenantID, ownerID, workflows.TenantAccess{View: true}, items); err != nil {
+		t.Fatalf("resolve permissions: %v", err)
+	}
+	if p := items[0].Permissions; p == nil || !p.CanView || p.CanEdit {
+		t.Fatalf("unexpected resolved permissions %+v", p)
+	}
+}
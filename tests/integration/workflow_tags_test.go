@@ -0,0 +1,78 @@
+package integration
+
+import (
+	"context"
+	"errors"
+	"slices"
+	"testing"
+
+	"github.com/neural-chilli/aceryx/internal/workflows"
+)
+
+func TestWorkflowTagsRenameMergeAndDelete(t *testing.T) {
+	ctx := context.Background()
+	db, cleanup := setupPostgresWithMigrations(t)
+	defer cleanup()
+
+	tenantID, principalID := seedTenantAndPrincipal(t, ctx, db, "tags")
+	_, wfA := seedMinimalCaseTypeAndWorkflow(t, ctx, db, tenantID, principalID)
+	_, wfB := seedMinimalCaseTypeAndWorkflow(t, ctx, db, tenantID, principalID)
+	svc := workflows.NewService(db)
+
+	if _, err := svc.SetWorkflowTags(ctx, tenantID, wfA, []string{"Loans ", "kyc", "loans"}); err != nil {
+		t.Fatalf("tag workflow A: %v", err)
+	}
+	if _, err := svc.SetWorkflowTags(ctx, tenantID, wfB, []string{"lending", "archived"}); err != nil {
+		t.Fatalf("tag workflow B: %v", err)
+	}
+	if _, err := svc.SetWorkflowTags(ctx, tenantID, wfB, []string{"lending"}); err != nil {
+		t.Fatalf("retag workflow B: %v", err)
+	}
+
+	counts := func() map[string]int {
+		tags, err := svc.ListTags(ctx, tenantID)
+		if err != nil {
+			t.Fatalf("list tags: %v", err)
+		}
+		out := map[string]int{}
+		for _, tag := range tags {
+			out[tag.Name] = tag.WorkflowCount
+		}
+		return out
+	}
+	if got := counts(); got["loans"] != 1 || got["kyc"] != 1 || got["lending"] != 1 || got["archived"] != 0 {
+		t.Fatalf("unexpected counts %v", got)
+	}
+
+	if _, err := svc.RenameTag(ctx, tenantID, "kyc", "lending"); !errors.Is(err, workflows.ErrTagExists) {
+		t.Fatalf("expected ErrTagExists, got %v", err)
+	}
+	if _, err := svc.RenameTag(ctx, tenantID, "kyc", "Know Your Customer"); err != nil {
+		t.Fatalf("rename: %v", err)
+	}
+	merged, err := svc.MergeTags(ctx, tenantID, []string{"loans", "lending"}, "credit")
+	if err != nil {
+		t.Fatalf("merge: %v", err)
+	}
+	if merged.WorkflowCount != 2 {
+		t.Fatalf("expected merged tag on both workflows, got %+v", merged)
+	}
+	wf, err := svc.Get(ctx, tenantID, wfA)
+	if err != nil {
+		t.Fatalf("get workflow: %v", err)
+	}
+	if !slices.Equal(wf.Tags, []string{"credit", "know your customer"}) {
+		t.Fatalf("unexpected workflow tags %v", wf.Tags)
+	}
+
+	if err := svc.DeleteTag(ctx, tenantID, "credit"); !errors.Is(err, workflows.ErrTagInUse) {
+		t.Fatalf("expected ErrTagInUse, got %v", err)
+	}
+	pruned, err := svc.PruneTags(ctx, tenantID)
+	if err != nil || !slices.Equal(pruned, []string{"archived"}) {
+		t.Fatalf("prune: %v %v", pruned, err)
+	}
+	if err := svc.DeleteTag(ctx, tenantID, "archived"); !errors.Is(err, workflows.ErrTagNotFound) {
+		t.Fatalf("expected ErrTagNotFound, got %v", err)
+	}
+}
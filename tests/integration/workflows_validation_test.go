@@ -125,6 +125,63 @@ func TestWorkflowsHTTPIntegration_RejectInvalidASTOnDraftSaveAndPublish(t *testi
 		}
 	})
 
+	t.Run("graph save keeps draft ast and yaml in sync", func(t *testing.T) {
+		ruleConfig := map[string]any{"outcomes": map[string]any{
+			"approved": map[string]any{"condition": "case.data.review.decision == 'approve'"},
+		}}
+		graph := map[string]any{
+			"nodes": []map[string]any{
+				{"id": "review_decision", "type": "rule", "position": map[string]any{"x": 0, "y": 0}, "data": map[string]any{
+					"stepType": "rule",
+					"config":   ruleConfig,
+				}},
+				{"id": "insert_customer", "type": "integration", "position": map[string]any{"x": 250, "y": 0}, "data": map[string]any{
+					"stepType": "integration",
+					"config":   map[string]any{"connector": "postgres", "action": "insert"},
+				}},
+			},
+			"edges": []map[string]any{
+				{"source": "review_decision", "target": "insert_customer", "data": map[string]any{"edgeType": "dependency"}},
+			},
+		}
+		raw, _ := json.Marshal(graph)
+		req := httptest.NewRequest(http.MethodPut, "/workflows/"+workflowID.String()+"/versions/draft/graph", bytes.NewReader(raw))
+		req.Header.Set("Authorization", "Bearer "+login.Token)
+		req.Header.Set("Content-Type", "application/json")
+		w := httptest.NewRecorder()
+		router.ServeHTTP(w, req)
+		if w.Code != http.StatusOK {
+			t.Fatalf("expected 200 for graph save, got status=%d body=%s", w.Code, w.Body.String())
+		}
+
+		var astRaw, yamlSource string
+		if err := db.QueryRowContext(ctx, `
+SELECT ast::text, COALESCE(yaml_source, '')
+FROM workflow_versions
+WHERE workflow_id = $1 AND status = 'draft'
+`, workflowID).Scan(&astRaw, &yamlSource); err != nil {
+			t.Fatalf("load draft: %v", err)
+		}
+		if !strings.Contains(astRaw, `"depends_on": ["review_decision"]`) {
+			t.Fatalf("expected dependency in draft ast, got %s", astRaw)
+		}
+		if !strings.Contains(yamlSource, "insert_customer") || strings.Contains(yamlSource, "insert_customer_onboarding") {
+			t.Fatalf("expected yaml source regenerated from graph, got %s", yamlSource)
+		}
+
+		edges := graph["edges"].([]map[string]any)
+		edges[0]["target"] = "missing"
+		raw, _ = json.Marshal(graph)
+		req = httptest.NewRequest(http.MethodPut, "/workflows/"+workflowID.String()+"/versions/draft/graph", bytes.NewReader(raw))
+		req.Header.Set("Authorization", "Bearer "+login.Token)
+		req.Header.Set("Content-Type", "application/json")
+		w = httptest.NewRecorder()
+		router.ServeHTTP(w, req)
+		if w.Code != http.StatusBadRequest || !strings.Contains(w.Body.String(), "invalid graph:") {
+			t.Fatalf("expected 400 invalid graph for dangling edge, got status=%d body=%s", w.Code, w.Body.String())
+		}
+	})
+
 	t.Run("publish rejects invalid draft ast persisted in db", func(t *testing.T) {
 		if _, err := db.ExecContext(ctx, `
 UPDATE workflow_versions